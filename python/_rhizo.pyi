@@ -1,20 +1,79 @@
 """Type stubs for the _rhizo Rust extension module (rhizo-core)."""
 
-from typing import List, Dict, Optional, Tuple, Union
+from decimal import Decimal
+from typing import BinaryIO, Callable, List, Dict, Mapping, Optional, Tuple, Union
 import pyarrow as pa
 
+class RhizoError(Exception):
+    """Base class for all Rhizo-specific errors."""
+    code: str
+    category: str
+    context: Dict[str, Union[str, int]]
+    retryable: bool
+    retry_after: Optional[float]
+
+class ConflictError(RhizoError, ValueError):
+    """Concurrent modification; the operation may succeed if retried."""
+
+class WriteConflictError(ConflictError): ...
+class SnapshotConflictError(ConflictError): ...
+class MergeConflictError(ConflictError): ...
+
+class NotFoundError(RhizoError, IOError):
+    """A table, version, ref or other object does not exist."""
+
+class BranchNotFoundError(NotFoundError): ...
+class ChunkNotFoundError(NotFoundError): ...
+
+class IntegrityError(RhizoError, ValueError):
+    """Stored data failed verification."""
+
+class HashMismatchError(IntegrityError): ...
+
 class PyChunkStore:
-    def __init__(self, path: str) -> None: ...
+    def __init__(
+        self,
+        path: str,
+        compression: Optional[str] = None,
+        compression_level: Optional[int] = None,
+        max_delta_chain: Optional[int] = None,
+        integrity_journal: bool = False,
+        max_open_files: Optional[int] = None,
+        chunking: Optional[str] = None,
+        chunk_size: Optional[int] = None,
+        memory_cache_bytes: int = 0,
+    ) -> None: ...
     def put(self, data: bytes) -> str: ...
     def get(self, hash: str) -> bytes: ...
     def get_verified(self, hash: str) -> bytes: ...
     def exists(self, hash: str) -> bool: ...
+    def memory_cache_stats(self) -> Dict[str, int]: ...
     def delete(self, hash: str) -> None: ...
     def put_batch(self, chunks: List[bytes]) -> List[str]: ...
     def get_batch(self, hashes: List[str]) -> List[bytes]: ...
     def get_batch_verified(self, hashes: List[str]) -> List[bytes]: ...
+    def put_many(self, chunks: List[bytes]) -> List[str]: ...
+    def get_many(self, hashes: List[str]) -> List[bytes]: ...
+    def exists_many(self, hashes: List[str]) -> List[bool]: ...
     def get_mmap(self, hash: str) -> bytes: ...
     def get_mmap_batch(self, hashes: List[str]) -> List[bytes]: ...
+    def put_delta(self, data: bytes, base_hash: str) -> str: ...
+    def delta_info(self, hash: str) -> Optional[Tuple[str, int]]: ...
+    def materialize(self, hash: str) -> bool: ...
+    def put_stream(self, file: BinaryIO) -> str: ...
+    def put_chunked(self, file: Union[BinaryIO, bytes]) -> List[str]: ...
+    def get_stream(self, hash: str) -> ChunkReader: ...
+
+class ChunkReader:
+    """Binary file-like reader over a stored chunk."""
+    size: int
+    closed: bool
+
+    def read(self, size: int = -1) -> bytes: ...
+    def readable(self) -> bool: ...
+    def close(self) -> None: ...
+    def __enter__(self) -> ChunkReader: ...
+    def __exit__(self, *args: object) -> bool: ...
 
 class PyTableVersion:
     table_name: str
@@ -32,16 +91,160 @@ class PyTableVersion:
         chunk_hashes: List[str],
     ) -> None: ...
 
+class PySchemaChange:
+    """A change in a table's schema lineage."""
+    version: int
+    schema_hash: str
+    previous_hash: Optional[str]
+    fields: Optional[List[Tuple[str, str, bool]]]
+    migration: Optional[Tuple[str, str, Optional[str]]]
+    created_at: int
+
 class PyCatalog:
-    def __init__(self, path: str) -> None: ...
+    def __init__(
+        self,
+        path: str,
+        branch_path: Optional[str] = None,
+        lock_timeout_ms: Optional[int] = None,
+    ) -> None: ...
     def commit(self, version: PyTableVersion) -> int: ...
     def get_version(
         self,
         table_name: str,
         version: Optional[int] = None,
+        branch: Optional[str] = None,
     ) -> PyTableVersion: ...
+    def get_version_as_of(self, table_name: str, snapshot: Union[int, str]) -> PyTableVersion: ...
     def list_versions(self, table_name: str) -> List[int]: ...
-    def list_tables(self) -> List[str]: ...
+    def list_tables(
+        self,
+        prefix: Optional[str] = None,
+        branch: Optional[str] = None,
+    ) -> List[str]: ...
+    def schema_history(self, table_name: str) -> List[PySchemaChange]: ...
+    def algebraic_schema(
+        self, table_name: str, version: Optional[int] = None
+    ) -> Optional[PyTableAlgebraicSchema]: ...
+    def set_algebraic_schema(
+        self, table_name: str, schema: Optional[PyTableAlgebraicSchema] = None
+    ) -> int: ...
+    def algebraic_registry(self, versions: Dict[str, int]) -> PyAlgebraicSchemaRegistry: ...
+    def table_origin(self, table_name: str) -> Optional[str]: ...
+    def set_table_origin(self, table_name: str, branch: str) -> None: ...
+    def pin(
+        self,
+        name: str,
+        versions: Dict[str, int],
+        description: Optional[str] = None,
+    ) -> PyPin: ...
+    def resolve_pin(self, name: str) -> PyPin: ...
+    def list_pins(self) -> List[PyPin]: ...
+    def delete_pin(self, name: str) -> PyPin: ...
+    def pinned_versions(self) -> Dict[str, List[int]]: ...
+    def create_view(
+        self,
+        name: str,
+        sql: str,
+        sources: List[str],
+        materialize: bool = False,
+        description: Optional[str] = None,
+        replace: bool = False,
+    ) -> PyView: ...
+    def get_view(self, name: str) -> PyView: ...
+    def view_exists(self, name: str) -> bool: ...
+    def list_views(self) -> List[PyView]: ...
+    def drop_view(self, name: str) -> PyView: ...
+    def view_base_tables(self, name: str) -> List[str]: ...
+    def hold(
+        self,
+        table_name: str,
+        version: int,
+        reason: Optional[str] = None,
+    ) -> PyLegalHold: ...
+    def release_hold(self, table_name: str, version: int) -> PyLegalHold: ...
+    def lock_owner(self, table_name: str) -> Optional[PyLockOwner]: ...
+    def break_lock(self, table_name: str, force: bool = False) -> Optional[PyLockOwner]: ...
+    def list_holds(self) -> List[PyLegalHold]: ...
+    def held_versions(self) -> Dict[str, List[int]]: ...
+    def set_retention_policy(
+        self,
+        table_name: str,
+        keep_last: Optional[int] = None,
+        keep_newer_than_secs: Optional[int] = None,
+    ) -> None: ...
+    def retention_policy(self, table_name: str) -> Optional[Dict[str, Optional[int]]]: ...
+    def prune_history(self, table_name: str) -> List[Tuple[List[int], int]]: ...
+    def squash_history(self, table_name: str) -> List[Tuple[int, List[int], int]]: ...
+    def redaction_history(
+        self, table_name: str
+    ) -> List[Tuple[str, Optional[str], List[int], int, int]]: ...
+    def squashed_into(self, table_name: str, version: int) -> Optional[int]: ...
+    def list_tables_matching(self, pattern: str) -> List[str]: ...
+    def drop_table(self, table_name: str) -> List[PyTableVersion]: ...
+    def rename_table(self, old_name: str, new_name: str) -> int: ...
+    def recover_pending_commits(self) -> List[PyPendingCommit]: ...
+
+class PyPin:
+    """An immutable, GC-protected set of pinned table versions."""
+    name: str
+    versions: Dict[str, int]
+    created_at: int
+    description: Optional[str]
+
+class PyView:
+    """A named query that reads like a table."""
+    name: str
+    sql: str
+    sources: List[str]
+    materialize: bool
+    created_at: int
+    description: Optional[str]
+    @staticmethod
+    def materialization_key(source_versions: Dict[str, int]) -> str: ...
+
+class PyTag:
+    """A named, immutable release point over table versions."""
+    name: str
+    versions: Dict[str, int]
+    created_at: int
+    source_branch: Optional[str]
+    message: Optional[str]
+
+class PyTagManager:
+    def __init__(self, catalog_path: str, branch_path: Optional[str] = None) -> None: ...
+    def create(
+        self,
+        name: str,
+        versions: Optional[Dict[str, int]] = None,
+        branch: Optional[str] = None,
+        message: Optional[str] = None,
+    ) -> PyTag: ...
+    def get(self, name: str) -> PyTag: ...
+    def list(self) -> List[PyTag]: ...
+    def delete(self, name: str) -> PyTag: ...
+    def get_version(self, tag: str, table_name: str) -> PyTableVersion: ...
+
+class PyLegalHold:
+    """A legal hold protecting one table version from deletion."""
+    table_name: str
+    version: int
+    reason: Optional[str]
+    created_at: int
+
+class PyLockOwner:
+    """The process recorded as holding a catalog table lock."""
+    pid: int
+    host: str
+    acquired_at: int
+    def is_alive(self) -> Optional[bool]: ...
+    def is_stale(self) -> bool: ...
+
+class PyPendingCommit:
+    """A commit intent whose commit never completed (orphaned chunks)."""
+    intent_id: str
+    table_name: str
+    chunk_hashes: List[str]
+    created_at: int
 
 class PyBranch:
     """A branch represents a named pointer to table versions."""
@@ -50,6 +253,68 @@ class PyBranch:
     created_at: int
     parent_branch: Optional[str]
     description: Optional[str]
+    immutable: bool
+    snapshot_id: Optional[int]
+
+class PyRepoSnapshot:
+    """A branch's table versions under a repository-wide snapshot id."""
+    id: int
+    branch: str
+    versions: Dict[str, int]
+    created_at: int
+    def __str__(self) -> str: ...
+
+class PyBranchCommit:
+    """One head change in a branch's history."""
+    id: int
+    branch: str
+    parents: List[int]
+    tx_id: Optional[int]
+    changes: List[Tuple[str, Optional[int], Optional[int]]]
+    created_at: int
+
+class PyMergeRecord:
+    """A merge commit: the target's new heads and both parents."""
+    source: str
+    into: str
+    strategy: str
+    parents: List[int]
+    snapshot_id: int
+    versions: Dict[str, int]
+    replaced: Dict[str, int]
+    resolved: List[str]
+
+class PyArchivedBranch:
+    """A branch archived after being merged."""
+    branch: PyBranch
+    merged_into: str
+    archived_at: int
+
+class PyBranchRepair:
+    """Dangling branch heads found (and possibly rolled back) by repair_branch."""
+    branch: str
+    dangling: List[Tuple[str, int, Optional[int]]]
+    repaired: bool
+    @property
+    def is_clean(self) -> bool: ...
+
+class PyHeadRecord:
+    """The repository HEAD: which branch is the default."""
+    branch: str
+    updated_at: int
+    updated_by: Optional[str]
+    generation: int
+
+class PyHeadEvent:
+    """A change of HEAD."""
+    previous: Optional[str]
+    head: PyHeadRecord
+
+class PyCheckout:
+    """A workspace's claim on a branch."""
+    workspace: str
+    branch: str
+    checked_out_at: int
 
 class PyBranchDiff:
     """Result of comparing two branches."""
@@ -70,16 +335,156 @@ class PyBranchManager:
         from_branch: Optional[str] = None,
         description: Optional[str] = None,
     ) -> PyBranch: ...
+    def create_for_namespace(
+        self,
+        name: str,
+        selector: str,
+        from_branch: Optional[str] = None,
+        description: Optional[str] = None,
+    ) -> PyBranch: ...
     def get(self, name: str) -> PyBranch: ...
     def list(self) -> List[str]: ...
     def delete(self, name: str) -> None: ...
     def update_head(self, branch_name: str, table_name: str, version: int) -> None: ...
+    def update_heads(self, branch_name: str, versions: Dict[str, int]) -> PyRepoSnapshot: ...
+    def snapshot(self, snapshot_ref: Union[int, str]) -> PyRepoSnapshot: ...
+    def latest_snapshot(self, branch_name: str) -> Optional[PyRepoSnapshot]: ...
+    def snapshots(self, branch_name: Optional[str] = None) -> List[PyRepoSnapshot]: ...
+    def history(self, branch_name: str, limit: Optional[int] = None) -> List[PyBranchCommit]: ...
+    def common_ancestor(self, a: str, b: str) -> Optional[PyBranchCommit]: ...
+    def remove_table(self, table_name: str) -> List[str]: ...
+    def rename_table(self, old_name: str, new_name: str) -> List[str]: ...
     def get_table_version(self, branch_name: str, table_name: str) -> Optional[int]: ...
     def diff(self, source: str, target: str) -> PyBranchDiff: ...
     def can_fast_forward(self, source: str, target: str) -> bool: ...
-    def merge(self, source: str, into: str) -> None: ...
+    def merge_conflicts(self, source: str, into: str) -> List[str]: ...
+    def merge(
+        self,
+        source: str,
+        into: str,
+        resolutions: Optional[Dict[str, str]] = None,
+        delete_source: bool = False,
+    ) -> None: ...
+    def merge_commit(self, source: str, into: str, strategy: str = "strict") -> PyMergeRecord: ...
+    def merge_and_archive(self, source: str, into: str) -> PyArchivedBranch: ...
+    def archived(self) -> List[PyArchivedBranch]: ...
+    def unarchive(self, name: str) -> PyBranch: ...
+    def dangling_heads(self, name: str, catalog: PyCatalog) -> List[Tuple[str, int, Optional[int]]]: ...
+    def repair_branch(
+        self, name: str, catalog: PyCatalog, strategy: str = "rollback"
+    ) -> PyBranchRepair:
+        """Roll dangling heads back to existing versions ("rollback") or list them ("report")."""
+        ...
     def get_default(self) -> Optional[str]: ...
     def set_default(self, name: str) -> None: ...
+    def head(self) -> Optional[PyHeadRecord]: ...
+    def set_head(self, name: str, updated_by: Optional[str] = None) -> PyHeadRecord: ...
+    def compare_and_set_head(
+        self, expected_generation: int, name: str, updated_by: Optional[str] = None
+    ) -> PyHeadRecord: ...
+    def head_events(self, since_generation: int = 0) -> List[PyHeadEvent]: ...
+    def checkout(self, workspace: str, branch: str) -> PyCheckout: ...
+    def release_checkout(self, workspace: str) -> Optional[PyCheckout]: ...
+    def checkouts(self) -> List[PyCheckout]: ...
+    def set_immutable(self, name: str, immutable: bool) -> None: ...
+
+class FilterReport:
+    """Result of a repository filter run."""
+    version_map: Dict[str, Dict[int, int]]
+    branches: List[str]
+    versions_dropped: int
+    chunks_copied: int
+    bytes_copied: int
+    @property
+    def tables(self) -> List[str]: ...
+    @property
+    def versions_kept(self) -> int: ...
+
+def filter_repository(
+    source_store: PyChunkStore,
+    source_catalog: PyCatalog,
+    dest_store: PyChunkStore,
+    dest_catalog: PyCatalog,
+    source_branches: Optional[PyBranchManager] = None,
+    dest_branches: Optional[PyBranchManager] = None,
+    tables: Optional[List[str]] = None,
+    branches: Optional[List[str]] = None,
+    keep_versions: Optional[int] = None,
+) -> FilterReport:
+    """Copy a subset of one repository into another.
+
+    Kept versions are renumbered from 1 in the destination; parent links,
+    branch heads and fork points are rewritten to match.
+
+    Args:
+        tables: Table selectors (namespace prefixes or wildcard patterns)
+        branches: Branch names to keep; tables on no kept branch are dropped
+        keep_versions: Keep only the last N versions of each table
+
+    Returns:
+        FilterReport with the version mapping and copy statistics
+    """
+    ...
+
+class BackupManifest:
+    """Summary of a backup manifest."""
+    backup_id: str
+    created_at: int
+    parent_id: Optional[str]
+    tables: Dict[str, int]
+    branches: List[str]
+    versions: int
+    chunks: int
+    chunk_bytes: int
+    @property
+    def is_incremental(self) -> bool: ...
+
+class VerifyReport:
+    """Result of verifying a backup chain."""
+    backups: int
+    versions: int
+    chunks_verified: int
+    bytes_verified: int
+
+class RestoreReport:
+    """Result of restoring a backup chain."""
+    backups_applied: int
+    versions_restored: int
+    chunks_copied: int
+    bytes_copied: int
+    tables: List[str]
+    branches: List[str]
+
+def backup_repository(
+    source_store: PyChunkStore,
+    source_catalog: PyCatalog,
+    dest: str,
+    source_branches: Optional[PyBranchManager] = None,
+    incremental_since: Optional[str] = None,
+) -> BackupManifest:
+    """Back up a repository into a new backup directory.
+
+    With incremental_since, only versions and chunks missing from that
+    backup (and the chain it extends) are stored.
+    """
+    ...
+
+def verify_backup(path: str) -> VerifyReport:
+    """Verify manifest checksums, parent links and chunk hashes of a backup chain."""
+    ...
+
+def backup_chain(path: str) -> List[BackupManifest]:
+    """Manifests of a backup chain, base backup first."""
+    ...
+
+def restore_backup(
+    path: str,
+    target_store: PyChunkStore,
+    target_catalog: PyCatalog,
+    target_branches: Optional[PyBranchManager] = None,
+) -> RestoreReport:
+    """Verify a backup chain, then restore it into a repository."""
+    ...
 
 class PyTransactionInfo:
     """Information about a transaction."""
@@ -91,6 +496,7 @@ class PyTransactionInfo:
     committed_at: Optional[int]
     read_snapshot: Dict[str, int]
     written_tables: List[str]
+    savepoints: List[str]
 
 class PyRecoveryReport:
     """Result of transaction recovery process."""
@@ -103,25 +509,147 @@ class PyRecoveryReport:
     errors: List[str]
     is_clean: bool
 
+class PyEpochConfig:
+    """Epoch configuration for a TransactionManager."""
+    duration_ms: int
+    max_transactions: int
+    batching_enabled: bool
+
+    def __init__(
+        self,
+        duration_ms: int = 100,
+        max_transactions: int = 1000,
+        batching_enabled: bool = True,
+    ) -> None: ...
+    @staticmethod
+    def single_node() -> "PyEpochConfig": ...
+    @staticmethod
+    def high_throughput() -> "PyEpochConfig": ...
+    @staticmethod
+    def low_latency() -> "PyEpochConfig": ...
+    def is_immediate(self) -> bool: ...
+
+class PyEpochInfo:
+    """Metadata and statistics for a single epoch."""
+    epoch_id: int
+    status: str
+    started_at: int
+    ended_at: Optional[int]
+    transactions: List[int]
+    first_tx_id: Optional[int]
+    last_tx_id: Optional[int]
+    committed_count: int
+    aborted_count: int
+
+class PyCommitQueueStats:
+    """Commit queue metrics, keyed by "interactive", "normal" and "bulk"."""
+    waiting: Dict[str, int]
+    admitted: Dict[str, int]
+    mean_wait_ms: Dict[str, float]
+    max_wait_ms: Dict[str, float]
+    starvation_grants: int
+
+class PyCommitTimings:
+    """Phase timings of one commit attempt, in milliseconds."""
+    tx_id: int
+    branch: str
+    phases_ms: Dict[str, float]
+    total_ms: float
+    slowest_phase: Optional[str]
+    error: Optional[str]
+    @property
+    def succeeded(self) -> bool: ...
+
+class Transaction:
+    """A transaction from PyTransactionManager.transaction().
+
+    Commits when its with-block exits cleanly and aborts when it raises.
+    """
+    tx_id: int
+    state: str
+
+    def add_write(
+        self,
+        table_name: str,
+        new_version: int,
+        chunk_hashes: List[str],
+        partitions: Optional[List[str]] = None,
+        key_columns: Optional[List[str]] = None,
+        keys: Optional[List[str]] = None,
+    ) -> None: ...
+    def record_read(self, table_name: str, version: int) -> None: ...
+    def set_metadata(self, key: str, value: str) -> None: ...
+    def set_priority(self, priority: str) -> None: ...
+    def savepoint(self, name: str) -> None: ...
+    def rollback_to_savepoint(self, name: str) -> None: ...
+    def release_savepoint(self, name: str) -> None: ...
+    def commit(self) -> None: ...
+    def abort(self, reason: str = "User requested") -> None: ...
+    def __enter__(self) -> Transaction: ...
+    def __exit__(self, exc_type: object, exc_value: object, traceback: object) -> bool: ...
+
 class PyTransactionManager:
     """Manages cross-table ACID transactions."""
+    epoch_config: PyEpochConfig
+
     def __init__(
         self,
         base_path: str,
         catalog_path: str,
         branch_path: Optional[str] = None,
         auto_recover: bool = False,
+        epoch_config: Optional[PyEpochConfig] = None,
+        schema_registry: Optional[PyAlgebraicSchemaRegistry] = None,
+        priority_commits: bool = False,
+        max_bypass: Optional[int] = None,
+        conflict_granularity: str = "table",
+        conflict_window: str = "memory",
+        metrics_sink: Optional[Callable[[PyCommitTimings], None]] = None,
+        merge_store: Optional[PyChunkStore] = None,
     ) -> None: ...
+    @property
+    def conflict_granularity(self) -> str: ...
+    @property
+    def conflict_window(self) -> str: ...
     def begin(self, branch: Optional[str] = None) -> int: ...
+    def transaction(self, branch: Optional[str] = None, on_conflict: str = "abort") -> Transaction: ...
     def add_write(
         self,
         tx_id: int,
         table_name: str,
         new_version: int,
         chunk_hashes: List[str],
+        partitions: Optional[List[str]] = None,
+        key_columns: Optional[List[str]] = None,
+        keys: Optional[List[str]] = None,
     ) -> None: ...
+    def set_priority(self, tx_id: int, priority: str) -> None: ...
+    def set_metadata(self, tx_id: int, key: str, value: str) -> None: ...
+    def savepoint(self, tx_id: int, name: str) -> None: ...
+    def rollback_to_savepoint(self, tx_id: int, name: str) -> None: ...
+    def release_savepoint(self, tx_id: int, name: str) -> None: ...
+    def commit_queue_stats(self) -> Optional[PyCommitQueueStats]: ...
+    def explain_commit(self, tx_id: int) -> Optional[PyCommitTimings]: ...
+    def on_commit(self, callback: Callable[[PyTransactionInfo, List[PyTableChange]], None]) -> int:
+        """Call callback(info, changes) after every successful commit."""
+        ...
+    def on_abort(self, callback: Callable[[PyTransactionInfo, List[PyTableChange]], None]) -> int:
+        """Call callback(info, changes) after every abort."""
+        ...
+    def on_conflict(self, callback: Callable[[PyTransactionInfo, List[PyTableChange]], None]) -> int:
+        """Call callback(info, changes) when a commit fails with a conflict."""
+        ...
+    def remove_hook(self, hook_id: int) -> bool: ...
+    def drop_table(
+        self,
+        table_name: str,
+        purge_store: Optional[PyChunkStore] = None,
+        grace_period_secs: float = 3600.0,
+    ) -> List[PyTableVersion]: ...
+    def rename_table(self, old_name: str, new_name: str) -> int: ...
+    def merge(self, source: str, into: str, strategy: str = "strict") -> PyMergeRecord: ...
     def record_read(self, tx_id: int, table_name: str, version: int) -> None: ...
-    def commit(self, tx_id: int) -> None: ...
+    def commit(self, tx_id: int, on_conflict: str = "abort") -> None: ...
     def abort(self, tx_id: int, reason: str = "User requested") -> None: ...
     def get_transaction(self, tx_id: int) -> PyTransactionInfo: ...
     def active_transactions(self) -> List[PyTransactionInfo]: ...
@@ -136,8 +664,218 @@ class PyTransactionManager:
         tables: Optional[List[str]] = None,
         branch: Optional[str] = None,
         limit: Optional[int] = None,
+        until_tx_id: Optional[int] = None,
     ) -> List["PyChangelogEntry"]: ...
     def latest_tx_id(self) -> Optional[int]: ...
+    def create_cursor(self, name: str) -> ChangelogCursor: ...
+    def resume(self, name: str) -> ChangelogCursor: ...
+    def list_cursors(self) -> List[ChangelogCursor]: ...
+    def delete_cursor(self, name: str) -> None: ...
+    def current_epoch(self) -> PyEpochInfo: ...
+    def list_epochs(self) -> List[PyEpochInfo]: ...
+    def seal_epoch(self) -> PyEpochInfo: ...
+    def read_snapshot(
+        self, branch: Optional[str] = None
+    ) -> Mapping[str, Tuple[int, List[str]]]: ...
+
+
+class PyBatchAck:
+    """Acknowledgement that the batch holding a write committed."""
+    tx_id: int
+    branch: str
+    versions: Dict[str, int]
+    writes: int
+
+class PyMicroBatchStats:
+    """Counters of a PyMicroBatcher since it started."""
+    batches_committed: int
+    batches_failed: int
+    writes_committed: int
+    rows_committed: int
+    pending_writes: int
+
+class PyBatchTicket:
+    """Resolves when the batch holding a write commits or fails."""
+    def wait(self, timeout: Optional[float] = None) -> Optional[PyBatchAck]: ...
+
+class PyMicroBatcher:
+    """Buffers small appends per branch and commits each buffer as one transaction."""
+    def __init__(
+        self,
+        manager: PyTransactionManager,
+        max_delay_ms: float = 10.0,
+        max_rows: int = 10000,
+        max_retries: int = 3,
+    ) -> None: ...
+    def submit(
+        self, branch: str, table_name: str, chunk_hashes: List[str], rows: int = 0
+    ) -> PyBatchTicket: ...
+    def write(
+        self, branch: str, table_name: str, chunk_hashes: List[str], rows: int = 0
+    ) -> PyBatchAck: ...
+    def flush(self) -> None: ...
+    def stats(self) -> PyMicroBatchStats: ...
+    def close(self) -> None: ...
+
+class ChunkGcReport:
+    """Result of a chunk garbage collection run."""
+    dry_run: bool
+    chunks_scanned: int
+    chunks_referenced: int
+    chunks_in_flight: int
+    chunks_within_grace: int
+    collectable: List[str]
+    bytes_collectable: int
+    chunks_deleted: int
+    chunks_failed: int
+
+def sweep_chunks(
+    store: PyChunkStore,
+    catalog: PyCatalog,
+    transaction_manager: Optional[PyTransactionManager] = None,
+    grace_period_secs: float = 3600.0,
+    dry_run: bool = False,
+    only: Optional[List[str]] = None,
+) -> ChunkGcReport:
+    """Delete chunks no catalog version or in-flight transaction references.
+
+    Chunks modified within the grace period are kept. With dry_run, the
+    collectable chunks are reported but not deleted.
+    """
+    ...
+
+class VersionPruneReport:
+    """Result of a version pruning run."""
+    dry_run: bool
+    versions_pruned: Dict[str, List[int]]
+    versions_protected: Dict[str, List[int]]
+    unreferenced_chunks: List[str]
+
+def prune_versions(
+    catalog: PyCatalog,
+    branch_manager: Optional[PyBranchManager] = None,
+    transaction_manager: Optional[PyTransactionManager] = None,
+    keep_last: Optional[int] = None,
+    keep_newer_than_secs: Optional[int] = None,
+    dry_run: bool = False,
+) -> VersionPruneReport:
+    """Remove old table versions according to each table's retention policy.
+
+    keep_last / keep_newer_than_secs apply to tables without a policy of
+    their own. Held, pinned, tagged, branch-head and in-use versions are
+    kept; chunks only pruned versions referenced are reported.
+    """
+    ...
+
+def squash_versions(
+    catalog: PyCatalog,
+    table_name: str,
+    up_to_version: int,
+    branch_manager: Optional[PyBranchManager] = None,
+    transaction_manager: Optional[PyTransactionManager] = None,
+    dry_run: bool = False,
+) -> VersionPruneReport:
+    """Fold every version of a table before up_to_version into it.
+
+    The removed version numbers are kept in PyCatalog.squash_history. Fails
+    if a removed version is held, pinned, tagged, a branch head or fork
+    point, or read by an active transaction.
+    """
+    ...
+
+class GcPlan:
+    """Versions to delete, in order, to free a target number of bytes."""
+    target_bytes: int
+    unreferenced_bytes: int
+    reclaimable_bytes: int
+    meets_target: bool
+    steps: List[Tuple[str, int, int, int]]
+
+def gc_plan(
+    store: PyChunkStore,
+    catalog: PyCatalog,
+    target_bytes: int,
+    branch_manager: Optional[PyBranchManager] = None,
+    transaction_manager: Optional[PyTransactionManager] = None,
+    grace_period_secs: float = 3600.0,
+) -> GcPlan:
+    """Plan which versions to delete to free at least target_bytes.
+
+    Versions are ranked by the bytes only they hold, then by age. Steps are
+    (table_name, version, bytes, created_at). Latest, held, pinned, tagged,
+    branch-head and in-use versions are never planned.
+    """
+    ...
+
+def execute_gc_plan(
+    store: PyChunkStore,
+    catalog: PyCatalog,
+    plan: GcPlan,
+    branch_manager: Optional[PyBranchManager] = None,
+    transaction_manager: Optional[PyTransactionManager] = None,
+    grace_period_secs: float = 3600.0,
+) -> ChunkGcReport:
+    """Delete the versions of a plan and collect their chunks.
+
+    Fails without deleting anything if a planned version came into use.
+    """
+    ...
+
+class ChunkScrubReport:
+    """Result of a chunk integrity scrub."""
+    chunks_checked: int
+    chunks_intact: int
+    chunks_not_cached: int
+    chunks_unjournaled: int
+    damaged: Dict[str, str]
+    impacted_versions: Dict[str, List[int]]
+    impacted_branches: List[str]
+    dangling_heads: Dict[str, List[Tuple[str, int, Optional[int]]]]
+    @property
+    def is_clean(self) -> bool: ...
+
+def scrub_chunks(
+    store: PyChunkStore,
+    catalog: PyCatalog,
+    branch_manager: Optional[PyBranchManager] = None,
+) -> ChunkScrubReport:
+    """Compare chunk files against the store's integrity journal.
+
+    Damaged chunks map to "missing", "truncated", "extended" or "bit_rot".
+    Raises ValueError if the store was not opened with integrity_journal=True.
+    """
+    ...
+
+class FixtureReport:
+    """What build_fixture_repo wrote."""
+    tables: List[str]
+    branches: List[str]
+    versions_committed: int
+    chunks_written: int
+    bytes_written: int
+    last_commit_at: int
+
+def build_fixture_repo(
+    path: str,
+    *,
+    tables: int = 3,
+    versions: int = 5,
+    branches: int = 1,
+    branch_versions: int = 2,
+    rows: int = 100,
+    chunks: int = 1,
+    commit_interval_secs: int = 60,
+    start_time: int = 1_700_000_000,
+    seed: int = 0,
+) -> FixtureReport:
+    """Build a deterministic repository of a given shape at path.
+
+    main gets `versions` commit rounds writing every table; each branch
+    forks after a seeded round and commits `branch_versions` more. The
+    same arguments always produce the same repository. Raises ValueError
+    for an empty shape.
+    """
+    ...
 
 class PyTableChange:
     """A single table change within a committed transaction."""
@@ -145,8 +883,13 @@ class PyTableChange:
     old_version: Optional[int]
     new_version: int
     chunk_hashes: List[str]
+    branch: Optional[str]
+    kind: str
+    renamed_to: Optional[str]
+    merged_from: Optional[str]
 
     def is_new_table(self) -> bool: ...
+    def is_drop(self) -> bool: ...
 
 class PyChangelogEntry:
     """Entry in the changelog representing a committed transaction."""
@@ -154,12 +897,56 @@ class PyChangelogEntry:
     epoch_id: int
     committed_at: int
     branch: str
+    auto_merged: List[str]
     changes: List[PyTableChange]
+    metadata: Dict[str, str]
+    merge: Optional[PyMergeRecord]
 
     def changed_tables(self) -> List[str]: ...
     def contains_table(self, table_name: str) -> bool: ...
     def get_change(self, table_name: str) -> Optional[PyTableChange]: ...
     def change_count(self) -> int: ...
+    def is_auto_merge(self) -> bool: ...
+    def is_branch_merge(self) -> bool: ...
+
+class ChangelogCursor:
+    """A consumer's persisted position in the changelog."""
+    name: str
+    acked_tx_id: int
+    created_at: int
+    updated_at: int
+
+    def ack(self, tx_id: int) -> None: ...
+
+class ReplayReport:
+    """Result of a changelog replay."""
+    entries_applied: int
+    versions_created: int
+    tables_dropped: int
+    tables_renamed: int
+    chunks_copied: int
+    bytes_copied: int
+    first_tx_id: Optional[int]
+    last_tx_id: Optional[int]
+    heads: Dict[str, Dict[str, int]]
+
+def replay_changelog(
+    entries: List[PyChangelogEntry],
+    source_store: PyChunkStore,
+    source_catalog: PyCatalog,
+    target_store: PyChunkStore,
+    target_catalog: PyCatalog,
+    source_branches: Optional[PyBranchManager] = None,
+    target_branches: Optional[PyBranchManager] = None,
+    verify_heads: bool = False,
+) -> ReplayReport:
+    """Replay changelog entries onto another repository.
+
+    Raises ValueError if the entries do not continue the target's history,
+    disagree with the source catalog, or (with verify_heads) leave branch
+    heads different from the source.
+    """
+    ...
 
 # =============================================================================
 # Merkle Tree Types
@@ -443,6 +1230,203 @@ class PyParquetDecoder:
         ...
 
 
+# =============================================================================
+# Arrow Tables
+# =============================================================================
+
+ArrowData = Union[pa.RecordBatch, pa.Table, List[pa.RecordBatch]]
+
+def write_arrow(
+    store: PyChunkStore,
+    catalog: PyCatalog,
+    table_name: str,
+    data: ArrowData,
+    max_rows_per_chunk: int = 65536,
+    metadata: Optional[Dict[str, str]] = None,
+) -> PyTableVersion:
+    """Write Arrow data as the next version of a table.
+
+    Chunks are Arrow IPC streams of at most max_rows_per_chunk rows; the
+    version records the schema hash.
+    """
+    ...
+
+def write_patch(
+    store: PyChunkStore,
+    catalog: PyCatalog,
+    table_name: str,
+    delete_rows: Optional[List[int]] = None,
+    update_rows: Optional[List[int]] = None,
+    updates: Optional[ArrowData] = None,
+    inserts: Optional[ArrowData] = None,
+    max_patch_chain: int = 8,
+    metadata: Optional[Dict[str, str]] = None,
+) -> PyTableVersion:
+    """Apply row changes to the latest version of a table as patch chunks.
+
+    Positions count rows across the whole version; update_rows[i] is
+    replaced by row i of updates, inserts are appended. Only touched
+    chunks are stored again.
+    """
+    ...
+
+def read_arrow(
+    store: PyChunkStore,
+    catalog: PyCatalog,
+    table_name: str,
+    version: Optional[int] = None,
+) -> List[pa.RecordBatch]:
+    """Read a version of a table as RecordBatches (zero-copy).
+
+    Reads versions written by write_arrow and by the Python TableWriter.
+    """
+    ...
+
+def import_parquet(
+    store: PyChunkStore,
+    catalog: PyCatalog,
+    path: str,
+    table_name: str,
+    transaction_manager: Optional[PyTransactionManager] = None,
+    tx_id: Optional[int] = None,
+    max_rows_per_chunk: int = 65536,
+    metadata: Optional[Dict[str, str]] = None,
+) -> PyTableVersion:
+    """Import a Parquet file as the next version of a table.
+
+    With transaction_manager and tx_id the write is added to that
+    transaction instead of being committed directly.
+    """
+    ...
+
+def import_csv(
+    store: PyChunkStore,
+    catalog: PyCatalog,
+    path: str,
+    table_name: str,
+    schema: Optional[pa.Schema] = None,
+    has_header: bool = True,
+    delimiter: str = ",",
+    infer_rows: Optional[int] = 1000,
+    transaction_manager: Optional[PyTransactionManager] = None,
+    tx_id: Optional[int] = None,
+    max_rows_per_chunk: int = 65536,
+    metadata: Optional[Dict[str, str]] = None,
+) -> PyTableVersion:
+    """Import a CSV file as the next version of a table.
+
+    Column types are inferred from the first infer_rows records (all if
+    None) unless schema is given.
+    """
+    ...
+
+def import_jsonl(
+    store: PyChunkStore,
+    catalog: PyCatalog,
+    path: str,
+    table_name: str,
+    schema: Optional[pa.Schema] = None,
+    infer_rows: Optional[int] = 1000,
+    transaction_manager: Optional[PyTransactionManager] = None,
+    tx_id: Optional[int] = None,
+    max_rows_per_chunk: int = 65536,
+    metadata: Optional[Dict[str, str]] = None,
+) -> PyTableVersion:
+    """Import a JSON Lines file as the next version of a table."""
+    ...
+
+def export_parquet(
+    store: PyChunkStore,
+    catalog: PyCatalog,
+    table_name: str,
+    path: str,
+    version: Optional[int] = None,
+) -> PyTableVersion:
+    """Write a version of a table (default: latest) to a Parquet file."""
+    ...
+
+class ExportManifest:
+    """Manifest of a snapshot-consistent export."""
+    branch: str
+    tx_id: Optional[int]
+    format: str
+    exported_at: int
+    tables: Dict[str, int]
+    files: Dict[str, str]
+    rows: Dict[str, int]
+    event_tx_id: Optional[int]
+
+def export_tables(
+    store: PyChunkStore,
+    transaction_manager: PyTransactionManager,
+    dest: str,
+    tables: Optional[List[str]] = None,
+    branch: Optional[str] = None,
+    format: str = "parquet",
+    record_event: bool = False,
+) -> ExportManifest:
+    """Export several tables from one snapshot of a branch into dest.
+
+    A _manifest.json written last records the transaction id and table
+    versions. With record_event an empty transaction describing the export
+    is committed so it appears in the changelog.
+    """
+    ...
+
+def query(
+    store: PyChunkStore,
+    catalog: PyCatalog,
+    table_name: str,
+    version: Optional[int] = None,
+    branch: Optional[str] = None,
+    pin: Optional[str] = None,
+    columns: Optional[List[str]] = None,
+    filter: Optional[Union[PyPredicateFilter, List[PyPredicateFilter]]] = None,
+    branch_manager: Optional[PyBranchManager] = None,
+    at: Optional[str] = None,
+) -> List[pa.RecordBatch]:
+    """Read the rows of one table version matching a projection and filters.
+
+    The version is the one at the ref `at` or in pin, else version (checked
+    against branch when both are given), else the head of branch, else the
+    latest. Branch reads need branch_manager. Filters are combined with AND.
+    """
+    ...
+
+def resolve_ref(
+    catalog: PyCatalog,
+    reference: str,
+    branch_manager: Optional[PyBranchManager] = None,
+    transaction_manager: Optional[PyTransactionManager] = None,
+) -> Dict[str, int]:
+    """Resolve a ref to the table -> version mapping it names.
+
+    Refs: ``main``, ``main~3``, ``users@v7``, ``tag:release-1``,
+    ``tx:1234`` and ``repo@12``. Branch and snapshot refs need
+    branch_manager; ``tx:`` refs need transaction_manager.
+    """
+    ...
+
+def redact(
+    store: PyChunkStore,
+    catalog: PyCatalog,
+    table_name: str,
+    filter: Union[PyPredicateFilter, List[PyPredicateFilter]],
+    reason: Optional[str] = None,
+) -> Dict[int, int]:
+    """Remove the rows matching filter from every version of a table.
+
+    Versions keep their numbers, so branches, tags and pins read the
+    redacted data. Returns the rows removed per rewritten version; the
+    redaction is listed in PyCatalog.redaction_history.
+    """
+    ...
+
+def arrow_schema_hash(data: ArrowData) -> str:
+    """Schema hash write_arrow records for data with this schema."""
+    ...
+
+
 # =============================================================================
 # Phase R.2: Predicate Pushdown Types
 # =============================================================================
@@ -530,8 +1514,11 @@ class PyOpType:
     """Algebraic operation type classification.
 
     Operations are classified by their algebraic properties:
-    - Semilattice: Associative, commutative, idempotent (MAX, MIN, UNION, INTERSECT)
+    - Semilattice: Associative, commutative, idempotent (MAX, MIN, UNION, INTERSECT, OR_SET,
+      PN_COUNTER, LWW)
     - Abelian: Associative, commutative, has identity and inverse (ADD, MULTIPLY)
+    - Bounded: Addition kept within lower/upper bounds (BOUNDED_ADD)
+    - Custom: A registered merge function (PyOpType.custom(name))
     - Generic: No special properties (OVERWRITE, CONDITIONAL, UNKNOWN)
 
     Conflict-free operations (semilattice and Abelian) can be automatically merged.
@@ -541,13 +1528,27 @@ class PyOpType:
         """Create an operation type from string.
 
         Args:
-            op_type: One of "MAX", "MIN", "UNION", "INTERSECT", "ADD",
-                    "MULTIPLY", "OVERWRITE", "CONDITIONAL", "UNKNOWN"
+            op_type: One of "MAX", "MIN", "UNION", "INTERSECT", "OR_SET",
+                    "PN_COUNTER", "LWW", "ADD", "MULTIPLY", "BOUNDED_ADD",
+                    "OVERWRITE", "CONDITIONAL", "UNKNOWN"
         """
         ...
 
+    @staticmethod
+    def custom(name: str) -> "PyOpType":
+        """Operation merging with the function registered as name (see register_custom_merge)."""
+        ...
+
+    @staticmethod
+    def map(entry_op: "PyOpType") -> "PyOpType":
+        """Operation merging maps key by key, shared keys with entry_op."""
+        ...
+
     def is_conflict_free(self) -> bool:
-        """Check if this operation type can be auto-merged."""
+        """Check if this operation type can be auto-merged.
+
+        Custom operations are conflict-free while a function is registered.
+        """
         ...
 
     def is_semilattice(self) -> bool:
@@ -572,12 +1573,17 @@ class PyAlgebraicValue:
     Supports various types for different merge operations:
     - Integer: For counters, timestamps, numeric comparisons
     - Float: For continuous values
+    - Decimal: For exact amounts such as money (fixed-point)
     - StringSet: For tags, permissions (set operations)
     - IntSet: For ID collections
+    - ObservedRemoveSet: For string sets that also support removal
+    - PnCounter: For counters with separately audited increments/decrements
+    - LwwRegister: For overwritable fields (latest timestamp wins)
+    - BoundedCounter: For counters kept within bounds (stock, seats)
     - Boolean: For flags
     """
 
-    def __init__(self, value: Optional[Union[int, float, bool, List[str], List[int]]]) -> None:
+    def __init__(self, value: Optional[Union[int, float, Decimal, bool, List[str], List[int]]]) -> None:
         """Create an algebraic value from a Python value (type inferred)."""
         ...
 
@@ -591,6 +1597,18 @@ class PyAlgebraicValue:
         """Create a float value."""
         ...
 
+    @staticmethod
+    def decimal(value: Union[Decimal, int, str]) -> "PyAlgebraicValue":
+        """Create an exact decimal value (merges exactly with ADD, MAX and MIN).
+
+        Raises ValueError for NaN, infinities or more than 38 fractional digits.
+        """
+        ...
+
+    def to_decimal(self) -> Decimal:
+        """The value as a decimal.Decimal. Raises ValueError unless Decimal or Integer."""
+        ...
+
     @staticmethod
     def string_set(values: List[str]) -> "PyAlgebraicValue":
         """Create a string set value."""
@@ -601,6 +1619,71 @@ class PyAlgebraicValue:
         """Create an integer set value."""
         ...
 
+    @staticmethod
+    def or_set(elements: List[str]) -> "PyAlgebraicValue":
+        """Create an observed-remove set, each element under a fresh tag."""
+        ...
+
+    def or_set_removal(self, element: str) -> "PyAlgebraicValue":
+        """Delta removing an element's adds observed by this set (merge with OR_SET)."""
+        ...
+
+    def or_set_elements(self) -> List[str]:
+        """Elements present in an observed-remove set, sorted."""
+        ...
+
+    @staticmethod
+    def pn_counter(node: str, increments: int = 0, decrements: int = 0) -> "PyAlgebraicValue":
+        """Create a PN-counter holding one node's tallies (merge with PN_COUNTER)."""
+        ...
+
+    def pn_counter_totals(self) -> Tuple[int, int, int]:
+        """(value, total increments, total decrements) of a PN-counter."""
+        ...
+
+    @staticmethod
+    def bounded_counter(
+        origin: str, initial: int = 0, lower: Optional[int] = None, upper: Optional[int] = None
+    ) -> "PyAlgebraicValue":
+        """Create a bounded counter whose rights are all held by origin (merge with BOUNDED_ADD).
+
+        Raises:
+            ValueError: If initial is outside the bounds
+        """
+        ...
+
+    def bounded_update(self, node: str, delta: int) -> "PyAlgebraicValue":
+        """This bounded counter after node adds delta; ValueError if node lacks the rights."""
+        ...
+
+    def bounded_transfer(
+        self, from_node: str, to_node: str, decrement: int = 0, increment: int = 0
+    ) -> "PyAlgebraicValue":
+        """This bounded counter after from_node gives decrement/increment rights to to_node."""
+        ...
+
+    def bounded_state(self, node: str) -> Tuple[int, Optional[int], Optional[int]]:
+        """(value, decrement rights, increment rights) of node; rights are None on an open side."""
+        ...
+
+    @staticmethod
+    def lww(value: "PyAlgebraicValue", timestamp: int, node: str) -> "PyAlgebraicValue":
+        """Create a last-writer-wins register write (merge with LWW)."""
+        ...
+
+    def lww_parts(self) -> Tuple["PyAlgebraicValue", int, str]:
+        """(value, timestamp, node) of a last-writer-wins register write."""
+        ...
+
+    @staticmethod
+    def map(entries: Dict[str, "PyAlgebraicValue"]) -> "PyAlgebraicValue":
+        """Create a map value (merge with PyOpType.map(entry_op))."""
+        ...
+
+    def map_entries(self) -> Dict[str, "PyAlgebraicValue"]:
+        """Entries of a map value. Raises ValueError for other values."""
+        ...
+
     @staticmethod
     def boolean(value: bool) -> "PyAlgebraicValue":
         """Create a boolean value."""
@@ -612,7 +1695,7 @@ class PyAlgebraicValue:
         ...
 
     def is_numeric(self) -> bool:
-        """Check if this is a numeric type."""
+        """Check if this is a numeric type (Integer, Float or Decimal)."""
         ...
 
     def is_set(self) -> bool:
@@ -655,6 +1738,16 @@ class PyTableAlgebraicSchema:
         """Add a column with the specified operation type."""
         ...
 
+    def add_bounded_column(
+        self, column: str, lower: Optional[int] = None, upper: Optional[int] = None
+    ) -> None:
+        """Add a BOUNDED_ADD column whose merged values must stay within the bounds."""
+        ...
+
+    def get_bounds(self, column: str) -> Optional[Tuple[Optional[int], Optional[int]]]:
+        """(lower, upper) bounds of a column, or None."""
+        ...
+
     def get_op_type(self, column: str) -> PyOpType:
         """Get the operation type for a column."""
         ...
@@ -702,6 +1795,23 @@ class PyAlgebraicSchemaRegistry:
         """Get all registered table names."""
         ...
 
+    def unregister(self, table: str) -> Optional[PyTableAlgebraicSchema]:
+        """Remove a schema from the registry."""
+        ...
+
+    def extend(self, other: PyAlgebraicSchemaRegistry) -> None:
+        """Register every schema of `other`, replacing schemas of the same tables."""
+        ...
+
+    def save(self, path: str) -> None:
+        """Write the registry to a JSON file."""
+        ...
+
+    @staticmethod
+    def load(path: str) -> PyAlgebraicSchemaRegistry:
+        """Read a registry written by save(). Raises IOError if invalid."""
+        ...
+
 
 class PyMergeAnalysis:
     """Result of analyzing merge compatibility between branches."""
@@ -733,6 +1843,28 @@ class PyMergeOutcome:
     description: Optional[str]
 
 
+class PyTableMerge:
+    """Row merge of one table, as a branch merge would commit it."""
+
+    table: str
+    base_version: Optional[int]
+    source_version: int
+    target_version: int
+    version: int
+    rows: int
+    rows_from_source: int
+    rows_combined: int
+    rows_deleted: int
+
+
+class PyMergeSimulation:
+    """What a branch merge would do, without writing anything."""
+
+    outcome: PyMergeOutcome
+    heads: Dict[str, int]
+    tables: List[PyTableMerge]
+
+
 def algebraic_merge(
     op_type: PyOpType,
     value1: PyAlgebraicValue,
@@ -754,6 +1886,46 @@ def algebraic_merge(
     ...
 
 
+def register_custom_merge(
+    name: str,
+    merge: Callable[[PyAlgebraicValue, PyAlgebraicValue], PyAlgebraicValue],
+    samples: Optional[List[PyAlgebraicValue]] = None,
+) -> None:
+    """Register a merge function for PyOpType.custom(name), replacing any existing one.
+
+    The function must be commutative and associative; raising from it makes
+    the merge a conflict. With samples, both properties are checked on them
+    before the function is accepted.
+
+    Raises:
+        ValueError: If the name is empty or a property check fails
+    """
+    ...
+
+
+def unregister_custom_merge(name: str) -> bool:
+    """Remove a custom merge function. Returns False if none was registered."""
+    ...
+
+
+def custom_merge_names() -> List[str]:
+    """Names with a registered custom merge function, sorted."""
+    ...
+
+
+def verify_algebraic_laws(
+    op_type: PyOpType,
+    samples: Optional[List[PyAlgebraicValue]] = None,
+    count: int = 20,
+    seed: int = 0,
+) -> List[str]:
+    """Check the laws op_type promises on samples (or count values generated from seed).
+
+    Returns one description per violation; empty if the laws hold.
+    """
+    ...
+
+
 def analyze_merge(
     registry: PyAlgebraicSchemaRegistry,
     source_branch: PyBranch,
@@ -772,6 +1944,43 @@ def analyze_merge(
     ...
 
 
+def merge_branch_data(
+    registry: PyAlgebraicSchemaRegistry,
+    catalog: PyCatalog,
+    store: PyChunkStore,
+    branch_manager: PyBranchManager,
+    source: str,
+    into: str,
+    keys: Optional[Dict[str, List[str]]] = None,
+) -> PyMergeOutcome:
+    """Merge branch source into into, merging rows of tables both changed.
+
+    Rows are matched by key (default column "id", or `keys[table]`) and
+    cells changed on both sides are combined with the column's algebraic
+    operation. The merged rows are committed as a new version. If any row
+    cannot be merged, nothing is written and the outcome lists the
+    conflicting tables.
+    """
+    ...
+
+
+def simulate_branch_merge(
+    registry: PyAlgebraicSchemaRegistry,
+    catalog: PyCatalog,
+    store: PyChunkStore,
+    branch_manager: PyBranchManager,
+    source: str,
+    into: str,
+    keys: Optional[Dict[str, List[str]]] = None,
+) -> PyMergeSimulation:
+    """Run merge_branch_data in memory, writing no chunks, versions or branches.
+
+    Returns the outcome, the heads `into` would have afterwards and the
+    row statistics of each merged table.
+    """
+    ...
+
+
 # ============================================================================
 # Distributed Types (Coordination-Free Transactions)
 # ============================================================================
@@ -1079,6 +2288,15 @@ class PyVersionedUpdate:
         """Check if this update is concurrent with another."""
         ...
 
+    def to_json(self) -> str:
+        """Serialize to JSON string, to send the update to other nodes."""
+        ...
+
+    @staticmethod
+    def from_json(json: str) -> "PyVersionedUpdate":
+        """Deserialize from JSON string. Raises ValueError if invalid."""
+        ...
+
     def __repr__(self) -> str:
         ...
 
@@ -1204,6 +2422,77 @@ class PyLocalCommitProtocol:
         ...
 
 
+
+class PyCoordinationFreeManager:
+    """A node's coordination-free transaction manager.
+
+    Keeps the node's vector clock and the merged state of every key it has
+    seen: commit algebraic transactions locally, ship the updates (see
+    PyVersionedUpdate.to_json) and apply updates from other nodes.
+
+    Example:
+        >>> sf = PyCoordinationFreeManager(PyNodeId("sf"))
+        >>> tokyo = PyCoordinationFreeManager(PyNodeId("tokyo"))
+        >>> tx = PyAlgebraicTransaction()
+        >>> tx.add_operation(PyAlgebraicOperation("views", PyOpType("add"), PyAlgebraicValue(5)))
+        >>> update = sf.commit_local(tx)
+        >>> tokyo.receive_update(update)
+        >>> tokyo.get_state("views")
+    """
+
+    def __init__(self, node_id: PyNodeId, require_fully_algebraic: bool = True) -> None:
+        """Create a manager for node_id; optionally accept non-algebraic operations."""
+        ...
+
+    @property
+    def node_id(self) -> PyNodeId:
+        """This node's ID."""
+        ...
+
+    def clock(self) -> PyVectorClock:
+        """A copy of the node's vector clock."""
+        ...
+
+    def can_commit_locally(self, tx: PyAlgebraicTransaction) -> bool:
+        """Check if a transaction can be committed without coordination."""
+        ...
+
+    def commit_local(self, tx: PyAlgebraicTransaction) -> PyVersionedUpdate:
+        """Commit a transaction locally, applying it to this node's state.
+
+        Returns:
+            The update to send to other nodes
+
+        Raises:
+            ValueError: If the transaction cannot be committed locally, or
+                does not merge with the node's state
+        """
+        ...
+
+    def receive_update(self, update: PyVersionedUpdate) -> None:
+        """Apply an update committed on another node. Raises ValueError if it does not merge."""
+        ...
+
+    def merge_updates(self, update1: PyVersionedUpdate, update2: PyVersionedUpdate) -> PyVersionedUpdate:
+        """Merge two updates without touching this node's state."""
+        ...
+
+    def get_state(self, key: str) -> Optional[PyAlgebraicValue]:
+        """Current value of a key, or None if no update touched it."""
+        ...
+
+    def keys(self) -> List[str]:
+        """Keys with a value, sorted."""
+        ...
+
+    def update_count(self) -> int:
+        """Number of transactions committed on this node."""
+        ...
+
+    def __repr__(self) -> str:
+        ...
+
+
 # ============================================================================
 # Phase 4: Simulation Types (Multi-Node Convergence Testing)
 # ============================================================================
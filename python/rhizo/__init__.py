@@ -10,9 +10,13 @@ This module provides:
 - TransactionContext: ACID transactions across multiple tables
 - Subscriber: Stream changelog events with polling or callbacks
 - ChangeEvent: Individual table change within a transaction
+- ChangelogExporter: Mirror the changelog into a SQLite/Postgres table
 - ExportEngine: Export tables to Parquet, CSV, or JSON
 - ExportResult: Metadata from an export operation
 - Filter: Predicate filter builder for pushdown optimization
+- ReadAuditor, EventBus: Sampled read auditing published on an event bus
+- MergeEvent: Merge started/conflicted/completed events for UI tooling
+- RestGateway: Read-only HTTP+JSON gateway for browser clients
 
 Low-level types (from _rhizo):
 - PyChunkStore: Content-addressable chunk storage
@@ -27,15 +31,28 @@ Low-level types (from _rhizo):
 """
 
 from .writer import TableWriter
-from .database import Database, open
+from .database import Database, open, ephemeral_repo
 from .reader import TableReader, Filter
 from .engine import QueryEngine
 from .export import ExportEngine, ExportResult
-from .transaction import TransactionContext
+from .transaction import TransactionContext, RetryingTransaction, TransactionAttempt
 from .subscriber import Subscriber, ChangeEvent
+from .changelog_export import ChangelogExporter
 from .cache import CacheManager, CacheKey, CacheStats
-from .diff import DiffEngine, DiffResult, SchemaDiff, RowDiff
+from .diff import (
+    DiffEngine, DiffResult, SchemaDiff, RowDiff, ChangeReport, TableChangeSummary,
+)
 from .gc import GCPolicy, GCResult, GarbageCollector, AutoGC
+from .events import EventBus
+from .audit import ReadAuditor, ReadEvent, JsonlAuditSink, READ_TOPIC
+from .merge_events import (
+    MergeEvent, MERGE_STARTED_TOPIC, MERGE_CONFLICTED_TOPIC, MERGE_COMPLETED_TOPIC,
+)
+from .quota import Quota, QuotaEnforcer, QuotaEvent, QuotaStatus, QUOTA_TOPIC
+from .repo_filter import filter_database
+from .backup import backup_database, restore_database, list_backups
+from .admin import AdminAPI
+from .gateway import RestGateway
 from .table_meta import TableMeta, TableMetaStore
 from .schema_utils import serialize_schema, deserialize_schema, compare_schemas, SchemaComparisonResult
 from .olap_engine import OLAPEngine, is_datafusion_available
@@ -49,12 +66,23 @@ from .metrics import (
 )
 from .exceptions import (
     RhizoError,
+    ConflictError,
+    WriteConflictError,
+    SnapshotConflictError,
+    MergeConflictError,
+    NotFoundError,
+    BranchNotFoundError,
+    ChunkNotFoundError,
+    IntegrityError,
+    HashMismatchError,
     TableNotFoundError,
     VersionNotFoundError,
     EmptyResultError,
     SizeLimitExceededError,
     SchemaEvolutionError,
     PrimaryKeyViolationError,
+    QuotaExceededError,
+    RetriesExhaustedError,
 )
 
 # Re-export low-level types from _rhizo for convenience
@@ -100,6 +128,7 @@ from _rhizo import (
     PyAlgebraicTransaction,
     PyVersionedUpdate,
     PyLocalCommitProtocol,
+    PyCoordinationFreeManager,
 )
 
 # Simulation types (optional — only available when compiled with simulation support)
@@ -162,6 +191,7 @@ except ImportError:
 __all__ = [
     # High-level API
     "open",
+    "ephemeral_repo",
     "export",
     "Database",
     "TableWriter",
@@ -175,8 +205,11 @@ __all__ = [
     "CacheStats",
     "is_datafusion_available",
     "TransactionContext",
+    "RetryingTransaction",
+    "TransactionAttempt",
     "Subscriber",
     "ChangeEvent",
+    "ChangelogExporter",
     "Filter",
     # Metrics & Instrumentation (Coordination Bounds Validation)
     "AlgebraicSignature",
@@ -188,8 +221,36 @@ __all__ = [
     # Diff
     "DiffEngine",
     "DiffResult",
+    "ChangeReport",
+    "TableChangeSummary",
     "SchemaDiff",
     "RowDiff",
+    # Events & Auditing
+    "EventBus",
+    "ReadAuditor",
+    "ReadEvent",
+    "JsonlAuditSink",
+    "READ_TOPIC",
+    "MergeEvent",
+    "MERGE_STARTED_TOPIC",
+    "MERGE_CONFLICTED_TOPIC",
+    "MERGE_COMPLETED_TOPIC",
+    # Quotas
+    "Quota",
+    "QuotaEnforcer",
+    "QuotaEvent",
+    "QuotaStatus",
+    "QUOTA_TOPIC",
+    # Repository filtering
+    "filter_database",
+    # Backup and restore
+    "backup_database",
+    "restore_database",
+    "list_backups",
+    # Administration (legal holds, frozen branches)
+    "AdminAPI",
+    # Read-only HTTP gateway
+    "RestGateway",
     # Garbage Collection
     "GCPolicy",
     "GCResult",
@@ -204,10 +265,21 @@ __all__ = [
     "compare_schemas",
     # Exceptions
     "RhizoError",
+    "ConflictError",
+    "WriteConflictError",
+    "SnapshotConflictError",
+    "MergeConflictError",
+    "NotFoundError",
+    "BranchNotFoundError",
+    "ChunkNotFoundError",
+    "IntegrityError",
+    "HashMismatchError",
     "TableNotFoundError",
     "VersionNotFoundError",
     "EmptyResultError",
     "SizeLimitExceededError",
+    "QuotaExceededError",
+    "RetriesExhaustedError",
     "SchemaEvolutionError",
     "PrimaryKeyViolationError",
     # Low-level types
@@ -252,6 +324,7 @@ __all__ = [
     "PyAlgebraicTransaction",
     "PyVersionedUpdate",
     "PyLocalCommitProtocol",
+    "PyCoordinationFreeManager",
     # Simulation types
     "PyNetworkCondition",
     "PySimulationConfig",
@@ -6,8 +6,13 @@ from typing import List, Dict, Optional, Tuple, Union
 from .writer import TableWriter as TableWriter
 from .reader import TableReader as TableReader, Filter as Filter
 from .engine import QueryEngine as QueryEngine
-from .transaction import TransactionContext as TransactionContext
+from .transaction import (
+    TransactionContext as TransactionContext,
+    RetryingTransaction as RetryingTransaction,
+    TransactionAttempt as TransactionAttempt,
+)
 from .subscriber import Subscriber as Subscriber, ChangeEvent as ChangeEvent
+from .changelog_export import ChangelogExporter as ChangelogExporter
 from .cache import CacheManager as CacheManager, CacheKey as CacheKey, CacheStats as CacheStats
 from .olap_engine import OLAPEngine as OLAPEngine, is_datafusion_available as is_datafusion_available
 
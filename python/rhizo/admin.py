@@ -0,0 +1,134 @@
+"""
+Administrative operations: releasing legal holds and branch freezes, and
+breaking stale table locks.
+
+Placing a hold (``Database.hold``) or freezing a branch
+(``Database.freeze_branch``) is an ordinary operation. Listing and lifting
+them are kept here, behind ``Database.admin``, so application code paths
+that read and write data never release compliance retention by accident.
+Every release is logged at WARNING level for the audit trail.
+
+Example:
+    >>> db.hold("trades", reason="SEC-2024-117")
+    >>> db.admin.holds()
+    [PyLegalHold(table="trades", version=3)]
+    >>> db.admin.release_hold("trades", 3)
+"""
+
+from __future__ import annotations
+
+import logging
+from typing import TYPE_CHECKING, List, Optional
+
+if TYPE_CHECKING:
+    from _rhizo import PyBranchManager, PyCatalog, PyLegalHold, PyLockOwner
+
+logger = logging.getLogger(__name__)
+
+
+class AdminAPI:
+    """Lists and releases legal holds, immutable branches and table locks."""
+
+    def __init__(self, catalog: "PyCatalog", branch_manager: Optional["PyBranchManager"] = None):
+        self._catalog = catalog
+        self._branch_manager = branch_manager
+
+    def holds(self) -> List["PyLegalHold"]:
+        """
+        List all legal holds, sorted by table and version.
+
+        Returns:
+            Holds with table_name, version, reason and created_at
+        """
+        return self._catalog.list_holds()
+
+    def release_hold(self, table_name: str, version: int) -> "PyLegalHold":
+        """
+        Release a legal hold, making the version eligible for deletion again.
+
+        Args:
+            table_name: Held table
+            version: Held version
+
+        Returns:
+            The released hold
+
+        Raises:
+            IOError: If the version is not held
+        """
+        released = self._catalog.release_hold(table_name.lower(), version)
+        logger.warning(
+            "Legal hold released: %s v%d (reason: %s)",
+            released.table_name, released.version, released.reason,
+        )
+        return released
+
+    def frozen_branches(self) -> List[str]:
+        """
+        List immutable branches.
+
+        Returns:
+            Sorted branch names (empty if branching is disabled)
+        """
+        if self._branch_manager is None:
+            return []
+        return [
+            name for name in self._branch_manager.list()
+            if self._branch_manager.get(name).immutable
+        ]
+
+    def unfreeze_branch(self, name: str) -> None:
+        """
+        Make an immutable branch writable again.
+
+        Args:
+            name: Branch name
+
+        Raises:
+            RuntimeError: If branching is disabled
+            IOError: If the branch does not exist
+        """
+        if self._branch_manager is None:
+            raise RuntimeError("Cannot unfreeze branch: branch_manager not configured")
+        self._branch_manager.set_immutable(name, False)
+        logger.warning("Branch unfrozen: %s", name)
+
+    def lock_owner(self, table_name: str) -> Optional["PyLockOwner"]:
+        """
+        Report the process holding a table's commit lock.
+
+        Args:
+            table_name: Table whose commits are stuck
+
+        Returns:
+            The holder's pid, host and acquisition time, or None if the
+            lock is free
+        """
+        return self._catalog.lock_owner(table_name.lower())
+
+    def break_lock(self, table_name: str, force: bool = False) -> Optional["PyLockOwner"]:
+        """
+        Break a table's commit lock left behind by a dead process.
+
+        Only locks whose holder is known to have exited are broken. Pass
+        ``force=True`` when the holder is confirmed dead by other means
+        (e.g. a crashed host on a network filesystem); breaking a live
+        holder's lock lets two commits run at once.
+
+        Args:
+            table_name: Locked table
+            force: Break the lock even if the holder may be alive
+
+        Returns:
+            The owner the lock recorded, or None if the lock was free
+
+        Raises:
+            ValueError: If the holder may still be alive and force is False
+        """
+        owner = self._catalog.break_lock(table_name.lower(), force)
+        if owner is not None:
+            logger.warning(
+                "Table lock broken: %s (pid %d on %s, force=%s)",
+                table_name, owner.pid, owner.host, force,
+            )
+        return owner
@@ -0,0 +1,170 @@
+"""
+Read auditing — who read which table version, and when.
+
+Write auditing already comes from the changelog; this module covers reads.
+A ReadAuditor is attached to a QueryEngine (or Database) and publishes a
+ReadEvent on the event bus for every audited table read or query. Sinks
+such as JsonlAuditSink subscribe to the bus and persist the events.
+
+Auditing is off unless an auditor is configured. Sampling keeps the cost
+bounded on hot paths: ``sample_rate`` applies to every table, and
+``table_rates`` overrides it per table or namespace (``1.0`` to always
+audit sensitive tables, ``0.0`` to never audit noisy ones).
+
+Example:
+    >>> from rhizo.audit import READ_TOPIC, ReadAuditor, JsonlAuditSink
+    >>> auditor = ReadAuditor(sample_rate=0.1, table_rates={"pii": 1.0})
+    >>> auditor.bus.subscribe(READ_TOPIC, JsonlAuditSink("./audit/reads.jsonl"))
+    >>> db = rhizo.open("./mydata", read_auditor=auditor)
+"""
+
+from __future__ import annotations
+
+import getpass
+import json
+import random
+import threading
+import time
+from dataclasses import asdict, dataclass
+from typing import Callable, Dict, Optional, Union
+
+from .events import EventBus
+
+# Event bus topic for read events
+READ_TOPIC = "read"
+
+
+@dataclass(frozen=True)
+class ReadEvent:
+    """
+    A single audited table read.
+
+    Attributes:
+        table_name: Table that was read
+        version: Version that was read (None when the version was chosen
+                 inside the SQL, e.g. ``users VERSION 3``)
+        principal: Who performed the read
+        operation: "read" for direct table reads, "query" for SQL
+        branch: Branch the read resolved against (None if branchless)
+        timestamp: Unix timestamp (seconds, float) of the read
+    """
+    table_name: str
+    version: Optional[int]
+    principal: str
+    operation: str
+    branch: Optional[str]
+    timestamp: float
+
+    def to_dict(self) -> Dict[str, object]:
+        return asdict(self)
+
+
+def _default_principal() -> str:
+    try:
+        return getpass.getuser()
+    except Exception:
+        return "unknown"
+
+
+class ReadAuditor:
+    """
+    Samples table reads and publishes them as ReadEvents.
+
+    Args:
+        bus: EventBus to publish to (default: a new private bus)
+        sample_rate: Fraction of reads to audit, 0.0 - 1.0 (default: 1.0)
+        table_rates: Per-table overrides of sample_rate. Keys are table names
+                     or namespace prefixes; the longest matching key wins.
+        principal: Name of the reader, or a callable returning it per read
+                   (e.g. from request context). Default: the OS user.
+    """
+
+    def __init__(
+        self,
+        bus: Optional[EventBus] = None,
+        sample_rate: float = 1.0,
+        table_rates: Optional[Dict[str, float]] = None,
+        principal: Union[str, Callable[[], str], None] = None,
+    ):
+        for rate in [sample_rate, *(table_rates or {}).values()]:
+            if not 0.0 <= rate <= 1.0:
+                raise ValueError(f"Sample rates must be between 0.0 and 1.0, got {rate}")
+
+        self.bus = bus if bus is not None else EventBus()
+        self.sample_rate = sample_rate
+        self.table_rates = dict(table_rates or {})
+        self._principal = principal if principal is not None else _default_principal()
+        self._random = random.Random()
+
+    def rate_for(self, table_name: str) -> float:
+        """Effective sample rate for a table."""
+        best: Optional[str] = None
+        for key in self.table_rates:
+            if table_name == key or table_name.startswith((key + ".", key + "/")):
+                if best is None or len(key) > len(best):
+                    best = key
+        return self.table_rates[best] if best is not None else self.sample_rate
+
+    def should_audit(self, table_name: str) -> bool:
+        """Sampling decision for one read."""
+        rate = self.rate_for(table_name)
+        if rate >= 1.0:
+            return True
+        if rate <= 0.0:
+            return False
+        return self._random.random() < rate
+
+    def record(
+        self,
+        table_name: str,
+        version: Optional[int],
+        operation: str = "read",
+        branch: Optional[str] = None,
+    ) -> Optional[ReadEvent]:
+        """
+        Publish a read event unless it is sampled out.
+
+        Returns:
+            The published event, or None if not sampled
+        """
+        if not self.should_audit(table_name):
+            return None
+        return self.emit(table_name, version, operation, branch)
+
+    def emit(
+        self,
+        table_name: str,
+        version: Optional[int],
+        operation: str = "read",
+        branch: Optional[str] = None,
+    ) -> ReadEvent:
+        """Publish a read event without sampling (caller already sampled)."""
+        principal = self._principal() if callable(self._principal) else self._principal
+        event = ReadEvent(
+            table_name=table_name,
+            version=version,
+            principal=principal,
+            operation=operation,
+            branch=branch,
+            timestamp=time.time(),
+        )
+        self.bus.publish(READ_TOPIC, event)
+        return event
+
+
+class JsonlAuditSink:
+    """
+    Event bus handler that appends ReadEvents to a JSON Lines file.
+
+    Args:
+        path: File to append to (created if missing)
+    """
+
+    def __init__(self, path: str):
+        self.path = path
+        self._lock = threading.Lock()
+
+    def __call__(self, event: ReadEvent) -> None:
+        line = json.dumps(event.to_dict(), sort_keys=True)
+        with self._lock, open(self.path, "a", encoding="utf-8") as f:
+            f.write(line + "\n")
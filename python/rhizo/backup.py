@@ -0,0 +1,174 @@
+"""
+Backup and restore with incremental support.
+
+A backup is a directory holding a checksummed manifest and the chunks it
+adds. An incremental backup extends an earlier one and stores only versions
+and chunks the earlier chain lacks, so daily backups stay small. Restoring
+the newest backup of a chain verifies every backup in it first, then
+rebuilds tables, branches, pins and legal holds in an empty database.
+
+Example:
+    >>> from rhizo.backup import backup_database, restore_database
+    >>> backup_database("./prod_db", "/backups/mon")
+    >>> backup_database("./prod_db", "/backups/tue", incremental_since="/backups/mon")
+    >>> restore_database("/backups/tue", verify_only=True)
+    VerifyReport(backups=2, ...)
+    >>> restore_database("/backups/tue", "./recovered_db")
+"""
+
+from __future__ import annotations
+
+import json
+from dataclasses import asdict
+from pathlib import Path
+from typing import Any, List, Optional
+
+from .table_meta import TableMeta, TableMetaStore
+
+#: Primary keys and schema modes, stored next to the manifest
+TABLE_META_FILE = "table_meta.json"
+
+
+def backup_database(
+    source: str,
+    dest: str,
+    *,
+    incremental_since: Optional[str] = None,
+):
+    """
+    Back up the database at ``source`` into the new directory ``dest``.
+
+    Args:
+        source: Path of the database directory
+        dest: Backup directory (must not already hold a backup)
+        incremental_since: Directory of an earlier backup of the same
+                           database. Only versions and chunks missing from
+                           it (and the chain it extends) are stored.
+
+    Returns:
+        BackupManifest of the new backup
+
+    Raises:
+        FileNotFoundError: If the database does not exist
+        IOError: If ``incremental_since`` or its chain cannot be found
+        ValueError: If ``dest`` already holds a backup
+    """
+    from _rhizo import PyBranchManager, PyCatalog, PyChunkStore
+
+    source_path = Path(source).resolve()
+    if not (source_path / "catalog").is_dir():
+        raise FileNotFoundError(f"Database not found: {source}")
+
+    branches = None
+    if (source_path / "branches").is_dir():
+        branches = PyBranchManager(str(source_path / "branches"))
+
+    return _backup_handles(
+        PyChunkStore(str(source_path / "chunks")),
+        PyCatalog(str(source_path / "catalog")),
+        branches,
+        source_path / "catalog",
+        dest,
+        incremental_since,
+    )
+
+
+def restore_database(
+    src: str,
+    dest: Optional[str] = None,
+    *,
+    verify_only: bool = False,
+):
+    """
+    Restore the backup in ``src`` (and the chain it extends) into ``dest``.
+
+    Args:
+        src: Directory of the newest backup to restore
+        dest: Path of the database directory to restore into (created if
+              missing; must not contain any backed-up table). Not needed
+              with ``verify_only``.
+        verify_only: Only check manifest checksums, parent links and chunk
+                     hashes; write nothing
+
+    Returns:
+        VerifyReport with ``verify_only``, otherwise RestoreReport
+
+    Raises:
+        IOError: If a backup in the chain or one of its chunks is missing
+        ValueError: If a checksum fails, or ``dest`` already has a
+                    backed-up table or non-empty branch
+    """
+    from _rhizo import PyBranchManager, PyCatalog, PyChunkStore, verify_backup
+
+    if verify_only:
+        return verify_backup(str(Path(src).resolve()))
+    if dest is None:
+        raise ValueError("dest is required unless verify_only=True")
+
+    dest_path = Path(dest).resolve()
+    for sub in ("chunks", "catalog", "branches"):
+        (dest_path / sub).mkdir(parents=True, exist_ok=True)
+
+    return _restore_handles(
+        src,
+        PyChunkStore(str(dest_path / "chunks")),
+        PyCatalog(str(dest_path / "catalog")),
+        PyBranchManager(str(dest_path / "branches")),
+        dest_path / "catalog",
+    )
+
+
+def list_backups(src: str) -> List[Any]:
+    """
+    Manifests of the backup in ``src`` and every backup it extends.
+
+    Returns:
+        BackupManifest summaries, base backup first
+    """
+    from _rhizo import backup_chain
+
+    return backup_chain(str(Path(src).resolve()))
+
+
+def _backup_handles(store, catalog, branch_manager, catalog_dir: Path,
+                    dest: str, incremental_since: Optional[str] = None):
+    """Back up open storage handles; see ``backup_database``."""
+    from _rhizo import backup_repository
+
+    dest_path = Path(dest).resolve()
+    manifest = backup_repository(
+        store, catalog, str(dest_path),
+        source_branches=branch_manager,
+        incremental_since=str(Path(incremental_since).resolve()) if incremental_since else None,
+    )
+
+    meta_store = TableMetaStore(str(catalog_dir))
+    table_meta = {
+        table: asdict(meta_store.load(table))
+        for table in manifest.tables
+        if (Path(catalog_dir) / table / TableMetaStore.META_FILENAME).exists()
+    }
+    with open(dest_path / TABLE_META_FILE, "w") as f:
+        json.dump(table_meta, f, indent=2, sort_keys=True)
+
+    return manifest
+
+
+def _restore_handles(src: str, store, catalog, branch_manager, catalog_dir: Path):
+    """Restore into open storage handles; see ``restore_database``."""
+    from _rhizo import restore_backup
+
+    src_path = Path(src).resolve()
+    report = restore_backup(
+        str(src_path), store, catalog, target_branches=branch_manager,
+    )
+
+    meta_path = src_path / TABLE_META_FILE
+    if meta_path.exists():
+        with open(meta_path) as f:
+            table_meta = json.load(f)
+        meta_store = TableMetaStore(str(catalog_dir))
+        for table, meta in table_meta.items():
+            meta_store.save(table, TableMeta(**meta))
+
+    return report
@@ -0,0 +1,219 @@
+"""
+Changelog export - mirror the changelog into an external SQL table.
+
+ChangelogExporter copies every table change recorded in the changelog into
+a table in SQLite or Postgres, one row per (transaction, table):
+
+    tx_id | branch | table_name | old_version | new_version | committed_at | author
+
+so analysts can query history with plain SQL joins instead of the Rhizo
+API. The author comes from the transaction's "author" metadata
+(``engine.transaction(author=...)``) and is NULL when not set.
+
+The exporter resumes from the highest tx_id already in the target table,
+so it can be restarted (or run from cron) without duplicating rows. Run
+``start()`` to keep the table up to date from a background watcher.
+
+Example:
+    >>> import sqlite3
+    >>> from rhizo.changelog_export import ChangelogExporter
+    >>> conn = sqlite3.connect("history.db", check_same_thread=False)
+    >>> exporter = ChangelogExporter(engine.transaction_manager, conn)
+    >>> exporter.sync()       # Export everything committed so far
+    >>> exporter.start()      # Keep exporting new commits
+    >>> conn.execute(
+    ...     "SELECT author, COUNT(*) FROM rhizo_changelog GROUP BY author"
+    ... ).fetchall()
+"""
+
+from __future__ import annotations
+
+import re
+import sqlite3
+import threading
+from typing import TYPE_CHECKING, Any, List, Optional
+
+from .logging import get_logger
+from .subscriber import ChangeEvent
+
+if TYPE_CHECKING:
+    import _rhizo
+
+_logger = get_logger(__name__)
+
+DEFAULT_TABLE = "rhizo_changelog"
+
+# Parameter placeholder per supported dialect
+DIALECTS = {"sqlite": "?", "postgres": "%s"}
+
+_IDENTIFIER = re.compile(r"^[A-Za-z_][A-Za-z0-9_]*(\.[A-Za-z_][A-Za-z0-9_]*)?$")
+
+
+class ChangelogExporter:
+    """
+    Mirrors the changelog into a SQL table through a DB-API connection.
+
+    Args:
+        transaction_manager: The transaction manager whose changelog to export
+        connection: DB-API connection (sqlite3, psycopg, psycopg2, ...)
+        table: Target table name, optionally schema-qualified
+        dialect: "sqlite" or "postgres" (default: sqlite for sqlite3
+                 connections, postgres otherwise)
+        branch: Only export changes on this branch (None = all branches)
+        tables: Only export transactions touching these tables (None = all)
+        poll_interval: Seconds between polls when running in the background
+
+    Note:
+        start() uses the connection from a background thread; sqlite3
+        connections must be opened with ``check_same_thread=False``.
+    """
+
+    def __init__(
+        self,
+        transaction_manager: "_rhizo.PyTransactionManager",
+        connection: Any,
+        table: str = DEFAULT_TABLE,
+        dialect: Optional[str] = None,
+        branch: Optional[str] = None,
+        tables: Optional[List[str]] = None,
+        poll_interval: float = 1.0,
+    ):
+        if not _IDENTIFIER.match(table):
+            raise ValueError(f"Invalid table name: {table!r}")
+        if dialect is None:
+            dialect = "sqlite" if isinstance(connection, sqlite3.Connection) else "postgres"
+        if dialect not in DIALECTS:
+            raise ValueError(
+                f"Unknown dialect: {dialect!r} (expected one of {sorted(DIALECTS)})"
+            )
+
+        self._tx_manager = transaction_manager
+        self._connection = connection
+        self._table = table
+        self._placeholder = DIALECTS[dialect]
+        self._branch = branch
+        self._tables = tables
+        self._poll_interval = poll_interval
+        self._lock = threading.Lock()
+        self._stop = threading.Event()
+        self._thread: Optional[threading.Thread] = None
+
+        self._create_table()
+        self._last_tx_id = self._exported_tx_id()
+
+    @property
+    def table(self) -> str:
+        """Name of the target table."""
+        return self._table
+
+    @property
+    def last_tx_id(self) -> int:
+        """Highest transaction ID exported so far (0 if none)."""
+        return self._last_tx_id
+
+    @property
+    def is_running(self) -> bool:
+        """Check if the background watcher is running."""
+        return self._thread is not None and self._thread.is_alive()
+
+    def sync(self) -> int:
+        """
+        Export changes committed since the last sync.
+
+        The cursor only advances once the rows are committed, so a failed
+        sync is retried in full by the next one.
+
+        Returns:
+            Number of change rows exported
+        """
+        with self._lock:
+            entries = self._tx_manager.get_changelog(
+                since_tx_id=self._last_tx_id,
+                tables=self._tables,
+                branch=self._branch,
+            )
+            if not entries:
+                return 0
+
+            events: List[ChangeEvent] = []
+            for entry in entries:
+                events.extend(ChangeEvent.from_changelog_entry(entry))
+            inserted = self._insert(events) if events else 0
+            self._last_tx_id = entries[-1].tx_id
+            return inserted
+
+    def start(self) -> None:
+        """
+        Export new changes from a background thread, polling every
+        poll_interval seconds. Errors are logged and retried.
+
+        Raises:
+            RuntimeError: If the exporter is already running
+        """
+        if self.is_running:
+            raise RuntimeError("Changelog exporter already running")
+
+        self._stop.clear()
+        self._thread = threading.Thread(target=self._run, daemon=True)
+        self._thread.start()
+
+    def stop(self) -> None:
+        """Stop the background watcher. Safe to call if not running."""
+        self._stop.set()
+        if self._thread is not None:
+            self._thread.join(timeout=self._poll_interval * 2)
+            self._thread = None
+
+    def _run(self) -> None:
+        while not self._stop.is_set():
+            try:
+                self.sync()
+            except Exception as e:
+                _logger.warning("Changelog export error: %s", e)
+            self._stop.wait(self._poll_interval)
+
+    def _create_table(self) -> None:
+        cursor = self._connection.cursor()
+        cursor.execute(
+            f"CREATE TABLE IF NOT EXISTS {self._table} ("
+            "tx_id BIGINT NOT NULL, "
+            "branch TEXT NOT NULL, "
+            "table_name TEXT NOT NULL, "
+            "old_version BIGINT, "
+            "new_version BIGINT NOT NULL, "
+            "committed_at BIGINT NOT NULL, "
+            "author TEXT, "
+            "PRIMARY KEY (tx_id, table_name))"
+        )
+        self._connection.commit()
+
+    def _exported_tx_id(self) -> int:
+        cursor = self._connection.cursor()
+        cursor.execute(f"SELECT MAX(tx_id) FROM {self._table}")
+        row = cursor.fetchone()
+        return int(row[0]) if row and row[0] is not None else 0
+
+    def _insert(self, events: List[ChangeEvent]) -> int:
+        p = self._placeholder
+        rows = [
+            (
+                e.tx_id, e.branch, e.table_name, e.old_version,
+                e.new_version, e.committed_at, e.author,
+            )
+            for e in events
+        ]
+        cursor = self._connection.cursor()
+        try:
+            # ON CONFLICT keeps re-exports after a crash idempotent
+            cursor.executemany(
+                f"INSERT INTO {self._table} "
+                "(tx_id, branch, table_name, old_version, new_version, committed_at, author) "
+                f"VALUES ({p}, {p}, {p}, {p}, {p}, {p}, {p}) "
+                "ON CONFLICT (tx_id, table_name) DO NOTHING",
+                rows,
+            )
+            self._connection.commit()
+        except Exception:
+            self._connection.rollback()
+            raise
+        return len(rows)
@@ -1,13 +1,17 @@
 """
 Rhizo command-line interface.
 
-Provides read-only database inspection and verification commands.
+Provides database inspection, verification, filtering and backup commands.
 
 Usage:
     rhizo info <path>              Show database information
     rhizo tables <path>            List all tables
     rhizo versions <path> <table>  List versions of a table
     rhizo verify <path>            Verify database integrity
+    rhizo filter <src> <dest>      Copy a subset of a database
+    rhizo backup <path> <dest>     Back up a database
+    rhizo restore <src> <path>     Restore a backup into a database
+    rhizo serve <path>             Serve a read-only HTTP+JSON gateway
 
 Environment Variables:
     RHIZO_VERIFY_INTEGRITY: Set to 'false' for faster reads (default: true)
@@ -137,6 +141,112 @@ def cmd_verify(args: argparse.Namespace) -> int:
         return 1
 
 
+def cmd_filter(args: argparse.Namespace) -> int:
+    """Copy selected tables/branches into a new database."""
+    from rhizo.repo_filter import filter_database
+
+    source = Path(args.source).resolve()
+    if not source.exists():
+        print(f"Error: Database not found: {source}", file=sys.stderr)
+        return 1
+
+    try:
+        report = filter_database(
+            str(source),
+            args.dest,
+            tables=args.table or None,
+            branches=args.branch or None,
+            keep_versions=args.keep_versions,
+        )
+    except Exception as e:
+        print(f"Error: {e}", file=sys.stderr)
+        return 1
+
+    print(f"Filtered {source} -> {Path(args.dest).resolve()}")
+    for table_name in report.tables:
+        mapping = report.version_map[table_name]
+        print(f"  {table_name}: {len(mapping)} version(s) kept")
+    if report.branches:
+        print(f"Branches: {', '.join(report.branches)}")
+    print(
+        f"Versions dropped: {report.versions_dropped}, "
+        f"chunks copied: {report.chunks_copied} ({report.bytes_copied} bytes)"
+    )
+    return 0
+
+
+def cmd_backup(args: argparse.Namespace) -> int:
+    """Back up a database, optionally incrementally."""
+    from rhizo.backup import backup_database
+
+    try:
+        manifest = backup_database(
+            args.path, args.dest, incremental_since=args.incremental_since,
+        )
+    except Exception as e:
+        print(f"Error: {e}", file=sys.stderr)
+        return 1
+
+    kind = "Incremental backup" if manifest.is_incremental else "Full backup"
+    print(f"{kind} {manifest.backup_id} -> {Path(args.dest).resolve()}")
+    print(f"Tables: {len(manifest.tables)}, versions stored: {manifest.versions}")
+    print(f"Chunks stored: {manifest.chunks} ({manifest.chunk_bytes} bytes)")
+    return 0
+
+
+def cmd_restore(args: argparse.Namespace) -> int:
+    """Verify a backup chain and restore it into a database."""
+    from rhizo.backup import restore_database
+
+    if args.path is None and not args.verify_only:
+        print("Error: a database path is required unless --verify-only is given",
+              file=sys.stderr)
+        return 1
+
+    try:
+        report = restore_database(args.src, args.path, verify_only=args.verify_only)
+    except Exception as e:
+        print(f"Error: {e}", file=sys.stderr)
+        return 1
+
+    if args.verify_only:
+        print(f"Backup OK: {report.backups} backup(s), {report.versions} versions, "
+              f"{report.chunks_verified} chunks ({report.bytes_verified} bytes) verified")
+        return 0
+
+    print(f"Restored {Path(args.src).resolve()} -> {Path(args.path).resolve()}")
+    print(f"Backups applied: {report.backups_applied}, tables: {len(report.tables)}, "
+          f"versions: {report.versions_restored}")
+    if report.branches:
+        print(f"Branches: {', '.join(report.branches)}")
+    return 0
+
+
+def cmd_serve(args: argparse.Namespace) -> int:
+    """Serve a read-only HTTP+JSON gateway until interrupted."""
+    path = Path(args.path).resolve()
+    if not path.exists():
+        print(f"Error: Database not found: {path}", file=sys.stderr)
+        return 1
+
+    try:
+        with rhizo.open(str(path), verify_integrity=False) as db:
+            gateway = db.gateway(
+                host=args.host,
+                port=args.port,
+                allow_origin=None if args.no_cors else args.allow_origin,
+            )
+            print(f"Serving {path} on http://{args.host}:{args.port} (Ctrl+C to stop)")
+            try:
+                gateway.serve_forever()
+            except KeyboardInterrupt:
+                pass
+        return 0
+    except Exception as e:
+        print(f"Error: {e}", file=sys.stderr)
+        return 1
+
+
 def main(argv: list[str] | None = None) -> int:
     """Main CLI entry point."""
     parser = argparse.ArgumentParser(
@@ -193,6 +303,85 @@ def main(argv: list[str] | None = None) -> int:
     p_verify.add_argument("path", help="Path to database directory")
     p_verify.set_defaults(func=cmd_verify)
 
+    # filter command
+    p_filter = subparsers.add_parser(
+        "filter",
+        help="Copy a subset of a database",
+        description=(
+            "Create a new database containing only the selected tables and "
+            "branches, optionally with truncated history."
+        ),
+    )
+    p_filter.add_argument("source", help="Path to source database directory")
+    p_filter.add_argument("dest", help="Path to destination database directory")
+    p_filter.add_argument(
+        "--table", action="append", metavar="SELECTOR",
+        help="Table name, namespace or pattern to keep (repeatable; default: all)",
+    )
+    p_filter.add_argument(
+        "--branch", action="append", metavar="NAME",
+        help="Branch to keep (repeatable; default: all)",
+    )
+    p_filter.add_argument(
+        "--keep-versions", type=int, metavar="N",
+        help="Keep only the last N versions of each table",
+    )
+    p_filter.set_defaults(func=cmd_filter)
+
+    # backup command
+    p_backup = subparsers.add_parser(
+        "backup",
+        help="Back up a database",
+        description=(
+            "Write a full or incremental backup with a checksummed manifest."
+        ),
+    )
+    p_backup.add_argument("path", help="Path to database directory")
+    p_backup.add_argument("dest", help="Path to new backup directory")
+    p_backup.add_argument(
+        "--incremental-since", metavar="BACKUP",
+        help="Earlier backup to extend; only new versions and chunks are stored",
+    )
+    p_backup.set_defaults(func=cmd_backup)
+
+    # restore command
+    p_restore = subparsers.add_parser(
+        "restore",
+        help="Restore a backup into a database",
+        description=(
+            "Verify a backup and the chain it extends, then restore it into "
+            "an empty database."
+        ),
+    )
+    p_restore.add_argument("src", help="Path to the newest backup of the chain")
+    p_restore.add_argument(
+        "path", nargs="?", help="Path to database directory to restore into",
+    )
+    p_restore.add_argument(
+        "--verify-only", action="store_true",
+        help="Only verify checksums and chunks; write nothing",
+    )
+    p_restore.set_defaults(func=cmd_restore)
+
+    # serve command
+    p_serve = subparsers.add_parser(
+        "serve",
+        help="Serve a read-only HTTP+JSON gateway",
+        description=(
+            "Serve tables, versions, the changelog and the branch graph as "
+            "read-only JSON endpoints for browser clients."
+        ),
+    )
+    p_serve.add_argument("path", help="Path to database directory")
+    p_serve.add_argument("--host", default="127.0.0.1", help="Interface to bind (default: 127.0.0.1)")
+    p_serve.add_argument("--port", type=int, default=8080, help="Port to bind (default: 8080)")
+    p_serve.add_argument(
+        "--allow-origin", default="*", metavar="ORIGIN",
+        help="CORS Access-Control-Allow-Origin value (default: *)",
+    )
+    p_serve.add_argument("--no-cors", action="store_true", help="Send no CORS headers")
+    p_serve.set_defaults(func=cmd_serve)
+
     args = parser.parse_args(argv)
 
     if args.command is None:
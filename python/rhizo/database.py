@@ -15,14 +15,21 @@ For advanced features (branching, transactions, OLAP), use QueryEngine directly.
 
 from __future__ import annotations
 
+import contextlib
 import os
+import shutil
+import tempfile
 import warnings
 from pathlib import Path
-from typing import TYPE_CHECKING, Optional, Union, Dict, List, Any
+from typing import TYPE_CHECKING, Optional, Union, Dict, List, Any, Iterator
 
 from .export import ExportEngine, ExportResult
-from .diff import DiffEngine, DiffResult, SchemaDiff, RowDiff
+from .diff import DiffEngine, DiffResult, SchemaDiff, RowDiff, ChangeReport
 from .gc import GCPolicy, GCResult, GarbageCollector, AutoGC
+from .audit import ReadAuditor
+from .quota import Quota, QuotaEnforcer
+from .admin import AdminAPI
+from .gateway import RestGateway
 
 import pyarrow as pa
 
@@ -87,6 +94,8 @@ class Database:
         verify_integrity: bool = _DEFAULT_VERIFY_INTEGRITY,
         auto_gc: Optional[GCPolicy] = None,
         auto_gc_interval: float = 3600.0,
+        read_auditor: Optional[ReadAuditor] = None,
+        quotas: Optional[List[Quota]] = None,
     ):
         """
         Initialize a Database at the given path.
@@ -102,6 +111,10 @@ class Database:
                              Override default via RHIZO_VERIFY_INTEGRITY env var.
             auto_gc: If set, run background GC with this policy (default: None).
             auto_gc_interval: Seconds between auto-GC runs (default: 3600).
+            read_auditor: If set, publish a ReadEvent for every (sampled)
+                         table read and query (default: None).
+            quotas: Storage quotas enforced on writes, with warning events
+                   before the hard limit (default: None). See rhizo.quota.
         """
         self._path = Path(path).resolve()
         self._closed = False
@@ -159,8 +172,15 @@ class Database:
             transaction_manager=self._transaction_manager,
             verify_integrity=verify_integrity,
             catalog_path=str(catalog_dir),
+            read_auditor=read_auditor,
         )
 
+        # Optional storage quotas (checked by the writer before each commit)
+        self._quota_enforcer = None
+        if quotas:
+            self._quota_enforcer = QuotaEnforcer(self._catalog, self._store, quotas)
+            self._engine.writer.quota_enforcer = self._quota_enforcer
+
         # Create the DataFusion OLAP engine (primary, if available)
         self._olap_engine = None
         if DATAFUSION_AVAILABLE and OLAPEngine is not None:
@@ -211,6 +231,7 @@ class Database:
         query: str,
         versions: Optional[Dict[str, int]] = None,
         params: Optional[List[Any]] = None,
+        snapshot: Optional[Union[int, str]] = None,
     ) -> QueryResult:
         """
         Execute a SQL query using DataFusion (fast) or DuckDB (fallback).
@@ -223,6 +244,8 @@ class Database:
             versions: Optional dict mapping table names to specific versions
                      for time travel queries
             params: Optional query parameters (DuckDB fallback only)
+            snapshot: Repository snapshot id (e.g. 1234 or "repo@1234") to
+                      read every table at. Entries in versions take precedence.
 
         Returns:
             QueryResult with .to_pandas(), .to_arrow(), .to_dict() methods
@@ -234,6 +257,9 @@ class Database:
             >>> # With time travel
             >>> result = db.sql("SELECT * FROM users", versions={"users": 1})
             >>>
+            >>> # All tables as of a cited repository snapshot
+            >>> result = db.sql("SELECT * FROM users JOIN orders USING (id)", snapshot="repo@42")
+            >>>
             >>> # Convert to pandas
             >>> df = result.to_pandas()
 
@@ -241,10 +267,13 @@ class Database:
             For parameterized queries, use sql_duckdb() which supports params.
         """
         self._check_closed()
+        if snapshot is not None:
+            versions = {**self._engine.resolve_snapshot(snapshot), **(versions or {})}
 
         # Use DataFusion if available (26x faster)
         if self._olap_engine is not None:
             arrow_table = self._olap_engine.query(query, versions=versions)
+            self._engine.audit_query(query, versions or {})
             return QueryResult(
                 arrow_table=arrow_table,
                 row_count=arrow_table.num_rows,
@@ -319,11 +348,47 @@ class Database:
             primary_key=primary_key, schema_mode=schema_mode,
         )
 
+    def create_table(
+        self,
+        table_name: str,
+        schema: pa.Schema,
+        metadata: Optional[Dict[str, str]] = None,
+        *,
+        primary_key: Optional[List[str]] = None,
+        schema_mode: Optional[str] = None,
+    ) -> WriteResult:
+        """
+        Create an empty table with a schema, as version 1.
+
+        Args:
+            table_name: Name of the table (must be a valid SQL identifier)
+            schema: PyArrow schema of the table
+            metadata: Optional key-value metadata for version 1
+            primary_key: Columns that form the primary key (set once, immutable).
+            schema_mode: Schema evolution mode ("additive" or "flexible").
+
+        Returns:
+            WriteResult for version 1 (zero rows)
+
+        Example:
+            >>> import pyarrow as pa
+            >>> schema = pa.schema([("id", pa.int64()), ("name", pa.string())])
+            >>> db.create_table("users", schema, primary_key=["id"])
+            >>> db.read("users").num_rows
+            0
+        """
+        self._check_closed()
+        return self._engine.create_table(
+            table_name, schema, metadata=metadata,
+            primary_key=primary_key, schema_mode=schema_mode,
+        )
+
     def read(
         self,
         table_name: str,
         version: Optional[int] = None,
         columns: Optional[List[str]] = None,
+        snapshot: Optional[Union[int, str]] = None,
     ) -> pa.Table:
         """
         Read a table as an Arrow Table.
@@ -332,6 +397,8 @@ class Database:
             table_name: Name of the table to read
             version: Specific version to read (None for latest)
             columns: If specified, only read these columns (faster)
+            snapshot: Read the version recorded by this repository snapshot
+                      (e.g. 1234 or "repo@1234") instead of ``version``
 
         Returns:
             PyArrow Table containing the data
@@ -345,9 +412,18 @@ class Database:
             >>>
             >>> # Read only specific columns (faster)
             >>> table = db.read("users", columns=["name", "age"])
+            >>>
+            >>> # Read as of a repository snapshot
+            >>> table = db.read("users", snapshot="repo@42")
         """
         self._check_closed()
-        return self._engine.reader.read_arrow(table_name, version=version, columns=columns)
+        if snapshot is not None:
+            if version is not None:
+                raise ValueError("Pass either version or snapshot, not both")
+            version = self._engine.get_version_as_of(table_name, snapshot)
+        table = self._engine.reader.read_arrow(table_name, version=version, columns=columns)
+        self._engine.audit_read(table_name, version)
+        return table
 
     def read_pandas(
         self,
@@ -365,7 +441,9 @@ class Database:
             pandas DataFrame containing the data
         """
         self._check_closed()
-        return self._engine.reader.read_pandas(table_name, version=version)
+        df = self._engine.reader.read_pandas(table_name, version=version)
+        self._engine.audit_read(table_name, version)
+        return df
 
     def tables(self) -> List[str]:
         """
@@ -471,17 +549,77 @@ class Database:
             versions = self._catalog.list_versions(table_name)
             version_b = versions[-1]
 
+        return self._get_diff_engine().diff(
+            table_name, version_a, version_b,
+            key_columns=key_columns, schema=schema,
+        )
+
+    def changed_since(
+        self,
+        since: Union[str, int, Dict[str, int]],
+        tables: Optional[List[str]] = None,
+    ) -> ChangeReport:
+        """
+        Summarize how tables changed since a pin or transaction.
+
+        Intended for retraining triggers: compare the data a model was
+        trained on (recorded with pin()) against what is there now.
+
+        Args:
+            since: Recorded point. A pin name (str), a transaction ID (int;
+                   only changes committed through transactions are seen), or
+                   an explicit table -> version mapping.
+            tables: Tables to check (default: every current table)
+
+        Returns:
+            ChangeReport with per-table versions, rows, and bytes changed
+
+        Raises:
+            IOError: If the pin or a table does not exist
+            RuntimeError: If a transaction ID is given without transactions
+
+        Example:
+            >>> report = db.changed_since("churn/run-42", ["users", "events"])
+            >>> if report.total_rows_delta > 10_000:
+            ...     retrain()
+        """
+        self._check_closed()
+
+        current = self._engine._capture_snapshot(self._engine.current_branch)
+        if tables is not None:
+            missing = [t for t in tables if t.lower() not in current]
+            if missing:
+                raise IOError(f"Table not found: {missing[0]}")
+            current = {t.lower(): current[t.lower()] for t in tables}
+
+        if isinstance(since, str):
+            baseline: Dict[str, Optional[int]] = dict(self._catalog.resolve_pin(since).versions)
+            label = f"pin:{since}"
+        elif isinstance(since, int):
+            baseline = dict(current)
+            changes = self._engine.get_changes(since_tx_id=since, tables=list(current))
+            seen = set()
+            for entry in sorted(changes, key=lambda e: e["tx_id"]):
+                for change in entry["changes"]:
+                    name = change["table_name"]
+                    if name in current and name not in seen:
+                        baseline[name] = change["old_version"]
+                        seen.add(name)
+            label = f"tx:{since}"
+        else:
+            baseline = dict(since)
+            label = "versions"
+
+        return self._get_diff_engine().changed_since(baseline, current, label)
+
+    def _get_diff_engine(self) -> DiffEngine:
         if not hasattr(self, "_diff_engine") or self._diff_engine is None:
             self._diff_engine = DiffEngine(
                 self._catalog, self._store, self._engine.reader,
                 self._branch_manager,
                 table_meta_store=self._table_meta_store,
             )
-
-        return self._diff_engine.diff(
-            table_name, version_a, version_b,
-            key_columns=key_columns, schema=schema,
-        )
+        return self._diff_engine
 
     # =========================================================================
     # Schema & Primary Key API
@@ -607,11 +745,225 @@ class Database:
         meta.schema_mode = mode
         self._table_meta_store.save(table_name, meta)
 
+    def pin(
+        self,
+        name: str,
+        tables: Optional[List[str]] = None,
+        *,
+        versions: Optional[Dict[str, int]] = None,
+        description: Optional[str] = None,
+    ) -> Dict[str, int]:
+        """
+        Pin exact table versions under a name for reproducible runs.
+
+        Pins are immutable and protected from garbage collection, so a
+        training run can record its inputs and later read exactly the same
+        data back with ``db.sql(query, versions=db.resolve_pin(name))``.
+
+        Args:
+            name: Pin name (e.g. "churn-model/run-42")
+            tables: Tables to pin at their current version (default: all).
+                    Ignored if ``versions`` is given.
+            versions: Explicit table -> version mapping to pin
+            description: Optional note (experiment id, git commit, ...)
+
+        Returns:
+            The pinned table -> version mapping
+
+        Raises:
+            ValueError: If the pin already exists or the name is invalid
+            IOError: If a table or version does not exist
+
+        Example:
+            >>> db.pin("churn/run-42", ["users", "events"])
+            {'events': 3, 'users': 7}
+        """
+        self._check_closed()
+        if versions is None:
+            snapshot = self._engine._capture_snapshot(self._engine.current_branch)
+            if tables is None:
+                versions = snapshot
+            else:
+                versions = {}
+                for table in tables:
+                    key = table.lower()
+                    if key not in snapshot:
+                        raise IOError(f"Table not found: {table}")
+                    versions[key] = snapshot[key]
+        return dict(self._catalog.pin(name, versions, description).versions)
+
+    def resolve_pin(self, name: str) -> Dict[str, int]:
+        """
+        Get the table -> version mapping recorded by a pin.
+
+        Args:
+            name: Pin name
+
+        Returns:
+            Dict suitable for the ``versions`` argument of sql()
+
+        Raises:
+            IOError: If the pin does not exist
+        """
+        self._check_closed()
+        return dict(self._catalog.resolve_pin(name).versions)
+
+    def resolve_ref(self, reference: str) -> Dict[str, int]:
+        """
+        Get the table -> version mapping a ref names.
+
+        Refs are ``main`` (a branch head), ``main~3`` (three head changes
+        earlier), ``users@v7`` (one table version), ``tag:release-1``,
+        ``tx:1234`` (as of a committed transaction) and ``repo@12`` (a
+        repository snapshot).
+
+        Args:
+            reference: The ref to resolve
+
+        Returns:
+            Dict suitable for the ``versions`` argument of sql()
+
+        Raises:
+            ValueError: If the ref is malformed
+            IOError: If what it names does not exist
+
+        Example:
+            >>> db.sql("SELECT * FROM users", versions=db.resolve_ref("main~1"))
+        """
+        self._check_closed()
+        from _rhizo import resolve_ref
+
+        return resolve_ref(
+            self._catalog,
+            reference,
+            branch_manager=self._branch_manager,
+            transaction_manager=self._transaction_manager,
+        )
+
+    def pins(self) -> List[str]:
+        """
+        List all pin names.
+
+        Returns:
+            Sorted list of pin names
+        """
+        self._check_closed()
+        return [p.name for p in self._catalog.list_pins()]
+
+    def unpin(self, name: str) -> None:
+        """
+        Delete a pin, allowing GC to reclaim its versions.
+
+        Args:
+            name: Pin name
+
+        Raises:
+            IOError: If the pin does not exist
+        """
+        self._check_closed()
+        self._catalog.delete_pin(name)
+
+    def hold(
+        self,
+        table_name: str,
+        version: Optional[int] = None,
+        *,
+        reason: Optional[str] = None,
+    ) -> Any:
+        """
+        Place a legal hold on a table version.
+
+        Held versions are never deleted by garbage collection or any other
+        cleanup. Holds can only be listed and released through ``db.admin``.
+
+        Args:
+            table_name: Table to hold
+            version: Version to hold (default: latest)
+            reason: Why the data is held (case number, regulation, ...)
+
+        Returns:
+            The hold (table_name, version, reason, created_at)
+
+        Raises:
+            IOError: If the table or version does not exist
+
+        Example:
+            >>> db.hold("trades", reason="SEC-2024-117")
+        """
+        self._check_closed()
+        table_name = table_name.lower()
+        if version is None:
+            version = self._catalog.get_version(table_name).version
+        return self._catalog.hold(table_name, version, reason)
+
+    def freeze_branch(self, name: str) -> None:
+        """
+        Make a branch immutable.
+
+        Writes, merges into the branch and deleting it are rejected until an
+        administrator calls ``db.admin.unfreeze_branch(name)``. The branch can
+        still be read and branched from.
+
+        Args:
+            name: Branch name
+
+        Raises:
+            RuntimeError: If branching is disabled
+            IOError: If the branch does not exist
+        """
+        self._check_closed()
+        if self._branch_manager is None:
+            raise RuntimeError("Cannot freeze branch: branch_manager not configured")
+        self._branch_manager.set_immutable(name, True)
+
+    @property
+    def admin(self) -> AdminAPI:
+        """
+        Administrative API for listing and releasing legal holds and
+        branch freezes. See rhizo.admin.
+        """
+        self._check_closed()
+        return AdminAPI(self._catalog, self._branch_manager)
+
+    def gateway(
+        self,
+        *,
+        host: str = "127.0.0.1",
+        port: int = 8080,
+        allow_origin: Optional[str] = "*",
+    ) -> RestGateway:
+        """
+        Create a read-only HTTP+JSON gateway over this database.
+
+        The gateway is not started; call ``start()`` to serve on a
+        background thread or ``serve_forever()`` to block. See rhizo.gateway
+        for the endpoints.
+
+        Args:
+            host: Interface to bind (default: loopback only)
+            port: Port to bind; 0 picks a free port
+            allow_origin: CORS ``Access-Control-Allow-Origin`` value, or
+                None to send no CORS headers
+
+        Returns:
+            RestGateway over the catalog, branches and changelog
+        """
+        self._check_closed()
+        return RestGateway(
+            self._catalog,
+            self._branch_manager,
+            self._transaction_manager,
+            host=host,
+            port=port,
+            allow_origin=allow_origin,
+        )
+
     def gc(
         self,
         *,
         max_age_seconds: Optional[float] = None,
         max_versions_per_table: Optional[int] = None,
+        chunk_grace_seconds: float = 0.0,
     ) -> GCResult:
         """
         Run garbage collection to reclaim disk space.
@@ -620,11 +972,14 @@ class Database:
         At least one policy constraint must be provided.
 
         Safety: Never deletes the latest version, versions referenced by
-        branches, or versions referenced by active transactions.
+        branches, pinned or legally held versions, or versions referenced
+        by active transactions.
 
         Args:
             max_age_seconds: Delete versions older than this (seconds).
             max_versions_per_table: Keep at most this many versions per table.
+            chunk_grace_seconds: Keep unreferenced chunks written within
+                this many seconds (see ``sweep_chunks``).
 
         Returns:
             GCResult with counts of deleted versions, chunks, and bytes freed.
@@ -637,12 +992,65 @@ class Database:
         policy = GCPolicy(
             max_age_seconds=max_age_seconds,
             max_versions_per_table=max_versions_per_table,
+            chunk_grace_seconds=chunk_grace_seconds,
         )
         collector = GarbageCollector(
             self._catalog, self._store,
             self._branch_manager, self._transaction_manager,
         )
-        return collector.collect(policy)
+        result = collector.collect(policy)
+        if self._quota_enforcer is not None:
+            self._quota_enforcer.refresh()
+        return result
+
+    def sweep_chunks(
+        self,
+        *,
+        dry_run: bool = False,
+        grace_seconds: float = 3600.0,
+    ):
+        """
+        Delete chunks that no table version references, without deleting
+        any versions.
+
+        Chunks written by uncommitted transactions, and chunks written in
+        the last ``grace_seconds`` (which may belong to a commit in
+        progress in another process), are kept.
+
+        Args:
+            dry_run: Report what would be deleted without deleting.
+            grace_seconds: Minimum age of a chunk before it is swept.
+
+        Returns:
+            ChunkGcReport with the collectable chunk hashes and counts.
+
+        Example:
+            >>> report = db.sweep_chunks(dry_run=True)
+            >>> print(f"{report.bytes_collectable} bytes reclaimable")
+        """
+        self._check_closed()
+        from _rhizo import sweep_chunks
+
+        report = sweep_chunks(
+            self._store,
+            self._catalog,
+            self._transaction_manager,
+            grace_period_secs=grace_seconds,
+            dry_run=dry_run,
+        )
+        if not dry_run and self._quota_enforcer is not None:
+            self._quota_enforcer.refresh()
+        return report
+
+    @property
+    def quotas(self) -> Optional[QuotaEnforcer]:
+        """
+        Quota enforcer, or None if no quotas are configured.
+
+        Subscribe to ``quotas.bus`` (topic rhizo.quota.QUOTA_TOPIC) for
+        warnings, and use ``quotas.status()`` to export usage as metrics.
+        """
+        return self._quota_enforcer
 
     def export(
         self,
@@ -694,6 +1102,168 @@ class Database:
             format=format, compression=compression,
         )
 
+    def replay(
+        self,
+        target: Union[str, "Database"],
+        tx_range: Optional[Union[range, tuple]] = None,
+        *,
+        verify_heads: Optional[bool] = None,
+    ) -> Any:
+        """
+        Replay a range of the transaction log onto another database.
+
+        Table versions and branch heads are rebuilt by applying changelog
+        entries in transaction order, fetching chunks from this database.
+        Replaying the whole log onto an empty database reproduces every
+        transactional write; replaying consecutive ranges resumes where the
+        previous run stopped. Only writes made through transactions are in
+        the log.
+
+        Args:
+            target: Destination database, or the path of one (created if
+                    missing)
+            tx_range: Transactions to replay, as a ``range`` or an inclusive
+                      ``(first, last)`` tuple (default: the whole log)
+            verify_heads: Check that replayed branch heads equal this
+                          database's heads (default: True when the range
+                          reaches the latest transaction)
+
+        Returns:
+            ReplayReport with counts, the replayed tx_id range and the
+            resulting heads per branch
+
+        Raises:
+            RuntimeError: If transactions are disabled
+            ValueError: If the range does not continue the target's history,
+                        or the replayed heads do not match
+
+        Example:
+            >>> db.replay("./mirror", (1, 40))
+            >>> db.replay("./mirror", (41, db.engine.latest_tx_id()))
+        """
+        self._check_closed()
+        if self._transaction_manager is None:
+            raise RuntimeError("Cannot replay: transaction_manager not configured")
+        from _rhizo import replay_changelog
+
+        first, last = None, None
+        if isinstance(tx_range, range):
+            if tx_range.step != 1:
+                raise ValueError("tx_range must be contiguous")
+            if len(tx_range) == 0:
+                raise ValueError("tx_range is empty")
+            first, last = tx_range.start, tx_range[-1]
+        elif tx_range is not None:
+            first, last = tx_range
+
+        latest = self._transaction_manager.latest_tx_id()
+        entries = self._transaction_manager.get_changelog(
+            since_tx_id=first - 1 if first else None,
+            until_tx_id=last,
+        )
+        if verify_heads is None:
+            verify_heads = last is None or (latest is not None and last >= latest)
+
+        if isinstance(target, Database):
+            target._check_closed()
+            target_store, target_catalog = target._store, target._catalog
+            target_branches = target._branch_manager
+            target_catalog_dir = target.path / "catalog"
+        else:
+            from _rhizo import PyBranchManager, PyCatalog, PyChunkStore
+            target_path = Path(target).resolve()
+            for sub in ("chunks", "catalog", "branches"):
+                (target_path / sub).mkdir(parents=True, exist_ok=True)
+            target_store = PyChunkStore(str(target_path / "chunks"))
+            target_catalog = PyCatalog(str(target_path / "catalog"))
+            target_branches = PyBranchManager(str(target_path / "branches"))
+            target_catalog_dir = target_path / "catalog"
+
+        report = replay_changelog(
+            entries,
+            self._store, self._catalog,
+            target_store, target_catalog,
+            source_branches=self._branch_manager,
+            target_branches=target_branches,
+            verify_heads=verify_heads,
+        )
+
+        # Primary keys and schema modes live next to the versions
+        from rhizo.table_meta import TableMetaStore
+        target_meta = TableMetaStore(str(target_catalog_dir))
+        for tables in report.heads.values():
+            for table in tables:
+                if (self._path / "catalog" / table / TableMetaStore.META_FILENAME).exists():
+                    target_meta.save(table, self._table_meta_store.load(table))
+
+        return report
+
+    def backup(self, dest: str, incremental_since: Optional[str] = None) -> Any:
+        """
+        Back up the database into a new backup directory.
+
+        The backup holds a checksummed manifest, the chunk data and table
+        metadata. Branches, pins and legal holds are included. See
+        rhizo.backup.
+
+        Args:
+            dest: Backup directory (must not already hold a backup)
+            incremental_since: Directory of an earlier backup of this
+                               database; only versions and chunks missing
+                               from it (and the chain it extends) are stored
+
+        Returns:
+            BackupManifest of the new backup
+
+        Raises:
+            IOError: If ``incremental_since`` or its chain cannot be found
+            ValueError: If ``dest`` already holds a backup
+
+        Example:
+            >>> db.backup("/backups/mon")
+            >>> db.backup("/backups/tue", incremental_since="/backups/mon")
+        """
+        self._check_closed()
+        from .backup import _backup_handles
+        return _backup_handles(
+            self._store, self._catalog, self._branch_manager,
+            self._path / "catalog", dest, incremental_since,
+        )
+
+    def restore(self, src: str, *, verify_only: bool = False) -> Any:
+        """
+        Restore a backup (and the chain it extends) into this database.
+
+        Every backup in the chain is verified before anything is written.
+        The database must not contain any of the backed-up tables.
+
+        Args:
+            src: Directory of the newest backup to restore
+            verify_only: Only verify manifest checksums, parent links and
+                         chunk hashes; write nothing
+
+        Returns:
+            VerifyReport with ``verify_only``, otherwise RestoreReport
+
+        Raises:
+            IOError: If a backup in the chain or one of its chunks is missing
+            ValueError: If a checksum fails, or a backed-up table or
+                        non-empty branch already exists here
+
+        Example:
+            >>> with rhizo.open("./recovered") as db:
+            ...     db.restore("/backups/tue")
+        """
+        self._check_closed()
+        if verify_only:
+            from _rhizo import verify_backup
+            return verify_backup(str(Path(src).resolve()))
+        from .backup import _restore_handles
+        return _restore_handles(
+            src, self._store, self._catalog, self._branch_manager,
+            self._path / "catalog",
+        )
+
     def close(self) -> None:
         """
         Close the database connection.
@@ -744,6 +1314,8 @@ def open(
     verify_integrity: bool = _DEFAULT_VERIFY_INTEGRITY,
     auto_gc: Optional[GCPolicy] = None,
     auto_gc_interval: float = 3600.0,
+    read_auditor: Optional[ReadAuditor] = None,
+    quotas: Optional[List[Quota]] = None,
 ) -> Database:
     """
     Open or create a Rhizo database at the given path.
@@ -760,6 +1332,10 @@ def open(
                          Override default via RHIZO_VERIFY_INTEGRITY env var.
         auto_gc: If set, run background GC with this policy (default: None).
         auto_gc_interval: Seconds between auto-GC runs (default: 3600).
+        read_auditor: If set, audit table reads and queries (default: None).
+            See rhizo.audit.
+        quotas: Storage quotas with soft warnings (default: None).
+            See rhizo.quota.
 
     Returns:
         Database instance ready for use
@@ -802,4 +1378,33 @@ def open(
         verify_integrity=verify_integrity,
         auto_gc=auto_gc,
         auto_gc_interval=auto_gc_interval,
+        read_auditor=read_auditor,
+        quotas=quotas,
     )
+
+
+@contextlib.contextmanager
+def ephemeral_repo(*, keep: bool = False, **kwargs: Any) -> Iterator[Database]:
+    """
+    Open a database in a new, uniquely named temporary directory.
+
+    The database is closed and its directory deleted when the block exits,
+    so tests need no tempdir plumbing of their own.
+
+    Args:
+        keep: Leave the directory in place on exit (e.g. to inspect a
+            failing test); its location is ``db.path``.
+        **kwargs: Passed to ``rhizo.open``.
+
+    Example:
+        >>> with rhizo.ephemeral_repo() as db:
+        ...     db.write("users", df)
+        ...     assert db.read("users").num_rows == len(df)
+    """
+    path = tempfile.mkdtemp(prefix="rhizo_repo_")
+    try:
+        with open(path, **kwargs) as db:
+            yield db
+    finally:
+        if not keep:
+            shutil.rmtree(path, ignore_errors=True)
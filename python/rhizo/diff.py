@@ -29,6 +29,7 @@ from typing import TYPE_CHECKING, Dict, List, Optional, Set, Tuple
 
 import duckdb
 import pyarrow as pa
+import pyarrow.parquet as pq
 
 if TYPE_CHECKING:
     from _rhizo import (
@@ -164,6 +165,87 @@ class DiffResult:
         return self.summary()
 
 
+@dataclass
+class TableChangeSummary:
+    """How much one table changed since a recorded point.
+
+    Attributes:
+        table_name: Name of the table.
+        from_version: Version at the recorded point (None if the table
+            did not exist there).
+        to_version: Current version.
+        versions_added: Number of versions committed since the point.
+        rows_before: Row count at the recorded point.
+        rows_after: Current row count.
+        bytes_changed: Bytes of chunks in the current version that were
+            not part of the recorded version.
+    """
+
+    table_name: str
+    from_version: Optional[int]
+    to_version: int
+    versions_added: int = 0
+    rows_before: int = 0
+    rows_after: int = 0
+    bytes_changed: int = 0
+
+    @property
+    def changed(self) -> bool:
+        return self.from_version != self.to_version
+
+    @property
+    def rows_delta(self) -> int:
+        return self.rows_after - self.rows_before
+
+
+@dataclass
+class ChangeReport:
+    """Summary of changes across tables since a pin or transaction.
+
+    Attributes:
+        since: Description of the recorded point (e.g. "pin:run-1", "tx:42").
+        tables: Per-table change summaries.
+    """
+
+    since: str
+    tables: Dict[str, TableChangeSummary] = field(default_factory=dict)
+
+    @property
+    def changed(self) -> bool:
+        """True if any table changed."""
+        return any(t.changed for t in self.tables.values())
+
+    @property
+    def changed_tables(self) -> List[str]:
+        return sorted(name for name, t in self.tables.items() if t.changed)
+
+    @property
+    def total_rows_delta(self) -> int:
+        return sum(t.rows_delta for t in self.tables.values())
+
+    @property
+    def total_bytes_changed(self) -> int:
+        return sum(t.bytes_changed for t in self.tables.values())
+
+    def summary(self) -> str:
+        """Human-readable summary of the report."""
+        lines = [f"Changes since {self.since}: {len(self.changed_tables)} of {len(self.tables)} tables"]
+        for name in sorted(self.tables):
+            t = self.tables[name]
+            if not t.changed:
+                continue
+            origin = f"v{t.from_version}" if t.from_version is not None else "(new)"
+            lines.append(
+                f"  {name}: {origin} -> v{t.to_version}, "
+                f"+{t.versions_added} versions, {t.rows_delta:+d} rows, "
+                f"{t.bytes_changed} bytes changed"
+            )
+        return "\n".join(lines)
+
+    def __repr__(self) -> str:
+        return self.summary()
+
+
 # ---------------------------------------------------------------------------
 # DiffEngine
 # ---------------------------------------------------------------------------
@@ -291,6 +373,62 @@ class DiffEngine:
         )
         return result
 
+    def changed_since(
+        self,
+        baseline: Dict[str, Optional[int]],
+        current: Dict[str, int],
+        since: str,
+    ) -> ChangeReport:
+        """Summarize how tables changed between two sets of versions.
+
+        Row counts come from Parquet footers and byte counts from chunk
+        hashes, so no table data is decoded. Chunks shared between the two
+        versions (content-addressed) are not counted as changed bytes.
+
+        Args:
+            baseline: table -> version at the recorded point (None = absent).
+            current: table -> current version.
+            since: Description of the recorded point for the report.
+
+        Returns:
+            ChangeReport with one entry per table in ``current``.
+        """
+        report = ChangeReport(since=since)
+
+        for table_name, to_version in current.items():
+            from_version = baseline.get(table_name)
+            summary = TableChangeSummary(table_name, from_version, to_version)
+
+            new_hashes = self.catalog.get_version(table_name, to_version).chunk_hashes
+            summary.rows_after = self._count_rows(new_hashes)
+
+            if from_version is None:
+                old_hashes: List[str] = []
+            else:
+                old_hashes = self.catalog.get_version(table_name, from_version).chunk_hashes
+                summary.rows_before = self._count_rows(old_hashes)
+
+            if summary.changed:
+                summary.versions_added = sum(
+                    1 for v in self.catalog.list_versions(table_name)
+                    if v > (from_version or 0) and v <= to_version
+                )
+                old_set = set(old_hashes)
+                summary.bytes_changed = sum(
+                    len(self.store.get(h)) for h in set(new_hashes) if h not in old_set
+                )
+
+            report.tables[table_name] = summary
+
+        return report
+
+    def _count_rows(self, chunk_hashes: List[str]) -> int:
+        """Total row count of Parquet chunks, read from their footers."""
+        return sum(
+            pq.ParquetFile(pa.BufferReader(self.store.get(h))).metadata.num_rows
+            for h in chunk_hashes
+        )
+
     def _diff_schemas(self, schema_a: pa.Schema, schema_b: pa.Schema) -> SchemaDiff:
         """Compare two Arrow schemas."""
         names_a = {f.name for f in schema_a}
@@ -26,6 +26,11 @@ from .reader import TableReader
 from .writer import TableWriter, WriteResult
 from .olap_engine import OLAPEngine, is_datafusion_available
 from .logging import get_logger
+from .events import EventBus
+from .merge_events import (
+    MERGE_COMPLETED_TOPIC, MERGE_CONFLICTED_TOPIC, MERGE_STARTED_TOPIC,
+    RESOLUTIONS, ConflictResolver, MergeEvent,
+)
 from .exceptions import TableNotFoundError, validate_table_name
 
 _logger = get_logger(__name__)
@@ -36,8 +41,9 @@ _DEFAULT_VERIFY_INTEGRITY = os.environ.get("RHIZO_VERIFY_INTEGRITY", "true").low
 if TYPE_CHECKING:
     import pandas as pd
     import _rhizo
-    from .transaction import TransactionContext
+    from .transaction import RetryingTransaction, TransactionContext
     from .subscriber import Subscriber
+    from .audit import ReadAuditor
 
 
 # Backwards-compatible alias for the shared validation function
@@ -126,6 +132,8 @@ class QueryEngine:
         enable_olap: bool = True,
         olap_cache_size: int = 1_000_000_000,
         catalog_path: Optional[str] = None,
+        read_auditor: Optional["ReadAuditor"] = None,
+        event_bus: Optional[EventBus] = None,
     ):
         """
         Initialize the QueryEngine.
@@ -144,8 +152,14 @@ class QueryEngine:
                         fails or is unavailable. (default: True)
             olap_cache_size: Maximum cache size for OLAPEngine in bytes.
                             Only used if enable_olap=True. (default: 1GB)
+            read_auditor: Optional ReadAuditor. When set, queries and direct
+                         reads publish ReadEvents (subject to sampling).
+            event_bus: Bus for merge events (default: a new private bus,
+                      available as ``events``). See rhizo.merge_events.
         """
         self.store = store
+        self.read_auditor = read_auditor
+        self.events = event_bus if event_bus is not None else EventBus()
         self.catalog = catalog
         self.reader = TableReader(store, catalog, verify_integrity)
         self.writer = TableWriter(store, catalog, catalog_path=catalog_path)
@@ -163,6 +177,9 @@ class QueryEngine:
         # Cache of registered tables: (table_name, version) -> RegisteredTable
         self._registered: Dict[tuple, RegisteredTable] = {}
 
+        # Materialized view results: (view_name, source-version key) -> Arrow table
+        self._view_cache: Dict[tuple, pa.Table] = {}
+
         # Active transaction tracking (for nested transaction prevention)
         self._active_tx: Optional[int] = None
 
@@ -189,6 +206,7 @@ class QueryEngine:
         params: Optional[List[Any]] = None,
         branch: Optional[str] = None,
         use_olap: bool = True,
+        snapshot: Optional[Union[int, str]] = None,
     ) -> QueryResult:
         """
         Execute a SQL query with optional time travel and branching.
@@ -207,6 +225,8 @@ class QueryEngine:
             use_olap: If True and OLAPEngine is available, use DataFusion for
                      faster execution. Falls back to DuckDB on failure.
                      Set to False to force DuckDB path. (default: True)
+            snapshot: Repository snapshot id (e.g. 1234 or "repo@1234") to
+                      read the tables at. Entries in versions take precedence.
 
         Returns:
             QueryResult with Arrow table and metadata
@@ -227,14 +247,24 @@ class QueryEngine:
             >>>
             >>> # Time travel (overrides branch)
             >>> engine.query("SELECT * FROM users", versions={"users": 5})
+            >>>
+            >>> # Every table as of a cited snapshot
+            >>> engine.query("SELECT * FROM users JOIN orders USING (id)", snapshot="repo@1234")
         """
+        if snapshot is not None:
+            versions = {**self.resolve_snapshot(snapshot), **(versions or {})}
         versions = versions or {}
         effective_branch = branch or self._current_branch
 
-        # Try OLAP path if enabled and available (params not supported in OLAP)
-        if use_olap and self._olap is not None and params is None:
+        # Try OLAP path if enabled and available (params not supported in OLAP).
+        # Views are resolved by the DuckDB path only.
+        if (
+            use_olap and self._olap is not None and params is None
+            and not any(self.catalog.view_exists(t) for t in self._extract_table_names(sql))
+        ):
             try:
                 arrow_table = self._olap.query(sql, versions=versions, branch=effective_branch)
+                self.audit_query(sql, versions, effective_branch)
                 return QueryResult(
                     arrow_table=arrow_table,
                     row_count=arrow_table.num_rows,
@@ -245,7 +275,9 @@ class QueryEngine:
                 _logger.debug("OLAP query failed: %s. Falling back to DuckDB.", e)
 
         # DuckDB path (fallback or explicit)
-        return self._query_duckdb(sql, versions, params, effective_branch)
+        result = self._query_duckdb(sql, versions, params, effective_branch)
+        self.audit_query(sql, versions, effective_branch)
+        return result
 
     def _query_duckdb(
         self,
@@ -259,10 +291,9 @@ class QueryEngine:
         table_names = self._extract_table_names(sql)
 
         with self._conn_lock:
-            # Register each table with the appropriate version
+            # Register each table (or view) with the appropriate version
             for table_name in table_names:
-                version = versions.get(table_name)  # None means resolve via branch/latest
-                self._ensure_registered(table_name, version, branch)
+                self._ensure_source_registered(table_name, versions, branch)
 
             # Execute with retry: if regex missed a table, DuckDB's error tells us
             # which table is missing so we can register it and retry once.
@@ -271,7 +302,7 @@ class QueryEngine:
             except Exception as e:
                 missing = self._extract_missing_table(e)
                 if missing:
-                    self._ensure_registered(missing, versions.get(missing), branch)
+                    self._ensure_source_registered(missing, versions, branch)
                     result = self._conn.execute(sql, params or [])
                 else:
                     raise
@@ -284,6 +315,103 @@ class QueryEngine:
             column_names=arrow_table.column_names,
         )
 
+    def _ensure_source_registered(
+        self,
+        name: str,
+        versions: Dict[str, int],
+        branch: str,
+    ) -> None:
+        """Register a table or view referenced by a query. Thread-safe."""
+        if self.catalog.view_exists(name):
+            self._ensure_view_registered(name, versions, branch)
+        else:
+            # None means resolve via branch/latest
+            self._ensure_registered(name, versions.get(name), branch)
+
+    def _ensure_view_registered(
+        self,
+        view_name: str,
+        versions: Dict[str, int],
+        branch: str,
+    ) -> None:
+        """
+        Register a view's result with DuckDB under the view's name.
+
+        The view's query runs over its base tables at the versions this
+        query reads (explicit, branch head, then latest). Materialized views
+        reuse a cached result while those versions are unchanged.
+        """
+        from _rhizo import PyView
+
+        view = self.catalog.get_view(view_name)
+
+        source_versions = {}
+        for table_name in self.catalog.view_base_tables(view_name):
+            version = versions.get(table_name)
+            if version is None:
+                version = self._resolve_read_version(table_name, branch)
+            if version is None:
+                raise TableNotFoundError(table_name)
+            source_versions[table_name] = version
+        cache_key = (view.name, PyView.materialization_key(source_versions))
+
+        with self._conn_lock:
+            arrow_table = self._view_cache.get(cache_key) if view.materialize else None
+            if arrow_table is None:
+                # Nested views resolve recursively (_conn_lock is reentrant)
+                arrow_table = self._query_duckdb(view.sql, versions, None, branch).arrow_table
+                if view.materialize:
+                    self._view_cache[cache_key] = arrow_table
+
+            self._unregister_table(view.name)
+            self._conn.register(view.name, arrow_table)
+
+    # =========================================================================
+    # Read Auditing
+    # =========================================================================
+
+    def audit_query(self, sql: str, versions: Dict[str, int], branch: Optional[str] = None) -> None:
+        """
+        Publish ReadEvents for the tables a query read.
+
+        No-op unless a read_auditor is configured. Versions are resolved the
+        same way queries resolve them: explicit version, then branch head,
+        then catalog latest.
+        """
+        if self.read_auditor is None:
+            return
+        branch = branch or self._current_branch
+        table_names = set()
+        for name in self._extract_table_names(sql):
+            if self.catalog.view_exists(name):
+                table_names.update(self.catalog.view_base_tables(name))
+            else:
+                table_names.add(name)
+        for table_name in sorted(table_names):
+            if self.read_auditor.should_audit(table_name):
+                version = versions.get(table_name)
+                if version is None:
+                    version = self._resolve_read_version(table_name, branch)
+                self.read_auditor.emit(table_name, version, "query", branch)
+
+    def audit_read(self, table_name: str, version: Optional[int] = None) -> None:
+        """Publish a ReadEvent for a direct table read (no-op without an auditor)."""
+        if self.read_auditor is None or not self.read_auditor.should_audit(table_name):
+            return
+        if version is None:
+            version = self._resolve_read_version(table_name, None)
+        self.read_auditor.emit(table_name, version, "read", None)
+
+    def _resolve_read_version(self, table_name: str, branch: Optional[str]) -> Optional[int]:
+        if branch is not None and self.branch_manager is not None:
+            version = self.branch_manager.get_table_version(branch, table_name)
+            if version is not None:
+                return version
+        try:
+            return self.catalog.get_version(table_name).version
+        except OSError:
+            return None
+
     @staticmethod
     def _extract_missing_table(exc: Exception) -> Optional[str]:
         """Extract a missing table name from a DuckDB CatalogError."""
@@ -362,7 +490,9 @@ class QueryEngine:
             )
 
         effective_branch = branch or self._current_branch
-        return self._olap.query(sql, versions=versions, branch=effective_branch)
+        arrow_table = self._olap.query(sql, versions=versions, branch=effective_branch)
+        self.audit_query(sql, versions or {}, effective_branch)
+        return arrow_table
 
     def query_time_travel(
         self,
@@ -438,6 +568,11 @@ class QueryEngine:
         effective_branch = branch or self._current_branch
         arrow_table = self._olap.query_time_travel(sql, branch=effective_branch)
 
+        # Versions are chosen inside the SQL, so they are not resolved here
+        if self.read_auditor is not None:
+            for table_name in self._extract_table_names(sql):
+                self.read_auditor.record(table_name, None, "query", effective_branch)
+
         return QueryResult(
             arrow_table=arrow_table,
             row_count=arrow_table.num_rows,
@@ -643,10 +778,20 @@ class QueryEngine:
             WriteResult with version info
 
         Raises:
-            ValueError: If table_name is invalid
+            ValueError: If table_name is invalid or names a view, or the
+                        branch is immutable
         """
         # Validate table name to prevent path traversal
         validated_name = validate_table_name(table_name)
+        if self.catalog.view_exists(validated_name):
+            raise ValueError(f"Cannot write to a view: {validated_name}")
+
+        # Refuse before storing anything if the target branch is frozen
+        effective_branch = branch or self._current_branch
+        if self.branch_manager is not None:
+            if self.branch_manager.get(effective_branch).immutable:
+                raise ValueError(f"Branch is immutable: {effective_branch}")
+
         result = self.writer.write(
             validated_name, data, metadata,
             primary_key=primary_key, schema_mode=schema_mode,
@@ -654,7 +799,9 @@ class QueryEngine:
 
         # Update branch head if branch_manager is configured
         if self.branch_manager is not None:
-            effective_branch = branch or self._current_branch
+            # A brand-new table stays scoped to its branch until merged
+            if result.version == 1:
+                self.catalog.set_table_origin(validated_name, effective_branch)
             self.branch_manager.update_head(effective_branch, validated_name, result.version)
 
         # Invalidate cache for this table (force reload on next query)
@@ -666,15 +813,271 @@ class QueryEngine:
 
         return result
 
-    def list_tables(self) -> List[str]:
-        """List all tables in the catalog."""
-        return self.reader.list_tables()
+    def create_table(
+        self,
+        table_name: str,
+        schema: pa.Schema,
+        metadata: Optional[Dict[str, str]] = None,
+        branch: Optional[str] = None,
+        *,
+        primary_key: Optional[List[str]] = None,
+        schema_mode: Optional[str] = None,
+    ) -> WriteResult:
+        """
+        Create an empty table with the given schema.
+
+        Version 1 is recorded like any first write: scoped to the branch
+        until merged and visible to the changelog subscribers of that branch.
+
+        Args:
+            table_name: Name of the table (must be a valid SQL identifier)
+            schema: Arrow schema of the table
+            metadata: Optional metadata for version 1
+            branch: Branch to create the table on. If None, uses current_branch.
+            primary_key: Columns that form the primary key (set once, immutable).
+            schema_mode: Schema evolution mode ("additive" or "flexible").
+
+        Returns:
+            WriteResult for version 1
+
+        Raises:
+            ValueError: If the table already exists, the name is invalid or
+                        names a view, or the branch is immutable
+        """
+        validated_name = validate_table_name(table_name)
+        if self.catalog.view_exists(validated_name):
+            raise ValueError(f"Cannot write to a view: {validated_name}")
+
+        effective_branch = branch or self._current_branch
+        if self.branch_manager is not None:
+            if self.branch_manager.get(effective_branch).immutable:
+                raise ValueError(f"Branch is immutable: {effective_branch}")
+
+        result = self.writer.create_table(
+            validated_name, schema, metadata,
+            primary_key=primary_key, schema_mode=schema_mode,
+        )
+
+        if self.branch_manager is not None:
+            self.catalog.set_table_origin(validated_name, effective_branch)
+            self.branch_manager.update_head(effective_branch, validated_name, result.version)
+
+        self._invalidate_cache(validated_name)
+        if self._olap is not None:
+            self._olap.clear_cache(validated_name)
+
+        return result
+
+    def list_tables(self, branch: Optional[str] = None) -> List[str]:
+        """
+        List tables in the catalog.
+
+        Args:
+            branch: Only list tables visible on this branch. Tables created on
+                    another branch appear here once merged. Only used if
+                    branch_manager is configured.
+
+        Returns:
+            Table names
+        """
+        tables = self.reader.list_tables()
+        if branch is None or self.branch_manager is None:
+            return tables
+
+        head = self.branch_manager.get(branch).head
+        return [
+            t for t in tables
+            if t in head or self.catalog.table_origin(t) is None
+        ]
 
     def list_versions(self, table_name: str) -> List[int]:
         """List all versions of a table."""
         validated_name = validate_table_name(table_name)
         return self.reader.list_versions(validated_name)
 
+    def drop_table(
+        self,
+        table_name: str,
+        purge_chunks: bool = False,
+        grace_period_secs: float = 3600.0,
+    ) -> List[int]:
+        """
+        Drop a table and all of its versions.
+
+        The table is removed from every branch. With a transaction_manager
+        configured, the drop is also recorded in the changelog as a
+        tombstone so subscribers see the deletion.
+
+        Args:
+            table_name: Table to drop
+            purge_chunks: Also delete the dropped versions' chunks that
+                         nothing reaches any more, as sweep_chunks would:
+                         chunks another version or an in-flight transaction
+                         references, modified within the grace period, or
+                         a delta base of a kept chunk stay. Otherwise they
+                         stay in the store until garbage collection.
+            grace_period_secs: Minimum age of a purged chunk
+
+        Returns:
+            The dropped version numbers
+
+        Raises:
+            ValueError: If a version is pinned, tagged or held, or an
+                        immutable branch refers to the table (OSError when
+                        raised through the transaction manager)
+        """
+        validated_name = validate_table_name(table_name)
+
+        if self.transaction_manager is not None:
+            dropped = self.transaction_manager.drop_table(
+                validated_name,
+                purge_store=self.store if purge_chunks else None,
+                grace_period_secs=grace_period_secs,
+            )
+        else:
+            self._check_branches_writable(validated_name)
+            dropped = self.catalog.drop_table(validated_name)
+            if self.branch_manager is not None:
+                self.branch_manager.remove_table(validated_name)
+            if purge_chunks:
+                import _rhizo
+
+                _rhizo.sweep_chunks(
+                    self.store,
+                    self.catalog,
+                    grace_period_secs=grace_period_secs,
+                    only=list({h for v in dropped for h in v.chunk_hashes}),
+                )
+
+        self._invalidate_cache(validated_name)
+        if self._olap is not None:
+            self._olap.clear_cache(validated_name)
+
+        return [v.version for v in dropped]
+
+    def rename_table(self, old_name: str, new_name: str) -> int:
+        """
+        Rename a table, keeping its version history.
+
+        Every branch is pointed at the new name. With a transaction_manager
+        configured, the rename is recorded in the changelog.
+
+        Args:
+            old_name: Current table name
+            new_name: New table name (must be a valid SQL identifier)
+
+        Returns:
+            The table's latest version
+
+        Raises:
+            ValueError: If new_name is taken, a version is pinned, tagged or
+                        held, or an immutable branch refers to the table
+                        (OSError when raised through the transaction manager)
+        """
+        old_validated = validate_table_name(old_name)
+        new_validated = validate_table_name(new_name)
+
+        if self.transaction_manager is not None:
+            version = self.transaction_manager.rename_table(old_validated, new_validated)
+        else:
+            self._check_branches_writable(old_validated)
+            version = self.catalog.rename_table(old_validated, new_validated)
+            if self.branch_manager is not None:
+                self.branch_manager.rename_table(old_validated, new_validated)
+
+        for name in (old_validated, new_validated):
+            self._invalidate_cache(name)
+            if self._olap is not None:
+                self._olap.clear_cache(name)
+
+        return version
+
+    # =========================================================================
+    # Views
+    # =========================================================================
+
+    def create_view(
+        self,
+        name: str,
+        sql: str,
+        sources: Optional[List[str]] = None,
+        materialize: bool = False,
+        description: Optional[str] = None,
+        replace: bool = False,
+    ) -> "_rhizo.PyView":
+        """
+        Define a view: a named query that can be read like a table.
+
+        The view stores only its SQL. Queries that reference it run that SQL
+        over the base tables at the versions they read, so branches and time
+        travel (``versions={"orders": 3}``) apply to views too.
+
+        Args:
+            name: View name (shares the table namespace)
+            sql: Query defining the view
+            sources: Tables and views the query reads. Defaults to the
+                    existing tables and views referenced in sql.
+            materialize: Cache the result per set of base-table versions,
+                        so repeated reads skip recomputation until a base
+                        table changes
+            description: Optional note
+            replace: Redefine the view if it exists
+
+        Returns:
+            The stored view definition
+
+        Raises:
+            ValueError: If the name is taken by a table or view, there are no
+                        sources, or the view would depend on itself
+            OSError: If a source does not exist
+        """
+        validated_name = validate_table_name(name)
+        if sources is None:
+            existing = set(self.catalog.list_tables())
+            sources = sorted(
+                t for t in self._extract_table_names(sql)
+                if t in existing or self.catalog.view_exists(t)
+            )
+        else:
+            sources = [validate_table_name(s) for s in sources]
+
+        view = self.catalog.create_view(
+            validated_name, sql, sources,
+            materialize=materialize, description=description, replace=replace,
+        )
+        self._invalidate_view(validated_name)
+        return view
+
+    def get_view(self, name: str) -> "_rhizo.PyView":
+        """Get a view definition (OSError if it does not exist)."""
+        return self.catalog.get_view(validate_table_name(name))
+
+    def list_views(self) -> List[str]:
+        """List view names."""
+        return [v.name for v in self.catalog.list_views()]
+
+    def drop_view(self, name: str) -> None:
+        """Delete a view. Its base tables are untouched."""
+        validated_name = validate_table_name(name)
+        self.catalog.drop_view(validated_name)
+        self._invalidate_view(validated_name)
+
+    def _invalidate_view(self, view_name: str) -> None:
+        """Forget a view's registration and materialized results."""
+        with self._conn_lock:
+            for key in [k for k in self._view_cache if k[0] == view_name]:
+                del self._view_cache[key]
+            self._unregister_table(view_name)
+
+    def _check_branches_writable(self, table_name: str) -> None:
+        """Raise ValueError if an immutable branch refers to table_name."""
+        if self.branch_manager is None:
+            return
+        for name in self.branch_manager.list():
+            branch = self.branch_manager.get(name)
+            if branch.immutable and table_name in branch.head:
+                raise ValueError(f"Branch is immutable: {name}")
+
     # =========================================================================
     # Export Operations
     # =========================================================================
@@ -781,6 +1184,7 @@ class QueryEngine:
     def transaction(
         self,
         branch: Optional[str] = None,
+        author: Optional[str] = None,
     ) -> Generator["TransactionContext", None, None]:
         """
         Create a transaction context for atomic multi-table operations.
@@ -793,6 +1197,8 @@ class QueryEngine:
 
         Args:
             branch: Branch to operate on. If None, uses current_branch.
+            author: Recorded as the "author" metadata of the transaction's
+                    changelog entry
 
         Yields:
             TransactionContext for read/write operations
@@ -853,6 +1259,8 @@ class QueryEngine:
         ctx = TransactionContext(self, tx_id, effective_branch, snapshot)
 
         try:
+            if author is not None:
+                ctx.set_metadata("author", author)
             yield ctx
             # Commit on successful exit (if not already committed/aborted)
             if ctx.is_active:
@@ -867,6 +1275,49 @@ class QueryEngine:
             # Invalidate cache - versions may have changed
             self._registered.clear()
 
+    def retrying(
+        self,
+        branch: Optional[str] = None,
+        author: Optional[str] = None,
+        max_attempts: int = 5,
+        base_delay: float = 0.01,
+        max_delay: float = 1.0,
+    ) -> "RetryingTransaction":
+        """
+        Run a transaction, retrying it on write and snapshot conflicts.
+
+        Each attempt is a `transaction()` on a fresh snapshot. After a
+        conflict the loop waits a jittered exponential backoff (starting
+        at `base_delay` seconds, capped at `max_delay`) and runs the body
+        again; other errors propagate immediately.
+
+        Args:
+            branch: Branch to operate on. If None, uses current_branch.
+            author: Recorded as the "author" metadata of each attempt
+            max_attempts: Attempts before giving up, the first included
+            base_delay: Backoff in seconds after the first conflict
+            max_delay: Cap on the backoff in seconds
+
+        Returns:
+            RetryingTransaction yielding one context manager per attempt
+
+        Raises:
+            RetriesExhaustedError: If all `max_attempts` attempts conflict
+
+        Example:
+            >>> for attempt in engine.retrying(max_attempts=3):
+            ...     with attempt as tx:
+            ...         counts = tx.query("SELECT * FROM counters").to_pandas()
+            ...         counts["n"] += 1
+            ...         tx.write_table("counters", counts)
+        """
+        from .transaction import RetryingTransaction
+
+        return RetryingTransaction(
+            self, branch=branch, author=author, max_attempts=max_attempts,
+            base_delay=base_delay, max_delay=max_delay,
+        )
+
     def _capture_snapshot(self, branch: str) -> Dict[str, int]:
         """
         Capture the current version snapshot for a transaction.
@@ -963,6 +1414,76 @@ class QueryEngine:
         source = from_branch or self._current_branch
         return self.branch_manager.create(name, from_branch=source, description=description)
 
+    def current_snapshot(self, branch: Optional[str] = None) -> Optional[int]:
+        """
+        Repository snapshot id of a branch's current table versions.
+
+        The id can be cited on its own ("repo@1234") and passed back to
+        query(snapshot=...) or resolve_snapshot() to read exactly these
+        versions later.
+
+        Args:
+            branch: Branch name (default: current branch)
+
+        Returns:
+            The snapshot id, or None if the branch has not changed since
+            snapshots were introduced
+
+        Raises:
+            RuntimeError: If branch_manager is not configured
+        """
+        if self.branch_manager is None:
+            raise RuntimeError("Cannot get snapshot: branch_manager not configured")
+
+        snapshot = self.branch_manager.latest_snapshot(branch or self._current_branch)
+        return snapshot.id if snapshot is not None else None
+
+    def resolve_snapshot(self, snapshot: Union[int, str]) -> Dict[str, int]:
+        """
+        Resolve a repository snapshot id to its table versions.
+
+        Args:
+            snapshot: Snapshot id, as an int or a "repo@1234" string
+
+        Returns:
+            Dict mapping table names to versions
+
+        Raises:
+            IOError: If the snapshot doesn't exist
+            ValueError: If the reference can't be parsed
+            RuntimeError: If branch_manager is not configured
+        """
+        if self.branch_manager is None:
+            raise RuntimeError("Cannot resolve snapshot: branch_manager not configured")
+
+        return dict(self.branch_manager.snapshot(snapshot).versions)
+
+    def get_version_as_of(self, table_name: str, snapshot: Union[int, str]) -> int:
+        """
+        Version of a table recorded by a repository snapshot.
+
+        Reading every table at the version this returns gives a point-in-time
+        view aligned across all tables of the snapshot's branch.
+
+        Args:
+            table_name: Name of the table
+            snapshot: Snapshot id, as an int or a "repo@1234" string
+
+        Returns:
+            The table version
+
+        Raises:
+            IOError: If the snapshot doesn't exist
+            TableNotFoundError: If the table was not on the branch when the
+                                snapshot was recorded
+            RuntimeError: If branch_manager is not configured
+        """
+        validated_name = validate_table_name(table_name)
+        versions = self.resolve_snapshot(snapshot)
+        if validated_name not in versions:
+            raise TableNotFoundError(validated_name)
+        return versions[validated_name]
+
     def list_branches(self) -> List[str]:
         """
         List all branch names.
@@ -1014,28 +1535,84 @@ class QueryEngine:
             "target_only_changes": diff.target_only_changes,
         }
 
-    def merge_branch(self, source: str, into: Optional[str] = None) -> None:
+    def merge_branch(
+        self,
+        source: str,
+        into: Optional[str] = None,
+        *,
+        schema_registry: Optional["_rhizo.PyAlgebraicSchemaRegistry"] = None,
+        resolve: Optional[ConflictResolver] = None,
+        delete_source: bool = False,
+    ) -> MergeEvent:
         """
-        Merge source branch into target branch (fast-forward only).
+        Merge source branch into target branch.
+
+        Publishes MergeEvents on ``self.events`` (see rhizo.merge_events):
+        MERGE_STARTED_TOPIC before merging, MERGE_CONFLICTED_TOPIC if both
+        branches changed a table, and MERGE_COMPLETED_TOPIC once merged.
 
         Args:
             source: Branch to merge from
             into: Branch to merge into (default: current branch)
+            schema_registry: Algebraic schemas used to analyze the merge
+                            (default: none, so no table is auto-mergeable)
+            resolve: Called with the conflicted event; returns a decision
+                    per conflicting table, "source" or "target"
+            delete_source: Delete the source branch once the merge lands
+                          (it is kept if the merge fails)
+
+        Returns:
+            The completed MergeEvent
 
         Raises:
             RuntimeError: If branch_manager is not configured
-            ValueError: If merge conflict (branches have diverged)
+            ValueError: If a conflict is left unresolved or a decision is
+                        not "source" or "target"
         """
+        import _rhizo
+
         if self.branch_manager is None:
             raise RuntimeError("Cannot merge: branch_manager not configured")
 
         target = into or self._current_branch
-        self.branch_manager.merge(source, into=target)
+        registry = schema_registry if schema_registry is not None else _rhizo.PyAlgebraicSchemaRegistry()
+        analysis = _rhizo.analyze_merge(self.branch_manager.diff(source, target), registry)
+        conflicts = self.branch_manager.merge_conflicts(source, target)
+
+        def event(topic: str, resolutions: Dict[str, str]) -> MergeEvent:
+            return MergeEvent(topic, source, target, analysis, conflicts, resolutions)
+
+        self.events.publish(MERGE_STARTED_TOPIC, event(MERGE_STARTED_TOPIC, {}))
+
+        resolutions: Dict[str, str] = {}
+        if conflicts:
+            conflicted = event(MERGE_CONFLICTED_TOPIC, {})
+            self.events.publish(MERGE_CONFLICTED_TOPIC, conflicted)
+            if resolve is not None:
+                decisions = resolve(conflicted) or {}
+                for table, side in decisions.items():
+                    if table not in conflicts:
+                        continue
+                    if side not in RESOLUTIONS:
+                        raise ValueError(
+                            f"Unknown merge resolution for {table}: {side!r} "
+                            f"(expected 'source' or 'target')"
+                        )
+                    resolutions[table] = side
+
+        self.branch_manager.merge(
+            source, into=target, resolutions=resolutions, delete_source=delete_source
+        )
+
+        completed = event(MERGE_COMPLETED_TOPIC, resolutions)
+        self.events.publish(MERGE_COMPLETED_TOPIC, completed)
 
         # Invalidate cache if we merged into current branch
         if target == self._current_branch:
             self._registered.clear()
 
+        return completed
+
     def get_table_info(self, table_name: str, version: Optional[int] = None) -> Dict[str, Any]:
         """
         Get information about a table version.
@@ -1510,6 +2087,7 @@ class QueryEngine:
         since_tx_id: Optional[int] = None,
         branch: Optional[str] = None,
         poll_interval: float = 1.0,
+        cursor: Optional[str] = None,
     ) -> "Subscriber":
         """
         Create a subscriber for changelog events.
@@ -1523,6 +2101,8 @@ class QueryEngine:
                         None = start from current latest (won't replay history)
             branch: Filter to specific branch (None = all branches)
             poll_interval: Seconds between polls when waiting (default: 1.0)
+            cursor: Name of a persisted changelog cursor to resume from and
+                    acknowledge delivered transactions to (None = in-memory only)
 
         Returns:
             Subscriber instance for processing change events
@@ -1564,6 +2144,7 @@ class QueryEngine:
             tables=tables,
             branch=branch,
             poll_interval=poll_interval,
+            cursor=cursor,
         )
 
     def latest_tx_id(self) -> Optional[int]:
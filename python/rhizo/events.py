@@ -0,0 +1,96 @@
+"""
+In-process event bus.
+
+A minimal topic-based publish/subscribe hub used for operational events
+(e.g. read auditing). Handlers run synchronously on the publishing thread;
+an exception in one handler is logged and never propagates to the
+operation that published the event or to other handlers.
+
+Example:
+    >>> from rhizo.events import EventBus
+    >>> bus = EventBus()
+    >>> token = bus.subscribe("read", lambda event: print(event.table_name))
+    >>> bus.publish("read", event)
+    >>> bus.unsubscribe(token)
+"""
+
+from __future__ import annotations
+
+import itertools
+import threading
+from typing import Any, Callable, Dict, List, Tuple
+
+from .logging import get_logger
+
+_logger = get_logger(__name__)
+
+# Subscribe to this topic to receive events from every topic
+ALL_TOPICS = "*"
+
+Handler = Callable[[Any], None]
+
+
+class EventBus:
+    """Thread-safe, synchronous publish/subscribe hub."""
+
+    def __init__(self) -> None:
+        self._handlers: Dict[str, List[Tuple[int, Handler]]] = {}
+        self._ids = itertools.count(1)
+        self._lock = threading.Lock()
+
+    def subscribe(self, topic: str, handler: Handler) -> int:
+        """
+        Register a handler for a topic.
+
+        Args:
+            topic: Topic name, or ALL_TOPICS ("*") for every topic
+            handler: Callable invoked with each published event
+
+        Returns:
+            Subscription token for unsubscribe()
+        """
+        with self._lock:
+            token = next(self._ids)
+            self._handlers.setdefault(topic, []).append((token, handler))
+            return token
+
+    def unsubscribe(self, token: int) -> bool:
+        """
+        Remove a handler.
+
+        Returns:
+            True if the subscription existed
+        """
+        with self._lock:
+            for topic, handlers in self._handlers.items():
+                for i, (t, _) in enumerate(handlers):
+                    if t == token:
+                        del handlers[i]
+                        return True
+        return False
+
+    def has_subscribers(self, topic: str) -> bool:
+        """Whether publishing to a topic would reach any handler."""
+        with self._lock:
+            return bool(self._handlers.get(topic) or self._handlers.get(ALL_TOPICS))
+
+    def publish(self, topic: str, event: Any) -> int:
+        """
+        Deliver an event to the topic's handlers (and ALL_TOPICS handlers).
+
+        Returns:
+            Number of handlers that received the event without error
+        """
+        with self._lock:
+            handlers = list(self._handlers.get(topic, ()))
+            if topic != ALL_TOPICS:
+                handlers.extend(self._handlers.get(ALL_TOPICS, ()))
+
+        delivered = 0
+        for _, handler in handlers:
+            try:
+                handler(event)
+                delivered += 1
+            except Exception:
+                _logger.exception("Event handler failed for topic %r", topic)
+        return delivered
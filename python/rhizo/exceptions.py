@@ -11,13 +11,22 @@ from __future__ import annotations
 
 import re
 
-
-class RhizoError(Exception):
-    """Base class for all Rhizo-specific errors."""
-    pass
-
-
-class TableNotFoundError(RhizoError, IOError):
+# Raised by the native core; defined there so every layer shares the base
+from _rhizo import (
+    RhizoError,
+    ConflictError,
+    WriteConflictError,
+    SnapshotConflictError,
+    MergeConflictError,
+    NotFoundError,
+    BranchNotFoundError,
+    ChunkNotFoundError,
+    IntegrityError,
+    HashMismatchError,
+)
+
+
+class TableNotFoundError(NotFoundError):
     """
     Raised when a table does not exist in the catalog.
 
@@ -30,7 +39,7 @@ class TableNotFoundError(RhizoError, IOError):
         super().__init__(f"Table not found: {table_name}")
 
 
-class VersionNotFoundError(RhizoError, IOError):
+class VersionNotFoundError(NotFoundError):
     """
     Raised when a specific version does not exist for a table.
 
@@ -101,6 +110,35 @@ class SizeLimitExceededError(RhizoError, ValueError):
         )
 
 
+class QuotaExceededError(RhizoError):
+    """
+    Raised when a write would push storage usage past a hard quota.
+
+    The write's chunks may already be in the store; they are unreferenced
+    and reclaimed by the next garbage collection.
+    """
+
+    def __init__(self, quota_name: str, used_bytes: int, limit_bytes: int):
+        self.quota_name = quota_name
+        self.used_bytes = used_bytes
+        self.limit_bytes = limit_bytes
+        super().__init__(
+            f"Quota '{quota_name}' exceeded: {used_bytes:,} bytes > {limit_bytes:,} bytes limit"
+        )
+
+
+class RetriesExhaustedError(RhizoError, RuntimeError):
+    """
+    Raised when every attempt of a retried transaction hit a conflict.
+
+    The last conflict is chained as ``__cause__``.
+    """
+
+    def __init__(self, attempts: int):
+        self.attempts = attempts
+        super().__init__(f"Transaction gave up after {attempts} conflicting attempts")
+
+
 # Pre-compiled regex for table name validation
 _TABLE_NAME_PATTERN = re.compile(r'^[a-zA-Z_][a-zA-Z0-9_]*$')
 
@@ -0,0 +1,327 @@
+"""
+Read-only HTTP+JSON gateway for browser clients.
+
+Serves the catalog, changelog and branch graph over plain HTTP so dataset
+browser UIs can be built without extra middleware. Only GET (and CORS
+preflight OPTIONS) requests are accepted; nothing is ever written.
+
+Endpoints:
+    GET /tables                          Tables with their latest version
+    GET /tables/<name>/versions          Versions of a table
+    GET /tables/<name>/versions/<n>      One version, with its chunk hashes
+    GET /changelog                       Committed transactions
+    GET /branches                        Branch graph (nodes and edges)
+    GET /branches/<name>                 One branch with its head
+
+``/tables`` accepts ``branch`` to list the tables visible on a branch at
+their head versions. ``/changelog`` accepts ``since_tx_id``,
+``until_tx_id``, ``since_timestamp``, ``branch``, ``limit`` and a
+repeatable ``table``.
+
+Example:
+    >>> gateway = db.gateway(port=8080)
+    >>> gateway.start()
+    >>> # curl http://127.0.0.1:8080/tables
+    >>> gateway.stop()
+"""
+
+from __future__ import annotations
+
+import json
+import threading
+from http.server import BaseHTTPRequestHandler, ThreadingHTTPServer
+from typing import TYPE_CHECKING, Any, Dict, List, Optional, Tuple
+from urllib.parse import parse_qs, unquote, urlsplit
+
+from .logging import get_logger
+
+if TYPE_CHECKING:
+    from _rhizo import PyBranchManager, PyCatalog, PyTransactionManager
+
+_logger = get_logger(__name__)
+
+Response = Tuple[int, Dict[str, Any]]
+
+
+class GatewayError(Exception):
+    """A request the gateway answers with an error status."""
+
+    def __init__(self, status: int, message: str):
+        super().__init__(message)
+        self.status = status
+
+
+class RestGateway:
+    """
+    Serves read-only JSON views of a repository over HTTP.
+
+    Args:
+        catalog: Table version catalog
+        branch_manager: Enables ``/branches`` and ``/tables?branch=``
+        transaction_manager: Enables ``/changelog``
+        host: Interface to bind (default: loopback only)
+        port: Port to bind; 0 picks a free port (see ``url``)
+        allow_origin: ``Access-Control-Allow-Origin`` sent with every
+            response, or None to send no CORS headers
+    """
+
+    def __init__(
+        self,
+        catalog: "PyCatalog",
+        branch_manager: Optional["PyBranchManager"] = None,
+        transaction_manager: Optional["PyTransactionManager"] = None,
+        *,
+        host: str = "127.0.0.1",
+        port: int = 8080,
+        allow_origin: Optional[str] = "*",
+    ):
+        self._catalog = catalog
+        self._branch_manager = branch_manager
+        self._transaction_manager = transaction_manager
+        self._host = host
+        self._port = port
+        self._allow_origin = allow_origin
+        self._server: Optional[ThreadingHTTPServer] = None
+        self._thread: Optional[threading.Thread] = None
+
+    @property
+    def url(self) -> str:
+        """Base URL of the running gateway."""
+        if self._server is None:
+            raise RuntimeError("Gateway is not running")
+        host, port = self._server.server_address[:2]
+        return f"http://{host}:{port}"
+
+    @property
+    def running(self) -> bool:
+        return self._server is not None
+
+    def start(self) -> "RestGateway":
+        """Serve requests on a background thread."""
+        if self._server is not None:
+            raise RuntimeError("Gateway is already running")
+        self._server = ThreadingHTTPServer((self._host, self._port), self._handler_class())
+        self._server.daemon_threads = True
+        self._thread = threading.Thread(
+            target=self._server.serve_forever, name="rhizo-gateway", daemon=True,
+        )
+        self._thread.start()
+        _logger.info("Gateway listening on %s", self.url)
+        return self
+
+    def serve_forever(self) -> None:
+        """Serve requests on the calling thread until interrupted."""
+        if self._server is not None:
+            raise RuntimeError("Gateway is already running")
+        self._server = ThreadingHTTPServer((self._host, self._port), self._handler_class())
+        _logger.info("Gateway listening on %s", self.url)
+        try:
+            self._server.serve_forever()
+        finally:
+            self._server.server_close()
+            self._server = None
+
+    def stop(self) -> None:
+        """Stop a gateway started with ``start()``."""
+        if self._server is None:
+            return
+        self._server.shutdown()
+        self._server.server_close()
+        if self._thread is not None:
+            self._thread.join()
+        self._server = None
+        self._thread = None
+
+    def __enter__(self) -> "RestGateway":
+        return self.start()
+
+    def __exit__(self, *exc: Any) -> None:
+        self.stop()
+
+    # ------------------------------------------------------------------
+    # Routing
+    # ------------------------------------------------------------------
+
+    def handle(self, path: str) -> Response:
+        """
+        Answer a GET request for ``path`` (including any query string).
+
+        Returns:
+            (HTTP status, JSON-serializable body)
+        """
+        parts = urlsplit(path)
+        segments = [unquote(s) for s in parts.path.split("/") if s]
+        params = parse_qs(parts.query)
+        try:
+            return 200, self._route(segments, params)
+        except GatewayError as e:
+            return e.status, {"error": str(e)}
+        except (OSError, ValueError, KeyError) as e:
+            return 404, {"error": str(e)}
+
+    def _route(self, segments: List[str], params: Dict[str, List[str]]) -> Dict[str, Any]:
+        n = len(segments)
+        if segments[:1] == ["tables"]:
+            if n == 1:
+                return self._tables(_param(params, "branch"))
+            if n == 3 and segments[2] == "versions":
+                return self._versions(segments[1])
+            if n == 4 and segments[2] == "versions":
+                return self._version(segments[1], _int(segments[3], "version"))
+        elif segments == ["changelog"]:
+            return self._changelog(params)
+        elif segments[:1] == ["branches"]:
+            if n == 1:
+                return self._branches()
+            if n == 2:
+                return {"branch": _branch_json(self._branches_or_404().get(segments[1]))}
+        raise GatewayError(404, f"Unknown endpoint: /{'/'.join(segments)}")
+
+    def _tables(self, branch: Optional[str]) -> Dict[str, Any]:
+        if branch is None:
+            heads = {t: self._catalog.get_version(t).version for t in self._catalog.list_tables()}
+        else:
+            heads = dict(self._branches_or_404().get(branch).head)
+        tables = [
+            {"name": name, "version": version, "version_count": len(self._catalog.list_versions(name))}
+            for name, version in sorted(heads.items())
+        ]
+        return {"branch": branch, "tables": tables}
+
+    def _versions(self, table: str) -> Dict[str, Any]:
+        versions = [
+            _version_json(self._catalog.get_version(table, v), chunks=False)
+            for v in self._catalog.list_versions(table)
+        ]
+        if not versions:
+            raise GatewayError(404, f"Table not found: {table}")
+        return {"table": table, "versions": versions}
+
+    def _version(self, table: str, version: int) -> Dict[str, Any]:
+        return {"version": _version_json(self._catalog.get_version(table, version), chunks=True)}
+
+    def _changelog(self, params: Dict[str, List[str]]) -> Dict[str, Any]:
+        if self._transaction_manager is None:
+            raise GatewayError(404, "Changelog requires a transaction manager")
+        entries = self._transaction_manager.get_changelog(
+            since_tx_id=_int_param(params, "since_tx_id"),
+            since_timestamp=_int_param(params, "since_timestamp"),
+            tables=params.get("table"),
+            branch=_param(params, "branch"),
+            limit=_int_param(params, "limit"),
+            until_tx_id=_int_param(params, "until_tx_id"),
+        )
+        return {"entries": [_entry_json(e) for e in entries]}
+
+    def _branches(self) -> Dict[str, Any]:
+        manager = self._branches_or_404()
+        branches = [manager.get(name) for name in sorted(manager.list())]
+        edges = [
+            {"from": b.parent_branch, "to": b.name}
+            for b in branches if b.parent_branch is not None
+        ]
+        return {"branches": [_branch_json(b) for b in branches], "edges": edges}
+
+    def _branches_or_404(self) -> "PyBranchManager":
+        if self._branch_manager is None:
+            raise GatewayError(404, "Branches require a branch manager")
+        return self._branch_manager
+
+    def _handler_class(self) -> type:
+        gateway = self
+
+        class Handler(BaseHTTPRequestHandler):
+            def do_GET(self) -> None:
+                status, body = gateway.handle(self.path)
+                self._send(status, body)
+
+            def do_OPTIONS(self) -> None:
+                self._send(204, None)
+
+            def _read_only(self) -> None:
+                self._send(405, {"error": "The gateway is read-only"}, allow="GET, OPTIONS")
+
+            do_POST = do_PUT = do_PATCH = do_DELETE = _read_only
+
+            def _send(self, status: int, body: Optional[Dict[str, Any]], allow: Optional[str] = None) -> None:
+                payload = b"" if body is None else json.dumps(body).encode()
+                self.send_response(status)
+                if body is not None:
+                    self.send_header("Content-Type", "application/json")
+                self.send_header("Content-Length", str(len(payload)))
+                if allow is not None:
+                    self.send_header("Allow", allow)
+                if gateway._allow_origin is not None:
+                    self.send_header("Access-Control-Allow-Origin", gateway._allow_origin)
+                    self.send_header("Access-Control-Allow-Methods", "GET, OPTIONS")
+                    self.send_header("Access-Control-Allow-Headers", "Content-Type")
+                self.end_headers()
+                self.wfile.write(payload)
+
+            def log_message(self, format: str, *args: Any) -> None:
+                _logger.debug("%s - %s", self.address_string(), format % args)
+
+        return Handler
+
+
+def _param(params: Dict[str, List[str]], name: str) -> Optional[str]:
+    values = params.get(name)
+    return values[-1] if values else None
+
+
+def _int_param(params: Dict[str, List[str]], name: str) -> Optional[int]:
+    value = _param(params, name)
+    return None if value is None else _int(value, name)
+
+
+def _int(value: str, name: str) -> int:
+    try:
+        return int(value)
+    except ValueError:
+        raise GatewayError(400, f"{name} must be an integer, got {value!r}") from None
+
+
+def _version_json(v: Any, *, chunks: bool) -> Dict[str, Any]:
+    body = {
+        "table": v.table_name,
+        "version": v.version,
+        "parent_version": v.parent_version,
+        "created_at": v.created_at,
+        "schema_hash": v.schema_hash,
+        "chunk_count": len(v.chunk_hashes),
+        "metadata": dict(v.metadata),
+    }
+    if chunks:
+        body["chunk_hashes"] = list(v.chunk_hashes)
+    return body
+
+
+def _entry_json(entry: Any) -> Dict[str, Any]:
+    return {
+        "tx_id": entry.tx_id,
+        "epoch_id": entry.epoch_id,
+        "committed_at": entry.committed_at,
+        "branch": entry.branch,
+        "metadata": dict(entry.metadata),
+        "changes": [
+            {
+                "table": c.table_name,
+                "kind": c.kind,
+                "old_version": c.old_version,
+                "new_version": c.new_version,
+                "renamed_to": c.renamed_to,
+            }
+            for c in entry.changes
+        ],
+    }
+
+
+def _branch_json(branch: Any) -> Dict[str, Any]:
+    return {
+        "name": branch.name,
+        "parent": branch.parent_branch,
+        "created_at": branch.created_at,
+        "description": branch.description,
+        "immutable": branch.immutable,
+        "head": dict(branch.head),
+    }
@@ -9,6 +9,9 @@ Safety guarantees:
   - Latest version of each table is NEVER deleted.
   - Versions referenced by branch heads or fork points are NEVER deleted.
   - Versions referenced by active transaction snapshots are NEVER deleted.
+  - Versions held by a pin (``catalog.pin``) are NEVER deleted.
+  - Chunks written by uncommitted transactions are NEVER swept, and
+    ``GCPolicy.chunk_grace_seconds`` keeps recently written chunks.
   - Crash between phases only leaves orphaned chunks (cleaned on next GC).
 
 Example:
@@ -50,16 +53,28 @@ class GCPolicy:
             ``None`` disables time-based TTL.
         max_versions_per_table: Keep at most this many versions per table.
             ``None`` disables count-based retention.
+        namespace: Restrict version deletion to one namespace, given as a
+            prefix (``"sales"``) or wildcard pattern (``"sales.*"``).
+            ``None`` applies the policy to every table. The chunk sweep is
+            always global, so chunks shared across namespaces stay safe.
+        chunk_grace_seconds: Keep unreferenced chunks written within this
+            many seconds, protecting commits that have stored chunks but
+            not yet reached the catalog. ``0`` sweeps every unreferenced
+            chunk not claimed by an active transaction.
     """
 
     max_age_seconds: Optional[float] = None
     max_versions_per_table: Optional[int] = None
+    namespace: Optional[str] = None
+    chunk_grace_seconds: float = 0.0
 
     def __post_init__(self):
         if self.max_age_seconds is not None and self.max_age_seconds < 0:
             raise ValueError("max_age_seconds must be non-negative")
         if self.max_versions_per_table is not None and self.max_versions_per_table < 1:
             raise ValueError("max_versions_per_table must be >= 1")
+        if self.chunk_grace_seconds < 0:
+            raise ValueError("chunk_grace_seconds must be non-negative")
 
 
 @dataclass
@@ -149,7 +164,9 @@ class GarbageCollector:
         result.details = details
 
         # Phase 2: sweep unreferenced chunks
-        chunks_deleted, chunks_failed, bytes_freed = self._phase2_sweep_chunks()
+        chunks_deleted, chunks_failed, bytes_freed = self._phase2_sweep_chunks(
+            policy.chunk_grace_seconds
+        )
         result.chunks_deleted = chunks_deleted
         result.chunks_failed = chunks_failed
         result.bytes_freed = bytes_freed
@@ -204,6 +221,14 @@ class GarbageCollector:
                 # If we can't query active transactions, be conservative
                 logger.warning("Could not query active transactions for GC safety")
 
+        # 4. Pinned versions (reproducible experiment inputs)
+        for table_name, versions in self.catalog.pinned_versions().items():
+            protected.setdefault(table_name, set()).update(versions)
+
+        # 5. Legal holds (compliance retention)
+        for table_name, versions in self.catalog.held_versions().items():
+            protected.setdefault(table_name, set()).update(versions)
+
         return protected
 
     def _phase1_delete_versions(
@@ -219,7 +244,7 @@ class GarbageCollector:
         total_deleted = 0
         details: Dict[str, List[int]] = {}
 
-        for table_name in self.catalog.list_tables():
+        for table_name in self._select_tables(policy.namespace):
             versions = self.catalog.list_versions(table_name)
             if not versions:
                 continue
@@ -263,30 +288,32 @@ class GarbageCollector:
 
         return total_deleted, details
 
-    def _phase2_sweep_chunks(self) -> Tuple[int, int, int]:
+    def _select_tables(self, namespace: Optional[str]) -> List[str]:
+        """Tables covered by the policy (all tables when namespace is None)."""
+        if namespace is None:
+            return self.catalog.list_tables()
+        if "*" in namespace or "?" in namespace:
+            return self.catalog.list_tables_matching(namespace)
+        return self.catalog.list_tables(namespace)
+
+    def _phase2_sweep_chunks(self, grace_seconds: float = 0.0) -> Tuple[int, int, int]:
         """Phase 2: Delete unreferenced chunks from store.
 
+        Chunks referenced by any catalog version or by a transaction that
+        has not committed yet are kept, as are chunks written within
+        ``grace_seconds``.
+
         Returns (deleted_count, failed_count, bytes_freed).
         """
-        # Get all currently referenced chunk hashes
-        referenced = self.catalog.get_all_referenced_chunk_hashes()
-
-        # Estimate bytes that will be freed
-        all_hashes = self.store.list_chunk_hashes()
-        referenced_set = set(referenced)
-        bytes_freed = 0
-        for h in all_hashes:
-            if h not in referenced_set:
-                try:
-                    chunk_data = self.store.get(h)
-                    bytes_freed += len(chunk_data)
-                except Exception:
-                    pass
+        from _rhizo import sweep_chunks
 
-        # Run GC
-        deleted, failed = self.store.garbage_collect(referenced)
-
-        return deleted, failed, bytes_freed
+        report = sweep_chunks(
+            self.store,
+            self.catalog,
+            self.transaction_manager,
+            grace_period_secs=grace_seconds,
+        )
+        return report.chunks_deleted, report.chunks_failed, report.bytes_collectable
 
 
 class AutoGC:
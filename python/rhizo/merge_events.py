@@ -0,0 +1,91 @@
+"""
+Merge events — live merge status for UI tooling.
+
+QueryEngine.merge_branch publishes a MergeEvent on the engine's event bus
+when a merge starts, when it conflicts and when it completes. Every event
+carries the full MergeAnalysis (which tables are source-only, target-only,
+auto-mergeable or conflicting), so a web UI can render merge status as it
+happens.
+
+Conflicts can be resolved interactively: pass ``resolve`` to merge_branch.
+It receives the conflicted event and returns a decision per table,
+``"source"`` (take the merged branch's version) or ``"target"`` (keep the
+current one). Tables left undecided fail the merge.
+
+Example:
+    >>> from rhizo.merge_events import MERGE_CONFLICTED_TOPIC, MERGE_COMPLETED_TOPIC
+    >>> engine.events.subscribe(MERGE_COMPLETED_TOPIC, lambda e: ui.push(e.to_dict()))
+    >>> engine.merge_branch(
+    ...     "feature", into="main",
+    ...     resolve=lambda event: ui.ask_user(event.conflicts),
+    ... )
+"""
+
+from __future__ import annotations
+
+import time
+from dataclasses import dataclass, field
+from typing import TYPE_CHECKING, Callable, Dict, List, Optional
+
+if TYPE_CHECKING:
+    import _rhizo
+
+# Event bus topics, one per merge stage
+MERGE_STARTED_TOPIC = "merge.started"
+MERGE_CONFLICTED_TOPIC = "merge.conflicted"
+MERGE_COMPLETED_TOPIC = "merge.completed"
+MERGE_TOPICS = (MERGE_STARTED_TOPIC, MERGE_CONFLICTED_TOPIC, MERGE_COMPLETED_TOPIC)
+
+# Valid per-table conflict decisions
+RESOLUTIONS = ("source", "target")
+
+
+@dataclass(frozen=True)
+class MergeEvent:
+    """
+    One stage of a branch merge.
+
+    Attributes:
+        topic: MERGE_STARTED_TOPIC, MERGE_CONFLICTED_TOPIC or MERGE_COMPLETED_TOPIC
+        source_branch: Branch being merged
+        target_branch: Branch merged into
+        analysis: MergeAnalysis of the two branches
+        conflicts: Tables both branches changed
+        resolutions: Table -> "source" | "target" decisions applied so far
+        timestamp: Unix timestamp (seconds, float) of the event
+    """
+    topic: str
+    source_branch: str
+    target_branch: str
+    analysis: "_rhizo.PyMergeAnalysis"
+    conflicts: List[str] = field(default_factory=list)
+    resolutions: Dict[str, str] = field(default_factory=dict)
+    timestamp: float = field(default_factory=time.time)
+
+    @property
+    def unresolved(self) -> List[str]:
+        """Conflicting tables without a decision."""
+        return [t for t in self.conflicts if t not in self.resolutions]
+
+    def to_dict(self) -> Dict[str, object]:
+        """JSON-serializable form (for pushing to a UI)."""
+        return {
+            "topic": self.topic,
+            "source_branch": self.source_branch,
+            "target_branch": self.target_branch,
+            "analysis": {
+                "auto_mergeable": list(self.analysis.auto_mergeable),
+                "conflicting": list(self.analysis.conflicting),
+                "source_only": list(self.analysis.source_only),
+                "target_only": list(self.analysis.target_only),
+                "unchanged": list(self.analysis.unchanged),
+            },
+            "conflicts": list(self.conflicts),
+            "resolutions": dict(self.resolutions),
+            "timestamp": self.timestamp,
+        }
+
+
+# Called with the conflicted event; returns table -> "source" | "target"
+# (or None to leave the conflicts unresolved)
+ConflictResolver = Callable[[MergeEvent], Optional[Dict[str, str]]]
@@ -0,0 +1,226 @@
+"""
+Storage quotas with soft (warning) and hard (enforced) thresholds.
+
+A Quota caps the stored bytes of a set of tables (or the whole database).
+Usage counts each distinct chunk once across all retained versions, so
+deduplicated data is not double-charged and GC frees quota.
+
+When a write pushes usage past ``warn_at`` (default 80%) of the limit, a
+QuotaEvent is published on the event bus and logged, once per crossing.
+Writes that would exceed the limit fail with QuotaExceededError.
+
+Example:
+    >>> from rhizo.quota import Quota, QUOTA_TOPIC
+    >>> db = rhizo.open("./mydata", quotas=[Quota(10 * 2**30, name="total")])
+    >>> db.quotas.bus.subscribe(QUOTA_TOPIC, lambda e: notify(e.describe()))
+"""
+
+from __future__ import annotations
+
+import threading
+from dataclasses import dataclass
+from typing import TYPE_CHECKING, Dict, List, Optional, Sequence, Set
+
+from .events import EventBus
+from .exceptions import QuotaExceededError
+from .logging import get_logger
+
+if TYPE_CHECKING:
+    from _rhizo import PyCatalog, PyChunkStore
+
+_logger = get_logger(__name__)
+
+# Event bus topic for quota events
+QUOTA_TOPIC = "quota"
+
+
+@dataclass(frozen=True)
+class Quota:
+    """
+    Storage limit for a set of tables.
+
+    Attributes:
+        max_bytes: Hard limit; writes beyond it fail.
+        warn_at: Fraction of max_bytes at which a warning is emitted
+            (default: 0.8). Set to 1.0 to disable warnings.
+        tables: Tables covered by the quota (None = every table).
+        name: Name used in events and errors (default: derived from tables).
+    """
+
+    max_bytes: int
+    warn_at: float = 0.8
+    tables: Optional[Sequence[str]] = None
+    name: Optional[str] = None
+
+    def __post_init__(self):
+        if self.max_bytes <= 0:
+            raise ValueError("max_bytes must be positive")
+        if not 0.0 < self.warn_at <= 1.0:
+            raise ValueError("warn_at must be in (0.0, 1.0]")
+        if self.tables is not None:
+            object.__setattr__(self, "tables", tuple(t.lower() for t in self.tables))
+
+    @property
+    def label(self) -> str:
+        if self.name is not None:
+            return self.name
+        return "*" if self.tables is None else ",".join(self.tables)
+
+    def covers(self, table_name: str) -> bool:
+        return self.tables is None or table_name.lower() in self.tables
+
+
+@dataclass(frozen=True)
+class QuotaEvent:
+    """
+    Published on QUOTA_TOPIC when usage crosses a threshold.
+
+    Attributes:
+        quota: Quota label.
+        level: "warning" (soft threshold crossed) or "exceeded" (write refused).
+        used_bytes: Usage including the triggering write.
+        limit_bytes: The quota's max_bytes.
+        table_name: Table whose write triggered the event.
+    """
+
+    quota: str
+    level: str
+    used_bytes: int
+    limit_bytes: int
+    table_name: str
+
+    @property
+    def fraction(self) -> float:
+        return self.used_bytes / self.limit_bytes
+
+    def describe(self) -> str:
+        return (
+            f"Quota '{self.quota}' {self.level}: {self.used_bytes:,} of "
+            f"{self.limit_bytes:,} bytes ({self.fraction:.0%}) after write to '{self.table_name}'"
+        )
+
+
+@dataclass(frozen=True)
+class QuotaStatus:
+    """Point-in-time usage of one quota (e.g. for metrics export)."""
+
+    quota: str
+    used_bytes: int
+    limit_bytes: int
+    warning: bool
+
+    @property
+    def fraction(self) -> float:
+        return self.used_bytes / self.limit_bytes
+
+
+class QuotaEnforcer:
+    """
+    Tracks usage for a set of quotas and checks writes against them.
+
+    Usage is computed from the catalog on first use and then updated
+    incrementally as writes are charged. Call refresh() after operations
+    that free space outside the writer (e.g. garbage collection).
+
+    Args:
+        catalog: PyCatalog used to compute usage.
+        store: PyChunkStore used to size existing chunks.
+        quotas: Quotas to enforce.
+        bus: EventBus for QuotaEvents (default: a new private bus).
+    """
+
+    def __init__(
+        self,
+        catalog: "PyCatalog",
+        store: "PyChunkStore",
+        quotas: Sequence[Quota],
+        bus: Optional[EventBus] = None,
+    ):
+        self.catalog = catalog
+        self.store = store
+        self.quotas: List[Quota] = list(quotas)
+        self.bus = bus if bus is not None else EventBus()
+        self._lock = threading.Lock()
+        self._chunk_sizes: Dict[str, int] = {}
+        self._charged: Optional[List[Set[str]]] = None  # chunk hashes per quota
+        self._warned: List[bool] = [False] * len(self.quotas)
+
+    def refresh(self) -> None:
+        """Recompute usage from the catalog on next use."""
+        with self._lock:
+            self._charged = None
+
+    def status(self) -> List[QuotaStatus]:
+        """Current usage of every quota."""
+        with self._lock:
+            charged = self._ensure_usage()
+            return [
+                QuotaStatus(q.label, self._bytes(hashes), q.max_bytes, self._warned[i])
+                for i, (q, hashes) in enumerate(zip(self.quotas, charged))
+            ]
+
+    def charge(self, table_name: str, chunk_sizes: Dict[str, int]) -> None:
+        """
+        Check a write against every covering quota and account for it.
+
+        Args:
+            table_name: Table being written.
+            chunk_sizes: Chunk hash -> size in bytes for the new version.
+
+        Raises:
+            QuotaExceededError: If any covering quota would be exceeded.
+                Nothing is charged in that case.
+        """
+        with self._lock:
+            charged = self._ensure_usage()
+            self._chunk_sizes.update(chunk_sizes)
+
+            covering = [i for i, q in enumerate(self.quotas) if q.covers(table_name)]
+            projected = {
+                i: self._bytes(charged[i] | chunk_sizes.keys()) for i in covering
+            }
+
+            for i in covering:
+                quota = self.quotas[i]
+                if projected[i] > quota.max_bytes:
+                    self._emit(QuotaEvent(
+                        quota.label, "exceeded", projected[i], quota.max_bytes, table_name,
+                    ))
+                    raise QuotaExceededError(quota.label, projected[i], quota.max_bytes)
+
+            for i in covering:
+                quota = self.quotas[i]
+                charged[i].update(chunk_sizes)
+                above = projected[i] >= quota.warn_at * quota.max_bytes
+                if above and quota.warn_at < 1.0 and not self._warned[i]:
+                    self._emit(QuotaEvent(
+                        quota.label, "warning", projected[i], quota.max_bytes, table_name,
+                    ))
+                self._warned[i] = above
+
+    def _ensure_usage(self) -> List[Set[str]]:
+        if self._charged is None:
+            self._charged = [set() for _ in self.quotas]
+            for table_name in self.catalog.list_tables():
+                covering = [i for i, q in enumerate(self.quotas) if q.covers(table_name)]
+                if not covering:
+                    continue
+                for version in self.catalog.list_versions(table_name):
+                    hashes = self.catalog.get_version(table_name, version).chunk_hashes
+                    for i in covering:
+                        self._charged[i].update(hashes)
+        return self._charged
+
+    def _bytes(self, hashes) -> int:
+        total = 0
+        for h in hashes:
+            size = self._chunk_sizes.get(h)
+            if size is None:
+                size = len(self.store.get(h))
+                self._chunk_sizes[h] = size
+            total += size
+        return total
+
+    def _emit(self, event: QuotaEvent) -> None:
+        _logger.warning(event.describe())
+        self.bus.publish(QUOTA_TOPIC, event)
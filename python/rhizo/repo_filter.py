@@ -0,0 +1,94 @@
+"""
+Repository filtering: copy a subset of a database into a new one.
+
+Produces a new database containing only the chosen tables and branches,
+optionally with truncated history, so a slice of the data can be shared
+externally without handing over everything. Kept versions are renumbered
+from 1 and parent links, branch heads and fork points are rewritten to
+match, so the result opens like any other database.
+
+Example:
+    >>> from rhizo.repo_filter import filter_database
+    >>> report = filter_database("./prod_db", "./share_db",
+    ...                          tables=["orders"], keep_versions=1)
+    >>> report.version_map["orders"]
+    {12: 1}
+"""
+
+from __future__ import annotations
+
+from pathlib import Path
+from typing import List, Optional
+
+from .table_meta import TableMetaStore
+
+
+def filter_database(
+    source: str,
+    dest: str,
+    *,
+    tables: Optional[List[str]] = None,
+    branches: Optional[List[str]] = None,
+    keep_versions: Optional[int] = None,
+):
+    """
+    Copy a subset of the database at ``source`` into ``dest``.
+
+    Only data referenced by the copied versions is written to the
+    destination. Pins and transaction history are not copied.
+
+    Args:
+        source: Path of the source database directory
+        dest: Path of the destination database directory (created if
+              missing; must not already contain any selected table)
+        tables: Table selectors: names, namespace prefixes or wildcard
+                patterns (default: all tables)
+        branches: Branches to keep. Tables on none of them are dropped,
+                  as are versions newer than every kept branch head
+                  (default: all branches)
+        keep_versions: Keep only the last N versions of each table.
+                       Versions referenced by kept branches are always kept.
+
+    Returns:
+        FilterReport with the old -> new version mapping per table and
+        copy statistics
+
+    Raises:
+        FileNotFoundError: If the source database does not exist
+        ValueError: If the destination already contains a selected table
+                    or a non-empty selected branch
+    """
+    from _rhizo import PyBranchManager, PyCatalog, PyChunkStore, filter_repository
+
+    source_path = Path(source).resolve()
+    dest_path = Path(dest).resolve()
+    if not (source_path / "catalog").is_dir():
+        raise FileNotFoundError(f"Database not found: {source}")
+
+    for sub in ("chunks", "catalog", "branches"):
+        (dest_path / sub).mkdir(parents=True, exist_ok=True)
+
+    source_branches = None
+    if (source_path / "branches").is_dir():
+        source_branches = PyBranchManager(str(source_path / "branches"))
+
+    report = filter_repository(
+        PyChunkStore(str(source_path / "chunks")),
+        PyCatalog(str(source_path / "catalog")),
+        PyChunkStore(str(dest_path / "chunks")),
+        PyCatalog(str(dest_path / "catalog")),
+        source_branches=source_branches,
+        dest_branches=PyBranchManager(str(dest_path / "branches")),
+        tables=tables,
+        branches=branches,
+        keep_versions=keep_versions,
+    )
+
+    # Primary keys and schema modes live next to the versions
+    source_meta = TableMetaStore(str(source_path / "catalog"))
+    dest_meta = TableMetaStore(str(dest_path / "catalog"))
+    for table in report.tables:
+        if (source_path / "catalog" / table / TableMetaStore.META_FILENAME).exists():
+            dest_meta.save(table, source_meta.load(table))
+
+    return report
@@ -9,8 +9,8 @@ This enables the unified batch/stream model:
 - Stream: "What changed?" -> engine.subscribe() or engine.get_changes()
 """
 
-from dataclasses import dataclass
-from typing import TYPE_CHECKING, Optional, List, Callable, Iterator
+from dataclasses import dataclass, field
+from typing import TYPE_CHECKING, Optional, List, Callable, Iterator, Dict
 import time
 import threading
 
@@ -38,6 +38,7 @@ class ChangeEvent:
         old_version: Previous version (None if new table)
         new_version: New version after this change
         chunk_hashes: List of chunk hashes for the new version
+        metadata: Transaction metadata (e.g. "author")
     """
     tx_id: int
     committed_at: int
@@ -46,6 +47,7 @@ class ChangeEvent:
     old_version: Optional[int]
     new_version: int
     chunk_hashes: List[str]
+    metadata: Dict[str, str] = field(default_factory=dict)
 
     @classmethod
     def from_changelog_entry(cls, entry: "_rhizo.PyChangelogEntry") -> List["ChangeEvent"]:
@@ -68,6 +70,7 @@ class ChangeEvent:
                 old_version=change.old_version,
                 new_version=change.new_version,
                 chunk_hashes=change.chunk_hashes,
+                metadata=entry.metadata,
             ))
         return events
 
@@ -75,6 +78,11 @@ class ChangeEvent:
         """Check if this change created a new table."""
         return self.old_version is None
 
+    @property
+    def author(self) -> Optional[str]:
+        """Author recorded in the transaction metadata, if any."""
+        return self.metadata.get("author")
+
 
 class Subscriber:
     """
@@ -107,6 +115,11 @@ class Subscriber:
         >>> subscriber.start_background(on_change)  # Non-blocking
         >>> # ... do other work ...
         >>> subscriber.stop()  # Stop background processing
+
+    Example (resumable consumer):
+        >>> subscriber = Subscriber(tx_manager, cursor="search-indexer")
+        >>> for event in subscriber:  # After a restart, resumes where it left off
+        ...     process(event)
     """
 
     def __init__(
@@ -116,6 +129,7 @@ class Subscriber:
         tables: Optional[List[str]] = None,
         branch: Optional[str] = None,
         poll_interval: float = 1.0,
+        cursor: Optional[str] = None,
     ):
         """
         Create a new subscriber.
@@ -127,20 +141,32 @@ class Subscriber:
             tables: Only receive events for these tables. None = all tables
             branch: Only receive events for this branch. None = all branches
             poll_interval: Seconds between polls when waiting for new events (default: 1.0)
+            cursor: Name of a persisted changelog cursor. An existing cursor
+                    resumes after its last acknowledged transaction (ignoring
+                    since_tx_id); a new one is created at the usual start.
+                    Transactions are acknowledged once their events are delivered.
         """
         self._tx_manager = transaction_manager
         self._tables = tables
         self._branch = branch
         self._poll_interval = poll_interval
+        self._cursor: Optional["_rhizo.ChangelogCursor"] = None
 
         # Initialize cursor
-        if since_tx_id is not None:
+        if cursor is not None and any(c.name == cursor for c in self._tx_manager.list_cursors()):
+            self._cursor = self._tx_manager.resume(cursor)
+            self._last_tx_id = self._cursor.acked_tx_id
+        elif since_tx_id is not None:
             self._last_tx_id = since_tx_id
         else:
             # Start from current latest (don't replay history)
             latest = self._tx_manager.latest_tx_id()
             self._last_tx_id = latest if latest is not None else 0
 
+        if cursor is not None and self._cursor is None:
+            self._cursor = self._tx_manager.create_cursor(cursor)
+            self._cursor.ack(self._last_tx_id)
+
         # Background thread state
         self._running = False
         self._thread: Optional[threading.Thread] = None
@@ -162,6 +188,7 @@ class Subscriber:
                 for entry in entries:
                     for event in ChangeEvent.from_changelog_entry(entry):
                         yield event
+                    self._ack(entry.tx_id)
             else:
                 # No new events, wait before next poll
                 time.sleep(self._poll_interval)
@@ -180,6 +207,8 @@ class Subscriber:
         events = []
         for entry in entries:
             events.extend(ChangeEvent.from_changelog_entry(entry))
+        if entries:
+            self._ack(entries[-1].tx_id)
         return events
 
     def subscribe(self, callback: Callable[[ChangeEvent], None]) -> None:
@@ -236,6 +265,11 @@ class Subscriber:
         """Get the last processed transaction ID (cursor position)."""
         return self._last_tx_id
 
+    @property
+    def cursor(self) -> Optional["_rhizo.ChangelogCursor"]:
+        """The persisted changelog cursor, if the subscriber has one."""
+        return self._cursor
+
     @property
     def is_running(self) -> bool:
         """Check if background subscriber is running."""
@@ -258,6 +292,11 @@ class Subscriber:
 
         return entries
 
+    def _ack(self, tx_id: int) -> None:
+        """Persist delivery of everything up to tx_id, if using a cursor."""
+        if self._cursor is not None:
+            self._cursor.ack(tx_id)
+
     def _background_loop(self, callback: Callable[[ChangeEvent], None]) -> None:
         """Background thread main loop."""
         while self._running:
@@ -268,6 +307,7 @@ class Subscriber:
                         if not self._running:
                             return
                         callback(event)
+                    self._ack(entry.tx_id)
 
                 if not entries:
                     time.sleep(self._poll_interval)
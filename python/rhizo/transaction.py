@@ -27,16 +27,31 @@ Example:
     ...
     ...     # Auto-commits on exit
     ... # Rolls back if exception raised
+
+Transactions that lose a write or snapshot conflict can be retried on a
+fresh snapshot with ``engine.retrying()``:
+
+    >>> for attempt in engine.retrying(max_attempts=5):
+    ...     with attempt as tx:
+    ...         users = tx.query("SELECT * FROM users").to_pandas()
+    ...         tx.write_table("users", bump(users))
 """
 
 from __future__ import annotations
 
+import random
+import time
 from dataclasses import dataclass, field
-from typing import TYPE_CHECKING, Optional, Dict, List, Any, Union
+from typing import TYPE_CHECKING, Optional, Dict, List, Any, Iterator, Union
 
 import pyarrow as pa
 
-from rhizo.exceptions import validate_table_name
+from rhizo.exceptions import (
+    RetriesExhaustedError,
+    SnapshotConflictError,
+    WriteConflictError,
+    validate_table_name,
+)
 
 if TYPE_CHECKING:
     import pandas as pd
@@ -74,7 +89,6 @@ class TransactionContext:
     Future Extensions:
         - Row-level conflict detection (Phase 5.5)
         - Distributed transactions (Phase 5.x)
-        - Savepoints for partial rollback
     """
 
     def __init__(
@@ -101,6 +115,8 @@ class TransactionContext:
         self._aborted: bool = False
         self._buffered_writes: Dict[str, BufferedWrite] = {}
         self._temp_tables: List[str] = []  # Track temp tables for cleanup
+        # Savepoint name -> buffered writes at that point (oldest first)
+        self._savepoints: Dict[str, Dict[str, BufferedWrite]] = {}
 
     @property
     def tx_id(self) -> int:
@@ -234,6 +250,104 @@ class TransactionContext:
             metadata=metadata,
         )
 
+    def set_metadata(self, key: str, value: str) -> None:
+        """
+        Attach a metadata entry to this transaction.
+
+        Metadata is recorded in the transaction's changelog entry; the
+        "author" key is what changelog exports report as the author.
+
+        Args:
+            key: Metadata key
+            value: Metadata value
+
+        Raises:
+            RuntimeError: If transaction is no longer active
+        """
+        self._check_active("set metadata")
+        tx_manager = self._engine.transaction_manager
+        assert tx_manager is not None, "TransactionContext requires transaction_manager"
+
+        tx_manager.set_metadata(self._tx_id, key, value)
+
+    def savepoint(self, name: str) -> None:
+        """
+        Mark a point this transaction can roll back to.
+
+        Taking a savepoint with an existing name replaces it.
+
+        Args:
+            name: Savepoint name
+
+        Raises:
+            RuntimeError: If transaction is no longer active
+        """
+        self._check_active("savepoint")
+        tx_manager = self._engine.transaction_manager
+        assert tx_manager is not None, "TransactionContext requires transaction_manager"
+
+        tx_manager.savepoint(self._tx_id, name)
+        self._savepoints.pop(name, None)
+        self._savepoints[name] = dict(self._buffered_writes)
+
+    def rollback_to_savepoint(self, name: str) -> None:
+        """
+        Discard writes buffered since a savepoint.
+
+        Savepoints taken after it are discarded; the savepoint itself is
+        kept, so it can be rolled back to again.
+
+        Args:
+            name: Savepoint name
+
+        Raises:
+            RuntimeError: If transaction is no longer active
+            ValueError: If the savepoint does not exist
+        """
+        self._check_active("rollback to savepoint")
+        if name not in self._savepoints:
+            raise ValueError(f"Savepoint not found in transaction {self._tx_id}: {name}")
+        tx_manager = self._engine.transaction_manager
+        assert tx_manager is not None, "TransactionContext requires transaction_manager"
+
+        tx_manager.rollback_to_savepoint(self._tx_id, name)
+
+        names = list(self._savepoints)
+        for later in names[names.index(name) + 1:]:
+            del self._savepoints[later]
+        restored = dict(self._savepoints[name])
+
+        # Tables written only after the savepoint must stop shadowing
+        # the committed data in read-your-writes queries
+        for table_name in set(self._buffered_writes) - set(restored):
+            if table_name in self._temp_tables:
+                self._temp_tables.remove(table_name)
+            self._engine._invalidate_cache(table_name)
+        self._buffered_writes = restored
+
+    def release_savepoint(self, name: str) -> None:
+        """
+        Forget a savepoint (and any taken after it), keeping buffered writes.
+
+        Args:
+            name: Savepoint name
+
+        Raises:
+            RuntimeError: If transaction is no longer active
+            ValueError: If the savepoint does not exist
+        """
+        self._check_active("release savepoint")
+        if name not in self._savepoints:
+            raise ValueError(f"Savepoint not found in transaction {self._tx_id}: {name}")
+        tx_manager = self._engine.transaction_manager
+        assert tx_manager is not None, "TransactionContext requires transaction_manager"
+
+        tx_manager.release_savepoint(self._tx_id, name)
+
+        names = list(self._savepoints)
+        for released in names[names.index(name):]:
+            del self._savepoints[released]
+
     def commit(self) -> None:
         """
         Commit the transaction.
@@ -368,6 +482,7 @@ class TransactionContext:
 
         self._temp_tables.clear()
         self._buffered_writes.clear()
+        self._savepoints.clear()
 
     def __enter__(self) -> "TransactionContext":
         """
@@ -399,3 +514,101 @@ class TransactionContext:
             # Defensive cleanup: ensure temp tables are always cleaned up
             # even if commit/abort failed in an unexpected way
             self._cleanup_temp_tables()
+
+
+def is_conflict(error: BaseException) -> bool:
+    """Whether an error is a write or snapshot conflict."""
+    return isinstance(error, (WriteConflictError, SnapshotConflictError))
+
+
+class RetryingTransaction:
+    """
+    Attempts of an optimistic transaction, retried on conflicts.
+
+    Iterating yields one TransactionAttempt per attempt; each is used as a
+    context manager around the transaction body. When the body or the
+    commit raises a write or snapshot conflict, the transaction is aborted,
+    the conflict is suppressed and, after a jittered exponential backoff,
+    the loop runs the body again in a new transaction. Iteration stops
+    after the first successful commit.
+
+    The body must rebuild its writes from the new snapshot on every
+    attempt. Errors other than conflicts abort the transaction and
+    propagate without a retry.
+
+    Raises:
+        RetriesExhaustedError: When the last allowed attempt conflicts
+
+    This class should not be instantiated directly. Use
+    `QueryEngine.retrying()`.
+    """
+
+    def __init__(
+        self,
+        engine: "QueryEngine",
+        branch: Optional[str] = None,
+        author: Optional[str] = None,
+        max_attempts: int = 5,
+        base_delay: float = 0.01,
+        max_delay: float = 1.0,
+    ):
+        if max_attempts < 1:
+            raise ValueError(f"max_attempts must be at least 1, got {max_attempts}")
+        self._engine = engine
+        self._branch = branch
+        self._author = author
+        self.max_attempts = max_attempts
+        self.base_delay = base_delay
+        self.max_delay = max_delay
+        self.attempts = 0
+        self._done = False
+
+    def delay(self, attempt: int) -> float:
+        """Seconds to wait after attempt `attempt` (1-based) conflicted."""
+        delay = min(self.max_delay, self.base_delay * 2 ** (attempt - 1))
+        return random.uniform(delay / 2, delay)
+
+    def __iter__(self) -> Iterator["TransactionAttempt"]:
+        while not self._done and self.attempts < self.max_attempts:
+            self.attempts += 1
+            yield TransactionAttempt(self, self.attempts)
+
+    def _finish(self, attempt: int, error: Optional[BaseException]) -> bool:
+        """Handle the outcome of an attempt; True to suppress `error`."""
+        if error is None or not is_conflict(error):
+            self._done = True
+            return False
+        if attempt >= self.max_attempts:
+            self._done = True
+            raise RetriesExhaustedError(attempt) from error
+        time.sleep(self.delay(attempt))
+        return True
+
+
+class TransactionAttempt:
+    """
+    One attempt of a RetryingTransaction.
+
+    Entering it begins a transaction and returns its TransactionContext;
+    leaving it commits or aborts like `QueryEngine.transaction()`.
+    """
+
+    def __init__(self, retrying: RetryingTransaction, number: int):
+        self._retrying = retrying
+        self.number = number
+        self._cm: Any = None
+
+    def __enter__(self) -> TransactionContext:
+        r = self._retrying
+        self._cm = r._engine.transaction(branch=r._branch, author=r._author)
+        return self._cm.__enter__()
+
+    def __exit__(self, exc_type, exc_val, exc_tb) -> bool:
+        try:
+            self._cm.__exit__(exc_type, exc_val, exc_tb)
+        except Exception as e:
+            # The commit failed (the transaction is already aborted)
+            if self._retrying._finish(self.number, e):
+                return True
+            raise
+        return self._retrying._finish(self.number, exc_val)
@@ -33,6 +33,7 @@ from rhizo.schema_utils import (
 
 if TYPE_CHECKING:
     import pandas as pd
+    from rhizo.quota import QuotaEnforcer
 
 # Try to import native Parquet encoder (Phase 4)
 try:
@@ -109,6 +110,8 @@ class TableWriter:
         max_table_size_bytes: int = DEFAULT_MAX_TABLE_SIZE_BYTES,
         max_columns: int = DEFAULT_MAX_COLUMNS,
         catalog_path: Optional[str] = None,
+        quota_enforcer: Optional["QuotaEnforcer"] = None,
+        delta_chunks: bool = False,
     ):
         """
         Initialize the TableWriter.
@@ -126,6 +129,11 @@ class TableWriter:
                         Prevents schema explosion attacks.
             catalog_path: Path to catalog directory for table metadata.
                          Enables schema evolution and primary key enforcement.
+            quota_enforcer: Optional QuotaEnforcer checked before each commit.
+            delta_chunks: Store each chunk as a binary delta against the chunk
+                         at the same position in the table's latest version,
+                         when that is smaller. Saves space for slowly changing
+                         tables; reads are unaffected.
         """
         self.store = store
         self.catalog = catalog
@@ -135,6 +143,8 @@ class TableWriter:
         self.max_table_size_bytes = max_table_size_bytes
         self.max_columns = max_columns
         self._meta_store = TableMetaStore(catalog_path) if catalog_path else None
+        self.quota_enforcer = quota_enforcer
+        self.delta_chunks = delta_chunks
 
         # Initialize native encoder if available and requested
         self._native_encoder = None
@@ -169,6 +179,7 @@ class TableWriter:
             ValueError: If data is empty, invalid, or exceeds size limits
             SchemaEvolutionError: If schema change violates evolution policy
             PrimaryKeyViolationError: If data contains duplicate key values
+            QuotaExceededError: If the write would exceed a storage quota
         """
         table_name = validate_table_name(table_name)
 
@@ -214,12 +225,13 @@ class TableWriter:
             parquet_chunks = [self._to_parquet_bytes(chunk) for chunk in chunks]
         total_bytes = sum(len(p) for p in parquet_chunks)
 
-        # Store chunks: use zero-copy put() for single chunks,
-        # put_batch() for multiple chunks (parallelizes via Rayon)
-        if len(parquet_chunks) == 1:
-            chunk_hashes = [self.store.put(parquet_chunks[0])]
-        else:
-            chunk_hashes = self.store.put_batch(parquet_chunks)
+        chunk_hashes = self._store_chunks(table_name, parquet_chunks)
+
+        # Quota check before the version becomes visible
+        if self.quota_enforcer is not None:
+            self.quota_enforcer.charge(
+                table_name, {h: len(p) for h, p in zip(chunk_hashes, parquet_chunks)}
+            )
 
         # Commit with metadata (schema info attached to version)
         committed_version = self.catalog.commit_next_with_meta(
@@ -239,6 +251,69 @@ class TableWriter:
             total_bytes=total_bytes,
         )
 
+    def create_table(
+        self,
+        table_name: str,
+        schema: pa.Schema,
+        metadata: Optional[Dict[str, str]] = None,
+        *,
+        primary_key: Optional[List[str]] = None,
+        schema_mode: Optional[str] = None,
+    ) -> WriteResult:
+        """
+        Create a table with a schema but no rows.
+
+        Version 1 holds a single empty chunk carrying the schema, so reads
+        return an empty table with the right columns and later writes are
+        checked against the schema like any other version.
+
+        Args:
+            table_name: Name of the table to create
+            schema: Arrow schema of the table
+            metadata: Optional key-value metadata for version 1
+            primary_key: Columns that form the primary key (set once, immutable)
+            schema_mode: Schema evolution mode ("additive" or "flexible")
+
+        Returns:
+            WriteResult for version 1 (zero rows)
+
+        Raises:
+            ValueError: If the table already exists or the schema is invalid
+        """
+        table_name = validate_table_name(table_name)
+        if self._get_next_version(table_name) != 1:
+            raise ValueError(f"Table already exists: {table_name}")
+        if len(schema) > self.max_columns:
+            raise ValueError(
+                f"Column count ({len(schema)}) exceeds maximum "
+                f"({self.max_columns}). Reduce columns or increase max_columns."
+            )
+
+        table = schema.empty_table()
+        table_meta = self._validate_schema_and_pk(
+            table_name, table, primary_key, schema_mode
+        )
+        version_metadata = dict(metadata) if metadata else {}
+        version_metadata[SCHEMA_METADATA_KEY] = serialize_schema(schema)
+
+        chunk = self._to_parquet_bytes(table)
+        chunk_hashes = [self.store.put(chunk)]
+        committed_version = self.catalog.commit_next_with_meta(
+            table_name, chunk_hashes, version_metadata
+        )
+
+        if table_meta and self._meta_store:
+            self._meta_store.save(table_name, table_meta)
+
+        return WriteResult(
+            table_name=table_name,
+            version=committed_version,
+            chunk_count=1,
+            chunk_hashes=chunk_hashes,
+            total_rows=0,
+            total_bytes=len(chunk),
+        )
+
     def write_chunks_only(
         self,
         table_name: str,
@@ -267,6 +342,7 @@ class TableWriter:
             ValueError: If data is empty, invalid, or exceeds size limits
             SchemaEvolutionError: If schema change violates evolution policy
             PrimaryKeyViolationError: If data contains duplicate key values
+            QuotaExceededError: If the write would exceed a storage quota
         """
         table_name = validate_table_name(table_name)
 
@@ -306,12 +382,13 @@ class TableWriter:
             parquet_chunks = [self._to_parquet_bytes(chunk) for chunk in chunks]
         total_bytes = sum(len(p) for p in parquet_chunks)
 
-        # Store chunks: use zero-copy put() for single chunks,
-        # put_batch() for multiple chunks (parallelizes via Rayon)
-        if len(parquet_chunks) == 1:
-            chunk_hashes = [self.store.put(parquet_chunks[0])]
-        else:
-            chunk_hashes = self.store.put_batch(parquet_chunks)
+        chunk_hashes = self._store_chunks(table_name, parquet_chunks)
+
+        # Quota check before the version becomes visible
+        if self.quota_enforcer is not None:
+            self.quota_enforcer.charge(
+                table_name, {h: len(p) for h, p in zip(chunk_hashes, parquet_chunks)}
+            )
 
         # Determine what the next version WILL be (don't commit yet)
         next_version = self._get_next_version(table_name)
@@ -520,6 +597,28 @@ class TableWriter:
                 table_name, pk_columns, dup_groups
             )
 
+    def _store_chunks(self, table_name: str, parquet_chunks: List[bytes]) -> List[str]:
+        """Store serialized chunks, as deltas if enabled."""
+        if self.delta_chunks:
+            bases = self._latest_chunk_hashes(table_name)
+            if bases:
+                return [
+                    self.store.put_delta(chunk, bases[i]) if i < len(bases) else self.store.put(chunk)
+                    for i, chunk in enumerate(parquet_chunks)
+                ]
+
+        # Store chunks: use zero-copy put() for single chunks,
+        # put_batch() for multiple chunks (parallelizes via Rayon)
+        if len(parquet_chunks) == 1:
+            return [self.store.put(parquet_chunks[0])]
+        return self.store.put_batch(parquet_chunks)
+
+    def _latest_chunk_hashes(self, table_name: str) -> List[str]:
+        """Chunk hashes of the table's latest version (empty if none)."""
+        if self._get_next_version(table_name) == 1:
+            return []
+        return list(self.catalog.get_version(table_name).chunk_hashes)
+
     def _get_next_version(self, table_name: str) -> int:
         """Get the next version number for a table."""
         from .exceptions import TableNotFoundError
@@ -0,0 +1,191 @@
+//! Command-line arguments.
+
+use std::path::PathBuf;
+
+use clap::{Args, Parser, Subcommand};
+
+/// Inspect and administer a Rhizo repository
+#[derive(Debug, Parser)]
+#[command(name = "rhizo", version)]
+pub struct Cli {
+    /// Repository directory
+    #[arg(long, short = 'C', env = "RHIZO_REPO", default_value = ".", global = true)]
+    pub repo: PathBuf,
+
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// List, show, create or delete branches
+    #[command(subcommand)]
+    Branch(BranchCommand),
+
+    /// Version history of a table, newest first
+    Log(LogArgs),
+
+    /// Committed transactions, oldest first
+    Changelog(ChangelogArgs),
+
+    /// Commit stored chunks or files as a new table version
+    Commit(CommitArgs),
+
+    /// Import a CSV or JSON Lines file as a new table version
+    Import(ImportArgs),
+
+    /// Scan the transaction log after a crash
+    Recover(RecoverArgs),
+
+    /// Check transaction log, index and branch heads for consistency
+    Verify,
+
+    /// Delete unreferenced chunks, optionally pruning expired versions first
+    Gc(GcArgs),
+}
+
+#[derive(Debug, Subcommand)]
+pub enum BranchCommand {
+    /// List branches; the default branch is marked with `*`
+    List,
+
+    /// Show a branch and its table heads
+    Show { name: String },
+
+    /// Create a branch (from the default branch unless `--from` is given)
+    Create {
+        name: String,
+        /// Branch or other ref to start from (`main~2`, `tag:release-1`, ...)
+        #[arg(long)]
+        from: Option<String>,
+        #[arg(long, short)]
+        description: Option<String>,
+    },
+
+    /// Delete a branch
+    Delete { name: String },
+}
+
+#[derive(Debug, Args)]
+pub struct LogArgs {
+    pub table: String,
+
+    /// Start from this branch's head instead of the latest version
+    #[arg(long, short)]
+    pub branch: Option<String>,
+
+    /// Start from the version at this ref (`main~2`, `tag:release-1`,
+    /// `tx:42`, ...)
+    #[arg(long, conflicts_with = "branch")]
+    pub at: Option<String>,
+
+    /// Show at most this many versions
+    #[arg(long, short = 'n')]
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Args)]
+pub struct ChangelogArgs {
+    /// Only transactions after this one
+    #[arg(long)]
+    pub since_tx: Option<u64>,
+
+    /// Only transactions up to and including this one
+    #[arg(long)]
+    pub until_tx: Option<u64>,
+
+    #[arg(long, short)]
+    pub branch: Option<String>,
+
+    /// Only transactions touching this table (repeatable)
+    #[arg(long = "table", short = 't')]
+    pub tables: Vec<String>,
+
+    #[arg(long, short = 'n')]
+    pub limit: Option<usize>,
+
+    /// Print Debezium change events (one JSON line each) under this
+    /// logical server name instead of the summary
+    #[arg(long, value_name = "SERVER")]
+    pub debezium: Option<String>,
+}
+
+#[derive(Debug, Args)]
+pub struct CommitArgs {
+    pub table: String,
+
+    /// Hash of a chunk already in the store (repeatable)
+    #[arg(long = "chunk")]
+    pub chunks: Vec<String>,
+
+    /// File to store as a chunk (repeatable; after any `--chunk`)
+    #[arg(long = "file")]
+    pub files: Vec<PathBuf>,
+
+    /// Branch to commit on (the default branch if omitted)
+    #[arg(long, short)]
+    pub branch: Option<String>,
+
+    /// Recorded as the transaction's `message` metadata
+    #[arg(long, short)]
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Args)]
+pub struct ImportArgs {
+    pub file: PathBuf,
+
+    pub table: String,
+
+    /// `csv` or `jsonl` (from the file extension if omitted)
+    #[arg(long)]
+    pub format: Option<String>,
+
+    /// CSV field delimiter
+    #[arg(long, default_value_t = ',')]
+    pub delimiter: char,
+
+    /// The CSV file has no header line (columns are named column_1, ...)
+    #[arg(long)]
+    pub no_header: bool,
+
+    /// Records read to infer column types (all of them if 0)
+    #[arg(long, default_value_t = rhizo_core::table::DEFAULT_INFER_ROWS)]
+    pub infer_rows: usize,
+
+    /// Branch to commit on (the default branch if omitted)
+    #[arg(long, short)]
+    pub branch: Option<String>,
+
+    /// Recorded as the transaction's `message` metadata
+    #[arg(long, short)]
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Args)]
+pub struct RecoverArgs {
+    /// Abort transactions left pending and clear stale commit intents
+    /// (otherwise only report)
+    #[arg(long)]
+    pub apply: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct GcArgs {
+    /// Report what would be deleted without deleting it
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Keep unreferenced chunks younger than this many seconds
+    #[arg(long)]
+    pub grace_secs: Option<u64>,
+
+    /// Prune versions expired by retention policies before collecting
+    #[arg(long)]
+    pub prune: bool,
+
+    /// Also delete the unused old versions that free at least this many
+    /// bytes, largest first
+    #[arg(long, value_name = "BYTES")]
+    pub free: Option<u64>,
+}
@@ -0,0 +1,546 @@
+//! Subcommand implementations.
+//!
+//! Each command writes its report to `out` and returns whether the
+//! repository was found in good order (`false` makes `rhizo` exit 1).
+
+use std::error::Error;
+use std::fs;
+use std::io::Write;
+use std::time::Duration;
+
+use rhizo_core::gc::DEFAULT_GRACE_PERIOD;
+use rhizo_core::{
+    CatalogError, CdcExporter, ChangelogQuery, ChunkGc, GcPlanner, ImportOptions, JsonLinesSink, RefError, RefSpec, Repo,
+    TableChange, TableVersion, TableWrite, TableWriter, VersionPruner, WriteKind,
+};
+
+use crate::cli::{BranchCommand, ChangelogArgs, Cli, Command, CommitArgs, GcArgs, ImportArgs, LogArgs, RecoverArgs};
+
+pub type CommandResult = Result<bool, Box<dyn Error>>;
+
+/// Run a parsed command line against its repository.
+pub fn run(cli: &Cli, out: &mut dyn Write) -> CommandResult {
+    let repo = Repo::open(&cli.repo)?;
+    match &cli.command {
+        Command::Branch(command) => branch(&repo, command, out),
+        Command::Log(args) => log(&repo, args, out),
+        Command::Changelog(args) => changelog(&repo, args, out),
+        Command::Commit(args) => commit(&repo, args, out),
+        Command::Import(args) => import(&repo, args, out),
+        Command::Recover(args) => recover(&repo, args, out),
+        Command::Verify => verify(&repo, out),
+        Command::Gc(args) => gc(&repo, args, out),
+    }
+}
+
+fn branch(repo: &Repo, command: &BranchCommand, out: &mut dyn Write) -> CommandResult {
+    let branches = repo.branches();
+    match command {
+        BranchCommand::List => {
+            let default = branches.get_default()?;
+            for name in branches.list()? {
+                let marker = if default.as_deref() == Some(name.as_str()) { '*' } else { ' ' };
+                writeln!(out, "{} {}", marker, name)?;
+            }
+        }
+        BranchCommand::Show { name } => {
+            let branch = branches.get(name)?;
+            writeln!(out, "branch {}", branch.name)?;
+            writeln!(out, "created_at {}", branch.created_at)?;
+            if let Some(parent) = &branch.parent_branch {
+                writeln!(out, "parent {}", parent)?;
+            }
+            if let Some(description) = &branch.description {
+                writeln!(out, "description {}", description)?;
+            }
+            if branch.immutable {
+                writeln!(out, "immutable")?;
+            }
+            let mut heads: Vec<_> = branch.head.iter().collect();
+            heads.sort();
+            for (table, version) in heads {
+                writeln!(out, "  {} v{}", table, version)?;
+            }
+        }
+        BranchCommand::Create { name, from, description } => {
+            let branch = match from.as_deref() {
+                Some(from) if !matches!(RefSpec::parse(from)?, RefSpec::Branch { ancestor: 0, .. }) => {
+                    branches.create_at(name, &repo.resolve_ref(from)?, description.as_deref())?
+                }
+                from => branches.create(name, from, description.as_deref())?,
+            };
+            writeln!(out, "created branch {} ({} tables)", branch.name, branch.head.len())?;
+        }
+        BranchCommand::Delete { name } => {
+            branches.delete(name)?;
+            writeln!(out, "deleted branch {}", name)?;
+        }
+    }
+    Ok(true)
+}
+
+fn write_version(version: &TableVersion, out: &mut dyn Write) -> std::io::Result<()> {
+    writeln!(
+        out,
+        "v{}  created_at {}  {} chunk(s)",
+        version.version,
+        version.created_at,
+        version.chunk_hashes.len()
+    )?;
+    let mut metadata: Vec<_> = version.metadata.iter().collect();
+    metadata.sort();
+    for (key, value) in metadata {
+        writeln!(out, "    {}: {}", key, value)?;
+    }
+    Ok(())
+}
+
+/// Walk a table's parent chain from its latest version, a branch head or a ref
+fn log(repo: &Repo, args: &LogArgs, out: &mut dyn Write) -> CommandResult {
+    let catalog = repo.catalog();
+    let mut next = Some(match (&args.at, &args.branch) {
+        (Some(at), _) => {
+            let version = repo.resolve_ref(at)?.get(&args.table).ok_or_else(|| RefError::TableNotInRef {
+                reference: at.clone(),
+                table: args.table.clone(),
+            })?;
+            catalog.get_version(&args.table, Some(version))?
+        }
+        (None, Some(name)) => catalog.get_version_on_branch(&args.table, None, &repo.branches().get(name)?)?,
+        (None, None) => catalog.get_version(&args.table, None)?,
+    });
+    let mut shown = 0;
+    while let Some(version) = next.take() {
+        if args.limit.is_some_and(|limit| shown >= limit) {
+            break;
+        }
+        write_version(&version, out)?;
+        shown += 1;
+        if let Some(parent) = version.parent_version {
+            match catalog.get_version(&args.table, Some(parent)) {
+                Ok(parent) => next = Some(parent),
+                Err(CatalogError::VersionNotFound(..)) => writeln!(out, "v{}  (pruned)", parent)?,
+                Err(e) => return Err(e.into()),
+            }
+        }
+    }
+    Ok(true)
+}
+
+fn write_change(change: &TableChange, out: &mut dyn Write) -> std::io::Result<()> {
+    let branch = change.branch.as_ref().map(|b| format!("  [{}]", b)).unwrap_or_default();
+    match &change.kind {
+        WriteKind::Data => match change.old_version {
+            Some(old) => writeln!(
+                out,
+                "  {}  v{} -> v{}  {} chunk(s){}",
+                change.table_name,
+                old,
+                change.new_version,
+                change.chunk_hashes.len(),
+                branch
+            ),
+            None => writeln!(
+                out,
+                "  {}  new v{}  {} chunk(s){}",
+                change.table_name,
+                change.new_version,
+                change.chunk_hashes.len(),
+                branch
+            ),
+        },
+        WriteKind::Drop => writeln!(out, "  {}  dropped at v{}{}", change.table_name, change.new_version, branch),
+        WriteKind::Rename { to } => writeln!(out, "  {}  renamed to {}{}", change.table_name, to, branch),
+        WriteKind::Merge { from } => writeln!(
+            out,
+            "  {}  merged v{} from {}{}",
+            change.table_name, change.new_version, from, branch
+        ),
+    }
+}
+
+fn changelog(repo: &Repo, args: &ChangelogArgs, out: &mut dyn Write) -> CommandResult {
+    let query = ChangelogQuery {
+        since_tx_id: args.since_tx,
+        until_tx_id: args.until_tx,
+        since_timestamp: None,
+        tables: (!args.tables.is_empty()).then(|| args.tables.clone()),
+        branch: args.branch.clone(),
+        limit: args.limit,
+    };
+    let entries = repo.transaction_manager()?.get_changelog(query)?;
+    if let Some(server) = &args.debezium {
+        CdcExporter::new(server).export(&entries, &mut JsonLinesSink::new(out))?;
+        return Ok(true);
+    }
+    for entry in entries {
+        writeln!(
+            out,
+            "tx {}  epoch {}  {}  committed_at {}",
+            entry.tx_id, entry.epoch_id, entry.branch, entry.committed_at
+        )?;
+        if let Some(message) = entry.metadata.get("message") {
+            writeln!(out, "    {}", message)?;
+        }
+        for change in &entry.changes {
+            write_change(change, out)?;
+        }
+        if !entry.auto_merged.is_empty() {
+            writeln!(out, "  auto-merged: {}", entry.auto_merged.join(", "))?;
+        }
+        if let Some(merge) = &entry.merge {
+            writeln!(out, "  merge of {} ({})", merge.source, merge.strategy)?;
+        }
+    }
+    Ok(true)
+}
+
+fn commit(repo: &Repo, args: &CommitArgs, out: &mut dyn Write) -> CommandResult {
+    let store = repo.store();
+    let mut hashes = Vec::with_capacity(args.chunks.len() + args.files.len());
+    for hash in &args.chunks {
+        if !store.exists(hash)? {
+            return Err(format!("chunk {} is not in the store", hash).into());
+        }
+        hashes.push(hash.clone());
+    }
+    for path in &args.files {
+        hashes.push(store.put(&fs::read(path)?)?);
+    }
+    if hashes.is_empty() {
+        return Err("nothing to commit: give --chunk or --file".into());
+    }
+
+    let version = match repo.catalog().get_version(&args.table, None) {
+        Ok(latest) => latest.version + 1,
+        Err(CatalogError::TableNotFound(_)) => 1,
+        Err(e) => return Err(e.into()),
+    };
+    let chunks = hashes.len();
+    let transactions = repo.transaction_manager()?;
+    let tx_id = transactions.begin(args.branch.as_deref())?;
+    let staged = transactions
+        .add_write(tx_id, TableWrite::new(&args.table, version, hashes))
+        .and_then(|()| match &args.message {
+            Some(message) => transactions.set_metadata(tx_id, "message", message),
+            None => Ok(()),
+        })
+        .and_then(|()| transactions.commit(tx_id));
+    if let Err(e) = staged {
+        // Leave no pending record behind; a failed commit may already be aborted
+        let _ = transactions.abort(tx_id, &e.to_string());
+        return Err(e.into());
+    }
+    writeln!(out, "committed tx {}: {} v{} ({} chunk(s))", tx_id, args.table, version, chunks)?;
+    Ok(true)
+}
+
+fn import(repo: &Repo, args: &ImportArgs, out: &mut dyn Write) -> CommandResult {
+    let format = match &args.format {
+        Some(format) => format.to_ascii_lowercase(),
+        None => args
+            .file
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(str::to_ascii_lowercase)
+            .ok_or("cannot tell the file format: give --format csv or --format jsonl")?,
+    };
+    let jsonl = match format.as_str() {
+        "csv" => false,
+        "jsonl" | "ndjson" => true,
+        other => return Err(format!("unknown import format '{}' (expected csv or jsonl)", other).into()),
+    };
+    if !args.delimiter.is_ascii() {
+        return Err(format!("delimiter must be an ASCII character, got {:?}", args.delimiter).into());
+    }
+    let options = ImportOptions::new()
+        .with_header(!args.no_header)
+        .with_delimiter(args.delimiter as u8)
+        .with_infer_rows((args.infer_rows > 0).then_some(args.infer_rows));
+
+    let transactions = repo.transaction_manager()?;
+    let writer = TableWriter::new(repo.store(), repo.catalog());
+    let tx_id = transactions.begin(args.branch.as_deref())?;
+    let tx = Some((&transactions, tx_id));
+    let imported = if jsonl {
+        writer.import_jsonl(&args.file, &args.table, &options, tx)
+    } else {
+        writer.import_csv(&args.file, &args.table, &options, tx)
+    };
+    let committed = imported.map_err(Box::<dyn Error>::from).and_then(|version| {
+        if let Some(message) = &args.message {
+            transactions.set_metadata(tx_id, "message", message)?;
+        }
+        transactions.commit(tx_id)?;
+        Ok(version)
+    });
+    let version = match committed {
+        Ok(version) => version,
+        Err(e) => {
+            let _ = transactions.abort(tx_id, &e.to_string());
+            return Err(e);
+        }
+    };
+    writeln!(
+        out,
+        "imported tx {}: {} v{} ({} chunk(s))",
+        tx_id,
+        args.table,
+        version.version,
+        version.chunk_hashes.len()
+    )?;
+    Ok(true)
+}
+
+fn write_ids(out: &mut dyn Write, label: &str, ids: &[u64]) -> std::io::Result<()> {
+    if ids.is_empty() {
+        return Ok(());
+    }
+    let ids: Vec<String> = ids.iter().map(u64::to_string).collect();
+    writeln!(out, "{}: {}", label, ids.join(", "))
+}
+
+fn recover(repo: &Repo, args: &RecoverArgs, out: &mut dyn Write) -> CommandResult {
+    let transactions = repo.transaction_manager()?;
+    let report = if args.apply {
+        transactions.recover_and_apply()?
+    } else {
+        transactions.recover()?
+    };
+    writeln!(
+        out,
+        "scanned {} epoch(s), {} transaction(s)",
+        report.epochs_scanned, report.transactions_scanned
+    )?;
+    write_ids(out, "replayed", &report.replayed)?;
+    let pending = if args.apply { "rolled back" } else { "pending (run with --apply to roll back)" };
+    write_ids(out, pending, &report.rolled_back)?;
+    for warning in &report.warnings {
+        writeln!(out, "warning: {}", warning)?;
+    }
+    for error in &report.errors {
+        writeln!(out, "error: {}", error)?;
+    }
+
+    if args.apply {
+        for intent in repo.catalog().recover_pending_commits()? {
+            writeln!(
+                out,
+                "incomplete commit {} on {}: {} orphaned chunk(s)",
+                intent.intent_id,
+                intent.table_name,
+                intent.chunk_hashes.len()
+            )?;
+        }
+    }
+    Ok(report.errors.is_empty())
+}
+
+fn verify(repo: &Repo, out: &mut dyn Write) -> CommandResult {
+    let transactions = repo.transaction_manager()?;
+    let mut issues = transactions.verify_consistency()?;
+    issues.extend(transactions.log().verify_transaction_index()?);
+    let branches = repo.branches();
+    for name in branches.list()? {
+        for head in branches.dangling_heads(&name, repo.catalog())? {
+            issues.push(format!(
+                "branch {} head for {} points at missing v{}",
+                name, head.table, head.version
+            ));
+        }
+    }
+
+    for issue in &issues {
+        writeln!(out, "{}", issue)?;
+    }
+    if issues.is_empty() {
+        writeln!(out, "ok")?;
+    } else {
+        writeln!(out, "{} issue(s) found", issues.len())?;
+    }
+    Ok(issues.is_empty())
+}
+
+fn gc(repo: &Repo, args: &GcArgs, out: &mut dyn Write) -> CommandResult {
+    let transactions = repo.transaction_manager()?;
+    if args.prune {
+        let report = VersionPruner::new(repo.catalog())
+            .with_branches(repo.branches())
+            .with_transactions(&transactions)
+            .dry_run(args.dry_run)
+            .run()?;
+        let verb = if args.dry_run { "would prune" } else { "pruned" };
+        writeln!(out, "{} {} version(s)", verb, report.total_pruned())?;
+        for (table, versions) in &report.versions_pruned {
+            let versions: Vec<String> = versions.iter().map(|v| format!("v{}", v)).collect();
+            writeln!(out, "  {}: {}", table, versions.join(", "))?;
+        }
+    }
+
+    let grace_period = args.grace_secs.map_or(DEFAULT_GRACE_PERIOD, Duration::from_secs);
+    let mut target_met = true;
+    let mut executed = None;
+    if let Some(target) = args.free {
+        let planner = GcPlanner::new(repo.catalog(), repo.store())
+            .with_branches(repo.branches())
+            .with_transactions(&transactions)
+            .with_grace_period(grace_period);
+        let plan = planner.plan(target)?;
+        target_met = plan.meets_target();
+        let verb = if args.dry_run { "would free" } else { "freeing" };
+        let unreachable = if target_met { "" } else { " (target not reachable)" };
+        writeln!(out, "{} {} of {} bytes{}", verb, plan.reclaimable_bytes(), target, unreachable)?;
+        for step in &plan.steps {
+            writeln!(out, "  {} v{}: {} bytes", step.table_name, step.version, step.bytes)?;
+        }
+        if !args.dry_run {
+            executed = Some(planner.execute(&plan)?);
+        }
+    }
+    let report = match executed {
+        Some(report) => report,
+        None => ChunkGc::new(repo.catalog(), repo.store())
+            .with_transactions(&transactions)
+            .with_grace_period(grace_period)
+            .dry_run(args.dry_run)
+            .run()?,
+    };
+    writeln!(
+        out,
+        "scanned {} chunk(s): {} referenced, {} in flight, {} within grace period",
+        report.chunks_scanned, report.chunks_referenced, report.chunks_in_flight, report.chunks_within_grace
+    )?;
+    if args.dry_run {
+        writeln!(
+            out,
+            "would delete {} chunk(s), {} bytes",
+            report.collectable.len(),
+            report.bytes_collectable
+        )?;
+    } else {
+        writeln!(
+            out,
+            "deleted {} chunk(s), {} bytes",
+            report.chunks_deleted, report.bytes_collectable
+        )?;
+    }
+    Ok(report.chunks_failed == 0 && target_met)
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+
+    use super::*;
+
+    fn rhizo(repo: &Repo, args: &[&str]) -> (bool, String) {
+        let mut argv = vec!["rhizo", "--repo", repo.path().to_str().unwrap()];
+        argv.extend_from_slice(args);
+        let mut out = Vec::new();
+        let ok = run(&Cli::try_parse_from(argv).unwrap(), &mut out).unwrap();
+        (ok, String::from_utf8(out).unwrap())
+    }
+
+    #[test]
+    fn test_commit_log_and_changelog() {
+        let repo = Repo::ephemeral().unwrap();
+        let file = repo.path().join("users.csv");
+        fs::write(&file, "id\n1\n").unwrap();
+        let hash = repo.store().put(b"id\n1\n2\n").unwrap();
+
+        let (_, out) = rhizo(&repo, &["commit", "users", "--file", file.to_str().unwrap(), "-m", "initial load"]);
+        assert!(out.starts_with("committed tx "), "{}", out);
+        rhizo(&repo, &["commit", "users", "--chunk", &hash]);
+
+        let (_, out) = rhizo(&repo, &["log", "users"]);
+        let versions: Vec<&str> = out.lines().filter(|l| l.starts_with('v')).collect();
+        assert_eq!(versions.len(), 2);
+        assert!(versions[0].starts_with("v2 ") && versions[1].starts_with("v1 "));
+        assert_eq!(rhizo(&repo, &["log", "users", "-n", "1"]).1.lines().count(), 1);
+        assert!(rhizo(&repo, &["log", "users", "--at", "main~1"]).1.starts_with("v1 "));
+        assert!(rhizo(&repo, &["log", "users", "--at", "tx:2"]).1.starts_with("v2 "));
+
+        let (_, out) = rhizo(&repo, &["branch", "create", "hotfix", "--from", "users@v1"]);
+        assert_eq!(out, "created branch hotfix (1 tables)\n");
+        assert!(rhizo(&repo, &["log", "users", "--branch", "hotfix"]).1.starts_with("v1 "));
+
+        let (_, out) = rhizo(&repo, &["changelog", "--table", "users"]);
+        assert!(out.contains("initial load"));
+        assert!(out.contains("users  new v1  1 chunk(s)"));
+        assert!(out.contains("users  v1 -> v2  1 chunk(s)"));
+        let (_, since) = rhizo(&repo, &["changelog", "--since-tx", "1"]);
+        assert!(!since.contains("new v1"));
+        let (_, events) = rhizo(&repo, &["changelog", "--debezium", "warehouse"]);
+        let lines: Vec<&str> = events.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].contains(r#""op":"c""#) && lines[1].contains(r#""op":"u""#));
+        assert!(lines[1].contains(r#""name":"warehouse""#));
+
+        let mut out = Vec::new();
+        let cli = Cli::try_parse_from(["rhizo", "-C", repo.path().to_str().unwrap(), "commit", "users"]).unwrap();
+        assert!(run(&cli, &mut out).unwrap_err().to_string().contains("nothing to commit"));
+    }
+
+    #[test]
+    fn test_import_csv_and_jsonl() {
+        let repo = Repo::ephemeral().unwrap();
+        let csv = repo.path().join("users.csv");
+        fs::write(&csv, "id|name\n1|ann\n2|bob\n").unwrap();
+        let jsonl = repo.path().join("users.ndjson");
+        fs::write(&jsonl, "{\"id\": 3, \"name\": \"cy\"}\n").unwrap();
+
+        let (_, out) = rhizo(&repo, &["import", csv.to_str().unwrap(), "users", "--delimiter", "|", "-m", "onboard"]);
+        assert!(out.contains("users v1 (1 chunk(s))"), "{}", out);
+        let (_, out) = rhizo(&repo, &["import", jsonl.to_str().unwrap(), "users"]);
+        assert!(out.contains("users v2"), "{}", out);
+        assert!(rhizo(&repo, &["changelog", "--table", "users"]).1.contains("onboard"));
+
+        let mut out = Vec::new();
+        let cli = Cli::try_parse_from(["rhizo", "-C", repo.path().to_str().unwrap(), "import", "x.txt", "t"]).unwrap();
+        assert!(run(&cli, &mut out).unwrap_err().to_string().contains("unknown import format"));
+    }
+
+    #[test]
+    fn test_branches_verify_recover_and_gc() {
+        let repo = Repo::ephemeral().unwrap();
+        rhizo(&repo, &["commit", "users", "--chunk", &repo.store().put(b"a").unwrap()]);
+        let orphan = repo.store().put(b"never committed").unwrap();
+
+        rhizo(&repo, &["branch", "create", "dev", "-d", "experiments"]);
+        assert_eq!(rhizo(&repo, &["branch", "list"]).1, "  dev\n* main\n");
+        let (_, show) = rhizo(&repo, &["branch", "show", "dev"]);
+        assert!(show.contains("description experiments") && show.contains("  users v1"));
+
+        assert_eq!(rhizo(&repo, &["verify"]), (true, "ok\n".to_string()));
+        repo.branches().update_head("dev", "users", 7).unwrap();
+        let (ok, out) = rhizo(&repo, &["verify"]);
+        assert!(!ok && out.contains("branch dev head for users points at missing v7"));
+        rhizo(&repo, &["branch", "delete", "dev"]);
+
+        let (ok, out) = rhizo(&repo, &["recover"]);
+        assert!(ok && out.starts_with("scanned "));
+
+        let (_, out) = rhizo(&repo, &["gc", "--dry-run", "--grace-secs", "0", "--prune"]);
+        assert!(out.contains("would prune 0 version(s)") && out.contains("would delete 1 chunk(s)"));
+        assert!(repo.store().exists(&orphan).unwrap());
+        rhizo(&repo, &["gc", "--grace-secs", "0"]);
+        assert!(!repo.store().exists(&orphan).unwrap());
+    }
+
+    #[test]
+    fn test_gc_free_deletes_planned_versions() {
+        let repo = Repo::ephemeral().unwrap();
+        rhizo(&repo, &["commit", "users", "--chunk", &repo.store().put(&[b'a'; 100]).unwrap()]);
+        rhizo(&repo, &["commit", "users", "--chunk", &repo.store().put(&[b'b'; 10]).unwrap()]);
+        rhizo(&repo, &["commit", "users", "--chunk", &repo.store().put(&[b'c'; 10]).unwrap()]);
+
+        let (ok, out) = rhizo(&repo, &["gc", "--free", "50", "--grace-secs", "0", "--dry-run"]);
+        assert!(ok && out.starts_with("would free 100 of 50 bytes\n  users v1: 100 bytes\n"));
+        assert_eq!(repo.catalog().list_versions("users").unwrap(), vec![1, 2, 3]);
+
+        let (ok, out) = rhizo(&repo, &["gc", "--free", "1000", "--grace-secs", "0"]);
+        assert!(!ok && out.contains("freeing 110 of 1000 bytes (target not reachable)"));
+        assert!(out.contains("deleted 2 chunk(s), 110 bytes"));
+        assert_eq!(repo.catalog().list_versions("users").unwrap(), vec![3]);
+    }
+}
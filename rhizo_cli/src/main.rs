@@ -0,0 +1,42 @@
+//! `rhizo`: inspect and administer a repository from the command line.
+//!
+//! ```text
+//! rhizo branch list
+//! rhizo log users --branch dev
+//! rhizo log users --at tag:release-1
+//! rhizo branch create hotfix --from main~2
+//! rhizo changelog --since-tx 40 --table users
+//! rhizo changelog --since-tx 40 --debezium warehouse
+//! rhizo commit users --file part-0.parquet -m "nightly load"
+//! rhizo import events.csv events -m "initial load"
+//! rhizo recover --apply
+//! rhizo verify
+//! rhizo gc --prune --dry-run
+//! rhizo gc --free 10737418240
+//! ```
+//!
+//! Exits 0 on success, 1 if `verify`, `recover` or `gc` found problems,
+//! and 2 on errors.
+
+use std::io::{self, Write};
+use std::process::ExitCode;
+
+use clap::Parser;
+
+mod cli;
+mod commands;
+
+fn main() -> ExitCode {
+    let cli = cli::Cli::parse();
+    let mut out = io::stdout().lock();
+    let result = commands::run(&cli, &mut out);
+    let _ = out.flush();
+    match result {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::from(1),
+        Err(e) => {
+            eprintln!("rhizo: {}", e);
+            ExitCode::from(2)
+        }
+    }
+}
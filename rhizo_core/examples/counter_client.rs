@@ -0,0 +1,39 @@
+//! Command-line client for `counter_node`.
+//!
+//! ```text
+//! cargo run -p rhizo_core --example counter_client -- 127.0.0.1:7501 incr page_views 5
+//! cargo run -p rhizo_core --example counter_client -- 127.0.0.1:7502 tag page_views homepage
+//! cargo run -p rhizo_core --example counter_client -- 127.0.0.1:7503 get page_views
+//! cargo run -p rhizo_core --example counter_client -- 127.0.0.1:7503 state
+//! ```
+
+mod counter_service;
+
+use counter_service::{CounterClient, Result};
+
+const USAGE: &str = "usage: counter_client ADDR (incr KEY N | tag KEY TAG | get KEY | state | sync)";
+
+fn main() -> Result<()> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let Some((addr, command)) = args.split_first() else {
+        return Err(USAGE.into());
+    };
+    let mut client = CounterClient::connect(addr.as_str())?;
+
+    match command.iter().map(String::as_str).collect::<Vec<_>>().as_slice() {
+        ["incr", key, by] => println!("committed, archived as {}", client.increment(key, by.parse()?)?),
+        ["tag", key, tag] => println!("committed, archived as {}", client.tag(key, tag)?),
+        ["get", key] => match client.get(key)? {
+            Some(value) => println!("{} = {}", key, value),
+            None => println!("{} is not set", key),
+        },
+        ["state"] => {
+            for (key, value) in client.state()? {
+                println!("{} = {}", key, value);
+            }
+        }
+        ["sync"] => println!("synced with {} peers", client.sync()?),
+        _ => return Err(USAGE.into()),
+    }
+    Ok(())
+}
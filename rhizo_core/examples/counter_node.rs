@@ -0,0 +1,59 @@
+//! One node of the replicated counter service.
+//!
+//! Start three nodes that gossip with each other, then talk to any of them
+//! with `counter_client`:
+//!
+//! ```text
+//! cargo run -p rhizo_core --example counter_node -- --id a --gossip 127.0.0.1:7401 --api 127.0.0.1:7501 \
+//!     --peer 127.0.0.1:7402 --peer 127.0.0.1:7403 --data ./nodes/a
+//! cargo run -p rhizo_core --example counter_node -- --id b --gossip 127.0.0.1:7402 --api 127.0.0.1:7502 \
+//!     --peer 127.0.0.1:7401 --data ./nodes/b
+//! cargo run -p rhizo_core --example counter_node -- --id c --gossip 127.0.0.1:7403 --api 127.0.0.1:7503 \
+//!     --peer 127.0.0.1:7401 --data ./nodes/c
+//! ```
+
+mod counter_service;
+
+use std::net::TcpListener;
+use std::time::Duration;
+
+use counter_service::{CounterNode, Result};
+use rhizo_core::GossipConfig;
+
+const USAGE: &str = "usage: counter_node --id NAME --gossip ADDR --api ADDR --data DIR \
+                     [--peer ADDR]... [--interval-ms N]";
+
+fn main() -> Result<()> {
+    let mut id = None;
+    let mut gossip = None;
+    let mut api = None;
+    let mut data = None;
+    let mut peers = Vec::new();
+    let mut interval = Duration::from_millis(500);
+
+    let mut args = std::env::args().skip(1);
+    while let Some(flag) = args.next() {
+        let value = args.next().ok_or(USAGE)?;
+        match flag.as_str() {
+            "--id" => id = Some(value),
+            "--gossip" => gossip = Some(value),
+            "--api" => api = Some(value),
+            "--data" => data = Some(value),
+            "--peer" => peers.push(value.parse()?),
+            "--interval-ms" => interval = Duration::from_millis(value.parse()?),
+            _ => return Err(USAGE.into()),
+        }
+    }
+    let (Some(id), Some(gossip), Some(api), Some(data)) = (id, gossip, api, data) else {
+        return Err(USAGE.into());
+    };
+
+    let node = CounterNode::start(&id, gossip, data, GossipConfig::default().with_interval(interval))?;
+    for peer in peers {
+        node.add_peer(peer);
+    }
+    let server = node.serve(TcpListener::bind(api)?)?;
+    println!("node {} gossiping on {}, serving clients on {}", id, node.gossip_addr(), server.addr());
+    server.wait();
+    Ok(())
+}
@@ -0,0 +1,319 @@
+//! A replicated counter and tag service over the real distributed path.
+//!
+//! Each [`CounterNode`] commits client writes coordination-free through a
+//! [`CoordinationFreeManager`], archives every update it learns of in a
+//! content-addressed [`ChunkStore`], and propagates updates to its peers
+//! with a [`GossipNode`]. Counters are `AbelianAdd` integers and tags are
+//! `SemilatticeUnion` string sets, so nodes accept writes independently
+//! and still agree once gossip has run.
+//!
+//! Clients talk to a node over TCP, one JSON [`Request`] per line, each
+//! answered by one JSON [`Response`] line. Shared by the `counter_node` and
+//! `counter_client` examples and the `counter_service` integration test.
+
+#![allow(dead_code)]
+
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+
+use rhizo_core::{
+    AlgebraicOperation, AlgebraicTransaction, AlgebraicValue, ChunkStore, CoordinationFreeManager,
+    GossipConfig, GossipNode, NodeId, OpType, VersionedUpdate,
+};
+
+pub type Result<T> = std::result::Result<T, Box<dyn Error + Send + Sync>>;
+
+/// One client request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum Request {
+    /// Add `by` to a counter
+    Increment { key: String, by: i64 },
+    /// Add a tag to a tag set
+    Tag { key: String, tag: String },
+    /// Read one key
+    Get { key: String },
+    /// Read every key
+    State,
+    /// Run an anti-entropy round now instead of waiting for the next one
+    Sync,
+}
+
+/// The answer to one request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum Response {
+    /// A write committed locally; `archived` is the update's chunk hash
+    Committed { archived: String },
+    Value { value: Option<AlgebraicValue> },
+    State { values: BTreeMap<String, AlgebraicValue> },
+    /// Peers reached by the round
+    Synced { peers: usize },
+    Error { message: String },
+}
+
+/// One replica of the service
+pub struct CounterNode {
+    manager: CoordinationFreeManager,
+    gossip: GossipNode,
+    store: ChunkStore,
+    /// Gossip updates already mirrored into the manager
+    mirrored: Mutex<usize>,
+}
+
+impl CounterNode {
+    /// Bind gossip on `gossip_addr` and keep archived updates under
+    /// `data_dir`. Gossip rounds run in the background every
+    /// `config.interval`.
+    pub fn start(
+        node_id: &str,
+        gossip_addr: impl ToSocketAddrs,
+        data_dir: impl AsRef<Path>,
+        config: GossipConfig,
+    ) -> Result<Arc<Self>> {
+        let node = Arc::new(Self {
+            manager: CoordinationFreeManager::new(NodeId::new(node_id)),
+            gossip: GossipNode::bind(NodeId::new(node_id), gossip_addr, config)?,
+            store: ChunkStore::new(data_dir.as_ref().join("chunks"))?,
+            mirrored: Mutex::new(0),
+        });
+        node.gossip.start();
+        Ok(node)
+    }
+
+    pub fn gossip_addr(&self) -> SocketAddr {
+        self.gossip.local_addr()
+    }
+
+    pub fn add_peer(&self, addr: SocketAddr) {
+        self.gossip.add_peer(addr);
+    }
+
+    /// Archived updates, local and received
+    pub fn store(&self) -> &ChunkStore {
+        &self.store
+    }
+
+    pub fn handle(&self, request: Request) -> Response {
+        let result = match request {
+            Request::Increment { key, by } => {
+                self.commit(AlgebraicOperation::new(key, OpType::AbelianAdd, AlgebraicValue::integer(by)))
+            }
+            Request::Tag { key, tag } => self.commit(AlgebraicOperation::new(
+                key,
+                OpType::SemilatticeUnion,
+                AlgebraicValue::string_set([tag]),
+            )),
+            Request::Get { key } => self.get(&key).map(|value| Response::Value { value }),
+            Request::State => self.values().map(|values| Response::State { values }),
+            Request::Sync => {
+                let round = self.gossip.anti_entropy_round();
+                Ok(Response::Synced { peers: round.synced.len() })
+            }
+        };
+        result.unwrap_or_else(|e| Response::Error { message: e.to_string() })
+    }
+
+    /// Commit one operation locally and hand it to gossip
+    fn commit(&self, op: AlgebraicOperation) -> Result<Response> {
+        let mut tx = AlgebraicTransaction::new();
+        tx.add_operation(op);
+        let update = self.manager.commit_local(&tx)?;
+        let archived = self.archive(&update)?;
+        self.gossip.receive(update)?;
+        Ok(Response::Committed { archived })
+    }
+
+    /// Apply updates gossip received from peers since the last call
+    fn mirror(&self) -> Result<()> {
+        let mut mirrored = self.mirrored.lock();
+        let updates = self.gossip.updates_since(*mirrored);
+        for update in &updates {
+            *mirrored += 1;
+            // Local commits reached the manager first
+            if update.origin_node() != self.manager.node_id() {
+                self.manager.receive_update(update)?;
+                self.archive(update)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Store an update; every node archives it under the same hash
+    fn archive(&self, update: &VersionedUpdate) -> Result<String> {
+        Ok(self.store.put(&serde_json::to_vec(update)?)?)
+    }
+
+    fn get(&self, key: &str) -> Result<Option<AlgebraicValue>> {
+        self.mirror()?;
+        Ok(self.manager.get_state(key)?)
+    }
+
+    fn values(&self) -> Result<BTreeMap<String, AlgebraicValue>> {
+        self.mirror()?;
+        let mut values = BTreeMap::new();
+        for key in self.manager.keys()? {
+            if let Some(value) = self.manager.get_state(&key)? {
+                values.insert(key, value);
+            }
+        }
+        Ok(values)
+    }
+
+    /// Answer clients on `listener` until the returned server is shut down
+    pub fn serve(self: &Arc<Self>, listener: TcpListener) -> Result<ApiServer> {
+        let addr = listener.local_addr()?;
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let node = Arc::clone(self);
+        let stop = Arc::clone(&shutdown);
+        let thread = thread::spawn(move || {
+            for stream in listener.incoming() {
+                if stop.load(Ordering::SeqCst) {
+                    break;
+                }
+                let Ok(stream) = stream else { continue };
+                let node = Arc::clone(&node);
+                thread::spawn(move || {
+                    if let Err(e) = node.answer(stream) {
+                        eprintln!("client connection failed: {}", e);
+                    }
+                });
+            }
+        });
+        Ok(ApiServer {
+            addr,
+            shutdown,
+            thread: Some(thread),
+        })
+    }
+
+    fn answer(&self, stream: TcpStream) -> Result<()> {
+        let mut writer = BufWriter::new(stream.try_clone()?);
+        for line in BufReader::new(stream).lines() {
+            let response = match serde_json::from_str::<Request>(&line?) {
+                Ok(request) => self.handle(request),
+                Err(e) => Response::Error { message: format!("bad request: {}", e) },
+            };
+            serde_json::to_writer(&mut writer, &response)?;
+            writer.write_all(b"\n")?;
+            writer.flush()?;
+        }
+        Ok(())
+    }
+
+    pub fn shutdown(&self) {
+        self.gossip.shutdown();
+    }
+}
+
+/// The client listener of a [`CounterNode`]
+pub struct ApiServer {
+    addr: SocketAddr,
+    shutdown: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl ApiServer {
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Block until the listener stops
+    pub fn wait(mut self) {
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+impl Drop for ApiServer {
+    fn drop(&mut self) {
+        if self.shutdown.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        // Wake the listener blocked in accept()
+        let mut wake = self.addr;
+        if wake.ip().is_unspecified() {
+            wake.set_ip(std::net::Ipv4Addr::LOCALHOST.into());
+        }
+        let _ = TcpStream::connect(wake);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// A connection to one node's client listener
+pub struct CounterClient {
+    reader: BufReader<TcpStream>,
+    writer: BufWriter<TcpStream>,
+}
+
+impl CounterClient {
+    pub fn connect(addr: impl ToSocketAddrs) -> Result<Self> {
+        let stream = TcpStream::connect(addr)?;
+        Ok(Self {
+            reader: BufReader::new(stream.try_clone()?),
+            writer: BufWriter::new(stream),
+        })
+    }
+
+    pub fn request(&mut self, request: &Request) -> Result<Response> {
+        serde_json::to_writer(&mut self.writer, request)?;
+        self.writer.write_all(b"\n")?;
+        self.writer.flush()?;
+
+        let mut line = String::new();
+        if self.reader.read_line(&mut line)? == 0 {
+            return Err("node closed the connection".into());
+        }
+        match serde_json::from_str(&line)? {
+            Response::Error { message } => Err(message.into()),
+            response => Ok(response),
+        }
+    }
+
+    pub fn increment(&mut self, key: &str, by: i64) -> Result<String> {
+        match self.request(&Request::Increment { key: key.to_string(), by })? {
+            Response::Committed { archived } => Ok(archived),
+            other => Err(format!("unexpected response: {:?}", other).into()),
+        }
+    }
+
+    pub fn tag(&mut self, key: &str, tag: &str) -> Result<String> {
+        match self.request(&Request::Tag { key: key.to_string(), tag: tag.to_string() })? {
+            Response::Committed { archived } => Ok(archived),
+            other => Err(format!("unexpected response: {:?}", other).into()),
+        }
+    }
+
+    pub fn get(&mut self, key: &str) -> Result<Option<AlgebraicValue>> {
+        match self.request(&Request::Get { key: key.to_string() })? {
+            Response::Value { value } => Ok(value),
+            other => Err(format!("unexpected response: {:?}", other).into()),
+        }
+    }
+
+    pub fn state(&mut self) -> Result<BTreeMap<String, AlgebraicValue>> {
+        match self.request(&Request::State)? {
+            Response::State { values } => Ok(values),
+            other => Err(format!("unexpected response: {:?}", other).into()),
+        }
+    }
+
+    pub fn sync(&mut self) -> Result<usize> {
+        match self.request(&Request::Sync)? {
+            Response::Synced { peers } => Ok(peers),
+            other => Err(format!("unexpected response: {:?}", other).into()),
+        }
+    }
+}
@@ -0,0 +1,238 @@
+//! User-defined merge functions, registered at runtime.
+//!
+//! Values whose operation is `OpType::Custom(name)` merge with the function
+//! registered under `name`. Registration is process-wide, so every merge
+//! path ([`AlgebraicMerger::merge`](super::AlgebraicMerger::merge), branch
+//! data merges, coordination-free local commits) sees the same functions;
+//! while nothing is registered under a name, its operation is not
+//! conflict-free and merges of it conflict.
+//!
+//! A merge function must be commutative and associative, or replicas stop
+//! converging. [`CustomMergeRegistry::register_checked`] tests both
+//! properties on sample inputs before accepting a function.
+//!
+//! # Example
+//! ```
+//! use rhizo_core::algebraic::{AlgebraicMerger, AlgebraicValue, CustomMergeRegistry, MergeResult, OpType};
+//!
+//! // Keep the value with the larger absolute magnitude
+//! CustomMergeRegistry::register_checked(
+//!     "max_abs",
+//!     |a, b| match (a.as_integer(), b.as_integer()) {
+//!         (Some(x), Some(y)) => MergeResult::Merged(AlgebraicValue::integer(
+//!             if (x.abs(), x) >= (y.abs(), y) { x } else { y },
+//!         )),
+//!         _ => MergeResult::TypeMismatch { type1: a.type_name(), type2: b.type_name(), operation: OpType::custom("max_abs") },
+//!     },
+//!     &[AlgebraicValue::integer(-7), AlgebraicValue::integer(3), AlgebraicValue::integer(7)],
+//! )
+//! .unwrap();
+//!
+//! let merged = AlgebraicMerger::merge(OpType::custom("max_abs"), &(-7).into(), &3.into());
+//! assert_eq!(merged, MergeResult::Merged(AlgebraicValue::integer(-7)));
+//! ```
+
+use std::collections::BTreeMap;
+use std::sync::{Arc, RwLock};
+
+use thiserror::Error;
+
+use super::laws::{AlgebraicLaws, Law};
+use super::merge::MergeResult;
+use super::types::AlgebraicValue;
+use crate::error::{ErrorCategory, ErrorContext, ErrorInfo};
+
+/// A registered merge function
+pub type CustomMergeFn = Arc<dyn Fn(&AlgebraicValue, &AlgebraicValue) -> MergeResult + Send + Sync>;
+
+static FUNCTIONS: RwLock<BTreeMap<String, CustomMergeFn>> = RwLock::new(BTreeMap::new());
+
+/// Errors from registering a custom merge function.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CustomMergeError {
+    #[error("Invalid custom merge name: '{0}'")]
+    InvalidName(String),
+
+    #[error("Merge function '{name}' is not commutative: merging {left} and {right} depends on their order")]
+    NotCommutative {
+        name: String,
+        left: String,
+        right: String,
+    },
+
+    #[error("Merge function '{name}' is not associative: grouping {a}, {b} and {c} changes the result")]
+    NotAssociative {
+        name: String,
+        a: String,
+        b: String,
+        c: String,
+    },
+}
+
+impl ErrorInfo for CustomMergeError {
+    fn code(&self) -> &'static str {
+        match self {
+            Self::InvalidName(_) => "CUSTOM_MERGE_INVALID_NAME",
+            Self::NotCommutative { .. } => "CUSTOM_MERGE_NOT_COMMUTATIVE",
+            Self::NotAssociative { .. } => "CUSTOM_MERGE_NOT_ASSOCIATIVE",
+        }
+    }
+
+    fn category(&self) -> ErrorCategory {
+        ErrorCategory::InvalidArgument
+    }
+
+    fn context(&self) -> ErrorContext {
+        ErrorContext::new()
+    }
+}
+
+/// The process-wide registry of custom merge functions.
+pub struct CustomMergeRegistry;
+
+impl CustomMergeRegistry {
+    /// Register `merge` under `name`, replacing any function registered
+    /// under it. The function is trusted to be commutative and associative.
+    pub fn register<F>(name: impl Into<String>, merge: F) -> Result<(), CustomMergeError>
+    where
+        F: Fn(&AlgebraicValue, &AlgebraicValue) -> MergeResult + Send + Sync + 'static,
+    {
+        let name = name.into();
+        if name.trim().is_empty() {
+            return Err(CustomMergeError::InvalidName(name));
+        }
+        FUNCTIONS.write().unwrap_or_else(|e| e.into_inner()).insert(name, Arc::new(merge));
+        Ok(())
+    }
+
+    /// Register `merge` under `name` after checking it is commutative and
+    /// associative on `samples` (see [`check_properties`](Self::check_properties)).
+    pub fn register_checked<F>(
+        name: impl Into<String>,
+        merge: F,
+        samples: &[AlgebraicValue],
+    ) -> Result<(), CustomMergeError>
+    where
+        F: Fn(&AlgebraicValue, &AlgebraicValue) -> MergeResult + Send + Sync + 'static,
+    {
+        let name = name.into();
+        Self::check_properties(&name, &merge, samples)?;
+        Self::register(name, merge)
+    }
+
+    /// Check that `merge` is commutative on every pair of samples, and
+    /// associative on every triple whose inner merges succeed (see
+    /// [`AlgebraicLaws::verify_fn`]).
+    ///
+    /// Passing shows the properties hold on the samples only; choose them
+    /// to cover the value types and edge cases the function will see, or
+    /// generate them with [`AlgebraicLaws::samples`].
+    pub fn check_properties<F>(name: &str, merge: &F, samples: &[AlgebraicValue]) -> Result<(), CustomMergeError>
+    where
+        F: Fn(&AlgebraicValue, &AlgebraicValue) -> MergeResult + ?Sized,
+    {
+        let report = AlgebraicLaws::verify_fn(merge, &[Law::Commutativity, Law::Associativity], samples);
+        let Some(violation) = report.violations.into_iter().next() else {
+            return Ok(());
+        };
+        let name = name.to_string();
+        let inputs: Vec<String> = violation.inputs.iter().map(|v| v.to_string()).collect();
+        Err(match (violation.law, inputs.as_slice()) {
+            (Law::Associativity, [a, b, c]) => CustomMergeError::NotAssociative {
+                name,
+                a: a.clone(),
+                b: b.clone(),
+                c: c.clone(),
+            },
+            (_, inputs) => CustomMergeError::NotCommutative {
+                name,
+                left: inputs[0].clone(),
+                right: inputs[inputs.len() - 1].clone(),
+            },
+        })
+    }
+
+    /// Remove the function registered under `name`; false if there was none.
+    pub fn unregister(name: &str) -> bool {
+        FUNCTIONS.write().unwrap_or_else(|e| e.into_inner()).remove(name).is_some()
+    }
+
+    pub fn contains(name: &str) -> bool {
+        FUNCTIONS.read().unwrap_or_else(|e| e.into_inner()).contains_key(name)
+    }
+
+    /// Names with a registered function, sorted
+    pub fn names() -> Vec<String> {
+        FUNCTIONS.read().unwrap_or_else(|e| e.into_inner()).keys().cloned().collect()
+    }
+
+    /// The function registered under `name`. It is cloned out of the
+    /// registry, so it may itself register functions or merge values.
+    pub fn get(name: &str) -> Option<CustomMergeFn> {
+        FUNCTIONS.read().unwrap_or_else(|e| e.into_inner()).get(name).cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algebraic::{AlgebraicMerger, OpType};
+
+    fn gcd(a: &AlgebraicValue, b: &AlgebraicValue) -> MergeResult {
+        let (Some(mut x), Some(mut y)) = (a.as_integer(), b.as_integer()) else {
+            return MergeResult::TypeMismatch {
+                type1: a.type_name(),
+                type2: b.type_name(),
+                operation: OpType::custom("test_gcd"),
+            };
+        };
+        while y != 0 {
+            (x, y) = (y, x % y);
+        }
+        MergeResult::Merged(AlgebraicValue::integer(x.abs()))
+    }
+
+    #[test]
+    fn test_registered_function_merges() {
+        let samples: Vec<AlgebraicValue> = [12, 18, 7, 0].into_iter().map(AlgebraicValue::integer).collect();
+        CustomMergeRegistry::register_checked("test_gcd", gcd, &samples).unwrap();
+        let op = OpType::custom("test_gcd");
+        assert!(op.is_conflict_free());
+        assert_eq!(op.to_string(), "CUSTOM(test_gcd)");
+        assert!(CustomMergeRegistry::names().contains(&"test_gcd".to_string()));
+        assert_eq!(
+            AlgebraicMerger::merge(op.clone(), &12.into(), &18.into()),
+            MergeResult::Merged(AlgebraicValue::integer(6))
+        );
+
+        // Unregistered, the operation needs coordination again
+        assert!(CustomMergeRegistry::unregister("test_gcd"));
+        assert!(!op.is_conflict_free());
+        assert!(AlgebraicMerger::merge(op, &12.into(), &18.into()).is_conflict());
+    }
+
+    #[test]
+    fn test_check_rejects_unlawful_functions() {
+        let samples: Vec<AlgebraicValue> = [1, 2, 3].into_iter().map(AlgebraicValue::integer).collect();
+        let subtract = |a: &AlgebraicValue, b: &AlgebraicValue| {
+            MergeResult::Merged(AlgebraicValue::integer(a.as_integer().unwrap() - b.as_integer().unwrap()))
+        };
+        assert!(matches!(
+            CustomMergeRegistry::register_checked("test_subtract", subtract, &samples),
+            Err(CustomMergeError::NotCommutative { .. })
+        ));
+        assert!(!CustomMergeRegistry::contains("test_subtract"));
+
+        // Commutative but not associative: the mean of two values
+        let mean = |a: &AlgebraicValue, b: &AlgebraicValue| {
+            MergeResult::Merged(AlgebraicValue::float((a.as_float().unwrap() + b.as_float().unwrap()) / 2.0))
+        };
+        let err = CustomMergeRegistry::register_checked("test_mean", mean, &samples).unwrap_err();
+        assert_eq!(err.code(), "CUSTOM_MERGE_NOT_ASSOCIATIVE");
+        assert!(matches!(
+            CustomMergeRegistry::register(" ", mean),
+            Err(CustomMergeError::InvalidName(_))
+        ));
+    }
+}
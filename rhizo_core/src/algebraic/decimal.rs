@@ -0,0 +1,257 @@
+//! Exact fixed-point decimals for monetary values.
+//!
+//! A [`Decimal`] is an `i128` mantissa and a scale: `mantissa / 10^scale`.
+//! Sums are exact (the result takes the larger scale) and overflow is
+//! reported rather than wrapped, so financial counters merged with
+//! `AbelianAdd` come out to the cent whatever order replicas apply them in.
+//! The representation matches Arrow's `Decimal128`.
+//!
+//! # Example
+//! ```
+//! use rhizo_core::algebraic::{AlgebraicMerger, AlgebraicValue, Decimal, MergeResult, OpType};
+//!
+//! let a: Decimal = "0.10".parse().unwrap();
+//! let b: Decimal = "0.2".parse().unwrap();
+//! let merged = AlgebraicMerger::merge(OpType::AbelianAdd, &a.into(), &b.into());
+//! assert_eq!(merged, MergeResult::Merged(AlgebraicValue::Decimal("0.30".parse().unwrap())));
+//! ```
+
+use std::cmp::Ordering;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::error::{ErrorCategory, ErrorContext, ErrorInfo};
+
+/// Largest supported scale (digits after the point), as for Arrow `Decimal128`
+pub const MAX_SCALE: u32 = 38;
+
+/// Errors from building or parsing a [`Decimal`].
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DecimalError {
+    #[error("Invalid decimal: '{0}'")]
+    Parse(String),
+
+    #[error("Decimal scale {0} exceeds the maximum of {MAX_SCALE}")]
+    ScaleTooLarge(u32),
+
+    #[error("Decimal out of range: '{0}'")]
+    Overflow(String),
+}
+
+impl ErrorInfo for DecimalError {
+    fn code(&self) -> &'static str {
+        match self {
+            Self::Parse(_) => "DECIMAL_PARSE",
+            Self::ScaleTooLarge(_) => "DECIMAL_SCALE_TOO_LARGE",
+            Self::Overflow(_) => "DECIMAL_OVERFLOW",
+        }
+    }
+
+    fn category(&self) -> ErrorCategory {
+        ErrorCategory::InvalidArgument
+    }
+
+    fn context(&self) -> ErrorContext {
+        ErrorContext::new()
+    }
+}
+
+/// A fixed-point decimal: `mantissa / 10^scale`.
+///
+/// The scale is kept, so `1.50` and `1.5` are distinct values that compare
+/// numerically equal. Ordering is numeric, ties broken by scale, which
+/// makes MAX and MIN of equal amounts deterministic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(into = "String", try_from = "String")]
+pub struct Decimal {
+    mantissa: i128,
+    scale: u32,
+}
+
+impl Decimal {
+    pub fn new(mantissa: i128, scale: u32) -> Result<Self, DecimalError> {
+        if scale > MAX_SCALE {
+            return Err(DecimalError::ScaleTooLarge(scale));
+        }
+        Ok(Self { mantissa, scale })
+    }
+
+    pub fn mantissa(&self) -> i128 {
+        self.mantissa
+    }
+
+    pub fn scale(&self) -> u32 {
+        self.scale
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.mantissa == 0
+    }
+
+    /// The same value at `scale`; None if digits would be lost or the
+    /// mantissa overflows.
+    pub fn rescaled(&self, scale: u32) -> Option<Self> {
+        if scale > MAX_SCALE {
+            return None;
+        }
+        let mantissa = if scale >= self.scale {
+            self.mantissa.checked_mul(10i128.checked_pow(scale - self.scale)?)?
+        } else {
+            let divisor = 10i128.pow(self.scale - scale);
+            (self.mantissa % divisor == 0).then_some(self.mantissa / divisor)?
+        };
+        Some(Self { mantissa, scale })
+    }
+
+    /// Exact sum at the larger of the two scales; None on overflow.
+    pub fn checked_add(&self, other: &Self) -> Option<Self> {
+        let scale = self.scale.max(other.scale);
+        let (a, b) = (self.rescaled(scale)?, other.rescaled(scale)?);
+        Some(Self { mantissa: a.mantissa.checked_add(b.mantissa)?, scale })
+    }
+
+    /// Exact difference at the larger of the two scales; None on overflow.
+    pub fn checked_sub(&self, other: &Self) -> Option<Self> {
+        self.checked_add(&Self { mantissa: other.mantissa.checked_neg()?, scale: other.scale })
+    }
+
+    /// Nearest f64 (lossy)
+    pub fn to_f64(&self) -> f64 {
+        self.mantissa as f64 / 10f64.powi(self.scale as i32)
+    }
+
+    fn cmp_value(&self, other: &Self) -> Ordering {
+        let scale = self.scale.max(other.scale);
+        match (self.rescaled(scale), other.rescaled(scale)) {
+            (Some(a), Some(b)) => a.mantissa.cmp(&b.mantissa),
+            // Only a value too large in magnitude for the common scale
+            // fails to rescale, so it dominates the other
+            (None, _) => self.mantissa.signum().cmp(&0),
+            (_, None) => 0.cmp(&other.mantissa.signum()),
+        }
+    }
+}
+
+impl Ord for Decimal {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.cmp_value(other).then(self.scale.cmp(&other.scale))
+    }
+}
+
+impl PartialOrd for Decimal {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl From<i64> for Decimal {
+    fn from(v: i64) -> Self {
+        Self { mantissa: v.into(), scale: 0 }
+    }
+}
+
+impl FromStr for Decimal {
+    type Err = DecimalError;
+
+    /// Plain notation: an optional sign, digits, and an optional point
+    /// followed by more digits (`-12.50`, `+3`, `.5`).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || DecimalError::Parse(s.to_string());
+        let unsigned = s.strip_prefix(['-', '+']).unwrap_or(s);
+        let (whole, fraction) = unsigned.split_once('.').unwrap_or((unsigned, ""));
+        if whole.is_empty() && fraction.is_empty()
+            || !whole.chars().chain(fraction.chars()).all(|c| c.is_ascii_digit())
+        {
+            return Err(invalid());
+        }
+        let scale = fraction.len() as u32;
+        if scale > MAX_SCALE {
+            return Err(DecimalError::ScaleTooLarge(scale));
+        }
+        let mut mantissa: i128 = format!("{}{}", whole, fraction)
+            .parse()
+            .map_err(|_| DecimalError::Overflow(s.to_string()))?;
+        if s.starts_with('-') {
+            mantissa = -mantissa;
+        }
+        Ok(Self { mantissa, scale })
+    }
+}
+
+impl TryFrom<String> for Decimal {
+    type Error = DecimalError;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl From<Decimal> for String {
+    fn from(d: Decimal) -> Self {
+        d.to_string()
+    }
+}
+
+impl std::fmt::Display for Decimal {
+    /// Plain notation with exactly `scale` fractional digits
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let digits = self.mantissa.unsigned_abs().to_string();
+        let sign = if self.mantissa < 0 { "-" } else { "" };
+        let scale = self.scale as usize;
+        if scale == 0 {
+            return write!(f, "{}{}", sign, digits);
+        }
+        let digits = format!("{:0>width$}", digits, width = scale + 1);
+        let (whole, fraction) = digits.split_at(digits.len() - scale);
+        write!(f, "{}{}.{}", sign, whole, fraction)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn d(s: &str) -> Decimal {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_parse_display_roundtrip() {
+        for s in ["0", "-12.50", "0.001", "123456789012345678.90", "-0.05"] {
+            assert_eq!(d(s).to_string(), s);
+        }
+        assert_eq!(d("+3").to_string(), "3");
+        assert_eq!(d(".5"), Decimal::new(5, 1).unwrap());
+        for bad in ["", ".", "1.2.3", "1e5", "--1", "abc"] {
+            assert!(matches!(bad.parse::<Decimal>(), Err(DecimalError::Parse(_))), "{}", bad);
+        }
+        assert_eq!("1".repeat(40).parse::<Decimal>().unwrap_err().code(), "DECIMAL_OVERFLOW");
+        assert!(matches!(Decimal::new(1, 39), Err(DecimalError::ScaleTooLarge(39))));
+
+        let json = serde_json::to_string(&d("19.99")).unwrap();
+        assert_eq!(json, "\"19.99\"");
+        assert_eq!(serde_json::from_str::<Decimal>(&json).unwrap(), d("19.99"));
+    }
+
+    #[test]
+    fn test_exact_arithmetic_and_ordering() {
+        assert_eq!(d("0.1").checked_add(&d("0.2")), Some(d("0.3")));
+        assert_eq!(d("1.25").checked_add(&d("-3")), Some(d("-1.75")));
+        assert_eq!(d("10").checked_sub(&d("0.01")), Some(d("9.99")));
+        assert_eq!(Decimal::new(i128::MAX, 0).unwrap().checked_add(&d("1")), None);
+
+        assert_eq!(d("1.50").rescaled(1), Some(d("1.5")));
+        assert_eq!(d("1.55").rescaled(1), None);
+        assert_eq!(d("2").rescaled(2), Some(d("2.00")));
+
+        assert!(d("1.5") < d("1.50"));
+        assert!(d("-2") < d("-1.99"));
+        assert!(d("0.5") > d("-100"));
+        let huge = Decimal::new(i128::MAX, 0).unwrap();
+        assert!(huge > Decimal::new(1, MAX_SCALE).unwrap());
+        assert!(Decimal::new(-i128::MAX, 0).unwrap() < Decimal::new(-1, MAX_SCALE).unwrap());
+    }
+}
@@ -0,0 +1,348 @@
+//! Checks of the algebraic laws conflict-free merges rely on.
+//!
+//! Replicas converge only if merging is commutative and associative, and
+//! semilattice merges must also be idempotent. [`AlgebraicLaws::verify`]
+//! checks the laws an [`OpType`] promises on sample values, and
+//! [`AlgebraicLaws::samples`] generates such values from a seed, so tests
+//! of new operations or [custom merges](super::CustomMergeRegistry) can
+//! cover many inputs reproducibly.
+//!
+//! # Example
+//! ```
+//! use rhizo_core::algebraic::{AlgebraicLaws, OpType};
+//!
+//! let op = OpType::map(OpType::SemilatticeMax);
+//! let report = AlgebraicLaws::verify(op.clone(), &AlgebraicLaws::samples(&op, 20, 7));
+//! assert!(report.is_ok(), "{}", report);
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+use super::merge::{AlgebraicMerger, MergeResult};
+use super::types::{AlgebraicValue, LwwRegister, ObservedRemoveSet, OpType, PnCounter};
+use super::Decimal;
+
+/// A law of merge operations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Law {
+    /// merge(a, b) = merge(b, a)
+    Commutativity,
+    /// merge(merge(a, b), c) = merge(a, merge(b, c))
+    Associativity,
+    /// merge(a, a) = a
+    Idempotency,
+}
+
+impl Law {
+    /// The laws `op_type` promises: commutativity and associativity if it
+    /// is conflict-free, plus idempotency if it is a semilattice.
+    pub fn required_by(op_type: &OpType) -> Vec<Law> {
+        let mut laws = Vec::new();
+        if op_type.is_conflict_free() {
+            laws.extend([Law::Commutativity, Law::Associativity]);
+        }
+        if op_type.is_semilattice() {
+            laws.push(Law::Idempotency);
+        }
+        laws
+    }
+}
+
+impl std::fmt::Display for Law {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Commutativity => write!(f, "commutativity"),
+            Self::Associativity => write!(f, "associativity"),
+            Self::Idempotency => write!(f, "idempotency"),
+        }
+    }
+}
+
+/// Inputs on which a law does not hold.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LawViolation {
+    pub law: Law,
+    pub inputs: Vec<AlgebraicValue>,
+    /// The two results the law says agree
+    pub left: MergeResult,
+    pub right: MergeResult,
+}
+
+impl std::fmt::Display for LawViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let inputs: Vec<String> = self.inputs.iter().map(|v| v.to_string()).collect();
+        write!(
+            f,
+            "{} fails for ({}): {} vs {}",
+            self.law,
+            inputs.join(", "),
+            outcome(&self.left),
+            outcome(&self.right)
+        )
+    }
+}
+
+fn outcome(result: &MergeResult) -> String {
+    match result {
+        MergeResult::Merged(value) => value.to_string(),
+        MergeResult::Conflict { reason, .. } => format!("conflict ({})", reason),
+        MergeResult::TypeMismatch { type1, type2, .. } => format!("type mismatch ({}, {})", type1, type2),
+    }
+}
+
+/// Outcome of checking laws on a set of samples.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LawReport {
+    /// Laws checked
+    pub laws: Vec<Law>,
+    /// Law instances evaluated (pairs, triples or single values)
+    pub checks: usize,
+    pub violations: Vec<LawViolation>,
+}
+
+impl LawReport {
+    pub fn is_ok(&self) -> bool {
+        self.violations.is_empty()
+    }
+}
+
+impl std::fmt::Display for LawReport {
+    /// Summary line, then the first few violations
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let laws: Vec<String> = self.laws.iter().map(|l| l.to_string()).collect();
+        write!(
+            f,
+            "{} checks of [{}]: {} violations",
+            self.checks,
+            laws.join(", "),
+            self.violations.len()
+        )?;
+        for violation in self.violations.iter().take(5) {
+            write!(f, "\n  {}", violation)?;
+        }
+        Ok(())
+    }
+}
+
+/// Verifier of the algebraic laws of merge operations.
+pub struct AlgebraicLaws;
+
+impl AlgebraicLaws {
+    /// Check the laws `op_type` promises (see [`Law::required_by`]) on
+    /// every pair and triple of `samples`.
+    pub fn verify(op_type: OpType, samples: &[AlgebraicValue]) -> LawReport {
+        let laws = Law::required_by(&op_type);
+        Self::verify_fn(|a, b| AlgebraicMerger::merge(op_type.clone(), a, b), &laws, samples)
+    }
+
+    /// Check `laws` for an arbitrary merge function.
+    ///
+    /// Results agree if they merge to equal values or fail the same way.
+    /// Associativity is checked on triples whose inner merges succeed, and
+    /// idempotency on samples the function accepts, so samples outside a
+    /// function's domain are not violations.
+    pub fn verify_fn<F>(merge: F, laws: &[Law], samples: &[AlgebraicValue]) -> LawReport
+    where
+        F: Fn(&AlgebraicValue, &AlgebraicValue) -> MergeResult,
+    {
+        let mut report = LawReport {
+            laws: laws.to_vec(),
+            checks: 0,
+            violations: Vec::new(),
+        };
+        let mut check = |law: Law, inputs: &[&AlgebraicValue], left: MergeResult, right: MergeResult| {
+            report.checks += 1;
+            if !agree(&left, &right) {
+                report.violations.push(LawViolation {
+                    law,
+                    inputs: inputs.iter().map(|v| (*v).clone()).collect(),
+                    left,
+                    right,
+                });
+            }
+        };
+        for &law in laws {
+            match law {
+                Law::Commutativity => {
+                    for (i, a) in samples.iter().enumerate() {
+                        for b in &samples[i + 1..] {
+                            check(law, &[a, b], merge(a, b), merge(b, a));
+                        }
+                    }
+                }
+                Law::Associativity => {
+                    for a in samples {
+                        for b in samples {
+                            let MergeResult::Merged(ab) = merge(a, b) else { continue };
+                            for c in samples {
+                                let MergeResult::Merged(bc) = merge(b, c) else { continue };
+                                check(law, &[a, b, c], merge(&ab, c), merge(a, &bc));
+                            }
+                        }
+                    }
+                }
+                Law::Idempotency => {
+                    for a in samples {
+                        let aa = merge(a, a);
+                        if !matches!(aa, MergeResult::TypeMismatch { .. }) {
+                            check(law, &[a], aa, MergeResult::Merged(a.clone()));
+                        }
+                    }
+                }
+            }
+        }
+        report
+    }
+
+    /// Generate `count` values `op_type` merges, deterministically from
+    /// `seed`.
+    ///
+    /// Values are drawn from the operation's domain, and kept small enough
+    /// not to overflow: integers and decimals for numeric operations, sets,
+    /// counters and registers over a few shared elements and nodes (so
+    /// samples overlap), and maps of such values for `Map`. Operations
+    /// with no fixed domain (custom and generic ones) get integers.
+    pub fn samples(op_type: &OpType, count: usize, seed: u64) -> Vec<AlgebraicValue> {
+        let mut rng = SplitMix64(seed);
+        (0..count).map(|_| sample(op_type, &mut rng)).collect()
+    }
+}
+
+fn agree(left: &MergeResult, right: &MergeResult) -> bool {
+    match (left, right) {
+        (MergeResult::Merged(a), MergeResult::Merged(b)) => a == b,
+        (MergeResult::Conflict { .. }, MergeResult::Conflict { .. })
+        | (MergeResult::TypeMismatch { .. }, MergeResult::TypeMismatch { .. }) => true,
+        _ => false,
+    }
+}
+
+/// Small seeded generator (SplitMix64); samples need to be reproducible,
+/// not unpredictable.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    fn below(&mut self, n: u64) -> u64 {
+        self.next() % n
+    }
+
+    /// Uniform in `-n..=n`
+    fn around_zero(&mut self, n: u64) -> i64 {
+        self.below(2 * n + 1) as i64 - n as i64
+    }
+}
+
+const ELEMENTS: [&str; 5] = ["a", "b", "c", "d", "e"];
+const NODES: [&str; 3] = ["n0", "n1", "n2"];
+
+fn sample(op_type: &OpType, rng: &mut SplitMix64) -> AlgebraicValue {
+    match op_type {
+        OpType::SemilatticeMax | OpType::SemilatticeMin | OpType::AbelianAdd | OpType::BoundedAdd => {
+            if rng.below(2) == 0 {
+                AlgebraicValue::integer(rng.around_zero(1000))
+            } else {
+                let scale = rng.below(3) as u32;
+                AlgebraicValue::Decimal(Decimal::new(rng.around_zero(100_000).into(), scale).unwrap())
+            }
+        }
+        OpType::AbelianMultiply => AlgebraicValue::integer(rng.around_zero(4)),
+        OpType::SemilatticeUnion | OpType::SemilatticeIntersect => {
+            AlgebraicValue::string_set(ELEMENTS.into_iter().filter(|_| rng.below(2) == 0))
+        }
+        OpType::SemilatticeObservedRemove => {
+            let mut set = ObservedRemoveSet::new();
+            for _ in 0..rng.below(4) {
+                let element = ELEMENTS[rng.below(ELEMENTS.len() as u64) as usize];
+                // A tag names one add everywhere, so it always adds the same element
+                set.add_with_tag(element, format!("{}:{}", element, rng.below(3)));
+            }
+            if rng.below(3) == 0 {
+                set.remove(ELEMENTS[rng.below(ELEMENTS.len() as u64) as usize]);
+            }
+            set.into()
+        }
+        OpType::SemilatticePnCounter => {
+            let mut counter = PnCounter::new();
+            for node in NODES {
+                counter.increment(node, rng.below(20));
+                counter.decrement(node, rng.below(20));
+            }
+            counter.into()
+        }
+        OpType::LwwRegister => {
+            // A (timestamp, node) stamp names one write, so it always
+            // carries the same value
+            let (timestamp, node) = (rng.below(10), rng.below(NODES.len() as u64));
+            let value = AlgebraicValue::integer((timestamp * 10 + node) as i64);
+            LwwRegister::new(value, timestamp, NODES[node as usize]).into()
+        }
+        OpType::Map(entry_op) => AlgebraicValue::map(
+            ELEMENTS[..4]
+                .iter()
+                .filter_map(|key| if rng.below(2) == 0 { Some((*key, sample(entry_op, rng))) } else { None })
+                .collect::<Vec<_>>(),
+        ),
+        _ => AlgebraicValue::integer(rng.around_zero(1000)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_operations_obey_their_laws() {
+        let ops = [
+            OpType::SemilatticeMax,
+            OpType::SemilatticeMin,
+            OpType::SemilatticeUnion,
+            OpType::SemilatticeIntersect,
+            OpType::SemilatticeObservedRemove,
+            OpType::SemilatticePnCounter,
+            OpType::LwwRegister,
+            OpType::AbelianAdd,
+            OpType::AbelianMultiply,
+            OpType::BoundedAdd,
+            OpType::map(OpType::AbelianAdd),
+            OpType::map(OpType::SemilatticeUnion),
+        ];
+        for op in ops {
+            let samples = AlgebraicLaws::samples(&op, 16, 42);
+            assert_eq!(samples, AlgebraicLaws::samples(&op, 16, 42));
+            let report = AlgebraicLaws::verify(op.clone(), &samples);
+            assert!(report.is_ok(), "{}: {}", op, report);
+            assert_eq!(report.laws, Law::required_by(&op));
+            assert!(report.checks > 0);
+        }
+        assert!(Law::required_by(&OpType::GenericOverwrite).is_empty());
+    }
+
+    #[test]
+    fn test_reports_violations() {
+        let samples: Vec<AlgebraicValue> = [1, 2, 3].into_iter().map(AlgebraicValue::integer).collect();
+        let subtract = |a: &AlgebraicValue, b: &AlgebraicValue| {
+            MergeResult::Merged(AlgebraicValue::integer(a.as_integer().unwrap() - b.as_integer().unwrap()))
+        };
+        let report = AlgebraicLaws::verify_fn(subtract, &[Law::Commutativity, Law::Idempotency], &samples);
+        assert_eq!(report.checks, 6);
+        assert_eq!(report.violations.len(), 6);
+        let first = &report.violations[0];
+        assert_eq!(first.law, Law::Commutativity);
+        assert_eq!(first.to_string(), "commutativity fails for (1, 2): -1 vs 1");
+        assert!(report.to_string().starts_with("6 checks of [commutativity, idempotency]: 6 violations"));
+
+        // Float addition rounds, so it is not associative
+        let floats: Vec<AlgebraicValue> = [0.1, 0.2, 0.3].into_iter().map(AlgebraicValue::float).collect();
+        let report = AlgebraicLaws::verify(OpType::AbelianAdd, &floats);
+        assert!(report.violations.iter().all(|v| v.law == Law::Associativity));
+        assert!(!report.is_ok());
+    }
+}
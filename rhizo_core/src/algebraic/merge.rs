@@ -12,16 +12,36 @@
 //! - MIN: Returns the smaller value
 //! - UNION: Returns the set union
 //! - INTERSECT: Returns the set intersection
+//! - OR_SET: Returns the observed-remove join (adds and removals of both)
+//! - PN_COUNTER: Returns the per-node maximum of both tallies
+//! - LWW: Returns the write with the greatest (timestamp, node) stamp
 //!
 //! # Abelian Merges
 //!
 //! Abelian operations combine values via group operation:
 //!
-//! - ADD: Returns `a + b`
+//! - ADD: Returns `a + b`, exactly for decimals
 //! - MULTIPLY: Returns `a * b`
+//!
+//! # Bounded Merges
+//!
+//! - BOUNDED_ADD: Joins the replicas of a bounded counter, or adds plain
+//!   numeric deltas; a result outside the bounds is a conflict
+//!
+//! # Custom Merges
+//!
+//! - CUSTOM(name): Calls the function registered under `name` (see
+//!   [`CustomMergeRegistry`](super::CustomMergeRegistry))
+//!
+//! # Map Merges
+//!
+//! - MAP(op): Keeps keys present in one map and merges the entries of keys
+//!   present in both with `op`
 
+use super::custom::CustomMergeRegistry;
 use super::types::{AlgebraicValue, OpType};
-use std::collections::HashSet;
+use std::cmp::Ordering;
+use std::collections::{BTreeSet, HashSet};
 
 /// Result of attempting an algebraic merge.
 #[derive(Debug, Clone, PartialEq)]
@@ -158,13 +178,14 @@ impl AlgebraicMerger {
 
         // Check if operation is conflict-free
         if !op_type.is_conflict_free() {
+            let reason = match &op_type {
+                OpType::Custom(name) => format!("No merge function is registered as '{}'", name),
+                _ => format!("Operation type {} is not conflict-free", op_type),
+            };
             return MergeResult::Conflict {
                 value1: value1.clone(),
                 value2: value2.clone(),
-                reason: format!(
-                    "Operation type {} is not conflict-free",
-                    op_type
-                ),
+                reason,
             };
         }
 
@@ -173,8 +194,22 @@ impl AlgebraicMerger {
             OpType::SemilatticeMin => Self::merge_min(value1, value2),
             OpType::SemilatticeUnion => Self::merge_union(value1, value2),
             OpType::SemilatticeIntersect => Self::merge_intersect(value1, value2),
+            OpType::SemilatticeObservedRemove => Self::merge_observed_remove(value1, value2),
+            OpType::SemilatticePnCounter => Self::merge_pn_counter(value1, value2),
+            OpType::LwwRegister => Self::merge_lww(value1, value2),
             OpType::AbelianAdd => Self::merge_add(value1, value2),
             OpType::AbelianMultiply => Self::merge_multiply(value1, value2),
+            OpType::BoundedAdd => Self::merge_bounded(value1, value2),
+            OpType::Map(ref entry_op) => Self::merge_map(entry_op, value1, value2),
+            OpType::Custom(ref name) => match CustomMergeRegistry::get(name) {
+                Some(merge) => merge(value1, value2),
+                // Unregistered since the conflict-free check
+                None => MergeResult::Conflict {
+                    value1: value1.clone(),
+                    value2: value2.clone(),
+                    reason: format!("No merge function is registered as '{}'", name),
+                },
+            },
             _ => MergeResult::Conflict {
                 value1: value1.clone(),
                 value2: value2.clone(),
@@ -213,6 +248,12 @@ impl AlgebraicMerger {
             (AlgebraicValue::Float(a), AlgebraicValue::Integer(b)) => {
                 MergeResult::Merged(Self::numeric_result(a.max(*b as f64)))
             }
+            // Decimals compare exactly with decimals and integers
+            (AlgebraicValue::Decimal(_), AlgebraicValue::Decimal(_) | AlgebraicValue::Integer(_))
+            | (AlgebraicValue::Integer(_), AlgebraicValue::Decimal(_)) => {
+                let (a, b) = (v1.as_decimal().unwrap(), v2.as_decimal().unwrap());
+                MergeResult::Merged(AlgebraicValue::Decimal(a.max(b)))
+            }
             (AlgebraicValue::Boolean(a), AlgebraicValue::Boolean(b)) => {
                 // true > false in boolean ordering
                 MergeResult::Merged(AlgebraicValue::Boolean(*a || *b))
@@ -243,6 +284,11 @@ impl AlgebraicMerger {
             (AlgebraicValue::Float(a), AlgebraicValue::Integer(b)) => {
                 MergeResult::Merged(Self::numeric_result(a.min(*b as f64)))
             }
+            (AlgebraicValue::Decimal(_), AlgebraicValue::Decimal(_) | AlgebraicValue::Integer(_))
+            | (AlgebraicValue::Integer(_), AlgebraicValue::Decimal(_)) => {
+                let (a, b) = (v1.as_decimal().unwrap(), v2.as_decimal().unwrap());
+                MergeResult::Merged(AlgebraicValue::Decimal(a.min(b)))
+            }
             (AlgebraicValue::Boolean(a), AlgebraicValue::Boolean(b)) => {
                 // false < true in boolean ordering
                 MergeResult::Merged(AlgebraicValue::Boolean(*a && *b))
@@ -305,6 +351,66 @@ impl AlgebraicMerger {
         }
     }
 
+    /// Merge using OR_SET (semilattice join of observed-remove sets).
+    ///
+    /// Mathematical property: join(A, B) = join(B, A) and join(A, A) = A
+    fn merge_observed_remove(v1: &AlgebraicValue, v2: &AlgebraicValue) -> MergeResult {
+        match (v1, v2) {
+            (AlgebraicValue::ObservedRemoveSet(a), AlgebraicValue::ObservedRemoveSet(b)) => {
+                MergeResult::Merged(AlgebraicValue::ObservedRemoveSet(a.merge(b)))
+            }
+            _ => MergeResult::TypeMismatch {
+                type1: v1.type_name(),
+                type2: v2.type_name(),
+                operation: OpType::SemilatticeObservedRemove,
+            },
+        }
+    }
+
+    /// Merge using PN_COUNTER (semilattice join of per-node tallies).
+    ///
+    /// Mathematical property: join(A, B) = join(B, A) and join(A, A) = A
+    fn merge_pn_counter(v1: &AlgebraicValue, v2: &AlgebraicValue) -> MergeResult {
+        match (v1, v2) {
+            (AlgebraicValue::PnCounter(a), AlgebraicValue::PnCounter(b)) => {
+                MergeResult::Merged(AlgebraicValue::PnCounter(a.merge(b)))
+            }
+            _ => MergeResult::TypeMismatch {
+                type1: v1.type_name(),
+                type2: v2.type_name(),
+                operation: OpType::SemilatticePnCounter,
+            },
+        }
+    }
+
+    /// Merge using LWW (latest write by timestamp, node id breaking ties).
+    ///
+    /// Two different values with the same stamp cannot be ordered and are
+    /// reported as a conflict.
+    fn merge_lww(v1: &AlgebraicValue, v2: &AlgebraicValue) -> MergeResult {
+        match (v1, v2) {
+            (AlgebraicValue::LwwRegister(a), AlgebraicValue::LwwRegister(b)) => match a.cmp_stamp(b) {
+                Ordering::Greater => MergeResult::Merged(v1.clone()),
+                Ordering::Less => MergeResult::Merged(v2.clone()),
+                Ordering::Equal if a.value() == b.value() => MergeResult::Merged(v1.clone()),
+                Ordering::Equal => MergeResult::Conflict {
+                    value1: v1.clone(),
+                    value2: v2.clone(),
+                    reason: format!(
+                        "Different values written with the same stamp ({} by {})",
+                        a.timestamp(),
+                        a.node()
+                    ),
+                },
+            },
+            _ => MergeResult::TypeMismatch {
+                type1: v1.type_name(),
+                type2: v2.type_name(),
+                operation: OpType::LwwRegister,
+            },
+        }
+    }
+
     /// Merge using ADD (Abelian group addition).
     ///
     /// Mathematical property: a + b = b + a
@@ -331,6 +437,20 @@ impl AlgebraicMerger {
             (AlgebraicValue::Float(a), AlgebraicValue::Integer(b)) => {
                 MergeResult::Merged(Self::numeric_result(a + *b as f64))
             }
+            // Exact decimal addition; integers widen to decimals, floats
+            // would lose the exactness and are refused
+            (AlgebraicValue::Decimal(_), AlgebraicValue::Decimal(_) | AlgebraicValue::Integer(_))
+            | (AlgebraicValue::Integer(_), AlgebraicValue::Decimal(_)) => {
+                let (a, b) = (v1.as_decimal().unwrap(), v2.as_decimal().unwrap());
+                match a.checked_add(&b) {
+                    Some(sum) => MergeResult::Merged(AlgebraicValue::Decimal(sum)),
+                    None => MergeResult::Conflict {
+                        value1: v1.clone(),
+                        value2: v2.clone(),
+                        reason: format!("Decimal overflow: {} + {}", a, b),
+                    },
+                }
+            }
             _ => MergeResult::TypeMismatch {
                 type1: v1.type_name(),
                 type2: v2.type_name(),
@@ -373,33 +493,102 @@ impl AlgebraicMerger {
         }
     }
 
+    /// Merge using BOUNDED_ADD.
+    ///
+    /// Replicas of a [`BoundedCounter`](super::BoundedCounter) are joined;
+    /// the result is a conflict if they are different counters or if a node
+    /// spent rights it did not hold. Plain numbers carry no bounds and are
+    /// added; the bounds of an annotated column are checked by whoever
+    /// holds the annotation (see [`ColumnAlgebraic::bounds`](super::ColumnAlgebraic::bounds)).
+    fn merge_bounded(v1: &AlgebraicValue, v2: &AlgebraicValue) -> MergeResult {
+        match (v1, v2) {
+            (AlgebraicValue::BoundedCounter(a), AlgebraicValue::BoundedCounter(b)) => {
+                if !a.is_replica_of(b) {
+                    return MergeResult::Conflict {
+                        value1: v1.clone(),
+                        value2: v2.clone(),
+                        reason: "Bounded counters have different bounds, initial values or origins"
+                            .to_string(),
+                    };
+                }
+                let merged = a.merge(b);
+                match merged.validate() {
+                    Ok(()) => MergeResult::Merged(AlgebraicValue::BoundedCounter(merged)),
+                    Err(e) => MergeResult::Conflict {
+                        value1: v1.clone(),
+                        value2: v2.clone(),
+                        reason: e.to_string(),
+                    },
+                }
+            }
+            _ if v1.is_numeric() && v2.is_numeric() => Self::merge_add(v1, v2),
+            _ => MergeResult::TypeMismatch {
+                type1: v1.type_name(),
+                type2: v2.type_name(),
+                operation: OpType::BoundedAdd,
+            },
+        }
+    }
+
+    /// Merge using MAP (per-key merge with the nested operation).
+    ///
+    /// Inherits the nested operation's properties key by key. A conflict
+    /// on any key makes the whole merge a conflict.
+    fn merge_map(entry_op: &OpType, v1: &AlgebraicValue, v2: &AlgebraicValue) -> MergeResult {
+        let (AlgebraicValue::Map(a), AlgebraicValue::Map(b)) = (v1, v2) else {
+            return MergeResult::TypeMismatch {
+                type1: v1.type_name(),
+                type2: v2.type_name(),
+                operation: OpType::map(entry_op.clone()),
+            };
+        };
+        let mut merged = a.clone();
+        // Sorted, so the first conflicting key reported is the same either way round
+        for key in b.keys().collect::<BTreeSet<_>>() {
+            let entry = match a.get(key) {
+                None => b[key].clone(),
+                Some(existing) => match Self::merge(entry_op.clone(), existing, &b[key]) {
+                    MergeResult::Merged(value) => value,
+                    MergeResult::Conflict { reason, .. } => {
+                        return MergeResult::Conflict {
+                            value1: v1.clone(),
+                            value2: v2.clone(),
+                            reason: format!("Key '{}': {}", key, reason),
+                        }
+                    }
+                    mismatch @ MergeResult::TypeMismatch { .. } => return mismatch,
+                },
+            };
+            merged.insert(key.clone(), entry);
+        }
+        MergeResult::Merged(AlgebraicValue::Map(merged))
+    }
+
     /// Verify commutativity property: merge(a, b) = merge(b, a)
     ///
-    /// This is a test helper to verify the mathematical guarantees.
+    /// This is a test helper; [`AlgebraicLaws`](super::AlgebraicLaws) is
+    /// the public verifier.
     #[cfg(test)]
     pub fn verify_commutativity(
         op_type: OpType,
         v1: &AlgebraicValue,
         v2: &AlgebraicValue,
     ) -> bool {
-        let result1 = Self::merge(op_type, v1, v2);
-        let result2 = Self::merge(op_type, v2, v1);
-        result1 == result2
+        let merge = |a: &AlgebraicValue, b: &AlgebraicValue| Self::merge(op_type.clone(), a, b);
+        super::AlgebraicLaws::verify_fn(merge, &[super::Law::Commutativity], &[v1.clone(), v2.clone()]).is_ok()
     }
 
     /// Verify idempotency property: merge(a, a) = a (for semilattice operations)
     ///
-    /// This is a test helper to verify the mathematical guarantees.
+    /// This is a test helper; [`AlgebraicLaws`](super::AlgebraicLaws) is
+    /// the public verifier.
     #[cfg(test)]
     pub fn verify_idempotency(op_type: OpType, v: &AlgebraicValue) -> bool {
         if !op_type.is_semilattice() {
             return true; // Only semilattice operations need to be idempotent
         }
-        let result = Self::merge(op_type, v, v);
-        match result {
-            MergeResult::Merged(merged) => merged == *v,
-            _ => false,
-        }
+        // Unlike the verifier, a value outside the operation's domain fails
+        Self::merge(op_type, v, v) == MergeResult::Merged(v.clone())
     }
 }
 
@@ -522,8 +711,134 @@ mod tests {
         }
     }
 
+    // ============ LWW Tests ============
+
+    #[test]
+    fn test_merge_lww_latest_write_wins() {
+        use crate::algebraic::LwwRegister;
+        let lww = |v: i64, ts: u64, node: &str| AlgebraicValue::from(LwwRegister::new(v.into(), ts, node));
+
+        let old = lww(1, 100, "b");
+        let new = lww(2, 200, "a");
+        assert_eq!(AlgebraicMerger::merge(OpType::LwwRegister, &old, &new), MergeResult::Merged(new.clone()));
+        assert_eq!(AlgebraicMerger::merge(OpType::LwwRegister, &new, &old), MergeResult::Merged(new.clone()));
+        assert_eq!(AlgebraicMerger::merge(OpType::LwwRegister, &new, &new), MergeResult::Merged(new.clone()));
+
+        // Timestamp tie: the greater node id wins on every replica
+        let tie = lww(3, 200, "c");
+        assert_eq!(AlgebraicMerger::merge(OpType::LwwRegister, &new, &tie), MergeResult::Merged(tie.clone()));
+        assert_eq!(AlgebraicMerger::merge(OpType::LwwRegister, &tie, &new), MergeResult::Merged(tie));
+
+        let forged = lww(9, 200, "a");
+        assert!(matches!(
+            AlgebraicMerger::merge(OpType::LwwRegister, &new, &forged),
+            MergeResult::Conflict { .. }
+        ));
+        assert!(matches!(
+            AlgebraicMerger::merge(OpType::LwwRegister, &new, &AlgebraicValue::integer(2)),
+            MergeResult::TypeMismatch { .. }
+        ));
+    }
+
     // ============ INTERSECT Tests ============
 
+    #[test]
+    fn test_merge_bounded_counters() {
+        use crate::algebraic::{BoundedCounter, Bounds};
+        let mut shared = BoundedCounter::new("a", 5, Bounds::at_least(0)).unwrap();
+        shared.transfer_decrement_rights("a", "b", 2).unwrap();
+        let (mut a, mut b) = (shared.clone(), shared.clone());
+        a.decrement("a", 3).unwrap();
+        b.decrement("b", 2).unwrap();
+
+        let (a, b) = (AlgebraicValue::from(a), AlgebraicValue::from(b));
+        assert!(AlgebraicMerger::verify_commutativity(OpType::BoundedAdd, &a, &b));
+        let merged = AlgebraicMerger::merge(OpType::BoundedAdd, &a, &b).unwrap();
+        assert_eq!(merged.to_string(), "0");
+
+        // Different counters, and plain deltas, are not joined as replicas
+        let other = BoundedCounter::new("a", 5, Bounds::at_least(1)).unwrap();
+        assert!(AlgebraicMerger::merge(OpType::BoundedAdd, &a, &other.into()).is_conflict());
+        assert!(AlgebraicMerger::merge(OpType::BoundedAdd, &a, &AlgebraicValue::integer(1)).is_type_mismatch());
+        assert_eq!(
+            AlgebraicMerger::merge(OpType::BoundedAdd, &AlgebraicValue::integer(-3), &AlgebraicValue::integer(1)),
+            MergeResult::Merged(AlgebraicValue::integer(-2))
+        );
+    }
+
+    #[test]
+    fn test_merge_decimals_exactly() {
+        let dec = |s: &str| AlgebraicValue::Decimal(s.parse().unwrap());
+        assert_eq!(
+            AlgebraicMerger::merge(OpType::AbelianAdd, &dec("0.1"), &dec("0.20")),
+            MergeResult::Merged(dec("0.30"))
+        );
+        assert_eq!(
+            AlgebraicMerger::merge(OpType::AbelianAdd, &dec("19.99"), &AlgebraicValue::integer(-20)),
+            MergeResult::Merged(dec("-0.01"))
+        );
+        assert!(AlgebraicMerger::merge(OpType::AbelianAdd, &dec("1"), &AlgebraicValue::float(0.5)).is_type_mismatch());
+        let max = AlgebraicValue::decimal(i128::MAX, 0).unwrap();
+        assert!(AlgebraicMerger::merge(OpType::AbelianAdd, &max, &dec("1")).is_conflict());
+
+        // Equal amounts at different scales still merge deterministically
+        for op in [OpType::SemilatticeMax, OpType::SemilatticeMin] {
+            for (a, b) in [(dec("2.5"), dec("2.50")), (dec("3"), AlgebraicValue::integer(3)), (dec("-1.5"), dec("7"))] {
+                assert!(AlgebraicMerger::verify_commutativity(op.clone(), &a, &b));
+                assert!(AlgebraicMerger::verify_idempotency(op.clone(), &a));
+            }
+        }
+        assert_eq!(
+            AlgebraicMerger::merge(OpType::SemilatticeMax, &dec("2.5"), &AlgebraicValue::integer(2)),
+            MergeResult::Merged(dec("2.5"))
+        );
+        assert_eq!(
+            AlgebraicMerger::merge(OpType::SemilatticeMin, &dec("2.5"), &AlgebraicValue::integer(2)),
+            MergeResult::Merged(dec("2"))
+        );
+    }
+
+    #[test]
+    fn test_merge_maps_per_key() {
+        use crate::algebraic::{BoundedCounter, Bounds};
+        let op = OpType::map(OpType::AbelianAdd);
+        assert!(op.is_conflict_free() && !op.is_semilattice());
+        assert_eq!(op.to_string(), "MAP(ADD)");
+        let a = AlgebraicValue::map([("alice", 3.into()), ("bob", 1.into())]);
+        let b = AlgebraicValue::map([("bob", 4.into()), ("carol", 2.into())]);
+        assert!(AlgebraicMerger::verify_commutativity(op.clone(), &a, &b));
+        assert_eq!(
+            AlgebraicMerger::merge(op.clone(), &a, &b),
+            MergeResult::Merged(AlgebraicValue::map([
+                ("alice", 3.into()),
+                ("bob", 5.into()),
+                ("carol", 2.into()),
+            ]))
+        );
+        assert_eq!(
+            AlgebraicMerger::merge(op.clone(), &a, &AlgebraicValue::Null),
+            MergeResult::Merged(a.clone())
+        );
+        assert!(AlgebraicMerger::merge(op, &a, &AlgebraicValue::integer(1)).is_type_mismatch());
+
+        // Properties, and conflicts, follow the nested operation
+        let max = OpType::map(OpType::SemilatticeMax);
+        assert!(max.is_semilattice());
+        assert!(AlgebraicMerger::verify_idempotency(max, &a));
+        let overwrite = OpType::map(OpType::GenericOverwrite);
+        assert!(!overwrite.is_conflict_free());
+        assert!(AlgebraicMerger::merge(overwrite, &a, &b).is_conflict());
+        let seats = |lower| AlgebraicValue::from(BoundedCounter::new("a", 5, Bounds::at_least(lower)).unwrap());
+        let (x, y) = (
+            AlgebraicValue::map([("row_a", seats(0)), ("row_b", seats(0))]),
+            AlgebraicValue::map([("row_b", seats(1))]),
+        );
+        match AlgebraicMerger::merge(OpType::map(OpType::BoundedAdd), &x, &y) {
+            MergeResult::Conflict { reason, .. } => assert!(reason.starts_with("Key 'row_b'")),
+            other => panic!("Expected Conflict, got {:?}", other),
+        }
+    }
+
     #[test]
     fn test_merge_intersect_string_sets() {
         let v1 = AlgebraicValue::string_set(["a", "b", "c"]);
@@ -816,8 +1131,8 @@ mod tests {
 
         for op in [OpType::AbelianAdd, OpType::SemilatticeMax,
                     OpType::SemilatticeMin, OpType::AbelianMultiply] {
-            let ab = AlgebraicMerger::merge(op, &int_val, &float_val);
-            let ba = AlgebraicMerger::merge(op, &float_val, &int_val);
+            let ab = AlgebraicMerger::merge(op.clone(), &int_val, &float_val);
+            let ba = AlgebraicMerger::merge(op.clone(), &float_val, &int_val);
             assert_eq!(ab, ba, "Commutativity violated for {:?}", op);
         }
     }
@@ -872,7 +1187,7 @@ mod tests {
         // Max and Min are idempotent for integers
         for op in [OpType::SemilatticeMax, OpType::SemilatticeMin] {
             assert!(
-                AlgebraicMerger::verify_idempotency(op, &AlgebraicValue::integer(42)),
+                AlgebraicMerger::verify_idempotency(op.clone(), &AlgebraicValue::integer(42)),
                 "{:?} should be idempotent for integers",
                 op
             );
@@ -882,7 +1197,7 @@ mod tests {
         for op in [OpType::SemilatticeUnion, OpType::SemilatticeIntersect] {
             assert!(
                 AlgebraicMerger::verify_idempotency(
-                    op,
+                    op.clone(),
                     &AlgebraicValue::string_set(["a", "b"])
                 ),
                 "{:?} should be idempotent for sets",
@@ -903,7 +1218,7 @@ mod tests {
         for op in ops {
             assert!(
                 AlgebraicMerger::verify_commutativity(
-                    op,
+                    op.clone(),
                     &AlgebraicValue::integer(17),
                     &AlgebraicValue::integer(23),
                 ),
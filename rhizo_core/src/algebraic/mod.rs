@@ -18,6 +18,9 @@
 //! - `SemilatticeMin`: min(a, b) — first-update-timestamp wins
 //! - `SemilatticeUnion`: A ∪ B — add-only sets (tags, permissions)
 //! - `SemilatticeIntersect`: A ∩ B — common elements only
+//! - `SemilatticeObservedRemove`: OR-Set — sets with add-wins removal
+//! - `SemilatticePnCounter`: PN-Counter — per-node increment/decrement tallies
+//! - `LwwRegister`: latest (timestamp, node) write wins
 //!
 //! ## Abelian Group Operations
 //!
@@ -31,6 +34,26 @@
 //! - `AbelianAdd`: a + b — counters, inventory deltas
 //! - `AbelianMultiply`: a × b — scaling factors
 //!
+//! ## Bounded Operations
+//!
+//! `BoundedAdd` adds like `AbelianAdd` but keeps the value within a lower
+//! and/or upper bound. A `BoundedCounter` splits the slack to each bound
+//! between nodes as escrowed rights, so each node can update locally until
+//! its share runs out.
+//!
+//! ## User-Defined Operations
+//!
+//! `Custom(name)` merges with a function registered at runtime in the
+//! `CustomMergeRegistry`, optionally after checking it is commutative and
+//! associative on sample inputs. `AlgebraicLaws` checks the same laws for
+//! any operation, on given or generated samples.
+//!
+//! ## Composite Operations
+//!
+//! `Map(op)` merges maps of string keys entry by entry with the nested `op`,
+//! so one value can hold per-key CRDT state (e.g. a counter per user). It is
+//! conflict-free, and a semilattice, exactly when `op` is.
+//!
 //! # Key Insight
 //!
 //! If operations form these algebraic structures, **order doesn't matter**.
@@ -72,17 +95,35 @@
 //! - [`types`]: Core types (`OpType`, `AlgebraicValue`)
 //! - [`merge`]: Merge rules (`AlgebraicMerger`, `MergeResult`)
 //! - [`schema`]: Schema annotations (`ColumnAlgebraic`, `TableAlgebraicSchema`)
+//! - [`custom`]: User-defined merge functions (`CustomMergeRegistry`)
+//! - [`decimal`]: Exact fixed-point numbers (`Decimal`)
+//! - [`laws`]: Checks of the merge laws (`AlgebraicLaws`)
 
+mod custom;
+mod decimal;
+mod laws;
 mod merge;
 mod schema;
 mod types;
 
 // Re-export core types
-pub use types::{AlgebraicValue, OpType};
+pub use types::{
+    AlgebraicValue, BoundedCounter, BoundedCounterError, Bounds, LwwRegister, ObservedRemoveSet,
+    OpType, PnCounter,
+};
 
 // Re-export merge types
 pub use merge::{AlgebraicMerger, MergeResult};
 
+// Re-export custom merge types
+pub use custom::{CustomMergeError, CustomMergeFn, CustomMergeRegistry};
+
+// Re-export decimal types
+pub use decimal::{Decimal, DecimalError};
+
+// Re-export law checks
+pub use laws::{AlgebraicLaws, Law, LawReport, LawViolation};
+
 // Re-export schema types
 pub use schema::{AlgebraicSchemaRegistry, ColumnAlgebraic, TableAlgebraicSchema};
 
@@ -185,8 +226,8 @@ mod integration_tests {
             OpType::AbelianAdd,
             OpType::AbelianMultiply,
         ] {
-            let result1 = AlgebraicMerger::merge(op, &v1, &v2);
-            let result2 = AlgebraicMerger::merge(op, &v2, &v1);
+            let result1 = AlgebraicMerger::merge(op.clone(), &v1, &v2);
+            let result2 = AlgebraicMerger::merge(op.clone(), &v2, &v1);
             assert_eq!(
                 result1, result2,
                 "{:?} should be commutative",
@@ -204,7 +245,7 @@ mod integration_tests {
             OpType::SemilatticeMax,
             OpType::SemilatticeMin,
         ] {
-            let result = AlgebraicMerger::merge(op, &v, &v);
+            let result = AlgebraicMerger::merge(op.clone(), &v, &v);
             assert_eq!(
                 result.unwrap(),
                 v,
@@ -24,9 +24,13 @@
 //! assert!(!schema.get_op_type("name").is_conflict_free());
 //! ```
 
-use super::types::{AlgebraicValue, OpType};
+use super::types::{AlgebraicValue, Bounds, OpType};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::io;
+use std::path::Path;
+
+use crate::durability::{self, DurabilityLevel};
 
 /// Algebraic annotation for a single column.
 ///
@@ -49,20 +53,36 @@ pub struct ColumnAlgebraic {
 
     /// Optional description for documentation
     pub description: Option<String>,
+
+    /// Bounds a `BoundedAdd` column's merged values must stay within
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bounds: Option<Bounds>,
 }
 
 impl ColumnAlgebraic {
     /// Create a new column annotation with the specified operation type.
     pub fn new(column: impl Into<String>, op_type: OpType) -> Self {
-        let identity = Self::default_identity(op_type);
+        let identity = Self::default_identity(&op_type);
         Self {
             column: column.into(),
             op_type,
             identity,
             description: None,
+            bounds: None,
         }
     }
 
+    /// A `BoundedAdd` column whose values must stay within `bounds`.
+    pub fn bounded(column: impl Into<String>, bounds: Bounds) -> Self {
+        Self::new(column, OpType::BoundedAdd).with_bounds(bounds)
+    }
+
+    /// Set the bounds merged values must stay within.
+    pub fn with_bounds(mut self, bounds: Bounds) -> Self {
+        self.bounds = Some(bounds);
+        self
+    }
+
     /// Add a description to this annotation.
     pub fn with_description(mut self, desc: impl Into<String>) -> Self {
         self.description = Some(desc.into());
@@ -76,12 +96,17 @@ impl ColumnAlgebraic {
     }
 
     /// Get the default identity element for an operation type.
-    fn default_identity(op_type: OpType) -> Option<AlgebraicValue> {
+    fn default_identity(op_type: &OpType) -> Option<AlgebraicValue> {
         match op_type {
-            OpType::AbelianAdd => Some(AlgebraicValue::Integer(0)),
+            OpType::AbelianAdd | OpType::BoundedAdd => Some(AlgebraicValue::Integer(0)),
             OpType::AbelianMultiply => Some(AlgebraicValue::Integer(1)),
             OpType::SemilatticeUnion => Some(AlgebraicValue::StringSet(Default::default())),
             OpType::SemilatticeIntersect => None, // Universal set has no representation
+            OpType::SemilatticeObservedRemove => {
+                Some(AlgebraicValue::ObservedRemoveSet(Default::default()))
+            }
+            OpType::SemilatticePnCounter => Some(AlgebraicValue::PnCounter(Default::default())),
+            OpType::Map(_) => Some(AlgebraicValue::Map(Default::default())),
             OpType::SemilatticeMax => None,       // Negative infinity
             OpType::SemilatticeMin => None,       // Positive infinity
             _ => None,
@@ -183,8 +208,8 @@ impl TableAlgebraicSchema {
     pub fn get_op_type(&self, column: &str) -> OpType {
         self.columns
             .get(column)
-            .map(|c| c.op_type)
-            .unwrap_or(self.default_op_type)
+            .map(|c| c.op_type.clone())
+            .unwrap_or_else(|| self.default_op_type.clone())
     }
 
     /// Get the full column annotation if it exists.
@@ -192,6 +217,11 @@ impl TableAlgebraicSchema {
         self.columns.get(column)
     }
 
+    /// Get the bounds of a column, if it is annotated with any.
+    pub fn get_bounds(&self, column: &str) -> Option<Bounds> {
+        self.columns.get(column).and_then(|c| c.bounds)
+    }
+
     /// Check if all columns are conflict-free.
     ///
     /// Returns true if:
@@ -239,7 +269,12 @@ impl TableAlgebraicSchema {
 /// Registry for table algebraic schemas.
 ///
 /// This provides a centralized lookup for schemas across all tables.
-#[derive(Debug, Clone, Default)]
+/// It serializes as a JSON object of schemas keyed by table (see
+/// [`save`](Self::save) / [`load`](Self::load)); schemas versioned with
+/// table data live in the catalog instead (see
+/// [`FileCatalog::algebraic_registry`](crate::catalog::FileCatalog::algebraic_registry)).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
 pub struct AlgebraicSchemaRegistry {
     schemas: HashMap<String, TableAlgebraicSchema>,
 }
@@ -291,6 +326,29 @@ impl AlgebraicSchemaRegistry {
     pub fn clear(&mut self) {
         self.schemas.clear();
     }
+
+    /// Register every schema of `other`, replacing schemas of the same tables.
+    pub fn extend(&mut self, other: &AlgebraicSchemaRegistry) {
+        for schema in other.schemas.values() {
+            self.register(schema.clone());
+        }
+    }
+
+    /// Write the registry to `path` as JSON, replacing the file atomically.
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let path = path.as_ref();
+        let json = serde_json::to_string_pretty(self)?;
+        durability::write_atomic(path, &path.with_extension("json.tmp"), json, DurabilityLevel::default())
+    }
+
+    /// Read a registry written by [`save`](Self::save).
+    ///
+    /// # Errors
+    /// `InvalidData` if the file is not a serialized registry.
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let json = std::fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&json)?)
+    }
 }
 
 #[cfg(test)]
@@ -441,6 +499,39 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_registry_save_and_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("schemas.json");
+
+        let mut registry = AlgebraicSchemaRegistry::new();
+        let mut inventory = TableAlgebraicSchema::new("inventory");
+        inventory.set_default(OpType::GenericOverwrite);
+        inventory.add_column("count", OpType::AbelianAdd);
+        registry.register(inventory);
+        registry.register(TableAlgebraicSchema::all_max("timestamps"));
+        registry.save(&path).unwrap();
+
+        let loaded = AlgebraicSchemaRegistry::load(&path).unwrap();
+        assert_eq!(loaded, registry);
+        assert_eq!(loaded.get_op_type("inventory", "count"), OpType::AbelianAdd);
+        assert_eq!(loaded.get_op_type("inventory", "name"), OpType::GenericOverwrite);
+
+        // Explicit schemas replace loaded ones of the same table
+        let mut merged = loaded;
+        merged.extend(&{
+            let mut r = AlgebraicSchemaRegistry::new();
+            r.register(TableAlgebraicSchema::all_additive("timestamps"));
+            r
+        });
+        assert_eq!(merged.get_op_type("timestamps", "any"), OpType::AbelianAdd);
+        assert!(merged.has_table("inventory"));
+
+        std::fs::write(&path, "not json").unwrap();
+        let err = AlgebraicSchemaRegistry::load(&path).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    }
+
     #[test]
     fn test_registry_unregister() {
         let mut registry = AlgebraicSchemaRegistry::new();
@@ -478,4 +569,20 @@ mod tests {
 
         assert_eq!(schema, parsed);
     }
+
+    #[test]
+    fn test_bounded_column_serialization() {
+        let mut schema = TableAlgebraicSchema::new("stock");
+        schema.add_column_annotation(ColumnAlgebraic::bounded("quantity", Bounds::at_least(0)));
+        schema.add_column("sold", OpType::AbelianAdd);
+        assert_eq!(schema.get_op_type("quantity"), OpType::BoundedAdd);
+        assert_eq!(schema.get_bounds("quantity"), Some(Bounds::at_least(0)));
+        assert_eq!(schema.get_bounds("sold"), None);
+
+        // Unbounded annotations serialize as before
+        let json = serde_json::to_string(&schema).unwrap();
+        assert_eq!(json.matches("bounds").count(), 1);
+        let parsed: TableAlgebraicSchema = serde_json::from_str(&json).unwrap();
+        assert_eq!(schema, parsed);
+    }
 }
@@ -23,13 +23,20 @@
 //! Conflicts become mathematically impossible!
 
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use thiserror::Error;
+
+use crate::error::{ErrorCategory, ErrorContext, ErrorInfo};
+use super::decimal::{Decimal, DecimalError};
 
 /// Algebraic operation classification.
 ///
 /// This enum categorizes operations by their algebraic properties,
 /// determining whether concurrent operations can be automatically merged.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+///
+/// Not `Copy`: [`OpType::Custom`] carries the name of a registered merge
+/// function.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum OpType {
     // === Semilattice Operations (always conflict-free) ===
     /// MAX(a, b) - larger value wins
@@ -56,6 +63,28 @@ pub enum OpType {
     /// Guarantees: idempotent, commutative, associative
     SemilatticeIntersect,
 
+    /// OR-SET(A, B) - observed-remove set
+    ///
+    /// Use for: sets whose elements can be removed (memberships, carts)
+    /// Guarantees: idempotent, commutative, associative. A removal only
+    /// cancels the adds it observed, so a concurrent re-add wins.
+    SemilatticeObservedRemove,
+
+    /// PN-COUNTER(A, B) - per-node increment and decrement tallies
+    ///
+    /// Use for: counters that must keep increments and decrements apart
+    /// (audited balances, bounded counters)
+    /// Guarantees: idempotent, commutative, associative. Each node's tallies
+    /// only grow, and merging keeps the larger tally per node.
+    SemilatticePnCounter,
+
+    /// LWW(a, b) - last-writer-wins register
+    ///
+    /// Use for: scalar fields that are overwritten (status, owner, price)
+    /// Guarantees: idempotent, commutative, associative. The write with the
+    /// greatest timestamp wins; the writing node breaks timestamp ties.
+    LwwRegister,
+
     // === Abelian Group Operations (conflict-free via combination) ===
     /// a + b - additive combination
     ///
@@ -70,6 +99,33 @@ pub enum OpType {
     /// Note: No inverse for 0
     AbelianMultiply,
 
+    // === Bounded Operations (conflict-free within escrowed rights) ===
+    /// a + b, kept within a configured lower/upper bound
+    ///
+    /// Use for: counters with an invariant (stock that can't go negative,
+    /// seats that can't be oversold)
+    /// Guarantees: commutative, associative. A merge that would leave the
+    /// bounds is a conflict; [`BoundedCounter`] values rule that out by
+    /// splitting the bound's slack between nodes as escrowed rights.
+    BoundedAdd,
+
+    // === User-Defined Operations ===
+    /// Merge with the function registered under this name
+    ///
+    /// Use for: domain-specific merges the built-in operations don't cover
+    /// Guarantees: whatever the registered function provides (it must be
+    /// commutative and associative). Conflict-free only while a function is
+    /// registered; see [`CustomMergeRegistry`](super::CustomMergeRegistry).
+    Custom(String),
+
+    // === Composite Operations ===
+    /// Per-key merge of maps, each entry merged with the nested operation
+    ///
+    /// Use for: CRDT maps (per-user counters, per-region maxima in one value)
+    /// Guarantees: those of the nested operation, key by key. Keys present
+    /// in only one map are kept as they are.
+    Map(Box<OpType>),
+
     // === Non-Algebraic Operations (may conflict) ===
     /// Direct value overwrite
     ///
@@ -92,6 +148,16 @@ pub enum OpType {
 }
 
 impl OpType {
+    /// A user-defined operation merging with the function registered as `name`.
+    pub fn custom(name: impl Into<String>) -> Self {
+        Self::Custom(name.into())
+    }
+
+    /// A map operation merging each entry with `entry_op`.
+    pub fn map(entry_op: OpType) -> Self {
+        Self::Map(Box::new(entry_op))
+    }
+
     /// Check if this operation type guarantees conflict-free merging.
     ///
     /// Returns `true` for semilattice and Abelian operations,
@@ -107,27 +173,41 @@ impl OpType {
     /// ```
     #[inline]
     pub fn is_conflict_free(&self) -> bool {
-        matches!(
-            self,
-            Self::SemilatticeMax
-                | Self::SemilatticeMin
-                | Self::SemilatticeUnion
-                | Self::SemilatticeIntersect
-                | Self::AbelianAdd
-                | Self::AbelianMultiply
-        )
+        match self {
+            Self::Custom(name) => super::CustomMergeRegistry::contains(name),
+            Self::Map(entry_op) => entry_op.is_conflict_free(),
+            _ => matches!(
+                self,
+                Self::SemilatticeMax
+                    | Self::SemilatticeMin
+                    | Self::SemilatticeUnion
+                    | Self::SemilatticeIntersect
+                    | Self::SemilatticeObservedRemove
+                    | Self::SemilatticePnCounter
+                    | Self::LwwRegister
+                    | Self::AbelianAdd
+                    | Self::AbelianMultiply
+                    | Self::BoundedAdd
+            ),
+        }
     }
 
     /// Check if this is a semilattice operation.
     #[inline]
     pub fn is_semilattice(&self) -> bool {
-        matches!(
-            self,
-            Self::SemilatticeMax
-                | Self::SemilatticeMin
-                | Self::SemilatticeUnion
-                | Self::SemilatticeIntersect
-        )
+        match self {
+            Self::Map(entry_op) => entry_op.is_semilattice(),
+            _ => matches!(
+                self,
+                Self::SemilatticeMax
+                    | Self::SemilatticeMin
+                    | Self::SemilatticeUnion
+                    | Self::SemilatticeIntersect
+                    | Self::SemilatticeObservedRemove
+                    | Self::SemilatticePnCounter
+                    | Self::LwwRegister
+            ),
+        }
     }
 
     /// Check if this is an Abelian (group) operation.
@@ -160,14 +240,19 @@ impl OpType {
     /// - AbelianAdd: 0 (a + 0 = a)
     /// - AbelianMultiply: 1 (a * 1 = a)
     /// - SemilatticeUnion: empty set
+    /// - SemilatticeObservedRemove: empty set
+    /// - SemilatticePnCounter: 0 (no tallies)
+    /// - BoundedAdd: 0
+    /// - Map: empty map
     /// - SemilatticeIntersect: universal set (represented as None)
     /// - SemilatticeMax: negative infinity (represented as None)
     /// - SemilatticeMin: positive infinity (represented as None)
     pub fn identity_hint(&self) -> Option<&'static str> {
         match self {
-            Self::AbelianAdd => Some("0"),
+            Self::AbelianAdd | Self::SemilatticePnCounter | Self::BoundedAdd => Some("0"),
             Self::AbelianMultiply => Some("1"),
-            Self::SemilatticeUnion => Some("empty_set"),
+            Self::SemilatticeUnion | Self::SemilatticeObservedRemove => Some("empty_set"),
+            Self::Map(_) => Some("empty_map"),
             _ => None,
         }
     }
@@ -179,8 +264,14 @@ impl OpType {
             Self::SemilatticeMin => "Minimum value wins (first-writer-wins)",
             Self::SemilatticeUnion => "Set union (add-only collection)",
             Self::SemilatticeIntersect => "Set intersection (common elements only)",
+            Self::SemilatticeObservedRemove => "Observed-remove set (add-wins, supports removal)",
+            Self::SemilatticePnCounter => "Per-node increment/decrement counter",
+            Self::LwwRegister => "Last-writer-wins register (latest timestamp wins)",
             Self::AbelianAdd => "Additive delta (counters, accumulators)",
             Self::AbelianMultiply => "Multiplicative scaling",
+            Self::BoundedAdd => "Additive delta kept within bounds (escrowed rights)",
+            Self::Custom(_) => "User-defined merge function",
+            Self::Map(_) => "Per-key merge with a nested operation",
             Self::GenericOverwrite => "Direct overwrite (may conflict)",
             Self::GenericConditional => "Conditional update (requires version match)",
             Self::Unknown => "Unknown operation type (conservative)",
@@ -202,8 +293,14 @@ impl std::fmt::Display for OpType {
             Self::SemilatticeMin => write!(f, "MIN"),
             Self::SemilatticeUnion => write!(f, "UNION"),
             Self::SemilatticeIntersect => write!(f, "INTERSECT"),
+            Self::SemilatticeObservedRemove => write!(f, "OR_SET"),
+            Self::SemilatticePnCounter => write!(f, "PN_COUNTER"),
+            Self::LwwRegister => write!(f, "LWW"),
             Self::AbelianAdd => write!(f, "ADD"),
             Self::AbelianMultiply => write!(f, "MULTIPLY"),
+            Self::BoundedAdd => write!(f, "BOUNDED_ADD"),
+            Self::Custom(name) => write!(f, "CUSTOM({})", name),
+            Self::Map(entry_op) => write!(f, "MAP({})", entry_op),
             Self::GenericOverwrite => write!(f, "OVERWRITE"),
             Self::GenericConditional => write!(f, "CONDITIONAL"),
             Self::Unknown => write!(f, "UNKNOWN"),
@@ -227,6 +324,11 @@ pub enum AlgebraicValue {
     /// Use for: metrics, continuous values, timestamps as floats
     Float(f64),
 
+    /// Exact fixed-point decimal
+    ///
+    /// Use for: money and other amounts that must sum without rounding
+    Decimal(Decimal),
+
     /// Set of strings
     ///
     /// Use for: tags, labels, string-based permissions
@@ -237,6 +339,31 @@ pub enum AlgebraicValue {
     /// Use for: ID collections, numeric flags
     IntSet(HashSet<i64>),
 
+    /// Observed-remove set of strings
+    ///
+    /// Use for: tags or memberships that can also be removed
+    ObservedRemoveSet(ObservedRemoveSet),
+
+    /// Counter with separate per-node increment and decrement tallies
+    ///
+    /// Use for: counters whose increments and decrements are audited
+    PnCounter(PnCounter),
+
+    /// Counter kept within bounds by per-node escrowed rights
+    ///
+    /// Use for: counters that must respect an invariant (stock, seats)
+    BoundedCounter(BoundedCounter),
+
+    /// Timestamped value of a last-writer-wins register
+    ///
+    /// Use for: overwritable scalar fields
+    LwwRegister(LwwRegister),
+
+    /// Map from string keys to values merged entry by entry
+    ///
+    /// Use for: per-key state in one value (per-user counters)
+    Map(HashMap<String, AlgebraicValue>),
+
     /// Boolean value
     ///
     /// Use for: flags with OR (union) or AND (intersect) semantics
@@ -279,6 +406,30 @@ impl AlgebraicValue {
         Self::IntSet(iter.into_iter().collect())
     }
 
+    /// Create a map from `(key, value)` pairs.
+    pub fn map<I, K>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = (K, AlgebraicValue)>,
+        K: Into<String>,
+    {
+        Self::Map(iter.into_iter().map(|(k, v)| (k.into(), v)).collect())
+    }
+
+    /// Create a decimal value from its mantissa and scale
+    /// (`mantissa / 10^scale`).
+    pub fn decimal(mantissa: i128, scale: u32) -> Result<Self, DecimalError> {
+        Decimal::new(mantissa, scale).map(Self::Decimal)
+    }
+
+    /// Exact value as a decimal: integers convert, floats do not.
+    pub fn as_decimal(&self) -> Option<Decimal> {
+        match self {
+            Self::Decimal(d) => Some(*d),
+            Self::Integer(v) => Some(Decimal::from(*v)),
+            _ => None,
+        }
+    }
+
     /// Create a boolean value.
     #[inline]
     pub fn boolean(v: bool) -> Self {
@@ -296,23 +447,29 @@ impl AlgebraicValue {
         match self {
             Self::Integer(_) => "Integer",
             Self::Float(_) => "Float",
+            Self::Decimal(_) => "Decimal",
             Self::StringSet(_) => "StringSet",
             Self::IntSet(_) => "IntSet",
+            Self::ObservedRemoveSet(_) => "ObservedRemoveSet",
+            Self::PnCounter(_) => "PnCounter",
+            Self::BoundedCounter(_) => "BoundedCounter",
+            Self::LwwRegister(_) => "LwwRegister",
+            Self::Map(_) => "Map",
             Self::Boolean(_) => "Boolean",
             Self::Null => "Null",
         }
     }
 
-    /// Check if this is a numeric type (Integer or Float).
+    /// Check if this is a numeric type (Integer, Float or Decimal).
     #[inline]
     pub fn is_numeric(&self) -> bool {
-        matches!(self, Self::Integer(_) | Self::Float(_))
+        matches!(self, Self::Integer(_) | Self::Float(_) | Self::Decimal(_))
     }
 
-    /// Check if this is a set type (StringSet or IntSet).
+    /// Check if this is a set type (StringSet, IntSet or ObservedRemoveSet).
     #[inline]
     pub fn is_set(&self) -> bool {
-        matches!(self, Self::StringSet(_) | Self::IntSet(_))
+        matches!(self, Self::StringSet(_) | Self::IntSet(_) | Self::ObservedRemoveSet(_))
     }
 
     /// Check if this is null.
@@ -369,6 +526,7 @@ impl std::fmt::Display for AlgebraicValue {
         match self {
             Self::Integer(v) => write!(f, "{}", v),
             Self::Float(v) => write!(f, "{}", v),
+            Self::Decimal(d) => write!(f, "{}", d),
             Self::StringSet(s) => {
                 let items: Vec<_> = s.iter().take(5).collect();
                 if s.len() > 5 {
@@ -385,6 +543,26 @@ impl std::fmt::Display for AlgebraicValue {
                     write!(f, "{:?}", items)
                 }
             }
+            Self::ObservedRemoveSet(s) => {
+                let items: Vec<_> = s.elements().take(5).collect();
+                if s.len() > 5 {
+                    write!(f, "{{{:?}... ({} total)}}", items, s.len())
+                } else {
+                    write!(f, "{:?}", items)
+                }
+            }
+            Self::PnCounter(c) => write!(f, "{}", c.value()),
+            Self::BoundedCounter(c) => write!(f, "{}", c.value()),
+            Self::LwwRegister(r) => write!(f, "{}", r.value()),
+            Self::Map(m) => {
+                let keys: BTreeSet<&String> = m.keys().collect();
+                let items: Vec<String> = keys.iter().take(5).map(|k| format!("{:?}: {}", k, m[*k])).collect();
+                if m.len() > 5 {
+                    write!(f, "{{{}... ({} total)}}", items.join(", "), m.len())
+                } else {
+                    write!(f, "{{{}}}", items.join(", "))
+                }
+            }
             Self::Boolean(v) => write!(f, "{}", v),
             Self::Null => write!(f, "null"),
         }
@@ -434,12 +612,739 @@ impl From<HashSet<i64>> for AlgebraicValue {
     }
 }
 
+impl From<Decimal> for AlgebraicValue {
+    fn from(v: Decimal) -> Self {
+        Self::Decimal(v)
+    }
+}
+
+impl From<HashMap<String, AlgebraicValue>> for AlgebraicValue {
+    fn from(v: HashMap<String, AlgebraicValue>) -> Self {
+        Self::Map(v)
+    }
+}
+
 impl<const N: usize> From<[&str; N]> for AlgebraicValue {
     fn from(arr: [&str; N]) -> Self {
         Self::StringSet(arr.iter().map(|s| s.to_string()).collect())
     }
 }
 
+impl From<ObservedRemoveSet> for AlgebraicValue {
+    fn from(v: ObservedRemoveSet) -> Self {
+        Self::ObservedRemoveSet(v)
+    }
+}
+
+impl From<PnCounter> for AlgebraicValue {
+    fn from(v: PnCounter) -> Self {
+        Self::PnCounter(v)
+    }
+}
+
+impl From<BoundedCounter> for AlgebraicValue {
+    fn from(v: BoundedCounter) -> Self {
+        Self::BoundedCounter(v)
+    }
+}
+
+impl From<LwwRegister> for AlgebraicValue {
+    fn from(v: LwwRegister) -> Self {
+        Self::LwwRegister(v)
+    }
+}
+
+/// An observed-remove set (OR-Set) of strings.
+///
+/// Every add carries a unique tag; a removal tombstones the tags it has
+/// observed for the element. An element is present while any of its tags
+/// is live, so an add concurrent with a removal survives the merge
+/// (add-wins). Merging unions both the tags and the tombstones, which is a
+/// semilattice join.
+///
+/// Adds and removals are usually sent as deltas: [`ObservedRemoveSet::added`]
+/// for an add, [`ObservedRemoveSet::removal`] for a remove, each merged into
+/// the replica's state.
+///
+/// # Example
+/// ```
+/// use rhizo_core::algebraic::{AlgebraicMerger, AlgebraicValue, ObservedRemoveSet, OpType};
+///
+/// let mut cart = ObservedRemoveSet::new();
+/// cart.add("apple");
+/// cart.add("pear");
+///
+/// // One replica removes "apple" while another adds it again
+/// let removal = cart.removal("apple");
+/// let re_add = ObservedRemoveSet::added("apple");
+///
+/// let merged = AlgebraicMerger::merge(
+///     OpType::SemilatticeObservedRemove,
+///     &AlgebraicValue::from(cart.merge(&removal)),
+///     &AlgebraicValue::from(re_add),
+/// )
+/// .unwrap();
+/// if let AlgebraicValue::ObservedRemoveSet(s) = merged {
+///     assert!(s.contains("apple"));
+///     assert!(s.contains("pear"));
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct ObservedRemoveSet {
+    /// Element -> live add tags
+    adds: BTreeMap<String, BTreeSet<String>>,
+    /// Tags of removed adds
+    removed: BTreeSet<String>,
+}
+
+impl ObservedRemoveSet {
+    /// Create an empty set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A delta adding one element under a fresh tag.
+    pub fn added(element: impl Into<String>) -> Self {
+        let mut delta = Self::new();
+        delta.add(element);
+        delta
+    }
+
+    /// Add an element under a fresh (random) tag.
+    pub fn add(&mut self, element: impl Into<String>) {
+        self.add_with_tag(element, uuid::Uuid::new_v4().to_string());
+    }
+
+    /// Add an element under an explicit tag.
+    ///
+    /// Tags must be unique per add across all replicas (for example the
+    /// node id plus a local counter); reusing a removed tag has no effect.
+    pub fn add_with_tag(&mut self, element: impl Into<String>, tag: impl Into<String>) {
+        let tag = tag.into();
+        if !self.removed.contains(&tag) {
+            self.adds.entry(element.into()).or_default().insert(tag);
+        }
+    }
+
+    /// A delta removing the element's adds observed by this replica.
+    pub fn removal(&self, element: &str) -> Self {
+        Self {
+            adds: BTreeMap::new(),
+            removed: self.adds.get(element).cloned().unwrap_or_default(),
+        }
+    }
+
+    /// Remove an element (every add of it observed so far).
+    pub fn remove(&mut self, element: &str) {
+        if let Some(tags) = self.adds.remove(element) {
+            self.removed.extend(tags);
+        }
+    }
+
+    /// Check if an element is present.
+    pub fn contains(&self, element: &str) -> bool {
+        self.adds.contains_key(element)
+    }
+
+    /// Present elements, in sorted order.
+    pub fn elements(&self) -> impl Iterator<Item = &str> {
+        self.adds.keys().map(String::as_str)
+    }
+
+    /// Number of present elements.
+    pub fn len(&self) -> usize {
+        self.adds.len()
+    }
+
+    /// Check if no element is present.
+    pub fn is_empty(&self) -> bool {
+        self.adds.is_empty()
+    }
+
+    /// Number of tombstoned tags retained for merging.
+    pub fn tombstone_count(&self) -> usize {
+        self.removed.len()
+    }
+
+    /// Join of two sets: all adds and all removals of either.
+    pub fn merge(&self, other: &ObservedRemoveSet) -> ObservedRemoveSet {
+        let removed: BTreeSet<String> = self.removed.union(&other.removed).cloned().collect();
+        let mut adds: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+        for (element, tags) in self.adds.iter().chain(other.adds.iter()) {
+            let live: Vec<&String> = tags.iter().filter(|t| !removed.contains(*t)).collect();
+            if !live.is_empty() {
+                adds.entry(element.clone())
+                    .or_default()
+                    .extend(live.into_iter().cloned());
+            }
+        }
+        ObservedRemoveSet { adds, removed }
+    }
+}
+
+/// A PN-Counter: an increment tally and a decrement tally per node.
+///
+/// A node only ever raises its own tallies, so merging two counters keeps
+/// the larger tally per node (a semilattice join) and re-delivering a
+/// counter has no effect. The effective value is the sum of increments
+/// minus the sum of decrements; unlike a mixed `AbelianAdd` integer, the
+/// increments and decrements stay separately auditable.
+///
+/// Updates are usually sent as deltas: [`PnCounter::node_delta`] carries one
+/// node's current tallies.
+///
+/// # Example
+/// ```
+/// use rhizo_core::algebraic::{AlgebraicMerger, AlgebraicValue, OpType, PnCounter};
+///
+/// let mut east = PnCounter::new();
+/// east.increment("east", 10);
+/// let mut west = PnCounter::new();
+/// west.decrement("west", 3);
+///
+/// let merged = AlgebraicMerger::merge(
+///     OpType::SemilatticePnCounter,
+///     &AlgebraicValue::from(east),
+///     &AlgebraicValue::from(west),
+/// )
+/// .unwrap();
+/// if let AlgebraicValue::PnCounter(c) = merged {
+///     assert_eq!(c.value(), 7);
+///     assert_eq!((c.increments(), c.decrements()), (10, 3));
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct PnCounter {
+    /// Node -> total incremented by that node
+    positive: BTreeMap<String, u64>,
+    /// Node -> total decremented by that node
+    negative: BTreeMap<String, u64>,
+}
+
+impl PnCounter {
+    /// Create a counter at zero.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add `amount` to `node`'s increment tally.
+    pub fn increment(&mut self, node: impl Into<String>, amount: u64) {
+        let tally = self.positive.entry(node.into()).or_default();
+        *tally = tally.saturating_add(amount);
+    }
+
+    /// Add `amount` to `node`'s decrement tally.
+    pub fn decrement(&mut self, node: impl Into<String>, amount: u64) {
+        let tally = self.negative.entry(node.into()).or_default();
+        *tally = tally.saturating_add(amount);
+    }
+
+    /// Effective value: total increments minus total decrements, saturated
+    /// to the i64 range.
+    pub fn value(&self) -> i64 {
+        let value = self.increments() as i128 - self.decrements() as i128;
+        value.clamp(i64::MIN as i128, i64::MAX as i128) as i64
+    }
+
+    /// Total incremented across all nodes.
+    pub fn increments(&self) -> u64 {
+        self.positive.values().fold(0, |acc, v| acc.saturating_add(*v))
+    }
+
+    /// Total decremented across all nodes.
+    pub fn decrements(&self) -> u64 {
+        self.negative.values().fold(0, |acc, v| acc.saturating_add(*v))
+    }
+
+    /// `node`'s increment and decrement tallies.
+    pub fn node_tallies(&self, node: &str) -> (u64, u64) {
+        (
+            self.positive.get(node).copied().unwrap_or(0),
+            self.negative.get(node).copied().unwrap_or(0),
+        )
+    }
+
+    /// Nodes with a tally, in sorted order.
+    pub fn nodes(&self) -> impl Iterator<Item = &str> {
+        let nodes: BTreeSet<&str> = self
+            .positive
+            .keys()
+            .chain(self.negative.keys())
+            .map(String::as_str)
+            .collect();
+        nodes.into_iter()
+    }
+
+    /// A delta carrying only `node`'s current tallies.
+    pub fn node_delta(&self, node: &str) -> Self {
+        let pick = |tallies: &BTreeMap<String, u64>| {
+            tallies
+                .get_key_value(node)
+                .map(|(k, v)| (k.clone(), *v))
+                .into_iter()
+                .collect()
+        };
+        Self {
+            positive: pick(&self.positive),
+            negative: pick(&self.negative),
+        }
+    }
+
+    /// Join of two counters: the larger tally per node.
+    pub fn merge(&self, other: &PnCounter) -> PnCounter {
+        let join = |a: &BTreeMap<String, u64>, b: &BTreeMap<String, u64>| {
+            let mut joined = a.clone();
+            for (node, tally) in b {
+                let entry = joined.entry(node.clone()).or_default();
+                *entry = (*entry).max(*tally);
+            }
+            joined
+        };
+        PnCounter {
+            positive: join(&self.positive, &other.positive),
+            negative: join(&self.negative, &other.negative),
+        }
+    }
+}
+
+/// Inclusive bounds on a counter's value; `None` leaves that side open.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct Bounds {
+    pub lower: Option<i64>,
+    pub upper: Option<i64>,
+}
+
+impl Bounds {
+    pub fn new(lower: Option<i64>, upper: Option<i64>) -> Self {
+        Self { lower, upper }
+    }
+
+    /// Bounds with only a lower limit (e.g. `at_least(0)` for stock)
+    pub fn at_least(lower: i64) -> Self {
+        Self::new(Some(lower), None)
+    }
+
+    pub fn contains(&self, value: i64) -> bool {
+        self.lower.is_none_or(|l| value >= l) && self.upper.is_none_or(|u| value <= u)
+    }
+
+    /// Whether a numeric value (or a counter's value) lies within the
+    /// bounds. Null is within any bounds; other values are not.
+    pub fn contains_value(&self, value: &AlgebraicValue) -> bool {
+        match value {
+            AlgebraicValue::Integer(v) => self.contains(*v),
+            AlgebraicValue::Float(v) => {
+                self.lower.is_none_or(|l| *v >= l as f64) && self.upper.is_none_or(|u| *v <= u as f64)
+            }
+            AlgebraicValue::Decimal(d) => {
+                self.lower.is_none_or(|l| *d >= Decimal::from(l))
+                    && self.upper.is_none_or(|u| *d <= Decimal::from(u))
+            }
+            AlgebraicValue::PnCounter(c) => self.contains(c.value()),
+            AlgebraicValue::BoundedCounter(c) => self.contains(c.value()),
+            AlgebraicValue::Null => true,
+            _ => false,
+        }
+    }
+}
+
+impl std::fmt::Display for Bounds {
+    /// Range syntax: `0..=100`, `0..`, `..=100`, `..`
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(lower) = self.lower {
+            write!(f, "{}", lower)?;
+        }
+        match self.upper {
+            Some(upper) => write!(f, "..={}", upper),
+            None => write!(f, ".."),
+        }
+    }
+}
+
+/// Errors from updating a [`BoundedCounter`].
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum BoundedCounterError {
+    #[error("Value {value} is outside the bounds {bounds}")]
+    OutOfBounds { value: i64, bounds: Bounds },
+
+    #[error(
+        "Node '{node}' holds {available} {direction} rights but needs {requested}; \
+         transfer rights from another node first"
+    )]
+    InsufficientRights {
+        node: String,
+        /// "increment" or "decrement"
+        direction: &'static str,
+        requested: u64,
+        available: u64,
+    },
+}
+
+impl ErrorInfo for BoundedCounterError {
+    fn code(&self) -> &'static str {
+        match self {
+            Self::OutOfBounds { .. } => "BOUNDED_COUNTER_OUT_OF_BOUNDS",
+            Self::InsufficientRights { .. } => "BOUNDED_COUNTER_INSUFFICIENT_RIGHTS",
+        }
+    }
+
+    fn category(&self) -> ErrorCategory {
+        match self {
+            Self::OutOfBounds { .. } => ErrorCategory::InvalidArgument,
+            Self::InsufficientRights { .. } => ErrorCategory::FailedPrecondition,
+        }
+    }
+
+    fn context(&self) -> ErrorContext {
+        ErrorContext::new()
+    }
+}
+
+/// A counter kept within [`Bounds`] without coordination, by escrow.
+///
+/// The slack between the initial value and each bound is a budget of
+/// rights: decrementing by `n` spends `n` decrement rights, incrementing
+/// spends increment rights. The node that creates the counter holds the
+/// whole budget; it hands parts of it to other nodes with
+/// [`transfer_decrement_rights`](Self::transfer_decrement_rights) /
+/// [`transfer_increment_rights`](Self::transfer_increment_rights). A node's
+/// increments also earn it decrement rights, and its decrements increment
+/// rights.
+///
+/// The rights of all nodes always add up to the distance from the value to
+/// each bound, so as long as every node only spends rights it holds, the
+/// merged value stays within the bounds, whatever order updates arrive in.
+/// Each node updates only under its own name; a node that runs out must
+/// receive a transfer (which needs contact with a node that has rights to
+/// spare) before it can update again.
+///
+/// Tallies and transfers only grow, so merging keeps the larger of each,
+/// like a [`PnCounter`].
+///
+/// # Example
+/// ```
+/// use rhizo_core::algebraic::{AlgebraicMerger, AlgebraicValue, BoundedCounter, Bounds, OpType};
+///
+/// // 10 items in stock; stock can't go negative
+/// let mut east = BoundedCounter::new("east", 10, Bounds::at_least(0)).unwrap();
+/// east.transfer_decrement_rights("east", "west", 4).unwrap();
+/// let mut west = east.clone();
+///
+/// east.decrement("east", 6).unwrap();
+/// west.decrement("west", 4).unwrap();
+/// assert!(west.decrement("west", 1).is_err()); // west's share is spent
+///
+/// let merged = AlgebraicMerger::merge(OpType::BoundedAdd, &east.into(), &west.into()).unwrap();
+/// assert_eq!(merged.to_string(), "0");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BoundedCounter {
+    bounds: Bounds,
+    /// Value the counter was created with
+    initial: i64,
+    /// Node that created the counter and was given the whole budget
+    origin: String,
+    /// Per-node increments and decrements
+    counter: PnCounter,
+    /// Giver -> receiver -> total decrement rights transferred
+    decrement_transfers: BTreeMap<String, BTreeMap<String, u64>>,
+    /// Giver -> receiver -> total increment rights transferred
+    increment_transfers: BTreeMap<String, BTreeMap<String, u64>>,
+}
+
+impl BoundedCounter {
+    /// A counter at `initial` whose whole budget is held by `origin`.
+    ///
+    /// # Errors
+    /// `OutOfBounds` if `initial` is not within `bounds`.
+    pub fn new(
+        origin: impl Into<String>,
+        initial: i64,
+        bounds: Bounds,
+    ) -> Result<Self, BoundedCounterError> {
+        if !bounds.contains(initial) {
+            return Err(BoundedCounterError::OutOfBounds { value: initial, bounds });
+        }
+        Ok(Self {
+            bounds,
+            initial,
+            origin: origin.into(),
+            counter: PnCounter::new(),
+            decrement_transfers: BTreeMap::new(),
+            increment_transfers: BTreeMap::new(),
+        })
+    }
+
+    pub fn bounds(&self) -> Bounds {
+        self.bounds
+    }
+
+    pub fn initial(&self) -> i64 {
+        self.initial
+    }
+
+    /// Node that was given the whole budget
+    pub fn origin(&self) -> &str {
+        &self.origin
+    }
+
+    /// Current value: the initial value plus all increments, minus all
+    /// decrements.
+    pub fn value(&self) -> i64 {
+        let value = self.initial as i128 + self.counter.value() as i128;
+        value.clamp(i64::MIN as i128, i64::MAX as i128) as i64
+    }
+
+    /// Per-node increment and decrement tallies
+    pub fn tallies(&self) -> &PnCounter {
+        &self.counter
+    }
+
+    /// Add `amount` as `node`, spending its increment rights.
+    pub fn increment(&mut self, node: impl Into<String>, amount: u64) -> Result<(), BoundedCounterError> {
+        let node = node.into();
+        self.require(&node, "increment", self.increment_rights(&node), amount)?;
+        self.counter.increment(node, amount);
+        Ok(())
+    }
+
+    /// Subtract `amount` as `node`, spending its decrement rights.
+    pub fn decrement(&mut self, node: impl Into<String>, amount: u64) -> Result<(), BoundedCounterError> {
+        let node = node.into();
+        self.require(&node, "decrement", self.decrement_rights(&node), amount)?;
+        self.counter.decrement(node, amount);
+        Ok(())
+    }
+
+    /// Decrement rights `node` holds, or `None` with no lower bound.
+    pub fn decrement_rights(&self, node: &str) -> Option<u64> {
+        self.raw_decrement_rights(node).map(Self::non_negative)
+    }
+
+    /// Increment rights `node` holds, or `None` with no upper bound.
+    pub fn increment_rights(&self, node: &str) -> Option<u64> {
+        self.raw_increment_rights(node).map(Self::non_negative)
+    }
+
+    /// Give `amount` of `from`'s decrement rights to `to`. A no-op without
+    /// a lower bound.
+    pub fn transfer_decrement_rights(
+        &mut self,
+        from: &str,
+        to: &str,
+        amount: u64,
+    ) -> Result<(), BoundedCounterError> {
+        if self.bounds.lower.is_none() || from == to {
+            return Ok(());
+        }
+        self.require(from, "decrement", self.decrement_rights(from), amount)?;
+        Self::record_transfer(&mut self.decrement_transfers, from, to, amount);
+        Ok(())
+    }
+
+    /// Give `amount` of `from`'s increment rights to `to`. A no-op without
+    /// an upper bound.
+    pub fn transfer_increment_rights(
+        &mut self,
+        from: &str,
+        to: &str,
+        amount: u64,
+    ) -> Result<(), BoundedCounterError> {
+        if self.bounds.upper.is_none() || from == to {
+            return Ok(());
+        }
+        self.require(from, "increment", self.increment_rights(from), amount)?;
+        Self::record_transfer(&mut self.increment_transfers, from, to, amount);
+        Ok(())
+    }
+
+    /// Check that no node has spent more rights than it held and that the
+    /// value is within the bounds.
+    pub fn validate(&self) -> Result<(), BoundedCounterError> {
+        for node in self.nodes() {
+            for (direction, rights) in [
+                ("decrement", self.raw_decrement_rights(node)),
+                ("increment", self.raw_increment_rights(node)),
+            ] {
+                if let Some(rights) = rights.filter(|r| *r < 0) {
+                    return Err(BoundedCounterError::InsufficientRights {
+                        node: node.to_string(),
+                        direction,
+                        requested: Self::non_negative(-rights),
+                        available: 0,
+                    });
+                }
+            }
+        }
+        if !self.bounds.contains(self.value()) {
+            return Err(BoundedCounterError::OutOfBounds { value: self.value(), bounds: self.bounds });
+        }
+        Ok(())
+    }
+
+    /// Whether `other` is a replica of the same counter (same bounds,
+    /// initial value and origin), so the two can be merged.
+    pub fn is_replica_of(&self, other: &BoundedCounter) -> bool {
+        self.bounds == other.bounds && self.initial == other.initial && self.origin == other.origin
+    }
+
+    /// Join of two replicas: the larger tally and transfer total per node.
+    /// Both must be replicas of the same counter (see
+    /// [`is_replica_of`](Self::is_replica_of)); `self`'s configuration is kept.
+    pub fn merge(&self, other: &BoundedCounter) -> BoundedCounter {
+        let join = |a: &BTreeMap<String, BTreeMap<String, u64>>, b: &BTreeMap<String, BTreeMap<String, u64>>| {
+            let mut joined = a.clone();
+            for (from, given) in b {
+                let entry = joined.entry(from.clone()).or_default();
+                for (to, amount) in given {
+                    let total = entry.entry(to.clone()).or_default();
+                    *total = (*total).max(*amount);
+                }
+            }
+            joined
+        };
+        BoundedCounter {
+            bounds: self.bounds,
+            initial: self.initial,
+            origin: self.origin.clone(),
+            counter: self.counter.merge(&other.counter),
+            decrement_transfers: join(&self.decrement_transfers, &other.decrement_transfers),
+            increment_transfers: join(&self.increment_transfers, &other.increment_transfers),
+        }
+    }
+
+    /// Every node with tallies or transfers, plus the origin.
+    fn nodes(&self) -> BTreeSet<&str> {
+        let mut nodes: BTreeSet<&str> = self.counter.nodes().collect();
+        nodes.insert(&self.origin);
+        for transfers in [&self.decrement_transfers, &self.increment_transfers] {
+            for (from, given) in transfers {
+                nodes.insert(from);
+                nodes.extend(given.keys().map(String::as_str));
+            }
+        }
+        nodes
+    }
+
+    /// Rights without clamping; negative when a node overspent.
+    fn raw_decrement_rights(&self, node: &str) -> Option<i128> {
+        let slack = self.bounds.lower.map(|l| self.initial as i128 - l as i128)?;
+        let (increments, decrements) = self.counter.node_tallies(node);
+        Some(self.rights(node, slack, &self.decrement_transfers) + increments as i128 - decrements as i128)
+    }
+
+    fn raw_increment_rights(&self, node: &str) -> Option<i128> {
+        let slack = self.bounds.upper.map(|u| u as i128 - self.initial as i128)?;
+        let (increments, decrements) = self.counter.node_tallies(node);
+        Some(self.rights(node, slack, &self.increment_transfers) + decrements as i128 - increments as i128)
+    }
+
+    /// `node`'s share of the initial slack plus the rights it received,
+    /// minus the rights it gave away.
+    fn rights(&self, node: &str, slack: i128, transfers: &BTreeMap<String, BTreeMap<String, u64>>) -> i128 {
+        let initial = if node == self.origin { slack } else { 0 };
+        let received: i128 = transfers.values().filter_map(|given| given.get(node)).map(|a| *a as i128).sum();
+        let sent: i128 = transfers.get(node).map_or(0, |given| given.values().map(|a| *a as i128).sum());
+        initial + received - sent
+    }
+
+    fn record_transfer(
+        transfers: &mut BTreeMap<String, BTreeMap<String, u64>>,
+        from: &str,
+        to: &str,
+        amount: u64,
+    ) {
+        let total = transfers.entry(from.to_string()).or_default().entry(to.to_string()).or_default();
+        *total = total.saturating_add(amount);
+    }
+
+    fn require(
+        &self,
+        node: &str,
+        direction: &'static str,
+        available: Option<u64>,
+        requested: u64,
+    ) -> Result<(), BoundedCounterError> {
+        match available {
+            Some(available) if available < requested => Err(BoundedCounterError::InsufficientRights {
+                node: node.to_string(),
+                direction,
+                requested,
+                available,
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    fn non_negative(rights: i128) -> u64 {
+        rights.clamp(0, u64::MAX as i128) as u64
+    }
+}
+
+/// A write to a last-writer-wins register: a value stamped with when and
+/// where it was written.
+///
+/// Of two writes, the one with the greater timestamp wins; equal
+/// timestamps are ordered by node id, so every replica picks the same
+/// winner whatever order writes arrive in. Timestamps are caller-supplied
+/// (e.g. milliseconds since the epoch, or a hybrid logical clock's wall
+/// time) and should be monotonic per node.
+///
+/// # Example
+/// ```
+/// use rhizo_core::algebraic::{AlgebraicMerger, AlgebraicValue, LwwRegister, OpType};
+///
+/// let older = LwwRegister::new(AlgebraicValue::integer(10), 1_000, "node-a");
+/// let newer = LwwRegister::new(AlgebraicValue::integer(12), 1_005, "node-b");
+///
+/// let merged = AlgebraicMerger::merge(OpType::LwwRegister, &newer.into(), &older.into()).unwrap();
+/// assert_eq!(merged.to_string(), "12");
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LwwRegister {
+    timestamp: u64,
+    node: String,
+    value: Box<AlgebraicValue>,
+}
+
+impl LwwRegister {
+    /// A write of `value` by `node` at `timestamp`.
+    pub fn new(value: AlgebraicValue, timestamp: u64, node: impl Into<String>) -> Self {
+        Self {
+            timestamp,
+            node: node.into(),
+            value: Box::new(value),
+        }
+    }
+
+    pub fn timestamp(&self) -> u64 {
+        self.timestamp
+    }
+
+    /// Node that made the write
+    pub fn node(&self) -> &str {
+        &self.node
+    }
+
+    /// The written value
+    pub fn value(&self) -> &AlgebraicValue {
+        &self.value
+    }
+
+    pub fn into_value(self) -> AlgebraicValue {
+        *self.value
+    }
+
+    /// Order of two writes: by timestamp, then node id.
+    ///
+    /// `Equal` means the same (timestamp, node) stamp, which should only
+    /// happen for the same write.
+    pub fn cmp_stamp(&self, other: &LwwRegister) -> std::cmp::Ordering {
+        (self.timestamp, &self.node).cmp(&(other.timestamp, &other.node))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -504,6 +1409,117 @@ mod tests {
         assert_eq!(OpType::GenericOverwrite.to_string(), "OVERWRITE");
     }
 
+    #[test]
+    fn test_observed_remove_set_add_wins() {
+        let mut base = ObservedRemoveSet::new();
+        base.add_with_tag("apple", "a1");
+        base.add_with_tag("pear", "a2");
+
+        // Concurrently: one replica removes apple, another re-adds it
+        let mut removed = base.clone();
+        removed.remove("apple");
+        let mut readded = base.clone();
+        readded.add_with_tag("apple", "b1");
+
+        let merged = removed.merge(&readded);
+        assert_eq!(merged, readded.merge(&removed));
+        assert!(merged.contains("apple"));
+        assert_eq!(merged.elements().collect::<Vec<_>>(), vec!["apple", "pear"]);
+
+        // A removal that observed the re-add wins
+        let merged = merged.merge(&merged.removal("apple"));
+        assert!(!merged.contains("apple"));
+        assert_eq!(merged.len(), 1);
+        assert_eq!(merged.tombstone_count(), 2);
+
+        // Idempotent
+        assert_eq!(merged.merge(&merged), merged);
+    }
+
+    #[test]
+    fn test_pn_counter_merge_keeps_tallies_apart() {
+        let mut a = PnCounter::new();
+        a.increment("a", 5);
+        a.decrement("a", 2);
+        let mut b = PnCounter::new();
+        b.increment("b", 1);
+        b.decrement("b", 7);
+
+        let merged = a.merge(&b);
+        assert_eq!(merged, b.merge(&a));
+        assert_eq!(merged.merge(&merged), merged);
+        assert_eq!(merged.value(), -3);
+        assert_eq!((merged.increments(), merged.decrements()), (6, 9));
+        assert_eq!(merged.node_tallies("a"), (5, 2));
+        assert_eq!(merged.nodes().collect::<Vec<_>>(), vec!["a", "b"]);
+
+        // Re-delivering a stale delta does not double count
+        let stale = a.node_delta("a");
+        a.increment("a", 1);
+        let merged = merged.merge(&a.node_delta("a")).merge(&stale);
+        assert_eq!(merged.node_tallies("a"), (6, 2));
+        assert_eq!(merged.value(), -2);
+    }
+
+    #[test]
+    fn test_bounded_counter_escrow() {
+        let mut stock = BoundedCounter::new("a", 10, Bounds::new(Some(0), Some(15))).unwrap();
+        assert_eq!((stock.decrement_rights("a"), stock.increment_rights("a")), (Some(10), Some(5)));
+        assert_eq!(stock.decrement_rights("b"), Some(0));
+        assert!(BoundedCounter::new("a", -1, Bounds::at_least(0)).is_err());
+
+        // Rights move between nodes, and a node cannot give away more than it holds
+        stock.transfer_decrement_rights("a", "b", 4).unwrap();
+        assert!(stock.transfer_decrement_rights("b", "c", 5).is_err());
+        let mut b = stock.clone();
+        stock.decrement("a", 6).unwrap();
+        b.decrement("b", 4).unwrap();
+        assert!(matches!(
+            b.decrement("b", 1),
+            Err(BoundedCounterError::InsufficientRights { requested: 1, available: 0, .. })
+        ));
+
+        // Decrements earn increment rights and vice versa
+        assert_eq!(b.increment_rights("b"), Some(4));
+        b.increment("b", 2).unwrap();
+        assert_eq!(b.decrement_rights("b"), Some(2));
+
+        let merged = stock.merge(&b);
+        assert_eq!(merged, b.merge(&stock));
+        assert_eq!(merged.merge(&merged), merged);
+        assert_eq!(merged.value(), 2);
+        merged.validate().unwrap();
+
+        // Spending without rights (e.g. a forged replica) fails validation
+        let mut forged = merged.clone();
+        forged.counter.decrement("c", 3);
+        assert!(forged.validate().is_err());
+
+        // An open side needs no rights
+        let mut open = BoundedCounter::new("a", 0, Bounds::at_least(0)).unwrap();
+        assert_eq!(open.increment_rights("z"), None);
+        open.increment("z", 100).unwrap();
+        assert_eq!(open.decrement_rights("z"), Some(100));
+        assert_eq!(Bounds::new(Some(0), Some(15)).to_string(), "0..=15");
+        assert_eq!(Bounds::default().to_string(), "..");
+    }
+
+    #[test]
+    fn test_lww_register_stamp_order() {
+        let a = LwwRegister::new(AlgebraicValue::integer(1), 5, "a");
+        let b = LwwRegister::new(AlgebraicValue::integer(2), 5, "b");
+        let c = LwwRegister::new(AlgebraicValue::integer(3), 4, "z");
+
+        assert_eq!(a.cmp_stamp(&b), std::cmp::Ordering::Less);
+        assert_eq!(c.cmp_stamp(&a), std::cmp::Ordering::Less);
+        assert_eq!(b.clone().into_value(), AlgebraicValue::integer(2));
+        assert!(OpType::LwwRegister.is_conflict_free());
+
+        let json = serde_json::to_string(&AlgebraicValue::from(b.clone())).unwrap();
+        let parsed: AlgebraicValue = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, AlgebraicValue::LwwRegister(b));
+    }
+
     #[test]
     fn test_optype_default() {
         assert_eq!(OpType::default(), OpType::Unknown);
@@ -0,0 +1,79 @@
+use thiserror::Error;
+
+use crate::branch::BranchError;
+use crate::catalog::CatalogError;
+use crate::chunk_store::ChunkStoreError;
+use crate::error::{ErrorCategory, ErrorContext, ErrorInfo};
+use crate::transaction::TransactionError;
+
+#[derive(Error, Debug)]
+#[non_exhaustive]
+pub enum ArtifactError {
+    #[error("Artifact not found: {0}")]
+    NotFound(String),
+
+    #[error("Artifact version not found: {0} v{1}")]
+    VersionNotFound(String, u64),
+
+    #[error("Invalid artifact name: {0}")]
+    InvalidName(String),
+
+    /// User metadata may not use the keys the artifact store keeps for
+    /// itself
+    #[error("Reserved artifact metadata key: {0}")]
+    ReservedMetadataKey(String),
+
+    #[error("Catalog error: {0}")]
+    Catalog(#[from] CatalogError),
+
+    #[error("Chunk store error: {0}")]
+    ChunkStore(#[from] ChunkStoreError),
+
+    #[error("Transaction error: {0}")]
+    Transaction(#[from] TransactionError),
+
+    #[error("Branch error: {0}")]
+    Branch(#[from] BranchError),
+}
+
+impl ErrorInfo for ArtifactError {
+    fn code(&self) -> &'static str {
+        match self {
+            ArtifactError::NotFound(_) => "ARTIFACT_NOT_FOUND",
+            ArtifactError::VersionNotFound(..) => "ARTIFACT_VERSION_NOT_FOUND",
+            ArtifactError::InvalidName(_) => "ARTIFACT_INVALID_NAME",
+            ArtifactError::ReservedMetadataKey(_) => "ARTIFACT_RESERVED_METADATA_KEY",
+            ArtifactError::Catalog(e) => e.code(),
+            ArtifactError::ChunkStore(e) => e.code(),
+            ArtifactError::Transaction(e) => e.code(),
+            ArtifactError::Branch(e) => e.code(),
+        }
+    }
+
+    fn category(&self) -> ErrorCategory {
+        match self {
+            ArtifactError::NotFound(_) | ArtifactError::VersionNotFound(..) => ErrorCategory::NotFound,
+            ArtifactError::InvalidName(_) | ArtifactError::ReservedMetadataKey(_) => ErrorCategory::InvalidArgument,
+            ArtifactError::Catalog(e) => e.category(),
+            ArtifactError::ChunkStore(e) => e.category(),
+            ArtifactError::Transaction(e) => e.category(),
+            ArtifactError::Branch(e) => e.category(),
+        }
+    }
+
+    fn context(&self) -> ErrorContext {
+        match self {
+            ArtifactError::NotFound(name) | ArtifactError::InvalidName(name) => {
+                ErrorContext::new().with_table(name.clone())
+            }
+            ArtifactError::VersionNotFound(name, version) => {
+                ErrorContext::new().with_table(name.clone()).with_version(*version)
+            }
+            ArtifactError::ReservedMetadataKey(key) => ErrorContext::new().with_key(key.clone()),
+            ArtifactError::Catalog(e) => e.context(),
+            ArtifactError::ChunkStore(e) => e.context(),
+            ArtifactError::Transaction(e) => e.context(),
+            ArtifactError::Branch(e) => e.context(),
+        }
+    }
+}
@@ -0,0 +1,15 @@
+//! Versioned non-tabular artifacts (ML models, config blobs, ...).
+//!
+//! An artifact is a named byte sequence with a version history. Each
+//! version is stored exactly like a table version: its bytes as chunks in
+//! the [`ChunkStore`](crate::ChunkStore), its chunk list and metadata as a
+//! catalog version of the table `_artifacts/{name}`, written through the
+//! transaction manager. Artifacts therefore get the same semantics as
+//! tables: deduplicated storage, branch-scoped heads, changelog entries,
+//! tags, pins and garbage collection.
+
+pub mod error;
+pub mod store;
+
+pub use error::ArtifactError;
+pub use store::{artifact_table, Artifact, ArtifactStore, ARTIFACT_NAMESPACE, DEFAULT_ARTIFACT_CHUNK_SIZE};
@@ -0,0 +1,315 @@
+use std::collections::HashMap;
+
+use super::error::ArtifactError;
+use crate::catalog::{namespace, CatalogError, TableVersion};
+use crate::changelog::{ChangelogEntry, ChangelogQuery};
+use crate::chunk_store::ChunkStore;
+use crate::transaction::{TableWrite, TransactionManager};
+
+/// Catalog namespace holding one table per artifact
+pub const ARTIFACT_NAMESPACE: &str = "_artifacts";
+
+/// Bytes per chunk when [`ArtifactStore::put`] splits an artifact
+pub const DEFAULT_ARTIFACT_CHUNK_SIZE: usize = 4 * 1024 * 1024;
+
+/// Prefix of the metadata keys the store sets itself
+const RESERVED_PREFIX: &str = "rhizo.artifact.";
+const SIZE_KEY: &str = "rhizo.artifact.size";
+
+/// Catalog table storing the versions of artifact `name`
+pub fn artifact_table(name: &str) -> String {
+    format!("{}/{}", ARTIFACT_NAMESPACE, name)
+}
+
+/// One version of an artifact
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Artifact {
+    pub name: String,
+    pub version: u64,
+    /// Chunks holding the bytes, in order
+    pub chunk_hashes: Vec<String>,
+    /// Total size in bytes
+    pub size: u64,
+    /// User metadata (e.g. framework, metrics, content type)
+    pub metadata: HashMap<String, String>,
+    /// Unix timestamp of the commit
+    pub created_at: i64,
+}
+
+impl Artifact {
+    fn from_version(name: &str, mut version: TableVersion) -> Self {
+        let size = version
+            .metadata
+            .get(SIZE_KEY)
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(0);
+        version.metadata.retain(|key, _| !key.starts_with(RESERVED_PREFIX));
+        Self {
+            name: name.to_string(),
+            version: version.version,
+            chunk_hashes: version.chunk_hashes,
+            size,
+            metadata: version.metadata,
+            created_at: version.created_at,
+        }
+    }
+}
+
+/// Reads and writes versioned artifacts on one branch.
+///
+/// Writes go through the transaction manager, so every new version is a
+/// commit on the branch with a changelog entry; reads without an explicit
+/// version resolve to the branch head.
+///
+/// # Example
+///
+/// ```ignore
+/// let artifacts = ArtifactStore::new(&manager, repo.store()).on_branch("experiment");
+/// let model = artifacts.put("models/churn", &weights, HashMap::from([("auc".into(), "0.91".into())]))?;
+/// let bytes = artifacts.read("models/churn", Some(model.version))?;
+/// ```
+pub struct ArtifactStore<'a> {
+    manager: &'a TransactionManager,
+    store: &'a ChunkStore,
+    branch: Option<String>,
+    chunk_size: usize,
+}
+
+impl<'a> ArtifactStore<'a> {
+    pub fn new(manager: &'a TransactionManager, store: &'a ChunkStore) -> Self {
+        Self {
+            manager,
+            store,
+            branch: None,
+            chunk_size: DEFAULT_ARTIFACT_CHUNK_SIZE,
+        }
+    }
+
+    /// Read and write on `branch` instead of the default branch
+    pub fn on_branch(mut self, branch: impl Into<String>) -> Self {
+        self.branch = Some(branch.into());
+        self
+    }
+
+    /// Split artifacts written by [`put`](Self::put) into chunks of this
+    /// many bytes
+    pub fn with_chunk_size(mut self, bytes: usize) -> Self {
+        self.chunk_size = bytes.max(1);
+        self
+    }
+
+    /// Store `data` as the next version of artifact `name`.
+    ///
+    /// # Errors
+    /// - `ArtifactError::InvalidName` if `name` is not a valid table name
+    /// - `ArtifactError::ReservedMetadataKey` if a key starts with
+    ///   `rhizo.artifact.`
+    pub fn put(&self, name: &str, data: &[u8], metadata: HashMap<String, String>) -> Result<Artifact, ArtifactError> {
+        let chunk_hashes = data
+            .chunks(self.chunk_size)
+            .map(|chunk| self.store.put(chunk))
+            .collect::<Result<Vec<_>, _>>()?;
+        self.commit(name, chunk_hashes, data.len() as u64, metadata)
+    }
+
+    /// Commit chunks already in the store as the next version of artifact
+    /// `name`. The chunks are concatenated in order when read; each is read
+    /// and verified here to fail early on a missing or damaged chunk.
+    pub fn put_chunks(
+        &self,
+        name: &str,
+        chunk_hashes: Vec<String>,
+        metadata: HashMap<String, String>,
+    ) -> Result<Artifact, ArtifactError> {
+        let mut size = 0;
+        for hash in &chunk_hashes {
+            size += self.store.get_verified(hash)?.len() as u64;
+        }
+        self.commit(name, chunk_hashes, size, metadata)
+    }
+
+    /// An artifact version, or the branch head when `version` is None
+    pub fn get(&self, name: &str, version: Option<u64>) -> Result<Artifact, ArtifactError> {
+        let table = Self::table(name)?;
+        let catalog = self.manager.catalog();
+        let result = match (version, self.manager.branch_manager()) {
+            (None, Some(branches)) => {
+                let branch = branches.get(&self.branch_name()?)?;
+                catalog.get_version_on_branch(&table, None, &branch)
+            }
+            _ => catalog.get_version(&table, version),
+        };
+        match result {
+            Ok(tv) => Ok(Artifact::from_version(name, tv)),
+            Err(CatalogError::TableNotFound(_)) => Err(ArtifactError::NotFound(name.to_string())),
+            Err(CatalogError::VersionNotFound(_, v)) => Err(ArtifactError::VersionNotFound(name.to_string(), v)),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// The bytes of an artifact version (the branch head when `version` is
+    /// None), verified against their chunk hashes
+    pub fn read(&self, name: &str, version: Option<u64>) -> Result<Vec<u8>, ArtifactError> {
+        let artifact = self.get(name, version)?;
+        let mut data = Vec::with_capacity(artifact.size as usize);
+        for hash in &artifact.chunk_hashes {
+            data.extend_from_slice(&self.store.get_verified(hash)?);
+        }
+        Ok(data)
+    }
+
+    /// Every version of an artifact, oldest first
+    pub fn history(&self, name: &str) -> Result<Vec<Artifact>, ArtifactError> {
+        let table = Self::table(name)?;
+        let catalog = self.manager.catalog();
+        if !catalog.table_exists(&table)? {
+            return Err(ArtifactError::NotFound(name.to_string()));
+        }
+        catalog
+            .list_versions(&table)?
+            .into_iter()
+            .map(|v| Ok(Artifact::from_version(name, catalog.get_version(&table, Some(v))?)))
+            .collect()
+    }
+
+    /// Names of the artifacts visible on the branch, sorted
+    pub fn list(&self) -> Result<Vec<String>, ArtifactError> {
+        let catalog = self.manager.catalog();
+        let tables = match self.manager.branch_manager() {
+            Some(branches) => catalog.list_tables_on_branch(&branches.get(&self.branch_name()?)?)?,
+            None => catalog.list_tables()?,
+        };
+        let prefix = format!("{}/", ARTIFACT_NAMESPACE);
+        let mut names: Vec<String> = tables
+            .iter()
+            .filter_map(|t| t.strip_prefix(&prefix).map(str::to_string))
+            .collect();
+        names.sort();
+        Ok(names)
+    }
+
+    /// Changelog entries that wrote artifact `name`, oldest first
+    pub fn changelog(&self, name: &str) -> Result<Vec<ChangelogEntry>, ArtifactError> {
+        let query = ChangelogQuery::new().for_tables(vec![Self::table(name)?]);
+        Ok(self.manager.get_changelog(query)?)
+    }
+
+    /// Delete an artifact with its whole history, on every branch (see
+    /// `TransactionManager::drop_table`). Returns the removed versions.
+    pub fn delete(&self, name: &str) -> Result<Vec<Artifact>, ArtifactError> {
+        let table = Self::table(name)?;
+        if !self.manager.catalog().table_exists(&table)? {
+            return Err(ArtifactError::NotFound(name.to_string()));
+        }
+        Ok(self
+            .manager
+            .drop_table(&table)?
+            .into_iter()
+            .map(|tv| Artifact::from_version(name, tv))
+            .collect())
+    }
+
+    fn commit(
+        &self,
+        name: &str,
+        chunk_hashes: Vec<String>,
+        size: u64,
+        metadata: HashMap<String, String>,
+    ) -> Result<Artifact, ArtifactError> {
+        let table = Self::table(name)?;
+        if let Some(key) = metadata.keys().find(|k| k.starts_with(RESERVED_PREFIX)) {
+            return Err(ArtifactError::ReservedMetadataKey(key.clone()));
+        }
+
+        let catalog = self.manager.catalog();
+        let next = match catalog.get_version(&table, None) {
+            Ok(latest) => latest.version + 1,
+            Err(CatalogError::TableNotFound(_)) => 1,
+            Err(e) => return Err(e.into()),
+        };
+        let mut write = TableWrite::new(&table, next, chunk_hashes).with_metadata(SIZE_KEY, size.to_string());
+        write.metadata.extend(metadata);
+
+        let tx_id = self.manager.begin(self.branch.as_deref())?;
+        if let Err(e) = self.manager.add_write(tx_id, write) {
+            self.manager.abort(tx_id, "artifact write failed").ok();
+            return Err(e.into());
+        }
+        self.manager.commit(tx_id)?;
+        Ok(Artifact::from_version(name, catalog.get_version(&table, Some(next))?))
+    }
+
+    fn branch_name(&self) -> Result<String, ArtifactError> {
+        match &self.branch {
+            Some(branch) => Ok(branch.clone()),
+            None => Ok(self.manager.default_branch()?),
+        }
+    }
+
+    fn table(name: &str) -> Result<String, ArtifactError> {
+        namespace::validate_table_name(name).map_err(|e| match e {
+            CatalogError::InvalidTableName(reason) => ArtifactError::InvalidName(reason),
+            e => e.into(),
+        })?;
+        Ok(artifact_table(name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repo::Repo;
+
+    #[test]
+    fn test_artifacts_are_versioned_like_tables() {
+        let repo = Repo::ephemeral().unwrap();
+        let manager = repo.transaction_manager().unwrap();
+        let artifacts = ArtifactStore::new(&manager, repo.store()).with_chunk_size(4);
+
+        let meta = HashMap::from([("framework".to_string(), "torch".to_string())]);
+        let v1 = artifacts.put("models/churn", b"weights-v1", meta.clone()).unwrap();
+        assert_eq!((v1.version, v1.size, v1.chunk_hashes.len()), (1, 10, 3));
+        assert_eq!(v1.metadata, meta);
+        let v2 = artifacts.put("models/churn", b"weights-v2", HashMap::new()).unwrap();
+        assert_eq!(v2.version, 2);
+        // Unchanged leading chunks are shared between versions
+        assert_eq!(v1.chunk_hashes[..2], v2.chunk_hashes[..2]);
+
+        assert_eq!(artifacts.read("models/churn", None).unwrap(), b"weights-v2");
+        assert_eq!(artifacts.read("models/churn", Some(1)).unwrap(), b"weights-v1");
+        assert_eq!(artifacts.history("models/churn").unwrap(), vec![v1, v2.clone()]);
+        assert_eq!(artifacts.changelog("models/churn").unwrap().len(), 2);
+
+        let stored = artifacts.put_chunks("config", v2.chunk_hashes.clone(), HashMap::new()).unwrap();
+        assert_eq!(stored.size, 10);
+        assert_eq!(artifacts.list().unwrap(), vec!["config", "models/churn"]);
+
+        assert!(matches!(artifacts.get("models/churn", Some(9)), Err(ArtifactError::VersionNotFound(_, 9))));
+        assert!(matches!(artifacts.get("missing", None), Err(ArtifactError::NotFound(_))));
+        assert!(matches!(artifacts.put("a..b", b"", HashMap::new()), Err(ArtifactError::InvalidName(_))));
+        let reserved = HashMap::from([(SIZE_KEY.to_string(), "1".to_string())]);
+        assert!(matches!(artifacts.put("x", b"", reserved), Err(ArtifactError::ReservedMetadataKey(_))));
+
+        assert_eq!(artifacts.delete("config").unwrap().len(), 1);
+        assert_eq!(artifacts.list().unwrap(), vec!["models/churn"]);
+    }
+
+    #[test]
+    fn test_artifact_heads_follow_branches() {
+        let repo = Repo::ephemeral().unwrap();
+        let manager = repo.transaction_manager().unwrap();
+        let main = ArtifactStore::new(&manager, repo.store());
+        main.put("config", b"retries=3", HashMap::new()).unwrap();
+        repo.branches().create("experiment", Some("main"), None).unwrap();
+        manager.clear_recent_committed().unwrap();
+
+        let experiment = ArtifactStore::new(&manager, repo.store()).on_branch("experiment");
+        experiment.put("config", b"retries=5", HashMap::new()).unwrap();
+        experiment.put("models/new", b"w", HashMap::new()).unwrap();
+
+        assert_eq!(main.read("config", None).unwrap(), b"retries=3");
+        assert_eq!(experiment.read("config", None).unwrap(), b"retries=5");
+        assert_eq!(main.list().unwrap(), vec!["config"]);
+        assert_eq!(experiment.list().unwrap(), vec!["config", "models/new"]);
+    }
+}
@@ -0,0 +1,446 @@
+use std::collections::{BTreeSet, HashSet};
+use std::path::{Path, PathBuf};
+
+use super::error::BackupError;
+use super::manifest::{BackupManifest, CHUNKS_DIR, MANIFEST_FILE};
+use crate::branch::BranchError;
+use crate::catalog::CatalogError;
+use crate::chunk_store::ChunkStore;
+use crate::filter::RepoRef;
+
+/// Result of verifying a backup chain.
+#[derive(Debug, Clone, Default)]
+pub struct VerifyReport {
+    /// Backups in the chain (1 for a full backup)
+    pub backups: usize,
+
+    /// Version records across the chain
+    pub versions: usize,
+
+    /// Chunks read back and hash-checked
+    pub chunks_verified: usize,
+
+    /// Bytes read back and hash-checked
+    pub bytes_verified: u64,
+}
+
+/// Result of a restore.
+#[derive(Debug, Clone, Default)]
+pub struct RestoreReport {
+    /// Backups applied, base first
+    pub backups_applied: usize,
+
+    /// Version records written to the target
+    pub versions_restored: usize,
+
+    /// Chunks written to the target store
+    pub chunks_copied: usize,
+
+    /// Bytes written to the target store
+    pub bytes_copied: u64,
+
+    /// Tables restored
+    pub tables: Vec<String>,
+
+    /// Branches restored
+    pub branches: Vec<String>,
+}
+
+struct ChainLink {
+    dir: PathBuf,
+    manifest: BackupManifest,
+    checksum: String,
+}
+
+/// A backup together with the chain of backups it extends.
+///
+/// Opening a set reads every manifest in the chain and checks its
+/// checksum and parent link; [`BackupSet::verify`] additionally reads back
+/// every chunk.
+pub struct BackupSet {
+    /// Base backup first
+    chain: Vec<ChainLink>,
+}
+
+impl BackupSet {
+    /// Open the backup in `dir` and every backup it extends.
+    pub fn open(dir: impl AsRef<Path>) -> Result<Self, BackupError> {
+        let mut chain = Vec::new();
+        let mut dir = dir.as_ref().to_path_buf();
+        loop {
+            let (manifest, checksum) = BackupManifest::read(&dir)?;
+            let parent = manifest.parent.clone();
+            chain.push(ChainLink {
+                dir: dir.clone(),
+                manifest,
+                checksum,
+            });
+
+            let Some(parent) = parent else { break };
+            let parent_dir = Self::locate_parent(&dir, &parent.path).ok_or_else(|| {
+                BackupError::ParentNotFound {
+                    backup_id: parent.backup_id.clone(),
+                    path: PathBuf::from(&parent.path),
+                }
+            })?;
+
+            let (found, found_checksum) = BackupManifest::read(&parent_dir)?;
+            if found.backup_id != parent.backup_id {
+                return Err(BackupError::ParentMismatch {
+                    expected: parent.backup_id,
+                    found: found.backup_id,
+                });
+            }
+            if found_checksum != parent.manifest_checksum {
+                return Err(BackupError::ParentMismatch {
+                    expected: parent.manifest_checksum,
+                    found: found_checksum,
+                });
+            }
+            dir = parent_dir;
+        }
+
+        chain.reverse();
+        Ok(Self { chain })
+    }
+
+    /// The recorded parent path, or a sibling of `dir` with the same name
+    /// (the chain was moved as a whole)
+    fn locate_parent(dir: &Path, recorded: &str) -> Option<PathBuf> {
+        let recorded = PathBuf::from(recorded);
+        if recorded.join(MANIFEST_FILE).exists() {
+            return Some(recorded);
+        }
+        let sibling = dir.parent()?.join(recorded.file_name()?);
+        sibling.join(MANIFEST_FILE).exists().then_some(sibling)
+    }
+
+    /// Manifest of the newest backup in the chain
+    pub fn latest(&self) -> &BackupManifest {
+        &self.chain[self.chain.len() - 1].manifest
+    }
+
+    /// Directory of the newest backup in the chain
+    pub fn path(&self) -> &Path {
+        &self.chain[self.chain.len() - 1].dir
+    }
+
+    /// Manifest checksum of the newest backup in the chain
+    pub fn checksum(&self) -> &str {
+        &self.chain[self.chain.len() - 1].checksum
+    }
+
+    /// Manifests in the chain, base first
+    pub fn manifests(&self) -> impl Iterator<Item = &BackupManifest> {
+        self.chain.iter().map(|link| &link.manifest)
+    }
+
+    /// Every chunk stored anywhere in the chain
+    pub fn chunk_hashes(&self) -> HashSet<String> {
+        self.manifests()
+            .flat_map(|m| m.chunks.iter().map(|c| c.hash.clone()))
+            .collect()
+    }
+
+    /// Check that every chunk listed in the chain is present and intact,
+    /// and that every version's chunks are stored somewhere in the chain.
+    pub fn verify(&self) -> Result<VerifyReport, BackupError> {
+        let mut report = VerifyReport {
+            backups: self.chain.len(),
+            ..Default::default()
+        };
+
+        for link in &self.chain {
+            let store = ChunkStore::new(link.dir.join(CHUNKS_DIR))?;
+            let hashes: Vec<&str> = link.manifest.chunks.iter().map(|c| c.hash.as_str()).collect();
+            let present = store.exists_many(&hashes)?;
+            for (chunk, present) in link.manifest.chunks.iter().zip(present) {
+                if !present {
+                    return Err(BackupError::MissingChunk {
+                        backup_id: link.manifest.backup_id.clone(),
+                        hash: chunk.hash.clone(),
+                    });
+                }
+                let data = store.get_verified(&chunk.hash)?;
+                report.chunks_verified += 1;
+                report.bytes_verified += data.len() as u64;
+            }
+        }
+
+        let stored = self.chunk_hashes();
+        for version in self.manifests().flat_map(|m| &m.versions) {
+            if let Some(hash) = version.referenced_chunks().into_iter().find(|h| !stored.contains(h)) {
+                return Err(BackupError::UnreferencedChunk {
+                    table: version.table_name.clone(),
+                    version: version.version,
+                    hash,
+                });
+            }
+            report.versions += 1;
+        }
+
+        Ok(report)
+    }
+
+    /// Verify the chain, then rebuild its repository in `target`.
+    ///
+    /// None of the backed-up tables may exist in the target; branches may
+    /// only exist there with an empty head (as created when a repository
+    /// is opened).
+    pub fn restore(&self, target: RepoRef<'_>) -> Result<RestoreReport, BackupError> {
+        self.verify()?;
+        let latest = self.latest();
+
+        // Validate the target before writing anything
+        for table in latest.tables.keys() {
+            if target.catalog.table_exists(table)? {
+                return Err(BackupError::TableExists(table.clone()));
+            }
+        }
+        if let Some(branches) = target.branches {
+            for branch in &latest.branches {
+                match branches.get(&branch.name) {
+                    Ok(existing) if !existing.head.is_empty() => {
+                        return Err(BackupError::BranchExists(branch.name.clone()));
+                    }
+                    Ok(_) | Err(BranchError::BranchNotFound(_)) => {}
+                    Err(e) => return Err(e.into()),
+                }
+            }
+        }
+
+        let mut report = RestoreReport {
+            backups_applied: self.chain.len(),
+            tables: latest.tables.keys().cloned().collect(),
+            ..Default::default()
+        };
+
+        for link in &self.chain {
+            let store = ChunkStore::new(link.dir.join(CHUNKS_DIR))?;
+            let hashes: Vec<&str> = link.manifest.chunks.iter().map(|c| c.hash.as_str()).collect();
+            let present = target.store.exists_many(&hashes)?;
+            for (chunk, present) in link.manifest.chunks.iter().zip(present) {
+                if !present {
+                    let data = store.get(&chunk.hash)?;
+                    target.store.put(&data)?;
+                    report.chunks_copied += 1;
+                    report.bytes_copied += data.len() as u64;
+                }
+            }
+        }
+
+        let mut versions: Vec<_> = self.manifests().flat_map(|m| m.versions.iter()).collect();
+        versions.sort_by(|a, b| (&a.table_name, a.version).cmp(&(&b.table_name, b.version)));
+        for version in versions {
+            target.catalog.restore_version(version.clone())?;
+            report.versions_restored += 1;
+        }
+
+        for (table, origin) in &latest.table_origins {
+            target.catalog.set_table_origin(table, origin)?;
+        }
+        for pin in &latest.pins {
+            match target.catalog.pin(&pin.name, pin.versions.clone(), pin.description.clone()) {
+                Ok(_) | Err(CatalogError::PinAlreadyExists(_)) => {}
+                Err(e) => return Err(e.into()),
+            }
+        }
+        for hold in &latest.holds {
+            target.catalog.restore_hold(hold)?;
+        }
+
+        if let Some(branches) = target.branches {
+            if !latest.branches.is_empty() {
+                for branch in &latest.branches {
+                    branches.restore(branch)?;
+                    report.branches.push(branch.name.clone());
+                }
+                if let Some(default) = &latest.default_branch {
+                    branches.set_default(default)?;
+                }
+
+                // Drop placeholder branches the target created on open
+                let restored: BTreeSet<&str> = report.branches.iter().map(String::as_str).collect();
+                for name in branches.list()? {
+                    if !restored.contains(name.as_str()) && branches.get(&name)?.head.is_empty() {
+                        branches.delete(&name)?;
+                    }
+                }
+            }
+        }
+
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backup::Backup;
+    use crate::branch::BranchManager;
+    use crate::catalog::{FileCatalog, TableVersion};
+    use std::fs;
+
+    struct TestRepo {
+        dir: PathBuf,
+        catalog: FileCatalog,
+        store: ChunkStore,
+        branches: BranchManager,
+    }
+
+    impl TestRepo {
+        fn new() -> Self {
+            let dir = std::env::temp_dir().join(format!("rhizo_backup_test_{}", uuid::Uuid::new_v4()));
+            Self {
+                catalog: FileCatalog::new(dir.join("catalog")).unwrap(),
+                store: ChunkStore::new(dir.join("chunks")).unwrap(),
+                branches: BranchManager::new(dir.join("branches")).unwrap(),
+                dir,
+            }
+        }
+
+        fn as_ref(&self) -> RepoRef<'_> {
+            RepoRef::new(&self.catalog, &self.store).with_branches(&self.branches)
+        }
+
+        fn write(&self, table: &str, data: &[u8]) -> u64 {
+            let hash = self.store.put(data).unwrap();
+            let next = self.catalog.list_versions(table).map(|v| v.len() as u64).unwrap_or(0) + 1;
+            self.catalog.commit(TableVersion::new(table, next, vec![hash])).unwrap();
+            self.branches.update_head("main", table, next).unwrap();
+            next
+        }
+    }
+
+    impl Drop for TestRepo {
+        fn drop(&mut self) {
+            fs::remove_dir_all(&self.dir).ok();
+        }
+    }
+
+    #[test]
+    fn test_incremental_backup_and_restore() {
+        let source = TestRepo::new();
+        source.write("orders", b"v1");
+        source.write("users", b"u1");
+        source.catalog.hold("orders", 1, Some("case-7".to_string())).unwrap();
+
+        let backups = TestRepo::new();
+        let full = Backup::new(source.as_ref()).run(backups.dir.join("full")).unwrap();
+        assert!(!full.is_incremental());
+        assert_eq!(full.versions.len(), 2);
+
+        source.write("orders", b"v2");
+        source.write("orders", b"v1"); // dedupes against the full backup
+        let chain = BackupSet::open(backups.dir.join("full")).unwrap();
+        let incr = Backup::new(source.as_ref())
+            .incremental_since(&chain)
+            .run(backups.dir.join("incr"))
+            .unwrap();
+        assert_eq!(incr.parent.as_ref().unwrap().backup_id, full.backup_id);
+        assert_eq!(incr.versions.len(), 2);
+        assert_eq!(incr.chunks.len(), 1);
+        assert_eq!(incr.tables["orders"], 3);
+
+        let set = BackupSet::open(backups.dir.join("incr")).unwrap();
+        let verified = set.verify().unwrap();
+        assert_eq!(verified.backups, 2);
+        assert_eq!(verified.versions, 4);
+        assert_eq!(verified.chunks_verified, 3);
+
+        let target = TestRepo::new();
+        let report = set.restore(target.as_ref()).unwrap();
+        assert_eq!(report.versions_restored, 4);
+        assert_eq!(report.tables, vec!["orders", "users"]);
+
+        assert_eq!(target.catalog.list_versions("orders").unwrap(), vec![1, 2, 3]);
+        let v3 = target.catalog.get_version("orders", None).unwrap();
+        assert_eq!(target.store.get(&v3.chunk_hashes[0]).unwrap(), b"v1");
+        assert_eq!(target.branches.get("main").unwrap().head["orders"], 3);
+        let holds = target.catalog.list_holds().unwrap();
+        assert_eq!(holds.len(), 1);
+        assert_eq!(holds[0].reason.as_deref(), Some("case-7"));
+
+        // Restoring again onto the same target is refused
+        assert!(matches!(set.restore(target.as_ref()), Err(BackupError::TableExists(_))));
+    }
+
+    #[test]
+    fn test_restore_keeps_version_gaps() {
+        let source = TestRepo::new();
+        for i in 0..3u8 {
+            source.write("events", &[i; 4]);
+        }
+        source.catalog.delete_version("events", 1).unwrap();
+
+        let backups = TestRepo::new();
+        Backup::new(source.as_ref()).run(backups.dir.join("b")).unwrap();
+
+        let target = TestRepo::new();
+        BackupSet::open(backups.dir.join("b"))
+            .unwrap()
+            .restore(target.as_ref())
+            .unwrap();
+        assert_eq!(target.catalog.list_versions("events").unwrap(), vec![2, 3]);
+    }
+
+    #[test]
+    fn test_verify_detects_tampering() {
+        let source = TestRepo::new();
+        source.write("orders", b"payload");
+
+        let backups = TestRepo::new();
+        let dir = backups.dir.join("b");
+        let manifest = Backup::new(source.as_ref()).run(&dir).unwrap();
+
+        // Corrupt chunk data
+        let store = ChunkStore::new(dir.join(CHUNKS_DIR)).unwrap();
+        let hash = &manifest.chunks[0].hash;
+        let chunk_file = dir.join(CHUNKS_DIR).join(&hash[0..2]).join(&hash[2..4]).join(hash);
+        fs::write(&chunk_file, b"tampered").unwrap();
+        assert!(store.exists(hash).unwrap());
+        assert!(matches!(
+            BackupSet::open(&dir).unwrap().verify(),
+            Err(BackupError::ChunkStore(_))
+        ));
+
+        // Edit the manifest
+        let path = dir.join(MANIFEST_FILE);
+        let edited = fs::read_to_string(&path).unwrap().replace("orders", "ordens");
+        fs::write(&path, edited).unwrap();
+        assert!(matches!(
+            BackupSet::open(&dir),
+            Err(BackupError::ManifestChecksumMismatch(_))
+        ));
+    }
+
+    #[test]
+    fn test_incremental_chain_moved_together() {
+        let source = TestRepo::new();
+        source.write("orders", b"v1");
+
+        let backups = TestRepo::new();
+        let old = backups.dir.join("old");
+        fs::create_dir_all(&old).unwrap();
+        let chain = {
+            Backup::new(source.as_ref()).run(old.join("full")).unwrap();
+            BackupSet::open(old.join("full")).unwrap()
+        };
+        source.write("orders", b"v2");
+        Backup::new(source.as_ref())
+            .incremental_since(&chain)
+            .run(old.join("incr"))
+            .unwrap();
+
+        let moved = backups.dir.join("moved");
+        fs::rename(&old, &moved).unwrap();
+        let set = BackupSet::open(moved.join("incr")).unwrap();
+        assert_eq!(set.manifests().count(), 2);
+
+        fs::remove_dir_all(moved.join("full")).unwrap();
+        assert!(matches!(
+            BackupSet::open(moved.join("incr")),
+            Err(BackupError::ParentNotFound { .. })
+        ));
+    }
+}
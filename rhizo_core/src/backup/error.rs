@@ -0,0 +1,126 @@
+use std::path::PathBuf;
+use std::time::Duration;
+
+use thiserror::Error;
+
+use crate::branch::BranchError;
+use crate::catalog::CatalogError;
+use crate::chunk_store::ChunkStoreError;
+use crate::error::{ErrorCategory, ErrorContext, ErrorInfo};
+
+#[derive(Error, Debug)]
+#[non_exhaustive]
+pub enum BackupError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("Catalog error: {0}")]
+    Catalog(#[from] CatalogError),
+
+    #[error("Chunk store error: {0}")]
+    ChunkStore(#[from] ChunkStoreError),
+
+    #[error("Branch error: {0}")]
+    Branch(#[from] BranchError),
+
+    #[error("Not a backup (no manifest): {0}")]
+    NotABackup(PathBuf),
+
+    #[error("Backup already exists: {0}")]
+    BackupExists(PathBuf),
+
+    #[error("Unsupported backup format version: {0}")]
+    UnsupportedFormat(u32),
+
+    #[error("Manifest checksum mismatch: {0}")]
+    ManifestChecksumMismatch(PathBuf),
+
+    #[error("Parent backup {backup_id} not found (expected at {path})")]
+    ParentNotFound { backup_id: String, path: PathBuf },
+
+    #[error("Parent backup mismatch: expected {expected}, found {found}")]
+    ParentMismatch { expected: String, found: String },
+
+    #[error("Backup {backup_id} is missing chunk {hash}")]
+    MissingChunk { backup_id: String, hash: String },
+
+    #[error("No backup in the chain contains chunk {hash} of {table} v{version}")]
+    UnreferencedChunk { table: String, version: u64, hash: String },
+
+    #[error("Table already exists in restore target: {0}")]
+    TableExists(String),
+
+    #[error("Branch already exists in restore target: {0}")]
+    BranchExists(String),
+}
+
+impl ErrorInfo for BackupError {
+    fn code(&self) -> &'static str {
+        match self {
+            BackupError::Io(_) => "BACKUP_IO",
+            BackupError::Json(_) => "BACKUP_JSON",
+            BackupError::Catalog(e) => e.code(),
+            BackupError::ChunkStore(e) => e.code(),
+            BackupError::Branch(e) => e.code(),
+            BackupError::NotABackup(_) => "BACKUP_NOT_A_BACKUP",
+            BackupError::BackupExists(_) => "BACKUP_EXISTS",
+            BackupError::UnsupportedFormat(_) => "BACKUP_UNSUPPORTED_FORMAT",
+            BackupError::ManifestChecksumMismatch(_) => "BACKUP_MANIFEST_CHECKSUM_MISMATCH",
+            BackupError::ParentNotFound { .. } => "BACKUP_PARENT_NOT_FOUND",
+            BackupError::ParentMismatch { .. } => "BACKUP_PARENT_MISMATCH",
+            BackupError::MissingChunk { .. } => "BACKUP_MISSING_CHUNK",
+            BackupError::UnreferencedChunk { .. } => "BACKUP_UNREFERENCED_CHUNK",
+            BackupError::TableExists(_) => "BACKUP_TABLE_EXISTS",
+            BackupError::BranchExists(_) => "BACKUP_BRANCH_EXISTS",
+        }
+    }
+
+    fn category(&self) -> ErrorCategory {
+        match self {
+            BackupError::Io(_) => ErrorCategory::Io,
+            BackupError::Json(_) => ErrorCategory::Internal,
+            BackupError::Catalog(e) => e.category(),
+            BackupError::ChunkStore(e) => e.category(),
+            BackupError::Branch(e) => e.category(),
+            BackupError::NotABackup(_) | BackupError::ParentNotFound { .. } => ErrorCategory::NotFound,
+            BackupError::BackupExists(_) | BackupError::TableExists(_) | BackupError::BranchExists(_) => {
+                ErrorCategory::AlreadyExists
+            }
+            BackupError::UnsupportedFormat(_) => ErrorCategory::InvalidArgument,
+            BackupError::ManifestChecksumMismatch(_)
+            | BackupError::ParentMismatch { .. }
+            | BackupError::MissingChunk { .. }
+            | BackupError::UnreferencedChunk { .. } => ErrorCategory::DataIntegrity,
+        }
+    }
+
+    fn context(&self) -> ErrorContext {
+        match self {
+            BackupError::Catalog(e) => e.context(),
+            BackupError::ChunkStore(e) => e.context(),
+            BackupError::Branch(e) => e.context(),
+            BackupError::NotABackup(path)
+            | BackupError::BackupExists(path)
+            | BackupError::ManifestChecksumMismatch(path)
+            | BackupError::ParentNotFound { path, .. } => ErrorContext::new().with_path(path.clone()),
+            BackupError::MissingChunk { hash, .. } => ErrorContext::new().with_chunk(hash.clone()),
+            BackupError::UnreferencedChunk { table, version, hash } => ErrorContext::new()
+                .with_table(table.clone())
+                .with_version(*version)
+                .with_chunk(hash.clone()),
+            BackupError::TableExists(table) => ErrorContext::new().with_table(table.clone()),
+            BackupError::BranchExists(branch) => ErrorContext::new().with_branch(branch.clone()),
+            _ => ErrorContext::new(),
+        }
+    }
+
+    fn retry_after(&self) -> Option<Duration> {
+        match self {
+            BackupError::Catalog(e) => e.retry_after(),
+            _ => None,
+        }
+    }
+}
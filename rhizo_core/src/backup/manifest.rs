@@ -0,0 +1,126 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use super::error::BackupError;
+use crate::branch::Branch;
+use crate::catalog::{LegalHold, Pin, TableVersion};
+
+/// Current on-disk backup format
+pub const BACKUP_FORMAT_VERSION: u32 = 1;
+
+/// Manifest file in the backup directory
+pub const MANIFEST_FILE: &str = "manifest.json";
+
+/// Hex BLAKE3 checksum of the manifest file's bytes
+pub const MANIFEST_CHECKSUM_FILE: &str = "manifest.blake3";
+
+/// Chunk store directory in the backup directory
+pub const CHUNKS_DIR: &str = "chunks";
+
+/// Reference from an incremental backup to the backup it extends.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupParent {
+    pub backup_id: String,
+
+    /// Directory of the parent backup at the time of the backup. If it has
+    /// moved, a sibling directory with the same name is tried.
+    pub path: String,
+
+    /// Manifest checksum of the parent, pinning its exact contents
+    pub manifest_checksum: String,
+}
+
+/// A chunk stored in a backup.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupChunk {
+    pub hash: String,
+    pub size: u64,
+}
+
+/// Describes one backup: what it contains and what it extends.
+///
+/// Version records and chunks are incremental (only what the parent chain
+/// lacks); table heads, origins, branches, pins and holds are a full
+/// snapshot of the repository at backup time.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupManifest {
+    pub format_version: u32,
+    pub backup_id: String,
+
+    /// Unix timestamp when the backup was taken
+    pub created_at: i64,
+
+    /// Backup this one extends, if incremental
+    pub parent: Option<BackupParent>,
+
+    /// Latest version of every table at backup time
+    pub tables: BTreeMap<String, u64>,
+
+    /// Version records added by this backup
+    pub versions: Vec<TableVersion>,
+
+    /// Chunks added by this backup
+    pub chunks: Vec<BackupChunk>,
+
+    /// Branch each table was created on
+    pub table_origins: BTreeMap<String, String>,
+
+    pub branches: Vec<Branch>,
+    pub default_branch: Option<String>,
+    pub pins: Vec<Pin>,
+    pub holds: Vec<LegalHold>,
+}
+
+impl BackupManifest {
+    /// Whether this backup extends another
+    pub fn is_incremental(&self) -> bool {
+        self.parent.is_some()
+    }
+
+    /// Total bytes of chunk data in this backup
+    pub fn chunk_bytes(&self) -> u64 {
+        self.chunks.iter().map(|c| c.size).sum()
+    }
+
+    /// Write the manifest and its checksum into `dir`, returning the checksum.
+    pub(crate) fn write(&self, dir: &Path) -> Result<String, BackupError> {
+        let bytes = serde_json::to_vec_pretty(self)?;
+        let checksum = blake3::hash(&bytes).to_hex().to_string();
+
+        // The checksum goes last: a backup without it is incomplete
+        let path = dir.join(MANIFEST_FILE);
+        let temp_path = path.with_extension("json.tmp");
+        fs::write(&temp_path, &bytes)?;
+        fs::rename(&temp_path, &path)?;
+        fs::write(dir.join(MANIFEST_CHECKSUM_FILE), &checksum)?;
+
+        Ok(checksum)
+    }
+
+    /// Read and checksum-verify the manifest in `dir`.
+    ///
+    /// Returns the manifest and its checksum.
+    pub fn read(dir: &Path) -> Result<(Self, String), BackupError> {
+        let path = dir.join(MANIFEST_FILE);
+        let checksum_path = dir.join(MANIFEST_CHECKSUM_FILE);
+        if !path.exists() || !checksum_path.exists() {
+            return Err(BackupError::NotABackup(dir.to_path_buf()));
+        }
+
+        let bytes = fs::read(&path)?;
+        let expected = fs::read_to_string(&checksum_path)?;
+        let checksum = blake3::hash(&bytes).to_hex().to_string();
+        if checksum != expected.trim() {
+            return Err(BackupError::ManifestChecksumMismatch(path));
+        }
+
+        let manifest: Self = serde_json::from_slice(&bytes)?;
+        if manifest.format_version != BACKUP_FORMAT_VERSION {
+            return Err(BackupError::UnsupportedFormat(manifest.format_version));
+        }
+        Ok((manifest, checksum))
+    }
+}
@@ -0,0 +1,16 @@
+//! Full and incremental backups with checksummed manifests.
+//!
+//! A backup is a directory with a `manifest.json`, its BLAKE3 checksum and a
+//! chunk store. Incremental backups extend a parent backup and store only
+//! what the parent chain lacks; [`BackupSet`] opens a backup with its chain
+//! to verify it or restore it into an empty repository.
+
+pub mod backup_set;
+pub mod error;
+pub mod manifest;
+pub mod writer;
+
+pub use backup_set::{BackupSet, RestoreReport, VerifyReport};
+pub use error::BackupError;
+pub use manifest::{BackupChunk, BackupManifest, BackupParent};
+pub use writer::Backup;
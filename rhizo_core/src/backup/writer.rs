@@ -0,0 +1,128 @@
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use super::backup_set::BackupSet;
+use super::error::BackupError;
+use super::manifest::{BackupChunk, BackupManifest, BackupParent, BACKUP_FORMAT_VERSION, CHUNKS_DIR, MANIFEST_FILE};
+use crate::chunk_store::ChunkStore;
+use crate::filter::RepoRef;
+
+/// Takes a full or incremental backup of a repository.
+///
+/// A backup directory holds a checksummed manifest and a chunk store with
+/// the chunks the backup adds. An incremental backup stores only versions
+/// newer than, and chunks missing from, the backup chain it extends; its
+/// manifest still carries a full snapshot of branches, pins and holds.
+///
+/// # Example
+///
+/// ```ignore
+/// let full = Backup::new(repo).run("/backups/monday")?;
+/// let chain = BackupSet::open("/backups/monday")?;
+/// let incr = Backup::new(repo).incremental_since(&chain).run("/backups/tuesday")?;
+/// ```
+pub struct Backup<'a> {
+    source: RepoRef<'a>,
+    parent: Option<&'a BackupSet>,
+}
+
+impl<'a> Backup<'a> {
+    pub fn new(source: RepoRef<'a>) -> Self {
+        Self { source, parent: None }
+    }
+
+    /// Store only what `parent` (and the chain it extends) does not have.
+    pub fn incremental_since(mut self, parent: &'a BackupSet) -> Self {
+        self.parent = Some(parent);
+        self
+    }
+
+    /// Write the backup to `dest`, which must not already hold a backup.
+    pub fn run(&self, dest: impl AsRef<Path>) -> Result<BackupManifest, BackupError> {
+        let dest = dest.as_ref();
+        if dest.join(MANIFEST_FILE).exists() {
+            return Err(BackupError::BackupExists(dest.to_path_buf()));
+        }
+        fs::create_dir_all(dest)?;
+        let store = ChunkStore::new(dest.join(CHUNKS_DIR))?;
+
+        let (mut known_chunks, parent_tables) = match self.parent {
+            Some(parent) => (parent.chunk_hashes(), parent.latest().tables.clone()),
+            None => (HashSet::new(), BTreeMap::new()),
+        };
+
+        let catalog = self.source.catalog;
+        let mut tables = BTreeMap::new();
+        let mut table_origins = BTreeMap::new();
+        let mut versions = Vec::new();
+        let mut chunks = Vec::new();
+
+        for table in catalog.list_tables()? {
+            let backed_up = parent_tables.get(&table).copied().unwrap_or(0);
+            let mut latest = 0;
+            for number in catalog.list_versions(&table)? {
+                latest = latest.max(number);
+                if number <= backed_up {
+                    continue;
+                }
+
+                let version = catalog.get_version(&table, Some(number))?;
+                for hash in &version.referenced_chunks() {
+                    if known_chunks.insert(hash.clone()) {
+                        let data = self.source.store.get_verified(hash)?;
+                        store.put(&data)?;
+                        chunks.push(BackupChunk {
+                            hash: hash.clone(),
+                            size: data.len() as u64,
+                        });
+                    }
+                }
+                versions.push(version);
+            }
+
+            if let Some(origin) = catalog.table_origin(&table)? {
+                table_origins.insert(table.clone(), origin);
+            }
+            tables.insert(table, latest);
+        }
+
+        let (branches, default_branch) = match self.source.branches {
+            Some(manager) => {
+                let branches = manager
+                    .list()?
+                    .iter()
+                    .map(|name| manager.get(name))
+                    .collect::<Result<Vec<_>, _>>()?;
+                (branches, manager.get_default()?)
+            }
+            None => (Vec::new(), None),
+        };
+
+        let manifest = BackupManifest {
+            format_version: BACKUP_FORMAT_VERSION,
+            backup_id: uuid::Uuid::new_v4().to_string(),
+            created_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_secs() as i64,
+            parent: self.parent.map(|parent| BackupParent {
+                backup_id: parent.latest().backup_id.clone(),
+                path: parent.path().display().to_string(),
+                manifest_checksum: parent.checksum().to_string(),
+            }),
+            tables,
+            versions,
+            chunks,
+            table_origins,
+            branches,
+            default_branch,
+            pins: catalog.list_pins()?,
+            holds: catalog.list_holds()?,
+        };
+        manifest.write(dest)?;
+
+        Ok(manifest)
+    }
+}
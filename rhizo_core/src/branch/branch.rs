@@ -11,6 +11,7 @@ use std::time::{SystemTime, UNIX_EPOCH};
 ///
 /// The `head` HashMap maps table names to their version numbers on this branch.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct Branch {
     /// Unique name for the branch (e.g., "main", "feature/scoring-v2")
     pub name: String,
@@ -34,6 +35,16 @@ pub struct Branch {
     /// None for root branches or branches created before this field existed.
     #[serde(default)]
     pub fork_point: Option<HashMap<String, u64>>,
+
+    /// Immutable branches cannot move their heads, be merged into or be
+    /// deleted (e.g. a release frozen for compliance retention)
+    #[serde(default)]
+    pub immutable: bool,
+
+    /// Repository snapshot recorded by the last change to the heads
+    /// (None for branches last changed before snapshots existed)
+    #[serde(default)]
+    pub snapshot_id: Option<u64>,
 }
 
 impl Branch {
@@ -51,6 +62,8 @@ impl Branch {
             parent_branch: None,
             description: None,
             fork_point: None, // Root branches have no fork point
+            immutable: false,
+            snapshot_id: None,
         }
     }
 
@@ -70,6 +83,8 @@ impl Branch {
             parent_branch: Some(parent.name.clone()),
             description: None,
             fork_point: Some(parent.head.clone()), // Snapshot for three-way merge
+            immutable: false,
+            snapshot_id: None,
         }
     }
 
@@ -91,6 +106,19 @@ impl Branch {
     }
 }
 
+/// A branch moved out of the branch list after being merged.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchivedBranch {
+    /// The branch as it was when archived
+    pub branch: Branch,
+
+    /// Branch it was merged into
+    pub merged_into: String,
+
+    /// Unix timestamp when it was archived
+    pub archived_at: i64,
+}
+
 /// Result of comparing two branches.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BranchDiff {
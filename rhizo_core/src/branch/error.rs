@@ -1,6 +1,12 @@
+use std::time::Duration;
+
 use thiserror::Error;
 
+use crate::catalog::CatalogError;
+use crate::error::{ErrorCategory, ErrorContext, ErrorInfo};
+
 #[derive(Error, Debug)]
+#[non_exhaustive]
 pub enum BranchError {
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
@@ -17,6 +23,9 @@ pub enum BranchError {
     #[error("Cannot delete default branch: {0}")]
     CannotDeleteDefault(String),
 
+    #[error("Branch is immutable: {0}")]
+    BranchImmutable(String),
+
     #[error("Invalid branch name: {0}")]
     InvalidBranchName(String),
 
@@ -28,4 +37,84 @@ pub enum BranchError {
 
     #[error("Algebraic merge conflict on tables: {0:?}")]
     AlgebraicConflict(Vec<String>),
+
+    #[error("HEAD changed: expected generation {expected}, found {actual}")]
+    HeadChanged { expected: u64, actual: u64 },
+
+    #[error("Branch {branch} is checked out by workspace {workspace}")]
+    BranchCheckedOut { branch: String, workspace: String },
+
+    #[error("Invalid workspace id: {0}")]
+    InvalidWorkspace(String),
+
+    #[error("Snapshot not found: repo@{0}")]
+    SnapshotNotFound(u64),
+
+    #[error("Catalog error: {0}")]
+    Catalog(#[from] CatalogError),
+}
+
+impl ErrorInfo for BranchError {
+    fn code(&self) -> &'static str {
+        match self {
+            BranchError::Io(_) => "BRANCH_IO",
+            BranchError::Json(_) => "BRANCH_JSON",
+            BranchError::BranchNotFound(_) => "BRANCH_NOT_FOUND",
+            BranchError::BranchAlreadyExists(_) => "BRANCH_EXISTS",
+            BranchError::CannotDeleteDefault(_) => "BRANCH_CANNOT_DELETE_DEFAULT",
+            BranchError::BranchImmutable(_) => "BRANCH_IMMUTABLE",
+            BranchError::InvalidBranchName(_) => "BRANCH_INVALID_NAME",
+            BranchError::MergeConflict(_) => "BRANCH_MERGE_CONFLICT",
+            BranchError::CannotFastForward { .. } => "BRANCH_CANNOT_FAST_FORWARD",
+            BranchError::AlgebraicConflict(_) => "BRANCH_ALGEBRAIC_CONFLICT",
+            BranchError::HeadChanged { .. } => "BRANCH_HEAD_CHANGED",
+            BranchError::BranchCheckedOut { .. } => "BRANCH_CHECKED_OUT",
+            BranchError::InvalidWorkspace(_) => "BRANCH_INVALID_WORKSPACE",
+            BranchError::SnapshotNotFound(_) => "BRANCH_SNAPSHOT_NOT_FOUND",
+            BranchError::Catalog(e) => e.code(),
+        }
+    }
+
+    fn category(&self) -> ErrorCategory {
+        match self {
+            BranchError::Io(_) => ErrorCategory::Io,
+            BranchError::Json(_) => ErrorCategory::Internal,
+            BranchError::BranchNotFound(_) | BranchError::SnapshotNotFound(_) => ErrorCategory::NotFound,
+            BranchError::BranchAlreadyExists(_) => ErrorCategory::AlreadyExists,
+            BranchError::InvalidBranchName(_) | BranchError::InvalidWorkspace(_) => ErrorCategory::InvalidArgument,
+            BranchError::MergeConflict(_)
+            | BranchError::AlgebraicConflict(_)
+            | BranchError::HeadChanged { .. } => ErrorCategory::Conflict,
+            BranchError::CannotDeleteDefault(_)
+            | BranchError::BranchImmutable(_)
+            | BranchError::BranchCheckedOut { .. }
+            | BranchError::CannotFastForward { .. } => ErrorCategory::FailedPrecondition,
+            BranchError::Catalog(e) => e.category(),
+        }
+    }
+
+    fn context(&self) -> ErrorContext {
+        match self {
+            BranchError::BranchNotFound(branch)
+            | BranchError::BranchAlreadyExists(branch)
+            | BranchError::CannotDeleteDefault(branch)
+            | BranchError::BranchImmutable(branch)
+            | BranchError::BranchCheckedOut { branch, .. }
+            | BranchError::CannotFastForward { source_branch: branch, .. } => {
+                ErrorContext::new().with_branch(branch.clone())
+            }
+            BranchError::MergeConflict(tables) | BranchError::AlgebraicConflict(tables) if tables.len() == 1 => {
+                ErrorContext::new().with_table(tables[0].clone())
+            }
+            BranchError::Catalog(e) => e.context(),
+            _ => ErrorContext::new(),
+        }
+    }
+
+    fn retry_after(&self) -> Option<Duration> {
+        match self {
+            BranchError::Catalog(e) => e.retry_after(),
+            _ => None,
+        }
+    }
 }
@@ -0,0 +1,162 @@
+//! Repository HEAD: the default branch pointer and workspace checkouts.
+//!
+//! HEAD is a JSON record replaced atomically (write to a temp file, then
+//! rename) under the branch directory's lock. Every change bumps its
+//! `generation`, so callers can update it with compare-and-set, and is
+//! appended to an event log that other processes can follow.
+//!
+//! A checkout records which branch a workspace (a process, service or
+//! working copy) is using. Branches that are checked out cannot be deleted.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use super::error::BranchError;
+use crate::durability::{self, DurabilityLevel};
+
+/// HEAD record, next to the branch files
+pub(super) const HEAD_FILE: &str = "_HEAD";
+
+/// Pre-HEAD default branch file (read if HEAD does not exist yet)
+pub(super) const LEGACY_DEFAULT_FILE: &str = "_default.txt";
+
+/// Append-only log of HEAD changes (one JSON event per line)
+pub(super) const HEAD_EVENTS_FILE: &str = "_head_events.jsonl";
+
+/// Directory holding one file per checkout
+pub(super) const CHECKOUTS_DIR: &str = "_checkouts";
+
+/// Lock serializing HEAD updates, checkouts and branch deletion
+pub(super) const LOCK_FILE: &str = ".lock";
+
+pub(super) fn now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64
+}
+
+/// Which branch is the repository's default, and who last changed it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HeadRecord {
+    /// Default branch
+    pub branch: String,
+
+    /// Unix timestamp of the last change
+    pub updated_at: i64,
+
+    /// Who made the last change (user, service or workspace), if given
+    #[serde(default)]
+    pub updated_by: Option<String>,
+
+    /// Incremented on every change; 0 for a HEAD migrated from the
+    /// legacy default branch file
+    pub generation: u64,
+}
+
+/// A change of HEAD, as recorded in the event log.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HeadEvent {
+    /// Default branch before the change (None when HEAD was first set)
+    pub previous: Option<String>,
+
+    /// HEAD after the change
+    pub head: HeadRecord,
+}
+
+/// A workspace's claim on a branch.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Checkout {
+    /// Workspace identifier
+    pub workspace: String,
+
+    /// Branch checked out
+    pub branch: String,
+
+    /// Unix timestamp of the checkout
+    pub checked_out_at: i64,
+}
+
+/// Read HEAD, falling back to the legacy default branch file
+pub(super) fn read_head(branches_dir: &Path) -> Result<Option<HeadRecord>, BranchError> {
+    let path = branches_dir.join(HEAD_FILE);
+    if path.exists() {
+        return Ok(Some(serde_json::from_str(&fs::read_to_string(&path)?)?));
+    }
+
+    let legacy = branches_dir.join(LEGACY_DEFAULT_FILE);
+    if legacy.exists() {
+        return Ok(Some(HeadRecord {
+            branch: fs::read_to_string(&legacy)?.trim().to_string(),
+            updated_at: fs::metadata(&legacy)?
+                .modified()
+                .ok()
+                .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+                .map_or(0, |d| d.as_secs() as i64),
+            updated_by: None,
+            generation: 0,
+        }));
+    }
+
+    Ok(None)
+}
+
+/// Replace HEAD atomically and append the change to the event log.
+/// The caller holds the branch lock.
+pub(super) fn write_head(branches_dir: &Path, event: &HeadEvent, level: DurabilityLevel) -> Result<(), BranchError> {
+    let path = branches_dir.join(HEAD_FILE);
+    let temp_path = path.with_extension("tmp");
+    durability::write_atomic(&path, &temp_path, serde_json::to_string_pretty(&event.head)?, level)?;
+
+    durability::append_line(&branches_dir.join(HEAD_EVENTS_FILE), &serde_json::to_string(event)?, level)?;
+
+    // HEAD supersedes the legacy file; leaving it would only mislead
+    let legacy = branches_dir.join(LEGACY_DEFAULT_FILE);
+    if legacy.exists() {
+        durability::remove_file(&legacy, level)?;
+    }
+    Ok(())
+}
+
+/// HEAD changes with a generation greater than `since`, oldest first
+pub(super) fn read_events(branches_dir: &Path, since: u64) -> Result<Vec<HeadEvent>, BranchError> {
+    let path = branches_dir.join(HEAD_EVENTS_FILE);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut events = Vec::new();
+    for line in BufReader::new(fs::File::open(&path)?).lines() {
+        let line = line?;
+        // A torn final line from a crash mid-append is skipped
+        let Ok(event) = serde_json::from_str::<HeadEvent>(&line) else {
+            continue;
+        };
+        if event.head.generation > since {
+            events.push(event);
+        }
+    }
+    Ok(events)
+}
+
+/// Every checkout, sorted by workspace
+pub(super) fn read_checkouts(branches_dir: &Path) -> Result<Vec<Checkout>, BranchError> {
+    let dir = branches_dir.join(CHECKOUTS_DIR);
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut checkouts = Vec::new();
+    for entry in fs::read_dir(&dir)? {
+        let path = entry?.path();
+        if path.extension().and_then(|e| e.to_str()) != Some("json") {
+            continue;
+        }
+        checkouts.push(serde_json::from_str::<Checkout>(&fs::read_to_string(&path)?)?);
+    }
+    checkouts.sort_by(|a, b| a.workspace.cmp(&b.workspace));
+    Ok(checkouts)
+}
@@ -0,0 +1,217 @@
+//! Per-branch head history: how each branch reached its current heads.
+//!
+//! Every head change records a [`RepoSnapshot`](super::RepoSnapshot); the
+//! history keeps, per branch, one [`BranchCommit`] per snapshot with the
+//! table versions that moved, the transaction that moved them (if any), and
+//! the snapshot(s) it followed. A branch's first commit follows the head of
+//! the branch it was forked from, so commits across branches form one
+//! ancestry graph that [`common_ancestor`] can walk.
+//!
+//! Histories are append-only JSON-lines files in `_history/<branch>.jsonl`
+//! next to the branch files, appended under the snapshot lock. They outlive
+//! the branch: deleting and recreating a branch continues the same file.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+
+use super::error::BranchError;
+use crate::durability::{self, DurabilityLevel};
+
+/// Directory holding one history file per branch
+pub(super) const HISTORY_DIR: &str = "_history";
+
+/// One head change on a branch.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BranchCommit {
+    /// Id of the repository snapshot the change recorded
+    pub id: u64,
+
+    /// Branch whose heads changed
+    pub branch: String,
+
+    /// Commits this one follows: the branch's previous commit, or for a
+    /// new branch the head of the branch it was forked from, then for a
+    /// merge the merged branch's head. Empty for a root commit.
+    #[serde(default)]
+    pub parents: Vec<u64>,
+
+    /// Transaction that made the change, if it came from one
+    #[serde(default)]
+    pub tx_id: Option<u64>,
+
+    /// Tables whose head moved, sorted by table
+    #[serde(default)]
+    pub changes: Vec<HeadChange>,
+
+    /// Unix timestamp when recorded
+    pub created_at: i64,
+}
+
+impl BranchCommit {
+    /// Whether the commit merged another branch's head into this one
+    pub fn is_merge(&self) -> bool {
+        self.parents.len() > 1
+    }
+}
+
+/// A table head moving from one version to another.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HeadChange {
+    pub table: String,
+
+    /// Version before the change (None if the table was added)
+    pub old_version: Option<u64>,
+
+    /// Version after the change (None if the table was removed)
+    pub new_version: Option<u64>,
+}
+
+/// Changes taking `old` heads to `new` heads, sorted by table
+pub(super) fn diff(old: &HashMap<String, u64>, new: &HashMap<String, u64>) -> Vec<HeadChange> {
+    let tables: HashSet<&String> = old.keys().chain(new.keys()).collect();
+    let mut changes: Vec<HeadChange> = tables
+        .into_iter()
+        .filter(|table| old.get(*table) != new.get(*table))
+        .map(|table| HeadChange {
+            table: table.clone(),
+            old_version: old.get(table).copied(),
+            new_version: new.get(table).copied(),
+        })
+        .collect();
+    changes.sort_by(|a, b| a.table.cmp(&b.table));
+    changes
+}
+
+fn dir(branches_dir: &Path) -> PathBuf {
+    branches_dir.join(HISTORY_DIR)
+}
+
+fn path(branches_dir: &Path, branch: &str) -> PathBuf {
+    dir(branches_dir).join(format!("{}.jsonl", branch.replace("/", "__")))
+}
+
+/// Append a commit to its branch's history. The caller holds the snapshot
+/// lock.
+pub(super) fn append(branches_dir: &Path, commit: &BranchCommit, level: DurabilityLevel) -> Result<(), BranchError> {
+    fs::create_dir_all(dir(branches_dir))?;
+    durability::append_line(&path(branches_dir, &commit.branch), &serde_json::to_string(commit)?, level)?;
+    Ok(())
+}
+
+fn read_file(path: &Path) -> Result<Vec<BranchCommit>, BranchError> {
+    let mut commits = Vec::new();
+    for line in BufReader::new(fs::File::open(path)?).lines() {
+        let line = line?;
+        // A torn final line from a crash mid-append is skipped
+        if let Ok(commit) = serde_json::from_str::<BranchCommit>(&line) {
+            commits.push(commit);
+        }
+    }
+    Ok(commits)
+}
+
+/// A branch's commits, oldest first
+pub(super) fn read(branches_dir: &Path, branch: &str) -> Result<Vec<BranchCommit>, BranchError> {
+    let path = path(branches_dir, branch);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    read_file(&path)
+}
+
+/// Every branch's commits, by id
+pub(super) fn read_all(branches_dir: &Path) -> Result<HashMap<u64, BranchCommit>, BranchError> {
+    let dir = dir(branches_dir);
+    if !dir.exists() {
+        return Ok(HashMap::new());
+    }
+
+    let mut commits = HashMap::new();
+    for entry in fs::read_dir(&dir)? {
+        let path = entry?.path();
+        if path.extension().and_then(|e| e.to_str()) != Some("jsonl") {
+            continue;
+        }
+        for commit in read_file(&path)? {
+            commits.insert(commit.id, commit);
+        }
+    }
+    Ok(commits)
+}
+
+/// `start` and every commit it descends from
+fn ancestors(commits: &HashMap<u64, BranchCommit>, start: u64) -> HashSet<u64> {
+    let mut seen = HashSet::new();
+    let mut pending = vec![start];
+    while let Some(id) = pending.pop() {
+        if !seen.insert(id) {
+            continue;
+        }
+        if let Some(commit) = commits.get(&id) {
+            pending.extend(&commit.parents);
+        }
+    }
+    seen
+}
+
+/// The newest commit that both `a` and `b` descend from (or are)
+pub(super) fn common_ancestor(commits: &HashMap<u64, BranchCommit>, a: u64, b: u64) -> Option<BranchCommit> {
+    let of_a = ancestors(commits, a);
+    ancestors(commits, b)
+        .into_iter()
+        .filter(|id| of_a.contains(id))
+        .filter_map(|id| commits.get(&id))
+        .max_by_key(|commit| commit.id)
+        .cloned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn commit(id: u64, parents: &[u64]) -> BranchCommit {
+        BranchCommit {
+            id,
+            branch: "b".to_string(),
+            parents: parents.to_vec(),
+            tx_id: None,
+            changes: Vec::new(),
+            created_at: 0,
+        }
+    }
+
+    #[test]
+    fn test_diff_and_common_ancestor() {
+        let old = HashMap::from([("a".to_string(), 1), ("b".to_string(), 1), ("c".to_string(), 1)]);
+        let new = HashMap::from([("a".to_string(), 2), ("c".to_string(), 1), ("d".to_string(), 1)]);
+        let changes = diff(&old, &new);
+        let summary: Vec<_> = changes
+            .iter()
+            .map(|c| (c.table.as_str(), c.old_version, c.new_version))
+            .collect();
+        assert_eq!(summary, vec![("a", Some(1), Some(2)), ("b", Some(1), None), ("d", None, Some(1))]);
+
+        // 1 - 2 - 4 - 6
+        //      \     /
+        //       3 - 5 - 7
+        let commits: HashMap<u64, BranchCommit> = [
+            commit(1, &[]),
+            commit(2, &[1]),
+            commit(3, &[2]),
+            commit(4, &[2]),
+            commit(5, &[3]),
+            commit(6, &[4, 5]),
+            commit(7, &[5]),
+        ]
+        .into_iter()
+        .map(|c| (c.id, c))
+        .collect();
+        assert_eq!(common_ancestor(&commits, 4, 5).unwrap().id, 2);
+        assert_eq!(common_ancestor(&commits, 6, 7).unwrap().id, 5);
+        assert_eq!(common_ancestor(&commits, 6, 4).unwrap().id, 4);
+        assert!(common_ancestor(&commits, 8, 1).is_none());
+    }
+}
@@ -1,23 +1,39 @@
+use fs2::FileExt;
 use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 
-use super::branch::{Branch, BranchDiff};
+use super::branch::{ArchivedBranch, Branch, BranchDiff};
 use super::error::BranchError;
+use super::head::{self, Checkout, HeadEvent, HeadRecord};
+use super::history::{self, BranchCommit};
+use super::merge::{MergeRecord, MergeResolution, MergeStrategy};
+use super::read_cache::{FileStamp, ReadCache};
+use super::repair::{BranchRepair, DanglingHead, RepairStrategy};
+use super::snapshot::{self, RepoSnapshot};
+use crate::catalog::{namespace, CatalogError, FileCatalog, TableVersion};
+use crate::durability::{self, DurabilityLevel};
+use crate::refspec::ResolvedRef;
 
 const DEFAULT_BRANCH: &str = "main";
 const BRANCHES_DIR: &str = "_branches";
-const DEFAULT_FILE: &str = "_default.txt";
+
+/// Directory (under the branches dir) holding archived branches
+const ARCHIVE_DIR: &str = "_archive";
 
 /// Manages branches for UDR tables.
 ///
 /// Branches are stored as JSON files in a `_branches` subdirectory.
-/// The `_default.txt` file contains the name of the default branch.
+/// The `_HEAD` record names the default branch (see [`HeadRecord`]).
+/// Every change to a branch's heads records a [`RepoSnapshot`] under
+/// `_snapshots`, citable by its repository-wide id.
 ///
 /// Branch names with slashes (e.g., "feature/test") are stored with
 /// slashes converted to double underscores (e.g., "feature__test.json").
 pub struct BranchManager {
     base_path: PathBuf,
+    durability: DurabilityLevel,
+    reads: ReadCache,
 }
 
 impl BranchManager {
@@ -30,18 +46,34 @@ impl BranchManager {
         let branches_dir = base_path.join(BRANCHES_DIR);
         fs::create_dir_all(&branches_dir)?;
 
-        let manager = Self { base_path };
+        let manager = Self {
+            base_path,
+            durability: DurabilityLevel::default(),
+            reads: ReadCache::default(),
+        };
 
         // Create main branch if it doesn't exist
         if manager.list()?.is_empty() {
-            let main = Branch::new(DEFAULT_BRANCH, HashMap::new());
-            manager.save_branch(&main)?;
+            let mut main = Branch::new(DEFAULT_BRANCH, HashMap::new());
+            manager.save_with_snapshot(&mut main)?;
             manager.set_default(DEFAULT_BRANCH)?;
         }
 
         Ok(manager)
     }
 
+    /// Flush branch, HEAD and snapshot files as `level` requires (see
+    /// [`crate::durability`]). The `main` branch `new` creates is written
+    /// before the level applies.
+    pub fn with_durability(mut self, level: DurabilityLevel) -> Self {
+        self.durability = level;
+        self
+    }
+
+    pub fn durability(&self) -> DurabilityLevel {
+        self.durability
+    }
+
     /// Create a new branch from an existing branch.
     ///
     /// If `from_branch` is None, creates from the default branch.
@@ -51,6 +83,52 @@ impl BranchManager {
         name: &str,
         from_branch: Option<&str>,
         description: Option<&str>,
+    ) -> Result<Branch, BranchError> {
+        self.create_filtered(name, from_branch, description, None)
+    }
+
+    /// Create a branch containing only the tables of one namespace.
+    ///
+    /// `selector` is a namespace prefix (`sales`) or wildcard pattern
+    /// (`sales.*`, `org/**`); see [`crate::catalog::namespace::selects`].
+    /// Tables outside the selection are absent from both the new head and
+    /// its fork point.
+    pub fn create_for_namespace(
+        &self,
+        name: &str,
+        from_branch: Option<&str>,
+        selector: &str,
+        description: Option<&str>,
+    ) -> Result<Branch, BranchError> {
+        self.create_filtered(name, from_branch, description, Some(selector))
+    }
+
+    /// Create a branch at a resolved ref (`main~2`, `tag:release-1`, ...).
+    ///
+    /// The ref's versions become both the head and the fork point, and the
+    /// ref's branch, if it has one, the parent.
+    pub fn create_at(&self, name: &str, at: &ResolvedRef, description: Option<&str>) -> Result<Branch, BranchError> {
+        self.validate_branch_name(name)?;
+        if self.branch_exists(name) {
+            return Err(BranchError::BranchAlreadyExists(name.to_string()));
+        }
+
+        let mut branch = Branch::new(name, at.versions.clone());
+        branch.parent_branch = at.branch.clone();
+        branch.fork_point = Some(at.versions.clone());
+        if let Some(desc) = description {
+            branch = branch.with_description(desc);
+        }
+        self.save_with_snapshot(&mut branch)?;
+        Ok(branch)
+    }
+
+    fn create_filtered(
+        &self,
+        name: &str,
+        from_branch: Option<&str>,
+        description: Option<&str>,
+        selector: Option<&str>,
     ) -> Result<Branch, BranchError> {
         // Validate branch name
         self.validate_branch_name(name)?;
@@ -69,12 +147,20 @@ impl BranchManager {
 
         // Create new branch (zero-copy - only copies the HashMap)
         let mut branch = Branch::from_branch(name, &source);
+        // The fork's first commit follows the source's head
+        branch.snapshot_id = source.snapshot_id;
+        if let Some(selector) = selector {
+            branch.head.retain(|table, _| namespace::selects(table, selector));
+            if let Some(fork_point) = branch.fork_point.as_mut() {
+                fork_point.retain(|table, _| namespace::selects(table, selector));
+            }
+        }
         if let Some(desc) = description {
             branch = branch.with_description(desc);
         }
 
         // Save the branch
-        self.save_branch(&branch)?;
+        self.save_with_snapshot(&mut branch)?;
 
         Ok(branch)
     }
@@ -91,6 +177,64 @@ impl BranchManager {
         Ok(branch)
     }
 
+    /// The table version a read of `table_name` on `branch` sees: the
+    /// version the branch head names, or the latest version of an unscoped
+    /// table (see [`FileCatalog::get_version_on_branch`]).
+    ///
+    /// Branch heads are cached together with the versions read through
+    /// them, so a repeated read checks the branch and version files'
+    /// metadata instead of reading and parsing both.
+    pub fn resolve_read(
+        &self,
+        catalog: &FileCatalog,
+        branch: &str,
+        table_name: &str,
+    ) -> Result<TableVersion, BranchError> {
+        let (branch_stamp, branch_data) = self.get_stamped(branch)?;
+        let Some(head) = branch_data.get_table_version(table_name) else {
+            // The latest version moves without the branch file changing
+            return Ok(catalog.get_version_on_branch(table_name, None, &branch_data)?);
+        };
+
+        let version_stamp = FileStamp::of(&catalog.version_path(table_name, head))?;
+        if let Some(stamp) = version_stamp {
+            if let Some(version) = self.reads.table(branch, branch_stamp, table_name, stamp) {
+                return Ok(version);
+            }
+        }
+        let version = catalog.get_version_on_branch(table_name, None, &branch_data)?;
+        if let Some(stamp) = version_stamp {
+            self.reads.insert_table(branch, branch_stamp, stamp, &version);
+        }
+        Ok(version)
+    }
+
+    /// Stamp of a branch's file, without reading it. An unchanged stamp
+    /// means an unchanged branch.
+    pub(crate) fn head_stamp(&self, name: &str) -> Result<FileStamp, BranchError> {
+        FileStamp::of(&self.branch_path(name))?
+            .ok_or_else(|| BranchError::BranchNotFound(name.to_string()))
+    }
+
+    /// A branch through the read cache, with the stamp of the file it was
+    /// read from. The stamp is taken first, so a concurrent write leaves a
+    /// stale stamp (and a re-read next time), never stale contents.
+    pub(crate) fn get_stamped(&self, name: &str) -> Result<(FileStamp, Branch), BranchError> {
+        let path = self.branch_path(name);
+        let Some(stamp) = FileStamp::of(&path)? else {
+            self.reads.remove(name);
+            return Err(BranchError::BranchNotFound(name.to_string()));
+        };
+        if let Some(branch) = self.reads.branch(name, stamp) {
+            return Ok((stamp, branch));
+        }
+
+        let json = fs::read_to_string(&path)?;
+        let branch: Branch = serde_json::from_str(&json)?;
+        self.reads.insert_branch(name, stamp, &branch);
+        Ok((stamp, branch))
+    }
+
     /// List all branch names.
     pub fn list(&self) -> Result<Vec<String>, BranchError> {
         let branches_dir = self.base_path.join(BRANCHES_DIR);
@@ -121,24 +265,63 @@ impl BranchManager {
 
     /// Delete a branch.
     ///
-    /// Cannot delete the default branch.
+    /// Cannot delete the default branch or a branch checked out by a
+    /// workspace. Holds the branch lock, so HEAD cannot move onto the
+    /// branch while it is being deleted.
     pub fn delete(&self, name: &str) -> Result<(), BranchError> {
+        let _lock = self.lock()?;
+        let branch = self.get(name)?;
+        self.ensure_deletable(&branch)?;
+
+        durability::remove_file(&self.branch_path(name), self.durability)?;
+        Ok(())
+    }
+
+    /// Refuse to delete immutable, default or checked-out branches.
+    /// The caller holds the branch lock.
+    fn ensure_deletable(&self, branch: &Branch) -> Result<(), BranchError> {
+        Self::ensure_mutable(branch)?;
+
         // Check if it's the default branch
         if let Some(default) = self.get_default()? {
-            if default == name {
-                return Err(BranchError::CannotDeleteDefault(name.to_string()));
+            if default == branch.name {
+                return Err(BranchError::CannotDeleteDefault(branch.name.clone()));
             }
         }
 
-        let path = self.branch_path(name);
-        if !path.exists() {
-            return Err(BranchError::BranchNotFound(name.to_string()));
+        if let Some(checkout) = self.checkouts()?.into_iter().find(|c| c.branch == branch.name) {
+            return Err(BranchError::BranchCheckedOut {
+                branch: branch.name.clone(),
+                workspace: checkout.workspace,
+            });
         }
-
-        fs::remove_file(&path)?;
         Ok(())
     }
 
+    /// Write a branch verbatim, replacing any existing branch of that name.
+    ///
+    /// Unlike `create`, no fork point or parent is derived; the branch is
+    /// stored exactly as given. Used when copying branches between
+    /// repositories.
+    pub fn restore(&self, branch: &Branch) -> Result<(), BranchError> {
+        self.validate_branch_name(&branch.name)?;
+        if let Ok(existing) = self.get(&branch.name) {
+            Self::ensure_mutable(&existing)?;
+        }
+        self.save_branch(branch)
+    }
+
+    /// Mark a branch immutable (or mutable again).
+    ///
+    /// Immutable branches reject head updates, merges into them and deletion.
+    /// Clearing the flag is an administrative action; callers are expected to
+    /// gate it accordingly.
+    pub fn set_immutable(&self, name: &str, immutable: bool) -> Result<(), BranchError> {
+        let mut branch = self.get(name)?;
+        branch.immutable = immutable;
+        self.save_branch(&branch)
+    }
+
     /// Update the head pointer for a table on a branch.
     pub fn update_head(
         &self,
@@ -146,12 +329,212 @@ impl BranchManager {
         table_name: &str,
         version: u64,
     ) -> Result<(), BranchError> {
-        let mut branch = self.get(branch_name)?;
-        branch.set_table_version(table_name, version);
-        self.save_branch(&branch)?;
+        self.update_heads(branch_name, &HashMap::from([(table_name.to_string(), version)]))?;
         Ok(())
     }
 
+    /// Update several head pointers on a branch as one change, recorded
+    /// as a single repository snapshot.
+    pub fn update_heads(
+        &self,
+        branch_name: &str,
+        versions: &HashMap<String, u64>,
+    ) -> Result<RepoSnapshot, BranchError> {
+        self.commit_heads(branch_name, versions, None)
+    }
+
+    /// `update_heads` on behalf of a transaction, whose id is kept in the
+    /// branch's history.
+    pub fn commit_heads(
+        &self,
+        branch_name: &str,
+        versions: &HashMap<String, u64>,
+        tx_id: Option<u64>,
+    ) -> Result<RepoSnapshot, BranchError> {
+        let _lock = snapshot::lock(&self.branches_dir())?;
+        let mut branch = self.get(branch_name)?;
+        Self::ensure_mutable(&branch)?;
+        for (table_name, version) in versions {
+            branch.set_table_version(table_name, *version);
+        }
+        self.save_with_snapshot_locked(&mut branch, tx_id, None)
+    }
+
+    /// Heads on a branch that point at versions missing from `catalog`.
+    ///
+    /// Each is paired with the newest existing version below it, which
+    /// `repair_branch` would roll the head back to.
+    pub fn dangling_heads(&self, name: &str, catalog: &FileCatalog) -> Result<Vec<DanglingHead>, BranchError> {
+        let branch = self.get(name)?;
+        let mut dangling = Vec::new();
+        for (table, &version) in &branch.head {
+            let existing = match catalog.list_versions(table) {
+                Ok(versions) => versions,
+                Err(CatalogError::TableNotFound(_)) => Vec::new(),
+                Err(e) => return Err(e.into()),
+            };
+            if existing.binary_search(&version).is_err() {
+                dangling.push(DanglingHead {
+                    table: table.clone(),
+                    version,
+                    rollback_to: existing.into_iter().rev().find(|&v| v < version),
+                });
+            }
+        }
+        dangling.sort_by(|a, b| a.table.cmp(&b.table));
+        Ok(dangling)
+    }
+
+    /// Find dangling heads on a branch and, unless `strategy` is
+    /// `ReportOnly`, roll each back to its newest existing version
+    /// (dropping the table from the branch if it has none).
+    ///
+    /// The repair is recorded as a single repository snapshot. Heads moved
+    /// by another writer since detection are left alone.
+    pub fn repair_branch(
+        &self,
+        name: &str,
+        catalog: &FileCatalog,
+        strategy: RepairStrategy,
+    ) -> Result<BranchRepair, BranchError> {
+        let dangling = self.dangling_heads(name, catalog)?;
+        let mut repair = BranchRepair {
+            branch: name.to_string(),
+            dangling,
+            repaired: false,
+        };
+        if repair.is_clean() || strategy == RepairStrategy::ReportOnly {
+            return Ok(repair);
+        }
+
+        let _lock = snapshot::lock(&self.branches_dir())?;
+        let mut branch = self.get(name)?;
+        Self::ensure_mutable(&branch)?;
+        for head in &repair.dangling {
+            if branch.head.get(&head.table) != Some(&head.version) {
+                continue;
+            }
+            match head.rollback_to {
+                Some(version) => branch.set_table_version(&head.table, version),
+                None => {
+                    branch.head.remove(&head.table);
+                }
+            }
+        }
+        self.save_with_snapshot_locked(&mut branch, None, None)?;
+        repair.repaired = true;
+        Ok(repair)
+    }
+
+    /// Resolve a repository snapshot id to the versions it recorded.
+    pub fn snapshot(&self, id: u64) -> Result<RepoSnapshot, BranchError> {
+        snapshot::read(&self.branches_dir(), id)?.ok_or(BranchError::SnapshotNotFound(id))
+    }
+
+    /// The snapshot recorded by the branch's latest head change.
+    ///
+    /// None for branches last changed before snapshots existed.
+    pub fn latest_snapshot(&self, branch_name: &str) -> Result<Option<RepoSnapshot>, BranchError> {
+        match self.get(branch_name)?.snapshot_id {
+            Some(id) => self.snapshot(id).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Snapshots recorded on a branch (or every branch), oldest first.
+    pub fn snapshots(&self, branch_name: Option<&str>) -> Result<Vec<RepoSnapshot>, BranchError> {
+        snapshot::list(&self.branches_dir(), branch_name)
+    }
+
+    /// A branch's head changes, newest first, at most `limit` of them.
+    ///
+    /// History is kept from the first change after upgrading; it is not
+    /// removed when the branch is deleted.
+    pub fn history(&self, name: &str, limit: Option<usize>) -> Result<Vec<BranchCommit>, BranchError> {
+        let mut commits = history::read(&self.branches_dir(), name)?;
+        if commits.is_empty() && !self.branch_exists(name) {
+            return Err(BranchError::BranchNotFound(name.to_string()));
+        }
+        commits.reverse();
+        if let Some(limit) = limit {
+            commits.truncate(limit);
+        }
+        Ok(commits)
+    }
+
+    /// The newest commit both branches' current heads descend from.
+    ///
+    /// None when the branches share no recorded history (for instance a
+    /// branch created at a ref, or heads last changed before history was
+    /// kept).
+    pub fn common_ancestor(&self, a: &str, b: &str) -> Result<Option<BranchCommit>, BranchError> {
+        let (Some(head_a), Some(head_b)) = (self.get(a)?.snapshot_id, self.get(b)?.snapshot_id) else {
+            return Ok(None);
+        };
+        let commits = history::read_all(&self.branches_dir())?;
+        Ok(history::common_ancestor(&commits, head_a, head_b))
+    }
+
+    /// Branches whose head or fork point refer to a table
+    pub fn branches_with_table(&self, table_name: &str) -> Result<Vec<Branch>, BranchError> {
+        let mut found = Vec::new();
+        for name in self.list()? {
+            let branch = self.get(&name)?;
+            let in_fork_point = branch
+                .fork_point
+                .as_ref()
+                .is_some_and(|fp| fp.contains_key(table_name));
+            if branch.head.contains_key(table_name) || in_fork_point {
+                found.push(branch);
+            }
+        }
+        Ok(found)
+    }
+
+    /// Remove a dropped table from every branch (head and fork point).
+    ///
+    /// Fails before changing anything if an immutable branch refers to the
+    /// table. Returns the names of the branches that were updated.
+    pub fn remove_table(&self, table_name: &str) -> Result<Vec<String>, BranchError> {
+        self.rewrite_table_refs(table_name, |refs| {
+            refs.remove(table_name);
+        })
+    }
+
+    /// Point every branch at a table's new name (head and fork point).
+    ///
+    /// Fails before changing anything if an immutable branch refers to the
+    /// table. Returns the names of the branches that were updated.
+    pub fn rename_table(&self, old_name: &str, new_name: &str) -> Result<Vec<String>, BranchError> {
+        self.rewrite_table_refs(old_name, |refs| {
+            if let Some(version) = refs.remove(old_name) {
+                refs.insert(new_name.to_string(), version);
+            }
+        })
+    }
+
+    fn rewrite_table_refs(
+        &self,
+        table_name: &str,
+        rewrite: impl Fn(&mut HashMap<String, u64>),
+    ) -> Result<Vec<String>, BranchError> {
+        let branches = self.branches_with_table(table_name)?;
+        for branch in &branches {
+            Self::ensure_mutable(branch)?;
+        }
+
+        let mut updated = Vec::with_capacity(branches.len());
+        for mut branch in branches {
+            rewrite(&mut branch.head);
+            if let Some(fork_point) = branch.fork_point.as_mut() {
+                rewrite(fork_point);
+            }
+            self.save_with_snapshot(&mut branch)?;
+            updated.push(branch.name);
+        }
+        Ok(updated)
+    }
+
     /// Get the version of a table on a branch.
     ///
     /// Returns None if the table doesn't exist on this branch.
@@ -177,6 +560,17 @@ impl BranchManager {
         Ok(!diff.has_conflicts)
     }
 
+    /// Tables a merge of source into target would conflict on.
+    ///
+    /// Only three-way merges (source has a fork point) can conflict; the
+    /// forward-only merge used without one never does.
+    pub fn merge_conflicts(&self, source: &str, into: &str) -> Result<Vec<String>, BranchError> {
+        if self.get(source)?.fork_point.is_none() {
+            return Ok(Vec::new());
+        }
+        Ok(self.diff(source, into)?.conflicting_tables())
+    }
+
     /// Merge source branch into target branch.
     ///
     /// Uses three-way merge when a fork point is available:
@@ -188,17 +582,247 @@ impl BranchManager {
     /// Without a fork point, uses safe forward-only merge: source tables
     /// are applied only when they are new to the target or have a higher
     /// version. Target-only tables and higher target versions are preserved.
-    pub fn merge_fast_forward(&self, source: &str, into: &str) -> Result<(), BranchError> {
+    ///
+    /// With `delete_source`, the source branch is deleted in the same step,
+    /// and only if the merge lands: the merge is refused up front when the
+    /// source could not be deleted (default branch, checked out, immutable),
+    /// and the target is restored if the deletion itself fails.
+    pub fn merge_fast_forward(&self, source: &str, into: &str, delete_source: bool) -> Result<(), BranchError> {
+        self.merge_with_resolutions(source, into, &HashMap::new(), delete_source)
+    }
+
+    /// Merge source into target, settling true conflicts with per-table
+    /// resolutions (e.g. decisions collected from a user).
+    ///
+    /// Behaves like [`merge_fast_forward`](Self::merge_fast_forward), except
+    /// that a conflicting table listed in `resolutions` takes the chosen
+    /// side's version instead of failing the merge. Conflicts without a
+    /// resolution still fail with `MergeConflict`, listing only those tables.
+    pub fn merge_with_resolutions(
+        &self,
+        source: &str,
+        into: &str,
+        resolutions: &HashMap<String, MergeResolution>,
+        delete_source: bool,
+    ) -> Result<(), BranchError> {
+        if !delete_source {
+            let source_branch = self.get(source)?;
+            let target_branch = self.get(into)?;
+            Self::ensure_mutable(&target_branch)?;
+            let mut merged = Self::merged(&source_branch, target_branch, resolutions)?;
+            return self.save_with_snapshot(&mut merged).map(|_| ());
+        }
+
+        let _lock = self.lock()?;
+        let (source_branch, target_branch) = self.prepare_merge_and_remove(source, into)?;
+        let mut merged = Self::merged(&source_branch, target_branch.clone(), resolutions)?;
+        self.save_with_snapshot(&mut merged)?;
+
+        if let Err(e) = durability::remove_file(&self.branch_path(source), self.durability) {
+            self.save_branch(&target_branch)?;
+            return Err(e.into());
+        }
+        Ok(())
+    }
+
+    /// Merge source into target as a merge commit, settling true conflicts
+    /// by `strategy`.
+    ///
+    /// Classifies changes like [`merge_fast_forward`](Self::merge_fast_forward),
+    /// but the result is always recorded as a commit on the target whose
+    /// parents are both branches' heads, even when no head moved, so the
+    /// merge stays visible in [`history`](Self::history). The source is
+    /// left in place.
+    pub fn merge(&self, source: &str, into: &str, strategy: MergeStrategy) -> Result<MergeRecord, BranchError> {
+        self.commit_merge(source, into, strategy, None)
+    }
+
+    /// `merge` on behalf of a transaction, whose id is kept in the target's
+    /// history.
+    pub fn commit_merge(
+        &self,
+        source: &str,
+        into: &str,
+        strategy: MergeStrategy,
+        tx_id: Option<u64>,
+    ) -> Result<MergeRecord, BranchError> {
+        if source == into {
+            return Err(BranchError::InvalidBranchName(format!(
+                "Cannot merge branch into itself: {}",
+                source
+            )));
+        }
+
+        let _lock = snapshot::lock(&self.branches_dir())?;
         let source_branch = self.get(source)?;
-        let mut target_branch = self.get(into)?;
+        let target_branch = self.get(into)?;
+        Self::ensure_mutable(&target_branch)?;
+
+        let mut resolved = Vec::new();
+        let mut resolutions = HashMap::new();
+        if let (Some(resolution), Some(_)) = (strategy.resolution(), &source_branch.fork_point) {
+            resolved = BranchDiff::compute(&source_branch, &target_branch).conflicting_tables();
+            resolved.sort();
+            resolutions.extend(resolved.iter().map(|table| (table.clone(), resolution)));
+        }
+        let mut merged = Self::merged(&source_branch, target_branch.clone(), &resolutions)?;
+        let snapshot = self.save_with_snapshot_locked(&mut merged, tx_id, source_branch.snapshot_id)?;
+
+        let versions: HashMap<String, u64> = merged
+            .head
+            .iter()
+            .filter(|(table, version)| target_branch.head.get(*table) != Some(version))
+            .map(|(table, version)| (table.clone(), *version))
+            .collect();
+        let replaced = versions
+            .keys()
+            .filter_map(|table| Some((table.clone(), *target_branch.head.get(table)?)))
+            .collect();
+        Ok(MergeRecord {
+            source: source.to_string(),
+            into: into.to_string(),
+            strategy,
+            parents: target_branch.snapshot_id.into_iter().chain(source_branch.snapshot_id).collect(),
+            snapshot_id: snapshot.id,
+            versions,
+            replaced,
+            resolved,
+        })
+    }
 
+    /// The branch `target_branch` would become if `source_branch` were
+    /// merged into it with `resolutions`, without saving anything.
+    ///
+    /// Fails as [`merge_with_resolutions`](Self::merge_with_resolutions)
+    /// would: on unresolved conflicts or an immutable target.
+    pub fn preview_merge(
+        source_branch: &Branch,
+        target_branch: Branch,
+        resolutions: &HashMap<String, MergeResolution>,
+    ) -> Result<Branch, BranchError> {
+        Self::ensure_mutable(&target_branch)?;
+        Self::merged(source_branch, target_branch, resolutions)
+    }
+
+    /// Merge source into target and move the source branch to the archive,
+    /// as one step.
+    ///
+    /// Safer than deleting: the archived branch keeps its heads and can be
+    /// brought back with [`unarchive`](Self::unarchive). The source is archived
+    /// only if the merge lands, under the same rules as
+    /// `merge_fast_forward(.., true)`.
+    pub fn merge_and_archive(&self, source: &str, into: &str) -> Result<ArchivedBranch, BranchError> {
+        let _lock = self.lock()?;
+        let (source_branch, target_branch) = self.prepare_merge_and_remove(source, into)?;
+        let mut merged = Self::merged(&source_branch, target_branch.clone(), &HashMap::new())?;
+
+        let archived = ArchivedBranch {
+            branch: source_branch,
+            merged_into: into.to_string(),
+            archived_at: head::now(),
+        };
+        let archive_path = self.archive_path(&archived)?;
+        durability::write(&archive_path, serde_json::to_string_pretty(&archived)?, self.durability)?;
+
+        let landed = self
+            .save_with_snapshot(&mut merged)
+            .and_then(|_| durability::remove_file(&self.branch_path(source), self.durability).map_err(BranchError::from));
+        if let Err(e) = landed {
+            // Undo both halves so neither the merge nor the archive is partial
+            self.save_branch(&target_branch)?;
+            durability::remove_file(&archive_path, self.durability)?;
+            return Err(e);
+        }
+        Ok(archived)
+    }
+
+    /// Branches archived by `merge_and_archive`, oldest first
+    pub fn archived(&self) -> Result<Vec<ArchivedBranch>, BranchError> {
+        Ok(self.archive_entries()?.into_iter().map(|(_, a)| a).collect())
+    }
+
+    /// Bring back the most recently archived branch with this name,
+    /// removing it from the archive.
+    pub fn unarchive(&self, name: &str) -> Result<Branch, BranchError> {
+        let _lock = self.lock()?;
+        if self.branch_exists(name) {
+            return Err(BranchError::BranchAlreadyExists(name.to_string()));
+        }
+        let (path, archived) = self
+            .archive_entries()?
+            .into_iter()
+            .rev()
+            .find(|(_, a)| a.branch.name == name)
+            .ok_or_else(|| BranchError::BranchNotFound(name.to_string()))?;
+
+        self.save_branch(&archived.branch)?;
+        durability::remove_file(&path, self.durability)?;
+        Ok(archived.branch)
+    }
+
+    fn archive_entries(&self) -> Result<Vec<(PathBuf, ArchivedBranch)>, BranchError> {
+        let dir = self.branches_dir().join(ARCHIVE_DIR);
+        if !dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut entries = Vec::new();
+        for entry in fs::read_dir(&dir)? {
+            let path = entry?.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("json") {
+                continue;
+            }
+            let archived = serde_json::from_str::<ArchivedBranch>(&fs::read_to_string(&path)?)?;
+            entries.push((path, archived));
+        }
+        entries.sort_by(|(_, a), (_, b)| {
+            (a.archived_at, &a.branch.name).cmp(&(b.archived_at, &b.branch.name))
+        });
+        Ok(entries)
+    }
+
+    /// Load both sides of a merge that removes its source, refusing up front
+    /// anything that would stop the source from being removed afterwards.
+    /// The caller holds the branch lock.
+    fn prepare_merge_and_remove(&self, source: &str, into: &str) -> Result<(Branch, Branch), BranchError> {
+        if source == into {
+            return Err(BranchError::InvalidBranchName(format!(
+                "Cannot merge branch into itself: {}",
+                source
+            )));
+        }
+        let source_branch = self.get(source)?;
+        let target_branch = self.get(into)?;
+        Self::ensure_mutable(&target_branch)?;
+        self.ensure_deletable(&source_branch)?;
+        Ok((source_branch, target_branch))
+    }
+
+    /// The target branch after merging source into it
+    fn merged(
+        source_branch: &Branch,
+        mut target_branch: Branch,
+        resolutions: &HashMap<String, MergeResolution>,
+    ) -> Result<Branch, BranchError> {
         if source_branch.fork_point.is_some() {
             // Three-way merge: use diff to classify changes
-            let diff = self.diff(source, into)?;
+            let diff = BranchDiff::compute(source_branch, &target_branch);
+
+            let unresolved: Vec<String> = diff
+                .conflicting_tables()
+                .into_iter()
+                .filter(|t| !resolutions.contains_key(t))
+                .collect();
+            if !unresolved.is_empty() {
+                return Err(BranchError::MergeConflict(unresolved));
+            }
 
-            if diff.has_conflicts {
-                let conflicting = diff.conflicting_tables();
-                return Err(BranchError::MergeConflict(conflicting));
+            // Resolved conflicts: the target keeps its version unless the
+            // source side was chosen
+            for (table, src_version, _) in &diff.modified {
+                if resolutions.get(table) == Some(&MergeResolution::Source) {
+                    target_branch.set_table_version(table, *src_version);
+                }
             }
 
             // Apply source-only changes (source diverged from base, target didn't)
@@ -224,39 +848,154 @@ impl BranchManager {
             }
         }
 
-        self.save_branch(&target_branch)?;
-
-        Ok(())
+        Ok(target_branch)
     }
 
     /// Get the default branch name.
     pub fn get_default(&self) -> Result<Option<String>, BranchError> {
-        let path = self.base_path.join(BRANCHES_DIR).join(DEFAULT_FILE);
+        Ok(self.head()?.map(|h| h.branch))
+    }
+
+    /// Set the default branch.
+    pub fn set_default(&self, name: &str) -> Result<(), BranchError> {
+        self.set_head(name, None).map(|_| ())
+    }
+
+    /// The repository HEAD record, if a default branch is set.
+    pub fn head(&self) -> Result<Option<HeadRecord>, BranchError> {
+        head::read_head(&self.branches_dir())
+    }
+
+    /// Point HEAD at a branch, whatever it currently names.
+    pub fn set_head(&self, name: &str, updated_by: Option<&str>) -> Result<HeadRecord, BranchError> {
+        let _lock = self.lock()?;
+        self.update_head_locked(name, updated_by)
+    }
+
+    /// Point HEAD at a branch only if HEAD is still at `expected_generation`
+    /// (0 also matches a repository with no HEAD yet).
+    ///
+    /// Fails with `BranchError::HeadChanged` if another writer moved HEAD
+    /// since the caller read it.
+    pub fn compare_and_set_head(
+        &self,
+        expected_generation: u64,
+        name: &str,
+        updated_by: Option<&str>,
+    ) -> Result<HeadRecord, BranchError> {
+        let _lock = self.lock()?;
+        let actual = self.head()?.map_or(0, |h| h.generation);
+        if actual != expected_generation {
+            return Err(BranchError::HeadChanged {
+                expected: expected_generation,
+                actual,
+            });
+        }
+        self.update_head_locked(name, updated_by)
+    }
+
+    /// HEAD changes after `since_generation`, oldest first.
+    ///
+    /// Followers poll with the generation of the last event they saw.
+    pub fn head_events(&self, since_generation: u64) -> Result<Vec<HeadEvent>, BranchError> {
+        head::read_events(&self.branches_dir(), since_generation)
+    }
+
+    /// Record that a workspace uses a branch, replacing the workspace's
+    /// previous checkout. A checked-out branch cannot be deleted.
+    pub fn checkout(&self, workspace: &str, branch: &str) -> Result<Checkout, BranchError> {
+        let path = self.checkout_path(workspace)?;
+        let _lock = self.lock()?;
+        if !self.branch_exists(branch) {
+            return Err(BranchError::BranchNotFound(branch.to_string()));
+        }
+
+        let checkout = Checkout {
+            workspace: workspace.to_string(),
+            branch: branch.to_string(),
+            checked_out_at: head::now(),
+        };
+        fs::create_dir_all(path.parent().expect("checkout file has a parent"))?;
+        let temp_path = path.with_extension("json.tmp");
+        durability::write_atomic(&path, &temp_path, serde_json::to_string_pretty(&checkout)?, self.durability)?;
+        Ok(checkout)
+    }
+
+    /// Release a workspace's checkout, returning it if there was one.
+    pub fn release_checkout(&self, workspace: &str) -> Result<Option<Checkout>, BranchError> {
+        let path = self.checkout_path(workspace)?;
+        let _lock = self.lock()?;
         if !path.exists() {
             return Ok(None);
         }
-        let name = fs::read_to_string(&path)?.trim().to_string();
-        Ok(Some(name))
+        let checkout = serde_json::from_str(&fs::read_to_string(&path)?)?;
+        durability::remove_file(&path, self.durability)?;
+        Ok(Some(checkout))
     }
 
-    /// Set the default branch.
-    pub fn set_default(&self, name: &str) -> Result<(), BranchError> {
-        // Verify branch exists
+    /// All checkouts, sorted by workspace.
+    pub fn checkouts(&self) -> Result<Vec<Checkout>, BranchError> {
+        head::read_checkouts(&self.branches_dir())
+    }
+
+    // --- Private helpers ---
+
+    fn branches_dir(&self) -> PathBuf {
+        self.base_path.join(BRANCHES_DIR)
+    }
+
+    /// Exclusive lock over HEAD, checkouts and branch deletion
+    fn lock(&self) -> Result<fs::File, BranchError> {
+        let lock_file = fs::OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(false)
+            .open(self.branches_dir().join(head::LOCK_FILE))?;
+        lock_file.lock_exclusive()?;
+        Ok(lock_file)
+    }
+
+    fn update_head_locked(&self, name: &str, updated_by: Option<&str>) -> Result<HeadRecord, BranchError> {
+        // Verify branch exists (deletion holds the same lock)
         if !self.branch_exists(name) {
             return Err(BranchError::BranchNotFound(name.to_string()));
         }
 
-        let path = self.base_path.join(BRANCHES_DIR).join(DEFAULT_FILE);
-        let temp_path = path.with_extension("tmp");
+        let previous = self.head()?;
+        let record = HeadRecord {
+            branch: name.to_string(),
+            updated_at: head::now(),
+            updated_by: updated_by.map(str::to_string),
+            generation: previous.as_ref().map_or(0, |h| h.generation) + 1,
+        };
+        head::write_head(
+            &self.branches_dir(),
+            &HeadEvent {
+                previous: previous.map(|h| h.branch),
+                head: record.clone(),
+            },
+            self.durability,
+        )?;
+        Ok(record)
+    }
 
-        fs::write(&temp_path, name)?;
-        fs::rename(&temp_path, &path)?;
+    fn checkout_path(&self, workspace: &str) -> Result<PathBuf, BranchError> {
+        if workspace.is_empty() || workspace.chars().any(char::is_control) {
+            return Err(BranchError::InvalidWorkspace(format!("{:?}", workspace)));
+        }
+        Ok(self
+            .branches_dir()
+            .join(head::CHECKOUTS_DIR)
+            .join(format!("{}.json", namespace::encode_table_name(workspace))))
+    }
 
+    fn ensure_mutable(branch: &Branch) -> Result<(), BranchError> {
+        if branch.immutable {
+            return Err(BranchError::BranchImmutable(branch.name.clone()));
+        }
         Ok(())
     }
 
-    // --- Private helpers ---
-
     fn branch_path(&self, name: &str) -> PathBuf {
         // Convert slashes to double underscores for filesystem safety
         let safe_name = name.replace("/", "__");
@@ -265,17 +1004,77 @@ impl BranchManager {
             .join(format!("{}.json", safe_name))
     }
 
+    /// Archive file for a branch: `<name>@<archived_at>.json`, with a
+    /// counter when the same branch is archived twice in one second
+    fn archive_path(&self, archived: &ArchivedBranch) -> Result<PathBuf, BranchError> {
+        let dir = self.branches_dir().join(ARCHIVE_DIR);
+        fs::create_dir_all(&dir)?;
+
+        let stem = format!("{}@{}", archived.branch.name.replace("/", "__"), archived.archived_at);
+        let mut path = dir.join(format!("{}.json", stem));
+        let mut n = 1;
+        while path.exists() {
+            path = dir.join(format!("{}-{}.json", stem, n));
+            n += 1;
+        }
+        Ok(path)
+    }
+
     fn branch_exists(&self, name: &str) -> bool {
         self.branch_path(name).exists()
     }
 
+    /// Save a branch whose heads changed, recording a repository snapshot
+    fn save_with_snapshot(&self, branch: &mut Branch) -> Result<RepoSnapshot, BranchError> {
+        let _lock = snapshot::lock(&self.branches_dir())?;
+        self.save_with_snapshot_locked(branch, None, None)
+    }
+
+    /// `save_with_snapshot` for callers already holding the snapshot lock.
+    ///
+    /// The change is appended to the branch's history as a commit following
+    /// `branch.snapshot_id` (the branch's previous commit, or the source
+    /// head for a new fork) and, for a merge, the merged branch's head.
+    fn save_with_snapshot_locked(
+        &self,
+        branch: &mut Branch,
+        tx_id: Option<u64>,
+        merged_from: Option<u64>,
+    ) -> Result<RepoSnapshot, BranchError> {
+        let parent = branch.snapshot_id;
+        let old_heads = if self.branch_exists(&branch.name) {
+            self.get(&branch.name)?.head
+        } else if let Some(id) = parent {
+            snapshot::read(&self.branches_dir(), id)?.map(|s| s.versions).unwrap_or_default()
+        } else {
+            HashMap::new()
+        };
+
+        let snapshot = snapshot::record(&self.branches_dir(), branch, self.durability)?;
+        branch.snapshot_id = Some(snapshot.id);
+        if let Err(e) = self.save_branch(branch) {
+            snapshot::discard(&self.branches_dir(), snapshot.id);
+            return Err(e);
+        }
+
+        let commit = BranchCommit {
+            id: snapshot.id,
+            branch: branch.name.clone(),
+            parents: parent.into_iter().chain(merged_from).collect(),
+            tx_id,
+            changes: history::diff(&old_heads, &branch.head),
+            created_at: snapshot.created_at,
+        };
+        history::append(&self.branches_dir(), &commit, self.durability)?;
+        Ok(snapshot)
+    }
+
     fn save_branch(&self, branch: &Branch) -> Result<(), BranchError> {
         let path = self.branch_path(&branch.name);
         let temp_path = path.with_extension("json.tmp");
 
         let json = serde_json::to_string_pretty(branch)?;
-        fs::write(&temp_path, &json)?;
-        fs::rename(&temp_path, &path)?;
+        durability::write_atomic(&path, &temp_path, &json, self.durability)?;
 
         Ok(())
     }
@@ -370,6 +1169,36 @@ mod tests {
         fs::remove_dir_all(&dir).ok();
     }
 
+    #[test]
+    fn test_create_branch_for_namespace() {
+        let dir = temp_dir();
+        let manager = BranchManager::new(&dir).unwrap();
+
+        manager.update_head("main", "sales.orders", 3).unwrap();
+        manager.update_head("main", "sales/eu/invoices", 2).unwrap();
+        manager.update_head("main", "hr.people", 1).unwrap();
+
+        let sales = manager
+            .create_for_namespace("sales-only", None, "sales", None)
+            .unwrap();
+        let mut tables: Vec<_> = sales.head.keys().cloned().collect();
+        tables.sort();
+        assert_eq!(tables, vec!["sales.orders", "sales/eu/invoices"]);
+        assert_eq!(sales.fork_point.as_ref().unwrap().len(), 2);
+
+        let shallow = manager
+            .create_for_namespace("sales-dotted", None, "sales.*", None)
+            .unwrap();
+        assert_eq!(shallow.head.len(), 1);
+        assert_eq!(shallow.get_table_version("sales.orders"), Some(3));
+
+        // Persisted and source branch untouched
+        assert_eq!(manager.get("sales-only").unwrap().head.len(), 2);
+        assert_eq!(manager.get("main").unwrap().head.len(), 3);
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
     #[test]
     fn test_create_branch_already_exists() {
         let dir = temp_dir();
@@ -383,6 +1212,32 @@ mod tests {
         fs::remove_dir_all(&dir).ok();
     }
 
+    #[test]
+    fn test_create_branch_at_ref() {
+        let dir = temp_dir();
+        let manager = BranchManager::new(&dir).unwrap();
+        manager.update_head("main", "users", 1).unwrap();
+        let at = manager.latest_snapshot("main").unwrap().unwrap();
+        manager.update_head("main", "users", 2).unwrap();
+
+        let at = ResolvedRef {
+            spec: crate::refspec::RefSpec::Snapshot(at.id),
+            branch: Some(at.branch),
+            versions: at.versions,
+        };
+        let hotfix = manager.create_at("hotfix", &at, Some("Patch v1")).unwrap();
+        assert_eq!(hotfix.get_table_version("users"), Some(1));
+        assert_eq!(hotfix.fork_point, Some(HashMap::from([("users".to_string(), 1)])));
+        assert_eq!(hotfix.parent_branch.as_deref(), Some("main"));
+        assert!(hotfix.snapshot_id.is_some());
+        assert!(matches!(
+            manager.create_at("hotfix", &at, None),
+            Err(BranchError::BranchAlreadyExists(_))
+        ));
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
     #[test]
     fn test_branch_not_found() {
         let dir = temp_dir();
@@ -437,6 +1292,84 @@ mod tests {
         fs::remove_dir_all(&dir).ok();
     }
 
+    #[test]
+    fn test_head_compare_and_set_and_events() {
+        let dir = temp_dir();
+        let manager = BranchManager::new(&dir).unwrap();
+        manager.create("release", None, None).unwrap();
+
+        let head = manager.head().unwrap().unwrap();
+        assert_eq!((head.branch.as_str(), head.generation), ("main", 1));
+
+        let moved = manager.compare_and_set_head(1, "release", Some("deploy-bot")).unwrap();
+        assert_eq!(moved.generation, 2);
+        assert_eq!(moved.updated_by.as_deref(), Some("deploy-bot"));
+        assert_eq!(manager.get_default().unwrap().as_deref(), Some("release"));
+
+        // A writer that read generation 1 lost the race
+        assert!(matches!(
+            manager.compare_and_set_head(1, "main", None),
+            Err(BranchError::HeadChanged { expected: 1, actual: 2 })
+        ));
+        assert!(matches!(manager.set_head("missing", None), Err(BranchError::BranchNotFound(_))));
+
+        let events = manager.head_events(1).unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].previous.as_deref(), Some("main"));
+        assert_eq!(events[0].head, moved);
+        assert_eq!(manager.head_events(0).unwrap().len(), 2);
+
+        // HEAD is not mistaken for a branch
+        assert_eq!(manager.list().unwrap(), vec!["main", "release"]);
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_legacy_default_file_is_read() {
+        let dir = temp_dir();
+        let branches_dir = dir.join(BRANCHES_DIR);
+        fs::create_dir_all(&branches_dir).unwrap();
+        let main = Branch::new("main", HashMap::new());
+        fs::write(branches_dir.join("main.json"), serde_json::to_string(&main).unwrap()).unwrap();
+        fs::write(branches_dir.join(head::LEGACY_DEFAULT_FILE), "main\n").unwrap();
+
+        let manager = BranchManager::new(&dir).unwrap();
+        let head = manager.head().unwrap().unwrap();
+        assert_eq!((head.branch.as_str(), head.generation), ("main", 0));
+
+        manager.compare_and_set_head(0, "main", None).unwrap();
+        assert!(!branches_dir.join(head::LEGACY_DEFAULT_FILE).exists());
+        assert_eq!(manager.head().unwrap().unwrap().generation, 1);
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_cannot_delete_checked_out_branch() {
+        let dir = temp_dir();
+        let manager = BranchManager::new(&dir).unwrap();
+        manager.create("feature", None, None).unwrap();
+
+        manager.checkout("worker/1", "feature").unwrap();
+        assert!(matches!(
+            manager.delete("feature"),
+            Err(BranchError::BranchCheckedOut { ref workspace, .. }) if workspace == "worker/1"
+        ));
+        assert_eq!(manager.checkouts().unwrap()[0].branch, "feature");
+        assert!(matches!(manager.checkout("", "main"), Err(BranchError::InvalidWorkspace(_))));
+        assert!(matches!(manager.checkout("worker/2", "missing"), Err(BranchError::BranchNotFound(_))));
+
+        // Switching the workspace away releases the branch
+        manager.checkout("worker/1", "main").unwrap();
+        manager.delete("feature").unwrap();
+        assert_eq!(manager.release_checkout("worker/1").unwrap().unwrap().branch, "main");
+        assert_eq!(manager.release_checkout("worker/1").unwrap(), None);
+        assert!(manager.checkouts().unwrap().is_empty());
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
     #[test]
     fn test_update_head() {
         let dir = temp_dir();
@@ -491,7 +1424,7 @@ mod tests {
         // Should be able to fast-forward
         assert!(manager.can_fast_forward("feature", "main").unwrap());
 
-        manager.merge_fast_forward("feature", "main").unwrap();
+        manager.merge_fast_forward("feature", "main", false).unwrap();
 
         // Main should now have orders
         let main = manager.get("main").unwrap();
@@ -519,7 +1452,7 @@ mod tests {
         // Should NOT be able to fast-forward (true conflict — both changed users)
         assert!(!manager.can_fast_forward("feature", "main").unwrap());
 
-        let result = manager.merge_fast_forward("feature", "main");
+        let result = manager.merge_fast_forward("feature", "main", false);
         assert!(matches!(result, Err(BranchError::MergeConflict(_))));
 
         fs::remove_dir_all(&dir).ok();
@@ -547,7 +1480,7 @@ mod tests {
 
         // Three-way merge should succeed
         assert!(manager.can_fast_forward("feature", "main").unwrap());
-        manager.merge_fast_forward("feature", "main").unwrap();
+        manager.merge_fast_forward("feature", "main", false).unwrap();
 
         // After merge: main should have users=2 (from feature) and orders=2 (kept)
         let main = manager.get("main").unwrap();
@@ -557,6 +1490,221 @@ mod tests {
         fs::remove_dir_all(&dir).ok();
     }
 
+    #[test]
+    fn test_merge_with_resolutions() {
+        let dir = temp_dir();
+        let manager = BranchManager::new(&dir).unwrap();
+
+        manager.update_head("main", "users", 1).unwrap();
+        manager.update_head("main", "orders", 1).unwrap();
+        manager.create("feature", Some("main"), None).unwrap();
+
+        // Both branches change both tables
+        for table in ["users", "orders"] {
+            manager.update_head("feature", table, 2).unwrap();
+            manager.update_head("main", table, 3).unwrap();
+        }
+
+        assert_eq!(manager.merge_conflicts("feature", "main").unwrap(), vec!["orders", "users"]);
+
+        // Only the unresolved table is reported
+        let partial = HashMap::from([("users".to_string(), MergeResolution::Source)]);
+        let result = manager.merge_with_resolutions("feature", "main", &partial, false);
+        assert!(matches!(result, Err(BranchError::MergeConflict(t)) if t == vec!["orders".to_string()]));
+        assert_eq!(manager.get("main").unwrap().get_table_version("users"), Some(3));
+
+        let resolutions = HashMap::from([
+            ("users".to_string(), MergeResolution::Source),
+            ("orders".to_string(), MergeResolution::Target),
+        ]);
+        manager.merge_with_resolutions("feature", "main", &resolutions, false).unwrap();
+        let main = manager.get("main").unwrap();
+        assert_eq!(main.get_table_version("users"), Some(2));
+        assert_eq!(main.get_table_version("orders"), Some(3));
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_merge_commit_records_both_parents() {
+        let dir = temp_dir();
+        let manager = BranchManager::new(&dir).unwrap();
+        manager.update_head("main", "users", 1).unwrap();
+        manager.create("feature", Some("main"), None).unwrap();
+        manager.update_head("feature", "users", 2).unwrap();
+        manager.update_head("feature", "orders", 1).unwrap();
+        manager.update_head("main", "users", 3).unwrap();
+        let main_head = manager.get("main").unwrap().snapshot_id.unwrap();
+        let feature_head = manager.get("feature").unwrap().snapshot_id.unwrap();
+
+        let strict = manager.merge("feature", "main", MergeStrategy::Strict);
+        assert!(matches!(strict, Err(BranchError::MergeConflict(t)) if t == vec!["users".to_string()]));
+
+        let merge = manager.merge("feature", "main", MergeStrategy::PreferSource).unwrap();
+        assert_eq!(merge.parents, vec![main_head, feature_head]);
+        assert_eq!(merge.resolved, vec!["users"]);
+        assert_eq!(
+            merge.versions,
+            HashMap::from([("users".to_string(), 2), ("orders".to_string(), 1)])
+        );
+        assert_eq!(merge.replaced, HashMap::from([("users".to_string(), 3)]));
+
+        let commit = &manager.history("main", Some(1)).unwrap()[0];
+        assert!(commit.is_merge());
+        assert_eq!(commit.id, merge.snapshot_id);
+        assert_eq!(commit.parents, merge.parents);
+        assert_eq!(manager.common_ancestor("main", "feature").unwrap().unwrap().id, feature_head);
+
+        // Nothing left to merge: still recorded, as a merge that moved nothing
+        let again = manager.merge("feature", "main", MergeStrategy::Strict).unwrap();
+        assert!(again.versions.is_empty());
+        assert!(manager.history("main", Some(1)).unwrap()[0].is_merge());
+        assert!(manager.merge("main", "main", MergeStrategy::Strict).is_err());
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_merge_and_delete_source() {
+        let dir = temp_dir();
+        let manager = BranchManager::new(&dir).unwrap();
+        manager.update_head("main", "users", 1).unwrap();
+        manager.create("feature", Some("main"), None).unwrap();
+        manager.update_head("feature", "users", 2).unwrap();
+
+        // A checked-out source is refused before anything is merged
+        manager.checkout("ws-1", "feature").unwrap();
+        let result = manager.merge_fast_forward("feature", "main", true);
+        assert!(matches!(result, Err(BranchError::BranchCheckedOut { .. })));
+        assert_eq!(manager.get("main").unwrap().get_table_version("users"), Some(1));
+
+        manager.release_checkout("ws-1").unwrap();
+        manager.merge_fast_forward("feature", "main", true).unwrap();
+        assert_eq!(manager.get("main").unwrap().get_table_version("users"), Some(2));
+        assert_eq!(manager.list().unwrap(), vec!["main"]);
+
+        // A conflicting merge leaves the source in place
+        manager.create("a", Some("main"), None).unwrap();
+        manager.update_head("a", "users", 3).unwrap();
+        manager.update_head("main", "users", 4).unwrap();
+        assert!(manager.merge_fast_forward("a", "main", true).is_err());
+        assert!(manager.get("a").is_ok());
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_repo_snapshots_track_head_changes() {
+        let dir = temp_dir();
+        let manager = BranchManager::new(&dir).unwrap();
+        let initial = manager.latest_snapshot("main").unwrap().unwrap();
+        assert!(initial.versions.is_empty());
+
+        manager.update_head("main", "users", 1).unwrap();
+        let multi = manager
+            .update_heads("main", &HashMap::from([("users".to_string(), 2), ("orders".to_string(), 1)]))
+            .unwrap();
+        assert_eq!(multi.id, initial.id + 2);
+        assert_eq!(multi.to_string(), format!("repo@{}", multi.id));
+
+        // Ids are repository-wide: the next change on any branch continues them
+        let feature = manager.create("feature", Some("main"), None).unwrap();
+        assert_eq!(feature.snapshot_id, Some(multi.id + 1));
+        manager.update_head("feature", "users", 3).unwrap();
+        manager.merge_fast_forward("feature", "main", false).unwrap();
+        assert_eq!(manager.get("main").unwrap().snapshot_id, Some(multi.id + 3));
+
+        // Old snapshots still resolve to what they recorded
+        let cited = manager.snapshot(RepoSnapshot::parse_ref("repo@3").unwrap()).unwrap();
+        assert_eq!(cited, multi);
+        assert_eq!(cited.versions.get("users"), Some(&2));
+        let ids: Vec<u64> = manager.snapshots(Some("main")).unwrap().iter().map(|s| s.id).collect();
+        assert_eq!(ids, vec![1, 2, 3, 6]);
+        assert!(matches!(manager.snapshot(99), Err(BranchError::SnapshotNotFound(99))));
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_history_and_common_ancestor() {
+        let dir = temp_dir();
+        let manager = BranchManager::new(&dir).unwrap();
+        manager.update_head("main", "users", 1).unwrap();
+        let fork = manager.create("feature", Some("main"), None).unwrap();
+        manager.update_head("main", "users", 2).unwrap();
+        manager.update_head("feature", "orders", 1).unwrap();
+        manager.commit_heads("feature", &HashMap::from([("users".to_string(), 3)]), Some(7)).unwrap();
+
+        let history = manager.history("feature", None).unwrap();
+        assert_eq!(history.len(), 3);
+        let latest = &history[0];
+        assert_eq!(latest.tx_id, Some(7));
+        assert_eq!(latest.parents, vec![history[1].id]);
+        assert_eq!(
+            latest.changes,
+            vec![history::HeadChange {
+                table: "users".to_string(),
+                old_version: Some(1),
+                new_version: Some(3),
+            }]
+        );
+        // The fork follows main's head at the time and changes nothing
+        assert_eq!(history[2].id, fork.snapshot_id.unwrap());
+        assert!(history[2].changes.is_empty());
+        assert_eq!(manager.history("main", Some(2)).unwrap().len(), 2);
+
+        let base = manager.common_ancestor("main", "feature").unwrap().unwrap();
+        assert_eq!(base.branch, "main");
+        assert_eq!(history[2].parents, vec![base.id]);
+        assert_eq!(manager.snapshot(base.id).unwrap().versions.get("users"), Some(&1));
+
+        // A branch created at a ref shares no recorded history
+        let at = ResolvedRef {
+            spec: crate::refspec::RefSpec::Snapshot(base.id),
+            branch: None,
+            versions: HashMap::from([("users".to_string(), 1)]),
+        };
+        manager.create_at("detached", &at, None).unwrap();
+        assert!(manager.common_ancestor("main", "detached").unwrap().is_none());
+        assert!(matches!(manager.history("missing", None), Err(BranchError::BranchNotFound(_))));
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_merge_and_archive() {
+        let dir = temp_dir();
+        let manager = BranchManager::new(&dir).unwrap();
+        manager.update_head("main", "users", 1).unwrap();
+        manager.create("feature/x", Some("main"), None).unwrap();
+        manager.update_head("feature/x", "orders", 1).unwrap();
+
+        let archived = manager.merge_and_archive("feature/x", "main").unwrap();
+        assert_eq!(archived.merged_into, "main");
+        assert_eq!(manager.get("main").unwrap().get_table_version("orders"), Some(1));
+        assert!(matches!(manager.get("feature/x"), Err(BranchError::BranchNotFound(_))));
+        assert_eq!(manager.list().unwrap(), vec!["main"]);
+
+        let listed = manager.archived().unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].branch.name, "feature/x");
+        assert_eq!(listed[0].branch.get_table_version("orders"), Some(1));
+
+        // The default branch can be merged elsewhere but never archived
+        manager.create("other", None, None).unwrap();
+        assert!(matches!(
+            manager.merge_and_archive("main", "other"),
+            Err(BranchError::CannotDeleteDefault(_))
+        ));
+
+        // Archived branches can be brought back
+        assert!(manager.unarchive("feature/x").unwrap().fork_point.is_some());
+        assert!(manager.get("feature/x").is_ok());
+        assert!(manager.archived().unwrap().is_empty());
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
     #[test]
     fn test_three_way_true_conflict_in_manager() {
         // Both branches change the same table — true conflict
@@ -574,7 +1722,7 @@ mod tests {
 
         // Should fail — true conflict
         assert!(!manager.can_fast_forward("feature", "main").unwrap());
-        let result = manager.merge_fast_forward("feature", "main");
+        let result = manager.merge_fast_forward("feature", "main", false);
         assert!(matches!(result, Err(BranchError::MergeConflict(_))));
 
         fs::remove_dir_all(&dir).ok();
@@ -600,7 +1748,7 @@ mod tests {
         manager.save_branch(&legacy).unwrap();
 
         // Merge legacy into main — should NOT destroy orders
-        manager.merge_fast_forward("legacy", "main").unwrap();
+        manager.merge_fast_forward("legacy", "main", false).unwrap();
 
         let main = manager.get("main").unwrap();
         // orders must still be v5 (target-only, never touched by source)
@@ -624,7 +1772,7 @@ mod tests {
         legacy.set_table_version("users", 5);
         manager.save_branch(&legacy).unwrap();
 
-        manager.merge_fast_forward("legacy", "main").unwrap();
+        manager.merge_fast_forward("legacy", "main", false).unwrap();
 
         let main = manager.get("main").unwrap();
         assert_eq!(main.get_table_version("users"), Some(5));
@@ -644,7 +1792,7 @@ mod tests {
         legacy.set_table_version("metrics", 3);
         manager.save_branch(&legacy).unwrap();
 
-        manager.merge_fast_forward("legacy", "main").unwrap();
+        manager.merge_fast_forward("legacy", "main", false).unwrap();
 
         let main = manager.get("main").unwrap();
         assert_eq!(main.get_table_version("users"), Some(1)); // preserved
@@ -669,7 +1817,7 @@ mod tests {
         legacy.set_table_version("products", 5);  // equal
         manager.save_branch(&legacy).unwrap();
 
-        manager.merge_fast_forward("legacy", "main").unwrap();
+        manager.merge_fast_forward("legacy", "main", false).unwrap();
 
         let main = manager.get("main").unwrap();
         assert_eq!(main.get_table_version("users"), Some(10));    // kept (higher)
@@ -698,7 +1846,7 @@ mod tests {
         manager.update_head("main", "orders", 2).unwrap();
 
         // Three-way merge should auto-resolve
-        manager.merge_fast_forward("feature", "main").unwrap();
+        manager.merge_fast_forward("feature", "main", false).unwrap();
 
         let main = manager.get("main").unwrap();
         assert_eq!(main.get_table_version("users"), Some(2));   // from feature
@@ -742,4 +1890,118 @@ mod tests {
 
         fs::remove_dir_all(&dir).ok();
     }
+
+    #[test]
+    fn test_immutable_branch() {
+        let dir = temp_dir();
+        let manager = BranchManager::new(&dir).unwrap();
+        manager.update_head("main", "users", 1).unwrap();
+        manager.create("release", None, None).unwrap();
+        manager.create("feature", None, None).unwrap();
+        manager.update_head("feature", "users", 2).unwrap();
+
+        manager.set_immutable("release", true).unwrap();
+        assert!(manager.get("release").unwrap().immutable);
+
+        let immutable = |r: Result<(), BranchError>| matches!(r, Err(BranchError::BranchImmutable(ref n)) if n == "release");
+        assert!(immutable(manager.update_head("release", "users", 2)));
+        assert!(immutable(manager.merge_fast_forward("feature", "release", false)));
+        assert!(immutable(manager.delete("release")));
+        assert_eq!(manager.get_table_version("release", "users").unwrap(), Some(1));
+
+        // Reading from and branching off an immutable branch still works
+        manager.create("hotfix", Some("release"), None).unwrap();
+        assert!(!manager.get("hotfix").unwrap().immutable);
+
+        manager.set_immutable("release", false).unwrap();
+        manager.update_head("release", "users", 2).unwrap();
+        manager.delete("release").unwrap();
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_repair_branch_rolls_back_dangling_heads() {
+        use crate::catalog::TableVersion;
+
+        let dir = temp_dir();
+        let manager = BranchManager::new(&dir).unwrap();
+        let catalog = FileCatalog::new(dir.join("catalog")).unwrap();
+        for v in 1..=3 {
+            catalog.commit(TableVersion::new("users", v, vec![format!("h{}", v)])).unwrap();
+        }
+        catalog.commit(TableVersion::new("orders", 1, vec!["o1".to_string()])).unwrap();
+        manager.update_head("main", "users", 2).unwrap();
+        manager.update_head("main", "orders", 1).unwrap();
+        manager.update_head("main", "gone", 4).unwrap();
+        catalog.delete_version("users", 2).unwrap();
+
+        let branch = manager.get("main").unwrap();
+        assert!(matches!(
+            catalog.get_version_on_branch("users", None, &branch),
+            Err(CatalogError::DanglingHead { version: 2, .. })
+        ));
+
+        let report = manager.repair_branch("main", &catalog, RepairStrategy::ReportOnly).unwrap();
+        assert!(!report.repaired);
+        assert_eq!(
+            report.dangling,
+            vec![
+                DanglingHead { table: "gone".to_string(), version: 4, rollback_to: None },
+                DanglingHead { table: "users".to_string(), version: 2, rollback_to: Some(1) },
+            ]
+        );
+        assert_eq!(manager.get_table_version("main", "users").unwrap(), Some(2));
+
+        let repair = manager.repair_branch("main", &catalog, RepairStrategy::default()).unwrap();
+        assert!(repair.repaired);
+        let main = manager.get("main").unwrap();
+        assert_eq!(main.head.get("users"), Some(&1));
+        assert_eq!(main.head.get("orders"), Some(&1));
+        assert!(!main.head.contains_key("gone"));
+        assert!(manager.repair_branch("main", &catalog, RepairStrategy::default()).unwrap().is_clean());
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_resolve_read_follows_heads_written_elsewhere() {
+        use crate::catalog::TableVersion;
+
+        let dir = temp_dir();
+        let manager = BranchManager::new(&dir).unwrap();
+        let catalog = FileCatalog::new(dir.join("catalog")).unwrap();
+        for v in 1..=3 {
+            catalog.commit(TableVersion::new("users", v, vec![format!("h{}", v)])).unwrap();
+        }
+        catalog.commit(TableVersion::new("orders", 1, vec!["o1".to_string()])).unwrap();
+        manager.update_head("main", "users", 1).unwrap();
+
+        assert_eq!(manager.resolve_read(&catalog, "main", "users").unwrap().version, 1);
+        assert_eq!(manager.resolve_read(&catalog, "main", "users").unwrap().chunk_hashes, vec!["h1"]);
+        // Unscoped tables the head does not name read their latest version
+        assert_eq!(manager.resolve_read(&catalog, "main", "orders").unwrap().version, 1);
+
+        // Another process moves the head and then deletes the branch
+        let other = BranchManager::new(&dir).unwrap();
+        other.create("dev", None, None).unwrap();
+        other.update_head("main", "users", 2).unwrap();
+        assert_eq!(manager.resolve_read(&catalog, "main", "users").unwrap().version, 2);
+        assert_eq!(manager.get_stamped("main").unwrap().1.head.get("users"), Some(&2));
+
+        assert_eq!(manager.resolve_read(&catalog, "dev", "users").unwrap().version, 1);
+        other.delete("dev").unwrap();
+        assert!(matches!(
+            manager.resolve_read(&catalog, "dev", "users"),
+            Err(BranchError::BranchNotFound(_))
+        ));
+
+        catalog.delete_version("users", 2).unwrap();
+        assert!(matches!(
+            manager.resolve_read(&catalog, "main", "users"),
+            Err(BranchError::Catalog(CatalogError::DanglingHead { version: 2, .. }))
+        ));
+
+        fs::remove_dir_all(&dir).ok();
+    }
 }
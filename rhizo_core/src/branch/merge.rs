@@ -116,6 +116,112 @@ impl MergeOutcome {
     }
 }
 
+/// Which side's version a conflicting table takes in a merge.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MergeResolution {
+    /// Take the source branch's version
+    Source,
+    /// Keep the target branch's version
+    Target,
+}
+
+impl std::fmt::Display for MergeResolution {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MergeResolution::Source => write!(f, "source"),
+            MergeResolution::Target => write!(f, "target"),
+        }
+    }
+}
+
+/// How [`BranchManager::merge`](super::BranchManager::merge) settles
+/// tables both branches changed since the fork point.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+pub enum MergeStrategy {
+    /// Fail with `MergeConflict` on any true conflict
+    #[default]
+    Strict,
+    /// Conflicting tables take the source branch's version
+    PreferSource,
+    /// Conflicting tables keep the target branch's version
+    PreferTarget,
+}
+
+impl MergeStrategy {
+    /// The resolution this strategy applies to every conflict, if any
+    pub fn resolution(&self) -> Option<MergeResolution> {
+        match self {
+            MergeStrategy::Strict => None,
+            MergeStrategy::PreferSource => Some(MergeResolution::Source),
+            MergeStrategy::PreferTarget => Some(MergeResolution::Target),
+        }
+    }
+}
+
+impl std::fmt::Display for MergeStrategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MergeStrategy::Strict => write!(f, "strict"),
+            MergeStrategy::PreferSource => write!(f, "prefer-source"),
+            MergeStrategy::PreferTarget => write!(f, "prefer-target"),
+        }
+    }
+}
+
+impl std::str::FromStr for MergeStrategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "strict" => Ok(MergeStrategy::Strict),
+            "prefer-source" | "source" => Ok(MergeStrategy::PreferSource),
+            "prefer-target" | "target" => Ok(MergeStrategy::PreferTarget),
+            other => Err(format!("Unknown merge strategy: {}", other)),
+        }
+    }
+}
+
+/// A merge commit: the target branch's new heads and the two commits
+/// they descend from.
+///
+/// Kept in the branch history (as a commit with both parents) and, for
+/// merges made through the transaction manager, on the transaction that
+/// recorded the merge.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+pub struct MergeRecord {
+    /// Branch merged from
+    pub source: String,
+
+    /// Branch merged into
+    pub into: String,
+
+    /// How conflicts were settled
+    pub strategy: MergeStrategy,
+
+    /// Snapshot ids of the target's and the source's heads before the
+    /// merge (either is absent for heads recorded before snapshots existed)
+    pub parents: Vec<u64>,
+
+    /// Snapshot id recorded for the merged heads
+    pub snapshot_id: u64,
+
+    /// Tables whose head on the target moved, with their merged version
+    pub versions: HashMap<String, u64>,
+
+    /// The target's versions of those tables before the merge (tables new
+    /// to the target are absent)
+    #[serde(default)]
+    pub replaced: HashMap<String, u64>,
+
+    /// Conflicting tables settled by the strategy
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub resolved: Vec<String>,
+}
+
 /// Analyzer for algebraic merge compatibility.
 ///
 /// This determines which tables can be auto-merged based on their
@@ -159,6 +265,25 @@ impl<'a> MergeAnalyzer<'a> {
         }
     }
 
+    /// Analyze tables changed concurrently on both sides, outside a branch
+    /// diff (e.g. a commit racing other commits on the same branch).
+    ///
+    /// Every table lands in `auto_mergeable` or `conflicting`.
+    pub fn analyze_tables<S: AsRef<str>>(&self, tables: &[S]) -> MergeAnalysis {
+        let (auto_mergeable, conflicting) = tables
+            .iter()
+            .map(|t| t.as_ref().to_string())
+            .partition(|t| self.can_auto_merge_table(t));
+
+        MergeAnalysis {
+            auto_mergeable,
+            conflicting,
+            source_only: Vec::new(),
+            target_only: Vec::new(),
+            unchanged: Vec::new(),
+        }
+    }
+
     /// Check if a table can be auto-merged based on its schema.
     ///
     /// A table can be auto-merged if:
@@ -341,6 +466,17 @@ mod tests {
         assert!(conflict_cols.contains(&"name".to_string()));
     }
 
+    #[test]
+    fn test_analyze_tables_without_diff() {
+        let registry = create_test_registry();
+        let analyzer = MergeAnalyzer::new(&registry);
+
+        let analysis = analyzer.analyze_tables(&["counters", "settings", "unknown"]);
+
+        assert_eq!(analysis.auto_mergeable, vec!["counters"]);
+        assert_eq!(analysis.conflicting, vec!["settings", "unknown"]);
+    }
+
     #[test]
     fn test_merge_outcome_success() {
         let outcome = MergeOutcome::success(
@@ -2,10 +2,20 @@
 
 pub mod branch;
 pub mod error;
+pub mod head;
+pub mod history;
 pub mod manager;
 pub mod merge;
+mod read_cache;
+pub mod repair;
+pub mod snapshot;
 
-pub use branch::{Branch, BranchDiff};
+pub use branch::{ArchivedBranch, Branch, BranchDiff};
 pub use error::BranchError;
+pub use head::{Checkout, HeadEvent, HeadRecord};
+pub use history::{BranchCommit, HeadChange};
 pub use manager::BranchManager;
-pub use merge::{MergeAnalysis, MergeAnalyzer, MergeOutcome};
+pub(crate) use read_cache::FileStamp;
+pub use merge::{MergeAnalysis, MergeAnalyzer, MergeOutcome, MergeRecord, MergeResolution, MergeStrategy};
+pub use repair::{BranchRepair, DanglingHead, RepairStrategy};
+pub use snapshot::RepoSnapshot;
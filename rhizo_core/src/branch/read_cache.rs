@@ -0,0 +1,125 @@
+//! Branch heads cached together with the table versions they name.
+//!
+//! Resolving a read on a branch takes the branch file and a catalog version
+//! file. Both are cached here and checked against the files' metadata on
+//! every lookup, so a read after a commit by any process sees the new
+//! head: a `stat` per file replaces reading and parsing it.
+
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::time::SystemTime;
+
+use parking_lot::Mutex;
+
+use super::branch::Branch;
+use crate::catalog::TableVersion;
+
+/// Identity of a file's contents, as far as its metadata tells.
+///
+/// Branch and version files are replaced by rename, so the inode changes
+/// with every write even when the size and (coarse) mtime do not.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct FileStamp {
+    modified: Option<SystemTime>,
+    len: u64,
+    inode: u64,
+}
+
+impl FileStamp {
+    /// Stamp of the file at `path`, or `None` if it does not exist
+    pub(super) fn of(path: &Path) -> io::Result<Option<Self>> {
+        let meta = match fs::metadata(path) {
+            Ok(meta) => meta,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e),
+        };
+        #[cfg(unix)]
+        let inode = std::os::unix::fs::MetadataExt::ino(&meta);
+        #[cfg(not(unix))]
+        let inode = 0;
+        Ok(Some(Self {
+            modified: meta.modified().ok(),
+            len: meta.len(),
+            inode,
+        }))
+    }
+}
+
+struct CachedBranch {
+    stamp: FileStamp,
+    branch: Branch,
+    /// Table -> the version its head names, with that version file's stamp
+    tables: HashMap<String, (FileStamp, TableVersion)>,
+}
+
+/// Branch name -> the branch and the table versions read through it
+#[derive(Default)]
+pub(super) struct ReadCache {
+    branches: Mutex<HashMap<String, CachedBranch>>,
+}
+
+impl ReadCache {
+    /// The cached branch, if its file still has `stamp`
+    pub(super) fn branch(&self, name: &str, stamp: FileStamp) -> Option<Branch> {
+        let branches = self.branches.lock();
+        branches.get(name).filter(|c| c.stamp == stamp).map(|c| c.branch.clone())
+    }
+
+    /// Cache a branch read when its file had `stamp`, dropping the table
+    /// versions read through an older head
+    pub(super) fn insert_branch(&self, name: &str, stamp: FileStamp, branch: &Branch) {
+        let mut branches = self.branches.lock();
+        if branches.get(name).is_some_and(|c| c.stamp == stamp) {
+            return;
+        }
+        branches.insert(
+            name.to_string(),
+            CachedBranch {
+                stamp,
+                branch: branch.clone(),
+                tables: HashMap::new(),
+            },
+        );
+    }
+
+    /// The cached version of `table` read through `branch`, if the branch
+    /// file still has `branch_stamp` and the version file `version_stamp`
+    pub(super) fn table(
+        &self,
+        branch: &str,
+        branch_stamp: FileStamp,
+        table: &str,
+        version_stamp: FileStamp,
+    ) -> Option<TableVersion> {
+        let branches = self.branches.lock();
+        let cached = branches.get(branch).filter(|c| c.stamp == branch_stamp)?;
+        cached
+            .tables
+            .get(table)
+            .filter(|(stamp, _)| *stamp == version_stamp)
+            .map(|(_, version)| version.clone())
+    }
+
+    /// Cache a table version read through a branch whose file had
+    /// `branch_stamp`. Ignored if the branch was re-read since.
+    pub(super) fn insert_table(
+        &self,
+        branch: &str,
+        branch_stamp: FileStamp,
+        version_stamp: FileStamp,
+        version: &TableVersion,
+    ) {
+        let mut branches = self.branches.lock();
+        if let Some(cached) = branches.get_mut(branch).filter(|c| c.stamp == branch_stamp) {
+            cached
+                .tables
+                .insert(version.table_name.clone(), (version_stamp, version.clone()));
+        }
+    }
+
+    pub(super) fn remove(&self, name: &str) {
+        self.branches.lock().remove(name);
+    }
+}
@@ -0,0 +1,54 @@
+//! Repair of branch heads that point at versions the catalog no longer has.
+//!
+//! A partial restore or a manual catalog cleanup can leave a branch head
+//! naming a version with no record. Reads through the branch then fail
+//! with `CatalogError::DanglingHead`; [`BranchManager::repair_branch`]
+//! finds such heads and, if asked, rolls each back to the newest older
+//! version that still exists.
+//!
+//! [`BranchManager::repair_branch`]: super::BranchManager::repair_branch
+
+use serde::{Deserialize, Serialize};
+
+/// What `repair_branch` does with dangling heads.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum RepairStrategy {
+    /// Move each dangling head to the newest existing version below it,
+    /// or drop the table from the branch if there is none
+    #[default]
+    RollbackToLatestExisting,
+    /// Report dangling heads without changing the branch
+    ReportOnly,
+}
+
+/// A branch head naming a missing table version.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DanglingHead {
+    pub table: String,
+
+    /// The missing version the head points at
+    pub version: u64,
+
+    /// Version the head is (or would be) rolled back to; `None` removes
+    /// the table from the branch
+    pub rollback_to: Option<u64>,
+}
+
+/// Outcome of `repair_branch`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BranchRepair {
+    pub branch: String,
+
+    /// Dangling heads found, sorted by table
+    pub dangling: Vec<DanglingHead>,
+
+    /// True if the branch heads were rewritten
+    pub repaired: bool,
+}
+
+impl BranchRepair {
+    /// True if every head pointed at an existing version
+    pub fn is_clean(&self) -> bool {
+        self.dangling.is_empty()
+    }
+}
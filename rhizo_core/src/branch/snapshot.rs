@@ -0,0 +1,142 @@
+//! Repository snapshots: one number for a consistent cross-table state.
+//!
+//! Every change to a branch's heads (a commit, merge, table drop or rename,
+//! or creating the branch) records a [`RepoSnapshot`]: the branch's full
+//! table → version map under a repository-wide id that only increases. The
+//! id can be cited on its own (`repo@1234`) and resolved back to the exact
+//! versions later, instead of enumerating every table.
+//!
+//! Snapshots live in `_snapshots/<id>.json` next to the branch files, with
+//! the last assigned id in `_snapshots/_seq`, both written under
+//! `_snapshots/.lock`.
+
+use fs2::FileExt;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::branch::Branch;
+use super::error::BranchError;
+use super::head;
+use crate::durability::{self, DurabilityLevel};
+
+/// Directory holding snapshots
+pub(super) const SNAPSHOTS_DIR: &str = "_snapshots";
+
+/// Last assigned snapshot id
+const SEQ_FILE: &str = "_seq";
+
+/// Lock serializing id assignment (and the head update that uses it)
+const LOCK_FILE: &str = ".lock";
+
+/// A branch's table versions at one point, under a repository-wide id.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RepoSnapshot {
+    /// Repository-wide id, increasing with every recorded snapshot
+    pub id: u64,
+
+    /// Branch whose heads were recorded
+    pub branch: String,
+
+    /// Table -> version on the branch
+    pub versions: HashMap<String, u64>,
+
+    /// Unix timestamp when recorded
+    pub created_at: i64,
+}
+
+impl RepoSnapshot {
+    /// Parse a snapshot citation, `repo@1234` or a bare `1234`.
+    pub fn parse_ref(reference: &str) -> Option<u64> {
+        let id = reference.trim();
+        id.strip_prefix("repo@").unwrap_or(id).parse().ok()
+    }
+}
+
+impl fmt::Display for RepoSnapshot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "repo@{}", self.id)
+    }
+}
+
+fn dir(branches_dir: &Path) -> PathBuf {
+    branches_dir.join(SNAPSHOTS_DIR)
+}
+
+fn path(branches_dir: &Path, id: u64) -> PathBuf {
+    dir(branches_dir).join(format!("{}.json", id))
+}
+
+/// Take the snapshot lock; held until the returned file is dropped
+pub(super) fn lock(branches_dir: &Path) -> Result<fs::File, BranchError> {
+    fs::create_dir_all(dir(branches_dir))?;
+    let file = fs::OpenOptions::new()
+        .create(true)
+        .write(true)
+        .truncate(false)
+        .open(dir(branches_dir).join(LOCK_FILE))?;
+    file.lock_exclusive()?;
+    Ok(file)
+}
+
+/// Assign the next id to the branch's current heads and write the
+/// snapshot. The caller holds the snapshot lock.
+pub(super) fn record(branches_dir: &Path, branch: &Branch, level: DurabilityLevel) -> Result<RepoSnapshot, BranchError> {
+    let seq_path = dir(branches_dir).join(SEQ_FILE);
+    let last = if seq_path.exists() {
+        fs::read_to_string(&seq_path)?.trim().parse::<u64>().unwrap_or(0)
+    } else {
+        0
+    };
+
+    let snapshot = RepoSnapshot {
+        id: last + 1,
+        branch: branch.name.clone(),
+        versions: branch.head.clone(),
+        created_at: head::now(),
+    };
+    let snapshot_path = path(branches_dir, snapshot.id);
+    durability::write(&snapshot_path, serde_json::to_string_pretty(&snapshot)?, level)?;
+
+    let temp_path = seq_path.with_extension("tmp");
+    durability::write_atomic(&seq_path, &temp_path, snapshot.id.to_string(), level)?;
+    Ok(snapshot)
+}
+
+/// Remove a snapshot whose head update did not land (best effort; the id
+/// is not reused)
+pub(super) fn discard(branches_dir: &Path, id: u64) {
+    let _ = fs::remove_file(path(branches_dir, id));
+}
+
+pub(super) fn read(branches_dir: &Path, id: u64) -> Result<Option<RepoSnapshot>, BranchError> {
+    let path = path(branches_dir, id);
+    if !path.exists() {
+        return Ok(None);
+    }
+    Ok(Some(serde_json::from_str(&fs::read_to_string(path)?)?))
+}
+
+/// Every snapshot, optionally of one branch, oldest first
+pub(super) fn list(branches_dir: &Path, branch: Option<&str>) -> Result<Vec<RepoSnapshot>, BranchError> {
+    let dir = dir(branches_dir);
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut snapshots = Vec::new();
+    for entry in fs::read_dir(&dir)? {
+        let path = entry?.path();
+        if path.extension().and_then(|e| e.to_str()) != Some("json") {
+            continue;
+        }
+        let snapshot: RepoSnapshot = serde_json::from_str(&fs::read_to_string(&path)?)?;
+        if branch.is_none_or(|b| b == snapshot.branch) {
+            snapshots.push(snapshot);
+        }
+    }
+    snapshots.sort_by_key(|s| s.id);
+    Ok(snapshots)
+}
@@ -1,6 +1,12 @@
+use std::time::Duration;
+
 use thiserror::Error;
 
+use super::lock::{describe, LockOwner};
+use crate::error::{io_retry_after, ErrorCategory, ErrorContext, ErrorInfo, LOCK_RETRY_DELAY};
+
 #[derive(Error, Debug)]
+#[non_exhaustive]
 pub enum CatalogError {
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
@@ -11,6 +17,9 @@ pub enum CatalogError {
     #[error("Table not found: {0}")]
     TableNotFound(String),
 
+    #[error("Table already exists: {0}")]
+    TableAlreadyExists(String),
+
     #[error("Version not found: {0} v{1}")]
     VersionNotFound(String, u64),
 
@@ -23,6 +32,182 @@ pub enum CatalogError {
     #[error("Failed to acquire file lock for table: {0}")]
     LockError(String),
 
+    #[error("Timed out after {waited:?} waiting for the lock on {table} ({})", describe(.owner))]
+    LockTimeout { table: String, waited: Duration, owner: Option<LockOwner> },
+
+    #[error("Lock on {table} is {}; it is not known to be stale", describe(.owner))]
+    LockHeld { table: String, owner: Option<LockOwner> },
+
     #[error("Cannot delete latest version: {0} v{1}")]
     CannotDeleteLatest(String, u64),
+
+    #[error("Invalid table name: {0}")]
+    InvalidTableName(String),
+
+    #[error("Invalid pin name: {0}")]
+    InvalidPinName(String),
+
+    #[error("Pin not found: {0}")]
+    PinNotFound(String),
+
+    #[error("Pin already exists: {0}")]
+    PinAlreadyExists(String),
+
+    #[error("Version is pinned: {0} v{1} (pin: {2})")]
+    VersionPinned(String, u64, String),
+
+    #[error("Version is under legal hold: {0} v{1}")]
+    VersionHeld(String, u64),
+
+    #[error("No legal hold on {0} v{1}")]
+    HoldNotFound(String, u64),
+
+    #[error("Invalid tag name: {0}")]
+    InvalidTagName(String),
+
+    #[error("Tag not found: {0}")]
+    TagNotFound(String),
+
+    #[error("Tag already exists: {0}")]
+    TagAlreadyExists(String),
+
+    #[error("Version is tagged: {0} v{1} (tag: {2})")]
+    VersionTagged(String, u64, String),
+
+    #[error("View not found: {0}")]
+    ViewNotFound(String),
+
+    #[error("View already exists: {0}")]
+    ViewAlreadyExists(String),
+
+    #[error("Invalid view {0}: {1}")]
+    InvalidView(String, String),
+
+    #[error("Branch {branch} head for {table} points at missing v{version}; repair the branch")]
+    DanglingHead { table: String, version: u64, branch: String },
+
+    #[error("Invalid schema hash: {0}")]
+    InvalidSchemaHash(String),
+
+    #[error("Schema of {table} changes from {expected} to {found}; commit with a schema migration")]
+    SchemaConflict { table: String, expected: String, found: String },
+}
+
+impl ErrorInfo for CatalogError {
+    fn code(&self) -> &'static str {
+        match self {
+            CatalogError::Io(_) => "CATALOG_IO",
+            CatalogError::Json(_) => "CATALOG_JSON",
+            CatalogError::TableNotFound(_) => "CATALOG_TABLE_NOT_FOUND",
+            CatalogError::TableAlreadyExists(_) => "CATALOG_TABLE_EXISTS",
+            CatalogError::VersionNotFound(..) => "CATALOG_VERSION_NOT_FOUND",
+            CatalogError::InvalidVersion { .. } => "CATALOG_INVALID_VERSION",
+            CatalogError::LatestPointerCorrupted(_) => "CATALOG_LATEST_POINTER_CORRUPTED",
+            CatalogError::LockError(_) => "CATALOG_LOCK",
+            CatalogError::LockTimeout { .. } => "CATALOG_LOCK_TIMEOUT",
+            CatalogError::LockHeld { .. } => "CATALOG_LOCK_HELD",
+            CatalogError::CannotDeleteLatest(..) => "CATALOG_CANNOT_DELETE_LATEST",
+            CatalogError::InvalidTableName(_) => "CATALOG_INVALID_TABLE_NAME",
+            CatalogError::InvalidPinName(_) => "CATALOG_INVALID_PIN_NAME",
+            CatalogError::PinNotFound(_) => "CATALOG_PIN_NOT_FOUND",
+            CatalogError::PinAlreadyExists(_) => "CATALOG_PIN_EXISTS",
+            CatalogError::VersionPinned(..) => "CATALOG_VERSION_PINNED",
+            CatalogError::VersionHeld(..) => "CATALOG_VERSION_HELD",
+            CatalogError::HoldNotFound(..) => "CATALOG_HOLD_NOT_FOUND",
+            CatalogError::InvalidTagName(_) => "CATALOG_INVALID_TAG_NAME",
+            CatalogError::TagNotFound(_) => "CATALOG_TAG_NOT_FOUND",
+            CatalogError::TagAlreadyExists(_) => "CATALOG_TAG_EXISTS",
+            CatalogError::VersionTagged(..) => "CATALOG_VERSION_TAGGED",
+            CatalogError::ViewNotFound(_) => "CATALOG_VIEW_NOT_FOUND",
+            CatalogError::ViewAlreadyExists(_) => "CATALOG_VIEW_EXISTS",
+            CatalogError::InvalidView(..) => "CATALOG_INVALID_VIEW",
+            CatalogError::DanglingHead { .. } => "CATALOG_DANGLING_HEAD",
+            CatalogError::InvalidSchemaHash(_) => "CATALOG_INVALID_SCHEMA_HASH",
+            CatalogError::SchemaConflict { .. } => "CATALOG_SCHEMA_CONFLICT",
+        }
+    }
+
+    fn category(&self) -> ErrorCategory {
+        match self {
+            CatalogError::Io(_) => ErrorCategory::Io,
+            CatalogError::Json(_) => ErrorCategory::Internal,
+            CatalogError::TableNotFound(_)
+            | CatalogError::VersionNotFound(..)
+            | CatalogError::PinNotFound(_)
+            | CatalogError::TagNotFound(_)
+            | CatalogError::ViewNotFound(_)
+            | CatalogError::HoldNotFound(..) => ErrorCategory::NotFound,
+            // A concurrent commit took the version first
+            CatalogError::InvalidVersion { .. }
+            | CatalogError::LockError(_)
+            | CatalogError::LockTimeout { .. } => ErrorCategory::Conflict,
+            CatalogError::LatestPointerCorrupted(_) | CatalogError::DanglingHead { .. } => ErrorCategory::DataIntegrity,
+            CatalogError::InvalidTableName(_)
+            | CatalogError::InvalidPinName(_)
+            | CatalogError::InvalidTagName(_)
+            | CatalogError::InvalidView(..)
+            | CatalogError::InvalidSchemaHash(_) => ErrorCategory::InvalidArgument,
+            CatalogError::TableAlreadyExists(_)
+            | CatalogError::PinAlreadyExists(_)
+            | CatalogError::TagAlreadyExists(_)
+            | CatalogError::ViewAlreadyExists(_) => ErrorCategory::AlreadyExists,
+            CatalogError::CannotDeleteLatest(..)
+            | CatalogError::VersionPinned(..)
+            | CatalogError::VersionTagged(..)
+            | CatalogError::VersionHeld(..)
+            | CatalogError::LockHeld { .. }
+            | CatalogError::SchemaConflict { .. } => ErrorCategory::FailedPrecondition,
+        }
+    }
+
+    fn context(&self) -> ErrorContext {
+        match self {
+            CatalogError::TableNotFound(table)
+            | CatalogError::TableAlreadyExists(table)
+            | CatalogError::LatestPointerCorrupted(table)
+            | CatalogError::LockError(table) => ErrorContext::new().with_table(table.clone()),
+            CatalogError::LockTimeout { table, .. }
+            | CatalogError::LockHeld { table, .. }
+            | CatalogError::SchemaConflict { table, .. } => {
+                ErrorContext::new().with_table(table.clone())
+            }
+            CatalogError::VersionNotFound(table, version)
+            | CatalogError::CannotDeleteLatest(table, version)
+            | CatalogError::VersionHeld(table, version)
+            | CatalogError::HoldNotFound(table, version) => {
+                ErrorContext::new().with_table(table.clone()).with_version(*version)
+            }
+            CatalogError::InvalidVersion { got, .. } => ErrorContext::new().with_version(*got),
+            CatalogError::DanglingHead { table, version, branch } => ErrorContext::new()
+                .with_table(table.clone())
+                .with_version(*version)
+                .with_branch(branch.clone()),
+            CatalogError::PinNotFound(name)
+            | CatalogError::PinAlreadyExists(name)
+            | CatalogError::TagNotFound(name)
+            | CatalogError::TagAlreadyExists(name)
+            | CatalogError::ViewNotFound(name)
+            | CatalogError::ViewAlreadyExists(name)
+            | CatalogError::InvalidView(name, _) => ErrorContext::new().with_key(name.clone()),
+            CatalogError::VersionPinned(table, version, name) | CatalogError::VersionTagged(table, version, name) => {
+                ErrorContext::new()
+                    .with_table(table.clone())
+                    .with_version(*version)
+                    .with_key(name.clone())
+            }
+            _ => ErrorContext::new(),
+        }
+    }
+
+    /// Lock failures and transient I/O are retryable. A version that
+    /// another commit took first (`InvalidVersion`) is not: the caller must
+    /// re-read the latest version before committing again. Neither is a
+    /// lock timeout, whose holder may be stuck (see `FileCatalog::lock_owner`).
+    fn retry_after(&self) -> Option<Duration> {
+        match self {
+            CatalogError::LockError(_) => Some(LOCK_RETRY_DELAY),
+            CatalogError::Io(e) => io_retry_after(e),
+            _ => None,
+        }
+    }
 }
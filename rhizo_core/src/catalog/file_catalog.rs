@@ -1,10 +1,42 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
 use std::path::{Path, PathBuf};
-use fs2::FileExt;
+use std::time::Duration;
 use serde::{Deserialize, Serialize};
 use super::error::CatalogError;
-use super::version::TableVersion;
+use super::version::{TableVersion, ALGEBRAIC_SCHEMA_METADATA_KEY};
+use super::namespace;
+use super::pin::{self, Pin};
+use super::hold::LegalHold;
+use super::lock::{self, LockOwner, DEFAULT_LOCK_TIMEOUT};
+use super::schema::{SchemaChange, SchemaMigration, SchemaRegistry, SCHEMAS_DIR};
+use super::retention::{PruneRecord, RedactionRecord, RetentionPolicy, SquashRecord};
+use super::tag::{self, Tag};
+use super::view::{self, View};
+use crate::algebraic::{AlgebraicSchemaRegistry, TableAlgebraicSchema};
+use crate::branch::{Branch, RepoSnapshot};
+use crate::durability::{self, DurabilityLevel};
+
+/// File recording the branch a table was created on (branch-scoped tables only)
+const ORIGIN_FILE: &str = "origin";
+
+/// Directory (under the catalog root) holding version pins
+const PINS_DIR: &str = ".pins";
+
+/// File (per table directory) holding legal holds, keyed by version
+const HOLDS_FILE: &str = "_holds.json";
+
+/// File (per table directory) holding the table's retention policy
+const RETENTION_FILE: &str = "_retention.json";
+
+/// File (per table directory) recording pruned versions
+const PRUNED_FILE: &str = "_pruned.json";
+
+/// File (per table directory) recording squashed versions
+const SQUASHED_FILE: &str = "_squashed.json";
+
+/// File (per table directory) recording redactions
+const REDACTED_FILE: &str = "_redacted.json";
 
 /// A pending commit intent written to disk before the actual catalog commit.
 ///
@@ -12,6 +44,7 @@ use super::version::TableVersion;
 /// intent files survive on disk. On recovery, they identify which chunks were
 /// part of an incomplete commit and can be cleaned up.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct PendingCommit {
     /// Unique identifier for this pending commit
     pub intent_id: String,
@@ -25,6 +58,8 @@ pub struct PendingCommit {
 
 pub struct FileCatalog {
     base_path: PathBuf,
+    durability: DurabilityLevel,
+    lock_timeout: Duration,
 }
 
 impl FileCatalog {
@@ -33,7 +68,53 @@ impl FileCatalog {
         fs::create_dir_all(&base_path)?;
         // Ensure the pending intents directory exists
         fs::create_dir_all(base_path.join(".pending"))?;
-        Ok(Self { base_path })
+        Ok(Self {
+            base_path,
+            durability: DurabilityLevel::default(),
+            lock_timeout: DEFAULT_LOCK_TIMEOUT,
+        })
+    }
+
+    /// Flush version, pointer and intent files as `level` requires
+    /// (see [`crate::durability`])
+    pub fn with_durability(mut self, level: DurabilityLevel) -> Self {
+        self.durability = level;
+        self
+    }
+
+    pub fn durability(&self) -> DurabilityLevel {
+        self.durability
+    }
+
+    /// Give up on a table or directory lock after `timeout`, failing with
+    /// `LockTimeout` (default [`DEFAULT_LOCK_TIMEOUT`])
+    pub fn with_lock_timeout(mut self, timeout: Duration) -> Self {
+        self.lock_timeout = timeout;
+        self
+    }
+
+    pub fn lock_timeout(&self) -> Duration {
+        self.lock_timeout
+    }
+
+    /// Root directory of the catalog
+    pub(super) fn base_path(&self) -> &Path {
+        &self.base_path
+    }
+
+    /// Whether a version file exists for the table
+    pub(super) fn version_exists(&self, table_name: &str, version: u64) -> bool {
+        self.table_dir(table_name).join(format!("{}.json", version)).exists()
+    }
+
+    /// File holding one version of a table
+    pub(crate) fn version_path(&self, table_name: &str, version: u64) -> PathBuf {
+        self.table_dir(table_name).join(format!("{}.json", version))
+    }
+
+    /// Directory holding a table's versions (name is filesystem-encoded)
+    fn table_dir(&self, table_name: &str) -> PathBuf {
+        self.base_path.join(namespace::encode_table_name(table_name))
     }
 
     /// Acquire an exclusive file lock for a table directory.
@@ -41,54 +122,41 @@ impl FileCatalog {
     /// Returns the lock file handle — the lock is held until the handle is dropped.
     /// This provides cross-process mutual exclusion for the read-modify-write
     /// sequence in commit (read latest → check version → write → update latest).
+    /// Waits at most the catalog's lock timeout.
     fn acquire_table_lock(&self, table_name: &str) -> Result<fs::File, CatalogError> {
-        let table_dir = self.base_path.join(table_name);
+        let table_dir = self.table_dir(table_name);
         fs::create_dir_all(&table_dir)?;
-        let lock_path = table_dir.join(".lock");
-        let lock_file = fs::OpenOptions::new()
-            .create(true)
-            .write(true)
-            .truncate(false)
-            .open(&lock_path)
-            .map_err(|e| CatalogError::LockError(
-                format!("{}: {}", table_name, e)
-            ))?;
-        lock_file.lock_exclusive().map_err(|e| CatalogError::LockError(
-            format!("{}: {}", table_name, e)
-        ))?;
-        Ok(lock_file)
+        lock::acquire(&table_dir.join(".lock"), table_name, self.lock_timeout)
+    }
+
+    /// The process holding a table's lock, or `None` when it is free (or
+    /// its holder recorded no owner).
+    ///
+    /// A commit that fails with `LockTimeout` reports the same owner; use
+    /// [`LockOwner::is_stale`] to tell whether it is safe to break.
+    pub fn lock_owner(&self, table_name: &str) -> Result<Option<LockOwner>, CatalogError> {
+        namespace::validate_table_name(table_name)?;
+        lock::holder(&self.table_dir(table_name).join(".lock"), table_name)
+    }
+
+    /// Break a table's lock left behind by a holder that no longer runs,
+    /// returning the owner it recorded (`None` if the lock was free).
+    ///
+    /// Fails with `LockHeld` unless the owner is known to be gone; `force`
+    /// breaks it anyway, which is only safe once the holder is confirmed
+    /// dead by other means (e.g. a crashed host on a network filesystem).
+    pub fn break_lock(&self, table_name: &str, force: bool) -> Result<Option<LockOwner>, CatalogError> {
+        namespace::validate_table_name(table_name)?;
+        lock::break_lock(&self.table_dir(table_name).join(".lock"), table_name, force)
     }
 
     pub fn commit(&self, version: TableVersion) -> Result<u64, CatalogError> {
-        let table_dir = self.base_path.join(&version.table_name);
-        fs::create_dir_all(&table_dir)?;
+        namespace::validate_table_name(&version.table_name)?;
+        fs::create_dir_all(self.table_dir(&version.table_name))?;
 
         // Acquire cross-process file lock for this table
         let _lock = self.acquire_table_lock(&version.table_name)?;
-
-        // Check version sequence (safe under lock)
-        let expected_version = self.get_latest_version_num(&version.table_name)? + 1;
-        if version.version != expected_version {
-            return Err(CatalogError::InvalidVersion {
-                expected: expected_version,
-                got: version.version,
-            });
-        }
-
-        // Write version file atomically (write to temp, then rename)
-        let version_path = table_dir.join(format!("{}.json", version.version));
-        let temp_version_path = version_path.with_extension("json.tmp");
-        let json = serde_json::to_string_pretty(&version)?;
-        fs::write(&temp_version_path, &json)?;
-        fs::rename(&temp_version_path, &version_path)?;
-
-        // Update latest pointer atomically
-        let latest_path = table_dir.join("latest");
-        let temp_latest_path = table_dir.join("latest.tmp");
-        fs::write(&temp_latest_path, version.version.to_string())?;
-        fs::rename(&temp_latest_path, &latest_path)?;
-
-        Ok(version.version)
+        self.commit_inner(version)
         // _lock dropped here — file lock released
     }
 
@@ -107,6 +175,8 @@ impl FileCatalog {
         table_name: &str,
         chunk_hashes: Vec<String>,
     ) -> Result<u64, CatalogError> {
+        namespace::validate_table_name(table_name)?;
+
         // Step 1: Write pending intent to disk BEFORE committing
         let intent_id = self.write_pending_intent(table_name, &chunk_hashes)?;
 
@@ -140,6 +210,8 @@ impl FileCatalog {
         metadata: HashMap<String, String>,
         schema_hash: Option<String>,
     ) -> Result<u64, CatalogError> {
+        namespace::validate_table_name(table_name)?;
+
         let intent_id = self.write_pending_intent(table_name, &chunk_hashes)?;
 
         let result = {
@@ -159,8 +231,8 @@ impl FileCatalog {
     }
 
     /// Internal commit without acquiring the lock (caller must hold it).
-    fn commit_inner(&self, version: TableVersion) -> Result<u64, CatalogError> {
-        let table_dir = self.base_path.join(&version.table_name);
+    fn commit_inner(&self, mut version: TableVersion) -> Result<u64, CatalogError> {
+        let table_dir = self.table_dir(&version.table_name);
         fs::create_dir_all(&table_dir)?;
 
         // Check version sequence
@@ -172,22 +244,63 @@ impl FileCatalog {
             });
         }
 
+        // Refuse a schema change the table's lineage does not allow
+        if let Some(hash) = &version.schema_hash {
+            self.check_schema_change(&version.table_name, hash, version.schema_migration().as_ref())?;
+        }
+
+        // Algebraic annotations stay with the table until a version sets them
+        if !version.metadata.contains_key(ALGEBRAIC_SCHEMA_METADATA_KEY) {
+            if let Some(parent) = version.parent_version {
+                if let Some(json) = self
+                    .get_version(&version.table_name, Some(parent))
+                    .ok()
+                    .and_then(|p| p.metadata.get(ALGEBRAIC_SCHEMA_METADATA_KEY).cloned())
+                {
+                    version.metadata.insert(ALGEBRAIC_SCHEMA_METADATA_KEY.to_string(), json);
+                }
+            }
+        }
+
+        self.write_version(&table_dir, &version)
+    }
+
+    /// Write a version file and advance the latest pointer (caller holds the lock)
+    fn write_version(&self, table_dir: &Path, version: &TableVersion) -> Result<u64, CatalogError> {
         // Write version file atomically (write to temp, then rename)
         let version_path = table_dir.join(format!("{}.json", version.version));
         let temp_version_path = version_path.with_extension("json.tmp");
-        let json = serde_json::to_string_pretty(&version)?;
-        fs::write(&temp_version_path, &json)?;
-        fs::rename(&temp_version_path, &version_path)?;
+        let json = serde_json::to_string_pretty(version)?;
+        durability::write_atomic(&version_path, &temp_version_path, &json, self.durability)?;
 
         // Update latest pointer atomically
         let latest_path = table_dir.join("latest");
         let temp_latest_path = table_dir.join("latest.tmp");
-        fs::write(&temp_latest_path, version.version.to_string())?;
-        fs::rename(&temp_latest_path, &latest_path)?;
+        durability::write_atomic(&latest_path, &temp_latest_path, version.version.to_string(), self.durability)?;
 
         Ok(version.version)
     }
 
+    /// Write a version record verbatim, as copied from another repository.
+    ///
+    /// Unlike [`FileCatalog::commit`], gaps are allowed (the source may have
+    /// garbage-collected older versions); the version only has to be newer
+    /// than the table's latest.
+    pub fn restore_version(&self, version: TableVersion) -> Result<u64, CatalogError> {
+        namespace::validate_table_name(&version.table_name)?;
+        let _lock = self.acquire_table_lock(&version.table_name)?;
+
+        let latest = self.get_latest_version_num(&version.table_name)?;
+        if version.version <= latest {
+            return Err(CatalogError::InvalidVersion {
+                expected: latest + 1,
+                got: version.version,
+            });
+        }
+
+        self.write_version(&self.table_dir(&version.table_name), &version)
+    }
+
     // === Write-Ahead Intent Log ===
 
     /// Write a pending commit intent to disk.
@@ -213,8 +326,7 @@ impl FileCatalog {
         let intent_path = pending_dir.join(format!("{}.json", intent_id));
         let temp_path = intent_path.with_extension("json.tmp");
         let json = serde_json::to_string(&pending)?;
-        fs::write(&temp_path, &json)?;
-        fs::rename(&temp_path, &intent_path)?;
+        durability::write_atomic(&intent_path, &temp_path, &json, self.durability)?;
 
         Ok(intent_id)
     }
@@ -283,7 +395,7 @@ impl FileCatalog {
     /// Check whether a set of chunk hashes is referenced by any committed version
     /// of the given table.
     fn is_committed(&self, table_name: &str, chunk_hashes: &[String]) -> Result<bool, CatalogError> {
-        let table_dir = self.base_path.join(table_name);
+        let table_dir = self.table_dir(table_name);
         if !table_dir.exists() {
             return Ok(false);
         }
@@ -299,7 +411,7 @@ impl FileCatalog {
     }
 
     pub fn get_version(&self, table_name: &str, version: Option<u64>) -> Result<TableVersion, CatalogError> {
-        let table_dir = self.base_path.join(table_name);
+        let table_dir = self.table_dir(table_name);
         
         if !table_dir.exists() {
             return Err(CatalogError::TableNotFound(table_name.to_string()));
@@ -323,7 +435,7 @@ impl FileCatalog {
     }
 
     pub fn list_versions(&self, table_name: &str) -> Result<Vec<u64>, CatalogError> {
-        let table_dir = self.base_path.join(table_name);
+        let table_dir = self.table_dir(table_name);
         
         if !table_dir.exists() {
             return Err(CatalogError::TableNotFound(table_name.to_string()));
@@ -361,84 +473,1054 @@ impl FileCatalog {
                     if name.starts_with('.') {
                         continue;
                     }
-                    tables.push(name.to_string());
+                    if let Some(decoded) = namespace::decode_table_name(name) {
+                        tables.push(decoded);
+                    }
+                }
+            }
+        }
+
+        tables.sort();
+        Ok(tables)
+    }
+
+    /// List tables under a namespace prefix (e.g. `sales` or `org/team`).
+    ///
+    /// Matches on segment boundaries, see [`namespace::in_namespace`].
+    pub fn list_tables_with_prefix(&self, prefix: &str) -> Result<Vec<String>, CatalogError> {
+        Ok(self
+            .list_tables()?
+            .into_iter()
+            .filter(|t| namespace::in_namespace(t, prefix))
+            .collect())
+    }
+
+    /// List tables matching a wildcard pattern (e.g. `sales.*`, `org/**`).
+    ///
+    /// See [`namespace::matches_pattern`] for the pattern syntax.
+    pub fn list_tables_matching(&self, pattern: &str) -> Result<Vec<String>, CatalogError> {
+        Ok(self
+            .list_tables()?
+            .into_iter()
+            .filter(|t| namespace::matches_pattern(t, pattern))
+            .collect())
+    }
+
+    /// Collect chunk hashes referenced by any version of the selected tables.
+    ///
+    /// `selector` is a namespace prefix or wildcard pattern
+    /// (see [`namespace::selects`]).
+    pub fn get_referenced_chunk_hashes_in(
+        &self,
+        selector: &str,
+    ) -> Result<std::collections::HashSet<String>, CatalogError> {
+        let mut referenced = std::collections::HashSet::new();
+
+        for table_name in self.list_tables()? {
+            if !namespace::selects(&table_name, selector) {
+                continue;
+            }
+            for version_num in self.list_versions(&table_name)? {
+                let version = self.get_version(&table_name, Some(version_num))?;
+                referenced.extend(version.referenced_chunks());
+            }
+        }
+
+        Ok(referenced)
+    }
+
+    /// Delete a specific version of a table from the catalog.
+    ///
+    /// Removes the version JSON file from disk and returns the deleted
+    /// `TableVersion` so the caller can collect its chunk hashes for GC.
+    ///
+    /// # Errors
+    /// - `CannotDeleteLatest` if `version` is the current latest version.
+    /// - `TableNotFound` if the table directory does not exist.
+    /// - `VersionNotFound` if the version file does not exist.
+    pub fn delete_version(
+        &self,
+        table_name: &str,
+        version: u64,
+    ) -> Result<TableVersion, CatalogError> {
+        let table_dir = self.table_dir(table_name);
+        if !table_dir.exists() {
+            return Err(CatalogError::TableNotFound(table_name.to_string()));
+        }
+
+        let _lock = self.acquire_table_lock(table_name)?;
+
+        let latest = self.get_latest_version_num(table_name)?;
+        if version == latest {
+            return Err(CatalogError::CannotDeleteLatest(
+                table_name.to_string(),
+                version,
+            ));
+        }
+
+        let version_path = table_dir.join(format!("{}.json", version));
+        if !version_path.exists() {
+            return Err(CatalogError::VersionNotFound(
+                table_name.to_string(),
+                version,
+            ));
+        }
+
+        self.ensure_unprotected(table_name, Some(version))?;
+
+        let json = fs::read_to_string(&version_path)?;
+        let table_version: TableVersion = serde_json::from_str(&json)?;
+
+        durability::remove_file(&version_path, self.durability)?;
+
+        Ok(table_version)
+    }
+
+    /// Fail if a version of the table (`None` = any version) is under
+    /// legal hold, pinned or tagged.
+    fn ensure_unprotected(&self, table_name: &str, version: Option<u64>) -> Result<(), CatalogError> {
+        let matches = |v: Option<u64>| v.is_some() && (version.is_none() || v == version);
+
+        if let Some(&held) = self
+            .read_holds(table_name)?
+            .keys()
+            .find(|&&v| matches(Some(v)))
+        {
+            return Err(CatalogError::VersionHeld(table_name.to_string(), held));
+        }
+
+        if let Some(pin) = self
+            .list_pins()?
+            .into_iter()
+            .find(|p| matches(p.get(table_name)))
+        {
+            return Err(CatalogError::VersionPinned(
+                table_name.to_string(),
+                pin.get(table_name).unwrap_or_default(),
+                pin.name,
+            ));
+        }
+
+        if let Some(tag) = tag::read_tags(&self.base_path)?
+            .into_iter()
+            .find(|t| matches(t.get(table_name)))
+        {
+            return Err(CatalogError::VersionTagged(
+                table_name.to_string(),
+                tag.get(table_name).unwrap_or_default(),
+                tag.name,
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Remove a table and all of its versions from the catalog.
+    ///
+    /// Returns the removed versions, oldest first, so the caller can
+    /// collect their chunk hashes for GC. Branch heads and the changelog
+    /// are not touched here (see `TransactionManager::drop_table`).
+    ///
+    /// # Errors
+    /// - `TableNotFound` if the table has no versions.
+    /// - `VersionHeld` / `VersionPinned` / `VersionTagged` if any version is
+    ///   protected; release it first.
+    pub fn drop_table(&self, table_name: &str) -> Result<Vec<TableVersion>, CatalogError> {
+        if !self.table_exists(table_name)? {
+            return Err(CatalogError::TableNotFound(table_name.to_string()));
+        }
+
+        let _lock = self.acquire_table_lock(table_name)?;
+        self.ensure_unprotected(table_name, None)?;
+
+        let mut versions = Vec::new();
+        for version in self.list_versions(table_name)? {
+            versions.push(self.get_version(table_name, Some(version))?);
+        }
+
+        // Hide the table with one rename before deleting its files
+        self.discard_dir(&self.table_dir(table_name))?;
+        Ok(versions)
+    }
+
+    /// Rename a table, keeping its full version history.
+    ///
+    /// Every version record is rewritten under the new name in a staging
+    /// directory that is then moved into place, so readers see either the
+    /// old table or the complete new one. Returns the latest version.
+    ///
+    /// # Errors
+    /// - `TableNotFound` if `old_name` has no versions.
+    /// - `TableAlreadyExists` if `new_name` is taken.
+    /// - `VersionHeld` / `VersionPinned` / `VersionTagged` if any version of
+    ///   `old_name` is protected (pins and tags refer to tables by name).
+    pub fn rename_table(&self, old_name: &str, new_name: &str) -> Result<u64, CatalogError> {
+        namespace::validate_table_name(new_name)?;
+        if !self.table_exists(old_name)? {
+            return Err(CatalogError::TableNotFound(old_name.to_string()));
+        }
+
+        let _lock = self.acquire_table_lock(old_name)?;
+        self.ensure_unprotected(old_name, None)?;
+
+        let new_dir = self.table_dir(new_name);
+        if new_dir.exists() || self.view_exists(new_name) {
+            return Err(CatalogError::TableAlreadyExists(new_name.to_string()));
+        }
+
+        let old_dir = self.table_dir(old_name);
+        let staging = self.base_path.join(format!(".renaming-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&staging)?;
+        for version in self.list_versions(old_name)? {
+            let mut record = self.get_version(old_name, Some(version))?;
+            record.table_name = new_name.to_string();
+            durability::write(
+                &staging.join(format!("{}.json", version)),
+                serde_json::to_string_pretty(&record)?,
+                self.durability,
+            )?;
+        }
+        for file in ["latest", ORIGIN_FILE, RETENTION_FILE, PRUNED_FILE, SQUASHED_FILE, REDACTED_FILE] {
+            if old_dir.join(file).exists() {
+                durability::write(&staging.join(file), fs::read(old_dir.join(file))?, self.durability)?;
+            }
+        }
+
+        if let Some(parent) = new_dir.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        durability::rename(&staging, &new_dir, self.durability)?;
+        self.discard_dir(&old_dir)?;
+
+        self.get_latest_version_num(new_name)
+    }
+
+    /// Move a directory out of sight (internal names start with `.`) and
+    /// delete it
+    fn discard_dir(&self, dir: &Path) -> Result<(), CatalogError> {
+        let trash = self.base_path.join(format!(".dropped-{}", uuid::Uuid::new_v4()));
+        durability::rename(dir, &trash, self.durability)?;
+        fs::remove_dir_all(&trash)?;
+        Ok(())
+    }
+
+    /// Collect all chunk hashes referenced by all versions of all tables.
+    ///
+    /// Scans every table and every version in the catalog, returning the
+    /// union of all `chunk_hashes` and base chunks. Used by garbage collection to determine
+    /// which chunks in the store are still needed.
+    pub fn get_all_referenced_chunk_hashes(
+        &self,
+    ) -> Result<std::collections::HashSet<String>, CatalogError> {
+        let mut referenced = std::collections::HashSet::new();
+
+        for table_name in self.list_tables()? {
+            for version_num in self.list_versions(&table_name)? {
+                let version = self.get_version(&table_name, Some(version_num))?;
+                for hash in version.referenced_chunks() {
+                    referenced.insert(hash);
                 }
             }
         }
 
-        tables.sort();
-        Ok(tables)
+        Ok(referenced)
+    }
+
+    // === Branch-Scoped Visibility ===
+
+    /// Check whether a table has at least one committed version
+    pub fn table_exists(&self, table_name: &str) -> Result<bool, CatalogError> {
+        Ok(self.get_latest_version_num(table_name)? > 0)
+    }
+
+    /// Record the branch a table is being created on.
+    ///
+    /// Tables with an origin are only visible on branches whose head contains
+    /// them (see [`FileCatalog::is_visible_on`]). The first recorded origin
+    /// wins; later calls for the same table are no-ops.
+    pub fn set_table_origin(&self, table_name: &str, branch: &str) -> Result<(), CatalogError> {
+        namespace::validate_table_name(table_name)?;
+
+        let _lock = self.acquire_table_lock(table_name)?;
+        let origin_path = self.table_dir(table_name).join(ORIGIN_FILE);
+        if origin_path.exists() {
+            return Ok(());
+        }
+
+        let temp_path = origin_path.with_extension("tmp");
+        durability::write_atomic(&origin_path, &temp_path, branch, self.durability)?;
+        Ok(())
+    }
+
+    /// Branch a table was created on, or `None` for unscoped tables
+    pub fn table_origin(&self, table_name: &str) -> Result<Option<String>, CatalogError> {
+        let origin_path = self.table_dir(table_name).join(ORIGIN_FILE);
+        if !origin_path.exists() {
+            return Ok(None);
+        }
+        Ok(Some(fs::read_to_string(&origin_path)?.trim().to_string()))
+    }
+
+    /// Check whether a table is visible on a branch.
+    ///
+    /// Unscoped tables (no recorded origin) are visible everywhere. Tables
+    /// created on a branch are visible only where the branch head contains
+    /// them, i.e. on their origin branch and on branches they were merged into.
+    pub fn is_visible_on(&self, table_name: &str, branch: &Branch) -> Result<bool, CatalogError> {
+        if branch.head.contains_key(table_name) {
+            return Ok(true);
+        }
+        Ok(self.table_exists(table_name)? && self.table_origin(table_name)?.is_none())
+    }
+
+    /// List the tables visible on a branch
+    pub fn list_tables_on_branch(&self, branch: &Branch) -> Result<Vec<String>, CatalogError> {
+        let mut visible = Vec::new();
+        for table in self.list_tables()? {
+            if self.is_visible_on(&table, branch)? {
+                visible.push(table);
+            }
+        }
+        Ok(visible)
+    }
+
+    /// Get a table version as seen from a branch.
+    ///
+    /// With `version = None` this resolves to the branch head (falling back to
+    /// latest for unscoped tables not yet pinned by the branch). Tables not
+    /// visible on the branch return `TableNotFound`; a head naming a version
+    /// the catalog lost returns `DanglingHead` (see `BranchManager::repair_branch`).
+    pub fn get_version_on_branch(
+        &self,
+        table_name: &str,
+        version: Option<u64>,
+        branch: &Branch,
+    ) -> Result<TableVersion, CatalogError> {
+        if !self.is_visible_on(table_name, branch)? {
+            return Err(CatalogError::TableNotFound(table_name.to_string()));
+        }
+        let Some(head) = version.is_none().then(|| branch.get_table_version(table_name)).flatten() else {
+            return self.get_version(table_name, version);
+        };
+        match self.get_version(table_name, Some(head)) {
+            Err(CatalogError::VersionNotFound(..) | CatalogError::TableNotFound(_)) => Err(CatalogError::DanglingHead {
+                table: table_name.to_string(),
+                version: head,
+                branch: branch.name.clone(),
+            }),
+            result => result,
+        }
+    }
+
+    /// Get a table version as recorded by a repository snapshot.
+    ///
+    /// Reading every table through the same snapshot gives a consistent
+    /// point-in-time view of the branch. Tables not on the branch when the
+    /// snapshot was recorded return `TableNotFound`.
+    pub fn get_version_as_of(
+        &self,
+        table_name: &str,
+        snapshot: &RepoSnapshot,
+    ) -> Result<TableVersion, CatalogError> {
+        let version = snapshot
+            .versions
+            .get(table_name)
+            .copied()
+            .ok_or_else(|| CatalogError::TableNotFound(table_name.to_string()))?;
+        self.get_version(table_name, Some(version))
+    }
+
+    // === Schemas ===
+
+    /// Full schemas behind the schema hashes versions record
+    pub fn schemas(&self) -> SchemaRegistry {
+        SchemaRegistry::new(self.base_path.join(SCHEMAS_DIR), self.durability)
+    }
+
+    /// Check that the next version of a table may have `schema_hash` (see
+    /// [`SchemaRegistry::check_change`]). Commits run this check; callers
+    /// can run it first to fail before writing anything.
+    ///
+    /// # Errors
+    /// - `SchemaConflict` if the latest version has a different schema hash
+    ///   and the change is neither additive nor allowed by `migration`.
+    pub fn check_schema_change(
+        &self,
+        table_name: &str,
+        schema_hash: &str,
+        migration: Option<&SchemaMigration>,
+    ) -> Result<(), CatalogError> {
+        let latest = match self.get_latest_version_num(table_name)? {
+            0 => return Ok(()),
+            latest => self.get_version(table_name, Some(latest))?,
+        };
+        match latest.schema_hash {
+            Some(current) => self.schemas().check_change(table_name, &current, schema_hash, migration),
+            None => Ok(()),
+        }
+    }
+
+    /// How a table's schema hash changed across its versions, oldest first.
+    /// Versions without a schema hash are skipped.
+    pub fn schema_history(&self, table_name: &str) -> Result<Vec<SchemaChange>, CatalogError> {
+        let schemas = self.schemas();
+        let mut history: Vec<SchemaChange> = Vec::new();
+        for version in self.list_versions(table_name)? {
+            let record = self.get_version(table_name, Some(version))?;
+            let Some(hash) = record.schema_hash.clone() else { continue };
+            let previous_hash = history.last().map(|c| c.schema_hash.clone());
+            if previous_hash.as_ref() == Some(&hash) {
+                continue;
+            }
+            history.push(SchemaChange {
+                version,
+                schema: schemas.get(&hash)?,
+                migration: record.schema_migration().filter(|m| m.to_hash == hash),
+                schema_hash: hash,
+                previous_hash,
+                created_at: record.created_at,
+            });
+        }
+        Ok(history)
+    }
+
+    // === Algebraic Schemas ===
+
+    /// Algebraic column annotations carried by a table version (the latest
+    /// if `version` is None)
+    pub fn algebraic_schema(
+        &self,
+        table_name: &str,
+        version: Option<u64>,
+    ) -> Result<Option<TableAlgebraicSchema>, CatalogError> {
+        Ok(self.get_version(table_name, version)?.algebraic_schema())
+    }
+
+    /// Commit the next version of a table with the latest version's data
+    /// and new algebraic annotations (`None` clears them).
+    ///
+    /// Like any commit, the version becomes a branch's head only once the
+    /// branch is updated to it.
+    pub fn set_algebraic_schema(
+        &self,
+        table_name: &str,
+        schema: Option<&TableAlgebraicSchema>,
+    ) -> Result<u64, CatalogError> {
+        namespace::validate_table_name(table_name)?;
+        let _lock = self.acquire_table_lock(table_name)?;
+
+        let latest = self.get_version(table_name, None)?;
+        let mut version = TableVersion::new(table_name, latest.version + 1, latest.chunk_hashes);
+        version.schema_hash = latest.schema_hash;
+        version.metadata = latest.metadata;
+        self.commit_inner(version.with_algebraic_schema(schema))
+    }
+
+    /// Registry of the algebraic annotations carried by the given table
+    /// versions (e.g. a branch's heads). Unannotated tables are left out.
+    pub fn algebraic_registry(
+        &self,
+        versions: &HashMap<String, u64>,
+    ) -> Result<AlgebraicSchemaRegistry, CatalogError> {
+        let mut registry = AlgebraicSchemaRegistry::new();
+        for (table, &version) in versions {
+            if let Some(schema) = self.algebraic_schema(table, Some(version))? {
+                registry.register(schema);
+            }
+        }
+        Ok(registry)
+    }
+
+    // === Version Pins ===
+
+    fn pin_path(&self, name: &str) -> PathBuf {
+        self.base_path
+            .join(PINS_DIR)
+            .join(format!("{}.json", namespace::encode_table_name(name)))
+    }
+
+    /// Acquire the exclusive lock guarding pin creation and deletion
+    fn acquire_pins_lock(&self) -> Result<fs::File, CatalogError> {
+        self.acquire_dir_lock(PINS_DIR)
+    }
+
+    /// Acquire the exclusive lock of a directory under the catalog root
+    /// (pins, tags), creating the directory if needed
+    pub(super) fn acquire_dir_lock(&self, dir_name: &str) -> Result<fs::File, CatalogError> {
+        let dir = self.base_path.join(dir_name);
+        fs::create_dir_all(&dir)?;
+        lock::acquire(&dir.join(".lock"), dir_name, self.lock_timeout)
+    }
+
+    /// Create an immutable pin over a set of table versions.
+    ///
+    /// Every pinned version must exist. Pins cannot be overwritten; delete the
+    /// pin first to reuse its name. While the pin exists, its versions cannot
+    /// be removed with [`FileCatalog::delete_version`].
+    pub fn pin(
+        &self,
+        name: &str,
+        versions: HashMap<String, u64>,
+        description: Option<String>,
+    ) -> Result<Pin, CatalogError> {
+        pin::validate_pin_name(name)?;
+        for (table, &version) in &versions {
+            if !self.table_dir(table).join(format!("{}.json", version)).exists() {
+                return Err(CatalogError::VersionNotFound(table.clone(), version));
+            }
+        }
+
+        let _lock = self.acquire_pins_lock()?;
+        let path = self.pin_path(name);
+        if path.exists() {
+            return Err(CatalogError::PinAlreadyExists(name.to_string()));
+        }
+
+        let mut new_pin = Pin::new(name, versions);
+        if let Some(desc) = description {
+            new_pin = new_pin.with_description(desc);
+        }
+
+        let temp_path = path.with_extension("json.tmp");
+        durability::write_atomic(&path, &temp_path, serde_json::to_string_pretty(&new_pin)?, self.durability)?;
+
+        Ok(new_pin)
+    }
+
+    /// Look up a pin by name
+    pub fn resolve_pin(&self, name: &str) -> Result<Pin, CatalogError> {
+        pin::validate_pin_name(name)?;
+        let path = self.pin_path(name);
+        if !path.exists() {
+            return Err(CatalogError::PinNotFound(name.to_string()));
+        }
+        Ok(serde_json::from_str(&fs::read_to_string(&path)?)?)
+    }
+
+    /// List all pins, sorted by name
+    pub fn list_pins(&self) -> Result<Vec<Pin>, CatalogError> {
+        let pins_dir = self.base_path.join(PINS_DIR);
+        if !pins_dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut pins = Vec::new();
+        for entry in fs::read_dir(&pins_dir)? {
+            let path = entry?.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("json") {
+                continue;
+            }
+            pins.push(serde_json::from_str::<Pin>(&fs::read_to_string(&path)?)?);
+        }
+
+        pins.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(pins)
+    }
+
+    /// Look up a tag by name (see [`TagManager`](super::TagManager) to
+    /// create or delete one)
+    pub fn resolve_tag(&self, name: &str) -> Result<Tag, CatalogError> {
+        tag::read_tag(&self.base_path, name)
+    }
+
+    /// Delete a pin, releasing its versions for garbage collection
+    pub fn delete_pin(&self, name: &str) -> Result<Pin, CatalogError> {
+        let _lock = self.acquire_pins_lock()?;
+        let existing = self.resolve_pin(name)?;
+        durability::remove_file(&self.pin_path(name), self.durability)?;
+        Ok(existing)
+    }
+
+    /// All pinned versions: table_name -> versions held by at least one pin
+    pub fn pinned_versions(
+        &self,
+    ) -> Result<HashMap<String, std::collections::HashSet<u64>>, CatalogError> {
+        let mut pinned: HashMap<String, std::collections::HashSet<u64>> = HashMap::new();
+        for pin in self.list_pins()? {
+            for (table, version) in pin.versions {
+                pinned.entry(table).or_default().insert(version);
+            }
+        }
+        Ok(pinned)
+    }
+
+    // === Legal Holds ===
+
+    fn read_holds(&self, table_name: &str) -> Result<BTreeMap<u64, LegalHold>, CatalogError> {
+        let path = self.table_dir(table_name).join(HOLDS_FILE);
+        if !path.exists() {
+            return Ok(BTreeMap::new());
+        }
+        let holds: Vec<LegalHold> = serde_json::from_str(&fs::read_to_string(&path)?)?;
+        Ok(holds.into_iter().map(|h| (h.version, h)).collect())
+    }
+
+    fn write_holds(&self, table_name: &str, holds: &BTreeMap<u64, LegalHold>) -> Result<(), CatalogError> {
+        let path = self.table_dir(table_name).join(HOLDS_FILE);
+        if holds.is_empty() {
+            if path.exists() {
+                durability::remove_file(&path, self.durability)?;
+            }
+            return Ok(());
+        }
+        let temp_path = path.with_extension("json.tmp");
+        let list: Vec<&LegalHold> = holds.values().collect();
+        durability::write_atomic(&path, &temp_path, serde_json::to_string_pretty(&list)?, self.durability)?;
+        Ok(())
+    }
+
+    /// Place a legal hold on a table version.
+    ///
+    /// Held versions cannot be deleted until the hold is released. Placing a
+    /// hold on an already-held version keeps and returns the existing hold.
+    pub fn hold(
+        &self,
+        table_name: &str,
+        version: u64,
+        reason: Option<String>,
+    ) -> Result<LegalHold, CatalogError> {
+        if !self.table_dir(table_name).join(format!("{}.json", version)).exists() {
+            return Err(CatalogError::VersionNotFound(table_name.to_string(), version));
+        }
+
+        // Same lock as delete_version, so a hold cannot race a deletion
+        let _lock = self.acquire_table_lock(table_name)?;
+        let mut holds = self.read_holds(table_name)?;
+        if let Some(existing) = holds.get(&version) {
+            return Ok(existing.clone());
+        }
+
+        let new_hold = LegalHold::new(table_name, version, reason);
+        holds.insert(version, new_hold.clone());
+        self.write_holds(table_name, &holds)?;
+        Ok(new_hold)
+    }
+
+    /// Write a legal hold verbatim (keeping its reason and creation time),
+    /// as copied from another repository
+    pub fn restore_hold(&self, hold: &LegalHold) -> Result<(), CatalogError> {
+        if !self.table_dir(&hold.table_name).join(format!("{}.json", hold.version)).exists() {
+            return Err(CatalogError::VersionNotFound(hold.table_name.clone(), hold.version));
+        }
+
+        let _lock = self.acquire_table_lock(&hold.table_name)?;
+        let mut holds = self.read_holds(&hold.table_name)?;
+        holds.insert(hold.version, hold.clone());
+        self.write_holds(&hold.table_name, &holds)
+    }
+
+    /// Release the legal hold on a table version
+    pub fn release_hold(&self, table_name: &str, version: u64) -> Result<LegalHold, CatalogError> {
+        let _lock = self.acquire_table_lock(table_name)?;
+        let mut holds = self.read_holds(table_name)?;
+        let released = holds
+            .remove(&version)
+            .ok_or_else(|| CatalogError::HoldNotFound(table_name.to_string(), version))?;
+        self.write_holds(table_name, &holds)?;
+        Ok(released)
+    }
+
+    /// List all legal holds, sorted by table and version
+    pub fn list_holds(&self) -> Result<Vec<LegalHold>, CatalogError> {
+        let mut holds = Vec::new();
+        for table_name in self.list_tables()? {
+            holds.extend(self.read_holds(&table_name)?.into_values());
+        }
+        Ok(holds)
+    }
+
+    /// All held versions: table_name -> versions under legal hold
+    pub fn held_versions(
+        &self,
+    ) -> Result<HashMap<String, std::collections::HashSet<u64>>, CatalogError> {
+        let mut held: HashMap<String, std::collections::HashSet<u64>> = HashMap::new();
+        for hold in self.list_holds()? {
+            held.entry(hold.table_name).or_default().insert(hold.version);
+        }
+        Ok(held)
+    }
+
+    /// Set (or with `None`, clear) the retention policy of a table
+    pub fn set_retention_policy(
+        &self,
+        table_name: &str,
+        policy: Option<RetentionPolicy>,
+    ) -> Result<(), CatalogError> {
+        if !self.table_exists(table_name)? {
+            return Err(CatalogError::TableNotFound(table_name.to_string()));
+        }
+        let path = self.table_dir(table_name).join(RETENTION_FILE);
+        match policy {
+            Some(policy) => {
+                let temp_path = path.with_extension("json.tmp");
+                durability::write_atomic(&path, &temp_path, serde_json::to_string_pretty(&policy)?, self.durability)?;
+            }
+            None if path.exists() => durability::remove_file(&path, self.durability)?,
+            None => {}
+        }
+        Ok(())
+    }
+
+    /// The retention policy of a table, if one is set
+    pub fn retention_policy(&self, table_name: &str) -> Result<Option<RetentionPolicy>, CatalogError> {
+        let path = self.table_dir(table_name).join(RETENTION_FILE);
+        if !path.exists() {
+            return Ok(None);
+        }
+        Ok(Some(serde_json::from_str(&fs::read_to_string(&path)?)?))
+    }
+
+    /// True if a table version is under legal hold, pinned or tagged
+    pub fn is_protected(&self, table_name: &str, version: u64) -> Result<bool, CatalogError> {
+        match self.ensure_unprotected(table_name, Some(version)) {
+            Ok(()) => Ok(false),
+            Err(
+                CatalogError::VersionHeld(..)
+                | CatalogError::VersionPinned(..)
+                | CatalogError::VersionTagged(..),
+            ) => Ok(true),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Delete several old versions of a table and record them in the
+    /// table's prune history.
+    ///
+    /// Nothing is deleted unless every version can be: the checks of
+    /// [`delete_version`](Self::delete_version) apply to each. Returns the
+    /// deleted versions so the caller can collect their chunk hashes for GC.
+    pub fn prune_versions(
+        &self,
+        table_name: &str,
+        versions: &[u64],
+    ) -> Result<Vec<TableVersion>, CatalogError> {
+        let table_dir = self.table_dir(table_name);
+        if !table_dir.exists() {
+            return Err(CatalogError::TableNotFound(table_name.to_string()));
+        }
+
+        let _lock = self.acquire_table_lock(table_name)?;
+
+        let latest = self.get_latest_version_num(table_name)?;
+        let mut versions = versions.to_vec();
+        versions.sort_unstable();
+        versions.dedup();
+
+        let mut pruned = Vec::with_capacity(versions.len());
+        for &version in &versions {
+            if version == latest {
+                return Err(CatalogError::CannotDeleteLatest(table_name.to_string(), version));
+            }
+            let version_path = table_dir.join(format!("{}.json", version));
+            if !version_path.exists() {
+                return Err(CatalogError::VersionNotFound(table_name.to_string(), version));
+            }
+            self.ensure_unprotected(table_name, Some(version))?;
+            pruned.push(serde_json::from_str::<TableVersion>(&fs::read_to_string(&version_path)?)?);
+        }
+        if pruned.is_empty() {
+            return Ok(pruned);
+        }
+
+        let mut history = self.prune_history(table_name)?;
+        history.push(PruneRecord::new(table_name, versions.clone()));
+        let path = table_dir.join(PRUNED_FILE);
+        let temp_path = path.with_extension("json.tmp");
+        durability::write_atomic(&path, &temp_path, serde_json::to_string_pretty(&history)?, self.durability)?;
+
+        for version in versions {
+            durability::remove_file(&table_dir.join(format!("{}.json", version)), self.durability)?;
+        }
+        Ok(pruned)
+    }
+
+    /// Past `prune_versions` calls on a table, oldest first
+    pub fn prune_history(&self, table_name: &str) -> Result<Vec<PruneRecord>, CatalogError> {
+        let path = self.table_dir(table_name).join(PRUNED_FILE);
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+        Ok(serde_json::from_str(&fs::read_to_string(&path)?)?)
+    }
+
+    /// Fold every version of a table before `up_to_version` into it,
+    /// making `up_to_version` the table's first version.
+    ///
+    /// Version records hold a table's full chunk list, so the base keeps
+    /// its data; it loses its parent and the removed versions are recorded
+    /// in the table's squash history (see [`squashed_into`](Self::squashed_into)).
+    /// Nothing is removed unless every older version can be deleted (none
+    /// under legal hold, pinned or tagged). Returns the removed versions so
+    /// the caller can collect their chunk hashes for GC. Branch heads are
+    /// not checked here (see `VersionPruner::squash_versions`).
+    ///
+    /// # Errors
+    /// - `TableNotFound` / `VersionNotFound` if `up_to_version` does not exist.
+    /// - `VersionHeld` / `VersionPinned` / `VersionTagged` if an older
+    ///   version is protected.
+    pub fn squash_versions(
+        &self,
+        table_name: &str,
+        up_to_version: u64,
+    ) -> Result<Vec<TableVersion>, CatalogError> {
+        let table_dir = self.table_dir(table_name);
+        if !table_dir.exists() {
+            return Err(CatalogError::TableNotFound(table_name.to_string()));
+        }
+
+        let _lock = self.acquire_table_lock(table_name)?;
+
+        let base_path = table_dir.join(format!("{}.json", up_to_version));
+        if !base_path.exists() {
+            return Err(CatalogError::VersionNotFound(table_name.to_string(), up_to_version));
+        }
+        let versions: Vec<u64> = self
+            .list_versions(table_name)?
+            .into_iter()
+            .filter(|&v| v < up_to_version)
+            .collect();
+
+        let mut squashed = Vec::with_capacity(versions.len());
+        for &version in &versions {
+            self.ensure_unprotected(table_name, Some(version))?;
+            squashed.push(self.get_version(table_name, Some(version))?);
+        }
+        if squashed.is_empty() {
+            return Ok(squashed);
+        }
+
+        let mut history = self.squash_history(table_name)?;
+        history.push(SquashRecord::new(table_name, up_to_version, versions.clone()));
+        let path = table_dir.join(SQUASHED_FILE);
+        let temp_path = path.with_extension("json.tmp");
+        durability::write_atomic(&path, &temp_path, serde_json::to_string_pretty(&history)?, self.durability)?;
+
+        let mut base: TableVersion = serde_json::from_str(&fs::read_to_string(&base_path)?)?;
+        base.parent_version = None;
+        let temp_path = base_path.with_extension("json.tmp");
+        durability::write_atomic(&base_path, &temp_path, serde_json::to_string_pretty(&base)?, self.durability)?;
+
+        for version in versions {
+            durability::remove_file(&table_dir.join(format!("{}.json", version)), self.durability)?;
+        }
+        Ok(squashed)
     }
 
-    /// Delete a specific version of a table from the catalog.
+    /// Past `squash_versions` calls on a table, oldest first
+    pub fn squash_history(&self, table_name: &str) -> Result<Vec<SquashRecord>, CatalogError> {
+        let path = self.table_dir(table_name).join(SQUASHED_FILE);
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+        Ok(serde_json::from_str(&fs::read_to_string(&path)?)?)
+    }
+
+    /// Replace the chunk lists and metadata of existing versions of a table
+    /// in place, keeping their version numbers, and append `record` to the
+    /// table's redaction history.
     ///
-    /// Removes the version JSON file from disk and returns the deleted
-    /// `TableVersion` so the caller can collect its chunk hashes for GC.
+    /// Branch heads, pins and tags name versions by number, so they see
+    /// the rewritten chunks without being touched. The history is written
+    /// before the versions, so an interrupted rewrite is still on record.
     ///
     /// # Errors
-    /// - `CannotDeleteLatest` if `version` is the current latest version.
-    /// - `TableNotFound` if the table directory does not exist.
-    /// - `VersionNotFound` if the version file does not exist.
-    pub fn delete_version(
+    /// - `TableNotFound` / `VersionNotFound` if a version does not exist.
+    /// - `VersionHeld` if a version is under legal hold; nothing is
+    ///   rewritten then.
+    pub fn rewrite_versions(
         &self,
         table_name: &str,
-        version: u64,
-    ) -> Result<TableVersion, CatalogError> {
-        let table_dir = self.base_path.join(table_name);
+        rewrites: &[TableVersion],
+        record: RedactionRecord,
+    ) -> Result<(), CatalogError> {
+        let table_dir = self.table_dir(table_name);
         if !table_dir.exists() {
             return Err(CatalogError::TableNotFound(table_name.to_string()));
         }
 
         let _lock = self.acquire_table_lock(table_name)?;
 
-        let latest = self.get_latest_version_num(table_name)?;
-        if version == latest {
-            return Err(CatalogError::CannotDeleteLatest(
-                table_name.to_string(),
-                version,
-            ));
+        let holds = self.read_holds(table_name)?;
+        let mut records = Vec::with_capacity(rewrites.len());
+        for rewrite in rewrites {
+            let path = table_dir.join(format!("{}.json", rewrite.version));
+            if !path.exists() {
+                return Err(CatalogError::VersionNotFound(table_name.to_string(), rewrite.version));
+            }
+            if holds.contains_key(&rewrite.version) {
+                return Err(CatalogError::VersionHeld(table_name.to_string(), rewrite.version));
+            }
+            let mut current: TableVersion = serde_json::from_str(&fs::read_to_string(&path)?)?;
+            current.chunk_hashes = rewrite.chunk_hashes.clone();
+            current.metadata = rewrite.metadata.clone();
+            records.push((path, current));
         }
 
-        let version_path = table_dir.join(format!("{}.json", version));
-        if !version_path.exists() {
-            return Err(CatalogError::VersionNotFound(
-                table_name.to_string(),
-                version,
-            ));
-        }
+        let mut history = self.redaction_history(table_name)?;
+        history.push(record);
+        let path = table_dir.join(REDACTED_FILE);
+        let temp_path = path.with_extension("json.tmp");
+        durability::write_atomic(&path, &temp_path, serde_json::to_string_pretty(&history)?, self.durability)?;
 
-        let json = fs::read_to_string(&version_path)?;
-        let table_version: TableVersion = serde_json::from_str(&json)?;
+        for (path, version) in records {
+            let temp_path = path.with_extension("json.tmp");
+            durability::write_atomic(&path, &temp_path, serde_json::to_string_pretty(&version)?, self.durability)?;
+        }
+        Ok(())
+    }
 
-        fs::remove_file(&version_path)?;
+    /// Past redactions of a table, oldest first
+    pub fn redaction_history(&self, table_name: &str) -> Result<Vec<RedactionRecord>, CatalogError> {
+        let path = self.table_dir(table_name).join(REDACTED_FILE);
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+        Ok(serde_json::from_str(&fs::read_to_string(&path)?)?)
+    }
 
-        Ok(table_version)
+    /// The base version a squashed version of a table was folded into, or
+    /// `None` if it was never squashed
+    pub fn squashed_into(&self, table_name: &str, version: u64) -> Result<Option<u64>, CatalogError> {
+        Ok(self
+            .squash_history(table_name)?
+            .into_iter()
+            .find(|r| r.versions.contains(&version))
+            .map(|r| r.base_version))
     }
 
-    /// Collect all chunk hashes referenced by all versions of all tables.
+    /// Define a view, or redefine it if `replace` is set.
     ///
-    /// Scans every table and every version in the catalog, returning the
-    /// union of all `chunk_hashes`. Used by garbage collection to determine
-    /// which chunks in the store are still needed.
-    pub fn get_all_referenced_chunk_hashes(
-        &self,
-    ) -> Result<std::collections::HashSet<String>, CatalogError> {
-        let mut referenced = std::collections::HashSet::new();
+    /// Views share the table namespace: the name must be a valid table name
+    /// not used by a table. Every source must be an existing table or view,
+    /// and a view may not read itself, directly or through other views.
+    pub fn create_view(&self, view: View, replace: bool) -> Result<View, CatalogError> {
+        namespace::validate_table_name(&view.name)?;
+        if self.table_exists(&view.name)? {
+            return Err(CatalogError::TableAlreadyExists(view.name));
+        }
+        if view.sources.is_empty() {
+            return Err(CatalogError::InvalidView(view.name, "no sources".to_string()));
+        }
 
-        for table_name in self.list_tables()? {
-            for version_num in self.list_versions(&table_name)? {
-                let version = self.get_version(&table_name, Some(version_num))?;
-                for hash in version.chunk_hashes {
-                    referenced.insert(hash);
+        let _lock = self.acquire_dir_lock(view::VIEWS_DIR)?;
+        let path = self.view_path(&view.name);
+        if path.exists() && !replace {
+            return Err(CatalogError::ViewAlreadyExists(view.name));
+        }
+        for source in &view.sources {
+            if source == &view.name || self.view_reads(source, &view.name)? {
+                return Err(CatalogError::InvalidView(
+                    view.name.clone(),
+                    format!("reads itself through {}", source),
+                ));
+            }
+        }
+
+        let temp_path = path.with_extension("json.tmp");
+        durability::write_atomic(&path, &temp_path, serde_json::to_string_pretty(&view)?, self.durability)?;
+        Ok(view)
+    }
+
+    /// Look up a view by name
+    pub fn get_view(&self, name: &str) -> Result<View, CatalogError> {
+        let path = self.view_path(name);
+        if !path.exists() {
+            return Err(CatalogError::ViewNotFound(name.to_string()));
+        }
+        Ok(serde_json::from_str(&fs::read_to_string(&path)?)?)
+    }
+
+    /// Whether a view of this name exists
+    pub fn view_exists(&self, name: &str) -> bool {
+        self.view_path(name).exists()
+    }
+
+    /// List all views, sorted by name
+    pub fn list_views(&self) -> Result<Vec<View>, CatalogError> {
+        let views_dir = self.base_path.join(view::VIEWS_DIR);
+        if !views_dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut views = Vec::new();
+        for entry in fs::read_dir(&views_dir)? {
+            let path = entry?.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("json") {
+                continue;
+            }
+            views.push(serde_json::from_str::<View>(&fs::read_to_string(&path)?)?);
+        }
+
+        views.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(views)
+    }
+
+    /// Delete a view. Views reading it fail at read time until redefined.
+    pub fn drop_view(&self, name: &str) -> Result<View, CatalogError> {
+        let _lock = self.acquire_dir_lock(view::VIEWS_DIR)?;
+        let existing = self.get_view(name)?;
+        durability::remove_file(&self.view_path(name), self.durability)?;
+        Ok(existing)
+    }
+
+    /// Tables a view ultimately reads, through any nested views (sorted).
+    ///
+    /// Readers resolve these to versions to run or cache the view; see
+    /// [`View::materialization_key`].
+    pub fn view_base_tables(&self, name: &str) -> Result<Vec<String>, CatalogError> {
+        let mut tables = std::collections::BTreeSet::new();
+        let mut pending = vec![name.to_string()];
+        let mut seen = std::collections::HashSet::new();
+        while let Some(current) = pending.pop() {
+            if !seen.insert(current.clone()) {
+                continue;
+            }
+            for source in self.get_view(&current)?.sources {
+                if self.view_exists(&source) {
+                    pending.push(source);
+                } else if self.table_exists(&source)? {
+                    tables.insert(source);
+                } else {
+                    return Err(CatalogError::TableNotFound(source));
                 }
             }
         }
+        Ok(tables.into_iter().collect())
+    }
 
-        Ok(referenced)
+    /// Whether `source` exists and, if it is a view, whether it reads the
+    /// view `target` (directly or through other views)
+    fn view_reads(&self, source: &str, target: &str) -> Result<bool, CatalogError> {
+        if !self.view_exists(source) {
+            return if self.table_exists(source)? {
+                Ok(false)
+            } else {
+                Err(CatalogError::TableNotFound(source.to_string()))
+            };
+        }
+
+        let mut pending = vec![source.to_string()];
+        let mut seen = std::collections::HashSet::new();
+        while let Some(current) = pending.pop() {
+            if current == target {
+                return Ok(true);
+            }
+            if !seen.insert(current.clone()) || !self.view_exists(&current) {
+                continue;
+            }
+            pending.extend(self.get_view(&current)?.sources);
+        }
+        Ok(false)
+    }
+
+    fn view_path(&self, name: &str) -> PathBuf {
+        self.base_path
+            .join(view::VIEWS_DIR)
+            .join(format!("{}.json", namespace::encode_table_name(name)))
     }
 
     fn get_latest_version_num(&self, table_name: &str) -> Result<u64, CatalogError> {
-        let latest_path = self.base_path.join(table_name).join("latest");
+        let latest_path = self.table_dir(table_name).join("latest");
 
         if !latest_path.exists() {
             return Ok(0);
@@ -538,6 +1620,179 @@ mod tests {
         fs::remove_dir_all(&dir).ok();
     }
 
+    #[test]
+    fn test_namespaced_tables() {
+        let dir = temp_dir();
+        let catalog = FileCatalog::new(&dir).unwrap();
+
+        catalog.commit_next_version("sales.orders", vec!["o1".to_string()]).unwrap();
+        catalog.commit_next_version("sales/eu/invoices", vec!["i1".to_string()]).unwrap();
+        catalog.commit_next_version("salesforce.leads", vec![]).unwrap();
+        catalog.commit_next_version("users", vec![]).unwrap();
+
+        // Nested names stay a single directory under the catalog root
+        assert!(dir.join("sales%2Feu%2Finvoices").is_dir());
+        assert!(!dir.join("sales").exists());
+
+        let all = catalog.list_tables().unwrap();
+        assert_eq!(all, vec!["sales.orders", "sales/eu/invoices", "salesforce.leads", "users"]);
+
+        let sales = catalog.list_tables_with_prefix("sales").unwrap();
+        assert_eq!(sales, vec!["sales.orders", "sales/eu/invoices"]);
+
+        let matched = catalog.list_tables_matching("sales*.*").unwrap();
+        assert_eq!(matched, vec!["sales.orders", "salesforce.leads"]);
+
+        let tv = catalog.get_version("sales/eu/invoices", None).unwrap();
+        assert_eq!(tv.chunk_hashes, vec!["i1"]);
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_branch_scoped_visibility() {
+        let dir = temp_dir();
+        let catalog = FileCatalog::new(&dir).unwrap();
+
+        catalog.commit_next_version("shared", vec![]).unwrap();
+        catalog.set_table_origin("experiment", "feature").unwrap();
+        catalog.commit_next_version("experiment", vec!["e1".to_string()]).unwrap();
+
+        // First origin wins
+        catalog.set_table_origin("experiment", "other").unwrap();
+        assert_eq!(catalog.table_origin("experiment").unwrap().as_deref(), Some("feature"));
+        assert_eq!(catalog.table_origin("shared").unwrap(), None);
+
+        let main = Branch::new("main", HashMap::new());
+        let mut feature_head = HashMap::new();
+        feature_head.insert("experiment".to_string(), 1);
+        let feature = Branch::new("feature", feature_head);
+
+        assert_eq!(catalog.list_tables_on_branch(&main).unwrap(), vec!["shared"]);
+        assert_eq!(catalog.list_tables_on_branch(&feature).unwrap(), vec!["experiment", "shared"]);
+        assert_eq!(catalog.list_tables().unwrap(), vec!["experiment", "shared"]);
+
+        assert!(catalog.get_version_on_branch("experiment", None, &feature).is_ok());
+        assert!(matches!(
+            catalog.get_version_on_branch("experiment", None, &main),
+            Err(CatalogError::TableNotFound(_))
+        ));
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_get_version_as_of_snapshot() {
+        let dir = temp_dir();
+        let catalog = FileCatalog::new(&dir).unwrap();
+
+        catalog.commit_next_version("users", vec!["u1".to_string()]).unwrap();
+        catalog.commit_next_version("users", vec!["u2".to_string()]).unwrap();
+        catalog.commit_next_version("orders", vec!["o1".to_string()]).unwrap();
+
+        let snapshot = RepoSnapshot {
+            id: 7,
+            branch: "main".to_string(),
+            versions: HashMap::from([("users".to_string(), 1)]),
+            created_at: 0,
+        };
+
+        let tv = catalog.get_version_as_of("users", &snapshot).unwrap();
+        assert_eq!((tv.version, tv.chunk_hashes), (1, vec!["u1".to_string()]));
+        assert!(matches!(
+            catalog.get_version_as_of("orders", &snapshot),
+            Err(CatalogError::TableNotFound(_))
+        ));
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_schema_changes_need_migration() {
+        use arrow::datatypes::{DataType, Field, Schema};
+
+        let dir = temp_dir();
+        let catalog = FileCatalog::new(&dir).unwrap();
+        let schemas = catalog.schemas();
+        let id = Field::new("id", DataType::Int64, false);
+        let v1 = schemas.register_arrow(&Schema::new(vec![id.clone()])).unwrap();
+        let v2 = schemas
+            .register_arrow(&Schema::new(vec![id, Field::new("name", DataType::Utf8, true)]))
+            .unwrap();
+        let v3 = schemas.register_arrow(&Schema::new(vec![Field::new("id", DataType::Utf8, false)])).unwrap();
+        let version = |n: u64, hash: &str| TableVersion::new("users", n, vec![]).with_schema_hash(hash);
+
+        catalog.commit(version(1, &v1)).unwrap();
+        // Adding a nullable column is compatible
+        catalog.commit(version(2, &v2)).unwrap();
+
+        // Dropping and retyping columns needs a migration from the latest hash
+        assert!(matches!(
+            catalog.commit(version(3, &v3)),
+            Err(CatalogError::SchemaConflict { ref expected, .. }) if *expected == v2
+        ));
+        let stale = SchemaMigration::new(&v1, &v3);
+        assert!(catalog.commit(version(3, &v3).with_schema_migration(&stale)).is_err());
+        let migration = SchemaMigration::new(&v2, &v3).with_description("ids become strings");
+        catalog.commit(version(3, &v3).with_schema_migration(&migration)).unwrap();
+        catalog.commit(version(4, &v3)).unwrap();
+
+        let history = catalog.schema_history("users").unwrap();
+        let summary: Vec<_> = history
+            .iter()
+            .map(|c| (c.version, c.schema_hash.as_str(), c.migration.is_some()))
+            .collect();
+        assert_eq!(summary, vec![(1, v1.as_str(), false), (2, v2.as_str(), false), (3, v3.as_str(), true)]);
+        assert_eq!(history[2].previous_hash.as_deref(), Some(v2.as_str()));
+        assert_eq!(history[1].schema.as_ref().unwrap().fields.len(), 2);
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_algebraic_schema_versioned_with_table() {
+        let dir = temp_dir();
+        let catalog = FileCatalog::new(&dir).unwrap();
+        let counters = TableAlgebraicSchema::all_additive("counters");
+
+        catalog.commit_next_version("counters", vec!["c1".to_string()]).unwrap();
+        assert_eq!(catalog.algebraic_schema("counters", None).unwrap(), None);
+        assert_eq!(catalog.set_algebraic_schema("counters", Some(&counters)).unwrap(), 2);
+        assert_eq!(catalog.get_version("counters", Some(2)).unwrap().chunk_hashes, vec!["c1"]);
+
+        // Inherited by later commits, cleared explicitly
+        catalog.commit_next_version("counters", vec!["c3".to_string()]).unwrap();
+        catalog.set_algebraic_schema("counters", None).unwrap();
+        catalog.commit_next_version("counters", vec!["c5".to_string()]).unwrap();
+        let annotated: Vec<bool> = (1..=5)
+            .map(|v| catalog.algebraic_schema("counters", Some(v)).unwrap().is_some())
+            .collect();
+        assert_eq!(annotated, vec![false, true, true, false, false]);
+
+        // Annotations follow a rename
+        catalog.rename_table("counters", "tallies").unwrap();
+        let registry = catalog
+            .algebraic_registry(&HashMap::from([("tallies".to_string(), 3)]))
+            .unwrap();
+        assert_eq!(registry.get_op_type("tallies", "hits"), crate::algebraic::OpType::AbelianAdd);
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_invalid_table_name_rejected() {
+        let dir = temp_dir();
+        let catalog = FileCatalog::new(&dir).unwrap();
+
+        for name in ["../escape", ".pending", "a..b", ""] {
+            let result = catalog.commit_next_version(name, vec![]);
+            assert!(matches!(result, Err(CatalogError::InvalidTableName(_))), "{:?}", name);
+        }
+        assert!(catalog.list_tables().unwrap().is_empty());
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
     #[test]
     fn test_time_travel() {
         let dir = temp_dir();
@@ -881,6 +2136,66 @@ mod tests {
         fs::remove_dir_all(&dir).ok();
     }
 
+    #[test]
+    fn test_prune_versions_is_all_or_nothing_and_recorded() {
+        let dir = temp_dir();
+        let catalog = FileCatalog::new(&dir).unwrap();
+
+        for v in 1..=4 {
+            catalog.commit(TableVersion::new("tbl", v, vec![format!("h{}", v)])).unwrap();
+        }
+        catalog.set_retention_policy("tbl", Some(RetentionPolicy::new().with_keep_last(2))).unwrap();
+        assert_eq!(catalog.retention_policy("tbl").unwrap().unwrap().keep_last, Some(2));
+        catalog.hold("tbl", 2, None).unwrap();
+        assert!(catalog.is_protected("tbl", 2).unwrap());
+
+        assert!(matches!(catalog.prune_versions("tbl", &[1, 2]), Err(CatalogError::VersionHeld(_, 2))));
+        assert_eq!(catalog.list_versions("tbl").unwrap(), vec![1, 2, 3, 4]);
+
+        let pruned = catalog.prune_versions("tbl", &[3, 1]).unwrap();
+        assert_eq!(pruned.iter().map(|v| v.version).collect::<Vec<_>>(), vec![1, 3]);
+        assert_eq!(catalog.list_versions("tbl").unwrap(), vec![2, 4]);
+        let history = catalog.prune_history("tbl").unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].versions, vec![1, 3]);
+
+        catalog.set_retention_policy("tbl", None).unwrap();
+        assert!(catalog.retention_policy("tbl").unwrap().is_none());
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_squash_versions_keeps_base_and_records_mapping() {
+        let dir = temp_dir();
+        let catalog = FileCatalog::new(&dir).unwrap();
+
+        for v in 1..=5 {
+            catalog.commit(TableVersion::new("tbl", v, vec![format!("h{}", v)])).unwrap();
+        }
+        catalog.hold("tbl", 2, None).unwrap();
+        assert!(matches!(catalog.squash_versions("tbl", 4), Err(CatalogError::VersionHeld(_, 2))));
+        assert_eq!(catalog.list_versions("tbl").unwrap(), vec![1, 2, 3, 4, 5]);
+        catalog.release_hold("tbl", 2).unwrap();
+
+        let squashed = catalog.squash_versions("tbl", 4).unwrap();
+        assert_eq!(squashed.iter().map(|v| v.version).collect::<Vec<_>>(), vec![1, 2, 3]);
+        assert_eq!(catalog.list_versions("tbl").unwrap(), vec![4, 5]);
+        let base = catalog.get_version("tbl", Some(4)).unwrap();
+        assert_eq!(base.parent_version, None);
+        assert_eq!(base.chunk_hashes, vec!["h4"]);
+        assert_eq!(catalog.squashed_into("tbl", 2).unwrap(), Some(4));
+        assert_eq!(catalog.squashed_into("tbl", 5).unwrap(), None);
+
+        // Nothing older is left; the next commit continues the numbering
+        assert!(catalog.squash_versions("tbl", 4).unwrap().is_empty());
+        assert_eq!(catalog.squash_history("tbl").unwrap().len(), 1);
+        catalog.commit(TableVersion::new("tbl", 6, vec![])).unwrap();
+        assert!(matches!(catalog.squash_versions("tbl", 3), Err(CatalogError::VersionNotFound(_, 3))));
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
     // =========================================================================
     // get_all_referenced_chunk_hashes tests
     // =========================================================================
@@ -914,4 +2229,218 @@ mod tests {
 
         fs::remove_dir_all(&dir).ok();
     }
+
+    // ========================================================================
+    // Version pin tests
+    // ========================================================================
+
+    #[test]
+    fn test_pin_and_resolve() {
+        let dir = temp_dir();
+        let catalog = FileCatalog::new(&dir).unwrap();
+
+        catalog.commit(TableVersion::new("users", 1, vec![])).unwrap();
+        catalog.commit(TableVersion::new("users", 2, vec![])).unwrap();
+        catalog.commit(TableVersion::new("events", 1, vec![])).unwrap();
+
+        let versions = HashMap::from([("users".to_string(), 1), ("events".to_string(), 1)]);
+        catalog.pin("run/42", versions, Some("baseline".to_string())).unwrap();
+
+        let pin = catalog.resolve_pin("run/42").unwrap();
+        assert_eq!(pin.get("users"), Some(1));
+        assert_eq!(pin.get("events"), Some(1));
+        assert_eq!(pin.description.as_deref(), Some("baseline"));
+
+        // Pins are immutable and not listed as tables
+        let again = catalog.pin("run/42", HashMap::from([("users".to_string(), 2)]), None);
+        assert!(matches!(again, Err(CatalogError::PinAlreadyExists(_))));
+        assert_eq!(catalog.list_tables().unwrap(), vec!["events", "users"]);
+        assert_eq!(catalog.list_pins().unwrap().len(), 1);
+
+        // Unknown versions cannot be pinned
+        let missing = catalog.pin("bad", HashMap::from([("users".to_string(), 9)]), None);
+        assert!(matches!(missing, Err(CatalogError::VersionNotFound(_, 9))));
+        assert!(matches!(catalog.resolve_pin("bad"), Err(CatalogError::PinNotFound(_))));
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_pinned_version_protected_from_delete() {
+        let dir = temp_dir();
+        let catalog = FileCatalog::new(&dir).unwrap();
+
+        catalog.commit(TableVersion::new("tbl", 1, vec![])).unwrap();
+        catalog.commit(TableVersion::new("tbl", 2, vec![])).unwrap();
+        catalog.pin("keep", HashMap::from([("tbl".to_string(), 1)]), None).unwrap();
+
+        assert_eq!(catalog.pinned_versions().unwrap()["tbl"].len(), 1);
+        let result = catalog.delete_version("tbl", 1);
+        assert!(matches!(result, Err(CatalogError::VersionPinned(_, 1, ref p)) if p == "keep"));
+
+        catalog.delete_pin("keep").unwrap();
+        assert!(catalog.delete_version("tbl", 1).is_ok());
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_legal_hold_blocks_delete() {
+        let dir = temp_dir();
+        let catalog = FileCatalog::new(&dir).unwrap();
+
+        catalog.commit(TableVersion::new("tbl", 1, vec![])).unwrap();
+        catalog.commit(TableVersion::new("tbl", 2, vec![])).unwrap();
+
+        let hold = catalog.hold("tbl", 1, Some("case-42".to_string())).unwrap();
+        assert_eq!(hold.reason.as_deref(), Some("case-42"));
+        // Re-holding keeps the original hold
+        assert_eq!(catalog.hold("tbl", 1, None).unwrap(), hold);
+        assert!(matches!(catalog.hold("tbl", 9, None), Err(CatalogError::VersionNotFound(_, 9))));
+
+        assert_eq!(catalog.list_holds().unwrap(), vec![hold]);
+        assert!(catalog.held_versions().unwrap()["tbl"].contains(&1));
+        // The holds file is not mistaken for a version
+        assert_eq!(catalog.list_versions("tbl").unwrap(), vec![1, 2]);
+
+        let result = catalog.delete_version("tbl", 1);
+        assert!(matches!(result, Err(CatalogError::VersionHeld(_, 1))));
+
+        catalog.release_hold("tbl", 1).unwrap();
+        assert!(matches!(catalog.release_hold("tbl", 1), Err(CatalogError::HoldNotFound(_, 1))));
+        assert!(catalog.list_holds().unwrap().is_empty());
+        assert!(catalog.delete_version("tbl", 1).is_ok());
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_views() {
+        let dir = temp_dir();
+        let catalog = FileCatalog::new(&dir).unwrap();
+        catalog.commit(TableVersion::new("orders", 1, vec!["o1".into()])).unwrap();
+        catalog.commit(TableVersion::new("users", 1, vec!["u1".into()])).unwrap();
+
+        let sql = "SELECT u.id, count(*) AS n FROM users u JOIN orders o ON o.user_id = u.id GROUP BY u.id";
+        let view = View::new("order_counts", sql, vec!["users".into(), "orders".into()]).with_materialize(true);
+        catalog.create_view(view.clone(), false).unwrap();
+        catalog
+            .create_view(View::new("top_users", "SELECT * FROM order_counts WHERE n > 10", vec!["order_counts".into()]), false)
+            .unwrap();
+
+        assert_eq!(catalog.get_view("order_counts").unwrap(), view);
+        assert_eq!(catalog.view_base_tables("top_users").unwrap(), vec!["orders", "users"]);
+        // Views are not tables
+        assert_eq!(catalog.list_tables().unwrap(), vec!["orders", "users"]);
+        assert_eq!(
+            catalog.list_views().unwrap().iter().map(|v| v.name.as_str()).collect::<Vec<_>>(),
+            vec!["order_counts", "top_users"]
+        );
+
+        assert!(matches!(catalog.create_view(view.clone(), false), Err(CatalogError::ViewAlreadyExists(_))));
+        assert!(matches!(
+            catalog.create_view(View::new("users", "SELECT 1", vec!["orders".into()]), false),
+            Err(CatalogError::TableAlreadyExists(_))
+        ));
+        assert!(matches!(
+            catalog.create_view(View::new("bad", "SELECT * FROM nope", vec!["nope".into()]), false),
+            Err(CatalogError::TableNotFound(_))
+        ));
+        // Redefining order_counts over top_users would be a cycle
+        assert!(matches!(
+            catalog.create_view(View::new("order_counts", "SELECT * FROM top_users", vec!["top_users".into()]), true),
+            Err(CatalogError::InvalidView(..))
+        ));
+        assert!(matches!(catalog.rename_table("orders", "top_users"), Err(CatalogError::TableAlreadyExists(_))));
+
+        assert_eq!(
+            View::materialization_key(&HashMap::from([("users".to_string(), 2), ("orders".to_string(), 4)])),
+            "orders@4,users@2"
+        );
+
+        catalog.drop_view("top_users").unwrap();
+        assert!(matches!(catalog.get_view("top_users"), Err(CatalogError::ViewNotFound(_))));
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_rename_and_drop_table() {
+        let dir = temp_dir();
+        let catalog = FileCatalog::new(&dir).unwrap();
+        catalog.commit(TableVersion::new("users", 1, vec!["a".into()])).unwrap();
+        catalog.commit(TableVersion::new("users", 2, vec!["b".into()])).unwrap();
+        catalog.commit(TableVersion::new("orders", 1, vec![])).unwrap();
+
+        // Pinned tables can be neither renamed nor dropped
+        catalog.pin("keep", HashMap::from([("users".to_string(), 1)]), None).unwrap();
+        assert!(matches!(catalog.rename_table("users", "people"), Err(CatalogError::VersionPinned(_, 1, _))));
+        assert!(matches!(catalog.drop_table("users"), Err(CatalogError::VersionPinned(_, 1, _))));
+        catalog.delete_pin("keep").unwrap();
+
+        assert!(matches!(catalog.rename_table("users", "orders"), Err(CatalogError::TableAlreadyExists(_))));
+        assert!(matches!(catalog.rename_table("missing", "x"), Err(CatalogError::TableNotFound(_))));
+
+        assert_eq!(catalog.rename_table("users", "people").unwrap(), 2);
+        assert_eq!(catalog.list_tables().unwrap(), vec!["orders", "people"]);
+        let v1 = catalog.get_version("people", Some(1)).unwrap();
+        assert_eq!((v1.table_name.as_str(), v1.chunk_hashes.clone()), ("people", vec!["a".to_string()]));
+        assert!(matches!(catalog.get_version("users", None), Err(CatalogError::TableNotFound(_))));
+
+        let dropped = catalog.drop_table("people").unwrap();
+        assert_eq!(dropped.iter().map(|v| v.version).collect::<Vec<_>>(), vec![1, 2]);
+        assert_eq!(catalog.list_tables().unwrap(), vec!["orders"]);
+        assert!(!catalog.table_exists("people").unwrap());
+
+        // A dropped name starts over at version 1
+        catalog.commit(TableVersion::new("people", 1, vec![])).unwrap();
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_rename_keeps_squash_mapping() {
+        let dir = temp_dir();
+        let catalog = FileCatalog::new(&dir).unwrap();
+        for v in 1..=4 {
+            catalog.commit(TableVersion::new("events", v, vec![format!("h{}", v)])).unwrap();
+        }
+        catalog.squash_versions("events", 3).unwrap();
+
+        catalog.rename_table("events", "activity").unwrap();
+        assert_eq!(catalog.list_versions("activity").unwrap(), vec![3, 4]);
+        assert_eq!(catalog.squashed_into("activity", 1).unwrap(), Some(3));
+        assert_eq!(catalog.squash_history("activity").unwrap().len(), 1);
+        assert!(catalog.squash_history("events").unwrap().is_empty());
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_lock_timeout_reports_owner_and_break_lock() {
+        let dir = temp_dir();
+        let catalog = FileCatalog::new(&dir).unwrap().with_lock_timeout(Duration::from_millis(20));
+        catalog.commit(TableVersion::new("users", 1, vec![])).unwrap();
+        assert_eq!(catalog.lock_owner("users").unwrap(), None);
+        assert_eq!(catalog.break_lock("users", false).unwrap(), None);
+
+        let held = catalog.acquire_table_lock("users").unwrap();
+        let owner = catalog.lock_owner("users").unwrap().unwrap();
+        assert_eq!(owner.pid, std::process::id());
+
+        match catalog.commit(TableVersion::new("users", 2, vec![])) {
+            Err(CatalogError::LockTimeout { table, owner: Some(reported), .. }) => {
+                assert_eq!((table.as_str(), reported), ("users", owner.clone()));
+            }
+            other => panic!("expected a lock timeout, got {:?}", other),
+        }
+
+        // A live holder is only broken when forced
+        assert!(matches!(catalog.break_lock("users", false), Err(CatalogError::LockHeld { .. })));
+        assert_eq!(catalog.break_lock("users", true).unwrap(), Some(owner));
+        catalog.commit(TableVersion::new("users", 2, vec![])).unwrap();
+        drop(held);
+
+        fs::remove_dir_all(&dir).ok();
+    }
 }
@@ -0,0 +1,34 @@
+use serde::{Deserialize, Serialize};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A legal hold on one table version.
+///
+/// Held versions cannot be deleted by any path (garbage collection,
+/// retention, explicit `FileCatalog::delete_version`) until the hold is
+/// released. Holds are stored with the table and managed through
+/// `FileCatalog::hold` / `FileCatalog::release_hold`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LegalHold {
+    pub table_name: String,
+    pub version: u64,
+
+    /// Why the version is held (case number, regulation, ticket)
+    pub reason: Option<String>,
+
+    /// Unix timestamp when the hold was placed
+    pub created_at: i64,
+}
+
+impl LegalHold {
+    pub fn new(table_name: impl Into<String>, version: u64, reason: Option<String>) -> Self {
+        Self {
+            table_name: table_name.into(),
+            version,
+            reason,
+            created_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_secs() as i64,
+        }
+    }
+}
@@ -0,0 +1,198 @@
+//! Catalog lock files with acquisition timeouts and owner diagnostics.
+//!
+//! Table and directory locks are `fs2` exclusive locks on a `.lock` file.
+//! A holder writes its pid, host and acquisition time into the file, so a
+//! caller that times out can report who holds the lock, and an operator can
+//! tell a live holder from a stale one before breaking it.
+
+use std::fmt;
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::Path;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use fs2::FileExt;
+use serde::{Deserialize, Serialize};
+
+use super::error::CatalogError;
+
+/// How long a commit waits for a table lock by default
+pub const DEFAULT_LOCK_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Longest pause between attempts while waiting for a lock
+const MAX_BACKOFF: Duration = Duration::from_millis(50);
+
+/// The process recorded as holding a catalog lock
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LockOwner {
+    pub pid: u32,
+    pub host: String,
+    /// Unix seconds when the lock was taken
+    pub acquired_at: i64,
+}
+
+impl LockOwner {
+    fn current() -> Self {
+        Self {
+            pid: std::process::id(),
+            host: local_host(),
+            acquired_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs() as i64)
+                .unwrap_or(0),
+        }
+    }
+
+    /// Whether the owning process is still running: `None` when that cannot
+    /// be told from here (another host, or no process table to look at)
+    pub fn is_alive(&self) -> Option<bool> {
+        if self.host != local_host() {
+            return None;
+        }
+        if self.pid == std::process::id() {
+            return Some(true);
+        }
+        let proc = Path::new("/proc");
+        if proc.join("self").exists() {
+            return Some(proc.join(self.pid.to_string()).exists());
+        }
+        None
+    }
+
+    /// Held by a process on this host that no longer runs
+    pub fn is_stale(&self) -> bool {
+        self.is_alive() == Some(false)
+    }
+}
+
+impl fmt::Display for LockOwner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "pid {} on {} (since {})", self.pid, self.host, self.acquired_at)
+    }
+}
+
+/// Describe a lock holder in error messages
+pub(super) fn describe(owner: &Option<LockOwner>) -> String {
+    match owner {
+        Some(owner) => format!("held by {}", owner),
+        None => "holder unknown".to_string(),
+    }
+}
+
+fn local_host() -> String {
+    ["HOSTNAME", "COMPUTERNAME"]
+        .iter()
+        .find_map(|var| std::env::var(var).ok())
+        .or_else(|| fs::read_to_string("/etc/hostname").ok())
+        .map(|host| host.trim().to_string())
+        .filter(|host| !host.is_empty())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+fn open(path: &Path, name: &str) -> Result<File, CatalogError> {
+    fs::OpenOptions::new()
+        .create(true)
+        .read(true)
+        .write(true)
+        .truncate(false)
+        .open(path)
+        .map_err(|e| CatalogError::LockError(format!("{}: {}", name, e)))
+}
+
+/// Take the exclusive lock on `path`, waiting at most `timeout`, and
+/// record this process as its owner. The lock is held until the returned
+/// handle is dropped.
+pub(super) fn acquire(path: &Path, name: &str, timeout: Duration) -> Result<File, CatalogError> {
+    let file = open(path, name)?;
+    let started = Instant::now();
+    let mut backoff = Duration::from_millis(1);
+    loop {
+        match file.try_lock_exclusive() {
+            Ok(()) => break,
+            Err(e) if e.kind() != fs2::lock_contended_error().kind() => {
+                return Err(CatalogError::LockError(format!("{}: {}", name, e)));
+            }
+            Err(_) if started.elapsed() >= timeout => {
+                return Err(CatalogError::LockTimeout {
+                    table: name.to_string(),
+                    waited: started.elapsed(),
+                    owner: read_owner(path),
+                });
+            }
+            Err(_) => {
+                std::thread::sleep(backoff.min(timeout.saturating_sub(started.elapsed())));
+                backoff = (backoff * 2).min(MAX_BACKOFF);
+            }
+        }
+    }
+
+    // Diagnostics only: a lock whose owner could not be written still excludes
+    let owner = serde_json::to_vec(&LockOwner::current())?;
+    let _ = file.set_len(0).and_then(|()| (&file).write_all(&owner));
+    Ok(file)
+}
+
+/// The recorded owner of the lock on `path`, if it is currently held
+pub(super) fn holder(path: &Path, name: &str) -> Result<Option<LockOwner>, CatalogError> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let file = open(path, name)?;
+    if file.try_lock_exclusive().is_ok() {
+        let _ = FileExt::unlock(&file);
+        return Ok(None);
+    }
+    Ok(read_owner(path))
+}
+
+/// Remove the lock file at `path` if it is held by a process that is gone,
+/// or by anyone when `force` is set. Returns the owner it recorded.
+///
+/// Breaking a lock whose holder is alive lets two writers in at once, so
+/// without `force` only locks known to be stale are broken. A lock that
+/// is not held is left alone.
+pub(super) fn break_lock(path: &Path, name: &str, force: bool) -> Result<Option<LockOwner>, CatalogError> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let file = open(path, name)?;
+    if file.try_lock_exclusive().is_ok() {
+        let _ = FileExt::unlock(&file);
+        return Ok(None);
+    }
+    let owner = read_owner(path);
+    if !force && !owner.as_ref().is_some_and(LockOwner::is_stale) {
+        return Err(CatalogError::LockHeld {
+            table: name.to_string(),
+            owner,
+        });
+    }
+    drop(file);
+    fs::remove_file(path)?;
+    Ok(owner)
+}
+
+fn read_owner(path: &Path) -> Option<LockOwner> {
+    let bytes = fs::read(path).ok()?;
+    serde_json::from_slice(&bytes).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lock_owner_liveness() {
+        let me = LockOwner::current();
+        assert_eq!(me.is_alive(), Some(true));
+        assert!(!me.is_stale());
+
+        let elsewhere = LockOwner { host: format!("{}-other", me.host), ..me.clone() };
+        assert_eq!(elsewhere.is_alive(), None);
+
+        if Path::new("/proc/self").exists() {
+            let gone = LockOwner { pid: u32::MAX, ..me };
+            assert!(gone.is_stale());
+        }
+    }
+}
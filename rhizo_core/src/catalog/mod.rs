@@ -1,7 +1,22 @@
 pub mod error;
 pub mod version;
 pub mod file_catalog;
+pub mod namespace;
+pub mod pin;
+pub mod hold;
+pub mod lock;
+pub mod retention;
+pub mod tag;
+pub mod view;
+pub mod schema;
 
 pub use error::CatalogError;
-pub use version::TableVersion;
+pub use version::{TableVersion, ALGEBRAIC_SCHEMA_METADATA_KEY, BASE_CHUNKS_METADATA_KEY};
+pub use pin::Pin;
+pub use hold::LegalHold;
+pub use lock::{LockOwner, DEFAULT_LOCK_TIMEOUT};
+pub use retention::{PruneRecord, RedactedVersion, RedactionRecord, RetentionPolicy, SquashRecord};
+pub use tag::{Tag, TagManager};
+pub use view::View;
+pub use schema::{SchemaChange, SchemaField, SchemaMigration, SchemaRegistry, TableSchema, SCHEMA_MIGRATION_METADATA_KEY};
 pub use file_catalog::{FileCatalog, PendingCommit};
@@ -0,0 +1,275 @@
+//! Hierarchical table names.
+//!
+//! Tables may be qualified as `schema.table` or `path/to/table`. Both `.` and
+//! `/` act as namespace separators, so `sales.orders` and `sales/orders` live
+//! in the same `sales` namespace (but are distinct tables).
+//!
+//! On disk every table is still a single directory under the catalog root.
+//! Characters that are unsafe in file names (including `/`) are
+//! percent-encoded, so nested names never create nested directories and can
+//! never escape the catalog root.
+
+use super::error::CatalogError;
+
+/// Characters that separate namespace segments in a table name
+pub const NAMESPACE_SEPARATORS: [char; 2] = ['.', '/'];
+
+/// Maximum length of a (decoded) table name
+pub const MAX_TABLE_NAME_LEN: usize = 512;
+
+/// Characters that must be percent-encoded in a table directory name
+const ENCODED_CHARS: [char; 10] = ['%', '/', '\\', ':', '*', '?', '"', '<', '>', '|'];
+
+/// Validate a table name before it is written to the catalog.
+///
+/// Rules:
+/// - non-empty and at most `MAX_TABLE_NAME_LEN` characters
+/// - no control characters
+/// - every segment between separators is non-empty (rejects `a..b`,
+///   `.hidden`, `trailing/`, `..`)
+/// - no backslashes (they are path separators on Windows)
+/// - no wildcard characters (`*`, `?`), so patterns stay unambiguous
+pub fn validate_table_name(name: &str) -> Result<(), CatalogError> {
+    let invalid = |reason: &str| {
+        Err(CatalogError::InvalidTableName(format!("{:?}: {}", name, reason)))
+    };
+
+    if name.is_empty() {
+        return invalid("name cannot be empty");
+    }
+    if name.chars().count() > MAX_TABLE_NAME_LEN {
+        return invalid("name too long");
+    }
+    if name.chars().any(|c| c.is_control()) {
+        return invalid("contains control characters");
+    }
+    if name.contains('\\') {
+        return invalid("contains a backslash");
+    }
+    if is_pattern(name) {
+        return invalid("contains wildcard characters");
+    }
+    if name.split(NAMESPACE_SEPARATORS).any(|segment| segment.is_empty()) {
+        return invalid("contains an empty namespace segment");
+    }
+
+    Ok(())
+}
+
+/// Encode a table name as a single, filesystem-safe directory name.
+///
+/// Plain names (letters, digits, `_`, `-`, `.`) are returned unchanged, so
+/// catalogs created before namespaces existed keep their layout.
+pub fn encode_table_name(name: &str) -> String {
+    let mut encoded = String::with_capacity(name.len());
+    for c in name.chars() {
+        if ENCODED_CHARS.contains(&c) || c.is_control() {
+            let mut buf = [0u8; 4];
+            for byte in c.encode_utf8(&mut buf).bytes() {
+                encoded.push_str(&format!("%{:02X}", byte));
+            }
+        } else {
+            encoded.push(c);
+        }
+    }
+    encoded
+}
+
+/// Decode a directory name produced by `encode_table_name`.
+///
+/// Returns `None` if the name contains a malformed escape sequence.
+pub fn decode_table_name(encoded: &str) -> Option<String> {
+    let bytes = encoded.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let hex = encoded.get(i + 1..i + 3)?;
+            decoded.push(u8::from_str_radix(hex, 16).ok()?);
+            i += 3;
+        } else {
+            decoded.push(bytes[i]);
+            i += 1;
+        }
+    }
+
+    String::from_utf8(decoded).ok()
+}
+
+/// Namespace of a table: everything before the last separator.
+///
+/// Returns `None` for unqualified names.
+///
+/// # Example
+/// ```
+/// use rhizo_core::catalog::namespace::namespace_of;
+/// assert_eq!(namespace_of("sales.eu/orders"), Some("sales.eu"));
+/// assert_eq!(namespace_of("orders"), None);
+/// ```
+pub fn namespace_of(name: &str) -> Option<&str> {
+    name.rfind(NAMESPACE_SEPARATORS).map(|idx| &name[..idx])
+}
+
+/// Check whether a table lives under a namespace prefix.
+///
+/// Matching is done on segment boundaries: `sales` contains `sales.orders`
+/// and `sales/eu/orders` but not `salesforce.leads`. A prefix ending in a
+/// separator is matched literally. The empty prefix matches every table.
+pub fn in_namespace(name: &str, prefix: &str) -> bool {
+    if prefix.is_empty() || prefix.ends_with(NAMESPACE_SEPARATORS) {
+        return name.starts_with(prefix);
+    }
+
+    match name.strip_prefix(prefix) {
+        Some(rest) => rest.is_empty() || rest.starts_with(NAMESPACE_SEPARATORS),
+        None => false,
+    }
+}
+
+/// Match a table name against a wildcard pattern.
+///
+/// - `*` matches any run of characters within one segment
+/// - `**` matches any run of characters, including separators
+/// - `?` matches exactly one non-separator character
+///
+/// # Example
+/// ```
+/// use rhizo_core::catalog::namespace::matches_pattern;
+/// assert!(matches_pattern("sales.orders", "sales.*"));
+/// assert!(!matches_pattern("sales/eu/orders", "sales/*"));
+/// assert!(matches_pattern("sales/eu/orders", "sales/**"));
+/// ```
+pub fn matches_pattern(name: &str, pattern: &str) -> bool {
+    let name: Vec<char> = name.chars().collect();
+    let pattern: Vec<char> = pattern.chars().collect();
+    matches_from(&name, &pattern)
+}
+
+fn matches_from(name: &[char], pattern: &[char]) -> bool {
+    match pattern.first() {
+        None => name.is_empty(),
+        Some('*') => {
+            let deep = pattern.get(1) == Some(&'*');
+            let rest = if deep { &pattern[2..] } else { &pattern[1..] };
+
+            // Try every split point; a single `*` may not consume separators
+            for i in 0..=name.len() {
+                if matches_from(&name[i..], rest) {
+                    return true;
+                }
+                if i < name.len() && !deep && NAMESPACE_SEPARATORS.contains(&name[i]) {
+                    break;
+                }
+            }
+            false
+        }
+        Some('?') => match name.first() {
+            Some(c) if !NAMESPACE_SEPARATORS.contains(c) => matches_from(&name[1..], &pattern[1..]),
+            _ => false,
+        },
+        Some(p) => match name.first() {
+            Some(c) if c == p => matches_from(&name[1..], &pattern[1..]),
+            _ => false,
+        },
+    }
+}
+
+/// True if the pattern contains wildcard characters
+pub fn is_pattern(pattern: &str) -> bool {
+    pattern.contains(['*', '?'])
+}
+
+/// Check a table against a selector that is either a wildcard pattern or a
+/// plain namespace prefix.
+///
+/// This is what namespace-wide operations (branching, GC) accept, so callers
+/// can pass `sales`, `sales.*` or `sales/**` interchangeably.
+pub fn selects(name: &str, selector: &str) -> bool {
+    if is_pattern(selector) {
+        matches_pattern(name, selector)
+    } else {
+        in_namespace(name, selector)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_table_name() {
+        assert!(validate_table_name("users").is_ok());
+        assert!(validate_table_name("sales.orders").is_ok());
+        assert!(validate_table_name("org/team/events").is_ok());
+        assert!(validate_table_name("my-table_2").is_ok());
+
+        for bad in ["", "..", "../etc", ".hidden", "a..b", "a//b", "trailing/", "a\\b", "a\0b", "sales.*"] {
+            assert!(
+                matches!(validate_table_name(bad), Err(CatalogError::InvalidTableName(_))),
+                "expected {:?} to be rejected", bad
+            );
+        }
+    }
+
+    #[test]
+    fn test_encode_roundtrip() {
+        for name in ["users", "sales.orders", "org/team/events", "weird:name*?", "100%"] {
+            let encoded = encode_table_name(name);
+            assert!(!encoded.contains('/'));
+            assert_eq!(decode_table_name(&encoded).as_deref(), Some(name));
+        }
+    }
+
+    #[test]
+    fn test_plain_names_unchanged() {
+        assert_eq!(encode_table_name("users"), "users");
+        assert_eq!(encode_table_name("sales.orders"), "sales.orders");
+        assert_eq!(encode_table_name("a/b"), "a%2Fb");
+    }
+
+    #[test]
+    fn test_decode_malformed() {
+        assert_eq!(decode_table_name("bad%2"), None);
+        assert_eq!(decode_table_name("bad%zz"), None);
+    }
+
+    #[test]
+    fn test_in_namespace() {
+        assert!(in_namespace("sales.orders", "sales"));
+        assert!(in_namespace("sales/eu/orders", "sales"));
+        assert!(in_namespace("sales/eu/orders", "sales/eu"));
+        assert!(in_namespace("sales", "sales"));
+        assert!(!in_namespace("salesforce.leads", "sales"));
+        assert!(in_namespace("salesforce.leads", ""));
+        assert!(in_namespace("sales.orders", "sales."));
+        assert!(!in_namespace("sales/orders", "sales."));
+    }
+
+    #[test]
+    fn test_namespace_of() {
+        assert_eq!(namespace_of("a.b.c"), Some("a.b"));
+        assert_eq!(namespace_of("a/b"), Some("a"));
+        assert_eq!(namespace_of("plain"), None);
+    }
+
+    #[test]
+    fn test_matches_pattern() {
+        assert!(matches_pattern("sales.orders", "sales.*"));
+        assert!(matches_pattern("sales.orders", "*.orders"));
+        assert!(matches_pattern("sales.orders", "sales.ord?rs"));
+        assert!(!matches_pattern("sales.eu.orders", "sales.*"));
+        assert!(matches_pattern("sales.eu.orders", "sales.**"));
+        assert!(matches_pattern("a/b/c/d", "a/**/d"));
+        assert!(!matches_pattern("users", "sales.*"));
+        assert!(matches_pattern("users", "users"));
+        assert!(matches_pattern("anything/at/all", "**"));
+    }
+
+    #[test]
+    fn test_selects() {
+        assert!(selects("sales/eu/orders", "sales"));
+        assert!(!selects("sales/eu/orders", "sales/*"));
+        assert!(selects("sales/eu/orders", "sales/**"));
+    }
+}
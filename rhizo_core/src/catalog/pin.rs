@@ -0,0 +1,95 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use super::error::CatalogError;
+
+/// A named, immutable set of table versions.
+///
+/// Pins record the exact data inputs of a run (e.g. a model training job) so
+/// it can be reproduced later. Pinned versions are protected from deletion:
+/// `FileCatalog::delete_version` refuses to remove them until the pin is
+/// deleted.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Pin {
+    /// Unique pin name (e.g., "churn-model/2024-06-01")
+    pub name: String,
+
+    /// Pinned versions: table_name -> version number
+    pub versions: HashMap<String, u64>,
+
+    /// Unix timestamp when the pin was created
+    pub created_at: i64,
+
+    /// Optional free-form description (experiment id, commit, notes)
+    pub description: Option<String>,
+}
+
+impl Pin {
+    pub fn new(name: impl Into<String>, versions: HashMap<String, u64>) -> Self {
+        Self {
+            name: name.into(),
+            versions,
+            created_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_secs() as i64,
+            description: None,
+        }
+    }
+
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Pinned version of a table, if the table is part of this pin
+    pub fn get(&self, table_name: &str) -> Option<u64> {
+        self.versions.get(table_name).copied()
+    }
+}
+
+/// Validate a pin name.
+///
+/// Same rules as branch names: alphanumerics, `-`, `_` and `/` (no leading
+/// underscore, no empty segments), so names map safely onto file names.
+pub fn validate_pin_name(name: &str) -> Result<(), CatalogError> {
+    validate_ref_name(name).map_err(|reason| CatalogError::InvalidPinName(format!("{:?}: {}", name, reason)))
+}
+
+/// Naming rules shared by pins, tags and changelog cursors; returns the
+/// reason a name is invalid
+pub(crate) fn validate_ref_name(name: &str) -> Result<(), &'static str> {
+    if name.is_empty() {
+        return Err("name cannot be empty");
+    }
+    if name.starts_with('_') {
+        return Err("name cannot start with underscore");
+    }
+    if !name.chars().all(|c| c.is_alphanumeric() || c == '-' || c == '_' || c == '/') {
+        return Err("contains invalid characters");
+    }
+    if name.split('/').any(|segment| segment.is_empty()) {
+        return Err("contains an empty segment");
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_pin_name() {
+        assert!(validate_pin_name("baseline").is_ok());
+        assert!(validate_pin_name("churn-model/run_42").is_ok());
+
+        for bad in ["", "_hidden", "a//b", "/abs", "trailing/", "../up", "a.b", "a b"] {
+            assert!(
+                matches!(validate_pin_name(bad), Err(CatalogError::InvalidPinName(_))),
+                "expected {:?} to be rejected", bad
+            );
+        }
+    }
+}
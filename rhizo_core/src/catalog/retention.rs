@@ -0,0 +1,202 @@
+use serde::{Deserialize, Serialize};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// How many old versions of a table to keep.
+///
+/// A version is kept if any rule keeps it; the latest version is always
+/// kept. A policy with no rules keeps everything. Retention never removes
+/// versions under legal hold, pinned or tagged, see
+/// `FileCatalog::prune_versions`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RetentionPolicy {
+    /// Keep the newest N versions
+    pub keep_last: Option<usize>,
+
+    /// Keep versions created less than this many seconds ago
+    pub keep_newer_than_secs: Option<u64>,
+}
+
+impl RetentionPolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_keep_last(mut self, n: usize) -> Self {
+        self.keep_last = Some(n);
+        self
+    }
+
+    pub fn with_keep_newer_than(mut self, secs: u64) -> Self {
+        self.keep_newer_than_secs = Some(secs);
+        self
+    }
+
+    /// True if the policy keeps every version
+    pub fn keeps_all(&self) -> bool {
+        self.keep_last.is_none() && self.keep_newer_than_secs.is_none()
+    }
+
+    /// Versions the policy would remove, ascending.
+    ///
+    /// `versions` are `(version, created_at)` pairs; `now` is a Unix
+    /// timestamp.
+    pub fn expired(&self, versions: &[(u64, i64)], now: i64) -> Vec<u64> {
+        if self.keeps_all() {
+            return Vec::new();
+        }
+        let mut sorted = versions.to_vec();
+        sorted.sort_unstable_by_key(|&(version, _)| std::cmp::Reverse(version));
+
+        let mut expired: Vec<u64> = sorted
+            .iter()
+            .enumerate()
+            .skip(1)
+            .filter(|(rank, (_, created_at))| {
+                let by_count = self.keep_last.is_some_and(|n| *rank < n);
+                let by_age = self
+                    .keep_newer_than_secs
+                    .is_some_and(|secs| now.saturating_sub(*created_at) < secs as i64);
+                !by_count && !by_age
+            })
+            .map(|(_, (version, _))| *version)
+            .collect();
+        expired.sort_unstable();
+        expired
+    }
+}
+
+/// One `prune_versions` call, recorded in the table's prune history
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PruneRecord {
+    pub table_name: String,
+
+    /// Versions removed, ascending
+    pub versions: Vec<u64>,
+
+    /// Unix timestamp of the prune
+    pub pruned_at: i64,
+}
+
+impl PruneRecord {
+    pub fn new(table_name: impl Into<String>, versions: Vec<u64>) -> Self {
+        Self {
+            table_name: table_name.into(),
+            versions,
+            pruned_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_secs() as i64,
+        }
+    }
+}
+
+/// One `squash_versions` call: the versions folded into a table's new
+/// base version, kept so old version numbers can still be traced
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SquashRecord {
+    pub table_name: String,
+
+    /// The version kept as the table's first version
+    pub base_version: u64,
+
+    /// Versions removed, ascending
+    pub versions: Vec<u64>,
+
+    /// Unix timestamp of the squash
+    pub squashed_at: i64,
+}
+
+impl SquashRecord {
+    pub fn new(table_name: impl Into<String>, base_version: u64, versions: Vec<u64>) -> Self {
+        Self {
+            table_name: table_name.into(),
+            base_version,
+            versions,
+            squashed_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_secs() as i64,
+        }
+    }
+}
+
+/// One version rewritten by a redaction
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RedactedVersion {
+    pub version: u64,
+
+    /// Rows removed from the version
+    pub rows_removed: u64,
+
+    /// Chunks of the version before the rewrite that it no longer
+    /// references
+    pub replaced_chunks: Vec<String>,
+}
+
+/// One redaction of a table, recorded in the table's redaction history.
+///
+/// Records which versions were rewritten and how many rows each lost, not
+/// the removed data, so the history can be kept for audits.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RedactionRecord {
+    pub table_name: String,
+
+    /// The predicate matched rows were removed by, as text
+    pub predicate: String,
+
+    /// Why the rows were removed (e.g. a request ticket)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+
+    /// Rewritten versions, ascending (versions without matching rows are
+    /// left alone and not listed)
+    pub versions: Vec<RedactedVersion>,
+
+    /// Unix timestamp of the redaction
+    pub redacted_at: i64,
+}
+
+impl RedactionRecord {
+    pub fn new(table_name: impl Into<String>, predicate: impl Into<String>, versions: Vec<RedactedVersion>) -> Self {
+        Self {
+            table_name: table_name.into(),
+            predicate: predicate.into(),
+            reason: None,
+            versions,
+            redacted_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_secs() as i64,
+        }
+    }
+
+    pub fn with_reason(mut self, reason: impl Into<String>) -> Self {
+        self.reason = Some(reason.into());
+        self
+    }
+
+    /// Rows removed across all versions
+    pub fn rows_removed(&self) -> u64 {
+        self.versions.iter().map(|v| v.rows_removed).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rules_keep_union_and_latest() {
+        let versions = [(1, 100), (2, 200), (3, 300), (4, 400)];
+
+        assert!(RetentionPolicy::new().expired(&versions, 1000).is_empty());
+        assert_eq!(RetentionPolicy::new().with_keep_last(2).expired(&versions, 1000), vec![1, 2]);
+        assert_eq!(RetentionPolicy::new().with_keep_newer_than(750).expired(&versions, 1000), vec![1, 2]);
+        assert_eq!(
+            RetentionPolicy::new().with_keep_last(1).with_keep_newer_than(750).expired(&versions, 1000),
+            vec![1, 2]
+        );
+        // The latest version survives even a policy keeping nothing
+        assert_eq!(RetentionPolicy::new().with_keep_last(0).expired(&versions, 1000), vec![1, 2, 3]);
+    }
+}
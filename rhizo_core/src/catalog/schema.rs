@@ -0,0 +1,282 @@
+//! Full table schemas keyed by hash, and the migrations between them.
+//!
+//! A [`TableVersion`](super::TableVersion) records only its schema's hash. The
+//! [`SchemaRegistry`] keeps the schema behind each hash so the catalog can
+//! tell a compatible change from a breaking one: a commit whose hash
+//! differs from the table's latest version is accepted if it only adds
+//! nullable columns, or if it carries a [`SchemaMigration`] from the old
+//! hash to the new one. Anything else is rejected with
+//! [`CatalogError::SchemaConflict`].
+
+use arrow::datatypes::Schema;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use super::error::CatalogError;
+use crate::durability::{self, DurabilityLevel};
+
+/// Directory (under the catalog root) holding one file per schema hash
+pub(super) const SCHEMAS_DIR: &str = ".schemas";
+
+/// Version metadata key holding the [`SchemaMigration`] (JSON) that
+/// allowed the version's schema change
+pub const SCHEMA_MIGRATION_METADATA_KEY: &str = "schema_migration";
+
+/// One column of a [`TableSchema`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SchemaField {
+    pub name: String,
+
+    /// Arrow data type, as displayed (e.g. "Int64", "Utf8")
+    pub data_type: String,
+
+    pub nullable: bool,
+}
+
+/// The columns a schema hash stands for.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TableSchema {
+    /// Hash of the fields (see [`schema_hash`](crate::table::schema_hash))
+    pub hash: String,
+
+    pub fields: Vec<SchemaField>,
+}
+
+impl TableSchema {
+    pub fn from_arrow(schema: &Schema) -> Self {
+        Self {
+            hash: crate::table::schema_hash(schema),
+            fields: schema
+                .fields()
+                .iter()
+                .map(|f| SchemaField {
+                    name: f.name().clone(),
+                    data_type: f.data_type().to_string(),
+                    nullable: f.is_nullable(),
+                })
+                .collect(),
+        }
+    }
+
+    pub fn field(&self, name: &str) -> Option<&SchemaField> {
+        self.fields.iter().find(|f| f.name == name)
+    }
+
+    /// Whether this schema keeps every column of `older` unchanged and
+    /// only adds nullable ones, so rows of `older` still fit it
+    pub fn extends(&self, older: &TableSchema) -> bool {
+        older.fields.iter().all(|f| self.field(&f.name) == Some(f))
+            && self
+                .fields
+                .iter()
+                .all(|f| f.nullable || older.field(&f.name).is_some())
+    }
+}
+
+/// Record allowing a table's schema to change in a way that is not an
+/// additive extension (dropped or retyped columns).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SchemaMigration {
+    /// Hash of the schema being migrated from
+    pub from_hash: String,
+
+    /// Hash of the schema being migrated to
+    pub to_hash: String,
+
+    /// What changed and why
+    #[serde(default)]
+    pub description: Option<String>,
+
+    /// Unix timestamp when the migration was written
+    pub created_at: i64,
+}
+
+impl SchemaMigration {
+    pub fn new(from_hash: impl Into<String>, to_hash: impl Into<String>) -> Self {
+        Self {
+            from_hash: from_hash.into(),
+            to_hash: to_hash.into(),
+            description: None,
+            created_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_secs() as i64,
+        }
+    }
+
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Whether the migration allows moving from `from` to `to`
+    pub fn allows(&self, from: &str, to: &str) -> bool {
+        self.from_hash == from && self.to_hash == to
+    }
+}
+
+/// A point in a table's schema lineage: the first version with a schema
+/// hash, and every later version whose hash differs from its predecessor's.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SchemaChange {
+    /// First version with this schema
+    pub version: u64,
+
+    pub schema_hash: String,
+
+    /// Hash the previous version had (None for the first schema)
+    pub previous_hash: Option<String>,
+
+    /// Full schema, if it was registered
+    pub schema: Option<TableSchema>,
+
+    /// Migration the version was committed with, if any
+    pub migration: Option<SchemaMigration>,
+
+    /// Unix timestamp of the version
+    pub created_at: i64,
+}
+
+/// Content-addressed store of full schemas, one JSON file per hash under
+/// the catalog root. Obtained from [`FileCatalog::schemas`](super::FileCatalog::schemas).
+#[derive(Debug, Clone)]
+pub struct SchemaRegistry {
+    dir: PathBuf,
+    durability: DurabilityLevel,
+}
+
+impl SchemaRegistry {
+    pub(super) fn new(dir: PathBuf, durability: DurabilityLevel) -> Self {
+        Self { dir, durability }
+    }
+
+    fn path(&self, hash: &str) -> Option<PathBuf> {
+        // Hashes are hex; anything else cannot name a registered schema
+        (!hash.is_empty() && hash.chars().all(|c| c.is_ascii_hexdigit()))
+            .then(|| self.dir.join(format!("{}.json", hash)))
+    }
+
+    /// Store a schema under its hash, returning the hash. Registering the
+    /// same schema again is a no-op.
+    pub fn register(&self, schema: &TableSchema) -> Result<String, CatalogError> {
+        let path = self
+            .path(&schema.hash)
+            .ok_or_else(|| CatalogError::InvalidSchemaHash(schema.hash.clone()))?;
+        if !path.exists() {
+            fs::create_dir_all(&self.dir)?;
+            let json = serde_json::to_string_pretty(schema)?;
+            let temp = path.with_extension(format!("json.{}.tmp", uuid::Uuid::new_v4()));
+            durability::write_atomic(&path, &temp, json, self.durability)?;
+        }
+        Ok(schema.hash.clone())
+    }
+
+    /// Register the schema of an Arrow schema, returning its hash
+    pub fn register_arrow(&self, schema: &Schema) -> Result<String, CatalogError> {
+        self.register(&TableSchema::from_arrow(schema))
+    }
+
+    /// Schema registered under `hash`, if any
+    pub fn get(&self, hash: &str) -> Result<Option<TableSchema>, CatalogError> {
+        match self.path(hash) {
+            Some(path) if path.exists() => Ok(Some(serde_json::from_str(&fs::read_to_string(path)?)?)),
+            _ => Ok(None),
+        }
+    }
+
+    pub fn contains(&self, hash: &str) -> bool {
+        self.path(hash).is_some_and(|p| p.exists())
+    }
+
+    /// Every registered hash, sorted
+    pub fn list(&self) -> Result<Vec<String>, CatalogError> {
+        if !self.dir.exists() {
+            return Ok(Vec::new());
+        }
+        let mut hashes = Vec::new();
+        for entry in fs::read_dir(&self.dir)? {
+            let name = entry?.file_name();
+            if let Some(hash) = name.to_str().and_then(|n| n.strip_suffix(".json")) {
+                hashes.push(hash.to_string());
+            }
+        }
+        hashes.sort();
+        Ok(hashes)
+    }
+
+    /// Check that a table may move from schema hash `from` to `to`: the
+    /// same hash, an additive extension (both schemas registered), or a
+    /// change `migration` allows.
+    pub fn check_change(
+        &self,
+        table: &str,
+        from: &str,
+        to: &str,
+        migration: Option<&SchemaMigration>,
+    ) -> Result<(), CatalogError> {
+        if from == to || migration.is_some_and(|m| m.allows(from, to)) {
+            return Ok(());
+        }
+        if let (Some(old), Some(new)) = (self.get(from)?, self.get(to)?) {
+            if new.extends(&old) {
+                return Ok(());
+            }
+        }
+        Err(CatalogError::SchemaConflict {
+            table: table.to_string(),
+            expected: from.to_string(),
+            found: to.to_string(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::datatypes::{DataType, Field};
+
+    fn schema(fields: &[(&str, DataType, bool)]) -> TableSchema {
+        TableSchema::from_arrow(&Schema::new(
+            fields
+                .iter()
+                .map(|(name, ty, nullable)| Field::new(*name, ty.clone(), *nullable))
+                .collect::<Vec<_>>(),
+        ))
+    }
+
+    #[test]
+    fn test_register_and_check_change() {
+        let dir = tempfile::tempdir().unwrap();
+        let registry = SchemaRegistry::new(dir.path().join(SCHEMAS_DIR), DurabilityLevel::None);
+
+        let v1 = schema(&[("id", DataType::Int64, false)]);
+        let added = schema(&[("id", DataType::Int64, false), ("name", DataType::Utf8, true)]);
+        let required = schema(&[("id", DataType::Int64, false), ("name", DataType::Utf8, false)]);
+        let retyped = schema(&[("id", DataType::Utf8, false)]);
+        for s in [&v1, &added, &required, &retyped] {
+            assert_eq!(registry.register(s).unwrap(), s.hash);
+        }
+        registry.register(&v1).unwrap();
+        assert_eq!(registry.list().unwrap().len(), 4);
+        assert_eq!(registry.get(&added.hash).unwrap(), Some(added.clone()));
+        assert_eq!(registry.get("../escape").unwrap(), None);
+
+        // Nullable columns may be added; anything else needs a migration
+        registry.check_change("t", &v1.hash, &added.hash, None).unwrap();
+        for breaking in [&required, &retyped] {
+            assert!(matches!(
+                registry.check_change("t", &v1.hash, &breaking.hash, None),
+                Err(CatalogError::SchemaConflict { .. })
+            ));
+        }
+        assert!(registry.check_change("t", &added.hash, &v1.hash, None).is_err());
+        let migration = SchemaMigration::new(&added.hash, &v1.hash).with_description("drop name");
+        registry.check_change("t", &added.hash, &v1.hash, Some(&migration)).unwrap();
+        assert!(registry.check_change("t", &v1.hash, &retyped.hash, Some(&migration)).is_err());
+
+        // Unregistered schemas cannot be shown to be compatible
+        assert!(registry.check_change("t", &v1.hash, "abc123", None).is_err());
+    }
+}
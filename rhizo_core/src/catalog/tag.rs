@@ -0,0 +1,294 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use super::error::CatalogError;
+use super::file_catalog::FileCatalog;
+use super::namespace;
+use super::pin;
+use super::version::TableVersion;
+use crate::branch::Branch;
+use crate::durability;
+
+/// Directory (under the catalog root) holding tags
+pub(super) const TAGS_DIR: &str = ".tags";
+
+/// A named, immutable release point (e.g. "v2024-q3").
+///
+/// Unlike a branch, a tag never moves: it is created once over a fixed set
+/// of table versions and can only be deleted. Tagged versions are protected
+/// from deletion like pinned ones.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Tag {
+    /// Unique tag name
+    pub name: String,
+
+    /// Tagged versions: table_name -> version number
+    pub versions: HashMap<String, u64>,
+
+    /// Unix timestamp when the tag was created
+    pub created_at: i64,
+
+    /// Branch whose head was tagged, if the tag was taken from a branch
+    #[serde(default)]
+    pub source_branch: Option<String>,
+
+    /// Optional release notes
+    #[serde(default)]
+    pub message: Option<String>,
+}
+
+impl Tag {
+    pub fn new(name: impl Into<String>, versions: HashMap<String, u64>) -> Self {
+        Self {
+            name: name.into(),
+            versions,
+            created_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_secs() as i64,
+            source_branch: None,
+            message: None,
+        }
+    }
+
+    pub fn with_message(mut self, message: impl Into<String>) -> Self {
+        self.message = Some(message.into());
+        self
+    }
+
+    /// Tagged version of a table, if the table is part of this tag
+    pub fn get(&self, table_name: &str) -> Option<u64> {
+        self.versions.get(table_name).copied()
+    }
+}
+
+/// Validate a tag name. Same rules as pin and branch names.
+pub fn validate_tag_name(name: &str) -> Result<(), CatalogError> {
+    pin::validate_ref_name(name).map_err(|reason| CatalogError::InvalidTagName(format!("{:?}: {}", name, reason)))
+}
+
+/// Read one tag stored under a catalog root
+pub(super) fn read_tag(catalog_root: &Path, name: &str) -> Result<Tag, CatalogError> {
+    validate_tag_name(name)?;
+    let path = catalog_root
+        .join(TAGS_DIR)
+        .join(format!("{}.json", namespace::encode_table_name(name)));
+    if !path.exists() {
+        return Err(CatalogError::TagNotFound(name.to_string()));
+    }
+    Ok(serde_json::from_str(&fs::read_to_string(&path)?)?)
+}
+
+/// Read every tag stored under a catalog root, sorted by name
+pub(super) fn read_tags(catalog_root: &Path) -> Result<Vec<Tag>, CatalogError> {
+    let tags_dir = catalog_root.join(TAGS_DIR);
+    if !tags_dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut tags = Vec::new();
+    for entry in fs::read_dir(&tags_dir)? {
+        let path = entry?.path();
+        if path.extension().and_then(|e| e.to_str()) != Some("json") {
+            continue;
+        }
+        tags.push(serde_json::from_str::<Tag>(&fs::read_to_string(&path)?)?);
+    }
+
+    tags.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(tags)
+}
+
+/// Manages tags: named, immutable snapshots of table versions.
+///
+/// Tags are stored as JSON files in the catalog's `.tags` directory, next to
+/// pins, so `FileCatalog::delete_version` can refuse to remove a tagged
+/// version.
+///
+/// # Example
+///
+/// ```ignore
+/// let tags = TagManager::new(Arc::clone(&catalog));
+/// tags.create_from_branch("v2024-q3", &branches.get("main")?, Some("Q3 release"))?;
+/// let users = tags.get_version("v2024-q3", "users")?;
+/// ```
+pub struct TagManager {
+    catalog: Arc<FileCatalog>,
+}
+
+impl TagManager {
+    pub fn new(catalog: Arc<FileCatalog>) -> Self {
+        Self { catalog }
+    }
+
+    /// Tag an explicit set of table versions. Every version must exist.
+    pub fn create(
+        &self,
+        name: &str,
+        versions: HashMap<String, u64>,
+        message: Option<&str>,
+    ) -> Result<Tag, CatalogError> {
+        let mut tag = Tag::new(name, versions);
+        if let Some(message) = message {
+            tag = tag.with_message(message);
+        }
+        self.save_new(tag)
+    }
+
+    /// Tag the current head of a branch.
+    pub fn create_from_branch(
+        &self,
+        name: &str,
+        branch: &Branch,
+        message: Option<&str>,
+    ) -> Result<Tag, CatalogError> {
+        let mut tag = Tag::new(name, branch.head.clone());
+        tag.source_branch = Some(branch.name.clone());
+        if let Some(message) = message {
+            tag = tag.with_message(message);
+        }
+        self.save_new(tag)
+    }
+
+    /// Tag the latest version of every table in the catalog.
+    pub fn create_from_latest(&self, name: &str, message: Option<&str>) -> Result<Tag, CatalogError> {
+        let mut versions = HashMap::new();
+        for table in self.catalog.list_tables()? {
+            versions.insert(table.clone(), self.catalog.get_version(&table, None)?.version);
+        }
+        self.create(name, versions, message)
+    }
+
+    /// Look up a tag by name
+    pub fn get(&self, name: &str) -> Result<Tag, CatalogError> {
+        read_tag(self.catalog.base_path(), name)
+    }
+
+    /// List all tags, sorted by name
+    pub fn list(&self) -> Result<Vec<Tag>, CatalogError> {
+        read_tags(self.catalog.base_path())
+    }
+
+    /// Delete a tag, releasing its versions for deletion and garbage
+    /// collection
+    pub fn delete(&self, name: &str) -> Result<Tag, CatalogError> {
+        let _lock = self.catalog.acquire_dir_lock(TAGS_DIR)?;
+        let existing = self.get(name)?;
+        durability::remove_file(&self.tag_path(name), self.catalog.durability())?;
+        Ok(existing)
+    }
+
+    /// The version of `table_name` recorded by a tag
+    pub fn get_version(&self, tag: &str, table_name: &str) -> Result<TableVersion, CatalogError> {
+        let version = self
+            .get(tag)?
+            .get(table_name)
+            .ok_or_else(|| CatalogError::TableNotFound(table_name.to_string()))?;
+        self.catalog.get_version(table_name, Some(version))
+    }
+
+    fn tag_path(&self, name: &str) -> PathBuf {
+        self.catalog
+            .base_path()
+            .join(TAGS_DIR)
+            .join(format!("{}.json", namespace::encode_table_name(name)))
+    }
+
+    fn save_new(&self, tag: Tag) -> Result<Tag, CatalogError> {
+        validate_tag_name(&tag.name)?;
+        for (table, &version) in &tag.versions {
+            if !self.catalog.version_exists(table, version) {
+                return Err(CatalogError::VersionNotFound(table.clone(), version));
+            }
+        }
+
+        let _lock = self.catalog.acquire_dir_lock(TAGS_DIR)?;
+        let path = self.tag_path(&tag.name);
+        if path.exists() {
+            return Err(CatalogError::TagAlreadyExists(tag.name));
+        }
+
+        let temp_path = path.with_extension("json.tmp");
+        durability::write_atomic(&path, &temp_path, serde_json::to_string_pretty(&tag)?, self.catalog.durability())?;
+        Ok(tag)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup() -> (PathBuf, Arc<FileCatalog>, TagManager) {
+        let dir = std::env::temp_dir().join(format!("rhizo_tag_test_{}", uuid::Uuid::new_v4()));
+        let catalog = Arc::new(FileCatalog::new(&dir).unwrap());
+        for (table, versions) in [("users", 2), ("orders", 1)] {
+            for v in 1..=versions {
+                catalog
+                    .commit(TableVersion::new(table, v, vec![format!("{}{}", table, v)]))
+                    .unwrap();
+            }
+        }
+        let tags = TagManager::new(Arc::clone(&catalog));
+        (dir, catalog, tags)
+    }
+
+    #[test]
+    fn test_tags_are_immutable_release_points() {
+        let (dir, catalog, tags) = setup();
+
+        let tag = tags.create_from_latest("v2024-q3", Some("Q3 release")).unwrap();
+        assert_eq!(tag.get("users"), Some(2));
+        assert_eq!(tag.get("orders"), Some(1));
+
+        // Later commits do not move the tag
+        catalog.commit(TableVersion::new("users", 3, vec!["users3".into()])).unwrap();
+        assert_eq!(tags.get("v2024-q3").unwrap(), tag);
+        assert_eq!(tags.get_version("v2024-q3", "users").unwrap().version, 2);
+
+        assert!(matches!(
+            tags.create_from_latest("v2024-q3", None),
+            Err(CatalogError::TagAlreadyExists(_))
+        ));
+        assert!(matches!(
+            tags.create("bad", HashMap::from([("users".to_string(), 9)]), None),
+            Err(CatalogError::VersionNotFound(_, 9))
+        ));
+        assert!(matches!(tags.create("a..b", HashMap::new(), None), Err(CatalogError::InvalidTagName(_))));
+
+        // Tagged versions cannot be deleted until the tag is
+        assert!(matches!(
+            catalog.delete_version("users", 2),
+            Err(CatalogError::VersionTagged(_, 2, ref name)) if name == "v2024-q3"
+        ));
+        assert_eq!(tags.delete("v2024-q3").unwrap().name, "v2024-q3");
+        assert!(matches!(tags.get("v2024-q3"), Err(CatalogError::TagNotFound(_))));
+        catalog.delete_version("users", 2).unwrap();
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_tag_from_branch() {
+        let (dir, _catalog, tags) = setup();
+        let mut branch = Branch::new("release/1", HashMap::new());
+        branch.set_table_version("users", 1);
+
+        tags.create_from_branch("rc/1", &branch, None).unwrap();
+        tags.create("baseline", HashMap::from([("orders".to_string(), 1)]), None).unwrap();
+
+        let listed = tags.list().unwrap();
+        assert_eq!(listed.iter().map(|t| t.name.as_str()).collect::<Vec<_>>(), vec!["baseline", "rc/1"]);
+        assert_eq!(listed[1].source_branch.as_deref(), Some("release/1"));
+        assert_eq!(listed[1].versions, HashMap::from([("users".to_string(), 1)]));
+        assert!(matches!(
+            tags.get_version("rc/1", "orders"),
+            Err(CatalogError::TableNotFound(_))
+        ));
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
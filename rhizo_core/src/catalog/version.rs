@@ -1,7 +1,21 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+use super::schema::{SchemaMigration, SCHEMA_MIGRATION_METADATA_KEY};
+use crate::algebraic::TableAlgebraicSchema;
+
+/// Version metadata key listing, comma-separated, chunks the version's
+/// chunks are encoded against (such as the bases of row patches). They are
+/// kept alive like the version's own chunks.
+pub const BASE_CHUNKS_METADATA_KEY: &str = "base_chunks";
+
+/// Version metadata key holding the table's algebraic column annotations
+/// as JSON (`null` once cleared). A version committed without it inherits
+/// its parent version's annotations.
+pub const ALGEBRAIC_SCHEMA_METADATA_KEY: &str = "algebraic_schema";
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct TableVersion {
     pub table_name: String,
     pub version: u64,
@@ -37,4 +51,58 @@ impl TableVersion {
         self.schema_hash = Some(hash.into());
         self
     }
+
+    /// Chunks listed under [`BASE_CHUNKS_METADATA_KEY`]
+    pub fn base_chunks(&self) -> Vec<String> {
+        self.metadata
+            .get(BASE_CHUNKS_METADATA_KEY)
+            .map(|list| list.split(',').filter(|h| !h.is_empty()).map(String::from).collect())
+            .unwrap_or_default()
+    }
+
+    /// Set the algebraic annotations the version carries, or clear them
+    /// with `None`
+    pub fn with_algebraic_schema(mut self, schema: Option<&TableAlgebraicSchema>) -> Self {
+        self.metadata.insert(ALGEBRAIC_SCHEMA_METADATA_KEY.to_string(), algebraic_schema_json(schema));
+        self
+    }
+
+    /// Algebraic annotations under [`ALGEBRAIC_SCHEMA_METADATA_KEY`], named
+    /// after this table (None if absent, cleared or unreadable)
+    pub fn algebraic_schema(&self) -> Option<TableAlgebraicSchema> {
+        let json = self.metadata.get(ALGEBRAIC_SCHEMA_METADATA_KEY)?;
+        let mut schema: TableAlgebraicSchema = serde_json::from_str::<Option<_>>(json).ok()??;
+        schema.table = self.table_name.clone();
+        Some(schema)
+    }
+
+    /// Record the migration allowing the version's schema change (see
+    /// [`SchemaRegistry::check_change`](super::SchemaRegistry::check_change))
+    pub fn with_schema_migration(self, migration: &SchemaMigration) -> Self {
+        self.with_metadata(SCHEMA_MIGRATION_METADATA_KEY, schema_migration_json(migration))
+    }
+
+    /// Migration under [`SCHEMA_MIGRATION_METADATA_KEY`] (None if absent or
+    /// unreadable)
+    pub fn schema_migration(&self) -> Option<SchemaMigration> {
+        serde_json::from_str(self.metadata.get(SCHEMA_MIGRATION_METADATA_KEY)?).ok()
+    }
+
+    /// Every chunk reading the version needs: its own chunks, then its
+    /// base chunks
+    pub fn referenced_chunks(&self) -> Vec<String> {
+        let mut chunks = self.chunk_hashes.clone();
+        chunks.extend(self.base_chunks());
+        chunks
+    }
+}
+
+/// Metadata value for [`ALGEBRAIC_SCHEMA_METADATA_KEY`]
+pub(crate) fn algebraic_schema_json(schema: Option<&TableAlgebraicSchema>) -> String {
+    serde_json::to_string(&schema).expect("algebraic schemas serialize to JSON")
+}
+
+/// Metadata value for [`SCHEMA_MIGRATION_METADATA_KEY`]
+pub(crate) fn schema_migration_json(migration: &SchemaMigration) -> String {
+    serde_json::to_string(migration).expect("schema migrations serialize to JSON")
 }
@@ -0,0 +1,75 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Directory (under the catalog root) holding views
+pub(super) const VIEWS_DIR: &str = ".views";
+
+/// A named query that reads like a table.
+///
+/// Views store logic, not data: the query runs over its sources at the
+/// versions being read (branch head, time travel), so teams can share
+/// derived tables without copying them. With `materialize` set, readers
+/// may cache the result for each set of source versions.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct View {
+    /// View name; shares the table namespace and naming rules
+    pub name: String,
+
+    /// SQL query defining the view
+    pub sql: String,
+
+    /// Tables and views the query reads
+    pub sources: Vec<String>,
+
+    /// Whether readers should cache results per source-version set
+    #[serde(default)]
+    pub materialize: bool,
+
+    /// Unix timestamp when the view was (re)defined
+    pub created_at: i64,
+
+    /// Optional free-form description
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+impl View {
+    pub fn new(name: impl Into<String>, sql: impl Into<String>, sources: Vec<String>) -> Self {
+        Self {
+            name: name.into(),
+            sql: sql.into(),
+            sources,
+            materialize: false,
+            created_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_secs() as i64,
+            description: None,
+        }
+    }
+
+    pub fn with_materialize(mut self, materialize: bool) -> Self {
+        self.materialize = materialize;
+        self
+    }
+
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Stable key for one set of base-table versions, e.g. `orders@4,users@2`.
+    ///
+    /// Readers cache materialized results under this key; any change to a
+    /// base table's version yields a different key.
+    pub fn materialization_key(source_versions: &HashMap<String, u64>) -> String {
+        let mut entries: Vec<_> = source_versions.iter().collect();
+        entries.sort();
+        entries
+            .iter()
+            .map(|(table, version)| format!("{}@{}", table, version))
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+}
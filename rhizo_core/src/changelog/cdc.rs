@@ -0,0 +1,301 @@
+//! Debezium-compatible change data capture export.
+//!
+//! Each table change in a changelog entry becomes one change event in the
+//! shape Debezium connectors emit (with `schemas.enable=false`), so
+//! existing CDC pipelines can consume rhizo commits unchanged:
+//!
+//! ```text
+//! {"before": {"table": "users", "version": 1},
+//!  "after":  {"table": "users", "version": 2, "chunk_hashes": ["..."]},
+//!  "source": {"version": "0.1.0", "connector": "rhizo", "name": "warehouse",
+//!             "ts_ms": 1700000000000, "db": "main", "table": "users",
+//!             "tx_id": 42, "epoch_id": 3},
+//!  "op": "u",
+//!  "ts_ms": 1700000000123,
+//!  "transaction": {"id": "42", "total_order": 1, "data_collection_order": 1}}
+//! ```
+//!
+//! Events go to a [`CdcSink`]. [`JsonLinesSink`] writes one envelope per
+//! line to a file or stdout; a Kafka producer plugs in by implementing the
+//! trait and publishing each record's `key` and `value` to its `topic`.
+
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use super::entry::{ChangelogEntry, TableChange};
+use super::error::CdcError;
+use crate::transaction::WriteKind;
+
+/// Connector name reported in every event's `source` block
+pub const CDC_CONNECTOR: &str = "rhizo";
+
+/// Debezium operation type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CdcOp {
+    /// A new table (first version, or the new name of a renamed table)
+    #[serde(rename = "c")]
+    Create,
+    /// A new version of an existing table
+    #[serde(rename = "u")]
+    Update,
+    /// A dropped table (or the old name of a renamed table)
+    #[serde(rename = "d")]
+    Delete,
+}
+
+/// A table version as the `before` or `after` image of an event
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CdcTableVersion {
+    pub table: String,
+    pub version: u64,
+    /// Chunks of the version; only known for the `after` image of a write
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chunk_hashes: Option<Vec<String>>,
+}
+
+/// Origin of an event (Debezium's `source` block)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CdcSource {
+    /// rhizo version that produced the event
+    pub version: String,
+    pub connector: String,
+    /// Logical server name the exporter was created with
+    pub name: String,
+    /// Commit time in milliseconds
+    pub ts_ms: i64,
+    /// Branch the change was written to
+    pub db: String,
+    pub table: String,
+    pub tx_id: u64,
+    pub epoch_id: u64,
+}
+
+/// Position of an event within its transaction
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CdcTransaction {
+    pub id: String,
+    /// 1-based position among all events of the transaction
+    pub total_order: u64,
+    /// 1-based position among the transaction's events for this table
+    pub data_collection_order: u64,
+}
+
+/// A Debezium change event envelope
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CdcEnvelope {
+    pub before: Option<CdcTableVersion>,
+    pub after: Option<CdcTableVersion>,
+    pub source: CdcSource,
+    pub op: CdcOp,
+    /// Time the event was produced, in milliseconds
+    pub ts_ms: i64,
+    pub transaction: CdcTransaction,
+}
+
+/// An envelope addressed for a message broker
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CdcRecord {
+    /// `{server}.{branch}.{table}`, following Debezium's topic naming
+    pub topic: String,
+    /// Message key (`{"table": ...}`), so a table's events share a partition
+    pub key: serde_json::Value,
+    pub value: CdcEnvelope,
+}
+
+/// Destination of exported change events.
+///
+/// # Example
+///
+/// ```ignore
+/// struct KafkaSink(rdkafka::producer::BaseProducer);
+///
+/// impl CdcSink for KafkaSink {
+///     fn send(&mut self, record: &CdcRecord) -> Result<(), CdcError> {
+///         let key = serde_json::to_vec(&record.key)?;
+///         let value = serde_json::to_vec(&record.value)?;
+///         self.0
+///             .send(BaseRecord::to(&record.topic).key(&key).payload(&value))
+///             .map_err(|(e, _)| CdcError::Sink(e.to_string()))
+///     }
+/// }
+/// ```
+pub trait CdcSink {
+    fn send(&mut self, record: &CdcRecord) -> Result<(), CdcError>;
+
+    /// Called once after an export; buffered sinks should deliver here
+    fn flush(&mut self) -> Result<(), CdcError> {
+        Ok(())
+    }
+}
+
+/// Writes each envelope as one JSON line
+pub struct JsonLinesSink<W: Write> {
+    writer: W,
+}
+
+impl<W: Write> JsonLinesSink<W> {
+    pub fn new(writer: W) -> Self {
+        Self { writer }
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl JsonLinesSink<BufWriter<File>> {
+    /// Append to the file at `path`, creating it if needed
+    pub fn create(path: impl AsRef<Path>) -> Result<Self, CdcError> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self::new(BufWriter::new(file)))
+    }
+}
+
+impl JsonLinesSink<io::Stdout> {
+    pub fn stdout() -> Self {
+        Self::new(io::stdout())
+    }
+}
+
+impl<W: Write> CdcSink for JsonLinesSink<W> {
+    fn send(&mut self, record: &CdcRecord) -> Result<(), CdcError> {
+        serde_json::to_writer(&mut self.writer, &record.value)?;
+        self.writer.write_all(b"\n")?;
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<(), CdcError> {
+        self.writer.flush()?;
+        Ok(())
+    }
+}
+
+/// Outcome of an export
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CdcExportReport {
+    /// Changelog entries exported
+    pub entries: usize,
+    /// Events sent to the sink
+    pub records: usize,
+    /// Last exported transaction, to resume from
+    pub last_tx_id: Option<u64>,
+}
+
+/// Converts changelog entries into Debezium change events.
+///
+/// # Example
+///
+/// ```ignore
+/// let entries = tx_manager.get_changelog(ChangelogQuery::new().since_tx(last))?;
+/// let report = CdcExporter::new("warehouse").export(&entries, &mut JsonLinesSink::stdout())?;
+/// ```
+pub struct CdcExporter {
+    server_name: String,
+}
+
+impl CdcExporter {
+    /// `server_name` is the logical name of this repository in events and
+    /// topic names
+    pub fn new(server_name: impl Into<String>) -> Self {
+        Self { server_name: server_name.into() }
+    }
+
+    pub fn server_name(&self) -> &str {
+        &self.server_name
+    }
+
+    /// The events for one entry: one per change, two for a rename (a
+    /// delete of the old name and a create of the new one)
+    pub fn records(&self, entry: &ChangelogEntry) -> Vec<CdcRecord> {
+        let ts_ms = unix_millis();
+        let mut records = Vec::new();
+        for change in &entry.changes {
+            let branch = entry.branch_of(change);
+            for (op, before, after) in Self::images(change) {
+                let table = after.as_ref().or(before.as_ref()).map(|v| v.table.clone()).unwrap_or_default();
+                let total_order = records.len() as u64 + 1;
+                records.push(CdcRecord {
+                    topic: format!("{}.{}.{}", self.server_name, branch, table),
+                    key: serde_json::json!({ "table": table }),
+                    value: CdcEnvelope {
+                        before,
+                        after,
+                        source: CdcSource {
+                            version: env!("CARGO_PKG_VERSION").to_string(),
+                            connector: CDC_CONNECTOR.to_string(),
+                            name: self.server_name.clone(),
+                            ts_ms: entry.committed_at * 1000,
+                            db: branch.to_string(),
+                            table,
+                            tx_id: entry.tx_id,
+                            epoch_id: entry.epoch_id,
+                        },
+                        op,
+                        ts_ms,
+                        transaction: CdcTransaction {
+                            id: entry.tx_id.to_string(),
+                            total_order,
+                            data_collection_order: 1,
+                        },
+                    },
+                });
+            }
+        }
+        records
+    }
+
+    /// Send the events of `entries` to `sink` in transaction order, then
+    /// flush it
+    pub fn export(&self, entries: &[ChangelogEntry], sink: &mut dyn CdcSink) -> Result<CdcExportReport, CdcError> {
+        let mut ordered: Vec<&ChangelogEntry> = entries.iter().collect();
+        ordered.sort_by_key(|e| e.tx_id);
+
+        let mut report = CdcExportReport::default();
+        for entry in ordered {
+            for record in self.records(entry) {
+                sink.send(&record)?;
+                report.records += 1;
+            }
+            report.entries += 1;
+            report.last_tx_id = Some(entry.tx_id);
+        }
+        sink.flush()?;
+        Ok(report)
+    }
+
+    /// Operation and before/after images of each event for a change
+    fn images(change: &TableChange) -> Vec<(CdcOp, Option<CdcTableVersion>, Option<CdcTableVersion>)> {
+        let image = |table: &str, chunks: Option<Vec<String>>| {
+            Some(CdcTableVersion { table: table.to_string(), version: change.new_version, chunk_hashes: chunks })
+        };
+        let table = change.table_name.as_str();
+        match &change.kind {
+            WriteKind::Data | WriteKind::Merge { .. } => {
+                let after = image(table, Some(change.chunk_hashes.clone()));
+                match change.old_version {
+                    None => vec![(CdcOp::Create, None, after)],
+                    Some(old) => {
+                        let before = CdcTableVersion { table: table.to_string(), version: old, chunk_hashes: None };
+                        vec![(CdcOp::Update, Some(before), after)]
+                    }
+                }
+            }
+            WriteKind::Drop => vec![(CdcOp::Delete, image(table, None), None)],
+            WriteKind::Rename { to } => vec![
+                (CdcOp::Delete, image(table, None), None),
+                (CdcOp::Create, None, image(to, None)),
+            ],
+        }
+    }
+}
+
+fn unix_millis() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as i64)
+        .unwrap_or(0)
+}
@@ -0,0 +1,158 @@
+//! Named, persisted positions of changelog consumers.
+//!
+//! A streaming consumer reads entries after its cursor, processes them and
+//! acknowledges the last one it finished. The acknowledged transaction is
+//! stored as `{log}/cursors/{name}.json`, so after a restart the consumer
+//! resumes right after it. Cursors are independent: each consumer moves
+//! only its own.
+//!
+//! ```ignore
+//! let mut cursor = manager.resume("search-indexer")?;
+//! for entry in manager.get_changelog(cursor.query().with_limit(100))? {
+//!     index(&entry)?;
+//!     cursor.ack(entry.tx_id)?;
+//! }
+//! ```
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use super::query::ChangelogQuery;
+use crate::catalog::namespace::encode_table_name;
+use crate::catalog::pin::validate_ref_name;
+use crate::durability::{self, DurabilityLevel};
+use crate::transaction::{TransactionError, TxId};
+
+/// A consumer's position in the changelog
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChangelogCursor {
+    /// Unique consumer name
+    pub name: String,
+
+    /// Last transaction the consumer acknowledged (0 before the first ack)
+    pub acked_tx_id: TxId,
+
+    /// Unix timestamp when the cursor was created
+    pub created_at: i64,
+
+    /// Unix timestamp of the last acknowledgement
+    pub updated_at: i64,
+
+    #[serde(skip)]
+    path: PathBuf,
+
+    #[serde(skip)]
+    durability: DurabilityLevel,
+}
+
+impl ChangelogCursor {
+    /// Query for the entries the consumer has not acknowledged yet
+    pub fn query(&self) -> ChangelogQuery {
+        ChangelogQuery::new().since_tx(self.acked_tx_id)
+    }
+
+    /// Record that every entry up to and including `tx_id` is processed.
+    ///
+    /// Acknowledging a transaction at or before the cursor is a no-op, so
+    /// redelivered entries can be acknowledged again safely.
+    pub fn ack(&mut self, tx_id: TxId) -> Result<(), TransactionError> {
+        if tx_id <= self.acked_tx_id {
+            return Ok(());
+        }
+        self.acked_tx_id = tx_id;
+        self.updated_at = unix_now();
+        self.save()
+    }
+
+    fn save(&self) -> Result<(), TransactionError> {
+        let temp_path = self.path.with_extension("json.tmp");
+        durability::write_atomic(&self.path, &temp_path, serde_json::to_string_pretty(self)?, self.durability)?;
+        Ok(())
+    }
+}
+
+/// Cursors stored under one directory
+pub(crate) struct CursorStore {
+    dir: PathBuf,
+    durability: DurabilityLevel,
+}
+
+impl CursorStore {
+    pub(crate) fn new(dir: impl AsRef<Path>, durability: DurabilityLevel) -> Self {
+        Self { dir: dir.as_ref().to_path_buf(), durability }
+    }
+
+    /// Create a cursor at the start of the changelog
+    pub(crate) fn create(&self, name: &str) -> Result<ChangelogCursor, TransactionError> {
+        let path = self.path(name)?;
+        if path.exists() {
+            return Err(TransactionError::CursorAlreadyExists(name.to_string()));
+        }
+        fs::create_dir_all(&self.dir)?;
+        let now = unix_now();
+        let cursor = ChangelogCursor {
+            name: name.to_string(),
+            acked_tx_id: 0,
+            created_at: now,
+            updated_at: now,
+            path,
+            durability: self.durability,
+        };
+        cursor.save()?;
+        Ok(cursor)
+    }
+
+    /// Load a cursor as last acknowledged
+    pub(crate) fn get(&self, name: &str) -> Result<ChangelogCursor, TransactionError> {
+        let path = self.path(name)?;
+        if !path.exists() {
+            return Err(TransactionError::CursorNotFound(name.to_string()));
+        }
+        self.load(path)
+    }
+
+    /// All cursors, sorted by name
+    pub(crate) fn list(&self) -> Result<Vec<ChangelogCursor>, TransactionError> {
+        if !self.dir.exists() {
+            return Ok(Vec::new());
+        }
+        let mut cursors = Vec::new();
+        for entry in fs::read_dir(&self.dir)? {
+            let path = entry?.path();
+            if path.extension().and_then(|e| e.to_str()) == Some("json") {
+                cursors.push(self.load(path)?);
+            }
+        }
+        cursors.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(cursors)
+    }
+
+    pub(crate) fn delete(&self, name: &str) -> Result<ChangelogCursor, TransactionError> {
+        let cursor = self.get(name)?;
+        durability::remove_file(&cursor.path, self.durability)?;
+        Ok(cursor)
+    }
+
+    fn load(&self, path: PathBuf) -> Result<ChangelogCursor, TransactionError> {
+        let mut cursor: ChangelogCursor = serde_json::from_str(&fs::read_to_string(&path)?)?;
+        cursor.path = path;
+        cursor.durability = self.durability;
+        Ok(cursor)
+    }
+
+    fn path(&self, name: &str) -> Result<PathBuf, TransactionError> {
+        validate_ref_name(name)
+            .map_err(|reason| TransactionError::InvalidCursorName(format!("{:?}: {}", name, reason)))?;
+        Ok(self.dir.join(format!("{}.json", encode_table_name(name))))
+    }
+}
+
+fn unix_now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
+}
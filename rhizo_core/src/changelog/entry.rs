@@ -3,7 +3,8 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
-use crate::transaction::TransactionRecord;
+use crate::branch::MergeRecord;
+use crate::transaction::{TransactionRecord, WriteKind};
 
 /// A single table change within a commit.
 ///
@@ -12,7 +13,11 @@ use crate::transaction::TransactionRecord;
 /// - The previous version (None if this is a new table)
 /// - The new version after this commit
 /// - The chunk hashes for the new version
+/// - The branch written, when it differs from the transaction's branch
+/// - Whether the table was dropped or renamed instead (a tombstone, with
+///   no chunks), or its head moved by a branch merge
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct TableChange {
     /// Table that was modified
     pub table_name: String,
@@ -25,6 +30,15 @@ pub struct TableChange {
 
     /// Chunk hashes for the new version
     pub chunk_hashes: Vec<String>,
+
+    /// Branch whose head was updated, if overridden for this write
+    /// (None = the entry's branch)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub branch: Option<String>,
+
+    /// Data write, drop, rename or merge
+    #[serde(default, skip_serializing_if = "WriteKind::is_data")]
+    pub kind: WriteKind,
 }
 
 impl TableChange {
@@ -40,12 +54,41 @@ impl TableChange {
             old_version,
             new_version,
             chunk_hashes,
+            branch: None,
+            kind: WriteKind::Data,
         }
     }
 
+    /// Set the branch this write targeted
+    pub fn with_branch(mut self, branch: impl Into<String>) -> Self {
+        self.branch = Some(branch.into());
+        self
+    }
+
     /// Check if this is a new table (no previous version)
     pub fn is_new_table(&self) -> bool {
-        self.old_version.is_none()
+        self.kind.is_data() && self.old_version.is_none()
+    }
+
+    /// Check if the table was dropped
+    pub fn is_drop(&self) -> bool {
+        self.kind == WriteKind::Drop
+    }
+
+    /// Branch the head was merged from, if this change came from a merge
+    pub fn merged_from(&self) -> Option<&str> {
+        match &self.kind {
+            WriteKind::Merge { from } => Some(from),
+            _ => None,
+        }
+    }
+
+    /// New name of the table, if it was renamed
+    pub fn renamed_to(&self) -> Option<&str> {
+        match &self.kind {
+            WriteKind::Rename { to } => Some(to),
+            _ => None,
+        }
     }
 }
 
@@ -71,6 +114,7 @@ impl TableChange {
 /// }
 /// ```
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct ChangelogEntry {
     /// Transaction ID (monotonically increasing, unique)
     pub tx_id: u64,
@@ -89,6 +133,15 @@ pub struct ChangelogEntry {
 
     /// User-provided metadata (if any)
     pub metadata: HashMap<String, String>,
+
+    /// Tables whose conflicts with concurrent commits were resolved by
+    /// algebraic merge (empty for ordinary commits)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub auto_merged: Vec<String>,
+
+    /// The branch merge this entry recorded (None for ordinary commits)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub merge: Option<MergeRecord>,
 }
 
 impl ChangelogEntry {
@@ -106,6 +159,8 @@ impl ChangelogEntry {
             branch: branch.into(),
             changes: Vec::new(),
             metadata: HashMap::new(),
+            auto_merged: Vec::new(),
+            merge: None,
         }
     }
 
@@ -122,11 +177,19 @@ impl ChangelogEntry {
         previous_versions: &HashMap<String, u64>,
     ) -> Self {
         let changes = tx.writes.iter().map(|w| {
+            // A merge replaced the target branch's head, not the table's
+            // latest version
+            let old_version = match (&w.kind, &tx.merge) {
+                (WriteKind::Merge { .. }, Some(merge)) => merge.replaced.get(&w.table_name).copied(),
+                _ => previous_versions.get(&w.table_name).copied(),
+            };
             TableChange {
                 table_name: w.table_name.clone(),
-                old_version: previous_versions.get(&w.table_name).copied(),
+                old_version,
                 new_version: w.new_version,
                 chunk_hashes: w.chunk_hashes.clone(),
+                branch: w.branch.clone(),
+                kind: w.kind.clone(),
             }
         }).collect();
 
@@ -137,6 +200,8 @@ impl ChangelogEntry {
             branch: tx.branch.clone(),
             changes,
             metadata: tx.metadata.clone(),
+            auto_merged: tx.auto_merged.clone(),
+            merge: tx.merge.clone(),
         }
     }
 
@@ -160,6 +225,21 @@ impl ChangelogEntry {
         self.changes.iter().find(|c| c.table_name == table_name)
     }
 
+    /// Branch whose head a change updated
+    pub fn branch_of<'a>(&'a self, change: &'a TableChange) -> &'a str {
+        change.branch.as_deref().unwrap_or(&self.branch)
+    }
+
+    /// Whether the commit was an automatic algebraic merge
+    pub fn is_auto_merge(&self) -> bool {
+        !self.auto_merged.is_empty()
+    }
+
+    /// Whether the entry records a branch merge rather than new data
+    pub fn is_branch_merge(&self) -> bool {
+        self.merge.is_some()
+    }
+
     /// Number of tables changed in this entry
     pub fn change_count(&self) -> usize {
         self.changes.len()
@@ -0,0 +1,131 @@
+//! Error types for changelog replay and CDC export.
+
+use std::time::Duration;
+
+use thiserror::Error;
+
+use crate::branch::BranchError;
+use crate::catalog::CatalogError;
+use crate::chunk_store::ChunkStoreError;
+use crate::error::{io_retry_after, ErrorCategory, ErrorContext, ErrorInfo};
+
+/// Errors that can occur while replaying a changelog
+#[derive(Error, Debug)]
+#[non_exhaustive]
+pub enum ReplayError {
+    #[error("Catalog error: {0}")]
+    Catalog(#[from] CatalogError),
+
+    #[error("Chunk store error: {0}")]
+    ChunkStore(#[from] ChunkStoreError),
+
+    #[error("Branch error: {0}")]
+    Branch(#[from] BranchError),
+
+    /// The target is not at the version the change builds on (e.g. the
+    /// range does not start at the beginning of the log)
+    #[error("Cannot replay tx {tx_id}: {table} is at v{found} in target, change creates v{expected}")]
+    OutOfOrder {
+        tx_id: u64,
+        table: String,
+        expected: u64,
+        found: u64,
+    },
+
+    /// The changelog and the source catalog disagree on a version's data
+    #[error("Changelog does not match source catalog: {0} v{1}")]
+    ContentMismatch(String, u64),
+
+    /// Replayed branch heads differ from the source heads
+    #[error("Replayed heads differ from source: {0:?}")]
+    HeadMismatch(Vec<String>),
+}
+
+impl ErrorInfo for ReplayError {
+    fn code(&self) -> &'static str {
+        match self {
+            ReplayError::Catalog(e) => e.code(),
+            ReplayError::ChunkStore(e) => e.code(),
+            ReplayError::Branch(e) => e.code(),
+            ReplayError::OutOfOrder { .. } => "REPLAY_OUT_OF_ORDER",
+            ReplayError::ContentMismatch(..) => "REPLAY_CONTENT_MISMATCH",
+            ReplayError::HeadMismatch(_) => "REPLAY_HEAD_MISMATCH",
+        }
+    }
+
+    fn category(&self) -> ErrorCategory {
+        match self {
+            ReplayError::Catalog(e) => e.category(),
+            ReplayError::ChunkStore(e) => e.category(),
+            ReplayError::Branch(e) => e.category(),
+            ReplayError::OutOfOrder { .. } => ErrorCategory::FailedPrecondition,
+            ReplayError::ContentMismatch(..) | ReplayError::HeadMismatch(_) => ErrorCategory::DataIntegrity,
+        }
+    }
+
+    fn context(&self) -> ErrorContext {
+        match self {
+            ReplayError::Catalog(e) => e.context(),
+            ReplayError::ChunkStore(e) => e.context(),
+            ReplayError::Branch(e) => e.context(),
+            ReplayError::OutOfOrder { tx_id, table, expected, .. } => ErrorContext::new()
+                .with_tx_id(*tx_id)
+                .with_table(table.clone())
+                .with_version(*expected),
+            ReplayError::ContentMismatch(table, version) => {
+                ErrorContext::new().with_table(table.clone()).with_version(*version)
+            }
+            ReplayError::HeadMismatch(_) => ErrorContext::new(),
+        }
+    }
+
+    fn retry_after(&self) -> Option<Duration> {
+        match self {
+            ReplayError::Catalog(e) => e.retry_after(),
+            _ => None,
+        }
+    }
+}
+
+/// Errors that can occur while exporting change events
+#[derive(Error, Debug)]
+#[non_exhaustive]
+pub enum CdcError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+
+    /// A custom sink (e.g. a Kafka producer) failed to deliver an event
+    #[error("CDC sink error: {0}")]
+    Sink(String),
+}
+
+impl ErrorInfo for CdcError {
+    fn code(&self) -> &'static str {
+        match self {
+            CdcError::Io(_) => "CDC_IO",
+            CdcError::Json(_) => "CDC_JSON",
+            CdcError::Sink(_) => "CDC_SINK",
+        }
+    }
+
+    fn category(&self) -> ErrorCategory {
+        match self {
+            CdcError::Io(_) | CdcError::Sink(_) => ErrorCategory::Io,
+            CdcError::Json(_) => ErrorCategory::Internal,
+        }
+    }
+
+    fn context(&self) -> ErrorContext {
+        ErrorContext::new()
+    }
+
+    fn retry_after(&self) -> Option<Duration> {
+        match self {
+            CdcError::Io(e) => io_retry_after(e),
+            _ => None,
+        }
+    }
+}
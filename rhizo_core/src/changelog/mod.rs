@@ -4,6 +4,9 @@
 //! - `ChangelogEntry` - A committed transaction viewed as changelog entry
 //! - `TableChange` - A single table modification within a commit
 //! - `ChangelogQuery` - Builder for filtering changelog queries
+//! - `ChangelogReplay` - Rebuilds a repository by applying entries in order
+//! - `ChangelogCursor` - A consumer's persisted position, for resuming
+//! - `CdcExporter` - Debezium-style change events for CDC pipelines
 //!
 //! The changelog is built on top of the TransactionLog, providing a
 //! streaming-friendly view of committed transactions. This enables
@@ -11,11 +14,23 @@
 //! - Batch: "What is the state at version V?" (via QueryEngine.query())
 //! - Stream: "What changed since version V?" (via changelog)
 
+mod cdc;
+mod cursor;
 mod entry;
+mod error;
 mod query;
+mod replay;
 
+pub use cdc::{
+    CdcEnvelope, CdcExportReport, CdcExporter, CdcOp, CdcRecord, CdcSink, CdcSource, CdcTableVersion, CdcTransaction,
+    JsonLinesSink, CDC_CONNECTOR,
+};
+pub use cursor::ChangelogCursor;
+pub(crate) use cursor::CursorStore;
 pub use entry::{ChangelogEntry, TableChange};
+pub use error::{CdcError, ReplayError};
 pub use query::ChangelogQuery;
+pub use replay::{ChangelogReplay, ReplayReport};
 
 #[cfg(test)]
 mod tests;
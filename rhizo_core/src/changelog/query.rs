@@ -16,6 +16,9 @@ pub struct ChangelogQuery {
     /// Start from this transaction ID (exclusive)
     pub since_tx_id: Option<u64>,
 
+    /// Stop at this transaction ID (inclusive)
+    pub until_tx_id: Option<u64>,
+
     /// Start from this timestamp (inclusive, Unix seconds)
     pub since_timestamp: Option<i64>,
 
@@ -43,6 +46,14 @@ impl ChangelogQuery {
         self
     }
 
+    /// Filter to entries up to and including this transaction ID.
+    ///
+    /// Combined with `since_tx`, selects the range `(since, until]`.
+    pub fn until_tx(mut self, tx_id: u64) -> Self {
+        self.until_tx_id = Some(tx_id);
+        self
+    }
+
     /// Filter to entries at or after this timestamp.
     ///
     /// Only entries with committed_at >= timestamp will be returned.
@@ -73,7 +84,7 @@ impl ChangelogQuery {
 
     /// Check if an entry matches this query's filters.
     ///
-    /// Note: This does NOT check since_tx_id or since_timestamp,
+    /// Note: This does NOT check since_tx_id, until_tx_id or since_timestamp,
     /// which are typically handled by the query executor for efficiency.
     pub fn matches_entry(&self, entry: &super::entry::ChangelogEntry) -> bool {
         // Check branch filter
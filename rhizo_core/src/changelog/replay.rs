@@ -0,0 +1,278 @@
+//! Deterministic replay of changelog entries onto another repository.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use super::entry::{ChangelogEntry, TableChange};
+use super::error::ReplayError;
+use crate::branch::{Branch, BranchError, BranchManager};
+use crate::catalog::{CatalogError, FileCatalog, TableVersion};
+use crate::filter::RepoRef;
+use crate::transaction::WriteKind;
+
+/// Result of a replay run.
+#[derive(Debug, Clone, Default)]
+pub struct ReplayReport {
+    /// Changelog entries applied, in tx_id order
+    pub entries_applied: usize,
+
+    /// Table versions committed to the target
+    pub versions_created: usize,
+
+    /// Tables dropped and renamed by tombstone entries
+    pub tables_dropped: usize,
+    pub tables_renamed: usize,
+
+    /// Chunks written to the target store
+    pub chunks_copied: usize,
+
+    /// Bytes written to the target store
+    pub bytes_copied: u64,
+
+    /// First and last replayed transaction
+    pub first_tx_id: Option<u64>,
+    pub last_tx_id: Option<u64>,
+
+    /// Branch heads written by the replay: branch -> table -> version
+    pub heads: BTreeMap<String, BTreeMap<String, u64>>,
+}
+
+/// Rebuilds table versions and branch heads by applying changelog entries
+/// in transaction order.
+///
+/// Chunks are fetched from the source store (verified) and version records
+/// are taken from the source catalog when it still has them, so replaying
+/// the same entries always produces the same repository. Each change must
+/// create exactly the next version of its table in the target; replaying
+/// onto an empty repository therefore requires the range to start at the
+/// beginning of the log.
+///
+/// # Example
+///
+/// ```ignore
+/// let entries = tx_manager.get_changelog(ChangelogQuery::new())?;
+/// let report = ChangelogReplay::new(source)
+///     .verify_heads(true)
+///     .run(&entries, target)?;
+/// ```
+pub struct ChangelogReplay<'a> {
+    source: RepoRef<'a>,
+    verify_heads: bool,
+}
+
+impl<'a> ChangelogReplay<'a> {
+    pub fn new(source: RepoRef<'a>) -> Self {
+        Self {
+            source,
+            verify_heads: false,
+        }
+    }
+
+    /// Require every replayed branch head to equal the source head.
+    ///
+    /// Only meaningful when the replayed range ends at the source's latest
+    /// transaction and every write went through the transaction log.
+    pub fn verify_heads(mut self, verify: bool) -> Self {
+        self.verify_heads = verify;
+        self
+    }
+
+    /// Apply `entries` to `target`.
+    pub fn run(&self, entries: &[ChangelogEntry], target: RepoRef<'_>) -> Result<ReplayReport, ReplayError> {
+        let mut ordered: Vec<&ChangelogEntry> = entries.iter().collect();
+        ordered.sort_by_key(|e| e.tx_id);
+
+        let mut report = ReplayReport::default();
+        let mut copied: HashSet<String> = HashSet::new();
+
+        for entry in ordered {
+            // Check the whole entry first so a bad range fails before
+            // anything from that transaction is written
+            for change in &entry.changes {
+                let found = Self::latest_version(target.catalog, &change.table_name)?;
+                // Drops and renames apply to the version they recorded, and
+                // merges point at a version that already exists
+                let in_order = match change.kind {
+                    WriteKind::Data => found + 1 == change.new_version,
+                    WriteKind::Merge { .. } => found >= change.new_version,
+                    _ => found == change.new_version,
+                };
+                if !in_order {
+                    return Err(ReplayError::OutOfOrder {
+                        tx_id: entry.tx_id,
+                        table: change.table_name.clone(),
+                        expected: change.new_version,
+                        found,
+                    });
+                }
+            }
+
+            for change in &entry.changes {
+                if !change.kind.is_data() {
+                    self.apply_table_operation(entry.branch_of(change), change, target, &mut report)?;
+                    continue;
+                }
+
+                let new: Vec<&str> = change
+                    .chunk_hashes
+                    .iter()
+                    .filter(|hash| copied.insert((*hash).clone()))
+                    .map(String::as_str)
+                    .collect();
+                let present = target.store.exists_many(&new)?;
+                for (hash, present) in new.into_iter().zip(present) {
+                    if !present {
+                        let data = self.source.store.get_verified(hash)?;
+                        target.store.put(&data)?;
+                        report.chunks_copied += 1;
+                        report.bytes_copied += data.len() as u64;
+                    }
+                }
+
+                let version = self.version_record(entry, change)?;
+                target.catalog.commit(version)?;
+                report.versions_created += 1;
+
+                if let Some(branches) = target.branches {
+                    let branch = entry.branch_of(change);
+                    if change.new_version == 1 {
+                        if let Some(origin) = self.source.catalog.table_origin(&change.table_name)? {
+                            target.catalog.set_table_origin(&change.table_name, &origin)?;
+                        }
+                    }
+                    Self::ensure_branch(branches, branch)?;
+                    branches.update_head(branch, &change.table_name, change.new_version)?;
+                    report
+                        .heads
+                        .entry(branch.to_string())
+                        .or_default()
+                        .insert(change.table_name.clone(), change.new_version);
+                }
+            }
+
+            report.entries_applied += 1;
+            report.first_tx_id.get_or_insert(entry.tx_id);
+            report.last_tx_id = Some(entry.tx_id);
+        }
+
+        if self.verify_heads {
+            self.check_heads(&report)?;
+        }
+
+        Ok(report)
+    }
+
+    /// Replay a drop or rename tombstone, or a head moved by a merge
+    fn apply_table_operation(
+        &self,
+        branch: &str,
+        change: &TableChange,
+        target: RepoRef<'_>,
+        report: &mut ReplayReport,
+    ) -> Result<(), ReplayError> {
+        let table = &change.table_name;
+        match &change.kind {
+            WriteKind::Data => {}
+            WriteKind::Drop => {
+                target.catalog.drop_table(table)?;
+                if let Some(branches) = target.branches {
+                    branches.remove_table(table)?;
+                }
+                for head in report.heads.values_mut() {
+                    head.remove(table);
+                }
+                report.tables_dropped += 1;
+            }
+            WriteKind::Rename { to } => {
+                target.catalog.rename_table(table, to)?;
+                if let Some(branches) = target.branches {
+                    branches.rename_table(table, to)?;
+                }
+                for head in report.heads.values_mut() {
+                    if let Some(version) = head.remove(table) {
+                        head.insert(to.clone(), version);
+                    }
+                }
+                report.tables_renamed += 1;
+            }
+            WriteKind::Merge { .. } => {
+                if let Some(branches) = target.branches {
+                    Self::ensure_branch(branches, branch)?;
+                    branches.update_head(branch, table, change.new_version)?;
+                    report
+                        .heads
+                        .entry(branch.to_string())
+                        .or_default()
+                        .insert(table.clone(), change.new_version);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// The version record to commit: the source's record if it still exists
+    /// (preserving timestamps, schema hash and metadata), otherwise one
+    /// rebuilt from the changelog.
+    fn version_record(&self, entry: &ChangelogEntry, change: &TableChange) -> Result<TableVersion, ReplayError> {
+        match self.source.catalog.get_version(&change.table_name, Some(change.new_version)) {
+            Ok(version) => {
+                if version.chunk_hashes != change.chunk_hashes {
+                    return Err(ReplayError::ContentMismatch(change.table_name.clone(), change.new_version));
+                }
+                Ok(version)
+            }
+            Err(CatalogError::TableNotFound(_)) | Err(CatalogError::VersionNotFound(..)) => {
+                let mut version = TableVersion::new(&change.table_name, change.new_version, change.chunk_hashes.clone());
+                version.created_at = entry.committed_at;
+                Ok(version)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn latest_version(catalog: &FileCatalog, table: &str) -> Result<u64, ReplayError> {
+        if !catalog.table_exists(table)? {
+            return Ok(0);
+        }
+        Ok(catalog.get_version(table, None)?.version)
+    }
+
+    fn ensure_branch(branches: &BranchManager, name: &str) -> Result<(), ReplayError> {
+        match branches.get(name) {
+            Ok(_) => Ok(()),
+            Err(BranchError::BranchNotFound(_)) => {
+                branches.restore(&Branch::new(name, HashMap::new()))?;
+                Ok(())
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn check_heads(&self, report: &ReplayReport) -> Result<(), ReplayError> {
+        let Some(source_branches) = self.source.branches else {
+            return Ok(());
+        };
+
+        let mut mismatches = Vec::new();
+        for (branch, tables) in &report.heads {
+            let source_head = source_branches.get(branch)?.head;
+            for (table, &replayed) in tables {
+                let expected = source_head.get(table).copied();
+                if expected != Some(replayed) {
+                    mismatches.push(format!(
+                        "{}:{} replayed v{}, source {}",
+                        branch,
+                        table,
+                        replayed,
+                        expected.map_or("absent".to_string(), |v| format!("v{}", v)),
+                    ));
+                }
+            }
+        }
+
+        if mismatches.is_empty() {
+            Ok(())
+        } else {
+            Err(ReplayError::HeadMismatch(mismatches))
+        }
+    }
+}
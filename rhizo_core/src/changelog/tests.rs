@@ -207,3 +207,343 @@ mod serialization_tests {
         assert_eq!(entry, deserialized);
     }
 }
+
+mod replay_tests {
+    use super::*;
+    use crate::branch::{BranchManager, MergeStrategy};
+    use crate::catalog::FileCatalog;
+    use crate::chunk_store::ChunkStore;
+    use crate::filter::RepoRef;
+    use crate::transaction::TransactionManager;
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    struct Source {
+        _dir: TempDir,
+        store: ChunkStore,
+        catalog: Arc<FileCatalog>,
+        branches: Arc<BranchManager>,
+        tx: TransactionManager,
+    }
+
+    impl Source {
+        fn new() -> Self {
+            let dir = TempDir::new().unwrap();
+            let catalog = Arc::new(FileCatalog::new(dir.path().join("catalog")).unwrap());
+            let branches = Arc::new(BranchManager::new(dir.path().join("branches")).unwrap());
+            let tx = TransactionManager::new(dir.path().join("tx"), catalog.clone(), Some(branches.clone())).unwrap();
+            Self {
+                store: ChunkStore::new(dir.path().join("chunks")).unwrap(),
+                _dir: dir,
+                catalog,
+                branches,
+                tx,
+            }
+        }
+
+        fn commit(&self, branch: &str, writes: &[(&str, u64, &[u8])]) {
+            let tx_id = self.tx.begin(Some(branch)).unwrap();
+            for (table, version, data) in writes {
+                let hash = self.store.put(data).unwrap();
+                self.tx.add_write(tx_id, TableWrite::new(*table, *version, vec![hash])).unwrap();
+            }
+            self.tx.commit(tx_id).unwrap();
+            self.tx.clear_recent_committed().unwrap();
+        }
+
+        fn as_ref(&self) -> RepoRef<'_> {
+            RepoRef::new(&self.catalog, &self.store).with_branches(&self.branches)
+        }
+    }
+
+    struct Target {
+        _dir: TempDir,
+        store: ChunkStore,
+        catalog: FileCatalog,
+        branches: BranchManager,
+    }
+
+    impl Target {
+        fn new() -> Self {
+            let dir = TempDir::new().unwrap();
+            Self {
+                store: ChunkStore::new(dir.path().join("chunks")).unwrap(),
+                catalog: FileCatalog::new(dir.path().join("catalog")).unwrap(),
+                branches: BranchManager::new(dir.path().join("branches")).unwrap(),
+                _dir: dir,
+            }
+        }
+
+        fn as_ref(&self) -> RepoRef<'_> {
+            RepoRef::new(&self.catalog, &self.store).with_branches(&self.branches)
+        }
+    }
+
+    fn populated_source() -> Source {
+        let source = Source::new();
+        source.commit("main", &[("users", 1, b"u1"), ("orders", 1, b"o1")]);
+        source.branches.create("feature", Some("main"), None).unwrap();
+        source.commit("feature", &[("users", 2, b"u2")]);
+        source.commit("main", &[("orders", 2, b"o2")]);
+        source
+    }
+
+    #[test]
+    fn test_full_replay_reproduces_source() {
+        let source = populated_source();
+        let target = Target::new();
+        let entries = source.tx.get_changelog(ChangelogQuery::new()).unwrap();
+
+        let report = ChangelogReplay::new(source.as_ref())
+            .verify_heads(true)
+            .run(&entries, target.as_ref())
+            .unwrap();
+
+        assert_eq!(report.entries_applied, 3);
+        assert_eq!(report.versions_created, 4);
+        assert_eq!(report.chunks_copied, 4);
+        assert_eq!((report.first_tx_id, report.last_tx_id), (Some(1), Some(3)));
+
+        for table in ["users", "orders"] {
+            for version in source.catalog.list_versions(table).unwrap() {
+                let original = source.catalog.get_version(table, Some(version)).unwrap();
+                let replayed = target.catalog.get_version(table, Some(version)).unwrap();
+                assert_eq!(replayed.chunk_hashes, original.chunk_hashes);
+                assert_eq!(replayed.created_at, original.created_at);
+                for hash in &replayed.chunk_hashes {
+                    assert!(target.store.exists(hash).unwrap());
+                }
+            }
+        }
+        assert_eq!(target.branches.get("feature").unwrap().head["users"], 2);
+        assert_eq!(target.branches.get("main").unwrap().head["orders"], 2);
+    }
+
+    #[test]
+    fn test_replay_in_ranges_is_resumable() {
+        let source = populated_source();
+        let target = Target::new();
+        let replay = ChangelogReplay::new(source.as_ref());
+
+        let first = source.tx.get_changelog(ChangelogQuery::new().until_tx(2)).unwrap();
+        assert_eq!(replay.run(&first, target.as_ref()).unwrap().last_tx_id, Some(2));
+
+        let rest = source.tx.get_changelog(ChangelogQuery::new().since_tx(2)).unwrap();
+        replay.verify_heads(true).run(&rest, target.as_ref()).unwrap();
+        assert_eq!(target.catalog.list_versions("orders").unwrap(), vec![1, 2]);
+    }
+
+    #[test]
+    fn test_replay_rejects_gap() {
+        let source = populated_source();
+        let target = Target::new();
+        let entries = source.tx.get_changelog(ChangelogQuery::new().since_tx(1)).unwrap();
+
+        let result = ChangelogReplay::new(source.as_ref()).run(&entries, target.as_ref());
+        assert!(matches!(
+            result,
+            Err(ReplayError::OutOfOrder { tx_id: 2, expected: 2, found: 0, .. })
+        ));
+        assert!(target.catalog.list_tables().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_replay_detects_content_mismatch() {
+        let source = populated_source();
+        let target = Target::new();
+        let mut entries = source.tx.get_changelog(ChangelogQuery::new()).unwrap();
+        entries[0].changes[0].chunk_hashes = vec![source.store.put(b"tampered").unwrap()];
+
+        let result = ChangelogReplay::new(source.as_ref()).run(&entries, target.as_ref());
+        assert!(matches!(result, Err(ReplayError::ContentMismatch(_, 1))));
+    }
+
+    #[test]
+    fn test_replay_applies_drop_and_rename() {
+        let source = populated_source();
+        source.tx.rename_table("users", "customers").unwrap();
+        source.tx.drop_table("orders").unwrap();
+
+        let entries = source.tx.get_changelog(ChangelogQuery::new()).unwrap();
+        let rename = &entries[3].changes[0];
+        assert_eq!(rename.renamed_to(), Some("customers"));
+        assert_eq!((rename.old_version, rename.new_version), (Some(2), 2));
+        let drop = &entries[4].changes[0];
+        assert!(drop.is_drop() && drop.chunk_hashes.is_empty());
+        assert_eq!(drop.table_name, "orders");
+
+        let target = Target::new();
+        let report = ChangelogReplay::new(source.as_ref())
+            .verify_heads(true)
+            .run(&entries, target.as_ref())
+            .unwrap();
+        assert_eq!((report.tables_renamed, report.tables_dropped), (1, 1));
+        assert_eq!(target.catalog.list_tables().unwrap(), vec!["customers"]);
+        assert_eq!(target.catalog.list_versions("customers").unwrap(), vec![1, 2]);
+        assert_eq!(
+            target.branches.get("feature").unwrap().head,
+            HashMap::from([("customers".to_string(), 2)])
+        );
+        assert_eq!(
+            target.branches.get("main").unwrap().head,
+            HashMap::from([("customers".to_string(), 1)])
+        );
+    }
+
+    #[test]
+    fn test_replay_applies_merge() {
+        let source = populated_source();
+        source.tx.merge("feature", "main", MergeStrategy::Strict).unwrap();
+
+        let entries = source.tx.get_changelog(ChangelogQuery::new()).unwrap();
+        let merge = &entries[3];
+        assert!(merge.is_branch_merge());
+        assert_eq!(merge.changes[0].merged_from(), Some("feature"));
+        assert_eq!((merge.changes[0].old_version, merge.changes[0].new_version), (Some(1), 2));
+
+        let target = Target::new();
+        let report = ChangelogReplay::new(source.as_ref())
+            .verify_heads(true)
+            .run(&entries, target.as_ref())
+            .unwrap();
+        assert_eq!(report.versions_created, 4);
+        assert_eq!(
+            target.branches.get("main").unwrap().head,
+            HashMap::from([("users".to_string(), 2), ("orders".to_string(), 2)])
+        );
+    }
+}
+
+mod cursor_tests {
+    use super::*;
+    use crate::catalog::FileCatalog;
+    use crate::transaction::{TransactionError, TransactionManager};
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    fn manager(dir: &TempDir) -> TransactionManager {
+        let catalog = Arc::new(FileCatalog::new(dir.path().join("catalog")).unwrap());
+        TransactionManager::new(dir.path(), catalog, None).unwrap()
+    }
+
+    fn commit(manager: &TransactionManager, version: u64) -> u64 {
+        let tx = manager.begin(None).unwrap();
+        manager.add_write(tx, TableWrite::new("users", version, vec![format!("c{}", version)])).unwrap();
+        manager.commit(tx).unwrap();
+        tx
+    }
+
+    #[test]
+    fn test_cursors_resume_independently_after_restart() {
+        let dir = TempDir::new().unwrap();
+        let tm = manager(&dir);
+        let tx1 = commit(&tm, 1);
+        let tx2 = commit(&tm, 2);
+
+        let mut indexer = tm.create_cursor("search-indexer").unwrap();
+        let mut audit = tm.create_cursor("audit/eu").unwrap();
+        assert!(matches!(tm.create_cursor("audit/eu"), Err(TransactionError::CursorAlreadyExists(_))));
+        assert!(matches!(tm.create_cursor("_bad"), Err(TransactionError::InvalidCursorName(_))));
+
+        let pending = tm.get_changelog(indexer.query()).unwrap();
+        assert_eq!(pending.iter().map(|e| e.tx_id).collect::<Vec<_>>(), vec![tx1, tx2]);
+        indexer.ack(tx2).unwrap();
+        indexer.ack(tx1).unwrap(); // redelivery does not move the cursor back
+        audit.ack(tx1).unwrap();
+        let tx3 = commit(&tm, 3);
+        drop(tm);
+
+        let tm = manager(&dir);
+        let indexer = tm.resume("search-indexer").unwrap();
+        assert_eq!(indexer.acked_tx_id, tx2);
+        let unseen = |cursor: &ChangelogCursor| {
+            tm.get_changelog(cursor.query()).unwrap().iter().map(|e| e.tx_id).collect::<Vec<_>>()
+        };
+        assert_eq!(unseen(&indexer), vec![tx3]);
+        assert_eq!(unseen(&tm.resume("audit/eu").unwrap()), vec![tx2, tx3]);
+
+        let names: Vec<_> = tm.list_cursors().unwrap().into_iter().map(|c| c.name).collect();
+        assert_eq!(names, vec!["audit/eu", "search-indexer"]);
+        tm.delete_cursor("audit/eu").unwrap();
+        assert!(matches!(tm.resume("audit/eu"), Err(TransactionError::CursorNotFound(_))));
+    }
+}
+
+mod cdc_tests {
+    use super::*;
+    use crate::transaction::WriteKind;
+
+    struct Collect(Vec<CdcRecord>, bool);
+
+    impl CdcSink for Collect {
+        fn send(&mut self, record: &CdcRecord) -> Result<(), CdcError> {
+            self.0.push(record.clone());
+            Ok(())
+        }
+
+        fn flush(&mut self) -> Result<(), CdcError> {
+            self.1 = true;
+            Ok(())
+        }
+    }
+
+    fn entries() -> Vec<ChangelogEntry> {
+        let mut second = ChangelogEntry::new(2, 1, 1_700_000_100, "main");
+        second.add_change(TableChange::new("users", Some(1), 2, vec!["u2".to_string()]));
+        let mut dropped = TableChange::new("orders", Some(1), 1, vec![]);
+        dropped.kind = WriteKind::Drop;
+        second.add_change(dropped);
+        let mut renamed = TableChange::new("events", Some(3), 3, vec![]);
+        renamed.kind = WriteKind::Rename { to: "audit".to_string() };
+        second.add_change(renamed.with_branch("dev"));
+
+        let mut first = ChangelogEntry::new(1, 1, 1_700_000_000, "main");
+        first.add_change(TableChange::new("users", None, 1, vec!["u1".to_string()]));
+        vec![second, first]
+    }
+
+    #[test]
+    fn test_export_emits_debezium_envelopes_in_tx_order() {
+        let mut sink = Collect(Vec::new(), false);
+        let report = CdcExporter::new("warehouse").export(&entries(), &mut sink).unwrap();
+        assert_eq!((report.entries, report.records, report.last_tx_id), (2, 5, Some(2)));
+        assert!(sink.1);
+
+        let ops: Vec<_> = sink.0.iter().map(|r| (r.value.op, r.topic.as_str())).collect();
+        assert_eq!(
+            ops,
+            vec![
+                (CdcOp::Create, "warehouse.main.users"),
+                (CdcOp::Update, "warehouse.main.users"),
+                (CdcOp::Delete, "warehouse.main.orders"),
+                (CdcOp::Delete, "warehouse.dev.events"),
+                (CdcOp::Create, "warehouse.dev.audit"),
+            ]
+        );
+
+        let update = &sink.0[1];
+        assert_eq!(update.key, serde_json::json!({"table": "users"}));
+        assert_eq!(update.value.before.as_ref().map(|v| v.version), Some(1));
+        assert_eq!(update.value.after.as_ref().unwrap().chunk_hashes, Some(vec!["u2".to_string()]));
+        assert_eq!(update.value.source.ts_ms, 1_700_000_100_000);
+        assert_eq!(update.value.source.connector, CDC_CONNECTOR);
+        assert_eq!(sink.0[4].value.transaction.total_order, 4);
+
+        let json = serde_json::to_value(&update.value).unwrap();
+        assert_eq!(json["op"], "u");
+        assert_eq!(json["source"]["name"], "warehouse");
+        assert_eq!(json["transaction"]["id"], "2");
+        assert!(json["before"].get("chunk_hashes").is_none());
+    }
+
+    #[test]
+    fn test_json_lines_sink_writes_one_envelope_per_line() {
+        let mut sink = JsonLinesSink::new(Vec::new());
+        CdcExporter::new("warehouse").export(&entries(), &mut sink).unwrap();
+        let out = String::from_utf8(sink.into_inner()).unwrap();
+        let envelopes: Vec<CdcEnvelope> = out.lines().map(|l| serde_json::from_str(l).unwrap()).collect();
+        assert_eq!(envelopes.len(), 5);
+        assert_eq!(envelopes[2].before.as_ref().unwrap().table, "orders");
+        assert!(envelopes[2].after.is_none());
+    }
+}
@@ -0,0 +1,248 @@
+//! Pluggable storage for chunk bytes.
+//!
+//! By default a `ChunkStore` keeps chunks in a local directory. With
+//! [`ChunkStore::with_backend`](super::ChunkStore::with_backend) the
+//! authoritative copy lives in a [`ChunkBackend`] (object storage, usually)
+//! and the local directory becomes a bounded cache of hot chunks: writes go
+//! through to the backend, reads are served from the cache and fetched on a
+//! miss, and the least recently used chunks are evicted past the capacity.
+//!
+//! Backends with object tags can also label each chunk with the table,
+//! version and transaction that committed it ([`ChunkTags`]), so bucket
+//! lifecycle rules and cost reports can be scoped per table without
+//! consulting the catalog.
+
+use std::collections::{BTreeMap, HashMap};
+use std::io::Write;
+use std::path::Path;
+use std::time::SystemTime;
+
+use parking_lot::Mutex;
+
+use super::error::ChunkStoreError;
+
+/// Size and age of a stored chunk
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChunkStat {
+    /// Stored size in bytes (after pipeline stages)
+    pub size: u64,
+    /// Last write, if the backend reports it
+    pub modified: Option<SystemTime>,
+}
+
+/// Tag key naming the table a chunk was committed to
+pub const TABLE_TAG: &str = "rhizo-table";
+
+/// Tag key holding the version a chunk was committed in
+pub const VERSION_TAG: &str = "rhizo-version";
+
+/// Tag key holding the transaction that committed a chunk
+pub const TX_TAG: &str = "rhizo-tx";
+
+/// What committed a chunk, attached to remote objects as tags.
+///
+/// Chunks are deduplicated, so a chunk shared by several versions carries
+/// the tags of the last commit that tagged it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChunkTags {
+    pub table_name: String,
+    pub version: u64,
+    /// Transaction that committed the version, if it went through one
+    pub tx_id: Option<u64>,
+}
+
+impl ChunkTags {
+    pub fn new(table_name: impl Into<String>, version: u64) -> Self {
+        Self {
+            table_name: table_name.into(),
+            version,
+            tx_id: None,
+        }
+    }
+
+    pub fn with_tx_id(mut self, tx_id: u64) -> Self {
+        self.tx_id = Some(tx_id);
+        self
+    }
+
+    /// (key, value) pairs, in the order they are sent
+    pub fn pairs(&self) -> Vec<(&'static str, String)> {
+        let mut pairs = vec![
+            (TABLE_TAG, self.table_name.clone()),
+            (VERSION_TAG, self.version.to_string()),
+        ];
+        if let Some(tx_id) = self.tx_id {
+            pairs.push((TX_TAG, tx_id.to_string()));
+        }
+        pairs
+    }
+}
+
+/// Remote storage for content-addressed chunks.
+///
+/// Keys are chunk hashes; values are the stored bytes (after any
+/// [`ChunkPipeline`](super::ChunkPipeline) stages). Chunks are immutable,
+/// so `put` of an existing hash may be skipped or overwrite identically.
+pub trait ChunkBackend: Send + Sync {
+    /// Short identifier used in errors and logs (e.g. "s3")
+    fn name(&self) -> &str;
+
+    fn put(&self, hash: &str, data: &[u8]) -> Result<(), ChunkStoreError>;
+
+    /// Upload a chunk from a file. The default reads the file into memory;
+    /// backends that can stream uploads should override it.
+    fn put_file(&self, hash: &str, path: &Path) -> Result<(), ChunkStoreError> {
+        self.put(hash, &std::fs::read(path)?)
+    }
+
+    /// Fetch a chunk. Missing chunks are [`ChunkStoreError::NotFound`].
+    fn get(&self, hash: &str) -> Result<Vec<u8>, ChunkStoreError>;
+
+    /// Fetch a chunk into `out`, returning its stored size. The default
+    /// buffers the whole chunk; backends that can stream downloads should
+    /// override it.
+    fn get_to(&self, hash: &str, out: &mut dyn Write) -> Result<u64, ChunkStoreError> {
+        let data = self.get(hash)?;
+        out.write_all(&data)?;
+        Ok(data.len() as u64)
+    }
+
+    fn exists(&self, hash: &str) -> Result<bool, ChunkStoreError>;
+
+    /// Size and modification time of a chunk. The default fetches the
+    /// whole chunk and reports no modification time; backends with object
+    /// metadata should override it.
+    fn stat(&self, hash: &str) -> Result<ChunkStat, ChunkStoreError> {
+        Ok(ChunkStat {
+            size: self.get(hash)?.len() as u64,
+            modified: None,
+        })
+    }
+
+    /// Delete a chunk. Deleting a missing chunk is not an error.
+    fn delete(&self, hash: &str) -> Result<(), ChunkStoreError>;
+
+    /// Every stored chunk hash, in no particular order
+    fn list(&self) -> Result<Vec<String>, ChunkStoreError>;
+
+    /// Replace the tags of a stored chunk. The default ignores them, for
+    /// backends without object tags.
+    fn set_tags(&self, _hash: &str, _tags: &ChunkTags) -> Result<(), ChunkStoreError> {
+        Ok(())
+    }
+}
+
+#[derive(Default)]
+struct CacheState {
+    /// hash -> (size, last access tick)
+    entries: HashMap<String, (u64, u64)>,
+    /// last access tick -> hash, oldest first
+    order: BTreeMap<u64, String>,
+    bytes: u64,
+    tick: u64,
+}
+
+/// Byte-bounded LRU index over the chunks cached on local disk.
+///
+/// Only tracks sizes and recency; callers delete the evicted files.
+pub(crate) struct ChunkCache {
+    capacity: u64,
+    state: Mutex<CacheState>,
+}
+
+impl ChunkCache {
+    pub(crate) fn new(capacity: u64) -> Self {
+        Self {
+            capacity,
+            state: Mutex::new(CacheState::default()),
+        }
+    }
+
+    /// Bytes currently cached
+    pub(crate) fn bytes(&self) -> u64 {
+        self.state.lock().bytes
+    }
+
+    /// Record an access; returns false if the chunk is not cached
+    pub(crate) fn touch(&self, hash: &str) -> bool {
+        let mut state = self.state.lock();
+        state.tick += 1;
+        let tick = state.tick;
+        let Some(entry) = state.entries.get_mut(hash) else {
+            return false;
+        };
+        let old = std::mem::replace(&mut entry.1, tick);
+        state.order.remove(&old);
+        state.order.insert(tick, hash.to_string());
+        true
+    }
+
+    /// Record a newly cached chunk; returns the chunks to evict.
+    ///
+    /// The chunk just inserted is never evicted, even if it alone exceeds
+    /// the capacity, so the caller can still read it.
+    pub(crate) fn insert(&self, hash: &str, size: u64) -> Vec<String> {
+        if self.touch(hash) {
+            return Vec::new();
+        }
+
+        let mut state = self.state.lock();
+        state.tick += 1;
+        let tick = state.tick;
+        state.entries.insert(hash.to_string(), (size, tick));
+        state.order.insert(tick, hash.to_string());
+        state.bytes += size;
+
+        let mut evicted = Vec::new();
+        while state.bytes > self.capacity {
+            let Some((&oldest, _)) = state.order.iter().next() else { break };
+            if oldest == tick {
+                break;
+            }
+            let victim = state.order.remove(&oldest).unwrap_or_default();
+            if let Some((victim_size, _)) = state.entries.remove(&victim) {
+                state.bytes -= victim_size;
+            }
+            evicted.push(victim);
+        }
+        evicted
+    }
+
+    pub(crate) fn remove(&self, hash: &str) {
+        let mut state = self.state.lock();
+        if let Some((size, tick)) = state.entries.remove(hash) {
+            state.order.remove(&tick);
+            state.bytes -= size;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cache_evicts_least_recently_used() {
+        let cache = ChunkCache::new(100);
+        assert!(cache.insert("a", 40).is_empty());
+        assert!(cache.insert("b", 40).is_empty());
+        assert!(cache.touch("a"));
+
+        // "b" is now the least recently used
+        assert_eq!(cache.insert("c", 40), vec!["b"]);
+        assert_eq!(cache.bytes(), 80);
+        assert!(!cache.touch("b"));
+
+        cache.remove("a");
+        assert_eq!(cache.bytes(), 40);
+    }
+
+    #[test]
+    fn test_cache_keeps_oversized_newest_entry() {
+        let cache = ChunkCache::new(10);
+        cache.insert("small", 5);
+        assert_eq!(cache.insert("huge", 50), vec!["small"]);
+        assert!(cache.touch("huge"));
+        assert_eq!(cache.bytes(), 50);
+    }
+}
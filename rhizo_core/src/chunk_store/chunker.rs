@@ -0,0 +1,299 @@
+//! Splitting byte streams into chunks before they are stored.
+//!
+//! Fixed-size chunking cuts every `size` bytes, so inserting one byte near
+//! the start of a file shifts every later boundary and no chunk of the new
+//! version matches the old one. Content-defined chunking (FastCDC) cuts
+//! where a rolling Gear hash of the last bytes matches a mask, so
+//! boundaries move with the content and the chunks after an edit
+//! deduplicate against the previous version.
+//!
+//! Boundaries are a pure function of the bytes and the chunker's sizes:
+//! the Gear table is generated from a fixed seed and must never change, or
+//! stores would stop deduplicating against chunks they already hold.
+
+use std::io::{self, Read};
+
+/// Default average chunk size of content-defined chunking
+pub const DEFAULT_AVG_CHUNK_SIZE: usize = 1024 * 1024;
+
+/// Smallest average size content-defined chunking accepts
+const MIN_AVG_CHUNK_SIZE: usize = 64;
+
+/// Gear hash value of each byte, from splitmix64 with a fixed seed
+const GEAR: [u64; 256] = gear_table();
+
+const fn gear_table() -> [u64; 256] {
+    let mut table = [0u64; 256];
+    let mut state: u64 = 0x5248_495a_4f43_4443; // "RHIZOCDC"
+    let mut i = 0;
+    while i < 256 {
+        state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        table[i] = z ^ (z >> 31);
+        i += 1;
+    }
+    table
+}
+
+/// The `bits` most significant bits. The Gear hash shifts left, so its
+/// high bits depend on the most bytes.
+const fn high_bits(bits: u32) -> u64 {
+    if bits == 0 {
+        0
+    } else {
+        !0u64 << (64 - bits)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    Fixed,
+    ContentDefined {
+        /// Mask before the average size: harder to match
+        strict: u64,
+        /// Mask after the average size: easier to match
+        loose: u64,
+    },
+}
+
+/// Where to cut a byte stream into chunks.
+///
+/// # Example
+///
+/// ```
+/// use rhizo_core::Chunker;
+///
+/// let chunker = Chunker::content_defined(256, 1024, 4096);
+/// let data: Vec<u8> = (0..20_000u32).flat_map(|i| i.to_le_bytes()).collect();
+/// let chunks: Vec<&[u8]> = chunker.chunks(&data).collect();
+/// assert_eq!(chunks.concat(), data);
+/// assert!(chunks.iter().all(|c| c.len() <= 4096));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Chunker {
+    mode: Mode,
+    min: usize,
+    avg: usize,
+    max: usize,
+}
+
+impl Default for Chunker {
+    /// Content-defined, averaging [`DEFAULT_AVG_CHUNK_SIZE`]
+    fn default() -> Self {
+        Self::content_defined(DEFAULT_AVG_CHUNK_SIZE / 4, DEFAULT_AVG_CHUNK_SIZE, DEFAULT_AVG_CHUNK_SIZE * 4)
+    }
+}
+
+impl Chunker {
+    /// Cut every `size` bytes (at least 1)
+    pub fn fixed(size: usize) -> Self {
+        let size = size.max(1);
+        Self {
+            mode: Mode::Fixed,
+            min: size,
+            avg: size,
+            max: size,
+        }
+    }
+
+    /// FastCDC with chunks of `min` to `max` bytes, averaging about `avg`.
+    ///
+    /// `avg` is rounded down to a power of two (at least 64), `min` is
+    /// capped at it and `max` raised to it.
+    pub fn content_defined(min: usize, avg: usize, max: usize) -> Self {
+        let bits = avg.max(MIN_AVG_CHUNK_SIZE).ilog2();
+        let avg = 1usize << bits;
+        Self {
+            mode: Mode::ContentDefined {
+                strict: high_bits(bits + 1),
+                loose: high_bits(bits - 1),
+            },
+            min: min.min(avg),
+            avg,
+            max: max.max(avg),
+        }
+    }
+
+    /// Whether boundaries follow the content rather than fixed offsets
+    pub fn is_content_defined(&self) -> bool {
+        matches!(self.mode, Mode::ContentDefined { .. })
+    }
+
+    pub fn min_size(&self) -> usize {
+        self.min
+    }
+
+    pub fn avg_size(&self) -> usize {
+        self.avg
+    }
+
+    pub fn max_size(&self) -> usize {
+        self.max
+    }
+
+    /// Length of the first chunk of `data`. Only final when `data` holds at
+    /// least `max_size` bytes or is the end of the stream.
+    pub fn cut(&self, data: &[u8]) -> usize {
+        let (strict, loose) = match self.mode {
+            Mode::Fixed => return data.len().min(self.max),
+            Mode::ContentDefined { strict, loose } => (strict, loose),
+        };
+        if data.len() <= self.min {
+            return data.len();
+        }
+        let end = data.len().min(self.max);
+        let normal = end.min(self.avg);
+        let mut hash = 0u64;
+        for (i, &byte) in data.iter().enumerate().take(end).skip(self.min) {
+            hash = (hash << 1).wrapping_add(GEAR[byte as usize]);
+            let mask = if i < normal { strict } else { loose };
+            if hash & mask == 0 {
+                return i + 1;
+            }
+        }
+        end
+    }
+
+    /// Split bytes in memory
+    pub fn chunks<'a>(&self, data: &'a [u8]) -> impl Iterator<Item = &'a [u8]> + 'a {
+        let chunker = *self;
+        let mut rest = data;
+        std::iter::from_fn(move || {
+            if rest.is_empty() {
+                return None;
+            }
+            let (chunk, tail) = rest.split_at(chunker.cut(rest));
+            rest = tail;
+            Some(chunk)
+        })
+    }
+
+    /// Split a stream, holding at most `max_size` bytes in memory
+    pub fn split<R: Read>(&self, reader: R) -> ChunkSplitter<R> {
+        ChunkSplitter {
+            chunker: *self,
+            reader,
+            buf: Vec::new(),
+            eof: false,
+        }
+    }
+}
+
+/// Chunks of a stream, from [`Chunker::split`]
+pub struct ChunkSplitter<R> {
+    chunker: Chunker,
+    reader: R,
+    buf: Vec<u8>,
+    eof: bool,
+}
+
+impl<R: Read> ChunkSplitter<R> {
+    fn fill(&mut self) -> io::Result<()> {
+        let want = self.chunker.max;
+        while !self.eof && self.buf.len() < want {
+            let start = self.buf.len();
+            self.buf.resize(want, 0);
+            match self.reader.read(&mut self.buf[start..]) {
+                Ok(0) => {
+                    self.buf.truncate(start);
+                    self.eof = true;
+                }
+                Ok(n) => self.buf.truncate(start + n),
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => self.buf.truncate(start),
+                Err(e) => {
+                    self.buf.truncate(start);
+                    return Err(e);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<R: Read> Iterator for ChunkSplitter<R> {
+    type Item = io::Result<Vec<u8>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Err(e) = self.fill() {
+            return Some(Err(e));
+        }
+        if self.buf.is_empty() {
+            return None;
+        }
+        let cut = self.chunker.cut(&self.buf);
+        let rest = self.buf.split_off(cut);
+        Some(Ok(std::mem::replace(&mut self.buf, rest)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Deterministic pseudo-random bytes
+    fn noise(len: usize, seed: u64) -> Vec<u8> {
+        let mut state = seed;
+        (0..len)
+            .map(|_| {
+                state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+                (state >> 56) as u8
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_fixed_chunks() {
+        let data = noise(10_000, 1);
+        let chunks: Vec<&[u8]> = Chunker::fixed(4096).chunks(&data).collect();
+        assert_eq!(chunks.iter().map(|c| c.len()).collect::<Vec<_>>(), vec![4096, 4096, 1808]);
+        assert_eq!(Chunker::fixed(0).max_size(), 1);
+        assert_eq!(Chunker::fixed(8).chunks(&[]).count(), 0);
+    }
+
+    #[test]
+    fn test_content_defined_bounds_and_stream_agree() {
+        let chunker = Chunker::content_defined(512, 2000, 8192);
+        assert_eq!(chunker.avg_size(), 1024);
+        let data = noise(200_000, 2);
+
+        let chunks: Vec<&[u8]> = chunker.chunks(&data).collect();
+        assert_eq!(chunks.concat(), data);
+        let (last, rest) = chunks.split_last().unwrap();
+        assert!(rest.iter().all(|c| c.len() >= 512 && c.len() <= 8192));
+        assert!(last.len() <= 8192);
+        let avg = data.len() / chunks.len();
+        assert!(avg > 512 && avg < 4096, "average chunk {}", avg);
+
+        // Reading in odd-sized pieces does not move boundaries
+        struct Trickle<'a>(&'a [u8]);
+        impl Read for Trickle<'_> {
+            fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+                let n = buf.len().min(777).min(self.0.len());
+                buf[..n].copy_from_slice(&self.0[..n]);
+                self.0 = &self.0[n..];
+                Ok(n)
+            }
+        }
+        let streamed: Vec<Vec<u8>> = chunker.split(Trickle(&data)).collect::<io::Result<_>>().unwrap();
+        assert_eq!(streamed, chunks.iter().map(|c| c.to_vec()).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_content_defined_boundaries_survive_an_insert() {
+        let chunker = Chunker::content_defined(256, 1024, 4096);
+        let original = noise(100_000, 3);
+        let mut edited = original.clone();
+        edited.splice(10..10, b"inserted".iter().copied());
+
+        let before: std::collections::HashSet<&[u8]> = chunker.chunks(&original).collect();
+        let after: Vec<&[u8]> = chunker.chunks(&edited).collect();
+        let shared = after.iter().filter(|c| before.contains(*c)).count();
+        assert!(shared + 2 >= after.len(), "{} of {} chunks shared", shared, after.len());
+
+        let fixed = Chunker::fixed(1024);
+        let before: std::collections::HashSet<&[u8]> = fixed.chunks(&original).collect();
+        assert_eq!(fixed.chunks(&edited).filter(|c| before.contains(c)).count(), 0);
+    }
+}
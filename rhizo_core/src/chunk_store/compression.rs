@@ -0,0 +1,358 @@
+//! Transparent chunk compression.
+//!
+//! Compressed chunks start with a small header naming the codec:
+//!
+//! ```text
+//! 0x89 'R' 'Z' 'C'   magic
+//! 0x01               header version
+//! codec              0 = stored uncompressed, 1 = zstd, 2 = lz4 (frame format)
+//! payload...
+//! ```
+//!
+//! Reads follow the header, not the configured codec, so a store can
+//! switch codecs without rewriting old chunks. Chunks without the header
+//! are returned verbatim, which keeps stores written before compression
+//! existed readable. Like PNG's signature, the leading `0x89` keeps the
+//! magic out of text and Parquet/Arrow data.
+//!
+//! Only a [`CompressionStage`] reads the header: a store opened without
+//! one returns compressed chunks as stored. To stop compressing a store
+//! that already holds compressed chunks, keep a stage with
+//! [`ChunkCompression::None`], which writes new chunks uncompressed and
+//! still decodes the old ones.
+//!
+//! Streamed writes (`ChunkStore::put_stream`) always use the configured
+//! codec: falling back to codec 0 for incompressible data needs the whole
+//! chunk up front.
+
+use std::io::{self, Cursor, Read, Write};
+
+use super::chunker::Chunker;
+use super::delta::DEFAULT_MAX_DELTA_CHAIN;
+use super::error::ChunkStoreError;
+use super::pipeline::ChunkStage;
+
+const MAGIC: [u8; 4] = [0x89, b'R', b'Z', b'C'];
+const HEADER_VERSION: u8 = 1;
+const HEADER_LEN: usize = MAGIC.len() + 2;
+
+const CODEC_NONE: u8 = 0;
+const CODEC_ZSTD: u8 = 1;
+const CODEC_LZ4: u8 = 2;
+
+/// Default zstd level: a good ratio at several hundred MB/s
+pub const DEFAULT_ZSTD_LEVEL: i32 = 3;
+
+/// Codec for newly written chunks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ChunkCompression {
+    /// Store chunks verbatim
+    #[default]
+    None,
+    /// zstd at the given level (1-22)
+    Zstd { level: i32 },
+    /// LZ4 frame format: faster than zstd, lower ratio
+    Lz4,
+}
+
+impl ChunkCompression {
+    /// zstd at [`DEFAULT_ZSTD_LEVEL`]
+    pub fn zstd() -> Self {
+        ChunkCompression::Zstd { level: DEFAULT_ZSTD_LEVEL }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ChunkCompression::None => "none",
+            ChunkCompression::Zstd { .. } => "zstd",
+            ChunkCompression::Lz4 => "lz4",
+        }
+    }
+}
+
+/// Store-wide chunk settings for [`ChunkStore::with_config`](super::ChunkStore::with_config).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChunkStoreConfig {
+    pub compression: ChunkCompression,
+    /// Verify content hashes on every read, not only in `get_verified`
+    pub verify_on_read: bool,
+    /// Longest delta chain `put_delta` builds before storing a full chunk
+    /// (0 disables delta chunks)
+    pub max_delta_chain: u32,
+    /// Journal the size and CRC32 of chunk files as they are written
+    /// (see [`ChunkStore::with_integrity_journal`](super::ChunkStore::with_integrity_journal))
+    pub integrity_journal: bool,
+    /// Most chunk files held open at once (see
+    /// [`ChunkStore::with_max_open_files`](super::ChunkStore::with_max_open_files));
+    /// `None` for no limit
+    pub max_open_files: Option<usize>,
+    /// How `put_chunked` splits streams (see
+    /// [`ChunkStore::with_chunker`](super::ChunkStore::with_chunker))
+    pub chunker: Chunker,
+    /// Bytes of recently read chunks kept in memory (see
+    /// [`ChunkStore::with_memory_cache`](super::ChunkStore::with_memory_cache));
+    /// 0 disables the cache
+    pub memory_cache_bytes: u64,
+}
+
+impl Default for ChunkStoreConfig {
+    fn default() -> Self {
+        Self {
+            compression: ChunkCompression::default(),
+            verify_on_read: false,
+            max_delta_chain: DEFAULT_MAX_DELTA_CHAIN,
+            integrity_journal: false,
+            max_open_files: None,
+            chunker: Chunker::default(),
+            memory_cache_bytes: 0,
+        }
+    }
+}
+
+impl ChunkStoreConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_compression(mut self, compression: ChunkCompression) -> Self {
+        self.compression = compression;
+        self
+    }
+
+    pub fn with_verify_on_read(mut self, verify: bool) -> Self {
+        self.verify_on_read = verify;
+        self
+    }
+
+    pub fn with_max_delta_chain(mut self, max_delta_chain: u32) -> Self {
+        self.max_delta_chain = max_delta_chain;
+        self
+    }
+
+    pub fn with_integrity_journal(mut self, enabled: bool) -> Self {
+        self.integrity_journal = enabled;
+        self
+    }
+
+    pub fn with_max_open_files(mut self, limit: Option<usize>) -> Self {
+        self.max_open_files = limit;
+        self
+    }
+
+    pub fn with_chunker(mut self, chunker: Chunker) -> Self {
+        self.chunker = chunker;
+        self
+    }
+
+    pub fn with_memory_cache(mut self, bytes: u64) -> Self {
+        self.memory_cache_bytes = bytes;
+        self
+    }
+}
+
+/// Pipeline stage that compresses chunks behind a codec header.
+///
+/// [`ChunkStoreConfig`] adds it automatically; add it to a
+/// [`ChunkPipeline`](super::ChunkPipeline) directly to combine compression
+/// with other stages (compress before encrypting).
+#[derive(Debug, Clone, Copy)]
+pub struct CompressionStage {
+    compression: ChunkCompression,
+}
+
+impl CompressionStage {
+    pub fn new(compression: ChunkCompression) -> Self {
+        Self { compression }
+    }
+
+    fn compress(&self, data: &[u8]) -> Result<Option<(u8, Vec<u8>)>, ChunkStoreError> {
+        let pipeline_err = |e: io::Error| ChunkStoreError::Pipeline(format!("{}: {}", self.name(), e));
+        Ok(match self.compression {
+            ChunkCompression::None => None,
+            ChunkCompression::Zstd { level } => {
+                Some((CODEC_ZSTD, zstd::bulk::compress(data, level).map_err(pipeline_err)?))
+            }
+            ChunkCompression::Lz4 => {
+                let mut encoder = lz4_flex::frame::FrameEncoder::new(Vec::with_capacity(data.len() / 2));
+                io::Write::write_all(&mut encoder, data).map_err(pipeline_err)?;
+                let compressed = encoder
+                    .finish()
+                    .map_err(|e| ChunkStoreError::Pipeline(format!("{}: {}", self.name(), e)))?;
+                Some((CODEC_LZ4, compressed))
+            }
+        })
+    }
+}
+
+impl ChunkStage for CompressionStage {
+    fn name(&self) -> &str {
+        self.compression.name()
+    }
+
+    fn encode(&self, data: Vec<u8>) -> Result<Vec<u8>, ChunkStoreError> {
+        let (codec, payload) = match self.compress(&data)? {
+            Some((codec, compressed)) if compressed.len() < data.len() => (codec, compressed),
+            // Incompressible (already-compressed Parquet pages, random bytes)
+            _ => (CODEC_NONE, data),
+        };
+        let mut out = Vec::with_capacity(HEADER_LEN + payload.len());
+        out.extend_from_slice(&MAGIC);
+        out.push(HEADER_VERSION);
+        out.push(codec);
+        out.extend_from_slice(&payload);
+        Ok(out)
+    }
+
+    fn encode_stream(&self, input: &mut dyn Read, output: &mut dyn Write) -> Result<(), ChunkStoreError> {
+        let pipeline_err = |e: io::Error| ChunkStoreError::Pipeline(format!("{}: {}", self.name(), e));
+        let codec = match self.compression {
+            ChunkCompression::None => CODEC_NONE,
+            ChunkCompression::Zstd { .. } => CODEC_ZSTD,
+            ChunkCompression::Lz4 => CODEC_LZ4,
+        };
+        output.write_all(&MAGIC)?;
+        output.write_all(&[HEADER_VERSION, codec])?;
+        match self.compression {
+            ChunkCompression::None => {
+                io::copy(input, output)?;
+            }
+            ChunkCompression::Zstd { level } => {
+                let mut encoder = zstd::stream::write::Encoder::new(output, level).map_err(pipeline_err)?;
+                io::copy(input, &mut encoder).map_err(pipeline_err)?;
+                encoder.finish().map_err(pipeline_err)?;
+            }
+            ChunkCompression::Lz4 => {
+                let mut encoder = lz4_flex::frame::FrameEncoder::new(output);
+                io::copy(input, &mut encoder).map_err(pipeline_err)?;
+                encoder
+                    .finish()
+                    .map_err(|e| ChunkStoreError::Pipeline(format!("{}: {}", self.name(), e)))?;
+            }
+        }
+        Ok(())
+    }
+
+    fn decoder<'a>(&'a self, mut stored: Box<dyn Read + 'a>) -> Result<Box<dyn Read + 'a>, ChunkStoreError> {
+        let mut header = Vec::with_capacity(HEADER_LEN);
+        (&mut stored).take(HEADER_LEN as u64).read_to_end(&mut header)?;
+        if header.len() < HEADER_LEN || header[..MAGIC.len()] != MAGIC {
+            // Written without compression: hand back the bytes already read
+            return Ok(Box::new(Cursor::new(header).chain(stored)));
+        }
+
+        let (version, codec) = (header[MAGIC.len()], header[MAGIC.len() + 1]);
+        if version != HEADER_VERSION {
+            return Err(ChunkStoreError::Pipeline(format!(
+                "unsupported compression header version {}",
+                version
+            )));
+        }
+        match codec {
+            CODEC_NONE => Ok(stored),
+            CODEC_ZSTD => Ok(Box::new(zstd::stream::read::Decoder::new(stored)?)),
+            CODEC_LZ4 => Ok(Box::new(lz4_flex::frame::FrameDecoder::new(stored))),
+            other => Err(ChunkStoreError::Pipeline(format!("unknown compression codec {}", other))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chunk_store::{ChunkPipeline, ChunkStore};
+    use std::sync::Arc;
+
+    fn compressible() -> Vec<u8> {
+        (0..100_000u32).flat_map(|i| (i % 100).to_le_bytes()).collect()
+    }
+
+    fn roundtrip(compression: ChunkCompression, data: &[u8]) -> (Vec<u8>, Vec<u8>) {
+        let pipeline = ChunkPipeline::new().with_stage(Arc::new(CompressionStage::new(compression)));
+        let stored = pipeline.encode(data).unwrap();
+        let hash = blake3::hash(data).to_hex().to_string();
+        let decoded = pipeline.decode(&stored[..], Some(&hash), data.len()).unwrap();
+        (stored, decoded)
+    }
+
+    #[test]
+    fn test_codecs_roundtrip_with_header() {
+        let data = compressible();
+        for (compression, codec) in [(ChunkCompression::zstd(), CODEC_ZSTD), (ChunkCompression::Lz4, CODEC_LZ4)] {
+            let (stored, decoded) = roundtrip(compression, &data);
+            assert_eq!(decoded, data);
+            assert_eq!(&stored[..4], &MAGIC);
+            assert_eq!(stored[5], codec);
+            assert!(stored.len() < data.len() / 4, "{} did not compress", compression.name());
+        }
+    }
+
+    #[test]
+    fn test_incompressible_data_stored_raw() {
+        let data: Vec<u8> = (0..64u32).flat_map(|i| blake3::hash(&i.to_le_bytes()).as_bytes().to_vec()).collect();
+        let (stored, decoded) = roundtrip(ChunkCompression::zstd(), &data);
+        assert_eq!(decoded, data);
+        assert_eq!(stored[5], CODEC_NONE);
+        assert_eq!(stored.len(), data.len() + HEADER_LEN);
+    }
+
+    #[test]
+    fn test_store_reads_chunks_from_before_compression() {
+        let dir = std::env::temp_dir().join(format!("rhizo_compression_test_{}", uuid::Uuid::new_v4()));
+        let data = compressible();
+
+        let plain = ChunkStore::new(&dir).unwrap();
+        let old = plain.put(b"written before compression").unwrap();
+        let short = plain.put(b"abc").unwrap();
+
+        let zstd = ChunkStore::with_config(&dir, ChunkStoreConfig::new().with_compression(ChunkCompression::zstd()))
+            .unwrap();
+        let new = zstd.put(&data).unwrap();
+        assert_eq!(zstd.get(&old).unwrap(), b"written before compression");
+        assert_eq!(zstd.get_verified(&short).unwrap(), b"abc");
+        assert_eq!(zstd.get_verified(&new).unwrap(), data);
+        assert!(zstd.stat(&new).unwrap().size < data.len() as u64 / 4);
+
+        // Switching codecs keeps zstd chunks readable
+        let lz4 = ChunkStore::with_config(&dir, ChunkStoreConfig::new().with_compression(ChunkCompression::Lz4))
+            .unwrap();
+        assert_eq!(lz4.get(&new).unwrap(), data);
+
+        // Without a compression stage the header is not decoded; a stage
+        // with no codec stops compressing and still reads old chunks
+        assert_eq!(&plain.get(&new).unwrap()[..4], &MAGIC);
+        let off = ChunkStore::new(&dir)
+            .unwrap()
+            .with_pipeline(ChunkPipeline::new().with_stage(Arc::new(CompressionStage::new(ChunkCompression::None))));
+        assert_eq!(off.get_verified(&new).unwrap(), data);
+        let more = [&data[..], b"more"].concat();
+        let raw = off.put(&more).unwrap();
+        assert_eq!(off.get_verified(&raw).unwrap(), more);
+        assert_eq!(off.stat(&raw).unwrap().size, (more.len() + HEADER_LEN) as u64);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_streamed_encoding_decodes_like_bulk() {
+        let data = compressible();
+        for compression in [ChunkCompression::zstd(), ChunkCompression::Lz4, ChunkCompression::None] {
+            let stage = CompressionStage::new(compression);
+            let mut stored = Vec::new();
+            stage.encode_stream(&mut &data[..], &mut stored).unwrap();
+            assert_eq!(&stored[..4], &MAGIC);
+
+            let pipeline = ChunkPipeline::new().with_stage(Arc::new(stage));
+            let hash = blake3::hash(&data).to_hex().to_string();
+            assert_eq!(pipeline.decode(&stored[..], Some(&hash), 0).unwrap(), data);
+        }
+    }
+
+    #[test]
+    fn test_unknown_codec_is_pipeline_error() {
+        let stage = CompressionStage::new(ChunkCompression::Lz4);
+        let mut stored = MAGIC.to_vec();
+        stored.extend_from_slice(&[HEADER_VERSION, 9, 0, 0]);
+        let pipeline = ChunkPipeline::new().with_stage(Arc::new(stage));
+        assert!(matches!(pipeline.decode(&stored[..], None, 0), Err(ChunkStoreError::Pipeline(_))));
+    }
+}
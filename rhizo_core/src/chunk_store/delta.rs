@@ -0,0 +1,282 @@
+//! Binary delta encoding for chunks that differ slightly from a base chunk.
+//!
+//! A delta chunk is stored as a fixed header naming its base chunk and its
+//! depth in the delta chain, followed by a list of copy/insert operations
+//! (passed through the store's pipeline like any chunk). Operations are
+//! found by indexing the base in fixed-size blocks and matching them in the
+//! target with a rolling hash, extending each match in both directions.
+//!
+//! Deltas never change content addressing: a delta chunk is named by the
+//! hash of its reconstructed bytes, which is checked on every reconstruction.
+
+use std::collections::HashMap;
+use std::io::Read;
+
+use super::error::ChunkStoreError;
+
+/// Default longest delta chain before a chunk is stored in full
+pub const DEFAULT_MAX_DELTA_CHAIN: u32 = 8;
+
+/// Marks a delta chunk file
+const MAGIC: &[u8; 4] = b"RZD\x01";
+
+/// Base hash (64 hex characters) plus chain depth
+pub(crate) const HEADER_LEN: usize = MAGIC.len() + 64 + 4;
+
+/// Matching granularity: shorter matches are stored as literals
+const BLOCK: usize = 32;
+
+/// Multiplier of the rolling polynomial hash
+const PRIME: u64 = 0x0100_0000_01b3;
+
+const OP_COPY: u8 = 1;
+const OP_INSERT: u8 = 2;
+
+/// Where a delta chunk sits in its chain.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeltaInfo {
+    /// Chunk the delta applies to (itself a full chunk or another delta)
+    pub base: String,
+    /// Number of deltas to apply to reach this chunk from a full chunk
+    pub depth: u32,
+}
+
+impl DeltaInfo {
+    pub(crate) fn header(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(HEADER_LEN);
+        out.extend_from_slice(MAGIC);
+        out.extend_from_slice(self.base.as_bytes());
+        out.extend_from_slice(&self.depth.to_le_bytes());
+        out
+    }
+
+    pub(crate) fn read_header(reader: &mut impl Read) -> Result<Self, ChunkStoreError> {
+        let mut header = [0u8; HEADER_LEN];
+        reader.read_exact(&mut header)?;
+        if &header[..MAGIC.len()] != MAGIC {
+            return Err(ChunkStoreError::CorruptDelta("bad magic".to_string()));
+        }
+        let base = std::str::from_utf8(&header[MAGIC.len()..MAGIC.len() + 64])
+            .ok()
+            .filter(|b| b.chars().all(|c| c.is_ascii_hexdigit()))
+            .ok_or_else(|| ChunkStoreError::CorruptDelta("bad base hash".to_string()))?;
+        let depth = u32::from_le_bytes(header[HEADER_LEN - 4..].try_into().expect("4 bytes"));
+        Ok(Self {
+            base: base.to_string(),
+            depth,
+        })
+    }
+}
+
+/// Operations turning `base` into `target`.
+pub(crate) fn diff(base: &[u8], target: &[u8]) -> Vec<u8> {
+    let mut out = Vec::new();
+    put_varint(&mut out, target.len() as u64);
+
+    // First occurrence of each aligned base block
+    let mut index: HashMap<u64, usize> = HashMap::new();
+    for offset in (0..base.len().saturating_sub(BLOCK - 1)).step_by(BLOCK) {
+        index.entry(block_hash(&base[offset..offset + BLOCK])).or_insert(offset);
+    }
+    let top = PRIME.wrapping_pow(BLOCK as u32 - 1);
+
+    let mut literal_start = 0;
+    let mut i = 0;
+    let mut hash = None;
+    while i + BLOCK <= target.len() {
+        let h = *hash.get_or_insert_with(|| block_hash(&target[i..i + BLOCK]));
+        let matched = index
+            .get(&h)
+            .copied()
+            .filter(|&offset| base[offset..offset + BLOCK] == target[i..i + BLOCK]);
+
+        let Some(mut offset) = matched else {
+            if i + BLOCK < target.len() {
+                hash = Some(
+                    h.wrapping_sub(u64::from(target[i]).wrapping_mul(top))
+                        .wrapping_mul(PRIME)
+                        .wrapping_add(u64::from(target[i + BLOCK])),
+                );
+            }
+            i += 1;
+            continue;
+        };
+
+        let mut start = i;
+        while start > literal_start && offset > 0 && base[offset - 1] == target[start - 1] {
+            start -= 1;
+            offset -= 1;
+        }
+        let mut len = i + BLOCK - start;
+        while offset + len < base.len() && start + len < target.len() && base[offset + len] == target[start + len] {
+            len += 1;
+        }
+
+        put_insert(&mut out, &target[literal_start..start]);
+        out.push(OP_COPY);
+        put_varint(&mut out, offset as u64);
+        put_varint(&mut out, len as u64);
+
+        i = start + len;
+        literal_start = i;
+        hash = None;
+    }
+    put_insert(&mut out, &target[literal_start..]);
+    out
+}
+
+/// Rebuild the target from `base` and the operations produced by [`diff`].
+pub(crate) fn apply(base: &[u8], ops: &[u8]) -> Result<Vec<u8>, ChunkStoreError> {
+    let corrupt = |msg: &str| ChunkStoreError::CorruptDelta(msg.to_string());
+    let mut pos = 0;
+    let target_len = get_varint(ops, &mut pos).ok_or_else(|| corrupt("truncated length"))? as usize;
+    let mut out = Vec::with_capacity(target_len);
+
+    while pos < ops.len() {
+        let op = ops[pos];
+        pos += 1;
+        match op {
+            OP_COPY => {
+                let offset = get_varint(ops, &mut pos).ok_or_else(|| corrupt("truncated copy"))? as usize;
+                let len = get_varint(ops, &mut pos).ok_or_else(|| corrupt("truncated copy"))? as usize;
+                let range = offset
+                    .checked_add(len)
+                    .filter(|&end| end <= base.len())
+                    .map(|end| offset..end)
+                    .ok_or_else(|| corrupt("copy past the end of the base chunk"))?;
+                out.extend_from_slice(&base[range]);
+            }
+            OP_INSERT => {
+                let len = get_varint(ops, &mut pos).ok_or_else(|| corrupt("truncated insert"))? as usize;
+                let bytes = ops
+                    .get(pos..pos.saturating_add(len))
+                    .ok_or_else(|| corrupt("truncated insert"))?;
+                out.extend_from_slice(bytes);
+                pos += len;
+            }
+            _ => return Err(corrupt(&format!("unknown operation {}", op))),
+        }
+        if out.len() > target_len {
+            return Err(corrupt("output longer than recorded"));
+        }
+    }
+
+    if out.len() != target_len {
+        return Err(corrupt("output shorter than recorded"));
+    }
+    Ok(out)
+}
+
+fn block_hash(block: &[u8]) -> u64 {
+    block
+        .iter()
+        .fold(0u64, |h, &b| h.wrapping_mul(PRIME).wrapping_add(u64::from(b)))
+}
+
+fn put_insert(out: &mut Vec<u8>, literal: &[u8]) {
+    if !literal.is_empty() {
+        out.push(OP_INSERT);
+        put_varint(out, literal.len() as u64);
+        out.extend_from_slice(literal);
+    }
+}
+
+/// LEB128
+fn put_varint(out: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        out.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+fn get_varint(data: &[u8], pos: &mut usize) -> Option<u64> {
+    let mut value = 0u64;
+    for shift in (0..64).step_by(7) {
+        let byte = *data.get(*pos)?;
+        *pos += 1;
+        value |= u64::from(byte & 0x7f) << shift;
+        if byte < 0x80 {
+            return Some(value);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(len: usize, seed: u64) -> Vec<u8> {
+        let mut x = seed;
+        (0..len)
+            .map(|_| {
+                x = x.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+                (x >> 56) as u8
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_small_edits_produce_small_deltas() {
+        let base = sample(200_000, 7);
+        let mut target = base.clone();
+        target[1_000..1_010].copy_from_slice(b"0123456789");
+        target.splice(50_000..50_000, b"inserted".iter().copied());
+        target.drain(120_000..120_500);
+        target.extend_from_slice(b"appended tail");
+
+        let ops = diff(&base, &target);
+        assert!(ops.len() < 200, "delta is {} bytes", ops.len());
+        assert_eq!(apply(&base, &ops).unwrap(), target);
+    }
+
+    #[test]
+    fn test_unrelated_and_degenerate_inputs() {
+        let base = sample(10_000, 1);
+        let other = sample(10_000, 2);
+        for (b, t) in [
+            (&base[..], &other[..]),
+            (&base[..], &b""[..]),
+            (&b""[..], &other[..]),
+            (&base[..], &base[..17]),
+            (&base[..5], &base[..]),
+        ] {
+            assert_eq!(apply(b, &diff(b, t)).unwrap(), t);
+        }
+        // Unrelated data is stored as one literal
+        assert!(diff(&base, &other).len() < other.len() + 16);
+    }
+
+    #[test]
+    fn test_corrupt_operations_are_rejected() {
+        let base = sample(1_000, 3);
+        let mut target = base.clone();
+        target[500] ^= 0xff;
+        let ops = diff(&base, &target);
+
+        assert!(matches!(apply(&base[..100], &ops), Err(ChunkStoreError::CorruptDelta(_))));
+        assert!(matches!(apply(&base, &ops[..ops.len() - 1]), Err(ChunkStoreError::CorruptDelta(_))));
+        let mut bad = ops.clone();
+        bad[2] = 9;
+        assert!(apply(&base, &bad).is_err());
+    }
+
+    #[test]
+    fn test_header_roundtrip() {
+        let info = DeltaInfo {
+            base: "ab".repeat(32),
+            depth: 3,
+        };
+        let header = info.header();
+        assert_eq!(header.len(), HEADER_LEN);
+        assert_eq!(DeltaInfo::read_header(&mut &header[..]).unwrap(), info);
+
+        let mut bad = header.clone();
+        bad[0] = b'X';
+        assert!(matches!(
+            DeltaInfo::read_header(&mut &bad[..]),
+            Err(ChunkStoreError::CorruptDelta(_))
+        ));
+    }
+}
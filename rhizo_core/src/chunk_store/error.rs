@@ -1,9 +1,16 @@
+use std::io;
+use std::time::Duration;
+
 use thiserror::Error;
 
+use super::handles::is_handle_exhaustion;
+use crate::error::{ErrorCategory, ErrorContext, ErrorInfo, IO_RETRY_DELAY};
+
 #[derive(Error, Debug)]
+#[non_exhaustive]
 pub enum ChunkStoreError {
     #[error("IO error: {0}")]
-    Io(#[from] std::io::Error),
+    Io(std::io::Error),
 
     #[error("Chunk not found: {0}")]
     NotFound(String),
@@ -13,4 +20,76 @@ pub enum ChunkStoreError {
 
     #[error("Hash mismatch: expected {expected}, got {actual}")]
     HashMismatch { expected: String, actual: String },
+
+    #[error("Chunk pipeline error: {0}")]
+    Pipeline(String),
+
+    #[error("Chunk backend error: {0}")]
+    Backend(String),
+
+    #[error("Corrupt delta chunk: {0}")]
+    CorruptDelta(String),
+
+    /// The store's open file limit (or the OS's, when `limit` is `None`)
+    /// was reached
+    #[error(
+        "Too many open chunk files (limit {}) after waiting {waited_ms} ms",
+        limit.map_or_else(|| "set by the OS".to_string(), |l| l.to_string())
+    )]
+    TooManyOpenFiles { limit: Option<usize>, waited_ms: u64 },
+}
+
+impl From<io::Error> for ChunkStoreError {
+    fn from(e: io::Error) -> Self {
+        if is_handle_exhaustion(&e) {
+            return ChunkStoreError::TooManyOpenFiles { limit: None, waited_ms: 0 };
+        }
+        ChunkStoreError::Io(e)
+    }
+}
+
+impl ErrorInfo for ChunkStoreError {
+    fn code(&self) -> &'static str {
+        match self {
+            ChunkStoreError::Io(_) => "CHUNK_IO",
+            ChunkStoreError::NotFound(_) => "CHUNK_NOT_FOUND",
+            ChunkStoreError::InvalidHash(_) => "CHUNK_INVALID_HASH",
+            ChunkStoreError::HashMismatch { .. } => "CHUNK_HASH_MISMATCH",
+            ChunkStoreError::Pipeline(_) => "CHUNK_PIPELINE",
+            ChunkStoreError::Backend(_) => "CHUNK_BACKEND",
+            ChunkStoreError::CorruptDelta(_) => "CHUNK_CORRUPT_DELTA",
+            ChunkStoreError::TooManyOpenFiles { .. } => "CHUNK_TOO_MANY_OPEN_FILES",
+        }
+    }
+
+    fn category(&self) -> ErrorCategory {
+        match self {
+            ChunkStoreError::Io(_) | ChunkStoreError::Backend(_) | ChunkStoreError::TooManyOpenFiles { .. } => {
+                ErrorCategory::Io
+            }
+            ChunkStoreError::NotFound(_) => ErrorCategory::NotFound,
+            ChunkStoreError::InvalidHash(_) => ErrorCategory::InvalidArgument,
+            ChunkStoreError::HashMismatch { .. }
+            | ChunkStoreError::Pipeline(_)
+            | ChunkStoreError::CorruptDelta(_) => {
+                ErrorCategory::DataIntegrity
+            }
+        }
+    }
+
+    fn context(&self) -> ErrorContext {
+        match self {
+            ChunkStoreError::NotFound(hash) | ChunkStoreError::HashMismatch { expected: hash, .. } => {
+                ErrorContext::new().with_chunk(hash.clone())
+            }
+            _ => ErrorContext::new(),
+        }
+    }
+
+    fn retry_after(&self) -> Option<Duration> {
+        match self {
+            ChunkStoreError::TooManyOpenFiles { .. } => Some(IO_RETRY_DELAY),
+            _ => None,
+        }
+    }
 }
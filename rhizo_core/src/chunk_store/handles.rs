@@ -0,0 +1,172 @@
+//! Soft limit on the chunk files a store holds open at once.
+//!
+//! Bulk operations (`put_batch`, `get_mmap_batch`, ...) fan out over the
+//! Rayon pool and can otherwise open one file per chunk in flight. Every
+//! chunk file open takes a permit from the store's [`HandlePool`]; when
+//! the pool is exhausted, callers queue until a handle is released or
+//! the wait times out with [`ChunkStoreError::TooManyOpenFiles`].
+//!
+//! Operations that hold two files at once (a stored chunk and the
+//! temporary file it is decoded into) take both permits together, so two
+//! callers can never each hold half of what they need.
+
+use std::io;
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::{Duration, Instant};
+
+use super::error::ChunkStoreError;
+
+/// How long a caller queues for a file handle by default
+pub const DEFAULT_HANDLE_WAIT: Duration = Duration::from_secs(30);
+
+/// Counting semaphore over open chunk files
+#[derive(Debug)]
+pub(crate) struct HandlePool {
+    /// `None` leaves handles unlimited (only the OS limit applies)
+    limit: Option<usize>,
+    wait: Duration,
+    open: Mutex<usize>,
+    released: Condvar,
+}
+
+impl HandlePool {
+    pub(crate) fn new(limit: Option<usize>, wait: Duration) -> Self {
+        Self {
+            limit,
+            wait,
+            open: Mutex::new(0),
+            released: Condvar::new(),
+        }
+    }
+
+    pub(crate) fn limit(&self) -> Option<usize> {
+        self.limit
+    }
+
+    pub(crate) fn wait(&self) -> Duration {
+        self.wait
+    }
+
+    /// Handles currently held through the pool
+    pub(crate) fn in_use(&self) -> usize {
+        *self.open.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Take `count` handles, queuing while the pool is exhausted.
+    ///
+    /// Asking for more handles than the limit fails right away: waiting
+    /// could never satisfy it.
+    pub(crate) fn acquire(self: &Arc<Self>, count: usize) -> Result<HandlePermit, ChunkStoreError> {
+        let Some(limit) = self.limit else {
+            return Ok(HandlePermit { pool: Arc::clone(self), count: 0 });
+        };
+        let exceeded = |waited: Duration| ChunkStoreError::TooManyOpenFiles {
+            limit: Some(limit),
+            waited_ms: waited.as_millis() as u64,
+        };
+        if count > limit {
+            return Err(exceeded(Duration::ZERO));
+        }
+
+        let start = Instant::now();
+        let mut open = self.open.lock().unwrap_or_else(|e| e.into_inner());
+        while *open + count > limit {
+            let waited = start.elapsed();
+            let Some(remaining) = self.wait.checked_sub(waited).filter(|r| !r.is_zero()) else {
+                return Err(exceeded(waited));
+            };
+            open = self
+                .released
+                .wait_timeout(open, remaining)
+                .unwrap_or_else(|e| e.into_inner())
+                .0;
+        }
+        *open += count;
+        Ok(HandlePermit { pool: Arc::clone(self), count })
+    }
+
+    fn release(&self, count: usize) {
+        if count == 0 {
+            return;
+        }
+        let mut open = self.open.lock().unwrap_or_else(|e| e.into_inner());
+        *open -= count;
+        self.released.notify_all();
+    }
+}
+
+/// Handles taken from a [`HandlePool`], returned when dropped
+#[derive(Debug)]
+pub(crate) struct HandlePermit {
+    pool: Arc<HandlePool>,
+    count: usize,
+}
+
+impl HandlePermit {
+    /// Return all but `keep` of the handles early
+    pub(crate) fn shrink_to(&mut self, keep: usize) {
+        if keep < self.count {
+            self.pool.release(self.count - keep);
+            self.count = keep;
+        }
+    }
+}
+
+impl Drop for HandlePermit {
+    fn drop(&mut self) {
+        self.pool.release(self.count);
+    }
+}
+
+/// Whether an OS error reports the process or system file table is full
+pub(crate) fn is_handle_exhaustion(e: &io::Error) -> bool {
+    #[cfg(unix)]
+    const CODES: &[i32] = &[23, 24]; // ENFILE, EMFILE
+    #[cfg(windows)]
+    const CODES: &[i32] = &[4]; // ERROR_TOO_MANY_OPEN_FILES
+    #[cfg(not(any(unix, windows)))]
+    const CODES: &[i32] = &[];
+    e.raw_os_error().is_some_and(|code| CODES.contains(&code))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    #[test]
+    fn test_permits_queue_until_released() {
+        let pool = Arc::new(HandlePool::new(Some(2), Duration::from_secs(5)));
+        let mut held = pool.acquire(2).unwrap();
+        assert_eq!(pool.in_use(), 2);
+
+        let waiter = {
+            let pool = Arc::clone(&pool);
+            thread::spawn(move || pool.acquire(1).map(|_| ()))
+        };
+        thread::sleep(Duration::from_millis(50));
+        held.shrink_to(1);
+        waiter.join().unwrap().unwrap();
+        drop(held);
+        assert_eq!(pool.in_use(), 0);
+    }
+
+    #[test]
+    fn test_exhausted_pool_times_out() {
+        let pool = Arc::new(HandlePool::new(Some(1), Duration::from_millis(20)));
+        let _held = pool.acquire(1).unwrap();
+        match pool.acquire(1) {
+            Err(ChunkStoreError::TooManyOpenFiles { limit: Some(1), waited_ms }) => assert!(waited_ms >= 20),
+            other => panic!("expected TooManyOpenFiles, got {:?}", other.map(|_| ())),
+        }
+        // More than the limit can never be granted
+        assert!(matches!(
+            pool.acquire(2),
+            Err(ChunkStoreError::TooManyOpenFiles { waited_ms: 0, .. })
+        ));
+
+        let unlimited = Arc::new(HandlePool::new(None, Duration::ZERO));
+        let _many: Vec<_> = (0..100).map(|_| unlimited.acquire(1).unwrap()).collect();
+        assert_eq!(unlimited.in_use(), 0);
+    }
+}
@@ -0,0 +1,302 @@
+//! Sidecar journal of stored chunk metadata.
+//!
+//! Content hashes verify a chunk only once it has been read back and
+//! decoded, and a failed check does not say what went wrong. The journal
+//! records, when a chunk file is written, its stored size, a CRC32 of the
+//! stored bytes, its codec and when it was written. Comparing a chunk file
+//! against its record tells a truncated or missing file (an interrupted
+//! copy, a full disk) from one whose bytes changed in place (bit rot),
+//! without decoding it.
+//!
+//! The journal is an append-only JSON-lines file in the store directory.
+//! Later records for a hash replace earlier ones; a torn final line (from
+//! a crash mid-append) is ignored.
+
+use std::collections::HashMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use super::error::ChunkStoreError;
+
+/// File name of the journal inside the store directory
+pub const JOURNAL_FILE: &str = "integrity.jsonl";
+
+/// Metadata recorded for a chunk file when it is written.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JournalEntry {
+    /// Content hash of the chunk
+    pub hash: String,
+    /// Size of the chunk file in bytes (after pipeline stages)
+    pub size: u64,
+    /// CRC32 of the chunk file
+    pub crc32: u32,
+    /// Unix timestamp (seconds) when the file was written
+    pub created_at: u64,
+    /// Pipeline stages the chunk was stored through, joined with `+`
+    /// ("none" for an identity pipeline)
+    pub codec: String,
+    /// Stored as a delta against another chunk
+    #[serde(default)]
+    pub delta: bool,
+}
+
+impl JournalEntry {
+    pub(crate) fn new(hash: &str, stored: &[u8], codec: &str, delta: bool) -> Self {
+        Self::with_checksum(hash, stored.len() as u64, crc32(stored), codec, delta)
+    }
+
+    pub(crate) fn with_checksum(hash: &str, size: u64, crc32: u32, codec: &str, delta: bool) -> Self {
+        Self {
+            hash: hash.to_string(),
+            size,
+            crc32,
+            created_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+            codec: codec.to_string(),
+            delta,
+        }
+    }
+
+    /// Compare a chunk file against this record.
+    pub fn check_file(&self, path: &Path) -> Result<ChunkCondition, ChunkStoreError> {
+        let file = match File::open(path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(ChunkCondition::Missing),
+            Err(e) => return Err(e.into()),
+        };
+        let actual = file.metadata()?.len();
+        if actual < self.size {
+            return Ok(ChunkCondition::Truncated { expected: self.size, actual });
+        }
+        if actual > self.size {
+            return Ok(ChunkCondition::Extended { expected: self.size, actual });
+        }
+        let actual_crc = crc32_reader(BufReader::new(file))?;
+        if actual_crc != self.crc32 {
+            return Ok(ChunkCondition::BitRot {
+                expected_crc: self.crc32,
+                actual_crc,
+            });
+        }
+        Ok(ChunkCondition::Intact)
+    }
+}
+
+/// State of a chunk file compared against its journal record.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChunkCondition {
+    /// Size and CRC match
+    Intact,
+    /// The chunk file is gone
+    Missing,
+    /// The file is shorter than when it was written
+    Truncated { expected: u64, actual: u64 },
+    /// The file is longer than when it was written
+    Extended { expected: u64, actual: u64 },
+    /// Same size, different bytes
+    BitRot { expected_crc: u32, actual_crc: u32 },
+    /// Not in the local cache of a store backed by a remote; the backend
+    /// holds the authoritative copy
+    NotCached,
+}
+
+impl ChunkCondition {
+    /// True unless the file is intact or legitimately not held locally
+    pub fn is_damaged(&self) -> bool {
+        !matches!(self, ChunkCondition::Intact | ChunkCondition::NotCached)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+enum JournalRecord {
+    Put(JournalEntry),
+    Delete { hash: String },
+}
+
+/// Append-only metadata journal for a chunk store.
+#[derive(Debug)]
+pub struct ChunkJournal {
+    path: PathBuf,
+    append: Mutex<()>,
+}
+
+impl ChunkJournal {
+    /// Open (or start) the journal of the store in `store_dir`.
+    pub fn open(store_dir: impl AsRef<Path>) -> Result<Self, ChunkStoreError> {
+        let store_dir = store_dir.as_ref();
+        fs::create_dir_all(store_dir)?;
+        Ok(Self {
+            path: store_dir.join(JOURNAL_FILE),
+            append: Mutex::new(()),
+        })
+    }
+
+    /// Path of the journal file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub(crate) fn record_put(&self, entry: JournalEntry) -> Result<(), ChunkStoreError> {
+        self.append(&JournalRecord::Put(entry))
+    }
+
+    pub(crate) fn record_delete(&self, hash: &str) -> Result<(), ChunkStoreError> {
+        self.append(&JournalRecord::Delete { hash: hash.to_string() })
+    }
+
+    fn append(&self, record: &JournalRecord) -> Result<(), ChunkStoreError> {
+        let mut line = serde_json::to_vec(record)
+            .map_err(|e| ChunkStoreError::Io(io::Error::other(e)))?;
+        line.push(b'\n');
+        let _guard = self.append.lock();
+        let mut file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        file.write_all(&line)?;
+        Ok(())
+    }
+
+    /// Current record of every journaled chunk, keyed by hash.
+    pub fn entries(&self) -> Result<HashMap<String, JournalEntry>, ChunkStoreError> {
+        let file = match File::open(&self.path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(HashMap::new()),
+            Err(e) => return Err(e.into()),
+        };
+        let mut entries = HashMap::new();
+        for (index, line) in BufReader::new(file).lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str::<JournalRecord>(&line) {
+                Ok(JournalRecord::Put(entry)) => {
+                    entries.insert(entry.hash.clone(), entry);
+                }
+                Ok(JournalRecord::Delete { hash }) => {
+                    entries.remove(&hash);
+                }
+                Err(e) => {
+                    warn!(path = %self.path.display(), line = index + 1, error = %e, "Skipping unreadable journal record");
+                }
+            }
+        }
+        Ok(entries)
+    }
+}
+
+const CRC32_TABLE: [u32; 256] = {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 != 0 { (crc >> 1) ^ 0xEDB8_8320 } else { crc >> 1 };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+};
+
+fn crc32_update(mut crc: u32, data: &[u8]) -> u32 {
+    for &byte in data {
+        crc = CRC32_TABLE[((crc ^ byte as u32) & 0xFF) as usize] ^ (crc >> 8);
+    }
+    crc
+}
+
+/// CRC-32 (IEEE) of `data`
+pub fn crc32(data: &[u8]) -> u32 {
+    !crc32_update(!0, data)
+}
+
+/// CRC-32 (IEEE) of everything `reader` yields
+pub(crate) fn crc32_reader(mut reader: impl Read) -> io::Result<u32> {
+    let mut crc = !0;
+    let mut buf = [0u8; 64 * 1024];
+    loop {
+        match reader.read(&mut buf) {
+            Ok(0) => return Ok(!crc),
+            Ok(n) => crc = crc32_update(crc, &buf[..n]),
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("journal_test_{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_crc32_known_value() {
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+        assert_eq!(crc32(b""), 0);
+        assert_eq!(crc32_reader(&b"123456789"[..]).unwrap(), 0xCBF4_3926);
+    }
+
+    #[test]
+    fn test_entries_replay_deletes_and_skip_torn_line() {
+        let dir = temp_dir();
+        let journal = ChunkJournal::open(&dir).unwrap();
+        journal.record_put(JournalEntry::new("aa", b"one", "none", false)).unwrap();
+        journal.record_put(JournalEntry::new("bb", b"two", "zstd", false)).unwrap();
+        journal.record_delete("aa").unwrap();
+        OpenOptions::new()
+            .append(true)
+            .open(journal.path())
+            .unwrap()
+            .write_all(b"{\"op\":\"put\",\"hash\":\"cc\"")
+            .unwrap();
+
+        let entries = journal.entries().unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries["bb"].codec, "zstd");
+        assert_eq!(entries["bb"].crc32, crc32(b"two"));
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_check_file_classifies_damage() {
+        let dir = temp_dir();
+        let path = dir.join("chunk");
+        let entry = JournalEntry::new("aa", b"hello world", "none", false);
+
+        fs::write(&path, b"hello world").unwrap();
+        assert_eq!(entry.check_file(&path).unwrap(), ChunkCondition::Intact);
+
+        fs::write(&path, b"hello").unwrap();
+        assert_eq!(
+            entry.check_file(&path).unwrap(),
+            ChunkCondition::Truncated { expected: 11, actual: 5 }
+        );
+
+        fs::write(&path, b"hello world!").unwrap();
+        assert!(matches!(entry.check_file(&path).unwrap(), ChunkCondition::Extended { .. }));
+
+        fs::write(&path, b"hellO world").unwrap();
+        assert!(matches!(entry.check_file(&path).unwrap(), ChunkCondition::BitRot { .. }));
+
+        fs::remove_file(&path).unwrap();
+        assert_eq!(entry.check_file(&path).unwrap(), ChunkCondition::Missing);
+
+        fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -0,0 +1,175 @@
+//! Size-bounded in-memory cache of decoded chunks.
+//!
+//! Chunks are immutable under their hash, so a cached copy stays valid
+//! until the chunk is deleted; `ChunkStore::delete` drops it. Entries
+//! remember whether their bytes were checked against the hash, so
+//! `get_verified` never returns bytes only `get` has seen without
+//! hashing them first.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use parking_lot::Mutex;
+
+/// Hit and miss counters of a store's memory cache, from
+/// [`ChunkStore::memory_cache_stats`](super::ChunkStore::memory_cache_stats)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MemoryCacheStats {
+    /// Reads answered from memory
+    pub hits: u64,
+    /// Reads that went to disk (or the backend)
+    pub misses: u64,
+    /// Chunks currently cached
+    pub entries: usize,
+    /// Bytes currently cached
+    pub bytes: u64,
+    /// Most bytes the cache holds
+    pub capacity: u64,
+}
+
+struct Entry {
+    data: Vec<u8>,
+    verified: bool,
+    tick: u64,
+}
+
+#[derive(Default)]
+struct CacheState {
+    entries: HashMap<String, Entry>,
+    /// Access tick -> hash, oldest first
+    order: BTreeMap<u64, String>,
+    bytes: u64,
+    tick: u64,
+}
+
+/// LRU of chunk bytes holding at most `capacity` bytes
+pub(crate) struct MemoryCache {
+    capacity: u64,
+    state: Mutex<CacheState>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl MemoryCache {
+    pub(crate) fn new(capacity: u64) -> Self {
+        Self {
+            capacity,
+            state: Mutex::new(CacheState::default()),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    pub(crate) fn capacity(&self) -> u64 {
+        self.capacity
+    }
+
+    /// A cached chunk and whether its bytes were checked against the
+    /// hash, counting the hit or miss
+    pub(crate) fn get(&self, hash: &str) -> Option<(Vec<u8>, bool)> {
+        let mut state = self.state.lock();
+        state.tick += 1;
+        let tick = state.tick;
+        let Some(entry) = state.entries.get_mut(hash) else {
+            drop(state);
+            self.misses.fetch_add(1, Ordering::Relaxed);
+            return None;
+        };
+        let old = std::mem::replace(&mut entry.tick, tick);
+        let found = (entry.data.clone(), entry.verified);
+        state.order.remove(&old);
+        state.order.insert(tick, hash.to_string());
+        drop(state);
+        self.hits.fetch_add(1, Ordering::Relaxed);
+        Some(found)
+    }
+
+    /// Record that a cached chunk's bytes match its hash
+    pub(crate) fn mark_verified(&self, hash: &str) {
+        if let Some(entry) = self.state.lock().entries.get_mut(hash) {
+            entry.verified = true;
+        }
+    }
+
+    /// Cache a chunk read from storage, evicting the least recently used
+    /// chunks to make room. Chunks larger than the whole cache are not kept.
+    pub(crate) fn insert(&self, hash: &str, data: &[u8], verified: bool) {
+        let size = data.len() as u64;
+        if size > self.capacity {
+            return;
+        }
+        let mut state = self.state.lock();
+        if let Some(entry) = state.entries.get_mut(hash) {
+            entry.verified |= verified;
+            return;
+        }
+        state.tick += 1;
+        let tick = state.tick;
+        state.bytes += size;
+        state.entries.insert(
+            hash.to_string(),
+            Entry {
+                data: data.to_vec(),
+                verified,
+                tick,
+            },
+        );
+        state.order.insert(tick, hash.to_string());
+
+        while state.bytes > self.capacity {
+            let Some((_, victim)) = state.order.pop_first() else { break };
+            if let Some(evicted) = state.entries.remove(&victim) {
+                state.bytes -= evicted.data.len() as u64;
+            }
+        }
+    }
+
+    pub(crate) fn remove(&self, hash: &str) {
+        let mut state = self.state.lock();
+        if let Some(entry) = state.entries.remove(hash) {
+            state.order.remove(&entry.tick);
+            state.bytes -= entry.data.len() as u64;
+        }
+    }
+
+    pub(crate) fn stats(&self) -> MemoryCacheStats {
+        let state = self.state.lock();
+        MemoryCacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            entries: state.entries.len(),
+            bytes: state.bytes,
+            capacity: self.capacity,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_memory_cache_evicts_least_recently_used() {
+        let cache = MemoryCache::new(10);
+        cache.insert("a", b"aaaa", false);
+        cache.insert("b", b"bbbb", true);
+        assert_eq!(cache.get("a"), Some((b"aaaa".to_vec(), false)));
+
+        // "b" is now the least recently used
+        cache.insert("c", b"cccc", false);
+        assert_eq!(cache.get("b"), None);
+        assert!(cache.get("c").is_some());
+
+        // Too large to ever fit
+        cache.insert("huge", &[0u8; 11], true);
+        assert_eq!(cache.get("huge"), None);
+
+        cache.mark_verified("a");
+        assert_eq!(cache.get("a"), Some((b"aaaa".to_vec(), true)));
+        cache.remove("a");
+
+        let stats = cache.stats();
+        assert_eq!((stats.hits, stats.misses), (3, 2));
+        assert_eq!((stats.entries, stats.bytes, stats.capacity), (1, 4, 10));
+    }
+}
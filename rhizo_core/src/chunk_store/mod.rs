@@ -1,5 +1,25 @@
+pub mod backend;
+pub mod chunker;
+pub mod compression;
+pub mod delta;
 pub mod error;
+mod handles;
+pub mod journal;
+mod memory_cache;
+pub mod pipeline;
+#[cfg(feature = "s3")]
+pub mod s3;
 pub mod store;
 
+pub use backend::{ChunkBackend, ChunkStat, ChunkTags, TABLE_TAG, TX_TAG, VERSION_TAG};
+pub use chunker::{ChunkSplitter, Chunker, DEFAULT_AVG_CHUNK_SIZE};
+pub use compression::{ChunkCompression, ChunkStoreConfig, CompressionStage};
+pub use delta::DeltaInfo;
 pub use error::ChunkStoreError;
-pub use store::{ChunkMmap, ChunkStore};
+pub use handles::DEFAULT_HANDLE_WAIT;
+pub use journal::{ChunkCondition, ChunkJournal, JournalEntry};
+pub use memory_cache::MemoryCacheStats;
+pub use pipeline::{ChunkPipeline, ChunkStage};
+#[cfg(feature = "s3")]
+pub use s3::{S3Backend, S3Config, S3Credentials};
+pub use store::{ChunkMmap, ChunkReader, ChunkStore};
@@ -0,0 +1,382 @@
+//! Composable encode/decode pipeline for stored chunk bytes.
+//!
+//! Chunks are content-addressed by the BLAKE3 hash of their *logical*
+//! bytes. Stages such as compression or encryption transform those bytes
+//! before they hit disk. On the write path stages run in the order they
+//! were added; on the read path their decoders are chained in reverse and
+//! driven as a single stream, so decrypt -> decompress -> verify happens in
+//! one pass into one output buffer instead of materializing every
+//! intermediate form.
+
+use std::fmt;
+use std::io::{self, Read, Write};
+use std::sync::Arc;
+
+use super::error::ChunkStoreError;
+
+/// Read buffer size for streaming decodes
+const READ_BLOCK: usize = 64 * 1024;
+
+/// One transformation applied to chunk bytes at rest.
+///
+/// `encode` runs on the write path with the whole chunk; `decoder` wraps
+/// a stream of stored bytes in a stream of decoded bytes so stages can be
+/// fused on the read path.
+pub trait ChunkStage: Send + Sync {
+    /// Short identifier (e.g. "zstd", "aes-gcm") used in errors and logs
+    fn name(&self) -> &str;
+
+    /// Transform logical bytes into their stored form.
+    fn encode(&self, data: Vec<u8>) -> Result<Vec<u8>, ChunkStoreError>;
+
+    /// Streaming form of `encode`, used by `ChunkStore::put_stream`.
+    ///
+    /// The default reads the whole input into memory and calls `encode`;
+    /// stages that can transform a stream should override it.
+    fn encode_stream(&self, input: &mut dyn Read, output: &mut dyn Write) -> Result<(), ChunkStoreError> {
+        let mut data = Vec::new();
+        input.read_to_end(&mut data)?;
+        output.write_all(&self.encode(data)?)?;
+        Ok(())
+    }
+
+    /// Wrap a reader of stored bytes in a reader of decoded bytes.
+    ///
+    /// I/O errors returned by the decoder (corrupt input, failed
+    /// authentication) are reported as [`ChunkStoreError::Pipeline`].
+    fn decoder<'a>(&'a self, stored: Box<dyn Read + 'a>) -> Result<Box<dyn Read + 'a>, ChunkStoreError>;
+}
+
+/// Ordered stages plus read-side verification policy.
+///
+/// The default pipeline has no stages: chunks are stored verbatim and
+/// `ChunkStore` keeps its zero-copy fast paths.
+///
+/// # Example
+///
+/// ```ignore
+/// let pipeline = ChunkPipeline::new()
+///     .with_stage(Arc::new(Compression::zstd(3)))   // compress first...
+///     .with_stage(Arc::new(Encryption::new(key)))   // ...then encrypt
+///     .verify_on_read(true);
+/// let store = ChunkStore::new(path)?.with_pipeline(pipeline);
+/// ```
+#[derive(Clone, Default)]
+pub struct ChunkPipeline {
+    stages: Vec<Arc<dyn ChunkStage>>,
+    verify_on_read: bool,
+}
+
+impl fmt::Debug for ChunkPipeline {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ChunkPipeline")
+            .field("stages", &self.stage_names())
+            .field("verify_on_read", &self.verify_on_read)
+            .finish()
+    }
+}
+
+impl ChunkPipeline {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a stage. Writes apply stages in the order added; reads undo
+    /// them in reverse.
+    pub fn with_stage(mut self, stage: Arc<dyn ChunkStage>) -> Self {
+        self.stages.push(stage);
+        self
+    }
+
+    /// Verify the content hash on every read, not only in `get_verified`.
+    pub fn verify_on_read(mut self, verify: bool) -> Self {
+        self.verify_on_read = verify;
+        self
+    }
+
+    /// Whether plain reads verify content hashes
+    pub fn verifies_on_read(&self) -> bool {
+        self.verify_on_read
+    }
+
+    /// True when chunks are stored verbatim
+    pub fn is_identity(&self) -> bool {
+        self.stages.is_empty()
+    }
+
+    /// Stage names in write order
+    pub fn stage_names(&self) -> Vec<&str> {
+        self.stages.iter().map(|s| s.name()).collect()
+    }
+
+    pub(crate) fn stages(&self) -> &[Arc<dyn ChunkStage>] {
+        &self.stages
+    }
+
+    /// Transform logical bytes into their stored form.
+    pub fn encode(&self, data: &[u8]) -> Result<Vec<u8>, ChunkStoreError> {
+        let mut out = data.to_vec();
+        for stage in &self.stages {
+            out = stage.encode(out)?;
+        }
+        Ok(out)
+    }
+
+    /// Decode stored bytes in a single streaming pass.
+    ///
+    /// Decoders are chained in reverse stage order and read into one output
+    /// buffer (pre-sized with `size_hint`). With `expected_hash`, each block
+    /// is hashed as it is produced and the result checked at the end.
+    pub fn decode<'a>(
+        &'a self,
+        stored: impl Read + 'a,
+        expected_hash: Option<&str>,
+        size_hint: usize,
+    ) -> Result<Vec<u8>, ChunkStoreError> {
+        let mut reader = self.decoder(stored)?;
+
+        let mut hasher = expected_hash.map(|_| blake3::Hasher::new());
+        let mut out = Vec::with_capacity(size_hint);
+        loop {
+            let start = out.len();
+            out.resize(start + READ_BLOCK, 0);
+            let n = match reader.read(&mut out[start..]) {
+                Ok(n) => n,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {
+                    out.truncate(start);
+                    continue;
+                }
+                Err(e) => return Err(self.decode_error(e)),
+            };
+            out.truncate(start + n);
+            if n == 0 {
+                break;
+            }
+            if let Some(hasher) = hasher.as_mut() {
+                hasher.update(&out[start..]);
+            }
+        }
+
+        check_hash(expected_hash, hasher)?;
+        Ok(out)
+    }
+
+    /// Decode stored bytes into `out` without buffering the chunk, like
+    /// [`decode`](Self::decode). Returns the decoded length.
+    pub fn decode_to<'a>(
+        &'a self,
+        stored: impl Read + 'a,
+        out: &mut dyn Write,
+        expected_hash: Option<&str>,
+    ) -> Result<u64, ChunkStoreError> {
+        let mut reader = self.decoder(stored)?;
+
+        let mut hasher = expected_hash.map(|_| blake3::Hasher::new());
+        let mut buf = vec![0u8; READ_BLOCK];
+        let mut len = 0;
+        loop {
+            let n = match reader.read(&mut buf) {
+                Ok(0) => break,
+                Ok(n) => n,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(self.decode_error(e)),
+            };
+            if let Some(hasher) = hasher.as_mut() {
+                hasher.update(&buf[..n]);
+            }
+            out.write_all(&buf[..n])?;
+            len += n as u64;
+        }
+
+        check_hash(expected_hash, hasher)?;
+        Ok(len)
+    }
+
+    /// Chain the stage decoders in reverse order
+    fn decoder<'a>(&'a self, stored: impl Read + 'a) -> Result<Box<dyn Read + 'a>, ChunkStoreError> {
+        let mut reader: Box<dyn Read + 'a> = Box::new(stored);
+        for stage in self.stages.iter().rev() {
+            reader = stage.decoder(reader)?;
+        }
+        Ok(reader)
+    }
+
+    fn decode_error(&self, e: io::Error) -> ChunkStoreError {
+        ChunkStoreError::Pipeline(format!("{}: {}", self.stage_names().join(" -> "), e))
+    }
+}
+
+fn check_hash(expected: Option<&str>, hasher: Option<blake3::Hasher>) -> Result<(), ChunkStoreError> {
+    if let (Some(expected), Some(hasher)) = (expected, hasher) {
+        let actual = hasher.finalize().to_hex().to_string();
+        if actual != expected {
+            return Err(ChunkStoreError::HashMismatch {
+                expected: expected.to_string(),
+                actual,
+            });
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chunk_store::ChunkStore;
+
+    /// Reversible byte mask standing in for encryption
+    struct Mask(u8);
+
+    struct MaskReader<'a> {
+        inner: Box<dyn Read + 'a>,
+        key: u8,
+    }
+
+    impl Read for MaskReader<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let n = self.inner.read(buf)?;
+            buf[..n].iter_mut().for_each(|b| *b ^= self.key);
+            Ok(n)
+        }
+    }
+
+    impl ChunkStage for Mask {
+        fn name(&self) -> &str {
+            "mask"
+        }
+
+        fn encode(&self, mut data: Vec<u8>) -> Result<Vec<u8>, ChunkStoreError> {
+            data.iter_mut().for_each(|b| *b ^= self.0);
+            Ok(data)
+        }
+
+        fn decoder<'a>(&'a self, stored: Box<dyn Read + 'a>) -> Result<Box<dyn Read + 'a>, ChunkStoreError> {
+            Ok(Box::new(MaskReader { inner: stored, key: self.0 }))
+        }
+    }
+
+    /// Length-prefixed framing standing in for compression; rejects
+    /// truncated input
+    struct Framed;
+
+    struct FramedReader<'a> {
+        inner: Box<dyn Read + 'a>,
+        remaining: Option<u32>,
+    }
+
+    impl Read for FramedReader<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let remaining = match self.remaining {
+                Some(r) => r,
+                None => {
+                    let mut len = [0u8; 4];
+                    self.inner.read_exact(&mut len)?;
+                    u32::from_le_bytes(len)
+                }
+            };
+            if remaining == 0 || buf.is_empty() {
+                self.remaining = Some(remaining);
+                return Ok(0);
+            }
+            let want = buf.len().min(remaining as usize);
+            let n = self.inner.read(&mut buf[..want])?;
+            if n == 0 {
+                return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "truncated frame"));
+            }
+            self.remaining = Some(remaining - n as u32);
+            Ok(n)
+        }
+    }
+
+    impl ChunkStage for Framed {
+        fn name(&self) -> &str {
+            "framed"
+        }
+
+        fn encode(&self, data: Vec<u8>) -> Result<Vec<u8>, ChunkStoreError> {
+            let mut out = (data.len() as u32).to_le_bytes().to_vec();
+            out.extend_from_slice(&data);
+            Ok(out)
+        }
+
+        fn decoder<'a>(&'a self, stored: Box<dyn Read + 'a>) -> Result<Box<dyn Read + 'a>, ChunkStoreError> {
+            Ok(Box::new(FramedReader { inner: stored, remaining: None }))
+        }
+    }
+
+    fn pipeline() -> ChunkPipeline {
+        ChunkPipeline::new()
+            .with_stage(Arc::new(Framed))
+            .with_stage(Arc::new(Mask(0x5a)))
+    }
+
+    #[test]
+    fn test_roundtrip_reverses_stage_order() {
+        let data: Vec<u8> = (0..200_000u32).map(|i| (i % 251) as u8).collect();
+        let p = pipeline();
+        let stored = p.encode(&data).unwrap();
+        assert_eq!(stored.len(), data.len() + 4);
+        assert_ne!(&stored[4..], &data[..]);
+
+        let hash = blake3::hash(&data).to_hex().to_string();
+        let decoded = p.decode(&stored[..], Some(&hash), data.len()).unwrap();
+        assert_eq!(decoded, data);
+        assert_eq!(p.stage_names(), vec!["framed", "mask"]);
+    }
+
+    #[test]
+    fn test_decode_detects_hash_mismatch() {
+        let p = pipeline();
+        let stored = p.encode(b"payload").unwrap();
+        let wrong = blake3::hash(b"other").to_hex().to_string();
+        assert!(matches!(
+            p.decode(&stored[..], Some(&wrong), 0),
+            Err(ChunkStoreError::HashMismatch { .. })
+        ));
+        // Without an expected hash nothing is checked
+        assert_eq!(p.decode(&stored[..], None, 0).unwrap(), b"payload");
+    }
+
+    #[test]
+    fn test_decoder_errors_are_pipeline_errors() {
+        let p = pipeline();
+        let stored = p.encode(b"payload").unwrap();
+        let err = p.decode(&stored[..6], None, 0).unwrap_err();
+        assert!(matches!(err, ChunkStoreError::Pipeline(ref msg) if msg.contains("framed -> mask")));
+    }
+
+    #[test]
+    fn test_identity_pipeline() {
+        let p = ChunkPipeline::new();
+        assert!(p.is_identity());
+        assert_eq!(p.encode(b"abc").unwrap(), b"abc");
+        assert_eq!(p.decode(&b"abc"[..], None, 3).unwrap(), b"abc");
+    }
+
+    #[test]
+    fn test_store_reads_through_pipeline() {
+        let dir = std::env::temp_dir().join(format!("rhizo_pipeline_test_{}", uuid::Uuid::new_v4()));
+        let store = ChunkStore::new(&dir).unwrap().with_pipeline(pipeline());
+
+        // Content address is the logical bytes, not the stored form
+        let hash = store.put(b"hello pipeline").unwrap();
+        assert_eq!(hash, blake3::hash(b"hello pipeline").to_hex().to_string());
+        let path = dir.join(&hash[0..2]).join(&hash[2..4]).join(&hash);
+        assert_ne!(std::fs::read(&path).unwrap(), b"hello pipeline");
+
+        assert_eq!(store.get(&hash).unwrap(), b"hello pipeline");
+        assert_eq!(store.get_verified(&hash).unwrap(), b"hello pipeline");
+        assert!(matches!(store.get_mmap(&hash), Err(ChunkStoreError::Pipeline(_))));
+
+        // Tampering is caught by the fused verification
+        let tampered = pipeline().encode(b"hello pipelinE").unwrap();
+        std::fs::write(&path, tampered).unwrap();
+        assert!(store.get(&hash).is_ok());
+        assert!(matches!(store.get_verified(&hash), Err(ChunkStoreError::HashMismatch { .. })));
+
+        let verifying = ChunkStore::new(&dir).unwrap().with_pipeline(pipeline().verify_on_read(true));
+        assert!(matches!(verifying.get(&hash), Err(ChunkStoreError::HashMismatch { .. })));
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
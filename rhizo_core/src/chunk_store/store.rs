@@ -1,14 +1,33 @@
+use std::collections::{HashMap, HashSet};
 use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, Read, Write};
 use std::ops::Deref;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
 use rayon::prelude::*;
 use memmap2::Mmap;
 use tracing::warn;
+use super::backend::{ChunkBackend, ChunkCache, ChunkStat, ChunkTags};
+use super::chunker::Chunker;
+use super::compression::{ChunkCompression, ChunkStoreConfig, CompressionStage};
+use super::delta::{self, DeltaInfo, DEFAULT_MAX_DELTA_CHAIN};
 use super::error::ChunkStoreError;
+use super::handles::{HandlePermit, HandlePool, DEFAULT_HANDLE_WAIT};
+use super::journal::{crc32_reader, ChunkCondition, ChunkJournal, JournalEntry};
+use super::memory_cache::{MemoryCache, MemoryCacheStats};
+use super::pipeline::ChunkPipeline;
 
 /// BLAKE3 hashes are 64 hex characters (256 bits)
 const EXPECTED_HASH_LEN: usize = 64;
 
+/// Buffer size for `put_stream`
+const STREAM_BLOCK: usize = 1024 * 1024;
+
+/// Extension of delta chunk files, stored next to where the full chunk
+/// file would be
+const DELTA_EXT: &str = "delta";
+
 /// A memory-mapped chunk that keeps the underlying file handle alive.
 ///
 /// On Windows, the file handle must remain open while the memory mapping is in use.
@@ -21,12 +40,13 @@ pub struct ChunkMmap {
     mmap: Mmap,
     #[allow(dead_code)] // Kept alive to maintain the memory mapping
     file: File,
+    _permit: HandlePermit,
 }
 
 impl ChunkMmap {
     /// Create a new ChunkMmap from a file path.
-    fn new(file: File, mmap: Mmap) -> Self {
-        Self { mmap, file }
+    fn new(file: File, mmap: Mmap, permit: HandlePermit) -> Self {
+        Self { mmap, file, _permit: permit }
     }
 
     /// Get the length of the memory-mapped region.
@@ -54,15 +74,326 @@ impl AsRef<[u8]> for ChunkMmap {
     }
 }
 
+/// Streaming reader over a chunk's logical bytes, from
+/// [`ChunkStore::get_stream`].
+///
+/// When the store verifies reads, reaching the end of a chunk whose bytes
+/// do not match its hash fails with an `InvalidData` error wrapping
+/// [`ChunkStoreError::HashMismatch`].
+pub struct ChunkReader {
+    // Field order matters: the file must be closed before the spill is removed
+    inner: BufReader<File>,
+    len: u64,
+    verify: Option<(String, blake3::Hasher)>,
+    /// Decoded copy for non-identity pipelines, removed on drop
+    _spill: Option<SpillFile>,
+    _permit: HandlePermit,
+}
+
+impl ChunkReader {
+    /// Logical size of the chunk in bytes
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+impl Read for ChunkReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        if n > 0 {
+            if let Some((_, hasher)) = self.verify.as_mut() {
+                hasher.update(&buf[..n]);
+            }
+        } else if !buf.is_empty() {
+            if let Some((expected, hasher)) = self.verify.take() {
+                let actual = hasher.finalize().to_hex().to_string();
+                if actual != expected {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        ChunkStoreError::HashMismatch { expected, actual },
+                    ));
+                }
+            }
+        }
+        Ok(n)
+    }
+}
+
+/// Temporary file in the store directory, removed on drop unless it has
+/// been renamed into place. The `.tmp` suffix lets
+/// `cleanup_orphaned_temp_files` reclaim it after a crash.
+struct SpillFile {
+    path: PathBuf,
+}
+
+impl SpillFile {
+    fn create(dir: &Path) -> Result<(Self, File), ChunkStoreError> {
+        let path = dir.join(format!("{}.spill.tmp", uuid::Uuid::new_v4()));
+        let file = File::create(&path)?;
+        Ok((Self { path }, file))
+    }
+
+    fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for SpillFile {
+    fn drop(&mut self) {
+        if let Err(e) = fs::remove_file(&self.path) {
+            if e.kind() != io::ErrorKind::NotFound {
+                warn!(path = %self.path.display(), error = %e, "Failed to remove spill file");
+            }
+        }
+    }
+}
+
+/// Authoritative remote storage plus the local hot-chunk cache index
+struct Remote {
+    backend: Arc<dyn ChunkBackend>,
+    cache: ChunkCache,
+}
+
 pub struct ChunkStore {
     base_path: PathBuf,
+    pipeline: ChunkPipeline,
+    remote: Option<Remote>,
+    max_delta_chain: u32,
+    journal: Option<ChunkJournal>,
+    handles: Arc<HandlePool>,
+    chunker: Chunker,
+    memory: Option<MemoryCache>,
 }
 
 impl ChunkStore {
     pub fn new(base_path: impl AsRef<Path>) -> Result<Self, ChunkStoreError> {
         let base_path = base_path.as_ref().to_path_buf();
         fs::create_dir_all(&base_path)?;
-        Ok(Self { base_path })
+        Ok(Self {
+            base_path,
+            pipeline: ChunkPipeline::default(),
+            remote: None,
+            max_delta_chain: DEFAULT_MAX_DELTA_CHAIN,
+            journal: None,
+            handles: Arc::new(HandlePool::new(None, DEFAULT_HANDLE_WAIT)),
+            chunker: Chunker::default(),
+            memory: None,
+        })
+    }
+
+    /// Open a store with compression and read verification settings.
+    ///
+    /// Compressed chunks record their codec, so a store opened with
+    /// compression reads chunks written uncompressed or with another codec.
+    /// A store opened without compression returns compressed chunks as
+    /// stored; once compression is enabled, open the store with it
+    /// everywhere (see [`CompressionStage`](super::CompressionStage) for
+    /// turning it off).
+    pub fn with_config(base_path: impl AsRef<Path>, config: ChunkStoreConfig) -> Result<Self, ChunkStoreError> {
+        let mut pipeline = ChunkPipeline::new().verify_on_read(config.verify_on_read);
+        if config.compression != ChunkCompression::None {
+            pipeline = pipeline.with_stage(Arc::new(CompressionStage::new(config.compression)));
+        }
+        let mut store = Self::new(base_path)?
+            .with_pipeline(pipeline)
+            .with_max_delta_chain(config.max_delta_chain)
+            .with_chunker(config.chunker)
+            .with_memory_cache(config.memory_cache_bytes);
+        if let Some(limit) = config.max_open_files {
+            store = store.with_max_open_files(limit);
+        }
+        if config.integrity_journal {
+            return store.with_integrity_journal();
+        }
+        Ok(store)
+    }
+
+    /// Store chunks through `pipeline` (compression, encryption, ...).
+    ///
+    /// Every process opening the same store must use the same stages.
+    /// Memory-mapped reads are only available with an identity pipeline.
+    pub fn with_pipeline(mut self, pipeline: ChunkPipeline) -> Self {
+        self.pipeline = pipeline;
+        self
+    }
+
+    /// The pipeline chunks are stored through
+    pub fn pipeline(&self) -> &ChunkPipeline {
+        &self.pipeline
+    }
+
+    /// Longest delta chain `put_delta` builds (0 disables delta chunks).
+    ///
+    /// Reading a chunk whose chain is longer (written under a higher limit)
+    /// stores it in full.
+    pub fn with_max_delta_chain(mut self, max_delta_chain: u32) -> Self {
+        self.max_delta_chain = max_delta_chain;
+        self
+    }
+
+    /// How `put_chunked` splits streams (default: content-defined, averaging
+    /// [`DEFAULT_AVG_CHUNK_SIZE`](super::DEFAULT_AVG_CHUNK_SIZE)).
+    ///
+    /// Writers of the same data should use the same chunker; chunks cut
+    /// differently do not deduplicate.
+    pub fn with_chunker(mut self, chunker: Chunker) -> Self {
+        self.chunker = chunker;
+        self
+    }
+
+    /// The chunker `put_chunked` uses
+    pub fn chunker(&self) -> &Chunker {
+        &self.chunker
+    }
+
+    /// Keep up to `capacity` bytes of recently read chunks in memory (0
+    /// disables the cache, the default).
+    ///
+    /// `get`, `get_verified` and the batch reads that use them answer
+    /// from memory when they can; streaming and memory-mapped reads always
+    /// go to disk. Chunks deleted through this store are dropped from the
+    /// cache, but not chunks deleted by another process.
+    pub fn with_memory_cache(mut self, capacity: u64) -> Self {
+        self.memory = (capacity > 0).then(|| MemoryCache::new(capacity));
+        self
+    }
+
+    /// Hits, misses and size of the memory cache (all zero without one)
+    pub fn memory_cache_stats(&self) -> MemoryCacheStats {
+        self.memory.as_ref().map_or_else(MemoryCacheStats::default, MemoryCache::stats)
+    }
+
+    /// The memory cache capacity in bytes (0 without one)
+    pub fn memory_cache_capacity(&self) -> u64 {
+        self.memory.as_ref().map_or(0, MemoryCache::capacity)
+    }
+
+    /// Hold at most `limit` chunk files open at once.
+    ///
+    /// Operations past the limit queue until a handle is released, for up
+    /// to [`DEFAULT_HANDLE_WAIT`] (see `with_handle_wait`), then fail with
+    /// `ChunkStoreError::TooManyOpenFiles`. `ChunkReader`s and `ChunkMmap`s
+    /// hold their handle until dropped, so a `get_mmap_batch` of more
+    /// chunks than the limit fails without waiting. The limit is per
+    /// store: set it below the process's descriptor limit, leaving room
+    /// for the catalog and transaction log.
+    pub fn with_max_open_files(mut self, limit: usize) -> Self {
+        self.handles = Arc::new(HandlePool::new(Some(limit.max(1)), self.handles.wait()));
+        self
+    }
+
+    /// How long operations queue for a file handle under `with_max_open_files`
+    pub fn with_handle_wait(mut self, wait: Duration) -> Self {
+        self.handles = Arc::new(HandlePool::new(self.handles.limit(), wait));
+        self
+    }
+
+    /// The open chunk file limit, if any
+    pub fn max_open_files(&self) -> Option<usize> {
+        self.handles.limit()
+    }
+
+    /// Chunk files currently held open under the limit (0 without one)
+    pub fn open_files(&self) -> usize {
+        self.handles.in_use()
+    }
+
+    /// Keep chunks in `backend`, using the local directory as a cache of at
+    /// most `cache_capacity` bytes of recently used chunks.
+    ///
+    /// Writes go through to the backend before they are cached; reads that
+    /// miss the cache fetch from the backend. Chunk files already in the
+    /// local directory are adopted into the cache.
+    pub fn with_backend(
+        mut self,
+        backend: Arc<dyn ChunkBackend>,
+        cache_capacity: u64,
+    ) -> Result<Self, ChunkStoreError> {
+        let cache = ChunkCache::new(cache_capacity);
+        for hash in self.list_local_hashes()? {
+            // Delta chunks are local only and never evicted
+            let Ok(meta) = fs::metadata(self.hash_to_path(&hash)?) else {
+                continue;
+            };
+            let size = meta.len();
+            for victim in cache.insert(&hash, size) {
+                self.evict(&victim);
+            }
+        }
+        self.remote = Some(Remote { backend, cache });
+        Ok(self)
+    }
+
+    /// Record the size, CRC32 and codec of every chunk file written from
+    /// now on in a sidecar journal, so `check_integrity` can tell bit rot
+    /// from truncation. Chunks written earlier stay unjournaled.
+    pub fn with_integrity_journal(mut self) -> Result<Self, ChunkStoreError> {
+        self.journal = Some(ChunkJournal::open(&self.base_path)?);
+        Ok(self)
+    }
+
+    /// The integrity journal, if enabled
+    pub fn integrity_journal(&self) -> Option<&ChunkJournal> {
+        self.journal.as_ref()
+    }
+
+    /// Compare the chunk file `entry` describes against it.
+    ///
+    /// A chunk missing from the local cache of a backend-backed store is
+    /// `NotCached`, not `Missing`.
+    pub fn check_integrity(&self, entry: &JournalEntry) -> Result<ChunkCondition, ChunkStoreError> {
+        self.validate_hash(&entry.hash)?;
+        let path = if entry.delta {
+            self.delta_path(&entry.hash)?
+        } else {
+            self.hash_to_path(&entry.hash)?
+        };
+        match entry.check_file(&path)? {
+            ChunkCondition::Missing if self.remote.is_some() => Ok(ChunkCondition::NotCached),
+            condition => Ok(condition),
+        }
+    }
+
+    /// Journal a newly written chunk file. Best effort: a chunk whose
+    /// record failed to append is reported as unjournaled by scrubs.
+    fn journal_put(&self, entry: impl FnOnce(&str) -> io::Result<JournalEntry>) {
+        let Some(journal) = &self.journal else { return };
+        let codec = match self.pipeline.stage_names() {
+            names if names.is_empty() => "none".to_string(),
+            names => names.join("+"),
+        };
+        let result = entry(&codec).map_err(ChunkStoreError::from).and_then(|e| journal.record_put(e));
+        if let Err(e) = result {
+            warn!(path = %journal.path().display(), error = %e, "Failed to journal chunk");
+        }
+    }
+
+    /// The remote backend, if chunks are not purely local
+    pub fn backend(&self) -> Option<&Arc<dyn ChunkBackend>> {
+        self.remote.as_ref().map(|r| &r.backend)
+    }
+
+    /// Label the remote objects of `hashes` with `tags`. A no-op for purely
+    /// local stores and for backends without object tags.
+    pub fn tag_chunks(&self, hashes: &[String], tags: &ChunkTags) -> Result<(), ChunkStoreError> {
+        let Some(remote) = &self.remote else {
+            return Ok(());
+        };
+        let mut seen = HashSet::new();
+        let distinct: Vec<&str> = hashes.iter().map(String::as_str).filter(|hash| seen.insert(*hash)).collect();
+        distinct.par_iter().try_for_each(|hash| {
+            self.validate_hash(hash)?;
+            remote.backend.set_tags(hash, tags)
+        })
+    }
+
+    /// Bytes held in the local hot-chunk cache (0 for purely local stores)
+    pub fn cached_bytes(&self) -> u64 {
+        self.remote.as_ref().map_or(0, |r| r.cache.bytes())
     }
 
     /// Compute BLAKE3 hash, using multithreaded hashing for large buffers.
@@ -85,65 +416,340 @@ impl ChunkStore {
 
     pub fn put(&self, data: &[u8]) -> Result<String, ChunkStoreError> {
         let hash = Self::blake3_hash(data);
+        self.put_hashed(&hash, data)?;
+        Ok(hash)
+    }
+
+    /// `put` of a chunk whose hash is already known
+    fn put_hashed(&self, hash: &str, data: &[u8]) -> Result<(), ChunkStoreError> {
+        let chunk_path = self.hash_to_path(hash)?;
+
+        if let Some(remote) = &self.remote {
+            // A cached chunk has already been written through
+            if chunk_path.exists() {
+                remote.cache.touch(hash);
+                self.refresh_mtime(&chunk_path);
+                return Ok(());
+            }
+            let encoded;
+            let stored = if self.pipeline.is_identity() {
+                data
+            } else {
+                encoded = self.pipeline.encode(data)?;
+                &encoded
+            };
+            remote.backend.put(hash, stored)?;
+            self.cache_locally(remote, hash, &chunk_path, stored)?;
+            self.journal_put(|codec| Ok(JournalEntry::new(hash, stored, codec, false)));
+            return Ok(());
+        }
+
+        if chunk_path.exists() {
+            self.refresh_mtime(&chunk_path);
+        } else {
+            let encoded;
+            let stored = if self.pipeline.is_identity() {
+                data
+            } else {
+                encoded = self.pipeline.encode(data)?;
+                &encoded
+            };
+            self.write_chunk_file(hash, &chunk_path, stored)?;
+            self.journal_put(|codec| Ok(JournalEntry::new(hash, stored, codec, false)));
+        }
+
+        Ok(())
+    }
+
+    /// Store a chunk read from `reader` without holding it in memory.
+    ///
+    /// The bytes are hashed as they are copied into a temporary file in
+    /// the store directory, which becomes the chunk file once pipeline
+    /// stages (if any) have been streamed over it. Returns the same hash as
+    /// `put` of the same bytes.
+    pub fn put_stream(&self, mut reader: impl Read) -> Result<String, ChunkStoreError> {
+        let permit = self.handles.acquire(1)?;
+        let (spill, mut file) = SpillFile::create(&self.base_path)?;
+        let mut hasher = blake3::Hasher::new();
+        let mut buf = vec![0u8; STREAM_BLOCK];
+        loop {
+            let n = match reader.read(&mut buf) {
+                Ok(0) => break,
+                Ok(n) => n,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e.into()),
+            };
+            if n >= 128 * 1024 {
+                hasher.update_rayon(&buf[..n]);
+            } else {
+                hasher.update(&buf[..n]);
+            }
+            file.write_all(&buf[..n])?;
+        }
+        drop(file);
+        drop(permit);
+
+        let hash = hasher.finalize().to_hex().to_string();
         let chunk_path = self.hash_to_path(&hash)?;
+        if chunk_path.exists() {
+            if let Some(remote) = &self.remote {
+                remote.cache.touch(&hash);
+            }
+            self.refresh_mtime(&chunk_path);
+            return Ok(hash);
+        }
 
-        if !chunk_path.exists() {
-            if let Some(parent) = chunk_path.parent() {
-                fs::create_dir_all(parent)?;
+        let stored = self.encode_spilled(spill)?;
+        let permit = self.handles.acquire(1)?;
+        if let Some(remote) = &self.remote {
+            remote.backend.put_file(&hash, stored.path())?;
+        }
+        let size = fs::metadata(stored.path())?.len();
+        self.journal_put(|codec| {
+            let crc = crc32_reader(BufReader::new(File::open(stored.path())?))?;
+            Ok(JournalEntry::with_checksum(&hash, size, crc, codec, false))
+        });
+        drop(permit);
+        Self::install_chunk_file(stored.path(), &chunk_path)?;
+        if let Some(remote) = &self.remote {
+            for victim in remote.cache.insert(&hash, size) {
+                self.evict(&victim);
             }
-            // Atomic write: write to temp file then rename
-            // Use unique temp file name to avoid collisions in parallel writes
-            let temp_name = format!("{}.{}.tmp", hash, uuid::Uuid::new_v4());
-            let temp_path = chunk_path.with_file_name(&temp_name);
-            fs::write(&temp_path, data)?;
-
-            // Rename may fail if another thread created the chunk first - that's OK
-            // because content-addressed storage means both wrote the same data
-            match fs::rename(&temp_path, &chunk_path) {
-                Ok(()) => {}
-                Err(_) if chunk_path.exists() => {
-                    // Another thread beat us - clean up our temp file
-                    if let Err(e) = fs::remove_file(&temp_path) {
-                        warn!(
-                            path = %temp_path.display(),
-                            error = %e,
-                            "Failed to remove orphaned temp file after concurrent write"
-                        );
-                    }
-                    // This is not an error - the chunk exists with correct content
+        }
+        Ok(hash)
+    }
+
+    /// Split a stream with the store's chunker and store each chunk.
+    ///
+    /// Returns the chunk hashes in stream order; their concatenation is the
+    /// stream. With the default content-defined chunker, a new version of a
+    /// file shares every chunk with the old one except those around an
+    /// edit. At most the chunker's maximum chunk size is held in memory.
+    pub fn put_chunked(&self, reader: impl Read) -> Result<Vec<String>, ChunkStoreError> {
+        self.chunker
+            .split(reader)
+            .map(|chunk| self.put(&chunk?))
+            .collect()
+    }
+
+    /// Stream a spilled chunk through the pipeline stages, one temporary
+    /// file per stage
+    fn encode_spilled(&self, mut current: SpillFile) -> Result<SpillFile, ChunkStoreError> {
+        for stage in self.pipeline.stages() {
+            let _permit = self.handles.acquire(2)?;
+            let (next, file) = SpillFile::create(&self.base_path)?;
+            let mut input = BufReader::new(File::open(current.path())?);
+            let mut output = BufWriter::new(file);
+            stage.encode_stream(&mut input, &mut output)?;
+            output.flush()?;
+            current = next;
+        }
+        Ok(current)
+    }
+
+    /// Mark a deduplicated chunk as recently written, so garbage collection
+    /// keeps it within its grace period while the new reference is being
+    /// committed. Best effort: a failure only shortens that window.
+    fn refresh_mtime(&self, chunk_path: &Path) {
+        let refreshed = self.handles.acquire(1).and_then(|_permit| {
+            let file = File::options().write(true).open(chunk_path)?;
+            Ok(file.set_modified(SystemTime::now())?)
+        });
+        if let Err(e) = refreshed {
+            warn!(path = %chunk_path.display(), error = %e, "Failed to refresh chunk modification time");
+        }
+    }
+
+    /// Atomically write stored bytes to a chunk file
+    fn write_chunk_file(&self, hash: &str, chunk_path: &Path, stored: &[u8]) -> Result<(), ChunkStoreError> {
+        if let Some(parent) = chunk_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        // Atomic write: write to temp file then rename
+        // Use unique temp file name to avoid collisions in parallel writes
+        let temp_name = format!("{}.{}.tmp", hash, uuid::Uuid::new_v4());
+        let temp_path = chunk_path.with_file_name(&temp_name);
+        let permit = self.handles.acquire(1)?;
+        fs::write(&temp_path, stored)?;
+        drop(permit);
+        Self::install_chunk_file(&temp_path, chunk_path)
+    }
+
+    /// Rename a fully written temporary file to its chunk path
+    fn install_chunk_file(temp_path: &Path, chunk_path: &Path) -> Result<(), ChunkStoreError> {
+        if let Some(parent) = chunk_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        // Rename may fail if another thread created the chunk first - that's OK
+        // because content-addressed storage means both wrote the same data
+        match fs::rename(temp_path, chunk_path) {
+            Ok(()) => {}
+            Err(_) if chunk_path.exists() => {
+                // Another thread beat us - clean up our temp file
+                if let Err(e) = fs::remove_file(temp_path) {
+                    warn!(
+                        path = %temp_path.display(),
+                        error = %e,
+                        "Failed to remove orphaned temp file after concurrent write"
+                    );
                 }
-                Err(e) => {
-                    // Actual error - clean up and return
-                    if let Err(cleanup_err) = fs::remove_file(&temp_path) {
-                        warn!(
-                            path = %temp_path.display(),
-                            error = %cleanup_err,
-                            "Failed to remove temp file after write error"
-                        );
-                    }
-                    return Err(ChunkStoreError::Io(e));
+                // This is not an error - the chunk exists with correct content
+            }
+            Err(e) => {
+                // Actual error - clean up and return
+                if let Err(cleanup_err) = fs::remove_file(temp_path) {
+                    warn!(
+                        path = %temp_path.display(),
+                        error = %cleanup_err,
+                        "Failed to remove temp file after write error"
+                    );
                 }
+                return Err(ChunkStoreError::Io(e));
             }
         }
 
-        Ok(hash)
+        Ok(())
     }
 
-    pub fn get(&self, hash: &str) -> Result<Vec<u8>, ChunkStoreError> {
+    /// Write a chunk into the local cache and evict past the capacity
+    fn cache_locally(
+        &self,
+        remote: &Remote,
+        hash: &str,
+        chunk_path: &Path,
+        stored: &[u8],
+    ) -> Result<(), ChunkStoreError> {
+        self.write_chunk_file(hash, chunk_path, stored)?;
+        for victim in remote.cache.insert(hash, stored.len() as u64) {
+            self.evict(&victim);
+        }
+        Ok(())
+    }
+
+    /// Drop a chunk from the local cache; the backend keeps its copy
+    fn evict(&self, hash: &str) {
+        let Ok(chunk_path) = self.hash_to_path(hash) else { return };
+        if let Err(e) = fs::remove_file(&chunk_path) {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                warn!(path = %chunk_path.display(), error = %e, "Failed to evict cached chunk");
+            }
+        }
+    }
+
+    /// Make sure a chunk is on local disk, fetching it from the backend on a
+    /// cache miss. No-op for purely local stores.
+    fn ensure_cached(&self, hash: &str, chunk_path: &Path) -> Result<(), ChunkStoreError> {
+        let Some(remote) = &self.remote else {
+            return Ok(());
+        };
+        if chunk_path.exists() {
+            if !remote.cache.touch(hash) {
+                // Cached by another process sharing the directory
+                let size = fs::metadata(chunk_path)?.len();
+                for victim in remote.cache.insert(hash, size) {
+                    self.evict(&victim);
+                }
+            }
+            return Ok(());
+        }
+        // Download to disk rather than memory: chunks may be large
+        let permit = self.handles.acquire(1)?;
+        let (spill, mut file) = SpillFile::create(&self.base_path)?;
+        let size = remote.backend.get_to(hash, &mut file)?;
+        drop(file);
+        drop(permit);
+        Self::install_chunk_file(spill.path(), chunk_path)?;
+        for victim in remote.cache.insert(hash, size) {
+            self.evict(&victim);
+        }
+        Ok(())
+    }
+
+    /// Run a read against the local chunk file, fetching it first if needed.
+    ///
+    /// A concurrent eviction can remove the file between the fetch and the
+    /// read; in that case the chunk is fetched once more.
+    fn read_local<T>(
+        &self,
+        hash: &str,
+        read: impl Fn(&Path) -> Result<T, ChunkStoreError>,
+    ) -> Result<T, ChunkStoreError> {
         self.validate_hash(hash)?;
         let chunk_path = self.hash_to_path(hash)?;
-
-        if !chunk_path.exists() {
-            return Err(ChunkStoreError::NotFound(hash.to_string()));
+        self.ensure_cached(hash, &chunk_path)?;
+        match read(&chunk_path) {
+            Err(ChunkStoreError::NotFound(_)) if self.remote.is_some() => {
+                self.ensure_cached(hash, &chunk_path)?;
+                read(&chunk_path)
+            }
+            result => result,
         }
+    }
 
-        Ok(fs::read(&chunk_path)?)
+    pub fn get(&self, hash: &str) -> Result<Vec<u8>, ChunkStoreError> {
+        let Some(memory) = &self.memory else {
+            return self.read_chunk(hash);
+        };
+        if let Some((data, _)) = memory.get(hash) {
+            return Ok(data);
+        }
+        let data = self.read_chunk(hash)?;
+        memory.insert(hash, &data, self.pipeline.verifies_on_read());
+        Ok(data)
     }
 
     /// Get chunk data with integrity verification.
     /// Returns error if the data doesn't hash to the expected value.
     pub fn get_verified(&self, hash: &str) -> Result<Vec<u8>, ChunkStoreError> {
-        let data = self.get(hash)?;
+        let Some(memory) = &self.memory else {
+            return self.read_verified(hash);
+        };
+        if let Some((data, verified)) = memory.get(hash) {
+            if verified {
+                return Ok(data);
+            }
+            if Self::blake3_hash(&data) == hash {
+                memory.mark_verified(hash);
+                return Ok(data);
+            }
+            // Read from storage again; a corrupt copy there fails below
+            memory.remove(hash);
+        }
+        let data = self.read_verified(hash)?;
+        memory.insert(hash, &data, true);
+        Ok(data)
+    }
+
+    /// `get` without the memory cache
+    fn read_chunk(&self, hash: &str) -> Result<Vec<u8>, ChunkStoreError> {
+        self.read_local(hash, |chunk_path| {
+            if !chunk_path.exists() {
+                return self.read_delta(hash)?.ok_or_else(|| ChunkStoreError::NotFound(hash.to_string()));
+            }
+
+            if self.pipeline.is_identity() && !self.pipeline.verifies_on_read() {
+                let _permit = self.handles.acquire(1)?;
+                return Ok(fs::read(chunk_path)?);
+            }
+            let expected = self.pipeline.verifies_on_read().then_some(hash);
+            self.read_through_pipeline(chunk_path, expected)
+        })
+    }
+
+    /// `get_verified` without the memory cache
+    fn read_verified(&self, hash: &str) -> Result<Vec<u8>, ChunkStoreError> {
+        if !self.pipeline.is_identity() {
+            // Decode and verify in the same pass
+            return self.read_local(hash, |chunk_path| {
+                if !chunk_path.exists() {
+                    // Reconstruction verifies every step of the chain
+                    return self.read_delta(hash)?.ok_or_else(|| ChunkStoreError::NotFound(hash.to_string()));
+                }
+                self.read_through_pipeline(chunk_path, Some(hash))
+            });
+        }
+
+        let data = self.read_chunk(hash)?;
         let actual_hash = Self::blake3_hash(&data);
 
         if actual_hash != hash {
@@ -156,6 +762,77 @@ impl ChunkStore {
         Ok(data)
     }
 
+    /// Open a chunk for streaming reads.
+    ///
+    /// With an identity pipeline the reader streams the chunk file itself.
+    /// Otherwise the chunk is decoded into a temporary file first (removed
+    /// when the reader is dropped), so memory use stays bounded either way.
+    /// Hashes are verified as `get` does: only if the store verifies reads.
+    /// Delta chunks are reconstructed in memory before being spilled.
+    pub fn get_stream(&self, hash: &str) -> Result<ChunkReader, ChunkStoreError> {
+        let verify = self.pipeline.verifies_on_read();
+        self.read_local(hash, |chunk_path| {
+            // Decoding holds the chunk file and its decoded copy at once
+            let mut permit = self.handles.acquire(if self.pipeline.is_identity() { 1 } else { 2 })?;
+            let file = match Self::open_chunk(chunk_path, hash) {
+                Ok(file) => file,
+                Err(ChunkStoreError::NotFound(_)) => {
+                    drop(permit);
+                    return self.delta_reader(hash);
+                }
+                Err(e) => return Err(e),
+            };
+            if self.pipeline.is_identity() {
+                return Ok(ChunkReader {
+                    len: file.metadata()?.len(),
+                    inner: BufReader::new(file),
+                    verify: verify.then(|| (hash.to_string(), blake3::Hasher::new())),
+                    _spill: None,
+                    _permit: permit,
+                });
+            }
+
+            let (spill, out) = SpillFile::create(&self.base_path)?;
+            let mut out = BufWriter::new(out);
+            let len = self
+                .pipeline
+                .decode_to(BufReader::new(file), &mut out, verify.then_some(hash))?;
+            out.flush()?;
+            drop(out);
+            permit.shrink_to(1);
+            Ok(ChunkReader {
+                inner: BufReader::new(File::open(spill.path())?),
+                len,
+                verify: None,
+                _spill: Some(spill),
+                _permit: permit,
+            })
+        })
+    }
+
+    /// Open a chunk file, reporting a missing file as `NotFound`
+    fn open_chunk(chunk_path: &Path, hash: &str) -> Result<File, ChunkStoreError> {
+        File::open(chunk_path).map_err(|e| {
+            if e.kind() == io::ErrorKind::NotFound {
+                ChunkStoreError::NotFound(hash.to_string())
+            } else {
+                ChunkStoreError::Io(e)
+            }
+        })
+    }
+
+    /// Stream a stored chunk through the pipeline into a single buffer
+    fn read_through_pipeline(
+        &self,
+        chunk_path: &Path,
+        expected_hash: Option<&str>,
+    ) -> Result<Vec<u8>, ChunkStoreError> {
+        let _permit = self.handles.acquire(1)?;
+        let file = File::open(chunk_path)?;
+        let size_hint = file.metadata()?.len() as usize;
+        self.pipeline.decode(BufReader::new(file), expected_hash, size_hint)
+    }
+
     /// Get a memory-mapped view of a chunk.
     ///
     /// This is faster than `get()` for large chunks because:
@@ -180,23 +857,38 @@ impl ChunkStore {
     /// # Errors
     /// - `ChunkStoreError::NotFound` if the chunk doesn't exist
     /// - `ChunkStoreError::InvalidHash` if the hash format is invalid
+    /// - `ChunkStoreError::Pipeline` if chunks are stored through a
+    ///   non-identity pipeline (the mapped bytes would not be the chunk)
     /// - `ChunkStoreError::Io` for other I/O errors
+    ///
+    /// A delta chunk is materialized (stored in full) to be mapped.
     pub fn get_mmap(&self, hash: &str) -> Result<ChunkMmap, ChunkStoreError> {
-        self.validate_hash(hash)?;
-        let chunk_path = self.hash_to_path(hash)?;
-
-        let file = File::open(&chunk_path).map_err(|e| {
-            if e.kind() == std::io::ErrorKind::NotFound {
-                ChunkStoreError::NotFound(hash.to_string())
-            } else {
-                ChunkStoreError::Io(e)
-            }
-        })?;
+        if !self.pipeline.is_identity() {
+            return Err(ChunkStoreError::Pipeline(format!(
+                "memory-mapped reads are unavailable with stages [{}]",
+                self.pipeline.stage_names().join(", ")
+            )));
+        }
+        self.read_local(hash, |chunk_path| {
+            let mut permit = self.handles.acquire(1)?;
+            let file = match Self::open_chunk(chunk_path, hash) {
+                Err(ChunkStoreError::NotFound(_)) => {
+                    // Materializing takes handles of its own
+                    drop(permit);
+                    if !self.materialize(hash)? {
+                        return Err(ChunkStoreError::NotFound(hash.to_string()));
+                    }
+                    permit = self.handles.acquire(1)?;
+                    Self::open_chunk(chunk_path, hash)?
+                }
+                result => result?,
+            };
 
-        // SAFETY: We're only reading the file, and chunks are immutable once written
-        // (content-addressed storage guarantees this)
-        let mmap = unsafe { Mmap::map(&file) }.map_err(ChunkStoreError::Io)?;
-        Ok(ChunkMmap::new(file, mmap))
+            // SAFETY: We're only reading the file, and chunks are immutable once written
+            // (content-addressed storage guarantees this)
+            let mmap = unsafe { Mmap::map(&file) }.map_err(ChunkStoreError::Io)?;
+            Ok(ChunkMmap::new(file, mmap, permit))
+        })
     }
 
     /// Get memory-mapped views of multiple chunks in parallel.
@@ -209,7 +901,17 @@ impl ChunkStore {
     ///
     /// # Returns
     /// Vector of memory-mapped views in the same order as input hashes
+    ///
+    /// # Errors
+    /// - `ChunkStoreError::TooManyOpenFiles` if `hashes` has more chunks
+    ///   than the store's open file limit (every mapping keeps its file open)
     pub fn get_mmap_batch(&self, hashes: &[&str]) -> Result<Vec<ChunkMmap>, ChunkStoreError> {
+        if let Some(limit) = self.handles.limit().filter(|&limit| hashes.len() > limit) {
+            return Err(ChunkStoreError::TooManyOpenFiles {
+                limit: Some(limit),
+                waited_ms: 0,
+            });
+        }
         hashes
             .par_iter()
             .map(|hash| self.get_mmap(hash))
@@ -218,16 +920,70 @@ impl ChunkStore {
 
     pub fn exists(&self, hash: &str) -> Result<bool, ChunkStoreError> {
         self.validate_hash(hash)?;
-        Ok(self.hash_to_path(hash)?.exists())
+        if self.hash_to_path(hash)?.exists() || self.delta_path(hash)?.exists() {
+            return Ok(true);
+        }
+        match &self.remote {
+            Some(remote) => remote.backend.exists(hash),
+            None => Ok(false),
+        }
+    }
+
+    /// Stored size and last modification time of a chunk.
+    ///
+    /// With a backend, the modification time is the later of the remote
+    /// object's and the locally cached copy's (which deduplicated writes
+    /// refresh).
+    pub fn stat(&self, hash: &str) -> Result<ChunkStat, ChunkStoreError> {
+        self.validate_hash(hash)?;
+        let chunk_path = self.hash_to_path(hash)?;
+        let metadata = match fs::metadata(&chunk_path) {
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => fs::metadata(self.delta_path(hash)?),
+            result => result,
+        };
+        let local = match metadata {
+            Ok(meta) => Some(ChunkStat {
+                size: meta.len(),
+                modified: meta.modified().ok(),
+            }),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+            Err(e) => return Err(e.into()),
+        };
+        match (&self.remote, local) {
+            (None, Some(stat)) => Ok(stat),
+            (None, None) => Err(ChunkStoreError::NotFound(hash.to_string())),
+            (Some(remote), local) => {
+                let mut stat = remote.backend.stat(hash)?;
+                if let Some(cached) = local.and_then(|l| l.modified) {
+                    stat.modified = Some(stat.modified.map_or(cached, |m| m.max(cached)));
+                }
+                Ok(stat)
+            }
+        }
     }
 
     pub fn delete(&self, hash: &str) -> Result<(), ChunkStoreError> {
         self.validate_hash(hash)?;
         let chunk_path = self.hash_to_path(hash)?;
 
+        if let Some(remote) = &self.remote {
+            remote.backend.delete(hash)?;
+            remote.cache.remove(hash);
+        }
+        if let Some(memory) = &self.memory {
+            memory.remove(hash);
+        }
+
         if chunk_path.exists() {
             fs::remove_file(&chunk_path)?;
         }
+        let delta_path = self.delta_path(hash)?;
+        if delta_path.exists() {
+            fs::remove_file(&delta_path)?;
+        }
+        if let Some(journal) = &self.journal {
+            journal.record_delete(hash)?;
+        }
 
         Ok(())
     }
@@ -304,12 +1060,21 @@ impl ChunkStore {
     // Garbage Collection
     // =========================================================================
 
-    /// List all chunk hashes stored on disk.
+    /// List all chunk hashes in the store.
     ///
     /// Walks the content-addressed directory tree and returns the hash of every
-    /// chunk file (excluding `.tmp` files). This is used by garbage collection
-    /// to identify unreferenced chunks.
+    /// chunk file (excluding `.tmp` files), or lists the backend when chunks
+    /// live remotely. This is used by garbage collection to identify
+    /// unreferenced chunks.
     pub fn list_chunk_hashes(&self) -> Result<Vec<String>, ChunkStoreError> {
+        match &self.remote {
+            Some(remote) => remote.backend.list(),
+            None => self.list_local_hashes(),
+        }
+    }
+
+    /// Hashes of the chunk files in the local directory
+    fn list_local_hashes(&self) -> Result<Vec<String>, ChunkStoreError> {
         let files = self.walk_directory(&self.base_path)?;
         let mut hashes = Vec::new();
 
@@ -322,10 +1087,18 @@ impl ChunkStore {
             }
 
             // The chunk file name IS the hash
-            if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
-                if name.len() == EXPECTED_HASH_LEN && name.chars().all(|c| c.is_ascii_hexdigit()) {
-                    hashes.push(name.to_string());
+            let Some(mut name) = path.file_name().and_then(|n| n.to_str()) else {
+                continue;
+            };
+            if let Some(hash) = name.strip_suffix(".delta") {
+                // Listed once if a full copy exists too
+                if path.with_extension("").exists() {
+                    continue;
                 }
+                name = hash;
+            }
+            if name.len() == EXPECTED_HASH_LEN && name.chars().all(|c| c.is_ascii_hexdigit()) {
+                hashes.push(name.to_string());
             }
         }
 
@@ -336,7 +1109,8 @@ impl ChunkStore {
     ///
     /// Compares all chunks on disk against the set of `referenced_hashes`
     /// (typically collected from all catalog versions across all tables and
-    /// branches). Any chunk not in the referenced set is deleted.
+    /// branches). Any chunk not in the referenced set, nor the base of a
+    /// referenced delta chunk, is deleted.
     ///
     /// Returns the number of chunks deleted and the number of deletion failures.
     ///
@@ -348,11 +1122,12 @@ impl ChunkStore {
         referenced_hashes: &std::collections::HashSet<String>,
     ) -> Result<(usize, usize), ChunkStoreError> {
         let all_hashes = self.list_chunk_hashes()?;
+        let bases = self.delta_bases(referenced_hashes.iter().map(String::as_str))?;
         let mut deleted = 0;
         let mut failed = 0;
 
         for hash in all_hashes {
-            if !referenced_hashes.contains(&hash) {
+            if !referenced_hashes.contains(&hash) && !bases.contains(&hash) {
                 match self.delete(&hash) {
                     Ok(()) => deleted += 1,
                     Err(e) => {
@@ -366,6 +1141,235 @@ impl ChunkStore {
         Ok((deleted, failed))
     }
 
+    // =========================================================================
+    // Delta Chunks
+    // =========================================================================
+
+    /// Store `data` as a binary delta against `base_hash` when worthwhile.
+    ///
+    /// Returns the same hash as `put`, and reads reconstruct the chunk
+    /// transparently. The chunk is stored in full instead when the delta is
+    /// not under half its size, when the chain through `base_hash` would
+    /// exceed the store's `max_delta_chain`, or when chunks live in a
+    /// backend (delta chunks are local only).
+    ///
+    /// Garbage collection keeps the bases of retained delta chunks;
+    /// deleting a base directly makes the deltas built on it unreadable.
+    ///
+    /// # Errors
+    /// - `ChunkStoreError::NotFound` if `base_hash` is not stored
+    pub fn put_delta(&self, data: &[u8], base_hash: &str) -> Result<String, ChunkStoreError> {
+        if !self.exists(base_hash)? {
+            return Err(ChunkStoreError::NotFound(base_hash.to_string()));
+        }
+        let hash = Self::blake3_hash(data);
+        if self.remote.is_some() || self.max_delta_chain == 0 || hash == base_hash {
+            return self.put(data);
+        }
+        let chunk_path = self.hash_to_path(&hash)?;
+        let delta_path = self.delta_path(&hash)?;
+        for path in [&chunk_path, &delta_path] {
+            if path.exists() {
+                self.refresh_mtime(path);
+                return Ok(hash);
+            }
+        }
+
+        let depth = self.delta_info(base_hash)?.map_or(1, |info| info.depth + 1);
+        if depth > self.max_delta_chain {
+            return self.put(data);
+        }
+        let ops = delta::diff(&self.get(base_hash)?, data);
+        if ops.len() >= data.len() / 2 {
+            return self.put(data);
+        }
+
+        let mut stored = DeltaInfo {
+            base: base_hash.to_string(),
+            depth,
+        }
+        .header();
+        stored.extend_from_slice(&self.pipeline.encode(&ops)?);
+        self.write_chunk_file(&hash, &delta_path, &stored)?;
+        self.journal_put(|codec| Ok(JournalEntry::new(&hash, &stored, codec, true)));
+        Ok(hash)
+    }
+
+    /// Base and chain depth of a delta chunk, or `None` for a chunk stored
+    /// in full.
+    pub fn delta_info(&self, hash: &str) -> Result<Option<DeltaInfo>, ChunkStoreError> {
+        self.validate_hash(hash)?;
+        if self.hash_to_path(hash)?.exists() {
+            return Ok(None);
+        }
+        let _permit = self.handles.acquire(1)?;
+        match File::open(self.delta_path(hash)?) {
+            Ok(file) => DeltaInfo::read_header(&mut BufReader::new(file)).map(Some),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => match self.exists(hash)? {
+                true => Ok(None),
+                false => Err(ChunkStoreError::NotFound(hash.to_string())),
+            },
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Store a delta chunk in full. Returns false if `hash` was not stored
+    /// as a delta.
+    ///
+    /// Deltas built on the chunk keep working: their base is now a full
+    /// chunk.
+    pub fn materialize(&self, hash: &str) -> Result<bool, ChunkStoreError> {
+        self.validate_hash(hash)?;
+        if self.hash_to_path(hash)?.exists() {
+            return Ok(false);
+        }
+        match self.read_delta(hash)? {
+            Some(data) => {
+                self.replace_delta(hash, &data)?;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    /// Bases of the delta chains leading to `hashes`, followed to their
+    /// full chunks. Chunks in the result must be kept for `hashes` to
+    /// stay readable.
+    pub fn delta_bases<'a>(
+        &self,
+        hashes: impl IntoIterator<Item = &'a str>,
+    ) -> Result<HashSet<String>, ChunkStoreError> {
+        let deltas = self.delta_chunks()?;
+        let mut bases = HashSet::new();
+        if deltas.is_empty() {
+            return Ok(bases);
+        }
+        for hash in hashes {
+            let mut current = hash;
+            while let Some(info) = deltas.get(current) {
+                if !bases.insert(info.base.clone()) {
+                    break;
+                }
+                current = &info.base;
+            }
+        }
+        Ok(bases)
+    }
+
+    /// Every chunk stored only as a delta, with its header
+    fn delta_chunks(&self) -> Result<HashMap<String, DeltaInfo>, ChunkStoreError> {
+        let mut deltas = HashMap::new();
+        if self.remote.is_some() {
+            return Ok(deltas);
+        }
+        for path in self.walk_directory(&self.base_path)? {
+            if path.extension().is_none_or(|ext| ext != DELTA_EXT) || path.with_extension("").exists() {
+                continue;
+            }
+            let Some(hash) = path.file_stem().and_then(|s| s.to_str()) else {
+                continue;
+            };
+            let _permit = self.handles.acquire(1)?;
+            let file = match File::open(&path) {
+                Ok(file) => file,
+                // Materialized or deleted concurrently
+                Err(e) if e.kind() == io::ErrorKind::NotFound => continue,
+                Err(e) => return Err(e.into()),
+            };
+            deltas.insert(hash.to_string(), DeltaInfo::read_header(&mut BufReader::new(file))?);
+        }
+        Ok(deltas)
+    }
+
+    /// Rebuild a delta chunk from its chain, or `None` if `hash` is not
+    /// stored as a delta. Every step is checked against its hash.
+    ///
+    /// A chain longer than `max_delta_chain` (written under a higher limit)
+    /// is stored in full once read.
+    fn read_delta(&self, hash: &str) -> Result<Option<Vec<u8>>, ChunkStoreError> {
+        let mut chain: Vec<(String, DeltaInfo, Vec<u8>)> = Vec::new();
+        let mut current = hash.to_string();
+        loop {
+            if !chain.is_empty() && self.hash_to_path(&current)?.exists() {
+                break;
+            }
+            let _permit = self.handles.acquire(1)?;
+            let mut reader = match File::open(self.delta_path(&current)?) {
+                Ok(file) => BufReader::new(file),
+                Err(e) if e.kind() == io::ErrorKind::NotFound && chain.is_empty() => return Ok(None),
+                // A missing base is reported by `get` below
+                Err(e) if e.kind() == io::ErrorKind::NotFound => break,
+                Err(e) => return Err(e.into()),
+            };
+            let info = DeltaInfo::read_header(&mut reader)?;
+            if chain.last().is_some_and(|(_, prev, _)| info.depth >= prev.depth) {
+                return Err(ChunkStoreError::CorruptDelta(format!(
+                    "chain depth does not decrease at {}",
+                    current
+                )));
+            }
+            let ops = self.pipeline.decode(reader, None, 0)?;
+            let base = info.base.clone();
+            chain.push((current, info, ops));
+            current = base;
+        }
+
+        let mut data = self.get(&current)?;
+        for (expected, _, ops) in chain.iter().rev() {
+            data = delta::apply(&data, ops)?;
+            let actual = Self::blake3_hash(&data);
+            if actual != *expected {
+                return Err(ChunkStoreError::HashMismatch {
+                    expected: expected.clone(),
+                    actual,
+                });
+            }
+        }
+
+        if chain[0].1.depth > self.max_delta_chain {
+            if let Err(e) = self.replace_delta(hash, &data) {
+                warn!(hash = %hash, error = %e, "Failed to materialize long delta chain");
+            }
+        }
+        Ok(Some(data))
+    }
+
+    /// Write the full chunk for a delta, then drop the delta file
+    fn replace_delta(&self, hash: &str, data: &[u8]) -> Result<(), ChunkStoreError> {
+        let chunk_path = self.hash_to_path(hash)?;
+        let encoded;
+        let stored = if self.pipeline.is_identity() {
+            data
+        } else {
+            encoded = self.pipeline.encode(data)?;
+            &encoded
+        };
+        self.write_chunk_file(hash, &chunk_path, stored)?;
+        self.journal_put(|codec| Ok(JournalEntry::new(hash, stored, codec, false)));
+        match fs::remove_file(self.delta_path(hash)?) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+
+    /// Reader over a reconstructed delta chunk, spilled to disk
+    fn delta_reader(&self, hash: &str) -> Result<ChunkReader, ChunkStoreError> {
+        let data = self
+            .read_delta(hash)?
+            .ok_or_else(|| ChunkStoreError::NotFound(hash.to_string()))?;
+        let permit = self.handles.acquire(1)?;
+        let (spill, mut file) = SpillFile::create(&self.base_path)?;
+        file.write_all(&data)?;
+        drop(file);
+        Ok(ChunkReader {
+            inner: BufReader::new(File::open(spill.path())?),
+            len: data.len() as u64,
+            verify: None,
+            _spill: Some(spill),
+            _permit: permit,
+        })
+    }
+
     // =========================================================================
     // Batch Operations (Parallel)
     // =========================================================================
@@ -395,10 +1399,28 @@ impl ChunkStore {
     /// # std::fs::remove_dir_all(&dir).ok();
     /// ```
     pub fn put_batch(&self, chunks: &[&[u8]]) -> Result<Vec<String>, ChunkStoreError> {
-        chunks
+        self.put_many(chunks)
+    }
+
+    /// Store many chunks at once, writing each distinct chunk once.
+    ///
+    /// Hashing and the writes (or backend uploads) run on the Rayon pool,
+    /// so a version with hundreds of chunks costs a few rounds of
+    /// filesystem or network latency instead of hundreds. Returns the
+    /// hashes in input order, duplicates included.
+    pub fn put_many(&self, chunks: &[&[u8]]) -> Result<Vec<String>, ChunkStoreError> {
+        let hashes: Vec<String> = chunks.par_iter().map(|data| Self::blake3_hash(data)).collect();
+        let mut seen = HashSet::new();
+        let distinct: Vec<(&str, &[u8])> = hashes
+            .iter()
+            .zip(chunks)
+            .filter(|(hash, _)| seen.insert(hash.as_str()))
+            .map(|(hash, data)| (hash.as_str(), *data))
+            .collect();
+        distinct
             .par_iter()
-            .map(|data| self.put(data))
-            .collect()
+            .try_for_each(|(hash, data)| self.put_hashed(hash, data))?;
+        Ok(hashes)
     }
 
     /// Retrieve multiple chunks in parallel by their hashes.
@@ -426,10 +1448,19 @@ impl ChunkStore {
     /// # std::fs::remove_dir_all(&dir).ok();
     /// ```
     pub fn get_batch(&self, hashes: &[&str]) -> Result<Vec<Vec<u8>>, ChunkStoreError> {
-        hashes
-            .par_iter()
-            .map(|hash| self.get(hash))
-            .collect()
+        self.get_many(hashes)
+    }
+
+    /// Read many chunks at once, in parallel, reading each distinct hash
+    /// once. Results are in input order; the first failure fails the batch.
+    pub fn get_many(&self, hashes: &[&str]) -> Result<Vec<Vec<u8>>, ChunkStoreError> {
+        self.read_many(hashes, |hash| self.get(hash))
+    }
+
+    /// Check many chunks for existence at once, in parallel. Results are
+    /// in input order.
+    pub fn exists_many(&self, hashes: &[&str]) -> Result<Vec<bool>, ChunkStoreError> {
+        hashes.par_iter().map(|hash| self.exists(hash)).collect()
     }
 
     /// Retrieve multiple chunks with verification in parallel.
@@ -446,10 +1477,26 @@ impl ChunkStore {
     /// # Errors
     /// Returns `ChunkStoreError::HashMismatch` if any chunk fails verification
     pub fn get_batch_verified(&self, hashes: &[&str]) -> Result<Vec<Vec<u8>>, ChunkStoreError> {
-        hashes
+        self.read_many(hashes, |hash| self.get_verified(hash))
+    }
+
+    /// Run `read` once per distinct hash in parallel, then lay the results
+    /// out in input order
+    fn read_many(
+        &self,
+        hashes: &[&str],
+        read: impl Fn(&str) -> Result<Vec<u8>, ChunkStoreError> + Sync,
+    ) -> Result<Vec<Vec<u8>>, ChunkStoreError> {
+        let mut seen = HashSet::new();
+        let distinct: Vec<&str> = hashes.iter().copied().filter(|hash| seen.insert(*hash)).collect();
+        if distinct.len() == hashes.len() {
+            return hashes.par_iter().map(|hash| read(hash)).collect();
+        }
+        let data = distinct
             .par_iter()
-            .map(|hash| self.get_verified(hash))
-            .collect()
+            .map(|hash| Ok((*hash, read(hash)?)))
+            .collect::<Result<HashMap<&str, Vec<u8>>, ChunkStoreError>>()?;
+        Ok(hashes.iter().map(|hash| data[hash].clone()).collect())
     }
 
     /// Validate that a hash string is properly formatted.
@@ -469,6 +1516,10 @@ impl ChunkStore {
         Ok(())
     }
 
+    fn delta_path(&self, hash: &str) -> Result<PathBuf, ChunkStoreError> {
+        Ok(self.hash_to_path(hash)?.with_extension(DELTA_EXT))
+    }
+
     fn hash_to_path(&self, hash: &str) -> Result<PathBuf, ChunkStoreError> {
         // For internal use after put(), we trust the hash is valid
         // For external use, validate_hash should be called first
@@ -488,6 +1539,7 @@ impl ChunkStore {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::error::ErrorInfo;
     use std::fs;
     use std::io::Write;
 
@@ -1253,4 +2305,533 @@ mod tests {
 
         fs::remove_dir_all(&dir).ok();
     }
+
+    // =========================================================================
+    // Streaming
+    // =========================================================================
+
+    fn no_spill_files(dir: &Path) -> bool {
+        fs::read_dir(dir).unwrap().all(|e| !e.unwrap().file_name().to_string_lossy().ends_with(".tmp"))
+    }
+
+    #[test]
+    fn test_put_chunked_dedups_across_versions() {
+        let dir = temp_dir();
+        let store = ChunkStore::new(&dir).unwrap().with_chunker(Chunker::content_defined(1024, 4096, 16384));
+        let mut state = 7u64;
+        let v1: Vec<u8> = (0..500_000)
+            .map(|_| {
+                state = state.wrapping_mul(6364136223846793005).wrapping_add(1);
+                (state >> 56) as u8
+            })
+            .collect();
+        let mut v2 = v1.clone();
+        v2.splice(250_000..250_000, b"a small edit".iter().copied());
+
+        let h1 = store.put_chunked(&v1[..]).unwrap();
+        let h2 = store.put_chunked(&v2[..]).unwrap();
+        assert_eq!(store.get_batch(&h1.iter().map(String::as_str).collect::<Vec<_>>()).unwrap().concat(), v1);
+        let new: Vec<_> = h2.iter().filter(|h| !h1.contains(h)).collect();
+        assert!(new.len() <= 2, "{} of {} chunks rewritten", new.len(), h2.len());
+        assert!(store.put_chunked(&b""[..]).unwrap().is_empty());
+
+        let fixed = ChunkStore::with_config(&dir, ChunkStoreConfig::new().with_chunker(Chunker::fixed(100_000))).unwrap();
+        assert_eq!(fixed.put_chunked(&v1[..]).unwrap().len(), 5);
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_put_stream_matches_put() {
+        let dir = temp_dir();
+        let store = ChunkStore::new(&dir).unwrap();
+        let data: Vec<u8> = (0..3_000_000u32).map(|i| (i % 251) as u8).collect();
+
+        // Short reads exercise the incremental hashing
+        struct Trickle<'a>(&'a [u8]);
+        impl Read for Trickle<'_> {
+            fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+                let n = buf.len().min(self.0.len()).min(1000);
+                buf[..n].copy_from_slice(&self.0[..n]);
+                self.0 = &self.0[n..];
+                Ok(n)
+            }
+        }
+        let hash = store.put_stream(Trickle(&data)).unwrap();
+        assert_eq!(hash, ChunkStore::blake3_hash(&data));
+        assert_eq!(store.put(&data).unwrap(), hash);
+        assert_eq!(store.put_stream(&data[..]).unwrap(), hash);
+        assert!(no_spill_files(&dir));
+
+        let mut reader = store.get_stream(&hash).unwrap();
+        assert_eq!(reader.len(), data.len() as u64);
+        let mut read = Vec::new();
+        reader.read_to_end(&mut read).unwrap();
+        assert_eq!(read, data);
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_streaming_through_pipeline() {
+        let dir = temp_dir();
+        let config = ChunkStoreConfig::new()
+            .with_compression(ChunkCompression::zstd())
+            .with_verify_on_read(true);
+        let store = ChunkStore::with_config(&dir, config).unwrap();
+        let data: Vec<u8> = (0..500_000u32).flat_map(|i| (i % 100).to_le_bytes()).collect();
+
+        let hash = store.put_stream(&data[..]).unwrap();
+        let path = store.hash_to_path(&hash).unwrap();
+        assert!(fs::metadata(&path).unwrap().len() < data.len() as u64 / 4);
+        assert_eq!(store.get_verified(&hash).unwrap(), data);
+
+        let mut reader = store.get_stream(&hash).unwrap();
+        assert!(!no_spill_files(&dir));
+        let mut read = Vec::new();
+        reader.read_to_end(&mut read).unwrap();
+        assert_eq!(read, data);
+        drop(reader);
+        assert!(no_spill_files(&dir));
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_get_stream_verifies_at_end() {
+        let dir = temp_dir();
+        let store = ChunkStore::new(&dir)
+            .unwrap()
+            .with_pipeline(ChunkPipeline::new().verify_on_read(true));
+        let hash = store.put(b"original").unwrap();
+        fs::write(store.hash_to_path(&hash).unwrap(), b"tampered").unwrap();
+
+        let err = store.get_stream(&hash).unwrap().read_to_end(&mut Vec::new()).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+        assert!(matches!(
+            err.into_inner().unwrap().downcast::<ChunkStoreError>().as_deref(),
+            Ok(ChunkStoreError::HashMismatch { .. })
+        ));
+        assert!(matches!(store.get_stream(&fake_valid_hash()), Err(ChunkStoreError::NotFound(_))));
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    // =========================================================================
+    // Delta Chunks
+    // =========================================================================
+
+    /// Slowly changing chunk: each version edits a few bytes of the last
+    fn versions(count: usize) -> Vec<Vec<u8>> {
+        let mut current: Vec<u8> = (0..100_000u32).map(|i| (i.wrapping_mul(2654435761) >> 24) as u8).collect();
+        (0..count)
+            .map(|v| {
+                current[v * 1000..v * 1000 + 8].copy_from_slice(&(v as u64).to_le_bytes());
+                current.clone()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_delta_chunks_reconstruct_transparently() {
+        let dir = temp_dir();
+        let store = ChunkStore::new(&dir).unwrap();
+        let versions = versions(4);
+
+        let mut hashes = vec![store.put(&versions[0]).unwrap()];
+        for data in &versions[1..] {
+            let hash = store.put_delta(data, hashes.last().unwrap()).unwrap();
+            assert_eq!(hash, ChunkStore::blake3_hash(data));
+            hashes.push(hash);
+        }
+
+        assert_eq!(store.delta_info(&hashes[0]).unwrap(), None);
+        let info = store.delta_info(&hashes[3]).unwrap().unwrap();
+        assert_eq!((info.base.as_str(), info.depth), (hashes[2].as_str(), 3));
+        assert!(store.stat(&hashes[3]).unwrap().size < 1000);
+
+        for (hash, data) in hashes.iter().zip(&versions) {
+            assert!(store.exists(hash).unwrap());
+            assert_eq!(&store.get(hash).unwrap(), data);
+            assert_eq!(&store.get_verified(hash).unwrap(), data);
+            let mut streamed = Vec::new();
+            store.get_stream(hash).unwrap().read_to_end(&mut streamed).unwrap();
+            assert_eq!(&streamed, data);
+        }
+        let mut listed = store.list_chunk_hashes().unwrap();
+        listed.sort();
+        let mut expected = hashes.clone();
+        expected.sort();
+        assert_eq!(listed, expected);
+
+        // Mapping needs the bytes on disk; deltas built on it keep working
+        assert_eq!(&store.get_mmap(&hashes[1]).unwrap()[..], &versions[1][..]);
+        assert_eq!(store.delta_info(&hashes[1]).unwrap(), None);
+        assert_eq!(store.get(&hashes[3]).unwrap(), versions[3]);
+        assert!(!store.materialize(&hashes[1]).unwrap());
+
+        assert!(no_spill_files(&dir));
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_delta_chain_limit_materializes() {
+        let dir = temp_dir();
+        let store = ChunkStore::new(&dir).unwrap().with_max_delta_chain(2);
+        let versions = versions(4);
+
+        let mut hashes = vec![store.put(&versions[0]).unwrap()];
+        for data in &versions[1..] {
+            hashes.push(store.put_delta(data, hashes.last().unwrap()).unwrap());
+        }
+        let depths: Vec<_> = hashes
+            .iter()
+            .map(|h| store.delta_info(h).unwrap().map_or(0, |info| info.depth))
+            .collect();
+        assert_eq!(depths, vec![0, 1, 2, 0]);
+
+        // Unrelated data is not worth a delta
+        let other: Vec<u8> = (0..50_000u64)
+            .map(|i| (i.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407) >> 56) as u8)
+            .collect();
+        let other_hash = store.put_delta(&other, &hashes[0]).unwrap();
+        assert_eq!(store.delta_info(&other_hash).unwrap(), None);
+
+        // Chains written under a higher limit are stored in full once read
+        let strict = ChunkStore::new(&dir).unwrap().with_max_delta_chain(1);
+        assert_eq!(strict.get(&hashes[2]).unwrap(), versions[2]);
+        assert_eq!(strict.delta_info(&hashes[2]).unwrap(), None);
+        assert_eq!(strict.delta_info(&hashes[1]).unwrap().unwrap().depth, 1);
+
+        assert!(matches!(
+            store.put_delta(b"data", &fake_valid_hash()),
+            Err(ChunkStoreError::NotFound(_))
+        ));
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_delta_chunks_through_pipeline_and_gc() {
+        let dir = temp_dir();
+        let config = ChunkStoreConfig::new().with_compression(ChunkCompression::zstd());
+        let store = ChunkStore::with_config(&dir, config).unwrap();
+        let versions = versions(3);
+        let base = store.put(&versions[0]).unwrap();
+        let mid = store.put_delta(&versions[1], &base).unwrap();
+        let head = store.put_delta(&versions[2], &mid).unwrap();
+        assert_eq!(store.get(&head).unwrap(), versions[2]);
+
+        // Only the head is referenced: its chain is kept
+        let referenced: std::collections::HashSet<String> = [head.clone()].into();
+        assert_eq!(
+            store.delta_bases([head.as_str()]).unwrap(),
+            [base.clone(), mid.clone()].into()
+        );
+        assert_eq!(store.garbage_collect(&referenced).unwrap(), (0, 0));
+        assert_eq!(store.get(&head).unwrap(), versions[2]);
+
+        // A corrupted delta is caught by the chain's hash checks
+        let path = store.delta_path(&mid).unwrap();
+        let mut stored = fs::read(&path).unwrap();
+        stored.truncate(delta::HEADER_LEN);
+        stored.extend_from_slice(&store.pipeline().encode(&delta::diff(&versions[0], b"other")).unwrap());
+        fs::write(&path, stored).unwrap();
+        assert!(matches!(store.get(&head), Err(ChunkStoreError::HashMismatch { .. })));
+
+        store.delete(&mid).unwrap();
+        assert!(!path.exists());
+        assert!(matches!(store.get(&head), Err(ChunkStoreError::NotFound(_))));
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    // =========================================================================
+    // Remote Backend
+    // =========================================================================
+
+    /// In-memory backend that counts fetches and keeps tags
+    #[derive(Default)]
+    struct MemoryBackend {
+        chunks: parking_lot::Mutex<std::collections::HashMap<String, Vec<u8>>>,
+        tags: parking_lot::Mutex<std::collections::HashMap<String, ChunkTags>>,
+        gets: std::sync::atomic::AtomicUsize,
+    }
+
+    impl ChunkBackend for MemoryBackend {
+        fn name(&self) -> &str {
+            "memory"
+        }
+
+        fn put(&self, hash: &str, data: &[u8]) -> Result<(), ChunkStoreError> {
+            self.chunks.lock().insert(hash.to_string(), data.to_vec());
+            Ok(())
+        }
+
+        fn get(&self, hash: &str) -> Result<Vec<u8>, ChunkStoreError> {
+            self.gets.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            self.chunks
+                .lock()
+                .get(hash)
+                .cloned()
+                .ok_or_else(|| ChunkStoreError::NotFound(hash.to_string()))
+        }
+
+        fn exists(&self, hash: &str) -> Result<bool, ChunkStoreError> {
+            Ok(self.chunks.lock().contains_key(hash))
+        }
+
+        fn delete(&self, hash: &str) -> Result<(), ChunkStoreError> {
+            self.chunks.lock().remove(hash);
+            Ok(())
+        }
+
+        fn list(&self) -> Result<Vec<String>, ChunkStoreError> {
+            Ok(self.chunks.lock().keys().cloned().collect())
+        }
+
+        fn set_tags(&self, hash: &str, tags: &ChunkTags) -> Result<(), ChunkStoreError> {
+            if !self.chunks.lock().contains_key(hash) {
+                return Err(ChunkStoreError::NotFound(hash.to_string()));
+            }
+            self.tags.lock().insert(hash.to_string(), tags.clone());
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_committed_chunks_are_tagged() {
+        use crate::catalog::FileCatalog;
+        use crate::transaction::{TableWrite, TransactionManager};
+
+        let dir = temp_dir();
+        let backend = Arc::new(MemoryBackend::default());
+        let store = Arc::new(ChunkStore::new(dir.join("chunks")).unwrap().with_backend(backend.clone(), 1 << 20).unwrap());
+        let catalog = Arc::new(FileCatalog::new(dir.join("catalog")).unwrap());
+
+        let batch = arrow::record_batch::RecordBatch::try_from_iter(vec![(
+            "id",
+            Arc::new(arrow::array::Int64Array::from(vec![1, 2])) as _,
+        )])
+        .unwrap();
+        let written = crate::table::TableWriter::new(&store, &catalog).write("users", &[batch]).unwrap();
+        let tags = backend.tags.lock().get(&written.chunk_hashes[0]).cloned();
+        assert_eq!(tags, Some(ChunkTags::new("users", 1)));
+
+        let manager = TransactionManager::new(dir.join("tx"), Arc::clone(&catalog), None).unwrap();
+        manager.tag_chunks_on_commit(Arc::clone(&store));
+        let chunk = store.put(b"order rows").unwrap();
+        let tx_id = manager.begin(None).unwrap();
+        manager.add_write(tx_id, TableWrite::new("orders", 1, vec![chunk.clone()])).unwrap();
+        manager.commit(tx_id).unwrap();
+        let tags = backend.tags.lock().get(&chunk).cloned().unwrap();
+        assert_eq!(tags, ChunkTags::new("orders", 1).with_tx_id(tx_id));
+        assert_eq!(tags.pairs().last(), Some(&("rhizo-tx", tx_id.to_string())));
+
+        // Local stores have nothing to tag
+        let local = ChunkStore::new(dir.join("local")).unwrap();
+        let hash = local.put(b"x").unwrap();
+        local.tag_chunks(&[hash], &ChunkTags::new("users", 1)).unwrap();
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_backend_write_through_and_cache_eviction() {
+        let dir = temp_dir();
+        let backend = Arc::new(MemoryBackend::default());
+        let store = ChunkStore::new(&dir)
+            .unwrap()
+            .with_backend(backend.clone(), 100)
+            .unwrap();
+
+        let a = store.put(&[1u8; 60]).unwrap();
+        let b = store.put(&[2u8; 60]).unwrap();
+        assert!(backend.exists(&a).unwrap() && backend.exists(&b).unwrap());
+
+        // Caching `b` evicted `a` locally; the backend still serves it
+        assert!(!store.hash_to_path(&a).unwrap().exists());
+        assert_eq!(store.cached_bytes(), 60);
+        assert!(store.exists(&a).unwrap());
+        assert_eq!(store.get(&a).unwrap(), vec![1u8; 60]);
+        assert_eq!(backend.gets.load(std::sync::atomic::Ordering::SeqCst), 1);
+
+        // `a` is hot again: served from the cache, mmap works, `b` was evicted
+        assert_eq!(&store.get_mmap(&a).unwrap()[..], &[1u8; 60][..]);
+        assert_eq!(store.get_verified(&b).unwrap(), vec![2u8; 60]);
+        assert_eq!(backend.gets.load(std::sync::atomic::Ordering::SeqCst), 2);
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_backend_streaming_write_through_and_fetch() {
+        let dir = temp_dir();
+        let backend = Arc::new(MemoryBackend::default());
+        let store = ChunkStore::new(&dir)
+            .unwrap()
+            .with_backend(backend.clone(), 100)
+            .unwrap();
+
+        let a = store.put_stream(&[1u8; 60][..]).unwrap();
+        let b = store.put_stream(&[2u8; 60][..]).unwrap();
+        assert_eq!(backend.chunks.lock().get(&a).map(Vec::len), Some(60));
+        assert_eq!(store.cached_bytes(), 60);
+
+        // `a` was evicted; streaming it fetches it back to disk
+        let mut read = Vec::new();
+        store.get_stream(&a).unwrap().read_to_end(&mut read).unwrap();
+        assert_eq!(read, vec![1u8; 60]);
+        assert_eq!(backend.gets.load(std::sync::atomic::Ordering::SeqCst), 1);
+        assert!(!store.hash_to_path(&b).unwrap().exists());
+        assert!(no_spill_files(&dir));
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_backend_delete_and_garbage_collect() {
+        let dir = temp_dir();
+        let backend = Arc::new(MemoryBackend::default());
+        let store = ChunkStore::new(&dir)
+            .unwrap()
+            .with_backend(backend.clone(), 1024)
+            .unwrap();
+
+        let keep = store.put(b"keep").unwrap();
+        let drop = store.put(b"drop").unwrap();
+
+        // A fresh cache directory sees every chunk through the backend
+        let cold_dir = temp_dir();
+        let cold = ChunkStore::new(&cold_dir)
+            .unwrap()
+            .with_backend(backend.clone(), 1024)
+            .unwrap();
+        assert_eq!(cold.list_chunk_hashes().unwrap().len(), 2);
+        assert_eq!(cold.get(&keep).unwrap(), b"keep");
+
+        let referenced: std::collections::HashSet<String> = [keep.clone()].into_iter().collect();
+        assert_eq!(store.garbage_collect(&referenced).unwrap(), (1, 0));
+        assert!(!backend.exists(&drop).unwrap());
+        assert!(!store.exists(&drop).unwrap());
+        assert!(matches!(cold.get(&drop), Err(ChunkStoreError::NotFound(_))));
+
+        fs::remove_dir_all(&dir).ok();
+        fs::remove_dir_all(&cold_dir).ok();
+    }
+
+    #[test]
+    fn test_integrity_journal_records_written_chunks() {
+        let dir = temp_dir();
+        let config = ChunkStoreConfig::new()
+            .with_compression(ChunkCompression::zstd())
+            .with_integrity_journal(true);
+        let store = ChunkStore::with_config(&dir, config).unwrap();
+
+        let data = b"journaled chunk ".repeat(64);
+        let put = store.put(&data).unwrap();
+        let streamed = store.put_stream(&b"streamed chunk"[..]).unwrap();
+        let gone = store.put(b"deleted").unwrap();
+        store.delete(&gone).unwrap();
+
+        let entries = store.integrity_journal().unwrap().entries().unwrap();
+        assert_eq!(entries.len(), 2);
+        let entry = &entries[&put];
+        assert_eq!(entry.codec, "zstd");
+        assert_eq!(entry.size, fs::metadata(store.hash_to_path(&put).unwrap()).unwrap().len());
+        assert!(entry.size < data.len() as u64);
+        for entry in entries.values() {
+            assert_eq!(store.check_integrity(entry).unwrap(), ChunkCondition::Intact);
+        }
+        assert!(entries.contains_key(&streamed));
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_max_open_files_queues_and_limits() {
+        let dir = temp_dir();
+        let store = ChunkStore::new(&dir)
+            .unwrap()
+            .with_max_open_files(2)
+            .with_handle_wait(Duration::from_millis(50));
+        assert_eq!(store.max_open_files(), Some(2));
+
+        // Bulk writes and reads queue on the pool instead of failing
+        let data: Vec<Vec<u8>> = (0..64).map(|i| format!("chunk {}", i).into_bytes()).collect();
+        let chunks: Vec<&[u8]> = data.iter().map(Vec::as_slice).collect();
+        let hashes = store.put_batch(&chunks).unwrap();
+        let refs: Vec<&str> = hashes.iter().map(String::as_str).collect();
+        assert_eq!(store.get_batch(&refs).unwrap(), data);
+        assert_eq!(store.open_files(), 0);
+
+        // Readers and mappings hold their handle until dropped
+        let reader = store.get_stream(&hashes[0]).unwrap();
+        let mmap = store.get_mmap(&hashes[1]).unwrap();
+        assert_eq!(store.open_files(), 2);
+        let e = store.get(&hashes[2]).unwrap_err();
+        assert!(matches!(e, ChunkStoreError::TooManyOpenFiles { limit: Some(2), .. }), "{:?}", e);
+        assert!(e.is_retryable());
+        drop((reader, mmap));
+        assert_eq!(store.get(&hashes[2]).unwrap(), data[2]);
+
+        assert!(matches!(
+            store.get_mmap_batch(&refs[..3]),
+            Err(ChunkStoreError::TooManyOpenFiles { waited_ms: 0, .. })
+        ));
+        assert_eq!(store.get_mmap_batch(&refs[..2]).unwrap().len(), 2);
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_many_variants_dedup_and_keep_order() {
+        let dir = temp_dir();
+        let store = ChunkStore::new(&dir).unwrap().with_memory_cache(1024);
+        let chunks: Vec<&[u8]> = vec![b"a", b"b", b"a", b"c"];
+        let hashes = store.put_many(&chunks).unwrap();
+        assert_eq!(hashes[0], hashes[2]);
+        assert_eq!(store.list_chunk_hashes().unwrap().len(), 3);
+
+        let refs: Vec<&str> = hashes.iter().map(String::as_str).collect();
+        assert_eq!(store.get_many(&refs).unwrap(), chunks);
+        // Each distinct chunk was read once
+        assert_eq!(store.memory_cache_stats().misses, 3);
+
+        let missing = fake_valid_hash();
+        assert_eq!(store.exists_many(&[refs[1], &missing, refs[3]]).unwrap(), vec![true, false, true]);
+        assert!(matches!(store.get_many(&[refs[0], &missing]), Err(ChunkStoreError::NotFound(_))));
+        assert!(matches!(store.exists_many(&["abc"]), Err(ChunkStoreError::InvalidHash(_))));
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_memory_cache_serves_repeated_reads() {
+        let dir = temp_dir();
+        let store = ChunkStore::with_config(&dir, ChunkStoreConfig::new().with_memory_cache(1024)).unwrap();
+        assert_eq!(store.memory_cache_capacity(), 1024);
+        let hash = store.put(b"hot chunk").unwrap();
+
+        assert_eq!(store.get(&hash).unwrap(), b"hot chunk");
+        assert_eq!(store.get(&hash).unwrap(), b"hot chunk");
+        // A batch reads a repeated hash once
+        assert_eq!(store.get_batch(&[&hash, &hash]).unwrap().len(), 2);
+        let stats = store.memory_cache_stats();
+        assert_eq!((stats.hits, stats.misses, stats.entries), (2, 1, 1));
+
+        // Served from memory even with the file gone, until deleted here
+        fs::remove_file(store.hash_to_path(&hash).unwrap()).unwrap();
+        assert_eq!(store.get_verified(&hash).unwrap(), b"hot chunk");
+        store.delete(&hash).unwrap();
+        assert!(matches!(store.get(&hash), Err(ChunkStoreError::NotFound(_))));
+        assert_eq!(store.memory_cache_stats().bytes, 0);
+
+        // Bytes only `get` has seen are hashed before `get_verified` trusts them
+        let hash = store.put(b"will rot").unwrap();
+        store.get(&hash).unwrap();
+        store.memory.as_ref().unwrap().remove(&hash);
+        store.memory.as_ref().unwrap().insert(&hash, b"rotten!!", false);
+        assert!(matches!(store.get_verified(&hash), Ok(data) if data == b"will rot"));
+
+        assert_eq!(ChunkStore::new(&dir).unwrap().memory_cache_stats(), MemoryCacheStats::default());
+        fs::remove_dir_all(&dir).ok();
+    }
 }
@@ -0,0 +1,105 @@
+use thiserror::Error;
+
+use crate::branch::BranchError;
+use crate::catalog::CatalogError;
+use crate::chunk_store::ChunkStoreError;
+use crate::error::{ErrorCategory, ErrorContext, ErrorInfo};
+use crate::parquet::ParquetError;
+
+#[derive(Error, Debug)]
+#[non_exhaustive]
+pub enum DataMergeError {
+    #[error("Cannot merge {table} row {key}: {reason}")]
+    RowConflict {
+        table: String,
+        key: String,
+        reason: String,
+    },
+
+    #[error("Schemas of {table} differ between the merged versions")]
+    SchemaMismatch { table: String },
+
+    #[error("Key column {column} of {table} is missing or of an unsupported type")]
+    InvalidKey { table: String, column: String },
+
+    #[error("Duplicate key {key} in {table} v{version}")]
+    DuplicateKey {
+        table: String,
+        version: u64,
+        key: String,
+    },
+
+    #[error("Merged value {value} does not fit column {column} of {table}")]
+    Unrepresentable {
+        table: String,
+        column: String,
+        value: String,
+    },
+
+    #[error("Catalog error: {0}")]
+    Catalog(#[from] CatalogError),
+
+    #[error("Chunk store error: {0}")]
+    ChunkStore(#[from] ChunkStoreError),
+
+    #[error("Branch error: {0}")]
+    Branch(#[from] BranchError),
+
+    #[error("Parquet error: {0}")]
+    Parquet(#[from] ParquetError),
+
+    #[error("Arrow error: {0}")]
+    Arrow(#[from] arrow::error::ArrowError),
+}
+
+impl ErrorInfo for DataMergeError {
+    fn code(&self) -> &'static str {
+        match self {
+            DataMergeError::RowConflict { .. } => "DATA_MERGE_ROW_CONFLICT",
+            DataMergeError::SchemaMismatch { .. } => "DATA_MERGE_SCHEMA_MISMATCH",
+            DataMergeError::InvalidKey { .. } => "DATA_MERGE_INVALID_KEY",
+            DataMergeError::DuplicateKey { .. } => "DATA_MERGE_DUPLICATE_KEY",
+            DataMergeError::Unrepresentable { .. } => "DATA_MERGE_UNREPRESENTABLE",
+            DataMergeError::Catalog(e) => e.code(),
+            DataMergeError::ChunkStore(e) => e.code(),
+            DataMergeError::Branch(e) => e.code(),
+            DataMergeError::Parquet(e) => e.code(),
+            DataMergeError::Arrow(_) => "DATA_MERGE_ARROW",
+        }
+    }
+
+    fn category(&self) -> ErrorCategory {
+        match self {
+            DataMergeError::RowConflict { .. } => ErrorCategory::Conflict,
+            DataMergeError::SchemaMismatch { .. }
+            | DataMergeError::DuplicateKey { .. }
+            | DataMergeError::Unrepresentable { .. } => ErrorCategory::FailedPrecondition,
+            DataMergeError::InvalidKey { .. } => ErrorCategory::InvalidArgument,
+            DataMergeError::Catalog(e) => e.category(),
+            DataMergeError::ChunkStore(e) => e.category(),
+            DataMergeError::Branch(e) => e.category(),
+            DataMergeError::Parquet(e) => e.category(),
+            DataMergeError::Arrow(_) => ErrorCategory::Internal,
+        }
+    }
+
+    fn context(&self) -> ErrorContext {
+        match self {
+            DataMergeError::RowConflict { table, key, .. } => {
+                ErrorContext::new().with_table(table).with_key(key)
+            }
+            DataMergeError::SchemaMismatch { table }
+            | DataMergeError::InvalidKey { table, .. }
+            | DataMergeError::Unrepresentable { table, .. } => ErrorContext::new().with_table(table),
+            DataMergeError::DuplicateKey { table, version, key } => ErrorContext::new()
+                .with_table(table)
+                .with_version(*version)
+                .with_key(key),
+            DataMergeError::Catalog(e) => e.context(),
+            DataMergeError::ChunkStore(e) => e.context(),
+            DataMergeError::Branch(e) => e.context(),
+            DataMergeError::Parquet(e) => e.context(),
+            DataMergeError::Arrow(_) => ErrorContext::new(),
+        }
+    }
+}
@@ -0,0 +1,1252 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use arrow::array::{Array, ArrayRef, AsArray, BooleanArray, Decimal128Array, Float64Array, Int64Array};
+use arrow::compute::{cast_with_options, concat_batches, interleave, CastOptions};
+use arrow::datatypes::{
+    DataType, Decimal128Type, Field, Float32Type, Float64Type, Int16Type, Int32Type, Int64Type, Int8Type, Schema,
+    UInt16Type, UInt32Type, UInt64Type, UInt8Type,
+};
+use arrow::record_batch::RecordBatch;
+
+use super::error::DataMergeError;
+use crate::algebraic::{
+    AlgebraicMerger, AlgebraicSchemaRegistry, AlgebraicValue, Bounds, MergeResult, OpType, TableAlgebraicSchema,
+};
+use crate::branch::{BranchDiff, BranchManager, MergeOutcome, MergeResolution};
+use crate::catalog::FileCatalog;
+use crate::chunk_store::ChunkStore;
+use crate::parquet::{ParquetDecoder, ParquetEncoder};
+
+/// Key column used for tables without one set by [`DataMerger::with_key`]
+const DEFAULT_KEY: &str = "id";
+const DEFAULT_CHUNK_ROWS: usize = 100_000;
+
+/// Metadata key recording the source version a merged version came from
+pub const MERGE_SOURCE_KEY: &str = "merge_source_version";
+/// Metadata key recording the fork-point version of a merge
+pub const MERGE_BASE_KEY: &str = "merge_base_version";
+
+/// Result of merging one table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TableMerge {
+    pub table: String,
+    /// Fork-point version (None if the table was added on both sides)
+    pub base_version: Option<u64>,
+    pub source_version: u64,
+    pub target_version: u64,
+    /// Version committed with the merged rows
+    pub version: u64,
+    /// Rows in the merged version
+    pub rows: usize,
+    /// Rows added or changed only on the source
+    pub rows_from_source: usize,
+    /// Rows with at least one cell combined from both sides
+    pub rows_combined: usize,
+    /// Rows deleted on one side and unchanged on the other
+    pub rows_deleted: usize,
+}
+
+/// Result of [`DataMerger::simulate_merge`].
+#[derive(Debug, Clone)]
+pub struct MergeSimulation {
+    /// What the merge would report
+    pub outcome: MergeOutcome,
+    /// Heads of the target branch after the merge
+    pub heads: HashMap<String, u64>,
+    /// Row-merged tables, with the versions they would be committed as
+    pub tables: Vec<TableMerge>,
+}
+
+/// Three-way merger of table data.
+///
+/// Cells changed on both sides are combined by the column's operation in
+/// the registry, or for tables it lacks, in the annotations versioned with
+/// the target (else source) version.
+///
+/// # Example
+///
+/// ```ignore
+/// let merger = DataMerger::new(&catalog, &store, &registry).with_key("events", ["event_id"]);
+/// let outcome = merger.merge_branches(&branches, "feature", "main")?;
+/// assert!(outcome.success);
+/// ```
+pub struct DataMerger<'a> {
+    catalog: &'a FileCatalog,
+    store: &'a ChunkStore,
+    registry: &'a AlgebraicSchemaRegistry,
+    keys: HashMap<String, Vec<String>>,
+    chunk_rows: usize,
+}
+
+/// Where a merged cell comes from.
+enum Cell {
+    Target(usize),
+    Source(usize),
+    Value(AlgebraicValue),
+}
+
+/// Merged rows of a table, not yet written.
+struct MergePlan {
+    merge: TableMerge,
+    batch: RecordBatch,
+    metadata: HashMap<String, String>,
+    schema_hash: Option<String>,
+}
+
+/// What a branch merge needs before its branch files are touched.
+enum BranchPlan {
+    /// No table changed on both sides (or no fork point): a plain branch merge
+    FastForward,
+    /// Some rows cannot be merged
+    Conflict(MergeOutcome),
+    /// Tables changed on both sides, merged row by row
+    Rows(Vec<MergePlan>),
+}
+
+/// Rows of one version, indexed by key.
+struct Rows {
+    batch: RecordBatch,
+    index: HashMap<Key, usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum KeyPart {
+    Null,
+    Int(i64),
+    UInt(u64),
+    Bool(bool),
+    Str(String),
+}
+
+type Key = Vec<KeyPart>;
+
+impl<'a> DataMerger<'a> {
+    pub fn new(catalog: &'a FileCatalog, store: &'a ChunkStore, registry: &'a AlgebraicSchemaRegistry) -> Self {
+        Self {
+            catalog,
+            store,
+            registry,
+            keys: HashMap::new(),
+            chunk_rows: DEFAULT_CHUNK_ROWS,
+        }
+    }
+
+    /// Match rows of `table` by these columns (default: `id`).
+    pub fn with_key<I, S>(mut self, table: impl Into<String>, columns: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.keys.insert(table.into(), columns.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Maximum rows per written chunk
+    pub fn with_chunk_rows(mut self, rows: usize) -> Self {
+        self.chunk_rows = rows.max(1);
+        self
+    }
+
+    /// Merge `source` into `target` against `base` and commit the result
+    /// as the next version of `table`.
+    ///
+    /// # Errors
+    /// - `DataMergeError::RowConflict` for the first row that cannot be merged;
+    ///   nothing is written
+    pub fn merge_table(
+        &self,
+        table: &str,
+        base: Option<u64>,
+        source: u64,
+        target: u64,
+    ) -> Result<TableMerge, DataMergeError> {
+        let plan = self.plan(table, base, source, target)?;
+        self.write(plan)
+    }
+
+    /// Merge rows not yet committed into committed version `target` of
+    /// `table`, against `base`: `chunk_hashes` hold the rows a writer
+    /// derived from `base` while `target` was committed concurrently.
+    ///
+    /// The merged rows are stored as new chunks, which are returned; no
+    /// table version is committed. The rows are labelled as version
+    /// `target + 1` in errors.
+    ///
+    /// # Errors
+    /// - `DataMergeError::RowConflict` for the first row that cannot be merged;
+    ///   nothing is written
+    pub fn merge_chunks(
+        &self,
+        table: &str,
+        base: Option<u64>,
+        chunk_hashes: &[String],
+        target: u64,
+    ) -> Result<Vec<String>, DataMergeError> {
+        let plan = self.plan_chunks(table, base, target + 1, chunk_hashes, None, target)?;
+        self.store_rows(&plan.batch)
+    }
+
+    /// Merge branch `source` into `into`, merging the rows of tables both
+    /// branches changed since `source` forked.
+    ///
+    /// Tables changed on one side are merged by version as in
+    /// [`BranchManager::merge_fast_forward`]. If any table has a row
+    /// conflict, nothing is written and the outcome lists the conflicting
+    /// tables. A source without a fork point is merged forward-only.
+    pub fn merge_branches(
+        &self,
+        branches: &BranchManager,
+        source: &str,
+        into: &str,
+    ) -> Result<MergeOutcome, DataMergeError> {
+        let diff = branches.diff(source, into)?;
+        let fast_forwarded = fast_forwarded(&diff);
+        let fork_point = branches.get(source)?.fork_point;
+
+        let plans = match self.plan_branches(fork_point.as_ref(), &diff, source, into)? {
+            BranchPlan::FastForward => {
+                branches.merge_fast_forward(source, into, false)?;
+                return Ok(MergeOutcome::success(source, into, fast_forwarded, Vec::new()));
+            }
+            BranchPlan::Conflict(outcome) => return Ok(outcome),
+            BranchPlan::Rows(plans) => plans,
+        };
+
+        let mut heads = HashMap::new();
+        let mut merged = Vec::new();
+        for plan in plans {
+            let table_merge = self.write(plan)?;
+            heads.insert(table_merge.table.clone(), table_merge.version);
+            merged.push(table_merge.table);
+        }
+        // The merged versions become the target's heads; the branch merge
+        // then keeps them and fast-forwards everything else
+        branches.update_heads(into, &heads)?;
+        branches.merge_with_resolutions(source, into, &keep_target(&merged), false)?;
+
+        Ok(MergeOutcome::success(source, into, fast_forwarded, merged))
+    }
+
+    /// Run [`merge_branches`](Self::merge_branches) without writing
+    /// anything: rows are merged in memory, and no chunk, table version or
+    /// branch file changes.
+    ///
+    /// The simulation reports the heads `into` would have afterwards, with
+    /// each row-merged table at the version the merge would commit (the
+    /// next one, unless another writer commits to the table first).
+    /// Errors a real merge would hit (an immutable target, unreadable
+    /// chunks) are returned the same way; row conflicts give an
+    /// unsuccessful outcome and `into`'s current heads.
+    pub fn simulate_merge(
+        &self,
+        branches: &BranchManager,
+        source: &str,
+        into: &str,
+    ) -> Result<MergeSimulation, DataMergeError> {
+        let source_branch = branches.get(source)?;
+        let mut target_branch = branches.get(into)?;
+        let diff = BranchDiff::compute(&source_branch, &target_branch);
+        let fast_forwarded = fast_forwarded(&diff);
+
+        let plans = match self.plan_branches(source_branch.fork_point.as_ref(), &diff, source, into)? {
+            BranchPlan::FastForward => Vec::new(),
+            BranchPlan::Conflict(outcome) => {
+                return Ok(MergeSimulation {
+                    outcome,
+                    heads: target_branch.head,
+                    tables: Vec::new(),
+                })
+            }
+            BranchPlan::Rows(plans) => plans,
+        };
+
+        let mut tables = Vec::with_capacity(plans.len());
+        for plan in plans {
+            let version = self.catalog.get_version(&plan.merge.table, None)?.version + 1;
+            target_branch.set_table_version(&plan.merge.table, version);
+            tables.push(TableMerge { version, ..plan.merge });
+        }
+        let merged: Vec<String> = tables.iter().map(|t| t.table.clone()).collect();
+        let result = BranchManager::preview_merge(&source_branch, target_branch, &keep_target(&merged))?;
+
+        Ok(MergeSimulation {
+            outcome: MergeOutcome::success(source, into, fast_forwarded, merged),
+            heads: result.head,
+            tables,
+        })
+    }
+
+    /// Plan the row merges of a branch merge, or decide it needs none.
+    fn plan_branches(
+        &self,
+        fork_point: Option<&HashMap<String, u64>>,
+        diff: &BranchDiff,
+        source: &str,
+        into: &str,
+    ) -> Result<BranchPlan, DataMergeError> {
+        let Some(fork_point) = fork_point.filter(|_| !diff.modified.is_empty()) else {
+            return Ok(BranchPlan::FastForward);
+        };
+
+        let mut plans = Vec::new();
+        let mut conflicts = Vec::new();
+        let mut reasons = Vec::new();
+        for (table, source_version, target_version) in &diff.modified {
+            let base = fork_point.get(table).copied();
+            match self.plan(table, base, *source_version, *target_version) {
+                Ok(plan) => plans.push(plan),
+                Err(e @ DataMergeError::RowConflict { .. }) => {
+                    conflicts.push(table.clone());
+                    reasons.push(e.to_string());
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        if !conflicts.is_empty() {
+            let outcome = MergeOutcome::conflict(source, into, conflicts).with_description(reasons.join("; "));
+            return Ok(BranchPlan::Conflict(outcome));
+        }
+        Ok(BranchPlan::Rows(plans))
+    }
+
+    fn plan(
+        &self,
+        table: &str,
+        base: Option<u64>,
+        source: u64,
+        target: u64,
+    ) -> Result<MergePlan, DataMergeError> {
+        let source_record = self.catalog.get_version(table, Some(source))?;
+        self.plan_chunks(table, base, source, &source_record.chunk_hashes, source_record.algebraic_schema(), target)
+    }
+
+    /// Plan merging the source rows in `source_chunks` (labelled `source`
+    /// in the result and in errors) into committed version `target`.
+    fn plan_chunks(
+        &self,
+        table: &str,
+        base: Option<u64>,
+        source: u64,
+        source_chunks: &[String],
+        source_annotations: Option<TableAlgebraicSchema>,
+        target: u64,
+    ) -> Result<MergePlan, DataMergeError> {
+        let target_record = self.catalog.get_version(table, Some(target))?;
+        let annotations = target_record.algebraic_schema().or(source_annotations);
+        let target_batch = self.load(&target_record.chunk_hashes)?;
+        let source_batch = self.load(source_chunks)?;
+        let base_batch = match base {
+            Some(v) => self.load(&self.catalog.get_version(table, Some(v))?.chunk_hashes)?,
+            None => None,
+        };
+
+        let Some(schema) = [&target_batch, &source_batch, &base_batch]
+            .iter()
+            .find_map(|b| b.as_ref().map(|b| b.schema()))
+        else {
+            // Every side is empty
+            return Ok(MergePlan {
+                merge: TableMerge {
+                    table: table.to_string(),
+                    base_version: base,
+                    source_version: source,
+                    target_version: target,
+                    version: 0,
+                    rows: 0,
+                    rows_from_source: 0,
+                    rows_combined: 0,
+                    rows_deleted: 0,
+                },
+                batch: RecordBatch::new_empty(Arc::new(Schema::empty())),
+                metadata: target_record.metadata,
+                schema_hash: target_record.schema_hash,
+            });
+        };
+        let empty = || RecordBatch::new_empty(schema.clone());
+        let [target_batch, source_batch, base_batch] =
+            [target_batch, source_batch, base_batch].map(|b| b.unwrap_or_else(empty));
+        if [&source_batch, &base_batch].iter().any(|b| !same_columns(b.schema_ref(), &schema)) {
+            return Err(DataMergeError::SchemaMismatch { table: table.to_string() });
+        }
+
+        let key_columns = self
+            .keys
+            .get(table)
+            .cloned()
+            .unwrap_or_else(|| vec![DEFAULT_KEY.to_string()]);
+        let key_indices = key_columns
+            .iter()
+            .map(|column| {
+                schema.index_of(column).map_err(|_| DataMergeError::InvalidKey {
+                    table: table.to_string(),
+                    column: column.clone(),
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let index = |batch: RecordBatch, version: u64| Rows::new(table, version, batch, &key_indices, &key_columns);
+        let target_rows = index(target_batch, target)?;
+        let source_rows = index(source_batch, source)?;
+        let base_rows = index(base_batch, base.unwrap_or(0))?;
+
+        let mut merge = TableMerge {
+            table: table.to_string(),
+            base_version: base,
+            source_version: source,
+            target_version: target,
+            version: 0,
+            rows: 0,
+            rows_from_source: 0,
+            rows_combined: 0,
+            rows_deleted: 0,
+        };
+        let mut columns: Vec<Vec<Cell>> = (0..schema.fields().len()).map(|_| Vec::new()).collect();
+        let push = |columns: &mut Vec<Vec<Cell>>, cells: Vec<Cell>| {
+            for (column, cell) in columns.iter_mut().zip(cells) {
+                column.push(cell);
+            }
+        };
+
+        // Rows of the target, in target order
+        for t in 0..target_rows.batch.num_rows() {
+            let key = target_rows.key(&key_indices, t);
+            let b = base_rows.index.get(&key).copied();
+            match (b, source_rows.index.get(&key).copied()) {
+                (b, Some(s)) => {
+                    let (cells, from_source, combined) = self.merge_row(
+                        table,
+                        annotations.as_ref(),
+                        &key,
+                        &base_rows,
+                        b,
+                        &source_rows,
+                        s,
+                        &target_rows,
+                        t,
+                    )?;
+                    merge.rows_from_source += usize::from(from_source && !combined);
+                    merge.rows_combined += usize::from(combined);
+                    push(&mut columns, cells);
+                }
+                (Some(b), None) => {
+                    if !rows_equal(&base_rows.batch, b, &target_rows.batch, t) {
+                        return Err(conflict(table, &key, "deleted on the source, changed on the target"));
+                    }
+                    merge.rows_deleted += 1;
+                }
+                (None, None) => push(&mut columns, (0..schema.fields().len()).map(|_| Cell::Target(t)).collect()),
+            }
+        }
+        // Rows only the source has, in source order
+        for s in 0..source_rows.batch.num_rows() {
+            let key = source_rows.key(&key_indices, s);
+            if target_rows.index.contains_key(&key) {
+                continue;
+            }
+            match base_rows.index.get(&key) {
+                Some(&b) => {
+                    if !rows_equal(&base_rows.batch, b, &source_rows.batch, s) {
+                        return Err(conflict(table, &key, "deleted on the target, changed on the source"));
+                    }
+                    merge.rows_deleted += 1;
+                }
+                None => {
+                    merge.rows_from_source += 1;
+                    push(&mut columns, (0..schema.fields().len()).map(|_| Cell::Source(s)).collect());
+                }
+            }
+        }
+
+        let arrays = schema
+            .fields()
+            .iter()
+            .zip(&columns)
+            .enumerate()
+            .map(|(i, (field, cells))| {
+                build_column(table, field, cells, source_rows.batch.column(i), target_rows.batch.column(i))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let batch = RecordBatch::try_new(schema, arrays)?;
+        merge.rows = batch.num_rows();
+
+        Ok(MergePlan {
+            merge,
+            batch,
+            metadata: target_record.metadata,
+            schema_hash: target_record.schema_hash,
+        })
+    }
+
+    /// Merge a row present in source and target. Returns the cells and
+    /// whether any came from the source or were combined.
+    #[allow(clippy::too_many_arguments)]
+    fn merge_row(
+        &self,
+        table: &str,
+        annotations: Option<&TableAlgebraicSchema>,
+        key: &Key,
+        base: &Rows,
+        b: Option<usize>,
+        source: &Rows,
+        s: usize,
+        target: &Rows,
+        t: usize,
+    ) -> Result<(Vec<Cell>, bool, bool), DataMergeError> {
+        let schema = target.batch.schema();
+        let mut cells = Vec::with_capacity(schema.fields().len());
+        let (mut from_source, mut combined) = (false, false);
+
+        for (col, field) in schema.fields().iter().enumerate() {
+            let (sc, tc) = (source.batch.column(col), target.batch.column(col));
+            if cells_equal(sc, s, tc, t) {
+                cells.push(Cell::Target(t));
+                continue;
+            }
+            if let Some(b) = b {
+                let bc = base.batch.column(col);
+                if cells_equal(bc, b, sc, s) {
+                    cells.push(Cell::Target(t));
+                    continue;
+                }
+                if cells_equal(bc, b, tc, t) {
+                    cells.push(Cell::Source(s));
+                    from_source = true;
+                    continue;
+                }
+            }
+
+            // Changed on both sides
+            let column_conflict = |reason: String| conflict(table, key, &format!("column {}: {}", field.name(), reason));
+            let base_value = match b {
+                Some(b) => cell_value(base.batch.column(col), b),
+                None => Some(AlgebraicValue::Null),
+            };
+            let (Some(bv), Some(sv), Some(tv)) = (base_value, cell_value(sc, s), cell_value(tc, t)) else {
+                return Err(column_conflict(format!(
+                    "changed on both branches and {} values cannot be merged",
+                    field.data_type()
+                )));
+            };
+            // The registry overrides annotations versioned with the table
+            let schema = match annotations {
+                Some(schema) if !self.registry.has_table(table) => Some(schema),
+                _ => self.registry.get(table),
+            };
+            let op = schema.map_or(OpType::Unknown, |s| s.get_op_type(field.name()));
+            let bounds = schema.and_then(|s| s.get_bounds(field.name()));
+            cells.push(Cell::Value(combine(op, bounds, &bv, &sv, &tv).map_err(column_conflict)?));
+            combined = true;
+        }
+        Ok((cells, from_source, combined))
+    }
+
+    fn write(&self, plan: MergePlan) -> Result<TableMerge, DataMergeError> {
+        let hashes = self.store_rows(&plan.batch)?;
+        let merge = plan.merge;
+        let mut metadata = plan.metadata;
+        metadata.insert(MERGE_SOURCE_KEY.to_string(), merge.source_version.to_string());
+        match merge.base_version {
+            Some(base) => metadata.insert(MERGE_BASE_KEY.to_string(), base.to_string()),
+            None => metadata.remove(MERGE_BASE_KEY),
+        };
+        let version = self
+            .catalog
+            .commit_next_version_with_meta(&merge.table, hashes, metadata, plan.schema_hash)?;
+        Ok(TableMerge { version, ..merge })
+    }
+
+    /// Store `batch` as chunks of at most `chunk_rows` rows
+    fn store_rows(&self, batch: &RecordBatch) -> Result<Vec<String>, DataMergeError> {
+        let encoder = ParquetEncoder::new();
+        let rows = batch.num_rows();
+        let mut hashes = Vec::new();
+        let mut offset = 0;
+        while offset < rows {
+            let len = self.chunk_rows.min(rows - offset);
+            let bytes = encoder.encode(&batch.slice(offset, len))?;
+            hashes.push(self.store.put(&bytes)?);
+            offset += len;
+        }
+        Ok(hashes)
+    }
+
+    /// Decode and concatenate the chunks of a version (None if it has none).
+    fn load(&self, chunk_hashes: &[String]) -> Result<Option<RecordBatch>, DataMergeError> {
+        let decoder = ParquetDecoder::new();
+        let batches = chunk_hashes
+            .iter()
+            .map(|hash| Ok(decoder.decode(&self.store.get(hash)?)?))
+            .collect::<Result<Vec<_>, DataMergeError>>()?;
+        match batches.first() {
+            Some(first) => Ok(Some(concat_batches(&first.schema(), &batches)?)),
+            None => Ok(None),
+        }
+    }
+}
+
+/// Tables a branch merge takes from the source as a whole
+fn fast_forwarded(diff: &BranchDiff) -> Vec<String> {
+    diff.source_only_changes
+        .iter()
+        .map(|(t, _, _)| t.clone())
+        .chain(diff.added_in_source.iter().map(|(t, _)| t.clone()))
+        .collect()
+}
+
+/// Resolutions keeping the target's (already merged) version of `tables`
+fn keep_target(tables: &[String]) -> HashMap<String, MergeResolution> {
+    tables.iter().map(|t| (t.clone(), MergeResolution::Target)).collect()
+}
+
+impl Rows {
+    fn new(
+        table: &str,
+        version: u64,
+        batch: RecordBatch,
+        key_indices: &[usize],
+        key_columns: &[String],
+    ) -> Result<Self, DataMergeError> {
+        for (&i, column) in key_indices.iter().zip(key_columns) {
+            if batch.num_rows() > 0 && key_part(batch.column(i), 0).is_none() {
+                return Err(DataMergeError::InvalidKey {
+                    table: table.to_string(),
+                    column: column.clone(),
+                });
+            }
+        }
+        let mut rows = Self {
+            batch,
+            index: HashMap::new(),
+        };
+        let mut seen = HashSet::new();
+        for row in 0..rows.batch.num_rows() {
+            let key = rows.key(key_indices, row);
+            if !seen.insert(key.clone()) {
+                return Err(DataMergeError::DuplicateKey {
+                    table: table.to_string(),
+                    version,
+                    key: display_key(&key),
+                });
+            }
+            rows.index.insert(key, row);
+        }
+        Ok(rows)
+    }
+
+    /// Key of `row`; key column types were checked in `new`
+    fn key(&self, key_indices: &[usize], row: usize) -> Key {
+        key_indices
+            .iter()
+            .map(|&i| key_part(self.batch.column(i), row).unwrap_or(KeyPart::Null))
+            .collect()
+    }
+}
+
+fn key_part(array: &ArrayRef, row: usize) -> Option<KeyPart> {
+    if array.is_null(row) {
+        return Some(KeyPart::Null);
+    }
+    let part = match array.data_type() {
+        DataType::Utf8 => KeyPart::Str(array.as_string::<i32>().value(row).to_string()),
+        DataType::LargeUtf8 => KeyPart::Str(array.as_string::<i64>().value(row).to_string()),
+        DataType::UInt64 => KeyPart::UInt(array.as_primitive::<UInt64Type>().value(row)),
+        DataType::Boolean => KeyPart::Bool(array.as_boolean().value(row)),
+        _ => match cell_value(array, row)? {
+            AlgebraicValue::Integer(v) => KeyPart::Int(v),
+            _ => return None,
+        },
+    };
+    Some(part)
+}
+
+fn display_key(key: &Key) -> String {
+    key.iter()
+        .map(|part| match part {
+            KeyPart::Null => "null".to_string(),
+            KeyPart::Int(v) => v.to_string(),
+            KeyPart::UInt(v) => v.to_string(),
+            KeyPart::Bool(v) => v.to_string(),
+            KeyPart::Str(v) => v.clone(),
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+fn conflict(table: &str, key: &Key, reason: &str) -> DataMergeError {
+    DataMergeError::RowConflict {
+        table: table.to_string(),
+        key: display_key(key),
+        reason: reason.to_string(),
+    }
+}
+
+/// Same column names and types, in the same order
+fn same_columns(a: &Schema, b: &Schema) -> bool {
+    a.fields().len() == b.fields().len()
+        && a
+            .fields()
+            .iter()
+            .zip(b.fields())
+            .all(|(x, y)| x.name() == y.name() && x.data_type() == y.data_type())
+}
+
+fn cells_equal(a: &ArrayRef, i: usize, b: &ArrayRef, j: usize) -> bool {
+    a.slice(i, 1).to_data() == b.slice(j, 1).to_data()
+}
+
+fn rows_equal(a: &RecordBatch, i: usize, b: &RecordBatch, j: usize) -> bool {
+    a.columns().iter().zip(b.columns()).all(|(x, y)| cells_equal(x, i, y, j))
+}
+
+/// A cell as an algebraic value; None for types that cannot be merged.
+fn cell_value(array: &ArrayRef, row: usize) -> Option<AlgebraicValue> {
+    if array.is_null(row) {
+        return Some(AlgebraicValue::Null);
+    }
+    let value = match array.data_type() {
+        DataType::Int8 => AlgebraicValue::Integer(array.as_primitive::<Int8Type>().value(row).into()),
+        DataType::Int16 => AlgebraicValue::Integer(array.as_primitive::<Int16Type>().value(row).into()),
+        DataType::Int32 => AlgebraicValue::Integer(array.as_primitive::<Int32Type>().value(row).into()),
+        DataType::Int64 => AlgebraicValue::Integer(array.as_primitive::<Int64Type>().value(row)),
+        DataType::UInt8 => AlgebraicValue::Integer(array.as_primitive::<UInt8Type>().value(row).into()),
+        DataType::UInt16 => AlgebraicValue::Integer(array.as_primitive::<UInt16Type>().value(row).into()),
+        DataType::UInt32 => AlgebraicValue::Integer(array.as_primitive::<UInt32Type>().value(row).into()),
+        DataType::UInt64 => AlgebraicValue::Integer(array.as_primitive::<UInt64Type>().value(row).try_into().ok()?),
+        DataType::Float32 => AlgebraicValue::Float(array.as_primitive::<Float32Type>().value(row).into()),
+        DataType::Float64 => AlgebraicValue::Float(array.as_primitive::<Float64Type>().value(row)),
+        DataType::Boolean => AlgebraicValue::Boolean(array.as_boolean().value(row)),
+        DataType::Decimal128(_, scale) => AlgebraicValue::decimal(
+            array.as_primitive::<Decimal128Type>().value(row),
+            u32::try_from(*scale).ok()?,
+        )
+        .ok()?,
+        _ => return None,
+    };
+    Some(value)
+}
+
+/// Combine a cell both sides changed from `base`.
+fn combine(
+    op: OpType,
+    bounds: Option<Bounds>,
+    base: &AlgebraicValue,
+    source: &AlgebraicValue,
+    target: &AlgebraicValue,
+) -> Result<AlgebraicValue, String> {
+    if !op.is_conflict_free() {
+        return Err(format!("changed on both branches and {} is not conflict-free", op));
+    }
+    let value = if (op.is_abelian() || op == OpType::BoundedAdd) && !base.is_null() {
+        rebase(&op, base, source, target)?
+    } else {
+        match AlgebraicMerger::merge(op.clone(), source, target) {
+            MergeResult::Merged(value) => value,
+            MergeResult::Conflict { reason, .. } => return Err(reason),
+            MergeResult::TypeMismatch { type1, type2, operation } => {
+                return Err(format!("cannot merge {} with {} using {}", type1, type2, operation))
+            }
+        }
+    };
+    match bounds {
+        Some(bounds) if op == OpType::BoundedAdd && !bounds.contains_value(&value) => {
+            Err(format!("merged value {} is outside the bounds {}", value, bounds))
+        }
+        _ => Ok(value),
+    }
+}
+
+/// Apply the source's change from `base` to the target: `target + source - base`
+/// for ADD and BOUNDED_ADD, `target * source / base` for MULTIPLY.
+fn rebase(
+    op: &OpType,
+    base: &AlgebraicValue,
+    source: &AlgebraicValue,
+    target: &AlgebraicValue,
+) -> Result<AlgebraicValue, String> {
+    if [base, source, target].iter().any(|v| matches!(v, AlgebraicValue::Decimal(_))) {
+        let (Some(b), Some(s), Some(t)) = (base.as_decimal(), source.as_decimal(), target.as_decimal()) else {
+            return Err(format!("{} cannot mix decimals with floats", op));
+        };
+        return match op {
+            OpType::AbelianAdd | OpType::BoundedAdd => s
+                .checked_add(&t)
+                .and_then(|sum| sum.checked_sub(&b))
+                .map(AlgebraicValue::Decimal)
+                .ok_or_else(|| "decimal overflow".to_string()),
+            _ => Err(format!("{} is not supported for decimals", op)),
+        };
+    }
+    if let (AlgebraicValue::Integer(b), AlgebraicValue::Integer(s), AlgebraicValue::Integer(t)) =
+        (base, source, target)
+    {
+        let (b, s, t) = (i128::from(*b), i128::from(*s), i128::from(*t));
+        let exact = match op {
+            OpType::AbelianAdd | OpType::BoundedAdd => Some(s + t - b),
+            _ if b == 0 => None,
+            _ => s.checked_mul(t).filter(|p| p % b == 0).map(|p| p / b),
+        };
+        if let Some(value) = exact.and_then(|v| i64::try_from(v).ok()) {
+            return Ok(AlgebraicValue::Integer(value));
+        }
+        if matches!(op, OpType::AbelianAdd | OpType::BoundedAdd) {
+            return Err("integer overflow".to_string());
+        }
+    }
+    let (Some(b), Some(s), Some(t)) = (base.as_float(), source.as_float(), target.as_float()) else {
+        return Err(format!("{} needs numeric values on every side", op));
+    };
+    match op {
+        OpType::AbelianAdd | OpType::BoundedAdd => Ok(AlgebraicValue::Float(s + t - b)),
+        _ if b == 0.0 => Err("cannot apply a product change to a zero base".to_string()),
+        _ => Ok(AlgebraicValue::Float(s * t / b)),
+    }
+}
+
+/// Assemble a merged column. Columns with only copied cells are gathered
+/// as-is; columns with combined cells are rebuilt from values.
+fn build_column(
+    table: &str,
+    field: &Field,
+    cells: &[Cell],
+    source: &ArrayRef,
+    target: &ArrayRef,
+) -> Result<ArrayRef, DataMergeError> {
+    if !cells.iter().any(|c| matches!(c, Cell::Value(_))) {
+        let indices: Vec<(usize, usize)> = cells
+            .iter()
+            .map(|c| match c {
+                Cell::Target(i) => (0, *i),
+                Cell::Source(i) => (1, *i),
+                Cell::Value(_) => unreachable!("no combined cells"),
+            })
+            .collect();
+        return Ok(interleave(&[target.as_ref(), source.as_ref()], &indices)?);
+    }
+
+    let unrepresentable = |value: &AlgebraicValue| DataMergeError::Unrepresentable {
+        table: table.to_string(),
+        column: field.name().clone(),
+        value: format!("{:?}", value),
+    };
+    // Combined cells only occur in columns cell_value supports
+    let values: Vec<AlgebraicValue> = cells
+        .iter()
+        .map(|c| match c {
+            Cell::Target(i) => cell_value(target, *i).unwrap_or_default(),
+            Cell::Source(i) => cell_value(source, *i).unwrap_or_default(),
+            Cell::Value(v) => v.clone(),
+        })
+        .collect();
+
+    let array: ArrayRef = match field.data_type() {
+        DataType::Boolean => Arc::new(
+            values
+                .iter()
+                .map(|v| match v {
+                    AlgebraicValue::Null => Ok(None),
+                    AlgebraicValue::Boolean(b) => Ok(Some(*b)),
+                    other => Err(unrepresentable(other)),
+                })
+                .collect::<Result<BooleanArray, _>>()?,
+        ),
+        DataType::Float32 | DataType::Float64 => Arc::new(
+            values
+                .iter()
+                .map(|v| match v {
+                    AlgebraicValue::Null => Ok(None),
+                    other => other.as_float().map(Some).ok_or_else(|| unrepresentable(other)),
+                })
+                .collect::<Result<Float64Array, _>>()?,
+        ),
+        data_type if data_type.is_integer() => Arc::new(
+            values
+                .iter()
+                .map(|v| match v {
+                    AlgebraicValue::Null => Ok(None),
+                    other => other.as_integer().map(Some).ok_or_else(|| unrepresentable(other)),
+                })
+                .collect::<Result<Int64Array, _>>()?,
+        ),
+        DataType::Decimal128(precision, scale) => {
+            let scale = u32::try_from(*scale).map_err(|_| unrepresentable(&values[0]))?;
+            // Rescaled exactly to the column's scale, then held to its precision
+            let array = values
+                .iter()
+                .map(|v| match v {
+                    AlgebraicValue::Null => Ok(None),
+                    other => other
+                        .as_decimal()
+                        .and_then(|d| d.rescaled(scale))
+                        .map(|d| Some(d.mantissa()))
+                        .ok_or_else(|| unrepresentable(other)),
+                })
+                .collect::<Result<Decimal128Array, _>>()?
+                .with_precision_and_scale(*precision, scale as i8)?;
+            if array.validate_decimal_precision(*precision).is_err() {
+                let value = values.iter().find(|v| !v.is_null()).cloned().unwrap_or_default();
+                return Err(unrepresentable(&value));
+            }
+            Arc::new(array)
+        }
+        _ => return Err(unrepresentable(&values[0])),
+    };
+    if array.data_type() == field.data_type() {
+        return Ok(array);
+    }
+    let options = CastOptions {
+        safe: false,
+        ..Default::default()
+    };
+    cast_with_options(&array, field.data_type(), &options).map_err(|_| {
+        let value = values.iter().find(|v| !v.is_null()).cloned().unwrap_or_default();
+        unrepresentable(&value)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repo::Repo;
+    use arrow::array::StringArray;
+
+    type Row = (i64, i64, f64, &'static str);
+
+    fn registry() -> AlgebraicSchemaRegistry {
+        let mut schema = TableAlgebraicSchema::new("stats");
+        schema.add_column("views", OpType::AbelianAdd);
+        schema.add_column("peak", OpType::SemilatticeMax);
+        let mut registry = AlgebraicSchemaRegistry::new();
+        registry.register(schema);
+        registry
+    }
+
+    fn write(repo: &Repo, version: u64, rows: &[Row]) {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Int64, false),
+            Field::new("views", DataType::Int64, true),
+            Field::new("peak", DataType::Float64, true),
+            Field::new("name", DataType::Utf8, true),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema,
+            vec![
+                Arc::new(Int64Array::from_iter_values(rows.iter().map(|r| r.0))),
+                Arc::new(Int64Array::from_iter_values(rows.iter().map(|r| r.1))),
+                Arc::new(Float64Array::from_iter_values(rows.iter().map(|r| r.2))),
+                Arc::new(StringArray::from_iter_values(rows.iter().map(|r| r.3))),
+            ],
+        )
+        .unwrap();
+        let hash = repo.store().put(&ParquetEncoder::new().encode(&batch).unwrap()).unwrap();
+        repo.catalog()
+            .commit(crate::catalog::TableVersion::new("stats", version, vec![hash]))
+            .unwrap();
+    }
+
+    fn read(repo: &Repo, version: u64) -> Vec<(i64, i64, f64, String)> {
+        let record = repo.catalog().get_version("stats", Some(version)).unwrap();
+        let mut rows = Vec::new();
+        for hash in &record.chunk_hashes {
+            let batch = ParquetDecoder::new().decode(&repo.store().get(hash).unwrap()).unwrap();
+            for i in 0..batch.num_rows() {
+                rows.push((
+                    batch.column(0).as_primitive::<Int64Type>().value(i),
+                    batch.column(1).as_primitive::<Int64Type>().value(i),
+                    batch.column(2).as_primitive::<Float64Type>().value(i),
+                    batch.column(3).as_string::<i32>().value(i).to_string(),
+                ));
+            }
+        }
+        rows.sort_by_key(|r| r.0);
+        rows
+    }
+
+    /// stats v1 on main, `feature` forked from it, `main` and `feature`
+    /// then commit v2 and v3.
+    fn diverged(repo: &Repo, main: &[Row], feature: &[Row]) {
+        write(repo, 1, &[(1, 10, 1.0, "a"), (2, 20, 2.0, "b"), (3, 30, 3.0, "c")]);
+        let branches = repo.branches();
+        branches.update_head("main", "stats", 1).unwrap();
+        branches.create("feature", Some("main"), None).unwrap();
+        write(repo, 2, main);
+        branches.update_head("main", "stats", 2).unwrap();
+        write(repo, 3, feature);
+        branches.update_head("feature", "stats", 3).unwrap();
+    }
+
+    #[test]
+    fn test_merges_rows_both_branches_changed() {
+        let repo = Repo::ephemeral().unwrap();
+        diverged(
+            &repo,
+            &[(1, 15, 4.0, "a"), (2, 20, 2.0, "b"), (3, 30, 3.0, "c2")],
+            &[(1, 12, 3.0, "a"), (3, 30, 3.0, "c"), (4, 40, 4.0, "d")],
+        );
+        let registry = registry();
+        let merger = DataMerger::new(repo.catalog(), repo.store(), &registry);
+
+        let outcome = merger.merge_branches(repo.branches(), "feature", "main").unwrap();
+        assert!(outcome.success);
+        assert_eq!(outcome.algebraically_merged, vec!["stats".to_string()]);
+
+        let head = repo.branches().get("main").unwrap().head["stats"];
+        assert_eq!(head, 4);
+        assert_eq!(
+            read(&repo, head),
+            vec![
+                (1, 17, 4.0, "a".to_string()),
+                (3, 30, 3.0, "c2".to_string()),
+                (4, 40, 4.0, "d".to_string()),
+            ]
+        );
+        let record = repo.catalog().get_version("stats", Some(head)).unwrap();
+        assert_eq!(record.metadata[MERGE_SOURCE_KEY], "3");
+        assert_eq!(record.metadata[MERGE_BASE_KEY], "1");
+    }
+
+    #[test]
+    fn test_merges_with_versioned_annotations() {
+        let repo = Repo::ephemeral().unwrap();
+        write(&repo, 1, &[(1, 10, 1.0, "a")]);
+        let schema = registry().get("stats").cloned();
+        assert_eq!(repo.catalog().set_algebraic_schema("stats", schema.as_ref()).unwrap(), 2);
+        let branches = repo.branches();
+        branches.update_head("main", "stats", 2).unwrap();
+        branches.create("feature", Some("main"), None).unwrap();
+        write(&repo, 3, &[(1, 15, 4.0, "a")]);
+        branches.update_head("main", "stats", 3).unwrap();
+        write(&repo, 4, &[(1, 12, 3.0, "a")]);
+        branches.update_head("feature", "stats", 4).unwrap();
+
+        // Later versions inherit the annotations, so no registry is needed
+        assert_eq!(repo.catalog().algebraic_schema("stats", Some(4)).unwrap(), schema);
+        let empty = AlgebraicSchemaRegistry::new();
+        let outcome = DataMerger::new(repo.catalog(), repo.store(), &empty)
+            .merge_branches(repo.branches(), "feature", "main")
+            .unwrap();
+        assert!(outcome.success);
+        assert_eq!(read(&repo, 5), vec![(1, 17, 4.0, "a".to_string())]);
+
+        // An explicit registry entry takes precedence
+        let mut overwrite = TableAlgebraicSchema::new("stats");
+        overwrite.set_default(OpType::GenericOverwrite);
+        let mut registry = AlgebraicSchemaRegistry::new();
+        registry.register(overwrite);
+        write(&repo, 6, &[(1, 20, 5.0, "a")]);
+        branches.update_head("main", "stats", 6).unwrap();
+        write(&repo, 7, &[(1, 30, 5.0, "a")]);
+        branches.update_head("feature", "stats", 7).unwrap();
+        let outcome = DataMerger::new(repo.catalog(), repo.store(), &registry)
+            .merge_branches(repo.branches(), "feature", "main")
+            .unwrap();
+        assert!(!outcome.success);
+    }
+
+    #[test]
+    fn test_simulate_merge_writes_nothing() {
+        let repo = Repo::ephemeral().unwrap();
+        diverged(
+            &repo,
+            &[(1, 15, 4.0, "a"), (2, 20, 2.0, "b"), (3, 30, 3.0, "c2")],
+            &[(1, 12, 3.0, "a"), (3, 30, 3.0, "c"), (4, 40, 4.0, "d")],
+        );
+        repo.catalog()
+            .commit(crate::catalog::TableVersion::new("extra", 1, Vec::new()))
+            .unwrap();
+        repo.branches().update_head("feature", "extra", 1).unwrap();
+        let registry = registry();
+        let merger = DataMerger::new(repo.catalog(), repo.store(), &registry);
+        let chunks_before = repo.store().list_chunk_hashes().unwrap().len();
+
+        let simulation = merger.simulate_merge(repo.branches(), "feature", "main").unwrap();
+        assert!(simulation.outcome.success);
+        assert_eq!(simulation.outcome.algebraically_merged, vec!["stats".to_string()]);
+        assert_eq!(simulation.outcome.fast_forwarded, vec!["extra".to_string()]);
+        assert_eq!(simulation.heads, HashMap::from([("stats".to_string(), 4), ("extra".to_string(), 1)]));
+        assert_eq!(
+            (simulation.tables[0].version, simulation.tables[0].rows, simulation.tables[0].rows_combined),
+            (4, 3, 1)
+        );
+
+        assert_eq!(repo.catalog().list_versions("stats").unwrap(), vec![1, 2, 3]);
+        assert_eq!(repo.store().list_chunk_hashes().unwrap().len(), chunks_before);
+        let main = repo.branches().get("main").unwrap();
+        assert_eq!((main.head["stats"], main.head.get("extra")), (2, None));
+
+        // The real merge lands where the simulation said
+        merger.merge_branches(repo.branches(), "feature", "main").unwrap();
+        assert_eq!(repo.branches().get("main").unwrap().head, simulation.heads);
+    }
+
+    #[test]
+    fn test_simulate_merge_reports_conflicts() {
+        let repo = Repo::ephemeral().unwrap();
+        diverged(
+            &repo,
+            &[(1, 10, 1.0, "main"), (2, 20, 2.0, "b"), (3, 30, 3.0, "c")],
+            &[(1, 10, 1.0, "feature"), (2, 20, 2.0, "b"), (3, 30, 3.0, "c")],
+        );
+        let registry = registry();
+        let simulation = DataMerger::new(repo.catalog(), repo.store(), &registry)
+            .simulate_merge(repo.branches(), "feature", "main")
+            .unwrap();
+        assert!(!simulation.outcome.success);
+        assert_eq!(simulation.outcome.conflicts, vec!["stats".to_string()]);
+        assert_eq!(simulation.heads["stats"], 2);
+        assert!(simulation.tables.is_empty());
+    }
+
+    #[test]
+    fn test_table_merge_counts_rows() {
+        let repo = Repo::ephemeral().unwrap();
+        diverged(
+            &repo,
+            &[(1, 15, 1.0, "a"), (2, 20, 2.0, "b"), (3, 30, 3.0, "c")],
+            &[(1, 12, 1.0, "a"), (2, 20, 2.0, "b2"), (3, 30, 3.0, "c"), (5, 50, 5.0, "e")],
+        );
+        let registry = registry();
+        let merge = DataMerger::new(repo.catalog(), repo.store(), &registry)
+            .with_chunk_rows(2)
+            .merge_table("stats", Some(1), 3, 2)
+            .unwrap();
+
+        assert_eq!(
+            (merge.rows, merge.rows_from_source, merge.rows_combined, merge.rows_deleted),
+            (4, 2, 1, 0)
+        );
+        assert_eq!(repo.catalog().get_version("stats", Some(merge.version)).unwrap().chunk_hashes.len(), 2);
+        assert_eq!(read(&repo, merge.version)[0], (1, 17, 1.0, "a".to_string()));
+    }
+
+    #[test]
+    fn test_conflicting_rows_write_nothing() {
+        let repo = Repo::ephemeral().unwrap();
+        diverged(
+            &repo,
+            &[(1, 10, 1.0, "main"), (2, 20, 2.0, "b"), (3, 31, 3.0, "c")],
+            &[(1, 10, 1.0, "feature"), (2, 20, 2.0, "b")],
+        );
+        let registry = registry();
+        let merger = DataMerger::new(repo.catalog(), repo.store(), &registry);
+
+        let err = merger.merge_table("stats", Some(1), 3, 2).unwrap_err();
+        assert!(matches!(&err, DataMergeError::RowConflict { key, reason, .. }
+            if key == "1" && reason.contains("column name")));
+
+        let outcome = merger.merge_branches(repo.branches(), "feature", "main").unwrap();
+        assert!(!outcome.success);
+        assert_eq!(outcome.conflicts, vec!["stats".to_string()]);
+        assert_eq!(repo.branches().get("main").unwrap().head["stats"], 2);
+        assert_eq!(repo.catalog().list_versions("stats").unwrap(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_bounded_column_conflicts_past_bound() {
+        use crate::algebraic::{Bounds, ColumnAlgebraic};
+
+        // Both branches sell stock the base had: 10 - 6 - 7 would go negative
+        let repo = Repo::ephemeral().unwrap();
+        diverged(&repo, &[(1, 4, 1.0, "a")], &[(1, 3, 1.0, "a")]);
+        let mut schema = TableAlgebraicSchema::new("stats");
+        schema.add_column_annotation(ColumnAlgebraic::bounded("views", Bounds::at_least(0)));
+        let mut registry = AlgebraicSchemaRegistry::new();
+        registry.register(schema);
+
+        let err = DataMerger::new(repo.catalog(), repo.store(), &registry)
+            .merge_table("stats", Some(1), 3, 2)
+            .unwrap_err();
+        assert!(matches!(&err, DataMergeError::RowConflict { key, reason, .. }
+            if key == "1" && reason.contains("outside the bounds 0..")));
+    }
+
+    #[test]
+    fn test_merges_decimal_column_exactly() {
+        use arrow::array::Decimal128Array;
+
+        let repo = Repo::ephemeral().unwrap();
+        let write_balances = |version: u64, cents: &[i128]| {
+            let schema = Arc::new(Schema::new(vec![
+                Field::new("id", DataType::Int64, false),
+                Field::new("balance", DataType::Decimal128(10, 2), true),
+            ]));
+            let balances = Decimal128Array::from_iter_values(cents.iter().copied())
+                .with_precision_and_scale(10, 2)
+                .unwrap();
+            let batch = RecordBatch::try_new(
+                schema,
+                vec![Arc::new(Int64Array::from_iter_values(1..=cents.len() as i64)), Arc::new(balances)],
+            )
+            .unwrap();
+            let hash = repo.store().put(&ParquetEncoder::new().encode(&batch).unwrap()).unwrap();
+            repo.catalog()
+                .commit(crate::catalog::TableVersion::new("ledger", version, vec![hash]))
+                .unwrap();
+        };
+        // 100.00 deposited 0.10 on one branch and 0.20 on the other
+        write_balances(1, &[10000, 5000]);
+        write_balances(2, &[10010, 5000]);
+        write_balances(3, &[10020, 99999_99999]);
+        write_balances(4, &[10000, 60000_00000]);
+        let mut schema = TableAlgebraicSchema::new("ledger");
+        schema.add_column("balance", OpType::AbelianAdd);
+        let mut registry = AlgebraicSchemaRegistry::new();
+        registry.register(schema);
+        let merger = DataMerger::new(repo.catalog(), repo.store(), &registry);
+
+        let merged = merger.merge_table("ledger", Some(1), 3, 2).unwrap();
+        assert_eq!(merged.rows_combined, 1);
+        let record = repo.catalog().get_version("ledger", Some(merged.version)).unwrap();
+        let batch = ParquetDecoder::new().decode(&repo.store().get(&record.chunk_hashes[0]).unwrap()).unwrap();
+        let balances = batch.column(1).as_primitive::<Decimal128Type>();
+        assert_eq!(balances.value_as_string(0), "100.30");
+        assert_eq!(balances.value_as_string(1), "99999999.99");
+
+        // A sum past the column's precision cannot be written back
+        let err = merger.merge_table("ledger", Some(1), 4, 3).unwrap_err();
+        assert!(matches!(err, DataMergeError::Unrepresentable { .. }));
+    }
+
+    #[test]
+    fn test_delete_against_change_conflicts() {
+        let repo = Repo::ephemeral().unwrap();
+        diverged(
+            &repo,
+            &[(1, 10, 1.0, "a"), (2, 20, 2.0, "b"), (3, 31, 3.0, "c")],
+            &[(1, 10, 1.0, "a"), (2, 20, 2.0, "b")],
+        );
+        let registry = registry();
+        let err = DataMerger::new(repo.catalog(), repo.store(), &registry)
+            .merge_table("stats", Some(1), 3, 2)
+            .unwrap_err();
+        assert!(matches!(&err, DataMergeError::RowConflict { key, reason, .. }
+            if key == "3" && reason.starts_with("deleted on the source")));
+    }
+
+    #[test]
+    fn test_rebase_applies_source_change() {
+        let (b, s, t) = (AlgebraicValue::integer(10), AlgebraicValue::integer(12), AlgebraicValue::integer(15));
+        assert_eq!(rebase(&OpType::AbelianAdd, &b, &s, &t), Ok(AlgebraicValue::integer(17)));
+        assert_eq!(rebase(&OpType::AbelianMultiply, &b, &AlgebraicValue::integer(20), &t), Ok(AlgebraicValue::integer(30)));
+        assert_eq!(
+            rebase(&OpType::AbelianAdd, &AlgebraicValue::float(1.0), &AlgebraicValue::float(1.5), &AlgebraicValue::integer(2)),
+            Ok(AlgebraicValue::float(2.5))
+        );
+        assert!(rebase(&OpType::AbelianMultiply, &AlgebraicValue::integer(0), &s, &t).is_err());
+        assert!(combine(OpType::GenericOverwrite, None, &b, &s, &t).is_err());
+        assert_eq!(combine(OpType::SemilatticeMax, None, &b, &s, &t), Ok(AlgebraicValue::integer(15)));
+
+        // Bounded columns rebase like ADD, then check the bounds
+        let (s, t) = (AlgebraicValue::integer(4), AlgebraicValue::integer(3));
+        assert_eq!(combine(OpType::BoundedAdd, None, &b, &s, &t), Ok(AlgebraicValue::integer(-3)));
+        assert_eq!(combine(OpType::BoundedAdd, Some(Bounds::at_least(-5)), &b, &s, &t), Ok(AlgebraicValue::integer(-3)));
+        let err = combine(OpType::BoundedAdd, Some(Bounds::at_least(0)), &b, &s, &t).unwrap_err();
+        assert_eq!(err, "merged value -3 is outside the bounds 0..");
+
+        // Decimals rebase exactly; mixing in floats would not be exact
+        let dec = |s: &str| AlgebraicValue::Decimal(s.parse().unwrap());
+        assert_eq!(rebase(&OpType::AbelianAdd, &dec("0.10"), &dec("0.3"), &AlgebraicValue::integer(1)), Ok(dec("1.20")));
+        assert!(rebase(&OpType::AbelianAdd, &dec("0.10"), &AlgebraicValue::float(0.3), &dec("1")).is_err());
+        assert!(rebase(&OpType::AbelianMultiply, &dec("1"), &dec("2"), &dec("3")).is_err());
+    }
+}
@@ -0,0 +1,26 @@
+//! Row-level three-way merge of table data.
+//!
+//! Branch merges compare version numbers only, so a table changed on both
+//! branches is a conflict even when every change could be combined.
+//! [`DataMerger`] reads the rows of both versions and of the fork-point
+//! version, matches them by key, and merges changed cells column by column
+//! with the operation the [`AlgebraicSchemaRegistry`](crate::AlgebraicSchemaRegistry)
+//! assigns to the column. The merged rows are written as new chunks and
+//! committed as a new version of the table.
+//!
+//! Per row:
+//!
+//! - changed on one side only: that side's row;
+//! - added on one side: kept; deleted on one side and unchanged on the
+//!   other: deleted;
+//! - changed on both: each cell changed on both sides is combined. Abelian
+//!   operations apply the source's change to the target (`ADD`:
+//!   `target + source - base`), semilattice operations join the two
+//!   values. Any other operation, or a row deleted on one side and changed
+//!   on the other, is a conflict.
+
+pub mod error;
+pub mod merger;
+
+pub use error::DataMergeError;
+pub use merger::{DataMerger, MergeSimulation, TableMerge};
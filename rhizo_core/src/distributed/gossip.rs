@@ -0,0 +1,827 @@
+//! Gossip networking: anti-entropy propagation of updates over TCP.
+//!
+//! A [`GossipNode`] is the networked counterpart of a
+//! [`SimulatedNode`](super::SimulatedNode). It commits algebraic
+//! transactions locally, keeps every update it has seen, and periodically
+//! reconciles with its peers so that all nodes end up holding the same set
+//! of updates — and therefore, since the operations are algebraic, the same
+//! state.
+//!
+//! # Protocol
+//!
+//! Messages are serde-serialized JSON, one per line. One anti-entropy
+//! exchange (push-pull) between an initiator and a peer:
+//!
+//! ```text
+//! initiator                                  peer
+//!     │── Sync  { from, known update ids } ──▶│
+//!     │◀── Delta { updates initiator lacks,   │
+//!     │            known update ids }  ───────│
+//!     │── Push  { updates peer lacks } ──────▶│
+//! ```
+//!
+//! Updates are identified by [`VersionedUpdate::dedup_key`], so an update
+//! received over several paths is applied once.
+//!
+//! # Convergence
+//!
+//! After one exchange both sides hold the union of their updates. With a
+//! connected peer graph and no new commits, every node holds every update
+//! after at most `diameter` rounds (each round syncs with `fanout` peers,
+//! rotating through the peer list), and all nodes agree on every key — the
+//! same guarantee [`SimulatedCluster::verify_convergence`](super::SimulatedCluster::verify_convergence)
+//! checks in simulation. Unreachable peers are skipped and retried on later
+//! rounds, so partitions heal by themselves.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use rhizo_core::distributed::{
+//!     AlgebraicOperation, AlgebraicTransaction, GossipConfig, GossipNode, NodeId,
+//! };
+//! use rhizo_core::algebraic::{AlgebraicValue, OpType};
+//!
+//! let node = GossipNode::bind(NodeId::new("sf"), "0.0.0.0:7400", GossipConfig::default())?;
+//! node.add_peer("10.0.0.2:7400".parse().unwrap());
+//! node.start();
+//!
+//! let mut tx = AlgebraicTransaction::new();
+//! tx.add_operation(AlgebraicOperation::new(
+//!     "page_views",
+//!     OpType::AbelianAdd,
+//!     AlgebraicValue::integer(100),
+//! ));
+//! node.commit(tx)?;
+//! # Ok::<(), rhizo_core::distributed::GossipError>(())
+//! ```
+
+use std::collections::{HashMap, HashSet};
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use parking_lot::{Mutex, RwLock};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use super::local_commit::{
+    AlgebraicTransaction, LocalCommitError, LocalCommitProtocol, VersionedUpdate,
+};
+use super::simulation::apply_to_state;
+use super::state_store::{NodeStateError, NodeStateStore};
+use super::vector_clock::{NodeId, VectorClock};
+use crate::algebraic::{AlgebraicValue, OpType};
+use crate::error::{ErrorCategory, ErrorContext, ErrorInfo};
+
+/// Errors from gossip networking.
+#[derive(Error, Debug)]
+#[non_exhaustive]
+pub enum GossipError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("Local commit failed: {0}")]
+    Commit(#[from] LocalCommitError),
+
+    #[error("State store error: {0}")]
+    State(#[from] NodeStateError),
+
+    #[error("Protocol error with {peer}: {message}")]
+    Protocol { peer: SocketAddr, message: String },
+}
+
+impl ErrorInfo for GossipError {
+    fn code(&self) -> &'static str {
+        match self {
+            GossipError::Io(_) => "GOSSIP_IO",
+            GossipError::Json(_) => "GOSSIP_JSON",
+            GossipError::Commit(e) => e.code(),
+            GossipError::State(e) => e.code(),
+            GossipError::Protocol { .. } => "GOSSIP_PROTOCOL",
+        }
+    }
+
+    fn category(&self) -> ErrorCategory {
+        match self {
+            GossipError::Io(_) => ErrorCategory::Io,
+            GossipError::Json(_) | GossipError::Protocol { .. } => ErrorCategory::Internal,
+            GossipError::Commit(e) => e.category(),
+            GossipError::State(e) => e.category(),
+        }
+    }
+
+    fn context(&self) -> ErrorContext {
+        match self {
+            GossipError::Commit(e) => e.context(),
+            _ => ErrorContext::new(),
+        }
+    }
+}
+
+/// Configuration for a gossip node.
+#[derive(Debug, Clone)]
+pub struct GossipConfig {
+    /// Time between anti-entropy rounds when running in the background
+    pub interval: Duration,
+    /// Peers synced per round (0 = all peers)
+    pub fanout: usize,
+    /// Timeout for connecting to a peer
+    pub connect_timeout: Duration,
+    /// Read/write timeout for one exchange
+    pub io_timeout: Duration,
+}
+
+impl Default for GossipConfig {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(1),
+            fanout: 3,
+            connect_timeout: Duration::from_secs(2),
+            io_timeout: Duration::from_secs(5),
+        }
+    }
+}
+
+impl GossipConfig {
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    pub fn with_fanout(mut self, fanout: usize) -> Self {
+        self.fanout = fanout;
+        self
+    }
+
+    pub fn with_connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = timeout;
+        self
+    }
+
+    pub fn with_io_timeout(mut self, timeout: Duration) -> Self {
+        self.io_timeout = timeout;
+        self
+    }
+}
+
+/// Counters for a gossip node.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct GossipStats {
+    /// Anti-entropy rounds run
+    pub rounds: u64,
+    /// Exchanges completed (initiated or accepted)
+    pub syncs: u64,
+    /// Exchanges that failed (unreachable peer, timeout, bad message)
+    pub failed_syncs: u64,
+    /// Updates sent to peers
+    pub updates_sent: u64,
+    /// New updates received from peers
+    pub updates_received: u64,
+    /// Received updates ignored because they were already applied
+    pub duplicates_ignored: u64,
+}
+
+/// Outcome of one exchange with a peer.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SyncReport {
+    /// New updates received from the peer
+    pub received: usize,
+    /// Updates sent to the peer
+    pub sent: usize,
+}
+
+/// Outcome of one anti-entropy round.
+#[derive(Debug, Default, Clone)]
+pub struct GossipRound {
+    /// Peers synced successfully, with what was exchanged
+    pub synced: Vec<(SocketAddr, SyncReport)>,
+    /// Peers that could not be synced, with the error message
+    pub failed: Vec<(SocketAddr, String)>,
+}
+
+/// Wire messages, serialized as one JSON object per line
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum WireMessage {
+    Sync { from: NodeId, known: Vec<String> },
+    Delta { updates: Vec<VersionedUpdate>, known: Vec<String> },
+    Push { updates: Vec<VersionedUpdate> },
+}
+
+/// Updates and state held by one node
+#[derive(Debug, Default)]
+struct Replica {
+    clock: VectorClock,
+    state: HashMap<String, (OpType, AlgebraicValue)>,
+    updates: Vec<VersionedUpdate>,
+    applied: HashSet<String>,
+    /// Where updates are persisted before being applied, if anywhere
+    store: Option<NodeStateStore>,
+}
+
+impl Replica {
+    fn from_store(store: NodeStateStore) -> Self {
+        Self {
+            clock: store.clock().clone(),
+            state: store.state().clone(),
+            updates: store.logged_updates().to_vec(),
+            applied: store.applied().clone(),
+            store: Some(store),
+        }
+    }
+
+    /// Persist and apply an update unless already applied; true if it was new
+    fn receive(&mut self, update: VersionedUpdate) -> Result<bool, GossipError> {
+        let key = update.dedup_key();
+        if self.applied.contains(&key) {
+            return Ok(false);
+        }
+        if let Some(store) = &mut self.store {
+            store.record(&update)?;
+        }
+        self.applied.insert(key);
+        apply_to_state(&mut self.state, &mut self.clock, &update);
+        self.updates.push(update);
+        Ok(true)
+    }
+
+    fn known(&self) -> Vec<String> {
+        self.applied.iter().cloned().collect()
+    }
+
+    /// Updates whose keys are not in `known`
+    fn missing_from(&self, known: &[String]) -> Vec<VersionedUpdate> {
+        let known: HashSet<&str> = known.iter().map(String::as_str).collect();
+        self.updates
+            .iter()
+            .filter(|u| !known.contains(u.dedup_key().as_str()))
+            .cloned()
+            .collect()
+    }
+}
+
+struct Inner {
+    node_id: NodeId,
+    config: GossipConfig,
+    local_addr: SocketAddr,
+    replica: Mutex<Replica>,
+    peers: RwLock<Vec<SocketAddr>>,
+    stats: Mutex<GossipStats>,
+    round: AtomicU64,
+    shutdown: AtomicBool,
+}
+
+/// A node that commits algebraic transactions locally and propagates them
+/// to peers over TCP.
+///
+/// Binding starts a listener thread that answers peers' exchanges. Rounds
+/// run on demand with [`anti_entropy_round`](Self::anti_entropy_round), or
+/// every `config.interval` after [`start`](Self::start). Threads stop on
+/// [`shutdown`](Self::shutdown) or when the node is dropped.
+pub struct GossipNode {
+    inner: Arc<Inner>,
+    threads: Mutex<Vec<JoinHandle<()>>>,
+}
+
+impl GossipNode {
+    /// Bind a listener and start answering peers. State is kept in memory
+    /// only; see [`bind_with_store`](Self::bind_with_store).
+    pub fn bind(
+        node_id: NodeId,
+        addr: impl ToSocketAddrs,
+        config: GossipConfig,
+    ) -> Result<Self, GossipError> {
+        Self::bind_replica(node_id, addr, config, Replica::default())
+    }
+
+    /// Bind a node whose updates are persisted to `store` before they are
+    /// applied. The node starts from the state, clock and updates the store
+    /// recovered, so a restarted node resumes where it stopped.
+    pub fn bind_with_store(
+        node_id: NodeId,
+        addr: impl ToSocketAddrs,
+        config: GossipConfig,
+        store: NodeStateStore,
+    ) -> Result<Self, GossipError> {
+        Self::bind_replica(node_id, addr, config, Replica::from_store(store))
+    }
+
+    fn bind_replica(
+        node_id: NodeId,
+        addr: impl ToSocketAddrs,
+        config: GossipConfig,
+        replica: Replica,
+    ) -> Result<Self, GossipError> {
+        let listener = TcpListener::bind(addr)?;
+        let inner = Arc::new(Inner {
+            node_id,
+            config,
+            local_addr: listener.local_addr()?,
+            replica: Mutex::new(replica),
+            peers: RwLock::new(Vec::new()),
+            stats: Mutex::new(GossipStats::default()),
+            round: AtomicU64::new(0),
+            shutdown: AtomicBool::new(false),
+        });
+
+        let listener_inner = Arc::clone(&inner);
+        let handle = thread::spawn(move || listen(listener_inner, listener));
+
+        Ok(Self {
+            inner,
+            threads: Mutex::new(vec![handle]),
+        })
+    }
+
+    /// This node's identifier.
+    pub fn node_id(&self) -> &NodeId {
+        &self.inner.node_id
+    }
+
+    /// Address the listener is bound to.
+    pub fn local_addr(&self) -> SocketAddr {
+        self.inner.local_addr
+    }
+
+    /// Add a peer (ignored if already known or if it is this node).
+    pub fn add_peer(&self, addr: SocketAddr) {
+        let mut peers = self.inner.peers.write();
+        if addr != self.inner.local_addr && !peers.contains(&addr) {
+            peers.push(addr);
+        }
+    }
+
+    /// Remove a peer; true if it was known.
+    pub fn remove_peer(&self, addr: SocketAddr) -> bool {
+        let mut peers = self.inner.peers.write();
+        let before = peers.len();
+        peers.retain(|p| *p != addr);
+        peers.len() != before
+    }
+
+    /// Known peers, in the order they were added.
+    pub fn peers(&self) -> Vec<SocketAddr> {
+        self.inner.peers.read().clone()
+    }
+
+    /// Commit a transaction locally; peers receive it on later rounds.
+    pub fn commit(&self, tx: AlgebraicTransaction) -> Result<VersionedUpdate, GossipError> {
+        let mut replica = self.inner.replica.lock();
+        let update = LocalCommitProtocol::commit_local(&tx, &self.inner.node_id, &mut replica.clock)?;
+        replica.receive(update.clone())?;
+        Ok(update)
+    }
+
+    /// Apply an update obtained out of band; false if already applied.
+    pub fn receive(&self, update: VersionedUpdate) -> Result<bool, GossipError> {
+        self.inner.replica.lock().receive(update)
+    }
+
+    /// Checkpoint the state store, if the node has one.
+    ///
+    /// The store's log is truncated, so after a restart this node can only
+    /// re-send updates applied since; checkpoint once peers have converged.
+    pub fn checkpoint(&self) -> Result<(), GossipError> {
+        if let Some(store) = &mut self.inner.replica.lock().store {
+            store.checkpoint()?;
+        }
+        Ok(())
+    }
+
+    /// Current value for a key.
+    pub fn get_state(&self, key: &str) -> Option<AlgebraicValue> {
+        self.inner.replica.lock().state.get(key).map(|(_, v)| v.clone())
+    }
+
+    /// All keys in state.
+    pub fn keys(&self) -> Vec<String> {
+        self.inner.replica.lock().state.keys().cloned().collect()
+    }
+
+    /// Snapshot of the full state (key -> value).
+    pub fn state(&self) -> HashMap<String, AlgebraicValue> {
+        self.inner
+            .replica
+            .lock()
+            .state
+            .iter()
+            .map(|(k, (_, v))| (k.clone(), v.clone()))
+            .collect()
+    }
+
+    /// Current vector clock.
+    pub fn clock(&self) -> VectorClock {
+        self.inner.replica.lock().clock.clone()
+    }
+
+    /// Number of distinct updates applied (local and received).
+    pub fn update_count(&self) -> usize {
+        self.inner.replica.lock().updates.len()
+    }
+
+    /// Updates applied after the first `start`, in the order they were
+    /// applied. Keep `start + returned.len()` as a cursor to mirror this
+    /// node's updates into another component.
+    pub fn updates_since(&self, start: usize) -> Vec<VersionedUpdate> {
+        self.inner.replica.lock().updates.get(start..).unwrap_or_default().to_vec()
+    }
+
+    /// Counters since the node was bound.
+    pub fn stats(&self) -> GossipStats {
+        self.inner.stats.lock().clone()
+    }
+
+    /// Run one push-pull exchange with a peer.
+    pub fn sync_with(&self, peer: SocketAddr) -> Result<SyncReport, GossipError> {
+        let result = sync_with(&self.inner, peer);
+        let mut stats = self.inner.stats.lock();
+        match &result {
+            Ok(report) => {
+                stats.syncs += 1;
+                stats.updates_received += report.received as u64;
+                stats.updates_sent += report.sent as u64;
+            }
+            Err(_) => stats.failed_syncs += 1,
+        }
+        result
+    }
+
+    /// Sync with up to `fanout` peers, rotating through the peer list so
+    /// every peer is reached over successive rounds. Failures are reported,
+    /// not returned; the peer is retried on a later round.
+    pub fn anti_entropy_round(&self) -> GossipRound {
+        let peers = self.inner.peers.read().clone();
+        let round = self.inner.round.fetch_add(1, Ordering::SeqCst);
+        self.inner.stats.lock().rounds += 1;
+
+        let mut report = GossipRound::default();
+        if peers.is_empty() {
+            return report;
+        }
+
+        let fanout = match self.inner.config.fanout {
+            0 => peers.len(),
+            n => n.min(peers.len()),
+        };
+        let start = (round as usize).wrapping_mul(fanout) % peers.len();
+        for i in 0..fanout {
+            let peer = peers[(start + i) % peers.len()];
+            match self.sync_with(peer) {
+                Ok(sync) => report.synced.push((peer, sync)),
+                Err(e) => {
+                    tracing::debug!(node = %self.inner.node_id, %peer, error = %e, "gossip sync failed");
+                    report.failed.push((peer, e.to_string()));
+                }
+            }
+        }
+        report
+    }
+
+    /// Run anti-entropy rounds every `config.interval` in the background.
+    /// Calling it again has no effect.
+    pub fn start(&self) {
+        let mut threads = self.threads.lock();
+        if threads.len() > 1 || self.inner.shutdown.load(Ordering::SeqCst) {
+            return;
+        }
+
+        let node = GossipNode {
+            inner: Arc::clone(&self.inner),
+            threads: Mutex::new(Vec::new()),
+        };
+        threads.push(thread::spawn(move || {
+            let step = Duration::from_millis(10).min(node.inner.config.interval);
+            while !node.inner.shutdown.load(Ordering::SeqCst) {
+                node.anti_entropy_round();
+
+                let mut waited = Duration::ZERO;
+                while waited < node.inner.config.interval && !node.inner.shutdown.load(Ordering::SeqCst) {
+                    thread::sleep(step);
+                    waited += step;
+                }
+            }
+        }));
+    }
+
+    /// Stop the listener and background rounds, waiting for them to exit.
+    pub fn shutdown(&self) {
+        if self.inner.shutdown.swap(true, Ordering::SeqCst) {
+            return;
+        }
+
+        // Wake the listener blocked in accept()
+        let mut wake = self.inner.local_addr;
+        if wake.ip().is_unspecified() {
+            wake.set_ip(if wake.is_ipv4() {
+                std::net::Ipv4Addr::LOCALHOST.into()
+            } else {
+                std::net::Ipv6Addr::LOCALHOST.into()
+            });
+        }
+        let _ = TcpStream::connect_timeout(&wake, self.inner.config.connect_timeout);
+
+        for handle in self.threads.lock().drain(..) {
+            let _ = handle.join();
+        }
+    }
+}
+
+impl Drop for GossipNode {
+    fn drop(&mut self) {
+        // Clones made for background rounds own no threads
+        if !self.threads.get_mut().is_empty() {
+            self.shutdown();
+        }
+    }
+}
+
+impl std::fmt::Debug for GossipNode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GossipNode")
+            .field("node_id", &self.inner.node_id)
+            .field("local_addr", &self.inner.local_addr)
+            .field("peers", &*self.inner.peers.read())
+            .finish()
+    }
+}
+
+fn listen(inner: Arc<Inner>, listener: TcpListener) {
+    for stream in listener.incoming() {
+        if inner.shutdown.load(Ordering::SeqCst) {
+            break;
+        }
+        let Ok(stream) = stream else {
+            continue;
+        };
+        let inner = Arc::clone(&inner);
+        thread::spawn(move || {
+            let peer = stream.peer_addr().ok();
+            let result = answer(&inner, stream);
+            let mut stats = inner.stats.lock();
+            match result {
+                Ok(report) => {
+                    stats.syncs += 1;
+                    stats.updates_received += report.received as u64;
+                    stats.updates_sent += report.sent as u64;
+                }
+                Err(e) => {
+                    stats.failed_syncs += 1;
+                    tracing::debug!(node = %inner.node_id, ?peer, error = %e, "gossip exchange failed");
+                }
+            }
+        });
+    }
+}
+
+fn configure(stream: &TcpStream, config: &GossipConfig) -> Result<(), GossipError> {
+    stream.set_read_timeout(Some(config.io_timeout))?;
+    stream.set_write_timeout(Some(config.io_timeout))?;
+    stream.set_nodelay(true)?;
+    Ok(())
+}
+
+fn send(writer: &mut BufWriter<&TcpStream>, message: &WireMessage) -> Result<(), GossipError> {
+    serde_json::to_writer(&mut *writer, message)?;
+    writer.write_all(b"\n")?;
+    writer.flush()?;
+    Ok(())
+}
+
+fn recv(reader: &mut BufReader<&TcpStream>, peer: SocketAddr) -> Result<WireMessage, GossipError> {
+    let mut line = String::new();
+    if reader.read_line(&mut line)? == 0 {
+        return Err(GossipError::Protocol {
+            peer,
+            message: "connection closed".to_string(),
+        });
+    }
+    Ok(serde_json::from_str(&line)?)
+}
+
+fn unexpected(peer: SocketAddr, expected: &str, got: &WireMessage) -> GossipError {
+    GossipError::Protocol {
+        peer,
+        message: format!("expected {}, got {:?}", expected, got),
+    }
+}
+
+/// Apply received updates, counting duplicates; returns how many were new
+fn apply_received(inner: &Inner, updates: Vec<VersionedUpdate>) -> Result<usize, GossipError> {
+    let total = updates.len();
+    let mut received = 0;
+    {
+        let mut replica = inner.replica.lock();
+        for update in updates {
+            if replica.receive(update)? {
+                received += 1;
+            }
+        }
+    }
+    inner.stats.lock().duplicates_ignored += (total - received) as u64;
+    Ok(received)
+}
+
+/// Initiator side of an exchange
+fn sync_with(inner: &Inner, peer: SocketAddr) -> Result<SyncReport, GossipError> {
+    let stream = TcpStream::connect_timeout(&peer, inner.config.connect_timeout)?;
+    configure(&stream, &inner.config)?;
+    let mut reader = BufReader::new(&stream);
+    let mut writer = BufWriter::new(&stream);
+
+    let known = inner.replica.lock().known();
+    send(
+        &mut writer,
+        &WireMessage::Sync {
+            from: inner.node_id.clone(),
+            known,
+        },
+    )?;
+
+    let (updates, peer_known) = match recv(&mut reader, peer)? {
+        WireMessage::Delta { updates, known } => (updates, known),
+        other => return Err(unexpected(peer, "delta", &other)),
+    };
+    let received = apply_received(inner, updates)?;
+
+    let push = inner.replica.lock().missing_from(&peer_known);
+    let sent = push.len();
+    send(&mut writer, &WireMessage::Push { updates: push })?;
+
+    Ok(SyncReport { received, sent })
+}
+
+/// Responder side of an exchange
+fn answer(inner: &Inner, stream: TcpStream) -> Result<SyncReport, GossipError> {
+    configure(&stream, &inner.config)?;
+    let peer = stream.peer_addr()?;
+    let mut reader = BufReader::new(&stream);
+    let mut writer = BufWriter::new(&stream);
+
+    let known = match recv(&mut reader, peer)? {
+        WireMessage::Sync { known, .. } => known,
+        other => return Err(unexpected(peer, "sync", &other)),
+    };
+
+    let (delta, own_known) = {
+        let replica = inner.replica.lock();
+        (replica.missing_from(&known), replica.known())
+    };
+    let sent = delta.len();
+    send(
+        &mut writer,
+        &WireMessage::Delta {
+            updates: delta,
+            known: own_known,
+        },
+    )?;
+
+    let received = match recv(&mut reader, peer)? {
+        WireMessage::Push { updates } => apply_received(inner, updates)?,
+        other => return Err(unexpected(peer, "push", &other)),
+    };
+
+    Ok(SyncReport { received, sent })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distributed::{AlgebraicOperation, SimulatedCluster};
+    use std::time::Instant;
+
+    fn add_tx(key: &str, value: i64) -> AlgebraicTransaction {
+        let mut tx = AlgebraicTransaction::new();
+        tx.add_operation(AlgebraicOperation::new(key, OpType::AbelianAdd, AlgebraicValue::integer(value)));
+        tx
+    }
+
+    fn node(name: &str, config: GossipConfig) -> GossipNode {
+        GossipNode::bind(NodeId::new(name), "127.0.0.1:0", config).unwrap()
+    }
+
+    #[test]
+    fn test_sync_exchanges_both_ways_and_deduplicates() {
+        let a = node("a", GossipConfig::default());
+        let b = node("b", GossipConfig::default());
+        a.commit(add_tx("counter", 10)).unwrap();
+        b.commit(add_tx("counter", 5)).unwrap();
+        b.commit(add_tx("counter", 1)).unwrap();
+
+        let report = a.sync_with(b.local_addr()).unwrap();
+        assert_eq!(report, SyncReport { received: 2, sent: 1 });
+        assert_eq!(a.get_state("counter"), Some(AlgebraicValue::integer(16)));
+
+        // The responder applies the push after the initiator returns
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while b.update_count() < 3 && Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(5));
+        }
+        assert_eq!(b.get_state("counter"), Some(AlgebraicValue::integer(16)));
+
+        // Nothing new the second time, and nothing applied twice
+        assert_eq!(a.sync_with(b.local_addr()).unwrap(), SyncReport::default());
+        let received = a.updates_since(1);
+        assert_eq!(received.len(), 2);
+        assert!(received.iter().all(|u| u.origin_node() == b.node_id()));
+        assert!(a.updates_since(5).is_empty());
+        let replayed = a.updates_since(0)[0].clone();
+        assert!(!a.receive(replayed).unwrap());
+        assert_eq!(a.get_state("counter"), Some(AlgebraicValue::integer(16)));
+    }
+
+    #[test]
+    fn test_rounds_converge_like_simulation() {
+        // Line topology a - b - c: a and c only meet through b
+        let config = GossipConfig::default().with_fanout(0);
+        let nodes: Vec<_> = ["a", "b", "c"].iter().map(|n| node(n, config.clone())).collect();
+        nodes[0].add_peer(nodes[1].local_addr());
+        nodes[1].add_peer(nodes[0].local_addr());
+        nodes[1].add_peer(nodes[2].local_addr());
+        nodes[2].add_peer(nodes[1].local_addr());
+
+        let mut cluster = SimulatedCluster::new(3);
+        for (i, value) in [10, 20, 30].into_iter().enumerate() {
+            nodes[i].commit(add_tx("counter", value)).unwrap();
+            cluster.commit_on_node(i, add_tx("counter", value)).unwrap();
+        }
+        cluster.propagate_all();
+
+        for _ in 0..2 {
+            for n in &nodes {
+                assert!(n.anti_entropy_round().failed.is_empty());
+            }
+        }
+
+        let expected = cluster.get_node_state(0, "counter").cloned();
+        assert_eq!(expected, Some(AlgebraicValue::integer(60)));
+        for n in &nodes {
+            assert_eq!(n.get_state("counter"), expected);
+            assert_eq!(n.update_count(), 3);
+        }
+    }
+
+    #[test]
+    fn test_node_with_store_recovers_after_restart() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let peer = node("peer", GossipConfig::default());
+        peer.commit(add_tx("counter", 4)).unwrap();
+
+        {
+            let store = NodeStateStore::open(temp.path()).unwrap();
+            let a = GossipNode::bind_with_store(NodeId::new("a"), "127.0.0.1:0", GossipConfig::default(), store).unwrap();
+            a.commit(add_tx("counter", 10)).unwrap();
+            a.sync_with(peer.local_addr()).unwrap();
+            assert_eq!(a.get_state("counter"), Some(AlgebraicValue::integer(14)));
+        }
+
+        let store = NodeStateStore::open(temp.path()).unwrap();
+        let a = GossipNode::bind_with_store(NodeId::new("a"), "127.0.0.1:0", GossipConfig::default(), store).unwrap();
+        assert_eq!(a.get_state("counter"), Some(AlgebraicValue::integer(14)));
+        assert_eq!(a.clock().get(&NodeId::new("a")), 1);
+
+        // Nothing is re-applied, and new commits continue the clock
+        assert_eq!(a.sync_with(peer.local_addr()).unwrap().received, 0);
+        let update = a.commit(add_tx("counter", 1)).unwrap();
+        assert_eq!(update.clock().get(&NodeId::new("a")), 2);
+    }
+
+    #[test]
+    fn test_background_rounds_and_unreachable_peers() {
+        let config = GossipConfig::default()
+            .with_interval(Duration::from_millis(20))
+            .with_connect_timeout(Duration::from_millis(200));
+        let a = node("a", config.clone());
+        let b = node("b", config);
+
+        // A peer that is not listening fails without stopping the round
+        let dead = {
+            let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+            listener.local_addr().unwrap()
+        };
+        a.add_peer(dead);
+        a.add_peer(b.local_addr());
+        let round = a.anti_entropy_round();
+        assert_eq!(round.failed.len(), 1);
+        assert_eq!(round.synced.len(), 1);
+        assert!(a.remove_peer(dead));
+
+        a.start();
+        b.commit(add_tx("counter", 7)).unwrap();
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while a.get_state("counter").is_none() && Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(10));
+        }
+        assert_eq!(a.get_state("counter"), Some(AlgebraicValue::integer(7)));
+        assert!(a.stats().rounds >= 2);
+
+        a.shutdown();
+        assert!(a.sync_with(b.local_addr()).is_ok());
+    }
+}
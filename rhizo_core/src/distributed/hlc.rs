@@ -0,0 +1,267 @@
+//! Hybrid logical clocks: constant-size timestamps for many-node deployments.
+//!
+//! A [`VectorClock`](super::VectorClock) keeps one entry per node that ever
+//! committed, so with many short-lived nodes every update carries a growing
+//! map. An [`HlcClock`] is a single timestamp — physical milliseconds plus a
+//! logical counter — that stays close to wall-clock time while still
+//! respecting causality:
+//!
+//! - a local event moves the clock past both its previous value and the
+//!   current physical time;
+//! - merging a received clock takes the later of the two, so the next tick
+//!   is after everything seen.
+//!
+//! # Trade-off
+//!
+//! If update A happened before update B, A's timestamp is lower. The
+//! converse does not hold: updates made independently on different nodes
+//! are ordered by their timestamps and only reported as
+//! [`CausalOrder::Concurrent`] on an exact tie. Algebraic operations merge
+//! the same way in either case, so the local commit protocol converges
+//! with either clock.
+//!
+//! # Usage
+//!
+//! ```
+//! use rhizo_core::distributed::{
+//!     AlgebraicOperation, AlgebraicTransaction, CausalOrder, HlcClock,
+//!     LocalCommitProtocol, NodeId,
+//! };
+//! use rhizo_core::algebraic::{OpType, AlgebraicValue};
+//!
+//! let node = NodeId::new("worker-7f3a");
+//! let mut clock = HlcClock::new();
+//!
+//! let mut tx = AlgebraicTransaction::new();
+//! tx.add_operation(AlgebraicOperation::new(
+//!     "page_views",
+//!     OpType::AbelianAdd,
+//!     AlgebraicValue::integer(1),
+//! ));
+//!
+//! let first = LocalCommitProtocol::commit_local(&tx, &node, &mut clock).unwrap();
+//! let second = LocalCommitProtocol::commit_local(&tx, &node, &mut clock).unwrap();
+//! assert_eq!(first.compare(&second), CausalOrder::Before);
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::fmt;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use super::vector_clock::{CausalClock, CausalOrder, NodeId};
+
+/// A hybrid logical clock timestamp.
+///
+/// Ordered by physical time, then logical counter; the node that last
+/// advanced the clock breaks ties when merging.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct HlcClock {
+    /// Physical component: milliseconds since the Unix epoch
+    wall_ms: u64,
+    /// Orders events within the same millisecond
+    logical: u32,
+    /// Node that last advanced the clock
+    node: Option<NodeId>,
+}
+
+impl HlcClock {
+    /// Create a new clock at time zero.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Physical component, in milliseconds since the Unix epoch.
+    pub fn wall_ms(&self) -> u64 {
+        self.wall_ms
+    }
+
+    /// Logical counter within `wall_ms`.
+    pub fn logical(&self) -> u32 {
+        self.logical
+    }
+
+    /// Node that last advanced the clock, if any.
+    pub fn node(&self) -> Option<&NodeId> {
+        self.node.as_ref()
+    }
+
+    /// Check if the clock has never ticked.
+    pub fn is_empty(&self) -> bool {
+        self.wall_ms == 0 && self.logical == 0
+    }
+
+    /// Advance the clock for a local event, given the physical time.
+    ///
+    /// The result is always after the previous value, even if physical
+    /// time went backwards.
+    pub fn tick_at(&mut self, node_id: &NodeId, physical_ms: u64) {
+        if physical_ms > self.wall_ms {
+            self.wall_ms = physical_ms;
+            self.logical = 0;
+        } else if self.logical == u32::MAX {
+            // Counter exhausted within one millisecond: borrow the next one
+            self.wall_ms += 1;
+            self.logical = 0;
+        } else {
+            self.logical += 1;
+        }
+        self.node = Some(node_id.clone());
+    }
+
+    fn timestamp(&self) -> (u64, u32) {
+        (self.wall_ms, self.logical)
+    }
+
+    fn merge_key(&self) -> (u64, u32, Option<&str>) {
+        (self.wall_ms, self.logical, self.node.as_ref().map(NodeId::as_str))
+    }
+}
+
+impl CausalClock for HlcClock {
+    fn tick(&mut self, node_id: &NodeId) {
+        self.tick_at(node_id, physical_now_ms());
+    }
+
+    fn merge(&mut self, other: &Self) {
+        if other.merge_key() > self.merge_key() {
+            *self = other.clone();
+        }
+    }
+
+    fn compare(&self, other: &Self) -> CausalOrder {
+        match self.timestamp().cmp(&other.timestamp()) {
+            Ordering::Less => CausalOrder::Before,
+            Ordering::Greater => CausalOrder::After,
+            Ordering::Equal if self.node == other.node => CausalOrder::Equal,
+            // Neither could have seen the other: merging moves past both
+            Ordering::Equal => CausalOrder::Concurrent,
+        }
+    }
+
+    /// `wall_ms.logical`
+    fn fingerprint(&self) -> String {
+        format!("{}.{}", self.wall_ms, self.logical)
+    }
+}
+
+impl fmt::Display for HlcClock {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.wall_ms, self.logical)?;
+        if let Some(node) = &self.node {
+            write!(f, "@{}", node)?;
+        }
+        Ok(())
+    }
+}
+
+fn physical_now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algebraic::{AlgebraicValue, OpType};
+    use crate::distributed::{AlgebraicOperation, AlgebraicTransaction, LocalCommitProtocol};
+
+    fn node(s: &str) -> NodeId {
+        NodeId::new(s)
+    }
+
+    #[test]
+    fn test_tick_is_monotonic_when_physical_time_goes_back() {
+        let n = node("a");
+        let mut clock = HlcClock::new();
+
+        clock.tick_at(&n, 1_000);
+        let first = clock.clone();
+        clock.tick_at(&n, 900);
+        let second = clock.clone();
+        clock.tick_at(&n, 1_000);
+
+        assert_eq!((second.wall_ms(), second.logical()), (1_000, 1));
+        assert_eq!((clock.wall_ms(), clock.logical()), (1_000, 2));
+        assert_eq!(first.compare(&second), CausalOrder::Before);
+        assert_eq!(clock.compare(&second), CausalOrder::After);
+
+        clock.tick_at(&n, 2_000);
+        assert_eq!((clock.wall_ms(), clock.logical()), (2_000, 0));
+    }
+
+    #[test]
+    fn test_receive_moves_past_sender() {
+        let (a, b) = (node("a"), node("b"));
+
+        // b's physical clock lags a's
+        let mut clock_a = HlcClock::new();
+        clock_a.tick_at(&a, 5_000);
+        let mut clock_b = HlcClock::new();
+        clock_b.tick_at(&b, 4_000);
+
+        clock_b.merge(&clock_a);
+        clock_b.tick_at(&b, 4_001);
+
+        assert_eq!(clock_a.compare(&clock_b), CausalOrder::Before);
+        assert_eq!((clock_b.wall_ms(), clock_b.logical()), (5_000, 1));
+    }
+
+    #[test]
+    fn test_merge_is_commutative_and_idempotent() {
+        let mut x = HlcClock::new();
+        x.tick_at(&node("a"), 10);
+        let mut y = HlcClock::new();
+        y.tick_at(&node("b"), 10);
+
+        let mut xy = x.clone();
+        xy.merge(&y);
+        let mut yx = y.clone();
+        yx.merge(&x);
+        assert_eq!(xy, yx);
+
+        let mut again = xy.clone();
+        again.merge(&y);
+        assert_eq!(again, xy);
+
+        // Same timestamp on different nodes: neither saw the other
+        assert_eq!(x.compare(&y), CausalOrder::Concurrent);
+    }
+
+    #[test]
+    fn test_local_commit_with_hlc() {
+        let (a, b) = (node("a"), node("b"));
+        let mut clock_a = HlcClock::new();
+        let mut clock_b = HlcClock::new();
+
+        let mut tx = AlgebraicTransaction::new();
+        tx.add_operation(AlgebraicOperation::new("views", OpType::AbelianAdd, AlgebraicValue::integer(2)));
+
+        let first = LocalCommitProtocol::commit_local(&tx, &a, &mut clock_a).unwrap();
+        let second = LocalCommitProtocol::commit_local(&tx, &a, &mut clock_a).unwrap();
+        let other = LocalCommitProtocol::commit_local(&tx, &b, &mut clock_b).unwrap();
+
+        assert_ne!(first.dedup_key(), second.dedup_key());
+        assert_eq!(first.compare(&second), CausalOrder::Before);
+
+        let merged = LocalCommitProtocol::merge_all(&[first, second, other]).unwrap();
+        assert_eq!(merged.operations()[0].value(), &AlgebraicValue::integer(6));
+        assert_ne!(merged.clock().compare(&clock_a), CausalOrder::Before);
+        assert_ne!(merged.clock().compare(&clock_b), CausalOrder::Before);
+    }
+
+    #[test]
+    fn test_serialization_roundtrip() {
+        let mut clock = HlcClock::new();
+        clock.tick_at(&node("a"), 42);
+        clock.tick_at(&node("a"), 42);
+
+        let json = serde_json::to_string(&clock).unwrap();
+        let parsed: HlcClock = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(parsed, clock);
+        assert_eq!(parsed.to_string(), "42.1@a");
+    }
+}
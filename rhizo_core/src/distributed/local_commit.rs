@@ -92,8 +92,9 @@
 //! assert_eq!(counter_op.value().as_integer(), Some(8));
 //! ```
 
-use super::vector_clock::{CausalOrder, NodeId, VectorClock};
+use super::vector_clock::{CausalClock, CausalOrder, NodeId, VectorClock};
 use crate::algebraic::{AlgebraicMerger, AlgebraicValue, MergeResult, OpType};
+use crate::error::{ErrorCategory, ErrorContext, ErrorInfo};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -135,7 +136,7 @@ impl AlgebraicOperation {
     /// Get the operation type.
     #[inline]
     pub fn op_type(&self) -> OpType {
-        self.op_type
+        self.op_type.clone()
     }
 
     /// Get the value.
@@ -229,26 +230,29 @@ impl AlgebraicTransaction {
 /// The result of a local commit: operations with their causal context.
 ///
 /// A VersionedUpdate represents a committed set of operations along with
-/// the vector clock at the time of commit. This allows other nodes to:
+/// the clock at the time of commit. This allows other nodes to:
 /// 1. Determine the causal relationship with their own state
 /// 2. Merge concurrent updates correctly
+///
+/// The clock is a [`VectorClock`] unless another [`CausalClock`] (such as
+/// [`HlcClock`](super::HlcClock)) is used to commit.
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct VersionedUpdate {
+pub struct VersionedUpdate<C = VectorClock> {
     /// The operations that were committed
     operations: Vec<AlgebraicOperation>,
-    /// The vector clock at commit time
-    clock: VectorClock,
+    /// The clock at commit time
+    clock: C,
     /// The node that created this update
     origin_node: NodeId,
     /// Optional update ID for deduplication
     update_id: Option<String>,
 }
 
-impl VersionedUpdate {
+impl<C: CausalClock> VersionedUpdate<C> {
     /// Create a new versioned update.
     pub fn new(
         operations: Vec<AlgebraicOperation>,
-        clock: VectorClock,
+        clock: C,
         origin_node: NodeId,
     ) -> Self {
         Self {
@@ -262,7 +266,7 @@ impl VersionedUpdate {
     /// Create with an explicit update ID.
     pub fn with_id(
         operations: Vec<AlgebraicOperation>,
-        clock: VectorClock,
+        clock: C,
         origin_node: NodeId,
         update_id: impl Into<String>,
     ) -> Self {
@@ -280,9 +284,9 @@ impl VersionedUpdate {
         &self.operations
     }
 
-    /// Get the vector clock.
+    /// Get the clock.
     #[inline]
-    pub fn clock(&self) -> &VectorClock {
+    pub fn clock(&self) -> &C {
         &self.clock
     }
 
@@ -298,19 +302,32 @@ impl VersionedUpdate {
         self.update_id.as_deref()
     }
 
+    /// Identity used to deduplicate this update when it arrives more than once.
+    ///
+    /// The explicit update ID if set, otherwise the origin node plus the full
+    /// clock (see [`CausalClock::fingerprint`]). The clock sum alone is not
+    /// unique across different updates.
+    pub fn dedup_key(&self) -> String {
+        if let Some(id) = self.update_id() {
+            return id.to_string();
+        }
+        format!("{}@{}", self.origin_node, self.clock.fingerprint())
+    }
+
     /// Compare this update's causality with another.
-    pub fn compare(&self, other: &VersionedUpdate) -> CausalOrder {
+    pub fn compare(&self, other: &VersionedUpdate<C>) -> CausalOrder {
         self.clock.compare(&other.clock)
     }
 
     /// Check if this update is concurrent with another.
-    pub fn is_concurrent_with(&self, other: &VersionedUpdate) -> bool {
-        self.clock.concurrent_with(&other.clock)
+    pub fn is_concurrent_with(&self, other: &VersionedUpdate<C>) -> bool {
+        self.compare(other) == CausalOrder::Concurrent
     }
 }
 
 /// Error type for local commit operations.
 #[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
 pub enum LocalCommitError {
     /// Transaction contains non-algebraic operations.
     NonAlgebraic {
@@ -330,6 +347,43 @@ pub enum LocalCommitError {
         type1: &'static str,
         type2: &'static str,
     },
+    /// A BOUNDED_ADD operation needs more rights than the node holds.
+    BoundExhausted {
+        key: String,
+        reason: String,
+    },
+}
+
+impl ErrorInfo for LocalCommitError {
+    fn code(&self) -> &'static str {
+        match self {
+            Self::NonAlgebraic { .. } => "LOCAL_COMMIT_NON_ALGEBRAIC",
+            Self::EmptyTransaction => "LOCAL_COMMIT_EMPTY_TRANSACTION",
+            Self::MergeFailed { .. } => "LOCAL_COMMIT_MERGE_FAILED",
+            Self::TypeMismatch { .. } => "LOCAL_COMMIT_TYPE_MISMATCH",
+            Self::BoundExhausted { .. } => "LOCAL_COMMIT_BOUND_EXHAUSTED",
+        }
+    }
+
+    fn category(&self) -> ErrorCategory {
+        match self {
+            Self::MergeFailed { .. } => ErrorCategory::Conflict,
+            Self::BoundExhausted { .. } => ErrorCategory::FailedPrecondition,
+            _ => ErrorCategory::InvalidArgument,
+        }
+    }
+
+    fn context(&self) -> ErrorContext {
+        match self {
+            Self::NonAlgebraic { keys, .. } if keys.len() == 1 => ErrorContext::new().with_key(keys[0].clone()),
+            Self::MergeFailed { key, .. }
+            | Self::TypeMismatch { key, .. }
+            | Self::BoundExhausted { key, .. } => {
+                ErrorContext::new().with_key(key.clone())
+            }
+            _ => ErrorContext::new(),
+        }
+    }
 }
 
 impl std::fmt::Display for LocalCommitError {
@@ -345,6 +399,9 @@ impl std::fmt::Display for LocalCommitError {
             Self::TypeMismatch { key, type1, type2 } => {
                 write!(f, "Type mismatch for key '{}': {} vs {}", key, type1, type2)
             }
+            Self::BoundExhausted { key, reason } => {
+                write!(f, "Bound budget exhausted for key '{}': {}", key, reason)
+            }
         }
     }
 }
@@ -364,7 +421,8 @@ impl LocalCommitProtocol {
     ///
     /// Returns `true` if all operations in the transaction are algebraic
     /// (semilattice or Abelian), meaning they commute and can be applied
-    /// in any order.
+    /// in any order, and every BOUNDED_ADD operation stays within the
+    /// rights its node holds.
     ///
     /// # Example
     /// ```
@@ -392,20 +450,60 @@ impl LocalCommitProtocol {
     /// ```
     #[inline]
     pub fn can_commit_locally(tx: &AlgebraicTransaction) -> bool {
-        !tx.is_empty() && tx.is_fully_algebraic()
+        !tx.is_empty() && tx.is_fully_algebraic() && Self::check_bounds(tx).is_ok()
+    }
+
+    /// Check BOUNDED_ADD operations against their escrow.
+    ///
+    /// Each must carry a [`BoundedCounter`](crate::algebraic::BoundedCounter)
+    /// in which no node spent more rights than it held: such updates merge
+    /// within the bounds whatever else commits concurrently. A plain delta
+    /// carries no rights, so its bound can only be checked with coordination.
+    /// Entries of maps merged with BOUNDED_ADD are checked the same way.
+    fn check_bounds(tx: &AlgebraicTransaction) -> Result<(), LocalCommitError> {
+        for op in tx.operations() {
+            if let Some(reason) = Self::bound_violation(&op.op_type(), op.value()) {
+                return Err(LocalCommitError::BoundExhausted {
+                    key: op.key().to_string(),
+                    reason,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    fn bound_violation(op_type: &OpType, value: &AlgebraicValue) -> Option<String> {
+        match (op_type, value) {
+            (OpType::BoundedAdd, AlgebraicValue::BoundedCounter(counter)) => {
+                counter.validate().err().map(|e| e.to_string())
+            }
+            (OpType::BoundedAdd, other) => Some(format!(
+                "{} carries no escrowed rights; use a BoundedCounter to commit locally",
+                other.type_name()
+            )),
+            (OpType::Map(entry_op), AlgebraicValue::Map(entries)) => {
+                let mut keys: Vec<&String> = entries.keys().collect();
+                keys.sort();
+                keys.into_iter().find_map(|k| {
+                    Self::bound_violation(entry_op, &entries[k]).map(|reason| format!("entry '{}': {}", k, reason))
+                })
+            }
+            _ => None,
+        }
     }
 
     /// Commit a transaction locally, returning a versioned update.
     ///
     /// This operation:
     /// 1. Validates that all operations are algebraic
-    /// 2. Increments the local vector clock
-    /// 3. Returns a VersionedUpdate that can be sent to other nodes
+    /// 2. Validates that bounded operations stay within their escrowed rights
+    /// 3. Ticks the local clock (a [`VectorClock`] or any other [`CausalClock`])
+    /// 4. Returns a VersionedUpdate that can be sent to other nodes
     ///
     /// # Arguments
     /// * `tx` - The transaction to commit
     /// * `node_id` - This node's ID
-    /// * `clock` - This node's vector clock (will be mutated)
+    /// * `clock` - This node's clock (will be mutated)
     ///
     /// # Returns
     /// * `Ok(VersionedUpdate)` - The committed update with causal context
@@ -435,11 +533,11 @@ impl LocalCommitProtocol {
     /// // Clock was incremented
     /// assert_eq!(clock.get(&node), 1);
     /// ```
-    pub fn commit_local(
+    pub fn commit_local<C: CausalClock>(
         tx: &AlgebraicTransaction,
         node_id: &NodeId,
-        clock: &mut VectorClock,
-    ) -> Result<VersionedUpdate, LocalCommitError> {
+        clock: &mut C,
+    ) -> Result<VersionedUpdate<C>, LocalCommitError> {
         // Validate: not empty
         if tx.is_empty() {
             return Err(LocalCommitError::EmptyTransaction);
@@ -456,6 +554,9 @@ impl LocalCommitProtocol {
             });
         }
 
+        // Validate: bounded operations are covered by escrow
+        Self::check_bounds(tx)?;
+
         // Increment the clock for this commit
         clock.tick(node_id);
 
@@ -473,7 +574,7 @@ impl LocalCommitProtocol {
     /// (potentially concurrent), this function:
     /// 1. Combines operations by key
     /// 2. Merges values using algebraic operations
-    /// 3. Computes the merged vector clock
+    /// 3. Computes the merged clock
     ///
     /// # Mathematical Guarantees
     ///
@@ -524,10 +625,10 @@ impl LocalCommitProtocol {
     /// assert_eq!(get_counter(&merged_ab), Some(8));
     /// assert_eq!(get_counter(&merged_ba), Some(8));
     /// ```
-    pub fn merge_updates(
-        update1: &VersionedUpdate,
-        update2: &VersionedUpdate,
-    ) -> Result<VersionedUpdate, LocalCommitError> {
+    pub fn merge_updates<C: CausalClock>(
+        update1: &VersionedUpdate<C>,
+        update2: &VersionedUpdate<C>,
+    ) -> Result<VersionedUpdate<C>, LocalCommitError> {
         // Group operations by key
         let mut by_key: HashMap<String, Vec<&AlgebraicOperation>> = HashMap::new();
 
@@ -552,8 +653,9 @@ impl LocalCommitProtocol {
             }
         }
 
-        // Merge the vector clocks
-        let merged_clock = VectorClock::max(update1.clock(), update2.clock());
+        // Merge the clocks
+        let mut merged_clock = update1.clock().clone();
+        merged_clock.merge(update2.clock());
 
         // The origin is a synthetic merge node
         // In practice, you might want to track this differently
@@ -598,7 +700,7 @@ impl LocalCommitProtocol {
 
         // Merge all subsequent values
         for op in ops.iter().skip(1) {
-            let merge_result = AlgebraicMerger::merge(op_type, &result_value, op.value());
+            let merge_result = AlgebraicMerger::merge(op_type.clone(), &result_value, op.value());
 
             match merge_result {
                 MergeResult::Merged(v) => result_value = v,
@@ -632,7 +734,9 @@ impl LocalCommitProtocol {
     ///
     /// Due to associativity, `merge([A, B, C])` produces the same result as
     /// `merge(merge(A, B), C)` or `merge(A, merge(B, C))`.
-    pub fn merge_all(updates: &[VersionedUpdate]) -> Result<VersionedUpdate, LocalCommitError> {
+    pub fn merge_all<C: CausalClock>(
+        updates: &[VersionedUpdate<C>],
+    ) -> Result<VersionedUpdate<C>, LocalCommitError> {
         if updates.is_empty() {
             return Err(LocalCommitError::EmptyTransaction);
         }
@@ -661,7 +765,7 @@ impl LocalCommitProtocol {
             }
         }
 
-        // Merge all vector clocks
+        // Merge all clocks
         let mut merged_clock = updates[0].clock().clone();
         for update in updates.iter().skip(1) {
             merged_clock.merge(update.clock());
@@ -1380,4 +1484,139 @@ mod tests {
         assert_eq!(get_counter(&merge_reverse), Some(150));
         assert_eq!(get_counter(&merge_all_result), Some(150));
     }
+
+    #[test]
+    fn test_concurrent_set_removal_commits_locally() {
+        use crate::algebraic::ObservedRemoveSet;
+
+        let node_a = NodeId::new("a");
+        let node_b = NodeId::new("b");
+        let mut clock_a = VectorClock::new();
+        let mut clock_b = VectorClock::new();
+
+        // Both nodes have seen "beta" added
+        let mut members = ObservedRemoveSet::new();
+        members.add_with_tag("beta", "a:1");
+
+        // A removes beta and adds gamma; B concurrently re-adds beta
+        let mut tx_a = AlgebraicTransaction::new();
+        tx_a.add_operation(AlgebraicOperation::new(
+            "members",
+            OpType::SemilatticeObservedRemove,
+            members.removal("beta").merge(&ObservedRemoveSet::added("gamma")).into(),
+        ));
+        let mut tx_b = AlgebraicTransaction::new();
+        tx_b.add_operation(AlgebraicOperation::new(
+            "members",
+            OpType::SemilatticeObservedRemove,
+            ObservedRemoveSet::added("beta").into(),
+        ));
+        assert!(LocalCommitProtocol::can_commit_locally(&tx_a));
+
+        let update_a = LocalCommitProtocol::commit_local(&tx_a, &node_a, &mut clock_a).unwrap();
+        let update_b = LocalCommitProtocol::commit_local(&tx_b, &node_b, &mut clock_b).unwrap();
+        let ab = LocalCommitProtocol::merge_updates(&update_a, &update_b).unwrap();
+        let ba = LocalCommitProtocol::merge_updates(&update_b, &update_a).unwrap();
+        assert_eq!(ab.operations()[0].value(), ba.operations()[0].value());
+
+        // Applied to the shared state, the concurrent re-add wins
+        let state = members.merge(match ab.operations()[0].value() {
+            AlgebraicValue::ObservedRemoveSet(delta) => delta,
+            other => panic!("Expected ObservedRemoveSet, got {:?}", other),
+        });
+        assert_eq!(state.elements().collect::<Vec<_>>(), vec!["beta", "gamma"]);
+    }
+
+    #[test]
+    fn test_bounded_counter_commits_locally_within_escrow() {
+        use crate::algebraic::{BoundedCounter, Bounds};
+
+        let node_a = NodeId::new("a");
+        let node_b = NodeId::new("b");
+        let mut clock_a = VectorClock::new();
+        let mut clock_b = VectorClock::new();
+
+        // 10 seats; a hands 3 of its decrement rights to b before they part
+        let mut seats = BoundedCounter::new("a", 10, Bounds::at_least(0)).unwrap();
+        seats.transfer_decrement_rights("a", "b", 3).unwrap();
+        let (mut at_a, mut at_b) = (seats.clone(), seats.clone());
+        at_a.decrement("a", 7).unwrap();
+        at_b.decrement("b", 3).unwrap();
+
+        let tx = |counter: &BoundedCounter| {
+            let mut tx = AlgebraicTransaction::new();
+            tx.add_operation(AlgebraicOperation::new("seats", OpType::BoundedAdd, counter.clone().into()));
+            tx
+        };
+        let update_a = LocalCommitProtocol::commit_local(&tx(&at_a), &node_a, &mut clock_a).unwrap();
+        let update_b = LocalCommitProtocol::commit_local(&tx(&at_b), &node_b, &mut clock_b).unwrap();
+        let merged = LocalCommitProtocol::merge_updates(&update_a, &update_b).unwrap();
+        assert_eq!(merged.operations()[0].value().to_string(), "0");
+
+        // b's budget is spent: selling another seat needs rights from elsewhere
+        assert!(at_b.decrement("b", 1).is_err());
+        let forged: BoundedCounter = serde_json::from_str(
+            &serde_json::to_string(&at_b)
+                .unwrap()
+                .replace(r#""negative":{"b":3}"#, r#""negative":{"b":4}"#),
+        )
+        .unwrap();
+        assert_eq!(forged.value(), 6);
+        assert!(!LocalCommitProtocol::can_commit_locally(&tx(&forged)));
+        let err = LocalCommitProtocol::commit_local(&tx(&forged), &node_b, &mut clock_b).unwrap_err();
+        assert_eq!(err.code(), "LOCAL_COMMIT_BOUND_EXHAUSTED");
+
+        // Plain deltas carry no rights
+        let mut delta = AlgebraicTransaction::new();
+        delta.add_operation(AlgebraicOperation::new("seats", OpType::BoundedAdd, AlgebraicValue::integer(-1)));
+        assert!(matches!(
+            LocalCommitProtocol::commit_local(&delta, &node_b, &mut clock_b),
+            Err(LocalCommitError::BoundExhausted { .. })
+        ));
+    }
+
+    #[test]
+    fn test_per_user_counter_map_commits_locally() {
+        let node_a = NodeId::new("a");
+        let node_b = NodeId::new("b");
+        let mut clock_a = VectorClock::new();
+        let mut clock_b = VectorClock::new();
+        let op = OpType::map(OpType::AbelianAdd);
+
+        let tx = |entries: AlgebraicValue| {
+            let mut tx = AlgebraicTransaction::new();
+            tx.add_operation(AlgebraicOperation::new("likes", op.clone(), entries));
+            tx
+        };
+        let tx_a = tx(AlgebraicValue::map([("alice", 2.into()), ("bob", 1.into())]));
+        let tx_b = tx(AlgebraicValue::map([("bob", 5.into())]));
+        assert!(LocalCommitProtocol::can_commit_locally(&tx_a));
+        let update_a = LocalCommitProtocol::commit_local(&tx_a, &node_a, &mut clock_a).unwrap();
+        let update_b = LocalCommitProtocol::commit_local(&tx_b, &node_b, &mut clock_b).unwrap();
+
+        let ab = LocalCommitProtocol::merge_updates(&update_a, &update_b).unwrap();
+        let ba = LocalCommitProtocol::merge_updates(&update_b, &update_a).unwrap();
+        let expected = AlgebraicValue::map([("alice", 2.into()), ("bob", 6.into())]);
+        assert_eq!(ab.operations()[0].value(), &expected);
+        assert_eq!(ba.operations()[0].value(), &expected);
+
+        // Entries merged with a coordinated operation keep the map coordinated
+        let mut overwrite = AlgebraicTransaction::new();
+        overwrite.add_operation(AlgebraicOperation::new(
+            "likes",
+            OpType::map(OpType::GenericOverwrite),
+            AlgebraicValue::map([("bob", 1.into())]),
+        ));
+        assert!(!LocalCommitProtocol::can_commit_locally(&overwrite));
+
+        // Bounded entries must carry escrow like top-level bounded values
+        let mut bounded = AlgebraicTransaction::new();
+        bounded.add_operation(AlgebraicOperation::new(
+            "seats",
+            OpType::map(OpType::BoundedAdd),
+            AlgebraicValue::map([("row_a", (-1).into())]),
+        ));
+        let err = LocalCommitProtocol::commit_local(&bounded, &node_a, &mut clock_a).unwrap_err();
+        assert!(err.to_string().contains("entry 'row_a'"));
+    }
 }
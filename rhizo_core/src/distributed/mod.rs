@@ -10,9 +10,11 @@
 //! │                      Distributed Module                      │
 //! ├─────────────────────────────────────────────────────────────┤
 //! │  VectorClock     - Causality tracking                       │
+//! │  HlcClock        - Constant-size hybrid logical clock       │
 //! │  LocalCommit     - Coordination-free commit protocol        │
 //! │  Simulation      - Multi-node convergence testing           │
-//! │  (Future) Gossip        - Anti-entropy propagation          │
+//! │  Gossip          - Anti-entropy propagation over TCP        │
+//! │  StateStore      - Durable node state and clock             │
 //! └─────────────────────────────────────────────────────────────┘
 //! ```
 //!
@@ -26,7 +28,9 @@
 //! - **Semilattice operations** (MAX, MIN, UNION): Idempotent merge
 //! - **Abelian operations** (ADD, MULTIPLY): Combine deltas
 //!
-//! Vector clocks track causality to determine when merge is needed.
+//! Vector clocks track causality to determine when merge is needed. Hybrid
+//! logical clocks trade exact concurrency detection for a constant size,
+//! for deployments with many ephemeral nodes.
 //!
 //! # Example: Vector Clocks
 //!
@@ -106,10 +110,15 @@
 //! assert_eq!(page_views, Some(150));
 //! ```
 
+pub mod gossip;
+mod hlc;
 mod local_commit;
 pub mod simulation;
+mod state_store;
 mod vector_clock;
 
+pub use gossip::{GossipConfig, GossipError, GossipNode, GossipRound, GossipStats, SyncReport};
+pub use hlc::HlcClock;
 pub use local_commit::{
     AlgebraicOperation, AlgebraicTransaction, LocalCommitError, LocalCommitProtocol,
     VersionedUpdate,
@@ -118,7 +127,5 @@ pub use simulation::{
     Message, NetworkCondition, SimulatedCluster, SimulatedNode, SimulationBuilder,
     SimulationConfig, SimulationStats,
 };
-pub use vector_clock::{CausalOrder, NodeId, VectorClock};
-
-// Future modules (Phase 5+):
-// mod gossip;
+pub use state_store::{NodeStateError, NodeStateStore};
+pub use vector_clock::{CausalClock, CausalOrder, NodeId, VectorClock};
@@ -162,26 +162,7 @@ impl SimulatedNode {
 
     /// Apply an update to local state.
     pub fn apply_update(&mut self, update: &VersionedUpdate) {
-        for op in update.operations() {
-            let key = op.key().to_string();
-
-            if let Some((existing_op_type, existing_value)) = self.state.get(&key) {
-                // Merge with existing value
-                if *existing_op_type == op.op_type() {
-                    let merge_result =
-                        AlgebraicMerger::merge(op.op_type(), existing_value, op.value());
-                    if let MergeResult::Merged(merged_value) = merge_result {
-                        self.state.insert(key, (op.op_type(), merged_value));
-                    }
-                }
-            } else {
-                // First value for this key
-                self.state.insert(key, (op.op_type(), op.value().clone()));
-            }
-        }
-
-        // Update clock
-        self.clock.merge(update.clock());
+        apply_to_state(&mut self.state, &mut self.clock, update);
     }
 
     /// Receive and apply an update from another node.
@@ -205,23 +186,8 @@ impl SimulatedNode {
     }
 
     /// Generate a unique ID for an update (for deduplication).
-    ///
-    /// Uses the full vector clock state (sorted for determinism) instead of
-    /// just the clock sum, which is not unique across different updates.
     fn generate_update_id(&self, update: &VersionedUpdate) -> String {
-        // Use explicit update_id if set
-        if let Some(id) = update.update_id() {
-            return id.to_string();
-        }
-        // Build deterministic clock representation: sorted node:time pairs
-        let mut entries: Vec<_> = update.clock().entries().collect();
-        entries.sort_by(|a, b| a.0.as_str().cmp(b.0.as_str()));
-        let clock_str: String = entries
-            .iter()
-            .map(|(node, time)| format!("{}={}", node.as_str(), time))
-            .collect::<Vec<_>>()
-            .join(",");
-        format!("{}@{}", update.origin_node(), clock_str)
+        update.dedup_key()
     }
 
     /// Get the current value for a key.
@@ -235,6 +201,36 @@ impl SimulatedNode {
     }
 }
 
+/// Merge an update's operations into `state` and its clock into `clock`.
+///
+/// Operations on a key merge with the existing value when the op types
+/// match; an operation of a different type than the key's is ignored.
+pub(super) fn apply_to_state(
+    state: &mut HashMap<String, (OpType, AlgebraicValue)>,
+    clock: &mut VectorClock,
+    update: &VersionedUpdate,
+) {
+    for op in update.operations() {
+        let key = op.key().to_string();
+
+        if let Some((existing_op_type, existing_value)) = state.get(&key) {
+            // Merge with existing value
+            if *existing_op_type == op.op_type() {
+                let merge_result = AlgebraicMerger::merge(op.op_type(), existing_value, op.value());
+                if let MergeResult::Merged(merged_value) = merge_result {
+                    state.insert(key, (op.op_type(), merged_value));
+                }
+            }
+        } else {
+            // First value for this key
+            state.insert(key, (op.op_type(), op.value().clone()));
+        }
+    }
+
+    // Update clock
+    clock.merge(update.clock());
+}
+
 /// A simulated cluster of nodes.
 #[derive(Debug)]
 pub struct SimulatedCluster {
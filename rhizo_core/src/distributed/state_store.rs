@@ -0,0 +1,320 @@
+//! Durable algebraic state for distributed nodes.
+//!
+//! A [`NodeStateStore`] keeps what a node has applied — key → (OpType,
+//! AlgebraicValue), its vector clock and the IDs of applied updates — on
+//! disk, so a node that restarts resumes from its last state instead of
+//! starting from zero (and does not re-apply updates peers send again).
+//!
+//! # Layout
+//!
+//! ```text
+//! <dir>/
+//! ├── snapshot.json   # State, clock and applied IDs at the last checkpoint
+//! └── updates.jsonl   # Updates applied since, one JSON object per line
+//! ```
+//!
+//! Each update is appended (and synced) to the log before it is applied in
+//! memory. Opening the store loads the snapshot and replays the log; a torn
+//! final line from a crash mid-append is discarded. A checkpoint replaces
+//! the snapshot atomically (temp file + rename) and then truncates the log;
+//! a crash in between only replays updates the snapshot already holds,
+//! which deduplication skips.
+
+use std::collections::{HashMap, HashSet};
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use super::local_commit::VersionedUpdate;
+use super::simulation::apply_to_state;
+use super::vector_clock::VectorClock;
+use crate::algebraic::{AlgebraicValue, OpType};
+use crate::error::{ErrorCategory, ErrorContext, ErrorInfo};
+
+const SNAPSHOT_FILE: &str = "snapshot.json";
+const LOG_FILE: &str = "updates.jsonl";
+
+/// Errors from the node state store.
+#[derive(Error, Debug)]
+#[non_exhaustive]
+pub enum NodeStateError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+impl ErrorInfo for NodeStateError {
+    fn code(&self) -> &'static str {
+        match self {
+            NodeStateError::Io(_) => "NODE_STATE_IO",
+            NodeStateError::Json(_) => "NODE_STATE_JSON",
+        }
+    }
+
+    fn category(&self) -> ErrorCategory {
+        match self {
+            NodeStateError::Io(_) => ErrorCategory::Io,
+            NodeStateError::Json(_) => ErrorCategory::DataIntegrity,
+        }
+    }
+
+    fn context(&self) -> ErrorContext {
+        ErrorContext::new()
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Snapshot {
+    clock: VectorClock,
+    state: HashMap<String, (OpType, AlgebraicValue)>,
+    applied: Vec<String>,
+}
+
+/// On-disk algebraic state of one node.
+#[derive(Debug)]
+pub struct NodeStateStore {
+    dir: PathBuf,
+    clock: VectorClock,
+    state: HashMap<String, (OpType, AlgebraicValue)>,
+    applied: HashSet<String>,
+    /// Updates applied since the last checkpoint, in log order
+    logged: Vec<VersionedUpdate>,
+    log: File,
+    sync_writes: bool,
+}
+
+impl NodeStateStore {
+    /// Open (or create) the store in `dir`, recovering any saved state.
+    pub fn open(dir: impl AsRef<Path>) -> Result<Self, NodeStateError> {
+        let dir = dir.as_ref().to_path_buf();
+        fs::create_dir_all(&dir)?;
+
+        let snapshot_path = dir.join(SNAPSHOT_FILE);
+        let snapshot: Snapshot = if snapshot_path.exists() {
+            serde_json::from_str(&fs::read_to_string(&snapshot_path)?)?
+        } else {
+            Snapshot::default()
+        };
+
+        let log_path = dir.join(LOG_FILE);
+        let logged = recover_log(&log_path)?;
+        let log = OpenOptions::new().create(true).append(true).open(&log_path)?;
+
+        let mut store = Self {
+            dir,
+            clock: snapshot.clock,
+            state: snapshot.state,
+            applied: snapshot.applied.into_iter().collect(),
+            logged: Vec::new(),
+            log,
+            sync_writes: true,
+        };
+        for update in logged {
+            if store.applied.insert(update.dedup_key()) {
+                apply_to_state(&mut store.state, &mut store.clock, &update);
+            }
+            store.logged.push(update);
+        }
+        Ok(store)
+    }
+
+    /// Whether each append is synced to disk before it is applied
+    /// (default: true). Without it a crash may lose the latest updates.
+    pub fn with_sync_writes(mut self, sync_writes: bool) -> Self {
+        self.sync_writes = sync_writes;
+        self
+    }
+
+    /// Directory holding the store.
+    pub fn path(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Persist and apply an update; false (and nothing written) if it was
+    /// already applied.
+    pub fn record(&mut self, update: &VersionedUpdate) -> Result<bool, NodeStateError> {
+        let key = update.dedup_key();
+        if self.applied.contains(&key) {
+            return Ok(false);
+        }
+
+        let mut line = serde_json::to_vec(update)?;
+        line.push(b'\n');
+        self.log.write_all(&line)?;
+        if self.sync_writes {
+            self.log.sync_data()?;
+        }
+
+        self.applied.insert(key);
+        apply_to_state(&mut self.state, &mut self.clock, update);
+        self.logged.push(update.clone());
+        Ok(true)
+    }
+
+    /// Whether an update has been applied.
+    pub fn contains(&self, update: &VersionedUpdate) -> bool {
+        self.applied.contains(&update.dedup_key())
+    }
+
+    /// Current value for a key.
+    pub fn get(&self, key: &str) -> Option<&AlgebraicValue> {
+        self.state.get(key).map(|(_, v)| v)
+    }
+
+    /// Full state: key -> (op type, value).
+    pub fn state(&self) -> &HashMap<String, (OpType, AlgebraicValue)> {
+        &self.state
+    }
+
+    /// The node's vector clock.
+    pub fn clock(&self) -> &VectorClock {
+        &self.clock
+    }
+
+    /// IDs of every applied update (see [`VersionedUpdate::dedup_key`]).
+    pub fn applied(&self) -> &HashSet<String> {
+        &self.applied
+    }
+
+    /// Updates applied since the last checkpoint, oldest first.
+    pub fn logged_updates(&self) -> &[VersionedUpdate] {
+        &self.logged
+    }
+
+    /// Write the current state as the snapshot and truncate the log.
+    ///
+    /// Updates folded into the snapshot are no longer available from
+    /// [`logged_updates`](Self::logged_updates); nodes that re-send their
+    /// history to peers should only checkpoint once peers have them.
+    pub fn checkpoint(&mut self) -> Result<(), NodeStateError> {
+        let mut applied: Vec<String> = self.applied.iter().cloned().collect();
+        applied.sort();
+        let snapshot = Snapshot {
+            clock: self.clock.clone(),
+            state: self.state.clone(),
+            applied,
+        };
+
+        let path = self.dir.join(SNAPSHOT_FILE);
+        let temp_path = path.with_extension("tmp");
+        {
+            let mut file = File::create(&temp_path)?;
+            file.write_all(&serde_json::to_vec_pretty(&snapshot)?)?;
+            file.sync_all()?;
+        }
+        fs::rename(&temp_path, &path)?;
+
+        self.log.set_len(0)?;
+        self.log.sync_all()?;
+        self.logged.clear();
+        Ok(())
+    }
+}
+
+/// Read the update log, truncating a torn final line so later appends
+/// start on a fresh line
+fn recover_log(path: &Path) -> Result<Vec<VersionedUpdate>, NodeStateError> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut updates = Vec::new();
+    let mut valid_len = 0u64;
+    let mut reader = BufReader::new(File::open(path)?);
+    let mut line = String::new();
+    loop {
+        line.clear();
+        let read = reader.read_line(&mut line)?;
+        if read == 0 || !line.ends_with('\n') {
+            break;
+        }
+        match serde_json::from_str::<VersionedUpdate>(&line) {
+            Ok(update) => updates.push(update),
+            // Only the final line can be torn; anything else is corruption
+            Err(e) if reader.fill_buf()?.is_empty() => {
+                tracing::warn!(path = %path.display(), error = %e, "discarding torn update log entry");
+                break;
+            }
+            Err(e) => return Err(e.into()),
+        }
+        valid_len += read as u64;
+    }
+
+    if fs::metadata(path)?.len() != valid_len {
+        OpenOptions::new().write(true).open(path)?.set_len(valid_len)?;
+    }
+    Ok(updates)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distributed::{AlgebraicOperation, AlgebraicTransaction, LocalCommitProtocol, NodeId};
+    use tempfile::TempDir;
+
+    fn update(node: &NodeId, clock: &mut VectorClock, key: &str, value: i64) -> VersionedUpdate {
+        let mut tx = AlgebraicTransaction::new();
+        tx.add_operation(AlgebraicOperation::new(key, OpType::AbelianAdd, AlgebraicValue::integer(value)));
+        LocalCommitProtocol::commit_local(&tx, node, clock).unwrap()
+    }
+
+    #[test]
+    fn test_state_and_clock_survive_restart() {
+        let temp = TempDir::new().unwrap();
+        let node = NodeId::new("sf");
+        let mut clock = VectorClock::new();
+        let first = update(&node, &mut clock, "views", 10);
+        let second = update(&node, &mut clock, "views", 5);
+
+        {
+            let mut store = NodeStateStore::open(temp.path()).unwrap();
+            assert!(store.record(&first).unwrap());
+            assert!(!store.record(&first).unwrap());
+            store.checkpoint().unwrap();
+            assert!(store.record(&second).unwrap());
+        }
+
+        // Snapshot plus replayed log
+        let mut store = NodeStateStore::open(temp.path()).unwrap();
+        assert_eq!(store.get("views"), Some(&AlgebraicValue::integer(15)));
+        assert_eq!(store.clock().get(&node), 2);
+        assert_eq!(store.logged_updates().len(), 1);
+
+        // Updates applied before the restart are still deduplicated
+        assert!(!store.record(&first).unwrap());
+        assert!(!store.record(&second).unwrap());
+        assert_eq!(store.get("views"), Some(&AlgebraicValue::integer(15)));
+    }
+
+    #[test]
+    fn test_torn_log_entry_is_discarded() {
+        let temp = TempDir::new().unwrap();
+        let node = NodeId::new("tokyo");
+        let mut clock = VectorClock::new();
+        let first = update(&node, &mut clock, "views", 3);
+        let second = update(&node, &mut clock, "views", 4);
+
+        {
+            let mut store = NodeStateStore::open(temp.path()).unwrap();
+            store.record(&first).unwrap();
+        }
+        // Simulate a crash mid-append
+        let mut log = OpenOptions::new().append(true).open(temp.path().join(LOG_FILE)).unwrap();
+        log.write_all(b"{\"operations\": [").unwrap();
+        drop(log);
+
+        let mut store = NodeStateStore::open(temp.path()).unwrap();
+        assert_eq!(store.get("views"), Some(&AlgebraicValue::integer(3)));
+        store.record(&second).unwrap();
+        drop(store);
+
+        let store = NodeStateStore::open(temp.path()).unwrap();
+        assert_eq!(store.get("views"), Some(&AlgebraicValue::integer(7)));
+        assert_eq!(store.logged_updates().len(), 2);
+    }
+}
@@ -334,6 +334,53 @@ impl fmt::Display for VectorClock {
     }
 }
 
+/// A logical clock that can stamp updates in the local commit protocol.
+///
+/// Implemented by [`VectorClock`] (exact causality, one entry per node) and
+/// [`HlcClock`](super::HlcClock) (constant size, causality-consistent
+/// total order).
+pub trait CausalClock: Clone + fmt::Debug {
+    /// Advance the clock for a local event on `node_id`.
+    fn tick(&mut self, node_id: &NodeId);
+
+    /// Fold in a clock received from another node.
+    ///
+    /// Must be commutative, associative and idempotent so merged clocks
+    /// agree regardless of delivery order.
+    fn merge(&mut self, other: &Self);
+
+    /// The causal relationship between this clock and another.
+    fn compare(&self, other: &Self) -> CausalOrder;
+
+    /// Deterministic text form, used to identify updates.
+    fn fingerprint(&self) -> String;
+}
+
+impl CausalClock for VectorClock {
+    fn tick(&mut self, node_id: &NodeId) {
+        VectorClock::tick(self, node_id);
+    }
+
+    fn merge(&mut self, other: &Self) {
+        VectorClock::merge(self, other);
+    }
+
+    fn compare(&self, other: &Self) -> CausalOrder {
+        VectorClock::compare(self, other)
+    }
+
+    /// `node=time` entries sorted by node, comma-separated.
+    fn fingerprint(&self) -> String {
+        let mut entries: Vec<_> = self.clocks.iter().collect();
+        entries.sort_by(|a, b| a.0.as_str().cmp(b.0.as_str()));
+        entries
+            .iter()
+            .map(|(node, time)| format!("{}={}", node.as_str(), time))
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+}
+
 /// The causal relationship between two events.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum CausalOrder {
@@ -0,0 +1,173 @@
+//! Crash consistency of catalog, transaction log and branch writes.
+//!
+//! Metadata files are replaced by writing a temporary file and renaming it
+//! over the target, so readers never see a partial file. Without fsync
+//! that only holds while the OS stays up: after a power loss the renamed
+//! file can be empty, or the rename itself can be lost. A
+//! [`DurabilityLevel`] chooses how much of that the writers guard against.
+//!
+//! ```
+//! use rhizo_core::{DurabilityLevel, FileCatalog};
+//!
+//! # let dir = tempfile::tempdir().unwrap();
+//! let catalog = FileCatalog::new(dir.path())
+//!     .unwrap()
+//!     .with_durability(DurabilityLevel::FsyncAll);
+//! assert_eq!(catalog.durability(), DurabilityLevel::FsyncAll);
+//! ```
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+/// How far metadata writes are flushed before they return
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DurabilityLevel {
+    /// Leave flushing to the OS. Fastest; a power loss can lose recent
+    /// commits or leave truncated metadata files behind.
+    #[default]
+    None,
+    /// Flush each file's contents before it is renamed into place, so a
+    /// file that survives a crash is complete. The latest renames may
+    /// still be lost.
+    FsyncData,
+    /// Also flush the parent directory after every rename, creation and
+    /// removal, so a write that returned survives power loss.
+    FsyncAll,
+}
+
+impl DurabilityLevel {
+    fn syncs_data(self) -> bool {
+        self != DurabilityLevel::None
+    }
+
+    fn syncs_dirs(self) -> bool {
+        self == DurabilityLevel::FsyncAll
+    }
+}
+
+/// Write `contents` to `temp_path` and rename it over `path`
+pub(crate) fn write_atomic(
+    path: &Path,
+    temp_path: &Path,
+    contents: impl AsRef<[u8]>,
+    level: DurabilityLevel,
+) -> io::Result<()> {
+    let mut file = File::create(temp_path)?;
+    file.write_all(contents.as_ref())?;
+    if level.syncs_data() {
+        file.sync_data()?;
+    }
+    drop(file);
+    rename(temp_path, path, level)
+}
+
+/// Write `contents` to a new file at `path`, for files nothing reads
+/// until a later rename or marker publishes them
+pub(crate) fn write(path: &Path, contents: impl AsRef<[u8]>, level: DurabilityLevel) -> io::Result<()> {
+    let mut file = File::create(path)?;
+    file.write_all(contents.as_ref())?;
+    if level.syncs_data() {
+        file.sync_data()?;
+    }
+    drop(file);
+    if level.syncs_dirs() {
+        sync_parent(path)?;
+    }
+    Ok(())
+}
+
+/// Rename `from` to `to`, then flush the directories involved
+pub(crate) fn rename(from: &Path, to: &Path, level: DurabilityLevel) -> io::Result<()> {
+    fs::rename(from, to)?;
+    if level.syncs_dirs() {
+        sync_parent(to)?;
+        if from.parent() != to.parent() {
+            sync_parent(from)?;
+        }
+    }
+    Ok(())
+}
+
+/// Append `line` and a newline to `path`, creating it if needed
+pub(crate) fn append_line(path: &Path, line: &str, level: DurabilityLevel) -> io::Result<()> {
+    let created = !path.exists();
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", line)?;
+    if level.syncs_data() {
+        file.sync_data()?;
+    }
+    if created && level.syncs_dirs() {
+        sync_parent(path)?;
+    }
+    Ok(())
+}
+
+/// Remove `path`, then flush its directory
+pub(crate) fn remove_file(path: &Path, level: DurabilityLevel) -> io::Result<()> {
+    fs::remove_file(path)?;
+    if level.syncs_dirs() {
+        sync_parent(path)?;
+    }
+    Ok(())
+}
+
+/// Flush a file or directory regardless of level, e.g. at shutdown.
+/// Missing paths are skipped.
+pub(crate) fn sync_path(path: &Path) -> io::Result<()> {
+    if path.is_dir() {
+        #[cfg(unix)]
+        File::open(path)?.sync_all()?;
+        return Ok(());
+    }
+    match File::open(path) {
+        Ok(file) => file.sync_all(),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(e),
+    }
+}
+
+/// Flush the directory entry of `path` (a no-op where directories cannot
+/// be opened, as on Windows)
+fn sync_parent(path: &Path) -> io::Result<()> {
+    let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) else {
+        return Ok(());
+    };
+    #[cfg(unix)]
+    File::open(dir)?.sync_all()?;
+    #[cfg(not(unix))]
+    let _ = dir;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_writes_at_every_level() {
+        let dir = tempfile::tempdir().unwrap();
+        for level in [DurabilityLevel::None, DurabilityLevel::FsyncData, DurabilityLevel::FsyncAll] {
+            let path = dir.path().join("latest");
+            write_atomic(&path, &path.with_extension("tmp"), "7", level).unwrap();
+            assert_eq!(fs::read_to_string(&path).unwrap(), "7");
+            assert!(!path.with_extension("tmp").exists());
+
+            let log = dir.path().join("events.jsonl");
+            append_line(&log, "a", level).unwrap();
+            append_line(&log, "b", level).unwrap();
+            assert_eq!(fs::read_to_string(&log).unwrap(), "a\nb\n");
+
+            remove_file(&path, level).unwrap();
+            remove_file(&log, level).unwrap();
+            assert!(!path.exists() && !log.exists());
+        }
+        assert_eq!(
+            serde_json::to_string(&DurabilityLevel::FsyncData).unwrap(),
+            "\"fsync_data\""
+        );
+    }
+}
@@ -0,0 +1,261 @@
+//! Machine-readable codes and context shared by every error type.
+//!
+//! Error messages are for humans and may change between releases. Clients
+//! that branch on errors (the Python bindings, RPC gateways, retry loops)
+//! should use [`ErrorInfo::code`] instead: codes are `DOMAIN_NAME` strings
+//! (e.g. `CATALOG_TABLE_NOT_FOUND`) that are never renamed or reused.
+//! [`ErrorInfo::category`] groups codes into coarse classes that map onto
+//! HTTP/gRPC status codes, [`ErrorInfo::context`] exposes the table,
+//! version, branch, transaction, chunk or path involved without parsing
+//! the message, and [`ErrorInfo::retry_after`] says whether repeating the
+//! operation can succeed.
+//!
+//! Error enums are `#[non_exhaustive]`; new variants get new codes, so
+//! clients should fall back on the category for codes they do not know.
+
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use serde::Serialize;
+
+/// Coarse class of an error, stable across releases.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum ErrorCategory {
+    /// A table, version, branch, chunk, key or transaction does not exist
+    NotFound,
+    /// The object to create already exists
+    AlreadyExists,
+    /// Malformed input (names, hashes, versions, configuration)
+    InvalidArgument,
+    /// Concurrent modification; the operation may succeed if retried
+    Conflict,
+    /// The repository is not in a state that allows the operation
+    /// (pinned or held versions, immutable branches, inactive transactions)
+    FailedPrecondition,
+    /// Stored data failed verification or is corrupt
+    DataIntegrity,
+    /// Filesystem, network or storage backend failure
+    Io,
+    /// Serialization failures and other internal errors
+    Internal,
+}
+
+impl ErrorCategory {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ErrorCategory::NotFound => "not_found",
+            ErrorCategory::AlreadyExists => "already_exists",
+            ErrorCategory::InvalidArgument => "invalid_argument",
+            ErrorCategory::Conflict => "conflict",
+            ErrorCategory::FailedPrecondition => "failed_precondition",
+            ErrorCategory::DataIntegrity => "data_integrity",
+            ErrorCategory::Io => "io",
+            ErrorCategory::Internal => "internal",
+        }
+    }
+}
+
+impl fmt::Display for ErrorCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// The objects an error is about. Fields are `None` when not applicable.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[non_exhaustive]
+pub struct ErrorContext {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub table: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub branch: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tx_id: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub epoch_id: Option<u64>,
+    /// Chunk hash
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chunk: Option<String>,
+    /// Algebraic key, pin name or key id
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<PathBuf>,
+}
+
+impl ErrorContext {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_table(mut self, table: impl Into<String>) -> Self {
+        self.table = Some(table.into());
+        self
+    }
+
+    pub fn with_version(mut self, version: u64) -> Self {
+        self.version = Some(version);
+        self
+    }
+
+    pub fn with_branch(mut self, branch: impl Into<String>) -> Self {
+        self.branch = Some(branch.into());
+        self
+    }
+
+    pub fn with_tx_id(mut self, tx_id: u64) -> Self {
+        self.tx_id = Some(tx_id);
+        self
+    }
+
+    pub fn with_epoch_id(mut self, epoch_id: u64) -> Self {
+        self.epoch_id = Some(epoch_id);
+        self
+    }
+
+    pub fn with_chunk(mut self, hash: impl Into<String>) -> Self {
+        self.chunk = Some(hash.into());
+        self
+    }
+
+    pub fn with_key(mut self, key: impl Into<String>) -> Self {
+        self.key = Some(key.into());
+        self
+    }
+
+    pub fn with_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.path = Some(path.into());
+        self
+    }
+
+    /// True when no field is set
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Stable code, category and context of an error.
+///
+/// Errors that wrap another rhizo error (e.g. `BackupError::Catalog`)
+/// report the wrapped error's code, context and retryability.
+pub trait ErrorInfo: std::error::Error {
+    /// Stable `DOMAIN_NAME` code, e.g. `CHUNK_NOT_FOUND`
+    fn code(&self) -> &'static str;
+
+    fn category(&self) -> ErrorCategory;
+
+    /// Objects involved in the error
+    fn context(&self) -> ErrorContext {
+        ErrorContext::default()
+    }
+
+    /// Suggested delay before retrying, or `None` if retrying the same
+    /// operation cannot succeed.
+    ///
+    /// Retryable errors are transient: lock contention, interrupted or
+    /// timed-out I/O, and transaction conflicts (rerun the transaction on
+    /// a fresh snapshot). Logical errors (missing objects, invalid input,
+    /// pinned versions, corruption) are not.
+    fn retry_after(&self) -> Option<Duration> {
+        None
+    }
+
+    fn is_retryable(&self) -> bool {
+        self.retry_after().is_some()
+    }
+}
+
+/// Backoff for lock contention
+pub(crate) const LOCK_RETRY_DELAY: Duration = Duration::from_millis(10);
+
+/// Backoff for transient I/O failures
+pub(crate) const IO_RETRY_DELAY: Duration = Duration::from_millis(50);
+
+/// Retry delay for I/O errors that are likely to succeed when repeated
+pub(crate) fn io_retry_after(e: &io::Error) -> Option<Duration> {
+    match e.kind() {
+        io::ErrorKind::Interrupted | io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut => Some(IO_RETRY_DELAY),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backup::BackupError;
+    use crate::branch::BranchError;
+    use crate::catalog::CatalogError;
+    use crate::chunk_store::ChunkStoreError;
+    use crate::transaction::TransactionError;
+
+    #[test]
+    fn test_codes_and_categories() {
+        let e = CatalogError::VersionNotFound("users".into(), 3);
+        assert_eq!(e.code(), "CATALOG_VERSION_NOT_FOUND");
+        assert_eq!(e.category(), ErrorCategory::NotFound);
+        assert_eq!(e.context(), ErrorContext::new().with_table("users").with_version(3));
+
+        let e = TransactionError::snapshot_conflict("orders", 1, 2);
+        assert_eq!(e.code(), "TX_SNAPSHOT_CONFLICT");
+        assert_eq!(e.category(), ErrorCategory::Conflict);
+        assert_eq!(e.context().table.as_deref(), Some("orders"));
+
+        let e = BranchError::BranchImmutable("release".into());
+        assert_eq!(e.category(), ErrorCategory::FailedPrecondition);
+        assert_eq!(e.context().branch.as_deref(), Some("release"));
+
+        let e = ChunkStoreError::Io(std::io::Error::other("disk"));
+        assert_eq!((e.code(), e.category()), ("CHUNK_IO", ErrorCategory::Io));
+        assert!(e.context().is_empty());
+    }
+
+    #[test]
+    fn test_wrapped_errors_delegate() {
+        let e = BackupError::from(ChunkStoreError::NotFound("ab".repeat(32)));
+        assert_eq!(e.code(), "CHUNK_NOT_FOUND");
+        assert_eq!(e.context().chunk, Some("ab".repeat(32)));
+
+        let e = BackupError::ParentNotFound {
+            backup_id: "b1".into(),
+            path: PathBuf::from("/backups/mon"),
+        };
+        assert_eq!(e.code(), "BACKUP_PARENT_NOT_FOUND");
+        assert_eq!(e.context().path, Some(PathBuf::from("/backups/mon")));
+    }
+
+    #[test]
+    fn test_retry_classification() {
+        let e = CatalogError::LockError("users".into());
+        assert_eq!(e.retry_after(), Some(LOCK_RETRY_DELAY));
+        assert!(!CatalogError::TableNotFound("users".into()).is_retryable());
+        assert!(!CatalogError::InvalidVersion { expected: 3, got: 2 }.is_retryable());
+        assert!(CatalogError::Io(io::Error::from(io::ErrorKind::Interrupted)).is_retryable());
+        assert!(!CatalogError::Io(io::Error::from(io::ErrorKind::PermissionDenied)).is_retryable());
+
+        // Conflicts: rerun the transaction right away
+        let e = TransactionError::snapshot_conflict("orders", 1, 2);
+        assert_eq!(e.retry_after(), Some(Duration::ZERO));
+        assert!(TransactionError::EpochFull(4, 1000).is_retryable());
+        assert!(!TransactionError::IntegrityError("checksum".into()).is_retryable());
+
+        // Wrappers classify by the wrapped error
+        assert!(BackupError::from(CatalogError::LockError("users".into())).is_retryable());
+        assert!(!BackupError::from(CatalogError::VersionHeld("users".into(), 1)).is_retryable());
+    }
+
+    #[test]
+    fn test_context_serializes_set_fields_only() {
+        let ctx = ErrorContext::new().with_tx_id(7).with_table("users");
+        assert_eq!(
+            serde_json::to_string(&ctx).unwrap(),
+            r#"{"table":"users","tx_id":7}"#
+        );
+        assert_eq!(serde_json::to_string(&ErrorCategory::FailedPrecondition).unwrap(), r#""failed_precondition""#);
+    }
+}
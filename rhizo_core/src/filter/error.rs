@@ -0,0 +1,71 @@
+use std::time::Duration;
+
+use thiserror::Error;
+
+use crate::branch::BranchError;
+use crate::catalog::CatalogError;
+use crate::chunk_store::ChunkStoreError;
+use crate::error::{ErrorCategory, ErrorContext, ErrorInfo};
+
+#[derive(Error, Debug)]
+#[non_exhaustive]
+pub enum FilterError {
+    #[error("Catalog error: {0}")]
+    Catalog(#[from] CatalogError),
+
+    #[error("Chunk store error: {0}")]
+    ChunkStore(#[from] ChunkStoreError),
+
+    #[error("Branch error: {0}")]
+    Branch(#[from] BranchError),
+
+    #[error("Table already exists in destination: {0}")]
+    TableExists(String),
+
+    #[error("Branch already exists in destination: {0}")]
+    BranchExists(String),
+
+    #[error("Branch filter requires branch managers for both repositories")]
+    BranchesUnavailable,
+}
+
+impl ErrorInfo for FilterError {
+    fn code(&self) -> &'static str {
+        match self {
+            FilterError::Catalog(e) => e.code(),
+            FilterError::ChunkStore(e) => e.code(),
+            FilterError::Branch(e) => e.code(),
+            FilterError::TableExists(_) => "FILTER_TABLE_EXISTS",
+            FilterError::BranchExists(_) => "FILTER_BRANCH_EXISTS",
+            FilterError::BranchesUnavailable => "FILTER_BRANCHES_UNAVAILABLE",
+        }
+    }
+
+    fn category(&self) -> ErrorCategory {
+        match self {
+            FilterError::Catalog(e) => e.category(),
+            FilterError::ChunkStore(e) => e.category(),
+            FilterError::Branch(e) => e.category(),
+            FilterError::TableExists(_) | FilterError::BranchExists(_) => ErrorCategory::AlreadyExists,
+            FilterError::BranchesUnavailable => ErrorCategory::InvalidArgument,
+        }
+    }
+
+    fn context(&self) -> ErrorContext {
+        match self {
+            FilterError::Catalog(e) => e.context(),
+            FilterError::ChunkStore(e) => e.context(),
+            FilterError::Branch(e) => e.context(),
+            FilterError::TableExists(table) => ErrorContext::new().with_table(table.clone()),
+            FilterError::BranchExists(branch) => ErrorContext::new().with_branch(branch.clone()),
+            FilterError::BranchesUnavailable => ErrorContext::new(),
+        }
+    }
+
+    fn retry_after(&self) -> Option<Duration> {
+        match self {
+            FilterError::Catalog(e) => e.retry_after(),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,15 @@
+//! Repository filtering.
+//!
+//! Produces a new repository containing only selected tables and branches,
+//! optionally with truncated history, so a subset of data can be shared
+//! externally without handing over the whole repository.
+//!
+//! Kept versions are renumbered from 1 in the destination and their parent
+//! links, branch heads and fork points are rewritten to match, so the result
+//! is a self-consistent repository rather than a sparse copy.
+
+pub mod error;
+pub mod repo_filter;
+
+pub use error::FilterError;
+pub use repo_filter::{FilterReport, RepoFilter, RepoRef};
@@ -0,0 +1,470 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+
+use super::error::FilterError;
+use crate::branch::{Branch, BranchManager};
+use crate::catalog::{namespace, FileCatalog};
+use crate::chunk_store::ChunkStore;
+
+/// The storage components of one repository.
+#[derive(Clone, Copy)]
+pub struct RepoRef<'a> {
+    pub catalog: &'a FileCatalog,
+    pub store: &'a ChunkStore,
+    pub branches: Option<&'a BranchManager>,
+}
+
+impl<'a> RepoRef<'a> {
+    pub fn new(catalog: &'a FileCatalog, store: &'a ChunkStore) -> Self {
+        Self {
+            catalog,
+            store,
+            branches: None,
+        }
+    }
+
+    pub fn with_branches(mut self, branches: &'a BranchManager) -> Self {
+        self.branches = Some(branches);
+        self
+    }
+}
+
+/// Result of a filter run.
+#[derive(Debug, Clone, Default)]
+pub struct FilterReport {
+    /// Per table: source version -> destination version
+    pub version_map: BTreeMap<String, BTreeMap<u64, u64>>,
+
+    /// Branches written to the destination
+    pub branches: Vec<String>,
+
+    /// Source versions of the selected tables that were not copied
+    pub versions_dropped: usize,
+
+    /// Chunks written to the destination store
+    pub chunks_copied: usize,
+
+    /// Bytes written to the destination store
+    pub bytes_copied: u64,
+}
+
+impl FilterReport {
+    /// Tables copied to the destination
+    pub fn tables(&self) -> Vec<&str> {
+        self.version_map.keys().map(String::as_str).collect()
+    }
+
+    /// Total versions copied to the destination
+    pub fn versions_kept(&self) -> usize {
+        self.version_map.values().map(BTreeMap::len).sum()
+    }
+}
+
+/// Copies a subset of one repository into another.
+///
+/// By default everything is copied. Narrow the copy with:
+/// - `with_tables`: table selectors (namespace prefixes or wildcard patterns,
+///   see [`namespace::selects`])
+/// - `with_branches`: branch names; tables not on any selected branch are
+///   dropped, as are versions newer than every selected head
+/// - `with_keep_versions`: keep only the last N versions of each table
+///
+/// Versions referenced by a selected branch's head or fork point are always
+/// kept, so branches stay resolvable and mergeable after truncation.
+///
+/// # Example
+///
+/// ```ignore
+/// let report = RepoFilter::new()
+///     .with_tables(["sales"])
+///     .with_branches(["main"])
+///     .with_keep_versions(3)
+///     .run(source, dest)?;
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct RepoFilter {
+    tables: Vec<String>,
+    branches: Vec<String>,
+    keep_versions: Option<usize>,
+}
+
+impl RepoFilter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_tables<I, S>(mut self, selectors: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.tables.extend(selectors.into_iter().map(Into::into));
+        self
+    }
+
+    pub fn with_branches<I, S>(mut self, names: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.branches.extend(names.into_iter().map(Into::into));
+        self
+    }
+
+    /// Keep only the last `n` versions of each table (at least one).
+    pub fn with_keep_versions(mut self, n: usize) -> Self {
+        self.keep_versions = Some(n.max(1));
+        self
+    }
+
+    fn selects_table(&self, table: &str) -> bool {
+        self.tables.is_empty() || self.tables.iter().any(|s| namespace::selects(table, s))
+    }
+
+    /// Copy the selected subset of `source` into `dest`.
+    ///
+    /// The destination must not already contain any of the selected tables,
+    /// or a non-empty branch with the name of a selected branch. Pins are
+    /// not copied.
+    pub fn run(&self, source: RepoRef<'_>, dest: RepoRef<'_>) -> Result<FilterReport, FilterError> {
+        let branches = self.select_branches(source, dest)?;
+
+        let mut tables: Vec<String> = source
+            .catalog
+            .list_tables()?
+            .into_iter()
+            .filter(|t| self.selects_table(t))
+            .collect();
+        if !self.branches.is_empty() {
+            tables.retain(|t| branches.iter().any(|b| b.head.contains_key(t)));
+        }
+
+        // Validate the destination before writing anything
+        for table in &tables {
+            if dest.catalog.table_exists(table)? {
+                return Err(FilterError::TableExists(table.clone()));
+            }
+        }
+        if let Some(dest_branches) = dest.branches {
+            for branch in &branches {
+                if let Ok(existing) = dest_branches.get(&branch.name) {
+                    if !existing.head.is_empty() {
+                        return Err(FilterError::BranchExists(branch.name.clone()));
+                    }
+                }
+            }
+        }
+
+        let mut report = FilterReport::default();
+        let mut copied: HashSet<String> = HashSet::new();
+
+        for table in &tables {
+            let versions = source.catalog.list_versions(table)?;
+            let kept = self.kept_versions(table, &versions, &branches);
+            report.versions_dropped += versions.len() - kept.len();
+
+            let mut version_map = BTreeMap::new();
+            let mut parent = None;
+            for (i, &old) in kept.iter().enumerate() {
+                let mut version = source.catalog.get_version(table, Some(old))?;
+                for hash in &version.referenced_chunks() {
+                    Self::copy_chunk(source.store, dest.store, hash, &mut copied, &mut report)?;
+                }
+
+                let new = i as u64 + 1;
+                version.version = new;
+                version.parent_version = parent;
+                dest.catalog.commit(version)?;
+
+                version_map.insert(old, new);
+                parent = Some(new);
+            }
+            report.version_map.insert(table.clone(), version_map);
+
+            if dest.branches.is_some() {
+                if let Some(origin) = Self::rewrite_origin(source.catalog.table_origin(table)?, table, &branches) {
+                    dest.catalog.set_table_origin(table, &origin)?;
+                }
+            }
+        }
+
+        if let Some(dest_branches) = dest.branches {
+            self.write_branches(source, dest_branches, &branches, &mut report)?;
+        }
+
+        Ok(report)
+    }
+
+    /// Resolve the selected source branches (sorted by name).
+    fn select_branches(&self, source: RepoRef<'_>, dest: RepoRef<'_>) -> Result<Vec<Branch>, FilterError> {
+        let source_branches = match (source.branches, dest.branches) {
+            (Some(source_branches), Some(_)) => source_branches,
+            _ if !self.branches.is_empty() => return Err(FilterError::BranchesUnavailable),
+            _ => return Ok(Vec::new()),
+        };
+
+        if self.branches.is_empty() {
+            return source_branches
+                .list()?
+                .iter()
+                .map(|name| source_branches.get(name))
+                .collect::<Result<_, _>>()
+                .map_err(FilterError::from);
+        }
+
+        let names: BTreeSet<&String> = self.branches.iter().collect();
+        names
+            .into_iter()
+            .map(|name| source_branches.get(name).map_err(FilterError::from))
+            .collect()
+    }
+
+    /// Source versions of a table to copy, ascending.
+    fn kept_versions(&self, table: &str, versions: &[u64], branches: &[Branch]) -> Vec<u64> {
+        let mut referenced = BTreeSet::new();
+        let mut newest_head = None;
+        for branch in branches {
+            if let Some(&v) = branch.head.get(table) {
+                referenced.insert(v);
+                newest_head = newest_head.max(Some(v));
+            }
+            if let Some(&v) = branch.fork_point.as_ref().and_then(|fp| fp.get(table)) {
+                referenced.insert(v);
+            }
+        }
+
+        // With a branch filter, versions past every selected head belong to
+        // other branches
+        let candidates: Vec<u64> = match newest_head {
+            Some(cap) if !self.branches.is_empty() => versions.iter().copied().filter(|&v| v <= cap).collect(),
+            _ => versions.to_vec(),
+        };
+
+        let mut kept: BTreeSet<u64> = match self.keep_versions {
+            Some(n) => candidates.iter().rev().take(n).copied().collect(),
+            None => candidates.iter().copied().collect(),
+        };
+        kept.extend(referenced.into_iter().filter(|v| candidates.contains(v)));
+        kept.into_iter().collect()
+    }
+
+    fn copy_chunk(
+        source: &ChunkStore,
+        dest: &ChunkStore,
+        hash: &str,
+        copied: &mut HashSet<String>,
+        report: &mut FilterReport,
+    ) -> Result<(), FilterError> {
+        if copied.contains(hash) {
+            return Ok(());
+        }
+        if !dest.exists(hash)? {
+            let data = source.get_verified(hash)?;
+            dest.put(&data)?;
+            report.chunks_copied += 1;
+            report.bytes_copied += data.len() as u64;
+        }
+        copied.insert(hash.to_string());
+        Ok(())
+    }
+
+    /// Keep a table's origin if its branch is copied; otherwise re-home it
+    /// on the first selected branch that contains it, so the table stays
+    /// scoped instead of becoming visible everywhere.
+    fn rewrite_origin(origin: Option<String>, table: &str, branches: &[Branch]) -> Option<String> {
+        let origin = origin?;
+        if branches.iter().any(|b| b.name == origin) {
+            return Some(origin);
+        }
+        branches
+            .iter()
+            .find(|b| b.head.contains_key(table))
+            .map(|b| b.name.clone())
+    }
+
+    fn write_branches(
+        &self,
+        source: RepoRef<'_>,
+        dest: &BranchManager,
+        branches: &[Branch],
+        report: &mut FilterReport,
+    ) -> Result<(), FilterError> {
+        if branches.is_empty() {
+            return Ok(());
+        }
+
+        let remap = |pointers: &HashMap<String, u64>| -> HashMap<String, u64> {
+            pointers
+                .iter()
+                .filter_map(|(table, old)| {
+                    let new = report.version_map.get(table)?.get(old)?;
+                    Some((table.clone(), *new))
+                })
+                .collect()
+        };
+        let selected: HashSet<&str> = branches.iter().map(|b| b.name.as_str()).collect();
+
+        let rewritten: Vec<Branch> = branches
+            .iter()
+            .map(|b| Branch {
+                name: b.name.clone(),
+                head: remap(&b.head),
+                created_at: b.created_at,
+                parent_branch: b.parent_branch.clone().filter(|p| selected.contains(p.as_str())),
+                description: b.description.clone(),
+                fork_point: b.fork_point.as_ref().map(&remap),
+                immutable: b.immutable,
+                // Snapshot ids belong to the source repository
+                snapshot_id: None,
+            })
+            .collect();
+        for branch in &rewritten {
+            dest.restore(branch)?;
+            report.branches.push(branch.name.clone());
+        }
+
+        let source_default = match source.branches {
+            Some(source_branches) => source_branches.get_default()?,
+            None => None,
+        };
+        let default = source_default
+            .filter(|d| selected.contains(d.as_str()))
+            .unwrap_or_else(|| branches[0].name.clone());
+        dest.set_default(&default)?;
+
+        // Drop placeholder branches the destination created on open
+        for name in dest.list()? {
+            if !selected.contains(name.as_str()) && dest.get(&name)?.head.is_empty() {
+                dest.delete(&name)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::catalog::TableVersion;
+    use std::fs;
+    use std::path::PathBuf;
+
+    struct TestRepo {
+        dir: PathBuf,
+        catalog: FileCatalog,
+        store: ChunkStore,
+        branches: BranchManager,
+    }
+
+    impl TestRepo {
+        fn new() -> Self {
+            let dir = std::env::temp_dir().join(format!("rhizo_filter_test_{}", uuid::Uuid::new_v4()));
+            Self {
+                catalog: FileCatalog::new(dir.join("catalog")).unwrap(),
+                store: ChunkStore::new(dir.join("chunks")).unwrap(),
+                branches: BranchManager::new(dir.join("branches")).unwrap(),
+                dir,
+            }
+        }
+
+        fn as_ref(&self) -> RepoRef<'_> {
+            RepoRef::new(&self.catalog, &self.store).with_branches(&self.branches)
+        }
+
+        fn write(&self, table: &str, data: &[u8], branch: &str) -> u64 {
+            let hash = self.store.put(data).unwrap();
+            let next = self.catalog.list_versions(table).map(|v| v.len() as u64).unwrap_or(0) + 1;
+            self.catalog
+                .commit(TableVersion::new(table, next, vec![hash]).with_metadata("k", "v"))
+                .unwrap();
+            self.branches.update_head(branch, table, next).unwrap();
+            next
+        }
+    }
+
+    impl Drop for TestRepo {
+        fn drop(&mut self) {
+            fs::remove_dir_all(&self.dir).ok();
+        }
+    }
+
+    #[test]
+    fn test_filter_tables_and_truncate_history() {
+        let source = TestRepo::new();
+        for i in 0..4u8 {
+            source.write("sales.orders", &[i; 8], "main");
+        }
+        source.write("hr.salaries", b"secret", "main");
+
+        let dest = TestRepo::new();
+        let report = RepoFilter::new()
+            .with_tables(["sales"])
+            .with_keep_versions(2)
+            .run(source.as_ref(), dest.as_ref())
+            .unwrap();
+
+        assert_eq!(report.tables(), vec!["sales.orders"]);
+        assert_eq!(report.version_map["sales.orders"], BTreeMap::from([(3, 1), (4, 2)]));
+        assert_eq!(report.versions_dropped, 2);
+        assert_eq!(report.chunks_copied, 2);
+
+        assert_eq!(dest.catalog.list_tables().unwrap(), vec!["sales.orders"]);
+        let v1 = dest.catalog.get_version("sales.orders", Some(1)).unwrap();
+        let v2 = dest.catalog.get_version("sales.orders", Some(2)).unwrap();
+        assert_eq!(v1.parent_version, None);
+        assert_eq!(v2.parent_version, Some(1));
+        assert_eq!(v2.metadata.get("k").map(String::as_str), Some("v"));
+        assert_eq!(dest.store.get(&v2.chunk_hashes[0]).unwrap(), vec![3u8; 8]);
+
+        // Only data for kept versions of selected tables is copied
+        assert_eq!(dest.store.list_chunk_hashes().unwrap().len(), 2);
+        assert!(!dest.store.exists(&blake3::hash(b"secret").to_hex()).unwrap());
+
+        // Branch heads point at the renumbered versions
+        let main = dest.branches.get("main").unwrap();
+        assert_eq!(main.head, HashMap::from([("sales.orders".to_string(), 2)]));
+    }
+
+    #[test]
+    fn test_filter_branches() {
+        let source = TestRepo::new();
+        source.write("shared", b"v1", "main");
+        source.branches.create("feature", Some("main"), None).unwrap();
+        source.write("shared", b"v2", "feature");
+        source.write("experiment", b"e1", "feature");
+        source.catalog.set_table_origin("experiment", "feature").unwrap();
+        source.write("main_only", b"m1", "main");
+
+        let dest = TestRepo::new();
+        let report = RepoFilter::new()
+            .with_branches(["feature"])
+            .run(source.as_ref(), dest.as_ref())
+            .unwrap();
+
+        assert_eq!(report.branches, vec!["feature"]);
+        assert_eq!(report.tables(), vec!["experiment", "shared"]);
+
+        // "main" was not selected: the placeholder is removed and the
+        // selected branch becomes the default
+        assert_eq!(dest.branches.list().unwrap(), vec!["feature"]);
+        assert_eq!(dest.branches.get_default().unwrap().as_deref(), Some("feature"));
+
+        let feature = dest.branches.get("feature").unwrap();
+        assert_eq!(feature.head["shared"], 2);
+        assert_eq!(feature.parent_branch, None);
+        assert_eq!(feature.fork_point.unwrap()["shared"], 1);
+        assert_eq!(dest.catalog.table_origin("experiment").unwrap().as_deref(), Some("feature"));
+    }
+
+    #[test]
+    fn test_filter_refuses_existing_destination_tables() {
+        let source = TestRepo::new();
+        source.write("orders", b"a", "main");
+
+        let dest = TestRepo::new();
+        dest.write("orders", b"b", "main");
+
+        let result = RepoFilter::new().run(source.as_ref(), dest.as_ref());
+        assert!(matches!(result, Err(FilterError::TableExists(t)) if t == "orders"));
+    }
+}
@@ -0,0 +1,65 @@
+use thiserror::Error;
+
+use crate::branch::BranchError;
+use crate::catalog::CatalogError;
+use crate::chunk_store::ChunkStoreError;
+use crate::error::{ErrorCategory, ErrorContext, ErrorInfo};
+use crate::parquet::ParquetError;
+use crate::repo::RepoError;
+
+#[derive(Error, Debug)]
+#[non_exhaustive]
+pub enum FixtureError {
+    #[error("Invalid fixture spec: {0}")]
+    InvalidSpec(String),
+
+    #[error("Repository error: {0}")]
+    Repo(#[from] RepoError),
+
+    #[error("Catalog error: {0}")]
+    Catalog(#[from] CatalogError),
+
+    #[error("Chunk store error: {0}")]
+    ChunkStore(#[from] ChunkStoreError),
+
+    #[error("Branch error: {0}")]
+    Branch(#[from] BranchError),
+
+    #[error("Parquet error: {0}")]
+    Parquet(#[from] ParquetError),
+}
+
+impl ErrorInfo for FixtureError {
+    fn code(&self) -> &'static str {
+        match self {
+            FixtureError::InvalidSpec(_) => "FIXTURE_INVALID_SPEC",
+            FixtureError::Repo(e) => e.code(),
+            FixtureError::Catalog(e) => e.code(),
+            FixtureError::ChunkStore(e) => e.code(),
+            FixtureError::Branch(e) => e.code(),
+            FixtureError::Parquet(e) => e.code(),
+        }
+    }
+
+    fn category(&self) -> ErrorCategory {
+        match self {
+            FixtureError::InvalidSpec(_) => ErrorCategory::InvalidArgument,
+            FixtureError::Repo(e) => e.category(),
+            FixtureError::Catalog(e) => e.category(),
+            FixtureError::ChunkStore(e) => e.category(),
+            FixtureError::Branch(e) => e.category(),
+            FixtureError::Parquet(e) => e.category(),
+        }
+    }
+
+    fn context(&self) -> ErrorContext {
+        match self {
+            FixtureError::InvalidSpec(_) => ErrorContext::new(),
+            FixtureError::Repo(e) => e.context(),
+            FixtureError::Catalog(e) => e.context(),
+            FixtureError::ChunkStore(e) => e.context(),
+            FixtureError::Branch(e) => e.context(),
+            FixtureError::Parquet(e) => e.context(),
+        }
+    }
+}
@@ -0,0 +1,434 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use arrow::array::{ArrayRef, Float64Array, Int64Array, StringArray};
+use arrow::datatypes::{DataType, Field, Schema};
+use arrow::record_batch::RecordBatch;
+
+use super::error::FixtureError;
+use crate::catalog::TableVersion;
+use crate::parquet::{ParquetEncoder, ParquetError};
+use crate::repo::Repo;
+
+const MAIN_BRANCH: &str = "main";
+const CATEGORIES: [&str; 5] = ["alpha", "beta", "gamma", "delta", "epsilon"];
+
+/// What a [`FixtureBuilder`] wrote.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FixtureReport {
+    /// Tables created, in creation order
+    pub tables: Vec<String>,
+    /// Branches forked from `main` (`branch_1`, `branch_2`, ...)
+    pub branches: Vec<String>,
+    /// Table versions committed, across all branches
+    pub versions_committed: usize,
+    /// Distinct chunks written
+    pub chunks_written: usize,
+    /// Encoded size of the distinct chunks
+    pub bytes_written: u64,
+    /// `created_at` of the last commit
+    pub last_commit_at: i64,
+}
+
+/// Builds a repository of a given shape from a seed.
+///
+/// `main` receives `versions` commit rounds, each writing a new version of
+/// every table. Round one writes every chunk; later rounds rewrite one
+/// seeded chunk per table and reuse the rest, as an incremental update
+/// would. Each branch forks from `main` after a seeded round and then
+/// commits `branch_versions` versions of seeded tables. Commits are
+/// stamped `commit_interval_secs` apart from `start_time`.
+///
+/// # Example
+///
+/// ```
+/// use rhizo_core::{FixtureBuilder, Repo};
+///
+/// let repo = Repo::ephemeral().unwrap();
+/// let report = FixtureBuilder::new()
+///     .with_tables(2)
+///     .with_versions(3)
+///     .with_branches(1)
+///     .with_seed(7)
+///     .build(&repo)
+///     .unwrap();
+///
+/// assert_eq!(report.tables, vec!["table_0", "table_1"]);
+/// assert_eq!(repo.catalog().list_versions("table_0").unwrap()[..3], [1, 2, 3]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct FixtureBuilder {
+    tables: usize,
+    versions: u64,
+    branches: usize,
+    branch_versions: u64,
+    rows: usize,
+    chunks: usize,
+    commit_interval_secs: i64,
+    start_time: i64,
+    seed: u64,
+}
+
+impl Default for FixtureBuilder {
+    fn default() -> Self {
+        Self {
+            tables: 3,
+            versions: 5,
+            branches: 1,
+            branch_versions: 2,
+            rows: 100,
+            chunks: 1,
+            commit_interval_secs: 60,
+            start_time: 1_700_000_000,
+            seed: 0,
+        }
+    }
+}
+
+impl FixtureBuilder {
+    /// Three tables, five versions, one branch, 100 rows in one chunk,
+    /// commits one minute apart, seed 0.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of tables (`table_0`, `table_1`, ...)
+    pub fn with_tables(mut self, tables: usize) -> Self {
+        self.tables = tables;
+        self
+    }
+
+    /// Commit rounds on `main`; every table gets one version per round
+    pub fn with_versions(mut self, versions: u64) -> Self {
+        self.versions = versions;
+        self
+    }
+
+    /// Number of branches forked from `main` (`branch_1`, `branch_2`, ...)
+    pub fn with_branches(mut self, branches: usize) -> Self {
+        self.branches = branches;
+        self
+    }
+
+    /// Versions committed on each branch after it forks
+    pub fn with_branch_versions(mut self, versions: u64) -> Self {
+        self.branch_versions = versions;
+        self
+    }
+
+    /// Rows in every table version
+    pub fn with_rows(mut self, rows: usize) -> Self {
+        self.rows = rows;
+        self
+    }
+
+    /// Chunks the rows of a version are split into
+    pub fn with_chunks(mut self, chunks: usize) -> Self {
+        self.chunks = chunks;
+        self
+    }
+
+    /// Seconds between consecutive commits
+    pub fn with_commit_interval_secs(mut self, secs: i64) -> Self {
+        self.commit_interval_secs = secs;
+        self
+    }
+
+    /// `created_at` of the first commit (Unix seconds)
+    pub fn with_start_time(mut self, start_time: i64) -> Self {
+        self.start_time = start_time;
+        self
+    }
+
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    fn validate(&self) -> Result<(), FixtureError> {
+        if self.tables == 0 {
+            return Err(FixtureError::InvalidSpec("at least one table is required".to_string()));
+        }
+        if self.versions == 0 {
+            return Err(FixtureError::InvalidSpec("at least one version is required".to_string()));
+        }
+        if self.chunks == 0 || self.rows < self.chunks {
+            return Err(FixtureError::InvalidSpec(format!(
+                "{} rows cannot fill {} chunks",
+                self.rows, self.chunks
+            )));
+        }
+        if self.commit_interval_secs < 0 {
+            return Err(FixtureError::InvalidSpec("commit interval must not be negative".to_string()));
+        }
+        Ok(())
+    }
+
+    /// Write the fixture into `repo`.
+    ///
+    /// # Errors
+    /// - `FixtureError::InvalidSpec` for an empty shape
+    /// - `FixtureError::Catalog` if a fixture table already exists in `repo`
+    pub fn build(&self, repo: &Repo) -> Result<FixtureReport, FixtureError> {
+        self.validate()?;
+        let mut run = FixtureRun {
+            spec: self,
+            repo,
+            rng: SplitMix64::new(self.seed),
+            encoder: ParquetEncoder::new(),
+            written: HashSet::new(),
+            commits: 0,
+            report: FixtureReport::default(),
+        };
+        run.report.tables = (0..self.tables).map(|t| format!("table_{}", t)).collect();
+
+        let fork_rounds: Vec<u64> = (0..self.branches).map(|_| run.rng.below(self.versions) + 1).collect();
+        let mut main_chunks: Vec<Vec<String>> = vec![Vec::new(); self.tables];
+
+        for round in 1..=self.versions {
+            let mut heads = HashMap::new();
+            for (t, chunks) in main_chunks.iter_mut().enumerate() {
+                *chunks = if round == 1 {
+                    (0..self.chunks)
+                        .map(|c| run.write_chunk(t, c, round))
+                        .collect::<Result<_, _>>()?
+                } else {
+                    run.rewrite_one(t, chunks, round)?
+                };
+                let parent = (round > 1).then(|| round - 1);
+                let version = run.commit(t, round, parent, chunks.clone())?;
+                heads.insert(run.report.tables[t].clone(), version);
+            }
+            repo.branches().update_heads(MAIN_BRANCH, &heads)?;
+
+            for (b, _) in fork_rounds.iter().enumerate().filter(|(_, fork)| **fork == round) {
+                let name = format!("branch_{}", b + 1);
+                let description = format!("Fixture branch forked after round {}", round);
+                repo.branches().create(&name, Some(MAIN_BRANCH), Some(&description))?;
+            }
+        }
+        // Report branches in index order, not fork order
+        run.report.branches = (1..=self.branches).map(|b| format!("branch_{}", b)).collect();
+
+        for name in run.report.branches.clone() {
+            for _ in 0..self.branch_versions {
+                let t = run.rng.below(self.tables as u64) as usize;
+                let table = run.report.tables[t].clone();
+                let head = repo.branches().get(&name)?.head[&table];
+                let chunks = repo.catalog().get_version(&table, Some(head))?.chunk_hashes;
+                let version = repo.catalog().get_version(&table, None)?.version + 1;
+                let chunks = run.rewrite_one(t, &chunks, version)?;
+                run.commit(t, version, Some(head), chunks)?;
+                repo.branches().update_head(&name, &table, version)?;
+            }
+        }
+
+        Ok(run.report)
+    }
+}
+
+/// State of one [`FixtureBuilder::build`] call.
+struct FixtureRun<'a> {
+    spec: &'a FixtureBuilder,
+    repo: &'a Repo,
+    rng: SplitMix64,
+    encoder: ParquetEncoder,
+    written: HashSet<String>,
+    commits: i64,
+    report: FixtureReport,
+}
+
+impl FixtureRun<'_> {
+    /// Replace one seeded chunk of `chunks` with freshly generated rows.
+    fn rewrite_one(&mut self, table: usize, chunks: &[String], version: u64) -> Result<Vec<String>, FixtureError> {
+        let index = self.rng.below(chunks.len() as u64) as usize;
+        let mut chunks = chunks.to_vec();
+        chunks[index] = self.write_chunk(table, index, version)?;
+        Ok(chunks)
+    }
+
+    /// Generate, encode and store rows `[start, end)` of chunk `index`.
+    fn write_chunk(&mut self, table: usize, index: usize, version: u64) -> Result<String, FixtureError> {
+        let (rows, chunks) = (self.spec.rows, self.spec.chunks);
+        let (start, end) = (index * rows / chunks, (index + 1) * rows / chunks);
+        let len = end - start;
+
+        let ids: Vec<i64> = (start..end).map(|i| i as i64).collect();
+        let values: Vec<f64> = (0..len).map(|_| (self.rng.next_f64() * 1_000_000.0).round() / 100.0).collect();
+        let categories: Vec<&str> = (0..len)
+            .map(|_| CATEGORIES[self.rng.below(CATEGORIES.len() as u64) as usize])
+            .collect();
+        let table_ids = vec![table as i64; len];
+
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Int64, false),
+            Field::new("table_id", DataType::Int64, false),
+            Field::new("version", DataType::Int64, false),
+            Field::new("category", DataType::Utf8, false),
+            Field::new("value", DataType::Float64, false),
+        ]));
+        let columns: Vec<ArrayRef> = vec![
+            Arc::new(Int64Array::from(ids)),
+            Arc::new(Int64Array::from(table_ids)),
+            Arc::new(Int64Array::from(vec![version as i64; len])),
+            Arc::new(StringArray::from(categories)),
+            Arc::new(Float64Array::from(values)),
+        ];
+        let batch = RecordBatch::try_new(schema, columns).map_err(ParquetError::from)?;
+
+        let bytes = self.encoder.encode(&batch)?;
+        let hash = self.repo.store().put(&bytes)?;
+        if self.written.insert(hash.clone()) {
+            self.report.chunks_written += 1;
+            self.report.bytes_written += bytes.len() as u64;
+        }
+        Ok(hash)
+    }
+
+    fn commit(
+        &mut self,
+        table: usize,
+        version: u64,
+        parent: Option<u64>,
+        chunks: Vec<String>,
+    ) -> Result<u64, FixtureError> {
+        let mut record = TableVersion::new(self.report.tables[table].clone(), version, chunks)
+            .with_metadata("fixture_seed", self.spec.seed.to_string());
+        record.parent_version = parent;
+        record.created_at = self.spec.start_time + self.commits * self.spec.commit_interval_secs;
+        self.report.last_commit_at = record.created_at;
+        self.commits += 1;
+        self.report.versions_committed += 1;
+        Ok(self.repo.catalog().commit(record)?)
+    }
+}
+
+/// SplitMix64: small, fast and identical on every platform, which is all
+/// fixture data needs.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in `[0, n)`; `n` must be non-zero
+    fn below(&mut self, n: u64) -> u64 {
+        self.next_u64() % n
+    }
+
+    /// Uniform in `[0, 1)`
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build(builder: &FixtureBuilder) -> (Repo, FixtureReport) {
+        let repo = Repo::ephemeral().unwrap();
+        let report = builder.build(&repo).unwrap();
+        (repo, report)
+    }
+
+    fn all_versions(repo: &Repo) -> Vec<(String, u64, Vec<String>, i64)> {
+        let mut out = Vec::new();
+        for table in repo.catalog().list_tables().unwrap() {
+            for v in repo.catalog().list_versions(&table).unwrap() {
+                let record = repo.catalog().get_version(&table, Some(v)).unwrap();
+                out.push((table.clone(), v, record.chunk_hashes, record.created_at));
+            }
+        }
+        out
+    }
+
+    #[test]
+    fn test_shape_and_timestamps() {
+        let builder = FixtureBuilder::new()
+            .with_tables(3)
+            .with_versions(4)
+            .with_branches(2)
+            .with_branch_versions(3)
+            .with_rows(40)
+            .with_chunks(4)
+            .with_commit_interval_secs(10)
+            .with_start_time(1_000);
+        let (repo, report) = build(&builder);
+
+        assert_eq!(report.tables, vec!["table_0", "table_1", "table_2"]);
+        assert_eq!(report.branches, vec!["branch_1", "branch_2"]);
+        assert_eq!(report.versions_committed, 3 * 4 + 2 * 3);
+        assert_eq!(report.last_commit_at, 1_000 + 17 * 10);
+        // Round one writes every chunk; each later commit rewrites one
+        assert_eq!(report.chunks_written, 3 * 4 + (3 * 3 + 2 * 3));
+
+        let main = repo.branches().get("main").unwrap();
+        assert!(report.tables.iter().all(|t| main.head[t] == 4));
+        for name in &report.branches {
+            let branch = repo.branches().get(name).unwrap();
+            let ahead: usize = report
+                .tables
+                .iter()
+                .map(|t| usize::from(branch.head[t] > 4))
+                .sum();
+            assert!(ahead >= 1);
+        }
+
+        let first = repo.catalog().get_version("table_0", Some(1)).unwrap();
+        let second = repo.catalog().get_version("table_0", Some(2)).unwrap();
+        assert_eq!(first.chunk_hashes.len(), 4);
+        let shared = first.chunk_hashes.iter().zip(&second.chunk_hashes).filter(|(a, b)| a == b).count();
+        assert_eq!(shared, 3);
+        assert_eq!(second.parent_version, Some(1));
+        assert_eq!(second.created_at - first.created_at, 30);
+    }
+
+    #[test]
+    fn test_same_seed_same_repository() {
+        let builder = FixtureBuilder::new().with_chunks(2).with_seed(42);
+        let (a, _) = build(&builder);
+        let (b, _) = build(&builder);
+        let (c, _) = build(&builder.clone().with_seed(43));
+
+        assert_eq!(all_versions(&a), all_versions(&b));
+        assert_ne!(all_versions(&a), all_versions(&c));
+    }
+
+    #[test]
+    fn test_chunks_decode_as_parquet() {
+        let (repo, _) = build(&FixtureBuilder::new().with_rows(25).with_chunks(2));
+        let record = repo.catalog().get_version("table_1", None).unwrap();
+        let rows: usize = record
+            .chunk_hashes
+            .iter()
+            .map(|h| {
+                let bytes = repo.store().get(h).unwrap();
+                crate::parquet::ParquetDecoder::new().decode(&bytes).unwrap().num_rows()
+            })
+            .sum();
+        assert_eq!(rows, 25);
+    }
+
+    #[test]
+    fn test_rejects_empty_shape_and_existing_tables() {
+        let repo = Repo::ephemeral().unwrap();
+        let err = FixtureBuilder::new().with_tables(0).build(&repo).unwrap_err();
+        assert!(matches!(err, FixtureError::InvalidSpec(_)));
+        let err = FixtureBuilder::new().with_rows(2).with_chunks(3).build(&repo).unwrap_err();
+        assert!(matches!(err, FixtureError::InvalidSpec(_)));
+
+        FixtureBuilder::new().build(&repo).unwrap();
+        let err = FixtureBuilder::new().build(&repo).unwrap_err();
+        assert!(matches!(err, FixtureError::Catalog(_)));
+    }
+}
@@ -0,0 +1,14 @@
+//! Deterministic repository fixtures for benchmarks and integration tests.
+//!
+//! [`FixtureBuilder`] fills a [`Repo`](crate::Repo) with a configurable
+//! shape — tables, versions per table, branches forked along the way —
+//! from a seed. Every version stores Parquet chunks of seeded rows, so the
+//! result reads like a repository written by `rhizo.Database`. The same
+//! seed and shape always produce the same chunk hashes, versions and
+//! commit timestamps.
+
+pub mod error;
+pub mod generator;
+
+pub use error::FixtureError;
+pub use generator::{FixtureBuilder, FixtureReport};
@@ -0,0 +1,401 @@
+//! Mark-and-sweep over the chunk store.
+//!
+//! The mark phase unions chunk hashes referenced by:
+//! - every version of every table in the catalog (pinned, held and branch
+//!   head versions are catalog versions, so they are covered),
+//! - in-flight transactions: records in the transaction log that are not
+//!   yet committed or aborted, committed writes whose version has not
+//!   reached the catalog (and was not dropped since), and writes buffered
+//!   in a `TransactionManager`.
+//!
+//! The sweep phase deletes stored chunks outside that set whose last
+//! modification is older than the grace period, except the bases of delta
+//! chunks that are kept. The window covers commits
+//! that have stored chunks but not yet recorded them anywhere;
+//! `ChunkStore::put` refreshes the modification time of chunks it
+//! deduplicates, so re-referenced chunks are protected too.
+
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, SystemTime};
+
+use tracing::warn;
+
+use super::error::GcError;
+use crate::catalog::{CatalogError, FileCatalog};
+use crate::chunk_store::{ChunkStore, ChunkStoreError};
+use crate::transaction::{TransactionLog, TransactionManager, TransactionRecord, WriteKind};
+
+/// Default safety window for chunks written by in-flight commits
+pub const DEFAULT_GRACE_PERIOD: Duration = Duration::from_secs(3600);
+
+/// Outcome of a collection run.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GcReport {
+    /// Nothing was deleted; `collectable` lists what would have been
+    pub dry_run: bool,
+    /// Chunks found in the store
+    pub chunks_scanned: usize,
+    /// Stored chunks referenced by a catalog version
+    pub chunks_referenced: usize,
+    /// Stored chunks referenced only by in-flight transactions
+    pub chunks_in_flight: usize,
+    /// Unreferenced chunks kept because they are younger than the grace period
+    pub chunks_within_grace: usize,
+    /// Unreferenced chunks kept as the base of a kept delta chunk
+    pub chunks_delta_bases: usize,
+    /// Unreferenced chunks past the grace period (deleted unless dry run)
+    pub collectable: Vec<String>,
+    /// Stored bytes of the collectable chunks
+    pub bytes_collectable: u64,
+    /// Chunks deleted
+    pub chunks_deleted: usize,
+    /// Chunks whose deletion failed
+    pub chunks_failed: usize,
+}
+
+/// Garbage collector for unreferenced chunks.
+///
+/// # Example
+///
+/// ```ignore
+/// let report = ChunkGc::new(&catalog, &store)
+///     .with_transactions(&tx_manager)
+///     .dry_run(true)
+///     .run()?;
+/// println!("{} chunks ({} bytes) reclaimable", report.collectable.len(), report.bytes_collectable);
+/// ```
+pub struct ChunkGc<'a> {
+    catalog: &'a FileCatalog,
+    store: &'a ChunkStore,
+    log: Option<&'a TransactionLog>,
+    manager: Option<&'a TransactionManager>,
+    grace_period: Duration,
+    dry_run: bool,
+    candidates: Option<HashSet<String>>,
+}
+
+impl<'a> ChunkGc<'a> {
+    pub fn new(catalog: &'a FileCatalog, store: &'a ChunkStore) -> Self {
+        Self {
+            catalog,
+            store,
+            log: None,
+            manager: None,
+            grace_period: DEFAULT_GRACE_PERIOD,
+            dry_run: false,
+            candidates: None,
+        }
+    }
+
+    /// Treat chunks of in-flight transactions in `log` as reachable.
+    pub fn with_transaction_log(mut self, log: &'a TransactionLog) -> Self {
+        self.log = Some(log);
+        self
+    }
+
+    /// Treat chunks of in-flight transactions in `manager` (its log and the
+    /// writes it buffers in memory) as reachable.
+    pub fn with_transactions(mut self, manager: &'a TransactionManager) -> Self {
+        self.log = Some(manager.log());
+        self.manager = Some(manager);
+        self
+    }
+
+    /// Keep unreferenced chunks modified within `grace_period`
+    /// (default [`DEFAULT_GRACE_PERIOD`]). Chunks whose age the store
+    /// cannot report are kept unless the period is zero.
+    pub fn with_grace_period(mut self, grace_period: Duration) -> Self {
+        self.grace_period = grace_period;
+        self
+    }
+
+    /// Report collectable chunks without deleting them.
+    pub fn dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Sweep only `hashes` (e.g. the chunks of a dropped table). Every
+    /// other stored chunk is kept, together with its delta bases.
+    pub fn with_candidates(mut self, hashes: impl IntoIterator<Item = String>) -> Self {
+        self.candidates = Some(hashes.into_iter().collect());
+        self
+    }
+
+    pub fn run(&self) -> Result<GcReport, GcError> {
+        // Anything modified after this instant is inside the window, even
+        // if it was written while the mark phase ran
+        let started = SystemTime::now();
+
+        let referenced = self.catalog.get_all_referenced_chunk_hashes()?;
+        let in_flight = self.in_flight_hashes()?;
+
+        let mut report = GcReport {
+            dry_run: self.dry_run,
+            ..GcReport::default()
+        };
+        let mut kept = Vec::new();
+        let mut expired = Vec::new();
+        for hash in self.store.list_chunk_hashes()? {
+            if self.candidates.as_ref().is_some_and(|c| !c.contains(&hash)) {
+                kept.push(hash);
+                continue;
+            }
+            report.chunks_scanned += 1;
+            if referenced.contains(&hash) {
+                report.chunks_referenced += 1;
+                kept.push(hash);
+                continue;
+            }
+            if in_flight.contains(&hash) {
+                report.chunks_in_flight += 1;
+                kept.push(hash);
+                continue;
+            }
+
+            let stat = match self.store.stat(&hash) {
+                Ok(stat) => stat,
+                // Deleted concurrently
+                Err(ChunkStoreError::NotFound(_)) => continue,
+                Err(e) => return Err(e.into()),
+            };
+            let is_expired = match stat.modified {
+                Some(modified) => started
+                    .duration_since(modified)
+                    .is_ok_and(|age| age >= self.grace_period),
+                None => self.grace_period.is_zero(),
+            };
+            if !is_expired {
+                report.chunks_within_grace += 1;
+                kept.push(hash);
+                continue;
+            }
+            expired.push((hash, stat.size));
+        }
+
+        // Kept delta chunks are unreadable without their chains
+        let bases = self.store.delta_bases(kept.iter().map(String::as_str))?;
+        for (hash, size) in expired {
+            if bases.contains(&hash) {
+                report.chunks_delta_bases += 1;
+                continue;
+            }
+            report.bytes_collectable += size;
+            report.collectable.push(hash);
+        }
+
+        if !self.dry_run {
+            for hash in &report.collectable {
+                match self.store.delete(hash) {
+                    Ok(()) => report.chunks_deleted += 1,
+                    Err(e) => {
+                        warn!(hash = %hash, error = %e, "Failed to delete unreferenced chunk");
+                        report.chunks_failed += 1;
+                    }
+                }
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Chunks written by transactions whose writes are not (yet) catalog
+    /// versions
+    pub(super) fn in_flight_hashes(&self) -> Result<HashSet<String>, GcError> {
+        let mut hashes = HashSet::new();
+
+        if let Some(log) = self.log {
+            let mut records = Vec::new();
+            for epoch_id in log.list_epochs()? {
+                for tx_id in log.list_transactions_in_epoch(epoch_id)? {
+                    let tx = log.read_transaction_from_epoch(tx_id, epoch_id)?;
+                    if !tx.is_aborted() {
+                        records.push(tx);
+                    }
+                }
+            }
+
+            // Newest first, so a table's drop tombstone is seen before the
+            // writes it removed (and after any that re-created the table)
+            let mut latest: HashMap<String, u64> = HashMap::new();
+            let mut dropped: HashMap<&str, u64> = HashMap::new();
+            for tx in records.iter().rev() {
+                for write in tx.writes.iter().filter(|w| tx.is_committed() && w.kind == WriteKind::Drop) {
+                    dropped.insert(&write.table_name, write.new_version);
+                }
+                for write in &tx.writes {
+                    let applied = tx.is_committed()
+                        && (dropped.get(write.table_name.as_str()).is_some_and(|&v| write.new_version <= v) || {
+                            let latest = match latest.get(&write.table_name) {
+                                Some(&v) => v,
+                                None => {
+                                    let v = self.latest_version(&write.table_name)?;
+                                    latest.insert(write.table_name.clone(), v);
+                                    v
+                                }
+                            };
+                            write.new_version <= latest
+                        });
+                    if !applied {
+                        hashes.extend(write.chunk_hashes.iter().cloned());
+                    }
+                }
+            }
+        }
+
+        if let Some(manager) = self.manager {
+            for tx in manager.active_transactions()? {
+                extend_with_writes(&mut hashes, &tx);
+            }
+        }
+
+        Ok(hashes)
+    }
+
+    fn latest_version(&self, table: &str) -> Result<u64, GcError> {
+        match self.catalog.get_version(table, None) {
+            Ok(version) => Ok(version.version),
+            Err(CatalogError::TableNotFound(_)) => Ok(0),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+fn extend_with_writes(hashes: &mut HashSet<String>, tx: &TransactionRecord) {
+    for write in &tx.writes {
+        hashes.extend(write.chunk_hashes.iter().cloned());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::catalog::TableVersion;
+    use crate::transaction::TableWrite;
+    use std::path::PathBuf;
+    use std::sync::Arc;
+
+    struct TestRepo {
+        dir: PathBuf,
+        catalog: Arc<FileCatalog>,
+        store: ChunkStore,
+    }
+
+    impl TestRepo {
+        fn new() -> Self {
+            let dir = std::env::temp_dir().join(format!("rhizo_gc_test_{}", uuid::Uuid::new_v4()));
+            Self {
+                catalog: Arc::new(FileCatalog::new(dir.join("catalog")).unwrap()),
+                store: ChunkStore::new(dir.join("chunks")).unwrap(),
+                dir,
+            }
+        }
+
+        fn write(&self, table: &str, data: &[u8]) -> String {
+            let hash = self.store.put(data).unwrap();
+            let next = self.catalog.list_versions(table).map(|v| v.len() as u64).unwrap_or(0) + 1;
+            self.catalog.commit(TableVersion::new(table, next, vec![hash.clone()])).unwrap();
+            hash
+        }
+    }
+
+    impl Drop for TestRepo {
+        fn drop(&mut self) {
+            std::fs::remove_dir_all(&self.dir).ok();
+        }
+    }
+
+    #[test]
+    fn test_collects_chunks_of_deleted_versions() {
+        let repo = TestRepo::new();
+        let old = repo.write("users", b"v1");
+        let current = repo.write("users", b"v2");
+        repo.catalog.delete_version("users", 1).unwrap();
+
+        // Freshly written chunks are inside the default window
+        let report = ChunkGc::new(&repo.catalog, &repo.store).run().unwrap();
+        assert_eq!(report.chunks_within_grace, 1);
+        assert!(report.collectable.is_empty());
+
+        let gc = ChunkGc::new(&repo.catalog, &repo.store).with_grace_period(Duration::ZERO);
+        let plan = gc.dry_run(true).run().unwrap();
+        assert_eq!(plan.collectable, vec![old.clone()]);
+        assert_eq!(plan.bytes_collectable, 2);
+        assert!(repo.store.exists(&old).unwrap());
+
+        let report = ChunkGc::new(&repo.catalog, &repo.store)
+            .with_grace_period(Duration::ZERO)
+            .run()
+            .unwrap();
+        assert_eq!((report.chunks_scanned, report.chunks_referenced), (2, 1));
+        assert_eq!(report.chunks_deleted, 1);
+        assert!(!repo.store.exists(&old).unwrap());
+        assert!(repo.store.exists(&current).unwrap());
+    }
+
+    #[test]
+    fn test_in_flight_transaction_chunks_are_reachable() {
+        let repo = TestRepo::new();
+        repo.write("users", b"v1");
+        let manager = TransactionManager::new(repo.dir.join("tx"), Arc::clone(&repo.catalog), None).unwrap();
+
+        // Chunks stored by a transaction that has not committed yet
+        let pending = repo.store.put(b"pending").unwrap();
+        let tx_id = manager.begin(None).unwrap();
+        manager.add_write(tx_id, TableWrite::new("orders", 1, vec![pending.clone()])).unwrap();
+
+        let report = ChunkGc::new(&repo.catalog, &repo.store)
+            .with_transactions(&manager)
+            .with_grace_period(Duration::ZERO)
+            .run()
+            .unwrap();
+        assert_eq!(report.chunks_in_flight, 1);
+        assert_eq!(report.chunks_deleted, 0);
+        assert!(repo.store.exists(&pending).unwrap());
+
+        // Once aborted, the chunk is garbage
+        manager.abort(tx_id, "test").unwrap();
+        let report = ChunkGc::new(&repo.catalog, &repo.store)
+            .with_transactions(&manager)
+            .with_grace_period(Duration::ZERO)
+            .run()
+            .unwrap();
+        assert_eq!(report.collectable, vec![pending]);
+    }
+
+    #[test]
+    fn test_dedup_put_refreshes_grace_window() {
+        let repo = TestRepo::new();
+        let hash = repo.store.put(b"shared").unwrap();
+        let path = repo.dir.join("chunks").join(&hash[0..2]).join(&hash[2..4]).join(&hash);
+        let old = SystemTime::now() - Duration::from_secs(7200);
+        std::fs::File::options().write(true).open(&path).unwrap().set_modified(old).unwrap();
+
+        let gc = ChunkGc::new(&repo.catalog, &repo.store).dry_run(true);
+        assert_eq!(gc.run().unwrap().collectable, vec![hash.clone()]);
+
+        // Storing the same bytes again (about to be committed) renews it
+        repo.store.put(b"shared").unwrap();
+        assert_eq!(gc.run().unwrap().chunks_within_grace, 1);
+    }
+
+    #[test]
+    fn test_bases_of_kept_delta_chunks_survive() {
+        let repo = TestRepo::new();
+        let v1: Vec<u8> = (0..20_000u32).map(|i| (i.wrapping_mul(2654435761) >> 24) as u8).collect();
+        let mut v2 = v1.clone();
+        v2[100] ^= 0xff;
+        let base = repo.write("users", &v1);
+        let head = repo.store.put_delta(&v2, &base).unwrap();
+        repo.catalog.commit(TableVersion::new("users", 2, vec![head.clone()])).unwrap();
+        repo.catalog.delete_version("users", 1).unwrap();
+
+        let report = ChunkGc::new(&repo.catalog, &repo.store)
+            .with_grace_period(Duration::ZERO)
+            .run()
+            .unwrap();
+        assert_eq!(report.chunks_delta_bases, 1);
+        assert!(report.collectable.is_empty());
+        assert_eq!(repo.store.get(&head).unwrap(), v2);
+    }
+}
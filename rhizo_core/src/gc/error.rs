@@ -0,0 +1,72 @@
+use std::time::Duration;
+
+use thiserror::Error;
+
+use crate::branch::BranchError;
+use crate::catalog::CatalogError;
+use crate::chunk_store::ChunkStoreError;
+use crate::error::{ErrorCategory, ErrorContext, ErrorInfo};
+use crate::transaction::TransactionError;
+
+#[derive(Error, Debug)]
+#[non_exhaustive]
+pub enum GcError {
+    #[error("Catalog error: {0}")]
+    Catalog(#[from] CatalogError),
+
+    #[error("Chunk store error: {0}")]
+    ChunkStore(#[from] ChunkStoreError),
+
+    #[error("Transaction error: {0}")]
+    Transaction(#[from] TransactionError),
+
+    #[error("Branch error: {0}")]
+    Branch(#[from] BranchError),
+
+    #[error("Cannot remove {table} v{version}: still in use by {user}")]
+    VersionInUse { table: String, version: u64, user: String },
+}
+
+impl ErrorInfo for GcError {
+    fn code(&self) -> &'static str {
+        match self {
+            GcError::Catalog(e) => e.code(),
+            GcError::ChunkStore(e) => e.code(),
+            GcError::Transaction(e) => e.code(),
+            GcError::Branch(e) => e.code(),
+            GcError::VersionInUse { .. } => "GC_VERSION_IN_USE",
+        }
+    }
+
+    fn category(&self) -> ErrorCategory {
+        match self {
+            GcError::Catalog(e) => e.category(),
+            GcError::ChunkStore(e) => e.category(),
+            GcError::Transaction(e) => e.category(),
+            GcError::Branch(e) => e.category(),
+            GcError::VersionInUse { .. } => ErrorCategory::FailedPrecondition,
+        }
+    }
+
+    fn context(&self) -> ErrorContext {
+        match self {
+            GcError::Catalog(e) => e.context(),
+            GcError::ChunkStore(e) => e.context(),
+            GcError::Transaction(e) => e.context(),
+            GcError::Branch(e) => e.context(),
+            GcError::VersionInUse { table, version, user } => ErrorContext::new()
+                .with_table(table.clone())
+                .with_version(*version)
+                .with_key(user.clone()),
+        }
+    }
+
+    fn retry_after(&self) -> Option<Duration> {
+        match self {
+            GcError::Catalog(e) => e.retry_after(),
+            GcError::Transaction(e) => e.retry_after(),
+            GcError::Branch(e) => e.retry_after(),
+            GcError::ChunkStore(_) | GcError::VersionInUse { .. } => None,
+        }
+    }
+}
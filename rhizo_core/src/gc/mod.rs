@@ -0,0 +1,19 @@
+//! Reachability-based garbage collection of chunks.
+//!
+//! Deleting or rewriting table versions leaves chunks no version refers to.
+//! [`ChunkGc`] computes the set of reachable chunk hashes from the catalog
+//! and the transaction log, then deletes (or, in dry-run mode, reports)
+//! every other chunk in the store that is older than a safety window.
+//! [`VersionPruner`] applies per-table retention policies first, removing
+//! the old versions that keep chunks reachable. [`GcPlanner`] picks the
+//! versions to delete to free a target number of bytes.
+
+pub mod collector;
+pub mod error;
+pub mod planner;
+pub mod pruner;
+
+pub use collector::{ChunkGc, GcReport, DEFAULT_GRACE_PERIOD};
+pub use error::GcError;
+pub use planner::{GcPlan, GcPlanner, PlannedDeletion};
+pub use pruner::{PruneReport, VersionPruner};
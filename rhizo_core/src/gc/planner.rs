@@ -0,0 +1,393 @@
+//! Planning which versions to delete to free a given amount of space.
+//!
+//! [`ChunkGc`] only reclaims chunks nothing refers to any more; to get
+//! below a storage quota an operator also has to pick old versions to
+//! drop. [`GcPlanner::plan`] counts the bytes a collection would free
+//! right away, then greedily adds deletable versions, largest reclaimable
+//! size first and oldest first among equals, until the target is met.
+//! A version's reclaimable size is the stored size of the chunks only it
+//! (and versions already in the plan) refers to, so chunks shared with
+//! surviving versions never count.
+//!
+//! Versions that are a table's latest, under legal hold, pinned, tagged,
+//! a branch head or fork point, or read by an active or read-only
+//! transaction are never planned. Chunks a collection would keep anyway
+//! (written by in-flight transactions, the base of a delta chunk, or
+//! younger than the grace period) do not count as reclaimable.
+//!
+//! [`GcPlanner::execute`] deletes the planned versions (recording them in
+//! the prune history) and runs the collection.
+//!
+//! [`ChunkGc`]: super::ChunkGc
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::time::{Duration, SystemTime};
+
+use super::collector::{ChunkGc, GcReport, DEFAULT_GRACE_PERIOD};
+use super::error::GcError;
+use super::pruner::version_users;
+use crate::branch::BranchManager;
+use crate::catalog::FileCatalog;
+use crate::chunk_store::{ChunkStore, ChunkStoreError};
+use crate::transaction::TransactionManager;
+
+/// One version a plan deletes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlannedDeletion {
+    pub table_name: String,
+    pub version: u64,
+
+    /// Unix timestamp the version was committed at
+    pub created_at: i64,
+
+    /// Chunks freed by deleting this version after the earlier steps
+    pub chunks: Vec<String>,
+
+    /// Stored bytes of `chunks`
+    pub bytes: u64,
+}
+
+/// Versions to delete, in order, to free at least `target_bytes`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GcPlan {
+    pub target_bytes: u64,
+
+    /// Bytes of chunks a collection frees without deleting any version
+    pub unreferenced_bytes: u64,
+
+    /// Versions to delete, in the order they were chosen
+    pub steps: Vec<PlannedDeletion>,
+}
+
+impl GcPlan {
+    /// Bytes the plan frees when executed
+    pub fn reclaimable_bytes(&self) -> u64 {
+        self.unreferenced_bytes + self.steps.iter().map(|s| s.bytes).sum::<u64>()
+    }
+
+    /// Whether executing the plan frees `target_bytes`; if not, it frees
+    /// as much as can be freed without touching versions in use
+    pub fn meets_target(&self) -> bool {
+        self.reclaimable_bytes() >= self.target_bytes
+    }
+
+    /// Table -> planned versions, ascending
+    pub fn versions(&self) -> BTreeMap<String, Vec<u64>> {
+        let mut versions: BTreeMap<String, Vec<u64>> = BTreeMap::new();
+        for step in &self.steps {
+            versions.entry(step.table_name.clone()).or_default().push(step.version);
+        }
+        for list in versions.values_mut() {
+            list.sort_unstable();
+        }
+        versions
+    }
+}
+
+/// A deletable version while planning
+struct Candidate {
+    table_name: String,
+    version: u64,
+    created_at: i64,
+    chunks: Vec<String>,
+}
+
+/// Plans (and executes) deletions that free a target number of bytes.
+///
+/// # Example
+///
+/// ```ignore
+/// let planner = GcPlanner::new(&catalog, &store)
+///     .with_branches(&branches)
+///     .with_transactions(&tx_manager);
+/// let plan = planner.plan(10 << 30)?;
+/// for step in &plan.steps {
+///     println!("{} v{}: {} bytes", step.table_name, step.version, step.bytes);
+/// }
+/// planner.execute(&plan)?;
+/// ```
+pub struct GcPlanner<'a> {
+    catalog: &'a FileCatalog,
+    store: &'a ChunkStore,
+    branches: Option<&'a BranchManager>,
+    manager: Option<&'a TransactionManager>,
+    grace_period: Duration,
+}
+
+impl<'a> GcPlanner<'a> {
+    pub fn new(catalog: &'a FileCatalog, store: &'a ChunkStore) -> Self {
+        Self {
+            catalog,
+            store,
+            branches: None,
+            manager: None,
+            grace_period: DEFAULT_GRACE_PERIOD,
+        }
+    }
+
+    /// Never plan versions that are a branch head or fork point.
+    pub fn with_branches(mut self, branches: &'a BranchManager) -> Self {
+        self.branches = Some(branches);
+        self
+    }
+
+    /// Never plan versions read by transactions of `manager`, and keep
+    /// the chunks its in-flight transactions wrote.
+    pub fn with_transactions(mut self, manager: &'a TransactionManager) -> Self {
+        self.manager = Some(manager);
+        self
+    }
+
+    /// Grace period of the collection (default [`DEFAULT_GRACE_PERIOD`]);
+    /// younger chunks are not counted as reclaimable.
+    pub fn with_grace_period(mut self, grace_period: Duration) -> Self {
+        self.grace_period = grace_period;
+        self
+    }
+
+    /// Plan the deletions that free at least `target_bytes`.
+    ///
+    /// Nothing is modified. If the target cannot be met, the plan holds
+    /// every version whose deletion frees space (see
+    /// [`GcPlan::meets_target`]).
+    pub fn plan(&self, target_bytes: u64) -> Result<GcPlan, GcError> {
+        let collector = self.collector().dry_run(true);
+        let started = SystemTime::now();
+        let unreferenced = collector.run()?;
+        let mut plan = GcPlan {
+            target_bytes,
+            unreferenced_bytes: unreferenced.bytes_collectable,
+            steps: Vec::new(),
+        };
+        if plan.meets_target() {
+            return Ok(plan);
+        }
+
+        let users = version_users(self.branches, self.manager)?;
+        let mut refcounts: HashMap<String, usize> = HashMap::new();
+        let mut candidates = Vec::new();
+        for table in self.catalog.list_tables()? {
+            let versions = self.catalog.list_versions(&table)?;
+            let latest = versions.last().copied();
+            for version in versions {
+                let record = self.catalog.get_version(&table, Some(version))?;
+                let chunks: HashSet<String> = record.referenced_chunks().into_iter().collect();
+                for hash in &chunks {
+                    *refcounts.entry(hash.clone()).or_default() += 1;
+                }
+                if Some(version) == latest
+                    || users.contains_key(&(table.clone(), version))
+                    || self.catalog.is_protected(&table, version)?
+                {
+                    continue;
+                }
+                let mut chunks: Vec<String> = chunks.into_iter().collect();
+                chunks.sort();
+                candidates.push(Candidate { table_name: table.clone(), version, created_at: record.created_at, chunks });
+            }
+        }
+
+        let sizes = self.reclaimable_sizes(&collector, &refcounts, started)?;
+        let mut reclaimed = plan.reclaimable_bytes();
+        while reclaimed < target_bytes {
+            // Chunks each candidate would free now, largest first, then oldest
+            let best = candidates
+                .iter()
+                .enumerate()
+                .map(|(i, c)| {
+                    let freed: Vec<&String> = c
+                        .chunks
+                        .iter()
+                        .filter(|h| refcounts[*h] == 1 && sizes.contains_key(*h))
+                        .collect();
+                    let bytes: u64 = freed.iter().map(|h| sizes[*h]).sum();
+                    (i, freed, bytes)
+                })
+                .filter(|(_, _, bytes)| *bytes > 0)
+                .max_by(|(a, _, a_bytes), (b, _, b_bytes)| {
+                    let (a, b) = (&candidates[*a], &candidates[*b]);
+                    a_bytes
+                        .cmp(b_bytes)
+                        .then(b.created_at.cmp(&a.created_at))
+                        .then(b.table_name.cmp(&a.table_name))
+                        .then(b.version.cmp(&a.version))
+                });
+            let Some((index, freed, bytes)) = best else {
+                break;
+            };
+            let freed: Vec<String> = freed.into_iter().cloned().collect();
+            let chosen = candidates.swap_remove(index);
+            for hash in &chosen.chunks {
+                if let Some(count) = refcounts.get_mut(hash) {
+                    *count -= 1;
+                }
+            }
+            reclaimed += bytes;
+            plan.steps.push(PlannedDeletion {
+                table_name: chosen.table_name,
+                version: chosen.version,
+                created_at: chosen.created_at,
+                chunks: freed,
+                bytes,
+            });
+        }
+        Ok(plan)
+    }
+
+    /// Delete the versions of `plan` and run the collection.
+    ///
+    /// # Errors
+    /// - `VersionInUse` if a planned version came into use by a branch or
+    ///   transaction since planning; nothing is deleted then.
+    /// - `Catalog` if a planned version was held, pinned, tagged or
+    ///   became the latest since planning; versions of tables handled
+    ///   before are already deleted.
+    pub fn execute(&self, plan: &GcPlan) -> Result<GcReport, GcError> {
+        let versions = plan.versions();
+        let users = version_users(self.branches, self.manager)?;
+        for (table, list) in &versions {
+            for &version in list {
+                if let Some(user) = users.get(&(table.clone(), version)) {
+                    return Err(GcError::VersionInUse {
+                        table: table.clone(),
+                        version,
+                        user: user.clone(),
+                    });
+                }
+            }
+        }
+        for (table, list) in &versions {
+            self.catalog.prune_versions(table, list)?;
+        }
+        self.collector().run()
+    }
+
+    fn collector(&self) -> ChunkGc<'a> {
+        let collector = ChunkGc::new(self.catalog, self.store).with_grace_period(self.grace_period);
+        match self.manager {
+            Some(manager) => collector.with_transactions(manager),
+            None => collector,
+        }
+    }
+
+    /// Stored size of every referenced chunk a collection would delete
+    /// once nothing refers to it
+    fn reclaimable_sizes(
+        &self,
+        collector: &ChunkGc<'_>,
+        refcounts: &HashMap<String, usize>,
+        started: SystemTime,
+    ) -> Result<HashMap<String, u64>, GcError> {
+        let in_flight = collector.in_flight_hashes()?;
+        let stored = self.store.list_chunk_hashes()?;
+        // Conservative: a base of any stored delta chunk may stay needed
+        let bases = self.store.delta_bases(stored.iter().map(String::as_str))?;
+
+        let mut sizes = HashMap::new();
+        for hash in refcounts.keys() {
+            if in_flight.contains(hash) || bases.contains(hash) {
+                continue;
+            }
+            let stat = match self.store.stat(hash) {
+                Ok(stat) => stat,
+                Err(ChunkStoreError::NotFound(_)) => continue,
+                Err(e) => return Err(e.into()),
+            };
+            let old_enough = match stat.modified {
+                Some(modified) => started.duration_since(modified).is_ok_and(|age| age >= self.grace_period),
+                None => self.grace_period.is_zero(),
+            };
+            if old_enough {
+                sizes.insert(hash.clone(), stat.size);
+            }
+        }
+        Ok(sizes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::catalog::TableVersion;
+    use tempfile::TempDir;
+
+    struct Repo {
+        _dir: TempDir,
+        catalog: FileCatalog,
+        store: ChunkStore,
+    }
+
+    impl Repo {
+        fn new() -> Self {
+            let dir = TempDir::new().unwrap();
+            let catalog = FileCatalog::new(dir.path().join("catalog")).unwrap();
+            let store = ChunkStore::new(dir.path().join("chunks")).unwrap();
+            Self { _dir: dir, catalog, store }
+        }
+
+        /// Store `chunks` and commit them as a version
+        fn commit(&self, table: &str, version: u64, chunks: &[&[u8]]) -> Vec<String> {
+            let hashes: Vec<String> = chunks.iter().map(|c| self.store.put(c).unwrap()).collect();
+            self.catalog.commit(TableVersion::new(table, version, hashes.clone())).unwrap();
+            hashes
+        }
+
+        fn planner(&self) -> GcPlanner<'_> {
+            GcPlanner::new(&self.catalog, &self.store).with_grace_period(Duration::ZERO)
+        }
+    }
+
+    #[test]
+    fn test_plan_picks_largest_versions_until_target() {
+        let repo = Repo::new();
+        let shared = vec![b's'; 50];
+        repo.commit("users", 1, &[&[b'a'; 100], &shared]);
+        repo.commit("users", 2, &[&[b'b'; 300], &shared]);
+        repo.commit("users", 3, &[&[b'c'; 10], &shared]);
+        repo.commit("orders", 1, &[&[b'o'; 200]]);
+        repo.commit("orders", 2, &[&[b'p'; 20]]);
+
+        let plan = repo.planner().plan(350).unwrap();
+        let chosen: Vec<(String, u64, u64)> =
+            plan.steps.iter().map(|s| (s.table_name.clone(), s.version, s.bytes)).collect();
+        assert_eq!(chosen, vec![("users".to_string(), 2, 300), ("orders".to_string(), 1, 200)]);
+        assert!(plan.meets_target());
+        assert_eq!(plan.unreferenced_bytes, 0);
+
+        // The shared chunk stays referenced by the latest version
+        let all = repo.planner().plan(u64::MAX).unwrap();
+        assert!(!all.meets_target());
+        assert_eq!(all.reclaimable_bytes(), 600);
+        let latest = repo.catalog.get_version("users", Some(3)).unwrap().chunk_hashes;
+        assert!(all.steps.iter().flat_map(|s| &s.chunks).all(|h| !latest.contains(h)));
+
+        let report = repo.planner().execute(&plan).unwrap();
+        assert_eq!(report.bytes_collectable, 500);
+        assert_eq!(repo.catalog.list_versions("users").unwrap(), vec![1, 3]);
+        assert_eq!(repo.catalog.list_versions("orders").unwrap(), vec![2]);
+        assert_eq!(repo.catalog.prune_history("users").unwrap()[0].versions, vec![2]);
+    }
+
+    #[test]
+    fn test_plan_counts_unreferenced_chunks_and_skips_protected_versions() {
+        let repo = Repo::new();
+        repo.store.put(&[b'x'; 400]).unwrap();
+        repo.commit("users", 1, &[&[b'a'; 100]]);
+        repo.commit("users", 2, &[&[b'b'; 300]]);
+        repo.commit("users", 3, &[&[b'c'; 10]]);
+
+        // Collecting the orphan is enough
+        let plan = repo.planner().plan(400).unwrap();
+        assert_eq!(plan.unreferenced_bytes, 400);
+        assert!(plan.steps.is_empty() && plan.meets_target());
+
+        repo.catalog.hold("users", 2, None).unwrap();
+        let plan = repo.planner().plan(1000).unwrap();
+        assert_eq!(plan.versions(), BTreeMap::from([("users".to_string(), vec![1])]));
+        assert!(!plan.meets_target());
+
+        // Chunks inside the grace period are not counted
+        let plan = GcPlanner::new(&repo.catalog, &repo.store).plan(1).unwrap();
+        assert_eq!(plan.reclaimable_bytes(), 0);
+    }
+}
@@ -0,0 +1,410 @@
+//! Retention-driven pruning of old table versions.
+//!
+//! For each table with a retention policy (or the pruner's default), the
+//! versions the policy expires are removed from the catalog unless they
+//! are still in use: under legal hold, pinned, tagged, a branch head or
+//! fork point, or read by an active transaction. Chunks that only pruned
+//! versions referred to are reported for a following [`ChunkGc`] run,
+//! which deletes them once they leave its grace period.
+//!
+//! [`VersionPruner::squash_versions`] instead folds a table's early history
+//! into one base version, under the same in-use rules.
+//!
+//! [`ChunkGc`]: super::ChunkGc
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use super::error::GcError;
+use crate::branch::BranchManager;
+use crate::catalog::{FileCatalog, RetentionPolicy, TableVersion};
+use crate::transaction::TransactionManager;
+
+/// Outcome of a pruning run.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PruneReport {
+    /// Nothing was deleted; `versions_pruned` lists what would have been
+    pub dry_run: bool,
+    /// Table -> versions removed (or expired, in a dry run), ascending
+    pub versions_pruned: BTreeMap<String, Vec<u64>>,
+    /// Table -> expired versions kept because they are still in use
+    pub versions_protected: BTreeMap<String, Vec<u64>>,
+    /// Chunks referenced only by pruned versions, sorted
+    pub unreferenced_chunks: Vec<String>,
+}
+
+impl PruneReport {
+    /// Total number of versions pruned across tables
+    pub fn total_pruned(&self) -> usize {
+        self.versions_pruned.values().map(Vec::len).sum()
+    }
+}
+
+/// Applies per-table retention policies to the catalog.
+///
+/// # Example
+///
+/// ```ignore
+/// let report = VersionPruner::new(&catalog)
+///     .with_branches(&branches)
+///     .with_transactions(&tx_manager)
+///     .run()?;
+/// // Chunks of pruned versions are now unreferenced
+/// ChunkGc::new(&catalog, &store).run()?;
+/// ```
+pub struct VersionPruner<'a> {
+    catalog: &'a FileCatalog,
+    branches: Option<&'a BranchManager>,
+    manager: Option<&'a TransactionManager>,
+    default_policy: Option<RetentionPolicy>,
+    dry_run: bool,
+}
+
+impl<'a> VersionPruner<'a> {
+    pub fn new(catalog: &'a FileCatalog) -> Self {
+        Self {
+            catalog,
+            branches: None,
+            manager: None,
+            default_policy: None,
+            dry_run: false,
+        }
+    }
+
+    /// Keep versions that are a branch head or fork point.
+    pub fn with_branches(mut self, branches: &'a BranchManager) -> Self {
+        self.branches = Some(branches);
+        self
+    }
+
+    /// Keep versions read by transactions active in `manager`.
+    pub fn with_transactions(mut self, manager: &'a TransactionManager) -> Self {
+        self.manager = Some(manager);
+        self
+    }
+
+    /// Policy for tables without one of their own (default: keep all).
+    pub fn with_default_policy(mut self, policy: RetentionPolicy) -> Self {
+        self.default_policy = Some(policy);
+        self
+    }
+
+    /// Report expired versions without deleting them.
+    pub fn dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    pub fn run(&self) -> Result<PruneReport, GcError> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+        let in_use = self.versions_in_use()?;
+
+        let mut report = PruneReport {
+            dry_run: self.dry_run,
+            ..PruneReport::default()
+        };
+        let mut released: HashSet<String> = HashSet::new();
+        for table in self.catalog.list_tables()? {
+            let policy = match self.catalog.retention_policy(&table)? {
+                Some(policy) => policy,
+                None => match &self.default_policy {
+                    Some(policy) => policy.clone(),
+                    None => continue,
+                },
+            };
+
+            let mut versions = Vec::new();
+            for version in self.catalog.list_versions(&table)? {
+                versions.push((version, self.catalog.get_version(&table, Some(version))?.created_at));
+            }
+
+            let mut expired = Vec::new();
+            let mut protected = Vec::new();
+            for version in policy.expired(&versions, now) {
+                if in_use.contains(&(table.clone(), version))
+                    || self.catalog.is_protected(&table, version)?
+                {
+                    protected.push(version);
+                } else {
+                    expired.push(version);
+                }
+            }
+            if !protected.is_empty() {
+                report.versions_protected.insert(table.clone(), protected);
+            }
+            if expired.is_empty() {
+                continue;
+            }
+
+            let pruned = if self.dry_run {
+                expired
+                    .iter()
+                    .map(|&v| self.catalog.get_version(&table, Some(v)))
+                    .collect::<Result<Vec<_>, _>>()?
+            } else {
+                self.catalog.prune_versions(&table, &expired)?
+            };
+            released.extend(pruned.iter().flat_map(TableVersion::referenced_chunks));
+            report.versions_pruned.insert(table, expired);
+        }
+
+        if !released.is_empty() {
+            let remaining = if self.dry_run {
+                // Nothing was deleted, so skip the versions that would be
+                self.surviving_chunks(&report.versions_pruned)?
+            } else {
+                self.catalog.get_all_referenced_chunk_hashes()?
+            };
+            let mut unreferenced: Vec<String> =
+                released.into_iter().filter(|h| !remaining.contains(h)).collect();
+            unreferenced.sort();
+            report.unreferenced_chunks = unreferenced;
+        }
+
+        Ok(report)
+    }
+
+    /// Fold every version of `table` before `up_to_version` into it (see
+    /// `FileCatalog::squash_versions`).
+    ///
+    /// Retention policies do not apply. The report lists the folded versions
+    /// under `versions_pruned`, and the chunks only they referenced. A dry
+    /// run also lists folded versions under legal hold, pinned or tagged in
+    /// `versions_protected`; the squash itself fails on them.
+    ///
+    /// # Errors
+    /// - `VersionInUse` if a folded version is a branch head or fork point,
+    ///   or read by an active transaction; branches cannot be moved onto
+    ///   the base without changing the data they see.
+    pub fn squash_versions(&self, table: &str, up_to_version: u64) -> Result<PruneReport, GcError> {
+        let in_use = self.version_users()?;
+        let base = self.catalog.get_version(table, Some(up_to_version))?;
+        let folded: Vec<u64> = self
+            .catalog
+            .list_versions(table)?
+            .into_iter()
+            .filter(|&v| v < up_to_version)
+            .collect();
+        for &version in &folded {
+            if let Some(user) = in_use.get(&(table.to_string(), version)) {
+                return Err(GcError::VersionInUse {
+                    table: table.to_string(),
+                    version,
+                    user: user.clone(),
+                });
+            }
+        }
+
+        let mut report = PruneReport {
+            dry_run: self.dry_run,
+            ..PruneReport::default()
+        };
+        if folded.is_empty() {
+            return Ok(report);
+        }
+        let removed = if self.dry_run {
+            for &version in &folded {
+                if self.catalog.is_protected(table, version)? {
+                    report.versions_protected.entry(table.to_string()).or_default().push(version);
+                }
+            }
+            folded
+                .iter()
+                .map(|&v| self.catalog.get_version(table, Some(v)))
+                .collect::<Result<Vec<_>, _>>()?
+        } else {
+            self.catalog.squash_versions(table, up_to_version)?
+        };
+
+        let remaining = if self.dry_run {
+            self.surviving_chunks(&BTreeMap::from([(table.to_string(), folded.clone())]))?
+        } else {
+            self.catalog.get_all_referenced_chunk_hashes()?
+        };
+        let released: HashSet<String> = removed.iter().flat_map(TableVersion::referenced_chunks).collect();
+        let base_chunks: HashSet<String> = base.referenced_chunks().into_iter().collect();
+        let mut unreferenced: Vec<String> = released
+            .into_iter()
+            .filter(|h| !remaining.contains(h) && !base_chunks.contains(h))
+            .collect();
+        unreferenced.sort();
+        report.unreferenced_chunks = unreferenced;
+        report.versions_pruned.insert(table.to_string(), folded);
+        Ok(report)
+    }
+
+    /// (table, version) pairs a branch or active transaction still refers to
+    fn versions_in_use(&self) -> Result<HashSet<(String, u64)>, GcError> {
+        Ok(self.version_users()?.into_keys().collect())
+    }
+
+    /// (table, version) pairs in use, with the branch or transaction using each
+    fn version_users(&self) -> Result<HashMap<(String, u64), String>, GcError> {
+        version_users(self.branches, self.manager)
+    }
+
+    /// Chunks referenced by catalog versions other than `pruned`
+    fn surviving_chunks(
+        &self,
+        pruned: &BTreeMap<String, Vec<u64>>,
+    ) -> Result<HashSet<String>, GcError> {
+        let mut hashes = HashSet::new();
+        for table in self.catalog.list_tables()? {
+            let skip = pruned.get(&table);
+            for version in self.catalog.list_versions(&table)? {
+                if skip.is_some_and(|s| s.contains(&version)) {
+                    continue;
+                }
+                hashes.extend(self.catalog.get_version(&table, Some(version))?.referenced_chunks());
+            }
+        }
+        Ok(hashes)
+    }
+}
+
+/// (table, version) pairs a branch head or fork point, an active
+/// transaction or a read-only transaction refers to, with the user of each
+pub(super) fn version_users(
+    branches: Option<&BranchManager>,
+    manager: Option<&TransactionManager>,
+) -> Result<HashMap<(String, u64), String>, GcError> {
+    let mut users = HashMap::new();
+    if let Some(manager) = manager {
+        for tx in manager.active_transactions()? {
+            let user = format!("transaction {}", tx.tx_id);
+            users.extend(tx.read_snapshot.iter().map(|(t, &v)| ((t.clone(), v), user.clone())));
+        }
+        for pin in manager.read_only_transactions() {
+            let user = format!("read-only transaction {}", pin.reader_id);
+            users.extend(pin.versions.iter().map(|(t, &v)| ((t.clone(), v), user.clone())));
+        }
+    }
+    if let Some(branches) = branches {
+        for name in branches.list()? {
+            let branch = branches.get(&name)?;
+            let user = format!("branch {}", name);
+            users.extend(
+                branch
+                    .head
+                    .into_iter()
+                    .chain(branch.fork_point.into_iter().flatten())
+                    .map(|key| (key, user.clone())),
+            );
+        }
+    }
+    Ok(users)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::catalog::TableVersion;
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    fn commit(catalog: &FileCatalog, table: &str, version: u64, chunks: &[&str]) {
+        let chunks = chunks.iter().map(|c| c.to_string()).collect();
+        catalog.commit(TableVersion::new(table, version, chunks)).unwrap();
+    }
+
+    #[test]
+    fn test_prunes_expired_versions_and_reports_released_chunks() {
+        let dir = TempDir::new().unwrap();
+        let catalog = FileCatalog::new(dir.path().join("catalog")).unwrap();
+        commit(&catalog, "users", 1, &["a", "shared"]);
+        commit(&catalog, "users", 2, &["b", "shared"]);
+        commit(&catalog, "users", 3, &["c", "shared"]);
+        commit(&catalog, "orders", 1, &["o1"]);
+        commit(&catalog, "orders", 2, &["o2"]);
+        catalog
+            .set_retention_policy("users", Some(RetentionPolicy::new().with_keep_last(1)))
+            .unwrap();
+        catalog.hold("users", 1, None).unwrap();
+
+        let dry = VersionPruner::new(&catalog).dry_run(true).run().unwrap();
+        assert_eq!(dry.versions_pruned["users"], vec![2]);
+        assert_eq!(dry.versions_protected["users"], vec![1]);
+        assert_eq!(dry.unreferenced_chunks, vec!["b"]);
+        assert_eq!(catalog.list_versions("users").unwrap(), vec![1, 2, 3]);
+
+        let report = VersionPruner::new(&catalog).run().unwrap();
+        assert_eq!(report.unreferenced_chunks, vec!["b"]);
+        assert_eq!(catalog.list_versions("users").unwrap(), vec![1, 3]);
+        // No policy, so untouched
+        assert_eq!(catalog.list_versions("orders").unwrap(), vec![1, 2]);
+
+        let report = VersionPruner::new(&catalog)
+            .with_default_policy(RetentionPolicy::new().with_keep_last(1))
+            .run()
+            .unwrap();
+        assert_eq!(report.versions_pruned.keys().collect::<Vec<_>>(), vec!["orders"]);
+        assert_eq!(report.unreferenced_chunks, vec!["o1"]);
+    }
+
+    #[test]
+    fn test_squash_versions_refuses_versions_in_use() {
+        let dir = TempDir::new().unwrap();
+        let catalog = FileCatalog::new(dir.path().join("catalog")).unwrap();
+        let branches = BranchManager::new(dir.path().join("branches")).unwrap();
+        commit(&catalog, "users", 1, &["a"]);
+        commit(&catalog, "users", 2, &["a", "b"]);
+        commit(&catalog, "users", 3, &["a", "c"]);
+        branches.update_head("main", "users", 2).unwrap();
+
+        let pruner = VersionPruner::new(&catalog).with_branches(&branches);
+        assert!(matches!(
+            pruner.squash_versions("users", 3),
+            Err(GcError::VersionInUse { version: 2, .. })
+        ));
+
+        branches.update_head("main", "users", 3).unwrap();
+        let dry = VersionPruner::new(&catalog).with_branches(&branches).dry_run(true).squash_versions("users", 3).unwrap();
+        assert_eq!(dry.versions_pruned["users"], vec![1, 2]);
+        assert_eq!(dry.unreferenced_chunks, vec!["b"]);
+        assert_eq!(catalog.list_versions("users").unwrap(), vec![1, 2, 3]);
+
+        let report = pruner.squash_versions("users", 3).unwrap();
+        assert_eq!(report.unreferenced_chunks, vec!["b"]);
+        assert_eq!(catalog.list_versions("users").unwrap(), vec![3]);
+        assert_eq!(catalog.squashed_into("users", 1).unwrap(), Some(3));
+    }
+
+    #[test]
+    fn test_branch_heads_are_kept() {
+        let dir = TempDir::new().unwrap();
+        let catalog = FileCatalog::new(dir.path().join("catalog")).unwrap();
+        let branches = BranchManager::new(dir.path().join("branches")).unwrap();
+        for v in 1..=3 {
+            commit(&catalog, "users", v, &[]);
+        }
+        branches.update_head("main", "users", 1).unwrap();
+        catalog
+            .set_retention_policy("users", Some(RetentionPolicy::new().with_keep_last(1)))
+            .unwrap();
+
+        let report = VersionPruner::new(&catalog).with_branches(&branches).run().unwrap();
+        assert_eq!(report.versions_pruned["users"], vec![2]);
+        assert_eq!(report.versions_protected["users"], vec![1]);
+    }
+
+    #[test]
+    fn test_read_only_transactions_pin_versions() {
+        let dir = TempDir::new().unwrap();
+        let catalog = Arc::new(FileCatalog::new(dir.path().join("catalog")).unwrap());
+        let manager = TransactionManager::new(dir.path(), Arc::clone(&catalog), None).unwrap();
+        commit(&catalog, "users", 1, &["a"]);
+        let reader = manager.begin_read_only(None).unwrap();
+        commit(&catalog, "users", 2, &["b"]);
+        catalog
+            .set_retention_policy("users", Some(RetentionPolicy::new().with_keep_last(1)))
+            .unwrap();
+
+        let pruner = VersionPruner::new(&catalog).with_transactions(&manager).dry_run(true);
+        assert_eq!(pruner.run().unwrap().versions_protected["users"], vec![1]);
+        drop(reader);
+        assert_eq!(pruner.run().unwrap().versions_pruned["users"], vec![1]);
+    }
+}
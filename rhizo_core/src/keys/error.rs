@@ -0,0 +1,57 @@
+use thiserror::Error;
+
+use crate::error::{ErrorCategory, ErrorContext, ErrorInfo};
+
+#[derive(Error, Debug)]
+#[non_exhaustive]
+pub enum KeyError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Key not found: {0}")]
+    NotFound(String),
+
+    #[error("Invalid key id: {0}")]
+    InvalidKeyId(String),
+
+    #[error("Invalid key material for {0}: {1}")]
+    InvalidKeyMaterial(String, String),
+
+    #[error("Key file is accessible by other users: {0}")]
+    InsecurePermissions(String),
+
+    #[error("Key provider error ({provider}): {message}")]
+    Backend { provider: String, message: String },
+}
+
+impl ErrorInfo for KeyError {
+    fn code(&self) -> &'static str {
+        match self {
+            KeyError::Io(_) => "KEY_IO",
+            KeyError::NotFound(_) => "KEY_NOT_FOUND",
+            KeyError::InvalidKeyId(_) => "KEY_INVALID_ID",
+            KeyError::InvalidKeyMaterial(..) => "KEY_INVALID_MATERIAL",
+            KeyError::InsecurePermissions(_) => "KEY_INSECURE_PERMISSIONS",
+            KeyError::Backend { .. } => "KEY_BACKEND",
+        }
+    }
+
+    fn category(&self) -> ErrorCategory {
+        match self {
+            KeyError::Io(_) | KeyError::Backend { .. } => ErrorCategory::Io,
+            KeyError::NotFound(_) => ErrorCategory::NotFound,
+            KeyError::InvalidKeyId(_) | KeyError::InvalidKeyMaterial(..) => ErrorCategory::InvalidArgument,
+            KeyError::InsecurePermissions(_) => ErrorCategory::FailedPrecondition,
+        }
+    }
+
+    fn context(&self) -> ErrorContext {
+        match self {
+            KeyError::NotFound(key_id) | KeyError::InvalidKeyId(key_id) | KeyError::InvalidKeyMaterial(key_id, _) => {
+                ErrorContext::new().with_key(key_id.clone())
+            }
+            KeyError::InsecurePermissions(path) => ErrorContext::new().with_path(path.clone()),
+            _ => ErrorContext::new(),
+        }
+    }
+}
@@ -0,0 +1,40 @@
+//! Key material providers for encryption and signing.
+//!
+//! Key material should never be hardcoded or committed alongside data.
+//! Components that need keys take a `KeyProvider` and ask for keys by id,
+//! so the source of the key (environment, key files, Vault, a cloud KMS)
+//! is a deployment decision rather than a code change.
+//!
+//! # Implementing a secret-manager plugin
+//!
+//! External secret managers implement `KeyProvider` directly:
+//!
+//! ```
+//! use rhizo_core::keys::{KeyError, KeyMaterial, KeyProvider};
+//!
+//! struct VaultKeyProvider { /* client, mount path, ... */ }
+//!
+//! impl KeyProvider for VaultKeyProvider {
+//!     fn name(&self) -> &str {
+//!         "vault"
+//!     }
+//!
+//!     fn get_key(&self, key_id: &str) -> Result<KeyMaterial, KeyError> {
+//!         // Fetch `secret/rhizo/<key_id>` from Vault, decode it, and
+//!         // return it. Map "no such secret" to `KeyError::NotFound` and
+//!         // transport failures to `KeyError::Backend`.
+//!         Err(KeyError::NotFound(key_id.to_string()))
+//!     }
+//! }
+//! ```
+//!
+//! Remote providers should usually be wrapped in `CachedKeyProvider` so
+//! that hot paths do not make a network round-trip per operation.
+
+pub mod error;
+pub mod provider;
+
+pub use error::KeyError;
+pub use provider::{
+    validate_key_id, CachedKeyProvider, EnvKeyProvider, FileKeyProvider, KeyMaterial, KeyProvider,
+};
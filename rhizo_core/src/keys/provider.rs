@@ -0,0 +1,355 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use parking_lot::RwLock;
+
+use super::error::KeyError;
+
+/// Secret key bytes.
+///
+/// The bytes are zeroed on drop and never printed by `Debug`, so keys do not
+/// leak through logs or error messages.
+#[derive(Clone, PartialEq, Eq)]
+pub struct KeyMaterial(Vec<u8>);
+
+impl KeyMaterial {
+    pub fn new(bytes: Vec<u8>) -> Self {
+        Self(bytes)
+    }
+
+    /// Parse hex-encoded key material (surrounding whitespace is ignored)
+    pub fn from_hex(key_id: &str, hex: &str) -> Result<Self, KeyError> {
+        let hex = hex.trim();
+        let invalid = |reason: &str| KeyError::InvalidKeyMaterial(key_id.to_string(), reason.to_string());
+
+        if hex.is_empty() {
+            return Err(invalid("empty"));
+        }
+        if !hex.len().is_multiple_of(2) {
+            return Err(invalid("odd number of hex digits"));
+        }
+
+        let bytes = (0..hex.len())
+            .step_by(2)
+            .map(|i| hex.get(i..i + 2).and_then(|b| u8::from_str_radix(b, 16).ok()))
+            .collect::<Option<Vec<u8>>>()
+            .ok_or_else(|| invalid("not valid hex"))?;
+
+        Ok(Self(bytes))
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl fmt::Debug for KeyMaterial {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "KeyMaterial(<{} bytes redacted>)", self.0.len())
+    }
+}
+
+impl Drop for KeyMaterial {
+    fn drop(&mut self) {
+        for byte in self.0.iter_mut() {
+            // Volatile so the compiler cannot elide the wipe of a dead buffer
+            unsafe { std::ptr::write_volatile(byte, 0) };
+        }
+    }
+}
+
+/// Source of key material for encryption and signing.
+///
+/// Keys are addressed by id (e.g. `"chunks-2024"`, `"commit-signing"`), which
+/// lets callers rotate keys by switching ids while old data stays readable
+/// with the old key.
+pub trait KeyProvider: Send + Sync {
+    /// Short provider name used in error messages (e.g. "env", "vault")
+    fn name(&self) -> &str;
+
+    /// Fetch the key material for `key_id`.
+    ///
+    /// Returns `KeyError::NotFound` if the provider has no such key.
+    fn get_key(&self, key_id: &str) -> Result<KeyMaterial, KeyError>;
+}
+
+impl<P: KeyProvider + ?Sized> KeyProvider for Arc<P> {
+    fn name(&self) -> &str {
+        (**self).name()
+    }
+
+    fn get_key(&self, key_id: &str) -> Result<KeyMaterial, KeyError> {
+        (**self).get_key(key_id)
+    }
+}
+
+/// Validate a key id.
+///
+/// Ids are limited to ASCII alphanumerics, `-` and `_` so they map safely
+/// onto file names and environment variable names.
+pub fn validate_key_id(key_id: &str) -> Result<(), KeyError> {
+    if key_id.is_empty()
+        || !key_id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err(KeyError::InvalidKeyId(key_id.to_string()));
+    }
+    Ok(())
+}
+
+/// Reads hex-encoded keys from environment variables.
+///
+/// Key `chunks-2024` is read from `RHIZO_KEY_CHUNKS_2024` with the default
+/// prefix: the id is upper-cased and `-` becomes `_`.
+#[derive(Debug, Clone)]
+pub struct EnvKeyProvider {
+    prefix: String,
+}
+
+impl EnvKeyProvider {
+    pub const DEFAULT_PREFIX: &'static str = "RHIZO_KEY_";
+
+    pub fn new() -> Self {
+        Self::with_prefix(Self::DEFAULT_PREFIX)
+    }
+
+    pub fn with_prefix(prefix: impl Into<String>) -> Self {
+        Self { prefix: prefix.into() }
+    }
+
+    /// Environment variable holding a key
+    pub fn var_name(&self, key_id: &str) -> String {
+        format!("{}{}", self.prefix, key_id.to_ascii_uppercase().replace('-', "_"))
+    }
+}
+
+impl Default for EnvKeyProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl KeyProvider for EnvKeyProvider {
+    fn name(&self) -> &str {
+        "env"
+    }
+
+    fn get_key(&self, key_id: &str) -> Result<KeyMaterial, KeyError> {
+        validate_key_id(key_id)?;
+        let value = std::env::var(self.var_name(key_id))
+            .map_err(|_| KeyError::NotFound(key_id.to_string()))?;
+        KeyMaterial::from_hex(key_id, &value)
+    }
+}
+
+/// Reads hex-encoded keys from `<dir>/<key_id>.key`.
+///
+/// On Unix, key files readable or writable by group or others are rejected
+/// (like SSH private keys); use `allow_insecure_permissions` to opt out,
+/// e.g. for keys mounted by an orchestrator that manages access itself.
+#[derive(Debug, Clone)]
+pub struct FileKeyProvider {
+    dir: PathBuf,
+    check_permissions: bool,
+}
+
+impl FileKeyProvider {
+    pub fn new(dir: impl AsRef<Path>) -> Self {
+        Self {
+            dir: dir.as_ref().to_path_buf(),
+            check_permissions: true,
+        }
+    }
+
+    pub fn allow_insecure_permissions(mut self) -> Self {
+        self.check_permissions = false;
+        self
+    }
+
+    /// Path of the file holding a key
+    pub fn key_path(&self, key_id: &str) -> PathBuf {
+        self.dir.join(format!("{}.key", key_id))
+    }
+
+    #[cfg(unix)]
+    fn check_file_permissions(&self, path: &Path) -> Result<(), KeyError> {
+        use std::os::unix::fs::PermissionsExt;
+
+        let mode = fs::metadata(path)?.permissions().mode();
+        if self.check_permissions && mode & 0o077 != 0 {
+            return Err(KeyError::InsecurePermissions(path.display().to_string()));
+        }
+        Ok(())
+    }
+
+    #[cfg(not(unix))]
+    fn check_file_permissions(&self, _path: &Path) -> Result<(), KeyError> {
+        Ok(())
+    }
+}
+
+impl KeyProvider for FileKeyProvider {
+    fn name(&self) -> &str {
+        "file"
+    }
+
+    fn get_key(&self, key_id: &str) -> Result<KeyMaterial, KeyError> {
+        validate_key_id(key_id)?;
+        let path = self.key_path(key_id);
+        if !path.exists() {
+            return Err(KeyError::NotFound(key_id.to_string()));
+        }
+
+        self.check_file_permissions(&path)?;
+        KeyMaterial::from_hex(key_id, &fs::read_to_string(&path)?)
+    }
+}
+
+/// Caches keys from another provider for a fixed time.
+///
+/// Intended for remote secret managers (Vault, cloud KMS) where every lookup
+/// is a network call. Failed lookups are not cached.
+pub struct CachedKeyProvider<P: KeyProvider> {
+    inner: P,
+    ttl: Duration,
+    cache: RwLock<HashMap<String, (KeyMaterial, Instant)>>,
+}
+
+impl<P: KeyProvider> CachedKeyProvider<P> {
+    pub fn new(inner: P, ttl: Duration) -> Self {
+        Self {
+            inner,
+            ttl,
+            cache: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Drop all cached keys (e.g. after a rotation)
+    pub fn invalidate(&self) {
+        self.cache.write().clear();
+    }
+
+    pub fn inner(&self) -> &P {
+        &self.inner
+    }
+}
+
+impl<P: KeyProvider> KeyProvider for CachedKeyProvider<P> {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn get_key(&self, key_id: &str) -> Result<KeyMaterial, KeyError> {
+        if let Some((key, fetched_at)) = self.cache.read().get(key_id) {
+            if fetched_at.elapsed() < self.ttl {
+                return Ok(key.clone());
+            }
+        }
+
+        let key = self.inner.get_key(key_id)?;
+        self.cache
+            .write()
+            .insert(key_id.to_string(), (key.clone(), Instant::now()));
+        Ok(key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn temp_dir() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("rhizo_keys_test_{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_key_material_hex_and_debug() {
+        let key = KeyMaterial::from_hex("k", " 00ff10\n").unwrap();
+        assert_eq!(key.as_bytes(), &[0x00, 0xff, 0x10]);
+        assert_eq!(format!("{:?}", key), "KeyMaterial(<3 bytes redacted>)");
+
+        assert!(KeyMaterial::from_hex("k", "abc").is_err());
+        assert!(KeyMaterial::from_hex("k", "zz").is_err());
+        assert!(KeyMaterial::from_hex("k", "").is_err());
+    }
+
+    #[test]
+    fn test_validate_key_id() {
+        assert!(validate_key_id("chunks-2024_v1").is_ok());
+        for bad in ["", "../etc/passwd", "a/b", "a.key", "a b"] {
+            assert!(matches!(validate_key_id(bad), Err(KeyError::InvalidKeyId(_))));
+        }
+    }
+
+    #[test]
+    fn test_env_provider() {
+        let provider = EnvKeyProvider::with_prefix("RHIZO_TEST_KEY_");
+        assert_eq!(provider.var_name("commit-signing"), "RHIZO_TEST_KEY_COMMIT_SIGNING");
+
+        std::env::set_var("RHIZO_TEST_KEY_COMMIT_SIGNING", "0102");
+        assert_eq!(provider.get_key("commit-signing").unwrap().as_bytes(), &[1, 2]);
+        assert!(matches!(provider.get_key("missing"), Err(KeyError::NotFound(_))));
+        std::env::remove_var("RHIZO_TEST_KEY_COMMIT_SIGNING");
+    }
+
+    #[test]
+    fn test_file_provider() {
+        let dir = temp_dir();
+        let provider = FileKeyProvider::new(&dir);
+        let path = provider.key_path("chunks");
+        fs::write(&path, "deadbeef\n").unwrap();
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(&path, fs::Permissions::from_mode(0o644)).unwrap();
+            assert!(matches!(provider.get_key("chunks"), Err(KeyError::InsecurePermissions(_))));
+            fs::set_permissions(&path, fs::Permissions::from_mode(0o600)).unwrap();
+        }
+
+        assert_eq!(provider.get_key("chunks").unwrap().as_bytes(), &[0xde, 0xad, 0xbe, 0xef]);
+        assert!(matches!(provider.get_key("other"), Err(KeyError::NotFound(_))));
+        assert!(matches!(provider.get_key("../chunks"), Err(KeyError::InvalidKeyId(_))));
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    struct CountingProvider(AtomicUsize);
+
+    impl KeyProvider for CountingProvider {
+        fn name(&self) -> &str {
+            "counting"
+        }
+
+        fn get_key(&self, _key_id: &str) -> Result<KeyMaterial, KeyError> {
+            self.0.fetch_add(1, Ordering::SeqCst);
+            Ok(KeyMaterial::new(vec![7; 32]))
+        }
+    }
+
+    #[test]
+    fn test_cached_provider() {
+        let cached = CachedKeyProvider::new(CountingProvider(AtomicUsize::new(0)), Duration::from_secs(60));
+        cached.get_key("a").unwrap();
+        cached.get_key("a").unwrap();
+        assert_eq!(cached.inner().0.load(Ordering::SeqCst), 1);
+
+        cached.invalidate();
+        cached.get_key("a").unwrap();
+        assert_eq!(cached.inner().0.load(Ordering::SeqCst), 2);
+    }
+}
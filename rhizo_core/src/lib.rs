@@ -1,43 +1,108 @@
 pub mod algebraic;
+pub mod artifact;
+pub mod backup;
 pub mod branch;
 pub mod catalog;
 pub mod changelog;
+pub mod data_merge;
 pub mod chunk_store;
 pub mod distributed;
+pub mod durability;
+pub mod error;
+pub mod filter;
+pub mod fixtures;
+pub mod gc;
+pub mod keys;
 pub mod merkle;
+#[cfg(feature = "async")]
+pub mod nonblocking;
 pub mod parquet;
+pub mod query;
+#[cfg(feature = "json-schema")]
+pub mod record_schema;
+pub mod refspec;
+pub mod repo;
+pub mod scrub;
+pub mod sql;
+pub mod table;
 pub mod transaction;
 
 pub use algebraic::{
-    AlgebraicMerger, AlgebraicSchemaRegistry, AlgebraicValue, ColumnAlgebraic, MergeResult,
-    OpType, TableAlgebraicSchema,
+    AlgebraicLaws, AlgebraicMerger, AlgebraicSchemaRegistry, AlgebraicValue, BoundedCounter, BoundedCounterError,
+    Bounds, ColumnAlgebraic, CustomMergeError, CustomMergeRegistry, Decimal, DecimalError, MergeResult, LwwRegister, ObservedRemoveSet, OpType, PnCounter,
+    TableAlgebraicSchema,
 };
+pub use artifact::{Artifact, ArtifactError, ArtifactStore};
+pub use backup::{Backup, BackupError, BackupManifest, BackupSet, RestoreReport, VerifyReport};
 pub use branch::{
-    Branch, BranchDiff, BranchError, BranchManager, MergeAnalysis, MergeAnalyzer, MergeOutcome,
+    ArchivedBranch, Branch, BranchCommit, BranchDiff, BranchError, BranchManager, Checkout, HeadChange, HeadEvent, HeadRecord, MergeAnalysis, MergeAnalyzer,
+    MergeOutcome, MergeRecord, MergeResolution, MergeStrategy, RepoSnapshot, BranchRepair, DanglingHead, RepairStrategy,
+};
+pub use catalog::{CatalogError, FileCatalog, ALGEBRAIC_SCHEMA_METADATA_KEY, BASE_CHUNKS_METADATA_KEY, LegalHold, LockOwner, PendingCommit, Pin, PruneRecord, RedactedVersion, RedactionRecord, RetentionPolicy, SchemaChange, SchemaField, SchemaMigration, SchemaRegistry, SquashRecord, TableSchema, TableVersion, Tag, TagManager, View, SCHEMA_MIGRATION_METADATA_KEY};
+pub use changelog::{
+    CdcEnvelope, CdcError, CdcExportReport, CdcExporter, CdcOp, CdcRecord, CdcSink, ChangelogCursor, ChangelogEntry,
+    ChangelogQuery, ChangelogReplay, JsonLinesSink, ReplayError, ReplayReport, TableChange,
+};
+pub use chunk_store::{
+    ChunkBackend, ChunkCompression, ChunkMmap, ChunkPipeline, ChunkReader, ChunkStage, ChunkStat, ChunkStore, ChunkTags, Chunker,
+    ChunkCondition, ChunkJournal, ChunkStoreConfig, ChunkStoreError, CompressionStage, DeltaInfo, JournalEntry, MemoryCacheStats,
+};
+#[cfg(feature = "s3")]
+pub use chunk_store::{S3Backend, S3Config, S3Credentials};
+pub use data_merge::{DataMergeError, DataMerger, MergeSimulation, TableMerge};
+pub use durability::DurabilityLevel;
+pub use error::{ErrorCategory, ErrorContext, ErrorInfo};
+pub use filter::{FilterError, FilterReport, RepoFilter, RepoRef};
+pub use fixtures::{FixtureBuilder, FixtureError, FixtureReport};
+pub use gc::{ChunkGc, GcError, GcPlan, GcPlanner, GcReport, PlannedDeletion, PruneReport, VersionPruner};
+pub use keys::{
+    CachedKeyProvider, EnvKeyProvider, FileKeyProvider, KeyError, KeyMaterial, KeyProvider,
 };
-pub use catalog::{CatalogError, FileCatalog, TableVersion};
-pub use changelog::{ChangelogEntry, ChangelogQuery, TableChange};
-pub use chunk_store::{ChunkMmap, ChunkStore, ChunkStoreError};
 pub use merkle::{
     build_tree, diff_trees, verify_tree, DataChunk, MerkleConfig, MerkleDiff, MerkleError,
     MerkleNode, MerkleTree,
 };
+#[cfg(feature = "async")]
+pub use nonblocking::{AsyncChunkStore, AsyncFileCatalog, AsyncTransactionManager};
 pub use parquet::{
     FilterOp, ParquetCompression, ParquetDecoder, ParquetEncoder, ParquetError, PredicateFilter,
     ScalarValue,
 };
+pub use query::{Query, QueryEngine, QueryError, QueryResult};
+#[cfg(feature = "json-schema")]
+pub use record_schema::{record_schemas, write_schemas, RECORD_SCHEMAS};
+pub use refspec::{RefError, RefResolver, RefSpec, ResolvedRef};
+pub use repo::{Repo, RepoError};
+pub use scrub::{
+    BackgroundScrubConfig, BackgroundScrubStats, BackgroundScrubber, ChunkScrubber, CorruptionSink, DamagedChunk,
+    IncrementalScrubber, ScrubError, ScrubReport, SliceReport,
+};
+pub use sql::{rewrite_time_travel, SqlError};
+#[cfg(feature = "datafusion")]
+pub use sql::{RhizoTable, SqlSession};
+pub use table::{
+    schema_hash, ExportFormat, ExportManifest, ExportedTable, ImportOptions, Redactor, RowPatch, TableError, TableExporter,
+    TableReader, TableWriter,
+};
 pub use transaction::{
-    Conflict, ConflictDetector, EpochConfig, EpochId, EpochMetadata, EpochStatus, RecoveryManager,
-    RecoveryReport, TableLevelConflictDetector, TableWrite, TransactionError, TransactionLog,
-    TransactionManager, TransactionRecord, TransactionStatus, TxId, WriteGranularity,
+    CompactionReport, Conflict, ConflictDetector, ConflictGranularity, ConflictWindow, EpochConfig, EpochId, EpochMetadata, EpochStatus, OnConflict, ReadSnapshot,
+    PartitionLevelConflictDetector, RecoveryManager, RecoveryReport, RowLevelConflictDetector, ShutdownReport, SnapshotTable,
+    TableLevelConflictDetector, TableWrite, TransactionError, TransactionIndex, TransactionLog, TxIndexEntry, LogSegment,
+    TransactionManager, TransactionRecord, TransactionStatus, TxId, WriteGranularity, WriteKind, Savepoint,
+    CommitPriority, CommitQueueStats, CommitScheduler, PriorityQueueStats, SharedSnapshot,
+    CommitMetricsSink, CommitPhase, CommitTimings, PinnedSnapshot, ReadOnlyTransaction, HookEvent, HookId, TransactionHook, RetryPolicy,
+    BatchAck, BatchTicket, BatchedWrite, MicroBatchConfig, MicroBatchStats, MicroBatcher,
     // Coordination-free mode (Phase 5)
     TransactionMode, CoordinationFreeConfig, CoordinationFreeError, CoordinationFreeManager,
 };
 
 pub use distributed::{
-    AlgebraicOperation, AlgebraicTransaction, CausalOrder, LocalCommitError, LocalCommitProtocol,
-    NodeId, VectorClock, VersionedUpdate,
+    AlgebraicOperation, AlgebraicTransaction, CausalClock, CausalOrder, HlcClock, LocalCommitError,
+    LocalCommitProtocol, NodeId, VectorClock, VersionedUpdate,
     // Simulation types (Phase 4)
     Message, NetworkCondition, SimulatedCluster, SimulatedNode, SimulationBuilder,
     SimulationConfig, SimulationStats,
+    // Gossip networking
+    GossipConfig, GossipError, GossipNode, GossipRound, GossipStats, SyncReport,
+    NodeStateError, NodeStateStore,
 };
@@ -1,7 +1,10 @@
 use std::io;
 use thiserror::Error;
 
+use crate::error::{ErrorCategory, ErrorContext, ErrorInfo};
+
 #[derive(Error, Debug)]
+#[non_exhaustive]
 pub enum MerkleError {
     #[error("IO error: {0}")]
     Io(#[from] io::Error),
@@ -27,3 +30,37 @@ pub enum MerkleError {
     #[error("Chunk store error: {0}")]
     ChunkStore(String),
 }
+
+impl ErrorInfo for MerkleError {
+    fn code(&self) -> &'static str {
+        match self {
+            MerkleError::Io(_) => "MERKLE_IO",
+            MerkleError::ChunkNotFound(_) => "MERKLE_CHUNK_NOT_FOUND",
+            MerkleError::InvalidChunkSize(_) => "MERKLE_INVALID_CHUNK_SIZE",
+            MerkleError::EmptyData => "MERKLE_EMPTY_DATA",
+            MerkleError::IntegrityError { .. } => "MERKLE_INTEGRITY",
+            MerkleError::TreeCorruption(_) => "MERKLE_TREE_CORRUPTION",
+            MerkleError::Serialization(_) => "MERKLE_SERIALIZATION",
+            MerkleError::ChunkStore(_) => "MERKLE_CHUNK_STORE",
+        }
+    }
+
+    fn category(&self) -> ErrorCategory {
+        match self {
+            MerkleError::Io(_) | MerkleError::ChunkStore(_) => ErrorCategory::Io,
+            MerkleError::ChunkNotFound(_) => ErrorCategory::NotFound,
+            MerkleError::InvalidChunkSize(_) | MerkleError::EmptyData => ErrorCategory::InvalidArgument,
+            MerkleError::IntegrityError { .. } | MerkleError::TreeCorruption(_) => ErrorCategory::DataIntegrity,
+            MerkleError::Serialization(_) => ErrorCategory::Internal,
+        }
+    }
+
+    fn context(&self) -> ErrorContext {
+        match self {
+            MerkleError::ChunkNotFound(hash) | MerkleError::IntegrityError { expected: hash, .. } => {
+                ErrorContext::new().with_chunk(hash.clone())
+            }
+            _ => ErrorContext::new(),
+        }
+    }
+}
@@ -0,0 +1,105 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use super::unblock;
+use crate::branch::Branch;
+use crate::catalog::{CatalogError, FileCatalog, TableVersion};
+
+/// [`FileCatalog`] calls run on the blocking pool
+#[derive(Clone)]
+pub struct AsyncFileCatalog {
+    inner: Arc<FileCatalog>,
+}
+
+impl AsyncFileCatalog {
+    pub fn new(catalog: Arc<FileCatalog>) -> Self {
+        Self { inner: catalog }
+    }
+
+    /// The wrapped catalog
+    pub fn inner(&self) -> &Arc<FileCatalog> {
+        &self.inner
+    }
+
+    /// Run `f` against the catalog on the blocking pool
+    pub async fn run<T, F>(&self, f: F) -> T
+    where
+        T: Send + 'static,
+        F: FnOnce(&FileCatalog) -> T + Send + 'static,
+    {
+        let catalog = Arc::clone(&self.inner);
+        unblock(move || f(&catalog)).await
+    }
+
+    pub async fn commit(&self, version: TableVersion) -> Result<u64, CatalogError> {
+        self.run(move |catalog| catalog.commit(version)).await
+    }
+
+    /// [`FileCatalog::commit_next_version_with_meta`]
+    pub async fn commit_next_version(
+        &self,
+        table_name: &str,
+        chunk_hashes: Vec<String>,
+        metadata: HashMap<String, String>,
+        schema_hash: Option<String>,
+    ) -> Result<u64, CatalogError> {
+        let table_name = table_name.to_string();
+        self.run(move |catalog| {
+            catalog.commit_next_version_with_meta(&table_name, chunk_hashes, metadata, schema_hash)
+        })
+        .await
+    }
+
+    pub async fn get_version(&self, table_name: &str, version: Option<u64>) -> Result<TableVersion, CatalogError> {
+        let table_name = table_name.to_string();
+        self.run(move |catalog| catalog.get_version(&table_name, version)).await
+    }
+
+    pub async fn list_versions(&self, table_name: &str) -> Result<Vec<u64>, CatalogError> {
+        let table_name = table_name.to_string();
+        self.run(move |catalog| catalog.list_versions(&table_name)).await
+    }
+
+    pub async fn list_tables(&self) -> Result<Vec<String>, CatalogError> {
+        self.run(|catalog| catalog.list_tables()).await
+    }
+
+    pub async fn list_tables_on_branch(&self, branch: Branch) -> Result<Vec<String>, CatalogError> {
+        self.run(move |catalog| catalog.list_tables_on_branch(&branch)).await
+    }
+
+    pub async fn table_exists(&self, table_name: &str) -> Result<bool, CatalogError> {
+        let table_name = table_name.to_string();
+        self.run(move |catalog| catalog.table_exists(&table_name)).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::*;
+    use crate::nonblocking::block_on;
+
+    #[test]
+    fn test_async_catalog_commits_and_lists() {
+        let dir = TempDir::new().unwrap();
+        let catalog = AsyncFileCatalog::new(Arc::new(FileCatalog::new(dir.path()).unwrap()));
+
+        block_on(async {
+            catalog.commit(TableVersion::new("users", 1, vec!["a".repeat(64)])).await.unwrap();
+            let meta = HashMap::from([("owner".to_string(), "ops".to_string())]);
+            let next = catalog.commit_next_version("users", vec![], meta, None).await.unwrap();
+            assert_eq!(next, 2);
+
+            assert_eq!(catalog.list_versions("users").await.unwrap(), vec![1, 2]);
+            assert_eq!(catalog.list_tables().await.unwrap(), vec!["users"]);
+            assert_eq!(catalog.get_version("users", None).await.unwrap().metadata["owner"], "ops");
+            assert!(!catalog.table_exists("orders").await.unwrap());
+            assert!(matches!(
+                catalog.get_version("orders", None).await,
+                Err(CatalogError::TableNotFound(_))
+            ));
+        });
+    }
+}
@@ -0,0 +1,43 @@
+//! Async facades over the storage APIs, for servers on a tokio runtime.
+//!
+//! The core stores do synchronous file IO. [`AsyncChunkStore`],
+//! [`AsyncFileCatalog`] and [`AsyncTransactionManager`] share the same
+//! `Arc`ed instances as synchronous callers and run each call on tokio's
+//! blocking pool, so awaiting them never stalls an executor thread. Each
+//! also has `run` for calls the facade does not wrap. Requires the `async`
+//! feature and must be awaited inside a tokio runtime.
+//!
+//! A panic in a blocking call resumes on the awaiting task, as it would
+//! have had the call been made directly.
+
+mod catalog;
+mod store;
+mod transaction;
+
+pub use catalog::AsyncFileCatalog;
+pub use store::AsyncChunkStore;
+pub use transaction::AsyncTransactionManager;
+
+/// Run `f` on the blocking pool and wait for it
+async fn unblock<T, F>(f: F) -> T
+where
+    T: Send + 'static,
+    F: FnOnce() -> T + Send + 'static,
+{
+    match tokio::task::spawn_blocking(f).await {
+        Ok(value) => value,
+        Err(err) => match err.try_into_panic() {
+            Ok(payload) => std::panic::resume_unwind(payload),
+            // Only when the runtime shuts down while the call is queued
+            Err(err) => panic!("blocking storage call cancelled: {}", err),
+        },
+    }
+}
+
+#[cfg(test)]
+fn block_on<F: std::future::Future>(future: F) -> F::Output {
+    tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap()
+        .block_on(future)
+}
@@ -0,0 +1,121 @@
+use std::sync::Arc;
+
+use super::unblock;
+use crate::chunk_store::{ChunkStat, ChunkStore, ChunkStoreError};
+
+/// [`ChunkStore`] calls run on the blocking pool
+#[derive(Clone)]
+pub struct AsyncChunkStore {
+    inner: Arc<ChunkStore>,
+}
+
+impl AsyncChunkStore {
+    pub fn new(store: Arc<ChunkStore>) -> Self {
+        Self { inner: store }
+    }
+
+    /// The wrapped store, for calls that never touch disk
+    pub fn inner(&self) -> &Arc<ChunkStore> {
+        &self.inner
+    }
+
+    /// Run `f` against the store on the blocking pool
+    pub async fn run<T, F>(&self, f: F) -> T
+    where
+        T: Send + 'static,
+        F: FnOnce(&ChunkStore) -> T + Send + 'static,
+    {
+        let store = Arc::clone(&self.inner);
+        unblock(move || f(&store)).await
+    }
+
+    pub async fn put(&self, data: Vec<u8>) -> Result<String, ChunkStoreError> {
+        self.run(move |store| store.put(&data)).await
+    }
+
+    pub async fn get(&self, hash: &str) -> Result<Vec<u8>, ChunkStoreError> {
+        let hash = hash.to_string();
+        self.run(move |store| store.get(&hash)).await
+    }
+
+    pub async fn get_verified(&self, hash: &str) -> Result<Vec<u8>, ChunkStoreError> {
+        let hash = hash.to_string();
+        self.run(move |store| store.get_verified(&hash)).await
+    }
+
+    pub async fn exists(&self, hash: &str) -> Result<bool, ChunkStoreError> {
+        let hash = hash.to_string();
+        self.run(move |store| store.exists(&hash)).await
+    }
+
+    pub async fn stat(&self, hash: &str) -> Result<ChunkStat, ChunkStoreError> {
+        let hash = hash.to_string();
+        self.run(move |store| store.stat(&hash)).await
+    }
+
+    pub async fn delete(&self, hash: &str) -> Result<(), ChunkStoreError> {
+        let hash = hash.to_string();
+        self.run(move |store| store.delete(&hash)).await
+    }
+
+    /// [`ChunkStore::put_many`], returning hashes in input order
+    pub async fn put_many(&self, chunks: Vec<Vec<u8>>) -> Result<Vec<String>, ChunkStoreError> {
+        self.run(move |store| {
+            let chunks: Vec<&[u8]> = chunks.iter().map(Vec::as_slice).collect();
+            store.put_many(&chunks)
+        })
+        .await
+    }
+
+    /// [`ChunkStore::get_many`], returning chunks in input order
+    pub async fn get_many(&self, hashes: Vec<String>) -> Result<Vec<Vec<u8>>, ChunkStoreError> {
+        self.run(move |store| {
+            let hashes: Vec<&str> = hashes.iter().map(String::as_str).collect();
+            store.get_many(&hashes)
+        })
+        .await
+    }
+
+    pub async fn exists_many(&self, hashes: Vec<String>) -> Result<Vec<bool>, ChunkStoreError> {
+        self.run(move |store| {
+            let hashes: Vec<&str> = hashes.iter().map(String::as_str).collect();
+            store.exists_many(&hashes)
+        })
+        .await
+    }
+
+    pub async fn list_chunk_hashes(&self) -> Result<Vec<String>, ChunkStoreError> {
+        self.run(|store| store.list_chunk_hashes()).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::*;
+    use crate::nonblocking::block_on;
+
+    #[test]
+    fn test_async_store_round_trip() {
+        let dir = TempDir::new().unwrap();
+        let store = Arc::new(ChunkStore::new(dir.path()).unwrap());
+        let chunks = AsyncChunkStore::new(Arc::clone(&store));
+
+        block_on(async {
+            let hash = chunks.put(b"hello".to_vec()).await.unwrap();
+            assert_eq!(chunks.get_verified(&hash).await.unwrap(), b"hello");
+            // Same store as synchronous callers
+            assert!(store.exists(&hash).unwrap());
+
+            let hashes = chunks.put_many(vec![b"a".to_vec(), b"b".to_vec()]).await.unwrap();
+            assert_eq!(chunks.get_many(hashes.clone()).await.unwrap(), vec![b"a".to_vec(), b"b".to_vec()]);
+
+            chunks.delete(&hash).await.unwrap();
+            let mut probe = hashes;
+            probe.push(hash);
+            assert_eq!(chunks.exists_many(probe).await.unwrap(), vec![true, true, false]);
+            assert!(matches!(chunks.get(&"0".repeat(64)).await, Err(ChunkStoreError::NotFound(_))));
+        });
+    }
+}
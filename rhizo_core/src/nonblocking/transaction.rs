@@ -0,0 +1,103 @@
+use std::sync::Arc;
+
+use super::unblock;
+use crate::changelog::{ChangelogEntry, ChangelogQuery};
+use crate::transaction::{OnConflict, TableWrite, TransactionError, TransactionManager, TransactionRecord, TxId};
+
+/// [`TransactionManager`] calls run on the blocking pool.
+///
+/// Transactions begun here are ordinary transactions of the wrapped
+/// manager, so they conflict-check against synchronous callers too.
+#[derive(Clone)]
+pub struct AsyncTransactionManager {
+    inner: Arc<TransactionManager>,
+}
+
+impl AsyncTransactionManager {
+    pub fn new(manager: Arc<TransactionManager>) -> Self {
+        Self { inner: manager }
+    }
+
+    /// The wrapped manager
+    pub fn inner(&self) -> &Arc<TransactionManager> {
+        &self.inner
+    }
+
+    /// Run `f` against the manager on the blocking pool
+    pub async fn run<T, F>(&self, f: F) -> T
+    where
+        T: Send + 'static,
+        F: FnOnce(&TransactionManager) -> T + Send + 'static,
+    {
+        let manager = Arc::clone(&self.inner);
+        unblock(move || f(&manager)).await
+    }
+
+    pub async fn begin(&self, branch: Option<&str>) -> Result<TxId, TransactionError> {
+        let branch = branch.map(str::to_string);
+        self.run(move |manager| manager.begin(branch.as_deref())).await
+    }
+
+    pub async fn add_write(&self, tx_id: TxId, write: TableWrite) -> Result<(), TransactionError> {
+        self.run(move |manager| manager.add_write(tx_id, write)).await
+    }
+
+    pub async fn commit(&self, tx_id: TxId) -> Result<(), TransactionError> {
+        self.commit_with(tx_id, OnConflict::Abort).await
+    }
+
+    pub async fn commit_with(&self, tx_id: TxId, on_conflict: OnConflict) -> Result<(), TransactionError> {
+        self.run(move |manager| manager.commit_with(tx_id, on_conflict)).await
+    }
+
+    pub async fn abort(&self, tx_id: TxId, reason: &str) -> Result<(), TransactionError> {
+        let reason = reason.to_string();
+        self.run(move |manager| manager.abort(tx_id, &reason)).await
+    }
+
+    pub async fn get_transaction(&self, tx_id: TxId) -> Result<TransactionRecord, TransactionError> {
+        self.run(move |manager| manager.get_transaction(tx_id)).await
+    }
+
+    pub async fn get_changelog(&self, query: ChangelogQuery) -> Result<Vec<ChangelogEntry>, TransactionError> {
+        self.run(move |manager| manager.get_changelog(query)).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::*;
+    use crate::catalog::FileCatalog;
+    use crate::nonblocking::{block_on, AsyncFileCatalog};
+    use crate::transaction::TransactionStatus;
+
+    #[test]
+    fn test_async_transaction_commits_to_catalog_and_changelog() {
+        let dir = TempDir::new().unwrap();
+        let catalog = Arc::new(FileCatalog::new(dir.path().join("catalog")).unwrap());
+        let manager = TransactionManager::new(dir.path().join("tx"), Arc::clone(&catalog), None).unwrap();
+        let transactions = AsyncTransactionManager::new(Arc::new(manager));
+        let catalog = AsyncFileCatalog::new(catalog);
+
+        block_on(async {
+            let tx_id = transactions.begin(None).await.unwrap();
+            transactions
+                .add_write(tx_id, TableWrite::new("users", 1, vec!["a".repeat(64)]))
+                .await
+                .unwrap();
+            transactions.commit(tx_id).await.unwrap();
+            assert_eq!(catalog.list_versions("users").await.unwrap(), vec![1]);
+
+            let changelog = transactions.get_changelog(ChangelogQuery::new()).await.unwrap();
+            assert_eq!(changelog.len(), 1);
+            assert_eq!(changelog[0].tx_id, tx_id);
+
+            let aborted = transactions.begin(None).await.unwrap();
+            transactions.abort(aborted, "changed my mind").await.unwrap();
+            let record = transactions.get_transaction(aborted).await.unwrap();
+            assert!(matches!(record.status, TransactionStatus::Aborted { .. }));
+        });
+    }
+}
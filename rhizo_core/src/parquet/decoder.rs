@@ -562,7 +562,7 @@ fn can_prune_row_group(
 }
 
 /// Apply multiple filters to a record batch, returning a boolean mask.
-fn apply_filters(
+pub(crate) fn apply_filters(
     batch: &RecordBatch,
     filters: &[PredicateFilter],
     schema: &arrow::datatypes::SchemaRef,
@@ -2,8 +2,11 @@
 
 use thiserror::Error;
 
+use crate::error::{ErrorCategory, ErrorInfo};
+
 /// Errors that can occur during Parquet operations.
 #[derive(Error, Debug)]
+#[non_exhaustive]
 pub enum ParquetError {
     /// Arrow error during conversion
     #[error("Arrow error: {0}")]
@@ -42,3 +45,25 @@ pub enum ParquetError {
     #[error("Row count overflow: total rows exceed usize::MAX")]
     RowCountOverflow,
 }
+
+impl ErrorInfo for ParquetError {
+    fn code(&self) -> &'static str {
+        match self {
+            ParquetError::Arrow(_) => "PARQUET_ARROW",
+            ParquetError::Parquet(_) => "PARQUET_FORMAT",
+            ParquetError::EmptyData => "PARQUET_EMPTY_DATA",
+            ParquetError::InvalidCompression(_) => "PARQUET_INVALID_COMPRESSION",
+            ParquetError::InvalidColumn(_) => "PARQUET_INVALID_COLUMN",
+            ParquetError::FileTooLarge { .. } => "PARQUET_FILE_TOO_LARGE",
+            ParquetError::InvalidRowCount(_) => "PARQUET_INVALID_ROW_COUNT",
+            ParquetError::RowCountOverflow => "PARQUET_ROW_COUNT_OVERFLOW",
+        }
+    }
+
+    fn category(&self) -> ErrorCategory {
+        match self {
+            ParquetError::Parquet(_) | ParquetError::InvalidRowCount(_) => ErrorCategory::DataIntegrity,
+            _ => ErrorCategory::InvalidArgument,
+        }
+    }
+}
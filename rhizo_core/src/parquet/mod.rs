@@ -43,6 +43,7 @@ mod error;
 mod filter;
 
 pub use decoder::ParquetDecoder;
+pub(crate) use decoder::apply_filters;
 pub use encoder::{ParquetEncoder, ParquetCompression};
 pub use error::ParquetError;
 pub use filter::{FilterOp, ScalarValue, PredicateFilter};
@@ -0,0 +1,376 @@
+//! Resolving and reading one table at a point in its history.
+
+use std::sync::Arc;
+
+use arrow::compute::filter_record_batch;
+use arrow::datatypes::{Schema, SchemaRef};
+use arrow::record_batch::RecordBatch;
+use rayon::prelude::*;
+
+use super::error::QueryError;
+use crate::branch::{BranchError, BranchManager};
+use crate::catalog::{FileCatalog, TableVersion};
+use crate::chunk_store::ChunkStore;
+use crate::parquet::{apply_filters, PredicateFilter};
+use crate::refspec::{RefResolver, RefSpec};
+use crate::table::TableReader;
+
+/// What to read from one table.
+///
+/// The version read is, in order of precedence: the version at `at` or in
+/// `pin`; the explicit `version` (which must be visible on `branch`, if
+/// given); the head of `branch`; the table's latest version. A ref or pin
+/// cannot be combined with any other read point.
+#[derive(Debug, Clone)]
+pub struct Query {
+    pub table: String,
+    pub version: Option<u64>,
+    pub branch: Option<String>,
+    pub pin: Option<String>,
+
+    /// Point in history to read at (`main~2`, `tag:release-1`, ...)
+    pub at: Option<RefSpec>,
+
+    /// Columns to return, in this order (`None` = all)
+    pub columns: Option<Vec<String>>,
+
+    /// Row filters, combined with AND
+    pub filters: Vec<PredicateFilter>,
+}
+
+impl Query {
+    pub fn new(table: impl Into<String>) -> Self {
+        Self {
+            table: table.into(),
+            version: None,
+            branch: None,
+            pin: None,
+            at: None,
+            columns: None,
+            filters: Vec::new(),
+        }
+    }
+
+    pub fn at_version(mut self, version: u64) -> Self {
+        self.version = Some(version);
+        self
+    }
+
+    pub fn on_branch(mut self, branch: impl Into<String>) -> Self {
+        self.branch = Some(branch.into());
+        self
+    }
+
+    pub fn at_pin(mut self, pin: impl Into<String>) -> Self {
+        self.pin = Some(pin.into());
+        self
+    }
+
+    pub fn at_ref(mut self, at: RefSpec) -> Self {
+        self.at = Some(at);
+        self
+    }
+
+    pub fn select<I, S>(mut self, columns: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.columns = Some(columns.into_iter().map(Into::into).collect());
+        self
+    }
+
+    pub fn filter(mut self, filter: PredicateFilter) -> Self {
+        self.filters.push(filter);
+        self
+    }
+}
+
+/// Rows of a table version that matched a [`Query`]
+#[derive(Debug, Clone)]
+pub struct QueryResult {
+    /// The version that was read
+    pub version: TableVersion,
+
+    /// Schema of `batches`, after projection
+    pub schema: SchemaRef,
+
+    /// Matching rows in table order; at least one (possibly empty) batch
+    pub batches: Vec<RecordBatch>,
+
+    /// Rows read before filtering
+    pub rows_scanned: usize,
+}
+
+impl QueryResult {
+    /// Rows returned after filtering
+    pub fn num_rows(&self) -> usize {
+        self.batches.iter().map(RecordBatch::num_rows).sum()
+    }
+}
+
+/// Reads tables at a branch head, pin or version with projection and
+/// predicate filtering.
+///
+/// # Example
+///
+/// ```ignore
+/// let result = QueryEngine::new(&store, &catalog)
+///     .with_branches(&branches)
+///     .query(&Query::new("users")
+///         .on_branch("dev")
+///         .select(["id", "name"])
+///         .filter(PredicateFilter::new("age", FilterOp::Gt, ScalarValue::Int64(21))))?;
+/// ```
+pub struct QueryEngine<'a> {
+    store: &'a ChunkStore,
+    catalog: &'a FileCatalog,
+    branches: Option<&'a BranchManager>,
+}
+
+impl<'a> QueryEngine<'a> {
+    pub fn new(store: &'a ChunkStore, catalog: &'a FileCatalog) -> Self {
+        Self {
+            store,
+            catalog,
+            branches: None,
+        }
+    }
+
+    /// Resolve branch reads against `branches` (required for `on_branch`).
+    pub fn with_branches(mut self, branches: &'a BranchManager) -> Self {
+        self.branches = Some(branches);
+        self
+    }
+
+    /// The catalog record a query reads, without loading any chunks
+    pub fn resolve(&self, query: &Query) -> Result<TableVersion, QueryError> {
+        let table = &query.table;
+        if let Some(at) = &query.at {
+            if query.pin.is_some() || query.branch.is_some() || query.version.is_some() {
+                return Err(QueryError::ConflictingReadPoints(table.clone()));
+            }
+            let mut resolver = RefResolver::new(self.catalog);
+            if let Some(branches) = self.branches {
+                resolver = resolver.with_branches(branches);
+            }
+            return Ok(resolver.table_version(at, table)?);
+        }
+        match (&query.pin, &query.branch) {
+            (Some(_), _) if query.version.is_some() => Err(QueryError::ConflictingReadPoints(table.clone())),
+            (Some(_), Some(_)) => Err(QueryError::ConflictingReadPoints(table.clone())),
+            (Some(pin), None) => {
+                let version = self.catalog.resolve_pin(pin)?.get(table).ok_or_else(|| QueryError::NotPinned {
+                    table: table.clone(),
+                    pin: pin.clone(),
+                })?;
+                Ok(self.catalog.get_version(table, Some(version))?)
+            }
+            (None, Some(branch)) => {
+                let branches = self.branches.ok_or_else(|| QueryError::NoBranchManager {
+                    table: table.clone(),
+                    branch: branch.clone(),
+                })?;
+                if query.version.is_some() {
+                    let branch = branches.get(branch)?;
+                    return Ok(self.catalog.get_version_on_branch(table, query.version, &branch)?);
+                }
+                match branches.resolve_read(self.catalog, branch, table) {
+                    Err(BranchError::Catalog(e)) => Err(e.into()),
+                    result => Ok(result?),
+                }
+            }
+            (None, None) => Ok(self.catalog.get_version(table, query.version)?),
+        }
+    }
+
+    /// Read the rows of a table version that match a query.
+    ///
+    /// # Errors
+    /// - `UnknownColumn` if a selected or filtered column does not exist.
+    /// - `InvalidFilter` if a filter value does not fit its column's type.
+    pub fn query(&self, query: &Query) -> Result<QueryResult, QueryError> {
+        let version = self.resolve(query)?;
+        let batches = TableReader::new(self.store, self.catalog).read_version(&version)?;
+        let schema = batches
+            .first()
+            .map(RecordBatch::schema)
+            .unwrap_or_else(|| Arc::new(Schema::empty()));
+
+        let unknown = |column: &str| QueryError::UnknownColumn {
+            table: query.table.clone(),
+            column: column.to_string(),
+        };
+        for filter in &query.filters {
+            schema.index_of(&filter.column).map_err(|_| unknown(&filter.column))?;
+        }
+        let projection = match &query.columns {
+            Some(columns) => Some(
+                columns
+                    .iter()
+                    .map(|c| schema.index_of(c).map_err(|_| unknown(c)))
+                    .collect::<Result<Vec<_>, _>>()?,
+            ),
+            None => None,
+        };
+        let out_schema = match &projection {
+            Some(indices) => Arc::new(schema.project(indices)?),
+            None => schema,
+        };
+
+        let rows_scanned = batches.iter().map(RecordBatch::num_rows).sum();
+        let mut matched: Vec<RecordBatch> = batches
+            .into_par_iter()
+            .map(|batch| self.apply(query, batch, projection.as_deref()))
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
+            .filter(|batch| batch.num_rows() > 0)
+            .collect();
+        if matched.is_empty() {
+            matched.push(RecordBatch::new_empty(Arc::clone(&out_schema)));
+        }
+
+        Ok(QueryResult {
+            version,
+            schema: out_schema,
+            batches: matched,
+            rows_scanned,
+        })
+    }
+
+    /// Filter, then project, one batch
+    fn apply(
+        &self,
+        query: &Query,
+        batch: RecordBatch,
+        projection: Option<&[usize]>,
+    ) -> Result<RecordBatch, QueryError> {
+        let batch = if query.filters.is_empty() {
+            batch
+        } else {
+            let mask = apply_filters(&batch, &query.filters, &batch.schema()).map_err(|e| {
+                QueryError::InvalidFilter {
+                    table: query.table.clone(),
+                    reason: e.to_string(),
+                }
+            })?;
+            filter_record_batch(&batch, &mask)?
+        };
+        match projection {
+            Some(indices) => Ok(batch.project(indices)?),
+            None => Ok(batch),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use arrow::array::{Int64Array, StringArray};
+    use tempfile::TempDir;
+
+    use super::*;
+    use crate::parquet::{FilterOp, ScalarValue};
+    use crate::table::TableWriter;
+
+    fn batch(ids: Vec<i64>) -> RecordBatch {
+        let names: Vec<String> = ids.iter().map(|i| format!("user-{}", i)).collect();
+        RecordBatch::try_from_iter(vec![
+            ("id", Arc::new(Int64Array::from(ids)) as _),
+            ("name", Arc::new(StringArray::from(names)) as _),
+        ])
+        .unwrap()
+    }
+
+    fn ids(result: &QueryResult) -> Vec<i64> {
+        result
+            .batches
+            .iter()
+            .flat_map(|b| b.column_by_name("id").unwrap().as_any().downcast_ref::<Int64Array>().unwrap().values().to_vec())
+            .collect()
+    }
+
+    fn setup() -> (TempDir, ChunkStore, FileCatalog, BranchManager) {
+        let dir = TempDir::new().unwrap();
+        let store = ChunkStore::new(dir.path().join("chunks")).unwrap();
+        let catalog = FileCatalog::new(dir.path().join("catalog")).unwrap();
+        let branches = BranchManager::new(dir.path().join("branches")).unwrap();
+        let writer = TableWriter::new(&store, &catalog).with_max_rows_per_chunk(2);
+        writer.write("users", &[batch(vec![1, 2, 3])]).unwrap();
+        writer.write("users", &[batch(vec![1, 2, 3, 4, 5])]).unwrap();
+        (dir, store, catalog, branches)
+    }
+
+    #[test]
+    fn test_projection_and_filters() {
+        let (_dir, store, catalog, _branches) = setup();
+        let engine = QueryEngine::new(&store, &catalog);
+
+        let result = engine
+            .query(
+                &Query::new("users")
+                    .select(["name", "id"])
+                    .filter(PredicateFilter::new("id", FilterOp::Gt, ScalarValue::Int64(1)))
+                    .filter(PredicateFilter::new("id", FilterOp::Ne, ScalarValue::Int64(4))),
+            )
+            .unwrap();
+        assert_eq!(result.version.version, 2);
+        assert_eq!(ids(&result), vec![2, 3, 5]);
+        assert_eq!((result.rows_scanned, result.num_rows()), (5, 3));
+        assert_eq!(result.schema.field(0).name(), "name");
+
+        // No match keeps the schema
+        let none = engine
+            .query(&Query::new("users").filter(PredicateFilter::new("id", FilterOp::Gt, ScalarValue::Int64(99))))
+            .unwrap();
+        assert_eq!(none.num_rows(), 0);
+        assert_eq!(none.batches[0].num_columns(), 2);
+
+        assert!(matches!(
+            engine.query(&Query::new("users").select(["missing"])),
+            Err(QueryError::UnknownColumn { .. })
+        ));
+        assert!(matches!(
+            engine.query(&Query::new("users").filter(PredicateFilter::new(
+                "id",
+                FilterOp::Eq,
+                ScalarValue::Utf8("1".to_string())
+            ))),
+            Err(QueryError::InvalidFilter { .. })
+        ));
+    }
+
+    #[test]
+    fn test_resolves_versions_branches_and_pins() {
+        let (_dir, store, catalog, branches) = setup();
+        branches.update_head("main", "users", 1).unwrap();
+        catalog.pin("run-1", HashMap::from([("users".to_string(), 1)]), None).unwrap();
+
+        let engine = QueryEngine::new(&store, &catalog);
+        assert_eq!(ids(&engine.query(&Query::new("users").at_version(1)).unwrap()), vec![1, 2, 3]);
+        assert_eq!(ids(&engine.query(&Query::new("users").at_pin("run-1")).unwrap()), vec![1, 2, 3]);
+        assert!(matches!(
+            engine.query(&Query::new("users").on_branch("main")),
+            Err(QueryError::NoBranchManager { .. })
+        ));
+        assert!(matches!(
+            engine.resolve(&Query::new("users").at_pin("run-1").at_version(2)),
+            Err(QueryError::ConflictingReadPoints(_))
+        ));
+
+        let engine = engine.with_branches(&branches);
+        assert_eq!(engine.resolve(&Query::new("users").on_branch("main")).unwrap().version, 1);
+        assert_eq!(engine.resolve(&Query::new("users").on_branch("main").at_version(2)).unwrap().version, 2);
+        assert_eq!(engine.resolve(&Query::new("users")).unwrap().version, 2);
+
+        let at = |r: &str| Query::new("users").at_ref(r.parse().unwrap());
+        assert_eq!(ids(&engine.query(&at("main")).unwrap()), vec![1, 2, 3]);
+        assert_eq!(engine.resolve(&at("users@v2")).unwrap().version, 2);
+        assert!(matches!(
+            engine.resolve(&at("main").on_branch("main")),
+            Err(QueryError::ConflictingReadPoints(_))
+        ));
+        assert!(matches!(engine.resolve(&at("tx:1")), Err(QueryError::Ref(_))));
+    }
+}
@@ -0,0 +1,101 @@
+use std::time::Duration;
+
+use thiserror::Error;
+
+use crate::branch::BranchError;
+use crate::catalog::CatalogError;
+use crate::error::{ErrorCategory, ErrorContext, ErrorInfo};
+use crate::refspec::RefError;
+use crate::table::TableError;
+
+#[derive(Error, Debug)]
+#[non_exhaustive]
+pub enum QueryError {
+    #[error("Query of {0} names a pin and a version or branch; use one")]
+    ConflictingReadPoints(String),
+
+    #[error("Reading {table} on branch {branch} requires a branch manager")]
+    NoBranchManager { table: String, branch: String },
+
+    #[error("Table {table} is not in pin {pin}")]
+    NotPinned { table: String, pin: String },
+
+    #[error("Table {table} has no column {column}")]
+    UnknownColumn { table: String, column: String },
+
+    #[error("Invalid filter on {table}: {reason}")]
+    InvalidFilter { table: String, reason: String },
+
+    #[error("Table error: {0}")]
+    Table(#[from] TableError),
+
+    #[error("Catalog error: {0}")]
+    Catalog(#[from] CatalogError),
+
+    #[error("Branch error: {0}")]
+    Branch(#[from] BranchError),
+
+    #[error("Ref error: {0}")]
+    Ref(#[from] RefError),
+
+    #[error("Arrow error: {0}")]
+    Arrow(#[from] arrow::error::ArrowError),
+}
+
+impl ErrorInfo for QueryError {
+    fn code(&self) -> &'static str {
+        match self {
+            QueryError::ConflictingReadPoints(_) => "QUERY_CONFLICTING_READ_POINTS",
+            QueryError::NoBranchManager { .. } => "QUERY_NO_BRANCH_MANAGER",
+            QueryError::NotPinned { .. } => "QUERY_NOT_PINNED",
+            QueryError::UnknownColumn { .. } => "QUERY_UNKNOWN_COLUMN",
+            QueryError::InvalidFilter { .. } => "QUERY_INVALID_FILTER",
+            QueryError::Table(e) => e.code(),
+            QueryError::Catalog(e) => e.code(),
+            QueryError::Branch(e) => e.code(),
+            QueryError::Ref(e) => e.code(),
+            QueryError::Arrow(_) => "QUERY_ARROW",
+        }
+    }
+
+    fn category(&self) -> ErrorCategory {
+        match self {
+            QueryError::ConflictingReadPoints(_)
+            | QueryError::UnknownColumn { .. }
+            | QueryError::InvalidFilter { .. } => ErrorCategory::InvalidArgument,
+            QueryError::NoBranchManager { .. } => ErrorCategory::FailedPrecondition,
+            QueryError::NotPinned { .. } => ErrorCategory::NotFound,
+            QueryError::Table(e) => e.category(),
+            QueryError::Catalog(e) => e.category(),
+            QueryError::Branch(e) => e.category(),
+            QueryError::Ref(e) => e.category(),
+            QueryError::Arrow(_) => ErrorCategory::Internal,
+        }
+    }
+
+    fn context(&self) -> ErrorContext {
+        match self {
+            QueryError::ConflictingReadPoints(table)
+            | QueryError::NotPinned { table, .. }
+            | QueryError::UnknownColumn { table, .. }
+            | QueryError::InvalidFilter { table, .. } => ErrorContext::new().with_table(table),
+            QueryError::NoBranchManager { table, branch } => {
+                ErrorContext::new().with_table(table).with_branch(branch)
+            }
+            QueryError::Table(e) => e.context(),
+            QueryError::Catalog(e) => e.context(),
+            QueryError::Branch(e) => e.context(),
+            QueryError::Ref(e) => e.context(),
+            QueryError::Arrow(_) => ErrorContext::new(),
+        }
+    }
+
+    fn retry_after(&self) -> Option<Duration> {
+        match self {
+            QueryError::Table(e) => e.retry_after(),
+            QueryError::Catalog(e) => e.retry_after(),
+            QueryError::Ref(e) => e.retry_after(),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,15 @@
+//! Snapshot reads of single tables with projection and filtering.
+//!
+//! [`QueryEngine`] resolves which version of a table to read (an explicit
+//! version, a branch head, a pin, or the latest version), loads the
+//! version's chunks through [`TableReader`](crate::table::TableReader) and
+//! applies column projection and [`PredicateFilter`](crate::PredicateFilter)s
+//! to the resulting Arrow batches. SQL over several tables is in
+//! [`sql`](crate::sql) and the Python `QueryEngine`; this is the building
+//! block for reading one table at a consistent point.
+
+pub mod engine;
+pub mod error;
+
+pub use engine::{Query, QueryEngine, QueryResult};
+pub use error::QueryError;
@@ -0,0 +1,178 @@
+//! JSON Schemas for the records Rhizo writes to disk.
+//!
+//! Catalog versions, branches, transaction log records, changelog entries
+//! and pending commit intents are stored as JSON. Their schemas are
+//! generated from the Rust types, so tools in other languages can parse
+//! repository files without depending on this crate. The published copies
+//! live in `schemas/` at the repository root and are checked against the
+//! types by the tests; after changing a record, regenerate them with
+//!
+//! ```text
+//! RHIZO_WRITE_SCHEMAS=1 cargo test -p rhizo_core --features json-schema record_schema
+//! ```
+//!
+//! Fields added to a record after its first release are optional in its
+//! schema. Rhizo ignores properties it does not know, and external readers
+//! should do the same.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use schemars::schema_for;
+
+use crate::branch::Branch;
+use crate::catalog::{PendingCommit, TableVersion};
+use crate::changelog::ChangelogEntry;
+use crate::transaction::TransactionRecord;
+
+/// Names of the published record schemas
+pub const RECORD_SCHEMAS: [&str; 5] = [
+    "branch",
+    "changelog_entry",
+    "pending_commit",
+    "table_version",
+    "transaction_record",
+];
+
+/// Schema of every on-disk record type, keyed by its name in [`RECORD_SCHEMAS`].
+pub fn record_schemas() -> BTreeMap<&'static str, serde_json::Value> {
+    let schemas = [
+        schema_for!(Branch),
+        schema_for!(ChangelogEntry),
+        schema_for!(PendingCommit),
+        schema_for!(TableVersion),
+        schema_for!(TransactionRecord),
+    ];
+    RECORD_SCHEMAS
+        .into_iter()
+        .zip(schemas.map(serde_json::Value::from))
+        .collect()
+}
+
+/// File name a record schema is published under
+pub fn schema_file_name(name: &str) -> String {
+    format!("{}.schema.json", name)
+}
+
+/// Write every record schema into `dir` as pretty-printed JSON.
+///
+/// Returns the paths written, in [`RECORD_SCHEMAS`] order.
+pub fn write_schemas(dir: impl AsRef<Path>) -> io::Result<Vec<PathBuf>> {
+    let dir = dir.as_ref();
+    fs::create_dir_all(dir)?;
+    let mut written = Vec::new();
+    for (name, schema) in record_schemas() {
+        let path = dir.join(schema_file_name(name));
+        let mut json = serde_json::to_string_pretty(&schema)?;
+        json.push('\n');
+        fs::write(&path, json)?;
+        written.push(path);
+    }
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use serde::Serialize;
+
+    use super::*;
+    use crate::changelog::TableChange;
+    use crate::transaction::{Savepoint, TableWrite, TransactionStatus, WriteGranularity};
+
+    fn published_dir() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("..").join("schemas")
+    }
+
+    fn sample_records() -> Vec<(&'static str, serde_json::Value)> {
+        fn json(record: impl Serialize) -> serde_json::Value {
+            serde_json::to_value(record).unwrap()
+        }
+
+        let mut branch = Branch::new("feature/x", HashMap::from([("users".to_string(), 3)]));
+        branch.fork_point = Some(HashMap::from([("users".to_string(), 2)]));
+        branch.snapshot_id = Some(7);
+
+        let mut tx = TransactionRecord::new(12, 4, "main".to_string());
+        tx.writes = vec![
+            TableWrite::new("users", 3, vec!["h1".to_string()])
+                .with_schema_hash("s1")
+                .with_granularity(WriteGranularity::Keys {
+                    key_columns: vec!["id".to_string()],
+                    affected_keys: vec![serde_json::json!(1)],
+                }),
+            TableWrite::rename_table("orders", "orders_v2", 5).with_branch("dev"),
+        ];
+        tx.savepoints = vec![Savepoint {
+            name: "before-orders".to_string(),
+            writes: tx.writes[..1].to_vec(),
+            read_snapshot: tx.read_snapshot.clone(),
+            created_at: 1,
+        }];
+        tx.status = TransactionStatus::Aborted {
+            reason: "conflict".to_string(),
+        };
+
+        let mut entry = ChangelogEntry::new(12, 4, 100, "main");
+        entry.changes = vec![
+            TableChange::new("users", Some(2), 3, vec!["h1".to_string()]),
+            TableChange::new("orders", Some(5), 5, vec![]).with_branch("dev"),
+        ];
+
+        vec![
+            ("branch", json(branch)),
+            ("changelog_entry", json(entry)),
+            (
+                "pending_commit",
+                json(PendingCommit {
+                    intent_id: "intent-1".to_string(),
+                    table_name: "users".to_string(),
+                    chunk_hashes: vec!["h1".to_string()],
+                    created_at: 100,
+                }),
+            ),
+            (
+                "table_version",
+                json(TableVersion::new("users", 3, vec!["h1".to_string()]).with_metadata("source", "etl")),
+            ),
+            ("transaction_record", json(tx)),
+        ]
+    }
+
+    #[test]
+    fn test_published_schemas_match_types() {
+        let dir = published_dir();
+        if std::env::var_os("RHIZO_WRITE_SCHEMAS").is_some() {
+            write_schemas(&dir).unwrap();
+        }
+        for (name, schema) in record_schemas() {
+            let path = dir.join(schema_file_name(name));
+            let published: serde_json::Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+            assert_eq!(
+                published, schema,
+                "{} is stale; regenerate it with RHIZO_WRITE_SCHEMAS=1",
+                path.display()
+            );
+        }
+    }
+
+    #[test]
+    fn test_records_validate_against_schemas() {
+        let schemas = record_schemas();
+        for (name, record) in sample_records() {
+            let validator = jsonschema::validator_for(&schemas[name]).unwrap();
+            let errors: Vec<String> = validator.iter_errors(&record).map(|e| e.to_string()).collect();
+            assert!(errors.is_empty(), "{}: {:?}", name, errors);
+        }
+
+        let validator = jsonschema::validator_for(&schemas["table_version"]).unwrap();
+        let mut bad = serde_json::to_value(TableVersion::new("users", 1, vec![])).unwrap();
+        bad["version"] = serde_json::json!("one");
+        assert!(!validator.is_valid(&bad));
+        bad.as_object_mut().unwrap().remove("version");
+        assert!(!validator.is_valid(&bad));
+    }
+}
@@ -0,0 +1,91 @@
+use std::time::Duration;
+
+use thiserror::Error;
+
+use crate::branch::BranchError;
+use crate::catalog::CatalogError;
+use crate::error::{ErrorCategory, ErrorContext, ErrorInfo};
+use crate::transaction::{TransactionError, TxId};
+
+#[derive(Error, Debug)]
+#[non_exhaustive]
+pub enum RefError {
+    #[error("Invalid ref {reference:?}: {reason}")]
+    InvalidRef { reference: String, reason: String },
+
+    #[error("Resolving {0} requires a branch manager")]
+    NoBranchManager(String),
+
+    #[error("Resolving tx:{0} requires a transaction log")]
+    NoTransactionLog(TxId),
+
+    #[error("Branch {branch} has fewer than {ancestor} earlier head(s)")]
+    AncestorNotFound { branch: String, ancestor: u32 },
+
+    #[error("Transaction {0} is not committed")]
+    NotCommitted(TxId),
+
+    #[error("Table {table} is not in {reference}")]
+    TableNotInRef { reference: String, table: String },
+
+    #[error("Catalog error: {0}")]
+    Catalog(#[from] CatalogError),
+
+    #[error("Branch error: {0}")]
+    Branch(#[from] BranchError),
+
+    #[error("Transaction error: {0}")]
+    Transaction(#[from] TransactionError),
+}
+
+impl ErrorInfo for RefError {
+    fn code(&self) -> &'static str {
+        match self {
+            RefError::InvalidRef { .. } => "REF_INVALID",
+            RefError::NoBranchManager(_) => "REF_NO_BRANCH_MANAGER",
+            RefError::NoTransactionLog(_) => "REF_NO_TRANSACTION_LOG",
+            RefError::AncestorNotFound { .. } => "REF_ANCESTOR_NOT_FOUND",
+            RefError::NotCommitted(_) => "REF_NOT_COMMITTED",
+            RefError::TableNotInRef { .. } => "REF_TABLE_NOT_FOUND",
+            RefError::Catalog(e) => e.code(),
+            RefError::Branch(e) => e.code(),
+            RefError::Transaction(e) => e.code(),
+        }
+    }
+
+    fn category(&self) -> ErrorCategory {
+        match self {
+            RefError::InvalidRef { .. } => ErrorCategory::InvalidArgument,
+            RefError::NoBranchManager(_) | RefError::NoTransactionLog(_) | RefError::NotCommitted(_) => {
+                ErrorCategory::FailedPrecondition
+            }
+            RefError::AncestorNotFound { .. } | RefError::TableNotInRef { .. } => ErrorCategory::NotFound,
+            RefError::Catalog(e) => e.category(),
+            RefError::Branch(e) => e.category(),
+            RefError::Transaction(e) => e.category(),
+        }
+    }
+
+    fn context(&self) -> ErrorContext {
+        match self {
+            RefError::AncestorNotFound { branch, .. } => ErrorContext::new().with_branch(branch.clone()),
+            RefError::NoTransactionLog(tx_id) | RefError::NotCommitted(tx_id) => {
+                ErrorContext::new().with_tx_id(*tx_id)
+            }
+            RefError::TableNotInRef { table, .. } => ErrorContext::new().with_table(table.clone()),
+            RefError::Catalog(e) => e.context(),
+            RefError::Branch(e) => e.context(),
+            RefError::Transaction(e) => e.context(),
+            _ => ErrorContext::new(),
+        }
+    }
+
+    fn retry_after(&self) -> Option<Duration> {
+        match self {
+            RefError::Catalog(e) => e.retry_after(),
+            RefError::Branch(e) => e.retry_after(),
+            RefError::Transaction(e) => e.retry_after(),
+            _ => None,
+        }
+    }
+}
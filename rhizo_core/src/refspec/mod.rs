@@ -0,0 +1,15 @@
+//! Naming points in history.
+//!
+//! One small grammar (`main`, `main~3`, `users@v7`, `tag:release-1`,
+//! `tx:1234`, `repo@1234`) shared by the catalog, branch, query, CLI and
+//! Python entry points, so tooling does not each invent its own
+//! `(table, Option<u64>)` or `(branch, version)` arguments. [`RefSpec`]
+//! parses and prints a ref; [`RefResolver`] turns it into table versions.
+
+pub mod error;
+pub mod resolver;
+pub mod spec;
+
+pub use error::RefError;
+pub use resolver::{RefResolver, ResolvedRef};
+pub use spec::RefSpec;
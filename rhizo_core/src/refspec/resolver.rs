@@ -0,0 +1,246 @@
+use std::collections::HashMap;
+
+use super::error::RefError;
+use super::spec::RefSpec;
+use crate::branch::BranchManager;
+use crate::catalog::{FileCatalog, TableVersion};
+use crate::transaction::{TransactionLog, WriteKind};
+
+/// The table versions a [`RefSpec`] names.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolvedRef {
+    /// The ref that was resolved
+    pub spec: RefSpec,
+
+    /// Branch the versions were taken from, when the ref records one
+    pub branch: Option<String>,
+
+    /// Table -> version
+    pub versions: HashMap<String, u64>,
+}
+
+impl ResolvedRef {
+    /// The version of a table at this ref, if the table is in it
+    pub fn get(&self, table_name: &str) -> Option<u64> {
+        self.versions.get(table_name).copied()
+    }
+}
+
+/// Resolves [`RefSpec`]s against a catalog and, for branch, snapshot and
+/// transaction refs, the branch manager and transaction log.
+///
+/// `main~N` counts head changes recorded as repository snapshots, so it
+/// only reaches back to the branch's creation, and to when snapshots were
+/// first recorded.
+///
+/// # Example
+///
+/// ```ignore
+/// let resolver = RefResolver::new(&catalog).with_branches(&branches);
+/// let users = resolver.table_version(&"main~2".parse()?, "users")?;
+/// ```
+pub struct RefResolver<'a> {
+    catalog: &'a FileCatalog,
+    branches: Option<&'a BranchManager>,
+    log: Option<&'a TransactionLog>,
+}
+
+impl<'a> RefResolver<'a> {
+    pub fn new(catalog: &'a FileCatalog) -> Self {
+        Self {
+            catalog,
+            branches: None,
+            log: None,
+        }
+    }
+
+    /// Resolve branch and snapshot refs against `branches`.
+    pub fn with_branches(mut self, branches: &'a BranchManager) -> Self {
+        self.branches = Some(branches);
+        self
+    }
+
+    /// Resolve `tx:` refs against `log` (e.g. `TransactionManager::log`).
+    pub fn with_transactions(mut self, log: &'a TransactionLog) -> Self {
+        self.log = Some(log);
+        self
+    }
+
+    /// The table versions `spec` names.
+    ///
+    /// # Errors
+    /// - `NoBranchManager` / `NoTransactionLog` if the ref needs a
+    ///   component the resolver was not given.
+    /// - `AncestorNotFound` if `name~N` reaches past the branch's recorded
+    ///   history.
+    /// - `NotCommitted` for a transaction that has not committed.
+    pub fn resolve(&self, spec: &RefSpec) -> Result<ResolvedRef, RefError> {
+        let (branch, versions) = match spec {
+            RefSpec::Branch { name, ancestor } => (Some(name.clone()), self.branch_versions(spec, name, *ancestor)?),
+            RefSpec::Version { table, version } => {
+                let version = self.catalog.get_version(table, Some(*version))?.version;
+                (None, HashMap::from([(table.clone(), version)]))
+            }
+            RefSpec::Tag(name) => {
+                let tag = self.catalog.resolve_tag(name)?;
+                (tag.source_branch, tag.versions)
+            }
+            RefSpec::Transaction(tx_id) => {
+                let log = self.log.ok_or(RefError::NoTransactionLog(*tx_id))?;
+                let tx = log.read_transaction(*tx_id)?;
+                if !tx.is_committed() {
+                    return Err(RefError::NotCommitted(*tx_id));
+                }
+                let mut versions = tx.read_snapshot.clone().into_map();
+                for write in &tx.writes {
+                    match &write.kind {
+                        WriteKind::Data | WriteKind::Merge { .. } => {
+                            versions.insert(write.table_name.clone(), write.new_version);
+                        }
+                        WriteKind::Drop => {
+                            versions.remove(&write.table_name);
+                        }
+                        WriteKind::Rename { to } => {
+                            versions.remove(&write.table_name);
+                            versions.insert(to.clone(), write.new_version);
+                        }
+                    }
+                }
+                (Some(tx.branch), versions)
+            }
+            RefSpec::Snapshot(id) => {
+                let branches = self.branches.ok_or_else(|| RefError::NoBranchManager(spec.to_string()))?;
+                let snapshot = branches.snapshot(*id)?;
+                (Some(snapshot.branch), snapshot.versions)
+            }
+        };
+        Ok(ResolvedRef {
+            spec: spec.clone(),
+            branch,
+            versions,
+        })
+    }
+
+    /// The catalog record of `table_name` at `spec`.
+    pub fn table_version(&self, spec: &RefSpec, table_name: &str) -> Result<TableVersion, RefError> {
+        let version = self.resolve(spec)?.get(table_name).ok_or_else(|| RefError::TableNotInRef {
+            reference: spec.to_string(),
+            table: table_name.to_string(),
+        })?;
+        Ok(self.catalog.get_version(table_name, Some(version))?)
+    }
+
+    fn branch_versions(&self, spec: &RefSpec, name: &str, ancestor: u32) -> Result<HashMap<String, u64>, RefError> {
+        let branches = self.branches.ok_or_else(|| RefError::NoBranchManager(spec.to_string()))?;
+        let branch = branches.get(name)?;
+        if ancestor == 0 {
+            return Ok(branch.head);
+        }
+
+        // Snapshots of an earlier branch with the same name are not history
+        let history: Vec<_> = branches
+            .snapshots(Some(name))?
+            .into_iter()
+            .filter(|s| s.created_at >= branch.created_at)
+            .collect();
+        let not_found = || RefError::AncestorNotFound {
+            branch: name.to_string(),
+            ancestor,
+        };
+        let current = history
+            .iter()
+            .rposition(|s| Some(s.id) == branch.snapshot_id)
+            .ok_or_else(not_found)?;
+        let index = current.checked_sub(ancestor as usize).ok_or_else(not_found)?;
+        Ok(history.into_iter().nth(index).map(|s| s.versions).unwrap_or_default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::catalog::TagManager;
+    use crate::repo::Repo;
+    use crate::transaction::TableWrite;
+
+    fn commit(manager: &crate::transaction::TransactionManager, writes: &[(&str, u64)]) -> u64 {
+        let tx_id = manager.begin(None).unwrap();
+        for &(table, version) in writes {
+            let hash = format!("{}{}", table, version);
+            manager.add_write(tx_id, TableWrite::new(table, version, vec![hash])).unwrap();
+        }
+        manager.commit(tx_id).unwrap();
+        tx_id
+    }
+
+    #[test]
+    fn test_resolves_every_ref_form() {
+        let repo = Repo::ephemeral().unwrap();
+        let manager = repo.transaction_manager().unwrap();
+        let tx1 = commit(&manager, &[("users", 1)]);
+        let tx2 = commit(&manager, &[("users", 2), ("orders", 1)]);
+        let main = repo.branches().get("main").unwrap();
+        TagManager::new(Arc::clone(repo.catalog()))
+            .create_from_branch("release-1", &main, None)
+            .unwrap();
+
+        let resolver = RefResolver::new(repo.catalog())
+            .with_branches(repo.branches())
+            .with_transactions(manager.log());
+        let resolve = |text: &str| resolver.resolve(&text.parse().unwrap()).unwrap();
+        let head = HashMap::from([("users".to_string(), 2), ("orders".to_string(), 1)]);
+
+        assert_eq!(resolve("main").versions, head);
+        assert_eq!(resolve("main~1").versions, HashMap::from([("users".to_string(), 1)]));
+        assert_eq!(resolve("tag:release-1").versions, head);
+        assert_eq!(resolve("tag:release-1").branch.as_deref(), Some("main"));
+        assert_eq!(resolve(&format!("tx:{}", tx1)).get("users"), Some(1));
+        assert_eq!(resolve(&format!("tx:{}", tx2)).versions, head);
+        assert_eq!(resolve(&format!("repo@{}", main.snapshot_id.unwrap())).versions, head);
+        assert_eq!(resolve("users@v1").versions, HashMap::from([("users".to_string(), 1)]));
+
+        assert_eq!(resolver.table_version(&"main~1".parse().unwrap(), "users").unwrap().version, 1);
+        assert!(matches!(
+            resolver.table_version(&"main~1".parse().unwrap(), "orders"),
+            Err(RefError::TableNotInRef { .. })
+        ));
+    }
+
+    #[test]
+    fn test_unresolvable_refs() {
+        let repo = Repo::ephemeral().unwrap();
+        let manager = repo.transaction_manager().unwrap();
+        commit(&manager, &[("users", 1)]);
+        let pending = manager.begin(None).unwrap();
+
+        let resolver = RefResolver::new(repo.catalog());
+        assert!(matches!(
+            resolver.resolve(&RefSpec::branch("main")),
+            Err(RefError::NoBranchManager(_))
+        ));
+        assert!(matches!(
+            resolver.resolve(&RefSpec::Transaction(1)),
+            Err(RefError::NoTransactionLog(1))
+        ));
+        assert!(matches!(
+            resolver.resolve(&"users@v9".parse().unwrap()),
+            Err(RefError::Catalog(_))
+        ));
+
+        let resolver = resolver.with_branches(repo.branches()).with_transactions(manager.log());
+        assert!(matches!(
+            resolver.resolve(&"main~5".parse().unwrap()),
+            Err(RefError::AncestorNotFound { ancestor: 5, .. })
+        ));
+        assert!(matches!(
+            resolver.resolve(&"nope".parse().unwrap()),
+            Err(RefError::Branch(_))
+        ));
+        assert!(matches!(
+            resolver.resolve(&RefSpec::Transaction(pending)),
+            Err(RefError::NotCommitted(_))
+        ));
+    }
+}
@@ -0,0 +1,155 @@
+use std::fmt;
+use std::str::FromStr;
+
+use super::error::RefError;
+use crate::transaction::TxId;
+
+/// A point in history, as named on the command line or in an API call.
+///
+/// | Form            | Meaning                                            |
+/// |-----------------|----------------------------------------------------|
+/// | `main`          | head of branch `main`                              |
+/// | `main~3`        | head of `main` three head changes ago (`main~` = `main~1`) |
+/// | `users@v7`      | version 7 of table `users` (`users@7` also accepted) |
+/// | `tag:release-1` | the versions recorded by tag `release-1`           |
+/// | `tx:1234`       | the versions transaction 1234 read, with its writes applied |
+/// | `repo@1234`     | repository snapshot 1234                           |
+///
+/// `repo@N` always names a snapshot, never version N of a table called
+/// `repo`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum RefSpec {
+    /// A branch head, `ancestor` head changes back (0 = current)
+    Branch { name: String, ancestor: u32 },
+
+    /// One version of one table
+    Version { table: String, version: u64 },
+
+    /// A tag
+    Tag(String),
+
+    /// The state a committed transaction produced
+    Transaction(TxId),
+
+    /// A repository snapshot
+    Snapshot(u64),
+}
+
+impl RefSpec {
+    /// The current head of a branch
+    pub fn branch(name: impl Into<String>) -> Self {
+        RefSpec::Branch { name: name.into(), ancestor: 0 }
+    }
+
+    /// Parse a ref; see the type docs for the accepted forms.
+    pub fn parse(reference: &str) -> Result<Self, RefError> {
+        let invalid = |reason: &str| RefError::InvalidRef {
+            reference: reference.to_string(),
+            reason: reason.to_string(),
+        };
+        let trimmed = reference.trim();
+        if trimmed.is_empty() {
+            return Err(invalid("empty ref"));
+        }
+
+        if let Some(name) = trimmed.strip_prefix("tag:") {
+            if name.is_empty() {
+                return Err(invalid("missing tag name"));
+            }
+            return Ok(RefSpec::Tag(name.to_string()));
+        }
+        if let Some(id) = trimmed.strip_prefix("tx:") {
+            return id
+                .parse()
+                .map(RefSpec::Transaction)
+                .map_err(|_| invalid("transaction id must be a number"));
+        }
+        if let Some(id) = trimmed.strip_prefix("repo@") {
+            return id
+                .parse()
+                .map(RefSpec::Snapshot)
+                .map_err(|_| invalid("snapshot id must be a number"));
+        }
+        if let Some((table, version)) = trimmed.rsplit_once('@') {
+            if table.is_empty() {
+                return Err(invalid("missing table name"));
+            }
+            let version = version.strip_prefix('v').unwrap_or(version);
+            return version
+                .parse()
+                .map(|version| RefSpec::Version { table: table.to_string(), version })
+                .map_err(|_| invalid("version must be a number, optionally prefixed with 'v'"));
+        }
+        if let Some((name, ancestor)) = trimmed.rsplit_once('~') {
+            if name.is_empty() {
+                return Err(invalid("missing branch name"));
+            }
+            let ancestor = if ancestor.is_empty() {
+                1
+            } else {
+                ancestor.parse().map_err(|_| invalid("ancestor count must be a number"))?
+            };
+            return Ok(RefSpec::Branch { name: name.to_string(), ancestor });
+        }
+        Ok(RefSpec::branch(trimmed))
+    }
+}
+
+impl FromStr for RefSpec {
+    type Err = RefError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        RefSpec::parse(s)
+    }
+}
+
+impl fmt::Display for RefSpec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RefSpec::Branch { name, ancestor: 0 } => write!(f, "{}", name),
+            RefSpec::Branch { name, ancestor } => write!(f, "{}~{}", name, ancestor),
+            RefSpec::Version { table, version } => write!(f, "{}@v{}", table, version),
+            RefSpec::Tag(name) => write!(f, "tag:{}", name),
+            RefSpec::Transaction(tx_id) => write!(f, "tx:{}", tx_id),
+            RefSpec::Snapshot(id) => write!(f, "repo@{}", id),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_round_trips() {
+        let cases = [
+            ("main", RefSpec::branch("main")),
+            ("main~3", RefSpec::Branch { name: "main".into(), ancestor: 3 }),
+            ("users@v7", RefSpec::Version { table: "users".into(), version: 7 }),
+            ("tag:release-1", RefSpec::Tag("release-1".into())),
+            ("tx:1234", RefSpec::Transaction(1234)),
+            ("repo@12", RefSpec::Snapshot(12)),
+        ];
+        for (text, spec) in cases {
+            assert_eq!(text.parse::<RefSpec>().unwrap(), spec);
+            assert_eq!(spec.to_string(), text);
+        }
+
+        assert_eq!(RefSpec::parse("main~").unwrap(), RefSpec::Branch { name: "main".into(), ancestor: 1 });
+        assert_eq!(
+            RefSpec::parse(" sales.orders@7 ").unwrap(),
+            RefSpec::Version { table: "sales.orders".into(), version: 7 }
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_malformed_refs() {
+        for text in ["", "  ", "tag:", "tx:abc", "repo@x", "@v3", "users@vx", "~2", "main~two"] {
+            assert!(
+                matches!(RefSpec::parse(text), Err(RefError::InvalidRef { .. })),
+                "{:?} should not parse",
+                text
+            );
+        }
+    }
+}
@@ -0,0 +1,58 @@
+use thiserror::Error;
+
+use crate::branch::BranchError;
+use crate::catalog::CatalogError;
+use crate::chunk_store::ChunkStoreError;
+use crate::error::{ErrorCategory, ErrorContext, ErrorInfo};
+use crate::transaction::TransactionError;
+
+#[derive(Error, Debug)]
+#[non_exhaustive]
+pub enum RepoError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Catalog error: {0}")]
+    Catalog(#[from] CatalogError),
+
+    #[error("Chunk store error: {0}")]
+    ChunkStore(#[from] ChunkStoreError),
+
+    #[error("Branch error: {0}")]
+    Branch(#[from] BranchError),
+
+    #[error("Transaction error: {0}")]
+    Transaction(#[from] TransactionError),
+}
+
+impl ErrorInfo for RepoError {
+    fn code(&self) -> &'static str {
+        match self {
+            RepoError::Io(_) => "REPO_IO",
+            RepoError::Catalog(e) => e.code(),
+            RepoError::ChunkStore(e) => e.code(),
+            RepoError::Branch(e) => e.code(),
+            RepoError::Transaction(e) => e.code(),
+        }
+    }
+
+    fn category(&self) -> ErrorCategory {
+        match self {
+            RepoError::Io(_) => ErrorCategory::Io,
+            RepoError::Catalog(e) => e.category(),
+            RepoError::ChunkStore(e) => e.category(),
+            RepoError::Branch(e) => e.category(),
+            RepoError::Transaction(e) => e.category(),
+        }
+    }
+
+    fn context(&self) -> ErrorContext {
+        match self {
+            RepoError::Io(_) => ErrorContext::new(),
+            RepoError::Catalog(e) => e.context(),
+            RepoError::ChunkStore(e) => e.context(),
+            RepoError::Branch(e) => e.context(),
+            RepoError::Transaction(e) => e.context(),
+        }
+    }
+}
@@ -0,0 +1,13 @@
+//! A repository's storage components opened together from one directory.
+//!
+//! [`Repo`] lays out the catalog, chunk store and branches the way the
+//! Python `Database` does (`catalog/`, `chunks/`, `branches/`,
+//! `transactions/`). [`Repo::ephemeral`] creates a uniquely named
+//! repository in the system temp directory and deletes it on drop, for
+//! tests and scratch work.
+
+pub mod error;
+pub mod repository;
+
+pub use error::RepoError;
+pub use repository::Repo;
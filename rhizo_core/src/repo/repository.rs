@@ -0,0 +1,296 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use tracing::warn;
+
+use super::error::RepoError;
+use crate::branch::{BranchError, BranchManager};
+use crate::catalog::{FileCatalog, TableVersion};
+use crate::chunk_store::ChunkStore;
+use crate::durability::{self, DurabilityLevel};
+use crate::filter::RepoRef;
+use crate::refspec::{RefError, RefResolver, RefSpec, ResolvedRef};
+use crate::transaction::{TransactionLog, TransactionManager};
+
+const CATALOG_DIR: &str = "catalog";
+const CHUNKS_DIR: &str = "chunks";
+const BRANCHES_DIR: &str = "branches";
+const TRANSACTIONS_DIR: &str = "transactions";
+
+/// Catalog, chunk store and branches of one repository directory.
+///
+/// # Example
+///
+/// ```
+/// use rhizo_core::{Repo, TableVersion};
+///
+/// let repo = Repo::ephemeral().unwrap();
+/// let hash = repo.store().put(b"alice,bob").unwrap();
+/// repo.catalog().commit(TableVersion::new("users", 1, vec![hash])).unwrap();
+/// let path = repo.path().to_path_buf();
+///
+/// drop(repo);
+/// assert!(!path.exists());
+/// ```
+pub struct Repo {
+    path: PathBuf,
+    catalog: Arc<FileCatalog>,
+    store: ChunkStore,
+    branches: Arc<BranchManager>,
+    durability: DurabilityLevel,
+    remove_on_drop: bool,
+}
+
+impl Repo {
+    /// Open (or create) the repository at `path`.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, RepoError> {
+        Self::open_with_durability(path, DurabilityLevel::default())
+    }
+
+    /// Open (or create) the repository at `path`, flushing catalog,
+    /// branch and transaction log writes as `durability` requires.
+    pub fn open_with_durability(path: impl AsRef<Path>, durability: DurabilityLevel) -> Result<Self, RepoError> {
+        let path = path.as_ref().to_path_buf();
+        fs::create_dir_all(&path)?;
+        Ok(Self {
+            catalog: Arc::new(FileCatalog::new(path.join(CATALOG_DIR))?.with_durability(durability)),
+            store: ChunkStore::new(path.join(CHUNKS_DIR))?,
+            branches: Arc::new(BranchManager::new(path.join(BRANCHES_DIR))?.with_durability(durability)),
+            durability,
+            path,
+            remove_on_drop: false,
+        })
+    }
+
+    /// Create a new, uniquely named repository in the system temp
+    /// directory, deleted when the `Repo` is dropped.
+    pub fn ephemeral() -> Result<Self, RepoError> {
+        let path = std::env::temp_dir().join(format!("rhizo_repo_{}", uuid::Uuid::new_v4()));
+        let mut repo = Self::open(path)?;
+        repo.remove_on_drop = true;
+        Ok(repo)
+    }
+
+    /// Delete (or keep) the repository directory on drop. Ephemeral
+    /// repositories default to deleting; pass `false` to inspect one after
+    /// a failed test.
+    pub fn with_cleanup(mut self, remove_on_drop: bool) -> Self {
+        self.remove_on_drop = remove_on_drop;
+        self
+    }
+
+    /// Replace the chunk store, e.g. with one opened with a config or
+    /// pipeline. The store should live under `path()` if the directory is
+    /// to be cleaned up.
+    pub fn with_store(mut self, store: ChunkStore) -> Self {
+        self.store = store;
+        self
+    }
+
+    /// Root directory of the repository
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Directory of the chunk store
+    pub fn chunks_path(&self) -> PathBuf {
+        self.path.join(CHUNKS_DIR)
+    }
+
+    pub fn catalog(&self) -> &Arc<FileCatalog> {
+        &self.catalog
+    }
+
+    pub fn store(&self) -> &ChunkStore {
+        &self.store
+    }
+
+    pub fn branches(&self) -> &Arc<BranchManager> {
+        &self.branches
+    }
+
+    pub fn durability(&self) -> DurabilityLevel {
+        self.durability
+    }
+
+    /// The components as a [`RepoRef`]
+    pub fn repo_ref(&self) -> RepoRef<'_> {
+        RepoRef::new(&self.catalog, &self.store).with_branches(&self.branches)
+    }
+
+    /// Open a transaction manager over this repository's catalog and
+    /// branches.
+    pub fn transaction_manager(&self) -> Result<TransactionManager, RepoError> {
+        let mut manager = TransactionManager::new(
+            self.path.join(TRANSACTIONS_DIR),
+            Arc::clone(&self.catalog),
+            Some(Arc::clone(&self.branches)),
+        )?;
+        manager.set_durability(self.durability);
+        Ok(manager)
+    }
+
+    /// The version of a table a read on `branch` sees (see
+    /// [`BranchManager::resolve_read`]).
+    pub fn resolve_read(&self, branch: &str, table_name: &str) -> Result<TableVersion, BranchError> {
+        self.branches.resolve_read(&self.catalog, branch, table_name)
+    }
+
+    /// Resolve a ref (`main~2`, `users@v7`, `tag:release-1`, `tx:42`,
+    /// `repo@12`) to the table versions it names.
+    pub fn resolve_ref(&self, reference: &str) -> Result<ResolvedRef, RefError> {
+        let spec = RefSpec::parse(reference)?;
+        let resolver = RefResolver::new(&self.catalog).with_branches(&self.branches);
+        match spec {
+            RefSpec::Transaction(_) => {
+                // Where `TransactionManager` keeps its log under its base path
+                let log = TransactionLog::new(self.path.join(TRANSACTIONS_DIR).join("transactions"))?;
+                resolver.with_transactions(&log).resolve(&spec)
+            }
+            _ => resolver.resolve(&spec),
+        }
+    }
+
+    /// Close the repository deterministically instead of on drop.
+    ///
+    /// Metadata directories are flushed to disk whatever the durability
+    /// level, and an ephemeral repository is removed here, with a failure
+    /// reported rather than logged. Shut down any transaction managers
+    /// first (`TransactionManager::shutdown`) so no transaction is left
+    /// for crash recovery.
+    pub fn close(mut self) -> Result<(), RepoError> {
+        if self.remove_on_drop {
+            self.remove_on_drop = false;
+            return match fs::remove_dir_all(&self.path) {
+                Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+                _ => Ok(()),
+            };
+        }
+        for dir in [CATALOG_DIR, BRANCHES_DIR, TRANSACTIONS_DIR] {
+            durability::sync_path(&self.path.join(dir))?;
+        }
+        durability::sync_path(&self.path)?;
+        Ok(())
+    }
+}
+
+impl Drop for Repo {
+    fn drop(&mut self) {
+        if !self.remove_on_drop {
+            return;
+        }
+        if let Err(e) = fs::remove_dir_all(&self.path) {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                warn!(path = %self.path.display(), error = %e, "Failed to remove ephemeral repository");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::catalog::TableVersion;
+
+    #[test]
+    fn test_ephemeral_repos_are_isolated_and_removed() {
+        let a = Repo::ephemeral().unwrap();
+        let b = Repo::ephemeral().unwrap();
+        assert_ne!(a.path(), b.path());
+
+        let hash = a.store().put(b"v1").unwrap();
+        a.catalog().commit(TableVersion::new("users", 1, vec![hash])).unwrap();
+        assert_eq!(b.catalog().list_tables().unwrap(), Vec::<String>::new());
+
+        let path = a.path().to_path_buf();
+        drop(a);
+        assert!(!path.exists());
+        assert!(b.path().exists());
+    }
+
+    #[test]
+    fn test_kept_repo_reopens() {
+        let repo = Repo::ephemeral().unwrap().with_cleanup(false);
+        let hash = repo.store().put(b"v1").unwrap();
+        repo.catalog().commit(TableVersion::new("users", 1, vec![hash])).unwrap();
+        repo.branches().update_head("main", "users", 1).unwrap();
+        let path = repo.path().to_path_buf();
+        drop(repo);
+
+        let reopened = Repo::open(&path).unwrap().with_cleanup(true);
+        assert_eq!(reopened.catalog().list_tables().unwrap(), vec!["users".to_string()]);
+        assert_eq!(reopened.branches().get("main").unwrap().head["users"], 1);
+        assert!(reopened.transaction_manager().is_ok());
+        drop(reopened);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_durability_reaches_every_writer() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = Repo::open_with_durability(dir.path(), DurabilityLevel::FsyncAll).unwrap();
+        assert_eq!(repo.catalog().durability(), DurabilityLevel::FsyncAll);
+        assert_eq!(repo.branches().durability(), DurabilityLevel::FsyncAll);
+
+        let tm = repo.transaction_manager().unwrap();
+        assert_eq!(tm.durability(), DurabilityLevel::FsyncAll);
+        let hash = repo.store().put(b"v1").unwrap();
+        let tx = tm.begin(None).unwrap();
+        tm.add_write(tx, crate::transaction::TableWrite::new("users", 1, vec![hash])).unwrap();
+        tm.commit(tx).unwrap();
+        repo.branches().create("dev", None, None).unwrap();
+        drop((tm, repo));
+
+        let reopened = Repo::open(dir.path()).unwrap();
+        assert_eq!(reopened.durability(), DurabilityLevel::None);
+        assert_eq!(reopened.branches().get("dev").unwrap().head["users"], 1);
+        assert_eq!(reopened.transaction_manager().unwrap().log().latest_committed_tx_id().unwrap(), Some(tx));
+    }
+
+    #[test]
+    fn test_close_after_shutdown_leaves_nothing_to_recover() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = Repo::open(dir.path()).unwrap();
+        let tm = repo.transaction_manager().unwrap();
+        let hash = repo.store().put(b"v1").unwrap();
+        let committed = tm.begin(None).unwrap();
+        tm.add_write(committed, crate::transaction::TableWrite::new("users", 1, vec![hash])).unwrap();
+        tm.commit(committed).unwrap();
+        let pending = tm.begin(None).unwrap();
+
+        let report = tm.shutdown(std::time::Duration::ZERO).unwrap();
+        assert_eq!(report.aborted, vec![pending]);
+        drop(tm);
+        repo.close().unwrap();
+
+        let reopened = Repo::open(dir.path()).unwrap();
+        let recovery = reopened.transaction_manager().unwrap().recover().unwrap();
+        assert!(recovery.rolled_back.is_empty());
+        assert_eq!(reopened.catalog().list_tables().unwrap(), vec!["users".to_string()]);
+
+        let ephemeral = Repo::ephemeral().unwrap();
+        let path = ephemeral.path().to_path_buf();
+        ephemeral.close().unwrap();
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_resolve_ref() {
+        let repo = Repo::ephemeral().unwrap();
+        let tm = repo.transaction_manager().unwrap();
+        for version in 1..=2 {
+            let hash = repo.store().put(format!("v{}", version).as_bytes()).unwrap();
+            let tx = tm.begin(None).unwrap();
+            tm.add_write(tx, crate::transaction::TableWrite::new("users", version, vec![hash])).unwrap();
+            tm.commit(tx).unwrap();
+        }
+        drop(tm);
+
+        assert_eq!(repo.resolve_ref("main").unwrap().get("users"), Some(2));
+        assert_eq!(repo.resolve_ref("main~1").unwrap().get("users"), Some(1));
+        assert_eq!(repo.resolve_ref("tx:1").unwrap().get("users"), Some(1));
+        assert!(matches!(repo.resolve_ref("main~x"), Err(RefError::InvalidRef { .. })));
+        assert_eq!(repo.resolve_read("main", "users").unwrap().version, 2);
+    }
+}
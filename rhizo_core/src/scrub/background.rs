@@ -0,0 +1,390 @@
+//! Incremental scrubbing in the background.
+//!
+//! A full [`ChunkScrubber`](super::ChunkScrubber) run reads every chunk at
+//! once. [`BackgroundScrubber`] spreads the same check over time instead:
+//! every tick it verifies a slice of the journaled chunks, least recently
+//! verified first, so the whole store is covered at a configurable rate
+//! without one disruptive job. Verification times are persisted next to
+//! the integrity journal, so a restarted scrubber picks up where the last
+//! one stopped. Damaged chunks are reported to a [`CorruptionSink`] as they
+//! are found.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use tracing::warn;
+
+use super::error::ScrubError;
+use super::scrubber::DamagedChunk;
+use crate::chunk_store::{ChunkCondition, ChunkStore};
+use crate::durability::{self, DurabilityLevel};
+
+/// File (next to the integrity journal) recording when each chunk was
+/// last verified
+pub const VERIFIED_FILE: &str = "scrub_verified.json";
+
+/// Receives every damaged chunk an incremental scrub finds.
+///
+/// Called on the scrubbing thread; implementations should hand the event
+/// off rather than block.
+pub trait CorruptionSink: Send + Sync {
+    fn on_corruption(&self, chunk: &DamagedChunk);
+}
+
+/// How fast a [`BackgroundScrubber`] works through the store
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BackgroundScrubConfig {
+    /// Fraction of the journaled chunks verified per hour (1.0 covers the
+    /// store hourly)
+    pub fraction_per_hour: f64,
+    /// Time between slices
+    pub tick: Duration,
+}
+
+impl Default for BackgroundScrubConfig {
+    /// The whole store once a day, in slices a minute apart
+    fn default() -> Self {
+        Self {
+            fraction_per_hour: 1.0 / 24.0,
+            tick: Duration::from_secs(60),
+        }
+    }
+}
+
+impl BackgroundScrubConfig {
+    pub fn with_fraction_per_hour(mut self, fraction: f64) -> Self {
+        self.fraction_per_hour = fraction;
+        self
+    }
+
+    pub fn with_tick(mut self, tick: Duration) -> Self {
+        self.tick = tick;
+        self
+    }
+
+    /// Chunks to verify per tick for a store of `total` journaled chunks.
+    /// Rounds up, so a small store still makes progress.
+    pub fn slice_size(&self, total: usize) -> usize {
+        let per_tick = total as f64 * self.fraction_per_hour * self.tick.as_secs_f64() / 3600.0;
+        (per_tick.ceil().max(0.0) as usize).min(total)
+    }
+}
+
+/// Outcome of one slice of an incremental scrub
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SliceReport {
+    /// Chunks compared against their journal records
+    pub chunks_checked: usize,
+    /// Chunks whose size and CRC matched
+    pub chunks_intact: usize,
+    /// Chunks not in the local cache of a backend-backed store
+    pub chunks_not_cached: usize,
+    /// Chunks that failed the check
+    pub damaged: Vec<DamagedChunk>,
+    /// Journaled chunks never verified, after this slice
+    pub never_verified: usize,
+}
+
+/// Verifies journaled chunks a slice at a time, least recently verified
+/// first.
+///
+/// # Example
+///
+/// ```ignore
+/// let scrub = IncrementalScrubber::new(Arc::clone(&store)).with_sink(alerts);
+/// let report = scrub.verify_next(100)?;
+/// ```
+pub struct IncrementalScrubber {
+    store: Arc<ChunkStore>,
+    sink: Option<Arc<dyn CorruptionSink>>,
+}
+
+impl IncrementalScrubber {
+    pub fn new(store: Arc<ChunkStore>) -> Self {
+        Self { store, sink: None }
+    }
+
+    /// Report damaged chunks to `sink`
+    pub fn with_sink(mut self, sink: Arc<dyn CorruptionSink>) -> Self {
+        self.sink = Some(sink);
+        self
+    }
+
+    /// When each journaled chunk was last verified (Unix milliseconds);
+    /// chunks never verified are absent
+    pub fn verified_at(&self) -> Result<HashMap<String, u64>, ScrubError> {
+        let path = self.state_path()?;
+        if !path.exists() {
+            return Ok(HashMap::new());
+        }
+        let content = fs::read_to_string(&path)?;
+        // A torn state file only costs re-verification
+        Ok(serde_json::from_str(&content).unwrap_or_default())
+    }
+
+    /// Number of journaled chunks
+    pub fn journaled(&self) -> Result<usize, ScrubError> {
+        let journal = self.store.integrity_journal().ok_or(ScrubError::JournalDisabled)?;
+        Ok(journal.entries()?.len())
+    }
+
+    /// Verify the `count` least recently verified chunks.
+    ///
+    /// # Errors
+    /// - `ScrubError::JournalDisabled` if the store has no integrity journal
+    pub fn verify_next(&self, count: usize) -> Result<SliceReport, ScrubError> {
+        let journal = self.store.integrity_journal().ok_or(ScrubError::JournalDisabled)?;
+        let entries = journal.entries()?;
+        let mut verified = self.verified_at()?;
+        verified.retain(|hash, _| entries.contains_key(hash));
+
+        let mut order: Vec<(u64, &String)> = entries
+            .keys()
+            .map(|hash| (verified.get(hash).copied().unwrap_or(0), hash))
+            .collect();
+        order.sort();
+
+        let mut report = SliceReport::default();
+        for (_, hash) in order.into_iter().take(count) {
+            let entry = &entries[hash];
+            report.chunks_checked += 1;
+            match self.store.check_integrity(entry)? {
+                ChunkCondition::Intact => report.chunks_intact += 1,
+                ChunkCondition::NotCached => report.chunks_not_cached += 1,
+                condition => {
+                    let damaged = DamagedChunk {
+                        hash: hash.clone(),
+                        condition,
+                        codec: entry.codec.clone(),
+                        created_at: entry.created_at,
+                    };
+                    warn!(hash = %damaged.hash, condition = ?damaged.condition, "Scrub found a damaged chunk");
+                    if let Some(sink) = &self.sink {
+                        sink.on_corruption(&damaged);
+                    }
+                    report.damaged.push(damaged);
+                }
+            }
+            verified.insert(hash.clone(), unix_millis());
+        }
+        report.never_verified = entries.len() - verified.len();
+
+        let path = self.state_path()?;
+        let content = serde_json::to_string(&verified).map_err(std::io::Error::from)?;
+        durability::write_atomic(&path, &path.with_extension("json.tmp"), content, DurabilityLevel::None)?;
+        Ok(report)
+    }
+
+    fn state_path(&self) -> Result<PathBuf, ScrubError> {
+        let journal = self.store.integrity_journal().ok_or(ScrubError::JournalDisabled)?;
+        Ok(journal.path().with_file_name(VERIFIED_FILE))
+    }
+}
+
+/// Totals of a [`BackgroundScrubber`] since it started
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BackgroundScrubStats {
+    /// Slices run
+    pub slices: u64,
+    pub chunks_verified: u64,
+    pub chunks_damaged: u64,
+    /// Slices that failed (the error is logged and the next tick retries)
+    pub errors: u64,
+}
+
+struct State {
+    stats: BackgroundScrubStats,
+    closed: bool,
+}
+
+struct Shared {
+    scrubber: IncrementalScrubber,
+    config: BackgroundScrubConfig,
+    state: Mutex<State>,
+    wake: Condvar,
+}
+
+/// Runs an [`IncrementalScrubber`] on a background thread, one slice per
+/// tick. Dropping it stops the thread.
+///
+/// # Example
+///
+/// ```ignore
+/// let scrubber = BackgroundScrubber::start(
+///     IncrementalScrubber::new(Arc::clone(&store)).with_sink(alerts),
+///     BackgroundScrubConfig::default().with_fraction_per_hour(0.1),
+/// )?;
+/// ```
+pub struct BackgroundScrubber {
+    shared: Arc<Shared>,
+    worker: Option<JoinHandle<()>>,
+}
+
+impl BackgroundScrubber {
+    /// # Errors
+    /// - `ScrubError::JournalDisabled` if the store has no integrity journal
+    pub fn start(scrubber: IncrementalScrubber, config: BackgroundScrubConfig) -> Result<Self, ScrubError> {
+        scrubber.store.integrity_journal().ok_or(ScrubError::JournalDisabled)?;
+        let shared = Arc::new(Shared {
+            scrubber,
+            config,
+            state: Mutex::new(State { stats: BackgroundScrubStats::default(), closed: false }),
+            wake: Condvar::new(),
+        });
+        let worker = {
+            let shared = Arc::clone(&shared);
+            thread::spawn(move || shared.run())
+        };
+        Ok(Self { shared, worker: Some(worker) })
+    }
+
+    pub fn config(&self) -> BackgroundScrubConfig {
+        self.shared.config
+    }
+
+    pub fn stats(&self) -> BackgroundScrubStats {
+        self.shared.lock().stats.clone()
+    }
+}
+
+impl Drop for BackgroundScrubber {
+    fn drop(&mut self) {
+        self.shared.lock().closed = true;
+        self.shared.wake.notify_one();
+        if let Some(worker) = self.worker.take() {
+            let _ = worker.join();
+        }
+    }
+}
+
+impl Shared {
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Worker thread: one slice per tick until closed
+    fn run(&self) {
+        loop {
+            {
+                let state = self.lock();
+                let (state, _) = self
+                    .wake
+                    .wait_timeout_while(state, self.config.tick, |s| !s.closed)
+                    .unwrap_or_else(|e| e.into_inner());
+                if state.closed {
+                    return;
+                }
+            }
+
+            let result = self
+                .scrubber
+                .journaled()
+                .and_then(|total| self.scrubber.verify_next(self.config.slice_size(total)));
+            let mut state = self.lock();
+            state.stats.slices += 1;
+            match result {
+                Ok(report) => {
+                    state.stats.chunks_verified += report.chunks_checked as u64;
+                    state.stats.chunks_damaged += report.damaged.len() as u64;
+                }
+                Err(e) => {
+                    state.stats.errors += 1;
+                    warn!(error = %e, "Background scrub slice failed");
+                }
+            }
+        }
+    }
+}
+
+fn unix_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chunk_store::ChunkStoreConfig;
+
+    struct Collect(Mutex<Vec<String>>);
+
+    impl CorruptionSink for Collect {
+        fn on_corruption(&self, chunk: &DamagedChunk) {
+            self.0.lock().unwrap().push(chunk.hash.clone());
+        }
+    }
+
+    fn store(dir: &tempfile::TempDir) -> Arc<ChunkStore> {
+        let config = ChunkStoreConfig::new().with_integrity_journal(true);
+        Arc::new(ChunkStore::with_config(dir.path(), config).unwrap())
+    }
+
+    #[test]
+    fn test_slices_cover_least_recently_verified_first() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = store(&dir);
+        let hashes: Vec<String> = (0..5).map(|i| store.put(format!("chunk-{}", i).as_bytes()).unwrap()).collect();
+        let sink = Arc::new(Collect(Mutex::new(Vec::new())));
+        let scrub = IncrementalScrubber::new(Arc::clone(&store)).with_sink(sink.clone());
+
+        let first = scrub.verify_next(2).unwrap();
+        assert_eq!((first.chunks_checked, first.never_verified), (2, 3));
+        let second = scrub.verify_next(2).unwrap();
+        assert_eq!(second.never_verified, 1);
+
+        // Damage shows up in the slice that reaches the chunk, and a fresh
+        // scrubber resumes from the persisted timestamps
+        let verified = scrub.verified_at().unwrap();
+        let last = hashes.iter().find(|h| !verified.contains_key(*h)).unwrap();
+        let path = dir.path().join(&last[0..2]).join(&last[2..4]).join(last);
+        fs::write(&path, b"rot").unwrap();
+        let resumed = IncrementalScrubber::new(Arc::clone(&store)).with_sink(sink.clone());
+        let third = resumed.verify_next(1).unwrap();
+        assert_eq!(third.damaged.len(), 1);
+        assert_eq!(third.never_verified, 0);
+        assert_eq!(*sink.0.lock().unwrap(), vec![last.clone()]);
+
+        // Oldest verifications come around again
+        let fourth = resumed.verify_next(2).unwrap();
+        assert_eq!(fourth.chunks_intact, 2);
+        let mut sorted = hashes.clone();
+        sorted.sort();
+        let refreshed = resumed.verified_at().unwrap();
+        let oldest_refreshed = sorted[..2].iter().map(|h| refreshed[h]).min().unwrap();
+        assert!(sorted[2..].iter().all(|h| refreshed[h] <= oldest_refreshed));
+    }
+
+    #[test]
+    fn test_background_scrubber_runs_slices() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = store(&dir);
+        for i in 0..4 {
+            store.put(format!("chunk-{}", i).as_bytes()).unwrap();
+        }
+        let config = BackgroundScrubConfig::default().with_tick(Duration::from_millis(5));
+        assert_eq!(config.slice_size(4), 1);
+        let config = config.with_fraction_per_hour(1_000_000.0);
+        assert_eq!(config.slice_size(4), 4);
+        assert_eq!(BackgroundScrubConfig::default().slice_size(10_000), 7);
+
+        let scrubber = BackgroundScrubber::start(IncrementalScrubber::new(Arc::clone(&store)), config).unwrap();
+        let deadline = std::time::Instant::now() + Duration::from_secs(5);
+        while scrubber.stats().slices < 2 && std::time::Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(5));
+        }
+        drop(scrubber);
+        assert_eq!(IncrementalScrubber::new(Arc::clone(&store)).verified_at().unwrap().len(), 4);
+
+        let plain = Arc::new(ChunkStore::new(tempfile::tempdir().unwrap().path()).unwrap());
+        assert!(matches!(
+            BackgroundScrubber::start(IncrementalScrubber::new(plain), config),
+            Err(ScrubError::JournalDisabled)
+        ));
+    }
+}
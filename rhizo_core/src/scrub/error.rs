@@ -0,0 +1,56 @@
+use thiserror::Error;
+
+use crate::branch::BranchError;
+use crate::catalog::CatalogError;
+use crate::chunk_store::ChunkStoreError;
+use crate::error::{ErrorCategory, ErrorContext, ErrorInfo};
+
+#[derive(Error, Debug)]
+#[non_exhaustive]
+pub enum ScrubError {
+    #[error("Chunk store has no integrity journal")]
+    JournalDisabled,
+
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Catalog error: {0}")]
+    Catalog(#[from] CatalogError),
+
+    #[error("Chunk store error: {0}")]
+    ChunkStore(#[from] ChunkStoreError),
+
+    #[error("Branch error: {0}")]
+    Branch(#[from] BranchError),
+}
+
+impl ErrorInfo for ScrubError {
+    fn code(&self) -> &'static str {
+        match self {
+            ScrubError::JournalDisabled => "SCRUB_JOURNAL_DISABLED",
+            ScrubError::Io(_) => "SCRUB_IO",
+            ScrubError::Catalog(e) => e.code(),
+            ScrubError::ChunkStore(e) => e.code(),
+            ScrubError::Branch(e) => e.code(),
+        }
+    }
+
+    fn category(&self) -> ErrorCategory {
+        match self {
+            ScrubError::JournalDisabled => ErrorCategory::InvalidArgument,
+            ScrubError::Io(_) => ErrorCategory::Io,
+            ScrubError::Catalog(e) => e.category(),
+            ScrubError::ChunkStore(e) => e.category(),
+            ScrubError::Branch(e) => e.category(),
+        }
+    }
+
+    fn context(&self) -> ErrorContext {
+        match self {
+            ScrubError::JournalDisabled | ScrubError::Io(_) => ErrorContext::new(),
+            ScrubError::Catalog(e) => e.context(),
+            ScrubError::ChunkStore(e) => e.context(),
+            ScrubError::Branch(e) => e.context(),
+        }
+    }
+}
@@ -0,0 +1,19 @@
+//! Integrity scrubbing of the chunk store.
+//!
+//! [`ChunkScrubber`] compares every journaled chunk file against its
+//! integrity journal record (see
+//! [`ChunkStore::with_integrity_journal`](crate::ChunkStore::with_integrity_journal)),
+//! classifies damaged chunks as missing, truncated, extended or bit rot,
+//! and reports the table versions and branches that read them.
+//! [`BackgroundScrubber`] spreads the same check over time, verifying a
+//! slice of the least recently verified chunks every tick.
+
+pub mod background;
+pub mod error;
+pub mod scrubber;
+
+pub use background::{
+    BackgroundScrubConfig, BackgroundScrubStats, BackgroundScrubber, CorruptionSink, IncrementalScrubber, SliceReport,
+};
+pub use error::ScrubError;
+pub use scrubber::{ChunkScrubber, DamagedChunk, ScrubReport};
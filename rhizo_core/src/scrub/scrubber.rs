@@ -0,0 +1,288 @@
+//! Journal-driven scrub of the chunk store.
+//!
+//! Every chunk with an integrity journal record is read back and compared
+//! against it. Damage found is traced to its readers: a table version is
+//! impacted if it references a damaged chunk, directly or through the
+//! delta chain of a chunk it references, and a branch is impacted if one
+//! of its heads is an impacted version. With branches attached, heads that
+//! point at versions missing from the catalog are reported too (see
+//! `BranchManager::repair_branch`).
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+
+use super::error::ScrubError;
+use crate::branch::{BranchManager, DanglingHead};
+use crate::catalog::FileCatalog;
+use crate::chunk_store::{ChunkCondition, ChunkStore};
+
+/// A chunk whose file no longer matches its journal record.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DamagedChunk {
+    pub hash: String,
+    pub condition: ChunkCondition,
+    /// Pipeline stages the chunk was stored through
+    pub codec: String,
+    /// Unix timestamp (seconds) when the chunk was written
+    pub created_at: u64,
+}
+
+/// Outcome of a scrub.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ScrubReport {
+    /// Journaled chunks compared against their files
+    pub chunks_checked: usize,
+    /// Chunks whose size and CRC matched
+    pub chunks_intact: usize,
+    /// Journaled chunks not in the local cache of a backend-backed store
+    pub chunks_not_cached: usize,
+    /// Stored chunks without a journal record (written before the
+    /// journal was enabled); not checked
+    pub chunks_unjournaled: usize,
+    /// Chunks that failed the check, by hash
+    pub damaged: Vec<DamagedChunk>,
+    /// Versions referencing a damaged chunk: table name -> versions
+    pub impacted_versions: BTreeMap<String, Vec<u64>>,
+    /// Branches whose head is an impacted version
+    pub impacted_branches: Vec<String>,
+    /// Heads naming versions missing from the catalog: branch -> heads
+    pub dangling_heads: BTreeMap<String, Vec<DanglingHead>>,
+}
+
+impl ScrubReport {
+    /// True if no damaged chunk or dangling branch head was found
+    pub fn is_clean(&self) -> bool {
+        self.damaged.is_empty() && self.dangling_heads.is_empty()
+    }
+}
+
+/// Compares chunk files against the store's integrity journal.
+///
+/// # Example
+///
+/// ```ignore
+/// let report = ChunkScrubber::new(&catalog, &store)
+///     .with_branches(&branch_manager)
+///     .run()?;
+/// for chunk in &report.damaged {
+///     println!("{}: {:?}", chunk.hash, chunk.condition);
+/// }
+/// ```
+pub struct ChunkScrubber<'a> {
+    catalog: &'a FileCatalog,
+    store: &'a ChunkStore,
+    branches: Option<&'a BranchManager>,
+}
+
+impl<'a> ChunkScrubber<'a> {
+    pub fn new(catalog: &'a FileCatalog, store: &'a ChunkStore) -> Self {
+        Self {
+            catalog,
+            store,
+            branches: None,
+        }
+    }
+
+    /// Report the branches of `branches` whose heads read damaged chunks
+    /// or point at missing versions.
+    pub fn with_branches(mut self, branches: &'a BranchManager) -> Self {
+        self.branches = Some(branches);
+        self
+    }
+
+    /// # Errors
+    /// - `ScrubError::JournalDisabled` if the store has no integrity journal
+    pub fn run(&self) -> Result<ScrubReport, ScrubError> {
+        let journal = self.store.integrity_journal().ok_or(ScrubError::JournalDisabled)?;
+        let entries = journal.entries()?;
+
+        let mut report = ScrubReport::default();
+        let mut hashes: Vec<&String> = entries.keys().collect();
+        hashes.sort();
+        for hash in hashes {
+            let entry = &entries[hash];
+            report.chunks_checked += 1;
+            match self.store.check_integrity(entry)? {
+                ChunkCondition::Intact => report.chunks_intact += 1,
+                ChunkCondition::NotCached => report.chunks_not_cached += 1,
+                condition => report.damaged.push(DamagedChunk {
+                    hash: hash.clone(),
+                    condition,
+                    codec: entry.codec.clone(),
+                    created_at: entry.created_at,
+                }),
+            }
+        }
+        report.chunks_unjournaled = self
+            .store
+            .list_chunk_hashes()?
+            .iter()
+            .filter(|hash| !entries.contains_key(*hash))
+            .count();
+
+        if !report.damaged.is_empty() {
+            let damaged: HashSet<&str> = report.damaged.iter().map(|c| c.hash.as_str()).collect();
+            report.impacted_versions = self.impacted_versions(&damaged)?;
+            report.impacted_branches = self.impacted_branches(&report.impacted_versions)?;
+        }
+        if let Some(manager) = self.branches {
+            for name in manager.list()? {
+                let dangling = manager.dangling_heads(&name, self.catalog)?;
+                if !dangling.is_empty() {
+                    report.dangling_heads.insert(name, dangling);
+                }
+            }
+        }
+        Ok(report)
+    }
+
+    fn impacted_versions(&self, damaged: &HashSet<&str>) -> Result<BTreeMap<String, Vec<u64>>, ScrubError> {
+        let mut impacted = BTreeMap::new();
+        for table in self.catalog.list_tables()? {
+            let mut versions = Vec::new();
+            for version in self.catalog.list_versions(&table)? {
+                let record = self.catalog.get_version(&table, Some(version))?;
+                let reads_damaged = record.chunk_hashes.iter().any(|h| damaged.contains(h.as_str()))
+                    || self
+                        .store
+                        .delta_bases(record.chunk_hashes.iter().map(String::as_str))?
+                        .iter()
+                        .any(|h| damaged.contains(h.as_str()));
+                if reads_damaged {
+                    versions.push(version);
+                }
+            }
+            if !versions.is_empty() {
+                impacted.insert(table, versions);
+            }
+        }
+        Ok(impacted)
+    }
+
+    fn impacted_branches(&self, impacted: &BTreeMap<String, Vec<u64>>) -> Result<Vec<String>, ScrubError> {
+        let Some(manager) = self.branches else {
+            return Ok(Vec::new());
+        };
+        let impacted: HashMap<&str, BTreeSet<u64>> = impacted
+            .iter()
+            .map(|(table, versions)| (table.as_str(), versions.iter().copied().collect()))
+            .collect();
+        let mut branches = Vec::new();
+        for name in manager.list()? {
+            let branch = manager.get(&name)?;
+            let reads_damaged = branch
+                .head
+                .iter()
+                .any(|(table, version)| impacted.get(table.as_str()).is_some_and(|v| v.contains(version)));
+            if reads_damaged {
+                branches.push(name);
+            }
+        }
+        Ok(branches)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::catalog::TableVersion;
+    use crate::chunk_store::ChunkStoreConfig;
+    use crate::repo::Repo;
+    use std::fs;
+    use std::path::PathBuf;
+
+    struct TestRepo {
+        repo: Repo,
+    }
+
+    impl TestRepo {
+        fn new() -> Self {
+            let repo = Repo::ephemeral().unwrap();
+            let config = ChunkStoreConfig::new().with_integrity_journal(true);
+            let store = ChunkStore::with_config(repo.chunks_path(), config).unwrap();
+            Self {
+                repo: repo.with_store(store),
+            }
+        }
+
+        fn scrubber(&self) -> ChunkScrubber<'_> {
+            ChunkScrubber::new(self.repo.catalog(), self.repo.store())
+        }
+
+        fn write(&self, table: &str, version: u64, data: &[u8]) -> String {
+            let hash = self.repo.store().put(data).unwrap();
+            self.repo.catalog().commit(TableVersion::new(table, version, vec![hash.clone()])).unwrap();
+            hash
+        }
+
+        fn chunk_path(&self, hash: &str) -> PathBuf {
+            self.repo.chunks_path().join(&hash[0..2]).join(&hash[2..4]).join(hash)
+        }
+    }
+
+    #[test]
+    fn test_clean_store() {
+        let repo = TestRepo::new();
+        repo.write("users", 1, b"alice,bob");
+        let report = repo.scrubber().run().unwrap();
+        assert!(report.is_clean());
+        assert_eq!((report.chunks_checked, report.chunks_intact), (1, 1));
+    }
+
+    #[test]
+    fn test_distinguishes_truncation_from_bit_rot_and_reports_impact() {
+        let repo = TestRepo::new();
+        let truncated = repo.write("users", 1, b"alice,bob,carol");
+        let rotted = repo.write("users", 2, b"alice,bob,dave!");
+        repo.write("orders", 1, b"order-1");
+        let branches = repo.repo.branches();
+        branches.update_head("main", "users", 2).unwrap();
+        branches.update_head("main", "orders", 1).unwrap();
+        branches.create("old-users", None, None).unwrap();
+        branches.update_head("old-users", "users", 1).unwrap();
+
+        fs::write(repo.chunk_path(&truncated), b"alice,").unwrap();
+        let mut bytes = fs::read(repo.chunk_path(&rotted)).unwrap();
+        bytes[3] ^= 0x10;
+        fs::write(repo.chunk_path(&rotted), bytes).unwrap();
+
+        let report = repo.scrubber().with_branches(branches).run().unwrap();
+
+        assert_eq!(report.chunks_intact, 1);
+        let condition = |hash: &str| report.damaged.iter().find(|c| c.hash == hash).unwrap().condition;
+        assert_eq!(condition(&truncated), ChunkCondition::Truncated { expected: 15, actual: 6 });
+        assert!(matches!(condition(&rotted), ChunkCondition::BitRot { .. }));
+        assert_eq!(report.impacted_versions, BTreeMap::from([("users".to_string(), vec![1, 2])]));
+        assert_eq!(report.impacted_branches, vec!["main".to_string(), "old-users".to_string()]);
+    }
+
+    #[test]
+    fn test_unjournaled_chunks_are_counted_not_checked() {
+        let repo = TestRepo::new();
+        let plain = ChunkStore::new(repo.repo.chunks_path()).unwrap();
+        plain.put(b"written before the journal").unwrap();
+        repo.write("users", 1, b"journaled");
+
+        let report = repo.scrubber().run().unwrap();
+        assert_eq!((report.chunks_checked, report.chunks_unjournaled), (1, 1));
+
+        let err = ChunkScrubber::new(repo.repo.catalog(), &plain).run().unwrap_err();
+        assert!(matches!(err, ScrubError::JournalDisabled));
+    }
+
+    #[test]
+    fn test_reports_dangling_branch_heads() {
+        let repo = TestRepo::new();
+        repo.write("users", 1, b"alice");
+        repo.write("users", 2, b"alice,bob");
+        let branches = repo.repo.branches();
+        branches.update_head("main", "users", 3).unwrap();
+        assert!(repo.scrubber().run().unwrap().is_clean());
+
+        let report = repo.scrubber().with_branches(branches).run().unwrap();
+        assert!(report.damaged.is_empty() && !report.is_clean());
+        assert_eq!(
+            report.dangling_heads["main"],
+            vec![DanglingHead { table: "users".to_string(), version: 3, rollback_to: Some(2) }]
+        );
+    }
+}
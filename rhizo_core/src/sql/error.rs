@@ -0,0 +1,81 @@
+use std::time::Duration;
+
+use thiserror::Error;
+
+use crate::error::{io_retry_after, ErrorCategory, ErrorContext, ErrorInfo};
+use crate::table::TableError;
+use crate::transaction::TransactionError;
+
+#[derive(Error, Debug)]
+#[non_exhaustive]
+pub enum SqlError {
+    #[error("Invalid time travel clause: {0}")]
+    InvalidTimeTravel(String),
+
+    #[error("Table not in read snapshot of branch {branch}: {table}")]
+    TableNotInSnapshot { table: String, branch: String },
+
+    #[error("Table error: {0}")]
+    Table(#[from] TableError),
+
+    #[error("Transaction error: {0}")]
+    Transaction(#[from] TransactionError),
+
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[cfg(feature = "datafusion")]
+    #[error("DataFusion error: {0}")]
+    DataFusion(#[from] datafusion::error::DataFusionError),
+}
+
+impl ErrorInfo for SqlError {
+    fn code(&self) -> &'static str {
+        match self {
+            SqlError::InvalidTimeTravel(_) => "SQL_INVALID_TIME_TRAVEL",
+            SqlError::TableNotInSnapshot { .. } => "SQL_TABLE_NOT_IN_SNAPSHOT",
+            SqlError::Table(e) => e.code(),
+            SqlError::Transaction(e) => e.code(),
+            SqlError::Io(_) => "SQL_IO",
+            #[cfg(feature = "datafusion")]
+            SqlError::DataFusion(_) => "SQL_DATAFUSION",
+        }
+    }
+
+    fn category(&self) -> ErrorCategory {
+        match self {
+            SqlError::InvalidTimeTravel(_) => ErrorCategory::InvalidArgument,
+            SqlError::TableNotInSnapshot { .. } => ErrorCategory::NotFound,
+            SqlError::Table(e) => e.category(),
+            SqlError::Transaction(e) => e.category(),
+            SqlError::Io(_) => ErrorCategory::Io,
+            #[cfg(feature = "datafusion")]
+            SqlError::DataFusion(e) => match e {
+                datafusion::error::DataFusionError::SQL(..)
+                | datafusion::error::DataFusionError::Plan(_)
+                | datafusion::error::DataFusionError::SchemaError(..) => ErrorCategory::InvalidArgument,
+                _ => ErrorCategory::Internal,
+            },
+        }
+    }
+
+    fn context(&self) -> ErrorContext {
+        match self {
+            SqlError::TableNotInSnapshot { table, branch } => {
+                ErrorContext::new().with_table(table).with_branch(branch)
+            }
+            SqlError::Table(e) => e.context(),
+            SqlError::Transaction(e) => e.context(),
+            _ => ErrorContext::new(),
+        }
+    }
+
+    fn retry_after(&self) -> Option<Duration> {
+        match self {
+            SqlError::Table(e) => e.retry_after(),
+            SqlError::Transaction(e) => e.retry_after(),
+            SqlError::Io(e) => io_retry_after(e),
+            _ => None,
+        }
+    }
+}
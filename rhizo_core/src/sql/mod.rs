@@ -0,0 +1,25 @@
+//! SQL over catalog snapshots and branches with DataFusion.
+//!
+//! [`rewrite_time_travel`] turns `FOR VERSION AS OF` and `FOR BRANCH AS OF`
+//! table clauses into table function calls DataFusion can plan. With the
+//! `datafusion` feature, [`SqlSession`] serves catalog tables to DataFusion
+//! as [`RhizoTable`] providers resolved against one
+//! [`ReadSnapshot`](crate::transaction::ReadSnapshot), and registers those
+//! table functions. The Python `OLAPEngine` wraps the `datafusion` Python
+//! package instead and does not need the feature.
+
+pub mod error;
+pub mod time_travel;
+
+#[cfg(feature = "datafusion")]
+pub mod provider;
+#[cfg(feature = "datafusion")]
+pub mod session;
+
+pub use error::SqlError;
+pub use time_travel::{rewrite_time_travel, BRANCH_FUNCTION, VERSION_FUNCTION};
+
+#[cfg(feature = "datafusion")]
+pub use provider::RhizoTable;
+#[cfg(feature = "datafusion")]
+pub use session::SqlSession;
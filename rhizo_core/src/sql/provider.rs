@@ -0,0 +1,219 @@
+//! DataFusion table providers over catalog versions.
+
+use std::any::Any;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+use arrow::datatypes::{DataType, Schema, SchemaRef};
+use arrow::record_batch::RecordBatch;
+use async_trait::async_trait;
+use datafusion::catalog::{SchemaProvider, Session, TableFunctionImpl, TableProvider};
+use datafusion::datasource::MemTable;
+use datafusion::error::{DataFusionError, Result as DfResult};
+use datafusion::logical_expr::{Expr, TableType};
+use datafusion::physical_plan::ExecutionPlan;
+use datafusion::scalar::ScalarValue;
+use parking_lot::Mutex;
+
+use super::error::SqlError;
+use super::time_travel::{BRANCH_FUNCTION, VERSION_FUNCTION};
+use crate::catalog::{FileCatalog, TableVersion};
+use crate::chunk_store::ChunkStore;
+use crate::table::{TableError, TableReader};
+use crate::transaction::{ReadSnapshot, TransactionManager};
+
+/// One table version, loaded into memory for DataFusion to scan.
+#[derive(Debug)]
+pub struct RhizoTable {
+    version: TableVersion,
+    data: MemTable,
+}
+
+impl RhizoTable {
+    /// Load every chunk of `version`.
+    pub fn load(store: &ChunkStore, catalog: &FileCatalog, version: TableVersion) -> Result<Self, SqlError> {
+        let batches = TableReader::new(store, catalog).read_version(&version)?;
+        let schema = batches
+            .first()
+            .map(RecordBatch::schema)
+            .unwrap_or_else(|| Arc::new(Schema::empty()));
+        let data = MemTable::try_new(schema, vec![batches])?;
+        Ok(Self { version, data })
+    }
+
+    /// The catalog version this table was loaded from
+    pub fn version(&self) -> &TableVersion {
+        &self.version
+    }
+}
+
+#[async_trait]
+impl TableProvider for RhizoTable {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.data.schema()
+    }
+
+    fn table_type(&self) -> TableType {
+        TableType::Base
+    }
+
+    async fn scan(
+        &self,
+        state: &dyn Session,
+        projection: Option<&Vec<usize>>,
+        filters: &[Expr],
+        limit: Option<usize>,
+    ) -> DfResult<Arc<dyn ExecutionPlan>> {
+        self.data.scan(state, projection, filters, limit).await
+    }
+}
+
+/// Read points shared by a session's schema and table functions.
+///
+/// Each table version is loaded at most once per session, and a branch's
+/// snapshot is captured the first time it is named, so every reference to
+/// it in later queries sees the same heads.
+pub(crate) struct Snapshots {
+    store: Arc<ChunkStore>,
+    manager: Arc<TransactionManager>,
+    snapshot: ReadSnapshot,
+    branches: Mutex<HashMap<String, ReadSnapshot>>,
+    loaded: Mutex<HashMap<(String, u64), Arc<RhizoTable>>>,
+}
+
+impl fmt::Debug for Snapshots {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Snapshots")
+            .field("branch", &self.snapshot.branch)
+            .field("tables", &self.snapshot.tables.len())
+            .finish()
+    }
+}
+
+impl Snapshots {
+    pub(crate) fn new(store: Arc<ChunkStore>, manager: Arc<TransactionManager>, snapshot: ReadSnapshot) -> Self {
+        Self {
+            store,
+            manager,
+            snapshot,
+            branches: Mutex::new(HashMap::new()),
+            loaded: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub(crate) fn snapshot(&self) -> &ReadSnapshot {
+        &self.snapshot
+    }
+
+    fn load(&self, table: &str, version: u64) -> Result<Arc<RhizoTable>, SqlError> {
+        let key = (table.to_string(), version);
+        if let Some(loaded) = self.loaded.lock().get(&key) {
+            return Ok(Arc::clone(loaded));
+        }
+        let catalog = self.manager.catalog();
+        let record = catalog.get_version(table, Some(version)).map_err(TableError::from)?;
+        let loaded = Arc::new(RhizoTable::load(&self.store, catalog, record)?);
+        self.loaded.lock().insert(key, Arc::clone(&loaded));
+        Ok(loaded)
+    }
+
+    fn load_on_branch(&self, table: &str, branch: &str) -> Result<Arc<RhizoTable>, SqlError> {
+        let version = {
+            let mut branches = self.branches.lock();
+            if !branches.contains_key(branch) {
+                let snapshot = self.manager.read_snapshot(Some(branch))?;
+                branches.insert(branch.to_string(), snapshot);
+            }
+            branches[branch].get(table).map(|t| t.version).ok_or_else(|| SqlError::TableNotInSnapshot {
+                table: table.to_string(),
+                branch: branch.to_string(),
+            })?
+        };
+        self.load(table, version)
+    }
+}
+
+fn external(e: SqlError) -> DataFusionError {
+    DataFusionError::External(Box::new(e))
+}
+
+/// The session's default schema: every table in its read snapshot
+#[derive(Debug)]
+pub(crate) struct RhizoSchema(pub(crate) Arc<Snapshots>);
+
+#[async_trait]
+impl SchemaProvider for RhizoSchema {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn table_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.0.snapshot.tables.keys().cloned().collect();
+        names.sort();
+        names
+    }
+
+    async fn table(&self, name: &str) -> DfResult<Option<Arc<dyn TableProvider>>> {
+        match self.0.snapshot.get(name) {
+            Some(t) => Ok(Some(self.0.load(name, t.version).map_err(external)?)),
+            None => Ok(None),
+        }
+    }
+
+    fn table_exist(&self, name: &str) -> bool {
+        self.0.snapshot.get(name).is_some()
+    }
+}
+
+fn literal<'a>(function: &str, arg: &'a Expr) -> DfResult<&'a ScalarValue> {
+    match arg {
+        Expr::Literal(value, ..) => Ok(value),
+        _ => Err(DataFusionError::Plan(format!("{} arguments must be literals, got {}", function, arg))),
+    }
+}
+
+fn table_arg(function: &str, arg: &Expr) -> DfResult<String> {
+    literal(function, arg)?
+        .try_as_str()
+        .flatten()
+        .map(str::to_string)
+        .ok_or_else(|| DataFusionError::Plan(format!("{} needs a table name string", function)))
+}
+
+/// `rhizo_version(table, version)`: a table at a catalog version
+#[derive(Debug)]
+pub(crate) struct VersionFunction(pub(crate) Arc<Snapshots>);
+
+impl TableFunctionImpl for VersionFunction {
+    fn call(&self, args: &[Expr]) -> DfResult<Arc<dyn TableProvider>> {
+        let [table, version] = args else {
+            return Err(DataFusionError::Plan(format!("{}(table, version) takes two arguments", VERSION_FUNCTION)));
+        };
+        let table = table_arg(VERSION_FUNCTION, table)?;
+        let version = match literal(VERSION_FUNCTION, version)?.cast_to(&DataType::UInt64)? {
+            ScalarValue::UInt64(Some(version)) => version,
+            _ => return Err(DataFusionError::Plan(format!("{} needs a version number", VERSION_FUNCTION))),
+        };
+        Ok(self.0.load(&table, version).map_err(external)?)
+    }
+}
+
+/// `rhizo_branch(table, branch)`: a table at a branch head
+#[derive(Debug)]
+pub(crate) struct BranchFunction(pub(crate) Arc<Snapshots>);
+
+impl TableFunctionImpl for BranchFunction {
+    fn call(&self, args: &[Expr]) -> DfResult<Arc<dyn TableProvider>> {
+        let [table, branch] = args else {
+            return Err(DataFusionError::Plan(format!("{}(table, branch) takes two arguments", BRANCH_FUNCTION)));
+        };
+        let table = table_arg(BRANCH_FUNCTION, table)?;
+        let branch = table_arg(BRANCH_FUNCTION, branch)?;
+        Ok(self.0.load_on_branch(&table, &branch).map_err(external)?)
+    }
+}
@@ -0,0 +1,260 @@
+//! Time-travel clauses on table references.
+//!
+//! DataFusion's SQL dialects have no `AS OF` clause for arbitrary tables,
+//! so the clauses are rewritten into table function calls before planning:
+//!
+//! ```text
+//! users FOR VERSION AS OF 3       ->  rhizo_version('users', 3) AS users
+//! users FOR BRANCH AS OF 'dev'    ->  rhizo_branch('users', 'dev') AS users
+//! users FOR VERSION AS OF 3 u     ->  rhizo_version('users', 3) u
+//! ```
+//!
+//! The clause must directly follow the table name. A reference keeps its
+//! name as an alias unless it has one of its own, so qualified columns
+//! (`users.id`) still resolve. String literals, quoted identifiers and
+//! comments are left alone.
+
+use super::error::SqlError;
+
+/// Table function reading a table at a catalog version
+pub const VERSION_FUNCTION: &str = "rhizo_version";
+
+/// Table function reading a table at a branch head
+pub const BRANCH_FUNCTION: &str = "rhizo_branch";
+
+/// Words that end a table reference, so cannot be an alias after it
+const CLAUSE_KEYWORDS: &[&str] = &[
+    "where", "join", "inner", "left", "right", "full", "cross", "natural", "on", "using", "group",
+    "order", "limit", "offset", "having", "union", "except", "intersect", "window", "qualify",
+    "lateral", "for",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TokenKind {
+    Word,
+    QuotedIdent,
+    StringLit,
+    Number,
+    Punct,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Token {
+    kind: TokenKind,
+    start: usize,
+    end: usize,
+}
+
+/// Split SQL into tokens, dropping whitespace and comments
+fn tokenize(sql: &str) -> Result<Vec<Token>, SqlError> {
+    let bytes = sql.as_bytes();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        let start = i;
+        let c = bytes[i];
+        let kind = match c {
+            _ if c.is_ascii_whitespace() => {
+                i += 1;
+                continue;
+            }
+            b'-' if bytes.get(i + 1) == Some(&b'-') => {
+                while i < bytes.len() && bytes[i] != b'\n' {
+                    i += 1;
+                }
+                continue;
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                let end = sql[i + 2..]
+                    .find("*/")
+                    .ok_or_else(|| SqlError::InvalidTimeTravel("unterminated comment".to_string()))?;
+                i += end + 4;
+                continue;
+            }
+            b'\'' | b'"' => {
+                // Quotes are escaped by doubling them
+                i += 1;
+                loop {
+                    match bytes.get(i) {
+                        None => {
+                            return Err(SqlError::InvalidTimeTravel(format!(
+                                "unterminated quote at offset {}",
+                                start
+                            )))
+                        }
+                        Some(&q) if q == c && bytes.get(i + 1) == Some(&c) => i += 2,
+                        Some(&q) if q == c => break,
+                        Some(_) => i += 1,
+                    }
+                }
+                i += 1;
+                if c == b'\'' {
+                    TokenKind::StringLit
+                } else {
+                    TokenKind::QuotedIdent
+                }
+            }
+            b'0'..=b'9' => {
+                while i < bytes.len() && (bytes[i].is_ascii_alphanumeric() || bytes[i] == b'.') {
+                    i += 1;
+                }
+                TokenKind::Number
+            }
+            _ if c == b'_' || c.is_ascii_alphabetic() || c >= 0x80 => {
+                while i < bytes.len()
+                    && (bytes[i] == b'_' || bytes[i] == b'$' || bytes[i].is_ascii_alphanumeric() || bytes[i] >= 0x80)
+                {
+                    i += 1;
+                }
+                TokenKind::Word
+            }
+            _ => {
+                i += 1;
+                TokenKind::Punct
+            }
+        };
+        tokens.push(Token { kind, start, end: i });
+    }
+    Ok(tokens)
+}
+
+/// Unquote a `'...'` or `"..."` token
+fn unquote(text: &str) -> String {
+    let quote = &text[..1];
+    text[1..text.len() - 1].replace(&quote.repeat(2), quote)
+}
+
+/// Rewrite `FOR VERSION AS OF <n>` and `FOR BRANCH AS OF '<name>'` table
+/// clauses into [`VERSION_FUNCTION`] and [`BRANCH_FUNCTION`] calls.
+///
+/// SQL without such clauses is returned unchanged.
+///
+/// # Errors
+/// `InvalidTimeTravel` if a clause does not follow a plain table name, has
+/// a malformed version or branch, or is combined with a second clause.
+pub fn rewrite_time_travel(sql: &str) -> Result<String, SqlError> {
+    let tokens = tokenize(sql)?;
+    let text = |t: &Token| &sql[t.start..t.end];
+    let is_word = |t: Option<&Token>, word: &str| {
+        t.is_some_and(|t| t.kind == TokenKind::Word && text(t).eq_ignore_ascii_case(word))
+    };
+
+    let mut out = String::with_capacity(sql.len());
+    let mut copied = 0;
+    let mut i = 0;
+    while i < tokens.len() {
+        let by_version = is_word(tokens.get(i + 1), "version");
+        if !(is_word(tokens.get(i), "for")
+            && (by_version || is_word(tokens.get(i + 1), "branch"))
+            && is_word(tokens.get(i + 2), "as")
+            && is_word(tokens.get(i + 3), "of"))
+        {
+            i += 1;
+            continue;
+        }
+        let clause = &sql[tokens[i].start..tokens.get(i + 3).map_or(sql.len(), |t| t.end)];
+
+        let table = match i.checked_sub(1).map(|j| tokens[j]) {
+            Some(t) if matches!(t.kind, TokenKind::Word | TokenKind::QuotedIdent) => t,
+            _ => return Err(SqlError::InvalidTimeTravel(format!("{} must follow a table name", clause))),
+        };
+        if i >= 2 && text(&tokens[i - 2]) == "." {
+            return Err(SqlError::InvalidTimeTravel(format!(
+                "{} needs an unqualified table name",
+                clause
+            )));
+        }
+        // Unquoted names are matched lowercased, like the rest of the query
+        let table_name = match table.kind {
+            TokenKind::Word => text(&table).to_lowercase(),
+            _ => unquote(text(&table)),
+        };
+
+        let point = tokens.get(i + 4);
+        let (function, argument) = match point {
+            Some(t) if by_version && t.kind == TokenKind::Number && text(t).parse::<u64>().is_ok() => {
+                (VERSION_FUNCTION, text(t).to_string())
+            }
+            Some(t) if !by_version && t.kind == TokenKind::StringLit => (BRANCH_FUNCTION, text(t).to_string()),
+            _ => {
+                let expected = if by_version { "a version number" } else { "a quoted branch name" };
+                return Err(SqlError::InvalidTimeTravel(format!("{} needs {}", clause, expected)));
+            }
+        };
+        if is_word(tokens.get(i + 5), "for") {
+            return Err(SqlError::InvalidTimeTravel(format!(
+                "{} has more than one time travel clause",
+                table_name
+            )));
+        }
+
+        out.push_str(&sql[copied..table.start]);
+        out.push_str(&format!("{}('{}', {})", function, table_name.replace('\'', "''"), argument));
+        let has_alias = match tokens.get(i + 5) {
+            Some(t) if t.kind == TokenKind::QuotedIdent => true,
+            Some(t) if t.kind == TokenKind::Word => {
+                !CLAUSE_KEYWORDS.iter().any(|k| text(t).eq_ignore_ascii_case(k))
+            }
+            _ => false,
+        };
+        if !has_alias {
+            out.push_str(" AS ");
+            out.push_str(text(&table));
+        }
+        copied = tokens[i + 4].end;
+        i += 5;
+    }
+    out.push_str(&sql[copied..]);
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rewrites_clauses_keeping_aliases() {
+        assert_eq!(
+            rewrite_time_travel("SELECT * FROM users FOR VERSION AS OF 3 WHERE id > 1").unwrap(),
+            "SELECT * FROM rhizo_version('users', 3) AS users WHERE id > 1"
+        );
+        assert_eq!(
+            rewrite_time_travel(
+                "select c.id from Users c join users for branch as of 'feature/x' AS old on c.id = old.id"
+            )
+            .unwrap(),
+            "select c.id from Users c join rhizo_branch('users', 'feature/x') AS old on c.id = old.id"
+        );
+        assert_eq!(
+            rewrite_time_travel(r#"SELECT 1 FROM "My Table" FOR VERSION AS OF 2 t, orders"#).unwrap(),
+            r#"SELECT 1 FROM rhizo_version('My Table', 2) t, orders"#
+        );
+    }
+
+    #[test]
+    fn test_leaves_literals_and_comments_alone() {
+        let sql = "SELECT 'x FOR VERSION AS OF 1' AS s FROM t -- t FOR VERSION AS OF 2\n";
+        assert_eq!(rewrite_time_travel(sql).unwrap(), sql);
+        assert_eq!(rewrite_time_travel("SELECT 1").unwrap(), "SELECT 1");
+    }
+
+    #[test]
+    fn test_rejects_malformed_clauses() {
+        for sql in [
+            "SELECT * FROM users FOR VERSION AS OF 'dev'",
+            "SELECT * FROM users FOR BRANCH AS OF 3",
+            "SELECT * FROM users FOR VERSION AS OF 1.5",
+            "SELECT * FROM s.users FOR VERSION AS OF 1",
+            "SELECT * FROM users FOR VERSION AS OF 1 FOR BRANCH AS OF 'dev'",
+            "SELECT * FROM (SELECT 1) FOR VERSION AS OF 1",
+            "SELECT * FROM users FOR VERSION AS OF",
+            "SELECT 'unterminated",
+        ] {
+            assert!(
+                matches!(rewrite_time_travel(sql), Err(SqlError::InvalidTimeTravel(_))),
+                "{}",
+                sql
+            );
+        }
+    }
+}
@@ -0,0 +1,146 @@
+use std::time::Duration;
+
+use thiserror::Error;
+
+use crate::catalog::CatalogError;
+use crate::chunk_store::ChunkStoreError;
+use crate::error::{io_retry_after, ErrorCategory, ErrorContext, ErrorInfo};
+use crate::parquet::ParquetError;
+use crate::transaction::TransactionError;
+
+#[derive(Error, Debug)]
+#[non_exhaustive]
+pub enum TableError {
+    #[error("No record batches to write to {0}")]
+    EmptyInput(String),
+
+    #[error("Batches written to {table} do not share one schema")]
+    SchemaMismatch { table: String },
+
+    #[error("Chunk {hash} of {table} v{version} has schema {found}, expected {expected}")]
+    SchemaHashMismatch {
+        table: String,
+        version: u64,
+        hash: String,
+        expected: String,
+        found: String,
+    },
+
+    #[error("Chunk {hash} of {table} v{version} holds no record batch")]
+    EmptyChunk {
+        table: String,
+        version: u64,
+        hash: String,
+    },
+
+    #[error("{table} v{version} uses unknown chunk format {format}")]
+    UnknownFormat {
+        table: String,
+        version: u64,
+        format: String,
+    },
+
+    #[error("Invalid filter for {table}: {reason}")]
+    InvalidFilter { table: String, reason: String },
+
+    #[error("Invalid row patch for {table}: {reason}")]
+    InvalidPatch { table: String, reason: String },
+
+    #[error("Patch chunk {hash} of {table} v{version} is corrupt: {reason}")]
+    CorruptPatch {
+        table: String,
+        version: u64,
+        hash: String,
+        reason: String,
+    },
+
+    #[error("Catalog error: {0}")]
+    Catalog(#[from] CatalogError),
+
+    #[error("Chunk store error: {0}")]
+    ChunkStore(#[from] ChunkStoreError),
+
+    #[error("Parquet error: {0}")]
+    Parquet(#[from] ParquetError),
+
+    #[error("Arrow error: {0}")]
+    Arrow(#[from] arrow::error::ArrowError),
+
+    #[error("Transaction error: {0}")]
+    Transaction(#[from] TransactionError),
+
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+impl ErrorInfo for TableError {
+    fn code(&self) -> &'static str {
+        match self {
+            TableError::EmptyInput(_) => "TABLE_EMPTY_INPUT",
+            TableError::SchemaMismatch { .. } => "TABLE_SCHEMA_MISMATCH",
+            TableError::SchemaHashMismatch { .. } => "TABLE_SCHEMA_HASH_MISMATCH",
+            TableError::EmptyChunk { .. } => "TABLE_EMPTY_CHUNK",
+            TableError::UnknownFormat { .. } => "TABLE_UNKNOWN_FORMAT",
+            TableError::InvalidFilter { .. } => "TABLE_INVALID_FILTER",
+            TableError::InvalidPatch { .. } => "TABLE_INVALID_PATCH",
+            TableError::CorruptPatch { .. } => "TABLE_CORRUPT_PATCH",
+            TableError::Catalog(e) => e.code(),
+            TableError::ChunkStore(e) => e.code(),
+            TableError::Parquet(e) => e.code(),
+            TableError::Arrow(_) => "TABLE_ARROW",
+            TableError::Transaction(e) => e.code(),
+            TableError::Io(_) => "TABLE_IO",
+        }
+    }
+
+    fn category(&self) -> ErrorCategory {
+        match self {
+            TableError::EmptyInput(_)
+            | TableError::SchemaMismatch { .. }
+            | TableError::InvalidFilter { .. }
+            | TableError::InvalidPatch { .. } => ErrorCategory::InvalidArgument,
+            TableError::SchemaHashMismatch { .. }
+            | TableError::EmptyChunk { .. }
+            | TableError::UnknownFormat { .. }
+            | TableError::CorruptPatch { .. } => ErrorCategory::DataIntegrity,
+            TableError::Catalog(e) => e.category(),
+            TableError::ChunkStore(e) => e.category(),
+            TableError::Parquet(e) => e.category(),
+            TableError::Arrow(_) => ErrorCategory::Internal,
+            TableError::Transaction(e) => e.category(),
+            TableError::Io(_) => ErrorCategory::Io,
+        }
+    }
+
+    fn context(&self) -> ErrorContext {
+        match self {
+            TableError::EmptyInput(table)
+            | TableError::SchemaMismatch { table }
+            | TableError::InvalidFilter { table, .. }
+            | TableError::InvalidPatch { table, .. } => ErrorContext::new().with_table(table),
+            TableError::SchemaHashMismatch { table, version, hash, .. }
+            | TableError::EmptyChunk { table, version, hash }
+            | TableError::CorruptPatch { table, version, hash, .. } => ErrorContext::new()
+                .with_table(table)
+                .with_version(*version)
+                .with_chunk(hash),
+            TableError::UnknownFormat { table, version, .. } => {
+                ErrorContext::new().with_table(table).with_version(*version)
+            }
+            TableError::Catalog(e) => e.context(),
+            TableError::ChunkStore(e) => e.context(),
+            TableError::Parquet(e) => e.context(),
+            TableError::Transaction(e) => e.context(),
+            TableError::Arrow(_) | TableError::Io(_) => ErrorContext::new(),
+        }
+    }
+
+    fn retry_after(&self) -> Option<Duration> {
+        match self {
+            TableError::Catalog(e) => e.retry_after(),
+            TableError::Transaction(e) => e.retry_after(),
+            TableError::Io(e) => io_retry_after(e),
+            _ => None,
+        }
+    }
+}
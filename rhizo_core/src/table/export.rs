@@ -0,0 +1,350 @@
+//! Snapshot-consistent export of several tables at once.
+//!
+//! [`TableExporter::export_tables`] opens one read-only transaction, so
+//! every exported table is read at the same point in time however many
+//! commits land during the export. Each table becomes one file in the
+//! destination directory, and a `_manifest.json` written last records the
+//! branch, the latest transaction the snapshot includes and the exported
+//! version of every table. Downstream loaders should wait for the manifest
+//! before picking up the files.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use parquet::file::reader::{FileReader, SerializedFileReader};
+use serde::{Deserialize, Serialize};
+
+use super::error::TableError;
+use super::reader::TableReader;
+use crate::catalog::namespace::encode_table_name;
+use crate::chunk_store::ChunkStore;
+use crate::durability;
+use crate::parquet::ParquetError;
+use crate::transaction::{TransactionManager, TxId};
+
+/// Name of the manifest written into the destination directory
+pub const EXPORT_MANIFEST_FILE: &str = "_manifest.json";
+
+/// Transaction metadata key marking an export event in the changelog
+pub const EXPORT_EVENT_KEY: &str = "event";
+
+/// File format of exported tables
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportFormat {
+    Parquet,
+    /// Comma-separated values with a header row
+    Csv,
+}
+
+impl ExportFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Parquet => "parquet",
+            ExportFormat::Csv => "csv",
+        }
+    }
+}
+
+impl std::fmt::Display for ExportFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.extension())
+    }
+}
+
+impl FromStr for ExportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "parquet" => Ok(ExportFormat::Parquet),
+            "csv" => Ok(ExportFormat::Csv),
+            other => Err(format!("unknown export format '{}' (expected parquet or csv)", other)),
+        }
+    }
+}
+
+/// One exported table in a manifest
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExportedTable {
+    pub version: u64,
+    /// File name relative to the export directory
+    pub file: String,
+    pub rows: u64,
+    pub bytes: u64,
+}
+
+/// Description of a completed export, stored as `_manifest.json`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExportManifest {
+    pub branch: String,
+    /// Latest transaction committed when the snapshot was taken; every
+    /// transaction up to it is reflected in the exported versions
+    pub tx_id: Option<TxId>,
+    pub format: ExportFormat,
+    /// Unix timestamp of the snapshot
+    pub exported_at: i64,
+    pub tables: BTreeMap<String, ExportedTable>,
+    /// Transaction recording the export in the changelog, if requested
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub event_tx_id: Option<TxId>,
+}
+
+impl ExportManifest {
+    /// Read the manifest of the export in `dest`
+    pub fn load(dest: impl AsRef<Path>) -> Result<Self, TableError> {
+        let data = fs::read(dest.as_ref().join(EXPORT_MANIFEST_FILE))?;
+        Ok(serde_json::from_slice(&data).map_err(io::Error::from)?)
+    }
+}
+
+/// Exports tables of a branch from a single snapshot.
+///
+/// # Example
+///
+/// ```ignore
+/// let manifest = TableExporter::new(&manager, &store)
+///     .with_changelog_event(true)
+///     .export_tables(Some("main"), &["orders", "users"], ExportFormat::Parquet, "/exports/nightly")?;
+/// ```
+pub struct TableExporter<'a> {
+    manager: &'a TransactionManager,
+    store: &'a ChunkStore,
+    record_event: bool,
+}
+
+impl<'a> TableExporter<'a> {
+    pub fn new(manager: &'a TransactionManager, store: &'a ChunkStore) -> Self {
+        Self { manager, store, record_event: false }
+    }
+
+    /// Also commit an empty transaction on the branch whose metadata
+    /// describes the export, so changelog consumers see it
+    pub fn with_changelog_event(mut self, record_event: bool) -> Self {
+        self.record_event = record_event;
+        self
+    }
+
+    /// Export `tables` (all tables visible on the branch when empty) as
+    /// of one snapshot of `branch` (`None` = default branch) into the
+    /// directory `dest`, creating it if needed. Existing files of the
+    /// same names are replaced.
+    ///
+    /// # Errors
+    /// - `TableError::Catalog` with `CatalogError::TableNotFound` if a
+    ///   requested table is not visible on the branch
+    pub fn export_tables(
+        &self,
+        branch: Option<&str>,
+        tables: &[&str],
+        format: ExportFormat,
+        dest: impl AsRef<Path>,
+    ) -> Result<ExportManifest, TableError> {
+        let dest = dest.as_ref();
+        // Read before the snapshot is taken, so it is a lower bound of the
+        // transactions the snapshot reflects
+        let tx_id = self.manager.latest_tx_id()?;
+        let snapshot = self.manager.begin_read_only(branch)?;
+        let names: Vec<&str> = if tables.is_empty() { snapshot.tables() } else { tables.to_vec() };
+
+        fs::create_dir_all(dest)?;
+        let reader = TableReader::new(self.store, self.manager.catalog());
+        let mut exported = BTreeMap::new();
+        for name in names {
+            let record = snapshot.resolve(name)?;
+            let file = format!("{}.{}", encode_table_name(name), format.extension());
+            let path = dest.join(&file);
+            let rows = match format {
+                ExportFormat::Parquet => {
+                    reader.export_parquet(name, Some(record.version), &path)?;
+                    parquet_rows(&path)?
+                }
+                ExportFormat::Csv => write_csv(&reader.read_version(&record)?, &path)?,
+            };
+            let bytes = fs::metadata(&path)?.len();
+            exported.insert(name.to_string(), ExportedTable { version: record.version, file, rows, bytes });
+        }
+
+        let mut manifest = ExportManifest {
+            branch: snapshot.branch().to_string(),
+            tx_id,
+            format,
+            exported_at: snapshot.began_at(),
+            tables: exported,
+            event_tx_id: None,
+        };
+        snapshot.close();
+
+        if self.record_event {
+            manifest.event_tx_id = Some(self.record_export(&manifest, dest)?);
+        }
+
+        let contents = serde_json::to_vec_pretty(&manifest).map_err(io::Error::from)?;
+        let path = dest.join(EXPORT_MANIFEST_FILE);
+        durability::write_atomic(&path, &temp_path(&path), contents, self.manager.durability())?;
+        Ok(manifest)
+    }
+
+    /// Commit an empty transaction on the export's branch describing it
+    fn record_export(&self, manifest: &ExportManifest, dest: &Path) -> Result<TxId, TableError> {
+        let versions: Vec<String> = manifest
+            .tables
+            .iter()
+            .map(|(name, table)| format!("{}@{}", name, table.version))
+            .collect();
+        let mut metadata = HashMap::new();
+        metadata.insert(EXPORT_EVENT_KEY, "export".to_string());
+        metadata.insert("export.format", manifest.format.to_string());
+        metadata.insert("export.dest", dest.display().to_string());
+        metadata.insert("export.tables", versions.join(","));
+        if let Some(tx_id) = manifest.tx_id {
+            metadata.insert("export.tx_id", tx_id.to_string());
+        }
+
+        let tx_id = self.manager.begin(Some(&manifest.branch))?;
+        for (key, value) in &metadata {
+            self.manager.set_metadata(tx_id, key, value)?;
+        }
+        if let Err(e) = self.manager.commit(tx_id) {
+            let _ = self.manager.abort(tx_id, "export event commit failed");
+            return Err(e.into());
+        }
+        Ok(tx_id)
+    }
+}
+
+fn temp_path(path: &Path) -> PathBuf {
+    let mut temp = path.as_os_str().to_owned();
+    temp.push(".tmp");
+    PathBuf::from(temp)
+}
+
+fn parquet_rows(path: &Path) -> Result<u64, TableError> {
+    let reader = SerializedFileReader::new(File::open(path)?).map_err(ParquetError::from)?;
+    Ok(reader.metadata().file_metadata().num_rows() as u64)
+}
+
+/// Write `batches` as CSV with a header row, atomically; returns the row
+/// count
+fn write_csv(batches: &[arrow::record_batch::RecordBatch], path: &Path) -> Result<u64, TableError> {
+    let temp = temp_path(path);
+    let written = (|| -> Result<u64, TableError> {
+        let mut writer = arrow::csv::Writer::new(BufWriter::new(File::create(&temp)?));
+        let mut rows = 0;
+        for batch in batches {
+            writer.write(batch)?;
+            rows += batch.num_rows() as u64;
+        }
+        writer.into_inner().flush()?;
+        Ok(rows)
+    })();
+    if written.is_err() {
+        let _ = fs::remove_file(&temp);
+    }
+    let rows = written?;
+    fs::rename(&temp, path)?;
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use arrow::array::{Int64Array, StringArray};
+    use arrow::record_batch::RecordBatch;
+    use tempfile::TempDir;
+
+    use super::*;
+    use crate::catalog::{CatalogError, FileCatalog};
+    use crate::changelog::ChangelogQuery;
+    use crate::table::TableWriter;
+
+    fn batch(ids: Vec<i64>) -> RecordBatch {
+        let names: Vec<String> = ids.iter().map(|i| format!("user-{}", i)).collect();
+        RecordBatch::try_from_iter(vec![
+            ("id", Arc::new(Int64Array::from(ids)) as _),
+            ("name", Arc::new(StringArray::from(names)) as _),
+        ])
+        .unwrap()
+    }
+
+    fn setup() -> (TempDir, ChunkStore, TransactionManager) {
+        let dir = TempDir::new().unwrap();
+        let store = ChunkStore::new(dir.path().join("chunks")).unwrap();
+        let catalog = Arc::new(FileCatalog::new(dir.path().join("catalog")).unwrap());
+        let manager = TransactionManager::new(dir.path().join("tx"), catalog, None).unwrap();
+        (dir, store, manager)
+    }
+
+    fn write(store: &ChunkStore, manager: &TransactionManager, table: &str, ids: Vec<i64>) {
+        TableWriter::new(store, manager.catalog()).write(table, &[batch(ids)]).unwrap();
+    }
+
+    #[test]
+    fn test_export_parquet_writes_files_and_manifest() {
+        let (dir, store, manager) = setup();
+        write(&store, &manager, "users", vec![1, 2, 3]);
+        write(&store, &manager, "users", vec![1, 2, 3, 4]);
+        write(&store, &manager, "orders", vec![10]);
+        let dest = dir.path().join("export");
+
+        let manifest = TableExporter::new(&manager, &store)
+            .export_tables(None, &[], ExportFormat::Parquet, &dest)
+            .unwrap();
+        assert_eq!(ExportManifest::load(&dest).unwrap(), manifest);
+        assert_eq!(manifest.format, ExportFormat::Parquet);
+        assert_eq!(manifest.event_tx_id, None);
+        assert_eq!(manifest.tables.keys().collect::<Vec<_>>(), vec!["orders", "users"]);
+
+        let users = &manifest.tables["users"];
+        assert_eq!((users.version, users.rows, users.file.as_str()), (2, 4, "users.parquet"));
+        assert_eq!(users.bytes, fs::metadata(dest.join("users.parquet")).unwrap().len());
+        assert_eq!(manifest.tables["orders"].rows, 1);
+        assert!(!dest.join("users.parquet.tmp").exists());
+
+        // The snapshot was released once the export finished
+        assert!(manager.read_only_transactions().is_empty());
+    }
+
+    #[test]
+    fn test_export_csv_records_changelog_event() {
+        let (dir, store, manager) = setup();
+        write(&store, &manager, "users", vec![1, 2]);
+        write(&store, &manager, "orders", vec![10]);
+        let dest = dir.path().join("export");
+
+        let manifest = TableExporter::new(&manager, &store)
+            .with_changelog_event(true)
+            .export_tables(None, &["users"], ExportFormat::Csv, &dest)
+            .unwrap();
+        assert_eq!(manifest.tables.keys().collect::<Vec<_>>(), vec!["users"]);
+        let csv = fs::read_to_string(dest.join("users.csv")).unwrap();
+        assert_eq!(csv, "id,name\n1,user-1\n2,user-2\n");
+        assert!(!dest.join("orders.csv").exists());
+
+        let event_tx = manifest.event_tx_id.unwrap();
+        let entries = manager.get_changelog(ChangelogQuery::new()).unwrap();
+        let event = entries.iter().find(|e| e.tx_id == event_tx).unwrap();
+        assert!(event.changes.is_empty());
+        assert_eq!(event.metadata[EXPORT_EVENT_KEY], "export");
+        assert_eq!(event.metadata["export.format"], "csv");
+        assert_eq!(event.metadata["export.tables"], "users@1");
+    }
+
+    #[test]
+    fn test_export_unknown_table_fails() {
+        let (dir, store, manager) = setup();
+        write(&store, &manager, "users", vec![1]);
+
+        let err = TableExporter::new(&manager, &store)
+            .export_tables(None, &["missing"], ExportFormat::Csv, dir.path().join("export"))
+            .unwrap_err();
+        assert!(matches!(err, TableError::Catalog(CatalogError::TableNotFound(_))));
+        assert!(!dir.path().join("export").join(EXPORT_MANIFEST_FILE).exists());
+        assert_eq!("CSV".parse::<ExportFormat>().unwrap(), ExportFormat::Csv);
+        assert!("orc".parse::<ExportFormat>().is_err());
+    }
+}
@@ -0,0 +1,35 @@
+//! Arrow record batches stored as table versions.
+//!
+//! Chunks are opaque bytes to the chunk store and the catalog.
+//! [`TableWriter`] serializes record batches into chunks (one Arrow IPC
+//! stream per chunk), records the schema hash in the committed
+//! [`TableVersion`](crate::TableVersion) and tags the version with its
+//! chunk format; [`TableReader`] turns the chunks of a version back into
+//! batches. [`TableExporter`] writes several tables from one snapshot
+//! as Parquet or CSV files for downstream warehouses, and [`Redactor`]
+//! removes rows from every retained version of a table.
+//! [`TableWriter::write_patch`] commits row changes as patch chunks on top
+//! of the previous version's chunks (see [`patch`]).
+
+pub mod error;
+pub mod export;
+pub mod patch;
+pub mod reader;
+pub mod redact;
+pub mod writer;
+
+pub use error::TableError;
+pub use export::{ExportFormat, ExportManifest, ExportedTable, TableExporter, EXPORT_MANIFEST_FILE};
+pub use patch::{RowPatch, DEFAULT_MAX_PATCH_CHAIN};
+pub use reader::TableReader;
+pub use redact::Redactor;
+pub use writer::{schema_hash, ImportOptions, TableWriter, DEFAULT_INFER_ROWS, DEFAULT_MAX_ROWS_PER_CHUNK};
+
+/// Version metadata key naming the encoding of the version's chunks
+pub const FORMAT_METADATA_KEY: &str = "chunk_format";
+
+/// Chunks are Arrow IPC streams (written by [`TableWriter`])
+pub const ARROW_IPC_FORMAT: &str = "arrow_ipc";
+
+/// Chunks are Parquet files (the default when no format is recorded)
+pub const PARQUET_FORMAT: &str = "parquet";
@@ -0,0 +1,372 @@
+//! Row-level patches between table versions.
+//!
+//! Updating a few rows of a large table would otherwise store every touched
+//! chunk again in full. [`TableWriter::write_patch`] instead stores, for
+//! each chunk a [`RowPatch`] touches, a patch chunk naming the chunk it
+//! builds on and holding only the deleted row positions and the replaced
+//! and appended rows. [`TableReader`] rebuilds the full chunk by applying
+//! the patch to its base, recursively if the base is a patch itself.
+//!
+//! A patch chunk is a fixed magic, its chain of base chunks (nearest
+//! first), the deleted positions and an Arrow IPC stream of the patch
+//! rows. Chains are bounded: a patch that would build on more than
+//! `max_patch_chain` chunks is stored as a full chunk instead. A patched
+//! version lists the bases of its patch chunks under
+//! [`BASE_CHUNKS_METADATA_KEY`], so pruning, backups and garbage
+//! collection keep them as long as the version exists.
+//!
+//! [`TableWriter::write_patch`]: super::TableWriter::write_patch
+//! [`TableReader`]: super::TableReader
+//! [`BASE_CHUNKS_METADATA_KEY`]: crate::catalog::BASE_CHUNKS_METADATA_KEY
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::io::Cursor;
+use std::sync::Arc;
+
+use arrow::array::{Array, ArrayRef, UInt64Array};
+use arrow::compute::interleave;
+use arrow::datatypes::{DataType, Field, Schema};
+use arrow::ipc::reader::StreamReader;
+use arrow::record_batch::RecordBatch;
+
+use super::writer::encode_ipc;
+
+/// Default longest patch chain before a chunk is stored in full
+pub const DEFAULT_MAX_PATCH_CHAIN: u32 = 8;
+
+/// Marks a patch chunk
+pub(super) const PATCH_MAGIC: &[u8; 4] = b"RZP\x01";
+
+/// Column of the patch rows holding the base position a row replaces
+/// (null for appended rows)
+const ROW_COLUMN: &str = "__rhizo_patch_row";
+
+/// Length of a chunk hash in a patch header
+const HASH_LEN: usize = 64;
+
+/// Row changes to apply to the latest version of a table.
+///
+/// Positions count rows across the whole version, in row order. Replaced
+/// rows keep their position; appended rows follow the last row.
+///
+/// # Example
+///
+/// ```ignore
+/// let patch = RowPatch::new()
+///     .delete_rows([3, 17])
+///     .update_rows(vec![5], updated)
+///     .insert_rows(new_rows);
+/// let version = TableWriter::new(&store, &catalog).write_patch("users", &patch)?;
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct RowPatch {
+    pub(super) deletes: BTreeSet<u64>,
+    pub(super) updates: Vec<(Vec<u64>, RecordBatch)>,
+    pub(super) inserts: Vec<RecordBatch>,
+}
+
+impl RowPatch {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Remove the rows at `rows`
+    pub fn delete_rows(mut self, rows: impl IntoIterator<Item = u64>) -> Self {
+        self.deletes.extend(rows);
+        self
+    }
+
+    /// Replace the row at `rows[i]` with row `i` of `batch`
+    pub fn update_rows(mut self, rows: Vec<u64>, batch: RecordBatch) -> Self {
+        self.updates.push((rows, batch));
+        self
+    }
+
+    /// Append the rows of `batch`
+    pub fn insert_rows(mut self, batch: RecordBatch) -> Self {
+        self.inserts.push(batch);
+        self
+    }
+
+    /// Whether the patch changes no row
+    pub fn is_empty(&self) -> bool {
+        self.deletes.is_empty()
+            && self.updates.iter().all(|(rows, _)| rows.is_empty())
+            && self.inserts.iter().all(|b| b.num_rows() == 0)
+    }
+}
+
+/// The changes a patch chunk applies to its base chunk
+#[derive(Debug, Clone)]
+pub(super) struct ChunkPatch {
+    /// Chunks the patch builds on, its own base first
+    pub bases: Vec<String>,
+
+    /// Base positions removed, ascending
+    pub deletes: Vec<u64>,
+
+    /// Replaced and appended rows, with their base position in
+    /// `ROW_COLUMN`
+    pub rows: RecordBatch,
+}
+
+impl ChunkPatch {
+    /// Patch `base` (with its own chain `base_chain`) by replacing the
+    /// rows at `replaced[i]` with row `i` of `replacements`, removing
+    /// `deletes` and appending `inserts`, all with the base's schema
+    pub fn new(
+        base: &str,
+        base_chain: &[String],
+        deletes: Vec<u64>,
+        replaced: Vec<u64>,
+        replacements: Option<RecordBatch>,
+        inserts: Option<RecordBatch>,
+        schema: &Schema,
+    ) -> Result<Self, arrow::error::ArrowError> {
+        let mut bases = vec![base.to_string()];
+        bases.extend(base_chain.iter().cloned());
+
+        let mut positions: Vec<Option<u64>> = replaced.into_iter().map(Some).collect();
+        let mut parts = Vec::new();
+        parts.extend(replacements);
+        if let Some(inserts) = inserts {
+            positions.extend(std::iter::repeat_n(None, inserts.num_rows()));
+            parts.push(inserts);
+        }
+        let data = arrow::compute::concat_batches(&Arc::new(schema.clone()), &parts)?;
+
+        let mut fields: Vec<Field> = schema.fields().iter().map(|f| f.as_ref().clone()).collect();
+        fields.push(Field::new(ROW_COLUMN, DataType::UInt64, true));
+        let mut columns = data.columns().to_vec();
+        columns.push(Arc::new(UInt64Array::from(positions)) as ArrayRef);
+        let rows = RecordBatch::try_new(Arc::new(Schema::new(fields)), columns)?;
+        Ok(Self { bases, deletes, rows })
+    }
+
+    pub fn encode(&self) -> Result<Vec<u8>, super::TableError> {
+        let mut out = Vec::new();
+        out.extend_from_slice(PATCH_MAGIC);
+        out.extend_from_slice(&(self.bases.len() as u32).to_le_bytes());
+        for base in &self.bases {
+            out.extend_from_slice(base.as_bytes());
+        }
+        out.extend_from_slice(&(self.deletes.len() as u32).to_le_bytes());
+        for row in &self.deletes {
+            out.extend_from_slice(&row.to_le_bytes());
+        }
+        out.extend(encode_ipc(&self.rows)?);
+        Ok(out)
+    }
+
+    /// Parse a patch chunk; the error describes what is malformed
+    pub fn decode(data: &[u8]) -> Result<Self, String> {
+        let mut rest = data.strip_prefix(PATCH_MAGIC.as_slice()).ok_or("bad magic")?;
+        let mut take = |len: usize| -> Result<&[u8], String> {
+            if rest.len() < len {
+                return Err("truncated header".to_string());
+            }
+            let (head, tail) = rest.split_at(len);
+            rest = tail;
+            Ok(head)
+        };
+
+        let count = u32::from_le_bytes(take(4)?.try_into().unwrap());
+        let mut bases = Vec::new();
+        for _ in 0..count {
+            let base = std::str::from_utf8(take(HASH_LEN)?).map_err(|_| "base hash is not UTF-8")?;
+            bases.push(base.to_string());
+        }
+        if bases.is_empty() {
+            return Err("no base chunk".to_string());
+        }
+        let count = u32::from_le_bytes(take(4)?.try_into().unwrap());
+        let mut deletes = Vec::new();
+        for _ in 0..count {
+            deletes.push(u64::from_le_bytes(take(8)?.try_into().unwrap()));
+        }
+
+        let reader = StreamReader::try_new(Cursor::new(rest), None).map_err(|e| e.to_string())?;
+        let schema = reader.schema();
+        let batches = reader.collect::<Result<Vec<_>, _>>().map_err(|e| e.to_string())?;
+        let rows = arrow::compute::concat_batches(&schema, &batches).map_err(|e| e.to_string())?;
+        match rows.schema().fields().last() {
+            Some(field) if field.name() == ROW_COLUMN && field.data_type() == &DataType::UInt64 => {}
+            _ => return Err("patch rows carry no position column".to_string()),
+        }
+        Ok(Self { bases, deletes, rows })
+    }
+
+    /// Rebuild the patched chunk from its base; the error describes why
+    /// the patch does not fit the base
+    pub fn apply(&self, base: &RecordBatch) -> Result<RecordBatch, String> {
+        let width = self.rows.num_columns() - 1;
+        if self.rows.schema().fields()[..width] != base.schema().fields()[..] {
+            return Err("patch rows do not match the base schema".to_string());
+        }
+        let positions = self.rows.column(width).as_any().downcast_ref::<UInt64Array>().unwrap();
+
+        let base_rows = base.num_rows() as u64;
+        let deleted: HashSet<u64> = self.deletes.iter().copied().collect();
+        let mut replaced = HashMap::new();
+        let mut appended = Vec::new();
+        for i in 0..positions.len() {
+            if positions.is_null(i) {
+                appended.push((1, i));
+                continue;
+            }
+            let row = positions.value(i);
+            if row >= base_rows || deleted.contains(&row) || replaced.insert(row, i).is_some() {
+                return Err(format!("row {} cannot be replaced", row));
+            }
+        }
+        if let Some(row) = self.deletes.iter().find(|&&r| r >= base_rows) {
+            return Err(format!("row {} is out of range", row));
+        }
+
+        let mut indices: Vec<(usize, usize)> = (0..base_rows)
+            .filter(|row| !deleted.contains(row))
+            .map(|row| match replaced.get(&row) {
+                Some(&i) => (1, i),
+                None => (0, row as usize),
+            })
+            .collect();
+        indices.extend(appended);
+
+        let columns = (0..width)
+            .map(|c| interleave(&[base.column(c).as_ref(), self.rows.column(c).as_ref()], &indices))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| e.to_string())?;
+        RecordBatch::try_new(base.schema(), columns).map_err(|e| e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow::array::{Int64Array, StringArray};
+    use tempfile::TempDir;
+
+    use super::*;
+    use crate::catalog::{FileCatalog, BASE_CHUNKS_METADATA_KEY};
+    use crate::chunk_store::ChunkStore;
+    use crate::gc::{ChunkGc, VersionPruner};
+    use crate::parquet::{FilterOp, PredicateFilter, ScalarValue};
+    use crate::table::{Redactor, TableError, TableReader, TableWriter};
+
+    fn batch(ids: Vec<i64>) -> RecordBatch {
+        let names: Vec<String> = ids.iter().map(|i| format!("user-{}", i)).collect();
+        RecordBatch::try_from_iter(vec![
+            ("id", Arc::new(Int64Array::from(ids)) as _),
+            ("name", Arc::new(StringArray::from(names)) as _),
+        ])
+        .unwrap()
+    }
+
+    fn ids(store: &ChunkStore, catalog: &FileCatalog, version: Option<u64>) -> Vec<i64> {
+        let batches = TableReader::new(store, catalog).read("users", version).unwrap();
+        batches
+            .iter()
+            .flat_map(|b| b.column(0).as_any().downcast_ref::<Int64Array>().unwrap().values().to_vec())
+            .collect()
+    }
+
+    #[test]
+    fn test_write_patch_stores_only_touched_chunks() {
+        let dir = TempDir::new().unwrap();
+        let store = ChunkStore::new(dir.path().join("chunks")).unwrap();
+        let catalog = FileCatalog::new(dir.path().join("catalog")).unwrap();
+        let writer = TableWriter::new(&store, &catalog).with_max_rows_per_chunk(3);
+        let v1 = writer.write("users", &[batch(vec![1, 2, 3, 4, 5, 6, 7])]).unwrap();
+
+        let patch = RowPatch::new()
+            .delete_rows([1])
+            .update_rows(vec![4], batch(vec![50]))
+            .insert_rows(batch(vec![8]));
+        let v2 = writer.write_patch("users", &patch).unwrap();
+        assert_eq!(ids(&store, &catalog, None), vec![1, 3, 4, 50, 6, 7, 8]);
+        assert_eq!(ids(&store, &catalog, Some(1)), vec![1, 2, 3, 4, 5, 6, 7]);
+        assert_eq!(v2.schema_hash, v1.schema_hash);
+        assert_eq!(v2.chunk_hashes.len(), 3);
+        assert!(v1.chunk_hashes.iter().zip(&v2.chunk_hashes).all(|(a, b)| a != b));
+        assert_eq!(v2.base_chunks().len(), 3);
+
+        // A patch on a patch records the whole chain
+        let v3 = writer.write_patch("users", &RowPatch::new().delete_rows([0])).unwrap();
+        assert_eq!(ids(&store, &catalog, None), vec![3, 4, 50, 6, 7, 8]);
+        assert_eq!(v3.base_chunks().len(), 4);
+        assert_eq!(v3.chunk_hashes[1..], v2.chunk_hashes[1..]);
+
+        // Pruning the old versions keeps the bases the latest one needs
+        VersionPruner::new(&catalog)
+            .with_default_policy(crate::catalog::RetentionPolicy { keep_last: Some(1), keep_newer_than_secs: None })
+            .run()
+            .unwrap();
+        ChunkGc::new(&catalog, &store).with_grace_period(std::time::Duration::ZERO).run().unwrap();
+        assert_eq!(ids(&store, &catalog, None), vec![3, 4, 50, 6, 7, 8]);
+
+        // Past the chain limit the chunk is stored in full again
+        let full = TableWriter::new(&store, &catalog)
+            .with_max_patch_chain(2)
+            .write_patch("users", &RowPatch::new().delete_rows([0]))
+            .unwrap();
+        assert_eq!(ids(&store, &catalog, None), vec![4, 50, 6, 7, 8]);
+        assert!(!store.get(&full.chunk_hashes[0]).unwrap().starts_with(PATCH_MAGIC));
+        assert_eq!(full.base_chunks().len(), 2);
+    }
+
+    #[test]
+    fn test_write_patch_rejects_invalid_patches_and_redacts_bases() {
+        let dir = TempDir::new().unwrap();
+        let store = ChunkStore::new(dir.path().join("chunks")).unwrap();
+        let catalog = FileCatalog::new(dir.path().join("catalog")).unwrap();
+        let writer = TableWriter::new(&store, &catalog);
+        writer.write("users", &[batch(vec![1, 2, 3])]).unwrap();
+
+        let invalid = |patch: RowPatch| matches!(writer.write_patch("users", &patch), Err(TableError::InvalidPatch { .. }));
+        assert!(invalid(RowPatch::new()));
+        assert!(invalid(RowPatch::new().delete_rows([3])));
+        assert!(invalid(RowPatch::new().delete_rows([1]).update_rows(vec![1], batch(vec![9]))));
+        assert!(invalid(RowPatch::new().update_rows(vec![0, 1], batch(vec![9]))));
+        let other = RecordBatch::try_from_iter(vec![("id", Arc::new(Int64Array::from(vec![9])) as _)]).unwrap();
+        assert!(matches!(
+            writer.write_patch("users", &RowPatch::new().insert_rows(other)),
+            Err(TableError::SchemaMismatch { .. })
+        ));
+
+        // v2 only deletes row 0, but its base still holds it: redaction
+        // stores the patch chunk in full and drops the base reference
+        writer.write_patch("users", &RowPatch::new().delete_rows([0])).unwrap();
+        let filter = vec![PredicateFilter::new("id", FilterOp::Eq, ScalarValue::Int64(1))];
+        let record = Redactor::new(&store, &catalog).redact("users", &filter).unwrap();
+        let rewritten: Vec<u64> = record.versions.iter().map(|v| v.version).collect();
+        assert_eq!(rewritten, vec![1, 2]);
+        let v2 = catalog.get_version("users", Some(2)).unwrap();
+        assert!(!v2.metadata.contains_key(BASE_CHUNKS_METADATA_KEY));
+        assert_eq!(ids(&store, &catalog, Some(2)), vec![2, 3]);
+    }
+
+    #[test]
+    fn test_patch_round_trips_and_applies() {
+        let base = batch(vec![1, 2, 3, 4]);
+        let patch = ChunkPatch::new(
+            &"a".repeat(64),
+            &["b".repeat(64)],
+            vec![0, 3],
+            vec![2],
+            Some(batch(vec![30])),
+            Some(batch(vec![5, 6])),
+            &base.schema(),
+        )
+        .unwrap();
+
+        let decoded = ChunkPatch::decode(&patch.encode().unwrap()).unwrap();
+        assert_eq!(decoded.bases, vec!["a".repeat(64), "b".repeat(64)]);
+        assert_eq!(decoded.deletes, vec![0, 3]);
+
+        let patched = decoded.apply(&base).unwrap();
+        let ids = patched.column(0).as_any().downcast_ref::<Int64Array>().unwrap();
+        assert_eq!(ids.values().to_vec(), vec![2, 30, 5, 6]);
+
+        assert!(ChunkPatch::decode(b"RZP\x01\x01").is_err());
+        assert!(decoded.apply(&batch(vec![1, 2])).is_err());
+    }
+}
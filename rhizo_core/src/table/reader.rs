@@ -0,0 +1,435 @@
+//! Reading table versions back as Arrow record batches.
+
+use std::ffi::OsString;
+use std::fs::{self, File};
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+
+use arrow::compute::concat_batches;
+use arrow::datatypes::SchemaRef;
+use arrow::ipc::reader::StreamReader;
+use arrow::record_batch::RecordBatch;
+use parquet::arrow::ArrowWriter;
+use parquet::basic::{Compression, ZstdLevel};
+use parquet::file::properties::WriterProperties;
+use rayon::prelude::*;
+
+use super::error::TableError;
+use super::patch::{ChunkPatch, PATCH_MAGIC};
+use super::writer::schema_hash;
+use super::{ARROW_IPC_FORMAT, FORMAT_METADATA_KEY, PARQUET_FORMAT};
+use crate::catalog::{FileCatalog, TableVersion};
+use crate::chunk_store::ChunkStore;
+use crate::parquet::{ParquetDecoder, ParquetError};
+
+/// Reconstructs the record batches of a table version from its chunks.
+///
+/// Versions written by [`TableWriter`](super::TableWriter) hold Arrow IPC
+/// chunks and are checked against the version's schema hash. Versions
+/// without a chunk format tag hold Parquet chunks, as written by the
+/// Python `TableWriter`. Row patch chunks of either format are applied to
+/// their base chunk.
+///
+/// # Example
+///
+/// ```ignore
+/// let batches = TableReader::new(&store, &catalog).read("events", None)?;
+/// ```
+pub struct TableReader<'a> {
+    store: &'a ChunkStore,
+    catalog: &'a FileCatalog,
+}
+
+impl<'a> TableReader<'a> {
+    pub fn new(store: &'a ChunkStore, catalog: &'a FileCatalog) -> Self {
+        Self { store, catalog }
+    }
+
+    /// Read a version of a table (`None` = latest), one or more batches per
+    /// chunk, in row order.
+    pub fn read(&self, table_name: &str, version: Option<u64>) -> Result<Vec<RecordBatch>, TableError> {
+        let record = self.catalog.get_version(table_name, version)?;
+        self.read_version(&record)
+    }
+
+    /// Read the batches of a catalog record
+    pub fn read_version(&self, record: &TableVersion) -> Result<Vec<RecordBatch>, TableError> {
+        let hashes: Vec<&str> = record.chunk_hashes.iter().map(String::as_str).collect();
+        let chunks = self.store.get_batch(&hashes)?;
+        let decoded: Vec<Vec<RecordBatch>> = record
+            .chunk_hashes
+            .par_iter()
+            .zip(chunks.par_iter())
+            .map(|(hash, data)| self.decode_chunk(record, hash, data))
+            .collect::<Result<_, _>>()?;
+        Ok(decoded.into_iter().flatten().collect())
+    }
+
+    /// Schema of a version of a table (`None` = latest), read from its
+    /// first chunk
+    pub fn schema(&self, table_name: &str, version: Option<u64>) -> Result<SchemaRef, TableError> {
+        let record = self.catalog.get_version(table_name, version)?;
+        let hash = record.chunk_hashes.first().ok_or_else(|| TableError::EmptyChunk {
+            table: record.table_name.clone(),
+            version: record.version,
+            hash: String::new(),
+        })?;
+        let data = self.store.get(hash)?;
+        let batches = self.decode_chunk(&record, hash, &data)?;
+        Ok(batches[0].schema())
+    }
+
+    /// Materialize a version of a table (`None` = latest) as a Parquet
+    /// file at `path`.
+    ///
+    /// Chunks are decoded one at a time, so memory use is bounded by the
+    /// largest chunk. A version made of a single Parquet chunk is copied
+    /// as is. The file is written next to `path` and renamed into place,
+    /// so readers never see a partial file. Returns the exported version.
+    pub fn export_parquet(
+        &self,
+        table_name: &str,
+        version: Option<u64>,
+        path: impl AsRef<Path>,
+    ) -> Result<TableVersion, TableError> {
+        let record = self.catalog.get_version(table_name, version)?;
+        let path = path.as_ref();
+        let mut temp = OsString::from(path.as_os_str());
+        temp.push(".tmp");
+        let temp = PathBuf::from(temp);
+
+        let written = self.write_parquet(&record, &temp);
+        if written.is_err() {
+            let _ = fs::remove_file(&temp);
+        }
+        written?;
+        fs::rename(&temp, path)?;
+        Ok(record)
+    }
+
+    fn write_parquet(&self, record: &TableVersion, path: &Path) -> Result<(), TableError> {
+        let untagged = !record.metadata.contains_key(FORMAT_METADATA_KEY);
+        if let [hash] = record.chunk_hashes.as_slice() {
+            if untagged || record.metadata[FORMAT_METADATA_KEY] == PARQUET_FORMAT {
+                let data = self.store.get(hash)?;
+                if !data.starts_with(PATCH_MAGIC) {
+                    fs::write(path, data)?;
+                    return Ok(());
+                }
+            }
+        }
+
+        let props = WriterProperties::builder()
+            .set_compression(Compression::ZSTD(ZstdLevel::default()))
+            .build();
+        let mut writer: Option<ArrowWriter<File>> = None;
+        for hash in &record.chunk_hashes {
+            let data = self.store.get(hash)?;
+            for batch in self.decode_chunk(record, hash, &data)? {
+                let writer = match &mut writer {
+                    Some(writer) => writer,
+                    None => writer.insert(
+                        ArrowWriter::try_new(File::create(path)?, batch.schema(), Some(props.clone()))
+                            .map_err(ParquetError::from)?,
+                    ),
+                };
+                writer.write(&batch).map_err(ParquetError::from)?;
+            }
+        }
+        let writer = writer.ok_or_else(|| TableError::EmptyChunk {
+            table: record.table_name.clone(),
+            version: record.version,
+            hash: String::new(),
+        })?;
+        writer.close().map_err(ParquetError::from)?;
+        Ok(())
+    }
+
+    pub(super) fn decode_chunk(
+        &self,
+        record: &TableVersion,
+        hash: &str,
+        data: &[u8],
+    ) -> Result<Vec<RecordBatch>, TableError> {
+        if data.starts_with(PATCH_MAGIC) {
+            return self.decode_patch(record, hash, data);
+        }
+        let format = record
+            .metadata
+            .get(FORMAT_METADATA_KEY)
+            .map(String::as_str)
+            .unwrap_or(PARQUET_FORMAT);
+        let batches = match format {
+            ARROW_IPC_FORMAT => {
+                StreamReader::try_new(Cursor::new(data), None)?.collect::<Result<Vec<_>, _>>()?
+            }
+            PARQUET_FORMAT => vec![ParquetDecoder::new().decode(data)?],
+            other => {
+                return Err(TableError::UnknownFormat {
+                    table: record.table_name.clone(),
+                    version: record.version,
+                    format: other.to_string(),
+                })
+            }
+        };
+
+        let Some(first) = batches.first() else {
+            return Err(TableError::EmptyChunk {
+                table: record.table_name.clone(),
+                version: record.version,
+                hash: hash.to_string(),
+            });
+        };
+        if format == ARROW_IPC_FORMAT {
+            if let Some(expected) = &record.schema_hash {
+                let found = schema_hash(&first.schema());
+                if &found != expected {
+                    return Err(TableError::SchemaHashMismatch {
+                        table: record.table_name.clone(),
+                        version: record.version,
+                        hash: hash.to_string(),
+                        expected: expected.clone(),
+                        found,
+                    });
+                }
+            }
+        }
+        Ok(batches)
+    }
+
+    /// Rebuild a patch chunk from its base, decoding the base like any
+    /// chunk of the version
+    fn decode_patch(&self, record: &TableVersion, hash: &str, data: &[u8]) -> Result<Vec<RecordBatch>, TableError> {
+        let corrupt = |reason: String| TableError::CorruptPatch {
+            table: record.table_name.clone(),
+            version: record.version,
+            hash: hash.to_string(),
+            reason,
+        };
+        let patch = ChunkPatch::decode(data).map_err(corrupt)?;
+        let base_hash = &patch.bases[0];
+        let batches = self.decode_chunk(record, base_hash, &self.store.get(base_hash)?)?;
+        let base = concat_batches(&batches[0].schema(), &batches)?;
+        Ok(vec![patch.apply(&base).map_err(corrupt)?])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    use arrow::array::{Int64Array, StringArray};
+    use tempfile::TempDir;
+
+    use super::*;
+    use crate::catalog::CatalogError;
+    use crate::parquet::ParquetEncoder;
+    use crate::table::TableWriter;
+    use crate::transaction::TransactionManager;
+
+    fn batch(ids: Vec<i64>) -> RecordBatch {
+        let names: Vec<String> = ids.iter().map(|i| format!("user-{}", i)).collect();
+        RecordBatch::try_from_iter(vec![
+            ("id", Arc::new(Int64Array::from(ids)) as _),
+            ("name", Arc::new(StringArray::from(names)) as _),
+        ])
+        .unwrap()
+    }
+
+    fn setup() -> (TempDir, ChunkStore, FileCatalog) {
+        let dir = TempDir::new().unwrap();
+        let store = ChunkStore::new(dir.path().join("chunks")).unwrap();
+        let catalog = FileCatalog::new(dir.path().join("catalog")).unwrap();
+        (dir, store, catalog)
+    }
+
+    #[test]
+    fn test_round_trip_splits_chunks_and_records_schema() {
+        let (_dir, store, catalog) = setup();
+        let written = TableWriter::new(&store, &catalog)
+            .with_max_rows_per_chunk(2)
+            .with_metadata("source", "test")
+            .write("users", &[batch(vec![1, 2, 3]), batch(vec![4])])
+            .unwrap();
+
+        assert_eq!(written.version, 1);
+        assert_eq!(written.chunk_hashes.len(), 3);
+        assert_eq!(written.metadata[FORMAT_METADATA_KEY], ARROW_IPC_FORMAT);
+        assert_eq!(written.metadata["source"], "test");
+        assert_eq!(written.schema_hash, Some(schema_hash(&batch(vec![]).schema())));
+
+        let reader = TableReader::new(&store, &catalog);
+        let batches = reader.read("users", None).unwrap();
+        let ids: Vec<i64> = batches
+            .iter()
+            .flat_map(|b| b.column(0).as_any().downcast_ref::<Int64Array>().unwrap().values().to_vec())
+            .collect();
+        assert_eq!(ids, vec![1, 2, 3, 4]);
+        assert_eq!(reader.schema("users", Some(1)).unwrap().field(1).name(), "name");
+
+        // An empty write keeps the schema
+        let empty = TableWriter::new(&store, &catalog).write("users", &[batch(vec![])]).unwrap();
+        assert_eq!(empty.chunk_hashes.len(), 1);
+        let batches = reader.read("users", Some(2)).unwrap();
+        assert_eq!(batches[0].num_rows(), 0);
+        assert_eq!(batches[0].schema().fields().len(), 2);
+    }
+
+    #[test]
+    fn test_rejects_bad_input_and_reads_parquet_versions() {
+        let (_dir, store, catalog) = setup();
+        let writer = TableWriter::new(&store, &catalog);
+        assert!(matches!(writer.write("users", &[]), Err(TableError::EmptyInput(_))));
+        let other = RecordBatch::try_from_iter(vec![("id", Arc::new(StringArray::from(vec!["a"])) as _)]).unwrap();
+        assert!(matches!(
+            writer.write("users", &[batch(vec![1]), other]),
+            Err(TableError::SchemaMismatch { .. })
+        ));
+
+        // Versions without a format tag are Parquet
+        let hash = store.put(&ParquetEncoder::new().encode(&batch(vec![7, 8])).unwrap()).unwrap();
+        catalog.commit(TableVersion::new("legacy", 1, vec![hash])).unwrap();
+        let batches = TableReader::new(&store, &catalog).read("legacy", None).unwrap();
+        assert_eq!(batches[0].num_rows(), 2);
+
+        // A chunk whose schema differs from the recorded hash is rejected
+        let mut record = writer.write("users", &[batch(vec![1])]).unwrap();
+        record.schema_hash = Some("0".repeat(64));
+        assert!(matches!(
+            TableReader::new(&store, &catalog).read_version(&record),
+            Err(TableError::SchemaHashMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_parquet_import_export_round_trip() {
+        let (dir, store, catalog) = setup();
+        let source = dir.path().join("users.parquet");
+        let encoded = ParquetEncoder::new().encode(&batch(vec![1, 2, 3, 4, 5])).unwrap();
+        std::fs::write(&source, encoded).unwrap();
+
+        let imported = TableWriter::new(&store, &catalog)
+            .with_max_rows_per_chunk(2)
+            .import_parquet(&source, "users", None)
+            .unwrap();
+        assert_eq!((imported.version, imported.chunk_hashes.len()), (1, 3));
+        let reader = TableReader::new(&store, &catalog);
+        assert_eq!(reader.read("users", None).unwrap().iter().map(|b| b.num_rows()).sum::<usize>(), 5);
+
+        // Multi-chunk versions are re-encoded into one file
+        let target = dir.path().join("out/users.parquet");
+        std::fs::create_dir_all(target.parent().unwrap()).unwrap();
+        reader.export_parquet("users", Some(1), &target).unwrap();
+        let exported = ParquetDecoder::new().decode(&std::fs::read(&target).unwrap()).unwrap();
+        assert_eq!(exported.num_rows(), 5);
+        assert!(!dir.path().join("out/users.parquet.tmp").exists());
+
+        // Through a transaction the version appears on commit
+        let shared = Arc::new(FileCatalog::new(dir.path().join("catalog")).unwrap());
+        let manager = TransactionManager::new(dir.path().join("tx"), shared, None).unwrap();
+        let tx_id = manager.begin(None).unwrap();
+        let pending = TableWriter::new(&store, &catalog)
+            .import_parquet(&source, "users", Some((&manager, tx_id)))
+            .unwrap();
+        assert_eq!(pending.version, 2);
+        assert_eq!(catalog.list_versions("users").unwrap(), vec![1]);
+        manager.commit(tx_id).unwrap();
+        let committed = catalog.get_version("users", None).unwrap();
+        assert_eq!((committed.version, committed.chunk_hashes.len()), (2, 1));
+
+        // A single Parquet chunk is copied byte for byte
+        reader.export_parquet("users", None, &target).unwrap();
+        assert_eq!(std::fs::read(&target).unwrap(), std::fs::read(&source).unwrap());
+    }
+
+    #[test]
+    fn test_csv_and_jsonl_import_infer_schema() {
+        use arrow::datatypes::{DataType, Field, Schema};
+
+        use crate::catalog::SchemaMigration;
+        use crate::table::ImportOptions;
+
+        let (dir, store, catalog) = setup();
+        let csv = dir.path().join("users.csv");
+        std::fs::write(&csv, "id;name;score\n1;ann;1.5\n2;bob;2\n3;cy;\n").unwrap();
+        let writer = TableWriter::new(&store, &catalog).with_max_rows_per_chunk(2);
+        let options = ImportOptions::new().with_delimiter(b';');
+
+        let imported = writer.import_csv(&csv, "users", &options, None).unwrap();
+        assert_eq!((imported.version, imported.chunk_hashes.len()), (1, 2));
+        let reader = TableReader::new(&store, &catalog);
+        let schema = reader.schema("users", None).unwrap();
+        let types: Vec<&DataType> = schema.fields().iter().map(|f| f.data_type()).collect();
+        assert_eq!(types, vec![&DataType::Int64, &DataType::Utf8, &DataType::Float64]);
+        assert_eq!(imported.schema_hash, Some(schema_hash(&schema)));
+
+        // An explicit schema overrides inference, and joins a transaction
+        let jsonl = dir.path().join("users.jsonl");
+        std::fs::write(&jsonl, "{\"id\": 4, \"name\": \"dee\"}\n{\"id\": 5}\n").unwrap();
+        let explicit = Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Float64, false),
+            Field::new("name", DataType::Utf8, true),
+        ]));
+        let shared = Arc::new(FileCatalog::new(dir.path().join("catalog")).unwrap());
+        let manager = TransactionManager::new(dir.path().join("tx"), shared, None).unwrap();
+        let tx_id = manager.begin(None).unwrap();
+        // Retyping `id` and dropping `score` is not additive
+        let migration = SchemaMigration::new(imported.schema_hash.clone().unwrap(), schema_hash(&explicit));
+        let pending = TableWriter::new(&store, &catalog)
+            .with_migration(&migration)
+            .import_jsonl(&jsonl, "users", &ImportOptions::new().with_schema(explicit), Some((&manager, tx_id)))
+            .unwrap();
+        assert_eq!(pending.version, 2);
+        manager.commit(tx_id).unwrap();
+        let batches = reader.read("users", Some(2)).unwrap();
+        assert_eq!(batches[0].schema().field(0).data_type(), &DataType::Float64);
+        assert_eq!(batches[0].num_rows(), 2);
+
+        let inferred = writer.import_jsonl(&jsonl, "inferred", &ImportOptions::new(), None).unwrap();
+        assert_eq!(reader.schema("inferred", None).unwrap().field(0).data_type(), &DataType::Int64);
+        assert_eq!(inferred.version, 1);
+
+        let empty = dir.path().join("empty.csv");
+        std::fs::write(&empty, "id,name\n").unwrap();
+        assert!(matches!(
+            writer.import_csv(&empty, "empty", &ImportOptions::new(), None),
+            Err(TableError::EmptyInput(_))
+        ));
+    }
+
+    #[test]
+    fn test_create_table_records_schema_without_rows() {
+        let (dir, store, catalog) = setup();
+        let schema = batch(vec![]).schema();
+        let writer = TableWriter::new(&store, &catalog).with_metadata("owner", "ops");
+        let created = writer
+            .create_table("users", schema.clone(), HashMap::from([("purpose".to_string(), "audit".to_string())]), None)
+            .unwrap();
+        assert_eq!(created.version, 1);
+        assert_eq!(created.schema_hash, Some(schema_hash(&schema)));
+        assert_eq!((created.metadata["owner"].as_str(), created.metadata["purpose"].as_str()), ("ops", "audit"));
+
+        let reader = TableReader::new(&store, &catalog);
+        assert_eq!(reader.schema("users", None).unwrap().fields(), schema.fields());
+        assert_eq!(reader.read("users", None).unwrap()[0].num_rows(), 0);
+        assert!(matches!(
+            writer.create_table("users", schema.clone(), HashMap::new(), None),
+            Err(TableError::Catalog(CatalogError::TableAlreadyExists(_)))
+        ));
+
+        // Through a transaction the table lands on the branch and in the changelog
+        let shared = Arc::new(FileCatalog::new(dir.path().join("catalog")).unwrap());
+        let branches = Arc::new(crate::branch::BranchManager::new(dir.path().join("branches")).unwrap());
+        let manager = TransactionManager::new(dir.path().join("tx"), shared, Some(Arc::clone(&branches))).unwrap();
+        let tx_id = manager.begin(None).unwrap();
+        writer.create_table("events", schema, HashMap::new(), Some((&manager, tx_id))).unwrap();
+        assert!(!catalog.table_exists("events").unwrap());
+        manager.commit(tx_id).unwrap();
+
+        assert_eq!(branches.get("main").unwrap().head["events"], 1);
+        let changelog = manager.get_changelog(crate::changelog::ChangelogQuery::new()).unwrap();
+        assert!(changelog[0].changes[0].is_new_table());
+        assert_eq!(catalog.get_version("events", None).unwrap().metadata["owner"], "ops");
+    }
+}
@@ -0,0 +1,273 @@
+//! Removing rows from every retained version of a table.
+//!
+//! Retention pruning drops whole versions; a deletion request for specific
+//! records (a user's rows, a leaked secret) has to reach every version that
+//! still holds them. [`Redactor::redact`] rewrites the chunks of each
+//! version without the rows matching a predicate and swaps them into the
+//! version in place, so version numbers, branch heads, tags and pins stay
+//! valid and simply read the redacted data. The catalog keeps a
+//! [`RedactionRecord`] per redaction listing what was rewritten.
+//!
+//! Row patch chunks are always stored in full again, so no base chunk
+//! keeps the removed rows reachable. The replaced chunks stay in the chunk
+//! store until garbage collection removes them as unreferenced.
+
+use std::collections::HashMap;
+
+use arrow::array::Array;
+use arrow::compute::{concat_batches, filter_record_batch, not, prep_null_mask_filter};
+use arrow::record_batch::RecordBatch;
+
+use super::error::TableError;
+use super::reader::TableReader;
+use super::patch::PATCH_MAGIC;
+use super::writer::encode_ipc;
+use super::{ARROW_IPC_FORMAT, FORMAT_METADATA_KEY, PARQUET_FORMAT};
+use crate::catalog::{FileCatalog, RedactedVersion, RedactionRecord, TableVersion, BASE_CHUNKS_METADATA_KEY};
+use crate::chunk_store::ChunkStore;
+use crate::parquet::{apply_filters, ParquetEncoder, PredicateFilter};
+
+/// A chunk after redaction: its replacement (`None` if no row is left) and
+/// the number of rows removed
+type RedactedChunk = (Option<String>, u64);
+
+/// Rewrites table versions without the rows matching a predicate.
+///
+/// # Example
+///
+/// ```ignore
+/// let record = Redactor::new(&store, &catalog)
+///     .with_reason("erasure request #42")
+///     .redact("users", &[PredicateFilter::new("id", FilterOp::Eq, ScalarValue::Int64(7))])?;
+/// println!("removed {} rows", record.rows_removed());
+/// ```
+pub struct Redactor<'a> {
+    store: &'a ChunkStore,
+    catalog: &'a FileCatalog,
+    reason: Option<String>,
+}
+
+impl<'a> Redactor<'a> {
+    pub fn new(store: &'a ChunkStore, catalog: &'a FileCatalog) -> Self {
+        Self { store, catalog, reason: None }
+    }
+
+    /// Record why the rows are removed in the redaction history
+    pub fn with_reason(mut self, reason: impl Into<String>) -> Self {
+        self.reason = Some(reason.into());
+        self
+    }
+
+    /// Remove the rows matching all of `filters` from every version of
+    /// `table_name`. Rows where a filter evaluates to null are kept.
+    ///
+    /// Versions without matching rows or patch chunks are left untouched; a
+    /// version whose rows all match is left without chunks. The record is appended to the
+    /// table's redaction history even if nothing matched.
+    ///
+    /// # Errors
+    /// - `InvalidFilter` if `filters` is empty or names a column a version
+    ///   does not have; nothing is rewritten then.
+    /// - `Catalog(VersionHeld)` if a version with matching rows is under
+    ///   legal hold; nothing is rewritten then.
+    pub fn redact(&self, table_name: &str, filters: &[PredicateFilter]) -> Result<RedactionRecord, TableError> {
+        if filters.is_empty() {
+            return Err(TableError::InvalidFilter {
+                table: table_name.to_string(),
+                reason: "redaction needs at least one filter".to_string(),
+            });
+        }
+
+        let reader = TableReader::new(self.store, self.catalog);
+        let mut redacted: HashMap<(String, String), Option<RedactedChunk>> = HashMap::new();
+        let mut rewrites = Vec::new();
+        let mut versions = Vec::new();
+        for version in self.catalog.list_versions(table_name)? {
+            let record = self.catalog.get_version(table_name, Some(version))?;
+            let format = record
+                .metadata
+                .get(FORMAT_METADATA_KEY)
+                .cloned()
+                .unwrap_or_else(|| PARQUET_FORMAT.to_string());
+
+            let mut chunk_hashes = Vec::with_capacity(record.chunk_hashes.len());
+            let mut replaced_chunks = Vec::new();
+            let mut rows_removed = 0;
+            for hash in &record.chunk_hashes {
+                let key = (format.clone(), hash.clone());
+                if !redacted.contains_key(&key) {
+                    let chunk = self.redact_chunk(&reader, &record, &format, hash, filters)?;
+                    redacted.insert(key.clone(), chunk);
+                }
+                let Some((replacement, removed)) = &redacted[&key] else {
+                    chunk_hashes.push(hash.clone());
+                    continue;
+                };
+                rows_removed += removed;
+                replaced_chunks.push(hash.clone());
+                chunk_hashes.extend(replacement.clone());
+            }
+
+            if !replaced_chunks.is_empty() {
+                versions.push(RedactedVersion { version, rows_removed, replaced_chunks });
+                let mut rewrite = TableVersion { chunk_hashes, ..record };
+                rewrite.metadata.remove(BASE_CHUNKS_METADATA_KEY);
+                rewrites.push(rewrite);
+            }
+        }
+
+        let predicate = filters.iter().map(ToString::to_string).collect::<Vec<_>>().join(" AND ");
+        let mut record = RedactionRecord::new(table_name, predicate, versions);
+        if let Some(reason) = &self.reason {
+            record = record.with_reason(reason.clone());
+        }
+        self.catalog.rewrite_versions(table_name, &rewrites, record.clone())?;
+        Ok(record)
+    }
+
+    /// Remove the matching rows of one chunk, storing the remaining rows as
+    /// a new chunk in the same format. `None` if the chunk is kept as is.
+    fn redact_chunk(
+        &self,
+        reader: &TableReader<'_>,
+        record: &TableVersion,
+        format: &str,
+        hash: &str,
+        filters: &[PredicateFilter],
+    ) -> Result<Option<RedactedChunk>, TableError> {
+        let data = self.store.get(hash)?;
+        let patched = data.starts_with(PATCH_MAGIC);
+        let batches = reader.decode_chunk(record, hash, &data)?;
+        let schema = batches[0].schema();
+
+        let mut kept = Vec::with_capacity(batches.len());
+        let mut removed = 0;
+        for batch in &batches {
+            let matches = apply_filters(batch, filters, &schema).map_err(|e| TableError::InvalidFilter {
+                table: record.table_name.clone(),
+                reason: format!("v{}: {}", record.version, e),
+            })?;
+            let matches = if matches.null_count() > 0 { prep_null_mask_filter(&matches) } else { matches };
+            removed += matches.true_count() as u64;
+            kept.push(filter_record_batch(batch, &not(&matches)?)?);
+        }
+        if removed == 0 && !patched {
+            return Ok(None);
+        }
+
+        let batch: RecordBatch = concat_batches(&schema, &kept)?;
+        if batch.num_rows() == 0 {
+            return Ok(Some((None, removed)));
+        }
+        let encoded = match format {
+            ARROW_IPC_FORMAT => encode_ipc(&batch)?,
+            _ => ParquetEncoder::new().encode(&batch)?,
+        };
+        Ok(Some((Some(self.store.put(&encoded)?), removed)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use arrow::array::{Int64Array, StringArray};
+    use tempfile::TempDir;
+
+    use super::*;
+    use crate::catalog::{CatalogError, TagManager};
+    use crate::parquet::{FilterOp, ScalarValue};
+    use crate::table::TableWriter;
+
+    fn batch(ids: Vec<i64>) -> RecordBatch {
+        let names: Vec<String> = ids.iter().map(|i| format!("user-{}", i)).collect();
+        RecordBatch::try_from_iter(vec![
+            ("id", Arc::new(Int64Array::from(ids)) as _),
+            ("name", Arc::new(StringArray::from(names)) as _),
+        ])
+        .unwrap()
+    }
+
+    fn ids(store: &ChunkStore, catalog: &FileCatalog, version: u64) -> Vec<i64> {
+        let batches = TableReader::new(store, catalog).read("users", Some(version)).unwrap();
+        batches
+            .iter()
+            .flat_map(|b| {
+                let col = b.column(0).as_any().downcast_ref::<Int64Array>().unwrap();
+                col.values().to_vec()
+            })
+            .collect()
+    }
+
+    fn id_is(id: i64) -> Vec<PredicateFilter> {
+        vec![PredicateFilter::new("id", FilterOp::Eq, ScalarValue::Int64(id))]
+    }
+
+    #[test]
+    fn test_redact_rewrites_all_versions_in_place() {
+        let dir = TempDir::new().unwrap();
+        let store = ChunkStore::new(dir.path().join("chunks")).unwrap();
+        let catalog = Arc::new(FileCatalog::new(dir.path().join("catalog")).unwrap());
+        let writer = TableWriter::new(&store, &catalog).with_max_rows_per_chunk(2);
+        writer.write("users", &[batch(vec![1, 2, 3])]).unwrap();
+        writer.write("users", &[batch(vec![4])]).unwrap();
+        writer.write("users", &[batch(vec![2, 5])]).unwrap();
+        let tags = TagManager::new(Arc::clone(&catalog));
+        tags.create_from_latest("release", None).unwrap();
+
+        let record = Redactor::new(&store, &catalog)
+            .with_reason("erasure request")
+            .redact("users", &id_is(2))
+            .unwrap();
+
+        assert_eq!(record.predicate, "id = 2");
+        assert_eq!(record.rows_removed(), 2);
+        let rewritten: Vec<u64> = record.versions.iter().map(|v| v.version).collect();
+        assert_eq!(rewritten, vec![1, 3]);
+        assert_eq!(catalog.list_versions("users").unwrap(), vec![1, 2, 3]);
+        assert_eq!(ids(&store, &catalog, 1), vec![1, 3]);
+        assert_eq!(ids(&store, &catalog, 2), vec![4]);
+        assert_eq!(ids(&store, &catalog, 3), vec![5]);
+
+        // The tag still names v3 and now reads the redacted rows
+        assert_eq!(tags.get("release").unwrap().get("users"), Some(3));
+
+        let history = catalog.redaction_history("users").unwrap();
+        assert_eq!(history, vec![record]);
+        assert_eq!(history[0].reason.as_deref(), Some("erasure request"));
+    }
+
+    #[test]
+    fn test_redact_empties_versions_and_rejects_bad_filters() {
+        let dir = TempDir::new().unwrap();
+        let store = ChunkStore::new(dir.path().join("chunks")).unwrap();
+        let catalog = FileCatalog::new(dir.path().join("catalog")).unwrap();
+        TableWriter::new(&store, &catalog).write("users", &[batch(vec![7])]).unwrap();
+        let redactor = Redactor::new(&store, &catalog);
+
+        assert!(matches!(redactor.redact("users", &[]), Err(TableError::InvalidFilter { .. })));
+        let missing = vec![PredicateFilter::new("email", FilterOp::Eq, ScalarValue::Utf8("x".into()))];
+        assert!(matches!(redactor.redact("users", &missing), Err(TableError::InvalidFilter { .. })));
+
+        let record = redactor.redact("users", &id_is(7)).unwrap();
+        assert_eq!(record.rows_removed(), 1);
+        assert!(catalog.get_version("users", Some(1)).unwrap().chunk_hashes.is_empty());
+        assert!(ids(&store, &catalog, 1).is_empty());
+    }
+
+    #[test]
+    fn test_redact_refuses_held_versions() {
+        let dir = TempDir::new().unwrap();
+        let store = ChunkStore::new(dir.path().join("chunks")).unwrap();
+        let catalog = FileCatalog::new(dir.path().join("catalog")).unwrap();
+        let writer = TableWriter::new(&store, &catalog);
+        writer.write("users", &[batch(vec![1, 2])]).unwrap();
+        writer.write("users", &[batch(vec![2, 3])]).unwrap();
+        catalog.hold("users", 1, Some("litigation".into())).unwrap();
+
+        let err = Redactor::new(&store, &catalog).redact("users", &id_is(2)).unwrap_err();
+        assert!(matches!(err, TableError::Catalog(CatalogError::VersionHeld(_, 1))));
+        assert_eq!(ids(&store, &catalog, 2), vec![2, 3]);
+        assert!(catalog.redaction_history("users").unwrap().is_empty());
+    }
+}
@@ -0,0 +1,577 @@
+//! Writing Arrow record batches as table versions.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fs::File;
+use std::io::{BufReader, Seek, SeekFrom};
+use std::path::Path;
+use std::sync::Arc;
+
+use arrow::compute::concat_batches;
+use arrow::datatypes::{Schema, SchemaRef};
+use arrow::error::ArrowError;
+use arrow::ipc::writer::StreamWriter;
+use arrow::record_batch::{RecordBatch, RecordBatchReader};
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+use tracing::warn;
+
+use super::error::TableError;
+use super::patch::{ChunkPatch, RowPatch, DEFAULT_MAX_PATCH_CHAIN, PATCH_MAGIC};
+use super::reader::TableReader;
+use super::{ARROW_IPC_FORMAT, FORMAT_METADATA_KEY, PARQUET_FORMAT};
+use crate::catalog::version::schema_migration_json;
+use crate::catalog::{
+    CatalogError, FileCatalog, SchemaMigration, TableVersion, BASE_CHUNKS_METADATA_KEY, SCHEMA_MIGRATION_METADATA_KEY,
+};
+use crate::chunk_store::{ChunkStore, ChunkTags};
+use crate::parquet::{ParquetEncoder, ParquetError};
+use crate::transaction::{TableWrite, TransactionManager, TxId};
+
+/// Default maximum number of rows per chunk
+pub const DEFAULT_MAX_ROWS_PER_CHUNK: usize = 65_536;
+
+/// Default number of records read to infer the schema of an imported file
+pub const DEFAULT_INFER_ROWS: usize = 1_000;
+
+/// How [`TableWriter::import_csv`] and [`TableWriter::import_jsonl`] read
+/// their input.
+///
+/// Without an explicit schema, column types are inferred from the first
+/// `infer_rows` records (all of them with `None`); a later value that
+/// does not fit the inferred type fails the import.
+#[derive(Debug, Clone)]
+pub struct ImportOptions {
+    pub schema: Option<SchemaRef>,
+    pub infer_rows: Option<usize>,
+    /// CSV only: the first line names the columns
+    pub has_header: bool,
+    /// CSV only: field delimiter
+    pub delimiter: u8,
+}
+
+impl Default for ImportOptions {
+    fn default() -> Self {
+        Self {
+            schema: None,
+            infer_rows: Some(DEFAULT_INFER_ROWS),
+            has_header: true,
+            delimiter: b',',
+        }
+    }
+}
+
+impl ImportOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Read the file with this schema instead of inferring one
+    pub fn with_schema(mut self, schema: SchemaRef) -> Self {
+        self.schema = Some(schema);
+        self
+    }
+
+    /// Infer the schema from at most `rows` records (`None` = all)
+    pub fn with_infer_rows(mut self, rows: Option<usize>) -> Self {
+        self.infer_rows = rows;
+        self
+    }
+
+    pub fn with_header(mut self, has_header: bool) -> Self {
+        self.has_header = has_header;
+        self
+    }
+
+    pub fn with_delimiter(mut self, delimiter: u8) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+}
+
+/// Hash identifying an Arrow schema: BLAKE3 over each field's name, data
+/// type and nullability, in order. Schema-level metadata is ignored.
+pub fn schema_hash(schema: &Schema) -> String {
+    let mut hasher = blake3::Hasher::new();
+    for field in schema.fields() {
+        hasher.update(field.name().as_bytes());
+        hasher.update(b"\0");
+        hasher.update(field.data_type().to_string().as_bytes());
+        hasher.update(if field.is_nullable() { b"\0?\n" } else { b"\0!\n" });
+    }
+    hasher.finalize().to_hex().to_string()
+}
+
+/// Serialize one batch as an Arrow IPC stream
+pub(crate) fn encode_ipc(batch: &RecordBatch) -> Result<Vec<u8>, TableError> {
+    let mut buffer = Vec::new();
+    let mut writer = StreamWriter::try_new(&mut buffer, &batch.schema())?;
+    writer.write(batch)?;
+    writer.finish()?;
+    drop(writer);
+    Ok(buffer)
+}
+
+/// Writes Arrow record batches to the chunk store and commits them as the
+/// next version of a table.
+///
+/// Each chunk is an Arrow IPC stream holding one batch of at most
+/// `max_rows_per_chunk` rows. The version records the schema hash (see
+/// [`schema_hash`]) and is tagged with the chunk format, so
+/// [`TableReader`](super::TableReader) can decode it. Versions committed
+/// directly label their chunks in the store's backend with the table and
+/// version (see [`ChunkStore::tag_chunks`]); writes added to a transaction
+/// are labelled by [`TransactionManager::tag_chunks_on_commit`].
+///
+/// # Example
+///
+/// ```ignore
+/// let version = TableWriter::new(&store, &catalog)
+///     .with_metadata("source", "ingest")
+///     .write("events", &[batch])?;
+/// ```
+pub struct TableWriter<'a> {
+    store: &'a ChunkStore,
+    catalog: &'a FileCatalog,
+    max_rows_per_chunk: usize,
+    max_patch_chain: u32,
+    metadata: HashMap<String, String>,
+}
+
+impl<'a> TableWriter<'a> {
+    pub fn new(store: &'a ChunkStore, catalog: &'a FileCatalog) -> Self {
+        Self {
+            store,
+            catalog,
+            max_rows_per_chunk: DEFAULT_MAX_ROWS_PER_CHUNK,
+            max_patch_chain: DEFAULT_MAX_PATCH_CHAIN,
+            metadata: HashMap::new(),
+        }
+    }
+
+    /// Split batches so no chunk holds more than `rows` rows
+    /// (default [`DEFAULT_MAX_ROWS_PER_CHUNK`]).
+    pub fn with_max_rows_per_chunk(mut self, rows: usize) -> Self {
+        self.max_rows_per_chunk = rows.max(1);
+        self
+    }
+
+    /// Longest patch chain [`write_patch`](Self::write_patch) builds before
+    /// storing a chunk in full (default [`DEFAULT_MAX_PATCH_CHAIN`]; 0
+    /// always stores full chunks).
+    pub fn with_max_patch_chain(mut self, max_patch_chain: u32) -> Self {
+        self.max_patch_chain = max_patch_chain;
+        self
+    }
+
+    /// Attach metadata to the versions this writer commits.
+    pub fn with_metadata(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.metadata.insert(key.into(), value.into());
+        self
+    }
+
+    /// Allow the versions this writer commits to change the table's schema
+    /// as `migration` describes (see
+    /// [`SchemaRegistry::check_change`](crate::catalog::SchemaRegistry::check_change)).
+    /// Adding nullable columns needs no migration.
+    pub fn with_migration(self, migration: &SchemaMigration) -> Self {
+        self.with_metadata(SCHEMA_MIGRATION_METADATA_KEY, schema_migration_json(migration))
+    }
+
+    /// Store `batches` as chunks without committing a version.
+    ///
+    /// Returns the chunk hashes, in row order, and the schema hash, for a
+    /// caller committing through a transaction.
+    pub fn write_chunks(
+        &self,
+        table_name: &str,
+        batches: &[RecordBatch],
+    ) -> Result<(Vec<String>, String), TableError> {
+        let first = batches
+            .first()
+            .ok_or_else(|| TableError::EmptyInput(table_name.to_string()))?;
+        let schema = first.schema();
+        if batches.iter().any(|b| b.schema().fields() != schema.fields()) {
+            return Err(TableError::SchemaMismatch {
+                table: table_name.to_string(),
+            });
+        }
+
+        let mut hashes = Vec::new();
+        for batch in batches {
+            let mut offset = 0;
+            while offset < batch.num_rows() {
+                let len = self.max_rows_per_chunk.min(batch.num_rows() - offset);
+                hashes.push(self.store.put(&encode_ipc(&batch.slice(offset, len))?)?);
+                offset += len;
+            }
+        }
+        if hashes.is_empty() {
+            // No rows: keep the schema with one empty chunk
+            hashes.push(self.store.put(&encode_ipc(first)?)?);
+        }
+        // Registered so later versions can be checked against it
+        let schema_hash = self.catalog.schemas().register_arrow(&schema)?;
+        Ok((hashes, schema_hash))
+    }
+
+    /// Write `batches` as the next version of `table_name`.
+    ///
+    /// # Errors
+    /// - `EmptyInput` if `batches` is empty.
+    /// - `SchemaMismatch` if the batches have different schemas.
+    pub fn write(
+        &self,
+        table_name: &str,
+        batches: &[RecordBatch],
+    ) -> Result<TableVersion, TableError> {
+        let (hashes, schema_hash) = self.write_chunks(table_name, batches)?;
+        let mut metadata = self.metadata.clone();
+        metadata.insert(FORMAT_METADATA_KEY.to_string(), ARROW_IPC_FORMAT.to_string());
+        let version =
+            self.catalog
+                .commit_next_version_with_meta(table_name, hashes, metadata, Some(schema_hash))?;
+        self.committed(table_name, version)
+    }
+
+    /// Create `table_name` with `schema` and no rows, as version 1.
+    ///
+    /// The version holds one empty chunk carrying the schema, as an empty
+    /// [`write`](Self::write) does, so readers get the schema back and
+    /// later writes are checked against its hash. `metadata` is stored on
+    /// the version on top of any set with
+    /// [`with_metadata`](Self::with_metadata).
+    ///
+    /// Without `tx` the version is committed to the catalog directly. With
+    /// `tx` the write is added to that transaction, so committing it puts
+    /// the table on the transaction's branch and in the changelog like any
+    /// other new table.
+    ///
+    /// # Errors
+    /// - `Catalog(TableAlreadyExists)` if the table has any version.
+    pub fn create_table(
+        &self,
+        table_name: &str,
+        schema: SchemaRef,
+        metadata: HashMap<String, String>,
+        tx: Option<(&TransactionManager, TxId)>,
+    ) -> Result<TableVersion, TableError> {
+        if self.catalog.table_exists(table_name)? {
+            return Err(CatalogError::TableAlreadyExists(table_name.to_string()).into());
+        }
+        let (hashes, schema_hash) = self.write_chunks(table_name, &[RecordBatch::new_empty(schema)])?;
+        let mut metadata: HashMap<String, String> = self.metadata.clone().into_iter().chain(metadata).collect();
+        metadata.insert(FORMAT_METADATA_KEY.to_string(), ARROW_IPC_FORMAT.to_string());
+
+        let Some((manager, tx_id)) = tx else {
+            let mut version = TableVersion::new(table_name, 1, hashes).with_schema_hash(schema_hash);
+            version.metadata = metadata;
+            self.catalog.commit(version)?;
+            return self.committed(table_name, 1);
+        };
+        let write = metadata.iter().fold(
+            TableWrite::new(table_name, 1, hashes.clone()).with_schema_hash(schema_hash.clone()),
+            |write, (key, value)| write.with_metadata(key, value),
+        );
+        manager.add_write(tx_id, write)?;
+        let mut version = TableVersion::new(table_name, 1, hashes).with_schema_hash(schema_hash);
+        version.metadata = metadata;
+        Ok(version)
+    }
+
+    /// Apply `patch` to the latest version of `table_name` and commit the
+    /// result as the next version.
+    ///
+    /// Chunks the patch does not touch are reused; each touched chunk is
+    /// stored as a patch chunk on top of the old one, in the old version's
+    /// chunk format, unless that would make the patch chain longer than
+    /// `max_patch_chain`. Appended rows go into the last chunk.
+    ///
+    /// # Errors
+    /// - `InvalidPatch` if the patch changes no row, names a row the
+    ///   version does not have, or deletes or replaces a row twice.
+    /// - `SchemaMismatch` if patch rows do not have the table's schema.
+    pub fn write_patch(&self, table_name: &str, patch: &RowPatch) -> Result<TableVersion, TableError> {
+        let invalid = |reason: String| TableError::InvalidPatch {
+            table: table_name.to_string(),
+            reason,
+        };
+        if patch.is_empty() {
+            return Err(invalid("the patch changes no row".to_string()));
+        }
+        let base = self.catalog.get_version(table_name, None)?;
+        if base.chunk_hashes.is_empty() {
+            return Err(invalid(format!("v{} has no chunk to patch", base.version)));
+        }
+
+        // Row offsets and patch chains of the base chunks
+        let reader = TableReader::new(self.store, self.catalog);
+        let mut offsets = Vec::with_capacity(base.chunk_hashes.len() + 1);
+        let mut chains = Vec::with_capacity(base.chunk_hashes.len());
+        let mut schema = None;
+        offsets.push(0u64);
+        for hash in &base.chunk_hashes {
+            let data = self.store.get(hash)?;
+            let batches = reader.decode_chunk(&base, hash, &data)?;
+            schema.get_or_insert_with(|| batches[0].schema());
+            offsets.push(offsets.last().unwrap() + batches.iter().map(|b| b.num_rows() as u64).sum::<u64>());
+            chains.push(match data.starts_with(PATCH_MAGIC) {
+                true => ChunkPatch::decode(&data).map(|p| p.bases).unwrap_or_default(),
+                false => Vec::new(),
+            });
+        }
+        let schema = schema.expect("at least one chunk");
+        let total = *offsets.last().unwrap();
+        let batches = patch.updates.iter().map(|(_, b)| b).chain(&patch.inserts);
+        if batches.into_iter().any(|b| b.schema().fields() != schema.fields()) {
+            return Err(TableError::SchemaMismatch { table: table_name.to_string() });
+        }
+
+        // Split the changes by chunk, in chunk-local positions
+        let chunk_of = |row: u64| offsets.partition_point(|&o| o <= row) - 1;
+        let mut deletes: BTreeMap<usize, Vec<u64>> = BTreeMap::new();
+        for &row in &patch.deletes {
+            if row >= total {
+                return Err(invalid(format!("row {} is out of range ({} rows)", row, total)));
+            }
+            let chunk = chunk_of(row);
+            deletes.entry(chunk).or_default().push(row - offsets[chunk]);
+        }
+        let mut replaced: BTreeMap<usize, Vec<(u64, &RecordBatch, u64)>> = BTreeMap::new();
+        let mut seen = HashSet::new();
+        for (rows, batch) in &patch.updates {
+            if rows.len() != batch.num_rows() {
+                return Err(invalid(format!("{} positions for {} replacement rows", rows.len(), batch.num_rows())));
+            }
+            for (i, &row) in rows.iter().enumerate() {
+                if row >= total {
+                    return Err(invalid(format!("row {} is out of range ({} rows)", row, total)));
+                }
+                if patch.deletes.contains(&row) || !seen.insert(row) {
+                    return Err(invalid(format!("row {} is changed twice", row)));
+                }
+                let chunk = chunk_of(row);
+                replaced.entry(chunk).or_default().push((row - offsets[chunk], batch, i as u64));
+            }
+        }
+        let last = base.chunk_hashes.len() - 1;
+        let inserts = patch.inserts.iter().filter(|b| b.num_rows() > 0).cloned().collect::<Vec<_>>();
+        let touched: BTreeSet<usize> = deletes
+            .keys()
+            .chain(replaced.keys())
+            .copied()
+            .chain((!inserts.is_empty()).then_some(last))
+            .collect();
+
+        let format = base.metadata.get(FORMAT_METADATA_KEY).cloned().unwrap_or_else(|| PARQUET_FORMAT.to_string());
+        let mut hashes = base.chunk_hashes.clone();
+        for chunk in touched {
+            let (positions, replacements) = match replaced.remove(&chunk) {
+                Some(rows) => {
+                    let positions = rows.iter().map(|(row, _, _)| *row).collect();
+                    let taken = rows
+                        .iter()
+                        .map(|(_, batch, i)| batch.slice(*i as usize, 1))
+                        .collect::<Vec<_>>();
+                    (positions, Some(concat_batches(&schema, &taken)?))
+                }
+                None => (Vec::new(), None),
+            };
+            let inserts = match chunk == last && !inserts.is_empty() {
+                true => Some(concat_batches(&schema, &inserts)?),
+                false => None,
+            };
+            let base_hash = &base.chunk_hashes[chunk];
+            let chunk_patch = ChunkPatch::new(
+                base_hash,
+                &chains[chunk],
+                deletes.remove(&chunk).unwrap_or_default(),
+                positions,
+                replacements,
+                inserts,
+                &schema,
+            )?;
+
+            let encoded = if chunk_patch.bases.len() as u32 > self.max_patch_chain {
+                let batches = reader.decode_chunk(&base, base_hash, &self.store.get(base_hash)?)?;
+                let full = chunk_patch
+                    .apply(&concat_batches(&schema, &batches)?)
+                    .map_err(invalid)?;
+                chains[chunk] = Vec::new();
+                match format.as_str() {
+                    ARROW_IPC_FORMAT => encode_ipc(&full)?,
+                    _ => ParquetEncoder::new().encode(&full)?,
+                }
+            } else {
+                chains[chunk] = chunk_patch.bases.clone();
+                chunk_patch.encode()?
+            };
+            hashes[chunk] = self.store.put(&encoded)?;
+        }
+
+        let mut bases: Vec<String> = chains.into_iter().flatten().collect();
+        bases.sort();
+        bases.dedup();
+        let mut metadata = base.metadata.clone();
+        metadata.extend(self.metadata.clone());
+        metadata.remove(BASE_CHUNKS_METADATA_KEY);
+        if !bases.is_empty() {
+            metadata.insert(BASE_CHUNKS_METADATA_KEY.to_string(), bases.join(","));
+        }
+        let version = self
+            .catalog
+            .commit_next_version_with_meta(table_name, hashes, metadata, base.schema_hash.clone())?;
+        self.committed(table_name, version)
+    }
+
+    /// Import a Parquet file as the next version of `table_name`.
+    ///
+    /// The file is read in batches of at most `max_rows_per_chunk` rows,
+    /// each stored as a Parquet chunk, so the whole file is never held in
+    /// memory. Without `tx` the version is committed to the catalog
+    /// directly. With `tx` the write is added to that transaction instead
+    /// and the returned version is the one it will commit; metadata set
+    /// with [`with_metadata`](Self::with_metadata) only applies to direct
+    /// commits.
+    ///
+    /// # Errors
+    /// - `EmptyInput` if the file holds no rows.
+    /// - `Parquet` if the file is not valid Parquet.
+    pub fn import_parquet(
+        &self,
+        path: impl AsRef<Path>,
+        table_name: &str,
+        tx: Option<(&TransactionManager, TxId)>,
+    ) -> Result<TableVersion, TableError> {
+        let reader = ParquetRecordBatchReaderBuilder::try_new(File::open(path)?)
+            .map_err(ParquetError::from)?
+            .with_batch_size(self.max_rows_per_chunk)
+            .build()
+            .map_err(ParquetError::from)?;
+        let schema = reader.schema();
+        self.import_batches(&schema, reader, table_name, tx)
+    }
+
+    /// Import a CSV file as the next version of `table_name`.
+    ///
+    /// Rows are converted in batches of at most `max_rows_per_chunk` rows,
+    /// each stored as a Parquet chunk, and committed like
+    /// [`import_parquet`](Self::import_parquet): directly, or as one write
+    /// of the transaction `tx`.
+    ///
+    /// # Errors
+    /// - `EmptyInput` if the file holds no rows.
+    /// - `Arrow` if the schema cannot be inferred or a value does not
+    ///   parse as its column's type.
+    pub fn import_csv(
+        &self,
+        path: impl AsRef<Path>,
+        table_name: &str,
+        options: &ImportOptions,
+        tx: Option<(&TransactionManager, TxId)>,
+    ) -> Result<TableVersion, TableError> {
+        let mut file = File::open(path)?;
+        let schema = match &options.schema {
+            Some(schema) => schema.clone(),
+            None => {
+                let format = arrow::csv::reader::Format::default()
+                    .with_header(options.has_header)
+                    .with_delimiter(options.delimiter);
+                let (schema, _) = format.infer_schema(&mut file, options.infer_rows)?;
+                file.seek(SeekFrom::Start(0))?;
+                Arc::new(schema)
+            }
+        };
+        let reader = arrow::csv::ReaderBuilder::new(schema.clone())
+            .with_header(options.has_header)
+            .with_delimiter(options.delimiter)
+            .with_batch_size(self.max_rows_per_chunk)
+            .build(file)?;
+        self.import_batches(&schema, reader, table_name, tx)
+    }
+
+    /// Import a JSON Lines file (one object per line) as the next version
+    /// of `table_name`, chunked and committed like
+    /// [`import_csv`](Self::import_csv). CSV-only options are ignored.
+    ///
+    /// # Errors
+    /// - `EmptyInput` if the file holds no records.
+    /// - `Arrow` if a line is not a JSON object or a value does not fit
+    ///   its column's type.
+    pub fn import_jsonl(
+        &self,
+        path: impl AsRef<Path>,
+        table_name: &str,
+        options: &ImportOptions,
+        tx: Option<(&TransactionManager, TxId)>,
+    ) -> Result<TableVersion, TableError> {
+        let mut file = BufReader::new(File::open(path)?);
+        let schema = match &options.schema {
+            Some(schema) => schema.clone(),
+            None => {
+                let (schema, _) = arrow::json::reader::infer_json_schema_from_seekable(&mut file, options.infer_rows)?;
+                Arc::new(schema)
+            }
+        };
+        let reader = arrow::json::ReaderBuilder::new(schema.clone())
+            .with_batch_size(self.max_rows_per_chunk)
+            .build(file)?;
+        self.import_batches(&schema, reader, table_name, tx)
+    }
+
+    /// The version this writer just committed, after tagging its chunks in
+    /// the chunk store's backend. The commit has happened, so a tagging
+    /// failure is only logged.
+    fn committed(&self, table_name: &str, version: u64) -> Result<TableVersion, TableError> {
+        let record = self.catalog.get_version(table_name, Some(version))?;
+        let tags = ChunkTags::new(table_name, version);
+        if let Err(e) = self.store.tag_chunks(&record.chunk_hashes, &tags) {
+            warn!(table = table_name, version, error = %e, "Failed to tag chunks");
+        }
+        Ok(record)
+    }
+
+    /// Store `batches` as Parquet chunks and commit them as the next
+    /// version of `table_name`, directly or through `tx`
+    fn import_batches(
+        &self,
+        schema: &Schema,
+        batches: impl Iterator<Item = Result<RecordBatch, ArrowError>>,
+        table_name: &str,
+        tx: Option<(&TransactionManager, TxId)>,
+    ) -> Result<TableVersion, TableError> {
+        let schema_hash = self.catalog.schemas().register_arrow(schema)?;
+        let encoder = ParquetEncoder::new();
+        let mut hashes = Vec::new();
+        for batch in batches {
+            let batch = batch?;
+            if batch.num_rows() > 0 {
+                hashes.push(self.store.put(&encoder.encode(&batch)?)?);
+            }
+        }
+        if hashes.is_empty() {
+            return Err(TableError::EmptyInput(table_name.to_string()));
+        }
+
+        let Some((manager, tx_id)) = tx else {
+            let version = self.catalog.commit_next_version_with_meta(
+                table_name,
+                hashes,
+                self.metadata.clone(),
+                Some(schema_hash),
+            )?;
+            return self.committed(table_name, version);
+        };
+        let next_version = match self.catalog.get_version(table_name, None) {
+            Ok(latest) => latest.version + 1,
+            Err(CatalogError::TableNotFound(_)) => 1,
+            Err(e) => return Err(e.into()),
+        };
+        let write = self.metadata.iter().fold(
+            TableWrite::new(table_name, next_version, hashes.clone()).with_schema_hash(schema_hash.clone()),
+            |write, (key, value)| write.with_metadata(key, value),
+        );
+        manager.add_write(tx_id, write)?;
+        let mut version = TableVersion::new(table_name, next_version, hashes).with_schema_hash(schema_hash);
+        version.metadata = self.metadata.clone();
+        Ok(version)
+    }
+}
@@ -0,0 +1,531 @@
+//! Micro-batching of small appends into fewer commits.
+//!
+//! High-frequency writers (an event or a handful of rows at a time) would
+//! otherwise create one table version and one transaction record per
+//! write. A [`MicroBatcher`] buffers appends per branch and commits each
+//! buffer as a single transaction once it holds `max_rows` rows or its
+//! oldest write has waited `max_delay`. Every writer holds a
+//! [`BatchTicket`] that resolves when the batch containing its write
+//! commits or fails, so each write is still acknowledged individually, at
+//! the cost of up to `max_delay` extra latency.
+
+use std::collections::HashMap;
+use std::sync::mpsc;
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use super::error::TransactionError;
+use super::manager::TransactionManager;
+use super::types::{TableWrite, TxId};
+use crate::catalog::CatalogError;
+
+/// Default longest time a write waits in a buffer
+pub const DEFAULT_BATCH_MAX_DELAY: Duration = Duration::from_millis(10);
+
+/// Default number of buffered rows that triggers a commit
+pub const DEFAULT_BATCH_MAX_ROWS: usize = 10_000;
+
+/// Default number of times a conflicting batch is rebuilt and retried
+pub const DEFAULT_BATCH_MAX_RETRIES: u32 = 3;
+
+/// When a branch's buffer is committed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MicroBatchConfig {
+    /// Longest time a write waits before its batch is committed
+    pub max_delay: Duration,
+
+    /// Buffered rows that trigger a commit before `max_delay` elapses
+    pub max_rows: usize,
+
+    /// Times a batch that conflicts with a commit made outside the batcher
+    /// is rebuilt on the new head and retried
+    pub max_retries: u32,
+}
+
+impl Default for MicroBatchConfig {
+    fn default() -> Self {
+        Self {
+            max_delay: DEFAULT_BATCH_MAX_DELAY,
+            max_rows: DEFAULT_BATCH_MAX_ROWS,
+            max_retries: DEFAULT_BATCH_MAX_RETRIES,
+        }
+    }
+}
+
+impl MicroBatchConfig {
+    pub fn with_max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+
+    pub fn with_max_rows(mut self, max_rows: usize) -> Self {
+        self.max_rows = max_rows.max(1);
+        self
+    }
+
+    pub fn with_max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+}
+
+/// Chunks appended to a table by one writer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BatchedWrite {
+    pub table_name: String,
+
+    /// Chunks appended after the table's current chunks
+    pub chunk_hashes: Vec<String>,
+
+    /// Rows in those chunks (counted toward `max_rows`)
+    pub rows: usize,
+}
+
+impl BatchedWrite {
+    pub fn new(table_name: impl Into<String>, chunk_hashes: Vec<String>, rows: usize) -> Self {
+        Self {
+            table_name: table_name.into(),
+            chunk_hashes,
+            rows,
+        }
+    }
+}
+
+/// Acknowledgement that the batch holding a write committed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BatchAck {
+    /// Transaction that committed the batch
+    pub tx_id: TxId,
+    pub branch: String,
+
+    /// Table -> version committed by the batch
+    pub versions: HashMap<String, u64>,
+
+    /// Writes committed together in the batch
+    pub writes: usize,
+}
+
+/// Counters of a batcher since it started
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MicroBatchStats {
+    pub batches_committed: u64,
+    pub batches_failed: u64,
+    pub writes_committed: u64,
+    pub rows_committed: u64,
+
+    /// Writes buffered and not yet committed
+    pub pending_writes: usize,
+}
+
+type AckResult = Result<BatchAck, TransactionError>;
+
+/// Resolves when the batch holding a write commits or fails
+pub struct BatchTicket {
+    receiver: mpsc::Receiver<AckResult>,
+}
+
+impl BatchTicket {
+    /// Block until the batch holding the write commits. The result is
+    /// delivered once; later calls report the batcher as stopped.
+    ///
+    /// # Errors
+    /// `BatchFailed` with the commit error if the batch failed.
+    pub fn wait(&self) -> AckResult {
+        self.receiver.recv().unwrap_or_else(|_| Err(stopped()))
+    }
+
+    /// Like [`wait`](Self::wait), but give up after `timeout` (`None`
+    /// if the batch has not committed yet).
+    pub fn wait_timeout(&self, timeout: Duration) -> Option<AckResult> {
+        match self.receiver.recv_timeout(timeout) {
+            Ok(result) => Some(result),
+            Err(mpsc::RecvTimeoutError::Timeout) => None,
+            Err(mpsc::RecvTimeoutError::Disconnected) => Some(Err(stopped())),
+        }
+    }
+}
+
+fn stopped() -> TransactionError {
+    TransactionError::InvalidState {
+        expected: "running micro-batcher".to_string(),
+        actual: "stopped".to_string(),
+    }
+}
+
+struct Buffer {
+    opened_at: Instant,
+    rows: usize,
+    writes: Vec<BatchedWrite>,
+    waiters: Vec<mpsc::Sender<AckResult>>,
+}
+
+#[derive(Default)]
+struct State {
+    buffers: HashMap<String, Buffer>,
+    flush_requested: bool,
+    closed: bool,
+    stats: MicroBatchStats,
+}
+
+struct Shared {
+    manager: Arc<TransactionManager>,
+    config: MicroBatchConfig,
+    state: Mutex<State>,
+    wake: Condvar,
+}
+
+/// Buffers appends per branch and commits each buffer as one transaction.
+///
+/// A background thread commits buffers as they become due. Dropping the
+/// batcher commits whatever is still buffered before the thread exits.
+///
+/// # Example
+///
+/// ```ignore
+/// let batcher = MicroBatcher::with_config(
+///     Arc::clone(&manager),
+///     MicroBatchConfig::default().with_max_delay(Duration::from_millis(5)),
+/// );
+/// let ticket = batcher.submit("main", BatchedWrite::new("events", vec![hash], 1))?;
+/// let ack = ticket.wait()?; // committed as part of ack.tx_id
+/// ```
+pub struct MicroBatcher {
+    shared: Arc<Shared>,
+    flusher: Option<JoinHandle<()>>,
+}
+
+impl MicroBatcher {
+    pub fn new(manager: Arc<TransactionManager>) -> Self {
+        Self::with_config(manager, MicroBatchConfig::default())
+    }
+
+    pub fn with_config(manager: Arc<TransactionManager>, config: MicroBatchConfig) -> Self {
+        let shared = Arc::new(Shared {
+            manager,
+            config,
+            state: Mutex::new(State::default()),
+            wake: Condvar::new(),
+        });
+        let flusher = {
+            let shared = Arc::clone(&shared);
+            thread::spawn(move || shared.run())
+        };
+        Self {
+            shared,
+            flusher: Some(flusher),
+        }
+    }
+
+    pub fn config(&self) -> MicroBatchConfig {
+        self.shared.config
+    }
+
+    /// Buffer an append to `branch`; the returned ticket resolves when its
+    /// batch commits.
+    pub fn submit(&self, branch: &str, write: BatchedWrite) -> Result<BatchTicket, TransactionError> {
+        let (sender, receiver) = mpsc::channel();
+        {
+            let mut state = self.shared.lock()?;
+            let buffer = state.buffers.entry(branch.to_string()).or_insert_with(|| Buffer {
+                opened_at: Instant::now(),
+                rows: 0,
+                writes: Vec::new(),
+                waiters: Vec::new(),
+            });
+            buffer.rows += write.rows;
+            buffer.writes.push(write);
+            buffer.waiters.push(sender);
+            state.stats.pending_writes += 1;
+        }
+        // A new buffer sets a new deadline; a full one is due now
+        self.shared.wake.notify_one();
+        Ok(BatchTicket { receiver })
+    }
+
+    /// Buffer an append and block until its batch commits
+    pub fn write(&self, branch: &str, write: BatchedWrite) -> AckResult {
+        self.submit(branch, write)?.wait()
+    }
+
+    /// Commit every buffer now instead of at its deadline. Does not wait
+    /// for the commits; wait on the tickets for that.
+    pub fn flush(&self) -> Result<(), TransactionError> {
+        self.shared.lock()?.flush_requested = true;
+        self.shared.wake.notify_one();
+        Ok(())
+    }
+
+    pub fn stats(&self) -> Result<MicroBatchStats, TransactionError> {
+        Ok(self.shared.lock()?.stats.clone())
+    }
+}
+
+impl Drop for MicroBatcher {
+    fn drop(&mut self) {
+        if let Ok(mut state) = self.shared.state.lock() {
+            state.closed = true;
+        }
+        self.shared.wake.notify_one();
+        if let Some(flusher) = self.flusher.take() {
+            let _ = flusher.join();
+        }
+    }
+}
+
+impl Shared {
+    fn lock(&self) -> Result<MutexGuard<'_, State>, TransactionError> {
+        self.state
+            .lock()
+            .map_err(|_| TransactionError::LockError("micro_batcher".to_string()))
+    }
+
+    /// Flusher thread: commit buffers as they become due until closed
+    fn run(&self) {
+        while let Ok(Some(due)) = self.next_due() {
+            for (branch, buffer) in due {
+                self.commit_buffer(&branch, buffer);
+            }
+        }
+    }
+
+    /// Wait until at least one buffer is due and take the due buffers.
+    /// `None` once the batcher is closed and every buffer is committed.
+    fn next_due(&self) -> Result<Option<Vec<(String, Buffer)>>, TransactionError> {
+        let mut state = self.lock()?;
+        loop {
+            if state.closed || state.flush_requested {
+                state.flush_requested = false;
+                if state.closed && state.buffers.is_empty() {
+                    return Ok(None);
+                }
+                let all: Vec<_> = state.buffers.drain().collect();
+                if !all.is_empty() {
+                    return Ok(Some(all));
+                }
+            }
+
+            let now = Instant::now();
+            let due: Vec<String> = state
+                .buffers
+                .iter()
+                .filter(|(_, b)| {
+                    b.rows >= self.config.max_rows || now.duration_since(b.opened_at) >= self.config.max_delay
+                })
+                .map(|(branch, _)| branch.clone())
+                .collect();
+            if !due.is_empty() {
+                let taken = due
+                    .into_iter()
+                    .filter_map(|branch| state.buffers.remove_entry(&branch))
+                    .collect();
+                return Ok(Some(taken));
+            }
+
+            let next_deadline = state.buffers.values().map(|b| b.opened_at + self.config.max_delay).min();
+            state = match next_deadline {
+                Some(deadline) => {
+                    let timeout = deadline.saturating_duration_since(now);
+                    self.wake
+                        .wait_timeout(state, timeout)
+                        .map_err(|_| TransactionError::LockError("micro_batcher".to_string()))?
+                        .0
+                }
+                None => self
+                    .wake
+                    .wait(state)
+                    .map_err(|_| TransactionError::LockError("micro_batcher".to_string()))?,
+            };
+        }
+    }
+
+    /// Commit one buffer, retrying conflicts, and acknowledge its writers
+    fn commit_buffer(&self, branch: &str, buffer: Buffer) {
+        let mut attempts = 0;
+        let result = loop {
+            match self.commit_once(branch, &buffer.writes) {
+                Err(e) if e.is_conflict() && attempts < self.config.max_retries => attempts += 1,
+                result => break result,
+            }
+        };
+
+        if let Ok(mut state) = self.state.lock() {
+            let stats = &mut state.stats;
+            stats.pending_writes = stats.pending_writes.saturating_sub(buffer.writes.len());
+            if result.is_ok() {
+                stats.batches_committed += 1;
+                stats.writes_committed += buffer.writes.len() as u64;
+                stats.rows_committed += buffer.rows as u64;
+            } else {
+                stats.batches_failed += 1;
+            }
+        }
+
+        // Writers that dropped their ticket are not waiting for the result
+        match result {
+            Ok(ack) => {
+                for waiter in buffer.waiters {
+                    let _ = waiter.send(Ok(ack.clone()));
+                }
+            }
+            Err(e) => {
+                let source = Arc::new(e);
+                for waiter in buffer.waiters {
+                    let _ = waiter.send(Err(TransactionError::BatchFailed {
+                        branch: branch.to_string(),
+                        source: Arc::clone(&source),
+                    }));
+                }
+            }
+        }
+    }
+
+    /// Commit `writes` to `branch` as one transaction: one new version per
+    /// table, holding the table's chunks on the branch followed by every
+    /// appended chunk in submission order
+    fn commit_once(&self, branch: &str, writes: &[BatchedWrite]) -> AckResult {
+        let manager = &self.manager;
+        let tx_id = manager.begin(Some(branch))?;
+        let result = self.stage_writes(tx_id, writes).and_then(|()| manager.commit(tx_id));
+        if let Err(e) = result {
+            let _ = manager.abort(tx_id, "micro-batch commit failed");
+            return Err(e);
+        }
+
+        // Versions actually committed (the catalog may have moved on
+        // between staging and commit)
+        let versions = manager
+            .get_transaction(tx_id)?
+            .writes
+            .into_iter()
+            .map(|w| (w.table_name, w.new_version))
+            .collect();
+        Ok(BatchAck {
+            tx_id,
+            branch: branch.to_string(),
+            versions,
+            writes: writes.len(),
+        })
+    }
+
+    fn stage_writes(&self, tx_id: TxId, writes: &[BatchedWrite]) -> Result<(), TransactionError> {
+        let manager = &self.manager;
+        let catalog = manager.catalog();
+        let catalog_err = |e: CatalogError| TransactionError::CatalogError(e.to_string());
+
+        let mut appended: Vec<(&str, Vec<String>)> = Vec::new();
+        for write in writes {
+            match appended.iter_mut().find(|(table, _)| *table == write.table_name) {
+                Some((_, chunks)) => chunks.extend(write.chunk_hashes.iter().cloned()),
+                None => appended.push((&write.table_name, write.chunk_hashes.clone())),
+            }
+        }
+
+        let snapshot = manager.get_transaction(tx_id)?.read_snapshot;
+        for (table, chunks) in appended {
+            let mut chunk_hashes = match snapshot.get(table) {
+                Some(&version) => catalog.get_version(table, Some(version)).map_err(catalog_err)?.chunk_hashes,
+                None => Vec::new(),
+            };
+            chunk_hashes.extend(chunks);
+            let next_version = match catalog.get_version(table, None) {
+                Ok(latest) => latest.version + 1,
+                Err(CatalogError::TableNotFound(_)) => 1,
+                Err(e) => return Err(catalog_err(e)),
+            };
+            manager.add_write(tx_id, TableWrite::new(table, next_version, chunk_hashes))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::branch::BranchManager;
+    use crate::catalog::FileCatalog;
+    use crate::transaction::EpochConfig;
+    use tempfile::TempDir;
+
+    fn setup() -> (TempDir, Arc<FileCatalog>, Arc<TransactionManager>) {
+        let dir = TempDir::new().unwrap();
+        let catalog = Arc::new(FileCatalog::new(dir.path().join("catalog")).unwrap());
+        let branches = Arc::new(BranchManager::new(dir.path().join("branches")).unwrap());
+        let manager = TransactionManager::with_config(
+            dir.path().join("tx"),
+            Arc::clone(&catalog),
+            Some(branches),
+            EpochConfig::default(),
+        )
+        .unwrap();
+        (dir, catalog, Arc::new(manager))
+    }
+
+    fn append(table: &str, chunk: &str, rows: usize) -> BatchedWrite {
+        BatchedWrite::new(table, vec![chunk.to_string()], rows)
+    }
+
+    #[test]
+    fn test_writes_within_delay_share_one_commit() {
+        let (_dir, catalog, manager) = setup();
+        let batcher = MicroBatcher::with_config(
+            Arc::clone(&manager),
+            MicroBatchConfig::default().with_max_delay(Duration::from_secs(60)),
+        );
+
+        let tickets: Vec<_> = ["a", "b", "c"]
+            .iter()
+            .map(|chunk| batcher.submit("main", append("events", chunk, 1)).unwrap())
+            .collect();
+        assert!(tickets[0].wait_timeout(Duration::from_millis(20)).is_none());
+        batcher.flush().unwrap();
+
+        let acks: Vec<_> = tickets.iter().map(|t| t.wait().unwrap()).collect();
+        assert!(acks.iter().all(|ack| ack == &acks[0]));
+        assert_eq!(acks[0].writes, 3);
+        assert_eq!(acks[0].versions["events"], 1);
+        assert_eq!(catalog.list_versions("events").unwrap(), vec![1]);
+        assert_eq!(catalog.get_version("events", None).unwrap().chunk_hashes, vec!["a", "b", "c"]);
+
+        // The next batch appends to the committed chunks
+        let ticket = batcher.submit("main", append("events", "d", 1)).unwrap();
+        batcher.flush().unwrap();
+        ticket.wait().unwrap();
+        assert_eq!(catalog.get_version("events", None).unwrap().chunk_hashes, vec!["a", "b", "c", "d"]);
+
+        let stats = batcher.stats().unwrap();
+        assert_eq!((stats.batches_committed, stats.writes_committed, stats.pending_writes), (2, 4, 0));
+    }
+
+    #[test]
+    fn test_row_limit_commits_early_and_drop_flushes() {
+        let (_dir, catalog, manager) = setup();
+        let config = MicroBatchConfig::default()
+            .with_max_delay(Duration::from_secs(60))
+            .with_max_rows(10);
+        let batcher = MicroBatcher::with_config(Arc::clone(&manager), config);
+
+        let full = batcher.submit("main", append("events", "a", 10)).unwrap();
+        assert_eq!(full.wait().unwrap().versions["events"], 1);
+
+        let pending = batcher.submit("main", append("events", "b", 1)).unwrap();
+        drop(batcher);
+        assert_eq!(pending.wait().unwrap().versions["events"], 2);
+        assert_eq!(catalog.list_versions("events").unwrap(), vec![1, 2]);
+    }
+
+    #[test]
+    fn test_failed_batch_reaches_every_writer() {
+        let (_dir, _catalog, manager) = setup();
+        let batcher = MicroBatcher::new(Arc::clone(&manager));
+        let tickets: Vec<_> = (0..2)
+            .map(|_| batcher.submit("missing", append("events", "a", 1)).unwrap())
+            .collect();
+        batcher.flush().unwrap();
+        for ticket in tickets {
+            let err = ticket.wait().unwrap_err();
+            assert!(matches!(err, TransactionError::BatchFailed { ref branch, .. } if branch == "missing"));
+        }
+        assert_eq!(batcher.stats().unwrap().batches_failed, 1);
+    }
+}
@@ -2,13 +2,17 @@
 //!
 //! This module provides pluggable conflict detection strategies:
 //! - `TableLevelConflictDetector` - Two transactions conflict if they write the same table
-//! - `PartitionLevelConflictDetector` - (Future) Conflict on same partition
-//! - `RowLevelConflictDetector` - (Future) Conflict on same row keys
+//! - `PartitionLevelConflictDetector` - Conflict on same partition
+//! - `RowLevelConflictDetector` - Conflict on same row keys, partitions or chunks
 //!
 //! The conflict detection strategy determines the concurrency/isolation trade-off.
+//! `ConflictGranularity` selects one on the `TransactionManager`, and
+//! `ConflictWindow` how far back it looks for concurrent commits.
 
 use std::collections::HashSet;
-use super::types::{TransactionRecord, WriteGranularity};
+use std::sync::Arc;
+
+use super::types::{TableWrite, TransactionRecord, WriteGranularity};
 
 /// Represents a detected conflict between two transactions
 #[derive(Debug, Clone)]
@@ -99,15 +103,13 @@ impl ConflictDetector for TableLevelConflictDetector {
     }
 }
 
-/// Partition-level conflict detection (Phase 5.5 - future)
+/// Partition-level conflict detection (Phase 5.5)
 ///
 /// Two transactions conflict only if they write to the same partition
 /// of the same table.
-#[allow(dead_code)] // Planned for Phase 5.5
 #[derive(Debug, Default)]
 pub struct PartitionLevelConflictDetector;
 
-#[allow(dead_code)] // Planned for Phase 5.5
 impl PartitionLevelConflictDetector {
     pub fn new() -> Self {
         Self
@@ -174,26 +176,66 @@ impl ConflictDetector for PartitionLevelConflictDetector {
     }
 }
 
-/// Row-level conflict detection (Phase 5.x - future)
+/// Row-level conflict detection
 ///
-/// Two transactions conflict only if they write to the same row keys.
-/// This provides the highest concurrency but requires key tracking.
-#[allow(dead_code)] // Planned for Phase 5.x
+/// Two transactions conflict only if they write overlapping parts of the
+/// same table: the same row keys, partitions or base chunks, depending on
+/// each write's granularity. A whole-table write overlaps everything, and
+/// writes that describe the same table at different granularities (or keys
+/// over different columns) are assumed to overlap.
 #[derive(Debug, Default)]
 pub struct RowLevelConflictDetector;
 
-#[allow(dead_code)] // Planned for Phase 5.x
 impl RowLevelConflictDetector {
     pub fn new() -> Self {
         Self
     }
+
+    /// Whether two writes to the same table may touch the same rows
+    fn overlaps(write1: &TableWrite, write2: &TableWrite) -> bool {
+        if !write1.kind.is_data() || !write2.kind.is_data() {
+            return true;
+        }
+        match (&write1.granularity, &write2.granularity) {
+            (WriteGranularity::Partitions(p1), WriteGranularity::Partitions(p2)) => intersects(p1, p2),
+            (WriteGranularity::Chunks(c1), WriteGranularity::Chunks(c2)) => intersects(c1, c2),
+            (
+                WriteGranularity::Keys { key_columns: cols1, affected_keys: keys1 },
+                WriteGranularity::Keys { key_columns: cols2, affected_keys: keys2 },
+            ) => {
+                // serde_json::Value is not Hash; compare canonical encodings
+                cols1 != cols2 || intersects(
+                    &keys1.iter().map(|k| k.to_string()).collect::<Vec<_>>(),
+                    &keys2.iter().map(|k| k.to_string()).collect::<Vec<_>>(),
+                )
+            }
+            _ => true,
+        }
+    }
+}
+
+fn intersects(a: &[String], b: &[String]) -> bool {
+    let a: HashSet<_> = a.iter().collect();
+    b.iter().any(|item| a.contains(item))
 }
 
 impl ConflictDetector for RowLevelConflictDetector {
     fn detect(&self, tx1: &TransactionRecord, tx2: &TransactionRecord) -> Option<Conflict> {
-        // TODO: Implement row-level conflict detection
-        // For now, fall back to table-level
-        TableLevelConflictDetector.detect(tx1, tx2)
+        let mut tables: Vec<String> = Vec::new();
+        for write1 in &tx1.writes {
+            let overlapping = tx2.writes.iter()
+                .filter(|write2| write2.table_name == write1.table_name)
+                .any(|write2| Self::overlaps(write1, write2));
+            if overlapping && !tables.contains(&write1.table_name) {
+                tables.push(write1.table_name.clone());
+            }
+        }
+
+        if tables.is_empty() {
+            None
+        } else {
+            Some(Conflict::new(tables, tx1.tx_id, tx2.tx_id))
+        }
     }
 
     fn name(&self) -> &'static str {
@@ -201,6 +243,81 @@ impl ConflictDetector for RowLevelConflictDetector {
     }
 }
 
+/// Granularity at which the `TransactionManager` detects write conflicts
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ConflictGranularity {
+    /// Any two writes to the same table conflict
+    #[default]
+    Table,
+
+    /// Writes conflict on shared partitions (`WriteGranularity::Partitions`)
+    Partition,
+
+    /// Writes conflict on shared row keys, partitions or chunks
+    Row,
+
+    /// Like `Row`, and whole-table writes are narrowed to the base chunks
+    /// they replace, so writers touching different chunks do not conflict
+    Chunk,
+}
+
+impl ConflictGranularity {
+    /// The detector implementing this granularity
+    pub fn detector(self) -> Arc<dyn ConflictDetector + Send + Sync> {
+        match self {
+            ConflictGranularity::Table => Arc::new(TableLevelConflictDetector),
+            ConflictGranularity::Partition => Arc::new(PartitionLevelConflictDetector),
+            ConflictGranularity::Row | ConflictGranularity::Chunk => Arc::new(RowLevelConflictDetector),
+        }
+    }
+}
+
+impl std::fmt::Display for ConflictGranularity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConflictGranularity::Table => write!(f, "Table"),
+            ConflictGranularity::Partition => write!(f, "Partition"),
+            ConflictGranularity::Row => write!(f, "Row"),
+            ConflictGranularity::Chunk => write!(f, "Chunk"),
+        }
+    }
+}
+
+/// How far back the `TransactionManager` looks for concurrent commits
+/// that are no longer in memory.
+///
+/// Conflicts are detected against a bounded window of recent commits
+/// (`EpochConfig::max_transactions`, cleared at epoch boundaries and on
+/// restart). Commits that fell out of it are only caught by snapshot
+/// validation. A wider window reads them back from the persistent log.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ConflictWindow {
+    /// Only commits still in memory
+    #[default]
+    Memory,
+
+    /// Also logged commits of transactions from the current epoch and the
+    /// `n` before it
+    Epochs(u64),
+
+    /// Also logged commits from the last `n` seconds
+    Seconds(u64),
+
+    /// Also every logged commit made since the transaction began
+    Lifetime,
+}
+
+impl std::fmt::Display for ConflictWindow {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConflictWindow::Memory => write!(f, "Memory"),
+            ConflictWindow::Epochs(n) => write!(f, "Epochs({})", n),
+            ConflictWindow::Seconds(n) => write!(f, "Seconds({})", n),
+            ConflictWindow::Lifetime => write!(f, "Lifetime"),
+        }
+    }
+}
+
 /// Multi-strategy conflict detector
 ///
 /// Uses different detection strategies based on write granularity.
@@ -353,6 +470,52 @@ mod tests {
         assert!(conflict.is_some());
     }
 
+    fn keyed_write(table: &str, column: &str, keys: &[i64]) -> TableWrite {
+        TableWrite::new(table, 2, vec!["chunk".to_string()]).with_granularity(WriteGranularity::Keys {
+            key_columns: vec![column.to_string()],
+            affected_keys: keys.iter().map(|&k| serde_json::json!(k)).collect(),
+        })
+    }
+
+    #[test]
+    fn test_row_level_conflicts_on_shared_keys_only() {
+        let detector = RowLevelConflictDetector::new();
+        let tx = |id, write: TableWrite| {
+            let mut tx = TransactionRecord::new(id, 1, "main".to_string());
+            tx.add_write(write);
+            tx
+        };
+
+        let base = tx(1, keyed_write("users", "id", &[1, 2]));
+        assert!(detector.detect(&base, &tx(2, keyed_write("users", "id", &[3, 4]))).is_none());
+        assert!(detector.detect(&base, &tx(2, keyed_write("orders", "id", &[1]))).is_none());
+
+        let conflict = detector.detect(&base, &tx(2, keyed_write("users", "id", &[2, 5]))).unwrap();
+        assert_eq!(conflict.tables, vec!["users"]);
+
+        // Different key columns or whole-table writes are assumed to overlap
+        assert!(detector.detect(&base, &tx(2, keyed_write("users", "email", &[9]))).is_some());
+        assert!(detector.detect(&base, &create_tx_with_writes(2, &["users"])).is_some());
+        assert!(detector.detect(&base, &tx(2, TableWrite::drop_table("users", 1))).is_some());
+    }
+
+    #[test]
+    fn test_row_level_chunk_overlap() {
+        let detector = ConflictGranularity::Chunk.detector();
+        let chunks = |id, replaced: &[&str]| {
+            let mut tx = TransactionRecord::new(id, 1, "main".to_string());
+            tx.add_write(TableWrite::new("events", 2, vec![]).with_granularity(WriteGranularity::Chunks(
+                replaced.iter().map(|c| c.to_string()).collect(),
+            )));
+            tx
+        };
+
+        assert!(detector.detect(&chunks(1, &["a"]), &chunks(2, &["b"])).is_none());
+        assert!(detector.detect(&chunks(1, &["a", "b"]), &chunks(2, &["b"])).is_some());
+        // Pure appends replace nothing
+        assert!(detector.detect(&chunks(1, &[]), &chunks(2, &[])).is_none());
+    }
+
     #[test]
     fn test_adaptive_detector() {
         let detector = AdaptiveConflictDetector::new();
@@ -389,6 +552,8 @@ mod tests {
         assert_eq!(TableLevelConflictDetector::new().name(), "TableLevelConflictDetector");
         assert_eq!(PartitionLevelConflictDetector::new().name(), "PartitionLevelConflictDetector");
         assert_eq!(RowLevelConflictDetector::new().name(), "RowLevelConflictDetector");
+        assert_eq!(ConflictGranularity::Row.detector().name(), "RowLevelConflictDetector");
+        assert_eq!(ConflictGranularity::default().detector().name(), "TableLevelConflictDetector");
         assert_eq!(AdaptiveConflictDetector::new().name(), "AdaptiveConflictDetector");
     }
 }
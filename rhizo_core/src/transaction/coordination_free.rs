@@ -41,6 +41,7 @@
 use std::sync::RwLock;
 
 use crate::algebraic::{AlgebraicSchemaRegistry, AlgebraicValue, OpType};
+use crate::error::{ErrorCategory, ErrorContext, ErrorInfo};
 use crate::distributed::{
     AlgebraicTransaction, LocalCommitError, LocalCommitProtocol, NodeId, VectorClock,
     VersionedUpdate,
@@ -48,6 +49,7 @@ use crate::distributed::{
 
 /// Error type for coordination-free operations
 #[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
 pub enum CoordinationFreeError {
     /// Transaction contains non-algebraic operations
     #[error("Transaction contains non-algebraic operations and cannot be committed coordination-free")]
@@ -70,6 +72,35 @@ pub enum CoordinationFreeError {
     MergeError(String),
 }
 
+impl ErrorInfo for CoordinationFreeError {
+    fn code(&self) -> &'static str {
+        match self {
+            CoordinationFreeError::NotFullyAlgebraic => "COORDINATION_FREE_NOT_ALGEBRAIC",
+            CoordinationFreeError::LocalCommit(e) => e.code(),
+            CoordinationFreeError::LockError(_) => "COORDINATION_FREE_LOCK",
+            CoordinationFreeError::SchemaError(_) => "COORDINATION_FREE_SCHEMA",
+            CoordinationFreeError::MergeError(_) => "COORDINATION_FREE_MERGE",
+        }
+    }
+
+    fn category(&self) -> ErrorCategory {
+        match self {
+            CoordinationFreeError::LocalCommit(e) => e.category(),
+            CoordinationFreeError::NotFullyAlgebraic | CoordinationFreeError::SchemaError(_) => {
+                ErrorCategory::InvalidArgument
+            }
+            CoordinationFreeError::LockError(_) | CoordinationFreeError::MergeError(_) => ErrorCategory::Conflict,
+        }
+    }
+
+    fn context(&self) -> ErrorContext {
+        match self {
+            CoordinationFreeError::LocalCommit(e) => e.context(),
+            _ => ErrorContext::new(),
+        }
+    }
+}
+
 /// Configuration for coordination-free mode
 #[derive(Debug, Clone)]
 pub struct CoordinationFreeConfig {
@@ -175,7 +206,7 @@ impl CoordinationFreeManager {
             .map_err(|_| CoordinationFreeError::LockError("clock".to_string()))?;
 
         // Commit using LocalCommitProtocol
-        let update = LocalCommitProtocol::commit_local(tx, &self.node_id, &mut clock)?;
+        let update = LocalCommitProtocol::commit_local(tx, &self.node_id, &mut *clock)?;
 
         // Apply to local state
         self.apply_update_to_state(&update)?;
@@ -1,10 +1,15 @@
 //! Error types for the transaction system.
 
+use std::sync::Arc;
+use std::time::Duration;
+
 use thiserror::Error;
 use super::types::TxId;
+use crate::error::{io_retry_after, ErrorCategory, ErrorContext, ErrorInfo, LOCK_RETRY_DELAY};
 
 /// Errors that can occur during transaction operations
 #[derive(Error, Debug)]
+#[non_exhaustive]
 pub enum TransactionError {
     /// I/O error during file operations
     #[error("IO error: {0}")]
@@ -42,6 +47,25 @@ pub enum TransactionError {
         current_version: u64,
     },
 
+    /// No savepoint with this name in the transaction
+    #[error("Savepoint not found in transaction {tx_id}: {name}")]
+    SavepointNotFound {
+        tx_id: TxId,
+        name: String,
+    },
+
+    /// No changelog cursor with this name
+    #[error("Changelog cursor not found: {0}")]
+    CursorNotFound(String),
+
+    /// A changelog cursor with this name already exists
+    #[error("Changelog cursor already exists: {0}")]
+    CursorAlreadyExists(String),
+
+    /// Changelog cursor name is not a valid ref name
+    #[error("Invalid changelog cursor name: {0}")]
+    InvalidCursorName(String),
+
     /// Attempted operation on non-active epoch
     #[error("Epoch {0} is not active")]
     EpochNotActive(u64),
@@ -77,6 +101,10 @@ pub enum TransactionError {
     #[error("Transaction {0} timed out after {1}ms")]
     Timeout(TxId, u64),
 
+    /// The manager is shutting down and accepts no new transactions
+    #[error("Transaction manager is shutting down")]
+    ShuttingDown,
+
     /// Nested transactions not supported
     #[error("Nested transactions not supported")]
     NestedTransaction,
@@ -92,8 +120,127 @@ pub enum TransactionError {
     /// Data integrity check failed (checksum mismatch)
     #[error("Integrity error: {0}")]
     IntegrityError(String),
+
+    /// A micro-batched commit failed; every write in the batch receives
+    /// the same underlying error
+    #[error("Batched commit on branch {branch} failed: {source}")]
+    BatchFailed {
+        branch: String,
+        source: Arc<TransactionError>,
+    },
+
+    /// `run_with_retry` kept conflicting until its policy ran out of
+    /// attempts; `source` is the last conflict
+    #[error("Transaction gave up after {attempts} attempts: {source}")]
+    RetriesExhausted {
+        attempts: u32,
+        source: Box<TransactionError>,
+    },
+}
+
+impl ErrorInfo for TransactionError {
+    fn code(&self) -> &'static str {
+        match self {
+            TransactionError::Io(_) => "TX_IO",
+            TransactionError::Json(_) => "TX_JSON",
+            TransactionError::TransactionNotFound(_) => "TX_NOT_FOUND",
+            TransactionError::TransactionNotActive(_) => "TX_NOT_ACTIVE",
+            TransactionError::AlreadyCommitted(_) => "TX_ALREADY_COMMITTED",
+            TransactionError::AlreadyAborted(_) => "TX_ALREADY_ABORTED",
+            TransactionError::WriteConflict(_) => "TX_WRITE_CONFLICT",
+            TransactionError::SnapshotConflict { .. } => "TX_SNAPSHOT_CONFLICT",
+            TransactionError::SavepointNotFound { .. } => "TX_SAVEPOINT_NOT_FOUND",
+            TransactionError::CursorNotFound(_) => "TX_CURSOR_NOT_FOUND",
+            TransactionError::CursorAlreadyExists(_) => "TX_CURSOR_ALREADY_EXISTS",
+            TransactionError::InvalidCursorName(_) => "TX_INVALID_CURSOR_NAME",
+            TransactionError::EpochNotActive(_) => "TX_EPOCH_NOT_ACTIVE",
+            TransactionError::EpochNotFound(_) => "TX_EPOCH_NOT_FOUND",
+            TransactionError::EpochFull(..) => "TX_EPOCH_FULL",
+            TransactionError::InvalidState { .. } => "TX_INVALID_STATE",
+            TransactionError::CatalogError(_) => "TX_CATALOG",
+            TransactionError::BranchError(_) => "TX_BRANCH",
+            TransactionError::RecoveryError(_) => "TX_RECOVERY",
+            TransactionError::Timeout(..) => "TX_TIMEOUT",
+            TransactionError::ShuttingDown => "TX_SHUTTING_DOWN",
+            TransactionError::NestedTransaction => "TX_NESTED",
+            TransactionError::InvalidConfig(_) => "TX_INVALID_CONFIG",
+            TransactionError::LockError(_) => "TX_LOCK",
+            TransactionError::IntegrityError(_) => "TX_INTEGRITY",
+            TransactionError::BatchFailed { source, .. } => source.code(),
+            TransactionError::RetriesExhausted { .. } => "TX_RETRIES_EXHAUSTED",
+        }
+    }
+
+    fn category(&self) -> ErrorCategory {
+        match self {
+            TransactionError::Io(_) | TransactionError::CatalogError(_) | TransactionError::BranchError(_) => {
+                ErrorCategory::Io
+            }
+            TransactionError::Json(_) | TransactionError::RecoveryError(_) => ErrorCategory::Internal,
+            TransactionError::TransactionNotFound(_)
+            | TransactionError::EpochNotFound(_)
+            | TransactionError::SavepointNotFound { .. }
+            | TransactionError::CursorNotFound(_) => ErrorCategory::NotFound,
+            TransactionError::CursorAlreadyExists(_) => ErrorCategory::AlreadyExists,
+            TransactionError::WriteConflict(_)
+            | TransactionError::SnapshotConflict { .. }
+            | TransactionError::Timeout(..)
+            | TransactionError::LockError(_) => ErrorCategory::Conflict,
+            TransactionError::TransactionNotActive(_)
+            | TransactionError::AlreadyCommitted(_)
+            | TransactionError::AlreadyAborted(_)
+            | TransactionError::EpochNotActive(_)
+            | TransactionError::EpochFull(..)
+            | TransactionError::InvalidState { .. }
+            | TransactionError::ShuttingDown
+            | TransactionError::NestedTransaction => ErrorCategory::FailedPrecondition,
+            TransactionError::InvalidConfig(_) | TransactionError::InvalidCursorName(_) => ErrorCategory::InvalidArgument,
+            TransactionError::IntegrityError(_) => ErrorCategory::DataIntegrity,
+            TransactionError::BatchFailed { source, .. } => source.category(),
+            TransactionError::RetriesExhausted { .. } => ErrorCategory::Conflict,
+        }
+    }
+
+    fn context(&self) -> ErrorContext {
+        match self {
+            TransactionError::TransactionNotFound(tx_id)
+            | TransactionError::TransactionNotActive(tx_id)
+            | TransactionError::AlreadyCommitted(tx_id)
+            | TransactionError::AlreadyAborted(tx_id)
+            | TransactionError::Timeout(tx_id, _)
+            | TransactionError::SavepointNotFound { tx_id, .. } => ErrorContext::new().with_tx_id(*tx_id),
+            TransactionError::EpochNotActive(epoch_id)
+            | TransactionError::EpochNotFound(epoch_id)
+            | TransactionError::EpochFull(epoch_id, _) => ErrorContext::new().with_epoch_id(*epoch_id),
+            TransactionError::WriteConflict(tables) if tables.len() == 1 => {
+                ErrorContext::new().with_table(tables[0].clone())
+            }
+            TransactionError::SnapshotConflict { table, current_version, .. } => {
+                ErrorContext::new().with_table(table.clone()).with_version(*current_version)
+            }
+            TransactionError::BatchFailed { branch, source } => source.context().with_branch(branch.clone()),
+            TransactionError::RetriesExhausted { source, .. } => source.context(),
+            _ => ErrorContext::new(),
+        }
+    }
+
+    /// Conflicts are retried immediately on a fresh snapshot; timeouts and
+    /// full epochs after a short wait for in-flight commits to drain.
+    fn retry_after(&self) -> Option<Duration> {
+        match self {
+            TransactionError::WriteConflict(_) | TransactionError::SnapshotConflict { .. } => Some(Duration::ZERO),
+            TransactionError::LockError(_) => Some(LOCK_RETRY_DELAY),
+            TransactionError::Timeout(..) | TransactionError::EpochFull(..) => Some(EPOCH_RETRY_DELAY),
+            TransactionError::Io(e) => io_retry_after(e),
+            TransactionError::BatchFailed { source, .. } => source.retry_after(),
+            _ => None,
+        }
+    }
 }
 
+/// Backoff for timeouts and full epochs
+const EPOCH_RETRY_DELAY: Duration = Duration::from_millis(100);
+
 impl TransactionError {
     /// Check if this error indicates a conflict
     pub fn is_conflict(&self) -> bool {
@@ -103,15 +250,9 @@ impl TransactionError {
         )
     }
 
-    /// Check if this error is retryable
+    /// Check if this error is retryable (see [`ErrorInfo::retry_after`])
     pub fn is_retryable(&self) -> bool {
-        matches!(
-            self,
-            TransactionError::WriteConflict(_)
-                | TransactionError::SnapshotConflict { .. }
-                | TransactionError::Timeout(_, _)
-                | TransactionError::LockError(_)
-        )
+        self.retry_after().is_some()
     }
 
     /// Create a write conflict error
@@ -0,0 +1,105 @@
+//! Callbacks run when transactions complete.
+//!
+//! Embedding applications register hooks on a [`TransactionManager`]
+//! (`on_commit`, `on_abort`, `on_conflict`) to invalidate caches, send
+//! notifications or record metrics. Each hook receives the transaction
+//! record and its table changes:
+//!
+//! - commit: the committed record and the changes as the changelog
+//!   reports them
+//! - abort: the aborted record and the writes it discarded
+//! - conflict: the still active record and the writes that could not be
+//!   committed (the caller may retry or abort)
+//!
+//! Hooks run on the thread that completed the transaction, after the
+//! commit lock is released, so they may call back into the manager. They
+//! delay the caller and should hand slow work to another thread. A hook
+//! that panics is logged and skipped; it does not change the outcome of
+//! the transaction.
+//!
+//! [`TransactionManager`]: super::TransactionManager
+
+use std::collections::HashMap;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+
+use tracing::warn;
+
+use super::types::TransactionRecord;
+use crate::changelog::{ChangelogEntry, TableChange};
+
+/// Identifies a registered hook, for `TransactionManager::remove_hook`
+pub type HookId = u64;
+
+/// A transaction hook
+pub type TransactionHook = Arc<dyn Fn(&TransactionRecord, &[TableChange]) + Send + Sync>;
+
+/// When a hook runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HookEvent {
+    Commit,
+    Abort,
+    /// A commit failed with a write or snapshot conflict
+    Conflict,
+}
+
+/// Hooks registered on one manager
+#[derive(Default)]
+pub(crate) struct HookRegistry {
+    next_id: AtomicU64,
+    hooks: RwLock<Vec<(HookId, HookEvent, TransactionHook)>>,
+}
+
+impl HookRegistry {
+    pub(crate) fn register(&self, event: HookEvent, hook: TransactionHook) -> HookId {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed) + 1;
+        self.hooks.write().unwrap_or_else(|e| e.into_inner()).push((id, event, hook));
+        id
+    }
+
+    pub(crate) fn remove(&self, id: HookId) -> bool {
+        let mut hooks = self.hooks.write().unwrap_or_else(|e| e.into_inner());
+        let before = hooks.len();
+        hooks.retain(|(hook_id, _, _)| *hook_id != id);
+        hooks.len() != before
+    }
+
+    /// Whether any hook runs on `event` (callers skip building the
+    /// arguments otherwise)
+    pub(crate) fn has(&self, event: HookEvent) -> bool {
+        self.hooks.read().unwrap_or_else(|e| e.into_inner()).iter().any(|(_, e, _)| *e == event)
+    }
+
+    /// Run the hooks of `event` in registration order. The list is copied
+    /// first, so hooks may register or remove hooks. A panicking hook is
+    /// logged and the remaining hooks still run.
+    pub(crate) fn fire(&self, event: HookEvent, record: &TransactionRecord, changes: &[TableChange]) {
+        let hooks: Vec<TransactionHook> = self
+            .hooks
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .filter(|(_, e, _)| *e == event)
+            .map(|(_, _, hook)| Arc::clone(hook))
+            .collect();
+        for hook in hooks {
+            if catch_unwind(AssertUnwindSafe(|| hook(record, changes))).is_err() {
+                warn!(tx_id = record.tx_id, event = ?event, "Transaction hook panicked");
+            }
+        }
+    }
+}
+
+impl std::fmt::Debug for HookRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let hooks = self.hooks.read().unwrap_or_else(|e| e.into_inner());
+        f.debug_struct("HookRegistry").field("hooks", &hooks.len()).finish()
+    }
+}
+
+/// Changes of a transaction against `previous_versions` (table -> version
+/// before the transaction)
+pub(crate) fn changes_of(tx: &TransactionRecord, previous_versions: &HashMap<String, u64>) -> Vec<TableChange> {
+    ChangelogEntry::from_transaction(tx, previous_versions).changes
+}
@@ -0,0 +1,234 @@
+//! Index of committed transactions by branch and table.
+//!
+//! The index is persisted next to the committed index as `_tx_index.jsonl`,
+//! one [`TxIndexEntry`] per committed transaction in commit order, so a
+//! cold start reads a single file instead of every transaction record.
+//! Entries are appended as transactions commit; lookups by branch or table
+//! and the table versions each transaction replaced are derived in memory
+//! when the file is loaded.
+
+use std::collections::{BTreeMap, HashMap};
+
+use serde::{Deserialize, Serialize};
+
+use super::types::{EpochId, TransactionRecord, TxId, WriteKind};
+
+/// Location and footprint of one committed transaction.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TxIndexEntry {
+    pub tx_id: TxId,
+    pub epoch_id: EpochId,
+    /// Line of the transaction in the committed index (commit order)
+    pub offset: u64,
+    pub branch: String,
+    /// Tables written, as named by the transaction's writes
+    pub tables: Vec<String>,
+    /// Table versions after the transaction; `None` for a table dropped
+    /// or renamed away
+    pub versions: Vec<(String, Option<u64>)>,
+}
+
+impl TxIndexEntry {
+    pub fn from_transaction(tx: &TransactionRecord, offset: u64) -> Self {
+        let mut tables: Vec<String> = Vec::new();
+        let mut versions = Vec::new();
+        for w in &tx.writes {
+            if !tables.contains(&w.table_name) {
+                tables.push(w.table_name.clone());
+            }
+            match &w.kind {
+                WriteKind::Data | WriteKind::Merge { .. } => versions.push((w.table_name.clone(), Some(w.new_version))),
+                WriteKind::Drop => versions.push((w.table_name.clone(), None)),
+                WriteKind::Rename { to } => {
+                    versions.push((w.table_name.clone(), None));
+                    versions.push((to.clone(), Some(w.new_version)));
+                }
+            }
+        }
+        Self {
+            tx_id: tx.tx_id,
+            epoch_id: tx.epoch_id,
+            offset,
+            branch: tx.branch.clone(),
+            tables,
+            versions,
+        }
+    }
+}
+
+/// Committed transactions in commit order, looked up by id, branch or table.
+#[derive(Debug, Clone, Default)]
+pub struct TransactionIndex {
+    entries: Vec<TxIndexEntry>,
+    /// Versions of each entry's tables before the entry committed
+    previous: Vec<HashMap<String, u64>>,
+    positions: HashMap<TxId, usize>,
+    by_branch: BTreeMap<String, Vec<TxId>>,
+    by_table: BTreeMap<String, Vec<TxId>>,
+    /// Table versions after the last entry
+    versions: HashMap<String, u64>,
+    /// Committed index lines covered by the entries
+    committed_lines: u64,
+}
+
+impl TransactionIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// All entries in commit order
+    pub fn entries(&self) -> &[TxIndexEntry] {
+        &self.entries
+    }
+
+    pub fn get(&self, tx_id: TxId) -> Option<&TxIndexEntry> {
+        self.positions.get(&tx_id).map(|&i| &self.entries[i])
+    }
+
+    pub fn contains(&self, tx_id: TxId) -> bool {
+        self.positions.contains_key(&tx_id)
+    }
+
+    /// Transactions committed on `branch`, in commit order
+    pub fn for_branch(&self, branch: &str) -> &[TxId] {
+        self.by_branch.get(branch).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// Transactions writing `table`, in commit order
+    pub fn for_table(&self, table: &str) -> &[TxId] {
+        self.by_table.get(table).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// Version each table written by `tx_id` had before it committed.
+    /// Tables created by the transaction are absent.
+    pub fn previous_versions(&self, tx_id: TxId) -> Option<&HashMap<String, u64>> {
+        self.positions.get(&tx_id).map(|&i| &self.previous[i])
+    }
+
+    /// Entries on `branch` (if given) writing any of `tables` (if given),
+    /// in commit order.
+    pub fn select(&self, branch: Option<&str>, tables: Option<&[String]>) -> Vec<&TxIndexEntry> {
+        let mut positions: Vec<usize> = match tables {
+            Some(tables) => {
+                let mut positions: Vec<usize> = tables
+                    .iter()
+                    .flat_map(|t| self.for_table(t))
+                    .map(|tx_id| self.positions[tx_id])
+                    .collect();
+                positions.sort_unstable();
+                positions.dedup();
+                positions
+            }
+            None => match branch {
+                Some(branch) => self.for_branch(branch).iter().map(|tx_id| self.positions[tx_id]).collect(),
+                None => (0..self.entries.len()).collect(),
+            },
+        };
+        if let Some(branch) = branch {
+            positions.retain(|&i| self.entries[i].branch == branch);
+        }
+        positions.into_iter().map(|i| &self.entries[i]).collect()
+    }
+
+    /// Committed index lines covered by the entries
+    pub(crate) fn committed_lines(&self) -> u64 {
+        self.committed_lines
+    }
+
+    pub(crate) fn set_committed_lines(&mut self, lines: u64) {
+        self.committed_lines = self.committed_lines.max(lines);
+    }
+
+    /// Add the next committed transaction. Returns false if it is
+    /// already indexed.
+    pub(crate) fn push(&mut self, entry: TxIndexEntry) -> bool {
+        if self.positions.contains_key(&entry.tx_id) {
+            return false;
+        }
+        let previous = entry
+            .tables
+            .iter()
+            .filter_map(|t| self.versions.get(t).map(|&v| (t.clone(), v)))
+            .collect();
+        for (table, version) in &entry.versions {
+            match version {
+                Some(v) => self.versions.insert(table.clone(), *v),
+                None => self.versions.remove(table),
+            };
+        }
+        for table in &entry.tables {
+            self.by_table.entry(table.clone()).or_default().push(entry.tx_id);
+        }
+        self.by_branch.entry(entry.branch.clone()).or_default().push(entry.tx_id);
+        self.positions.insert(entry.tx_id, self.entries.len());
+        self.committed_lines = self.committed_lines.max(entry.offset + 1);
+        self.previous.push(previous);
+        self.entries.push(entry);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transaction::TableWrite;
+
+    fn tx(tx_id: TxId, branch: &str, writes: Vec<TableWrite>) -> TransactionRecord {
+        let mut tx = TransactionRecord::new(tx_id, 1, branch.to_string());
+        tx.writes = writes;
+        tx
+    }
+
+    fn index(txs: &[TransactionRecord]) -> TransactionIndex {
+        let mut index = TransactionIndex::new();
+        for (offset, tx) in txs.iter().enumerate() {
+            index.push(TxIndexEntry::from_transaction(tx, offset as u64));
+        }
+        index
+    }
+
+    #[test]
+    fn test_lookups_by_branch_and_table() {
+        let mut index = index(&[
+            tx(1, "main", vec![TableWrite::new("users", 1, vec![])]),
+            tx(2, "dev", vec![TableWrite::new("orders", 1, vec![])]),
+            tx(3, "main", vec![TableWrite::new("users", 2, vec![]), TableWrite::new("orders", 2, vec![])]),
+        ]);
+
+        assert_eq!(index.for_branch("main"), &[1, 3]);
+        assert_eq!(index.for_table("orders"), &[2, 3]);
+        assert!(index.for_table("missing").is_empty());
+        assert_eq!(index.get(2).unwrap().offset, 1);
+
+        let ids = |entries: Vec<&TxIndexEntry>| entries.iter().map(|e| e.tx_id).collect::<Vec<_>>();
+        assert_eq!(ids(index.select(Some("main"), Some(&["orders".to_string()]))), vec![3]);
+        assert_eq!(ids(index.select(None, Some(&["users".to_string(), "orders".to_string()]))), vec![1, 2, 3]);
+        assert_eq!(ids(index.select(None, None)), vec![1, 2, 3]);
+        assert!(!index.push(TxIndexEntry::from_transaction(&tx(3, "main", vec![]), 3)));
+    }
+
+    #[test]
+    fn test_previous_versions_follow_drops_and_renames() {
+        let index = index(&[
+            tx(1, "main", vec![TableWrite::new("users", 1, vec![])]),
+            tx(2, "main", vec![TableWrite::rename_table("users", "people", 2)]),
+            tx(3, "main", vec![TableWrite::new("people", 3, vec![]), TableWrite::new("users", 1, vec![])]),
+            tx(4, "main", vec![TableWrite::drop_table("people", 3)]),
+        ]);
+
+        assert!(index.previous_versions(1).unwrap().is_empty());
+        assert_eq!(index.previous_versions(2).unwrap()["users"], 1);
+        let previous = index.previous_versions(3).unwrap();
+        assert_eq!(previous.get("people"), Some(&2));
+        assert_eq!(previous.get("users"), None);
+        assert_eq!(index.previous_versions(4).unwrap()["people"], 3);
+    }
+}
@@ -6,6 +6,15 @@
 //! +-- _config.json                # Epoch configuration
 //! +-- _sequence                   # Current tx_id counter (atomic)
 //! +-- _epoch_sequence             # Current epoch_id counter (atomic)
+//! +-- _committed_index            # Committed `epoch_id:tx_id` lines, in commit order
+//! +-- _tx_index.jsonl             # TxIndexEntry per committed transaction
+//! +-- _segments.json              # Compacted segments (see `segment`)
+//! |
+//! +-- segments/                   # Committed records of compacted epochs
+//! |   +-- 000001-000004.jsonl
+//! |
+//! +-- cursors/                    # ChangelogCursor per consumer
+//! |   +-- search-indexer.json
 //! |
 //! +-- epochs/                     # Epoch-organized transaction logs
 //!     +-- 000001/                 # Epoch directory
@@ -19,10 +28,14 @@
 
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
 
 use super::types::*;
 use super::epoch::*;
 use super::error::TransactionError;
+use super::index::{TransactionIndex, TxIndexEntry};
+use super::segment::{self, CompactionReport, LoadedSegment, LogSegment};
+use crate::durability::{self, DurabilityLevel};
 
 const EPOCHS_DIR: &str = "epochs";
 const CONFIG_FILE: &str = "_config.json";
@@ -30,8 +43,11 @@ const SEQUENCE_FILE: &str = "_sequence";
 const EPOCH_SEQUENCE_FILE: &str = "_epoch_sequence";
 const LATEST_COMMITTED_FILE: &str = "_latest_committed";
 const COMMITTED_INDEX_FILE: &str = "_committed_index";
+const TX_INDEX_FILE: &str = "_tx_index.jsonl";
 const EPOCH_META_FILE: &str = "_meta.json";
 const EPOCH_COMMITTED_MARKER: &str = "_committed";
+const SEGMENTS_DIR: &str = "segments";
+const SEGMENTS_FILE: &str = "_segments.json";
 
 /// Persistent storage configuration
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -66,6 +82,12 @@ impl StorageConfig {
 /// Persistent transaction log
 pub struct TransactionLog {
     base_path: PathBuf,
+    /// Loaded on first use, then kept current by `write_transaction`
+    index: Mutex<Option<Arc<TransactionIndex>>>,
+    /// Most recently read compacted segment; changelog reads walk
+    /// segments in order, so one suffices
+    segment_cache: Mutex<Option<Arc<LoadedSegment>>>,
+    durability: DurabilityLevel,
 }
 
 impl TransactionLog {
@@ -75,7 +97,27 @@ impl TransactionLog {
         let epochs_dir = base_path.join(EPOCHS_DIR);
         fs::create_dir_all(&epochs_dir)?;
 
-        Ok(Self { base_path })
+        Ok(Self {
+            base_path,
+            index: Mutex::new(None),
+            segment_cache: Mutex::new(None),
+            durability: DurabilityLevel::default(),
+        })
+    }
+
+    /// Flush transaction records, sequences and markers as `level`
+    /// requires (see [`crate::durability`])
+    pub fn with_durability(mut self, level: DurabilityLevel) -> Self {
+        self.durability = level;
+        self
+    }
+
+    pub(crate) fn set_durability(&mut self, level: DurabilityLevel) {
+        self.durability = level;
+    }
+
+    pub fn durability(&self) -> DurabilityLevel {
+        self.durability
     }
 
     /// Get the base path
@@ -102,8 +144,7 @@ impl TransactionLog {
 
         // Atomic write using temp file + rename
         let temp_path = path.with_extension("tmp");
-        fs::write(&temp_path, next.to_string())?;
-        fs::rename(&temp_path, &path)?;
+        durability::write_atomic(&path, &temp_path, next.to_string(), self.durability)?;
 
         Ok(next)
     }
@@ -145,8 +186,7 @@ impl TransactionLog {
 
         let path = self.base_path.join(EPOCH_SEQUENCE_FILE);
         let temp_path = path.with_extension("tmp");
-        fs::write(&temp_path, next.to_string())?;
-        fs::rename(&temp_path, &path)?;
+        durability::write_atomic(&path, &temp_path, next.to_string(), self.durability)?;
 
         Ok(next)
     }
@@ -164,8 +204,7 @@ impl TransactionLog {
         // Update epoch sequence
         let seq_path = self.base_path.join(EPOCH_SEQUENCE_FILE);
         let temp_path = seq_path.with_extension("tmp");
-        fs::write(&temp_path, epoch_id.to_string())?;
-        fs::rename(&temp_path, &seq_path)?;
+        durability::write_atomic(&seq_path, &temp_path, epoch_id.to_string(), self.durability)?;
 
         Ok(meta)
     }
@@ -219,8 +258,7 @@ impl TransactionLog {
         let json = serde_json::to_string_pretty(meta)?;
         let checksum = blake3::hash(json.as_bytes()).to_hex().to_string();
         let content = format!("{}\n{}", checksum, json);
-        fs::write(&temp_path, &content)?;
-        fs::rename(&temp_path, &meta_path)?;
+        durability::write_atomic(&meta_path, &temp_path, &content, self.durability)?;
 
         Ok(())
     }
@@ -228,7 +266,7 @@ impl TransactionLog {
     /// Mark epoch as committed (create marker file)
     pub fn mark_epoch_committed(&self, epoch_id: EpochId) -> Result<(), TransactionError> {
         let marker_path = self.epoch_dir(epoch_id).join(EPOCH_COMMITTED_MARKER);
-        fs::write(&marker_path, "")?;
+        durability::write(&marker_path, "", self.durability)?;
         Ok(())
     }
 
@@ -275,13 +313,13 @@ impl TransactionLog {
         let temp_path = tx_path.with_extension("json.tmp");
 
         let json = serde_json::to_string_pretty(tx)?;
-        fs::write(&temp_path, &json)?;
-        fs::rename(&temp_path, &tx_path)?;
+        durability::write_atomic(&tx_path, &temp_path, &json, self.durability)?;
 
         // Update committed tracking when persisting a committed transaction
         if tx.is_committed() {
             self.update_latest_committed(tx.tx_id)?;
             self.append_committed_index(tx.epoch_id, tx.tx_id)?;
+            self.index_committed(tx);
         }
 
         Ok(())
@@ -293,8 +331,7 @@ impl TransactionLog {
     fn update_latest_committed(&self, tx_id: TxId) -> Result<(), TransactionError> {
         let path = self.base_path.join(LATEST_COMMITTED_FILE);
         let temp_path = path.with_extension("tmp");
-        fs::write(&temp_path, tx_id.to_string())?;
-        fs::rename(&temp_path, &path)?;
+        durability::write_atomic(&path, &temp_path, tx_id.to_string(), self.durability)?;
         Ok(())
     }
 
@@ -303,13 +340,8 @@ impl TransactionLog {
     /// The index is an append-only text file with one `epoch_id:tx_id` per line,
     /// enabling O(entries) reads without scanning epoch directories.
     fn append_committed_index(&self, epoch_id: EpochId, tx_id: TxId) -> Result<(), TransactionError> {
-        use std::io::Write;
         let path = self.base_path.join(COMMITTED_INDEX_FILE);
-        let mut file = fs::OpenOptions::new()
-            .create(true)
-            .append(true)
-            .open(&path)?;
-        writeln!(file, "{}:{}", epoch_id, tx_id)?;
+        durability::append_line(&path, &format!("{}:{}", epoch_id, tx_id), self.durability)?;
         Ok(())
     }
 
@@ -401,6 +433,12 @@ impl TransactionLog {
             }
         }
 
+        for seg in self.segments()?.iter().rev() {
+            if let Some(tx) = self.load_segment(seg)?.records.get(&tx_id) {
+                return Ok(tx.clone());
+            }
+        }
+
         Err(TransactionError::TransactionNotFound(tx_id))
     }
 
@@ -412,7 +450,10 @@ impl TransactionLog {
     ) -> Result<TransactionRecord, TransactionError> {
         let tx_path = self.epoch_dir(epoch_id).join(format!("tx_{:06}.json", tx_id));
         if !tx_path.exists() {
-            return Err(TransactionError::TransactionNotFound(tx_id));
+            return self
+                .segment_for(epoch_id)?
+                .and_then(|loaded| loaded.records.get(&tx_id).cloned())
+                .ok_or(TransactionError::TransactionNotFound(tx_id));
         }
 
         let json = fs::read_to_string(&tx_path)?;
@@ -446,7 +487,16 @@ impl TransactionLog {
             }
         }
 
+        if let Some(loaded) = self.segment_for(epoch_id)? {
+            tx_ids.extend(
+                loaded.records.values()
+                    .filter(|tx| tx.epoch_id == epoch_id)
+                    .map(|tx| tx.tx_id),
+            );
+        }
+
         tx_ids.sort();
+        tx_ids.dedup();
         Ok(tx_ids)
     }
 
@@ -470,19 +520,26 @@ impl TransactionLog {
         let temp_path = path.with_extension("tmp");
 
         let json = serde_json::to_string_pretty(config)?;
-        fs::write(&temp_path, &json)?;
-        fs::rename(&temp_path, &path)?;
+        durability::write_atomic(&path, &temp_path, &json, self.durability)?;
 
         Ok(())
     }
 
     /// Initialize with default configuration if not exists
     pub fn initialize_if_needed(&self) -> Result<StorageConfig, TransactionError> {
+        self.initialize_with(EpochConfig::single_node())
+    }
+
+    /// Initialize with the given epoch configuration if not exists.
+    ///
+    /// An existing `_config.json` always wins, so reopening a log never
+    /// silently changes its epoch behavior.
+    pub fn initialize_with(&self, epoch_config: EpochConfig) -> Result<StorageConfig, TransactionError> {
         if let Some(config) = self.load_config()? {
             return Ok(config);
         }
 
-        let config = StorageConfig::new(EpochConfig::single_node());
+        let config = StorageConfig::new(epoch_config);
         self.save_config(&config)?;
         Ok(config)
     }
@@ -568,6 +625,347 @@ impl TransactionLog {
         Ok(committed)
     }
 
+    // === Transaction Index ===
+
+    /// The index of committed transactions by branch and table.
+    ///
+    /// Loaded from `_tx_index.jsonl` (or built from the committed
+    /// transactions if missing) on first use, then caught up with any
+    /// transactions committed since, including by other processes.
+    pub fn transaction_index(&self) -> Result<Arc<TransactionIndex>, TransactionError> {
+        let mut cached = self.index.lock()
+            .map_err(|_| TransactionError::LockError("transaction index".to_string()))?;
+        let index = match cached.take() {
+            Some(index) => index,
+            None => Arc::new(self.load_index()?),
+        };
+        let index = self.catch_up_index(index)?;
+        *cached = Some(Arc::clone(&index));
+        Ok(index)
+    }
+
+    /// Bring the transaction index up to date and flush the sequence,
+    /// pointer and index files to disk, whatever the durability level.
+    pub fn flush(&self) -> Result<(), TransactionError> {
+        self.transaction_index()?;
+        for file in [
+            SEQUENCE_FILE,
+            EPOCH_SEQUENCE_FILE,
+            LATEST_COMMITTED_FILE,
+            COMMITTED_INDEX_FILE,
+            TX_INDEX_FILE,
+        ] {
+            durability::sync_path(&self.base_path.join(file))?;
+        }
+        if let Some(epoch_id) = self.list_epochs()?.last() {
+            durability::sync_path(&self.epoch_dir(*epoch_id))?;
+        }
+        durability::sync_path(&self.base_path)?;
+        Ok(())
+    }
+
+    /// Compare the persisted transaction index with one rebuilt from the
+    /// committed transactions, after catching it up with recent commits.
+    ///
+    /// Returns a description of each difference; empty if the index is
+    /// consistent.
+    pub fn verify_transaction_index(&self) -> Result<Vec<String>, TransactionError> {
+        self.transaction_index()?;
+        let (persisted, malformed) = self.read_index_file()?;
+        let rebuilt = self.build_index()?;
+
+        let mut issues = Vec::new();
+        if malformed > 0 {
+            issues.push(format!("Transaction index: {} malformed line(s)", malformed));
+        }
+        let persisted: std::collections::BTreeMap<TxId, &TxIndexEntry> =
+            persisted.iter().map(|e| (e.tx_id, e)).collect();
+        for entry in rebuilt.entries() {
+            match persisted.get(&entry.tx_id) {
+                None => issues.push(format!("Transaction index: missing tx {}", entry.tx_id)),
+                Some(found) if *found != entry => {
+                    issues.push(format!("Transaction index: stale entry for tx {}", entry.tx_id))
+                }
+                Some(_) => {}
+            }
+        }
+        for tx_id in persisted.keys() {
+            if !rebuilt.contains(*tx_id) {
+                issues.push(format!("Transaction index: tx {} is not committed", tx_id));
+            }
+        }
+        Ok(issues)
+    }
+
+    /// Rebuild the transaction index from the committed transactions and
+    /// rewrite `_tx_index.jsonl`.
+    pub fn rebuild_transaction_index(&self) -> Result<Arc<TransactionIndex>, TransactionError> {
+        let mut cached = self.index.lock()
+            .map_err(|_| TransactionError::LockError("transaction index".to_string()))?;
+        let index = Arc::new(self.build_index()?);
+        self.write_index_file(&index)?;
+        *cached = Some(Arc::clone(&index));
+        Ok(index)
+    }
+
+    /// Append a newly committed transaction to the loaded index.
+    ///
+    /// The transaction is already durable, so failures only drop the
+    /// in-memory index; the next `transaction_index()` catches up from the
+    /// committed index.
+    fn index_committed(&self, tx: &TransactionRecord) {
+        let Ok(mut cached) = self.index.lock() else {
+            return;
+        };
+        let Some(index) = cached.as_mut() else {
+            return;
+        };
+        let entry = TxIndexEntry::from_transaction(tx, index.committed_lines());
+        if Arc::make_mut(index).push(entry.clone()) && self.append_index_entry(&entry).is_err() {
+            *cached = None;
+        }
+    }
+
+    /// Read `_tx_index.jsonl`, or build it if missing or torn.
+    fn load_index(&self) -> Result<TransactionIndex, TransactionError> {
+        let path = self.base_path.join(TX_INDEX_FILE);
+        if !path.exists() {
+            let index = self.build_index()?;
+            self.write_index_file(&index)?;
+            return Ok(index);
+        }
+
+        let (entries, malformed) = self.read_index_file()?;
+        let mut index = TransactionIndex::new();
+        for entry in entries {
+            index.push(entry);
+        }
+        if malformed > 0 {
+            // Appending after a torn line would corrupt the next entry
+            self.write_index_file(&index)?;
+        }
+        Ok(index)
+    }
+
+    /// Index committed transactions listed after those `index` covers.
+    ///
+    /// A committed index shorter than the one indexed was rewritten, so
+    /// the transaction index is rebuilt.
+    fn catch_up_index(&self, mut index: Arc<TransactionIndex>) -> Result<Arc<TransactionIndex>, TransactionError> {
+        let committed = self.committed_index_entries()?;
+        let covered = index.committed_lines() as usize;
+        if committed.len() < covered {
+            let rebuilt = self.build_index()?;
+            self.write_index_file(&rebuilt)?;
+            return Ok(Arc::new(rebuilt));
+        }
+
+        for (offset, &(epoch_id, tx_id)) in committed.iter().enumerate().skip(covered) {
+            if index.contains(tx_id) {
+                continue;
+            }
+            let Ok(tx) = self.read_transaction_from_epoch(tx_id, epoch_id) else {
+                continue;
+            };
+            let entry = TxIndexEntry::from_transaction(&tx, offset as u64);
+            self.append_index_entry(&entry)?;
+            Arc::make_mut(&mut index).push(entry);
+        }
+        if committed.len() > index.committed_lines() as usize {
+            Arc::make_mut(&mut index).set_committed_lines(committed.len() as u64);
+        }
+        Ok(index)
+    }
+
+    /// Build the index from the committed transactions.
+    fn build_index(&self) -> Result<TransactionIndex, TransactionError> {
+        let mut index = TransactionIndex::new();
+        let committed = self.committed_index_entries()?;
+        for (offset, &(epoch_id, tx_id)) in committed.iter().enumerate() {
+            if let Ok(tx) = self.read_transaction_from_epoch(tx_id, epoch_id) {
+                index.push(TxIndexEntry::from_transaction(&tx, offset as u64));
+            }
+        }
+        index.set_committed_lines(committed.len() as u64);
+        Ok(index)
+    }
+
+    fn committed_index_entries(&self) -> Result<Vec<(EpochId, TxId)>, TransactionError> {
+        match self.read_committed_index()? {
+            Some(entries) => Ok(entries),
+            None => self.rebuild_committed_index(),
+        }
+    }
+
+    /// Entries of `_tx_index.jsonl` and the number of lines that failed
+    /// to parse.
+    fn read_index_file(&self) -> Result<(Vec<TxIndexEntry>, usize), TransactionError> {
+        let path = self.base_path.join(TX_INDEX_FILE);
+        if !path.exists() {
+            return Ok((Vec::new(), 0));
+        }
+        let content = fs::read_to_string(&path)?;
+        let mut entries = Vec::new();
+        let mut malformed = 0;
+        for line in content.lines().filter(|l| !l.trim().is_empty()) {
+            match serde_json::from_str(line) {
+                Ok(entry) => entries.push(entry),
+                Err(_) => malformed += 1,
+            }
+        }
+        Ok((entries, malformed))
+    }
+
+    fn write_index_file(&self, index: &TransactionIndex) -> Result<(), TransactionError> {
+        let path = self.base_path.join(TX_INDEX_FILE);
+        let temp_path = path.with_extension("jsonl.tmp");
+        let mut content = String::new();
+        for entry in index.entries() {
+            content.push_str(&serde_json::to_string(entry)?);
+            content.push('\n');
+        }
+        durability::write_atomic(&path, &temp_path, content, self.durability)?;
+        Ok(())
+    }
+
+    fn append_index_entry(&self, entry: &TxIndexEntry) -> Result<(), TransactionError> {
+        let path = self.base_path.join(TX_INDEX_FILE);
+        durability::append_line(&path, &serde_json::to_string(entry)?, self.durability)?;
+        Ok(())
+    }
+
+    // === Compaction ===
+
+    /// Compacted segments, oldest first
+    pub fn segments(&self) -> Result<Vec<LogSegment>, TransactionError> {
+        let path = self.base_path.join(SEGMENTS_FILE);
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+        Ok(serde_json::from_str(&fs::read_to_string(&path)?)?)
+    }
+
+    /// Whether an epoch's records were rolled into a segment
+    pub fn is_epoch_compacted(&self, epoch_id: EpochId) -> Result<bool, TransactionError> {
+        Ok(self.segments()?.iter().any(|seg| seg.covers(epoch_id)))
+    }
+
+    /// Roll the epochs before `before_epoch` into a segment.
+    ///
+    /// Epochs are taken oldest first, stopping at the first one that
+    /// still holds a pending transaction, so a segment never hides work
+    /// recovery would need. Committed records move to the segment file;
+    /// aborted ones are dropped. Epoch metadata stays where it is.
+    pub fn compact(&self, before_epoch: EpochId) -> Result<CompactionReport, TransactionError> {
+        let mut segments = self.segments()?;
+        let compacted_through = segments.last().map_or(0, |seg| seg.last_epoch);
+
+        // Finish a compaction interrupted after its segment was recorded
+        for epoch_id in self.list_epochs()?.into_iter().filter(|&e| e <= compacted_through) {
+            self.remove_transaction_files(epoch_id)?;
+        }
+
+        let mut report = CompactionReport::default();
+        let mut committed = Vec::new();
+        for epoch_id in self.list_epochs()? {
+            if epoch_id <= compacted_through {
+                continue;
+            }
+            if epoch_id >= before_epoch {
+                break;
+            }
+            let mut records = Vec::new();
+            for tx_id in self.list_transactions_in_epoch(epoch_id)? {
+                records.push(self.read_transaction_from_epoch(tx_id, epoch_id)?);
+            }
+            if records.iter().any(|tx| !tx.is_committed() && !tx.is_aborted()) {
+                break;
+            }
+            for tx in records {
+                if tx.is_committed() {
+                    committed.push(tx);
+                } else {
+                    report.dropped += 1;
+                }
+            }
+            report.epochs.push(epoch_id);
+        }
+        let (Some(&first_epoch), Some(&last_epoch)) = (report.epochs.first(), report.epochs.last()) else {
+            return Ok(report);
+        };
+
+        committed.sort_by_key(|tx| tx.tx_id);
+        let seg = LogSegment {
+            first_epoch,
+            last_epoch,
+            transactions: committed.len(),
+            compacted_at: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_secs() as i64,
+        };
+
+        // Segment file, then manifest, then the files it replaces: a crash
+        // at any point leaves every committed record readable
+        let segments_dir = self.base_path.join(SEGMENTS_DIR);
+        fs::create_dir_all(&segments_dir)?;
+        let seg_path = segments_dir.join(seg.file_name());
+        durability::write_atomic(
+            &seg_path,
+            &seg_path.with_extension("jsonl.tmp"),
+            segment::encode_records(&committed)?,
+            self.durability,
+        )?;
+        segments.push(seg.clone());
+        let manifest_path = self.base_path.join(SEGMENTS_FILE);
+        durability::write_atomic(
+            &manifest_path,
+            &manifest_path.with_extension("json.tmp"),
+            serde_json::to_string_pretty(&segments)?,
+            self.durability,
+        )?;
+        for &epoch_id in &report.epochs {
+            self.remove_transaction_files(epoch_id)?;
+        }
+
+        report.transactions = seg.transactions;
+        report.segment = Some(seg);
+        Ok(report)
+    }
+
+    /// The segment holding `epoch_id`'s committed records, if compacted
+    fn segment_for(&self, epoch_id: EpochId) -> Result<Option<Arc<LoadedSegment>>, TransactionError> {
+        match self.segments()?.into_iter().find(|seg| seg.covers(epoch_id)) {
+            Some(seg) => Ok(Some(self.load_segment(&seg)?)),
+            None => Ok(None),
+        }
+    }
+
+    fn load_segment(&self, seg: &LogSegment) -> Result<Arc<LoadedSegment>, TransactionError> {
+        let mut cached = self.segment_cache.lock()
+            .map_err(|_| TransactionError::LockError("segment cache".to_string()))?;
+        if let Some(loaded) = cached.as_ref().filter(|loaded| loaded.segment == *seg) {
+            return Ok(Arc::clone(loaded));
+        }
+        let records = segment::read_records(&self.base_path.join(SEGMENTS_DIR).join(seg.file_name()))?;
+        let loaded = Arc::new(LoadedSegment { segment: seg.clone(), records });
+        *cached = Some(Arc::clone(&loaded));
+        Ok(loaded)
+    }
+
+    fn remove_transaction_files(&self, epoch_id: EpochId) -> Result<(), TransactionError> {
+        for entry in fs::read_dir(self.epoch_dir(epoch_id))? {
+            let path = entry?.path();
+            let is_record = path.file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| n.starts_with("tx_") && n.ends_with(".json"));
+            if is_record {
+                durability::remove_file(&path, self.durability)?;
+            }
+        }
+        Ok(())
+    }
+
     // === Private Helpers ===
 
     fn epoch_dir(&self, epoch_id: EpochId) -> PathBuf {
@@ -811,4 +1209,63 @@ mod tests {
         let loaded = log.get_epoch(1).unwrap();
         assert_eq!(loaded.epoch_id, 1);
     }
+
+    fn write_committed(log: &TransactionLog, tx_id: TxId, branch: &str, table: &str, version: u64) {
+        let mut tx = TransactionRecord::new(tx_id, 1, branch.to_string());
+        tx.add_write(TableWrite::new(table, version, vec![]));
+        tx.mark_committed();
+        log.write_transaction(&tx).unwrap();
+    }
+
+    #[test]
+    fn test_transaction_index_cold_start_and_incremental() {
+        let (log, temp) = create_test_log();
+        log.create_epoch(1).unwrap();
+        write_committed(&log, 1, "main", "users", 1);
+        write_committed(&log, 2, "dev", "orders", 1);
+
+        // Built from the log on first use and persisted
+        let index = log.transaction_index().unwrap();
+        assert_eq!(index.for_branch("dev"), &[2]);
+        assert!(log.base_path().join(TX_INDEX_FILE).exists());
+
+        // Appended as transactions commit
+        write_committed(&log, 3, "main", "users", 2);
+        let index = log.transaction_index().unwrap();
+        assert_eq!(index.for_table("users"), &[1, 3]);
+        assert_eq!(index.previous_versions(3).unwrap()["users"], 1);
+
+        // A fresh log reads the persisted file and catches up with commits
+        // made elsewhere
+        let other = TransactionLog::new(temp.path()).unwrap();
+        write_committed(&other, 4, "dev", "orders", 2);
+        let reopened = TransactionLog::new(temp.path()).unwrap();
+        let index = reopened.transaction_index().unwrap();
+        assert_eq!(index.len(), 4);
+        assert_eq!(index.get(4).unwrap().offset, 3);
+        assert!(reopened.verify_transaction_index().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_transaction_index_verify_and_rebuild() {
+        let (log, _temp) = create_test_log();
+        log.create_epoch(1).unwrap();
+        write_committed(&log, 1, "main", "users", 1);
+        write_committed(&log, 2, "main", "users", 2);
+        log.transaction_index().unwrap();
+
+        let path = log.base_path().join(TX_INDEX_FILE);
+        let content = fs::read_to_string(&path).unwrap();
+        let first = content.lines().next().unwrap().replace("\"main\"", "\"dev\"");
+        fs::write(&path, format!("{}\n{{\"tx_id\":", first)).unwrap();
+
+        let issues = log.verify_transaction_index().unwrap();
+        assert!(issues.iter().any(|i| i.contains("malformed")));
+        assert!(issues.iter().any(|i| i.contains("stale entry for tx 1")));
+        assert!(issues.iter().any(|i| i.contains("missing tx 2")));
+
+        let index = log.rebuild_transaction_index().unwrap();
+        assert_eq!(index.for_branch("main"), &[1, 2]);
+        assert!(log.verify_transaction_index().unwrap().is_empty());
+    }
 }
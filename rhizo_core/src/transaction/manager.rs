@@ -3,18 +3,43 @@
 //! The TransactionManager coordinates transactions across multiple tables,
 //! providing snapshot isolation with conflict detection.
 
-use std::collections::{HashMap, VecDeque};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
+
+use tracing::warn;
 
 use super::types::*;
 use super::epoch::*;
 use super::error::TransactionError;
 use super::log::TransactionLog;
-use super::conflict::{ConflictDetector, TableLevelConflictDetector};
+use super::conflict::{Conflict, ConflictDetector, ConflictGranularity, ConflictWindow, TableLevelConflictDetector};
+use super::hooks::{changes_of, HookEvent, HookId, HookRegistry};
+use super::read_only::{PinnedSnapshot, ReadOnlyTransaction, ReaderPins};
 use super::recovery::RecoveryReport;
-use crate::catalog::FileCatalog;
-use crate::branch::BranchManager;
+use super::retry::RetryPolicy;
+use super::segment::CompactionReport;
+use super::shutdown::ShutdownReport;
+use super::scheduler::{CommitPriority, CommitQueueStats, CommitScheduler};
+use super::snapshot::SharedSnapshot;
+use super::timings::{CommitMetricsSink, CommitPhase, CommitTimer, CommitTimings, COMMIT_TIMINGS_RETAINED};
+use crate::algebraic::AlgebraicSchemaRegistry;
+use crate::catalog::{CatalogError, FileCatalog, TableVersion};
+use crate::changelog::{ChangelogCursor, CursorStore, TableChange};
+use crate::chunk_store::{ChunkStore, ChunkTags};
+use crate::data_merge::{DataMergeError, DataMerger};
+use crate::branch::{BranchError, BranchManager, FileStamp, MergeAnalyzer, MergeRecord, MergeStrategy};
+use crate::durability::DurabilityLevel;
+use crate::gc::{ChunkGc, GcError, GcReport};
+
+/// A branch's published read snapshot, with the stamp of the branch file
+/// it was last checked against (`None` without a branch manager)
+struct PublishedSnapshot {
+    snapshot: SharedSnapshot,
+    stamp: Option<FileStamp>,
+}
 
 /// Manages cross-table ACID transactions
 pub struct TransactionManager {
@@ -39,20 +64,72 @@ pub struct TransactionManager {
     /// Maximum entries in recent_committed before oldest are evicted
     max_recent_committed: usize,
 
+    /// Latest read snapshot per branch, shared by every transaction that
+    /// begins on that head (see `capture_snapshot`)
+    published_snapshots: RwLock<HashMap<String, PublishedSnapshot>>,
+
     /// Serializes the commit critical section (conflict check → catalog write → recent_committed update).
     /// Prevents TOCTOU race where two concurrent commits both pass conflict checks before either applies writes.
     commit_lock: Mutex<()>,
 
+    /// Serializes epoch metadata read-modify-write cycles (begin, commit and
+    /// abort update the same epoch file from different threads)
+    epoch_lock: Mutex<()>,
+
+    /// Admits commits waiting for `commit_lock` by priority (if unset,
+    /// waiters get the lock in whatever order the mutex wakes them)
+    commit_scheduler: Option<Arc<CommitScheduler>>,
+
     /// Conflict detector (pluggable strategy)
     conflict_detector: Arc<dyn ConflictDetector + Send + Sync>,
 
+    /// Granularity of `conflict_detector` when set through
+    /// `set_conflict_granularity`; finer than `Table` enables rebasing
+    conflict_granularity: ConflictGranularity,
+
+    /// How far back conflict detection reads commits evicted from
+    /// `recent_committed`
+    conflict_window: ConflictWindow,
+
+    /// Algebraic schemas consulted by `OnConflict::Analyze` (empty by default)
+    schema_registry: Arc<AlgebraicSchemaRegistry>,
+
+    /// Chunk store `OnConflict::Analyze` merges rows in (`set_merge_store`)
+    merge_store: Option<Arc<ChunkStore>>,
+
+    /// Receives the phase timings of every commit attempt (optional)
+    metrics_sink: Option<Arc<dyn CommitMetricsSink>>,
+
+    /// Timings of the last `COMMIT_TIMINGS_RETAINED` commit attempts, for
+    /// `explain_commit`
+    commit_timings: Mutex<VecDeque<CommitTimings>>,
+
     /// Reference to catalog (for version resolution)
     catalog: Arc<FileCatalog>,
 
     /// Reference to branch manager (optional)
     branch_manager: Option<Arc<BranchManager>>,
+
+    /// Set by `shutdown`; `begin` refuses new transactions once set
+    shutting_down: AtomicBool,
+
+    /// Epochs kept uncompacted when `seal_epoch` compacts the log
+    /// (`None` leaves compaction to `compact_log`)
+    log_compaction: Option<u64>,
+
+    /// Snapshots pinned by open read-only transactions
+    readers: Arc<ReaderPins>,
+
+    /// Callbacks run when transactions commit, abort or conflict
+    hooks: HookRegistry,
 }
 
+/// Directory (under the transaction log) holding changelog cursors
+const CURSORS_DIR: &str = "cursors";
+
+/// How often `shutdown` checks whether in-flight transactions have drained
+const SHUTDOWN_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
 impl TransactionManager {
     /// Create a new TransactionManager
     pub fn new(
@@ -68,7 +145,7 @@ impl TransactionManager {
         base_path: impl AsRef<Path>,
         catalog: Arc<FileCatalog>,
         branch_manager: Option<Arc<BranchManager>>,
-        _config: EpochConfig,
+        config: EpochConfig,
     ) -> Result<Self, TransactionError> {
         let base_path = base_path.as_ref().to_path_buf();
         let tx_path = base_path.join("transactions");
@@ -76,8 +153,12 @@ impl TransactionManager {
 
         let log = TransactionLog::new(&tx_path)?;
 
-        // Initialize config if needed
-        let storage_config = log.initialize_if_needed()?;
+        // Initialize config if needed (persisted config takes precedence)
+        let storage_config = log.initialize_with(config)?;
+
+        // Load (or build and persist) the committed-transaction index now
+        // rather than on the first changelog query
+        log.transaction_index()?;
 
         let max_recent = storage_config.epoch_config.max_transactions as usize;
 
@@ -88,13 +169,37 @@ impl TransactionManager {
             active_transactions: RwLock::new(HashMap::new()),
             recent_committed: RwLock::new(VecDeque::new()),
             max_recent_committed: max_recent,
+            published_snapshots: RwLock::new(HashMap::new()),
             commit_lock: Mutex::new(()),
+            epoch_lock: Mutex::new(()),
+            commit_scheduler: None,
             conflict_detector: Arc::new(TableLevelConflictDetector::new()),
+            conflict_granularity: ConflictGranularity::Table,
+            conflict_window: ConflictWindow::Memory,
+            schema_registry: Arc::new(AlgebraicSchemaRegistry::new()),
+            merge_store: None,
+            metrics_sink: None,
+            commit_timings: Mutex::new(VecDeque::new()),
             catalog,
             branch_manager,
+            shutting_down: AtomicBool::new(false),
+            log_compaction: None,
+            readers: Arc::new(ReaderPins::default()),
+            hooks: HookRegistry::default(),
         })
     }
 
+    /// Compact the log every time an epoch is sealed, keeping the newest
+    /// `retain_epochs` epochs (including the new current one) as
+    /// individual records. `None` turns automatic compaction off.
+    pub fn set_log_compaction(&mut self, retain_epochs: Option<u64>) {
+        self.log_compaction = retain_epochs;
+    }
+
+    pub fn log_compaction(&self) -> Option<u64> {
+        self.log_compaction
+    }
+
     /// Set a custom conflict detector
     pub fn set_conflict_detector(
         &mut self,
@@ -103,11 +208,199 @@ impl TransactionManager {
         self.conflict_detector = detector;
     }
 
+    /// Detect write conflicts per table, partition, row key or chunk.
+    ///
+    /// Finer than `Table`, two transactions may write the same table if
+    /// their writes do not overlap. The later commit is rebased: chunks it
+    /// removed from the version it read are removed from the current
+    /// version, and chunks it added are appended. `Chunk` narrows
+    /// whole-table writes to the base chunks they replace.
+    ///
+    /// Rebasing needs the concurrent commits; if one was already evicted
+    /// (see `EpochConfig::max_transactions`) and is outside the conflict
+    /// window, the later commit fails with a snapshot conflict as under
+    /// `Table`.
+    pub fn set_conflict_granularity(&mut self, granularity: ConflictGranularity) {
+        self.conflict_detector = granularity.detector();
+        self.conflict_granularity = granularity;
+    }
+
+    /// Flush transaction log writes as `level` requires (see
+    /// [`crate::durability`]). Catalog and branch writes follow the
+    /// levels of the catalog and branch manager.
+    pub fn set_durability(&mut self, level: DurabilityLevel) {
+        self.log.set_durability(level);
+    }
+
+    pub fn durability(&self) -> DurabilityLevel {
+        self.log.durability()
+    }
+
+    /// The granularity set by `set_conflict_granularity`
+    pub fn conflict_granularity(&self) -> ConflictGranularity {
+        self.conflict_granularity
+    }
+
+    /// Check commits evicted from the in-memory window against the
+    /// persistent log.
+    ///
+    /// By default (`ConflictWindow::Memory`) only the last
+    /// `EpochConfig::max_transactions` commits of the current epoch are
+    /// checked for write conflicts, and older concurrent commits are left
+    /// to snapshot validation, which cannot tell overlapping writes from
+    /// stale reads. With a wider window, commits of transactions that
+    /// began before this one are read back from the log (through the
+    /// transaction index) so the result no longer depends on the
+    /// in-memory bound.
+    pub fn set_conflict_window(&mut self, window: ConflictWindow) {
+        self.conflict_window = window;
+    }
+
+    /// The window set by `set_conflict_window`
+    pub fn conflict_window(&self) -> ConflictWindow {
+        self.conflict_window
+    }
+
+    /// Set the algebraic schemas used to merge conflicting commits
+    /// (see [`OnConflict::Analyze`]). They override the annotations
+    /// versioned with the tables themselves.
+    pub fn set_schema_registry(&mut self, registry: Arc<AlgebraicSchemaRegistry>) {
+        self.schema_registry = registry;
+    }
+
+    /// Store the rows of conflicts [`OnConflict::Analyze`] merges in:
+    /// merged rows are read from and written to `store`. Without one,
+    /// those conflicts fail like any other.
+    pub fn set_merge_store(&mut self, store: Arc<ChunkStore>) {
+        self.merge_store = Some(store);
+    }
+
+    /// Admit waiting commits by priority (see [`CommitScheduler`]).
+    /// The scheduler may be shared between managers.
+    pub fn set_commit_scheduler(&mut self, scheduler: Arc<CommitScheduler>) {
+        self.commit_scheduler = Some(scheduler);
+    }
+
+    /// Queue metrics of the commit scheduler, if one is set
+    pub fn commit_queue_stats(&self) -> Result<Option<CommitQueueStats>, TransactionError> {
+        self.commit_scheduler.as_ref().map(|s| s.stats()).transpose()
+    }
+
+    /// Report the phase timings of every commit attempt to `sink`
+    /// (see [`CommitTimings`]).
+    pub fn set_metrics_sink(&mut self, sink: Arc<dyn CommitMetricsSink>) {
+        self.metrics_sink = Some(sink);
+    }
+
+    /// Phase timings of the latest commit attempt of `tx_id`, if it is
+    /// among the last `COMMIT_TIMINGS_RETAINED` attempts
+    pub fn explain_commit(&self, tx_id: TxId) -> Result<Option<CommitTimings>, TransactionError> {
+        let timings = self.commit_timings.lock()
+            .map_err(|_| TransactionError::LockError("commit_timings".to_string()))?;
+        Ok(timings.iter().rev().find(|t| t.tx_id == tx_id).cloned())
+    }
+
+    // === Hooks ===
+
+    /// Run `hook` after every successful commit with the committed record
+    /// and its changes.
+    ///
+    /// Hooks run on the committing thread once the commit lock is
+    /// released, so they may call back into the manager; slow work should
+    /// go to another thread.
+    pub fn on_commit(
+        &self,
+        hook: impl Fn(&TransactionRecord, &[TableChange]) + Send + Sync + 'static,
+    ) -> HookId {
+        self.hooks.register(HookEvent::Commit, Arc::new(hook))
+    }
+
+    /// Run `hook` after every abort with the aborted record and the writes
+    /// it discarded
+    pub fn on_abort(
+        &self,
+        hook: impl Fn(&TransactionRecord, &[TableChange]) + Send + Sync + 'static,
+    ) -> HookId {
+        self.hooks.register(HookEvent::Abort, Arc::new(hook))
+    }
+
+    /// Run `hook` when a commit fails with a write or snapshot conflict,
+    /// with the still active record and the writes that were refused
+    pub fn on_conflict(
+        &self,
+        hook: impl Fn(&TransactionRecord, &[TableChange]) + Send + Sync + 'static,
+    ) -> HookId {
+        self.hooks.register(HookEvent::Conflict, Arc::new(hook))
+    }
+
+    /// Tag the chunks of every committed write in `store`'s backend with
+    /// the table, version and transaction (see [`ChunkTags`]).
+    ///
+    /// Tagging runs as a commit hook: the commit has happened by then, so
+    /// failures are logged and the remaining chunks still tagged.
+    pub fn tag_chunks_on_commit(&self, store: Arc<ChunkStore>) -> HookId {
+        self.on_commit(move |tx, changes| {
+            for change in changes.iter().filter(|c| c.kind.is_data()) {
+                let tags = ChunkTags::new(&change.table_name, change.new_version).with_tx_id(tx.tx_id);
+                if let Err(e) = store.tag_chunks(&change.chunk_hashes, &tags) {
+                    warn!(tx_id = tx.tx_id, table = %change.table_name, error = %e, "Failed to tag chunks");
+                }
+            }
+        })
+    }
+
+    /// Unregister a hook; returns whether it was registered
+    pub fn remove_hook(&self, id: HookId) -> bool {
+        self.hooks.remove(id)
+    }
+
+    /// Run the commit hooks for a transaction that just committed. The
+    /// commit has happened, so failures are logged rather than returned.
+    fn fire_commit_hooks(&self, tx: &TransactionRecord) {
+        let index = match self.log.transaction_index() {
+            Ok(index) => index,
+            Err(e) => {
+                warn!(tx_id = tx.tx_id, error = %e, "Skipping commit hooks: transaction index unavailable");
+                return;
+            }
+        };
+        let previous = index.previous_versions(tx.tx_id).cloned().unwrap_or_default();
+        self.hooks.fire(HookEvent::Commit, tx, &changes_of(tx, &previous));
+    }
+
+    /// Hand the timings to the metrics sink and retain them. Runs after
+    /// the commit, so it cannot fail: a poisoned buffer is still used.
+    fn record_timings(&self, timings: CommitTimings) {
+        if let Some(sink) = &self.metrics_sink {
+            sink.record_commit(&timings);
+        }
+        let mut retained = self.commit_timings.lock().unwrap_or_else(|e| e.into_inner());
+        retained.push_back(timings);
+        while retained.len() > COMMIT_TIMINGS_RETAINED {
+            retained.pop_front();
+        }
+    }
+
     /// Get the epoch configuration
     pub fn config(&self) -> &EpochConfig {
         &self.config
     }
 
+    /// The underlying transaction log
+    pub fn log(&self) -> &TransactionLog {
+        &self.log
+    }
+
+    /// The catalog transactions commit to
+    pub fn catalog(&self) -> &FileCatalog {
+        &self.catalog
+    }
+
+    /// The branches whose heads commits update, if any
+    pub fn branch_manager(&self) -> Option<&Arc<BranchManager>> {
+        self.branch_manager.as_ref()
+    }
+
     /// Begin a new transaction
     pub fn begin(&self, branch: Option<&str>) -> Result<TxId, TransactionError> {
         // Get next transaction ID
@@ -132,6 +425,10 @@ impl TransactionManager {
         {
             let mut active = self.active_transactions.write()
                 .map_err(|_| TransactionError::LockError("active_transactions".to_string()))?;
+            // Checked under the lock so `shutdown` never misses a begin
+            if self.shutting_down.load(Ordering::SeqCst) {
+                return Err(TransactionError::ShuttingDown);
+            }
             active.insert(tx_id, tx.clone());
         }
 
@@ -139,13 +436,39 @@ impl TransactionManager {
         self.log.write_transaction(&tx)?;
 
         // Update epoch metadata
-        let mut epoch_meta = self.log.get_epoch(epoch_id)?;
-        epoch_meta.add_transaction(tx_id);
-        self.log.write_epoch_metadata(&epoch_meta)?;
+        self.update_epoch(epoch_id, |meta| meta.add_transaction(tx_id))?;
 
         Ok(tx_id)
     }
 
+    /// Begin a read-only transaction: a snapshot of the versions visible
+    /// on `branch` (the default branch if None), pinned until the returned
+    /// handle is dropped.
+    ///
+    /// Nothing is written to the transaction log and the transaction never
+    /// takes part in conflict detection or the commit critical section, so
+    /// it is much cheaper than `begin` for analytical reads.
+    ///
+    /// # Errors
+    /// - `TransactionError::ShuttingDown` after `shutdown`
+    pub fn begin_read_only(&self, branch: Option<&str>) -> Result<ReadOnlyTransaction, TransactionError> {
+        if self.shutting_down.load(Ordering::SeqCst) {
+            return Err(TransactionError::ShuttingDown);
+        }
+        let branch_name = match branch {
+            Some(b) => b.to_string(),
+            None => self.default_branch()?,
+        };
+        let snapshot = self.capture_snapshot(&branch_name)?;
+        let pin = self.readers.register(branch_name, snapshot);
+        Ok(ReadOnlyTransaction::new(pin, Arc::clone(&self.catalog), Arc::clone(&self.readers)))
+    }
+
+    /// Snapshots of the read-only transactions currently open, oldest first
+    pub fn read_only_transactions(&self) -> Vec<PinnedSnapshot> {
+        self.readers.list()
+    }
+
     /// Add a read to the transaction (for conflict detection)
     pub fn record_read(
         &self,
@@ -166,6 +489,47 @@ impl TransactionManager {
         Ok(())
     }
 
+    /// Set the priority a transaction's commit is admitted with. Has no
+    /// effect unless a commit scheduler is set.
+    pub fn set_priority(
+        &self,
+        tx_id: TxId,
+        priority: CommitPriority,
+    ) -> Result<(), TransactionError> {
+        let mut active = self.active_transactions.write()
+            .map_err(|_| TransactionError::LockError("active_transactions".to_string()))?;
+        let tx = active.get_mut(&tx_id)
+            .ok_or(TransactionError::TransactionNotFound(tx_id))?;
+
+        if !tx.is_active() {
+            return Err(TransactionError::TransactionNotActive(tx_id));
+        }
+
+        tx.priority = priority;
+        Ok(())
+    }
+
+    /// Attach a metadata entry (e.g. `author`) to a transaction. It is
+    /// persisted with the transaction and copied into its changelog entry.
+    pub fn set_metadata(
+        &self,
+        tx_id: TxId,
+        key: &str,
+        value: &str,
+    ) -> Result<(), TransactionError> {
+        let mut active = self.active_transactions.write()
+            .map_err(|_| TransactionError::LockError("active_transactions".to_string()))?;
+        let tx = active.get_mut(&tx_id)
+            .ok_or(TransactionError::TransactionNotFound(tx_id))?;
+
+        if !tx.is_active() {
+            return Err(TransactionError::TransactionNotActive(tx_id));
+        }
+
+        tx.set_metadata(key, value);
+        Ok(())
+    }
+
     /// Add a write to the transaction
     pub fn add_write(
         &self,
@@ -185,6 +549,56 @@ impl TransactionManager {
         Ok(())
     }
 
+    /// Take a savepoint: a named snapshot of the transaction's writes and
+    /// read snapshot that it can later roll back to. Taking a savepoint
+    /// with an existing name replaces it.
+    ///
+    /// Savepoints are persisted in the transaction record, so the log
+    /// always reflects the write set after the latest savepoint operation.
+    pub fn savepoint(&self, tx_id: TxId, name: &str) -> Result<(), TransactionError> {
+        self.update_savepoints(tx_id, name, |tx| {
+            tx.savepoint(name);
+            true
+        })
+    }
+
+    /// Roll back to a savepoint, restoring its writes and read snapshot.
+    /// Later savepoints are discarded; the savepoint itself is kept.
+    pub fn rollback_to_savepoint(&self, tx_id: TxId, name: &str) -> Result<(), TransactionError> {
+        self.update_savepoints(tx_id, name, |tx| tx.rollback_to_savepoint(name))
+    }
+
+    /// Release a savepoint (and any taken after it), keeping current writes.
+    pub fn release_savepoint(&self, tx_id: TxId, name: &str) -> Result<(), TransactionError> {
+        self.update_savepoints(tx_id, name, |tx| tx.release_savepoint(name))
+    }
+
+    /// Apply a savepoint operation to an active transaction and persist it
+    fn update_savepoints(
+        &self,
+        tx_id: TxId,
+        name: &str,
+        op: impl FnOnce(&mut TransactionRecord) -> bool,
+    ) -> Result<(), TransactionError> {
+        let mut active = self.active_transactions.write()
+            .map_err(|_| TransactionError::LockError("active_transactions".to_string()))?;
+        let tx = active.get_mut(&tx_id)
+            .ok_or(TransactionError::TransactionNotFound(tx_id))?;
+
+        if !tx.is_active() {
+            return Err(TransactionError::TransactionNotActive(tx_id));
+        }
+
+        if !op(tx) {
+            return Err(TransactionError::SavepointNotFound {
+                tx_id,
+                name: name.to_string(),
+            });
+        }
+
+        self.log.write_transaction(tx)
+    }
+
     /// Commit a transaction
     ///
     /// The commit path is serialized via `commit_lock` to prevent a TOCTOU race
@@ -192,8 +606,21 @@ impl TransactionManager {
     /// writes to the catalog. The lock is held from conflict detection through
     /// catalog write and recent_committed update, ensuring linearizable commits.
     pub fn commit(&self, tx_id: TxId) -> Result<(), TransactionError> {
+        self.commit_with(tx_id, OnConflict::Abort)
+    }
+
+    /// Commit a transaction, choosing how conflicts are handled.
+    ///
+    /// With [`OnConflict::Analyze`], tables that conflict with concurrent
+    /// commits (overlapping writes or stale reads) are passed to a
+    /// [`MergeAnalyzer`] over the manager's schema registry. If all of
+    /// them are auto-mergeable, the rows written are merged with the
+    /// concurrent versions in the merge store (see `set_merge_store`),
+    /// the result is committed and the tables are recorded in
+    /// `TransactionRecord::auto_merged` (and the changelog entry).
+    pub fn commit_with(&self, tx_id: TxId, on_conflict: OnConflict) -> Result<(), TransactionError> {
         // Get transaction from active set (clone required to release RwLock)
-        let mut tx = {
+        let tx = {
             let active = self.active_transactions.read()
                 .map_err(|_| TransactionError::LockError("active_transactions".to_string()))?;
             active.get(&tx_id)
@@ -205,17 +632,94 @@ impl TransactionManager {
             return Err(TransactionError::TransactionNotActive(tx_id));
         }
 
+        let mut timer = CommitTimer::start();
+        let branch = tx.branch.clone();
+        let attempted = self.hooks.has(HookEvent::Conflict).then(|| tx.clone());
+        let result = self.commit_timed(tx, on_conflict, &mut timer);
+        let timings = timer.finish(tx_id, branch, result.as_ref().err().map(|e| e.to_string()));
+        self.record_timings(timings);
+
+        match result {
+            Ok(Some(committed)) => {
+                self.fire_commit_hooks(&committed);
+                Ok(())
+            }
+            Ok(None) => Ok(()),
+            Err(e) => {
+                if let Some(attempted) = attempted.filter(|_| e.is_conflict()) {
+                    let changes = changes_of(&attempted, &attempted.read_snapshot);
+                    self.hooks.fire(HookEvent::Conflict, &attempted, &changes);
+                }
+                Err(e)
+            }
+        }
+    }
+
+    /// The commit path of `commit_with`, measured phase by phase. Returns
+    /// the committed record if commit hooks need it.
+    fn commit_timed(
+        &self,
+        mut tx: TransactionRecord,
+        on_conflict: OnConflict,
+        timer: &mut CommitTimer,
+    ) -> Result<Option<TransactionRecord>, TransactionError> {
+        let tx_id = tx.tx_id;
+
+        // Wait for our turn among queued commits (released after commit_lock)
         // === BEGIN SERIALIZED COMMIT CRITICAL SECTION ===
         // Hold commit_lock from conflict check through catalog write to prevent
         // two transactions from both passing conflict checks concurrently.
-        let _commit_guard = self.commit_lock.lock()
-            .map_err(|_| TransactionError::LockError("commit_lock".to_string()))?;
+        let (_permit, _commit_guard) = timer.measure(CommitPhase::Queue, || {
+            let permit = self.commit_scheduler
+                .as_ref()
+                .map(|s| s.acquire(tx.priority))
+                .transpose()?;
+            let guard = self.commit_lock.lock()
+                .map_err(|_| TransactionError::LockError("commit_lock".to_string()))?;
+            Ok::<_, TransactionError>((permit, guard))
+        })?;
+
+        // The transaction may have been aborted (e.g. by `shutdown`) while
+        // we waited for the lock
+        {
+            let active = self.active_transactions.read()
+                .map_err(|_| TransactionError::LockError("active_transactions".to_string()))?;
+            if !active.get(&tx_id).is_some_and(|t| t.is_active()) {
+                return Err(TransactionError::TransactionNotActive(tx_id));
+            }
+        }
+
+        match on_conflict {
+            OnConflict::Abort => {
+                timer.measure(CommitPhase::ConflictCheck, || {
+                    if self.conflict_granularity == ConflictGranularity::Chunk {
+                        self.narrow_to_chunks(&mut tx)?;
+                    }
+
+                    // Check for conflicts with recently committed transactions
+                    self.check_conflicts(&tx)
+                })?;
+
+                // Validate snapshot (tables we read haven't changed, or only
+                // by commits our writes do not overlap)
+                timer.measure(CommitPhase::SnapshotValidation, || {
+                    if self.conflict_granularity == ConflictGranularity::Table {
+                        self.validate_snapshot(&tx)
+                    } else {
+                        self.rebase_granular_writes(&mut tx)
+                    }
+                })?;
+            }
+            OnConflict::Analyze => {
+                timer.measure(CommitPhase::ConflictCheck, || self.resolve_conflicts(&mut tx))?;
+            }
+        }
 
-        // Check for conflicts with recently committed transactions
-        self.check_conflicts(&tx)?;
+        // Refuse before touching the catalog if a target branch is frozen
+        timer.measure(CommitPhase::BranchUpdates, || self.check_branches_writable(&tx))?;
 
-        // Validate snapshot (tables we read haven't changed)
-        self.validate_snapshot(&tx)?;
+        // ... or a write changes its table's schema without a migration
+        timer.measure(CommitPhase::CatalogWrites, || self.check_schema_changes(&tx))?;
 
         // Mark committed in place (no clone needed — we own tx)
         tx.mark_committed();
@@ -223,17 +727,27 @@ impl TransactionManager {
         // Apply writes to catalog and update branch heads.
         // apply_writes returns actual committed versions (which may differ from
         // pre-computed versions if another writer committed between planning and execution).
-        let committed_versions = self.apply_writes(&tx)?;
-        self.update_branch_heads(&tx, &committed_versions)?;
+        let committed_versions = timer.measure(CommitPhase::CatalogWrites, || self.apply_writes(&tx))?;
+        timer.measure(CommitPhase::BranchUpdates, || {
+            self.update_branch_heads(&tx, &committed_versions)?;
+            self.publish_snapshots(&tx, &committed_versions)
+        })?;
+
+        // Log the versions actually committed so the changelog can be
+        // replayed faithfully
+        for write in tx.writes.iter_mut() {
+            if let Some(&version) = committed_versions.get(&write.table_name) {
+                write.new_version = version;
+            }
+        }
 
-        // Persist committed status
-        self.log.write_transaction(&tx)?;
+        // Persist committed status and update epoch metadata
+        timer.measure(CommitPhase::LogPersistence, || {
+            self.log.write_transaction(&tx)?;
+            self.update_epoch(tx.epoch_id, |meta| meta.record_commit())
+        })?;
 
-        // Update epoch metadata
-        let epoch_id = tx.epoch_id;
-        let mut epoch_meta = self.log.get_epoch(epoch_id)?;
-        epoch_meta.record_commit();
-        self.log.write_epoch_metadata(&epoch_meta)?;
+        let committed = self.hooks.has(HookEvent::Commit).then(|| tx.clone());
 
         // Add to recently committed for conflict detection (bounded).
         // Move tx instead of cloning — this is the last use.
@@ -246,17 +760,18 @@ impl TransactionManager {
             }
         }
 
-        // === END SERIALIZED COMMIT CRITICAL SECTION ===
-        // commit_lock released here (drop of _commit_guard)
-
-        // Remove from active set (safe outside lock — tx_id is unique)
+        // Remove from active set before releasing the lock, so `shutdown`
+        // never sees a committed transaction as in flight
         {
             let mut active = self.active_transactions.write()
                 .map_err(|_| TransactionError::LockError("active_transactions".to_string()))?;
             active.remove(&tx_id);
         }
 
-        Ok(())
+        // === END SERIALIZED COMMIT CRITICAL SECTION ===
+        // commit_lock released here (drop of _commit_guard)
+
+        Ok(committed)
     }
 
     /// Abort a transaction
@@ -276,17 +791,68 @@ impl TransactionManager {
         self.log.write_transaction(tx)?;
 
         // Update epoch metadata
-        let mut epoch_meta = self.log.get_epoch(tx.epoch_id)?;
-        epoch_meta.record_abort();
-        self.log.write_epoch_metadata(&epoch_meta)?;
+        self.update_epoch(tx.epoch_id, |meta| meta.record_abort())?;
 
         // Remove from active set
         let tx_id = tx.tx_id;
-        active.remove(&tx_id);
+        let aborted = active.remove(&tx_id);
+        drop(active);
 
+        if let Some(tx) = aborted.filter(|_| self.hooks.has(HookEvent::Abort)) {
+            self.hooks.fire(HookEvent::Abort, &tx, &changes_of(&tx, &tx.read_snapshot));
+        }
         Ok(())
     }
 
+    /// Run `f` in a new transaction on `branch` and commit it, retrying
+    /// on write and snapshot conflicts.
+    ///
+    /// A conflicting attempt (whether `commit` or `f` itself failed with
+    /// the conflict) is aborted and, after the policy's backoff, `f` runs
+    /// again in a fresh transaction, so it must rebuild its writes from the
+    /// new snapshot rather than reuse results of an earlier attempt. Any
+    /// other error from `f` or `commit` aborts the transaction and is
+    /// returned as is.
+    ///
+    /// # Errors
+    /// `RetriesExhausted` with the last conflict once
+    /// `policy.max_attempts` attempts have conflicted.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// manager.run_with_retry(None, &RetryPolicy::new(), |tx| {
+    ///     let next = manager.get_transaction(tx)?.read_snapshot.get("users").map_or(1, |v| v + 1);
+    ///     manager.add_write(tx, TableWrite::new("users", next, chunks.clone()))
+    /// })?;
+    /// ```
+    pub fn run_with_retry<T>(
+        &self,
+        branch: Option<&str>,
+        policy: &RetryPolicy,
+        mut f: impl FnMut(TxId) -> Result<T, TransactionError>,
+    ) -> Result<T, TransactionError> {
+        let mut attempt = 1;
+        loop {
+            let tx_id = self.begin(branch)?;
+            let result = f(tx_id).and_then(|value| self.commit(tx_id).map(|()| value));
+            let err = match result {
+                Ok(value) => return Ok(value),
+                Err(e) => e,
+            };
+            // `f` may have ended the transaction itself
+            let _ = self.abort(tx_id, &format!("run_with_retry attempt {}: {}", attempt, err));
+            if !err.is_conflict() {
+                return Err(err);
+            }
+            if attempt >= policy.max_attempts {
+                return Err(TransactionError::RetriesExhausted { attempts: attempt, source: Box::new(err) });
+            }
+            std::thread::sleep(policy.delay(attempt));
+            attempt += 1;
+        }
+    }
+
     /// Get a transaction by ID
     pub fn get_transaction(&self, tx_id: TxId) -> Result<TransactionRecord, TransactionError> {
         // Check active first
@@ -324,6 +890,62 @@ impl TransactionManager {
         Ok(())
     }
 
+    // === Shutdown ===
+
+    /// Stop accepting transactions and settle the ones in flight.
+    ///
+    /// `begin` fails with [`TransactionError::ShuttingDown`] from the
+    /// moment this is called. In-flight transactions get `grace_period` to
+    /// commit or abort; whatever is still active afterwards is aborted
+    /// with reason "shutdown". Finally the in-memory snapshot and conflict
+    /// caches are dropped and the transaction log is flushed to disk, so
+    /// the next open finds nothing for crash recovery to roll back.
+    ///
+    /// Calling it again is harmless: there is nothing left to drain.
+    pub fn shutdown(&self, grace_period: Duration) -> Result<ShutdownReport, TransactionError> {
+        let start = Instant::now();
+        let in_flight: Vec<TxId> = {
+            let active = self.active_transactions.read()
+                .map_err(|_| TransactionError::LockError("active_transactions".to_string()))?;
+            self.shutting_down.store(true, Ordering::SeqCst);
+            active.keys().copied().collect()
+        };
+
+        while self.active_count()? > 0 && start.elapsed() < grace_period {
+            std::thread::sleep(SHUTDOWN_POLL_INTERVAL.min(grace_period.saturating_sub(start.elapsed())));
+        }
+        let waited = start.elapsed();
+
+        // Abort stragglers under commit_lock so none is mid-commit
+        let mut aborted = Vec::new();
+        {
+            let _commit_guard = self.commit_lock.lock()
+                .map_err(|_| TransactionError::LockError("commit_lock".to_string()))?;
+            for tx in self.active_transactions()? {
+                if tx.is_active() {
+                    self.abort(tx.tx_id, "shutdown")?;
+                    aborted.push(tx.tx_id);
+                }
+            }
+        }
+        aborted.sort_unstable();
+
+        self.published_snapshots.write()
+            .map_err(|_| TransactionError::LockError("published_snapshots".to_string()))?
+            .clear();
+        self.clear_recent_committed()?;
+        self.log.flush()?;
+
+        let mut drained: Vec<TxId> = in_flight.into_iter().filter(|id| !aborted.contains(id)).collect();
+        drained.sort_unstable();
+        Ok(ShutdownReport { drained, aborted, waited })
+    }
+
+    /// Whether `shutdown` has been called
+    pub fn is_shut_down(&self) -> bool {
+        self.shutting_down.load(Ordering::SeqCst)
+    }
+
     // === Recovery Methods ===
 
     /// Perform recovery after crash/restart
@@ -358,53 +980,422 @@ impl TransactionManager {
         super::recovery::verify_consistency(&self.log)
     }
 
-    // === Private helpers ===
+    /// Capture a consistent multi-table view without opening a transaction.
+    ///
+    /// Versions are resolved exactly as `begin()` resolves its read snapshot;
+    /// nothing is written to the transaction log.
+    pub fn read_snapshot(&self, branch: Option<&str>) -> Result<ReadSnapshot, TransactionError> {
+        let branch_name = match branch {
+            Some(b) => b.to_string(),
+            None => self.default_branch()?,
+        };
 
-    fn default_branch(&self) -> Result<String, TransactionError> {
-        if let Some(ref bm) = self.branch_manager {
-            bm.get_default()
-                .map_err(|e| TransactionError::BranchError(e.to_string()))?
-                .ok_or_else(|| TransactionError::BranchError("No default branch".to_string()))
-        } else {
-            Ok("main".to_string())
+        let versions = self.capture_snapshot(&branch_name)?;
+
+        let mut tables = HashMap::with_capacity(versions.len());
+        for (table, &version) in &versions {
+            let tv = self.catalog.get_version(table, Some(version))
+                .map_err(|e| TransactionError::CatalogError(e.to_string()))?;
+            tables.insert(table.clone(), SnapshotTable {
+                version,
+                chunk_hashes: tv.chunk_hashes,
+            });
         }
+
+        Ok(ReadSnapshot { branch: branch_name, tables })
     }
 
-    fn capture_snapshot(&self, branch: &str) -> Result<HashMap<String, u64>, TransactionError> {
-        let mut snapshot = HashMap::new();
+    // =========================================================================
+    // Table Drop / Rename
+    // =========================================================================
+
+    /// Drop a table: remove every version from the catalog and the table
+    /// from every branch, and record a tombstone in the changelog
+    /// (`WriteKind::Drop`).
+    ///
+    /// Returns the removed versions. Their chunks stay in the store until
+    /// garbage collection (or [`drop_table_and_purge`](Self::drop_table_and_purge))
+    /// removes the ones no other table references. A transaction that writes the table afterwards
+    /// creates it anew.
+    pub fn drop_table(&self, table_name: &str) -> Result<Vec<TableVersion>, TransactionError> {
+        let _commit_guard = self.commit_lock.lock()
+            .map_err(|_| TransactionError::LockError("commit_lock".to_string()))?;
+        self.drop_table_locked(table_name)
+    }
+
+    /// [`drop_table`](Self::drop_table), then delete the dropped versions'
+    /// chunks from `store` that nothing reaches any more.
+    ///
+    /// Reachability is [`ChunkGc`]'s, restricted to those chunks: chunks
+    /// referenced by another version or an in-flight transaction, modified
+    /// within `grace_period`, or the delta base of a kept chunk stay. The
+    /// commit lock is held throughout, so no commit starts referencing a
+    /// chunk between the check and the delete.
+    pub fn drop_table_and_purge(
+        &self,
+        table_name: &str,
+        store: &ChunkStore,
+        grace_period: Duration,
+    ) -> Result<(Vec<TableVersion>, GcReport), TransactionError> {
+        let _commit_guard = self.commit_lock.lock()
+            .map_err(|_| TransactionError::LockError("commit_lock".to_string()))?;
+        let dropped = self.drop_table_locked(table_name)?;
+
+        let chunks: HashSet<String> = dropped.iter().flat_map(|v| v.chunk_hashes.iter().cloned()).collect();
+        let report = ChunkGc::new(&self.catalog, store)
+            .with_transactions(self)
+            .with_grace_period(grace_period)
+            .with_candidates(chunks)
+            .run()
+            .map_err(|e| match e {
+                GcError::Transaction(e) => e,
+                e => TransactionError::CatalogError(e.to_string()),
+            })?;
+        Ok((dropped, report))
+    }
 
+    fn drop_table_locked(&self, table_name: &str) -> Result<Vec<TableVersion>, TransactionError> {
+        self.check_table_refs_writable(table_name)?;
+        let dropped = self.catalog.drop_table(table_name)
+            .map_err(|e| TransactionError::CatalogError(e.to_string()))?;
         if let Some(ref bm) = self.branch_manager {
-            // Use branch heads
-            let branch_data = bm.get(branch)
+            bm.remove_table(table_name)
                 .map_err(|e| TransactionError::BranchError(e.to_string()))?;
-            snapshot = branch_data.head;
-        } else {
-            // Use catalog latest versions
-            let tables = self.catalog.list_tables()
-                .map_err(|e| TransactionError::CatalogError(e.to_string()))?;
-            for table in tables {
-                if let Ok(version) = self.catalog.get_version(&table, None) {
-                    snapshot.insert(table, version.version);
-                }
-            }
         }
 
+        let last_version = dropped.last().map_or(0, |v| v.version);
+        self.record_table_operation(TableWrite::drop_table(table_name, last_version))?;
+        Ok(dropped)
+    }
+
+    /// Rename a table in the catalog and on every branch, keeping its
+    /// history, and record the rename in the changelog
+    /// (`WriteKind::Rename`). Returns the table's latest version.
+    pub fn rename_table(&self, old_name: &str, new_name: &str) -> Result<u64, TransactionError> {
+        let _commit_guard = self.commit_lock.lock()
+            .map_err(|_| TransactionError::LockError("commit_lock".to_string()))?;
+
+        self.check_table_refs_writable(old_name)?;
+        let version = self.catalog.rename_table(old_name, new_name)
+            .map_err(|e| TransactionError::CatalogError(e.to_string()))?;
+        if let Some(ref bm) = self.branch_manager {
+            bm.rename_table(old_name, new_name)
+                .map_err(|e| TransactionError::BranchError(e.to_string()))?;
+        }
+
+        self.record_table_operation(TableWrite::rename_table(old_name, new_name, version))?;
+        Ok(version)
+    }
+
+    /// Merge branch `source` into `into` as a merge commit (see
+    /// [`BranchManager::merge`]) and record it in the changelog: one
+    /// `WriteKind::Merge` write per head the merge moved, with the
+    /// [`MergeRecord`] on the transaction, so consumers can tell merges from
+    /// ordinary commits. Requires a branch manager.
+    pub fn merge(&self, source: &str, into: &str, strategy: MergeStrategy) -> Result<MergeRecord, TransactionError> {
+        let bm = self.branch_manager.as_ref()
+            .ok_or_else(|| TransactionError::BranchError("No branch manager to merge with".to_string()))?;
+        let _commit_guard = self.commit_lock.lock()
+            .map_err(|_| TransactionError::LockError("commit_lock".to_string()))?;
+
+        let tx_id = self.log.next_tx_id()?;
+        let merge = bm.commit_merge(source, into, strategy, Some(tx_id))
+            .map_err(|e| TransactionError::BranchError(e.to_string()))?;
+
+        let mut tables: Vec<(&String, &u64)> = merge.versions.iter().collect();
+        tables.sort();
+        let mut writes = Vec::with_capacity(tables.len());
+        for (table, &version) in tables {
+            let chunk_hashes = self.catalog.get_version(table, Some(version))
+                .map_err(|e| TransactionError::CatalogError(e.to_string()))?
+                .chunk_hashes;
+            writes.push(TableWrite::merge_table(table.as_str(), source, version, chunk_hashes));
+        }
+
+        let mut tx = TransactionRecord::new(tx_id, self.log.current_epoch_id()?, into.to_string());
+        tx.writes = writes;
+        tx.merge = Some(merge.clone());
+        self.log_operation(tx)?;
+        Ok(merge)
+    }
+
+    /// Refuse before touching the catalog if a frozen branch refers to the table
+    fn check_table_refs_writable(&self, table_name: &str) -> Result<(), TransactionError> {
+        if let Some(ref bm) = self.branch_manager {
+            let branches = bm.branches_with_table(table_name)
+                .map_err(|e| TransactionError::BranchError(e.to_string()))?;
+            if let Some(branch) = branches.into_iter().find(|b| b.immutable) {
+                return Err(TransactionError::BranchError(
+                    BranchError::BranchImmutable(branch.name).to_string(),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Log an already-applied table operation as a committed transaction so
+    /// it appears in the changelog (caller holds `commit_lock`)
+    fn record_table_operation(&self, write: TableWrite) -> Result<(), TransactionError> {
+        let tx_id = self.log.next_tx_id()?;
+        let epoch_id = self.log.current_epoch_id()?;
+        let mut tx = TransactionRecord::new(tx_id, epoch_id, self.default_branch()?);
+        tx.writes.push(write);
+        self.log_operation(tx)
+    }
+
+    /// Commit a transaction record for an operation that already happened
+    /// (caller holds `commit_lock`)
+    fn log_operation(&self, mut tx: TransactionRecord) -> Result<(), TransactionError> {
+        let (tx_id, epoch_id) = (tx.tx_id, tx.epoch_id);
+        tx.mark_committed();
+        self.log.write_transaction(&tx)?;
+
+        self.update_epoch(epoch_id, |meta| {
+            meta.add_transaction(tx_id);
+            meta.record_commit();
+        })?;
+
+        // Every published snapshot may list the table
+        self.published_snapshots.write()
+            .map_err(|_| TransactionError::LockError("published_snapshots".to_string()))?
+            .clear();
+        Ok(())
+    }
+
+    // =========================================================================
+    // Epoch Management
+    // =========================================================================
+
+    /// Read-modify-write an epoch's metadata under `epoch_lock`
+    fn update_epoch(
+        &self,
+        epoch_id: EpochId,
+        update: impl FnOnce(&mut EpochMetadata),
+    ) -> Result<EpochMetadata, TransactionError> {
+        let _guard = self.epoch_lock.lock()
+            .map_err(|_| TransactionError::LockError("epoch_lock".to_string()))?;
+        let mut meta = self.log.get_epoch(epoch_id)?;
+        update(&mut meta);
+        self.log.write_epoch_metadata(&meta)?;
+        Ok(meta)
+    }
+
+    /// Get metadata for the epoch new transactions are currently assigned to
+    pub fn current_epoch(&self) -> Result<EpochMetadata, TransactionError> {
+        let epoch_id = self.log.current_epoch_id()?;
+        self.log.get_epoch(epoch_id)
+    }
+
+    /// Get metadata for all epochs, oldest first
+    pub fn list_epochs(&self) -> Result<Vec<EpochMetadata>, TransactionError> {
+        self.log
+            .list_epochs()?
+            .into_iter()
+            .map(|id| self.log.get_epoch(id))
+            .collect()
+    }
+
+    /// Close the current epoch and open the next one.
+    ///
+    /// The sealed epoch is marked `Committed` if none of its transactions are
+    /// still active, otherwise `Committing` (recovery resolves the stragglers).
+    /// Later `begin()` calls are assigned to the new epoch. Returns the
+    /// metadata of the sealed epoch.
+    pub fn seal_epoch(&self) -> Result<EpochMetadata, TransactionError> {
+        // Serialize with commits so no commit straddles the boundary
+        let _guard = self.commit_lock.lock()
+            .map_err(|_| TransactionError::LockError("commit_lock".to_string()))?;
+
+        let epoch_id = self.log.current_epoch_id()?;
+
+        let has_active = {
+            let active = self.active_transactions.read()
+                .map_err(|_| TransactionError::LockError("active_transactions".to_string()))?;
+            active.values().any(|tx| tx.epoch_id == epoch_id)
+        };
+
+        let meta = self.update_epoch(epoch_id, |meta| {
+            if has_active {
+                meta.mark_committing();
+            } else {
+                meta.mark_committed();
+            }
+        })?;
+        if !has_active {
+            self.log.mark_epoch_committed(epoch_id)?;
+        }
+
+        let next = self.log.next_epoch_id()?;
+        self.log.create_epoch(next)?;
+
+        // Epoch boundary: validate_snapshot covers anything evicted here
+        self.clear_recent_committed()?;
+
+        if let Some(retain_epochs) = self.log_compaction {
+            self.compact_locked(retain_epochs)?;
+        }
+
+        Ok(meta)
+    }
+
+    /// Roll epochs older than the newest `retain_epochs` into a log
+    /// segment (see [`TransactionLog::compact`]).
+    ///
+    /// Epochs that active transactions belong to, and every epoch after
+    /// them, stay uncompacted. Changelog queries return the same entries
+    /// afterwards.
+    pub fn compact_log(&self, retain_epochs: u64) -> Result<CompactionReport, TransactionError> {
+        let _guard = self.commit_lock.lock()
+            .map_err(|_| TransactionError::LockError("commit_lock".to_string()))?;
+        self.compact_locked(retain_epochs)
+    }
+
+    /// `compact_log` for callers already holding `commit_lock`
+    fn compact_locked(&self, retain_epochs: u64) -> Result<CompactionReport, TransactionError> {
+        let mut before = self.log.current_epoch_id()?.saturating_sub(retain_epochs.saturating_sub(1));
+        {
+            let active = self.active_transactions.read()
+                .map_err(|_| TransactionError::LockError("active_transactions".to_string()))?;
+            if let Some(oldest) = active.values().map(|tx| tx.epoch_id).min() {
+                before = before.min(oldest);
+            }
+        }
+        self.log.compact(before)
+    }
+
+    // === Private helpers ===
+
+    pub(crate) fn default_branch(&self) -> Result<String, TransactionError> {
+        if let Some(ref bm) = self.branch_manager {
+            bm.get_default()
+                .map_err(|e| TransactionError::BranchError(e.to_string()))?
+                .ok_or_else(|| TransactionError::BranchError("No default branch".to_string()))
+        } else {
+            Ok("main".to_string())
+        }
+    }
+
+    /// Read the current versions of all tables visible on a branch.
+    ///
+    /// If the branch file is unchanged since the snapshot published for
+    /// the branch was captured, that snapshot is returned without reading
+    /// the head. Otherwise the head is read and, if it differs, published
+    /// in its place.
+    fn capture_snapshot(&self, branch: &str) -> Result<SharedSnapshot, TransactionError> {
+        let key = self.snapshot_key(branch);
+        let mut stamp = None;
+        if let Some(ref bm) = self.branch_manager {
+            let current_stamp = bm.head_stamp(branch)
+                .map_err(|e| TransactionError::BranchError(e.to_string()))?;
+            let published = self.published_snapshots.read()
+                .map_err(|_| TransactionError::LockError("published_snapshots".to_string()))?;
+            if let Some(current) = published.get(key).filter(|p| p.stamp == Some(current_stamp)) {
+                return Ok(current.snapshot.clone());
+            }
+        }
+
+        let mut snapshot = HashMap::new();
+        if let Some(ref bm) = self.branch_manager {
+            // Use branch heads, unread if unchanged since the last capture
+            let (branch_stamp, branch_data) = bm.get_stamped(branch)
+                .map_err(|e| TransactionError::BranchError(e.to_string()))?;
+            snapshot = branch_data.head;
+            stamp = Some(branch_stamp);
+        } else {
+            // Use catalog latest versions
+            let tables = self.catalog.list_tables()
+                .map_err(|e| TransactionError::CatalogError(e.to_string()))?;
+            for table in tables {
+                if let Ok(version) = self.catalog.get_version(&table, None) {
+                    snapshot.insert(table, version.version);
+                }
+            }
+
+            let published = self.published_snapshots.read()
+                .map_err(|_| TransactionError::LockError("published_snapshots".to_string()))?;
+            if let Some(current) = published.get(key).filter(|p| *p.snapshot == snapshot) {
+                return Ok(current.snapshot.clone());
+            }
+        }
+
+        let mut published = self.published_snapshots.write()
+            .map_err(|_| TransactionError::LockError("published_snapshots".to_string()))?;
+        if let Some(current) = published.get_mut(key).filter(|p| *p.snapshot == snapshot) {
+            // Same versions (e.g. already published by the commit that
+            // changed the file): keep sharing them
+            current.stamp = stamp;
+            return Ok(current.snapshot.clone());
+        }
+        let snapshot = SharedSnapshot::new(snapshot);
+        published.insert(key.to_string(), PublishedSnapshot { snapshot: snapshot.clone(), stamp });
         Ok(snapshot)
     }
 
+    /// Advance the published snapshots past a commit, so transactions that
+    /// begin next share one snapshot of the new heads.
+    fn publish_snapshots(
+        &self,
+        tx: &TransactionRecord,
+        committed_versions: &HashMap<String, u64>,
+    ) -> Result<(), TransactionError> {
+        let mut updates: HashMap<&str, Vec<(String, u64)>> = HashMap::new();
+        for write in &tx.writes {
+            let branch = write.branch.as_deref().unwrap_or(&tx.branch);
+            let version = committed_versions
+                .get(&write.table_name)
+                .copied()
+                .unwrap_or(write.new_version);
+            updates
+                .entry(self.snapshot_key(branch))
+                .or_default()
+                .push((write.table_name.clone(), version));
+        }
+
+        let mut published = self.published_snapshots.write()
+            .map_err(|_| TransactionError::LockError("published_snapshots".to_string()))?;
+        for (key, versions) in updates {
+            // Unpublished branches are captured on their next begin()
+            if let Some(current) = published.get_mut(key) {
+                current.snapshot = current.snapshot.with_versions(versions);
+            }
+        }
+        Ok(())
+    }
+
+    /// Without a branch manager every branch reads the catalog's latest
+    /// versions, so they share one published snapshot.
+    fn snapshot_key<'a>(&self, branch: &'a str) -> &'a str {
+        if self.branch_manager.is_some() { branch } else { "" }
+    }
+
     fn check_conflicts(&self, tx: &TransactionRecord) -> Result<(), TransactionError> {
+        match self.detect_conflicts(tx, true)?.pop() {
+            Some(conflict) => Err(TransactionError::WriteConflict(conflict.tables)),
+            None => Ok(()),
+        }
+    }
+
+    /// Conflicts with recently committed and preparing transactions
+    /// (only the first one if `first_only`)
+    fn detect_conflicts(&self, tx: &TransactionRecord, first_only: bool) -> Result<Vec<Conflict>, TransactionError> {
+        let mut conflicts = Vec::new();
+
         // Check against recently committed transactions
         let recent = self.recent_committed.read()
             .map_err(|_| TransactionError::LockError("recent_committed".to_string()))?;
+        let evicted = self.evicted_commits(tx, &recent)?;
 
-        for committed_tx in recent.iter() {
+        for committed_tx in recent.iter().chain(evicted.iter()) {
             // Only check transactions that started before us and committed after
-            if committed_tx.tx_id >= tx.tx_id {
+            if committed_tx.tx_id >= tx.tx_id || committed_before_snapshot(tx, committed_tx) {
                 continue;
             }
 
             if let Some(conflict) = self.conflict_detector.detect(tx, committed_tx) {
-                return Err(TransactionError::WriteConflict(conflict.tables));
+                conflicts.push(conflict);
+                if first_only {
+                    return Ok(conflicts);
+                }
             }
         }
 
@@ -423,35 +1414,299 @@ impl TransactionManager {
             }
 
             if let Some(conflict) = self.conflict_detector.detect(tx, other_tx) {
-                return Err(TransactionError::WriteConflict(conflict.tables));
+                conflicts.push(conflict);
+                if first_only {
+                    return Ok(conflicts);
+                }
             }
         }
 
-        Ok(())
+        Ok(conflicts)
+    }
+
+    /// Committed transactions that began before `tx`, are within the
+    /// conflict window and are no longer in `recent`, in commit order
+    fn evicted_commits(
+        &self,
+        tx: &TransactionRecord,
+        recent: &VecDeque<TransactionRecord>,
+    ) -> Result<Vec<TransactionRecord>, TransactionError> {
+        let (min_epoch, since) = match self.conflict_window {
+            ConflictWindow::Memory => return Ok(Vec::new()),
+            ConflictWindow::Epochs(n) => (Some(self.log.current_epoch_id()?.saturating_sub(n)), None),
+            ConflictWindow::Seconds(n) => (None, Some(unix_now().saturating_sub(n as i64))),
+            ConflictWindow::Lifetime => (None, Some(tx.started_at)),
+        };
+
+        let in_memory: HashMap<TxId, Option<i64>> = recent.iter()
+            .map(|r| (r.tx_id, r.committed_at))
+            .collect();
+        let index = self.log.transaction_index()?;
+        let mut evicted = Vec::new();
+
+        let before_window = |committed_at: Option<i64>| {
+            matches!((committed_at, since), (Some(at), Some(since)) if at < since)
+        };
+
+        // Newest first, so time windows stop at the first older commit
+        for entry in index.entries().iter().rev() {
+            if let Some(&committed_at) = in_memory.get(&entry.tx_id) {
+                if before_window(committed_at) {
+                    break;
+                }
+                continue;
+            }
+            if entry.tx_id >= tx.tx_id || min_epoch.is_some_and(|m| entry.epoch_id < m) {
+                continue;
+            }
+            let record = self.log.read_transaction_from_epoch(entry.tx_id, entry.epoch_id)?;
+            if before_window(record.committed_at) {
+                break;
+            }
+            evicted.push(record);
+        }
+
+        evicted.reverse();
+        Ok(evicted)
     }
 
     fn validate_snapshot(&self, tx: &TransactionRecord) -> Result<(), TransactionError> {
-        for (table, read_version) in &tx.read_snapshot {
-            let current_version = if let Some(ref bm) = self.branch_manager {
-                bm.get_table_version(&tx.branch, table)
-                    .map_err(|e| TransactionError::BranchError(e.to_string()))?
-            } else {
-                self.catalog.get_version(table, None)
-                    .map(|v| Some(v.version))
-                    .unwrap_or(None)
+        match self.stale_reads(tx, true)?.pop() {
+            Some((table, read_version, current_version)) => {
+                Err(TransactionError::snapshot_conflict(table, read_version, current_version))
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Narrow whole-table data writes to the chunks of the version read
+    /// that they no longer contain
+    fn narrow_to_chunks(&self, tx: &mut TransactionRecord) -> Result<(), TransactionError> {
+        for write in tx.writes.iter_mut() {
+            if !write.kind.is_data() || !matches!(write.granularity, WriteGranularity::WholeTable) {
+                continue;
+            }
+            // New tables stay whole-table: concurrent creators conflict
+            let Some(&base_version) = tx.read_snapshot.get(&write.table_name) else {
+                continue;
+            };
+            let base = self.catalog.get_version(&write.table_name, Some(base_version))
+                .map_err(|e| TransactionError::CatalogError(e.to_string()))?;
+            let written: HashSet<&str> = write.chunk_hashes.iter().map(String::as_str).collect();
+            let replaced = base.chunk_hashes.into_iter()
+                .filter(|hash| !written.contains(hash.as_str()))
+                .collect();
+            write.granularity = WriteGranularity::Chunks(replaced);
+        }
+        Ok(())
+    }
+
+    /// Validate the snapshot, rebasing granular writes to tables that
+    /// changed since it was taken.
+    ///
+    /// A stale table is accepted only if this transaction wrote it at
+    /// finer than whole-table granularity and every version committed
+    /// since came from a recent commit the detector finds no conflict
+    /// with; anything else is a snapshot conflict as usual. Disjoint keys
+    /// or partitions may still share a base chunk: if both sides replaced
+    /// one, the writes cannot be rebased and conflict.
+    fn rebase_granular_writes(&self, tx: &mut TransactionRecord) -> Result<(), TransactionError> {
+        let stale = self.stale_reads(tx, false)?;
+        if stale.is_empty() {
+            return Ok(());
+        }
+
+        let recent = self.recent_committed.read()
+            .map_err(|_| TransactionError::LockError("recent_committed".to_string()))?;
+        let evicted = self.evicted_commits(tx, &recent)?;
+
+        for (table, read_version, current_version) in stale {
+            let write_index = tx.writes.iter().position(|w| {
+                w.table_name == table
+                    && w.kind.is_data()
+                    && !matches!(w.granularity, WriteGranularity::WholeTable)
+            });
+            let explained: HashSet<u64> = recent.iter()
+                .chain(evicted.iter())
+                .filter(|other| self.conflict_detector.detect(tx, other).is_none())
+                .flat_map(|other| other.writes.iter())
+                .filter(|w| w.table_name == table)
+                .map(|w| w.new_version)
+                .collect();
+
+            let Some(index) = write_index.filter(|_| {
+                (read_version + 1..=current_version).all(|v| explained.contains(&v))
+            }) else {
+                return Err(TransactionError::snapshot_conflict(table, read_version, current_version));
             };
 
-            if let Some(current) = current_version {
+            let base = self.catalog.get_version(&table, Some(read_version))
+                .map_err(|e| TransactionError::CatalogError(e.to_string()))?;
+            let current = self.catalog.get_version(&table, Some(current_version))
+                .map_err(|e| TransactionError::CatalogError(e.to_string()))?;
+            let write = &mut tx.writes[index];
+            if replaced_by_both(&base.chunk_hashes, &write.chunk_hashes, &current.chunk_hashes) {
+                return Err(TransactionError::WriteConflict(vec![table]));
+            }
+            write.chunk_hashes = rebase_chunks(&base.chunk_hashes, &write.chunk_hashes, current.chunk_hashes);
+        }
+
+        Ok(())
+    }
+
+    /// Tables read at a version that is no longer current, as
+    /// `(table, read_version, current_version)` (only the first one if
+    /// `first_only`)
+    fn stale_reads(&self, tx: &TransactionRecord, first_only: bool) -> Result<Vec<(String, u64, u64)>, TransactionError> {
+        let mut stale = Vec::new();
+        for (table, read_version) in &tx.read_snapshot {
+            if let Some(current) = self.current_version(&tx.branch, table)? {
                 if current != *read_version {
-                    return Err(TransactionError::SnapshotConflict {
-                        table: table.clone(),
-                        read_version: *read_version,
-                        current_version: current,
-                    });
+                    stale.push((table.clone(), *read_version, current));
+                    if first_only {
+                        break;
+                    }
+                }
+            }
+        }
+
+        Ok(stale)
+    }
+
+    /// The version of `table` a read on `branch` sees now (the catalog's
+    /// latest without a branch manager)
+    fn current_version(&self, branch: &str, table: &str) -> Result<Option<u64>, TransactionError> {
+        if let Some(ref bm) = self.branch_manager {
+            bm.get_table_version(branch, table)
+                .map_err(|e| TransactionError::BranchError(e.to_string()))
+        } else {
+            Ok(self.catalog.get_version(table, None).map(|v| v.version).ok())
+        }
+    }
+
+    /// Merge the tables whose conflicts algebraic merge resolves into this
+    /// transaction's writes (recording them in `auto_merged`), or fail with
+    /// the first conflict it cannot resolve.
+    ///
+    /// Write conflicts are reported before stale reads, matching
+    /// `check_conflicts` / `validate_snapshot`.
+    fn resolve_conflicts(&self, tx: &mut TransactionRecord) -> Result<(), TransactionError> {
+        let written: BTreeSet<String> = self
+            .detect_conflicts(tx, false)?
+            .into_iter()
+            .flat_map(|c| c.tables)
+            .collect();
+        let stale = self.stale_reads(tx, false)?;
+        let tables: BTreeSet<&String> = written.iter().chain(stale.iter().map(|(t, _, _)| t)).collect();
+        let tables: Vec<&String> = tables.into_iter().collect();
+
+        let registry = self.merge_registry(&tables)?;
+        let analysis = MergeAnalyzer::new(&registry).analyze_tables(&tables);
+        let unmergeable: Vec<String> = written
+            .iter()
+            .filter(|t| analysis.conflicting.contains(t))
+            .cloned()
+            .collect();
+        if !unmergeable.is_empty() {
+            return Err(TransactionError::WriteConflict(unmergeable));
+        }
+        if let Some((table, read_version, current_version)) =
+            stale.into_iter().find(|(t, _, _)| analysis.conflicting.contains(t))
+        {
+            return Err(TransactionError::snapshot_conflict(table, read_version, current_version));
+        }
+
+        for table in &analysis.auto_mergeable {
+            self.merge_write(tx, table, &registry)?;
+        }
+        tx.auto_merged = analysis.auto_mergeable;
+        Ok(())
+    }
+
+    /// Replace this transaction's rows of `table` with their three-way
+    /// merge (against its snapshot) with the version committed since.
+    ///
+    /// Without a merge store the rows cannot be combined, and committing
+    /// ours alone would drop the concurrent changes: a write conflict.
+    fn merge_write(
+        &self,
+        tx: &mut TransactionRecord,
+        table: &str,
+        registry: &AlgebraicSchemaRegistry,
+    ) -> Result<(), TransactionError> {
+        let branch = tx.branch.clone();
+        let base = tx.read_snapshot.get(table).copied();
+        let Some(write) = tx.writes.iter_mut().find(|w| w.table_name == table && w.kind.is_data()) else {
+            // Only read: nothing of ours to combine
+            return Ok(());
+        };
+        let branch = write.branch.clone().unwrap_or(branch);
+        let Some(current) = self.current_version(&branch, table)?.filter(|&v| Some(v) != base) else {
+            return Ok(());
+        };
+        let Some(store) = self.merge_store.as_deref() else {
+            return Err(TransactionError::WriteConflict(vec![table.to_string()]));
+        };
+
+        let mut merger = DataMerger::new(&self.catalog, store, registry);
+        if let WriteGranularity::Keys { key_columns, .. } = &write.granularity {
+            merger = merger.with_key(table, key_columns.clone());
+        }
+        write.chunk_hashes = merger
+            .merge_chunks(table, base, &write.chunk_hashes, current)
+            .map_err(|e| match e {
+                DataMergeError::RowConflict { .. }
+                | DataMergeError::SchemaMismatch { .. }
+                | DataMergeError::InvalidKey { .. }
+                | DataMergeError::DuplicateKey { .. }
+                | DataMergeError::Unrepresentable { .. } => TransactionError::WriteConflict(vec![table.to_string()]),
+                e => TransactionError::CatalogError(e.to_string()),
+            })?;
+        Ok(())
+    }
+
+    /// Algebraic schemas of `tables`: the annotations versioned with each
+    /// table's latest version, overridden by the manager's registry
+    fn merge_registry(&self, tables: &[&String]) -> Result<AlgebraicSchemaRegistry, TransactionError> {
+        let mut registry = AlgebraicSchemaRegistry::new();
+        for table in tables {
+            match self.catalog.algebraic_schema(table, None) {
+                Ok(Some(schema)) => registry.register(schema),
+                Ok(None) | Err(CatalogError::TableNotFound(_)) => {}
+                Err(e) => return Err(TransactionError::CatalogError(e.to_string())),
+            }
+        }
+        registry.extend(&self.schema_registry);
+        Ok(registry)
+    }
+
+    fn check_branches_writable(&self, tx: &TransactionRecord) -> Result<(), TransactionError> {
+        if let Some(ref bm) = self.branch_manager {
+            let branches: HashSet<&String> = tx
+                .writes
+                .iter()
+                .map(|w| w.branch.as_ref().unwrap_or(&tx.branch))
+                .collect();
+            for name in branches {
+                let branch = bm.get(name).map_err(|e| TransactionError::BranchError(e.to_string()))?;
+                if branch.immutable {
+                    return Err(TransactionError::BranchError(
+                        BranchError::BranchImmutable(branch.name).to_string(),
+                    ));
                 }
             }
         }
+        Ok(())
+    }
 
+    fn check_schema_changes(&self, tx: &TransactionRecord) -> Result<(), TransactionError> {
+        for write in &tx.writes {
+            if let Some(ref hash) = write.schema_hash {
+                self.catalog
+                    .check_schema_change(&write.table_name, hash, write.schema_migration().as_ref())
+                    .map_err(|e| TransactionError::CatalogError(e.to_string()))?;
+            }
+        }
         Ok(())
     }
 
@@ -459,12 +1714,31 @@ impl TransactionManager {
         let mut committed_versions = HashMap::new();
 
         for write in &tx.writes {
+            // New tables created through a branch stay scoped to that branch
+            // until merged (see FileCatalog::is_visible_on).
+            if self.branch_manager.is_some() {
+                let exists = self.catalog.table_exists(&write.table_name)
+                    .map_err(|e| TransactionError::CatalogError(e.to_string()))?;
+                if !exists {
+                    let branch = write.branch.as_ref().unwrap_or(&tx.branch);
+                    self.catalog.set_table_origin(&write.table_name, branch)
+                        .map_err(|e| TransactionError::CatalogError(e.to_string()))?;
+                }
+            }
+
             // Use catalog-assigned versioning to prevent race conditions where
             // two transactions pre-computed the same next version number.
-            let actual_version = self.catalog.commit_next_version(
-                &write.table_name,
-                write.chunk_hashes.clone(),
-            ).map_err(|e| TransactionError::CatalogError(e.to_string()))?;
+            let actual_version = if write.metadata.is_empty() && write.schema_hash.is_none() {
+                self.catalog.commit_next_version(&write.table_name, write.chunk_hashes.clone())
+            } else {
+                self.catalog.commit_next_version_with_meta(
+                    &write.table_name,
+                    write.chunk_hashes.clone(),
+                    write.metadata.clone(),
+                    write.schema_hash.clone(),
+                )
+            }
+            .map_err(|e| TransactionError::CatalogError(e.to_string()))?;
 
             committed_versions.insert(write.table_name.clone(), actual_version);
         }
@@ -478,6 +1752,8 @@ impl TransactionManager {
         committed_versions: &HashMap<String, u64>,
     ) -> Result<(), TransactionError> {
         if let Some(ref bm) = self.branch_manager {
+            // One head update (and repository snapshot) per branch written
+            let mut heads: BTreeMap<&String, HashMap<String, u64>> = BTreeMap::new();
             for write in &tx.writes {
                 let branch = write.branch.as_ref().unwrap_or(&tx.branch);
                 // Use the actual committed version (from catalog), not the
@@ -486,7 +1762,10 @@ impl TransactionManager {
                     .get(&write.table_name)
                     .copied()
                     .unwrap_or(write.new_version);
-                bm.update_head(branch, &write.table_name, version)
+                heads.entry(branch).or_default().insert(write.table_name.clone(), version);
+            }
+            for (branch, versions) in heads {
+                bm.commit_heads(branch, &versions, Some(tx.tx_id))
                     .map_err(|e| TransactionError::BranchError(e.to_string()))?;
             }
         }
@@ -501,77 +1780,46 @@ impl TransactionManager {
     /// Query changelog entries matching the given criteria.
     ///
     /// This provides the streaming interface for the unified batch/stream model.
+    /// Candidates come from the transaction index, so only transactions on
+    /// the queried branch and tables are read.
     pub fn get_changelog(
         &self,
         query: crate::changelog::ChangelogQuery,
     ) -> Result<Vec<crate::changelog::ChangelogEntry>, TransactionError> {
         use crate::changelog::ChangelogEntry;
-        use std::collections::HashMap;
-
-        // Get committed transactions from log
-        let committed = self.log.list_committed_transactions()?;
 
+        let index = self.log.transaction_index()?;
         let mut entries = Vec::new();
-        let mut previous_versions: HashMap<String, u64> = HashMap::new();
 
-        for tx in committed {
+        for indexed in index.select(query.branch.as_deref(), query.tables.as_deref()) {
             // Filter by tx_id
-            if let Some(since_tx) = query.since_tx_id {
-                if tx.tx_id <= since_tx {
-                    // Still need to track versions for later entries
-                    for w in &tx.writes {
-                        previous_versions.insert(w.table_name.clone(), w.new_version);
-                    }
-                    continue;
-                }
+            if query.since_tx_id.is_some_and(|since_tx| indexed.tx_id <= since_tx) {
+                continue;
             }
-
-            // Filter by timestamp
-            if let Some(since_ts) = query.since_timestamp {
-                if let Some(committed_at) = tx.committed_at {
-                    if committed_at < since_ts {
-                        for w in &tx.writes {
-                            previous_versions.insert(w.table_name.clone(), w.new_version);
-                        }
-                        continue;
-                    }
-                }
+            if query.until_tx_id.is_some_and(|until_tx| indexed.tx_id > until_tx) {
+                continue;
             }
 
-            // Filter by branch
-            if let Some(ref branch) = query.branch {
-                if &tx.branch != branch {
-                    // Track versions even for non-matching branches
-                    for w in &tx.writes {
-                        previous_versions.insert(w.table_name.clone(), w.new_version);
-                    }
+            let Ok(tx) = self.log.read_transaction_from_epoch(indexed.tx_id, indexed.epoch_id) else {
+                continue;
+            };
+
+            // Filter by timestamp
+            if let (Some(since_ts), Some(committed_at)) = (query.since_timestamp, tx.committed_at) {
+                if committed_at < since_ts {
                     continue;
                 }
             }
 
-            // Filter by tables
-            let include = if let Some(ref tables) = query.tables {
-                tx.writes.iter().any(|w| tables.contains(&w.table_name))
-            } else {
-                true
-            };
-
-            if include {
-                let entry = ChangelogEntry::from_transaction(&tx, &previous_versions);
-                entries.push(entry);
+            let previous_versions = index.previous_versions(tx.tx_id).cloned().unwrap_or_default();
+            entries.push(ChangelogEntry::from_transaction(&tx, &previous_versions));
 
-                // Check limit
-                if let Some(limit) = query.limit {
-                    if entries.len() >= limit {
-                        break;
-                    }
+            // Check limit
+            if let Some(limit) = query.limit {
+                if entries.len() >= limit {
+                    break;
                 }
             }
-
-            // Track versions for future entries
-            for w in &tx.writes {
-                previous_versions.insert(w.table_name.clone(), w.new_version);
-            }
         }
 
         Ok(entries)
@@ -581,6 +1829,76 @@ impl TransactionManager {
     pub fn latest_tx_id(&self) -> Result<Option<u64>, TransactionError> {
         self.log.latest_committed_tx_id()
     }
+
+    // === Changelog Cursors ===
+
+    /// Create a named cursor at the start of the changelog. Acknowledge
+    /// `latest_tx_id()` right away to consume only new commits.
+    pub fn create_cursor(&self, name: &str) -> Result<ChangelogCursor, TransactionError> {
+        self.cursors().create(name)
+    }
+
+    /// Load a cursor at its last acknowledged transaction
+    pub fn resume(&self, name: &str) -> Result<ChangelogCursor, TransactionError> {
+        self.cursors().get(name)
+    }
+
+    /// All changelog cursors, sorted by name
+    pub fn list_cursors(&self) -> Result<Vec<ChangelogCursor>, TransactionError> {
+        self.cursors().list()
+    }
+
+    pub fn delete_cursor(&self, name: &str) -> Result<ChangelogCursor, TransactionError> {
+        self.cursors().delete(name)
+    }
+
+    fn cursors(&self) -> CursorStore {
+        CursorStore::new(self.log.base_path().join(CURSORS_DIR), self.log.durability())
+    }
+}
+
+pub(super) fn unix_now() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+/// Whether every write of `committed` was already visible when `tx` took
+/// its read snapshot (so `committed` finished before `tx` began)
+fn committed_before_snapshot(tx: &TransactionRecord, committed: &TransactionRecord) -> bool {
+    committed.writes.iter().all(|write| {
+        write.kind.is_data()
+            && write.branch.as_ref().unwrap_or(&committed.branch) == &tx.branch
+            && tx.read_snapshot.get(&write.table_name).is_some_and(|&v| v >= write.new_version)
+    })
+}
+
+/// Whether `written` and `current` both dropped a chunk of `base` (each
+/// rewrote it into a chunk of its own)
+fn replaced_by_both(base: &[String], written: &[String], current: &[String]) -> bool {
+    let written: HashSet<&str> = written.iter().map(String::as_str).collect();
+    let current: HashSet<&str> = current.iter().map(String::as_str).collect();
+    base.iter().any(|hash| !written.contains(hash.as_str()) && !current.contains(hash.as_str()))
+}
+
+/// Replay the change from `base` to `written` on top of `current`:
+/// chunks `written` dropped from `base` are dropped from `current`, and
+/// chunks it added are appended
+fn rebase_chunks(base: &[String], written: &[String], current: Vec<String>) -> Vec<String> {
+    let base: HashSet<&str> = base.iter().map(String::as_str).collect();
+    let written_set: HashSet<&str> = written.iter().map(String::as_str).collect();
+
+    let mut rebased: Vec<String> = current.into_iter()
+        .filter(|hash| written_set.contains(hash.as_str()) || !base.contains(hash.as_str()))
+        .collect();
+    let present: HashSet<String> = rebased.iter().cloned().collect();
+    rebased.extend(
+        written.iter()
+            .filter(|hash| !base.contains(hash.as_str()) && !present.contains(*hash))
+            .cloned(),
+    );
+    rebased
 }
 
 #[cfg(test)]
@@ -596,6 +1914,13 @@ mod tests {
         (manager, temp_dir)
     }
 
+    fn create_test_manager_with_epochs(config: EpochConfig) -> (TransactionManager, TempDir) {
+        let temp_dir = TempDir::new().unwrap();
+        let catalog = Arc::new(FileCatalog::new(temp_dir.path().join("catalog")).unwrap());
+        let manager = TransactionManager::with_config(temp_dir.path(), catalog, None, config).unwrap();
+        (manager, temp_dir)
+    }
+
     fn create_test_manager_with_branches() -> (TransactionManager, Arc<BranchManager>, TempDir) {
         let temp_dir = TempDir::new().unwrap();
         let catalog_path = temp_dir.path().join("catalog");
@@ -658,8 +1983,186 @@ mod tests {
         assert!(tx.is_committed());
         assert!(tx.committed_at.is_some());
 
-        // Active count should be 0
-        assert_eq!(manager.active_count().unwrap(), 0);
+        // Active count should be 0
+        assert_eq!(manager.active_count().unwrap(), 0);
+    }
+
+    #[test]
+    fn test_savepoints_roll_back_writes_and_reads() {
+        let (manager, _temp) = create_test_manager();
+
+        let tx_id = manager.begin(None).unwrap();
+        manager.add_write(tx_id, TableWrite::new("users", 1, vec!["a".to_string()])).unwrap();
+        manager.savepoint(tx_id, "before_orders").unwrap();
+
+        manager.add_write(tx_id, TableWrite::new("orders", 1, vec!["b".to_string()])).unwrap();
+        manager.record_read(tx_id, "products", 4).unwrap();
+        manager.savepoint(tx_id, "after_orders").unwrap();
+
+        // The log holds the write set as of the latest savepoint
+        let logged = manager.log().read_transaction(tx_id).unwrap();
+        assert_eq!(logged.written_tables(), vec!["users", "orders"]);
+        assert_eq!(logged.savepoint_names(), vec!["before_orders", "after_orders"]);
+
+        manager.rollback_to_savepoint(tx_id, "before_orders").unwrap();
+        let tx = manager.get_transaction(tx_id).unwrap();
+        assert_eq!(tx.written_tables(), vec!["users"]);
+        assert!(!tx.read_snapshot.contains_key("products"));
+        assert_eq!(tx.savepoint_names(), vec!["before_orders"]);
+        assert_eq!(manager.log().read_transaction(tx_id).unwrap().written_tables(), vec!["users"]);
+
+        let err = manager.rollback_to_savepoint(tx_id, "after_orders").unwrap_err();
+        assert!(matches!(err, TransactionError::SavepointNotFound { .. }));
+
+        manager.release_savepoint(tx_id, "before_orders").unwrap();
+        assert!(manager.get_transaction(tx_id).unwrap().savepoints.is_empty());
+
+        manager.savepoint(tx_id, "final").unwrap();
+        manager.commit(tx_id).unwrap();
+        let committed = manager.log().read_transaction(tx_id).unwrap();
+        assert!(committed.savepoints.is_empty());
+        assert_eq!(committed.written_tables(), vec!["users"]);
+    }
+
+    #[test]
+    fn test_metadata_reaches_changelog() {
+        use crate::changelog::ChangelogQuery;
+
+        let (manager, _temp) = create_test_manager();
+
+        let tx_id = manager.begin(None).unwrap();
+        manager.set_metadata(tx_id, "author", "alice").unwrap();
+        manager.add_write(tx_id, TableWrite::new("users", 1, vec!["a".to_string()])).unwrap();
+        manager.commit(tx_id).unwrap();
+
+        let entries = manager.get_changelog(ChangelogQuery::default()).unwrap();
+        assert_eq!(entries[0].metadata.get("author").map(String::as_str), Some("alice"));
+
+        let err = manager.set_metadata(tx_id, "author", "bob").unwrap_err();
+        assert!(matches!(err, TransactionError::TransactionNotFound(_) | TransactionError::TransactionNotActive(_)));
+    }
+
+    #[test]
+    fn test_run_with_retry_retries_conflicts() {
+        let (manager, _temp) = create_test_manager();
+        let policy = RetryPolicy::new().with_base_delay(Duration::from_millis(1)).with_max_attempts(3);
+        let next_version = |tx: TxId| {
+            let snapshot = manager.get_transaction(tx).unwrap().read_snapshot;
+            snapshot.get("users").map_or(1, |v| v + 1)
+        };
+
+        // A competing transaction that began earlier commits during the
+        // first attempt only
+        let competitor = manager.begin(None).unwrap();
+        let mut attempts = Vec::new();
+        let version = manager
+            .run_with_retry(None, &policy, |tx| {
+                let version = next_version(tx);
+                attempts.push(tx);
+                if attempts.len() == 1 {
+                    manager.add_write(competitor, TableWrite::new("users", version, vec!["b".to_string()]))?;
+                    manager.commit(competitor)?;
+                }
+                manager.add_write(tx, TableWrite::new("users", version, vec![format!("a{}", version)]))?;
+                Ok(version)
+            })
+            .unwrap();
+        assert_eq!(version, 2);
+        assert_eq!(attempts.len(), 2);
+        assert!(matches!(manager.get_transaction(attempts[0]).unwrap().status, TransactionStatus::Aborted { .. }));
+        assert_eq!(manager.catalog().get_version("users", None).unwrap().version, 2);
+
+        // Every attempt conflicts
+        let mut runs = 0;
+        let err = manager
+            .run_with_retry(None, &policy, |_| -> Result<(), _> {
+                runs += 1;
+                Err(TransactionError::write_conflict(vec!["users".to_string()]))
+            })
+            .unwrap_err();
+        assert!(matches!(err, TransactionError::RetriesExhausted { attempts: 3, ref source } if source.is_conflict()));
+        assert_eq!(runs, 3);
+
+        // Other errors are not retried
+        let mut runs = 0;
+        let err = manager
+            .run_with_retry(None, &policy, |_| -> Result<(), _> {
+                runs += 1;
+                Err(TransactionError::InvalidConfig("bad".to_string()))
+            })
+            .unwrap_err();
+        assert!(matches!(err, TransactionError::InvalidConfig(_)));
+        assert_eq!(runs, 1);
+        assert_eq!(manager.active_count().unwrap(), 0);
+    }
+
+    #[test]
+    fn test_hooks_see_commits_aborts_and_conflicts() {
+        let (manager, _temp) = create_test_manager();
+        let events: Arc<Mutex<Vec<String>>> = Arc::new(Mutex::new(Vec::new()));
+
+        let seen = Arc::clone(&events);
+        let commit_hook = manager.on_commit(move |tx, changes| {
+            let change = &changes[0];
+            seen.lock().unwrap().push(format!("commit {} {:?}->{}", tx.tx_id, change.old_version, change.new_version));
+        });
+        let seen = Arc::clone(&events);
+        manager.on_abort(move |tx, changes| {
+            seen.lock().unwrap().push(format!("abort {} {} write(s)", tx.tx_id, changes.len()));
+        });
+        let seen = Arc::clone(&events);
+        manager.on_conflict(move |tx, changes| {
+            assert!(tx.is_active());
+            seen.lock().unwrap().push(format!("conflict {} {}", tx.tx_id, changes[0].table_name));
+        });
+
+        let tx1 = manager.begin(None).unwrap();
+        manager.add_write(tx1, TableWrite::new("users", 1, vec!["a".to_string()])).unwrap();
+        let tx2 = manager.begin(None).unwrap();
+        manager.add_write(tx2, TableWrite::new("users", 1, vec!["b".to_string()])).unwrap();
+        manager.commit(tx1).unwrap();
+        assert!(manager.commit(tx2).unwrap_err().is_conflict());
+        manager.abort(tx2, "lost the race").unwrap();
+
+        let tx3 = manager.begin(None).unwrap();
+        manager.add_write(tx3, TableWrite::new("users", 2, vec!["c".to_string()])).unwrap();
+        manager.commit(tx3).unwrap();
+
+        assert!(manager.remove_hook(commit_hook));
+        assert!(!manager.remove_hook(commit_hook));
+        let tx4 = manager.begin(None).unwrap();
+        manager.add_write(tx4, TableWrite::new("users", 3, vec!["d".to_string()])).unwrap();
+        manager.commit(tx4).unwrap();
+
+        assert_eq!(
+            *events.lock().unwrap(),
+            vec![
+                format!("commit {} None->1", tx1),
+                format!("conflict {} users", tx2),
+                format!("abort {} 1 write(s)", tx2),
+                format!("commit {} Some(1)->2", tx3),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_failing_hook_does_not_fail_commit() {
+        let (manager, _temp) = create_test_manager();
+        let later_hook_runs = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+
+        manager.on_commit(|_, _| panic!("hook failed"));
+        let runs = Arc::clone(&later_hook_runs);
+        manager.on_commit(move |_, _| {
+            runs.fetch_add(1, Ordering::SeqCst);
+        });
+
+        let tx_id = manager.begin(None).unwrap();
+        manager.add_write(tx_id, TableWrite::new("users", 1, vec!["a".to_string()])).unwrap();
+        manager.commit(tx_id).unwrap();
+
+        assert!(manager.get_transaction(tx_id).unwrap().is_committed());
+        assert_eq!(later_hook_runs.load(Ordering::SeqCst), 1);
+        assert!(manager.explain_commit(tx_id).unwrap().is_some());
     }
 
     #[test]
@@ -819,6 +2322,43 @@ mod tests {
         assert_eq!(version, Some(1));
     }
 
+    #[test]
+    fn test_commit_records_one_repo_snapshot() {
+        let (manager, branches, _temp) = create_test_manager_with_branches();
+        let before = branches.latest_snapshot("main").unwrap().unwrap().id;
+
+        let tx_id = manager.begin(None).unwrap();
+        manager.add_write(tx_id, TableWrite::new("users", 1, vec!["a".to_string()])).unwrap();
+        manager.add_write(tx_id, TableWrite::new("orders", 1, vec!["b".to_string()])).unwrap();
+        manager.commit(tx_id).unwrap();
+
+        // Both tables land under a single snapshot id
+        let snapshot = branches.latest_snapshot("main").unwrap().unwrap();
+        assert_eq!(snapshot.id, before + 1);
+        assert_eq!(snapshot.versions, HashMap::from([("users".to_string(), 1), ("orders".to_string(), 1)]));
+
+        // ...and one history entry naming the transaction
+        let history = branches.history("main", Some(1)).unwrap();
+        assert_eq!(history[0].id, snapshot.id);
+        assert_eq!(history[0].tx_id, Some(tx_id));
+        assert_eq!(history[0].changes.len(), 2);
+    }
+
+    #[test]
+    fn test_commit_to_immutable_branch_rejected() {
+        let (manager, branches, _temp) = create_test_manager_with_branches();
+        branches.set_immutable("main", true).unwrap();
+
+        let tx_id = manager.begin(None).unwrap();
+        manager.add_write(tx_id, TableWrite::new("users", 1, vec!["chunk1".to_string()])).unwrap();
+
+        let err = manager.commit(tx_id).unwrap_err();
+        assert!(matches!(err, TransactionError::BranchError(ref msg) if msg.contains("immutable")));
+        // Nothing reached the catalog
+        assert!(manager.catalog.list_tables().unwrap().is_empty());
+        assert_eq!(branches.get_table_version("main", "users").unwrap(), None);
+    }
+
     #[test]
     fn test_transaction_persistence() {
         let temp_dir = TempDir::new().unwrap();
@@ -900,4 +2440,824 @@ mod tests {
         );
         assert!(is_conflict, "Expected conflict error, got: {:?}", err);
     }
+
+    /// Two blind writers of `users` (no reads, so snapshot validation
+    /// cannot see the overlap); the first commits and is evicted before
+    /// the second commits
+    fn commit_after_eviction(window: ConflictWindow) -> Result<(), TransactionError> {
+        let (mut manager, _temp) = create_test_manager();
+        manager.set_conflict_window(window);
+
+        let tx1 = manager.begin(None).unwrap();
+        let tx2 = manager.begin(None).unwrap();
+        manager.add_write(tx1, TableWrite::new("users", 1, vec!["chunk1".to_string()])).unwrap();
+        manager.add_write(tx2, TableWrite::new("users", 1, vec!["chunk2".to_string()])).unwrap();
+        manager.commit(tx1).unwrap();
+        manager.clear_recent_committed().unwrap();
+
+        manager.commit(tx2)
+    }
+
+    #[test]
+    fn test_conflict_window_reads_evicted_commits_from_log() {
+        assert_eq!(ConflictWindow::default(), ConflictWindow::Memory);
+        assert!(commit_after_eviction(ConflictWindow::Memory).is_ok());
+
+        for window in [ConflictWindow::Lifetime, ConflictWindow::Epochs(0), ConflictWindow::Seconds(3600)] {
+            let err = commit_after_eviction(window).unwrap_err();
+            assert!(matches!(err, TransactionError::WriteConflict(ref t) if t == &["users"]), "{window}: {err:?}");
+        }
+    }
+
+    #[test]
+    fn test_conflict_window_ignores_commits_before_begin() {
+        let (mut manager, _temp) = create_test_manager();
+        manager.set_conflict_window(ConflictWindow::Lifetime);
+
+        let tx1 = manager.begin(None).unwrap();
+        manager.add_write(tx1, TableWrite::new("users", 1, vec!["chunk1".to_string()])).unwrap();
+        manager.commit(tx1).unwrap();
+        manager.clear_recent_committed().unwrap();
+
+        // Began after tx1 committed: its write is in the snapshot
+        let tx2 = manager.begin(None).unwrap();
+        manager.add_write(tx2, TableWrite::new("users", 2, vec!["chunk2".to_string()])).unwrap();
+        manager.commit(tx2).unwrap();
+        assert_eq!(manager.conflict_window(), ConflictWindow::Lifetime);
+    }
+
+    #[test]
+    fn test_read_snapshot() {
+        let (manager, _temp) = create_test_manager();
+
+        let tx_id = manager.begin(None).unwrap();
+        manager.add_write(tx_id, TableWrite::new("users", 1, vec!["u1".to_string()])).unwrap();
+        manager.add_write(tx_id, TableWrite::new("orders", 1, vec!["o1".to_string(), "o2".to_string()])).unwrap();
+        manager.commit(tx_id).unwrap();
+
+        let snapshot = manager.read_snapshot(None).unwrap();
+        assert_eq!(snapshot.branch, "main");
+        assert_eq!(snapshot.tables.len(), 2);
+        assert_eq!(snapshot.get("users").unwrap().version, 1);
+        assert_eq!(snapshot.get("orders").unwrap().chunk_hashes, vec!["o1", "o2"]);
+
+        // No transaction is opened or logged
+        assert_eq!(manager.active_count().unwrap(), 0);
+        assert_eq!(manager.latest_tx_id().unwrap(), Some(tx_id));
+    }
+
+    #[test]
+    fn test_read_snapshot_follows_branch_heads() {
+        let (manager, branches, _temp) = create_test_manager_with_branches();
+
+        let tx1 = manager.begin(None).unwrap();
+        manager.add_write(tx1, TableWrite::new("users", 1, vec!["v1".to_string()])).unwrap();
+        manager.commit(tx1).unwrap();
+
+        branches.create("feature", None, None).unwrap();
+        manager.clear_recent_committed().unwrap();
+
+        let tx2 = manager.begin(None).unwrap();
+        manager.add_write(tx2, TableWrite::new("users", 2, vec!["v2".to_string()])).unwrap();
+        manager.commit(tx2).unwrap();
+
+        let main = manager.read_snapshot(None).unwrap();
+        let feature = manager.read_snapshot(Some("feature")).unwrap();
+
+        assert_eq!(main.versions().get("users"), Some(&2));
+        assert_eq!(feature.versions().get("users"), Some(&1));
+        assert_eq!(feature.get("users").unwrap().chunk_hashes, vec!["v1"]);
+    }
+
+    #[test]
+    fn test_transactions_share_published_snapshot() {
+        let (manager, branches, _temp) = create_test_manager_with_branches();
+
+        let tx1 = manager.begin(None).unwrap();
+        manager.add_write(tx1, TableWrite::new("users", 1, vec!["v1".to_string()])).unwrap();
+        manager.commit(tx1).unwrap();
+
+        // Begun on the same head: one shared allocation
+        let a = manager.begin(None).unwrap();
+        let b = manager.begin(None).unwrap();
+        let snap_a = manager.get_transaction(a).unwrap().read_snapshot;
+        let snap_b = manager.get_transaction(b).unwrap().read_snapshot;
+        assert!(snap_a.ptr_eq(&snap_b));
+        assert_eq!(snap_a.get("users"), Some(&1));
+
+        // An extra read copies only that transaction's snapshot
+        manager.record_read(b, "orders", 7).unwrap();
+        let snap_b = manager.get_transaction(b).unwrap().read_snapshot;
+        assert!(!snap_a.ptr_eq(&snap_b));
+        assert_eq!(manager.get_transaction(a).unwrap().read_snapshot.get("orders"), None);
+
+        // A commit publishes the next snapshot; later transactions see it
+        manager.clear_recent_committed().unwrap();
+        manager.add_write(a, TableWrite::new("users", 2, vec!["v2".to_string()])).unwrap();
+        manager.commit(a).unwrap();
+        let c = manager.begin(None).unwrap();
+        let d = manager.begin(None).unwrap();
+        let snap_c = manager.get_transaction(c).unwrap().read_snapshot;
+        assert_eq!(snap_c.get("users"), Some(&2));
+        assert!(snap_c.ptr_eq(&manager.get_transaction(d).unwrap().read_snapshot));
+
+        // Heads changed outside the manager are picked up
+        branches.update_head("main", "users", 1).unwrap();
+        let e = manager.begin(None).unwrap();
+        let snap_e = manager.get_transaction(e).unwrap().read_snapshot;
+        assert_eq!(snap_e.get("users"), Some(&1));
+
+        // ... and then shared while the branch file stays unchanged
+        let f = manager.begin(None).unwrap();
+        assert!(snap_e.ptr_eq(&manager.get_transaction(f).unwrap().read_snapshot));
+    }
+
+    #[test]
+    fn test_drop_table_and_purge_keeps_delta_bases() {
+        let (manager, temp) = create_test_manager();
+        let store = ChunkStore::new(temp.path().join("chunks")).unwrap();
+        let v1: Vec<u8> = (0..20_000u32).map(|i| (i.wrapping_mul(2654435761) >> 24) as u8).collect();
+        let mut v2 = v1.clone();
+        v2[100] ^= 0xff;
+        let base = store.put(&v1).unwrap();
+        let own = store.put(b"orders only").unwrap();
+        let delta = store.put_delta(&v2, &base).unwrap();
+
+        // users' only chunk is stored as a delta of one of orders' chunks
+        let tx = manager.begin(None).unwrap();
+        manager.add_write(tx, TableWrite::new("orders", 1, vec![base.clone(), own.clone()])).unwrap();
+        manager.add_write(tx, TableWrite::new("users", 1, vec![delta.clone()])).unwrap();
+        manager.commit(tx).unwrap();
+
+        let (dropped, report) = manager.drop_table_and_purge("orders", &store, Duration::ZERO).unwrap();
+        assert_eq!(dropped[0].chunk_hashes, vec![base.clone(), own.clone()]);
+        assert_eq!(report.chunks_scanned, 2);
+        assert_eq!(report.chunks_delta_bases, 1);
+        assert_eq!(report.collectable, vec![own.clone()]);
+        assert!(!store.exists(&own).unwrap());
+        assert!(store.exists(&base).unwrap());
+        assert_eq!(store.get(&delta).unwrap(), v2);
+    }
+
+    #[test]
+    fn test_drop_and_rename_table_update_branches_and_changelog() {
+        use crate::changelog::ChangelogQuery;
+
+        let (manager, branches, _temp) = create_test_manager_with_branches();
+        let tx = manager.begin(None).unwrap();
+        manager.add_write(tx, TableWrite::new("users", 1, vec!["u1".to_string()])).unwrap();
+        manager.add_write(tx, TableWrite::new("orders", 1, vec!["o1".to_string()])).unwrap();
+        manager.commit(tx).unwrap();
+        branches.create("release", None, None).unwrap();
+        branches.set_immutable("release", true).unwrap();
+
+        // A frozen branch still refers to the tables
+        assert!(matches!(manager.drop_table("orders"), Err(TransactionError::BranchError(_))));
+        assert!(manager.catalog.table_exists("orders").unwrap());
+        branches.set_immutable("release", false).unwrap();
+
+        assert_eq!(manager.rename_table("users", "customers").unwrap(), 1);
+        let dropped = manager.drop_table("orders").unwrap();
+        assert_eq!(dropped[0].chunk_hashes, vec!["o1"]);
+
+        for name in ["main", "release"] {
+            let branch = branches.get(name).unwrap();
+            assert_eq!(branch.head, HashMap::from([("customers".to_string(), 1)]));
+        }
+        assert_eq!(manager.catalog.list_tables().unwrap(), vec!["customers"]);
+
+        let entries = manager.get_changelog(ChangelogQuery::default()).unwrap();
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[1].changes[0].renamed_to(), Some("customers"));
+        assert!(entries[2].changes[0].is_drop());
+        assert_eq!(entries[2].changes[0].old_version, Some(1));
+        assert_eq!(manager.current_epoch().unwrap().committed_count, 3);
+
+        // New transactions no longer see the old names
+        let tx = manager.begin(None).unwrap();
+        let snapshot = manager.get_transaction(tx).unwrap().read_snapshot;
+        assert_eq!(*snapshot, HashMap::from([("customers".to_string(), 1)]));
+    }
+
+    #[test]
+    fn test_merge_recorded_in_changelog() {
+        use crate::changelog::ChangelogQuery;
+
+        let (manager, branches, _temp) = create_test_manager_with_branches();
+        let tx = manager.begin(None).unwrap();
+        manager.add_write(tx, TableWrite::new("users", 1, vec!["u1".to_string()])).unwrap();
+        manager.commit(tx).unwrap();
+        branches.create("feature", None, None).unwrap();
+        let tx = manager.begin(Some("feature")).unwrap();
+        manager.add_write(tx, TableWrite::new("orders", 1, vec!["o1".to_string()])).unwrap();
+        manager.commit(tx).unwrap();
+
+        let merge = manager.merge("feature", "main", MergeStrategy::Strict).unwrap();
+        assert_eq!(merge.versions, HashMap::from([("orders".to_string(), 1)]));
+        assert_eq!(branches.get_table_version("main", "orders").unwrap(), Some(1));
+
+        let entries = manager.get_changelog(ChangelogQuery::new().on_branch("main")).unwrap();
+        let last = entries.last().unwrap();
+        assert!(last.is_branch_merge());
+        assert_eq!(last.merge.as_ref(), Some(&merge));
+        let change = &last.changes[0];
+        assert_eq!(change.merged_from(), Some("feature"));
+        assert_eq!((change.old_version, change.new_version), (None, 1));
+        assert_eq!(change.chunk_hashes, vec!["o1"]);
+        assert!(!entries[0].is_branch_merge());
+
+        // The branch history names the transaction
+        let commit = &branches.history("main", Some(1)).unwrap()[0];
+        assert!(commit.is_merge());
+        assert_eq!(commit.tx_id, Some(last.tx_id));
+    }
+
+    #[test]
+    fn test_seal_epoch() {
+        let (manager, _temp) = create_test_manager();
+
+        let tx1 = manager.begin(None).unwrap();
+        manager.add_write(tx1, TableWrite::new("users", 1, vec!["c1".to_string()])).unwrap();
+        manager.commit(tx1).unwrap();
+
+        let first = manager.current_epoch().unwrap();
+        assert_eq!(first.epoch_id, 1);
+        assert_eq!(first.transactions, vec![tx1]);
+        assert_eq!(first.committed_count, 1);
+
+        let sealed = manager.seal_epoch().unwrap();
+        assert_eq!(sealed.epoch_id, 1);
+        assert!(sealed.is_committed());
+        assert!(sealed.ended_at.is_some());
+
+        // New transactions land in the next epoch
+        let tx2 = manager.begin(None).unwrap();
+        assert_eq!(manager.get_transaction(tx2).unwrap().epoch_id, 2);
+
+        let epochs = manager.list_epochs().unwrap();
+        assert_eq!(epochs.len(), 2);
+        assert!(epochs[0].is_committed());
+        assert!(epochs[1].is_active());
+        assert_eq!(epochs[1].transactions, vec![tx2]);
+    }
+
+    #[test]
+    fn test_seal_epoch_with_active_transaction() {
+        let (manager, _temp) = create_test_manager();
+
+        let tx_id = manager.begin(None).unwrap();
+        let sealed = manager.seal_epoch().unwrap();
+        assert_eq!(sealed.status, EpochStatus::Committing);
+
+        // The straddling transaction still commits into its own epoch
+        manager.add_write(tx_id, TableWrite::new("users", 1, vec!["c1".to_string()])).unwrap();
+        manager.commit(tx_id).unwrap();
+
+        let epochs = manager.list_epochs().unwrap();
+        assert_eq!(epochs[0].committed_count, 1);
+        assert_eq!(manager.current_epoch().unwrap().epoch_id, 2);
+    }
+
+    #[test]
+    fn test_log_compaction_preserves_changelog() {
+        let (mut manager, _temp) = create_test_manager();
+        let commit = |manager: &TransactionManager, version: u64| {
+            let tx = manager.begin(None).unwrap();
+            manager.add_write(tx, TableWrite::new("users", version, vec![format!("c{}", version)])).unwrap();
+            manager.commit(tx).unwrap();
+            tx
+        };
+
+        let tx1 = commit(&manager, 1);
+        let aborted = manager.begin(None).unwrap();
+        manager.abort(aborted, "test").unwrap();
+        manager.seal_epoch().unwrap();
+        commit(&manager, 2);
+        manager.seal_epoch().unwrap();
+        let before = manager.get_changelog(crate::changelog::ChangelogQuery::default()).unwrap();
+
+        let report = manager.compact_log(1).unwrap();
+        assert_eq!(report.epochs, vec![1, 2]);
+        assert_eq!((report.transactions, report.dropped), (2, 1));
+        assert_eq!(manager.log().list_transactions_in_epoch(1).unwrap(), vec![tx1]);
+        assert_eq!(manager.get_changelog(crate::changelog::ChangelogQuery::default()).unwrap(), before);
+        assert_eq!(manager.get_transaction(tx1).unwrap().writes[0].new_version, 1);
+        assert!(matches!(manager.get_transaction(aborted), Err(TransactionError::TransactionNotFound(_))));
+        assert!(manager.verify_consistency().unwrap().is_empty());
+        assert!(manager.compact_log(1).unwrap().is_empty());
+
+        // Automatic compaction on seal skips the epoch of a pending transaction
+        manager.set_log_compaction(Some(1));
+        let pending = manager.begin(None).unwrap();
+        manager.seal_epoch().unwrap();
+        assert_eq!(manager.log().segments().unwrap().len(), 1);
+        manager.commit(pending).unwrap();
+        manager.seal_epoch().unwrap();
+        let segments = manager.log().segments().unwrap();
+        assert_eq!((segments[1].first_epoch, segments[1].last_epoch), (3, 4));
+        assert_eq!(manager.get_changelog(crate::changelog::ChangelogQuery::default()).unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_with_config_persists_epoch_config() {
+        let temp_dir = TempDir::new().unwrap();
+        let catalog = Arc::new(FileCatalog::new(temp_dir.path().join("catalog")).unwrap());
+
+        let manager = TransactionManager::with_config(
+            temp_dir.path(),
+            catalog.clone(),
+            None,
+            EpochConfig::low_latency(),
+        ).unwrap();
+        assert_eq!(manager.config().max_transactions, 100);
+        drop(manager);
+
+        // Reopening keeps the persisted config
+        let reopened = TransactionManager::new(temp_dir.path(), catalog, None).unwrap();
+        assert_eq!(reopened.config().max_transactions, 100);
+    }
+
+    #[test]
+    fn test_new_table_scoped_to_creating_branch() {
+        let (manager, branches, temp) = create_test_manager_with_branches();
+        let catalog = FileCatalog::new(temp.path().join("catalog")).unwrap();
+
+        branches.create("feature", None, None).unwrap();
+
+        let tx = manager.begin(Some("feature")).unwrap();
+        manager.add_write(tx, TableWrite::new("scratch", 1, vec!["c1".to_string()])).unwrap();
+        manager.commit(tx).unwrap();
+
+        assert_eq!(catalog.table_origin("scratch").unwrap(), Some("feature".to_string()));
+
+        let main = branches.get("main").unwrap();
+        let feature = branches.get("feature").unwrap();
+        assert!(catalog.list_tables_on_branch(&main).unwrap().is_empty());
+        assert_eq!(catalog.list_tables_on_branch(&feature).unwrap(), vec!["scratch"]);
+        assert!(matches!(
+            catalog.get_version_on_branch("scratch", None, &main),
+            Err(crate::catalog::CatalogError::TableNotFound(_))
+        ));
+
+        // Merging makes it visible on main
+        branches.merge_fast_forward("feature", "main", false).unwrap();
+        let main = branches.get("main").unwrap();
+        assert_eq!(catalog.list_tables_on_branch(&main).unwrap(), vec!["scratch"]);
+        assert_eq!(catalog.get_version_on_branch("scratch", None, &main).unwrap().version, 1);
+    }
+
+    /// `(id, count)` rows stored as a chunk of `store`
+    fn counter_chunk(store: &ChunkStore, rows: &[(i64, i64)]) -> String {
+        use crate::parquet::ParquetEncoder;
+        use arrow::array::Int64Array;
+        use arrow::datatypes::{DataType, Field, Schema};
+        use arrow::record_batch::RecordBatch;
+
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Int64, false),
+            Field::new("count", DataType::Int64, false),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema,
+            vec![
+                Arc::new(Int64Array::from_iter_values(rows.iter().map(|r| r.0))),
+                Arc::new(Int64Array::from_iter_values(rows.iter().map(|r| r.1))),
+            ],
+        )
+        .unwrap();
+        store.put(&ParquetEncoder::new().encode(&batch).unwrap()).unwrap()
+    }
+
+    /// The `(id, count)` rows of the latest version of `table`
+    fn counter_rows(manager: &TransactionManager, store: &ChunkStore, table: &str) -> Vec<(i64, i64)> {
+        use crate::parquet::ParquetDecoder;
+        use arrow::array::AsArray;
+        use arrow::datatypes::Int64Type;
+
+        let mut rows = Vec::new();
+        for hash in manager.catalog.get_version(table, None).unwrap().chunk_hashes {
+            let batch = ParquetDecoder::new().decode(&store.get(&hash).unwrap()).unwrap();
+            let (ids, counts) = (batch.column(0).as_primitive::<Int64Type>(), batch.column(1).as_primitive::<Int64Type>());
+            rows.extend((0..batch.num_rows()).map(|i| (ids.value(i), counts.value(i))));
+        }
+        rows.sort_unstable();
+        rows
+    }
+
+    #[test]
+    fn test_analyze_merges_conflict_free_tables() {
+        use crate::algebraic::{OpType, TableAlgebraicSchema};
+        use crate::changelog::ChangelogQuery;
+
+        let (mut manager, temp) = create_test_manager();
+        let store = Arc::new(ChunkStore::new(temp.path().join("chunks")).unwrap());
+        manager.set_merge_store(Arc::clone(&store));
+        let mut registry = AlgebraicSchemaRegistry::new();
+        registry.register(TableAlgebraicSchema::all_additive("counters"));
+        let mut settings = TableAlgebraicSchema::new("settings");
+        settings.set_default(OpType::GenericOverwrite);
+        registry.register(settings);
+        manager.set_schema_registry(Arc::new(registry));
+
+        let tx1 = manager.begin(None).unwrap();
+        let tx2 = manager.begin(None).unwrap();
+        let tx3 = manager.begin(None).unwrap();
+        for (tx, count) in [(tx1, 1), (tx2, 2), (tx3, 3)] {
+            let chunk = counter_chunk(&store, &[(1, count)]);
+            manager.add_write(tx, TableWrite::new("counters", 1, vec![chunk])).unwrap();
+        }
+        manager.add_write(tx1, TableWrite::new("settings", 1, vec!["s1".to_string()])).unwrap();
+        manager.add_write(tx3, TableWrite::new("settings", 1, vec!["s3".to_string()])).unwrap();
+        manager.commit(tx1).unwrap();
+
+        // Overwrite semantics cannot be merged; only that table is reported
+        let result = manager.commit_with(tx3, OnConflict::Analyze);
+        assert!(matches!(result, Err(TransactionError::WriteConflict(t)) if t == vec!["settings"]));
+
+        // Conflict-free table: merged with tx1's version
+        manager.commit_with(tx2, OnConflict::Analyze).unwrap();
+        assert_eq!(manager.catalog.get_version("counters", None).unwrap().version, 2);
+        assert_eq!(counter_rows(&manager, &store, "counters"), vec![(1, 3)]);
+
+        let entries = manager.get_changelog(ChangelogQuery::default()).unwrap();
+        let merged: Vec<_> = entries.iter().filter(|e| e.is_auto_merge()).collect();
+        assert_eq!(merged.len(), 1);
+        assert_eq!(merged[0].tx_id, tx2);
+        assert_eq!(merged[0].auto_merged, vec!["counters"]);
+        assert_eq!(merged[0].get_change("counters").unwrap().new_version, 2);
+    }
+
+    #[test]
+    fn test_commit_rejects_schema_change_without_migration() {
+        use crate::catalog::{SchemaMigration, TableSchema};
+        use arrow::datatypes::{DataType, Field, Schema};
+
+        let (manager, _temp) = create_test_manager();
+        let register = |ty: DataType| {
+            let schema = TableSchema::from_arrow(&Schema::new(vec![Field::new("id", ty, false)]));
+            manager.catalog.schemas().register(&schema).unwrap()
+        };
+        let (ints, strings) = (register(DataType::Int64), register(DataType::Utf8));
+
+        let tx = manager.begin(None).unwrap();
+        manager.add_write(tx, TableWrite::new("users", 1, vec!["c1".to_string()]).with_schema_hash(&ints)).unwrap();
+        manager.commit(tx).unwrap();
+
+        // Nothing the transaction writes lands when one schema change is refused
+        let tx = manager.begin(None).unwrap();
+        manager.add_write(tx, TableWrite::new("orders", 1, vec!["o1".to_string()])).unwrap();
+        manager.add_write(tx, TableWrite::new("users", 2, vec!["c2".to_string()]).with_schema_hash(&strings)).unwrap();
+        assert!(matches!(manager.commit(tx), Err(TransactionError::CatalogError(e)) if e.contains("schema migration")));
+        assert!(!manager.catalog.table_exists("orders").unwrap());
+
+        let tx = manager.begin(None).unwrap();
+        let migration = SchemaMigration::new(&ints, &strings);
+        manager
+            .add_write(
+                tx,
+                TableWrite::new("users", 2, vec!["c2".to_string()])
+                    .with_schema_hash(&strings)
+                    .with_schema_migration(&migration),
+            )
+            .unwrap();
+        manager.commit(tx).unwrap();
+        let history = manager.catalog.schema_history("users").unwrap();
+        assert_eq!(history[1].migration.as_ref(), Some(&migration));
+    }
+
+    #[test]
+    fn test_analyze_uses_versioned_annotations() {
+        use crate::algebraic::TableAlgebraicSchema;
+
+        let (mut manager, temp) = create_test_manager();
+        let store = Arc::new(ChunkStore::new(temp.path().join("chunks")).unwrap());
+        manager.set_merge_store(Arc::clone(&store));
+        let counters = TableAlgebraicSchema::all_additive("counters");
+        let tx = manager.begin(None).unwrap();
+        let chunk = counter_chunk(&store, &[(1, 0)]);
+        manager
+            .add_write(tx, TableWrite::new("counters", 1, vec![chunk]).with_algebraic_schema(Some(&counters)))
+            .unwrap();
+        manager.commit(tx).unwrap();
+
+        let tx1 = manager.begin(None).unwrap();
+        let tx2 = manager.begin(None).unwrap();
+        manager.add_write(tx1, TableWrite::new("counters", 2, vec![counter_chunk(&store, &[(1, 1)])])).unwrap();
+        manager.add_write(tx2, TableWrite::new("counters", 2, vec![counter_chunk(&store, &[(1, 2)])])).unwrap();
+        manager.commit(tx1).unwrap();
+
+        // v2 inherited the annotations, so the conflict merges without a registry
+        assert_eq!(manager.catalog.algebraic_schema("counters", Some(2)).unwrap(), Some(counters));
+        manager.commit_with(tx2, OnConflict::Analyze).unwrap();
+        assert_eq!(manager.get_transaction(tx2).unwrap().auto_merged, vec!["counters"]);
+        assert_eq!(counter_rows(&manager, &store, "counters"), vec![(1, 3)]);
+    }
+
+    #[test]
+    fn test_analyze_adds_concurrent_deltas() {
+        use crate::algebraic::TableAlgebraicSchema;
+
+        let (mut manager, temp) = create_test_manager();
+        let store = Arc::new(ChunkStore::new(temp.path().join("chunks")).unwrap());
+        let mut registry = AlgebraicSchemaRegistry::new();
+        registry.register(TableAlgebraicSchema::all_additive("counters"));
+        manager.set_schema_registry(Arc::new(registry));
+
+        let setup = manager.begin(None).unwrap();
+        let chunk = counter_chunk(&store, &[(1, 10), (2, 5)]);
+        manager.add_write(setup, TableWrite::new("counters", 1, vec![chunk])).unwrap();
+        manager.commit(setup).unwrap();
+
+        // +3 on row 1, then +5 on row 1 and +2 on row 2, both from v1
+        let tx1 = manager.begin(None).unwrap();
+        let tx2 = manager.begin(None).unwrap();
+        manager.add_write(tx1, TableWrite::new("counters", 2, vec![counter_chunk(&store, &[(1, 13), (2, 5)])])).unwrap();
+        manager.add_write(tx2, TableWrite::new("counters", 2, vec![counter_chunk(&store, &[(1, 15), (2, 7)])])).unwrap();
+        manager.commit(tx1).unwrap();
+
+        // Without a merge store the rows cannot be combined
+        let result = manager.commit_with(tx2, OnConflict::Analyze);
+        assert!(matches!(result, Err(TransactionError::WriteConflict(t)) if t == vec!["counters"]));
+        assert_eq!(counter_rows(&manager, &store, "counters"), vec![(1, 13), (2, 5)]);
+
+        manager.set_merge_store(Arc::clone(&store));
+        manager.commit_with(tx2, OnConflict::Analyze).unwrap();
+        assert_eq!(manager.get_transaction(tx2).unwrap().auto_merged, vec!["counters"]);
+        assert_eq!(counter_rows(&manager, &store, "counters"), vec![(1, 18), (2, 7)]);
+    }
+
+    #[test]
+    fn test_analyze_without_schemas_behaves_like_abort() {
+        let (manager, _temp) = create_test_manager();
+
+        let tx1 = manager.begin(None).unwrap();
+        let tx2 = manager.begin(None).unwrap();
+        manager.add_write(tx1, TableWrite::new("users", 1, vec!["c1".to_string()])).unwrap();
+        manager.add_write(tx2, TableWrite::new("users", 1, vec!["c2".to_string()])).unwrap();
+        manager.commit(tx1).unwrap();
+
+        let result = manager.commit_with(tx2, OnConflict::Analyze);
+        assert!(matches!(result, Err(TransactionError::WriteConflict(_))));
+        assert!(manager.get_transaction(tx2).unwrap().auto_merged.is_empty());
+    }
+
+    #[test]
+    fn test_commit_scheduler_records_priorities() {
+        let (mut manager, _temp) = create_test_manager();
+        manager.set_commit_scheduler(Arc::new(CommitScheduler::new()));
+        let manager = Arc::new(manager);
+
+        let priorities = [CommitPriority::Bulk, CommitPriority::Interactive, CommitPriority::Interactive];
+        let handles: Vec<_> = priorities
+            .iter()
+            .enumerate()
+            .map(|(i, &priority)| {
+                let tx = manager.begin(None).unwrap();
+                manager.set_priority(tx, priority).unwrap();
+                manager.add_write(tx, TableWrite::new(format!("t{}", i), 1, vec![format!("c{}", i)])).unwrap();
+                let manager = Arc::clone(&manager);
+                std::thread::spawn(move || manager.commit(tx).map(|_| tx))
+            })
+            .collect();
+
+        for handle in handles {
+            let tx = handle.join().unwrap().unwrap();
+            assert!(manager.get_transaction(tx).unwrap().is_committed());
+        }
+
+        let stats = manager.commit_queue_stats().unwrap().unwrap();
+        assert_eq!(stats.get(CommitPriority::Interactive).admitted, 2);
+        assert_eq!(stats.get(CommitPriority::Bulk).admitted, 1);
+        assert_eq!(stats.waiting(), 0);
+        assert_eq!(manager.get_transaction(1).unwrap().priority, CommitPriority::Bulk);
+    }
+
+    #[test]
+    fn test_commit_timings_reach_sink_and_explain_commit() {
+        struct Collect(Mutex<Vec<CommitTimings>>);
+        impl CommitMetricsSink for Collect {
+            fn record_commit(&self, timings: &CommitTimings) {
+                self.0.lock().unwrap().push(timings.clone());
+            }
+        }
+
+        let (mut manager, _temp) = create_test_manager_with_epochs(EpochConfig::default());
+        let sink = Arc::new(Collect(Mutex::new(Vec::new())));
+        manager.set_metrics_sink(sink.clone());
+
+        let tx1 = manager.begin(None).unwrap();
+        let tx2 = manager.begin(None).unwrap();
+        manager.add_write(tx1, TableWrite::new("users", 1, vec!["a".to_string()])).unwrap();
+        manager.add_write(tx2, TableWrite::new("users", 1, vec!["b".to_string()])).unwrap();
+        manager.commit(tx1).unwrap();
+        assert!(manager.commit(tx2).is_err());
+
+        let committed = manager.explain_commit(tx1).unwrap().unwrap();
+        assert!(committed.succeeded());
+        for phase in CommitPhase::ALL {
+            assert!(committed.phases.iter().any(|(p, _)| *p == phase), "missing {}", phase);
+        }
+        let failed = manager.explain_commit(tx2).unwrap().unwrap();
+        assert_eq!(failed.phases.last().unwrap().0, CommitPhase::ConflictCheck);
+        assert!(failed.error.is_some());
+
+        let recorded = sink.0.lock().unwrap();
+        assert_eq!(recorded.iter().map(|t| t.tx_id).collect::<Vec<_>>(), vec![tx1, tx2]);
+        assert!(manager.explain_commit(999).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_commits_visible_at_begin_do_not_conflict() {
+        let (manager, _temp) = create_test_manager_with_epochs(EpochConfig::default());
+
+        for version in 1..=3 {
+            let tx = manager.begin(None).unwrap();
+            manager.add_write(tx, TableWrite::new("users", version, vec![format!("c{}", version)])).unwrap();
+            manager.commit(tx).unwrap();
+        }
+
+        // Still conflicts with a commit that landed after it began
+        let tx1 = manager.begin(None).unwrap();
+        let tx2 = manager.begin(None).unwrap();
+        manager.add_write(tx1, TableWrite::new("users", 4, vec!["a".to_string()])).unwrap();
+        manager.add_write(tx2, TableWrite::new("users", 4, vec!["b".to_string()])).unwrap();
+        manager.commit(tx1).unwrap();
+        assert!(matches!(manager.commit(tx2), Err(TransactionError::WriteConflict(_))));
+    }
+
+    #[test]
+    fn test_chunk_granularity_rebases_disjoint_writers() {
+        let (mut manager, _temp) = create_test_manager_with_epochs(EpochConfig::default());
+        let chunks = |names: &[&str]| names.iter().map(|c| c.to_string()).collect::<Vec<_>>();
+
+        let setup = manager.begin(None).unwrap();
+        manager.add_write(setup, TableWrite::new("events", 1, chunks(&["a", "b", "c"]))).unwrap();
+        manager.commit(setup).unwrap();
+        manager.clear_recent_committed().unwrap();
+
+        // Table granularity: any two writers of a table conflict
+        let tx1 = manager.begin(None).unwrap();
+        let tx2 = manager.begin(None).unwrap();
+        manager.add_write(tx1, TableWrite::new("events", 2, chunks(&["a1", "b", "c"]))).unwrap();
+        manager.add_write(tx2, TableWrite::new("events", 2, chunks(&["a", "b2", "c"]))).unwrap();
+        manager.commit(tx1).unwrap();
+        assert!(manager.commit(tx2).is_err());
+        manager.clear_recent_committed().unwrap();
+
+        manager.set_conflict_granularity(ConflictGranularity::Chunk);
+        assert_eq!(manager.conflict_granularity(), ConflictGranularity::Chunk);
+        let tx3 = manager.begin(None).unwrap();
+        let tx4 = manager.begin(None).unwrap();
+        let tx5 = manager.begin(None).unwrap();
+        manager.add_write(tx3, TableWrite::new("events", 3, chunks(&["b", "c", "d"]))).unwrap();
+        manager.add_write(tx4, TableWrite::new("events", 3, chunks(&["a1", "b4", "c", "e"]))).unwrap();
+        manager.add_write(tx5, TableWrite::new("events", 3, chunks(&["a5", "b", "c"]))).unwrap();
+        manager.commit(tx3).unwrap();
+        manager.commit(tx4).unwrap();
+
+        // tx4 replaced b and appended e on top of tx3 (which dropped a1, appended d)
+        let latest = manager.catalog.get_version("events", None).unwrap();
+        assert_eq!(latest.version, 4);
+        assert_eq!(latest.chunk_hashes, chunks(&["c", "d", "b4", "e"]));
+        assert!(matches!(
+            manager.get_transaction(tx4).unwrap().writes[0].granularity,
+            WriteGranularity::Chunks(ref replaced) if replaced == &chunks(&["b"])
+        ));
+
+        // tx5 replaced a1 as well, which tx3 already dropped
+        assert!(matches!(manager.commit(tx5), Err(TransactionError::WriteConflict(_))));
+    }
+
+    #[test]
+    fn test_row_granularity_allows_disjoint_keys() {
+        let (mut manager, _temp) = create_test_manager_with_epochs(EpochConfig::default());
+        manager.set_conflict_granularity(ConflictGranularity::Row);
+        let keyed = |chunks: &[&str], keys: &[i64]| {
+            TableWrite::new("users", 2, chunks.iter().map(|c| c.to_string()).collect())
+                .with_granularity(WriteGranularity::Keys {
+                    key_columns: vec!["id".to_string()],
+                    affected_keys: keys.iter().map(|&k| serde_json::json!(k)).collect(),
+                })
+        };
+
+        let setup = manager.begin(None).unwrap();
+        manager.add_write(setup, TableWrite::new("users", 1, vec!["base".to_string()])).unwrap();
+        manager.commit(setup).unwrap();
+        manager.clear_recent_committed().unwrap();
+
+        let tx1 = manager.begin(None).unwrap();
+        let tx2 = manager.begin(None).unwrap();
+        let tx3 = manager.begin(None).unwrap();
+        manager.add_write(tx1, keyed(&["base", "k1"], &[1])).unwrap();
+        manager.add_write(tx2, keyed(&["base", "k2"], &[2])).unwrap();
+        manager.add_write(tx3, keyed(&["base", "k1b"], &[1])).unwrap();
+        manager.commit(tx1).unwrap();
+        manager.commit(tx2).unwrap();
+        assert_eq!(
+            manager.catalog.get_version("users", None).unwrap().chunk_hashes,
+            vec!["base", "k1", "k2"]
+        );
+        assert!(matches!(manager.commit(tx3), Err(TransactionError::WriteConflict(_))));
+    }
+
+    #[test]
+    fn test_row_granularity_conflicts_on_shared_base_chunk() {
+        let (mut manager, _temp) = create_test_manager_with_epochs(EpochConfig::default());
+        manager.set_conflict_granularity(ConflictGranularity::Row);
+        let keyed = |chunk: &str, key: i64| {
+            TableWrite::new("users", 2, vec![chunk.to_string()])
+                .with_granularity(WriteGranularity::Keys {
+                    key_columns: vec!["id".to_string()],
+                    affected_keys: vec![serde_json::json!(key)],
+                })
+        };
+
+        // Both keys live in the table's only chunk
+        let setup = manager.begin(None).unwrap();
+        manager.add_write(setup, TableWrite::new("users", 1, vec!["base".to_string()])).unwrap();
+        manager.commit(setup).unwrap();
+        manager.clear_recent_committed().unwrap();
+
+        let tx1 = manager.begin(None).unwrap();
+        let tx2 = manager.begin(None).unwrap();
+        manager.add_write(tx1, keyed("base_k1", 1)).unwrap();
+        manager.add_write(tx2, keyed("base_k2", 2)).unwrap();
+        manager.commit(tx1).unwrap();
+
+        // Rebasing tx2 would keep both rewrites of "base"
+        assert!(matches!(manager.commit(tx2), Err(TransactionError::WriteConflict(_))));
+        assert_eq!(
+            manager.catalog.get_version("users", None).unwrap().chunk_hashes,
+            vec!["base_k1"]
+        );
+    }
+
+    #[test]
+    fn test_shutdown_drains_then_aborts_in_flight() {
+        let (manager, _temp) = create_test_manager();
+        let manager = Arc::new(manager);
+        let finishing = manager.begin(None).unwrap();
+        let stuck = manager.begin(None).unwrap();
+        manager.add_write(finishing, TableWrite::new("users", 1, vec!["c1".to_string()])).unwrap();
+
+        let committer = {
+            let manager = Arc::clone(&manager);
+            std::thread::spawn(move || {
+                std::thread::sleep(Duration::from_millis(30));
+                manager.commit(finishing)
+            })
+        };
+        let report = manager.shutdown(Duration::from_millis(500)).unwrap();
+        committer.join().unwrap().unwrap();
+
+        assert_eq!(report.drained, vec![finishing]);
+        assert_eq!(report.aborted, vec![stuck]);
+        assert!(!report.is_clean());
+        assert!(report.waited >= Duration::from_millis(500));
+        assert_eq!(manager.active_count().unwrap(), 0);
+        assert_eq!(
+            manager.log().read_transaction(stuck).unwrap().status,
+            TransactionStatus::Aborted { reason: "shutdown".to_string() }
+        );
+
+        assert!(manager.is_shut_down());
+        assert!(matches!(manager.begin(None), Err(TransactionError::ShuttingDown)));
+        assert!(matches!(manager.commit(stuck), Err(TransactionError::TransactionNotFound(_))));
+        let again = manager.shutdown(Duration::ZERO).unwrap();
+        assert!(again.drained.is_empty() && again.aborted.is_empty());
+    }
+
+    #[test]
+    fn test_read_only_transaction_pins_snapshot() {
+        let (manager, _temp) = create_test_manager();
+        let tx = manager.begin(None).unwrap();
+        manager.add_write(tx, TableWrite::new("users", 1, vec!["u1".to_string()])).unwrap();
+        manager.add_write(tx, TableWrite::new("orders", 1, vec!["o1".to_string()])).unwrap();
+        manager.commit(tx).unwrap();
+
+        let reader = manager.begin_read_only(None).unwrap();
+        assert_eq!(reader.branch(), "main");
+        assert_eq!(reader.tables(), vec!["orders", "users"]);
+
+        // Later commits neither move the snapshot nor conflict with it
+        let writer = manager.begin(None).unwrap();
+        manager.add_write(writer, TableWrite::new("users", 2, vec!["u2".to_string()])).unwrap();
+        manager.commit(writer).unwrap();
+        assert_eq!(reader.resolve("users").unwrap().chunk_hashes, vec!["u1"]);
+        assert_eq!(manager.begin_read_only(None).unwrap().version_of("users"), Some(2));
+        assert!(matches!(reader.resolve("events"), Err(crate::catalog::CatalogError::TableNotFound(_))));
+
+        // Readers are not logged and stay pinned until dropped
+        assert_eq!(manager.active_count().unwrap(), 0);
+        assert_eq!(manager.latest_tx_id().unwrap(), Some(writer));
+        let pins = manager.read_only_transactions();
+        assert_eq!(pins.len(), 1);
+        assert_eq!(pins[0].reader_id, reader.id());
+        assert_eq!(pins[0].versions.get("users"), Some(&1));
+        reader.close();
+        assert!(manager.read_only_transactions().is_empty());
+
+        manager.shutdown(Duration::ZERO).unwrap();
+        assert!(matches!(manager.begin_read_only(None), Err(TransactionError::ShuttingDown)));
+    }
 }
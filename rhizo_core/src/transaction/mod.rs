@@ -4,29 +4,68 @@
 //! - `TransactionManager` - Coordinates transactions with conflict detection
 //! - `TransactionRecord` - Complete transaction state and metadata
 //! - `TransactionLog` - Persistent storage for transaction records
+//! - `TransactionIndex` - Committed transactions by branch and table
+//! - `LogSegment` - Epochs of the log compacted into a single file
 //! - `EpochConfig` / `EpochMetadata` - Epoch-based organization
 //! - `ConflictDetector` - Pluggable conflict detection strategies
+//! - `SharedSnapshot` - Read snapshots shared between transactions
+//! - `ReadOnlyTransaction` - A pinned snapshot for readers, outside conflict detection
+//! - `CommitScheduler` - Optional priority ordering of waiting commits
+//! - `CommitTimings` - Per-phase timings of commits, for a `CommitMetricsSink`
+//! - `TransactionHook` - Callbacks on commit, abort and conflict
+//! - `RetryPolicy` - Backoff for `run_with_retry` on conflicts
+//! - `MicroBatcher` - Buffers small appends per branch into fewer commits
+//! - `ShutdownReport` - Transactions drained or aborted by a graceful shutdown
 //! - `CoordinationFreeManager` - Coordination-free mode for algebraic operations
 
 mod types;
 mod epoch;
 mod error;
 mod log;
+mod index;
+mod segment;
 mod conflict;
+mod hooks;
 mod manager;
+mod read_only;
 mod recovery;
+mod retry;
+mod shutdown;
 mod coordination_free;
+mod scheduler;
+mod snapshot;
+mod timings;
+mod batcher;
 
 pub use types::{
     TxId, EpochId, TransactionStatus, WriteGranularity,
-    TableWrite, TransactionRecord, TransactionMode,
+    TableWrite, WriteKind, TransactionRecord, Savepoint, TransactionMode, OnConflict, ReadSnapshot, SnapshotTable,
 };
 pub use epoch::{EpochConfig, EpochStatus, EpochMetadata};
 pub use error::TransactionError;
 pub use log::TransactionLog;
-pub use conflict::{Conflict, ConflictDetector, TableLevelConflictDetector};
+pub use index::{TransactionIndex, TxIndexEntry};
+pub use read_only::{PinnedSnapshot, ReadOnlyTransaction};
+pub use segment::{CompactionReport, LogSegment};
+pub use conflict::{
+    Conflict, ConflictDetector, ConflictGranularity, ConflictWindow, PartitionLevelConflictDetector, RowLevelConflictDetector,
+    TableLevelConflictDetector,
+};
+pub use hooks::{HookEvent, HookId, TransactionHook};
 pub use manager::TransactionManager;
 pub use recovery::{RecoveryReport, RecoveryManager};
+pub use retry::{RetryPolicy, DEFAULT_RETRY_BASE_DELAY, DEFAULT_RETRY_MAX_ATTEMPTS, DEFAULT_RETRY_MAX_DELAY};
+pub use shutdown::ShutdownReport;
 pub use coordination_free::{
     CoordinationFreeConfig, CoordinationFreeError, CoordinationFreeManager,
 };
+pub use scheduler::{
+    CommitPermit, CommitPriority, CommitQueueStats, CommitScheduler, PriorityQueueStats,
+    DEFAULT_MAX_BYPASS,
+};
+pub use snapshot::SharedSnapshot;
+pub use timings::{CommitMetricsSink, CommitPhase, CommitTimings, COMMIT_TIMINGS_RETAINED};
+pub use batcher::{
+    BatchAck, BatchTicket, BatchedWrite, MicroBatchConfig, MicroBatchStats, MicroBatcher,
+    DEFAULT_BATCH_MAX_DELAY, DEFAULT_BATCH_MAX_RETRIES, DEFAULT_BATCH_MAX_ROWS,
+};
@@ -0,0 +1,138 @@
+//! Read-only transactions with pinned snapshots.
+//!
+//! A read-only transaction fixes the table versions visible on a branch
+//! when it begins and resolves every table at that version, however many
+//! commits land meanwhile. It has no write set, so it is not written to the
+//! transaction log, never conflicts and never enters the commit critical
+//! section. While it is open its versions are pinned: `VersionPruner`
+//! keeps them like those of an active read-write transaction.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use super::snapshot::SharedSnapshot;
+use crate::catalog::{CatalogError, FileCatalog, TableVersion};
+
+/// The versions an open read-only transaction pins
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PinnedSnapshot {
+    /// Id of the read-only transaction (separate from write transaction ids)
+    pub reader_id: u64,
+    pub branch: String,
+    pub versions: SharedSnapshot,
+    /// Unix timestamp when the transaction began
+    pub began_at: i64,
+}
+
+/// Snapshots of the open read-only transactions of one manager
+#[derive(Debug, Default)]
+pub(crate) struct ReaderPins {
+    next_id: AtomicU64,
+    open: Mutex<HashMap<u64, PinnedSnapshot>>,
+}
+
+impl ReaderPins {
+    pub(crate) fn register(&self, branch: String, versions: SharedSnapshot) -> PinnedSnapshot {
+        let pin = PinnedSnapshot {
+            reader_id: self.next_id.fetch_add(1, Ordering::Relaxed) + 1,
+            branch,
+            versions,
+            began_at: super::manager::unix_now(),
+        };
+        self.lock().insert(pin.reader_id, pin.clone());
+        pin
+    }
+
+    pub(crate) fn list(&self) -> Vec<PinnedSnapshot> {
+        let mut pins: Vec<PinnedSnapshot> = self.lock().values().cloned().collect();
+        pins.sort_by_key(|p| p.reader_id);
+        pins
+    }
+
+    fn release(&self, reader_id: u64) {
+        self.lock().remove(&reader_id);
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<u64, PinnedSnapshot>> {
+        self.open.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// A consistent multi-table view of a branch, open until dropped.
+///
+/// # Example
+///
+/// ```ignore
+/// let reader = manager.begin_read_only(Some("main"))?;
+/// let orders = reader.resolve("orders")?;
+/// let users = reader.resolve("users")?; // same point in time as `orders`
+/// ```
+pub struct ReadOnlyTransaction {
+    pin: PinnedSnapshot,
+    catalog: Arc<FileCatalog>,
+    pins: Arc<ReaderPins>,
+}
+
+impl ReadOnlyTransaction {
+    pub(crate) fn new(pin: PinnedSnapshot, catalog: Arc<FileCatalog>, pins: Arc<ReaderPins>) -> Self {
+        Self { pin, catalog, pins }
+    }
+
+    pub fn id(&self) -> u64 {
+        self.pin.reader_id
+    }
+
+    pub fn branch(&self) -> &str {
+        &self.pin.branch
+    }
+
+    pub fn began_at(&self) -> i64 {
+        self.pin.began_at
+    }
+
+    /// Table name -> version pinned by this transaction
+    pub fn versions(&self) -> &SharedSnapshot {
+        &self.pin.versions
+    }
+
+    /// Pinned version of a table, if it was visible on the branch
+    pub fn version_of(&self, table_name: &str) -> Option<u64> {
+        self.pin.versions.get(table_name).copied()
+    }
+
+    /// Tables visible in the snapshot, sorted
+    pub fn tables(&self) -> Vec<&str> {
+        let mut tables: Vec<&str> = self.pin.versions.keys().map(String::as_str).collect();
+        tables.sort_unstable();
+        tables
+    }
+
+    /// The table's version as of the snapshot.
+    ///
+    /// # Errors
+    /// - `CatalogError::TableNotFound` if the table was not visible on the
+    ///   branch when the transaction began
+    pub fn resolve(&self, table_name: &str) -> Result<TableVersion, CatalogError> {
+        let version = self
+            .version_of(table_name)
+            .ok_or_else(|| CatalogError::TableNotFound(table_name.to_string()))?;
+        self.catalog.get_version(table_name, Some(version))
+    }
+
+    /// End the transaction, releasing its pinned versions (same as
+    /// dropping it)
+    pub fn close(self) {}
+}
+
+impl std::fmt::Debug for ReadOnlyTransaction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ReadOnlyTransaction").field("pin", &self.pin).finish_non_exhaustive()
+    }
+}
+
+impl Drop for ReadOnlyTransaction {
+    fn drop(&mut self) {
+        self.pins.release(self.pin.reader_id);
+    }
+}
@@ -263,8 +263,8 @@ pub fn verify_consistency(log: &TransactionLog) -> Result<Vec<String>, Transacti
 
         let tx_ids = log.list_transactions_in_epoch(*epoch_id)?;
 
-        // Verify transaction count matches
-        if tx_ids.len() != epoch_meta.transactions.len() {
+        // Verify transaction count matches (compaction drops aborted records)
+        if tx_ids.len() != epoch_meta.transactions.len() && !log.is_epoch_compacted(*epoch_id)? {
             issues.push(format!(
                 "Epoch {}: transaction count mismatch (files: {}, metadata: {})",
                 epoch_id,
@@ -302,6 +302,14 @@ pub fn verify_consistency(log: &TransactionLog) -> Result<Vec<String>, Transacti
         }
     }
 
+    // Regenerate a transaction index that disagrees with the log
+    let index_issues = log.verify_transaction_index()?;
+    if !index_issues.is_empty() {
+        issues.extend(index_issues);
+        log.rebuild_transaction_index()?;
+        issues.push("Transaction index regenerated".to_string());
+    }
+
     Ok(issues)
 }
 
@@ -459,6 +467,26 @@ mod tests {
         assert!(issues[0].contains("mismatch"));
     }
 
+    #[test]
+    fn test_verify_consistency_regenerates_transaction_index() {
+        let (log, temp) = create_test_log();
+        let mut meta = log.create_epoch(1).unwrap();
+        meta.add_transaction(1);
+        log.write_epoch_metadata(&meta).unwrap();
+
+        let mut tx = TransactionRecord::new(1, 1, "main".to_string());
+        tx.add_write(TableWrite::new("users", 1, vec![]));
+        tx.mark_committed();
+        log.write_transaction(&tx).unwrap();
+        log.transaction_index().unwrap();
+        std::fs::write(temp.path().join("_tx_index.jsonl"), "").unwrap();
+
+        let issues = verify_consistency(&log).unwrap();
+        assert!(issues.iter().any(|i| i.contains("missing tx 1")));
+        assert_eq!(issues.last().unwrap(), "Transaction index regenerated");
+        assert!(verify_consistency(&log).unwrap().is_empty());
+    }
+
     #[test]
     fn test_recovery_report() {
         let mut report = RecoveryReport::new();
@@ -0,0 +1,96 @@
+//! Backoff between attempts of an optimistic transaction.
+//!
+//! [`TransactionManager::run_with_retry`] runs a closure in a fresh
+//! transaction and, when the transaction loses a write or snapshot conflict,
+//! aborts it and runs the closure again on a new snapshot. A
+//! [`RetryPolicy`] bounds the number of attempts and spaces them with
+//! jittered exponential backoff, so writers contending for the same table
+//! do not keep colliding in lockstep.
+//!
+//! [`TransactionManager::run_with_retry`]: super::TransactionManager::run_with_retry
+
+use std::time::Duration;
+
+/// Default number of attempts (the first run included)
+pub const DEFAULT_RETRY_MAX_ATTEMPTS: u32 = 5;
+
+/// Default backoff before the second attempt
+pub const DEFAULT_RETRY_BASE_DELAY: Duration = Duration::from_millis(10);
+
+/// Default cap on the backoff between two attempts
+pub const DEFAULT_RETRY_MAX_DELAY: Duration = Duration::from_secs(1);
+
+/// How often and how far apart a conflicting transaction is retried
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Attempts before giving up, the first run included
+    pub max_attempts: u32,
+
+    /// Backoff after the first conflict; doubles with every further one
+    pub base_delay: Duration,
+
+    /// Cap on the backoff between two attempts
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: DEFAULT_RETRY_MAX_ATTEMPTS,
+            base_delay: DEFAULT_RETRY_BASE_DELAY,
+            max_delay: DEFAULT_RETRY_MAX_DELAY,
+        }
+    }
+}
+
+impl RetryPolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    pub fn with_base_delay(mut self, base_delay: Duration) -> Self {
+        self.base_delay = base_delay;
+        self
+    }
+
+    pub fn with_max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+
+    /// Backoff after attempt `attempt` (1-based) conflicted: the capped
+    /// exponential delay, scaled by a random factor between 0.5 and 1
+    pub fn delay(&self, attempt: u32) -> Duration {
+        let exponent = attempt.saturating_sub(1).min(31);
+        let delay = self.base_delay.saturating_mul(1 << exponent).min(self.max_delay);
+        let half = delay / 2;
+        let jitter = uuid::Uuid::new_v4().as_u128() as u64 % (half.as_nanos() as u64 + 1);
+        half + Duration::from_nanos(jitter)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_delay_doubles_within_jitter_and_caps() {
+        let policy = RetryPolicy::new()
+            .with_base_delay(Duration::from_millis(10))
+            .with_max_delay(Duration::from_millis(50));
+        for _ in 0..20 {
+            let first = policy.delay(1);
+            assert!(first >= Duration::from_millis(5) && first <= Duration::from_millis(10));
+            let second = policy.delay(2);
+            assert!(second >= Duration::from_millis(10) && second <= Duration::from_millis(20));
+            let capped = policy.delay(40);
+            assert!(capped >= Duration::from_millis(25) && capped <= Duration::from_millis(50));
+        }
+        assert_eq!(RetryPolicy::new().with_max_attempts(0).max_attempts, 1);
+    }
+}
@@ -0,0 +1,336 @@
+//! Priority-ordered admission to the commit critical section.
+//!
+//! By default commits enter the critical section in whatever order the
+//! commit mutex wakes them. With a `CommitScheduler` installed, waiting
+//! commits are admitted by priority instead, so short interactive commits
+//! are not stuck behind a queue of bulk loads.
+//!
+//! Priorities alone could starve bulk commits under a steady interactive
+//! load, so every waiter counts how many later arrivals were admitted ahead
+//! of it. Once that count reaches `max_bypass`, the waiter is admitted
+//! before any non-starved waiter, oldest first.
+
+use serde::{Deserialize, Serialize};
+use std::sync::{Condvar, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use super::error::TransactionError;
+
+/// Default number of times a waiting commit can be overtaken
+pub const DEFAULT_MAX_BYPASS: u32 = 16;
+
+/// Scheduling class of a commit
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+pub enum CommitPriority {
+    /// Large background loads; admitted last
+    Bulk,
+
+    /// Regular commits
+    #[default]
+    Normal,
+
+    /// Latency-sensitive commits; admitted first
+    Interactive,
+}
+
+impl CommitPriority {
+    /// All priorities, highest first
+    pub const ALL: [CommitPriority; 3] = [
+        CommitPriority::Interactive,
+        CommitPriority::Normal,
+        CommitPriority::Bulk,
+    ];
+
+    fn index(self) -> usize {
+        match self {
+            CommitPriority::Interactive => 0,
+            CommitPriority::Normal => 1,
+            CommitPriority::Bulk => 2,
+        }
+    }
+}
+
+impl std::fmt::Display for CommitPriority {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CommitPriority::Bulk => write!(f, "Bulk"),
+            CommitPriority::Normal => write!(f, "Normal"),
+            CommitPriority::Interactive => write!(f, "Interactive"),
+        }
+    }
+}
+
+/// Queue metrics for one priority
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PriorityQueueStats {
+    /// Commits currently waiting for admission
+    pub waiting: usize,
+
+    /// Commits admitted so far
+    pub admitted: u64,
+
+    /// Total time admitted commits spent waiting
+    pub total_wait: Duration,
+
+    /// Longest time a commit spent waiting
+    pub max_wait: Duration,
+}
+
+impl PriorityQueueStats {
+    /// Mean wait of admitted commits
+    pub fn mean_wait(&self) -> Duration {
+        if self.admitted == 0 {
+            Duration::ZERO
+        } else {
+            self.total_wait / self.admitted as u32
+        }
+    }
+}
+
+/// Snapshot of a scheduler's queue metrics
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CommitQueueStats {
+    /// Per-priority metrics, highest priority first (see [`CommitPriority::ALL`])
+    pub by_priority: [PriorityQueueStats; 3],
+
+    /// Commits admitted out of priority order because they hit `max_bypass`
+    pub starvation_grants: u64,
+}
+
+impl CommitQueueStats {
+    /// Metrics for one priority
+    pub fn get(&self, priority: CommitPriority) -> &PriorityQueueStats {
+        &self.by_priority[priority.index()]
+    }
+
+    /// Commits currently waiting, across priorities
+    pub fn waiting(&self) -> usize {
+        self.by_priority.iter().map(|s| s.waiting).sum()
+    }
+}
+
+struct Waiter {
+    ticket: u64,
+    priority: CommitPriority,
+    enqueued_at: Instant,
+    bypassed: u32,
+}
+
+#[derive(Default)]
+struct QueueState {
+    held: bool,
+    next_ticket: u64,
+    waiters: Vec<Waiter>,
+    stats: CommitQueueStats,
+}
+
+impl QueueState {
+    /// Index of the waiter to admit next
+    fn select(&self, max_bypass: u32) -> Option<usize> {
+        let starved = self
+            .waiters
+            .iter()
+            .enumerate()
+            .filter(|(_, w)| w.bypassed >= max_bypass)
+            .min_by_key(|(_, w)| w.ticket);
+        if let Some((i, _)) = starved {
+            return Some(i);
+        }
+        self.waiters
+            .iter()
+            .enumerate()
+            .max_by(|(_, a), (_, b)| a.priority.cmp(&b.priority).then(b.ticket.cmp(&a.ticket)))
+            .map(|(i, _)| i)
+    }
+}
+
+/// Admits commits to the critical section one at a time, highest priority
+/// first, with a bound on how often any commit can be overtaken.
+///
+/// # Example
+///
+/// ```ignore
+/// let mut manager = TransactionManager::new(path, catalog, None)?;
+/// manager.set_commit_scheduler(Arc::new(CommitScheduler::new()));
+///
+/// let tx = manager.begin(None)?;
+/// manager.set_priority(tx, CommitPriority::Interactive)?;
+/// manager.commit(tx)?;
+/// ```
+pub struct CommitScheduler {
+    max_bypass: u32,
+    state: Mutex<QueueState>,
+    admitted: Condvar,
+}
+
+impl Default for CommitScheduler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CommitScheduler {
+    pub fn new() -> Self {
+        Self::with_max_bypass(DEFAULT_MAX_BYPASS)
+    }
+
+    /// Scheduler where a waiting commit is overtaken at most `max_bypass`
+    /// times (0 makes admission strictly first-come, first-served)
+    pub fn with_max_bypass(max_bypass: u32) -> Self {
+        Self {
+            max_bypass,
+            state: Mutex::new(QueueState::default()),
+            admitted: Condvar::new(),
+        }
+    }
+
+    pub fn max_bypass(&self) -> u32 {
+        self.max_bypass
+    }
+
+    /// Wait until this commit is admitted. Admission lasts until the
+    /// returned permit is dropped.
+    pub fn acquire(&self, priority: CommitPriority) -> Result<CommitPermit<'_>, TransactionError> {
+        let mut state = self.lock()?;
+        let ticket = state.next_ticket;
+        state.next_ticket += 1;
+        state.waiters.push(Waiter {
+            ticket,
+            priority,
+            enqueued_at: Instant::now(),
+            bypassed: 0,
+        });
+        state.stats.by_priority[priority.index()].waiting += 1;
+
+        loop {
+            if !state.held {
+                let next = state.select(self.max_bypass).expect("this commit is waiting");
+                if state.waiters[next].ticket == ticket {
+                    let waiter = state.waiters.swap_remove(next);
+                    self.admit(&mut state, waiter);
+                    return Ok(CommitPermit { scheduler: self });
+                }
+            }
+            state = self
+                .admitted
+                .wait(state)
+                .map_err(|_| TransactionError::LockError("commit_scheduler".to_string()))?;
+        }
+    }
+
+    /// Current queue metrics
+    pub fn stats(&self) -> Result<CommitQueueStats, TransactionError> {
+        Ok(self.lock()?.stats.clone())
+    }
+
+    fn admit(&self, state: &mut QueueState, waiter: Waiter) {
+        state.held = true;
+        if self.max_bypass > 0 && waiter.bypassed >= self.max_bypass && !state.waiters.is_empty() {
+            state.stats.starvation_grants += 1;
+        }
+        for other in state.waiters.iter_mut().filter(|w| w.ticket < waiter.ticket) {
+            other.bypassed += 1;
+        }
+
+        let waited = waiter.enqueued_at.elapsed();
+        let stats = &mut state.stats.by_priority[waiter.priority.index()];
+        stats.waiting -= 1;
+        stats.admitted += 1;
+        stats.total_wait += waited;
+        stats.max_wait = stats.max_wait.max(waited);
+    }
+
+    fn release(&self) {
+        // A poisoned queue only means another waiter panicked; still release
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state.held = false;
+        drop(state);
+        self.admitted.notify_all();
+    }
+
+    fn lock(&self) -> Result<MutexGuard<'_, QueueState>, TransactionError> {
+        self.state
+            .lock()
+            .map_err(|_| TransactionError::LockError("commit_scheduler".to_string()))
+    }
+}
+
+/// Admission to the commit critical section; released on drop
+pub struct CommitPermit<'a> {
+    scheduler: &'a CommitScheduler,
+}
+
+impl Drop for CommitPermit<'_> {
+    fn drop(&mut self) {
+        self.scheduler.release();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::thread;
+
+    /// Queue `priorities` behind a held permit, then record admission order
+    fn admission_order(scheduler: Arc<CommitScheduler>, priorities: &[CommitPriority]) -> Vec<usize> {
+        let order = Arc::new(Mutex::new(Vec::new()));
+        let blocker = scheduler.acquire(CommitPriority::Normal).unwrap();
+
+        let handles: Vec<_> = priorities
+            .iter()
+            .enumerate()
+            .map(|(i, &priority)| {
+                let handle = {
+                    let scheduler = Arc::clone(&scheduler);
+                    let order = Arc::clone(&order);
+                    thread::spawn(move || {
+                        let _permit = scheduler.acquire(priority).unwrap();
+                        order.lock().unwrap().push(i);
+                    })
+                };
+                // Enqueue in index order
+                while scheduler.stats().unwrap().waiting() <= i {
+                    thread::yield_now();
+                }
+                handle
+            })
+            .collect();
+
+        drop(blocker);
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        Arc::try_unwrap(order).unwrap().into_inner().unwrap()
+    }
+
+    #[test]
+    fn test_higher_priority_admitted_first() {
+        use CommitPriority::*;
+        let scheduler = Arc::new(CommitScheduler::new());
+        let order = admission_order(Arc::clone(&scheduler), &[Bulk, Bulk, Normal, Interactive, Interactive]);
+        assert_eq!(order, vec![3, 4, 2, 0, 1]);
+
+        let stats = scheduler.stats().unwrap();
+        assert_eq!(stats.waiting(), 0);
+        assert_eq!(stats.get(Interactive).admitted, 2);
+        assert_eq!(stats.get(Normal).admitted, 2);
+        assert_eq!(stats.get(Bulk).admitted, 2);
+        assert!(stats.get(Bulk).max_wait >= stats.get(Interactive).max_wait);
+        assert_eq!(stats.starvation_grants, 0);
+    }
+
+    #[test]
+    fn test_bypass_limit_prevents_starvation() {
+        use CommitPriority::*;
+        let scheduler = Arc::new(CommitScheduler::with_max_bypass(2));
+        let order = admission_order(Arc::clone(&scheduler), &[Bulk, Interactive, Interactive, Interactive, Interactive]);
+        // The bulk commit is overtaken twice, then goes next
+        assert_eq!(order, vec![1, 2, 0, 3, 4]);
+        assert_eq!(scheduler.stats().unwrap().starvation_grants, 1);
+
+        let fifo = Arc::new(CommitScheduler::with_max_bypass(0));
+        assert_eq!(admission_order(fifo, &[Bulk, Interactive, Normal]), vec![0, 1, 2]);
+    }
+}
@@ -0,0 +1,99 @@
+//! Compacted segments of the transaction log.
+//!
+//! Every transaction is first written as its own `tx_*.json` file under its
+//! epoch directory, so a long-lived log holds one file per transaction ever
+//! run. Compaction rolls whole epochs older than a cutoff into a single
+//! segment file: the committed records, one JSON line each by transaction id.
+//! Aborted records are dropped. Epoch metadata and the committed index are
+//! left in place, so changelog queries, `read_transaction` and recovery see
+//! the same committed transactions before and after.
+//!
+//! ```text
+//! {base_path}/
+//! +-- _segments.json              # LogSegment per segment, oldest first
+//! +-- segments/
+//!     +-- 000001-000004.jsonl     # Committed records of epochs 1..=4
+//! ```
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use super::error::TransactionError;
+use super::types::{EpochId, TransactionRecord, TxId};
+
+/// One compacted run of epochs
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LogSegment {
+    /// First epoch rolled into the segment
+    pub first_epoch: EpochId,
+    /// Last epoch rolled into the segment (inclusive)
+    pub last_epoch: EpochId,
+    /// Committed transactions stored in the segment
+    pub transactions: usize,
+    /// Unix timestamp of the compaction
+    pub compacted_at: i64,
+}
+
+impl LogSegment {
+    /// Whether `epoch_id` was rolled into this segment
+    pub fn covers(&self, epoch_id: EpochId) -> bool {
+        (self.first_epoch..=self.last_epoch).contains(&epoch_id)
+    }
+
+    /// File name of the segment under the `segments` directory
+    pub(crate) fn file_name(&self) -> String {
+        format!("{:06}-{:06}.jsonl", self.first_epoch, self.last_epoch)
+    }
+}
+
+/// Outcome of a log compaction
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CompactionReport {
+    /// Epochs rolled into the new segment, in order
+    pub epochs: Vec<EpochId>,
+    /// Committed transactions written to the segment
+    pub transactions: usize,
+    /// Aborted transaction records dropped
+    pub dropped: usize,
+    /// The segment written, if any epoch was compacted
+    pub segment: Option<LogSegment>,
+}
+
+impl CompactionReport {
+    /// Whether compaction found nothing to do
+    pub fn is_empty(&self) -> bool {
+        self.epochs.is_empty()
+    }
+}
+
+/// A segment's records, loaded for lookups
+#[derive(Debug)]
+pub(crate) struct LoadedSegment {
+    pub(crate) segment: LogSegment,
+    pub(crate) records: BTreeMap<TxId, TransactionRecord>,
+}
+
+/// Serialize records as one JSON line each
+pub(crate) fn encode_records<'a>(
+    records: impl IntoIterator<Item = &'a TransactionRecord>,
+) -> Result<String, TransactionError> {
+    let mut content = String::new();
+    for record in records {
+        content.push_str(&serde_json::to_string(record)?);
+        content.push('\n');
+    }
+    Ok(content)
+}
+
+/// Read the records of a segment file
+pub(crate) fn read_records(path: &Path) -> Result<BTreeMap<TxId, TransactionRecord>, TransactionError> {
+    let mut records = BTreeMap::new();
+    for line in fs::read_to_string(path)?.lines().filter(|l| !l.trim().is_empty()) {
+        let record: TransactionRecord = serde_json::from_str(line)?;
+        records.insert(record.tx_id, record);
+    }
+    Ok(records)
+}
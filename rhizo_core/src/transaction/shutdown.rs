@@ -0,0 +1,25 @@
+//! Outcome of `TransactionManager::shutdown`.
+
+use std::time::Duration;
+
+use super::types::TxId;
+
+/// What happened to the transactions in flight when a manager shut down
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ShutdownReport {
+    /// Transactions that committed or aborted on their own within the
+    /// grace period
+    pub drained: Vec<TxId>,
+    /// Transactions still active when the grace period ran out, aborted
+    /// by the shutdown
+    pub aborted: Vec<TxId>,
+    /// How long the shutdown waited for in-flight transactions
+    pub waited: Duration,
+}
+
+impl ShutdownReport {
+    /// Whether every in-flight transaction finished on its own
+    pub fn is_clean(&self) -> bool {
+        self.aborted.is_empty()
+    }
+}
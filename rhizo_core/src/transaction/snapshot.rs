@@ -0,0 +1,116 @@
+//! Shared, immutable table-version snapshots.
+//!
+//! A transaction's read snapshot maps every table visible on its branch to
+//! a version. With many tables and many concurrent transactions, giving
+//! each transaction its own copy of that map dominates `begin()` cost and
+//! memory. `SharedSnapshot` is reference-counted instead: the manager
+//! publishes one snapshot per branch, every transaction that begins while
+//! it is current holds the same allocation, and a transaction only gets a
+//! private copy if it records an extra read (copy-on-write).
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::HashMap;
+use std::ops::Deref;
+use std::sync::Arc;
+
+/// Immutable table name -> version map, cheap to clone and share.
+///
+/// Dereferences to the underlying `HashMap` for lookups. Serializes as a
+/// plain map, so transaction log records are unchanged.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+pub struct SharedSnapshot(Arc<HashMap<String, u64>>);
+
+impl SharedSnapshot {
+    pub fn new(versions: HashMap<String, u64>) -> Self {
+        Self(Arc::new(versions))
+    }
+
+    /// Set a table's version, copying the map first if it is shared
+    pub fn insert(&mut self, table_name: impl Into<String>, version: u64) -> Option<u64> {
+        Arc::make_mut(&mut self.0).insert(table_name.into(), version)
+    }
+
+    /// A new snapshot with `updates` applied; `self` is unchanged
+    pub fn with_versions(&self, updates: impl IntoIterator<Item = (String, u64)>) -> Self {
+        let mut versions = (*self.0).clone();
+        versions.extend(updates);
+        Self::new(versions)
+    }
+
+    /// Whether both snapshots are the same allocation
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+
+    /// The underlying map (copied only if still shared)
+    pub fn into_map(self) -> HashMap<String, u64> {
+        Arc::try_unwrap(self.0).unwrap_or_else(|shared| (*shared).clone())
+    }
+}
+
+impl Deref for SharedSnapshot {
+    type Target = HashMap<String, u64>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl From<HashMap<String, u64>> for SharedSnapshot {
+    fn from(versions: HashMap<String, u64>) -> Self {
+        Self::new(versions)
+    }
+}
+
+impl FromIterator<(String, u64)> for SharedSnapshot {
+    fn from_iter<I: IntoIterator<Item = (String, u64)>>(iter: I) -> Self {
+        Self::new(iter.into_iter().collect())
+    }
+}
+
+impl<'a> IntoIterator for &'a SharedSnapshot {
+    type Item = (&'a String, &'a u64);
+    type IntoIter = std::collections::hash_map::Iter<'a, String, u64>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+impl Serialize for SharedSnapshot {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for SharedSnapshot {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        HashMap::deserialize(deserializer).map(Self::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_copy_on_write() {
+        let published = SharedSnapshot::new(HashMap::from([("users".to_string(), 3)]));
+        let mut tx_view = published.clone();
+        assert!(tx_view.ptr_eq(&published));
+
+        tx_view.insert("orders", 1);
+        assert!(!tx_view.ptr_eq(&published));
+        assert_eq!(published.get("orders"), None);
+        assert_eq!(tx_view.get("orders"), Some(&1));
+
+        let next = published.with_versions([("users".to_string(), 4)]);
+        assert_eq!(published.get("users"), Some(&3));
+        assert_eq!(next.get("users"), Some(&4));
+
+        let json = serde_json::to_string(&next).unwrap();
+        assert_eq!(json, r#"{"users":4}"#);
+        assert_eq!(serde_json::from_str::<SharedSnapshot>(&json).unwrap(), next);
+    }
+}
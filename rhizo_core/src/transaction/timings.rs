@@ -0,0 +1,163 @@
+//! Per-phase timings of transaction commits.
+//!
+//! `TransactionManager::commit` measures each phase of the commit path and
+//! hands the resulting [`CommitTimings`] to an optional
+//! [`CommitMetricsSink`]. The timings of recent commits are also kept in
+//! memory for `TransactionManager::explain_commit`, so a slow commit (for
+//! example on a networked filesystem) can be broken down after the fact.
+
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use super::types::TxId;
+
+/// Number of recent commits whose timings `explain_commit` can report
+pub const COMMIT_TIMINGS_RETAINED: usize = 256;
+
+/// A measured phase of the commit path, in execution order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CommitPhase {
+    /// Waiting for the commit scheduler and the commit lock
+    Queue,
+    /// Write conflicts with concurrent commits (and, with
+    /// `OnConflict::Analyze`, their resolution)
+    ConflictCheck,
+    /// Checking (or rebasing onto) tables changed since the snapshot
+    SnapshotValidation,
+    /// Committing new table versions to the catalog
+    CatalogWrites,
+    /// Moving branch heads and publishing read snapshots
+    BranchUpdates,
+    /// Writing the transaction record and epoch metadata
+    LogPersistence,
+}
+
+impl CommitPhase {
+    /// Every phase, in execution order
+    pub const ALL: [CommitPhase; 6] = [
+        CommitPhase::Queue,
+        CommitPhase::ConflictCheck,
+        CommitPhase::SnapshotValidation,
+        CommitPhase::CatalogWrites,
+        CommitPhase::BranchUpdates,
+        CommitPhase::LogPersistence,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CommitPhase::Queue => "queue",
+            CommitPhase::ConflictCheck => "conflict_check",
+            CommitPhase::SnapshotValidation => "snapshot_validation",
+            CommitPhase::CatalogWrites => "catalog_writes",
+            CommitPhase::BranchUpdates => "branch_updates",
+            CommitPhase::LogPersistence => "log_persistence",
+        }
+    }
+}
+
+impl fmt::Display for CommitPhase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Where the time of one commit attempt went
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommitTimings {
+    pub tx_id: TxId,
+    pub branch: String,
+    /// Phases reached, in execution order. A failed commit stops at the
+    /// phase that failed.
+    pub phases: Vec<(CommitPhase, Duration)>,
+    /// Wall time of the whole commit call
+    pub total: Duration,
+    /// Error message if the commit failed
+    pub error: Option<String>,
+}
+
+impl CommitTimings {
+    /// Time spent in `phase` (zero if it was not reached)
+    pub fn phase(&self, phase: CommitPhase) -> Duration {
+        self.phases
+            .iter()
+            .filter(|(p, _)| *p == phase)
+            .map(|(_, d)| *d)
+            .sum()
+    }
+
+    /// The phase that took longest
+    pub fn slowest_phase(&self) -> Option<CommitPhase> {
+        self.phases.iter().max_by_key(|(_, d)| *d).map(|(p, _)| *p)
+    }
+
+    pub fn succeeded(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// Receives the timings of every commit attempt.
+///
+/// Called on the committing thread after the commit lock is released;
+/// implementations should hand the timings off rather than block.
+pub trait CommitMetricsSink: Send + Sync {
+    fn record_commit(&self, timings: &CommitTimings);
+}
+
+/// Measures the phases of one commit
+pub(crate) struct CommitTimer {
+    started: Instant,
+    phases: Vec<(CommitPhase, Duration)>,
+}
+
+impl CommitTimer {
+    pub(crate) fn start() -> Self {
+        Self {
+            started: Instant::now(),
+            phases: Vec::with_capacity(CommitPhase::ALL.len()),
+        }
+    }
+
+    /// Run `f` as `phase`. The phase is recorded even if `f` fails.
+    pub(crate) fn measure<T>(&mut self, phase: CommitPhase, f: impl FnOnce() -> T) -> T {
+        let started = Instant::now();
+        let result = f();
+        self.phases.push((phase, started.elapsed()));
+        result
+    }
+
+    pub(crate) fn finish(self, tx_id: TxId, branch: String, error: Option<String>) -> CommitTimings {
+        CommitTimings {
+            tx_id,
+            branch,
+            phases: self.phases,
+            total: self.started.elapsed(),
+            error,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timer_records_phases_in_order() {
+        let mut timer = CommitTimer::start();
+        timer.measure(CommitPhase::Queue, || ());
+        let failed: Result<(), &str> = timer.measure(CommitPhase::ConflictCheck, || {
+            std::thread::sleep(Duration::from_millis(5));
+            Err("conflict")
+        });
+        let timings = timer.finish(7, "main".to_string(), failed.err().map(str::to_string));
+
+        assert_eq!(
+            timings.phases.iter().map(|(p, _)| *p).collect::<Vec<_>>(),
+            vec![CommitPhase::Queue, CommitPhase::ConflictCheck]
+        );
+        assert_eq!(timings.slowest_phase(), Some(CommitPhase::ConflictCheck));
+        assert!(timings.phase(CommitPhase::ConflictCheck) >= Duration::from_millis(5));
+        assert_eq!(timings.phase(CommitPhase::CatalogWrites), Duration::ZERO);
+        assert!(timings.total >= timings.phase(CommitPhase::ConflictCheck));
+        assert!(!timings.succeeded());
+    }
+}
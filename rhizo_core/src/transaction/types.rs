@@ -3,6 +3,13 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+use super::scheduler::CommitPriority;
+use crate::algebraic::TableAlgebraicSchema;
+use crate::branch::MergeRecord;
+use crate::catalog::version::{algebraic_schema_json, schema_migration_json};
+use crate::catalog::{SchemaMigration, ALGEBRAIC_SCHEMA_METADATA_KEY, SCHEMA_MIGRATION_METADATA_KEY};
+use super::snapshot::SharedSnapshot;
+
 /// Unique transaction identifier (monotonically increasing)
 pub type TxId = u64;
 
@@ -11,6 +18,7 @@ pub type EpochId = u64;
 
 /// Transaction status - designed for both single-node and distributed
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub enum TransactionStatus {
     /// Transaction is active and accepting operations
     Active,
@@ -42,6 +50,7 @@ impl std::fmt::Display for TransactionStatus {
 
 /// How granular is our conflict detection for a write
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub enum WriteGranularity {
     /// Entire table (Phase 5.0)
     #[default]
@@ -55,6 +64,9 @@ pub enum WriteGranularity {
         key_columns: Vec<String>,
         affected_keys: Vec<serde_json::Value>,
     },
+
+    /// Chunks of the base version that the write replaced or removed
+    Chunks(Vec<String>),
 }
 
 /// Transaction mode - determines commit behavior
@@ -78,6 +90,7 @@ pub enum WriteGranularity {
 /// let tx = manager.begin_coordination_free(None)?;
 /// ```
 #[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub enum TransactionMode {
     /// Traditional ACID transactions with conflict detection (default)
     #[default]
@@ -110,8 +123,72 @@ impl std::fmt::Display for TransactionMode {
     }
 }
 
+/// What `TransactionManager::commit_with` does when a commit conflicts
+/// with a concurrent one.
+///
+/// ```ignore
+/// manager.commit_with(tx_id, OnConflict::Analyze)?;
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OnConflict {
+    /// Fail with `WriteConflict` / `SnapshotConflict` (default)
+    #[default]
+    Abort,
+
+    /// Consult the manager's algebraic schemas: conflicts confined to
+    /// tables whose operations are all conflict-free (see `MergeAnalyzer`)
+    /// are merged by committing on top of the concurrent versions.
+    /// Any other conflict still fails.
+    ///
+    /// The new version holds this transaction's rows merged row by row
+    /// with the concurrent version (see `DataMerger`), read from and
+    /// written to the manager's merge store; the changelog entry is
+    /// flagged as an auto-merge. Without a merge store, or if a row
+    /// cannot be merged, the write conflict is returned instead.
+    Analyze,
+}
+
+/// What a write does to its table
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+pub enum WriteKind {
+    /// A new version of the table's data
+    #[default]
+    Data,
+
+    /// The table was dropped (a tombstone); `new_version` is its last version
+    Drop,
+
+    /// The table was renamed; `new_version` is its latest version, now
+    /// found under `to`
+    Rename { to: String },
+
+    /// A branch merge moved the head to `new_version`, an existing version
+    /// taken from branch `from`
+    Merge { from: String },
+}
+
+impl WriteKind {
+    /// Whether this is an ordinary data write
+    pub fn is_data(&self) -> bool {
+        matches!(self, WriteKind::Data)
+    }
+}
+
+impl std::fmt::Display for WriteKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WriteKind::Data => write!(f, "Data"),
+            WriteKind::Drop => write!(f, "Drop"),
+            WriteKind::Rename { to } => write!(f, "Rename({})", to),
+            WriteKind::Merge { from } => write!(f, "Merge({})", from),
+        }
+    }
+}
+
 /// A single table write within a transaction
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct TableWrite {
     /// Name of the table being written
     pub table_name: String,
@@ -130,6 +207,14 @@ pub struct TableWrite {
 
     /// Branch this write targets (None = current transaction branch)
     pub branch: Option<String>,
+
+    /// Data write, or a drop/rename tombstone
+    #[serde(default, skip_serializing_if = "WriteKind::is_data")]
+    pub kind: WriteKind,
+
+    /// Metadata stored on the committed table version
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub metadata: HashMap<String, String>,
 }
 
 impl TableWrite {
@@ -142,6 +227,33 @@ impl TableWrite {
             schema_hash: None,
             granularity: WriteGranularity::WholeTable,
             branch: None,
+            kind: WriteKind::Data,
+            metadata: HashMap::new(),
+        }
+    }
+
+    /// Tombstone recording that a table was dropped at `last_version`
+    pub fn drop_table(table_name: impl Into<String>, last_version: u64) -> Self {
+        Self {
+            kind: WriteKind::Drop,
+            ..Self::new(table_name, last_version, Vec::new())
+        }
+    }
+
+    /// Record that a merge from branch `from` moved the table's head to
+    /// an existing `version`
+    pub fn merge_table(table_name: impl Into<String>, from: impl Into<String>, version: u64, chunk_hashes: Vec<String>) -> Self {
+        Self {
+            kind: WriteKind::Merge { from: from.into() },
+            ..Self::new(table_name, version, chunk_hashes)
+        }
+    }
+
+    /// Record that a table was renamed (at its latest version)
+    pub fn rename_table(old_name: impl Into<String>, new_name: impl Into<String>, version: u64) -> Self {
+        Self {
+            kind: WriteKind::Rename { to: new_name.into() },
+            ..Self::new(old_name, version, Vec::new())
         }
     }
 
@@ -162,10 +274,92 @@ impl TableWrite {
         self.granularity = granularity;
         self
     }
+
+    /// Add a metadata entry for the committed version
+    pub fn with_metadata(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.metadata.insert(key.into(), value.into());
+        self
+    }
+
+    /// Version the table's algebraic annotations with the write, or clear
+    /// them with `None` (unset, the committed version keeps its parent's)
+    pub fn with_algebraic_schema(self, schema: Option<&TableAlgebraicSchema>) -> Self {
+        self.with_metadata(ALGEBRAIC_SCHEMA_METADATA_KEY, algebraic_schema_json(schema))
+    }
+
+    /// Record the migration allowing the write's schema change (see
+    /// [`SchemaRegistry::check_change`](crate::catalog::SchemaRegistry::check_change))
+    pub fn with_schema_migration(self, migration: &SchemaMigration) -> Self {
+        self.with_metadata(SCHEMA_MIGRATION_METADATA_KEY, schema_migration_json(migration))
+    }
+
+    /// Migration set with [`with_schema_migration`](Self::with_schema_migration)
+    pub fn schema_migration(&self) -> Option<SchemaMigration> {
+        serde_json::from_str(self.metadata.get(SCHEMA_MIGRATION_METADATA_KEY)?).ok()
+    }
+}
+
+/// A single table's entry in a read snapshot
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnapshotTable {
+    /// Version visible in the snapshot
+    pub version: u64,
+
+    /// Chunk hashes making up that version
+    pub chunk_hashes: Vec<String>,
+}
+
+/// Consistent multi-table view captured without opening a transaction.
+///
+/// Captured under the same rules as `TransactionManager::begin`, so every
+/// table is read as of the same branch head.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReadSnapshot {
+    /// Branch the snapshot was taken on
+    pub branch: String,
+
+    /// Table name -> version and chunks
+    pub tables: HashMap<String, SnapshotTable>,
+}
+
+impl ReadSnapshot {
+    /// Get the entry for a table, if it is visible in the snapshot
+    pub fn get(&self, table_name: &str) -> Option<&SnapshotTable> {
+        self.tables.get(table_name)
+    }
+
+    /// Table name -> version, in the same shape as `TransactionRecord::read_snapshot`
+    pub fn versions(&self) -> HashMap<String, u64> {
+        self.tables
+            .iter()
+            .map(|(name, t)| (name.clone(), t.version))
+            .collect()
+    }
+}
+
+/// A named point within a transaction that it can roll back to.
+///
+/// Captures the write set and read snapshot as they were when the savepoint
+/// was taken; rolling back restores both and discards later savepoints.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+pub struct Savepoint {
+    /// Savepoint name (unique within the transaction)
+    pub name: String,
+
+    /// Writes recorded before the savepoint
+    pub writes: Vec<TableWrite>,
+
+    /// Read snapshot at the savepoint
+    pub read_snapshot: SharedSnapshot,
+
+    /// Unix timestamp when the savepoint was taken
+    pub created_at: i64,
 }
 
 /// Complete transaction record - the source of truth
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct TransactionRecord {
     // === Identity ===
     /// Unique transaction ID (monotonically increasing)
@@ -183,8 +377,9 @@ pub struct TransactionRecord {
 
     // === Read Set (Snapshot) ===
     /// Tables read and their versions at transaction start
-    /// Used for conflict detection and debugging
-    pub read_snapshot: HashMap<String, u64>,
+    /// Used for conflict detection and debugging. Shared with other
+    /// transactions that began on the same branch head.
+    pub read_snapshot: SharedSnapshot,
 
     // === Write Set ===
     /// All writes this transaction will perform
@@ -203,10 +398,29 @@ pub struct TransactionRecord {
     #[serde(default)]
     pub mode: TransactionMode,
 
+    /// Admission priority when the manager has a commit scheduler
+    #[serde(default)]
+    pub priority: CommitPriority,
+
     // === Metadata ===
     /// User-provided metadata
     pub metadata: HashMap<String, String>,
 
+    /// Savepoints, oldest first. Only meaningful while the transaction is
+    /// active; cleared when it commits or aborts.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub savepoints: Vec<Savepoint>,
+
+    /// Tables whose conflicts were resolved by algebraic merge at commit
+    /// (`OnConflict::Analyze`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub auto_merged: Vec<String>,
+
+    /// The branch merge this transaction recorded (its writes are the heads
+    /// the merge moved)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub merge: Option<MergeRecord>,
+
     // === Extensibility ===
     /// Schema version for forward compatibility
     pub format_version: u32,
@@ -236,12 +450,16 @@ impl TransactionRecord {
             epoch_id,
             started_at: now,
             committed_at: None,
-            read_snapshot: HashMap::new(),
+            read_snapshot: SharedSnapshot::default(),
             writes: Vec::new(),
             status: TransactionStatus::Active,
             branch,
             mode,
+            priority: CommitPriority::default(),
             metadata: HashMap::new(),
+            savepoints: Vec::new(),
+            auto_merged: Vec::new(),
+            merge: None,
             format_version: Self::CURRENT_FORMAT_VERSION,
             extensions: None,
         }
@@ -289,7 +507,53 @@ impl TransactionRecord {
 
     /// Record a read from a table
     pub fn record_read(&mut self, table_name: impl Into<String>, version: u64) {
-        self.read_snapshot.insert(table_name.into(), version);
+        self.read_snapshot.insert(table_name, version);
+    }
+
+    /// Take a savepoint of the current writes and read snapshot. A
+    /// savepoint with the same name is replaced.
+    pub fn savepoint(&mut self, name: impl Into<String>) {
+        let name = name.into();
+        self.savepoints.retain(|sp| sp.name != name);
+        self.savepoints.push(Savepoint {
+            name,
+            writes: self.writes.clone(),
+            read_snapshot: self.read_snapshot.clone(),
+            created_at: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_secs() as i64,
+        });
+    }
+
+    /// Restore the writes and read snapshot captured by a savepoint,
+    /// discarding savepoints taken after it. The savepoint itself is kept,
+    /// so it can be rolled back to again. Returns false if there is no
+    /// savepoint with that name.
+    pub fn rollback_to_savepoint(&mut self, name: &str) -> bool {
+        let Some(pos) = self.savepoints.iter().position(|sp| sp.name == name) else {
+            return false;
+        };
+        self.savepoints.truncate(pos + 1);
+        let savepoint = &self.savepoints[pos];
+        self.writes = savepoint.writes.clone();
+        self.read_snapshot = savepoint.read_snapshot.clone();
+        true
+    }
+
+    /// Forget a savepoint and every savepoint taken after it, keeping the
+    /// current writes. Returns false if there is no savepoint with that name.
+    pub fn release_savepoint(&mut self, name: &str) -> bool {
+        let Some(pos) = self.savepoints.iter().position(|sp| sp.name == name) else {
+            return false;
+        };
+        self.savepoints.truncate(pos);
+        true
+    }
+
+    /// Get savepoint names, oldest first
+    pub fn savepoint_names(&self) -> Vec<&str> {
+        self.savepoints.iter().map(|sp| sp.name.as_str()).collect()
     }
 
     /// Mark transaction as preparing
@@ -306,6 +570,7 @@ impl TransactionRecord {
 
         self.status = TransactionStatus::Committed;
         self.committed_at = Some(now);
+        self.savepoints.clear();
     }
 
     /// Mark transaction as aborted
@@ -313,6 +578,7 @@ impl TransactionRecord {
         self.status = TransactionStatus::Aborted {
             reason: reason.into(),
         };
+        self.savepoints.clear();
     }
 
     /// Set metadata key-value pair
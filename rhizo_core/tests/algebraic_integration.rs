@@ -291,11 +291,11 @@ fn test_associativity() {
         OpType::SemilatticeMax,
         OpType::SemilatticeMin,
     ] {
-        let ab = AlgebraicMerger::merge(op, &a, &b).unwrap();
-        let ab_c = AlgebraicMerger::merge(op, &ab, &c);
+        let ab = AlgebraicMerger::merge(op.clone(), &a, &b).unwrap();
+        let ab_c = AlgebraicMerger::merge(op.clone(), &ab, &c);
 
-        let bc = AlgebraicMerger::merge(op, &b, &c).unwrap();
-        let a_bc = AlgebraicMerger::merge(op, &a, &bc);
+        let bc = AlgebraicMerger::merge(op.clone(), &b, &c).unwrap();
+        let a_bc = AlgebraicMerger::merge(op.clone(), &a, &bc);
 
         assert_eq!(ab_c, a_bc, "{:?} should be associative", op);
     }
@@ -309,11 +309,11 @@ fn test_set_associativity() {
     let c = AlgebraicValue::string_set(["3", "4"]);
 
     for op in [OpType::SemilatticeUnion, OpType::SemilatticeIntersect] {
-        let ab = AlgebraicMerger::merge(op, &a, &b).unwrap();
-        let ab_c = AlgebraicMerger::merge(op, &ab, &c);
+        let ab = AlgebraicMerger::merge(op.clone(), &a, &b).unwrap();
+        let ab_c = AlgebraicMerger::merge(op.clone(), &ab, &c);
 
-        let bc = AlgebraicMerger::merge(op, &b, &c).unwrap();
-        let a_bc = AlgebraicMerger::merge(op, &a, &bc);
+        let bc = AlgebraicMerger::merge(op.clone(), &b, &c).unwrap();
+        let a_bc = AlgebraicMerger::merge(op.clone(), &a, &bc);
 
         assert_eq!(ab_c, a_bc, "{:?} should be associative for sets", op);
     }
@@ -0,0 +1,68 @@
+//! Integration test for the counter service example: three nodes on real
+//! sockets, written to through their client listeners, converge by gossip.
+
+#[path = "../examples/counter_service/mod.rs"]
+mod counter_service;
+
+use std::collections::HashSet;
+use std::net::TcpListener;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use counter_service::{CounterClient, CounterNode};
+use rhizo_core::{AlgebraicValue, GossipConfig};
+use tempfile::TempDir;
+
+#[test]
+fn test_nodes_converge_through_gossip() {
+    let dir = TempDir::new().unwrap();
+    let config = GossipConfig::default().with_interval(Duration::from_millis(20)).with_fanout(0);
+    let nodes: Vec<_> = ["a", "b", "c"]
+        .iter()
+        .map(|id| CounterNode::start(id, "127.0.0.1:0", dir.path().join(id), config.clone()).unwrap())
+        .collect();
+
+    // Line topology a - b - c: a and c only meet through b
+    nodes[0].add_peer(nodes[1].gossip_addr());
+    nodes[1].add_peer(nodes[2].gossip_addr());
+    let servers: Vec<_> = nodes
+        .iter()
+        .map(|node| node.serve(TcpListener::bind("127.0.0.1:0").unwrap()).unwrap())
+        .collect();
+    let mut clients: Vec<_> = servers.iter().map(|s| CounterClient::connect(s.addr()).unwrap()).collect();
+
+    let mut archived = vec![
+        clients[0].increment("page_views", 5).unwrap(),
+        clients[2].increment("page_views", 3).unwrap(),
+        clients[1].increment("page_views", -1).unwrap(),
+    ];
+    archived.push(clients[0].tag("labels", "home").unwrap());
+    archived.push(clients[2].tag("labels", "sale").unwrap());
+
+    let expected_views = AlgebraicValue::integer(7);
+    let expected_labels = AlgebraicValue::string_set(["home", "sale"]);
+    let deadline = Instant::now() + Duration::from_secs(10);
+    for client in &mut clients {
+        loop {
+            let state = client.state().unwrap();
+            if state.get("page_views") == Some(&expected_views) && state.get("labels") == Some(&expected_labels) {
+                break;
+            }
+            assert!(Instant::now() < deadline, "nodes did not converge: {:?}", state);
+            thread::sleep(Duration::from_millis(20));
+        }
+    }
+
+    // Every node archived every update under the same content hash
+    for node in &nodes {
+        let stored: HashSet<String> = node.store().list_chunk_hashes().unwrap().into_iter().collect();
+        assert!(archived.iter().all(|hash| stored.contains(hash)));
+    }
+
+    assert!(clients[0].sync().unwrap() >= 1);
+    assert_eq!(clients[1].get("missing").unwrap(), None);
+
+    for node in &nodes {
+        node.shutdown();
+    }
+}
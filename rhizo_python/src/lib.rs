@@ -1,9 +1,12 @@
 use std::collections::HashMap;
+use std::io::Read;
 use std::str::FromStr;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
 use pyo3::prelude::*;
-use pyo3::exceptions::{PyIOError, PyValueError, PyRuntimeError};
+use pyo3::exceptions::{PyIOError, PyValueError, PyRuntimeError, PyTimeoutError};
+use pyo3::sync::PyOnceLock;
+use pyo3::types::{PyDict, PyTuple, PyType};
 
 // =============================================================================
 // Error Message Sanitization
@@ -124,24 +127,37 @@ fn sanitize_io_error(e: &std::io::Error) -> String {
     }
 }
 use rhizo_core::{
-    ChunkStore, ChunkStoreError,
-    FileCatalog, CatalogError, TableVersion,
-    Branch, BranchDiff, BranchError, BranchManager,
-    MergeAnalysis, MergeAnalyzer, MergeOutcome,
-    TransactionManager, TransactionRecord, TransactionError,
-    TableWrite, RecoveryReport,
-    ChangelogEntry, TableChange, ChangelogQuery,
+    ErrorContext, ErrorInfo,
+    ChunkStore, ChunkStoreError, ChunkCompression, ChunkStoreConfig, ChunkReader, Chunker,
+    FileCatalog, CatalogError, SchemaChange, TableVersion, Pin, LegalHold, LockOwner, PendingCommit, Tag, TagManager, View,
+    ArchivedBranch, Branch, BranchCommit, BranchDiff, BranchError, MergeRecord, MergeStrategy, BranchManager, Checkout, HeadEvent, HeadRecord, RepoSnapshot,
+    BranchRepair, DanglingHead, RepairStrategy,
+    FilterError, FilterReport, RepoFilter, RepoRef,
+    ChunkGc, GcError, GcPlan, GcPlanner, GcReport, PruneReport, RetentionPolicy, VersionPruner,
+    ChunkCondition, ChunkScrubber, ScrubError, ScrubReport,
+    FixtureBuilder, FixtureError, FixtureReport, Repo, RepoError,
+    DataMergeError, DataMerger, MergeSimulation, TableMerge,
+    Backup, BackupError, BackupManifest, BackupSet, RestoreReport, VerifyReport,
+    MergeAnalysis, MergeAnalyzer, MergeOutcome, MergeResolution,
+    TransactionManager, TransactionRecord, TransactionError, OnConflict,
+    BatchAck, BatchTicket, BatchedWrite, MicroBatchConfig, MicroBatchStats, MicroBatcher,
+    CommitMetricsSink, CommitPhase, CommitPriority, CommitQueueStats, CommitScheduler, CommitTimings, ConflictGranularity, ConflictWindow, WriteGranularity, WriteKind,
+    TableWrite, RecoveryReport, EpochConfig, EpochMetadata,
+    ChangelogCursor, ChangelogEntry, TableChange, ChangelogQuery, ChangelogReplay, ReplayError, ReplayReport,
     MerkleTree, MerkleNode, DataChunk, MerkleDiff, MerkleConfig, MerkleError,
     build_tree, diff_trees, verify_tree,
     ParquetEncoder, ParquetDecoder, ParquetCompression, ParquetError,
+    ExportFormat, ExportManifest, ImportOptions, Redactor, RowPatch, TableError, TableExporter, TableReader, TableWriter,
+    Query, QueryEngine, QueryError, RefError, RefResolver, RefSpec,
     FilterOp, ScalarValue, PredicateFilter,
     // Algebraic types
-    OpType, AlgebraicValue, AlgebraicMerger, MergeResult,
+    OpType, AlgebraicValue, AlgebraicMerger, MergeResult, ObservedRemoveSet, PnCounter, LwwRegister,
+    AlgebraicLaws, BoundedCounter, Bounds, ColumnAlgebraic, CustomMergeRegistry, Decimal,
     TableAlgebraicSchema, AlgebraicSchemaRegistry,
     // Distributed types
     VectorClock, NodeId, CausalOrder,
     AlgebraicOperation, AlgebraicTransaction, VersionedUpdate,
-    LocalCommitProtocol,
+    LocalCommitProtocol, CoordinationFreeConfig, CoordinationFreeError, CoordinationFreeManager,
     // Simulation types
     SimulatedCluster, SimulationConfig, SimulationStats, NetworkCondition,
 };
@@ -150,22 +166,203 @@ use rhizo_core::{
 use arrow_pyarrow::{ToPyArrow, FromPyArrow};
 use arrow::record_batch::RecordBatch;
 
+// =============================================================================
+// Typed Exceptions
+// =============================================================================
+
+/// Exception classes of the module as (name, bases, doc), bases first.
+///
+/// Each class also derives from the built-in exception its errors were
+/// raised as before it existed, so `except ValueError` and `except IOError`
+/// handlers keep catching them.
+const EXCEPTION_CLASSES: &[(&str, &[&str], &str)] = &[
+    ("RhizoError", &["Exception"], "Base class for all Rhizo-specific errors."),
+    (
+        "ConflictError",
+        &["RhizoError", "ValueError"],
+        "Concurrent modification; the operation may succeed if retried.",
+    ),
+    (
+        "WriteConflictError",
+        &["ConflictError"],
+        "A table the transaction wrote was committed by another transaction first.",
+    ),
+    (
+        "SnapshotConflictError",
+        &["ConflictError"],
+        "A table the transaction read changed before it committed.",
+    ),
+    (
+        "MergeConflictError",
+        &["ConflictError"],
+        "Both sides of a merge changed the same data in ways that cannot be combined.",
+    ),
+    ("NotFoundError", &["RhizoError", "IOError"], "A table, version, ref or other object does not exist."),
+    ("BranchNotFoundError", &["NotFoundError"], "The named branch does not exist."),
+    ("ChunkNotFoundError", &["NotFoundError"], "No chunk is stored under the hash."),
+    ("IntegrityError", &["RhizoError", "ValueError"], "Stored data failed verification."),
+    (
+        "HashMismatchError",
+        &["IntegrityError"],
+        "A chunk's content does not hash to the hash it is stored under.",
+    ),
+];
+
+/// Class raised for an error code, if more specific than the built-in one
+fn typed_exception(code: &str) -> Option<&'static str> {
+    Some(match code {
+        "TX_WRITE_CONFLICT" => "WriteConflictError",
+        "TX_SNAPSHOT_CONFLICT" => "SnapshotConflictError",
+        "BRANCH_MERGE_CONFLICT" | "BRANCH_ALGEBRAIC_CONFLICT" | "DATA_MERGE_ROW_CONFLICT" => {
+            "MergeConflictError"
+        }
+        "BRANCH_HEAD_CHANGED" => "ConflictError",
+        "BRANCH_NOT_FOUND" => "BranchNotFoundError",
+        "CHUNK_NOT_FOUND" => "ChunkNotFoundError",
+        "CATALOG_TABLE_NOT_FOUND"
+        | "CATALOG_VERSION_NOT_FOUND"
+        | "CATALOG_PIN_NOT_FOUND"
+        | "CATALOG_TAG_NOT_FOUND"
+        | "CATALOG_VIEW_NOT_FOUND"
+        | "CATALOG_HOLD_NOT_FOUND"
+        | "BRANCH_SNAPSHOT_NOT_FOUND"
+        | "REF_ANCESTOR_NOT_FOUND"
+        | "REF_TABLE_NOT_FOUND" => "NotFoundError",
+        "CHUNK_HASH_MISMATCH" => "HashMismatchError",
+        "MERKLE_INTEGRITY" => "IntegrityError",
+        _ => return None,
+    })
+}
+
+static EXCEPTIONS: PyOnceLock<HashMap<&'static str, Py<PyType>>> = PyOnceLock::new();
+
+/// The classes of EXCEPTION_CLASSES, created on first use
+fn exception_classes(py: Python<'_>) -> PyResult<&HashMap<&'static str, Py<PyType>>> {
+    EXCEPTIONS.get_or_try_init(py, || {
+        let builtins = py.import("builtins")?;
+        let type_ = builtins.getattr("type")?;
+        let mut classes: HashMap<&'static str, Py<PyType>> = HashMap::new();
+        for &(name, bases, doc) in EXCEPTION_CLASSES {
+            let bases = bases
+                .iter()
+                .map(|base| match classes.get(base) {
+                    Some(class) => Ok(class.bind(py).clone().into_any()),
+                    None => builtins.getattr(*base),
+                })
+                .collect::<PyResult<Vec<_>>>()?;
+            let namespace = PyDict::new(py);
+            namespace.set_item("__module__", "_rhizo")?;
+            namespace.set_item("__doc__", doc)?;
+            let class = type_.call1((name, PyTuple::new(py, bases)?, namespace))?;
+            classes.insert(name, class.cast_into::<PyType>()?.unbind());
+        }
+        Ok(classes)
+    })
+}
+
+/// Re-raise `err` as the typed exception for `code`, keeping its arguments
+fn retype(py: Python<'_>, err: PyErr, code: &str) -> PyErr {
+    let Some(name) = typed_exception(code) else {
+        return err;
+    };
+    let Ok(classes) = exception_classes(py) else {
+        return err;
+    };
+    let class = classes[name].bind(py);
+    let value = err.value(py);
+    if value.is_instance(class).unwrap_or(false) {
+        return err;
+    }
+    // Best effort: the original exception is better than none
+    match value.getattr("args").and_then(|args| class.call1(args.cast_into::<PyTuple>()?)) {
+        Ok(typed) => PyErr::from_value(typed),
+        Err(_) => err,
+    }
+}
+
+/// Stable code, category and context of a core error, attached to the
+/// Python exception as `code`, `category` and `context` attributes so
+/// callers can branch on errors without parsing messages.
+struct ErrorAttrs {
+    code: &'static str,
+    category: &'static str,
+    context: ErrorContext,
+    /// Seconds to wait before retrying; None if not retryable
+    retry_after: Option<f64>,
+}
+
+impl ErrorAttrs {
+    fn of(e: &impl ErrorInfo) -> Self {
+        Self {
+            code: e.code(),
+            category: e.category().as_str(),
+            context: e.context(),
+            retry_after: e.retry_after().map(|d| d.as_secs_f64()),
+        }
+    }
+
+    fn attach(self, err: PyErr) -> PyErr {
+        Python::attach(|py| {
+            let err = retype(py, err, self.code);
+            let context = PyDict::new(py);
+            let ctx = &self.context;
+            // Paths are left out for the same reason messages are sanitized
+            // Best effort: failing to set an attribute must not mask the error
+            for (name, value) in [("table", &ctx.table), ("branch", &ctx.branch), ("chunk", &ctx.chunk), ("key", &ctx.key)] {
+                if let Some(value) = value {
+                    let _ = context.set_item(name, value);
+                }
+            }
+            for (name, value) in [("version", ctx.version), ("tx_id", ctx.tx_id), ("epoch_id", ctx.epoch_id)] {
+                if let Some(value) = value {
+                    let _ = context.set_item(name, value);
+                }
+            }
+
+            let value = err.value(py);
+            let _ = value.setattr("code", self.code);
+            let _ = value.setattr("category", self.category);
+            let _ = value.setattr("context", context);
+            let _ = value.setattr("retryable", self.retry_after.is_some());
+            let _ = value.setattr("retry_after", self.retry_after);
+            err
+        })
+    }
+}
+
 /// Convert ChunkStoreError to appropriate Python exception
 fn chunk_err_to_py(e: ChunkStoreError) -> PyErr {
-    match e {
+    let info = ErrorAttrs::of(&e);
+    let err = match e {
         ChunkStoreError::NotFound(h) => PyIOError::new_err(format!("Chunk not found: {}", h)),
         ChunkStoreError::InvalidHash(msg) => PyValueError::new_err(format!("Invalid hash: {}", msg)),
         ChunkStoreError::HashMismatch { expected, actual } => {
             PyValueError::new_err(format!("Hash mismatch: expected {}, got {}", expected, actual))
         }
         ChunkStoreError::Io(e) => PyIOError::new_err(sanitize_io_error(&e)),
-    }
+        ChunkStoreError::Pipeline(msg) => {
+            PyIOError::new_err(format!("Chunk pipeline error: {}", sanitize_error_message(&msg)))
+        }
+        ChunkStoreError::Backend(msg) => {
+            PyIOError::new_err(format!("Chunk backend error: {}", sanitize_error_message(&msg)))
+        }
+        ChunkStoreError::CorruptDelta(msg) => {
+            PyIOError::new_err(format!("Corrupt delta chunk: {}", sanitize_error_message(&msg)))
+        }
+        e @ ChunkStoreError::TooManyOpenFiles { .. } => PyIOError::new_err(e.to_string()),
+        e => PyRuntimeError::new_err(sanitize_error_message(&e.to_string())),
+    };
+    info.attach(err)
 }
 
 /// Convert CatalogError to appropriate Python exception
 fn catalog_err_to_py(e: CatalogError) -> PyErr {
-    match e {
+    let info = ErrorAttrs::of(&e);
+    let err = match e {
         CatalogError::TableNotFound(t) => PyIOError::new_err(format!("Table not found: {}", t)),
+        CatalogError::TableAlreadyExists(t) => {
+            PyValueError::new_err(format!("Table already exists: {}", t))
+        }
         CatalogError::VersionNotFound(t, v) => {
             PyIOError::new_err(format!("Version not found: {} v{}", t, v))
         }
@@ -178,15 +375,61 @@ fn catalog_err_to_py(e: CatalogError) -> PyErr {
         CatalogError::Io(e) => PyIOError::new_err(sanitize_io_error(&e)),
         CatalogError::Json(e) => PyValueError::new_err(format!("JSON error: {}", sanitize_error_message(&e.to_string()))),
         CatalogError::LockError(msg) => PyIOError::new_err(format!("Lock error: {}", sanitize_error_message(&msg))),
+        e @ CatalogError::LockTimeout { .. } => PyTimeoutError::new_err(sanitize_error_message(&e.to_string())),
+        e @ CatalogError::LockHeld { .. } => PyValueError::new_err(sanitize_error_message(&e.to_string())),
         CatalogError::CannotDeleteLatest(t, v) => {
             PyValueError::new_err(format!("Cannot delete latest version: {} v{}", t, v))
         }
-    }
+        CatalogError::InvalidTableName(msg) => {
+            PyValueError::new_err(format!("Invalid table name: {}", sanitize_error_message(&msg)))
+        }
+        CatalogError::InvalidPinName(msg) => {
+            PyValueError::new_err(format!("Invalid pin name: {}", sanitize_error_message(&msg)))
+        }
+        CatalogError::PinNotFound(name) => PyIOError::new_err(format!("Pin not found: {}", name)),
+        CatalogError::PinAlreadyExists(name) => {
+            PyValueError::new_err(format!("Pin already exists: {}", name))
+        }
+        CatalogError::VersionPinned(t, v, pin) => {
+            PyValueError::new_err(format!("Version is pinned: {} v{} (pin: {})", t, v, pin))
+        }
+        CatalogError::VersionHeld(t, v) => {
+            PyValueError::new_err(format!("Version is under legal hold: {} v{}", t, v))
+        }
+        CatalogError::HoldNotFound(t, v) => {
+            PyIOError::new_err(format!("No legal hold on {} v{}", t, v))
+        }
+        CatalogError::InvalidTagName(msg) => {
+            PyValueError::new_err(format!("Invalid tag name: {}", sanitize_error_message(&msg)))
+        }
+        CatalogError::TagNotFound(name) => PyIOError::new_err(format!("Tag not found: {}", name)),
+        CatalogError::TagAlreadyExists(name) => {
+            PyValueError::new_err(format!("Tag already exists: {}", name))
+        }
+        CatalogError::VersionTagged(t, v, tag) => {
+            PyValueError::new_err(format!("Version is tagged: {} v{} (tag: {})", t, v, tag))
+        }
+        CatalogError::ViewNotFound(name) => PyIOError::new_err(format!("View not found: {}", name)),
+        CatalogError::ViewAlreadyExists(name) => {
+            PyValueError::new_err(format!("View already exists: {}", name))
+        }
+        CatalogError::InvalidView(name, reason) => {
+            PyValueError::new_err(format!("Invalid view {}: {}", name, reason))
+        }
+        e @ CatalogError::SchemaConflict { .. } => PyValueError::new_err(e.to_string()),
+        CatalogError::DanglingHead { table, version, branch } => PyIOError::new_err(format!(
+            "Branch {} head for {} points at missing v{}; run repair_branch",
+            branch, table, version
+        )),
+        e => PyRuntimeError::new_err(sanitize_error_message(&e.to_string())),
+    };
+    info.attach(err)
 }
 
 /// Convert BranchError to appropriate Python exception
 fn branch_err_to_py(e: BranchError) -> PyErr {
-    match e {
+    let info = ErrorAttrs::of(&e);
+    let err = match e {
         BranchError::BranchNotFound(name) => {
             PyIOError::new_err(format!("Branch not found: {}", name))
         }
@@ -196,6 +439,9 @@ fn branch_err_to_py(e: BranchError) -> PyErr {
         BranchError::CannotDeleteDefault(name) => {
             PyValueError::new_err(format!("Cannot delete default branch: {}", name))
         }
+        BranchError::BranchImmutable(name) => {
+            PyValueError::new_err(format!("Branch is immutable: {}", name))
+        }
         BranchError::InvalidBranchName(msg) => {
             PyValueError::new_err(format!("Invalid branch name: {}", msg))
         }
@@ -211,14 +457,32 @@ fn branch_err_to_py(e: BranchError) -> PyErr {
         BranchError::AlgebraicConflict(tables) => {
             PyValueError::new_err(format!("Algebraic merge conflict on tables: {:?}", tables))
         }
+        BranchError::HeadChanged { expected, actual } => PyValueError::new_err(format!(
+            "HEAD changed: expected generation {}, found {}",
+            expected, actual
+        )),
+        BranchError::BranchCheckedOut { branch, workspace } => PyValueError::new_err(format!(
+            "Branch {} is checked out by workspace {}",
+            branch, workspace
+        )),
+        BranchError::InvalidWorkspace(msg) => {
+            PyValueError::new_err(format!("Invalid workspace id: {}", msg))
+        }
+        BranchError::SnapshotNotFound(id) => {
+            PyIOError::new_err(format!("Snapshot not found: repo@{}", id))
+        }
         BranchError::Io(e) => PyIOError::new_err(sanitize_io_error(&e)),
         BranchError::Json(e) => PyValueError::new_err(format!("JSON error: {}", sanitize_error_message(&e.to_string()))),
-    }
+        BranchError::Catalog(e) => return catalog_err_to_py(e),
+        e => PyRuntimeError::new_err(sanitize_error_message(&e.to_string())),
+    };
+    info.attach(err)
 }
 
 /// Convert MerkleError to appropriate Python exception
 fn merkle_err_to_py(e: MerkleError) -> PyErr {
-    match e {
+    let info = ErrorAttrs::of(&e);
+    let err = match e {
         MerkleError::Io(e) => PyIOError::new_err(sanitize_io_error(&e)),
         MerkleError::ChunkNotFound(hash) => {
             PyValueError::new_err(format!("Chunk not found: {}", hash))
@@ -241,12 +505,15 @@ fn merkle_err_to_py(e: MerkleError) -> PyErr {
         MerkleError::ChunkStore(msg) => {
             PyIOError::new_err(format!("Chunk store error: {}", sanitize_error_message(&msg)))
         }
-    }
+        e => PyRuntimeError::new_err(sanitize_error_message(&e.to_string())),
+    };
+    info.attach(err)
 }
 
 /// Convert ParquetError to appropriate Python exception
 fn parquet_err_to_py(e: ParquetError) -> PyErr {
-    match e {
+    let info = ErrorAttrs::of(&e);
+    let err = match e {
         ParquetError::Arrow(e) => PyValueError::new_err(format!("Arrow error: {}", sanitize_error_message(&e.to_string()))),
         ParquetError::Parquet(e) => PyValueError::new_err(format!("Parquet error: {}", sanitize_error_message(&e.to_string()))),
         ParquetError::EmptyData => PyValueError::new_err("Cannot process empty data"),
@@ -268,7 +535,9 @@ fn parquet_err_to_py(e: ParquetError) -> PyErr {
         ParquetError::RowCountOverflow => {
             PyValueError::new_err("Row count overflow: total rows exceed maximum")
         }
-    }
+        e => PyRuntimeError::new_err(sanitize_error_message(&e.to_string())),
+    };
+    info.attach(err)
 }
 
 // =============================================================================
@@ -533,6 +802,688 @@ impl PyParquetDecoder {
     }
 }
 
+// =============================================================================
+// Arrow Tables
+// =============================================================================
+
+/// Convert TableError to appropriate Python exception
+fn table_err_to_py(e: TableError) -> PyErr {
+    let info = ErrorAttrs::of(&e);
+    let err = match e {
+        TableError::Catalog(e) => catalog_err_to_py(e),
+        TableError::ChunkStore(e) => chunk_err_to_py(e),
+        TableError::Parquet(e) => parquet_err_to_py(e),
+        TableError::Transaction(e) => tx_err_to_py(e),
+        TableError::Io(e) => PyIOError::new_err(sanitize_error_message(&e.to_string())),
+        e @ (TableError::EmptyInput(_)
+        | TableError::SchemaMismatch { .. }
+        | TableError::InvalidFilter { .. }
+        | TableError::InvalidPatch { .. }) => PyValueError::new_err(e.to_string()),
+        e @ (TableError::SchemaHashMismatch { .. }
+        | TableError::EmptyChunk { .. }
+        | TableError::UnknownFormat { .. }
+        | TableError::CorruptPatch { .. }) => PyIOError::new_err(e.to_string()),
+        e => PyValueError::new_err(sanitize_error_message(&e.to_string())),
+    };
+    info.attach(err)
+}
+
+/// Record batches of a pyarrow RecordBatch, Table (anything with
+/// `to_batches()`) or list of RecordBatches
+fn record_batches(data: &Bound<'_, PyAny>) -> PyResult<Vec<RecordBatch>> {
+    let invalid = |e: PyErr| {
+        PyValueError::new_err(format!("Invalid RecordBatch: {}", sanitize_error_message(&e.to_string())))
+    };
+    if let Ok(batch) = RecordBatch::from_pyarrow_bound(data) {
+        return Ok(vec![batch]);
+    }
+    let items = if data.hasattr("to_batches")? {
+        data.call_method0("to_batches")?
+    } else {
+        data.clone()
+    };
+    items
+        .try_iter()?
+        .map(|b| RecordBatch::from_pyarrow_bound(&b?).map_err(invalid))
+        .collect()
+}
+
+/// Write Arrow data as the next version of a table.
+///
+/// Each chunk is an Arrow IPC stream of at most `max_rows_per_chunk`
+/// rows. The committed version records the schema hash and is readable
+/// with `read_arrow`.
+///
+/// Args:
+///     store: PyChunkStore receiving the chunks
+///     catalog: PyCatalog receiving the version
+///     table_name: Table to write
+///     data: pyarrow RecordBatch, Table or list of RecordBatches
+///     max_rows_per_chunk: Maximum rows per chunk
+///     metadata: Optional metadata attached to the version
+///
+/// Returns:
+///     PyTableVersion: The committed version
+#[pyfunction]
+#[pyo3(signature = (store, catalog, table_name, data, max_rows_per_chunk=65536, metadata=None))]
+fn write_arrow(
+    py: Python<'_>,
+    store: &PyChunkStore,
+    catalog: &PyCatalog,
+    table_name: &str,
+    data: Bound<'_, PyAny>,
+    max_rows_per_chunk: usize,
+    metadata: Option<HashMap<String, String>>,
+) -> PyResult<PyTableVersion> {
+    let batches = record_batches(&data)?;
+    let mut writer = TableWriter::new(&store.inner, &catalog.inner).with_max_rows_per_chunk(max_rows_per_chunk);
+    for (key, value) in metadata.unwrap_or_default() {
+        writer = writer.with_metadata(key, value);
+    }
+    py.detach(|| writer.write(table_name, &batches))
+        .map(PyTableVersion::from)
+        .map_err(table_err_to_py)
+}
+
+/// Apply row changes to the latest version of a table as patch chunks.
+///
+/// Only the chunks the changes touch are stored again, as patches on top
+/// of the previous chunks; `read_arrow` applies them. Positions count rows
+/// across the whole latest version.
+///
+/// Args:
+///     store: PyChunkStore holding the chunks
+///     catalog: PyCatalog receiving the version
+///     table_name: Table to patch
+///     delete_rows: Positions of rows to remove
+///     update_rows: Positions of rows to replace, one per row of `updates`
+///     updates: Replacement rows (pyarrow RecordBatch, Table or list)
+///     inserts: Rows to append
+///     max_patch_chain: Longest chain of patches before a chunk is stored
+///         in full again
+///     metadata: Optional metadata attached to the version
+///
+/// Returns:
+///     PyTableVersion: The committed version
+///
+/// Example:
+///     >>> write_patch(store, catalog, "users", delete_rows=[3],
+///     ...             update_rows=[5], updates=pa.table({"id": [5], "name": ["e"]}))
+#[pyfunction]
+#[pyo3(signature = (store, catalog, table_name, delete_rows=None, update_rows=None, updates=None, inserts=None, max_patch_chain=8, metadata=None))]
+#[allow(clippy::too_many_arguments)]
+fn write_patch(
+    py: Python<'_>,
+    store: &PyChunkStore,
+    catalog: &PyCatalog,
+    table_name: &str,
+    delete_rows: Option<Vec<u64>>,
+    update_rows: Option<Vec<u64>>,
+    updates: Option<Bound<'_, PyAny>>,
+    inserts: Option<Bound<'_, PyAny>>,
+    max_patch_chain: u32,
+    metadata: Option<HashMap<String, String>>,
+) -> PyResult<PyTableVersion> {
+    let mut patch = RowPatch::new().delete_rows(delete_rows.unwrap_or_default());
+    let update_rows = update_rows.unwrap_or_default();
+    let updates = updates.map(|data| record_batches(&data)).transpose()?.unwrap_or_default();
+    let rows: usize = updates.iter().map(RecordBatch::num_rows).sum();
+    if rows != update_rows.len() {
+        return Err(PyValueError::new_err(format!(
+            "{} update positions for {} replacement rows",
+            update_rows.len(),
+            rows
+        )));
+    }
+    let mut offset = 0;
+    for batch in updates {
+        let len = batch.num_rows();
+        patch = patch.update_rows(update_rows[offset..offset + len].to_vec(), batch);
+        offset += len;
+    }
+    for batch in inserts.map(|data| record_batches(&data)).transpose()?.unwrap_or_default() {
+        patch = patch.insert_rows(batch);
+    }
+
+    let mut writer = TableWriter::new(&store.inner, &catalog.inner).with_max_patch_chain(max_patch_chain);
+    for (key, value) in metadata.unwrap_or_default() {
+        writer = writer.with_metadata(key, value);
+    }
+    py.detach(|| writer.write_patch(table_name, &patch))
+        .map(PyTableVersion::from)
+        .map_err(table_err_to_py)
+}
+
+/// Read a version of a table as pyarrow RecordBatches (zero-copy).
+///
+/// Reads versions written by `write_arrow` (Arrow IPC chunks, checked
+/// against the version's schema hash) and by the Python TableWriter
+/// (Parquet chunks).
+///
+/// Args:
+///     store: PyChunkStore holding the chunks
+///     catalog: PyCatalog holding the version
+///     table_name: Table to read
+///     version: Version to read (default: latest)
+///
+/// Returns:
+///     List[pyarrow.RecordBatch]: Batches in row order; combine with
+///     ``pyarrow.Table.from_batches``
+#[pyfunction]
+#[pyo3(signature = (store, catalog, table_name, version=None))]
+fn read_arrow<'py>(
+    py: Python<'py>,
+    store: &PyChunkStore,
+    catalog: &PyCatalog,
+    table_name: &str,
+    version: Option<u64>,
+) -> PyResult<Vec<Bound<'py, PyAny>>> {
+    let reader = TableReader::new(&store.inner, &catalog.inner);
+    let batches = py
+        .detach(|| reader.read(table_name, version))
+        .map_err(table_err_to_py)?;
+    batches
+        .iter()
+        .map(|batch| batch.to_pyarrow(py).map_err(|e| PyValueError::new_err(sanitize_error_message(&e.to_string()))))
+        .collect()
+}
+
+/// Import a Parquet file as the next version of a table.
+///
+/// The file is read in batches of at most `max_rows_per_chunk` rows, each
+/// stored as a Parquet chunk. Without a transaction the version is
+/// committed directly; with `transaction_manager` and `tx_id` the write is
+/// added to that transaction and appears when it commits.
+///
+/// Args:
+///     store: PyChunkStore receiving the chunks
+///     catalog: PyCatalog receiving the version
+///     path: Parquet file to import
+///     table_name: Table to write
+///     transaction_manager: Optional PyTransactionManager owning `tx_id`
+///     tx_id: Transaction to add the write to
+///     max_rows_per_chunk: Maximum rows per chunk
+///     metadata: Optional metadata attached to a directly committed version
+///
+/// Returns:
+///     PyTableVersion: The committed version, or the version the
+///     transaction will commit
+#[pyfunction]
+#[pyo3(signature = (store, catalog, path, table_name, transaction_manager=None, tx_id=None, max_rows_per_chunk=65536, metadata=None))]
+#[allow(clippy::too_many_arguments)]
+fn import_parquet(
+    py: Python<'_>,
+    store: &PyChunkStore,
+    catalog: &PyCatalog,
+    path: &str,
+    table_name: &str,
+    transaction_manager: Option<PyRef<'_, PyTransactionManager>>,
+    tx_id: Option<u64>,
+    max_rows_per_chunk: usize,
+    metadata: Option<HashMap<String, String>>,
+) -> PyResult<PyTableVersion> {
+    let tx = import_tx(&transaction_manager, tx_id)?;
+    let writer = import_writer(store, catalog, max_rows_per_chunk, metadata);
+    py.detach(|| writer.import_parquet(path, table_name, tx))
+        .map(PyTableVersion::from)
+        .map_err(table_err_to_py)
+}
+
+/// The transaction an import joins, if any
+fn import_tx<'a>(
+    transaction_manager: &'a Option<PyRef<'_, PyTransactionManager>>,
+    tx_id: Option<u64>,
+) -> PyResult<Option<(&'a TransactionManager, u64)>> {
+    match (transaction_manager, tx_id) {
+        (Some(manager), Some(tx_id)) => Ok(Some((manager.inner.as_ref(), tx_id))),
+        (None, None) => Ok(None),
+        _ => Err(PyValueError::new_err(
+            "transaction_manager and tx_id must be given together",
+        )),
+    }
+}
+
+fn import_writer<'a>(
+    store: &'a PyChunkStore,
+    catalog: &'a PyCatalog,
+    max_rows_per_chunk: usize,
+    metadata: Option<HashMap<String, String>>,
+) -> TableWriter<'a> {
+    let mut writer = TableWriter::new(&store.inner, &catalog.inner).with_max_rows_per_chunk(max_rows_per_chunk);
+    for (key, value) in metadata.unwrap_or_default() {
+        writer = writer.with_metadata(key, value);
+    }
+    writer
+}
+
+/// Import options from the Python arguments
+fn import_options(
+    schema: Option<&Bound<'_, PyAny>>,
+    infer_rows: Option<usize>,
+    has_header: bool,
+    delimiter: &str,
+) -> PyResult<ImportOptions> {
+    let delimiter = match delimiter.as_bytes() {
+        [byte] => *byte,
+        _ => return Err(PyValueError::new_err("delimiter must be a single ASCII character")),
+    };
+    let mut options = ImportOptions::new()
+        .with_infer_rows(infer_rows)
+        .with_header(has_header)
+        .with_delimiter(delimiter);
+    if let Some(schema) = schema {
+        let schema = arrow::datatypes::Schema::from_pyarrow_bound(schema)
+            .map_err(|e| PyValueError::new_err(sanitize_error_message(&e.to_string())))?;
+        options = options.with_schema(Arc::new(schema));
+    }
+    Ok(options)
+}
+
+/// Import a CSV file as the next version of a table.
+///
+/// Column types are inferred from the first `infer_rows` records (all of
+/// them if None) unless `schema` is given. Rows are stored as Parquet
+/// chunks of at most `max_rows_per_chunk` rows and committed in one step:
+/// directly, or as a write of the transaction `tx_id`.
+///
+/// Args:
+///     store: PyChunkStore receiving the chunks
+///     catalog: PyCatalog receiving the version
+///     path: CSV file to import
+///     table_name: Table to write
+///     schema: Optional pyarrow.Schema to read the file with
+///     has_header: The first line names the columns
+///     delimiter: Field delimiter (one ASCII character)
+///     infer_rows: Records read to infer the schema (None = all)
+///     transaction_manager: Optional PyTransactionManager owning `tx_id`
+///     tx_id: Transaction to add the write to
+///     max_rows_per_chunk: Maximum rows per chunk
+///     metadata: Optional metadata attached to a directly committed version
+///
+/// Returns:
+///     PyTableVersion: The committed version, or the version the
+///     transaction will commit
+#[pyfunction]
+#[pyo3(signature = (store, catalog, path, table_name, schema=None, has_header=true, delimiter=",", infer_rows=Some(1000), transaction_manager=None, tx_id=None, max_rows_per_chunk=65536, metadata=None))]
+#[allow(clippy::too_many_arguments)]
+fn import_csv(
+    py: Python<'_>,
+    store: &PyChunkStore,
+    catalog: &PyCatalog,
+    path: &str,
+    table_name: &str,
+    schema: Option<&Bound<'_, PyAny>>,
+    has_header: bool,
+    delimiter: &str,
+    infer_rows: Option<usize>,
+    transaction_manager: Option<PyRef<'_, PyTransactionManager>>,
+    tx_id: Option<u64>,
+    max_rows_per_chunk: usize,
+    metadata: Option<HashMap<String, String>>,
+) -> PyResult<PyTableVersion> {
+    let options = import_options(schema, infer_rows, has_header, delimiter)?;
+    let tx = import_tx(&transaction_manager, tx_id)?;
+    let writer = import_writer(store, catalog, max_rows_per_chunk, metadata);
+    py.detach(|| writer.import_csv(path, table_name, &options, tx))
+        .map(PyTableVersion::from)
+        .map_err(table_err_to_py)
+}
+
+/// Import a JSON Lines file (one object per line) as the next version of
+/// a table. Arguments are those of `import_csv` without the CSV-only
+/// `has_header` and `delimiter`.
+///
+/// Returns:
+///     PyTableVersion: The committed version, or the version the
+///     transaction will commit
+#[pyfunction]
+#[pyo3(signature = (store, catalog, path, table_name, schema=None, infer_rows=Some(1000), transaction_manager=None, tx_id=None, max_rows_per_chunk=65536, metadata=None))]
+#[allow(clippy::too_many_arguments)]
+fn import_jsonl(
+    py: Python<'_>,
+    store: &PyChunkStore,
+    catalog: &PyCatalog,
+    path: &str,
+    table_name: &str,
+    schema: Option<&Bound<'_, PyAny>>,
+    infer_rows: Option<usize>,
+    transaction_manager: Option<PyRef<'_, PyTransactionManager>>,
+    tx_id: Option<u64>,
+    max_rows_per_chunk: usize,
+    metadata: Option<HashMap<String, String>>,
+) -> PyResult<PyTableVersion> {
+    let options = import_options(schema, infer_rows, true, ",")?;
+    let tx = import_tx(&transaction_manager, tx_id)?;
+    let writer = import_writer(store, catalog, max_rows_per_chunk, metadata);
+    py.detach(|| writer.import_jsonl(path, table_name, &options, tx))
+        .map(PyTableVersion::from)
+        .map_err(table_err_to_py)
+}
+
+/// Write a version of a table (default: latest) to a Parquet file.
+///
+/// The file is written beside `path` and renamed into place.
+///
+/// Returns:
+///     PyTableVersion: The exported version
+#[pyfunction]
+#[pyo3(signature = (store, catalog, table_name, path, version=None))]
+fn export_parquet(
+    py: Python<'_>,
+    store: &PyChunkStore,
+    catalog: &PyCatalog,
+    table_name: &str,
+    path: &str,
+    version: Option<u64>,
+) -> PyResult<PyTableVersion> {
+    let reader = TableReader::new(&store.inner, &catalog.inner);
+    py.detach(|| reader.export_parquet(table_name, version, path))
+        .map(PyTableVersion::from)
+        .map_err(table_err_to_py)
+}
+
+/// Manifest of a snapshot-consistent export (see `export_tables`).
+#[pyclass(name = "ExportManifest")]
+#[derive(Clone)]
+struct PyExportManifest {
+    #[pyo3(get)]
+    branch: String,
+    /// Latest transaction reflected in the exported versions
+    #[pyo3(get)]
+    tx_id: Option<u64>,
+    /// "parquet" or "csv"
+    #[pyo3(get)]
+    format: String,
+    #[pyo3(get)]
+    exported_at: i64,
+    /// Exported version of every table
+    #[pyo3(get)]
+    tables: HashMap<String, u64>,
+    /// File of every table, relative to the export directory
+    #[pyo3(get)]
+    files: HashMap<String, String>,
+    /// Row count of every table
+    #[pyo3(get)]
+    rows: HashMap<String, u64>,
+    /// Transaction recording the export in the changelog, if any
+    #[pyo3(get)]
+    event_tx_id: Option<u64>,
+}
+
+#[pymethods]
+impl PyExportManifest {
+    fn __repr__(&self) -> String {
+        format!(
+            "ExportManifest(branch={}, tx_id={:?}, format={}, tables={})",
+            self.branch,
+            self.tx_id,
+            self.format,
+            self.tables.len(),
+        )
+    }
+}
+
+impl From<ExportManifest> for PyExportManifest {
+    fn from(manifest: ExportManifest) -> Self {
+        let mut tables = HashMap::new();
+        let mut files = HashMap::new();
+        let mut rows = HashMap::new();
+        for (name, table) in manifest.tables {
+            tables.insert(name.clone(), table.version);
+            files.insert(name.clone(), table.file);
+            rows.insert(name, table.rows);
+        }
+        Self {
+            branch: manifest.branch,
+            tx_id: manifest.tx_id,
+            format: manifest.format.to_string(),
+            exported_at: manifest.exported_at,
+            tables,
+            files,
+            rows,
+            event_tx_id: manifest.event_tx_id,
+        }
+    }
+}
+
+/// Export several tables from one snapshot of a branch.
+///
+/// Every table is read at the same point in time and written to `dest` as
+/// one file; a `_manifest.json` written last records the transaction id
+/// and table versions.
+///
+/// Args:
+///     store: PyChunkStore holding the chunks
+///     transaction_manager: PyTransactionManager to take the snapshot from
+///     dest: Directory to export into (created if needed)
+///     tables: Tables to export (default: all tables on the branch)
+///     branch: Branch to export (default: the default branch)
+///     format: "parquet" or "csv"
+///     record_event: Also commit an empty transaction describing the
+///         export, so it appears in the changelog
+///
+/// Returns:
+///     ExportManifest: The written manifest
+#[pyfunction]
+#[pyo3(signature = (store, transaction_manager, dest, tables=None, branch=None, format="parquet", record_event=false))]
+#[allow(clippy::too_many_arguments)]
+fn export_tables(
+    py: Python<'_>,
+    store: &PyChunkStore,
+    transaction_manager: &PyTransactionManager,
+    dest: &str,
+    tables: Option<Vec<String>>,
+    branch: Option<&str>,
+    format: &str,
+    record_event: bool,
+) -> PyResult<PyExportManifest> {
+    let format: ExportFormat = format.parse().map_err(PyValueError::new_err)?;
+    let tables = tables.unwrap_or_default();
+    let names: Vec<&str> = tables.iter().map(String::as_str).collect();
+    let exporter = TableExporter::new(&transaction_manager.inner, &store.inner).with_changelog_event(record_event);
+    py.detach(|| exporter.export_tables(branch, &names, format, dest))
+        .map(PyExportManifest::from)
+        .map_err(table_err_to_py)
+}
+
+/// A PyPredicateFilter or a list of them
+fn extract_filters(filter: &Bound<'_, PyAny>) -> PyResult<Vec<PredicateFilter>> {
+    match filter.extract::<PyPredicateFilter>() {
+        Ok(f) => Ok(vec![f.into_inner()]),
+        Err(_) => Ok(filter
+            .extract::<Vec<PyPredicateFilter>>()?
+            .into_iter()
+            .map(PyPredicateFilter::into_inner)
+            .collect()),
+    }
+}
+
+/// Remove the rows matching a filter from every version of a table.
+///
+/// Versions keep their numbers, so branches, tags and pins read the
+/// redacted data. The redaction is recorded in
+/// `PyCatalog.redaction_history`; the replaced chunks stay in the store
+/// until `sweep_chunks` removes them. Fails if a version with matching
+/// rows is under legal hold.
+///
+/// Args:
+///     store: PyChunkStore holding the chunks
+///     catalog: PyCatalog holding the table
+///     table_name: Table to redact
+///     filter: PyPredicateFilter or list of them, combined with AND
+///     reason: Why the rows are removed, kept in the history
+///
+/// Returns:
+///     Dict[int, int]: Rows removed per rewritten version
+///
+/// Example:
+///     >>> redact(store, catalog, "users", PyPredicateFilter("id", "eq", 7),
+///     ...        reason="erasure request #42")
+///     {1: 1, 3: 1}
+#[pyfunction]
+#[pyo3(signature = (store, catalog, table_name, filter, reason=None))]
+fn redact(
+    py: Python<'_>,
+    store: &PyChunkStore,
+    catalog: &PyCatalog,
+    table_name: &str,
+    filter: Bound<'_, PyAny>,
+    reason: Option<String>,
+) -> PyResult<HashMap<u64, u64>> {
+    let filters = extract_filters(&filter)?;
+    let mut redactor = Redactor::new(&store.inner, &catalog.inner);
+    if let Some(reason) = reason {
+        redactor = redactor.with_reason(reason);
+    }
+    py.detach(|| redactor.redact(table_name, &filters))
+        .map(|record| record.versions.into_iter().map(|v| (v.version, v.rows_removed)).collect())
+        .map_err(table_err_to_py)
+}
+
+/// Convert QueryError to appropriate Python exception
+fn query_err_to_py(e: QueryError) -> PyErr {
+    let info = ErrorAttrs::of(&e);
+    let err = match e {
+        QueryError::Table(e) => return table_err_to_py(e),
+        QueryError::Catalog(e) => return catalog_err_to_py(e),
+        QueryError::Branch(e) => return branch_err_to_py(e),
+        QueryError::Ref(e) => return ref_err_to_py(e),
+        e @ QueryError::NotPinned { .. } => PyIOError::new_err(e.to_string()),
+        e @ QueryError::Arrow(_) => PyRuntimeError::new_err(sanitize_error_message(&e.to_string())),
+        e => PyValueError::new_err(e.to_string()),
+    };
+    info.attach(err)
+}
+
+/// Convert RefError to appropriate Python exception
+fn ref_err_to_py(e: RefError) -> PyErr {
+    let info = ErrorAttrs::of(&e);
+    let err = match e {
+        RefError::Catalog(e) => return catalog_err_to_py(e),
+        RefError::Branch(e) => return branch_err_to_py(e),
+        RefError::Transaction(e) => return tx_err_to_py(e),
+        e @ (RefError::AncestorNotFound { .. } | RefError::TableNotInRef { .. }) => PyIOError::new_err(e.to_string()),
+        e => PyValueError::new_err(e.to_string()),
+    };
+    info.attach(err)
+}
+
+/// Resolve a ref to the table versions it names.
+///
+/// Refs: `main` (branch head), `main~3` (three head changes earlier),
+/// `users@v7` (one table version), `tag:release-1`, `tx:1234` (the
+/// versions a committed transaction read, with its writes applied) and
+/// `repo@12` (a repository snapshot).
+///
+/// Args:
+///     catalog: PyCatalog holding the versions
+///     reference: The ref to resolve
+///     branch_manager: PyBranchManager for branch and snapshot refs
+///     transaction_manager: PyTransactionManager for `tx:` refs
+///
+/// Returns:
+///     Dict[str, int]: table -> version
+///
+/// Raises:
+///     ValueError: If the ref is malformed or needs a missing manager
+///     IOError: If what it names does not exist
+///
+/// Example:
+///     >>> resolve_ref(catalog, "main~2", branch_manager=branches)
+///     {'users': 4, 'orders': 2}
+#[pyfunction]
+#[pyo3(signature = (catalog, reference, branch_manager=None, transaction_manager=None))]
+fn resolve_ref(
+    catalog: &PyCatalog,
+    reference: &str,
+    branch_manager: Option<&PyBranchManager>,
+    transaction_manager: Option<&PyTransactionManager>,
+) -> PyResult<HashMap<String, u64>> {
+    let spec = RefSpec::parse(reference).map_err(ref_err_to_py)?;
+    let mut resolver = RefResolver::new(&catalog.inner);
+    if let Some(branches) = branch_manager {
+        resolver = resolver.with_branches(&branches.inner);
+    }
+    if let Some(manager) = transaction_manager {
+        resolver = resolver.with_transactions(manager.inner.log());
+    }
+    resolver.resolve(&spec).map(|r| r.versions).map_err(ref_err_to_py)
+}
+
+/// Read the rows of one table version matching a projection and filters.
+///
+/// The version is the one at `at` or in `pin`, else `version` (checked
+/// against `branch` when both are given), else the head of `branch`, else
+/// the latest. Branch reads need `branch_manager`.
+///
+/// Args:
+///     store: PyChunkStore holding the chunks
+///     catalog: PyCatalog holding the version
+///     table_name: Table to read
+///     version: Version to read
+///     branch: Branch whose head to read
+///     pin: Pin whose version of the table to read
+///     columns: Columns to return, in order (default: all)
+///     filter: PyPredicateFilter or list of them, combined with AND
+///     branch_manager: PyBranchManager resolving `branch`
+///     at: Ref to read at (`main~2`, `tag:release-1`, `users@v7`, ...);
+///         not combinable with `version`, `branch` or `pin`
+///
+/// Returns:
+///     List[pyarrow.RecordBatch]: Matching rows; at least one batch
+///
+/// Example:
+///     >>> batches = query(store, catalog, "users", branch="dev",
+///     ...                 columns=["id", "name"],
+///     ...                 filter=PyPredicateFilter("age", "gt", 21),
+///     ...                 branch_manager=branches)
+#[pyfunction]
+#[pyo3(signature = (store, catalog, table_name, version=None, branch=None, pin=None, columns=None, filter=None, branch_manager=None, at=None))]
+#[allow(clippy::too_many_arguments)]
+fn query<'py>(
+    py: Python<'py>,
+    store: &PyChunkStore,
+    catalog: &PyCatalog,
+    table_name: &str,
+    version: Option<u64>,
+    branch: Option<String>,
+    pin: Option<String>,
+    columns: Option<Vec<String>>,
+    filter: Option<Bound<'py, PyAny>>,
+    branch_manager: Option<PyRef<'py, PyBranchManager>>,
+    at: Option<&str>,
+) -> PyResult<Vec<Bound<'py, PyAny>>> {
+    let mut q = Query::new(table_name);
+    q.version = version;
+    q.branch = branch;
+    q.pin = pin;
+    q.at = at.map(RefSpec::parse).transpose().map_err(ref_err_to_py)?;
+    q.columns = columns;
+    if let Some(filter) = filter {
+        q.filters = extract_filters(&filter)?;
+    }
+    let mut engine = QueryEngine::new(&store.inner, &catalog.inner);
+    if let Some(branches) = &branch_manager {
+        engine = engine.with_branches(&branches.inner);
+    }
+    let result = py.detach(|| engine.query(&q)).map_err(query_err_to_py)?;
+    result
+        .batches
+        .iter()
+        .map(|batch| batch.to_pyarrow(py).map_err(|e| PyValueError::new_err(sanitize_error_message(&e.to_string()))))
+        .collect()
+}
+
+/// Schema hash `write_arrow` records for data with this schema.
+#[pyfunction]
+fn arrow_schema_hash(batch: Bound<'_, PyAny>) -> PyResult<String> {
+    let batches = record_batches(&batch)?;
+    let first = batches
+        .first()
+        .ok_or_else(|| PyValueError::new_err("No record batches"))?;
+    Ok(rhizo_core::schema_hash(&first.schema()))
+}
+
 // =============================================================================
 // Phase R.2: Predicate Pushdown Types
 // =============================================================================
@@ -701,15 +1652,81 @@ impl PyPredicateFilter {
 
 #[pyclass]
 struct PyChunkStore {
-    inner: ChunkStore,
+    inner: Arc<ChunkStore>,
 }
 
 #[pymethods]
 impl PyChunkStore {
+    /// Open a chunk store.
+    ///
+    /// Args:
+    ///     path: Directory holding the chunks
+    ///     compression: Codec for new chunks: "zstd", "lz4" or None.
+    ///         With a codec set, chunks are readable whatever codec they
+    ///         were written with; with None, compressed chunks are returned
+    ///         as stored, so keep a codec once a store holds them.
+    ///     compression_level: zstd level (default 3)
+    ///     max_delta_chain: Longest chain of delta chunks `put_delta` builds
+    ///         before storing a chunk in full (default 8, 0 disables deltas)
+    ///     integrity_journal: Journal the size and CRC32 of chunk files as
+    ///         they are written, for `scrub_chunks`
+    ///     max_open_files: Most chunk files held open at once. Operations
+    ///         past the limit wait for a free handle, then raise OSError.
+    ///     chunking: How `put_chunked` splits streams: "cdc" (content-defined,
+    ///         the default) or "fixed"
+    ///     chunk_size: Average chunk size for "cdc" (default 1 MiB; chunks
+    ///         range from a quarter to four times it), exact size for "fixed"
+    ///     memory_cache_bytes: Bytes of recently read chunks kept in memory
+    ///         for `get` and `get_verified` (default 0, disabled)
     #[new]
-    fn new(path: &str) -> PyResult<Self> {
-        let inner = ChunkStore::new(path).map_err(chunk_err_to_py)?;
-        Ok(Self { inner })
+    #[pyo3(signature = (path, compression=None, compression_level=None, max_delta_chain=None, integrity_journal=false, max_open_files=None, chunking=None, chunk_size=None, memory_cache_bytes=0))]
+    #[allow(clippy::too_many_arguments)]
+    fn new(
+        path: &str,
+        compression: Option<&str>,
+        compression_level: Option<i32>,
+        max_delta_chain: Option<u32>,
+        integrity_journal: bool,
+        max_open_files: Option<usize>,
+        chunking: Option<&str>,
+        chunk_size: Option<usize>,
+        memory_cache_bytes: u64,
+    ) -> PyResult<Self> {
+        let compression = match compression.map(str::to_ascii_lowercase).as_deref() {
+            None | Some("none") => ChunkCompression::None,
+            Some("zstd") => compression_level.map_or_else(ChunkCompression::zstd, |level| ChunkCompression::Zstd { level }),
+            Some("lz4") => ChunkCompression::Lz4,
+            Some(other) => {
+                return Err(PyValueError::new_err(format!(
+                    "Unknown chunk compression: {} (expected 'zstd', 'lz4' or None)",
+                    other
+                )))
+            }
+        };
+        let chunker = match chunking.map(str::to_ascii_lowercase).as_deref() {
+            None | Some("cdc") => {
+                let avg = chunk_size.unwrap_or(rhizo_core::chunk_store::DEFAULT_AVG_CHUNK_SIZE);
+                Chunker::content_defined(avg / 4, avg, avg.saturating_mul(4))
+            }
+            Some("fixed") => Chunker::fixed(chunk_size.unwrap_or(rhizo_core::chunk_store::DEFAULT_AVG_CHUNK_SIZE)),
+            Some(other) => {
+                return Err(PyValueError::new_err(format!(
+                    "Unknown chunking: {} (expected 'cdc' or 'fixed')",
+                    other
+                )))
+            }
+        };
+        let mut config = ChunkStoreConfig::new()
+            .with_compression(compression)
+            .with_integrity_journal(integrity_journal)
+            .with_max_open_files(max_open_files)
+            .with_chunker(chunker)
+            .with_memory_cache(memory_cache_bytes);
+        if let Some(max_delta_chain) = max_delta_chain {
+            config = config.with_max_delta_chain(max_delta_chain);
+        }
+        let inner = ChunkStore::with_config(path, config).map_err(chunk_err_to_py)?;
+        Ok(Self { inner: Arc::new(inner) })
     }
 
     fn put(&self, data: &[u8]) -> PyResult<String> {
@@ -730,6 +1747,19 @@ impl PyChunkStore {
         self.inner.exists(hash).map_err(chunk_err_to_py)
     }
 
+    /// Memory cache counters: hits, misses, entries, bytes and capacity
+    /// (all zero without a cache).
+    fn memory_cache_stats(&self) -> HashMap<String, u64> {
+        let stats = self.inner.memory_cache_stats();
+        HashMap::from([
+            ("hits".to_string(), stats.hits),
+            ("misses".to_string(), stats.misses),
+            ("entries".to_string(), stats.entries as u64),
+            ("bytes".to_string(), stats.bytes),
+            ("capacity".to_string(), stats.capacity),
+        ])
+    }
+
     fn delete(&self, hash: &str) -> PyResult<()> {
         self.inner.delete(hash).map_err(chunk_err_to_py)
     }
@@ -802,6 +1832,35 @@ impl PyChunkStore {
         self.inner.get_batch_verified(&refs).map_err(chunk_err_to_py)
     }
 
+    /// Store many chunks at once, writing each distinct chunk once.
+    ///
+    /// Hashing and writes run in parallel with the GIL released.
+    ///
+    /// Returns:
+    ///     List of hashes in the same order as input chunks
+    fn put_many(&self, py: Python<'_>, chunks: Vec<Vec<u8>>) -> PyResult<Vec<String>> {
+        let refs: Vec<&[u8]> = chunks.iter().map(|c| c.as_slice()).collect();
+        py.detach(|| self.inner.put_many(&refs).map_err(chunk_err_to_py))
+    }
+
+    /// Read many chunks at once, in parallel, reading each distinct hash once.
+    ///
+    /// Returns:
+    ///     List of chunk data (bytes) in the same order as input hashes
+    fn get_many(&self, py: Python<'_>, hashes: Vec<String>) -> PyResult<Vec<Vec<u8>>> {
+        let refs: Vec<&str> = hashes.iter().map(|s| s.as_str()).collect();
+        py.detach(|| self.inner.get_many(&refs).map_err(chunk_err_to_py))
+    }
+
+    /// Check many chunks for existence at once, in parallel.
+    ///
+    /// Returns:
+    ///     List of booleans in the same order as input hashes
+    fn exists_many(&self, py: Python<'_>, hashes: Vec<String>) -> PyResult<Vec<bool>> {
+        let refs: Vec<&str> = hashes.iter().map(|s| s.as_str()).collect();
+        py.detach(|| self.inner.exists_many(&refs).map_err(chunk_err_to_py))
+    }
+
     // =========================================================================
     // Memory-Mapped Operations
     // =========================================================================
@@ -843,21 +1902,116 @@ impl PyChunkStore {
     }
 
     // =========================================================================
-    // Garbage Collection
+    // Delta Chunks
     // =========================================================================
 
-    /// List all chunk hashes stored on disk.
-    fn list_chunk_hashes(&self) -> PyResult<Vec<String>> {
-        self.inner.list_chunk_hashes().map_err(chunk_err_to_py)
-    }
-
-    /// Delete unreferenced chunks from the store.
-    ///
-    /// Compares all on-disk chunks against the provided set of referenced
-    /// hashes and deletes any chunk not in the set.
+    /// Store a chunk as a binary delta against `base_hash` when worthwhile.
     ///
-    /// Args:
-    ///     referenced_hashes: List of hash strings that are still in use.
+    /// Returns the same hash as `put(data)`; reads reconstruct the chunk
+    /// transparently. Falls back to a full chunk when the delta would not
+    /// save at least half the space or the delta chain would grow past
+    /// `max_delta_chain`.
+    ///
+    /// Raises:
+    ///     IOError: If the base chunk is not stored
+    fn put_delta(&self, py: Python<'_>, data: &[u8], base_hash: &str) -> PyResult<String> {
+        py.detach(|| self.inner.put_delta(data, base_hash))
+            .map_err(chunk_err_to_py)
+    }
+
+    /// `(base_hash, depth)` of a delta chunk, or None for a full chunk.
+    fn delta_info(&self, hash: &str) -> PyResult<Option<(String, u32)>> {
+        let info = self.inner.delta_info(hash).map_err(chunk_err_to_py)?;
+        Ok(info.map(|info| (info.base, info.depth)))
+    }
+
+    /// Store a delta chunk in full. Returns False if it was not a delta.
+    fn materialize(&self, hash: &str) -> PyResult<bool> {
+        self.inner.materialize(hash).map_err(chunk_err_to_py)
+    }
+
+    // =========================================================================
+    // Streaming Operations
+    // =========================================================================
+
+    /// Store a chunk read from a binary file-like object.
+    ///
+    /// The data is read in blocks, hashed incrementally and spilled to a
+    /// temporary file, so chunks larger than memory can be stored.
+    ///
+    /// Args:
+    ///     file: Object with a `read(size)` method returning bytes
+    ///
+    /// Returns:
+    ///     Hash of the chunk (the same as `put` of the same bytes)
+    ///
+    /// Example:
+    ///     >>> with open("big.parquet", "rb") as f:
+    ///     ...     h = store.put_stream(f)
+    fn put_stream(&self, file: Bound<'_, PyAny>) -> PyResult<String> {
+        let mut reader = PyFileReader { file, error: None };
+        let result = self.inner.put_stream(&mut reader);
+        match reader.error {
+            Some(err) => Err(err),
+            None => result.map_err(chunk_err_to_py),
+        }
+    }
+
+    /// Split a binary file-like object (or bytes) into chunks and store them.
+    ///
+    /// Chunk boundaries follow the store's `chunking`; with the default
+    /// content-defined chunking, storing an edited file again only adds the
+    /// chunks around the edit.
+    ///
+    /// Args:
+    ///     file: Object with a `read(size)` method returning bytes, or bytes
+    ///
+    /// Returns:
+    ///     List of chunk hashes in order; their concatenation is the data
+    ///
+    /// Example:
+    ///     >>> with open("events.csv", "rb") as f:
+    ///     ...     hashes = store.put_chunked(f)
+    fn put_chunked(&self, file: Bound<'_, PyAny>) -> PyResult<Vec<String>> {
+        if let Ok(data) = file.extract::<&[u8]>() {
+            return self.inner.put_chunked(data).map_err(chunk_err_to_py);
+        }
+        let mut reader = PyFileReader { file, error: None };
+        let result = self.inner.put_chunked(&mut reader);
+        match reader.error {
+            Some(err) => Err(err),
+            None => result.map_err(chunk_err_to_py),
+        }
+    }
+
+    /// Open a chunk as a binary file-like object.
+    ///
+    /// Reads stream from disk instead of loading the chunk into memory.
+    ///
+    /// Example:
+    ///     >>> with store.get_stream(h) as f:
+    ///     ...     shutil.copyfileobj(f, out)
+    fn get_stream(&self, hash: &str) -> PyResult<PyChunkReader> {
+        let reader = self.inner.get_stream(hash).map_err(chunk_err_to_py)?;
+        Ok(PyChunkReader { inner: Some(reader) })
+    }
+
+    // =========================================================================
+    // Garbage Collection
+    // =========================================================================
+
+    /// List all chunk hashes stored on disk.
+    fn list_chunk_hashes(&self) -> PyResult<Vec<String>> {
+        self.inner.list_chunk_hashes().map_err(chunk_err_to_py)
+    }
+
+    /// Delete unreferenced chunks from the store.
+    ///
+    /// Compares all on-disk chunks against the provided set of referenced
+    /// hashes and deletes any chunk not in the set.
+    ///
+    /// Args:
+    ///     referenced_hashes: List of hash strings that are still in use.
     ///
     /// Returns:
     ///     Tuple of (deleted_count, failed_count).
@@ -875,6 +2029,113 @@ impl PyChunkStore {
     }
 }
 
+/// `Read` over the `read` method of a Python file-like object
+struct PyFileReader<'py> {
+    file: Bound<'py, PyAny>,
+    /// Exception raised by the file object, re-raised as is
+    error: Option<PyErr>,
+}
+
+impl Read for PyFileReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let result = self.file.call_method1("read", (buf.len(),)).and_then(|data| {
+            let data: &[u8] = data.extract()?;
+            if data.len() > buf.len() {
+                return Err(PyValueError::new_err(format!(
+                    "read({}) returned {} bytes",
+                    buf.len(),
+                    data.len()
+                )));
+            }
+            buf[..data.len()].copy_from_slice(data);
+            Ok(data.len())
+        });
+        result.map_err(|err| {
+            let io_err = std::io::Error::other(err.to_string());
+            self.error = Some(err);
+            io_err
+        })
+    }
+}
+
+/// Binary file-like reader over a chunk, from `PyChunkStore.get_stream`.
+#[pyclass(name = "ChunkReader")]
+struct PyChunkReader {
+    /// None once closed
+    inner: Option<ChunkReader>,
+}
+
+impl PyChunkReader {
+    fn reader(&mut self) -> PyResult<&mut ChunkReader> {
+        self.inner
+            .as_mut()
+            .ok_or_else(|| PyValueError::new_err("I/O operation on closed chunk reader"))
+    }
+}
+
+#[pymethods]
+impl PyChunkReader {
+    /// Read up to `size` bytes (all remaining bytes if negative).
+    ///
+    /// Raises:
+    ///     ValueError: If the store verifies reads and the chunk does not
+    ///         match its hash (raised at the end of the chunk)
+    #[pyo3(signature = (size=-1))]
+    fn read(&mut self, py: Python<'_>, size: i64) -> PyResult<Vec<u8>> {
+        let reader = self.reader()?;
+        let mut buf = Vec::new();
+        let result = py.detach(|| match u64::try_from(size) {
+            Ok(limit) => reader.take(limit).read_to_end(&mut buf),
+            Err(_) => reader.read_to_end(&mut buf),
+        });
+        match result {
+            Ok(_) => Ok(buf),
+            Err(e) => Err(match e.into_inner().map(|inner| inner.downcast::<ChunkStoreError>()) {
+                Some(Ok(chunk_err)) => chunk_err_to_py(*chunk_err),
+                Some(Err(other)) => PyIOError::new_err(other.to_string()),
+                None => PyIOError::new_err("chunk read failed"),
+            }),
+        }
+    }
+
+    /// Logical size of the chunk in bytes
+    #[getter]
+    fn size(&mut self) -> PyResult<u64> {
+        Ok(self.reader()?.len())
+    }
+
+    fn readable(&self) -> bool {
+        true
+    }
+
+    #[getter]
+    fn closed(&self) -> bool {
+        self.inner.is_none()
+    }
+
+    /// Release the file (and any decoded temporary copy).
+    fn close(&mut self) {
+        self.inner = None;
+    }
+
+    fn __enter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    #[pyo3(signature = (*_args))]
+    fn __exit__(&mut self, _args: &Bound<'_, pyo3::types::PyTuple>) -> bool {
+        self.close();
+        false
+    }
+
+    fn __repr__(&self) -> String {
+        match &self.inner {
+            Some(reader) => format!("ChunkReader(size={})", reader.len()),
+            None => "ChunkReader(closed)".to_string(),
+        }
+    }
+}
+
 #[pyclass]
 #[derive(Clone)]
 struct PyTableVersion {
@@ -941,17 +2202,91 @@ impl From<PyTableVersion> for TableVersion {
     }
 }
 
+/// A change in a table's schema lineage
+#[pyclass]
+#[derive(Clone)]
+struct PySchemaChange {
+    #[pyo3(get)]
+    version: u64,
+    #[pyo3(get)]
+    schema_hash: String,
+    #[pyo3(get)]
+    previous_hash: Option<String>,
+    /// (name, data_type, nullable) per column, if the schema was registered
+    #[pyo3(get)]
+    fields: Option<Vec<(String, String, bool)>>,
+    /// (from_hash, to_hash, description) of the migration, if any
+    #[pyo3(get)]
+    migration: Option<(String, String, Option<String>)>,
+    #[pyo3(get)]
+    created_at: i64,
+}
+
+#[pymethods]
+impl PySchemaChange {
+    fn __repr__(&self) -> String {
+        format!(
+            "PySchemaChange(version={}, schema_hash={:?}, migration={})",
+            self.version,
+            self.schema_hash,
+            self.migration.is_some()
+        )
+    }
+}
+
+impl From<SchemaChange> for PySchemaChange {
+    fn from(c: SchemaChange) -> Self {
+        Self {
+            version: c.version,
+            schema_hash: c.schema_hash,
+            previous_hash: c.previous_hash,
+            fields: c
+                .schema
+                .map(|s| s.fields.into_iter().map(|f| (f.name, f.data_type, f.nullable)).collect()),
+            migration: c.migration.map(|m| (m.from_hash, m.to_hash, m.description)),
+            created_at: c.created_at,
+        }
+    }
+}
+
 #[pyclass]
 struct PyCatalog {
     inner: FileCatalog,
+    branches: Option<BranchManager>,
+}
+
+impl PyCatalog {
+    /// Resolve a branch for branch-scoped queries
+    fn branch(&self, name: &str) -> PyResult<Branch> {
+        let bm = self.branches.as_ref().ok_or_else(|| {
+            PyValueError::new_err("Branch-scoped queries require PyCatalog(path, branch_path=...)")
+        })?;
+        bm.get(name).map_err(branch_err_to_py)
+    }
 }
 
 #[pymethods]
 impl PyCatalog {
+    /// Open a catalog.
+    ///
+    /// Args:
+    ///     path: Catalog directory
+    ///     branch_path: Optional branch manager directory, enabling the
+    ///         `branch` argument of list_tables() and get_version()
+    ///     lock_timeout_ms: How long a commit waits for a table lock before
+    ///         raising TimeoutError (default 30 seconds)
     #[new]
-    fn new(path: &str) -> PyResult<Self> {
-        let inner = FileCatalog::new(path).map_err(catalog_err_to_py)?;
-        Ok(Self { inner })
+    #[pyo3(signature = (path, branch_path=None, lock_timeout_ms=None))]
+    fn new(path: &str, branch_path: Option<&str>, lock_timeout_ms: Option<u64>) -> PyResult<Self> {
+        let mut inner = FileCatalog::new(path).map_err(catalog_err_to_py)?;
+        if let Some(ms) = lock_timeout_ms {
+            inner = inner.with_lock_timeout(std::time::Duration::from_millis(ms));
+        }
+        let branches = match branch_path {
+            Some(p) => Some(BranchManager::new(p).map_err(branch_err_to_py)?),
+            None => None,
+        };
+        Ok(Self { inner, branches })
     }
 
     fn commit(&self, version: PyTableVersion) -> PyResult<u64> {
@@ -985,10 +2320,50 @@ impl PyCatalog {
             .map_err(catalog_err_to_py)
     }
 
-    #[pyo3(signature = (table_name, version=None))]
-    fn get_version(&self, table_name: &str, version: Option<u64>) -> PyResult<PyTableVersion> {
+    /// Get a table version.
+    ///
+    /// Args:
+    ///     table_name: Table to read
+    ///     version: Specific version (default: latest, or the branch head)
+    ///     branch: Resolve as seen from this branch; tables created on other
+    ///         branches and not merged here raise "Table not found"
+    #[pyo3(signature = (table_name, version=None, branch=None))]
+    fn get_version(
+        &self,
+        table_name: &str,
+        version: Option<u64>,
+        branch: Option<&str>,
+    ) -> PyResult<PyTableVersion> {
+        match branch {
+            Some(b) => {
+                let branch = self.branch(b)?;
+                self.inner.get_version_on_branch(table_name, version, &branch)
+            }
+            None => self.inner.get_version(table_name, version),
+        }
+        .map(|tv| tv.into())
+        .map_err(catalog_err_to_py)
+    }
+
+    /// Get a table version as recorded by a repository snapshot.
+    ///
+    /// Args:
+    ///     table_name: Table to resolve
+    ///     snapshot: Snapshot id, as an int or a "repo@N" string
+    ///
+    /// Raises IOError if the snapshot does not exist or the table was not
+    /// on the snapshot's branch. Requires PyCatalog(path, branch_path=...).
+    fn get_version_as_of(
+        &self,
+        table_name: &str,
+        snapshot: &Bound<'_, PyAny>,
+    ) -> PyResult<PyTableVersion> {
+        let bm = self.branches.as_ref().ok_or_else(|| {
+            PyValueError::new_err("Snapshot reads require PyCatalog(path, branch_path=...)")
+        })?;
+        let snapshot = bm.snapshot(snapshot_id(snapshot)?).map_err(branch_err_to_py)?;
         self.inner
-            .get_version(table_name, version)
+            .get_version_as_of(table_name, &snapshot)
             .map(|tv| tv.into())
             .map_err(catalog_err_to_py)
     }
@@ -997,8 +2372,80 @@ impl PyCatalog {
         self.inner.list_versions(table_name).map_err(catalog_err_to_py)
     }
 
-    fn list_tables(&self) -> PyResult<Vec<String>> {
-        self.inner.list_tables().map_err(catalog_err_to_py)
+    /// How the table's schema hash changed across its versions, oldest first.
+    fn schema_history(&self, table_name: &str) -> PyResult<Vec<PySchemaChange>> {
+        let history = self.inner.schema_history(table_name).map_err(catalog_err_to_py)?;
+        Ok(history.into_iter().map(PySchemaChange::from).collect())
+    }
+
+    /// Algebraic annotations carried by a table version (the latest by default).
+    #[pyo3(signature = (table_name, version=None))]
+    fn algebraic_schema(&self, table_name: &str, version: Option<u64>) -> PyResult<Option<PyTableAlgebraicSchema>> {
+        let schema = self.inner.algebraic_schema(table_name, version).map_err(catalog_err_to_py)?;
+        Ok(schema.map(|inner| PyTableAlgebraicSchema { inner }))
+    }
+
+    /// Commit the next version of a table with the latest version's data and
+    /// new algebraic annotations (None clears them). Returns the version.
+    #[pyo3(signature = (table_name, schema=None))]
+    fn set_algebraic_schema(&self, table_name: &str, schema: Option<PyRef<'_, PyTableAlgebraicSchema>>) -> PyResult<u64> {
+        self.inner
+            .set_algebraic_schema(table_name, schema.as_ref().map(|s| &s.inner))
+            .map_err(catalog_err_to_py)
+    }
+
+    /// Registry of the annotations carried by the given table versions,
+    /// e.g. a branch's heads.
+    fn algebraic_registry(&self, versions: HashMap<String, u64>) -> PyResult<PyAlgebraicSchemaRegistry> {
+        let inner = self.inner.algebraic_registry(&versions).map_err(catalog_err_to_py)?;
+        Ok(PyAlgebraicSchemaRegistry { inner })
+    }
+
+    /// List tables, optionally restricted to a namespace prefix or branch.
+    ///
+    /// Args:
+    ///     prefix: Namespace such as "sales" or "org/team". Matches on
+    ///         segment boundaries, so "sales" does not match "salesforce.x".
+    ///     branch: Only list tables visible on this branch. Without it, all
+    ///         tables are listed regardless of the branch that created them.
+    #[pyo3(signature = (prefix=None, branch=None))]
+    fn list_tables(&self, prefix: Option<&str>, branch: Option<&str>) -> PyResult<Vec<String>> {
+        let tables = match branch {
+            Some(b) => {
+                let branch = self.branch(b)?;
+                self.inner.list_tables_on_branch(&branch)
+            }
+            None => self.inner.list_tables(),
+        }
+        .map_err(catalog_err_to_py)?;
+
+        Ok(match prefix {
+            Some(p) => tables
+                .into_iter()
+                .filter(|t| rhizo_core::catalog::namespace::in_namespace(t, p))
+                .collect(),
+            None => tables,
+        })
+    }
+
+    /// Branch a table was created on, or None for unscoped tables.
+    fn table_origin(&self, table_name: &str) -> PyResult<Option<String>> {
+        self.inner.table_origin(table_name).map_err(catalog_err_to_py)
+    }
+
+    /// Scope a new table to the branch it is being created on.
+    ///
+    /// Call before the table's first commit. The first recorded origin wins.
+    fn set_table_origin(&self, table_name: &str, branch: &str) -> PyResult<()> {
+        self.inner.set_table_origin(table_name, branch).map_err(catalog_err_to_py)
+    }
+
+    /// List tables matching a wildcard pattern.
+    ///
+    /// "*" matches within one namespace segment, "**" across segments and
+    /// "?" a single character (e.g. "sales.*", "org/**/events").
+    fn list_tables_matching(&self, pattern: &str) -> PyResult<Vec<String>> {
+        self.inner.list_tables_matching(pattern).map_err(catalog_err_to_py)
     }
 
     /// Delete a specific version of a table.
@@ -1012,6 +2459,40 @@ impl PyCatalog {
             .map_err(catalog_err_to_py)
     }
 
+    /// Remove a table and all of its versions.
+    ///
+    /// Returns the removed versions, oldest first. Branch heads and the
+    /// changelog are not updated; use PyTransactionManager.drop_table for
+    /// that. Raises ValueError if any version is pinned, tagged or held.
+    fn drop_table(&self, table_name: &str) -> PyResult<Vec<PyTableVersion>> {
+        self.inner
+            .drop_table(table_name)
+            .map(|versions| versions.into_iter().map(Into::into).collect())
+            .map_err(catalog_err_to_py)
+    }
+
+    /// Rename a table, keeping its history. Returns its latest version.
+    ///
+    /// Raises ValueError if the new name is taken or any version is
+    /// pinned, tagged or held.
+    fn rename_table(&self, old_name: &str, new_name: &str) -> PyResult<u64> {
+        self.inner
+            .rename_table(old_name, new_name)
+            .map_err(catalog_err_to_py)
+    }
+
+    /// Recover commit intents left behind by crashed writers.
+    ///
+    /// Returns the intents whose commit never completed; their chunks are
+    /// orphaned and safe to sweep. Intents of commits that did complete are
+    /// removed silently. Every intent file is consumed.
+    fn recover_pending_commits(&self) -> PyResult<Vec<PyPendingCommit>> {
+        self.inner
+            .recover_pending_commits()
+            .map(|pending| pending.into_iter().map(PyPendingCommit::from).collect())
+            .map_err(catalog_err_to_py)
+    }
+
     /// Get all chunk hashes referenced by all versions of all tables.
     fn get_all_referenced_chunk_hashes(&self) -> PyResult<Vec<String>> {
         self.inner
@@ -1019,278 +2500,2643 @@ impl PyCatalog {
             .map(|set| set.into_iter().collect())
             .map_err(catalog_err_to_py)
     }
-}
-
-// ============================================================================
-// Branch Classes
-// ============================================================================
 
-#[pyclass]
-#[derive(Clone)]
-struct PyBranch {
-    #[pyo3(get)]
-    name: String,
-    #[pyo3(get)]
-    head: HashMap<String, u64>,
-    #[pyo3(get)]
-    created_at: i64,
-    #[pyo3(get)]
-    parent_branch: Option<String>,
-    #[pyo3(get)]
-    description: Option<String>,
-}
+    /// Pin exact table versions under a name for later reproduction.
+    ///
+    /// Pins are immutable and their versions cannot be deleted (including by
+    /// garbage collection) until the pin is removed with delete_pin().
+    ///
+    /// Args:
+    ///     name: Pin name (e.g. "churn-model/run-42")
+    ///     versions: Mapping of table name -> version
+    ///     description: Optional note (experiment id, git commit, ...)
+    #[pyo3(signature = (name, versions, description=None))]
+    fn pin(
+        &self,
+        name: &str,
+        versions: HashMap<String, u64>,
+        description: Option<String>,
+    ) -> PyResult<PyPin> {
+        self.inner
+            .pin(name, versions, description)
+            .map(|p| p.into())
+            .map_err(catalog_err_to_py)
+    }
 
-impl From<Branch> for PyBranch {
-    fn from(b: Branch) -> Self {
-        Self {
-            name: b.name,
-            head: b.head,
-            created_at: b.created_at,
-            parent_branch: b.parent_branch,
-            description: b.description,
-        }
+    /// Look up a pin by name.
+    fn resolve_pin(&self, name: &str) -> PyResult<PyPin> {
+        self.inner.resolve_pin(name).map(|p| p.into()).map_err(catalog_err_to_py)
     }
-}
 
-#[pyclass]
-#[derive(Clone)]
-struct PyBranchDiff {
-    #[pyo3(get)]
-    source_branch: String,
-    #[pyo3(get)]
-    target_branch: String,
-    #[pyo3(get)]
-    unchanged: Vec<String>,
-    #[pyo3(get)]
-    modified: Vec<(String, u64, u64)>,
-    #[pyo3(get)]
-    added_in_source: Vec<(String, u64)>,
-    #[pyo3(get)]
-    added_in_target: Vec<(String, u64)>,
-    #[pyo3(get)]
-    has_conflicts: bool,
-    #[pyo3(get)]
-    source_only_changes: Vec<(String, u64, u64)>,
-    #[pyo3(get)]
-    target_only_changes: Vec<(String, u64, u64)>,
-}
+    /// List all pins, sorted by name.
+    fn list_pins(&self) -> PyResult<Vec<PyPin>> {
+        self.inner
+            .list_pins()
+            .map(|pins| pins.into_iter().map(|p| p.into()).collect())
+            .map_err(catalog_err_to_py)
+    }
 
-impl From<BranchDiff> for PyBranchDiff {
-    fn from(d: BranchDiff) -> Self {
-        Self {
-            source_branch: d.source_branch,
-            target_branch: d.target_branch,
-            unchanged: d.unchanged,
-            modified: d.modified,
-            added_in_source: d.added_in_source,
-            added_in_target: d.added_in_target,
-            has_conflicts: d.has_conflicts,
-            source_only_changes: d.source_only_changes,
-            target_only_changes: d.target_only_changes,
-        }
+    /// Delete a pin, releasing its versions for garbage collection.
+    fn delete_pin(&self, name: &str) -> PyResult<PyPin> {
+        self.inner.delete_pin(name).map(|p| p.into()).map_err(catalog_err_to_py)
     }
-}
 
-#[pyclass]
-struct PyBranchManager {
-    inner: BranchManager,
-}
+    /// Define a view: a named query that reads like a table.
+    ///
+    /// Args:
+    ///     name: View name (table naming rules; must not be a table)
+    ///     sql: Query defining the view
+    ///     sources: Tables and views the query reads
+    ///     materialize: Let readers cache results per source-version set
+    ///     description: Optional note
+    ///     replace: Redefine the view if it exists
+    #[pyo3(signature = (name, sql, sources, materialize=false, description=None, replace=false))]
+    fn create_view(
+        &self,
+        name: &str,
+        sql: &str,
+        sources: Vec<String>,
+        materialize: bool,
+        description: Option<String>,
+        replace: bool,
+    ) -> PyResult<PyView> {
+        let mut view = View::new(name, sql, sources).with_materialize(materialize);
+        if let Some(description) = description {
+            view = view.with_description(description);
+        }
+        self.inner
+            .create_view(view, replace)
+            .map(|v| v.into())
+            .map_err(catalog_err_to_py)
+    }
 
-#[pymethods]
-impl PyBranchManager {
-    #[new]
-    fn new(path: &str) -> PyResult<Self> {
-        let inner = BranchManager::new(path).map_err(branch_err_to_py)?;
-        Ok(Self { inner })
+    /// Look up a view by name.
+    fn get_view(&self, name: &str) -> PyResult<PyView> {
+        self.inner.get_view(name).map(|v| v.into()).map_err(catalog_err_to_py)
     }
 
-    /// Create a new branch from an existing branch.
-    /// If from_branch is None, creates from the default branch (main).
-    #[pyo3(signature = (name, from_branch=None, description=None))]
-    fn create(
-        &self,
-        name: &str,
-        from_branch: Option<&str>,
-        description: Option<&str>,
-    ) -> PyResult<PyBranch> {
+    /// Check whether a view exists.
+    fn view_exists(&self, name: &str) -> bool {
+        self.inner.view_exists(name)
+    }
+
+    /// List all views, sorted by name.
+    fn list_views(&self) -> PyResult<Vec<PyView>> {
         self.inner
-            .create(name, from_branch, description)
-            .map(|b| b.into())
-            .map_err(branch_err_to_py)
+            .list_views()
+            .map(|views| views.into_iter().map(|v| v.into()).collect())
+            .map_err(catalog_err_to_py)
     }
 
-    /// Get a branch by name.
-    fn get(&self, name: &str) -> PyResult<PyBranch> {
-        self.inner.get(name).map(|b| b.into()).map_err(branch_err_to_py)
+    /// Delete a view, returning its definition.
+    fn drop_view(&self, name: &str) -> PyResult<PyView> {
+        self.inner.drop_view(name).map(|v| v.into()).map_err(catalog_err_to_py)
     }
 
-    /// List all branch names.
-    fn list(&self) -> PyResult<Vec<String>> {
-        self.inner.list().map_err(branch_err_to_py)
+    /// Tables a view ultimately reads, through nested views (sorted).
+    fn view_base_tables(&self, name: &str) -> PyResult<Vec<String>> {
+        self.inner.view_base_tables(name).map_err(catalog_err_to_py)
     }
 
-    /// Delete a branch. Cannot delete the default branch.
-    fn delete(&self, name: &str) -> PyResult<()> {
-        self.inner.delete(name).map_err(branch_err_to_py)
+    /// Get all pinned versions as table name -> list of versions.
+    fn pinned_versions(&self) -> PyResult<HashMap<String, Vec<u64>>> {
+        self.inner
+            .pinned_versions()
+            .map(|pinned| {
+                pinned
+                    .into_iter()
+                    .map(|(table, versions)| {
+                        let mut versions: Vec<u64> = versions.into_iter().collect();
+                        versions.sort_unstable();
+                        (table, versions)
+                    })
+                    .collect()
+            })
+            .map_err(catalog_err_to_py)
     }
 
-    /// Update the head pointer for a table on a branch.
-    fn update_head(&self, branch_name: &str, table_name: &str, version: u64) -> PyResult<()> {
+    /// Place a legal hold on a table version (no-op if already held).
+    #[pyo3(signature = (table_name, version, reason=None))]
+    fn hold(&self, table_name: &str, version: u64, reason: Option<String>) -> PyResult<PyLegalHold> {
         self.inner
-            .update_head(branch_name, table_name, version)
-            .map_err(branch_err_to_py)
+            .hold(table_name, version, reason)
+            .map(PyLegalHold::from)
+            .map_err(catalog_err_to_py)
     }
 
-    /// Get the version of a table on a branch.
-    fn get_table_version(&self, branch_name: &str, table_name: &str) -> PyResult<Option<u64>> {
+    /// Release the legal hold on a table version.
+    fn release_hold(&self, table_name: &str, version: u64) -> PyResult<PyLegalHold> {
         self.inner
-            .get_table_version(branch_name, table_name)
-            .map_err(branch_err_to_py)
+            .release_hold(table_name, version)
+            .map(PyLegalHold::from)
+            .map_err(catalog_err_to_py)
     }
 
-    /// Compare two branches.
-    fn diff(&self, source: &str, target: &str) -> PyResult<PyBranchDiff> {
+    /// The process holding a table's commit lock, or None when it is free.
+    fn lock_owner(&self, table_name: &str) -> PyResult<Option<PyLockOwner>> {
         self.inner
-            .diff(source, target)
-            .map(|d| d.into())
-            .map_err(branch_err_to_py)
+            .lock_owner(table_name)
+            .map(|owner| owner.map(PyLockOwner::from))
+            .map_err(catalog_err_to_py)
     }
 
-    /// Check if a fast-forward merge is possible.
-    fn can_fast_forward(&self, source: &str, target: &str) -> PyResult<bool> {
+    /// Break a table's commit lock left by a process that no longer runs.
+    ///
+    /// Raises ValueError if the holder may still be alive, unless `force`.
+    /// Returns the owner the lock recorded, or None if it was free.
+    #[pyo3(signature = (table_name, force=false))]
+    fn break_lock(&self, table_name: &str, force: bool) -> PyResult<Option<PyLockOwner>> {
         self.inner
-            .can_fast_forward(source, target)
-            .map_err(branch_err_to_py)
+            .break_lock(table_name, force)
+            .map(|owner| owner.map(PyLockOwner::from))
+            .map_err(catalog_err_to_py)
     }
 
-    /// Merge source branch into target branch (fast-forward only).
-    fn merge(&self, source: &str, into: &str) -> PyResult<()> {
+    /// List all legal holds, sorted by table and version.
+    fn list_holds(&self) -> PyResult<Vec<PyLegalHold>> {
         self.inner
-            .merge_fast_forward(source, into)
-            .map_err(branch_err_to_py)
+            .list_holds()
+            .map(|holds| holds.into_iter().map(PyLegalHold::from).collect())
+            .map_err(catalog_err_to_py)
     }
 
-    /// Get the default branch name.
-    fn get_default(&self) -> PyResult<Option<String>> {
-        self.inner.get_default().map_err(branch_err_to_py)
+    /// Get all held versions as table name -> list of versions.
+    fn held_versions(&self) -> PyResult<HashMap<String, Vec<u64>>> {
+        self.inner
+            .held_versions()
+            .map(|held| {
+                held.into_iter()
+                    .map(|(table, versions)| {
+                        let mut versions: Vec<u64> = versions.into_iter().collect();
+                        versions.sort_unstable();
+                        (table, versions)
+                    })
+                    .collect()
+            })
+            .map_err(catalog_err_to_py)
     }
 
-    /// Set the default branch.
-    fn set_default(&self, name: &str) -> PyResult<()> {
-        self.inner.set_default(name).map_err(branch_err_to_py)
+    /// Set a table's retention policy; with neither rule given, clear it.
+    ///
+    /// A version is kept if it is among the newest `keep_last` or younger
+    /// than `keep_newer_than_secs`; the latest version is always kept.
+    /// Policies are applied by `prune_versions`.
+    #[pyo3(signature = (table_name, keep_last=None, keep_newer_than_secs=None))]
+    fn set_retention_policy(
+        &self,
+        table_name: &str,
+        keep_last: Option<usize>,
+        keep_newer_than_secs: Option<u64>,
+    ) -> PyResult<()> {
+        let policy = RetentionPolicy { keep_last, keep_newer_than_secs };
+        let policy = if policy.keeps_all() { None } else { Some(policy) };
+        self.inner
+            .set_retention_policy(table_name, policy)
+            .map_err(catalog_err_to_py)
+    }
+
+    /// A table's retention policy as a dict (`keep_last`,
+    /// `keep_newer_than_secs`), or None if it has none.
+    fn retention_policy(&self, table_name: &str) -> PyResult<Option<HashMap<String, Option<u64>>>> {
+        let policy = self.inner.retention_policy(table_name).map_err(catalog_err_to_py)?;
+        Ok(policy.map(|p| {
+            HashMap::from([
+                ("keep_last".to_string(), p.keep_last.map(|n| n as u64)),
+                ("keep_newer_than_secs".to_string(), p.keep_newer_than_secs),
+            ])
+        }))
+    }
+
+    /// Versions removed from a table by past prunes, as
+    /// (versions, pruned_at) pairs, oldest first.
+    fn prune_history(&self, table_name: &str) -> PyResult<Vec<(Vec<u64>, i64)>> {
+        self.inner
+            .prune_history(table_name)
+            .map(|history| history.into_iter().map(|r| (r.versions, r.pruned_at)).collect())
+            .map_err(catalog_err_to_py)
+    }
+
+    /// Versions folded by past squashes, as (base_version, versions,
+    /// squashed_at) tuples, oldest first.
+    fn squash_history(&self, table_name: &str) -> PyResult<Vec<(u64, Vec<u64>, i64)>> {
+        self.inner
+            .squash_history(table_name)
+            .map(|history| history.into_iter().map(|r| (r.base_version, r.versions, r.squashed_at)).collect())
+            .map_err(catalog_err_to_py)
+    }
+
+    /// Past redactions of a table, as (predicate, reason, versions,
+    /// rows_removed, redacted_at) tuples, oldest first.
+    #[allow(clippy::type_complexity)]
+    fn redaction_history(&self, table_name: &str) -> PyResult<Vec<(String, Option<String>, Vec<u64>, u64, i64)>> {
+        self.inner
+            .redaction_history(table_name)
+            .map(|history| {
+                history
+                    .into_iter()
+                    .map(|r| {
+                        let rows = r.rows_removed();
+                        let versions = r.versions.iter().map(|v| v.version).collect();
+                        (r.predicate, r.reason, versions, rows, r.redacted_at)
+                    })
+                    .collect()
+            })
+            .map_err(catalog_err_to_py)
+    }
+
+    /// The base version a squashed version was folded into, or None.
+    fn squashed_into(&self, table_name: &str, version: u64) -> PyResult<Option<u64>> {
+        self.inner
+            .squashed_into(table_name, version)
+            .map_err(catalog_err_to_py)
     }
 }
 
-// ============================================================================
-// Transaction Classes
-// ============================================================================
+#[pyclass]
+#[derive(Clone)]
+struct PyLegalHold {
+    #[pyo3(get)]
+    table_name: String,
+    #[pyo3(get)]
+    version: u64,
+    #[pyo3(get)]
+    reason: Option<String>,
+    #[pyo3(get)]
+    created_at: i64,
+}
 
-/// Convert TransactionError to appropriate Python exception
-fn tx_err_to_py(e: TransactionError) -> PyErr {
-    match e {
-        TransactionError::TransactionNotFound(id) => {
-            PyValueError::new_err(format!("Transaction not found: {}", id))
-        }
-        TransactionError::TransactionNotActive(id) => {
-            PyValueError::new_err(format!("Transaction {} is not active", id))
-        }
-        TransactionError::AlreadyCommitted(id) => {
-            PyValueError::new_err(format!("Transaction {} already committed", id))
-        }
-        TransactionError::AlreadyAborted(id) => {
-            PyValueError::new_err(format!("Transaction {} already aborted", id))
-        }
-        TransactionError::WriteConflict(tables) => {
-            PyValueError::new_err(format!("Write conflict on tables: {:?}", tables))
+#[pymethods]
+impl PyLegalHold {
+    fn __repr__(&self) -> String {
+        format!("PyLegalHold(table={:?}, version={})", self.table_name, self.version)
+    }
+}
+
+impl From<LegalHold> for PyLegalHold {
+    fn from(h: LegalHold) -> Self {
+        Self {
+            table_name: h.table_name,
+            version: h.version,
+            reason: h.reason,
+            created_at: h.created_at,
         }
-        TransactionError::SnapshotConflict { table, read_version, current_version } => {
-            PyValueError::new_err(format!(
-                "Snapshot conflict: {} was v{}, now v{}",
-                table, read_version, current_version
-            ))
+    }
+}
+
+/// The process recorded as holding a catalog lock.
+#[pyclass]
+#[derive(Clone)]
+struct PyLockOwner {
+    #[pyo3(get)]
+    pid: u32,
+    #[pyo3(get)]
+    host: String,
+    #[pyo3(get)]
+    acquired_at: i64,
+    inner: LockOwner,
+}
+
+#[pymethods]
+impl PyLockOwner {
+    /// Whether the holder still runs; None if that cannot be told from here
+    fn is_alive(&self) -> Option<bool> {
+        self.inner.is_alive()
+    }
+
+    fn is_stale(&self) -> bool {
+        self.inner.is_stale()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("PyLockOwner(pid={}, host={:?})", self.pid, self.host)
+    }
+}
+
+impl From<LockOwner> for PyLockOwner {
+    fn from(owner: LockOwner) -> Self {
+        Self {
+            pid: owner.pid,
+            host: owner.host.clone(),
+            acquired_at: owner.acquired_at,
+            inner: owner,
         }
-        TransactionError::Io(e) => PyIOError::new_err(sanitize_io_error(&e)),
-        TransactionError::Json(e) => PyValueError::new_err(format!("JSON error: {}", sanitize_error_message(&e.to_string()))),
-        TransactionError::CatalogError(msg) => PyIOError::new_err(format!("Catalog error: {}", sanitize_error_message(&msg))),
-        TransactionError::BranchError(msg) => PyIOError::new_err(format!("Branch error: {}", sanitize_error_message(&msg))),
-        _ => PyRuntimeError::new_err(sanitize_error_message(&e.to_string())),
     }
 }
 
+/// An incomplete commit found by `PyCatalog.recover_pending_commits`.
 #[pyclass]
 #[derive(Clone)]
-struct PyTransactionInfo {
+struct PyPendingCommit {
     #[pyo3(get)]
-    tx_id: u64,
+    intent_id: String,
     #[pyo3(get)]
-    epoch_id: u64,
+    table_name: String,
+    /// Chunks written for the commit that no version references
     #[pyo3(get)]
-    status: String,
+    chunk_hashes: Vec<String>,
     #[pyo3(get)]
-    branch: String,
+    created_at: i64,
+}
+
+#[pymethods]
+impl PyPendingCommit {
+    fn __repr__(&self) -> String {
+        format!(
+            "PyPendingCommit(table={:?}, chunks={})",
+            self.table_name,
+            self.chunk_hashes.len()
+        )
+    }
+}
+
+impl From<PendingCommit> for PyPendingCommit {
+    fn from(p: PendingCommit) -> Self {
+        Self {
+            intent_id: p.intent_id,
+            table_name: p.table_name,
+            chunk_hashes: p.chunk_hashes,
+            created_at: p.created_at,
+        }
+    }
+}
+
+#[pyclass]
+#[derive(Clone)]
+struct PyPin {
     #[pyo3(get)]
-    started_at: i64,
+    name: String,
     #[pyo3(get)]
-    committed_at: Option<i64>,
+    versions: HashMap<String, u64>,
     #[pyo3(get)]
-    read_snapshot: HashMap<String, u64>,
+    created_at: i64,
     #[pyo3(get)]
-    written_tables: Vec<String>,
+    description: Option<String>,
 }
 
-impl From<TransactionRecord> for PyTransactionInfo {
-    fn from(tx: TransactionRecord) -> Self {
-        let written = tx.written_tables().into_iter().map(|s| s.to_string()).collect();
+#[pymethods]
+impl PyPin {
+    fn __repr__(&self) -> String {
+        format!("PyPin(name={:?}, tables={})", self.name, self.versions.len())
+    }
+}
+
+impl From<Pin> for PyPin {
+    fn from(p: Pin) -> Self {
         Self {
-            tx_id: tx.tx_id,
-            epoch_id: tx.epoch_id,
-            status: format!("{}", tx.status),
-            branch: tx.branch,
-            started_at: tx.started_at,
-            committed_at: tx.committed_at,
-            read_snapshot: tx.read_snapshot,
-            written_tables: written,
+            name: p.name,
+            versions: p.versions,
+            created_at: p.created_at,
+            description: p.description,
         }
     }
 }
 
+// ============================================================================
+// Tag Classes
+// ============================================================================
+
 #[pyclass]
 #[derive(Clone)]
-struct PyRecoveryReport {
+struct PyTag {
     #[pyo3(get)]
-    last_committed_epoch: Option<u64>,
+    name: String,
     #[pyo3(get)]
-    replayed: Vec<u64>,
+    versions: HashMap<String, u64>,
     #[pyo3(get)]
-    rolled_back: Vec<u64>,
+    created_at: i64,
     #[pyo3(get)]
-    already_aborted: Vec<u64>,
+    source_branch: Option<String>,
     #[pyo3(get)]
-    already_committed: Vec<u64>,
+    message: Option<String>,
+}
+
+/// A named query that reads like a table
+#[pyclass]
+#[derive(Clone)]
+struct PyView {
     #[pyo3(get)]
-    warnings: Vec<String>,
+    name: String,
     #[pyo3(get)]
-    errors: Vec<String>,
+    sql: String,
+    #[pyo3(get)]
+    sources: Vec<String>,
+    #[pyo3(get)]
+    materialize: bool,
+    #[pyo3(get)]
+    created_at: i64,
+    #[pyo3(get)]
+    description: Option<String>,
+}
+
+#[pymethods]
+impl PyView {
+    /// Cache key for a set of base-table versions
+    #[staticmethod]
+    fn materialization_key(source_versions: HashMap<String, u64>) -> String {
+        View::materialization_key(&source_versions)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("PyView(name={:?}, sources={:?})", self.name, self.sources)
+    }
+}
+
+impl From<View> for PyView {
+    fn from(v: View) -> Self {
+        Self {
+            name: v.name,
+            sql: v.sql,
+            sources: v.sources,
+            materialize: v.materialize,
+            created_at: v.created_at,
+            description: v.description,
+        }
+    }
+}
+
+#[pymethods]
+impl PyTag {
+    fn __repr__(&self) -> String {
+        format!("PyTag(name={:?}, tables={})", self.name, self.versions.len())
+    }
+}
+
+impl From<Tag> for PyTag {
+    fn from(t: Tag) -> Self {
+        Self {
+            name: t.name,
+            versions: t.versions,
+            created_at: t.created_at,
+            source_branch: t.source_branch,
+            message: t.message,
+        }
+    }
+}
+
+/// Named, immutable release points over table versions.
+///
+/// Tags never move once created; their versions cannot be deleted until
+/// the tag is.
+#[pyclass]
+struct PyTagManager {
+    inner: TagManager,
+    branches: Option<BranchManager>,
+}
+
+#[pymethods]
+impl PyTagManager {
+    /// Open the tags of a catalog.
+    ///
+    /// Args:
+    ///     catalog_path: Catalog directory
+    ///     branch_path: Optional branch manager directory, enabling
+    ///         create(..., branch=...)
+    #[new]
+    #[pyo3(signature = (catalog_path, branch_path=None))]
+    fn new(catalog_path: &str, branch_path: Option<&str>) -> PyResult<Self> {
+        let catalog = FileCatalog::new(catalog_path).map_err(catalog_err_to_py)?;
+        let branches = match branch_path {
+            Some(p) => Some(BranchManager::new(p).map_err(branch_err_to_py)?),
+            None => None,
+        };
+        Ok(Self {
+            inner: TagManager::new(Arc::new(catalog)),
+            branches,
+        })
+    }
+
+    /// Create a tag.
+    ///
+    /// Tags `versions` if given, else the head of `branch` if given, else
+    /// the latest version of every table.
+    ///
+    /// Args:
+    ///     name: Tag name (e.g. "v2024-q3")
+    ///     versions: Mapping of table name -> version
+    ///     branch: Branch whose head to tag (requires branch_path)
+    ///     message: Optional release notes
+    ///
+    /// Raises:
+    ///     ValueError: If the tag exists, or both versions and branch are given
+    #[pyo3(signature = (name, versions=None, branch=None, message=None))]
+    fn create(
+        &self,
+        name: &str,
+        versions: Option<HashMap<String, u64>>,
+        branch: Option<&str>,
+        message: Option<&str>,
+    ) -> PyResult<PyTag> {
+        let tag = match (versions, branch) {
+            (Some(_), Some(_)) => {
+                return Err(PyValueError::new_err("Pass either versions or branch, not both"));
+            }
+            (Some(versions), None) => self.inner.create(name, versions, message),
+            (None, Some(branch)) => {
+                let bm = self.branches.as_ref().ok_or_else(|| {
+                    PyValueError::new_err("Tagging a branch requires PyTagManager(path, branch_path=...)")
+                })?;
+                let branch = bm.get(branch).map_err(branch_err_to_py)?;
+                self.inner.create_from_branch(name, &branch, message)
+            }
+            (None, None) => self.inner.create_from_latest(name, message),
+        };
+        tag.map(|t| t.into()).map_err(catalog_err_to_py)
+    }
+
+    /// Look up a tag by name.
+    fn get(&self, name: &str) -> PyResult<PyTag> {
+        self.inner.get(name).map(|t| t.into()).map_err(catalog_err_to_py)
+    }
+
+    /// List all tags, sorted by name.
+    fn list(&self) -> PyResult<Vec<PyTag>> {
+        self.inner
+            .list()
+            .map(|tags| tags.into_iter().map(|t| t.into()).collect())
+            .map_err(catalog_err_to_py)
+    }
+
+    /// Delete a tag, releasing its versions. Returns the deleted tag.
+    fn delete(&self, name: &str) -> PyResult<PyTag> {
+        self.inner.delete(name).map(|t| t.into()).map_err(catalog_err_to_py)
+    }
+
+    /// The version of a table recorded by a tag.
+    fn get_version(&self, tag: &str, table_name: &str) -> PyResult<PyTableVersion> {
+        self.inner
+            .get_version(tag, table_name)
+            .map(|v| v.into())
+            .map_err(catalog_err_to_py)
+    }
+}
+
+// ============================================================================
+// Branch Classes
+// ============================================================================
+
+#[pyclass]
+#[derive(Clone)]
+struct PyBranch {
+    #[pyo3(get)]
+    name: String,
+    #[pyo3(get)]
+    head: HashMap<String, u64>,
+    #[pyo3(get)]
+    created_at: i64,
+    #[pyo3(get)]
+    parent_branch: Option<String>,
+    #[pyo3(get)]
+    description: Option<String>,
+    #[pyo3(get)]
+    immutable: bool,
+    #[pyo3(get)]
+    snapshot_id: Option<u64>,
+}
+
+/// A branch archived after being merged
+#[pyclass]
+#[derive(Clone)]
+struct PyArchivedBranch {
+    #[pyo3(get)]
+    branch: PyBranch,
+    #[pyo3(get)]
+    merged_into: String,
+    #[pyo3(get)]
+    archived_at: i64,
+}
+
+#[pymethods]
+impl PyArchivedBranch {
+    fn __repr__(&self) -> String {
+        format!(
+            "PyArchivedBranch(branch={:?}, merged_into={:?})",
+            self.branch.name, self.merged_into
+        )
+    }
+}
+
+impl From<ArchivedBranch> for PyArchivedBranch {
+    fn from(a: ArchivedBranch) -> Self {
+        Self {
+            branch: a.branch.into(),
+            merged_into: a.merged_into,
+            archived_at: a.archived_at,
+        }
+    }
+}
+
+/// Dangling branch heads found (and possibly rolled back) by repair_branch
+#[pyclass]
+#[derive(Clone)]
+struct PyBranchRepair {
+    #[pyo3(get)]
+    branch: String,
+    /// (table, missing version, version rolled back to or None)
+    #[pyo3(get)]
+    dangling: Vec<(String, u64, Option<u64>)>,
+    #[pyo3(get)]
+    repaired: bool,
+}
+
+#[pymethods]
+impl PyBranchRepair {
+    #[getter]
+    fn is_clean(&self) -> bool {
+        self.dangling.is_empty()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "PyBranchRepair(branch={:?}, dangling={}, repaired={})",
+            self.branch,
+            self.dangling.len(),
+            self.repaired
+        )
+    }
+}
+
+fn dangling_tuples(heads: Vec<DanglingHead>) -> Vec<(String, u64, Option<u64>)> {
+    heads.into_iter().map(|h| (h.table, h.version, h.rollback_to)).collect()
+}
+
+impl From<BranchRepair> for PyBranchRepair {
+    fn from(r: BranchRepair) -> Self {
+        Self {
+            branch: r.branch,
+            dangling: dangling_tuples(r.dangling),
+            repaired: r.repaired,
+        }
+    }
+}
+
+impl From<Branch> for PyBranch {
+    fn from(b: Branch) -> Self {
+        Self {
+            name: b.name,
+            head: b.head,
+            created_at: b.created_at,
+            parent_branch: b.parent_branch,
+            description: b.description,
+            immutable: b.immutable,
+            snapshot_id: b.snapshot_id,
+        }
+    }
+}
+
+/// A branch's table versions under a repository-wide snapshot id
+#[pyclass]
+#[derive(Clone)]
+struct PyRepoSnapshot {
+    #[pyo3(get)]
+    id: u64,
+    #[pyo3(get)]
+    branch: String,
+    #[pyo3(get)]
+    versions: HashMap<String, u64>,
+    #[pyo3(get)]
+    created_at: i64,
+}
+
+#[pymethods]
+impl PyRepoSnapshot {
+    fn __str__(&self) -> String {
+        format!("repo@{}", self.id)
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "PyRepoSnapshot(id={}, branch={:?}, tables={})",
+            self.id,
+            self.branch,
+            self.versions.len()
+        )
+    }
+}
+
+/// Snapshot id from an int or a "repo@N" string
+fn snapshot_id(snapshot_ref: &Bound<'_, PyAny>) -> PyResult<u64> {
+    if let Ok(id) = snapshot_ref.extract::<u64>() {
+        return Ok(id);
+    }
+    let reference: String = snapshot_ref.extract()?;
+    RepoSnapshot::parse_ref(&reference).ok_or_else(|| {
+        PyValueError::new_err(format!("Invalid snapshot reference: {:?}", reference))
+    })
+}
+
+impl From<RepoSnapshot> for PyRepoSnapshot {
+    fn from(s: RepoSnapshot) -> Self {
+        Self {
+            id: s.id,
+            branch: s.branch,
+            versions: s.versions,
+            created_at: s.created_at,
+        }
+    }
+}
+
+/// One head change in a branch's history
+#[pyclass]
+#[derive(Clone)]
+struct PyBranchCommit {
+    #[pyo3(get)]
+    id: u64,
+    #[pyo3(get)]
+    branch: String,
+    #[pyo3(get)]
+    parents: Vec<u64>,
+    #[pyo3(get)]
+    tx_id: Option<u64>,
+    /// (table, old_version, new_version) per moved head
+    #[pyo3(get)]
+    changes: Vec<(String, Option<u64>, Option<u64>)>,
+    #[pyo3(get)]
+    created_at: i64,
+}
+
+#[pymethods]
+impl PyBranchCommit {
+    fn __repr__(&self) -> String {
+        format!(
+            "PyBranchCommit(id={}, branch={:?}, parents={:?}, changes={})",
+            self.id,
+            self.branch,
+            self.parents,
+            self.changes.len()
+        )
+    }
+}
+
+impl From<BranchCommit> for PyBranchCommit {
+    fn from(c: BranchCommit) -> Self {
+        Self {
+            id: c.id,
+            branch: c.branch,
+            parents: c.parents,
+            tx_id: c.tx_id,
+            changes: c
+                .changes
+                .into_iter()
+                .map(|h| (h.table, h.old_version, h.new_version))
+                .collect(),
+            created_at: c.created_at,
+        }
+    }
+}
+
+/// A merge commit: the target's new heads and both parents
+#[pyclass]
+#[derive(Clone)]
+struct PyMergeRecord {
+    #[pyo3(get)]
+    source: String,
+    #[pyo3(get)]
+    into: String,
+    /// "strict", "prefer-source" or "prefer-target"
+    #[pyo3(get)]
+    strategy: String,
+    #[pyo3(get)]
+    parents: Vec<u64>,
+    #[pyo3(get)]
+    snapshot_id: u64,
+    /// Table -> merged version, for heads the merge moved
+    #[pyo3(get)]
+    versions: HashMap<String, u64>,
+    /// Table -> the target's version before the merge
+    #[pyo3(get)]
+    replaced: HashMap<String, u64>,
+    /// Conflicting tables settled by the strategy
+    #[pyo3(get)]
+    resolved: Vec<String>,
+}
+
+#[pymethods]
+impl PyMergeRecord {
+    fn __repr__(&self) -> String {
+        format!(
+            "PyMergeRecord(source={:?}, into={:?}, strategy={:?}, tables={})",
+            self.source,
+            self.into,
+            self.strategy,
+            self.versions.len()
+        )
+    }
+}
+
+impl From<MergeRecord> for PyMergeRecord {
+    fn from(m: MergeRecord) -> Self {
+        Self {
+            source: m.source,
+            into: m.into,
+            strategy: m.strategy.to_string(),
+            parents: m.parents,
+            snapshot_id: m.snapshot_id,
+            versions: m.versions,
+            replaced: m.replaced,
+            resolved: m.resolved,
+        }
+    }
+}
+
+fn parse_merge_strategy(strategy: &str) -> PyResult<MergeStrategy> {
+    strategy.to_ascii_lowercase().parse().map_err(|_| {
+        PyValueError::new_err(format!(
+            "Unknown merge strategy: {} (expected 'strict', 'prefer-source' or 'prefer-target')",
+            strategy
+        ))
+    })
+}
+
+/// The repository HEAD: which branch is the default
+#[pyclass]
+#[derive(Clone)]
+struct PyHeadRecord {
+    #[pyo3(get)]
+    branch: String,
+    #[pyo3(get)]
+    updated_at: i64,
+    #[pyo3(get)]
+    updated_by: Option<String>,
+    #[pyo3(get)]
+    generation: u64,
+}
+
+impl From<HeadRecord> for PyHeadRecord {
+    fn from(h: HeadRecord) -> Self {
+        Self {
+            branch: h.branch,
+            updated_at: h.updated_at,
+            updated_by: h.updated_by,
+            generation: h.generation,
+        }
+    }
+}
+
+/// A change of HEAD
+#[pyclass]
+#[derive(Clone)]
+struct PyHeadEvent {
+    #[pyo3(get)]
+    previous: Option<String>,
+    #[pyo3(get)]
+    head: PyHeadRecord,
+}
+
+impl From<HeadEvent> for PyHeadEvent {
+    fn from(e: HeadEvent) -> Self {
+        Self {
+            previous: e.previous,
+            head: e.head.into(),
+        }
+    }
+}
+
+/// A workspace's claim on a branch
+#[pyclass]
+#[derive(Clone)]
+struct PyCheckout {
+    #[pyo3(get)]
+    workspace: String,
+    #[pyo3(get)]
+    branch: String,
+    #[pyo3(get)]
+    checked_out_at: i64,
+}
+
+impl From<Checkout> for PyCheckout {
+    fn from(c: Checkout) -> Self {
+        Self {
+            workspace: c.workspace,
+            branch: c.branch,
+            checked_out_at: c.checked_out_at,
+        }
+    }
+}
+
+#[pyclass]
+#[derive(Clone)]
+struct PyBranchDiff {
+    #[pyo3(get)]
+    source_branch: String,
+    #[pyo3(get)]
+    target_branch: String,
+    #[pyo3(get)]
+    unchanged: Vec<String>,
+    #[pyo3(get)]
+    modified: Vec<(String, u64, u64)>,
+    #[pyo3(get)]
+    added_in_source: Vec<(String, u64)>,
+    #[pyo3(get)]
+    added_in_target: Vec<(String, u64)>,
+    #[pyo3(get)]
+    has_conflicts: bool,
+    #[pyo3(get)]
+    source_only_changes: Vec<(String, u64, u64)>,
+    #[pyo3(get)]
+    target_only_changes: Vec<(String, u64, u64)>,
+}
+
+impl From<BranchDiff> for PyBranchDiff {
+    fn from(d: BranchDiff) -> Self {
+        Self {
+            source_branch: d.source_branch,
+            target_branch: d.target_branch,
+            unchanged: d.unchanged,
+            modified: d.modified,
+            added_in_source: d.added_in_source,
+            added_in_target: d.added_in_target,
+            has_conflicts: d.has_conflicts,
+            source_only_changes: d.source_only_changes,
+            target_only_changes: d.target_only_changes,
+        }
+    }
+}
+
+#[pyclass]
+struct PyBranchManager {
+    inner: BranchManager,
+}
+
+#[pymethods]
+impl PyBranchManager {
+    #[new]
+    fn new(path: &str) -> PyResult<Self> {
+        let inner = BranchManager::new(path).map_err(branch_err_to_py)?;
+        Ok(Self { inner })
+    }
+
+    /// Create a new branch from an existing branch.
+    /// If from_branch is None, creates from the default branch (main).
+    #[pyo3(signature = (name, from_branch=None, description=None))]
+    fn create(
+        &self,
+        name: &str,
+        from_branch: Option<&str>,
+        description: Option<&str>,
+    ) -> PyResult<PyBranch> {
+        self.inner
+            .create(name, from_branch, description)
+            .map(|b| b.into())
+            .map_err(branch_err_to_py)
+    }
+
+    /// Create a branch containing only the tables of one namespace.
+    ///
+    /// Args:
+    ///     name: New branch name
+    ///     selector: Namespace prefix ("sales") or wildcard pattern ("sales.*")
+    ///     from_branch: Source branch (default: the default branch)
+    ///     description: Optional description
+    #[pyo3(signature = (name, selector, from_branch=None, description=None))]
+    fn create_for_namespace(
+        &self,
+        name: &str,
+        selector: &str,
+        from_branch: Option<&str>,
+        description: Option<&str>,
+    ) -> PyResult<PyBranch> {
+        self.inner
+            .create_for_namespace(name, from_branch, selector, description)
+            .map(|b| b.into())
+            .map_err(branch_err_to_py)
+    }
+
+    /// Get a branch by name.
+    fn get(&self, name: &str) -> PyResult<PyBranch> {
+        self.inner.get(name).map(|b| b.into()).map_err(branch_err_to_py)
+    }
+
+    /// List all branch names.
+    fn list(&self) -> PyResult<Vec<String>> {
+        self.inner.list().map_err(branch_err_to_py)
+    }
+
+    /// Delete a branch. Cannot delete the default branch or a branch
+    /// checked out by a workspace.
+    fn delete(&self, name: &str) -> PyResult<()> {
+        self.inner.delete(name).map_err(branch_err_to_py)
+    }
+
+    /// Update the head pointer for a table on a branch.
+    fn update_head(&self, branch_name: &str, table_name: &str, version: u64) -> PyResult<()> {
+        self.inner
+            .update_head(branch_name, table_name, version)
+            .map_err(branch_err_to_py)
+    }
+
+    /// Update several table heads on a branch at once.
+    ///
+    /// Returns the repository snapshot recorded for the new heads.
+    fn update_heads(
+        &self,
+        branch_name: &str,
+        versions: HashMap<String, u64>,
+    ) -> PyResult<PyRepoSnapshot> {
+        self.inner
+            .update_heads(branch_name, &versions)
+            .map(Into::into)
+            .map_err(branch_err_to_py)
+    }
+
+    /// Resolve a snapshot id (an int, or a "repo@N" string) to its
+    /// table versions.
+    fn snapshot(&self, snapshot_ref: &Bound<'_, PyAny>) -> PyResult<PyRepoSnapshot> {
+        self.inner
+            .snapshot(snapshot_id(snapshot_ref)?)
+            .map(Into::into)
+            .map_err(branch_err_to_py)
+    }
+
+    /// The snapshot recorded by a branch's latest head change, if any.
+    fn latest_snapshot(&self, branch_name: &str) -> PyResult<Option<PyRepoSnapshot>> {
+        self.inner
+            .latest_snapshot(branch_name)
+            .map(|s| s.map(Into::into))
+            .map_err(branch_err_to_py)
+    }
+
+    /// Snapshots recorded on a branch (or every branch), oldest first.
+    #[pyo3(signature = (branch_name=None))]
+    fn snapshots(&self, branch_name: Option<&str>) -> PyResult<Vec<PyRepoSnapshot>> {
+        self.inner
+            .snapshots(branch_name)
+            .map(|v| v.into_iter().map(Into::into).collect())
+            .map_err(branch_err_to_py)
+    }
+
+    /// A branch's head changes, newest first, at most `limit` of them.
+    #[pyo3(signature = (branch_name, limit=None))]
+    fn history(&self, branch_name: &str, limit: Option<usize>) -> PyResult<Vec<PyBranchCommit>> {
+        self.inner
+            .history(branch_name, limit)
+            .map(|v| v.into_iter().map(Into::into).collect())
+            .map_err(branch_err_to_py)
+    }
+
+    /// The newest commit both branches' heads descend from, if any.
+    fn common_ancestor(&self, a: &str, b: &str) -> PyResult<Option<PyBranchCommit>> {
+        self.inner
+            .common_ancestor(a, b)
+            .map(|c| c.map(Into::into))
+            .map_err(branch_err_to_py)
+    }
+
+    /// Remove a dropped table from every branch (head and fork point).
+    ///
+    /// Returns the names of the updated branches. Raises ValueError,
+    /// changing nothing, if an immutable branch refers to the table.
+    fn remove_table(&self, table_name: &str) -> PyResult<Vec<String>> {
+        self.inner.remove_table(table_name).map_err(branch_err_to_py)
+    }
+
+    /// Point every branch at a table's new name (head and fork point).
+    ///
+    /// Returns the names of the updated branches.
+    fn rename_table(&self, old_name: &str, new_name: &str) -> PyResult<Vec<String>> {
+        self.inner
+            .rename_table(old_name, new_name)
+            .map_err(branch_err_to_py)
+    }
+
+    /// Get the version of a table on a branch.
+    fn get_table_version(&self, branch_name: &str, table_name: &str) -> PyResult<Option<u64>> {
+        self.inner
+            .get_table_version(branch_name, table_name)
+            .map_err(branch_err_to_py)
+    }
+
+    /// Compare two branches.
+    fn diff(&self, source: &str, target: &str) -> PyResult<PyBranchDiff> {
+        self.inner
+            .diff(source, target)
+            .map(|d| d.into())
+            .map_err(branch_err_to_py)
+    }
+
+    /// Check if a fast-forward merge is possible.
+    fn can_fast_forward(&self, source: &str, target: &str) -> PyResult<bool> {
+        self.inner
+            .can_fast_forward(source, target)
+            .map_err(branch_err_to_py)
+    }
+
+    /// Tables a merge of source into target would conflict on.
+    fn merge_conflicts(&self, source: &str, into: &str) -> PyResult<Vec<String>> {
+        self.inner.merge_conflicts(source, into).map_err(branch_err_to_py)
+    }
+
+    /// Merge source branch into target branch.
+    ///
+    /// Args:
+    ///     source: Branch to merge from
+    ///     into: Branch to merge into
+    ///     resolutions: Optional table -> "source" | "target" decisions for
+    ///         tables both branches changed. Unresolved conflicts raise
+    ///         ValueError listing only those tables.
+    ///     delete_source: Delete the source branch if (and only if) the
+    ///         merge lands
+    #[pyo3(signature = (source, into, resolutions=None, delete_source=false))]
+    fn merge(
+        &self,
+        source: &str,
+        into: &str,
+        resolutions: Option<HashMap<String, String>>,
+        delete_source: bool,
+    ) -> PyResult<()> {
+        let resolutions = resolutions
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(table, side)| parse_merge_resolution(&side).map(|r| (table, r)))
+            .collect::<PyResult<HashMap<_, _>>>()?;
+        self.inner
+            .merge_with_resolutions(source, into, &resolutions, delete_source)
+            .map_err(branch_err_to_py)
+    }
+
+    /// Merge source into target as a merge commit with both branches' heads
+    /// as parents, recorded even when no head moves.
+    ///
+    /// Conflicts are settled by `strategy`: "strict" raises ValueError,
+    /// "prefer-source" / "prefer-target" take that side's version.
+    #[pyo3(signature = (source, into, strategy="strict"))]
+    fn merge_commit(&self, source: &str, into: &str, strategy: &str) -> PyResult<PyMergeRecord> {
+        self.inner
+            .merge(source, into, parse_merge_strategy(strategy)?)
+            .map(Into::into)
+            .map_err(branch_err_to_py)
+    }
+
+    /// Merge source into target and archive the source branch in one step.
+    ///
+    /// The source is archived only if the merge lands. Archived branches
+    /// are listed by archived() and can be brought back with unarchive().
+    fn merge_and_archive(&self, source: &str, into: &str) -> PyResult<PyArchivedBranch> {
+        self.inner
+            .merge_and_archive(source, into)
+            .map(Into::into)
+            .map_err(branch_err_to_py)
+    }
+
+    /// Branches archived by merge_and_archive(), oldest first.
+    fn archived(&self) -> PyResult<Vec<PyArchivedBranch>> {
+        self.inner
+            .archived()
+            .map(|a| a.into_iter().map(Into::into).collect())
+            .map_err(branch_err_to_py)
+    }
+
+    /// Heads on a branch that point at versions missing from the catalog,
+    /// as (table, missing version, newest older version or None) tuples.
+    fn dangling_heads(&self, name: &str, catalog: &PyCatalog) -> PyResult<Vec<(String, u64, Option<u64>)>> {
+        self.inner
+            .dangling_heads(name, &catalog.inner)
+            .map(dangling_tuples)
+            .map_err(branch_err_to_py)
+    }
+
+    /// Find dangling heads and, with strategy "rollback", move each to the
+    /// newest existing version below it (dropping the table if none).
+    /// Strategy "report" only lists them.
+    #[pyo3(signature = (name, catalog, strategy="rollback"))]
+    fn repair_branch(&self, name: &str, catalog: &PyCatalog, strategy: &str) -> PyResult<PyBranchRepair> {
+        let strategy = match strategy {
+            "rollback" => RepairStrategy::RollbackToLatestExisting,
+            "report" => RepairStrategy::ReportOnly,
+            other => {
+                return Err(PyValueError::new_err(format!(
+                    "Unknown repair strategy: {} (expected 'rollback' or 'report')",
+                    other
+                )))
+            }
+        };
+        self.inner
+            .repair_branch(name, &catalog.inner, strategy)
+            .map(Into::into)
+            .map_err(branch_err_to_py)
+    }
+
+    /// Bring back the most recently archived branch with this name.
+    fn unarchive(&self, name: &str) -> PyResult<PyBranch> {
+        self.inner.unarchive(name).map(Into::into).map_err(branch_err_to_py)
+    }
+
+    /// Get the default branch name.
+    fn get_default(&self) -> PyResult<Option<String>> {
+        self.inner.get_default().map_err(branch_err_to_py)
+    }
+
+    /// Set the default branch.
+    fn set_default(&self, name: &str) -> PyResult<()> {
+        self.inner.set_default(name).map_err(branch_err_to_py)
+    }
+
+    /// The repository HEAD record, or None if no default branch is set.
+    fn head(&self) -> PyResult<Option<PyHeadRecord>> {
+        self.inner.head().map(|h| h.map(Into::into)).map_err(branch_err_to_py)
+    }
+
+    /// Point HEAD at a branch unconditionally.
+    #[pyo3(signature = (name, updated_by=None))]
+    fn set_head(&self, name: &str, updated_by: Option<&str>) -> PyResult<PyHeadRecord> {
+        self.inner.set_head(name, updated_by).map(Into::into).map_err(branch_err_to_py)
+    }
+
+    /// Point HEAD at a branch only if HEAD is still at expected_generation.
+    ///
+    /// Raises ValueError if another writer moved HEAD first.
+    #[pyo3(signature = (expected_generation, name, updated_by=None))]
+    fn compare_and_set_head(
+        &self,
+        expected_generation: u64,
+        name: &str,
+        updated_by: Option<&str>,
+    ) -> PyResult<PyHeadRecord> {
+        self.inner
+            .compare_and_set_head(expected_generation, name, updated_by)
+            .map(Into::into)
+            .map_err(branch_err_to_py)
+    }
+
+    /// HEAD changes after since_generation, oldest first.
+    #[pyo3(signature = (since_generation=0))]
+    fn head_events(&self, since_generation: u64) -> PyResult<Vec<PyHeadEvent>> {
+        self.inner
+            .head_events(since_generation)
+            .map(|events| events.into_iter().map(Into::into).collect())
+            .map_err(branch_err_to_py)
+    }
+
+    /// Record that a workspace uses a branch (replacing its previous
+    /// checkout). Checked-out branches cannot be deleted.
+    fn checkout(&self, workspace: &str, branch: &str) -> PyResult<PyCheckout> {
+        self.inner.checkout(workspace, branch).map(Into::into).map_err(branch_err_to_py)
+    }
+
+    /// Release a workspace's checkout, returning it if there was one.
+    fn release_checkout(&self, workspace: &str) -> PyResult<Option<PyCheckout>> {
+        self.inner
+            .release_checkout(workspace)
+            .map(|c| c.map(Into::into))
+            .map_err(branch_err_to_py)
+    }
+
+    /// All checkouts, sorted by workspace.
+    fn checkouts(&self) -> PyResult<Vec<PyCheckout>> {
+        self.inner
+            .checkouts()
+            .map(|c| c.into_iter().map(Into::into).collect())
+            .map_err(branch_err_to_py)
+    }
+
+    /// Mark a branch immutable (or mutable again).
+    fn set_immutable(&self, name: &str, immutable: bool) -> PyResult<()> {
+        self.inner.set_immutable(name, immutable).map_err(branch_err_to_py)
+    }
+}
+
+// ============================================================================
+// Repository Filtering
+// ============================================================================
+
+/// Convert FilterError to appropriate Python exception
+fn filter_err_to_py(e: FilterError) -> PyErr {
+    let info = ErrorAttrs::of(&e);
+    let err = match e {
+        FilterError::Catalog(e) => catalog_err_to_py(e),
+        FilterError::ChunkStore(e) => chunk_err_to_py(e),
+        FilterError::Branch(e) => branch_err_to_py(e),
+        FilterError::TableExists(t) => {
+            PyValueError::new_err(format!("Table already exists in destination: {}", t))
+        }
+        FilterError::BranchExists(name) => {
+            PyValueError::new_err(format!("Branch already exists in destination: {}", name))
+        }
+        FilterError::BranchesUnavailable => PyValueError::new_err(
+            "Branch filter requires branch managers for both repositories",
+        ),
+        e => PyRuntimeError::new_err(sanitize_error_message(&e.to_string())),
+    };
+    info.attach(err)
+}
+
+/// Result of a repository filter run.
+#[pyclass(name = "FilterReport")]
+#[derive(Clone)]
+struct PyFilterReport {
+    /// Per table: source version -> destination version
+    #[pyo3(get)]
+    version_map: HashMap<String, HashMap<u64, u64>>,
+    /// Branches written to the destination
+    #[pyo3(get)]
+    branches: Vec<String>,
+    #[pyo3(get)]
+    versions_dropped: usize,
+    #[pyo3(get)]
+    chunks_copied: usize,
+    #[pyo3(get)]
+    bytes_copied: u64,
+}
+
+#[pymethods]
+impl PyFilterReport {
+    /// Tables copied to the destination
+    #[getter]
+    fn tables(&self) -> Vec<String> {
+        let mut tables: Vec<String> = self.version_map.keys().cloned().collect();
+        tables.sort();
+        tables
+    }
+
+    /// Total versions copied to the destination
+    #[getter]
+    fn versions_kept(&self) -> usize {
+        self.version_map.values().map(HashMap::len).sum()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "FilterReport(tables={}, versions_kept={}, versions_dropped={}, branches={:?}, chunks_copied={})",
+            self.version_map.len(),
+            self.versions_kept(),
+            self.versions_dropped,
+            self.branches,
+            self.chunks_copied,
+        )
+    }
+}
+
+impl From<FilterReport> for PyFilterReport {
+    fn from(report: FilterReport) -> Self {
+        Self {
+            version_map: report
+                .version_map
+                .into_iter()
+                .map(|(table, versions)| (table, versions.into_iter().collect()))
+                .collect(),
+            branches: report.branches,
+            versions_dropped: report.versions_dropped,
+            chunks_copied: report.chunks_copied,
+            bytes_copied: report.bytes_copied,
+        }
+    }
+}
+
+/// Copy a subset of one repository into another.
+///
+/// Kept versions are renumbered from 1 in the destination; parent links,
+/// branch heads and fork points are rewritten to match. Only chunks of
+/// copied versions are written to the destination store.
+///
+/// Args:
+///     source_store: Source PyChunkStore
+///     source_catalog: Source PyCatalog
+///     dest_store: Destination PyChunkStore
+///     dest_catalog: Destination PyCatalog (must not contain selected tables)
+///     source_branches: Source PyBranchManager (branches are copied only
+///         when both branch managers are given)
+///     dest_branches: Destination PyBranchManager
+///     tables: Table selectors (namespace prefixes or wildcard patterns)
+///     branches: Branch names to keep; tables on no kept branch are dropped
+///     keep_versions: Keep only the last N versions of each table
+///
+/// Returns:
+///     FilterReport with the version mapping and copy statistics
+///
+/// Example:
+///     >>> report = filter_repository(src_store, src_catalog, dst_store, dst_catalog,
+///     ...                            tables=["sales"], keep_versions=1)
+///     >>> report.version_map["sales.orders"]
+///     {7: 1}
+#[pyfunction]
+#[pyo3(signature = (
+    source_store, source_catalog, dest_store, dest_catalog,
+    source_branches=None, dest_branches=None,
+    tables=None, branches=None, keep_versions=None
+))]
+#[allow(clippy::too_many_arguments)]
+fn filter_repository(
+    source_store: &PyChunkStore,
+    source_catalog: &PyCatalog,
+    dest_store: &PyChunkStore,
+    dest_catalog: &PyCatalog,
+    source_branches: Option<&PyBranchManager>,
+    dest_branches: Option<&PyBranchManager>,
+    tables: Option<Vec<String>>,
+    branches: Option<Vec<String>>,
+    keep_versions: Option<usize>,
+) -> PyResult<PyFilterReport> {
+    let mut source = RepoRef::new(&source_catalog.inner, &source_store.inner);
+    if let Some(manager) = source_branches {
+        source = source.with_branches(&manager.inner);
+    }
+    let mut dest = RepoRef::new(&dest_catalog.inner, &dest_store.inner);
+    if let Some(manager) = dest_branches {
+        dest = dest.with_branches(&manager.inner);
+    }
+
+    let mut filter = RepoFilter::new()
+        .with_tables(tables.unwrap_or_default())
+        .with_branches(branches.unwrap_or_default());
+    if let Some(n) = keep_versions {
+        filter = filter.with_keep_versions(n);
+    }
+
+    filter
+        .run(source, dest)
+        .map(PyFilterReport::from)
+        .map_err(filter_err_to_py)
+}
+
+// ============================================================================
+// Backup and Restore
+// ============================================================================
+
+/// Convert BackupError to appropriate Python exception
+fn backup_err_to_py(e: BackupError) -> PyErr {
+    let info = ErrorAttrs::of(&e);
+    let err = match e {
+        BackupError::Catalog(e) => catalog_err_to_py(e),
+        BackupError::ChunkStore(e) => chunk_err_to_py(e),
+        BackupError::Branch(e) => branch_err_to_py(e),
+        BackupError::Io(e) => PyIOError::new_err(sanitize_io_error(&e)),
+        e @ (BackupError::NotABackup(_)
+        | BackupError::ParentNotFound { .. }
+        | BackupError::MissingChunk { .. }) => {
+            PyIOError::new_err(sanitize_error_message(&e.to_string()))
+        }
+        e => PyValueError::new_err(sanitize_error_message(&e.to_string())),
+    };
+    info.attach(err)
+}
+
+/// Summary of a backup manifest.
+#[pyclass(name = "BackupManifest")]
+#[derive(Clone)]
+struct PyBackupManifest {
+    #[pyo3(get)]
+    backup_id: String,
+    #[pyo3(get)]
+    created_at: i64,
+    /// Backup this one extends (None for a full backup)
+    #[pyo3(get)]
+    parent_id: Option<String>,
+    /// Latest version of every table at backup time
+    #[pyo3(get)]
+    tables: HashMap<String, u64>,
+    /// Branches at backup time
+    #[pyo3(get)]
+    branches: Vec<String>,
+    /// Version records stored in this backup
+    #[pyo3(get)]
+    versions: usize,
+    /// Chunks stored in this backup
+    #[pyo3(get)]
+    chunks: usize,
+    #[pyo3(get)]
+    chunk_bytes: u64,
+}
+
+#[pymethods]
+impl PyBackupManifest {
+    #[getter]
+    fn is_incremental(&self) -> bool {
+        self.parent_id.is_some()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "BackupManifest(backup_id={}, incremental={}, tables={}, versions={}, chunks={})",
+            self.backup_id,
+            self.is_incremental(),
+            self.tables.len(),
+            self.versions,
+            self.chunks,
+        )
+    }
+}
+
+impl From<&BackupManifest> for PyBackupManifest {
+    fn from(manifest: &BackupManifest) -> Self {
+        Self {
+            backup_id: manifest.backup_id.clone(),
+            created_at: manifest.created_at,
+            parent_id: manifest.parent.as_ref().map(|p| p.backup_id.clone()),
+            tables: manifest.tables.clone().into_iter().collect(),
+            branches: manifest.branches.iter().map(|b| b.name.clone()).collect(),
+            versions: manifest.versions.len(),
+            chunks: manifest.chunks.len(),
+            chunk_bytes: manifest.chunk_bytes(),
+        }
+    }
+}
+
+/// Result of verifying a backup chain.
+#[pyclass(name = "VerifyReport")]
+#[derive(Clone)]
+struct PyVerifyReport {
+    #[pyo3(get)]
+    backups: usize,
+    #[pyo3(get)]
+    versions: usize,
+    #[pyo3(get)]
+    chunks_verified: usize,
+    #[pyo3(get)]
+    bytes_verified: u64,
+}
+
+#[pymethods]
+impl PyVerifyReport {
+    fn __repr__(&self) -> String {
+        format!(
+            "VerifyReport(backups={}, versions={}, chunks_verified={}, bytes_verified={})",
+            self.backups, self.versions, self.chunks_verified, self.bytes_verified
+        )
+    }
+}
+
+impl From<VerifyReport> for PyVerifyReport {
+    fn from(report: VerifyReport) -> Self {
+        Self {
+            backups: report.backups,
+            versions: report.versions,
+            chunks_verified: report.chunks_verified,
+            bytes_verified: report.bytes_verified,
+        }
+    }
+}
+
+/// Result of restoring a backup chain.
+#[pyclass(name = "RestoreReport")]
+#[derive(Clone)]
+struct PyRestoreReport {
+    #[pyo3(get)]
+    backups_applied: usize,
+    #[pyo3(get)]
+    versions_restored: usize,
+    #[pyo3(get)]
+    chunks_copied: usize,
+    #[pyo3(get)]
+    bytes_copied: u64,
+    #[pyo3(get)]
+    tables: Vec<String>,
+    #[pyo3(get)]
+    branches: Vec<String>,
+}
+
+#[pymethods]
+impl PyRestoreReport {
+    fn __repr__(&self) -> String {
+        format!(
+            "RestoreReport(backups_applied={}, tables={}, versions_restored={}, chunks_copied={})",
+            self.backups_applied,
+            self.tables.len(),
+            self.versions_restored,
+            self.chunks_copied,
+        )
+    }
+}
+
+impl From<RestoreReport> for PyRestoreReport {
+    fn from(report: RestoreReport) -> Self {
+        Self {
+            backups_applied: report.backups_applied,
+            versions_restored: report.versions_restored,
+            chunks_copied: report.chunks_copied,
+            bytes_copied: report.bytes_copied,
+            tables: report.tables,
+            branches: report.branches,
+        }
+    }
+}
+
+/// Back up a repository into a new backup directory.
+///
+/// Args:
+///     source_store: Source PyChunkStore
+///     source_catalog: Source PyCatalog
+///     dest: Backup directory (must not already hold a backup)
+///     source_branches: Source PyBranchManager (branches are backed up
+///         only when given)
+///     incremental_since: Directory of an earlier backup; only versions and
+///         chunks missing from it and the chain it extends are stored
+///
+/// Returns:
+///     BackupManifest of the new backup
+///
+/// Example:
+///     >>> backup_repository(store, catalog, "/backups/mon", branches)
+///     >>> backup_repository(store, catalog, "/backups/tue", branches,
+///     ...                   incremental_since="/backups/mon")
+#[pyfunction]
+#[pyo3(signature = (source_store, source_catalog, dest, source_branches=None, incremental_since=None))]
+fn backup_repository(
+    source_store: &PyChunkStore,
+    source_catalog: &PyCatalog,
+    dest: &str,
+    source_branches: Option<&PyBranchManager>,
+    incremental_since: Option<&str>,
+) -> PyResult<PyBackupManifest> {
+    let mut source = RepoRef::new(&source_catalog.inner, &source_store.inner);
+    if let Some(manager) = source_branches {
+        source = source.with_branches(&manager.inner);
+    }
+
+    let parent = incremental_since
+        .map(BackupSet::open)
+        .transpose()
+        .map_err(backup_err_to_py)?;
+    let mut backup = Backup::new(source);
+    if let Some(parent) = &parent {
+        backup = backup.incremental_since(parent);
+    }
+
+    backup
+        .run(dest)
+        .map(|manifest| PyBackupManifest::from(&manifest))
+        .map_err(backup_err_to_py)
+}
+
+/// Check a backup and the chain it extends without restoring it.
+///
+/// Verifies every manifest checksum and parent link, reads back every
+/// chunk and checks its hash, and checks that every version's chunks are
+/// stored in the chain.
+///
+/// Args:
+///     path: Backup directory
+///
+/// Returns:
+///     VerifyReport
+///
+/// Raises:
+///     IOError: If a backup in the chain or one of its chunks is missing
+///     ValueError: If a manifest or chunk fails its checksum
+#[pyfunction]
+fn verify_backup(path: &str) -> PyResult<PyVerifyReport> {
+    BackupSet::open(path)
+        .and_then(|set| set.verify())
+        .map(PyVerifyReport::from)
+        .map_err(backup_err_to_py)
+}
+
+/// List the manifests of a backup chain, base backup first.
+#[pyfunction]
+fn backup_chain(path: &str) -> PyResult<Vec<PyBackupManifest>> {
+    let set = BackupSet::open(path).map_err(backup_err_to_py)?;
+    Ok(set.manifests().map(PyBackupManifest::from).collect())
+}
+
+/// Verify a backup chain, then restore it into a repository.
+///
+/// Args:
+///     path: Backup directory (the newest backup of the chain)
+///     target_store: Target PyChunkStore
+///     target_catalog: Target PyCatalog (must not contain backed-up tables)
+///     target_branches: Target PyBranchManager (branches are restored only
+///         when given)
+///
+/// Returns:
+///     RestoreReport
+#[pyfunction]
+#[pyo3(signature = (path, target_store, target_catalog, target_branches=None))]
+fn restore_backup(
+    path: &str,
+    target_store: &PyChunkStore,
+    target_catalog: &PyCatalog,
+    target_branches: Option<&PyBranchManager>,
+) -> PyResult<PyRestoreReport> {
+    let mut target = RepoRef::new(&target_catalog.inner, &target_store.inner);
+    if let Some(manager) = target_branches {
+        target = target.with_branches(&manager.inner);
+    }
+
+    BackupSet::open(path)
+        .and_then(|set| set.restore(target))
+        .map(PyRestoreReport::from)
+        .map_err(backup_err_to_py)
+}
+
+// ============================================================================
+// Transaction Classes
+// ============================================================================
+
+/// Convert TransactionError to appropriate Python exception
+fn tx_err_to_py(e: TransactionError) -> PyErr {
+    let info = ErrorAttrs::of(&e);
+    let err = match e {
+        TransactionError::TransactionNotFound(id) => {
+            PyValueError::new_err(format!("Transaction not found: {}", id))
+        }
+        TransactionError::TransactionNotActive(id) => {
+            PyValueError::new_err(format!("Transaction {} is not active", id))
+        }
+        TransactionError::AlreadyCommitted(id) => {
+            PyValueError::new_err(format!("Transaction {} already committed", id))
+        }
+        TransactionError::AlreadyAborted(id) => {
+            PyValueError::new_err(format!("Transaction {} already aborted", id))
+        }
+        TransactionError::SavepointNotFound { tx_id, name } => {
+            PyValueError::new_err(format!("Savepoint not found in transaction {}: {}", tx_id, name))
+        }
+        TransactionError::WriteConflict(tables) => {
+            PyValueError::new_err(format!("Write conflict on tables: {:?}", tables))
+        }
+        TransactionError::SnapshotConflict { table, read_version, current_version } => {
+            PyValueError::new_err(format!(
+                "Snapshot conflict: {} was v{}, now v{}",
+                table, read_version, current_version
+            ))
+        }
+        TransactionError::Io(e) => PyIOError::new_err(sanitize_io_error(&e)),
+        TransactionError::Json(e) => PyValueError::new_err(format!("JSON error: {}", sanitize_error_message(&e.to_string()))),
+        TransactionError::CatalogError(msg) => PyIOError::new_err(format!("Catalog error: {}", sanitize_error_message(&msg))),
+        TransactionError::BranchError(msg) => PyIOError::new_err(format!("Branch error: {}", sanitize_error_message(&msg))),
+        TransactionError::CursorNotFound(_)
+        | TransactionError::CursorAlreadyExists(_)
+        | TransactionError::InvalidCursorName(_) => PyValueError::new_err(sanitize_error_message(&e.to_string())),
+        _ => PyRuntimeError::new_err(sanitize_error_message(&e.to_string())),
+    };
+    info.attach(err)
+}
+
+#[pyclass]
+#[derive(Clone)]
+struct PyTransactionInfo {
+    #[pyo3(get)]
+    tx_id: u64,
+    #[pyo3(get)]
+    epoch_id: u64,
+    #[pyo3(get)]
+    status: String,
+    #[pyo3(get)]
+    branch: String,
+    #[pyo3(get)]
+    started_at: i64,
+    #[pyo3(get)]
+    committed_at: Option<i64>,
+    #[pyo3(get)]
+    read_snapshot: HashMap<String, u64>,
+    #[pyo3(get)]
+    written_tables: Vec<String>,
+    #[pyo3(get)]
+    savepoints: Vec<String>,
+}
+
+impl From<TransactionRecord> for PyTransactionInfo {
+    fn from(tx: TransactionRecord) -> Self {
+        let written = tx.written_tables().into_iter().map(|s| s.to_string()).collect();
+        let savepoints = tx.savepoint_names().into_iter().map(|s| s.to_string()).collect();
+        Self {
+            tx_id: tx.tx_id,
+            epoch_id: tx.epoch_id,
+            status: format!("{}", tx.status),
+            branch: tx.branch,
+            started_at: tx.started_at,
+            committed_at: tx.committed_at,
+            read_snapshot: tx.read_snapshot.into_map(),
+            written_tables: written,
+            savepoints,
+        }
+    }
+}
+
+/// Epoch configuration for a TransactionManager.
+///
+/// Example:
+///     >>> config = PyEpochConfig.high_throughput()
+///     >>> tm = PyTransactionManager(tx_dir, catalog_dir, epoch_config=config)
+#[pyclass]
+#[derive(Clone)]
+struct PyEpochConfig {
+    inner: EpochConfig,
+}
+
+#[pymethods]
+impl PyEpochConfig {
+    #[new]
+    #[pyo3(signature = (duration_ms=100, max_transactions=1000, batching_enabled=true))]
+    fn new(duration_ms: u64, max_transactions: u64, batching_enabled: bool) -> Self {
+        Self {
+            inner: EpochConfig { duration_ms, max_transactions, batching_enabled },
+        }
+    }
+
+    /// Each transaction is its own epoch (the TransactionManager default).
+    #[staticmethod]
+    fn single_node() -> Self {
+        Self { inner: EpochConfig::single_node() }
+    }
+
+    /// Large epochs batching many transactions.
+    #[staticmethod]
+    fn high_throughput() -> Self {
+        Self { inner: EpochConfig::high_throughput() }
+    }
+
+    /// Small epochs for faster commits.
+    #[staticmethod]
+    fn low_latency() -> Self {
+        Self { inner: EpochConfig::low_latency() }
+    }
+
+    /// Duration of each epoch in milliseconds (0 = immediate mode).
+    #[getter]
+    fn duration_ms(&self) -> u64 {
+        self.inner.duration_ms
+    }
+
+    #[setter]
+    fn set_duration_ms(&mut self, duration_ms: u64) {
+        self.inner.duration_ms = duration_ms;
+    }
+
+    /// Maximum transactions per epoch.
+    #[getter]
+    fn max_transactions(&self) -> u64 {
+        self.inner.max_transactions
+    }
+
+    #[setter]
+    fn set_max_transactions(&mut self, max_transactions: u64) {
+        self.inner.max_transactions = max_transactions;
+    }
+
+    /// Whether transactions are batched within epochs.
+    #[getter]
+    fn batching_enabled(&self) -> bool {
+        self.inner.batching_enabled
+    }
+
+    #[setter]
+    fn set_batching_enabled(&mut self, enabled: bool) {
+        self.inner.batching_enabled = enabled;
+    }
+
+    /// True if every transaction gets its own epoch.
+    fn is_immediate(&self) -> bool {
+        self.inner.is_immediate()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "EpochConfig(duration_ms={}, max_transactions={}, batching_enabled={})",
+            self.inner.duration_ms, self.inner.max_transactions, self.inner.batching_enabled
+        )
+    }
+}
+
+#[pyclass]
+#[derive(Clone)]
+struct PyEpochInfo {
+    #[pyo3(get)]
+    epoch_id: u64,
+    #[pyo3(get)]
+    status: String,
+    #[pyo3(get)]
+    started_at: i64,
+    #[pyo3(get)]
+    ended_at: Option<i64>,
+    #[pyo3(get)]
+    transactions: Vec<u64>,
+    #[pyo3(get)]
+    first_tx_id: Option<u64>,
+    #[pyo3(get)]
+    last_tx_id: Option<u64>,
+    #[pyo3(get)]
+    committed_count: u64,
+    #[pyo3(get)]
+    aborted_count: u64,
+}
+
+impl From<EpochMetadata> for PyEpochInfo {
+    fn from(meta: EpochMetadata) -> Self {
+        Self {
+            epoch_id: meta.epoch_id,
+            status: format!("{}", meta.status),
+            started_at: meta.started_at,
+            ended_at: meta.ended_at,
+            transactions: meta.transactions,
+            first_tx_id: meta.first_tx_id,
+            last_tx_id: meta.last_tx_id,
+            committed_count: meta.committed_count,
+            aborted_count: meta.aborted_count,
+        }
+    }
+}
+
+#[pymethods]
+impl PyEpochInfo {
+    fn __repr__(&self) -> String {
+        format!(
+            "EpochInfo(epoch_id={}, status={}, transactions={}, committed={}, aborted={})",
+            self.epoch_id, self.status, self.transactions.len(),
+            self.committed_count, self.aborted_count
+        )
+    }
+}
+
+#[pyclass]
+#[derive(Clone)]
+struct PyRecoveryReport {
+    #[pyo3(get)]
+    last_committed_epoch: Option<u64>,
+    #[pyo3(get)]
+    replayed: Vec<u64>,
+    #[pyo3(get)]
+    rolled_back: Vec<u64>,
+    #[pyo3(get)]
+    already_aborted: Vec<u64>,
+    #[pyo3(get)]
+    already_committed: Vec<u64>,
+    #[pyo3(get)]
+    warnings: Vec<String>,
+    #[pyo3(get)]
+    errors: Vec<String>,
+    #[pyo3(get)]
+    is_clean: bool,
+}
+
+impl From<RecoveryReport> for PyRecoveryReport {
+    fn from(r: RecoveryReport) -> Self {
+        let is_clean = r.is_clean();
+        Self {
+            last_committed_epoch: r.last_committed_epoch,
+            replayed: r.replayed,
+            rolled_back: r.rolled_back,
+            already_aborted: r.already_aborted,
+            already_committed: r.already_committed,
+            warnings: r.warnings,
+            errors: r.errors,
+            is_clean,
+        }
+    }
+}
+
+// =============================================================================
+// Chunk Garbage Collection
+// =============================================================================
+
+/// Convert GcError to appropriate Python exception
+fn gc_err_to_py(e: GcError) -> PyErr {
+    let info = ErrorAttrs::of(&e);
+    let err = match e {
+        GcError::Catalog(e) => catalog_err_to_py(e),
+        GcError::ChunkStore(e) => chunk_err_to_py(e),
+        GcError::Transaction(e) => tx_err_to_py(e),
+        GcError::Branch(e) => branch_err_to_py(e),
+        e @ GcError::VersionInUse { .. } => PyValueError::new_err(e.to_string()),
+        e => PyRuntimeError::new_err(sanitize_error_message(&e.to_string())),
+    };
+    info.attach(err)
+}
+
+/// Result of a chunk garbage collection run.
+#[pyclass(name = "ChunkGcReport")]
+#[derive(Clone)]
+struct PyChunkGcReport {
+    /// True if nothing was deleted
+    #[pyo3(get)]
+    dry_run: bool,
+    #[pyo3(get)]
+    chunks_scanned: usize,
+    /// Stored chunks referenced by a catalog version
+    #[pyo3(get)]
+    chunks_referenced: usize,
+    /// Stored chunks referenced only by in-flight transactions
+    #[pyo3(get)]
+    chunks_in_flight: usize,
+    /// Unreferenced chunks younger than the grace period
+    #[pyo3(get)]
+    chunks_within_grace: usize,
+    /// Hashes of unreferenced chunks past the grace period
+    #[pyo3(get)]
+    collectable: Vec<String>,
+    #[pyo3(get)]
+    bytes_collectable: u64,
+    #[pyo3(get)]
+    chunks_deleted: usize,
+    #[pyo3(get)]
+    chunks_failed: usize,
+}
+
+#[pymethods]
+impl PyChunkGcReport {
+    fn __repr__(&self) -> String {
+        format!(
+            "ChunkGcReport(scanned={}, collectable={}, bytes_collectable={}, deleted={})",
+            self.chunks_scanned,
+            self.collectable.len(),
+            self.bytes_collectable,
+            self.chunks_deleted,
+        )
+    }
+}
+
+impl From<GcReport> for PyChunkGcReport {
+    fn from(r: GcReport) -> Self {
+        Self {
+            dry_run: r.dry_run,
+            chunks_scanned: r.chunks_scanned,
+            chunks_referenced: r.chunks_referenced,
+            chunks_in_flight: r.chunks_in_flight,
+            chunks_within_grace: r.chunks_within_grace,
+            collectable: r.collectable,
+            bytes_collectable: r.bytes_collectable,
+            chunks_deleted: r.chunks_deleted,
+            chunks_failed: r.chunks_failed,
+        }
+    }
+}
+
+/// Delete chunks no catalog version or in-flight transaction references.
+///
+/// Chunks modified within the grace period are kept, so chunks stored by
+/// commits that have not yet reached the catalog survive.
+///
+/// Args:
+///     store: PyChunkStore to sweep
+///     catalog: PyCatalog whose versions keep chunks alive
+///     transaction_manager: Optional PyTransactionManager; chunks written
+///         by its uncommitted transactions are kept
+///     grace_period_secs: Minimum age of a chunk before it is collected
+///     dry_run: Report collectable chunks without deleting them
+///     only: Optional chunk hashes to sweep instead of the whole store;
+///         every other chunk is kept, with its delta bases
+///
+/// Returns:
+///     ChunkGcReport
+///
+/// Example:
+///     >>> report = sweep_chunks(store, catalog, tx_manager, dry_run=True)
+///     >>> report.bytes_collectable
+///     1048576
+#[pyfunction]
+#[pyo3(signature = (store, catalog, transaction_manager=None, grace_period_secs=3600.0, dry_run=false, only=None))]
+fn sweep_chunks(
+    py: Python<'_>,
+    store: &PyChunkStore,
+    catalog: &PyCatalog,
+    transaction_manager: Option<&PyTransactionManager>,
+    grace_period_secs: f64,
+    dry_run: bool,
+    only: Option<Vec<String>>,
+) -> PyResult<PyChunkGcReport> {
+    let grace_period = std::time::Duration::try_from_secs_f64(grace_period_secs)
+        .map_err(|_| PyValueError::new_err("grace_period_secs must be a non-negative number"))?;
+    let mut gc = ChunkGc::new(&catalog.inner, &store.inner)
+        .with_grace_period(grace_period)
+        .dry_run(dry_run);
+    if let Some(manager) = transaction_manager {
+        gc = gc.with_transactions(&manager.inner);
+    }
+    if let Some(hashes) = only {
+        gc = gc.with_candidates(hashes);
+    }
+    py.detach(|| gc.run())
+        .map(PyChunkGcReport::from)
+        .map_err(gc_err_to_py)
+}
+
+/// Result of a version pruning run.
+#[pyclass(name = "VersionPruneReport")]
+#[derive(Clone)]
+struct PyVersionPruneReport {
+    /// True if nothing was deleted
+    #[pyo3(get)]
+    dry_run: bool,
+    /// Table name -> versions removed (or that would be, in a dry run)
+    #[pyo3(get)]
+    versions_pruned: HashMap<String, Vec<u64>>,
+    /// Table name -> expired versions kept because they are still in use
+    #[pyo3(get)]
+    versions_protected: HashMap<String, Vec<u64>>,
+    /// Chunks referenced only by pruned versions, for `sweep_chunks`
+    #[pyo3(get)]
+    unreferenced_chunks: Vec<String>,
+}
+
+#[pymethods]
+impl PyVersionPruneReport {
+    fn __repr__(&self) -> String {
+        format!(
+            "VersionPruneReport(tables={}, versions_pruned={}, unreferenced_chunks={})",
+            self.versions_pruned.len(),
+            self.versions_pruned.values().map(Vec::len).sum::<usize>(),
+            self.unreferenced_chunks.len(),
+        )
+    }
+}
+
+impl From<PruneReport> for PyVersionPruneReport {
+    fn from(r: PruneReport) -> Self {
+        Self {
+            dry_run: r.dry_run,
+            versions_pruned: r.versions_pruned.into_iter().collect(),
+            versions_protected: r.versions_protected.into_iter().collect(),
+            unreferenced_chunks: r.unreferenced_chunks,
+        }
+    }
+}
+
+/// Remove old table versions according to each table's retention policy.
+///
+/// Versions under legal hold, pinned, tagged, at a branch head or fork
+/// point, or read by an active transaction are kept. Chunks only the
+/// pruned versions referenced are reported; `sweep_chunks` reclaims them.
+///
+/// Args:
+///     catalog: PyCatalog to prune
+///     branch_manager: Optional PyBranchManager whose heads are kept
+///     transaction_manager: Optional PyTransactionManager whose active
+///         transactions' snapshots are kept
+///     keep_last: Default policy for tables without one: keep the newest N
+///     keep_newer_than_secs: Default policy for tables without one: keep
+///         versions younger than this
+///     dry_run: Report expired versions without deleting them
+///
+/// Returns:
+///     VersionPruneReport
+///
+/// Example:
+///     >>> catalog.set_retention_policy("events", keep_last=10)
+///     >>> report = prune_versions(catalog, branches)
+///     >>> sweep_chunks(store, catalog)
+#[pyfunction]
+#[pyo3(signature = (catalog, branch_manager=None, transaction_manager=None, keep_last=None, keep_newer_than_secs=None, dry_run=false))]
+fn prune_versions(
+    py: Python<'_>,
+    catalog: &PyCatalog,
+    branch_manager: Option<&PyBranchManager>,
+    transaction_manager: Option<&PyTransactionManager>,
+    keep_last: Option<usize>,
+    keep_newer_than_secs: Option<u64>,
+    dry_run: bool,
+) -> PyResult<PyVersionPruneReport> {
+    let mut pruner = VersionPruner::new(&catalog.inner).dry_run(dry_run);
+    let default_policy = RetentionPolicy { keep_last, keep_newer_than_secs };
+    if !default_policy.keeps_all() {
+        pruner = pruner.with_default_policy(default_policy);
+    }
+    if let Some(branches) = branch_manager {
+        pruner = pruner.with_branches(&branches.inner);
+    }
+    if let Some(manager) = transaction_manager {
+        pruner = pruner.with_transactions(&manager.inner);
+    }
+    py.detach(|| pruner.run())
+        .map(PyVersionPruneReport::from)
+        .map_err(gc_err_to_py)
+}
+
+/// Versions to delete, in order, to free a target number of bytes.
+#[pyclass(name = "GcPlan")]
+#[derive(Clone)]
+struct PyGcPlan {
+    inner: GcPlan,
+}
+
+#[pymethods]
+impl PyGcPlan {
+    #[getter]
+    fn target_bytes(&self) -> u64 {
+        self.inner.target_bytes
+    }
+
+    /// Bytes of chunks a collection frees without deleting any version
+    #[getter]
+    fn unreferenced_bytes(&self) -> u64 {
+        self.inner.unreferenced_bytes
+    }
+
+    /// Bytes the plan frees when executed
+    #[getter]
+    fn reclaimable_bytes(&self) -> u64 {
+        self.inner.reclaimable_bytes()
+    }
+
+    /// Whether executing the plan frees `target_bytes`
+    #[getter]
+    fn meets_target(&self) -> bool {
+        self.inner.meets_target()
+    }
+
+    /// (table_name, version, bytes, created_at) per planned deletion, in
+    /// the order they were chosen
+    #[getter]
+    fn steps(&self) -> Vec<(String, u64, u64, i64)> {
+        self.inner
+            .steps
+            .iter()
+            .map(|s| (s.table_name.clone(), s.version, s.bytes, s.created_at))
+            .collect()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "GcPlan(target_bytes={}, reclaimable_bytes={}, versions={})",
+            self.inner.target_bytes,
+            self.inner.reclaimable_bytes(),
+            self.inner.steps.len(),
+        )
+    }
+}
+
+fn gc_planner<'a>(
+    store: &'a PyChunkStore,
+    catalog: &'a PyCatalog,
+    branch_manager: Option<&'a PyBranchManager>,
+    transaction_manager: Option<&'a PyTransactionManager>,
+    grace_period_secs: f64,
+) -> PyResult<GcPlanner<'a>> {
+    let grace_period = std::time::Duration::try_from_secs_f64(grace_period_secs)
+        .map_err(|_| PyValueError::new_err("grace_period_secs must be a non-negative number"))?;
+    let mut planner = GcPlanner::new(&catalog.inner, &store.inner).with_grace_period(grace_period);
+    if let Some(branches) = branch_manager {
+        planner = planner.with_branches(&branches.inner);
+    }
+    if let Some(manager) = transaction_manager {
+        planner = planner.with_transactions(&manager.inner);
+    }
+    Ok(planner)
+}
+
+/// Plan which versions to delete to free at least `target_bytes`.
+///
+/// Deletable versions are ranked by the bytes only they hold, then by age.
+/// Latest versions, versions under legal hold, pinned, tagged, at a branch
+/// head or fork point, or read by an active transaction are never planned.
+///
+/// Args:
+///     store: PyChunkStore holding the chunks
+///     catalog: PyCatalog to plan for
+///     target_bytes: Bytes to free
+///     branch_manager: Optional PyBranchManager whose heads are kept
+///     transaction_manager: Optional PyTransactionManager whose active
+///         transactions' snapshots and written chunks are kept
+///     grace_period_secs: Minimum age of a chunk before it counts
+///
+/// Returns:
+///     GcPlan: check `meets_target` before executing it
+///
+/// Example:
+///     >>> plan = gc_plan(store, catalog, 10 * 2**30, branches, tx_manager)
+///     >>> if plan.meets_target:
+///     ...     execute_gc_plan(store, catalog, plan, branches, tx_manager)
+#[pyfunction]
+#[pyo3(signature = (store, catalog, target_bytes, branch_manager=None, transaction_manager=None, grace_period_secs=3600.0))]
+fn gc_plan(
+    py: Python<'_>,
+    store: &PyChunkStore,
+    catalog: &PyCatalog,
+    target_bytes: u64,
+    branch_manager: Option<&PyBranchManager>,
+    transaction_manager: Option<&PyTransactionManager>,
+    grace_period_secs: f64,
+) -> PyResult<PyGcPlan> {
+    let planner = gc_planner(store, catalog, branch_manager, transaction_manager, grace_period_secs)?;
+    py.detach(|| planner.plan(target_bytes))
+        .map(|inner| PyGcPlan { inner })
+        .map_err(gc_err_to_py)
+}
+
+/// Delete the versions of a plan from `gc_plan` and collect their chunks.
+///
+/// Fails without deleting anything if a planned version has come into use
+/// since the plan was made.
+///
+/// Returns:
+///     ChunkGcReport
+#[pyfunction]
+#[pyo3(signature = (store, catalog, plan, branch_manager=None, transaction_manager=None, grace_period_secs=3600.0))]
+fn execute_gc_plan(
+    py: Python<'_>,
+    store: &PyChunkStore,
+    catalog: &PyCatalog,
+    plan: &PyGcPlan,
+    branch_manager: Option<&PyBranchManager>,
+    transaction_manager: Option<&PyTransactionManager>,
+    grace_period_secs: f64,
+) -> PyResult<PyChunkGcReport> {
+    let planner = gc_planner(store, catalog, branch_manager, transaction_manager, grace_period_secs)?;
+    py.detach(|| planner.execute(&plan.inner))
+        .map(PyChunkGcReport::from)
+        .map_err(gc_err_to_py)
+}
+
+/// Fold every version of a table before `up_to_version` into it.
+///
+/// `up_to_version` becomes the table's first version; the removed version
+/// numbers are kept in `PyCatalog.squash_history`. Fails if a removed
+/// version is under legal hold, pinned, tagged, a branch head or fork
+/// point, or read by an active transaction.
+///
+/// Args:
+///     catalog: PyCatalog to rewrite
+///     table_name: Table to squash
+///     up_to_version: Version to keep as the new base
+///     branch_manager: Optional PyBranchManager whose heads must survive
+///     transaction_manager: Optional PyTransactionManager whose active
+///         transactions' snapshots must survive
+///     dry_run: Report the versions that would be folded
+///
+/// Returns:
+///     VersionPruneReport: Folded versions and the chunks only they
+///     referenced, for `sweep_chunks`
+#[pyfunction]
+#[pyo3(signature = (catalog, table_name, up_to_version, branch_manager=None, transaction_manager=None, dry_run=false))]
+fn squash_versions(
+    py: Python<'_>,
+    catalog: &PyCatalog,
+    table_name: &str,
+    up_to_version: u64,
+    branch_manager: Option<&PyBranchManager>,
+    transaction_manager: Option<&PyTransactionManager>,
+    dry_run: bool,
+) -> PyResult<PyVersionPruneReport> {
+    let mut pruner = VersionPruner::new(&catalog.inner).dry_run(dry_run);
+    if let Some(branches) = branch_manager {
+        pruner = pruner.with_branches(&branches.inner);
+    }
+    if let Some(manager) = transaction_manager {
+        pruner = pruner.with_transactions(&manager.inner);
+    }
+    py.detach(|| pruner.squash_versions(table_name, up_to_version))
+        .map(PyVersionPruneReport::from)
+        .map_err(gc_err_to_py)
+}
+
+// =============================================================================
+// Chunk Scrubbing
+// =============================================================================
+
+/// Convert ScrubError to appropriate Python exception
+fn scrub_err_to_py(e: ScrubError) -> PyErr {
+    let info = ErrorAttrs::of(&e);
+    let err = match e {
+        ScrubError::JournalDisabled => PyValueError::new_err(
+            "Chunk store has no integrity journal (open it with integrity_journal=True)",
+        ),
+        ScrubError::Catalog(e) => catalog_err_to_py(e),
+        ScrubError::ChunkStore(e) => chunk_err_to_py(e),
+        ScrubError::Branch(e) => branch_err_to_py(e),
+        ScrubError::Io(e) => PyIOError::new_err(sanitize_io_error(&e)),
+        e => PyRuntimeError::new_err(sanitize_error_message(&e.to_string())),
+    };
+    info.attach(err)
+}
+
+/// Result of a chunk integrity scrub.
+#[pyclass(name = "ChunkScrubReport")]
+#[derive(Clone)]
+struct PyChunkScrubReport {
+    #[pyo3(get)]
+    chunks_checked: usize,
+    #[pyo3(get)]
+    chunks_intact: usize,
+    /// Journaled chunks not in the local cache of a backend-backed store
+    #[pyo3(get)]
+    chunks_not_cached: usize,
+    /// Stored chunks written before the journal was enabled
+    #[pyo3(get)]
+    chunks_unjournaled: usize,
+    /// Damaged chunk hash -> "missing", "truncated", "extended" or "bit_rot"
+    #[pyo3(get)]
+    damaged: HashMap<String, String>,
+    /// Table name -> versions that read a damaged chunk
+    #[pyo3(get)]
+    impacted_versions: HashMap<String, Vec<u64>>,
+    /// Branches whose head is an impacted version
+    #[pyo3(get)]
+    impacted_branches: Vec<String>,
+    /// Branch -> (table, missing version, rollback version) for heads
+    /// pointing at versions missing from the catalog
+    #[pyo3(get)]
+    dangling_heads: HashMap<String, Vec<(String, u64, Option<u64>)>>,
+}
+
+#[pymethods]
+impl PyChunkScrubReport {
+    #[getter]
+    fn is_clean(&self) -> bool {
+        self.damaged.is_empty() && self.dangling_heads.is_empty()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "ChunkScrubReport(checked={}, damaged={}, impacted_branches={:?})",
+            self.chunks_checked,
+            self.damaged.len(),
+            self.impacted_branches,
+        )
+    }
+}
+
+impl From<ScrubReport> for PyChunkScrubReport {
+    fn from(r: ScrubReport) -> Self {
+        let damaged = r
+            .damaged
+            .into_iter()
+            .map(|chunk| {
+                let condition = match chunk.condition {
+                    ChunkCondition::Missing => "missing",
+                    ChunkCondition::Truncated { .. } => "truncated",
+                    ChunkCondition::Extended { .. } => "extended",
+                    ChunkCondition::BitRot { .. } => "bit_rot",
+                    ChunkCondition::Intact => "intact",
+                    ChunkCondition::NotCached => "not_cached",
+                };
+                (chunk.hash, condition.to_string())
+            })
+            .collect();
+        Self {
+            chunks_checked: r.chunks_checked,
+            chunks_intact: r.chunks_intact,
+            chunks_not_cached: r.chunks_not_cached,
+            chunks_unjournaled: r.chunks_unjournaled,
+            damaged,
+            impacted_versions: r.impacted_versions.into_iter().collect(),
+            impacted_branches: r.impacted_branches,
+            dangling_heads: r
+                .dangling_heads
+                .into_iter()
+                .map(|(branch, heads)| (branch, dangling_tuples(heads)))
+                .collect(),
+        }
+    }
+}
+
+/// Compare chunk files against the store's integrity journal.
+///
+/// Damaged chunks are classified as missing, truncated, extended or bit
+/// rot, and traced to the table versions (and, given a branch manager,
+/// the branches) that read them. Given a branch manager, heads pointing at
+/// versions missing from the catalog are reported as well.
+///
+/// Args:
+///     store: PyChunkStore opened with integrity_journal=True
+///     catalog: PyCatalog whose versions reference the chunks
+///     branch_manager: Optional PyBranchManager to report impacted branches
+///
+/// Returns:
+///     ChunkScrubReport
+///
+/// Raises:
+///     ValueError: If the store has no integrity journal
+#[pyfunction]
+#[pyo3(signature = (store, catalog, branch_manager=None))]
+fn scrub_chunks(
+    py: Python<'_>,
+    store: &PyChunkStore,
+    catalog: &PyCatalog,
+    branch_manager: Option<&PyBranchManager>,
+) -> PyResult<PyChunkScrubReport> {
+    let mut scrubber = ChunkScrubber::new(&catalog.inner, &store.inner);
+    if let Some(manager) = branch_manager {
+        scrubber = scrubber.with_branches(&manager.inner);
+    }
+    py.detach(|| scrubber.run())
+        .map(PyChunkScrubReport::from)
+        .map_err(scrub_err_to_py)
+}
+
+// =============================================================================
+// Repository Fixtures
+// =============================================================================
+
+/// Convert FixtureError to appropriate Python exception
+fn fixture_err_to_py(e: FixtureError) -> PyErr {
+    let info = ErrorAttrs::of(&e);
+    let err = match e {
+        FixtureError::InvalidSpec(msg) => PyValueError::new_err(format!("Invalid fixture spec: {}", msg)),
+        FixtureError::Repo(RepoError::Io(e)) => PyIOError::new_err(sanitize_io_error(&e)),
+        FixtureError::Repo(RepoError::Catalog(e)) | FixtureError::Catalog(e) => catalog_err_to_py(e),
+        FixtureError::Repo(RepoError::ChunkStore(e)) | FixtureError::ChunkStore(e) => chunk_err_to_py(e),
+        FixtureError::Repo(RepoError::Branch(e)) | FixtureError::Branch(e) => branch_err_to_py(e),
+        FixtureError::Parquet(e) => parquet_err_to_py(e),
+        e => PyRuntimeError::new_err(sanitize_error_message(&e.to_string())),
+    };
+    info.attach(err)
+}
+
+/// What build_fixture_repo wrote.
+#[pyclass(name = "FixtureReport")]
+#[derive(Clone)]
+struct PyFixtureReport {
+    #[pyo3(get)]
+    tables: Vec<String>,
+    /// Branches forked from main
+    #[pyo3(get)]
+    branches: Vec<String>,
+    #[pyo3(get)]
+    versions_committed: usize,
+    /// Distinct chunks written
+    #[pyo3(get)]
+    chunks_written: usize,
+    #[pyo3(get)]
+    bytes_written: u64,
+    /// created_at of the last commit
     #[pyo3(get)]
-    is_clean: bool,
+    last_commit_at: i64,
 }
 
-impl From<RecoveryReport> for PyRecoveryReport {
-    fn from(r: RecoveryReport) -> Self {
-        let is_clean = r.is_clean();
+#[pymethods]
+impl PyFixtureReport {
+    fn __repr__(&self) -> String {
+        format!(
+            "FixtureReport(tables={}, branches={}, versions={}, chunks={})",
+            self.tables.len(),
+            self.branches.len(),
+            self.versions_committed,
+            self.chunks_written,
+        )
+    }
+}
+
+impl From<FixtureReport> for PyFixtureReport {
+    fn from(r: FixtureReport) -> Self {
         Self {
-            last_committed_epoch: r.last_committed_epoch,
-            replayed: r.replayed,
-            rolled_back: r.rolled_back,
-            already_aborted: r.already_aborted,
-            already_committed: r.already_committed,
-            warnings: r.warnings,
-            errors: r.errors,
-            is_clean,
+            tables: r.tables,
+            branches: r.branches,
+            versions_committed: r.versions_committed,
+            chunks_written: r.chunks_written,
+            bytes_written: r.bytes_written,
+            last_commit_at: r.last_commit_at,
         }
     }
 }
 
+/// Build a deterministic repository of a given shape at `path`.
+///
+/// `main` gets `versions` commit rounds writing every table; each branch
+/// forks after a seeded round and commits `branch_versions` more versions.
+/// Chunks hold seeded Parquet rows, so `rhizo.Database(path)` reads the
+/// result. The same arguments always produce the same repository.
+///
+/// Args:
+///     path: Repository directory (created if missing)
+///     tables: Number of tables (table_0, table_1, ...)
+///     versions: Commit rounds on main
+///     branches: Branches forked from main (branch_1, branch_2, ...)
+///     branch_versions: Versions committed on each branch
+///     rows: Rows in every table version
+///     chunks: Chunks each version's rows are split into
+///     commit_interval_secs: Seconds between consecutive commit timestamps
+///     start_time: Timestamp of the first commit (Unix seconds)
+///     seed: Seed for the data and the shape choices
+///
+/// Returns:
+///     FixtureReport
+///
+/// Raises:
+///     ValueError: If the shape is empty
+///     IOError: If a fixture table already exists at `path`
+#[pyfunction]
+#[pyo3(signature = (
+    path,
+    *,
+    tables=3,
+    versions=5,
+    branches=1,
+    branch_versions=2,
+    rows=100,
+    chunks=1,
+    commit_interval_secs=60,
+    start_time=1_700_000_000,
+    seed=0,
+))]
+#[allow(clippy::too_many_arguments)]
+fn build_fixture_repo(
+    py: Python<'_>,
+    path: &str,
+    tables: usize,
+    versions: u64,
+    branches: usize,
+    branch_versions: u64,
+    rows: usize,
+    chunks: usize,
+    commit_interval_secs: i64,
+    start_time: i64,
+    seed: u64,
+) -> PyResult<PyFixtureReport> {
+    let builder = FixtureBuilder::new()
+        .with_tables(tables)
+        .with_versions(versions)
+        .with_branches(branches)
+        .with_branch_versions(branch_versions)
+        .with_rows(rows)
+        .with_chunks(chunks)
+        .with_commit_interval_secs(commit_interval_secs)
+        .with_start_time(start_time)
+        .with_seed(seed);
+    py.detach(|| {
+        let repo = Repo::open(path)?;
+        builder.build(&repo)
+    })
+    .map(PyFixtureReport::from)
+    .map_err(fixture_err_to_py)
+}
+
 // =============================================================================
 // Changelog Types
 // =============================================================================
@@ -1307,15 +5153,37 @@ struct PyTableChange {
     new_version: u64,
     #[pyo3(get)]
     chunk_hashes: Vec<String>,
+    /// Branch written, if different from the entry's branch
+    #[pyo3(get)]
+    branch: Option<String>,
+    /// "write", "drop", "rename" or "merge"
+    #[pyo3(get)]
+    kind: String,
+    /// New table name, for renames
+    #[pyo3(get)]
+    renamed_to: Option<String>,
+    /// Source branch, for heads moved by a branch merge
+    #[pyo3(get)]
+    merged_from: Option<String>,
 }
 
 impl From<&TableChange> for PyTableChange {
     fn from(tc: &TableChange) -> Self {
+        let kind = match tc.kind {
+            WriteKind::Data => "write",
+            WriteKind::Drop => "drop",
+            WriteKind::Rename { .. } => "rename",
+            WriteKind::Merge { .. } => "merge",
+        };
         Self {
             table_name: tc.table_name.clone(),
             old_version: tc.old_version,
             new_version: tc.new_version,
             chunk_hashes: tc.chunk_hashes.clone(),
+            branch: tc.branch.clone(),
+            kind: kind.to_string(),
+            renamed_to: tc.renamed_to().map(str::to_string),
+            merged_from: tc.merged_from().map(str::to_string),
         }
     }
 }
@@ -1324,7 +5192,12 @@ impl From<&TableChange> for PyTableChange {
 impl PyTableChange {
     /// Check if this is a new table (no previous version).
     fn is_new_table(&self) -> bool {
-        self.old_version.is_none()
+        self.kind == "write" && self.old_version.is_none()
+    }
+
+    /// Check if the table was dropped (a tombstone with no chunks).
+    fn is_drop(&self) -> bool {
+        self.kind == "drop"
     }
 
     fn __repr__(&self) -> String {
@@ -1347,7 +5220,12 @@ struct PyChangelogEntry {
     committed_at: i64,
     #[pyo3(get)]
     branch: String,
+    /// Tables merged algebraically at commit (empty for ordinary commits)
+    #[pyo3(get)]
+    auto_merged: Vec<String>,
     changes: Vec<PyTableChange>,
+    /// Full entry, kept for replay
+    entry: ChangelogEntry,
 }
 
 impl From<ChangelogEntry> for PyChangelogEntry {
@@ -1356,8 +5234,10 @@ impl From<ChangelogEntry> for PyChangelogEntry {
             tx_id: entry.tx_id,
             epoch_id: entry.epoch_id,
             committed_at: entry.committed_at,
-            branch: entry.branch,
+            branch: entry.branch.clone(),
+            auto_merged: entry.auto_merged.clone(),
             changes: entry.changes.iter().map(PyTableChange::from).collect(),
+            entry,
         }
     }
 }
@@ -1370,6 +5250,12 @@ impl PyChangelogEntry {
         self.changes.clone()
     }
 
+    /// Metadata attached to the transaction (e.g. "author").
+    #[getter]
+    fn metadata(&self) -> HashMap<String, String> {
+        self.entry.metadata.clone()
+    }
+
     /// Get list of changed table names.
     fn changed_tables(&self) -> Vec<String> {
         self.changes.iter().map(|c| c.table_name.clone()).collect()
@@ -1392,6 +5278,22 @@ impl PyChangelogEntry {
         self.changes.len()
     }
 
+    /// Whether the commit was an automatic algebraic merge.
+    fn is_auto_merge(&self) -> bool {
+        !self.auto_merged.is_empty()
+    }
+
+    /// The branch merge this entry recorded, or None for ordinary commits.
+    #[getter]
+    fn merge(&self) -> Option<PyMergeRecord> {
+        self.entry.merge.clone().map(Into::into)
+    }
+
+    /// Whether the entry records a branch merge rather than new data.
+    fn is_branch_merge(&self) -> bool {
+        self.entry.is_branch_merge()
+    }
+
     fn __repr__(&self) -> String {
         format!(
             "PyChangelogEntry(tx_id={}, branch={}, changes={})",
@@ -1400,6 +5302,176 @@ impl PyChangelogEntry {
     }
 }
 
+/// A consumer's persisted position in the changelog.
+#[pyclass(name = "ChangelogCursor")]
+struct PyChangelogCursor {
+    inner: ChangelogCursor,
+}
+
+#[pymethods]
+impl PyChangelogCursor {
+    #[getter]
+    fn name(&self) -> String {
+        self.inner.name.clone()
+    }
+
+    /// Last acknowledged transaction (0 before the first ack)
+    #[getter]
+    fn acked_tx_id(&self) -> u64 {
+        self.inner.acked_tx_id
+    }
+
+    #[getter]
+    fn created_at(&self) -> i64 {
+        self.inner.created_at
+    }
+
+    #[getter]
+    fn updated_at(&self) -> i64 {
+        self.inner.updated_at
+    }
+
+    /// Record that every entry up to and including tx_id is processed.
+    ///
+    /// Acknowledging an entry at or before the cursor is a no-op.
+    fn ack(&mut self, tx_id: u64) -> PyResult<()> {
+        self.inner.ack(tx_id).map_err(tx_err_to_py)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("ChangelogCursor(name={:?}, acked_tx_id={})", self.inner.name, self.inner.acked_tx_id)
+    }
+}
+
+/// Result of a changelog replay.
+#[pyclass(name = "ReplayReport")]
+#[derive(Clone)]
+struct PyReplayReport {
+    #[pyo3(get)]
+    entries_applied: usize,
+    #[pyo3(get)]
+    versions_created: usize,
+    #[pyo3(get)]
+    tables_dropped: usize,
+    #[pyo3(get)]
+    tables_renamed: usize,
+    #[pyo3(get)]
+    chunks_copied: usize,
+    #[pyo3(get)]
+    bytes_copied: u64,
+    #[pyo3(get)]
+    first_tx_id: Option<u64>,
+    #[pyo3(get)]
+    last_tx_id: Option<u64>,
+    /// Branch heads written by the replay: branch -> table -> version
+    #[pyo3(get)]
+    heads: HashMap<String, HashMap<String, u64>>,
+}
+
+#[pymethods]
+impl PyReplayReport {
+    fn __repr__(&self) -> String {
+        format!(
+            "ReplayReport(entries_applied={}, versions_created={}, last_tx_id={})",
+            self.entries_applied,
+            self.versions_created,
+            self.last_tx_id.map_or("None".to_string(), |id| id.to_string())
+        )
+    }
+}
+
+impl From<ReplayReport> for PyReplayReport {
+    fn from(report: ReplayReport) -> Self {
+        Self {
+            entries_applied: report.entries_applied,
+            versions_created: report.versions_created,
+            tables_dropped: report.tables_dropped,
+            tables_renamed: report.tables_renamed,
+            chunks_copied: report.chunks_copied,
+            bytes_copied: report.bytes_copied,
+            first_tx_id: report.first_tx_id,
+            last_tx_id: report.last_tx_id,
+            heads: report
+                .heads
+                .into_iter()
+                .map(|(branch, tables)| (branch, tables.into_iter().collect()))
+                .collect(),
+        }
+    }
+}
+
+/// Convert ReplayError to appropriate Python exception
+fn replay_err_to_py(e: ReplayError) -> PyErr {
+    let info = ErrorAttrs::of(&e);
+    let err = match e {
+        ReplayError::Catalog(e) => catalog_err_to_py(e),
+        ReplayError::ChunkStore(e) => chunk_err_to_py(e),
+        ReplayError::Branch(e) => branch_err_to_py(e),
+        e @ (ReplayError::OutOfOrder { .. }
+        | ReplayError::ContentMismatch(..)
+        | ReplayError::HeadMismatch(_)) => PyValueError::new_err(e.to_string()),
+        e => PyRuntimeError::new_err(sanitize_error_message(&e.to_string())),
+    };
+    info.attach(err)
+}
+
+/// Replay changelog entries onto another repository.
+///
+/// Rebuilds table versions and branch heads by applying the entries in
+/// transaction order, copying chunks from the source store. Each change
+/// must create the next version of its table in the target.
+///
+/// Args:
+///     entries: Changelog entries (from PyTransactionManager.get_changelog)
+///     source_store: Source PyChunkStore (chunks are read from here)
+///     source_catalog: Source PyCatalog (version records are copied from here)
+///     target_store: Target PyChunkStore
+///     target_catalog: Target PyCatalog
+///     source_branches: Source PyBranchManager (needed for verify_heads)
+///     target_branches: Target PyBranchManager (branch heads are replayed
+///         only when given)
+///     verify_heads: Require replayed heads to equal the source heads
+///
+/// Returns:
+///     ReplayReport
+///
+/// Raises:
+///     ValueError: If the range does not continue the target's history, the
+///         changelog disagrees with the source catalog, or heads differ
+#[pyfunction]
+#[pyo3(signature = (
+    entries, source_store, source_catalog, target_store, target_catalog,
+    source_branches=None, target_branches=None, verify_heads=false
+))]
+#[allow(clippy::too_many_arguments)]
+fn replay_changelog(
+    entries: Vec<PyRef<'_, PyChangelogEntry>>,
+    source_store: &PyChunkStore,
+    source_catalog: &PyCatalog,
+    target_store: &PyChunkStore,
+    target_catalog: &PyCatalog,
+    source_branches: Option<&PyBranchManager>,
+    target_branches: Option<&PyBranchManager>,
+    verify_heads: bool,
+) -> PyResult<PyReplayReport> {
+    let entries: Vec<ChangelogEntry> = entries.iter().map(|e| e.entry.clone()).collect();
+
+    let mut source = RepoRef::new(&source_catalog.inner, &source_store.inner);
+    if let Some(manager) = source_branches {
+        source = source.with_branches(&manager.inner);
+    }
+    let mut target = RepoRef::new(&target_catalog.inner, &target_store.inner);
+    if let Some(manager) = target_branches {
+        target = target.with_branches(&manager.inner);
+    }
+
+    ChangelogReplay::new(source)
+        .verify_heads(verify_heads)
+        .run(&entries, target)
+        .map(PyReplayReport::from)
+        .map_err(replay_err_to_py)
+}
+
 // =============================================================================
 // Merkle Tree Types
 // =============================================================================
@@ -1688,31 +5760,419 @@ fn merkle_diff_trees(old_tree: &PyMerkleTree, new_tree: &PyMerkleTree) -> PyMerk
     PyMerkleDiff::from(diff_trees(&old_tree.inner, &new_tree.inner))
 }
 
-/// Verify integrity of a Merkle tree.
-///
-/// This function verifies that all chunk hashes match their actual data
-/// and that the tree structure is consistent.
-///
-/// Args:
-///     tree: The Merkle tree to verify
-///     chunk_store: A PyChunkStore to retrieve chunk data
-///
-/// Returns:
-///     True if verification passes
-///
-/// Raises:
-///     ValueError: If integrity check fails
-#[pyfunction]
-fn merkle_verify_tree(tree: &PyMerkleTree, chunk_store: &PyChunkStore) -> PyResult<bool> {
-    verify_tree(&tree.inner, |hash| {
-        chunk_store.inner.get(hash).ok()
-    }).map_err(merkle_err_to_py)
+/// Verify integrity of a Merkle tree.
+///
+/// This function verifies that all chunk hashes match their actual data
+/// and that the tree structure is consistent.
+///
+/// Args:
+///     tree: The Merkle tree to verify
+///     chunk_store: A PyChunkStore to retrieve chunk data
+///
+/// Returns:
+///     True if verification passes
+///
+/// Raises:
+///     ValueError: If integrity check fails
+#[pyfunction]
+fn merkle_verify_tree(tree: &PyMerkleTree, chunk_store: &PyChunkStore) -> PyResult<bool> {
+    verify_tree(&tree.inner, |hash| {
+        chunk_store.inner.get(hash).ok()
+    }).map_err(merkle_err_to_py)
+}
+
+// =============================================================================
+// Transaction Manager
+// =============================================================================
+
+fn parse_commit_priority(priority: &str) -> PyResult<CommitPriority> {
+    match priority.to_ascii_lowercase().as_str() {
+        "interactive" => Ok(CommitPriority::Interactive),
+        "normal" => Ok(CommitPriority::Normal),
+        "bulk" => Ok(CommitPriority::Bulk),
+        other => Err(PyValueError::new_err(format!(
+            "Unknown priority: {} (expected 'interactive', 'normal' or 'bulk')",
+            other
+        ))),
+    }
+}
+
+fn parse_merge_resolution(side: &str) -> PyResult<MergeResolution> {
+    match side.to_ascii_lowercase().as_str() {
+        "source" => Ok(MergeResolution::Source),
+        "target" => Ok(MergeResolution::Target),
+        other => Err(PyValueError::new_err(format!(
+            "Unknown merge resolution: {} (expected 'source' or 'target')",
+            other
+        ))),
+    }
+}
+
+fn parse_conflict_granularity(granularity: &str) -> PyResult<ConflictGranularity> {
+    match granularity.to_ascii_lowercase().as_str() {
+        "table" => Ok(ConflictGranularity::Table),
+        "partition" => Ok(ConflictGranularity::Partition),
+        "row" => Ok(ConflictGranularity::Row),
+        "chunk" => Ok(ConflictGranularity::Chunk),
+        other => Err(PyValueError::new_err(format!(
+            "Unknown conflict granularity: {} (expected 'table', 'partition', 'row' or 'chunk')",
+            other
+        ))),
+    }
+}
+
+fn parse_conflict_window(window: &str) -> PyResult<ConflictWindow> {
+    let window = window.to_ascii_lowercase();
+    let count = |n: &str| {
+        n.parse::<u64>().map_err(|_| PyValueError::new_err(format!("Invalid conflict window count: {}", n)))
+    };
+    match window.split_once(':') {
+        None if window == "memory" => Ok(ConflictWindow::Memory),
+        None if window == "lifetime" => Ok(ConflictWindow::Lifetime),
+        Some(("epochs", n)) => Ok(ConflictWindow::Epochs(count(n)?)),
+        Some(("seconds", n)) => Ok(ConflictWindow::Seconds(count(n)?)),
+        _ => Err(PyValueError::new_err(format!(
+            "Unknown conflict window: {} (expected 'memory', 'lifetime', 'epochs:N' or 'seconds:N')",
+            window
+        ))),
+    }
+}
+
+fn format_conflict_window(window: ConflictWindow) -> String {
+    match window {
+        ConflictWindow::Memory => "memory".to_string(),
+        ConflictWindow::Epochs(n) => format!("epochs:{}", n),
+        ConflictWindow::Seconds(n) => format!("seconds:{}", n),
+        ConflictWindow::Lifetime => "lifetime".to_string(),
+    }
+}
+
+/// Commit queue metrics, keyed by priority ("interactive", "normal", "bulk")
+#[pyclass]
+#[derive(Clone)]
+struct PyCommitQueueStats {
+    /// Commits currently waiting for admission
+    #[pyo3(get)]
+    waiting: HashMap<String, usize>,
+    /// Commits admitted so far
+    #[pyo3(get)]
+    admitted: HashMap<String, u64>,
+    #[pyo3(get)]
+    mean_wait_ms: HashMap<String, f64>,
+    #[pyo3(get)]
+    max_wait_ms: HashMap<String, f64>,
+    /// Commits admitted out of priority order to prevent starvation
+    #[pyo3(get)]
+    starvation_grants: u64,
+}
+
+#[pymethods]
+impl PyCommitQueueStats {
+    fn __repr__(&self) -> String {
+        format!(
+            "CommitQueueStats(waiting={}, admitted={}, starvation_grants={})",
+            self.waiting.values().sum::<usize>(),
+            self.admitted.values().sum::<u64>(),
+            self.starvation_grants
+        )
+    }
+}
+
+impl From<CommitQueueStats> for PyCommitQueueStats {
+    fn from(stats: CommitQueueStats) -> Self {
+        let mut out = Self {
+            waiting: HashMap::new(),
+            admitted: HashMap::new(),
+            mean_wait_ms: HashMap::new(),
+            max_wait_ms: HashMap::new(),
+            starvation_grants: stats.starvation_grants,
+        };
+        for priority in CommitPriority::ALL {
+            let key = priority.to_string().to_ascii_lowercase();
+            let s = stats.get(priority);
+            out.waiting.insert(key.clone(), s.waiting);
+            out.admitted.insert(key.clone(), s.admitted);
+            out.mean_wait_ms.insert(key.clone(), s.mean_wait().as_secs_f64() * 1000.0);
+            out.max_wait_ms.insert(key, s.max_wait.as_secs_f64() * 1000.0);
+        }
+        out
+    }
+}
+
+/// Phase timings of one commit attempt, in milliseconds
+#[pyclass]
+#[derive(Clone)]
+struct PyCommitTimings {
+    #[pyo3(get)]
+    tx_id: u64,
+    #[pyo3(get)]
+    branch: String,
+    /// Phase -> time spent, for the phases the commit reached: "queue",
+    /// "conflict_check", "snapshot_validation", "catalog_writes",
+    /// "branch_updates", "log_persistence"
+    #[pyo3(get)]
+    phases_ms: HashMap<String, f64>,
+    #[pyo3(get)]
+    total_ms: f64,
+    #[pyo3(get)]
+    slowest_phase: Option<String>,
+    /// Error message if the commit failed
+    #[pyo3(get)]
+    error: Option<String>,
+}
+
+#[pymethods]
+impl PyCommitTimings {
+    #[getter]
+    fn succeeded(&self) -> bool {
+        self.error.is_none()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "CommitTimings(tx_id={}, total_ms={:.3}, slowest_phase={})",
+            self.tx_id,
+            self.total_ms,
+            self.slowest_phase.as_deref().unwrap_or("none")
+        )
+    }
+}
+
+impl From<&CommitTimings> for PyCommitTimings {
+    fn from(timings: &CommitTimings) -> Self {
+        let phases_ms = CommitPhase::ALL
+            .iter()
+            .filter(|p| timings.phases.iter().any(|(reached, _)| reached == *p))
+            .map(|p| (p.to_string(), timings.phase(*p).as_secs_f64() * 1000.0))
+            .collect();
+        Self {
+            tx_id: timings.tx_id,
+            branch: timings.branch.clone(),
+            phases_ms,
+            total_ms: timings.total.as_secs_f64() * 1000.0,
+            slowest_phase: timings.slowest_phase().map(|p| p.to_string()),
+            error: timings.error.clone(),
+        }
+    }
+}
+
+/// Forwards commit timings to a Python callable
+struct PyCallableSink {
+    callback: Py<PyAny>,
+}
+
+impl CommitMetricsSink for PyCallableSink {
+    fn record_commit(&self, timings: &CommitTimings) {
+        Python::attach(|py| {
+            // A failing callback must not fail the commit it observes
+            if let Err(err) = self.callback.call1(py, (PyCommitTimings::from(timings),)) {
+                err.write_unraisable(py, Some(self.callback.bind(py)));
+            }
+        });
+    }
+}
+
+/// A transaction hook calling a Python callable with the transaction and
+/// its changes
+fn py_hook(callback: Py<PyAny>) -> impl Fn(&TransactionRecord, &[TableChange]) + Send + Sync + 'static {
+    move |tx, changes| {
+        Python::attach(|py| {
+            let changes: Vec<PyTableChange> = changes.iter().map(PyTableChange::from).collect();
+            // A failing callback must not fail the transaction it observes
+            if let Err(err) = callback.call1(py, (PyTransactionInfo::from(tx.clone()), changes)) {
+                err.write_unraisable(py, Some(callback.bind(py)));
+            }
+        });
+    }
+}
+
+fn parse_on_conflict(on_conflict: &str) -> PyResult<OnConflict> {
+    match on_conflict.to_ascii_lowercase().as_str() {
+        "abort" => Ok(OnConflict::Abort),
+        "analyze" => Ok(OnConflict::Analyze),
+        other => Err(PyValueError::new_err(format!(
+            "Unknown on_conflict: {} (expected 'abort' or 'analyze')",
+            other
+        ))),
+    }
+}
+
+/// Build a TableWrite from add_write's arguments
+fn table_write(
+    table_name: &str,
+    new_version: u64,
+    chunk_hashes: Vec<String>,
+    partitions: Option<Vec<String>>,
+    key_columns: Option<Vec<String>>,
+    keys: Option<Vec<String>>,
+) -> PyResult<TableWrite> {
+    let granularity = match (partitions, keys) {
+        (Some(_), Some(_)) => {
+            return Err(PyValueError::new_err("Pass either partitions or keys, not both"));
+        }
+        (Some(partitions), None) => WriteGranularity::Partitions(partitions),
+        (None, Some(keys)) => WriteGranularity::Keys {
+            key_columns: key_columns.unwrap_or_default(),
+            affected_keys: keys.into_iter().map(serde_json::Value::String).collect(),
+        },
+        (None, None) => WriteGranularity::WholeTable,
+    };
+    Ok(TableWrite::new(table_name, new_version, chunk_hashes).with_granularity(granularity))
+}
+
+/// A transaction begun by PyTransactionManager.transaction().
+///
+/// As a context manager it commits when the block exits cleanly and aborts
+/// when it raises; commit() and abort() may also be called explicitly, after
+/// which the block's exit does nothing.
+#[pyclass(name = "Transaction")]
+struct PyTransaction {
+    manager: Arc<TransactionManager>,
+    tx_id: u64,
+    on_conflict: OnConflict,
+    /// "active", "committed" or "aborted"
+    state: &'static str,
+}
+
+impl PyTransaction {
+    fn active_id(&self) -> PyResult<u64> {
+        if self.state != "active" {
+            return Err(PyValueError::new_err(format!(
+                "Transaction {} is already {}",
+                self.tx_id, self.state
+            )));
+        }
+        Ok(self.tx_id)
+    }
+}
+
+#[pymethods]
+impl PyTransaction {
+    #[getter]
+    fn tx_id(&self) -> u64 {
+        self.tx_id
+    }
+
+    /// "active", "committed" or "aborted"
+    #[getter]
+    fn state(&self) -> &'static str {
+        self.state
+    }
+
+    /// Add a write; arguments as for PyTransactionManager.add_write.
+    #[pyo3(signature = (table_name, new_version, chunk_hashes, partitions=None, key_columns=None, keys=None))]
+    fn add_write(
+        &self,
+        table_name: &str,
+        new_version: u64,
+        chunk_hashes: Vec<String>,
+        partitions: Option<Vec<String>>,
+        key_columns: Option<Vec<String>>,
+        keys: Option<Vec<String>>,
+    ) -> PyResult<()> {
+        let tx_id = self.active_id()?;
+        let write = table_write(table_name, new_version, chunk_hashes, partitions, key_columns, keys)?;
+        self.manager.add_write(tx_id, write).map_err(tx_err_to_py)
+    }
+
+    /// Record a read of `table_name` at `version` for conflict detection.
+    fn record_read(&self, table_name: &str, version: u64) -> PyResult<()> {
+        let tx_id = self.active_id()?;
+        self.manager.record_read(tx_id, table_name, version).map_err(tx_err_to_py)
+    }
+
+    /// Attach a metadata entry, copied into the changelog entry on commit.
+    fn set_metadata(&self, key: &str, value: &str) -> PyResult<()> {
+        let tx_id = self.active_id()?;
+        self.manager.set_metadata(tx_id, key, value).map_err(tx_err_to_py)
+    }
+
+    /// Set the commit priority: "interactive", "normal" or "bulk".
+    fn set_priority(&self, priority: &str) -> PyResult<()> {
+        let tx_id = self.active_id()?;
+        self.manager
+            .set_priority(tx_id, parse_commit_priority(priority)?)
+            .map_err(tx_err_to_py)
+    }
+
+    fn savepoint(&self, name: &str) -> PyResult<()> {
+        let tx_id = self.active_id()?;
+        self.manager.savepoint(tx_id, name).map_err(tx_err_to_py)
+    }
+
+    fn rollback_to_savepoint(&self, name: &str) -> PyResult<()> {
+        let tx_id = self.active_id()?;
+        self.manager.rollback_to_savepoint(tx_id, name).map_err(tx_err_to_py)
+    }
+
+    fn release_savepoint(&self, name: &str) -> PyResult<()> {
+        let tx_id = self.active_id()?;
+        self.manager.release_savepoint(tx_id, name).map_err(tx_err_to_py)
+    }
+
+    /// Commit now, with the on_conflict mode given to transaction().
+    ///
+    /// A failed commit leaves the transaction active.
+    ///
+    /// Raises:
+    ///     ValueError: If a conflict is detected or the transaction has
+    ///         already been committed or aborted
+    fn commit(&mut self) -> PyResult<()> {
+        let tx_id = self.active_id()?;
+        self.manager.commit_with(tx_id, self.on_conflict).map_err(tx_err_to_py)?;
+        self.state = "committed";
+        Ok(())
+    }
+
+    #[pyo3(signature = (reason="User requested"))]
+    fn abort(&mut self, reason: &str) -> PyResult<()> {
+        let tx_id = self.active_id()?;
+        self.manager.abort(tx_id, reason).map_err(tx_err_to_py)?;
+        self.state = "aborted";
+        Ok(())
+    }
+
+    fn __enter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    /// Commit on a clean exit, abort if the block raised. A commit that
+    /// fails aborts the transaction and raises the commit error; the block's
+    /// own exception is never suppressed.
+    fn __exit__(
+        &mut self,
+        exc_type: Option<&Bound<'_, PyAny>>,
+        _exc_value: Option<&Bound<'_, PyAny>>,
+        _traceback: Option<&Bound<'_, PyAny>>,
+    ) -> PyResult<bool> {
+        if self.state != "active" {
+            return Ok(false);
+        }
+        match exc_type {
+            None => {
+                if let Err(e) = self.commit() {
+                    // Best effort: the commit error is what the caller needs
+                    let _ = self.abort("Commit failed");
+                    return Err(e);
+                }
+            }
+            Some(exc_type) => {
+                let name = exc_type
+                    .getattr("__name__")
+                    .and_then(|n| n.extract::<String>())
+                    .unwrap_or_else(|_| "exception".to_string());
+                // The block's exception propagates even if the abort fails
+                let _ = self.abort(&format!("{} raised in transaction block", name));
+            }
+        }
+        Ok(false)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Transaction(tx_id={}, state={})", self.tx_id, self.state)
+    }
 }
 
-// =============================================================================
-// Transaction Manager
-// =============================================================================
-
 #[pyclass]
 struct PyTransactionManager {
     inner: Arc<TransactionManager>,
@@ -1727,22 +6187,75 @@ impl PyTransactionManager {
     ///     catalog_path: Path to catalog directory
     ///     branch_path: Optional path to branch manager directory
     ///     auto_recover: If True, run recovery on startup (default: False)
+    ///     epoch_config: Epoch configuration used when the log is first
+    ///         created (default: PyEpochConfig.single_node())
+    ///     schema_registry: Algebraic schemas consulted by
+    ///         commit(on_conflict="analyze"); copied at construction
+    ///     priority_commits: If True, commits waiting for the commit lock are
+    ///         admitted by priority (see set_priority) instead of FIFO
+    ///     max_bypass: With priority_commits, how many later commits may
+    ///         overtake a waiting one before it is admitted first
+    ///     conflict_granularity: "table" (default), "partition", "row" or
+    ///         "chunk"; finer granularities let transactions write the same
+    ///         table if their partitions, keys or chunks do not overlap
+    ///     conflict_window: How far back commits evicted from memory are
+    ///         read from the log for conflict checks: "memory" (default,
+    ///         none), "epochs:N", "seconds:N" or "lifetime" (everything
+    ///         committed since the transaction began)
+    ///     metrics_sink: Optional callable invoked with a PyCommitTimings
+    ///         after every commit attempt; exceptions it raises are reported
+    ///         as unraisable and do not affect the commit
+    ///     merge_store: PyChunkStore holding the tables' chunks, where
+    ///         commit(on_conflict="analyze") reads and writes merged rows;
+    ///         without it such conflicts fail like any other
     #[new]
-    #[pyo3(signature = (base_path, catalog_path, branch_path=None, auto_recover=false))]
+    #[pyo3(signature = (base_path, catalog_path, branch_path=None, auto_recover=false, epoch_config=None, schema_registry=None, priority_commits=false, max_bypass=None, conflict_granularity="table", conflict_window="memory", metrics_sink=None, merge_store=None))]
+    #[allow(clippy::too_many_arguments)]
     fn new(
         base_path: &str,
         catalog_path: &str,
         branch_path: Option<&str>,
         auto_recover: bool,
+        epoch_config: Option<PyEpochConfig>,
+        schema_registry: Option<PyRef<'_, PyAlgebraicSchemaRegistry>>,
+        priority_commits: bool,
+        max_bypass: Option<u32>,
+        conflict_granularity: &str,
+        conflict_window: &str,
+        metrics_sink: Option<Py<PyAny>>,
+        merge_store: Option<PyRef<'_, PyChunkStore>>,
     ) -> PyResult<Self> {
+        let granularity = parse_conflict_granularity(conflict_granularity)?;
+        let window = parse_conflict_window(conflict_window)?;
         let catalog = Arc::new(FileCatalog::new(catalog_path).map_err(catalog_err_to_py)?);
         let branch_manager = match branch_path {
             Some(p) => Some(Arc::new(BranchManager::new(p).map_err(branch_err_to_py)?)),
             None => None,
         };
 
-        let inner = TransactionManager::new(base_path, catalog, branch_manager)
+        let config = epoch_config
+            .map(|c| c.inner)
+            .unwrap_or_else(EpochConfig::single_node);
+        let mut inner = TransactionManager::with_config(base_path, catalog, branch_manager, config)
             .map_err(tx_err_to_py)?;
+        if let Some(registry) = schema_registry {
+            inner.set_schema_registry(Arc::new(registry.inner.clone()));
+        }
+        if priority_commits {
+            let scheduler = match max_bypass {
+                Some(n) => CommitScheduler::with_max_bypass(n),
+                None => CommitScheduler::new(),
+            };
+            inner.set_commit_scheduler(Arc::new(scheduler));
+        }
+        inner.set_conflict_granularity(granularity);
+        inner.set_conflict_window(window);
+        if let Some(callback) = metrics_sink {
+            inner.set_metrics_sink(Arc::new(PyCallableSink { callback }));
+        }
+        if let Some(store) = merge_store {
+            inner.set_merge_store(Arc::clone(&store.inner));
+        }
 
         // Optionally run recovery on startup
         if auto_recover {
@@ -1764,6 +6277,30 @@ impl PyTransactionManager {
         self.inner.begin(branch).map_err(tx_err_to_py)
     }
 
+    /// Begin a transaction wrapped in a Transaction object.
+    ///
+    /// Use it as a context manager: the transaction commits when the block
+    /// exits cleanly and aborts if it raises.
+    ///
+    ///     with tm.transaction("main") as tx:
+    ///         tx.record_read("users", 1)
+    ///         tx.add_write("users", 2, ["h1", "h2"])
+    ///
+    /// Args:
+    ///     branch: Optional branch name (default: current branch)
+    ///     on_conflict: Passed to commit() on clean exit ("abort" or "analyze")
+    #[pyo3(signature = (branch=None, on_conflict="abort"))]
+    fn transaction(&self, branch: Option<&str>, on_conflict: &str) -> PyResult<PyTransaction> {
+        let on_conflict = parse_on_conflict(on_conflict)?;
+        let tx_id = self.inner.begin(branch).map_err(tx_err_to_py)?;
+        Ok(PyTransaction {
+            manager: Arc::clone(&self.inner),
+            tx_id,
+            on_conflict,
+            state: "active",
+        })
+    }
+
     /// Add a write to a transaction.
     ///
     /// Args:
@@ -1771,17 +6308,154 @@ impl PyTransactionManager {
     ///     table_name: Table being written
     ///     new_version: New version number
     ///     chunk_hashes: List of chunk hashes
+    ///     partitions: Partitions written (for "partition"/"row" granularity)
+    ///     key_columns: Key columns of the rows written (with keys)
+    ///     keys: Row keys written, compared as strings (for "row" granularity)
+    #[pyo3(signature = (tx_id, table_name, new_version, chunk_hashes, partitions=None, key_columns=None, keys=None))]
+    #[allow(clippy::too_many_arguments)]
     fn add_write(
         &self,
         tx_id: u64,
         table_name: &str,
         new_version: u64,
         chunk_hashes: Vec<String>,
+        partitions: Option<Vec<String>>,
+        key_columns: Option<Vec<String>>,
+        keys: Option<Vec<String>>,
     ) -> PyResult<()> {
-        let write = TableWrite::new(table_name, new_version, chunk_hashes);
+        let write = table_write(table_name, new_version, chunk_hashes, partitions, key_columns, keys)?;
         self.inner.add_write(tx_id, write).map_err(tx_err_to_py)
     }
 
+    /// Conflict granularity: "table", "partition", "row" or "chunk"
+    #[getter]
+    fn conflict_granularity(&self) -> String {
+        self.inner.conflict_granularity().to_string().to_ascii_lowercase()
+    }
+
+    /// Conflict window: "memory", "epochs:N", "seconds:N" or "lifetime"
+    #[getter]
+    fn conflict_window(&self) -> String {
+        format_conflict_window(self.inner.conflict_window())
+    }
+
+    /// Set the priority a transaction's commit is admitted with.
+    ///
+    /// Only takes effect when the manager was created with
+    /// priority_commits=True.
+    ///
+    /// Args:
+    ///     tx_id: Transaction ID
+    ///     priority: "interactive", "normal" (default) or "bulk"
+    fn set_priority(&self, tx_id: u64, priority: &str) -> PyResult<()> {
+        let priority = parse_commit_priority(priority)?;
+        self.inner.set_priority(tx_id, priority).map_err(tx_err_to_py)
+    }
+
+    /// Attach a metadata entry (e.g. "author") to an active transaction.
+    ///
+    /// Metadata is copied into the transaction's changelog entry.
+    fn set_metadata(&self, tx_id: u64, key: &str, value: &str) -> PyResult<()> {
+        self.inner.set_metadata(tx_id, key, value).map_err(tx_err_to_py)
+    }
+
+    /// Drop a table: remove every version from the catalog and the table
+    /// from every branch, and record a tombstone in the changelog.
+    ///
+    /// Args:
+    ///     table_name: Table to drop
+    ///     purge_store: Optional PyChunkStore to delete the dropped
+    ///         versions' chunks from, as sweep_chunks would: chunks another
+    ///         version or an in-flight transaction references, modified
+    ///         within the grace period, or a delta base of a kept chunk stay.
+    ///         Without it the chunks stay until garbage collected.
+    ///     grace_period_secs: Minimum age of a purged chunk
+    ///
+    /// Returns:
+    ///     The removed versions
+    #[pyo3(signature = (table_name, purge_store=None, grace_period_secs=3600.0))]
+    fn drop_table(
+        &self,
+        py: Python<'_>,
+        table_name: &str,
+        purge_store: Option<&PyChunkStore>,
+        grace_period_secs: f64,
+    ) -> PyResult<Vec<PyTableVersion>> {
+        let dropped = match purge_store {
+            Some(store) => {
+                let grace_period = std::time::Duration::try_from_secs_f64(grace_period_secs)
+                    .map_err(|_| PyValueError::new_err("grace_period_secs must be a non-negative number"))?;
+                py.detach(|| self.inner.drop_table_and_purge(table_name, &store.inner, grace_period))
+                    .map(|(dropped, _)| dropped)
+            }
+            None => self.inner.drop_table(table_name),
+        };
+        dropped
+            .map(|versions| versions.into_iter().map(Into::into).collect())
+            .map_err(tx_err_to_py)
+    }
+
+    /// Rename a table in the catalog and on every branch and record the
+    /// rename in the changelog. Returns the table's latest version.
+    fn rename_table(&self, old_name: &str, new_name: &str) -> PyResult<u64> {
+        self.inner.rename_table(old_name, new_name).map_err(tx_err_to_py)
+    }
+
+    /// Merge branch `source` into `into` as a merge commit and record it in
+    /// the changelog, with one "merge" change per head it moved.
+    ///
+    /// `strategy` is "strict", "prefer-source" or "prefer-target".
+    #[pyo3(signature = (source, into, strategy="strict"))]
+    fn merge(&self, source: &str, into: &str, strategy: &str) -> PyResult<PyMergeRecord> {
+        self.inner
+            .merge(source, into, parse_merge_strategy(strategy)?)
+            .map(Into::into)
+            .map_err(tx_err_to_py)
+    }
+
+    /// Commit queue metrics, or None without priority_commits.
+    fn commit_queue_stats(&self) -> PyResult<Option<PyCommitQueueStats>> {
+        Ok(self.inner.commit_queue_stats().map_err(tx_err_to_py)?.map(Into::into))
+    }
+
+    /// Call `callback(info, changes)` after every successful commit, with
+    /// the committed PyTransactionInfo and its list of PyTableChange.
+    ///
+    /// Hooks run on the committing thread; exceptions they raise are
+    /// reported as unraisable and do not affect the transaction.
+    ///
+    /// Returns:
+    ///     int: Hook id for remove_hook
+    fn on_commit(&self, callback: Py<PyAny>) -> u64 {
+        self.inner.on_commit(py_hook(callback))
+    }
+
+    /// Call `callback(info, changes)` after every abort, with the writes
+    /// the transaction discarded.
+    fn on_abort(&self, callback: Py<PyAny>) -> u64 {
+        self.inner.on_abort(py_hook(callback))
+    }
+
+    /// Call `callback(info, changes)` when a commit fails with a write or
+    /// snapshot conflict, with the still active transaction and its
+    /// refused writes.
+    fn on_conflict(&self, callback: Py<PyAny>) -> u64 {
+        self.inner.on_conflict(py_hook(callback))
+    }
+
+    /// Unregister a hook; returns whether it was registered.
+    fn remove_hook(&self, hook_id: u64) -> bool {
+        self.inner.remove_hook(hook_id)
+    }
+
+    /// Phase timings of the latest commit attempt of a transaction.
+    ///
+    /// Returns None if the transaction was never committed here or its
+    /// attempt is no longer among the most recent ones retained.
+    fn explain_commit(&self, tx_id: u64) -> PyResult<Option<PyCommitTimings>> {
+        Ok(self.inner.explain_commit(tx_id).map_err(tx_err_to_py)?.as_ref().map(Into::into))
+    }
+
     /// Record a read for conflict detection.
     ///
     /// Args:
@@ -1797,15 +6471,42 @@ impl PyTransactionManager {
         self.inner.record_read(tx_id, table_name, version).map_err(tx_err_to_py)
     }
 
+    /// Take a savepoint of the transaction's writes and read snapshot.
+    ///
+    /// Taking a savepoint with an existing name replaces it.
+    fn savepoint(&self, tx_id: u64, name: &str) -> PyResult<()> {
+        self.inner.savepoint(tx_id, name).map_err(tx_err_to_py)
+    }
+
+    /// Roll back to a savepoint, undoing writes and reads recorded after it.
+    ///
+    /// Later savepoints are discarded; this one is kept.
+    ///
+    /// Raises:
+    ///     ValueError: If the savepoint does not exist
+    fn rollback_to_savepoint(&self, tx_id: u64, name: &str) -> PyResult<()> {
+        self.inner.rollback_to_savepoint(tx_id, name).map_err(tx_err_to_py)
+    }
+
+    /// Release a savepoint and any taken after it, keeping current writes.
+    fn release_savepoint(&self, tx_id: u64, name: &str) -> PyResult<()> {
+        self.inner.release_savepoint(tx_id, name).map_err(tx_err_to_py)
+    }
+
     /// Commit a transaction.
     ///
     /// Args:
     ///     tx_id: Transaction ID
+    ///     on_conflict: "abort" (default) fails on any conflict; "analyze"
+    ///         merges conflicts on tables whose algebraic schema is fully
+    ///         conflict-free and records the commit as an auto-merge
     ///
     /// Raises:
     ///     ValueError: If conflict detected or transaction not active
-    fn commit(&self, tx_id: u64) -> PyResult<()> {
-        self.inner.commit(tx_id).map_err(tx_err_to_py)
+    #[pyo3(signature = (tx_id, on_conflict="abort"))]
+    fn commit(&self, tx_id: u64, on_conflict: &str) -> PyResult<()> {
+        let on_conflict = parse_on_conflict(on_conflict)?;
+        self.inner.commit_with(tx_id, on_conflict).map_err(tx_err_to_py)
     }
 
     /// Abort a transaction.
@@ -1910,10 +6611,12 @@ impl PyTransactionManager {
     ///     tables: Filter to specific tables
     ///     branch: Filter to specific branch
     ///     limit: Maximum entries to return
+    ///     until_tx_id: Stop at this transaction (inclusive)
     ///
     /// Returns:
     ///     List of PyChangelogEntry objects
-    #[pyo3(signature = (since_tx_id=None, since_timestamp=None, tables=None, branch=None, limit=None))]
+    #[pyo3(signature = (since_tx_id=None, since_timestamp=None, tables=None, branch=None, limit=None, until_tx_id=None))]
+    #[allow(clippy::too_many_arguments)]
     fn get_changelog(
         &self,
         since_tx_id: Option<u64>,
@@ -1921,12 +6624,16 @@ impl PyTransactionManager {
         tables: Option<Vec<String>>,
         branch: Option<String>,
         limit: Option<usize>,
+        until_tx_id: Option<u64>,
     ) -> PyResult<Vec<PyChangelogEntry>> {
         // Build query
         let mut query = ChangelogQuery::new();
         if let Some(tx_id) = since_tx_id {
             query = query.since_tx(tx_id);
         }
+        if let Some(tx_id) = until_tx_id {
+            query = query.until_tx(tx_id);
+        }
         if let Some(ts) = since_timestamp {
             query = query.since_time(ts);
         }
@@ -1940,20 +6647,349 @@ impl PyTransactionManager {
             query = query.with_limit(l);
         }
 
-        // Execute query
-        let entries = self.inner.get_changelog(query).map_err(tx_err_to_py)?;
+        // Execute query
+        let entries = self.inner.get_changelog(query).map_err(tx_err_to_py)?;
+
+        Ok(entries.into_iter().map(PyChangelogEntry::from).collect())
+    }
+
+    /// Get the latest committed transaction ID.
+    ///
+    /// Returns None if no transactions have been committed yet.
+    ///
+    /// Returns:
+    ///     Latest transaction ID or None
+    fn latest_tx_id(&self) -> PyResult<Option<u64>> {
+        self.inner.latest_tx_id().map_err(tx_err_to_py)
+    }
+
+    /// Create a named changelog cursor at the start of the changelog.
+    ///
+    /// Raises:
+    ///     ValueError: If the name is invalid or already taken
+    fn create_cursor(&self, name: &str) -> PyResult<PyChangelogCursor> {
+        self.inner
+            .create_cursor(name)
+            .map(|inner| PyChangelogCursor { inner })
+            .map_err(tx_err_to_py)
+    }
+
+    /// Load a changelog cursor at its last acknowledged transaction.
+    ///
+    /// Raises:
+    ///     ValueError: If no cursor has this name
+    fn resume(&self, name: &str) -> PyResult<PyChangelogCursor> {
+        self.inner
+            .resume(name)
+            .map(|inner| PyChangelogCursor { inner })
+            .map_err(tx_err_to_py)
+    }
+
+    /// All changelog cursors, sorted by name.
+    fn list_cursors(&self) -> PyResult<Vec<PyChangelogCursor>> {
+        self.inner
+            .list_cursors()
+            .map(|cursors| cursors.into_iter().map(|inner| PyChangelogCursor { inner }).collect())
+            .map_err(tx_err_to_py)
+    }
+
+    fn delete_cursor(&self, name: &str) -> PyResult<()> {
+        self.inner.delete_cursor(name).map(|_| ()).map_err(tx_err_to_py)
+    }
+
+    // =========================================================================
+    // Epoch Methods
+    // =========================================================================
+
+    /// Epoch configuration in effect for this transaction log.
+    #[getter]
+    fn epoch_config(&self) -> PyEpochConfig {
+        PyEpochConfig { inner: self.inner.config().clone() }
+    }
+
+    /// Get the epoch new transactions are currently assigned to.
+    ///
+    /// Returns:
+    ///     PyEpochInfo for the current epoch
+    fn current_epoch(&self) -> PyResult<PyEpochInfo> {
+        self.inner
+            .current_epoch()
+            .map(|e| e.into())
+            .map_err(tx_err_to_py)
+    }
+
+    /// List all epochs, oldest first.
+    ///
+    /// Returns:
+    ///     List of PyEpochInfo with per-epoch transaction counts
+    fn list_epochs(&self) -> PyResult<Vec<PyEpochInfo>> {
+        self.inner
+            .list_epochs()
+            .map(|epochs| epochs.into_iter().map(|e| e.into()).collect())
+            .map_err(tx_err_to_py)
+    }
+
+    /// Close the current epoch and start a new one.
+    ///
+    /// Transactions begun afterwards are assigned to the new epoch. The
+    /// sealed epoch is "Committed" unless some of its transactions are still
+    /// active, in which case it is "Committing".
+    ///
+    /// Returns:
+    ///     PyEpochInfo for the sealed epoch
+    fn seal_epoch(&self) -> PyResult<PyEpochInfo> {
+        self.inner
+            .seal_epoch()
+            .map(|e| e.into())
+            .map_err(tx_err_to_py)
+    }
+
+    /// Capture a consistent multi-table snapshot without opening a transaction.
+    ///
+    /// Versions are resolved with the same rules as begin(), so read-only
+    /// jobs see every table as of the same branch head.
+    ///
+    /// Args:
+    ///     branch: Optional branch name (default: current branch)
+    ///
+    /// Returns:
+    ///     Read-only mapping of table name -> (version, chunk_hashes)
+    #[pyo3(signature = (branch=None))]
+    fn read_snapshot<'py>(
+        &self,
+        py: Python<'py>,
+        branch: Option<&str>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let snapshot = self.inner.read_snapshot(branch).map_err(tx_err_to_py)?;
+
+        let tables: HashMap<String, (u64, Vec<String>)> = snapshot
+            .tables
+            .into_iter()
+            .map(|(name, t)| (name, (t.version, t.chunk_hashes)))
+            .collect();
+
+        let proxy = py.import("types")?.getattr("MappingProxyType")?;
+        proxy.call1((tables,))
+    }
+}
+
+/// Acknowledgement that the batch holding a write committed
+#[pyclass]
+#[derive(Clone)]
+struct PyBatchAck {
+    /// Transaction that committed the batch
+    #[pyo3(get)]
+    tx_id: u64,
+    #[pyo3(get)]
+    branch: String,
+    /// Table -> version committed by the batch
+    #[pyo3(get)]
+    versions: HashMap<String, u64>,
+    /// Writes committed together in the batch
+    #[pyo3(get)]
+    writes: usize,
+}
+
+#[pymethods]
+impl PyBatchAck {
+    fn __repr__(&self) -> String {
+        format!("BatchAck(tx_id={}, branch='{}', writes={})", self.tx_id, self.branch, self.writes)
+    }
+}
+
+impl From<BatchAck> for PyBatchAck {
+    fn from(ack: BatchAck) -> Self {
+        Self {
+            tx_id: ack.tx_id,
+            branch: ack.branch,
+            versions: ack.versions,
+            writes: ack.writes,
+        }
+    }
+}
+
+/// Counters of a PyMicroBatcher since it started
+#[pyclass]
+#[derive(Clone)]
+struct PyMicroBatchStats {
+    #[pyo3(get)]
+    batches_committed: u64,
+    #[pyo3(get)]
+    batches_failed: u64,
+    #[pyo3(get)]
+    writes_committed: u64,
+    #[pyo3(get)]
+    rows_committed: u64,
+    /// Writes buffered and not yet committed
+    #[pyo3(get)]
+    pending_writes: usize,
+}
+
+impl From<MicroBatchStats> for PyMicroBatchStats {
+    fn from(stats: MicroBatchStats) -> Self {
+        Self {
+            batches_committed: stats.batches_committed,
+            batches_failed: stats.batches_failed,
+            writes_committed: stats.writes_committed,
+            rows_committed: stats.rows_committed,
+            pending_writes: stats.pending_writes,
+        }
+    }
+}
+
+/// Resolves when the batch holding a write commits or fails
+#[pyclass]
+struct PyBatchTicket {
+    ticket: Mutex<Option<BatchTicket>>,
+    ack: Mutex<Option<PyBatchAck>>,
+}
+
+#[pymethods]
+impl PyBatchTicket {
+    /// Wait for the batch holding the write to commit.
+    ///
+    /// Args:
+    ///     timeout: Seconds to wait (default: until the batch commits)
+    ///
+    /// Returns:
+    ///     PyBatchAck, or None if the timeout expired first
+    ///
+    /// Raises:
+    ///     RuntimeError: If the batch failed to commit (the error carries
+    ///         the code of the underlying failure)
+    #[pyo3(signature = (timeout=None))]
+    fn wait(&self, py: Python<'_>, timeout: Option<f64>) -> PyResult<Option<PyBatchAck>> {
+        let timeout = timeout
+            .map(std::time::Duration::try_from_secs_f64)
+            .transpose()
+            .map_err(|_| PyValueError::new_err("timeout must be a non-negative number"))?;
+        if let Some(ack) = self.ack.lock().unwrap_or_else(|e| e.into_inner()).clone() {
+            return Ok(Some(ack));
+        }
+        let ticket = self
+            .ticket
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .take()
+            .ok_or_else(|| PyRuntimeError::new_err("Batch result was already delivered"))?;
+        let (ticket, result) = py.detach(move || {
+            let result = match timeout {
+                Some(timeout) => ticket.wait_timeout(timeout),
+                None => Some(ticket.wait()),
+            };
+            (ticket, result)
+        });
+        match result {
+            None => {
+                *self.ticket.lock().unwrap_or_else(|e| e.into_inner()) = Some(ticket);
+                Ok(None)
+            }
+            Some(result) => {
+                let ack = PyBatchAck::from(result.map_err(tx_err_to_py)?);
+                *self.ack.lock().unwrap_or_else(|e| e.into_inner()) = Some(ack.clone());
+                Ok(Some(ack))
+            }
+        }
+    }
+}
+
+/// Buffers small appends per branch and commits each buffer as one
+/// transaction once it holds `max_rows` rows or its oldest write has
+/// waited `max_delay_ms`.
+///
+/// Example:
+///     >>> batcher = PyMicroBatcher(tm, max_delay_ms=5)
+///     >>> ticket = batcher.submit("main", "events", [chunk_hash], rows=1)
+///     >>> ack = ticket.wait()  # committed as part of ack.tx_id
+#[pyclass]
+struct PyMicroBatcher {
+    inner: std::sync::RwLock<Option<MicroBatcher>>,
+}
+
+impl PyMicroBatcher {
+    fn with_inner<T>(&self, f: impl FnOnce(&MicroBatcher) -> PyResult<T>) -> PyResult<T> {
+        let inner = self.inner.read().unwrap_or_else(|e| e.into_inner());
+        let batcher = inner
+            .as_ref()
+            .ok_or_else(|| PyRuntimeError::new_err("Micro-batcher is closed"))?;
+        f(batcher)
+    }
+}
+
+impl Drop for PyMicroBatcher {
+    fn drop(&mut self) {
+        // Dropping commits what is still buffered; do it off the thread
+        // holding the GIL, which a Python metrics sink may need
+        if let Some(batcher) = self.inner.get_mut().unwrap_or_else(|e| e.into_inner()).take() {
+            std::thread::spawn(move || drop(batcher));
+        }
+    }
+}
+
+#[pymethods]
+impl PyMicroBatcher {
+    /// Args:
+    ///     manager: PyTransactionManager committing the batches
+    ///     max_delay_ms: Longest time a write waits before its batch commits
+    ///     max_rows: Buffered rows that trigger a commit early
+    ///     max_retries: Times a batch conflicting with another commit is
+    ///         rebuilt and retried
+    #[new]
+    #[pyo3(signature = (manager, max_delay_ms=10.0, max_rows=10_000, max_retries=3))]
+    fn new(manager: &PyTransactionManager, max_delay_ms: f64, max_rows: usize, max_retries: u32) -> PyResult<Self> {
+        let max_delay = std::time::Duration::try_from_secs_f64(max_delay_ms / 1000.0)
+            .map_err(|_| PyValueError::new_err("max_delay_ms must be a non-negative number"))?;
+        let config = MicroBatchConfig::default()
+            .with_max_delay(max_delay)
+            .with_max_rows(max_rows)
+            .with_max_retries(max_retries);
+        Ok(Self {
+            inner: std::sync::RwLock::new(Some(MicroBatcher::with_config(Arc::clone(&manager.inner), config))),
+        })
+    }
+
+    /// Buffer chunks appended to a table on a branch.
+    ///
+    /// Returns:
+    ///     PyBatchTicket resolving when the batch commits
+    #[pyo3(signature = (branch, table_name, chunk_hashes, rows=0))]
+    fn submit(&self, branch: &str, table_name: &str, chunk_hashes: Vec<String>, rows: usize) -> PyResult<PyBatchTicket> {
+        let write = BatchedWrite::new(table_name, chunk_hashes, rows);
+        let ticket = self.with_inner(|b| b.submit(branch, write).map_err(tx_err_to_py))?;
+        Ok(PyBatchTicket {
+            ticket: Mutex::new(Some(ticket)),
+            ack: Mutex::new(None),
+        })
+    }
+
+    /// Buffer an append and wait for its batch to commit.
+    #[pyo3(signature = (branch, table_name, chunk_hashes, rows=0))]
+    fn write(
+        &self,
+        py: Python<'_>,
+        branch: &str,
+        table_name: &str,
+        chunk_hashes: Vec<String>,
+        rows: usize,
+    ) -> PyResult<PyBatchAck> {
+        let write = BatchedWrite::new(table_name, chunk_hashes, rows);
+        let ticket = self.with_inner(|b| b.submit(branch, write).map_err(tx_err_to_py))?;
+        py.detach(move || ticket.wait()).map(Into::into).map_err(tx_err_to_py)
+    }
+
+    /// Commit every buffer now. Does not wait; wait on the tickets.
+    fn flush(&self) -> PyResult<()> {
+        self.with_inner(|b| b.flush().map_err(tx_err_to_py))
+    }
 
-        Ok(entries.into_iter().map(PyChangelogEntry::from).collect())
+    fn stats(&self) -> PyResult<PyMicroBatchStats> {
+        self.with_inner(|b| b.stats().map(Into::into).map_err(tx_err_to_py))
     }
 
-    /// Get the latest committed transaction ID.
-    ///
-    /// Returns None if no transactions have been committed yet.
-    ///
-    /// Returns:
-    ///     Latest transaction ID or None
-    fn latest_tx_id(&self) -> PyResult<Option<u64>> {
-        self.inner.latest_tx_id().map_err(tx_err_to_py)
+    /// Commit what is still buffered and stop the batcher. Idempotent.
+    fn close(&self, py: Python<'_>) {
+        let batcher = self.inner.write().unwrap_or_else(|e| e.into_inner()).take();
+        py.detach(move || drop(batcher));
     }
 }
 
@@ -1971,8 +7007,13 @@ impl PyTransactionManager {
 ///   - SemilatticeMin: min(a, b) - first-writer-wins
 ///   - SemilatticeUnion: set union - add-only sets
 ///   - SemilatticeIntersect: set intersection
+///   - SemilatticeObservedRemove: observed-remove set - sets with removal
+///   - SemilatticePnCounter: per-node increment/decrement tallies
+///   - LwwRegister: last-writer-wins by (timestamp, node)
 ///   - AbelianAdd: a + b - counters, deltas
 ///   - AbelianMultiply: a * b - scaling factors
+///   - BoundedAdd: a + b within lower/upper bounds (escrowed rights)
+///   - Custom: the function registered with register_custom_merge
 ///
 /// Conflicting types:
 ///   - GenericOverwrite: may conflict
@@ -1988,8 +7029,9 @@ struct PyOpType {
 impl PyOpType {
     /// Create an operation type from a string.
     ///
-    /// Valid values: "max", "min", "union", "intersect", "add", "multiply",
-    ///               "overwrite", "conditional", "unknown"
+    /// Valid values: "max", "min", "union", "intersect", "or_set", "pn_counter",
+    ///               "lww", "add", "multiply", "bounded_add", "overwrite",
+    ///               "conditional", "unknown"
     #[new]
     fn new(op_type: &str) -> PyResult<Self> {
         let inner = match op_type.to_lowercase().as_str() {
@@ -1997,19 +7039,36 @@ impl PyOpType {
             "min" | "semilattice_min" => OpType::SemilatticeMin,
             "union" | "semilattice_union" => OpType::SemilatticeUnion,
             "intersect" | "semilattice_intersect" => OpType::SemilatticeIntersect,
+            "or_set" | "semilattice_observed_remove" => OpType::SemilatticeObservedRemove,
+            "pn_counter" | "semilattice_pn_counter" => OpType::SemilatticePnCounter,
+            "lww" | "lww_register" => OpType::LwwRegister,
             "add" | "abelian_add" => OpType::AbelianAdd,
             "multiply" | "abelian_multiply" => OpType::AbelianMultiply,
+            "bounded_add" => OpType::BoundedAdd,
             "overwrite" | "generic_overwrite" => OpType::GenericOverwrite,
             "conditional" | "generic_conditional" => OpType::GenericConditional,
             "unknown" => OpType::Unknown,
             _ => return Err(PyValueError::new_err(format!(
-                "Invalid operation type: '{}'. Valid: max, min, union, intersect, add, multiply, overwrite, conditional, unknown",
+                "Invalid operation type: '{}'. Valid: max, min, union, intersect, or_set, pn_counter, lww, add, multiply, bounded_add, overwrite, conditional, unknown",
                 op_type
             ))),
         };
         Ok(Self { inner })
     }
 
+    /// A user-defined operation merging with the function registered as
+    /// `name` (see register_custom_merge).
+    #[staticmethod]
+    fn custom(name: &str) -> Self {
+        Self { inner: OpType::custom(name) }
+    }
+
+    /// A map operation merging the entries of shared keys with `entry_op`.
+    #[staticmethod]
+    fn map(entry_op: &PyOpType) -> Self {
+        Self { inner: OpType::map(entry_op.inner.clone()) }
+    }
+
     /// Check if this operation type is conflict-free.
     fn is_conflict_free(&self) -> bool {
         self.inner.is_conflict_free()
@@ -2051,6 +7110,30 @@ struct PyAlgebraicValue {
     inner: AlgebraicValue,
 }
 
+impl PyAlgebraicValue {
+    /// Parse a `decimal.Decimal`, int or str as an exact decimal.
+    fn parse_decimal(value: &Bound<'_, PyAny>) -> PyResult<Decimal> {
+        // Fixed-point formatting avoids exponents ("1E+3") the parser rejects
+        let decimal_type = value.py().import("decimal")?.getattr("Decimal")?;
+        let text: String = if value.is_instance(&decimal_type)? {
+            value.call_method1("__format__", ("f",))?.extract()?
+        } else {
+            value.str()?.extract()?
+        };
+        text.parse().map_err(|e: rhizo_core::DecimalError| PyValueError::new_err(e.to_string()))
+    }
+
+    fn bounded(&self) -> PyResult<&BoundedCounter> {
+        match &self.inner {
+            AlgebraicValue::BoundedCounter(counter) => Ok(counter),
+            other => Err(PyValueError::new_err(format!(
+                "Expected BoundedCounter, got {}",
+                other.type_name()
+            ))),
+        }
+    }
+}
+
 #[pymethods]
 impl PyAlgebraicValue {
     /// Create an algebraic value from a Python value.
@@ -2058,6 +7141,7 @@ impl PyAlgebraicValue {
     /// Type inference:
     ///   - int → Integer
     ///   - float → Float
+    ///   - decimal.Decimal → Decimal
     ///   - bool → Boolean
     ///   - list/set of str → StringSet
     ///   - list/set of int → IntSet
@@ -2070,6 +7154,8 @@ impl PyAlgebraicValue {
             AlgebraicValue::Boolean(v)
         } else if let Ok(v) = value.extract::<i64>() {
             AlgebraicValue::Integer(v)
+        } else if value.is_instance(&value.py().import("decimal")?.getattr("Decimal")?)? {
+            AlgebraicValue::Decimal(Self::parse_decimal(value)?)
         } else if let Ok(v) = value.extract::<f64>() {
             AlgebraicValue::Float(v)
         } else if let Ok(v) = value.extract::<std::collections::HashSet<String>>() {
@@ -2082,7 +7168,7 @@ impl PyAlgebraicValue {
             AlgebraicValue::IntSet(v.into_iter().collect())
         } else {
             return Err(PyValueError::new_err(
-                "Unsupported value type. Use int, float, bool, set/list of str, set/list of int, or None"
+                "Unsupported value type. Use int, float, decimal.Decimal, bool, set/list of str, set/list of int, or None"
             ));
         };
         Ok(Self { inner })
@@ -2100,6 +7186,23 @@ impl PyAlgebraicValue {
         Self { inner: AlgebraicValue::Float(v) }
     }
 
+    /// Create an exact decimal value from a decimal.Decimal, int or str.
+    ///
+    /// Raises ValueError for NaN, infinities, exponent notation in a str,
+    /// or more than 38 fractional digits.
+    #[staticmethod]
+    fn decimal(value: &Bound<'_, PyAny>) -> PyResult<Self> {
+        Ok(Self { inner: AlgebraicValue::Decimal(Self::parse_decimal(value)?) })
+    }
+
+    /// The value as a decimal.Decimal (for Decimal and Integer values).
+    fn to_decimal<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let decimal = self.inner.as_decimal().ok_or_else(|| {
+            PyValueError::new_err(format!("Expected Decimal or Integer, got {}", self.inner.type_name()))
+        })?;
+        py.import("decimal")?.getattr("Decimal")?.call1((decimal.to_string(),))
+    }
+
     /// Create a string set value.
     #[staticmethod]
     fn string_set(values: Vec<String>) -> Self {
@@ -2112,6 +7215,165 @@ impl PyAlgebraicValue {
         Self { inner: AlgebraicValue::int_set(values) }
     }
 
+    /// Create an observed-remove set, adding each element under a fresh tag.
+    #[staticmethod]
+    fn or_set(elements: Vec<String>) -> Self {
+        let mut set = ObservedRemoveSet::new();
+        for element in elements {
+            set.add(element);
+        }
+        Self { inner: AlgebraicValue::ObservedRemoveSet(set) }
+    }
+
+    /// Delta removing an element's adds observed by this observed-remove set.
+    ///
+    /// Merge the result (with "or_set") to remove the element; adds made
+    /// concurrently elsewhere survive.
+    fn or_set_removal(&self, element: &str) -> PyResult<Self> {
+        match &self.inner {
+            AlgebraicValue::ObservedRemoveSet(set) => Ok(Self {
+                inner: AlgebraicValue::ObservedRemoveSet(set.removal(element)),
+            }),
+            other => Err(PyValueError::new_err(format!(
+                "Expected ObservedRemoveSet, got {}",
+                other.type_name()
+            ))),
+        }
+    }
+
+    /// Elements present in an observed-remove set, sorted.
+    fn or_set_elements(&self) -> PyResult<Vec<String>> {
+        match &self.inner {
+            AlgebraicValue::ObservedRemoveSet(set) => {
+                Ok(set.elements().map(str::to_string).collect())
+            }
+            other => Err(PyValueError::new_err(format!(
+                "Expected ObservedRemoveSet, got {}",
+                other.type_name()
+            ))),
+        }
+    }
+
+    /// Create a PN-counter holding one node's increment and decrement tallies.
+    ///
+    /// Merge counters with "pn_counter"; each node's tallies must only grow.
+    #[staticmethod]
+    #[pyo3(signature = (node, increments=0, decrements=0))]
+    fn pn_counter(node: &str, increments: u64, decrements: u64) -> Self {
+        let mut counter = PnCounter::new();
+        counter.increment(node, increments);
+        counter.decrement(node, decrements);
+        Self { inner: AlgebraicValue::PnCounter(counter) }
+    }
+
+    /// Effective value, total increments and total decrements of a PN-counter.
+    fn pn_counter_totals(&self) -> PyResult<(i64, u64, u64)> {
+        match &self.inner {
+            AlgebraicValue::PnCounter(counter) => {
+                Ok((counter.value(), counter.increments(), counter.decrements()))
+            }
+            other => Err(PyValueError::new_err(format!(
+                "Expected PnCounter, got {}",
+                other.type_name()
+            ))),
+        }
+    }
+
+    /// Create a bounded counter at `initial` whose whole budget of rights
+    /// is held by `origin`.
+    ///
+    /// Merge replicas with "bounded_add". Raises ValueError if `initial` is
+    /// outside the bounds.
+    #[staticmethod]
+    #[pyo3(signature = (origin, initial=0, lower=None, upper=None))]
+    fn bounded_counter(origin: &str, initial: i64, lower: Option<i64>, upper: Option<i64>) -> PyResult<Self> {
+        let counter = BoundedCounter::new(origin, initial, Bounds::new(lower, upper))
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        Ok(Self { inner: AlgebraicValue::BoundedCounter(counter) })
+    }
+
+    /// This bounded counter after `node` adds `delta` (negative to subtract).
+    ///
+    /// Raises ValueError if the node lacks the rights.
+    fn bounded_update(&self, node: &str, delta: i64) -> PyResult<Self> {
+        let mut counter = self.bounded()?.clone();
+        let result = if delta >= 0 {
+            counter.increment(node, delta.unsigned_abs())
+        } else {
+            counter.decrement(node, delta.unsigned_abs())
+        };
+        result.map_err(|e| PyValueError::new_err(e.to_string()))?;
+        Ok(Self { inner: AlgebraicValue::BoundedCounter(counter) })
+    }
+
+    /// This bounded counter after `from_node` gives rights to `to_node`.
+    ///
+    /// Raises ValueError if `from_node` lacks the rights.
+    #[pyo3(signature = (from_node, to_node, decrement=0, increment=0))]
+    fn bounded_transfer(&self, from_node: &str, to_node: &str, decrement: u64, increment: u64) -> PyResult<Self> {
+        let mut counter = self.bounded()?.clone();
+        counter
+            .transfer_decrement_rights(from_node, to_node, decrement)
+            .and_then(|()| counter.transfer_increment_rights(from_node, to_node, increment))
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        Ok(Self { inner: AlgebraicValue::BoundedCounter(counter) })
+    }
+
+    /// Value, decrement rights and increment rights of `node` in a bounded
+    /// counter (rights are None on an open side).
+    fn bounded_state(&self, node: &str) -> PyResult<(i64, Option<u64>, Option<u64>)> {
+        let counter = self.bounded()?;
+        Ok((counter.value(), counter.decrement_rights(node), counter.increment_rights(node)))
+    }
+
+    /// Create a last-writer-wins register write of `value` by `node` at
+    /// `timestamp`.
+    ///
+    /// Merge writes with "lww": the greatest timestamp wins, the node id
+    /// breaking ties.
+    #[staticmethod]
+    fn lww(value: &PyAlgebraicValue, timestamp: u64, node: &str) -> Self {
+        Self { inner: AlgebraicValue::LwwRegister(LwwRegister::new(value.inner.clone(), timestamp, node)) }
+    }
+
+    /// Value, timestamp and node of a last-writer-wins register write.
+    fn lww_parts(&self) -> PyResult<(PyAlgebraicValue, u64, String)> {
+        match &self.inner {
+            AlgebraicValue::LwwRegister(register) => Ok((
+                PyAlgebraicValue { inner: register.value().clone() },
+                register.timestamp(),
+                register.node().to_string(),
+            )),
+            other => Err(PyValueError::new_err(format!(
+                "Expected LwwRegister, got {}",
+                other.type_name()
+            ))),
+        }
+    }
+
+    /// Create a map from string keys to values.
+    ///
+    /// Merge maps with `PyOpType.map(entry_op)`: shared keys merge with
+    /// `entry_op`, other keys are kept.
+    #[staticmethod]
+    fn map(entries: HashMap<String, PyAlgebraicValue>) -> Self {
+        Self { inner: AlgebraicValue::Map(entries.into_iter().map(|(k, v)| (k, v.inner)).collect()) }
+    }
+
+    /// Entries of a map value.
+    fn map_entries(&self) -> PyResult<HashMap<String, PyAlgebraicValue>> {
+        match &self.inner {
+            AlgebraicValue::Map(entries) => Ok(entries
+                .iter()
+                .map(|(k, v)| (k.clone(), PyAlgebraicValue { inner: v.clone() }))
+                .collect()),
+            other => Err(PyValueError::new_err(format!(
+                "Expected Map, got {}",
+                other.type_name()
+            ))),
+        }
+    }
+
     /// Create a boolean value.
     #[staticmethod]
     fn boolean(v: bool) -> Self {
@@ -2124,7 +7386,7 @@ impl PyAlgebraicValue {
         Self { inner: AlgebraicValue::Null }
     }
 
-    /// Check if this is a numeric type.
+    /// Check if this is a numeric type (Integer, Float or Decimal).
     fn is_numeric(&self) -> bool {
         self.inner.is_numeric()
     }
@@ -2171,7 +7433,7 @@ fn algebraic_merge(
     value1: &PyAlgebraicValue,
     value2: &PyAlgebraicValue,
 ) -> PyResult<PyAlgebraicValue> {
-    match AlgebraicMerger::merge(op_type.inner, &value1.inner, &value2.inner) {
+    match AlgebraicMerger::merge(op_type.inner.clone(), &value1.inner, &value2.inner) {
         MergeResult::Merged(v) => Ok(PyAlgebraicValue { inner: v }),
         MergeResult::Conflict { reason, .. } => {
             Err(PyValueError::new_err(format!("Merge conflict: {}", reason)))
@@ -2185,6 +7447,87 @@ fn algebraic_merge(
     }
 }
 
+/// Register a Python callable as the merge function of `PyOpType.custom(name)`.
+///
+/// The callable takes two PyAlgebraicValue and returns the merged
+/// PyAlgebraicValue; raising makes the merge a conflict. It must be
+/// commutative and associative. With `samples`, both properties are
+/// checked on them first.
+///
+/// Raises:
+///     ValueError: If the name is empty or a property check fails
+#[pyfunction]
+#[pyo3(signature = (name, merge, samples=None))]
+fn register_custom_merge(
+    name: &str,
+    merge: Py<PyAny>,
+    samples: Option<Vec<PyAlgebraicValue>>,
+) -> PyResult<()> {
+    let merge = move |a: &AlgebraicValue, b: &AlgebraicValue| {
+        Python::attach(|py| {
+            let args = (PyAlgebraicValue { inner: a.clone() }, PyAlgebraicValue { inner: b.clone() });
+            let merged = merge
+                .call1(py, args)
+                .and_then(|v| Ok(v.bind(py).downcast::<PyAlgebraicValue>()?.borrow().inner.clone()));
+            match merged {
+                Ok(value) => MergeResult::Merged(value),
+                Err(err) => MergeResult::Conflict {
+                    value1: a.clone(),
+                    value2: b.clone(),
+                    reason: err.to_string(),
+                },
+            }
+        })
+    };
+    let result = match samples {
+        Some(samples) => {
+            let samples: Vec<AlgebraicValue> = samples.into_iter().map(|s| s.inner).collect();
+            CustomMergeRegistry::register_checked(name, merge, &samples)
+        }
+        None => CustomMergeRegistry::register(name, merge),
+    };
+    result.map_err(|e| PyValueError::new_err(e.to_string()))
+}
+
+/// Remove a custom merge function. Returns False if none was registered.
+#[pyfunction]
+fn unregister_custom_merge(name: &str) -> bool {
+    CustomMergeRegistry::unregister(name)
+}
+
+/// Names with a registered custom merge function, sorted.
+#[pyfunction]
+fn custom_merge_names() -> Vec<String> {
+    CustomMergeRegistry::names()
+}
+
+/// Check the algebraic laws an operation promises (commutativity and
+/// associativity if conflict-free, idempotency for semilattices).
+///
+/// Checks every pair and triple of `samples`, or of `count` values
+/// generated from `seed` when no samples are given.
+///
+/// Returns:
+///     One description per violation; empty if the laws hold
+#[pyfunction]
+#[pyo3(signature = (op_type, samples=None, count=20, seed=0))]
+fn verify_algebraic_laws(
+    op_type: &PyOpType,
+    samples: Option<Vec<PyAlgebraicValue>>,
+    count: usize,
+    seed: u64,
+) -> Vec<String> {
+    let samples = match samples {
+        Some(samples) => samples.into_iter().map(|s| s.inner).collect(),
+        None => AlgebraicLaws::samples(&op_type.inner, count, seed),
+    };
+    AlgebraicLaws::verify(op_type.inner.clone(), &samples)
+        .violations
+        .iter()
+        .map(|v| v.to_string())
+        .collect()
+}
+
 /// Schema-level algebraic configuration for a table.
 #[pyclass]
 #[derive(Clone)]
@@ -2204,7 +7547,7 @@ impl PyTableAlgebraicSchema {
     fn new(table: &str, default_op_type: Option<&PyOpType>) -> Self {
         let mut inner = TableAlgebraicSchema::new(table);
         if let Some(op) = default_op_type {
-            inner.set_default(op.inner);
+            inner.set_default(op.inner.clone());
         }
         Self { inner }
     }
@@ -2223,7 +7566,20 @@ impl PyTableAlgebraicSchema {
 
     /// Add a column with the specified operation type.
     fn add_column(&mut self, column: &str, op_type: &PyOpType) {
-        self.inner.add_column(column, op_type.inner);
+        self.inner.add_column(column, op_type.inner.clone());
+    }
+
+    /// Add a "bounded_add" column whose merged values must stay within
+    /// `lower` and `upper` (None leaves a side open).
+    #[pyo3(signature = (column, lower=None, upper=None))]
+    fn add_bounded_column(&mut self, column: &str, lower: Option<i64>, upper: Option<i64>) {
+        self.inner
+            .add_column_annotation(ColumnAlgebraic::bounded(column, Bounds::new(lower, upper)));
+    }
+
+    /// (lower, upper) bounds of a column, or None if it has none.
+    fn get_bounds(&self, column: &str) -> Option<(Option<i64>, Option<i64>)> {
+        self.inner.get_bounds(column).map(|b| (b.lower, b.upper))
     }
 
     /// Get the operation type for a column.
@@ -2310,6 +7666,25 @@ impl PyAlgebraicSchemaRegistry {
         self.inner.unregister(table).map(|s| PyTableAlgebraicSchema { inner: s })
     }
 
+    /// Register every schema of `other`, replacing schemas of the same tables.
+    fn extend(&mut self, other: PyRef<'_, PyAlgebraicSchemaRegistry>) {
+        self.inner.extend(&other.inner);
+    }
+
+    /// Write the registry to a JSON file.
+    fn save(&self, path: &str) -> PyResult<()> {
+        self.inner.save(path).map_err(|e| PyIOError::new_err(sanitize_io_error(&e)))
+    }
+
+    /// Read a registry written by save().
+    ///
+    /// Raises IOError if the file is missing or not a saved registry.
+    #[staticmethod]
+    fn load(path: &str) -> PyResult<Self> {
+        let inner = AlgebraicSchemaRegistry::load(path).map_err(|e| PyIOError::new_err(sanitize_io_error(&e)))?;
+        Ok(Self { inner })
+    }
+
     fn __repr__(&self) -> String {
         format!("PyAlgebraicSchemaRegistry(tables={})", self.inner.tables().len())
     }
@@ -2408,6 +7783,90 @@ impl PyMergeOutcome {
     }
 }
 
+/// Row merge of one table, as a branch merge would commit it.
+#[pyclass]
+#[derive(Clone)]
+struct PyTableMerge {
+    #[pyo3(get)]
+    table: String,
+    #[pyo3(get)]
+    base_version: Option<u64>,
+    #[pyo3(get)]
+    source_version: u64,
+    #[pyo3(get)]
+    target_version: u64,
+    #[pyo3(get)]
+    version: u64,
+    #[pyo3(get)]
+    rows: usize,
+    #[pyo3(get)]
+    rows_from_source: usize,
+    #[pyo3(get)]
+    rows_combined: usize,
+    #[pyo3(get)]
+    rows_deleted: usize,
+}
+
+impl From<TableMerge> for PyTableMerge {
+    fn from(t: TableMerge) -> Self {
+        Self {
+            table: t.table,
+            base_version: t.base_version,
+            source_version: t.source_version,
+            target_version: t.target_version,
+            version: t.version,
+            rows: t.rows,
+            rows_from_source: t.rows_from_source,
+            rows_combined: t.rows_combined,
+            rows_deleted: t.rows_deleted,
+        }
+    }
+}
+
+#[pymethods]
+impl PyTableMerge {
+    fn __repr__(&self) -> String {
+        format!(
+            "PyTableMerge(table='{}', version={}, rows={}, rows_combined={})",
+            self.table, self.version, self.rows, self.rows_combined
+        )
+    }
+}
+
+/// Result of `simulate_branch_merge`: what a merge would do, unwritten.
+#[pyclass]
+#[derive(Clone)]
+struct PyMergeSimulation {
+    #[pyo3(get)]
+    outcome: PyMergeOutcome,
+    #[pyo3(get)]
+    heads: HashMap<String, u64>,
+    #[pyo3(get)]
+    tables: Vec<PyTableMerge>,
+}
+
+impl From<MergeSimulation> for PyMergeSimulation {
+    fn from(s: MergeSimulation) -> Self {
+        Self {
+            outcome: s.outcome.into(),
+            heads: s.heads,
+            tables: s.tables.into_iter().map(PyTableMerge::from).collect(),
+        }
+    }
+}
+
+#[pymethods]
+impl PyMergeSimulation {
+    fn __repr__(&self) -> String {
+        format!(
+            "PyMergeSimulation(success={}, heads={}, tables={})",
+            self.outcome.success,
+            self.heads.len(),
+            self.tables.len()
+        )
+    }
+}
+
 // ============================================================================
 // Distributed Types (Coordination-Free Transactions)
 // ============================================================================
@@ -2655,7 +8114,7 @@ impl PyAlgebraicOperation {
     #[new]
     fn new(key: &str, op_type: &PyOpType, value: &PyAlgebraicValue) -> Self {
         Self {
-            inner: AlgebraicOperation::new(key, op_type.inner, value.inner.clone()),
+            inner: AlgebraicOperation::new(key, op_type.inner.clone(), value.inner.clone()),
         }
     }
 
@@ -2806,6 +8265,20 @@ impl PyVersionedUpdate {
         self.inner.is_concurrent_with(&other.inner)
     }
 
+    /// Serialize to JSON string, to send the update to other nodes.
+    fn to_json(&self) -> PyResult<String> {
+        serde_json::to_string(&self.inner)
+            .map_err(|e| PyValueError::new_err(format!("Serialization error: {}", sanitize_error_message(&e.to_string()))))
+    }
+
+    /// Deserialize from JSON string.
+    #[staticmethod]
+    fn from_json(json: &str) -> PyResult<PyVersionedUpdate> {
+        let inner: VersionedUpdate = serde_json::from_str(json)
+            .map_err(|e| PyValueError::new_err(format!("Deserialization error: {}", sanitize_error_message(&e.to_string()))))?;
+        Ok(PyVersionedUpdate { inner })
+    }
+
     fn __repr__(&self) -> String {
         format!(
             "VersionedUpdate(origin='{}', ops={}, clock={})",
@@ -2901,6 +8374,137 @@ impl PyLocalCommitProtocol {
     }
 }
 
+/// Convert CoordinationFreeError to appropriate Python exception
+fn cf_err_to_py(e: CoordinationFreeError) -> PyErr {
+    let info = ErrorAttrs::of(&e);
+    let err = match e {
+        CoordinationFreeError::LockError(_) => PyRuntimeError::new_err(sanitize_error_message(&e.to_string())),
+        e => PyValueError::new_err(sanitize_error_message(&e.to_string())),
+    };
+    info.attach(err)
+}
+
+/// A node's coordination-free transaction manager.
+///
+/// Keeps the node's vector clock and the merged state of every key it has
+/// seen, so a service can commit algebraic transactions locally, ship the
+/// updates (see PyVersionedUpdate.to_json) and apply the updates of other
+/// nodes without any coordination.
+///
+/// Example:
+///     >>> sf = PyCoordinationFreeManager(PyNodeId("sf"))
+///     >>> tokyo = PyCoordinationFreeManager(PyNodeId("tokyo"))
+///     >>> tx = PyAlgebraicTransaction()
+///     >>> tx.add_operation(PyAlgebraicOperation("views", PyOpType("add"), PyAlgebraicValue(5)))
+///     >>> update = sf.commit_local(tx)
+///     >>> tokyo.receive_update(update)
+///     >>> tokyo.get_state("views")
+#[pyclass]
+pub struct PyCoordinationFreeManager {
+    inner: CoordinationFreeManager,
+}
+
+#[pymethods]
+impl PyCoordinationFreeManager {
+    /// Create a manager for `node_id`.
+    ///
+    /// Args:
+    ///     node_id: This node's ID
+    ///     require_fully_algebraic: Reject transactions with non-algebraic
+    ///         operations (default True)
+    #[new]
+    #[pyo3(signature = (node_id, require_fully_algebraic=true))]
+    fn new(node_id: &PyNodeId, require_fully_algebraic: bool) -> Self {
+        let config = CoordinationFreeConfig {
+            require_fully_algebraic,
+            ..Default::default()
+        };
+        Self {
+            inner: CoordinationFreeManager::with_config(node_id.inner.clone(), config),
+        }
+    }
+
+    /// This node's ID.
+    #[getter]
+    fn node_id(&self) -> PyNodeId {
+        PyNodeId {
+            inner: self.inner.node_id().clone(),
+        }
+    }
+
+    /// A copy of the node's vector clock.
+    fn clock(&self) -> PyResult<PyVectorClock> {
+        self.inner
+            .clock()
+            .map(|inner| PyVectorClock { inner })
+            .map_err(cf_err_to_py)
+    }
+
+    /// Check if a transaction can be committed without coordination.
+    fn can_commit_locally(&self, tx: &PyAlgebraicTransaction) -> bool {
+        self.inner.can_commit_locally(&tx.inner)
+    }
+
+    /// Commit a transaction locally, applying it to this node's state.
+    ///
+    /// Returns:
+    ///     The update to send to other nodes
+    ///
+    /// Raises:
+    ///     ValueError: If the transaction cannot be committed locally, or
+    ///         does not merge with the node's state
+    fn commit_local(&self, tx: &PyAlgebraicTransaction) -> PyResult<PyVersionedUpdate> {
+        self.inner
+            .commit_local(&tx.inner)
+            .map(|inner| PyVersionedUpdate { inner })
+            .map_err(cf_err_to_py)
+    }
+
+    /// Apply an update committed on another node.
+    ///
+    /// Raises:
+    ///     ValueError: If the update does not merge with the node's state
+    fn receive_update(&self, update: &PyVersionedUpdate) -> PyResult<()> {
+        self.inner.receive_update(&update.inner).map_err(cf_err_to_py)
+    }
+
+    /// Merge two updates without touching this node's state.
+    fn merge_updates(
+        &self,
+        update1: &PyVersionedUpdate,
+        update2: &PyVersionedUpdate,
+    ) -> PyResult<PyVersionedUpdate> {
+        self.inner
+            .merge_updates(&update1.inner, &update2.inner)
+            .map(|inner| PyVersionedUpdate { inner })
+            .map_err(cf_err_to_py)
+    }
+
+    /// Current value of a key, or None if no update touched it.
+    fn get_state(&self, key: &str) -> PyResult<Option<PyAlgebraicValue>> {
+        self.inner
+            .get_state(key)
+            .map(|value| value.map(|inner| PyAlgebraicValue { inner }))
+            .map_err(cf_err_to_py)
+    }
+
+    /// Keys with a value, sorted.
+    fn keys(&self) -> PyResult<Vec<String>> {
+        let mut keys = self.inner.keys().map_err(cf_err_to_py)?;
+        keys.sort();
+        Ok(keys)
+    }
+
+    /// Number of transactions committed on this node.
+    fn update_count(&self) -> PyResult<usize> {
+        self.inner.update_count().map_err(cf_err_to_py)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("CoordinationFreeManager(node='{}')", self.inner.node_id())
+    }
+}
+
 // ============================================================================
 // Phase 4: Simulation Bindings (Multi-Node Convergence Testing)
 // ============================================================================
@@ -3335,26 +8939,188 @@ fn analyze_merge(diff: &PyBranchDiff, registry: &PyAlgebraicSchemaRegistry) -> P
     PyMergeAnalysis::from(analyzer.analyze(&rust_diff))
 }
 
+/// Convert DataMergeError to appropriate Python exception
+fn data_merge_err_to_py(e: DataMergeError) -> PyErr {
+    let info = ErrorAttrs::of(&e);
+    let err = match e {
+        DataMergeError::Catalog(e) => catalog_err_to_py(e),
+        DataMergeError::ChunkStore(e) => chunk_err_to_py(e),
+        DataMergeError::Branch(e) => branch_err_to_py(e),
+        DataMergeError::Parquet(e) => parquet_err_to_py(e),
+        e @ (DataMergeError::RowConflict { .. }
+        | DataMergeError::SchemaMismatch { .. }
+        | DataMergeError::InvalidKey { .. }
+        | DataMergeError::DuplicateKey { .. }
+        | DataMergeError::Unrepresentable { .. }) => PyValueError::new_err(e.to_string()),
+        e => PyRuntimeError::new_err(sanitize_error_message(&e.to_string())),
+    };
+    info.attach(err)
+}
+
+/// Merge branch `source` into `into`, merging the rows of tables both
+/// branches changed.
+///
+/// Rows are matched by key and cells changed on both sides are combined
+/// with the column's algebraic operation (ADD applies the source's change
+/// to the target, semilattice operations join). The merged rows are
+/// committed as a new version of the table. If any row cannot be merged,
+/// nothing is written and the outcome lists the conflicting tables.
+///
+/// Args:
+///     registry: Algebraic schemas of the tables
+///     catalog: PyCatalog holding the versions
+///     store: PyChunkStore holding the chunks
+///     branch_manager: PyBranchManager holding both branches
+///     source: Branch to merge from
+///     into: Branch to merge into
+///     keys: Optional table name -> key columns (default: "id")
+///
+/// Returns:
+///     PyMergeOutcome
+#[pyfunction]
+#[pyo3(signature = (registry, catalog, store, branch_manager, source, into, keys=None))]
+#[allow(clippy::too_many_arguments)]
+fn merge_branch_data(
+    py: Python<'_>,
+    registry: &PyAlgebraicSchemaRegistry,
+    catalog: &PyCatalog,
+    store: &PyChunkStore,
+    branch_manager: &PyBranchManager,
+    source: &str,
+    into: &str,
+    keys: Option<HashMap<String, Vec<String>>>,
+) -> PyResult<PyMergeOutcome> {
+    let mut merger = DataMerger::new(&catalog.inner, &store.inner, &registry.inner);
+    for (table, columns) in keys.unwrap_or_default() {
+        merger = merger.with_key(table, columns);
+    }
+    py.detach(|| merger.merge_branches(&branch_manager.inner, source, into))
+        .map(PyMergeOutcome::from)
+        .map_err(data_merge_err_to_py)
+}
+
+/// Simulate `merge_branch_data` without writing anything.
+///
+/// Rows are merged in memory exactly as the real merge would merge them,
+/// but no chunk, table version or branch changes, so a merge can be
+/// checked (e.g. in CI) before it is approved.
+///
+/// Args:
+///     registry: Algebraic schemas of the tables
+///     catalog: PyCatalog holding the versions
+///     store: PyChunkStore holding the chunks
+///     branch_manager: PyBranchManager holding both branches
+///     source: Branch to merge from
+///     into: Branch to merge into
+///     keys: Optional table name -> key columns (default: "id")
+///
+/// Returns:
+///     PyMergeSimulation with the outcome, the heads `into` would have,
+///     and per-table row statistics
+#[pyfunction]
+#[pyo3(signature = (registry, catalog, store, branch_manager, source, into, keys=None))]
+#[allow(clippy::too_many_arguments)]
+fn simulate_branch_merge(
+    py: Python<'_>,
+    registry: &PyAlgebraicSchemaRegistry,
+    catalog: &PyCatalog,
+    store: &PyChunkStore,
+    branch_manager: &PyBranchManager,
+    source: &str,
+    into: &str,
+    keys: Option<HashMap<String, Vec<String>>>,
+) -> PyResult<PyMergeSimulation> {
+    let mut merger = DataMerger::new(&catalog.inner, &store.inner, &registry.inner);
+    for (table, columns) in keys.unwrap_or_default() {
+        merger = merger.with_key(table, columns);
+    }
+    py.detach(|| merger.simulate_merge(&branch_manager.inner, source, into))
+        .map(PyMergeSimulation::from)
+        .map_err(data_merge_err_to_py)
+}
+
 #[pymodule]
 fn _rhizo(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    // Exceptions
+    for (name, class) in exception_classes(m.py())? {
+        m.add(*name, class.bind(m.py()))?;
+    }
+
     // Core storage
     m.add_class::<PyChunkStore>()?;
+    m.add_class::<PyChunkReader>()?;
     m.add_class::<PyTableVersion>()?;
+    m.add_class::<PySchemaChange>()?;
     m.add_class::<PyCatalog>()?;
+    m.add_class::<PyPin>()?;
+    m.add_class::<PyTag>()?;
+    m.add_class::<PyView>()?;
+    m.add_class::<PyTagManager>()?;
+    m.add_class::<PyLegalHold>()?;
+    m.add_class::<PyLockOwner>()?;
+    m.add_class::<PyPendingCommit>()?;
 
     // Branching
     m.add_class::<PyBranch>()?;
+    m.add_class::<PyArchivedBranch>()?;
+    m.add_class::<PyBranchRepair>()?;
+    m.add_class::<PyRepoSnapshot>()?;
+    m.add_class::<PyBranchCommit>()?;
+    m.add_class::<PyMergeRecord>()?;
     m.add_class::<PyBranchDiff>()?;
+    m.add_class::<PyHeadRecord>()?;
+    m.add_class::<PyHeadEvent>()?;
+    m.add_class::<PyCheckout>()?;
     m.add_class::<PyBranchManager>()?;
 
+    // Repository filtering
+    m.add_class::<PyFilterReport>()?;
+    m.add_function(wrap_pyfunction!(filter_repository, m)?)?;
+    m.add_class::<PyBackupManifest>()?;
+    m.add_class::<PyExportManifest>()?;
+    m.add_class::<PyVerifyReport>()?;
+    m.add_class::<PyRestoreReport>()?;
+    m.add_function(wrap_pyfunction!(backup_repository, m)?)?;
+    m.add_function(wrap_pyfunction!(verify_backup, m)?)?;
+    m.add_function(wrap_pyfunction!(backup_chain, m)?)?;
+    m.add_function(wrap_pyfunction!(restore_backup, m)?)?;
+
     // Transactions
     m.add_class::<PyTransactionManager>()?;
+    m.add_class::<PyTransaction>()?;
+    m.add_class::<PyCommitQueueStats>()?;
+    m.add_class::<PyCommitTimings>()?;
+    m.add_class::<PyBatchAck>()?;
+    m.add_class::<PyBatchTicket>()?;
+    m.add_class::<PyMicroBatchStats>()?;
+    m.add_class::<PyMicroBatcher>()?;
     m.add_class::<PyTransactionInfo>()?;
     m.add_class::<PyRecoveryReport>()?;
+    m.add_class::<PyEpochConfig>()?;
+    m.add_class::<PyEpochInfo>()?;
+
+    // Chunk GC
+    m.add_class::<PyChunkGcReport>()?;
+    m.add_class::<PyVersionPruneReport>()?;
+    m.add_function(wrap_pyfunction!(sweep_chunks, m)?)?;
+    m.add_function(wrap_pyfunction!(prune_versions, m)?)?;
+    m.add_function(wrap_pyfunction!(squash_versions, m)?)?;
+    m.add_class::<PyGcPlan>()?;
+    m.add_function(wrap_pyfunction!(gc_plan, m)?)?;
+    m.add_function(wrap_pyfunction!(execute_gc_plan, m)?)?;
+    m.add_class::<PyChunkScrubReport>()?;
+    m.add_function(wrap_pyfunction!(scrub_chunks, m)?)?;
+
+    // Fixtures
+    m.add_class::<PyFixtureReport>()?;
+    m.add_function(wrap_pyfunction!(build_fixture_repo, m)?)?;
 
     // Changelog
     m.add_class::<PyTableChange>()?;
     m.add_class::<PyChangelogEntry>()?;
+    m.add_class::<PyChangelogCursor>()?;
+    m.add_class::<PyReplayReport>()?;
+    m.add_function(wrap_pyfunction!(replay_changelog, m)?)?;
 
     // Merkle Tree
     m.add_class::<PyDataChunk>()?;
@@ -3369,6 +9135,18 @@ fn _rhizo(m: &Bound<'_, PyModule>) -> PyResult<()> {
     // Phase 4: Native Parquet (zero-copy Arrow FFI)
     m.add_class::<PyParquetEncoder>()?;
     m.add_class::<PyParquetDecoder>()?;
+    m.add_function(wrap_pyfunction!(write_arrow, m)?)?;
+    m.add_function(wrap_pyfunction!(read_arrow, m)?)?;
+    m.add_function(wrap_pyfunction!(write_patch, m)?)?;
+    m.add_function(wrap_pyfunction!(import_parquet, m)?)?;
+    m.add_function(wrap_pyfunction!(import_csv, m)?)?;
+    m.add_function(wrap_pyfunction!(import_jsonl, m)?)?;
+    m.add_function(wrap_pyfunction!(export_parquet, m)?)?;
+    m.add_function(wrap_pyfunction!(export_tables, m)?)?;
+    m.add_function(wrap_pyfunction!(query, m)?)?;
+    m.add_function(wrap_pyfunction!(resolve_ref, m)?)?;
+    m.add_function(wrap_pyfunction!(redact, m)?)?;
+    m.add_function(wrap_pyfunction!(arrow_schema_hash, m)?)?;
 
     // Phase R.2: Predicate Pushdown
     m.add_class::<PyFilterOp>()?;
@@ -3382,8 +9160,16 @@ fn _rhizo(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<PyAlgebraicSchemaRegistry>()?;
     m.add_class::<PyMergeAnalysis>()?;
     m.add_class::<PyMergeOutcome>()?;
+    m.add_class::<PyTableMerge>()?;
+    m.add_class::<PyMergeSimulation>()?;
     m.add_function(wrap_pyfunction!(algebraic_merge, m)?)?;
+    m.add_function(wrap_pyfunction!(register_custom_merge, m)?)?;
+    m.add_function(wrap_pyfunction!(unregister_custom_merge, m)?)?;
+    m.add_function(wrap_pyfunction!(custom_merge_names, m)?)?;
+    m.add_function(wrap_pyfunction!(verify_algebraic_laws, m)?)?;
     m.add_function(wrap_pyfunction!(analyze_merge, m)?)?;
+    m.add_function(wrap_pyfunction!(merge_branch_data, m)?)?;
+    m.add_function(wrap_pyfunction!(simulate_branch_merge, m)?)?;
 
     // Distributed (Coordination-Free Transactions)
     m.add_class::<PyNodeId>()?;
@@ -3395,6 +9181,7 @@ fn _rhizo(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<PyAlgebraicTransaction>()?;
     m.add_class::<PyVersionedUpdate>()?;
     m.add_class::<PyLocalCommitProtocol>()?;
+    m.add_class::<PyCoordinationFreeManager>()?;
 
     // Simulation (Multi-Node Convergence Testing)
     m.add_class::<PyNetworkCondition>()?;
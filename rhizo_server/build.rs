@@ -0,0 +1,42 @@
+//! Generates the `rhizo.v1.Repository` client and server stubs.
+//!
+//! The service is declared here rather than compiled from
+//! `proto/rhizo.proto`, so building needs no `protoc`. Message types live
+//! in `src/proto.rs`.
+
+use tonic_build::manual::{Builder, Method, Service};
+
+/// (method, route, input, output, server streaming)
+const METHODS: &[(&str, &str, &str, &str, bool)] = &[
+    ("list_tables", "ListTables", "ListTablesRequest", "ListTablesResponse", false),
+    ("list_versions", "ListVersions", "ListVersionsRequest", "ListVersionsResponse", false),
+    ("get_version", "GetVersion", "GetVersionRequest", "TableVersion", false),
+    ("list_branches", "ListBranches", "ListBranchesRequest", "ListBranchesResponse", false),
+    ("get_branch", "GetBranch", "GetBranchRequest", "Branch", false),
+    ("create_branch", "CreateBranch", "CreateBranchRequest", "Branch", false),
+    ("delete_branch", "DeleteBranch", "DeleteBranchRequest", "DeleteBranchResponse", false),
+    ("merge_branch", "MergeBranch", "MergeBranchRequest", "MergeBranchResponse", false),
+    ("begin", "Begin", "BeginRequest", "BeginResponse", false),
+    ("add_write", "AddWrite", "AddWriteRequest", "AddWriteResponse", false),
+    ("commit", "Commit", "CommitRequest", "CommitResponse", false),
+    ("abort", "Abort", "AbortRequest", "AbortResponse", false),
+    ("get_changelog", "GetChangelog", "GetChangelogRequest", "ChangelogEntry", true),
+];
+
+fn main() {
+    let mut service = Service::builder().name("Repository").package("rhizo.v1");
+    for &(name, route, input, output, streaming) in METHODS {
+        let mut method = Method::builder()
+            .name(name)
+            .route_name(route)
+            .input_type(format!("crate::proto::{}", input))
+            .output_type(format!("crate::proto::{}", output))
+            .codec_path("tonic_prost::ProstCodec");
+        if streaming {
+            method = method.server_streaming();
+        }
+        service = service.method(method.build());
+    }
+    Builder::new().compile(&[service.build()]);
+    println!("cargo:rerun-if-changed=build.rs");
+}
@@ -0,0 +1,93 @@
+//! Conversions between core types and wire messages.
+
+use std::fmt::Display;
+
+use rhizo_core::{ErrorCategory, ErrorInfo, WriteKind};
+use tonic::metadata::MetadataValue;
+use tonic::{Code, Status};
+
+use crate::proto;
+
+/// Metadata key carrying the stable `ErrorInfo::code` of a failed call
+pub const ERROR_CODE_KEY: &str = "rhizo-error-code";
+
+/// A core error as a gRPC status, coded by its category
+pub(crate) fn status<E: ErrorInfo + Display>(e: E) -> Status {
+    let code = match e.category() {
+        ErrorCategory::NotFound => Code::NotFound,
+        ErrorCategory::AlreadyExists => Code::AlreadyExists,
+        ErrorCategory::InvalidArgument => Code::InvalidArgument,
+        ErrorCategory::Conflict => Code::Aborted,
+        ErrorCategory::FailedPrecondition => Code::FailedPrecondition,
+        ErrorCategory::DataIntegrity => Code::DataLoss,
+        ErrorCategory::Io => Code::Unavailable,
+        _ => Code::Internal,
+    };
+    let mut status = Status::new(code, e.to_string());
+    status
+        .metadata_mut()
+        .insert(ERROR_CODE_KEY, MetadataValue::from_static(e.code()));
+    status
+}
+
+impl From<rhizo_core::TableVersion> for proto::TableVersion {
+    fn from(v: rhizo_core::TableVersion) -> Self {
+        Self {
+            table_name: v.table_name,
+            version: v.version,
+            chunk_hashes: v.chunk_hashes,
+            schema_hash: v.schema_hash,
+            created_at: v.created_at,
+            parent_version: v.parent_version,
+            metadata: v.metadata,
+        }
+    }
+}
+
+impl From<rhizo_core::Branch> for proto::Branch {
+    fn from(b: rhizo_core::Branch) -> Self {
+        Self {
+            name: b.name,
+            head: b.head,
+            created_at: b.created_at,
+            parent_branch: b.parent_branch,
+            description: b.description,
+            immutable: b.immutable,
+        }
+    }
+}
+
+impl From<rhizo_core::TableChange> for proto::TableChange {
+    fn from(c: rhizo_core::TableChange) -> Self {
+        let (kind, renamed_to, merged_from) = match c.kind {
+            WriteKind::Data => (proto::ChangeKind::Data, None, None),
+            WriteKind::Drop => (proto::ChangeKind::Drop, None, None),
+            WriteKind::Rename { to } => (proto::ChangeKind::Rename, Some(to), None),
+            WriteKind::Merge { from } => (proto::ChangeKind::Merge, None, Some(from)),
+        };
+        Self {
+            table_name: c.table_name,
+            old_version: c.old_version,
+            new_version: c.new_version,
+            chunk_hashes: c.chunk_hashes,
+            branch: c.branch,
+            kind: kind as i32,
+            renamed_to,
+            merged_from,
+        }
+    }
+}
+
+impl From<rhizo_core::ChangelogEntry> for proto::ChangelogEntry {
+    fn from(e: rhizo_core::ChangelogEntry) -> Self {
+        Self {
+            tx_id: e.tx_id,
+            epoch_id: e.epoch_id,
+            committed_at: e.committed_at,
+            branch: e.branch,
+            changes: e.changes.into_iter().map(Into::into).collect(),
+            metadata: e.metadata,
+            auto_merged: e.auto_merged,
+        }
+    }
+}
@@ -0,0 +1,43 @@
+//! gRPC access to a Rhizo repository.
+//!
+//! `rhizo_server` puts a repository's [`FileCatalog`], [`BranchManager`]
+//! and [`TransactionManager`] behind the `rhizo.v1.Repository` service
+//! (see `proto/rhizo.proto`), so clients in other languages and other
+//! processes share one repository through a single transaction manager.
+//!
+//! # Example
+//!
+//! ```ignore
+//! // Server
+//! rhizo_server::serve("./data", "127.0.0.1:50051".parse()?).await?;
+//!
+//! // Client
+//! let mut client = RepositoryClient::connect("http://127.0.0.1:50051").await?;
+//! let tx_id = client.begin(BeginRequest { branch: None }).await?.into_inner().tx_id;
+//! ```
+//!
+//! [`FileCatalog`]: rhizo_core::FileCatalog
+//! [`BranchManager`]: rhizo_core::BranchManager
+//! [`TransactionManager`]: rhizo_core::TransactionManager
+
+use std::net::SocketAddr;
+use std::path::Path;
+
+pub mod convert;
+pub mod proto;
+mod service;
+
+pub use convert::ERROR_CODE_KEY;
+pub use proto::repository_client::RepositoryClient;
+pub use proto::repository_server::RepositoryServer;
+pub use service::RepositoryService;
+
+/// Serve the repository at `path` on `addr` until the process exits.
+pub async fn serve(path: impl AsRef<Path>, addr: SocketAddr) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let service = RepositoryService::open(path)?;
+    tonic::transport::Server::builder()
+        .add_service(service.into_server())
+        .serve(addr)
+        .await?;
+    Ok(())
+}
@@ -0,0 +1,54 @@
+//! `rhizo-server <repo-path> [--listen ADDR]`
+//!
+//! Serves one repository over gRPC (default address 127.0.0.1:50051).
+
+use std::net::SocketAddr;
+use std::process::ExitCode;
+
+const DEFAULT_ADDR: &str = "127.0.0.1:50051";
+const USAGE: &str = "usage: rhizo-server <repo-path> [--listen ADDR]";
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let mut args = std::env::args().skip(1);
+    let mut path = None;
+    let mut addr = DEFAULT_ADDR.to_string();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--listen" => match args.next() {
+                Some(value) => addr = value,
+                None => return usage(),
+            },
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return ExitCode::SUCCESS;
+            }
+            _ if path.is_none() && !arg.starts_with('-') => path = Some(arg),
+            _ => return usage(),
+        }
+    }
+    let Some(path) = path else {
+        return usage();
+    };
+    let addr: SocketAddr = match addr.parse() {
+        Ok(addr) => addr,
+        Err(e) => {
+            eprintln!("invalid listen address {}: {}", addr, e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    eprintln!("serving {} on {}", path, addr);
+    match rhizo_server::serve(&path, addr).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("rhizo-server: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn usage() -> ExitCode {
+    eprintln!("{}", USAGE);
+    ExitCode::from(2)
+}
@@ -0,0 +1,254 @@
+//! Messages and service stubs of the `rhizo.v1` gRPC API.
+//!
+//! Written by hand instead of generated from `proto/rhizo.proto`, so the
+//! build needs no `protoc`; the field tags here and in the `.proto` file
+//! must stay in step.
+
+use std::collections::HashMap;
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ListTablesRequest {
+    #[prost(string, optional, tag = "1")]
+    pub branch: Option<String>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ListTablesResponse {
+    #[prost(string, repeated, tag = "1")]
+    pub tables: Vec<String>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ListVersionsRequest {
+    #[prost(string, tag = "1")]
+    pub table_name: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ListVersionsResponse {
+    #[prost(uint64, repeated, tag = "1")]
+    pub versions: Vec<u64>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct GetVersionRequest {
+    #[prost(string, tag = "1")]
+    pub table_name: String,
+    #[prost(uint64, optional, tag = "2")]
+    pub version: Option<u64>,
+    #[prost(string, optional, tag = "3")]
+    pub branch: Option<String>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct TableVersion {
+    #[prost(string, tag = "1")]
+    pub table_name: String,
+    #[prost(uint64, tag = "2")]
+    pub version: u64,
+    #[prost(string, repeated, tag = "3")]
+    pub chunk_hashes: Vec<String>,
+    #[prost(string, optional, tag = "4")]
+    pub schema_hash: Option<String>,
+    #[prost(int64, tag = "5")]
+    pub created_at: i64,
+    #[prost(uint64, optional, tag = "6")]
+    pub parent_version: Option<u64>,
+    #[prost(map = "string, string", tag = "7")]
+    pub metadata: HashMap<String, String>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ListBranchesRequest {}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ListBranchesResponse {
+    #[prost(string, repeated, tag = "1")]
+    pub names: Vec<String>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct GetBranchRequest {
+    #[prost(string, tag = "1")]
+    pub name: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Branch {
+    #[prost(string, tag = "1")]
+    pub name: String,
+    #[prost(map = "string, uint64", tag = "2")]
+    pub head: HashMap<String, u64>,
+    #[prost(int64, tag = "3")]
+    pub created_at: i64,
+    #[prost(string, optional, tag = "4")]
+    pub parent_branch: Option<String>,
+    #[prost(string, optional, tag = "5")]
+    pub description: Option<String>,
+    #[prost(bool, tag = "6")]
+    pub immutable: bool,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct CreateBranchRequest {
+    #[prost(string, tag = "1")]
+    pub name: String,
+    #[prost(string, optional, tag = "2")]
+    pub from_branch: Option<String>,
+    #[prost(string, optional, tag = "3")]
+    pub description: Option<String>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct DeleteBranchRequest {
+    #[prost(string, tag = "1")]
+    pub name: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct DeleteBranchResponse {}
+
+/// Side kept for a table both branches changed
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, prost::Enumeration)]
+#[repr(i32)]
+pub enum MergeSide {
+    Unspecified = 0,
+    Source = 1,
+    Target = 2,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct MergeBranchRequest {
+    #[prost(string, tag = "1")]
+    pub source: String,
+    #[prost(string, tag = "2")]
+    pub into: String,
+    /// Table -> [`MergeSide`]
+    #[prost(map = "string, enumeration(MergeSide)", tag = "3")]
+    pub resolutions: HashMap<String, i32>,
+    #[prost(bool, tag = "4")]
+    pub delete_source: bool,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct MergeBranchResponse {}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct BeginRequest {
+    #[prost(string, optional, tag = "1")]
+    pub branch: Option<String>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct BeginResponse {
+    #[prost(uint64, tag = "1")]
+    pub tx_id: u64,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct AddWriteRequest {
+    #[prost(uint64, tag = "1")]
+    pub tx_id: u64,
+    #[prost(string, tag = "2")]
+    pub table_name: String,
+    #[prost(uint64, tag = "3")]
+    pub new_version: u64,
+    #[prost(string, repeated, tag = "4")]
+    pub chunk_hashes: Vec<String>,
+    #[prost(string, optional, tag = "5")]
+    pub schema_hash: Option<String>,
+    #[prost(string, optional, tag = "6")]
+    pub branch: Option<String>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct AddWriteResponse {}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct CommitRequest {
+    #[prost(uint64, tag = "1")]
+    pub tx_id: u64,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct CommitResponse {
+    #[prost(int64, optional, tag = "1")]
+    pub committed_at: Option<i64>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct AbortRequest {
+    #[prost(uint64, tag = "1")]
+    pub tx_id: u64,
+    #[prost(string, tag = "2")]
+    pub reason: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct AbortResponse {}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct GetChangelogRequest {
+    #[prost(uint64, optional, tag = "1")]
+    pub since_tx_id: Option<u64>,
+    #[prost(uint64, optional, tag = "2")]
+    pub until_tx_id: Option<u64>,
+    #[prost(int64, optional, tag = "3")]
+    pub since_timestamp: Option<i64>,
+    #[prost(string, repeated, tag = "4")]
+    pub tables: Vec<String>,
+    #[prost(string, optional, tag = "5")]
+    pub branch: Option<String>,
+    #[prost(uint64, optional, tag = "6")]
+    pub limit: Option<u64>,
+}
+
+/// What a change did to its table
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, prost::Enumeration)]
+#[repr(i32)]
+pub enum ChangeKind {
+    Data = 0,
+    Drop = 1,
+    Rename = 2,
+    Merge = 3,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct TableChange {
+    #[prost(string, tag = "1")]
+    pub table_name: String,
+    #[prost(uint64, optional, tag = "2")]
+    pub old_version: Option<u64>,
+    #[prost(uint64, tag = "3")]
+    pub new_version: u64,
+    #[prost(string, repeated, tag = "4")]
+    pub chunk_hashes: Vec<String>,
+    #[prost(string, optional, tag = "5")]
+    pub branch: Option<String>,
+    #[prost(enumeration = "ChangeKind", tag = "6")]
+    pub kind: i32,
+    #[prost(string, optional, tag = "7")]
+    pub renamed_to: Option<String>,
+    #[prost(string, optional, tag = "8")]
+    pub merged_from: Option<String>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ChangelogEntry {
+    #[prost(uint64, tag = "1")]
+    pub tx_id: u64,
+    #[prost(uint64, tag = "2")]
+    pub epoch_id: u64,
+    #[prost(int64, tag = "3")]
+    pub committed_at: i64,
+    #[prost(string, tag = "4")]
+    pub branch: String,
+    #[prost(message, repeated, tag = "5")]
+    pub changes: Vec<TableChange>,
+    #[prost(map = "string, string", tag = "6")]
+    pub metadata: HashMap<String, String>,
+    #[prost(string, repeated, tag = "7")]
+    pub auto_merged: Vec<String>,
+}
+
+include!(concat!(env!("OUT_DIR"), "/rhizo.v1.Repository.rs"));
@@ -0,0 +1,388 @@
+//! The `rhizo.v1.Repository` service over one repository directory.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+
+use rhizo_core::{ChangelogQuery, MergeResolution, Repo, RepoError, TableWrite, TransactionManager};
+use tokio_stream::Iter;
+use tonic::{Request, Response, Status};
+
+use crate::convert::status;
+use crate::proto::repository_server::{Repository, RepositoryServer};
+use crate::proto::{self, MergeSide};
+
+/// Serves a repository's catalog, branches, transactions and changelog.
+///
+/// All clients share one `TransactionManager`, so transactions begun by
+/// different processes are checked against each other for conflicts just
+/// as they are within a single process. Calls run on tokio's blocking
+/// pool, since the core APIs do synchronous file IO.
+///
+/// # Example
+///
+/// ```ignore
+/// let service = RepositoryService::open("./data")?;
+/// tonic::transport::Server::builder()
+///     .add_service(service.into_server())
+///     .serve("127.0.0.1:50051".parse()?)
+///     .await?;
+/// ```
+#[derive(Clone)]
+pub struct RepositoryService {
+    repo: Arc<Repo>,
+    transactions: Arc<TransactionManager>,
+}
+
+impl RepositoryService {
+    /// Serve an already opened repository.
+    pub fn new(repo: Repo) -> Result<Self, RepoError> {
+        let transactions = Arc::new(repo.transaction_manager()?);
+        Ok(Self {
+            repo: Arc::new(repo),
+            transactions,
+        })
+    }
+
+    /// Open (or create) the repository at `path` and serve it.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, RepoError> {
+        Self::new(Repo::open(path)?)
+    }
+
+    /// The tonic service to add to a `Server`
+    pub fn into_server(self) -> RepositoryServer<Self> {
+        RepositoryServer::new(self)
+    }
+
+    /// Run `f` on the blocking pool and wrap its result in a response
+    async fn blocking<T, F>(&self, f: F) -> Result<Response<T>, Status>
+    where
+        T: Send + 'static,
+        F: FnOnce(&Repo, &TransactionManager) -> Result<T, Status> + Send + 'static,
+    {
+        let repo = Arc::clone(&self.repo);
+        let transactions = Arc::clone(&self.transactions);
+        tokio::task::spawn_blocking(move || f(&repo, &transactions))
+            .await
+            .map_err(|e| Status::internal(format!("request task failed: {}", e)))?
+            .map(Response::new)
+    }
+}
+
+fn merge_resolution(table: &str, side: i32) -> Result<MergeResolution, Status> {
+    match MergeSide::try_from(side) {
+        Ok(MergeSide::Source) => Ok(MergeResolution::Source),
+        Ok(MergeSide::Target) => Ok(MergeResolution::Target),
+        _ => Err(Status::invalid_argument(format!("No merge side given for table {}", table))),
+    }
+}
+
+#[tonic::async_trait]
+impl Repository for RepositoryService {
+    type GetChangelogStream = Iter<std::vec::IntoIter<Result<proto::ChangelogEntry, Status>>>;
+
+    async fn list_tables(
+        &self,
+        request: Request<proto::ListTablesRequest>,
+    ) -> Result<Response<proto::ListTablesResponse>, Status> {
+        let branch = request.into_inner().branch;
+        self.blocking(move |repo, _| {
+            let tables = match branch {
+                Some(name) => {
+                    let branch = repo.branches().get(&name).map_err(status)?;
+                    repo.catalog().list_tables_on_branch(&branch)
+                }
+                None => repo.catalog().list_tables(),
+            }
+            .map_err(status)?;
+            Ok(proto::ListTablesResponse { tables })
+        })
+        .await
+    }
+
+    async fn list_versions(
+        &self,
+        request: Request<proto::ListVersionsRequest>,
+    ) -> Result<Response<proto::ListVersionsResponse>, Status> {
+        let table = request.into_inner().table_name;
+        self.blocking(move |repo, _| {
+            let versions = repo.catalog().list_versions(&table).map_err(status)?;
+            Ok(proto::ListVersionsResponse { versions })
+        })
+        .await
+    }
+
+    async fn get_version(
+        &self,
+        request: Request<proto::GetVersionRequest>,
+    ) -> Result<Response<proto::TableVersion>, Status> {
+        let request = request.into_inner();
+        self.blocking(move |repo, _| {
+            let version = match &request.branch {
+                Some(name) => {
+                    let branch = repo.branches().get(name).map_err(status)?;
+                    repo.catalog().get_version_on_branch(&request.table_name, request.version, &branch)
+                }
+                None => repo.catalog().get_version(&request.table_name, request.version),
+            }
+            .map_err(status)?;
+            Ok(version.into())
+        })
+        .await
+    }
+
+    async fn list_branches(
+        &self,
+        _request: Request<proto::ListBranchesRequest>,
+    ) -> Result<Response<proto::ListBranchesResponse>, Status> {
+        self.blocking(|repo, _| {
+            let names = repo.branches().list().map_err(status)?;
+            Ok(proto::ListBranchesResponse { names })
+        })
+        .await
+    }
+
+    async fn get_branch(&self, request: Request<proto::GetBranchRequest>) -> Result<Response<proto::Branch>, Status> {
+        let name = request.into_inner().name;
+        self.blocking(move |repo, _| Ok(repo.branches().get(&name).map_err(status)?.into()))
+            .await
+    }
+
+    async fn create_branch(
+        &self,
+        request: Request<proto::CreateBranchRequest>,
+    ) -> Result<Response<proto::Branch>, Status> {
+        let request = request.into_inner();
+        self.blocking(move |repo, _| {
+            let branch = repo
+                .branches()
+                .create(&request.name, request.from_branch.as_deref(), request.description.as_deref())
+                .map_err(status)?;
+            Ok(branch.into())
+        })
+        .await
+    }
+
+    async fn delete_branch(
+        &self,
+        request: Request<proto::DeleteBranchRequest>,
+    ) -> Result<Response<proto::DeleteBranchResponse>, Status> {
+        let name = request.into_inner().name;
+        self.blocking(move |repo, _| {
+            repo.branches().delete(&name).map_err(status)?;
+            Ok(proto::DeleteBranchResponse {})
+        })
+        .await
+    }
+
+    async fn merge_branch(
+        &self,
+        request: Request<proto::MergeBranchRequest>,
+    ) -> Result<Response<proto::MergeBranchResponse>, Status> {
+        let request = request.into_inner();
+        let resolutions = request
+            .resolutions
+            .iter()
+            .map(|(table, &side)| merge_resolution(table, side).map(|r| (table.clone(), r)))
+            .collect::<Result<HashMap<_, _>, _>>()?;
+        self.blocking(move |repo, _| {
+            repo.branches()
+                .merge_with_resolutions(&request.source, &request.into, &resolutions, request.delete_source)
+                .map_err(status)?;
+            Ok(proto::MergeBranchResponse {})
+        })
+        .await
+    }
+
+    async fn begin(&self, request: Request<proto::BeginRequest>) -> Result<Response<proto::BeginResponse>, Status> {
+        let branch = request.into_inner().branch;
+        self.blocking(move |_, transactions| {
+            let tx_id = transactions.begin(branch.as_deref()).map_err(status)?;
+            Ok(proto::BeginResponse { tx_id })
+        })
+        .await
+    }
+
+    async fn add_write(
+        &self,
+        request: Request<proto::AddWriteRequest>,
+    ) -> Result<Response<proto::AddWriteResponse>, Status> {
+        let request = request.into_inner();
+        self.blocking(move |_, transactions| {
+            let mut write = TableWrite::new(request.table_name, request.new_version, request.chunk_hashes);
+            if let Some(hash) = request.schema_hash {
+                write = write.with_schema_hash(hash);
+            }
+            if let Some(branch) = request.branch {
+                write = write.with_branch(branch);
+            }
+            transactions.add_write(request.tx_id, write).map_err(status)?;
+            Ok(proto::AddWriteResponse {})
+        })
+        .await
+    }
+
+    async fn commit(&self, request: Request<proto::CommitRequest>) -> Result<Response<proto::CommitResponse>, Status> {
+        let tx_id = request.into_inner().tx_id;
+        self.blocking(move |_, transactions| {
+            transactions.commit(tx_id).map_err(status)?;
+            let committed_at = transactions.get_transaction(tx_id).ok().and_then(|tx| tx.committed_at);
+            Ok(proto::CommitResponse { committed_at })
+        })
+        .await
+    }
+
+    async fn abort(&self, request: Request<proto::AbortRequest>) -> Result<Response<proto::AbortResponse>, Status> {
+        let request = request.into_inner();
+        self.blocking(move |_, transactions| {
+            transactions.abort(request.tx_id, &request.reason).map_err(status)?;
+            Ok(proto::AbortResponse {})
+        })
+        .await
+    }
+
+    async fn get_changelog(
+        &self,
+        request: Request<proto::GetChangelogRequest>,
+    ) -> Result<Response<Self::GetChangelogStream>, Status> {
+        let request = request.into_inner();
+        let query = ChangelogQuery {
+            since_tx_id: request.since_tx_id,
+            until_tx_id: request.until_tx_id,
+            since_timestamp: request.since_timestamp,
+            tables: (!request.tables.is_empty()).then_some(request.tables),
+            branch: request.branch,
+            limit: request.limit.map(|limit| limit as usize),
+        };
+        self.blocking(move |_, transactions| {
+            let entries = transactions.get_changelog(query).map_err(status)?;
+            let messages: Vec<_> = entries.into_iter().map(|e| Ok(e.into())).collect();
+            Ok(tokio_stream::iter(messages))
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rhizo_core::TableVersion;
+    use tokio::net::TcpListener;
+    use tokio_stream::wrappers::TcpListenerStream;
+    use tokio_stream::StreamExt;
+    use tonic::transport::{Channel, Server};
+    use tonic::Code;
+
+    use super::*;
+    use crate::convert::ERROR_CODE_KEY;
+    use crate::proto::repository_client::RepositoryClient;
+
+    async fn serve(repo: Repo) -> RepositoryClient<Channel> {
+        let service = RepositoryService::new(repo).unwrap();
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(
+            Server::builder()
+                .add_service(service.into_server())
+                .serve_with_incoming(TcpListenerStream::new(listener)),
+        );
+        RepositoryClient::connect(format!("http://{}", addr)).await.unwrap()
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_transactions_branches_and_changelog_over_grpc() {
+        let repo = Repo::ephemeral().unwrap();
+        let hash = repo.store().put(b"alice,bob").unwrap();
+        let mut client = serve(repo).await;
+
+        let tx_id = client.begin(proto::BeginRequest { branch: None }).await.unwrap().into_inner().tx_id;
+        client
+            .add_write(proto::AddWriteRequest {
+                tx_id,
+                table_name: "users".to_string(),
+                new_version: 1,
+                chunk_hashes: vec![hash.clone()],
+                schema_hash: None,
+                branch: None,
+            })
+            .await
+            .unwrap();
+        let commit = client.commit(proto::CommitRequest { tx_id }).await.unwrap().into_inner();
+        assert!(commit.committed_at.is_some());
+
+        let version = client
+            .get_version(proto::GetVersionRequest {
+                table_name: "users".to_string(),
+                version: None,
+                branch: Some("main".to_string()),
+            })
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!((version.version, version.chunk_hashes), (1, vec![hash]));
+
+        let branch = client
+            .create_branch(proto::CreateBranchRequest {
+                name: "dev".to_string(),
+                from_branch: None,
+                description: Some("scratch".to_string()),
+            })
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(branch.head.get("users"), Some(&1));
+        let names = client.list_branches(proto::ListBranchesRequest {}).await.unwrap().into_inner().names;
+        assert!(names.contains(&"dev".to_string()));
+
+        let entries: Vec<_> = client
+            .get_changelog(proto::GetChangelogRequest::default())
+            .await
+            .unwrap()
+            .into_inner()
+            .collect::<Result<_, _>>()
+            .await
+            .unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].tx_id, tx_id);
+        assert_eq!(entries[0].changes[0].table_name, "users");
+        assert_eq!(entries[0].changes[0].kind, proto::ChangeKind::Data as i32);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_core_errors_map_to_status_codes() {
+        let repo = Repo::ephemeral().unwrap();
+        repo.catalog().commit(TableVersion::new("users", 1, vec![])).unwrap();
+        let mut client = serve(repo).await;
+
+        let missing = client
+            .get_version(proto::GetVersionRequest {
+                table_name: "users".to_string(),
+                version: Some(9),
+                branch: None,
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(missing.code(), Code::NotFound);
+        assert_eq!(missing.metadata().get(ERROR_CODE_KEY).unwrap(), "CATALOG_VERSION_NOT_FOUND");
+
+        let exists = client
+            .create_branch(proto::CreateBranchRequest {
+                name: "main".to_string(),
+                from_branch: None,
+                description: None,
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(exists.code(), Code::AlreadyExists);
+
+        let unresolved = client
+            .merge_branch(proto::MergeBranchRequest {
+                source: "main".to_string(),
+                into: "main".to_string(),
+                resolutions: HashMap::from([("users".to_string(), MergeSide::Unspecified as i32)]),
+                delete_source: false,
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(unresolved.code(), Code::InvalidArgument);
+    }
+}
@@ -0,0 +1,144 @@
+"""
+Tests for read auditing and the event bus.
+
+Run with: pytest tests/test_audit.py -v
+"""
+
+import json
+import os
+import shutil
+import tempfile
+
+import pandas as pd
+import pytest
+
+import rhizo
+from rhizo.audit import READ_TOPIC, JsonlAuditSink, ReadAuditor, ReadEvent
+from rhizo.events import ALL_TOPICS, EventBus
+
+
+@pytest.fixture
+def temp_dir():
+    d = tempfile.mkdtemp(prefix="rhizo_audit_test_")
+    yield d
+    shutil.rmtree(d, ignore_errors=True)
+
+
+class TestEventBus:
+
+    def test_publish_and_unsubscribe(self):
+        bus = EventBus()
+        received = []
+        token = bus.subscribe("read", received.append)
+
+        assert bus.publish("read", 1) == 1
+        assert bus.publish("other", 2) == 0
+        assert bus.unsubscribe(token)
+        assert bus.publish("read", 3) == 0
+        assert received == [1]
+
+    def test_wildcard_subscriber(self):
+        bus = EventBus()
+        received = []
+        bus.subscribe(ALL_TOPICS, received.append)
+        bus.publish("a", 1)
+        bus.publish("b", 2)
+        assert received == [1, 2]
+        assert bus.has_subscribers("anything")
+
+    def test_failing_handler_is_isolated(self):
+        bus = EventBus()
+        received = []
+
+        def broken(event):
+            raise RuntimeError("boom")
+
+        bus.subscribe("read", broken)
+        bus.subscribe("read", received.append)
+        assert bus.publish("read", "e") == 1
+        assert received == ["e"]
+
+
+class TestReadAuditor:
+
+    def test_record_publishes_event(self):
+        auditor = ReadAuditor(principal="alice")
+        events = []
+        auditor.bus.subscribe(READ_TOPIC, events.append)
+
+        auditor.record("users", 3, "read", "main")
+        assert len(events) == 1
+        event = events[0]
+        assert (event.table_name, event.version, event.principal) == ("users", 3, "alice")
+        assert event.branch == "main"
+
+    def test_callable_principal(self):
+        who = iter(["a", "b"])
+        auditor = ReadAuditor(principal=lambda: next(who))
+        assert auditor.record("t", 1).principal == "a"
+        assert auditor.record("t", 1).principal == "b"
+
+    def test_sampling(self):
+        never = ReadAuditor(sample_rate=0.0)
+        assert all(never.record("t", 1) is None for _ in range(100))
+
+        half = ReadAuditor(sample_rate=0.5)
+        hits = sum(half.record("t", 1) is not None for _ in range(2000))
+        assert 700 < hits < 1300
+
+    def test_table_rates_override(self):
+        auditor = ReadAuditor(
+            sample_rate=0.0,
+            table_rates={"pii": 1.0, "pii.logs": 0.0},
+        )
+        assert auditor.rate_for("pii.users") == 1.0
+        assert auditor.rate_for("pii.logs") == 0.0
+        assert auditor.rate_for("pii_other") == 0.0
+        assert auditor.record("pii/users", 1) is not None
+
+    def test_invalid_rate(self):
+        with pytest.raises(ValueError):
+            ReadAuditor(sample_rate=1.5)
+        with pytest.raises(ValueError):
+            ReadAuditor(table_rates={"t": -0.1})
+
+    def test_jsonl_sink(self, temp_dir):
+        path = os.path.join(temp_dir, "reads.jsonl")
+        auditor = ReadAuditor(principal="bob")
+        auditor.bus.subscribe(READ_TOPIC, JsonlAuditSink(path))
+
+        auditor.record("users", 1)
+        auditor.record("orders", 2, "query", "main")
+
+        with open(path) as f:
+            lines = [json.loads(line) for line in f]
+        assert [l["table_name"] for l in lines] == ["users", "orders"]
+        assert lines[1]["operation"] == "query"
+
+
+class TestDatabaseReadAudit:
+
+    def test_reads_and_queries_audited(self, temp_dir):
+        auditor = ReadAuditor(principal="analyst")
+        events = []
+        auditor.bus.subscribe(READ_TOPIC, events.append)
+
+        with rhizo.open(temp_dir, read_auditor=auditor) as db:
+            db.write("users", pd.DataFrame({"id": [1, 2]}))
+            db.write("users", pd.DataFrame({"id": [1, 2, 3]}))
+            assert events == []  # writes are not reads
+
+            db.read("users", version=1)
+            db.sql("SELECT COUNT(*) FROM users")
+
+        assert [(e.operation, e.table_name, e.version) for e in events] == [
+            ("read", "users", 1),
+            ("query", "users", 2),
+        ]
+        assert all(isinstance(e, ReadEvent) and e.principal == "analyst" for e in events)
+
+    def test_no_auditor_by_default(self, temp_dir):
+        with rhizo.open(temp_dir) as db:
+            db.write("t", pd.DataFrame({"x": [1]}))
+            db.read("t")
+            assert db.engine.read_auditor is None
@@ -218,6 +218,39 @@ class TestQueryEngineBranchIntegration:
         version = branches.get_table_version("main", "orders")
         assert version is not None
 
+    def test_merge_events_and_interactive_resolution(self, temp_storage):
+        """Merges publish events and conflicts can be resolved by callback."""
+        from rhizo.events import ALL_TOPICS
+        from rhizo.merge_events import MERGE_COMPLETED_TOPIC, MERGE_CONFLICTED_TOPIC, MERGE_STARTED_TOPIC
+
+        store, catalog, branches, _ = temp_storage
+        engine = QueryEngine(store, catalog, branch_manager=branches)
+        events = []
+        engine.events.subscribe(ALL_TOPICS, events.append)
+
+        engine.write_table("users", pd.DataFrame({"id": [1]}))
+        engine.create_branch("feature")
+        engine.write_table("users", pd.DataFrame({"id": [2]}), branch="feature")
+        engine.write_table("users", pd.DataFrame({"id": [3]}), branch="main")
+
+        # Undecided conflicts fail the merge after announcing them
+        with pytest.raises(ValueError, match="Merge conflict"):
+            engine.merge_branch("feature", into="main")
+        assert [e.topic for e in events] == [MERGE_STARTED_TOPIC, MERGE_CONFLICTED_TOPIC]
+        assert events[1].conflicts == ["users"]
+        assert events[1].analysis.conflicting == ["users"]
+
+        events.clear()
+        completed = engine.merge_branch(
+            "feature", into="main", resolve=lambda event: {t: "source" for t in event.unresolved},
+        )
+        assert [e.topic for e in events] == [
+            MERGE_STARTED_TOPIC, MERGE_CONFLICTED_TOPIC, MERGE_COMPLETED_TOPIC,
+        ]
+        assert completed.resolutions == {"users": "source"}
+        assert completed.to_dict()["analysis"]["conflicting"] == ["users"]
+        assert branches.get_table_version("main", "users") == 2
+
     def test_version_overrides_branch(self, temp_storage):
         """Test that explicit version parameter takes priority over branch."""
         store, catalog, branches, _ = temp_storage
@@ -119,6 +119,39 @@ class TestBranchManager:
             manager.create("duplicate")
 
 
+    def test_head_compare_and_set(self, temp_storage):
+        """Test HEAD generations, CAS updates and the change log."""
+        manager, _ = temp_storage
+        manager.create("release")
+
+        head = manager.head()
+        assert (head.branch, head.generation) == ("main", 1)
+
+        moved = manager.compare_and_set_head(1, "release", updated_by="deploy-bot")
+        assert moved.generation == 2
+        assert moved.updated_by == "deploy-bot"
+        assert manager.get_default() == "release"
+
+        with pytest.raises(ValueError, match="HEAD changed"):
+            manager.compare_and_set_head(1, "main")
+
+        events = manager.head_events(since_generation=1)
+        assert [(e.previous, e.head.branch) for e in events] == [("main", "release")]
+
+    def test_cannot_delete_checked_out_branch(self, temp_storage):
+        """Test that checked-out branches are protected from deletion."""
+        manager, _ = temp_storage
+        manager.create("feature")
+
+        manager.checkout("worker-1", "feature")
+        with pytest.raises(ValueError, match="checked out by workspace worker-1"):
+            manager.delete("feature")
+        assert [(c.workspace, c.branch) for c in manager.checkouts()] == [("worker-1", "feature")]
+
+        assert manager.release_checkout("worker-1").branch == "feature"
+        manager.delete("feature")
+        assert "feature" not in manager.list()
+
 class TestBranchHeadOperations:
     """Tests for branch head pointer operations."""
 
@@ -334,3 +367,60 @@ class TestBranchValidation:
         for name in invalid_names:
             with pytest.raises(ValueError, match="Invalid branch name"):
                 manager.create(name)
+
+
+class TestBranchScopedTables:
+    """Tests for tables created on a branch staying isolated until merged."""
+
+    def _commit(self, catalog, table):
+        catalog.commit(_rhizo.PyTableVersion(table, 1, ["a" * 64]))
+
+    def test_new_table_hidden_from_other_branches(self, temp_storage):
+        """A table created on a feature branch is invisible on main."""
+        manager, base_dir = temp_storage
+        catalog = _rhizo.PyCatalog(
+            os.path.join(base_dir, "catalog"),
+            branch_path=os.path.join(base_dir, "branches"),
+        )
+
+        self._commit(catalog, "shared")
+        manager.update_head("main", "shared", 1)
+        manager.create("feature", from_branch="main")
+
+        catalog.set_table_origin("scratch", "feature")
+        self._commit(catalog, "scratch")
+        manager.update_head("feature", "scratch", 1)
+
+        assert catalog.table_origin("scratch") == "feature"
+        assert catalog.table_origin("shared") is None
+        assert catalog.list_tables(branch="main") == ["shared"]
+        assert catalog.list_tables(branch="feature") == ["scratch", "shared"]
+        assert sorted(catalog.list_tables()) == ["scratch", "shared"]
+
+        with pytest.raises(IOError, match="not found"):
+            catalog.get_version("scratch", branch="main")
+        assert catalog.get_version("scratch", branch="feature").version == 1
+
+    def test_table_visible_after_merge(self, temp_storage):
+        """Merging a branch makes its new tables visible on the target."""
+        manager, base_dir = temp_storage
+        catalog = _rhizo.PyCatalog(
+            os.path.join(base_dir, "catalog"),
+            branch_path=os.path.join(base_dir, "branches"),
+        )
+
+        manager.create("feature", from_branch="main")
+        catalog.set_table_origin("scratch", "feature")
+        self._commit(catalog, "scratch")
+        manager.update_head("feature", "scratch", 1)
+
+        manager.merge("feature", "main")
+        assert catalog.list_tables(branch="main") == ["scratch"]
+
+    def test_branch_argument_requires_branch_path(self, temp_storage):
+        """Branch-scoped queries need a branch manager."""
+        _, base_dir = temp_storage
+        catalog = _rhizo.PyCatalog(os.path.join(base_dir, "catalog"))
+
+        with pytest.raises(ValueError, match="branch_path"):
+            catalog.list_tables(branch="main")
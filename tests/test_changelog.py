@@ -7,6 +7,7 @@ Tests the unified batch/stream model:
 """
 
 import pytest
+import sqlite3
 import tempfile
 import time
 import threading
@@ -14,7 +15,7 @@ import pandas as pd
 from pathlib import Path
 
 import _rhizo
-from rhizo import QueryEngine, Subscriber, ChangeEvent
+from rhizo import QueryEngine, Subscriber, ChangeEvent, ChangelogExporter
 
 
 @pytest.fixture
@@ -274,6 +275,41 @@ class TestSubscriberPoll:
         assert "users" in table_names
 
 
+class TestChangelogCursor:
+    """Tests for persisted changelog cursors."""
+
+    def test_subscriber_resumes_from_cursor(self, engine_with_tx):
+        """A subscriber with a cursor skips what an earlier one delivered."""
+        df = pd.DataFrame({"id": [1]})
+        first = engine_with_tx.subscribe(since_tx_id=0, cursor="indexer")
+        with engine_with_tx.transaction() as tx:
+            tx.write_table("table1", df)
+        assert [e.table_name for e in first.poll()] == ["table1"]
+
+        resumed = engine_with_tx.subscribe(since_tx_id=0, cursor="indexer")
+        assert resumed.last_tx_id == first.last_tx_id
+        assert resumed.poll() == []
+        with engine_with_tx.transaction() as tx:
+            tx.write_table("table2", df)
+        assert [e.table_name for e in resumed.poll()] == ["table2"]
+        assert resumed.cursor.acked_tx_id == resumed.last_tx_id
+
+    def test_cursor_api(self, engine_with_tx):
+        """Cursors are created, acknowledged, listed and deleted by name."""
+        tm = engine_with_tx.transaction_manager
+        cursor = tm.create_cursor("audit/eu")
+        assert cursor.acked_tx_id == 0
+        cursor.ack(3)
+        cursor.ack(1)
+        assert tm.resume("audit/eu").acked_tx_id == 3
+        assert [c.name for c in tm.list_cursors()] == ["audit/eu"]
+        with pytest.raises(ValueError, match="already exists"):
+            tm.create_cursor("audit/eu")
+        tm.delete_cursor("audit/eu")
+        with pytest.raises(ValueError, match="not found"):
+            tm.resume("audit/eu")
+
+
 class TestChangeEvent:
     """Tests for ChangeEvent dataclass."""
 
@@ -375,6 +411,72 @@ class TestSubscriberBackground:
             subscriber.stop()
 
 
+class TestChangelogExporter:
+    """Tests for mirroring the changelog into a SQL table."""
+
+    def test_sync_exports_rows_with_author(self, engine_with_tx):
+        conn = sqlite3.connect(":memory:")
+        exporter = ChangelogExporter(engine_with_tx.transaction_manager, conn)
+
+        df = pd.DataFrame({"id": [1]})
+        with engine_with_tx.transaction(author="alice") as tx:
+            tx.write_table("users", df)
+            tx.write_table("orders", df)
+        with engine_with_tx.transaction() as tx:
+            tx.write_table("users", df)
+
+        assert exporter.sync() == 3
+        assert exporter.sync() == 0
+
+        rows = conn.execute(
+            "SELECT table_name, old_version, new_version, author "
+            "FROM rhizo_changelog ORDER BY tx_id, table_name"
+        ).fetchall()
+        assert rows == [
+            ("orders", None, 1, "alice"),
+            ("users", None, 1, "alice"),
+            ("users", 1, 2, None),
+        ]
+
+    def test_resumes_from_exported_rows(self, engine_with_tx):
+        conn = sqlite3.connect(":memory:")
+        df = pd.DataFrame({"id": [1]})
+        with engine_with_tx.transaction() as tx:
+            tx.write_table("users", df)
+        ChangelogExporter(engine_with_tx.transaction_manager, conn).sync()
+
+        with engine_with_tx.transaction() as tx:
+            tx.write_table("orders", df)
+        exporter = ChangelogExporter(engine_with_tx.transaction_manager, conn)
+        assert exporter.sync() == 1
+        assert conn.execute("SELECT COUNT(*) FROM rhizo_changelog").fetchone() == (2,)
+
+    def test_background_watcher(self, engine_with_tx):
+        conn = sqlite3.connect(":memory:", check_same_thread=False)
+        exporter = ChangelogExporter(
+            engine_with_tx.transaction_manager, conn, table="history", poll_interval=0.05,
+        )
+        exporter.start()
+        try:
+            with engine_with_tx.transaction(author="bob") as tx:
+                tx.write_table("users", pd.DataFrame({"id": [1]}))
+
+            deadline = time.time() + 5
+            while exporter.last_tx_id == 0 and time.time() < deadline:
+                time.sleep(0.05)
+            assert conn.execute("SELECT author FROM history").fetchall() == [("bob",)]
+        finally:
+            exporter.stop()
+        assert exporter.is_running is False
+
+    def test_invalid_table_name(self, engine_with_tx):
+        with pytest.raises(ValueError, match="Invalid table name"):
+            ChangelogExporter(
+                engine_with_tx.transaction_manager, sqlite3.connect(":memory:"),
+                table="x; DROP TABLE y",
+            )
+
+
 class TestUnifiedBatchStream:
     """Tests demonstrating the unified batch/stream model."""
 
@@ -443,3 +545,55 @@ class TestUnifiedBatchStream:
         table_names = [c["changes"][0]["table_name"] for c in changes]
         assert "events_v2" in table_names
         assert "events_v3" in table_names
+
+
+class TestReplay:
+    """Tests for Database.replay() onto another database."""
+
+    def _populate(self, db):
+        for name, rows in (("orders", 2), ("users", 3), ("items", 4)):
+            with db.engine.transaction() as tx:
+                tx.write_table(name, pd.DataFrame({"id": list(range(rows))}))
+
+    def test_replay_full_log(self, tmp_path):
+        """Replaying the whole log reproduces tables and heads."""
+        from rhizo import Database
+
+        with Database(str(tmp_path / "src")) as src:
+            self._populate(src)
+            report = src.replay(str(tmp_path / "mirror"))
+
+            assert report.entries_applied == 3
+            assert report.heads["main"] == {"orders": 1, "users": 1, "items": 1}
+
+            with Database(str(tmp_path / "mirror")) as mirror:
+                assert sorted(mirror.tables()) == ["items", "orders", "users"]
+                assert len(mirror.read("items")) == 4
+
+    def test_replay_in_ranges(self, tmp_path):
+        """Consecutive ranges resume where the previous run stopped."""
+        from rhizo import Database
+
+        with Database(str(tmp_path / "src")) as src:
+            self._populate(src)
+            latest = src.engine.latest_tx_id()
+
+            first = src.replay(str(tmp_path / "mirror"), range(1, latest))
+            assert first.last_tx_id == latest - 1
+
+            rest = src.replay(str(tmp_path / "mirror"), (latest, latest))
+            assert rest.entries_applied == 1
+
+    def test_replay_gap_rejected(self, tmp_path):
+        """A range that skips history is rejected."""
+        from rhizo import Database
+
+        with Database(str(tmp_path / "src")) as src:
+            with src.engine.transaction() as tx:
+                tx.write_table("orders", pd.DataFrame({"id": [1]}))
+            with src.engine.transaction() as tx:
+                tx.write_table("orders", pd.DataFrame({"id": [1, 2]}))
+
+            latest = src.engine.latest_tx_id()
+            with pytest.raises(ValueError):
+                src.replay(str(tmp_path / "mirror"), (latest, latest))
@@ -1,7 +1,8 @@
 """
 Tests for rhizo.cli module.
 
-Covers all CLI commands (info, tables, versions, verify) and the main() parser.
+Covers all CLI commands (info, tables, versions, verify, filter, backup,
+restore) and the main() parser.
 """
 
 import tempfile
@@ -180,3 +181,87 @@ class TestCmdVerify:
         main(["verify", populated_db])
         out = capsys.readouterr().out
         assert "2 table(s)" in out
+
+
+# ---------------------------------------------------------------------------
+# cmd_filter
+# ---------------------------------------------------------------------------
+
+class TestCmdFilter:
+    """Test the 'filter' command."""
+
+    def test_filter_selected_table(self, populated_db, tmp_path, capsys):
+        dest = str(tmp_path / "filtered")
+        ret = main(["filter", populated_db, dest, "--table", "users"])
+        assert ret == 0
+        assert "users: 2 version(s) kept" in capsys.readouterr().out
+
+        with rhizo.open(dest) as db:
+            assert db.tables() == ["users"]
+            assert db.versions("users") == [1, 2]
+            assert db.read("users")["name"].tolist() == ["c"]
+
+    def test_filter_truncates_history(self, populated_db, tmp_path, capsys):
+        dest = str(tmp_path / "filtered")
+        ret = main(["filter", populated_db, dest, "--keep-versions", "1"])
+        assert ret == 0
+
+        with rhizo.open(dest) as db:
+            assert sorted(db.tables()) == ["orders", "users"]
+            # Latest users version (v2) is renumbered to v1
+            assert db.versions("users") == [1]
+            assert db.read("users")["name"].tolist() == ["c"]
+            assert db.info("users")["parent_version"] is None
+
+    def test_filter_existing_table_fails(self, populated_db, tmp_path, capsys):
+        dest = str(tmp_path / "filtered")
+        assert main(["filter", populated_db, dest, "--table", "users"]) == 0
+        ret = main(["filter", populated_db, dest, "--table", "users"])
+        assert ret == 1
+        assert "already exists" in capsys.readouterr().err
+
+    def test_filter_nonexistent_source(self, tmp_path, capsys):
+        ret = main(["filter", "/no/such/path", str(tmp_path / "out")])
+        assert ret == 1
+        assert "Error" in capsys.readouterr().err
+
+
+class TestCmdBackupRestore:
+    """Test the 'backup' and 'restore' commands."""
+
+    def test_incremental_backup_and_restore(self, populated_db, tmp_path, capsys):
+        full = str(tmp_path / "full")
+        incr = str(tmp_path / "incr")
+        assert main(["backup", populated_db, full]) == 0
+        assert "Full backup" in capsys.readouterr().out
+
+        with rhizo.open(populated_db) as db:
+            db.write("orders", pd.DataFrame({"id": [11], "value": [1.5]}))
+        assert main(["backup", populated_db, incr, "--incremental-since", full]) == 0
+        out = capsys.readouterr().out
+        assert "Incremental backup" in out
+        assert "versions stored: 1" in out
+
+        assert main(["restore", incr, "--verify-only"]) == 0
+        assert "2 backup(s)" in capsys.readouterr().out
+
+        restored = str(tmp_path / "restored")
+        assert main(["restore", incr, restored]) == 0
+        with rhizo.open(restored) as db:
+            assert sorted(db.tables()) == ["orders", "users"]
+            assert db.versions("orders") == [1, 2]
+            assert db.read("orders")["id"].tolist() == [11]
+
+    def test_restore_detects_tampered_manifest(self, populated_db, tmp_path, capsys):
+        backup = tmp_path / "b"
+        assert main(["backup", populated_db, str(backup)]) == 0
+        manifest = backup / "manifest.json"
+        manifest.write_text(manifest.read_text().replace("users", "usurp"))
+
+        assert main(["restore", str(backup), "--verify-only"]) == 1
+        assert "checksum" in capsys.readouterr().err
+
+    def test_restore_requires_path(self, populated_db, tmp_path, capsys):
+        assert main(["backup", populated_db, str(tmp_path / "b")]) == 0
+        assert main(["restore", str(tmp_path / "b")]) == 1
+        assert "required" in capsys.readouterr().err
@@ -9,6 +9,7 @@ import gc
 import warnings
 import tempfile
 import shutil
+from pathlib import Path
 
 import pandas as pd
 import pytest
@@ -54,6 +55,26 @@ class TestDatabaseLifecycle:
                 raise ValueError("test error")
         assert db._closed
 
+    def test_ephemeral_repo_is_isolated_and_removed(self):
+        """ephemeral_repo() opens a fresh directory and deletes it on exit."""
+        with rhizo.ephemeral_repo() as db, rhizo.ephemeral_repo() as other:
+            path = db.path
+            assert path != other.path
+            db.write("users", pd.DataFrame({"id": [1, 2]}))
+            assert other.tables() == []
+        assert db._closed
+        assert not path.exists()
+
+    def test_ephemeral_repo_keep(self):
+        """keep=True leaves the directory for inspection."""
+        with rhizo.ephemeral_repo(keep=True, enable_transactions=False) as db:
+            db.write("users", pd.DataFrame({"id": [1]}))
+        try:
+            with rhizo.open(str(db.path)) as reopened:
+                assert reopened.tables() == ["users"]
+        finally:
+            shutil.rmtree(db.path, ignore_errors=True)
+
     def test_operations_after_close_raise(self, temp_dir):
         """All operations raise RuntimeError after close."""
         db = rhizo.open(temp_dir)
@@ -165,9 +186,181 @@ class TestDatabaseBasicOperations:
             assert "alpha" in tables
             assert "beta" in tables
 
+    def test_create_table_without_rows(self, temp_dir):
+        """create_table records the schema as version 1 with no rows."""
+        import pyarrow as pa
+
+        schema = pa.schema([("id", pa.int64()), ("name", pa.string())])
+        with rhizo.open(temp_dir) as db:
+            result = db.create_table("users", schema, {"owner": "ops"})
+            assert (result.version, result.total_rows) == (1, 0)
+            assert "users" in db.tables()
+
+            empty = db.read("users")
+            assert empty.num_rows == 0
+            assert empty.schema.names == ["id", "name"]
+
+            with pytest.raises(ValueError, match="already exists"):
+                db.create_table("users", schema)
+
+            db.write("users", pd.DataFrame({"id": [1], "name": ["a"]}))
+            assert db.versions("users") == [1, 2]
+
     def test_repr(self, temp_dir):
         """repr shows open/closed status."""
         db = rhizo.open(temp_dir)
         assert "open" in repr(db)
         db.close()
         assert "closed" in repr(db)
+
+
+class TestDatabasePins:
+    """Version pins for reproducible runs."""
+
+    def test_pin_current_versions(self, temp_dir):
+        """pin() records current versions that sql() can read back."""
+        with rhizo.open(temp_dir) as db:
+            db.write("features", pd.DataFrame({"x": [1, 2]}))
+            db.write("labels", pd.DataFrame({"y": [0]}))
+            pinned = db.pin("run-1", ["features"], description="baseline")
+            assert pinned == {"features": 1}
+
+            db.write("features", pd.DataFrame({"x": [1, 2, 3]}))
+            versions = db.resolve_pin("run-1")
+            result = db.sql("SELECT * FROM features", versions=versions)
+            assert result.row_count == 2
+            assert db.pins() == ["run-1"]
+
+    def test_resolve_ref(self, temp_dir):
+        """resolve_ref() names the same versions as pins, for any ref form."""
+        with rhizo.open(temp_dir) as db:
+            db.write("features", pd.DataFrame({"x": [1, 2]}))
+            db.write("features", pd.DataFrame({"x": [1, 2, 3]}))
+            assert db.resolve_ref("main") == {"features": 2}
+            versions = db.resolve_ref("features@v1")
+            assert versions == {"features": 1}
+            assert db.sql("SELECT * FROM features", versions=versions).row_count == 2
+            with pytest.raises(ValueError):
+                db.resolve_ref("main~x")
+
+    def test_pin_is_immutable(self, temp_dir):
+        """Re-pinning an existing name fails until it is unpinned."""
+        with rhizo.open(temp_dir) as db:
+            db.write("t", pd.DataFrame({"x": [1]}))
+            db.pin("run-1")
+            with pytest.raises(ValueError, match="already exists"):
+                db.pin("run-1")
+            db.unpin("run-1")
+            assert db.pins() == []
+
+    def test_gc_keeps_pinned_version(self, temp_dir):
+        """GC never removes a pinned version."""
+        with rhizo.open(temp_dir) as db:
+            for n in range(3):
+                db.write("t", pd.DataFrame({"x": list(range(n + 1))}))
+            db.pin("run-1", versions={"t": 1})
+            db.gc(max_versions_per_table=1)
+            assert db.versions("t") == [1, 3]
+
+
+class TestDatabaseLegalHolds:
+    """Legal holds and immutable branches."""
+
+    def test_hold_protects_version_until_admin_release(self, temp_dir):
+        """Held versions survive GC; only db.admin lists and releases holds."""
+        with rhizo.open(temp_dir) as db:
+            for n in range(3):
+                db.write("trades", pd.DataFrame({"x": list(range(n + 1))}))
+            hold = db.hold("trades", 1, reason="case-42")
+            assert (hold.table_name, hold.version, hold.reason) == ("trades", 1, "case-42")
+
+            db.gc(max_versions_per_table=1)
+            assert db.versions("trades") == [1, 3]
+            assert [(h.table_name, h.version) for h in db.admin.holds()] == [("trades", 1)]
+
+            db.admin.release_hold("trades", 1)
+            assert db.admin.holds() == []
+            db.gc(max_versions_per_table=1)
+            assert db.versions("trades") == [3]
+
+    def test_hold_defaults_to_latest(self, temp_dir):
+        with rhizo.open(temp_dir) as db:
+            db.write("t", pd.DataFrame({"x": [1]}))
+            db.write("t", pd.DataFrame({"x": [2]}))
+            assert db.hold("t").version == 2
+
+    def test_frozen_branch_rejects_writes(self, temp_dir):
+        """Writes and merges into a frozen branch fail until unfrozen."""
+        with rhizo.open(temp_dir) as db:
+            db.write("t", pd.DataFrame({"x": [1]}))
+            db.engine.create_branch("release")
+            db.freeze_branch("release")
+            assert db.admin.frozen_branches() == ["release"]
+
+            with pytest.raises(ValueError, match="immutable"):
+                db.engine.write_table("t", pd.DataFrame({"x": [2]}), branch="release")
+            assert db.versions("t") == [1]
+
+            db.write("t", pd.DataFrame({"x": [3]}))
+            with pytest.raises(ValueError, match="immutable"):
+                db.engine.merge_branch("main", into="release")
+
+            db.admin.unfreeze_branch("release")
+            db.engine.merge_branch("main", into="release")
+            assert db.admin.frozen_branches() == []
+
+    def test_break_lock_requires_force_for_unknown_holder(self, temp_dir):
+        """A lock held by a process on another host is only broken when forced."""
+        fcntl = pytest.importorskip("fcntl")
+        import json
+
+        with rhizo.open(temp_dir) as db:
+            db.write("trades", pd.DataFrame({"x": [1]}))
+            assert db.admin.lock_owner("trades") is None
+
+            lock_path = Path(temp_dir) / "catalog" / "trades" / ".lock"
+            with open(lock_path, "w") as holder:
+                fcntl.flock(holder, fcntl.LOCK_EX)
+                holder.write(json.dumps({"pid": 4242, "host": "elsewhere", "acquired_at": 0}))
+                holder.flush()
+
+                owner = db.admin.lock_owner("trades")
+                assert (owner.pid, owner.host, owner.is_alive()) == (4242, "elsewhere", None)
+                with pytest.raises(ValueError, match="not known to be stale"):
+                    db.admin.break_lock("trades")
+                assert db.admin.break_lock("trades", force=True).pid == 4242
+
+            db.write("trades", pd.DataFrame({"x": [2]}))
+            assert db.versions("trades") == [1, 2]
+
+
+class TestDatabaseBackup:
+    """Full and incremental backups restored into a new database."""
+
+    def test_backup_restore_round_trip(self, temp_dir):
+        source = Path(temp_dir) / "src"
+        with rhizo.open(str(source)) as db:
+            db.write("users", pd.DataFrame({"id": [1, 2]}))
+            db.set_primary_key("users", ["id"])
+            db.hold("users", reason="audit")
+            db.backup(str(Path(temp_dir) / "full"))
+
+            db.write("users", pd.DataFrame({"id": [1, 2, 3]}))
+            manifest = db.backup(
+                str(Path(temp_dir) / "incr"),
+                incremental_since=str(Path(temp_dir) / "full"),
+            )
+            assert manifest.is_incremental
+            assert manifest.versions == 1
+
+        with rhizo.open(str(Path(temp_dir) / "restored")) as db:
+            report = db.restore(str(Path(temp_dir) / "incr"), verify_only=True)
+            assert report.backups == 2
+            assert db.tables() == []
+
+            report = db.restore(str(Path(temp_dir) / "incr"))
+            assert report.versions_restored == 2
+            assert db.versions("users") == [1, 2]
+            assert db.read("users")["id"].tolist() == [1, 2, 3]
+            assert db.primary_key("users") == ["id"]
+            assert [h.reason for h in db.admin.holds()] == ["audit"]
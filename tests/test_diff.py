@@ -11,6 +11,7 @@ Covers:
   - Edge cases (7 tests)
   - Display / summary (5 tests)
   - Database.diff() integration (5 tests)
+  - Database.changed_since() (4 tests)
 """
 
 from __future__ import annotations
@@ -744,3 +745,56 @@ class TestDatabaseDiff:
             mod = diff.rows.modified
             assert mod.column("__old_score")[0].as_py() == 92
             assert mod.column("__new_score")[0].as_py() == 95
+
+
+# ===========================================================================
+# Database.changed_since() (4 tests)
+# ===========================================================================
+
+class TestChangedSince:
+
+    def test_unchanged_since_pin(self, temp_dir):
+        with rhizo.open(temp_dir) as db:
+            db.write("features", pd.DataFrame({"x": [1, 2, 3]}))
+            db.pin("run-1")
+
+            report = db.changed_since("run-1")
+            assert not report.changed
+            assert report.tables["features"].rows_after == 3
+            assert report.total_bytes_changed == 0
+
+    def test_rows_and_versions_since_pin(self, temp_dir):
+        with rhizo.open(temp_dir) as db:
+            db.write("features", pd.DataFrame({"x": [1, 2, 3]}))
+            db.write("labels", pd.DataFrame({"y": [0, 1, 0]}))
+            db.pin("run-1")
+            db.write("features", pd.DataFrame({"x": [1, 2, 3, 4]}))
+            db.write("features", pd.DataFrame({"x": [1, 2, 3, 4, 5]}))
+
+            report = db.changed_since("run-1", ["features", "labels"])
+            assert report.changed_tables == ["features"]
+            features = report.tables["features"]
+            assert (features.from_version, features.to_version) == (1, 3)
+            assert features.versions_added == 2
+            assert features.rows_delta == 2
+            assert features.bytes_changed > 0
+            assert "features" in report.summary()
+
+    def test_new_table_since_pin(self, temp_dir):
+        with rhizo.open(temp_dir) as db:
+            db.write("a", pd.DataFrame({"x": [1]}))
+            db.pin("run-1")
+            db.write("b", pd.DataFrame({"x": [1, 2]}))
+
+            b = db.changed_since("run-1").tables["b"]
+            assert b.from_version is None
+            assert b.rows_before == 0 and b.rows_after == 2
+
+    def test_explicit_versions(self, temp_dir):
+        with rhizo.open(temp_dir) as db:
+            db.write("t", pd.DataFrame({"x": [1]}))
+            db.write("t", pd.DataFrame({"x": [1, 2]}))
+
+            report = db.changed_since({"t": 1})
+            assert report.since == "versions"
+            assert report.tables["t"].rows_delta == 1
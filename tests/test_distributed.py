@@ -4,7 +4,7 @@ import pytest
 from _rhizo import (
     PyNodeId, PyVectorClock, PyCausalOrder,
     PyAlgebraicOperation, PyAlgebraicTransaction, PyVersionedUpdate,
-    PyLocalCommitProtocol, PyOpType, PyAlgebraicValue, algebraic_merge,
+    PyLocalCommitProtocol, PyCoordinationFreeManager, PyOpType, PyAlgebraicValue, algebraic_merge,
 )
 
 
@@ -801,6 +801,65 @@ class TestVersionedUpdate:
         assert update_a.is_concurrent_with(update_b)
         assert update_a.compare(update_b).order == "concurrent"
 
+    def test_update_json_roundtrip(self):
+        """Test shipping an update between processes as JSON."""
+        tx = PyAlgebraicTransaction()
+        tx.add_operation(PyAlgebraicOperation("x", PyOpType("max"), PyAlgebraicValue.integer(7)))
+        update = PyLocalCommitProtocol.commit_local(tx, PyNodeId("a"), PyVectorClock())
+
+        restored = PyVersionedUpdate.from_json(update.to_json())
+        assert restored.origin_node == update.origin_node
+        assert restored.clock == update.clock
+        assert str(restored.operations()[0].value) == "7"
+        with pytest.raises(ValueError, match="Deserialization error"):
+            PyVersionedUpdate.from_json("{}")
+
+
+class TestCoordinationFreeManager:
+    """Tests for PyCoordinationFreeManager."""
+
+    @staticmethod
+    def tx(key, op, value):
+        tx = PyAlgebraicTransaction()
+        tx.add_operation(PyAlgebraicOperation(key, PyOpType(op), PyAlgebraicValue.integer(value)))
+        return tx
+
+    def test_nodes_converge_end_to_end(self):
+        """Two managers commit concurrently, exchange updates and converge."""
+        sf = PyCoordinationFreeManager(PyNodeId("sf"))
+        tokyo = PyCoordinationFreeManager(PyNodeId("tokyo"))
+        assert sf.node_id == PyNodeId("sf")
+
+        update_sf = sf.commit_local(self.tx("views", "add", 100))
+        sf.commit_local(self.tx("peak", "max", 3))
+        update_tokyo = tokyo.commit_local(self.tx("views", "add", 50))
+        assert sf.update_count() == 2
+        assert sf.clock().get(PyNodeId("sf")) == 2
+
+        # Updates travel as JSON between services
+        tokyo.receive_update(PyVersionedUpdate.from_json(update_sf.to_json()))
+        sf.receive_update(update_tokyo)
+        assert str(sf.get_state("views")) == str(tokyo.get_state("views")) == "150"
+        assert sf.keys() == ["peak", "views"]
+        assert tokyo.get_state("peak") is None
+        assert tokyo.clock().get(PyNodeId("sf")) == 1
+
+        merged = sf.merge_updates(update_sf, update_tokyo)
+        assert str(merged.operations()[0].value) == "150"
+
+    def test_rejects_non_algebraic(self):
+        """Non-algebraic transactions and mismatched operations raise."""
+        manager = PyCoordinationFreeManager(PyNodeId("a"))
+        tx = self.tx("name", "overwrite", 1)
+        assert manager.can_commit_locally(tx) is False
+        with pytest.raises(ValueError):
+            manager.commit_local(tx)
+
+        manager.commit_local(self.tx("x", "add", 1))
+        with pytest.raises(ValueError, match="Operation type mismatch") as excinfo:
+            manager.commit_local(self.tx("x", "max", 2))
+        assert excinfo.value.code == "COORDINATION_FREE_MERGE"
+
 
 class TestRealisticScenario:
     """Integration tests for realistic distributed scenarios."""
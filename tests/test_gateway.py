@@ -0,0 +1,130 @@
+"""
+Tests for the read-only HTTP+JSON gateway.
+
+Run with: pytest tests/test_gateway.py -v
+"""
+
+import json
+import os
+import urllib.error
+import urllib.request
+
+import _rhizo
+from rhizo.gateway import RestGateway
+
+
+def _repo(tmp_path):
+    """Two tables on main and a feature branch, committed through transactions."""
+    base = str(tmp_path)
+    store = _rhizo.PyChunkStore(os.path.join(base, "chunks"))
+    catalog = _rhizo.PyCatalog(os.path.join(base, "catalog"))
+    branches = _rhizo.PyBranchManager(os.path.join(base, "branches"))
+    tx_manager = _rhizo.PyTransactionManager(
+        os.path.join(base, "transactions"),
+        os.path.join(base, "catalog"),
+        os.path.join(base, "branches"),
+    )
+
+    def commit(branch, table, version, payload):
+        tx = tx_manager.begin(branch)
+        tx_manager.add_write(tx, table, version, [store.put(payload)])
+        tx_manager.commit(tx)
+
+    commit("main", "users", 1, b"alice")
+    commit("main", "orders", 1, b"order-1")
+    branches.create("feature", from_branch="main", description="try things")
+    commit("feature", "users", 2, b"alice,bob")
+    return RestGateway(catalog, branches, tx_manager, port=0)
+
+
+def _get(gateway, path, method="GET"):
+    request = urllib.request.Request(gateway.url + path, method=method)
+    try:
+        with urllib.request.urlopen(request) as response:
+            body = response.read()
+            return response.status, dict(response.headers), json.loads(body) if body else None
+    except urllib.error.HTTPError as e:
+        return e.code, dict(e.headers), json.loads(e.read())
+
+
+class TestRestGateway:
+    """Endpoints answered by RestGateway.handle and over HTTP."""
+
+    def test_tables_and_versions(self, tmp_path):
+        gateway = _repo(tmp_path)
+
+        status, body = gateway.handle("/tables")
+        assert status == 200
+        assert body["tables"] == [
+            {"name": "orders", "version": 1, "version_count": 1},
+            {"name": "users", "version": 2, "version_count": 2},
+        ]
+
+        _, body = gateway.handle("/tables?branch=main")
+        assert [(t["name"], t["version"]) for t in body["tables"]] == [("orders", 1), ("users", 1)]
+
+        _, body = gateway.handle("/tables/users/versions")
+        assert [v["version"] for v in body["versions"]] == [1, 2]
+        assert "chunk_hashes" not in body["versions"][0]
+
+        _, body = gateway.handle("/tables/users/versions/2")
+        assert body["version"]["parent_version"] == 1
+        assert len(body["version"]["chunk_hashes"]) == 1
+
+    def test_changelog_filters(self, tmp_path):
+        gateway = _repo(tmp_path)
+
+        _, body = gateway.handle("/changelog")
+        assert [e["branch"] for e in body["entries"]] == ["main", "main", "feature"]
+
+        _, body = gateway.handle("/changelog?table=users&branch=feature")
+        (entry,) = body["entries"]
+        assert entry["changes"][0]["old_version"] == 1
+        assert entry["changes"][0]["new_version"] == 2
+
+        _, body = gateway.handle("/changelog?table=users&table=orders&limit=2")
+        assert len(body["entries"]) == 2
+
+    def test_branch_graph(self, tmp_path):
+        gateway = _repo(tmp_path)
+
+        _, body = gateway.handle("/branches")
+        assert [b["name"] for b in body["branches"]] == ["feature", "main"]
+        assert body["edges"] == [{"from": "main", "to": "feature"}]
+
+        _, body = gateway.handle("/branches/feature")
+        assert body["branch"]["head"] == {"users": 2, "orders": 1}
+        assert body["branch"]["description"] == "try things"
+
+    def test_errors(self, tmp_path):
+        gateway = _repo(tmp_path)
+
+        assert gateway.handle("/nope")[0] == 404
+        assert gateway.handle("/tables/missing/versions")[0] == 404
+        assert gateway.handle("/branches/missing")[0] == 404
+        status, body = gateway.handle("/changelog?limit=ten")
+        assert status == 400
+        assert "limit" in body["error"]
+
+        without_changelog = RestGateway(_rhizo.PyCatalog(os.path.join(str(tmp_path), "catalog")))
+        assert without_changelog.handle("/changelog")[0] == 404
+        assert without_changelog.handle("/branches")[0] == 404
+
+    def test_http_is_read_only_with_cors(self, tmp_path):
+        with _repo(tmp_path) as gateway:
+            status, headers, body = _get(gateway, "/tables")
+            assert status == 200
+            assert headers["Content-Type"] == "application/json"
+            assert headers["Access-Control-Allow-Origin"] == "*"
+            assert len(body["tables"]) == 2
+
+            status, headers, _ = _get(gateway, "/tables", method="OPTIONS")
+            assert status == 204
+            assert "GET" in headers["Access-Control-Allow-Methods"]
+
+            status, headers, body = _get(gateway, "/tables", method="DELETE")
+            assert status == 405
+            assert headers["Allow"] == "GET, OPTIONS"
+
+            assert _get(gateway, "/tables/missing/versions")[0] == 404
+        assert not gateway.running
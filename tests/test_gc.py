@@ -8,6 +8,7 @@ Covers:
   - Count-based retention (7 tests)
   - Combined policy (4 tests)
   - Chunk sweep — phase 2 (5 tests)
+  - Reachability sweep (5 tests)
   - Two-phase integrity (4 tests)
   - AutoGC background thread (4 tests)
   - Database.gc() integration (5 tests)
@@ -126,7 +127,7 @@ class TestGCPolicy:
 # ===========================================================================
 
 class TestProtectedVersions:
-    """Protected version safety — 8 tests."""
+    """Protected version safety — 10 tests."""
 
     def test_latest_always_protected(self, gc_env):
         """Latest version must never be deleted, even with aggressive policy."""
@@ -249,6 +250,37 @@ class TestProtectedVersions:
         assert 1 in protected.get("t1", set())  # branch head
         assert 3 in protected.get("t1", set())  # latest
 
+    def test_pinned_version_protected(self, gc_env):
+        """Versions held by a pin survive GC until the pin is deleted."""
+        catalog = gc_env["catalog"]
+        for v in (1, 2, 3):
+            catalog.commit(_rhizo.PyTableVersion("t1", v, []))
+        catalog.pin("experiment", {"t1": 1})
+
+        gc = _make_gc(gc_env, branch_mgr=False, tx_mgr=False)
+        result = gc.collect(GCPolicy(max_versions_per_table=1))
+        assert catalog.list_versions("t1") == [1, 3]
+        assert result.versions_deleted == 1
+
+        catalog.delete_pin("experiment")
+        gc.collect(GCPolicy(max_versions_per_table=1))
+        assert catalog.list_versions("t1") == [3]
+
+    def test_legally_held_version_protected(self, gc_env):
+        """Versions under legal hold survive GC until the hold is released."""
+        catalog = gc_env["catalog"]
+        for v in (1, 2, 3):
+            catalog.commit(_rhizo.PyTableVersion("t1", v, []))
+        catalog.hold("t1", 2, "case-7")
+
+        gc = _make_gc(gc_env, branch_mgr=False, tx_mgr=False)
+        gc.collect(GCPolicy(max_versions_per_table=1))
+        assert catalog.list_versions("t1") == [2, 3]
+
+        catalog.release_hold("t1", 2)
+        gc.collect(GCPolicy(max_versions_per_table=1))
+        assert catalog.list_versions("t1") == [3]
+
 
 # ===========================================================================
 # TestTimeTTL
@@ -446,6 +478,43 @@ class TestCountRetention:
         assert result.versions_deleted == 0
 
 
+# ===========================================================================
+# TestNamespaceGC
+# ===========================================================================
+
+class TestNamespaceGC:
+    """GC restricted to one table namespace."""
+
+    @staticmethod
+    def _commit_versions(catalog, table_name, count):
+        for v in range(1, count + 1):
+            catalog.commit(_rhizo.PyTableVersion(table_name, v, []))
+
+    def test_prefix_limits_deletion(self, gc_env):
+        catalog = gc_env["catalog"]
+        self._commit_versions(catalog, "sales.orders", 3)
+        self._commit_versions(catalog, "sales/eu/invoices", 3)
+        self._commit_versions(catalog, "hr.people", 3)
+
+        gc = _make_gc(gc_env)
+        result = gc.collect(GCPolicy(max_versions_per_table=1, namespace="sales"))
+
+        assert set(result.details) == {"sales.orders", "sales/eu/invoices"}
+        assert catalog.list_versions("sales.orders") == [3]
+        assert catalog.list_versions("hr.people") == [1, 2, 3]
+
+    def test_pattern_limits_deletion(self, gc_env):
+        catalog = gc_env["catalog"]
+        self._commit_versions(catalog, "sales.orders", 2)
+        self._commit_versions(catalog, "sales/eu/invoices", 2)
+
+        gc = _make_gc(gc_env)
+        result = gc.collect(GCPolicy(max_versions_per_table=1, namespace="sales.*"))
+
+        assert list(result.details) == ["sales.orders"]
+        assert catalog.list_versions("sales/eu/invoices") == [1, 2]
+
+
 # ===========================================================================
 # TestCombinedPolicy
 # ===========================================================================
@@ -569,6 +638,102 @@ class TestChunkSweep:
         assert result.bytes_freed > 0
 
 
+# ===========================================================================
+# TestReachabilitySweep
+# ===========================================================================
+
+class TestReachabilitySweep:
+    """Reachability sweep (dry run, grace period, in-flight transactions) — 5 tests."""
+
+    def test_dry_run_reports_without_deleting(self, gc_env):
+        orphan = gc_env["store"].put(b"orphan chunk")
+        report = _rhizo.sweep_chunks(
+            gc_env["store"], gc_env["catalog"], grace_period_secs=0, dry_run=True
+        )
+        assert report.dry_run
+        assert report.collectable == [orphan]
+        assert report.bytes_collectable == len(b"orphan chunk")
+        assert report.chunks_deleted == 0
+        assert gc_env["store"].exists(orphan)
+
+    def test_recent_chunks_kept_within_grace_period(self, gc_env):
+        orphan = gc_env["store"].put(b"orphan chunk")
+        report = _rhizo.sweep_chunks(gc_env["store"], gc_env["catalog"])
+        assert report.chunks_within_grace == 1
+        assert gc_env["store"].exists(orphan)
+
+    def test_uncommitted_transaction_chunks_kept(self, gc_env):
+        tx_mgr = gc_env["tx_mgr"]
+        pending = gc_env["store"].put(b"pending write")
+        tx_id = tx_mgr.begin()
+        tx_mgr.add_write(tx_id, "orders", 1, [pending])
+
+        report = _rhizo.sweep_chunks(
+            gc_env["store"], gc_env["catalog"], tx_mgr, grace_period_secs=0
+        )
+        assert report.chunks_in_flight == 1
+        assert gc_env["store"].exists(pending)
+
+        tx_mgr.abort(tx_id)
+        report = _rhizo.sweep_chunks(
+            gc_env["store"], gc_env["catalog"], tx_mgr, grace_period_secs=0
+        )
+        assert report.chunks_deleted == 1
+        assert not gc_env["store"].exists(pending)
+
+    def test_negative_grace_period_rejected(self, gc_env):
+        with pytest.raises(ValueError):
+            _rhizo.sweep_chunks(gc_env["store"], gc_env["catalog"], grace_period_secs=-1)
+        with pytest.raises(ValueError):
+            GCPolicy(max_versions_per_table=1, chunk_grace_seconds=-1)
+
+    def test_database_sweep_chunks(self, temp_dir):
+        with rhizo.open(temp_dir) as db:
+            db.write("t1", pd.DataFrame({"x": list(range(100))}))
+            db.write("t1", pd.DataFrame({"x": list(range(100, 200))}))
+            db.gc(max_versions_per_table=1, chunk_grace_seconds=3600)
+
+            plan = db.sweep_chunks(dry_run=True, grace_seconds=0)
+            assert len(plan.collectable) >= 1
+            report = db.sweep_chunks(grace_seconds=0)
+            assert report.chunks_deleted == len(plan.collectable)
+            assert db.read("t1").num_rows == 100
+
+
+# ===========================================================================
+# TestGcPlan
+# ===========================================================================
+
+class TestGcPlan:
+    """Planning deletions to free a target number of bytes — 2 tests."""
+
+    def test_plan_picks_old_versions_and_executes(self, gc_env):
+        _write_version(gc_env, "t1", {"x": list(range(1000))})
+        _write_version(gc_env, "t1", {"x": list(range(1000, 2000))})
+        _write_version(gc_env, "t1", {"x": list(range(2000, 3000))})
+        store, catalog = gc_env["store"], gc_env["catalog"]
+
+        plan = _rhizo.gc_plan(store, catalog, 1, gc_env["branch_mgr"], grace_period_secs=0)
+        assert plan.meets_target
+        assert len(plan.steps) == 1
+        table, version, size, _ = plan.steps[0]
+        assert table == "t1" and version in (1, 2)
+        assert plan.reclaimable_bytes == size > 0
+        assert catalog.list_versions("t1") == [1, 2, 3]
+
+        report = _rhizo.execute_gc_plan(
+            store, catalog, plan, gc_env["branch_mgr"], grace_period_secs=0
+        )
+        assert report.bytes_collectable == size
+        assert version not in catalog.list_versions("t1")
+
+    def test_plan_never_deletes_latest_version(self, gc_env):
+        _write_version(gc_env, "t1", {"x": list(range(100))})
+        plan = _rhizo.gc_plan(gc_env["store"], gc_env["catalog"], 10**9, grace_period_secs=0)
+        assert not plan.meets_target
+        assert plan.steps == []
+
+
 # ===========================================================================
 # TestTwoPhaseIntegrity
 # ===========================================================================
@@ -3,6 +3,7 @@ Tests for PyO3 bindings (Rust↔Python bridge).
 
 Covers classes NOT already tested in test_rhizo.py:
 - BranchManager, Branch, BranchDiff
+- TagManager, Tag
 - TransactionManager, TransactionInfo, RecoveryReport
 - Changelog (ChangelogEntry, TableChange)
 - Merkle trees (MerkleConfig, MerkleTree, MerkleDiff, module functions)
@@ -10,8 +11,13 @@ Covers classes NOT already tested in test_rhizo.py:
 - Distributed types (NodeId, VectorClock, CausalOrder, LocalCommitProtocol)
 - Filter/Predicate (FilterOp, ScalarValue, PredicateFilter)
 - Parquet advanced (projection pushdown, predicate pushdown)
+- Arrow tables (write_arrow, read_arrow, write_patch, arrow_schema_hash)
+- Chunk scrubbing (scrub_chunks, ChunkScrubReport)
+- Version pruning (retention policies, prune_versions, VersionPruneReport)
+- Repository fixtures (build_fixture_repo, FixtureReport)
 """
 
+import json
 import os
 import tempfile
 import shutil
@@ -66,6 +72,69 @@ class TestPyBranchManager:
         v = bm.get_table_version("main", "users")
         assert v == 5
 
+    def test_repo_snapshots(self, temp_dir):
+        bm = _rhizo.PyBranchManager(os.path.join(temp_dir, "branches"))
+        snap = bm.update_heads("main", {"users": 3, "orders": 7})
+        assert str(snap) == f"repo@{snap.id}"
+        assert bm.get("main").snapshot_id == snap.id
+
+        bm.update_head("main", "users", 4)
+        later = bm.latest_snapshot("main")
+        assert later.id > snap.id
+        assert later.versions == {"users": 4, "orders": 7}
+
+        # Cited ids still resolve to the versions at the time
+        assert bm.snapshot(f"repo@{snap.id}").versions == {"users": 3, "orders": 7}
+        assert bm.snapshot(snap.id).branch == "main"
+        assert [s.id for s in bm.snapshots("main")][-2:] == [snap.id, later.id]
+        with pytest.raises(IOError):
+            bm.snapshot(later.id + 100)
+        with pytest.raises(ValueError):
+            bm.snapshot("head")
+
+    def test_branch_history_and_common_ancestor(self, temp_dir):
+        bm = _rhizo.PyBranchManager(os.path.join(temp_dir, "branches"))
+        base = bm.update_heads("main", {"users": 1})
+        bm.create("dev", "main", None)
+        bm.update_head("main", "users", 2)
+        bm.update_head("dev", "orders", 1)
+
+        latest = bm.history("dev", 1)[0]
+        assert latest.changes == [("orders", None, 1)]
+        assert len(bm.history("dev")) == 2
+        assert bm.common_ancestor("main", "dev").id == base.id
+
+    def test_catalog_schema_changes_need_migration(self, temp_dir):
+        catalog = _rhizo.PyCatalog(os.path.join(temp_dir, "catalog"))
+        catalog.commit_next_with_meta("users", ["u1"], None, "aa")
+        with pytest.raises(ValueError, match="schema migration"):
+            catalog.commit_next_with_meta("users", ["u2"], None, "bb")
+
+        migration = {"from_hash": "aa", "to_hash": "bb", "description": "retype id", "created_at": 0}
+        catalog.commit_next_with_meta("users", ["u2"], {"schema_migration": json.dumps(migration)}, "bb")
+        history = catalog.schema_history("users")
+        assert [(c.version, c.schema_hash) for c in history] == [(1, "aa"), (2, "bb")]
+        assert history[1].previous_hash == "aa"
+        assert history[1].migration == ("aa", "bb", "retype id")
+        assert history[1].fields is None
+
+    def test_catalog_get_version_as_of_snapshot(self, temp_dir):
+        branch_path = os.path.join(temp_dir, "branches")
+        bm = _rhizo.PyBranchManager(branch_path)
+        catalog = _rhizo.PyCatalog(os.path.join(temp_dir, "catalog"), branch_path=branch_path)
+        catalog.commit_next("users", ["u1"])
+        snap = bm.update_heads("main", {"users": 1})
+        catalog.commit_next("users", ["u2"])
+        catalog.commit_next("orders", ["o1"])
+        bm.update_heads("main", {"users": 2, "orders": 1})
+
+        tv = catalog.get_version_as_of("users", f"repo@{snap.id}")
+        assert (tv.version, tv.chunk_hashes) == (1, ["u1"])
+        with pytest.raises(IOError):
+            catalog.get_version_as_of("orders", snap.id)
+        with pytest.raises(ValueError):
+            _rhizo.PyCatalog(os.path.join(temp_dir, "catalog")).get_version_as_of("users", snap.id)
+
     def test_get_table_version_returns_none_for_missing(self, temp_dir):
         bm = _rhizo.PyBranchManager(os.path.join(temp_dir, "branches"))
         v = bm.get_table_version("main", "nonexistent")
@@ -111,6 +180,265 @@ class TestPyBranchManager:
         assert bm.get_table_version("main", "t1") == 2
         assert bm.get_table_version("main", "t2") == 1
 
+    def test_merge_deletes_or_archives_source(self, temp_dir):
+        bm = _rhizo.PyBranchManager(os.path.join(temp_dir, "branches"))
+        bm.create("a", "main", None)
+        bm.update_head("a", "t1", 1)
+        bm.create("b", "main", None)
+        bm.update_head("b", "t2", 1)
+
+        bm.merge("a", "main", delete_source=True)
+        assert "a" not in bm.list()
+        assert bm.get_table_version("main", "t1") == 1
+
+        archived = bm.merge_and_archive("b", "main")
+        assert archived.branch.name == "b"
+        assert archived.merged_into == "main"
+        assert "b" not in bm.list()
+        assert [a.branch.name for a in bm.archived()] == ["b"]
+
+        assert bm.unarchive("b").head == {"t2": 1}
+        assert "b" in bm.list()
+        assert bm.archived() == []
+
+    def test_repair_branch_rolls_back_dangling_heads(self, temp_dir):
+        bm = _rhizo.PyBranchManager(os.path.join(temp_dir, "branches"))
+        catalog = _rhizo.PyCatalog(os.path.join(temp_dir, "catalog"))
+        catalog.commit(_rhizo.PyTableVersion("users", 1, ["h1"]))
+        bm.update_head("main", "users", 3)
+
+        assert bm.dangling_heads("main", catalog) == [("users", 3, 1)]
+        report = bm.repair_branch("main", catalog, strategy="report")
+        assert not report.repaired and not report.is_clean
+        assert bm.get_table_version("main", "users") == 3
+
+        repair = bm.repair_branch("main", catalog)
+        assert repair.repaired and repair.dangling == [("users", 3, 1)]
+        assert bm.get_table_version("main", "users") == 1
+        assert bm.repair_branch("main", catalog).is_clean
+
+        with pytest.raises(ValueError, match="repair strategy"):
+            bm.repair_branch("main", catalog, strategy="drop")
+
+
+# ===================================================================
+# TAG MANAGER BINDINGS
+# ===================================================================
+
+class TestPyTagManager:
+    """Test PyTagManager and PyTag bindings."""
+
+    def _catalog(self, temp_dir):
+        catalog = _rhizo.PyCatalog(os.path.join(temp_dir, "catalog"))
+        for table, version in [("users", 1), ("users", 2), ("orders", 1)]:
+            catalog.commit(_rhizo.PyTableVersion(table, version, []))
+        return catalog
+
+    def test_tag_latest_and_resolve(self, temp_dir):
+        catalog = self._catalog(temp_dir)
+        tags = _rhizo.PyTagManager(os.path.join(temp_dir, "catalog"))
+
+        tag = tags.create("v2024-q3", message="Q3 release")
+        assert tag.versions == {"users": 2, "orders": 1}
+        assert tag.message == "Q3 release"
+
+        catalog.commit(_rhizo.PyTableVersion("users", 3, []))
+        assert tags.get("v2024-q3").versions["users"] == 2
+        assert tags.get_version("v2024-q3", "users").version == 2
+        assert [t.name for t in tags.list()] == ["v2024-q3"]
+
+        with pytest.raises(ValueError, match="Tag already exists"):
+            tags.create("v2024-q3")
+        with pytest.raises(ValueError, match="Version is tagged"):
+            catalog.delete_version("users", 2)
+
+        assert tags.delete("v2024-q3").name == "v2024-q3"
+        with pytest.raises(IOError, match="Tag not found"):
+            tags.get("v2024-q3")
+
+    def test_tag_branch_head_or_explicit_versions(self, temp_dir):
+        self._catalog(temp_dir)
+        branch_path = os.path.join(temp_dir, "branches")
+        bm = _rhizo.PyBranchManager(branch_path)
+        bm.update_head("main", "users", 1)
+        tags = _rhizo.PyTagManager(os.path.join(temp_dir, "catalog"), branch_path)
+
+        tag = tags.create("rc-1", branch="main")
+        assert tag.versions == {"users": 1}
+        assert tag.source_branch == "main"
+
+        assert tags.create("baseline", versions={"orders": 1}).source_branch is None
+        with pytest.raises(ValueError, match="not both"):
+            tags.create("both", versions={"orders": 1}, branch="main")
+        with pytest.raises(IOError, match="Version not found"):
+            tags.create("missing", versions={"orders": 7})
+
+
+class TestPyCatalogViews:
+    """Test PyCatalog view registry and PyView bindings."""
+
+    def test_create_get_and_drop_view(self, temp_dir):
+        catalog = _rhizo.PyCatalog(os.path.join(temp_dir, "catalog"))
+        catalog.commit(_rhizo.PyTableVersion("orders", 1, []))
+
+        view = catalog.create_view(
+            "big_orders", "SELECT * FROM orders WHERE total > 100", ["orders"],
+            materialize=True, description="Orders over 100",
+        )
+        assert view.materialize
+        assert catalog.get_view("big_orders").description == "Orders over 100"
+        assert catalog.view_exists("big_orders")
+        assert [v.name for v in catalog.list_views()] == ["big_orders"]
+        assert "big_orders" not in catalog.list_tables()
+
+        catalog.create_view("big_order_count", "SELECT COUNT(*) FROM big_orders", ["big_orders"])
+        assert catalog.view_base_tables("big_order_count") == ["orders"]
+        assert _rhizo.PyView.materialization_key({"users": 2, "orders": 4}) == "orders@4,users@2"
+
+        with pytest.raises(ValueError, match="View already exists"):
+            catalog.create_view("big_orders", "SELECT 1", ["orders"])
+        with pytest.raises(ValueError, match="Invalid view"):
+            catalog.create_view("big_orders", "SELECT 1", ["big_order_count"], replace=True)
+        with pytest.raises(IOError, match="Table not found"):
+            catalog.create_view("missing", "SELECT * FROM nope", ["nope"])
+
+        assert catalog.drop_view("big_order_count").name == "big_order_count"
+        with pytest.raises(IOError, match="View not found"):
+            catalog.get_view("big_order_count")
+
+
+# ===================================================================
+# CHUNK SCRUB BINDINGS
+# ===================================================================
+
+class TestPyChunkScrub:
+    """Test scrub_chunks and ChunkScrubReport."""
+
+    def test_scrub_reports_damage_and_impacted_branches(self, temp_dir):
+        chunks_dir = os.path.join(temp_dir, "chunks")
+        store = _rhizo.PyChunkStore(chunks_dir, integrity_journal=True)
+        catalog = _rhizo.PyCatalog(os.path.join(temp_dir, "catalog"))
+        branches = _rhizo.PyBranchManager(os.path.join(temp_dir, "branches"))
+
+        short = store.put(b"truncate me please")
+        rot = store.put(b"flip one of my bits")
+        catalog.commit(_rhizo.PyTableVersion("users", 1, [short]))
+        catalog.commit(_rhizo.PyTableVersion("users", 2, [rot]))
+        branches.update_head("main", "users", 2)
+
+        assert _rhizo.scrub_chunks(store, catalog, branches).is_clean
+
+        def chunk_path(h):
+            return os.path.join(chunks_dir, h[0:2], h[2:4], h)
+
+        with open(chunk_path(short), "r+b") as f:
+            f.truncate(4)
+        with open(chunk_path(rot), "r+b") as f:
+            f.seek(2)
+            byte = f.read(1)
+            f.seek(2)
+            f.write(bytes([byte[0] ^ 0x01]))
+
+        report = _rhizo.scrub_chunks(store, catalog, branches)
+        assert report.damaged == {short: "truncated", rot: "bit_rot"}
+        assert report.impacted_versions == {"users": [1, 2]}
+        assert report.impacted_branches == ["main"]
+
+        with pytest.raises(ValueError, match="integrity journal"):
+            _rhizo.scrub_chunks(_rhizo.PyChunkStore(chunks_dir), catalog)
+
+
+class TestPyVersionPruning:
+    """Test retention policies and prune_versions."""
+
+    def test_prune_applies_policies_and_keeps_branch_heads(self, temp_dir):
+        store = _rhizo.PyChunkStore(os.path.join(temp_dir, "chunks"))
+        catalog = _rhizo.PyCatalog(os.path.join(temp_dir, "catalog"))
+        branches = _rhizo.PyBranchManager(os.path.join(temp_dir, "branches"))
+
+        chunks = [store.put(f"users v{v}".encode()) for v in range(1, 5)]
+        for v, chunk in enumerate(chunks, start=1):
+            catalog.commit(_rhizo.PyTableVersion("users", v, [chunk]))
+        catalog.commit(_rhizo.PyTableVersion("orders", 1, []))
+        catalog.commit(_rhizo.PyTableVersion("orders", 2, []))
+        branches.update_head("main", "users", 1)
+
+        catalog.set_retention_policy("users", keep_last=1)
+        assert catalog.retention_policy("users") == {"keep_last": 1, "keep_newer_than_secs": None}
+        assert catalog.retention_policy("orders") is None
+
+        plan = _rhizo.prune_versions(catalog, branches, dry_run=True)
+        assert plan.dry_run
+        assert plan.versions_pruned == {"users": [2, 3]}
+        assert catalog.list_versions("users") == [1, 2, 3, 4]
+
+        report = _rhizo.prune_versions(catalog, branches)
+        assert report.versions_pruned == {"users": [2, 3]}
+        assert report.versions_protected == {"users": [1]}
+        assert report.unreferenced_chunks == sorted(chunks[1:3])
+        assert catalog.list_versions("users") == [1, 4]
+        assert catalog.list_versions("orders") == [1, 2]
+        assert [versions for versions, _ in catalog.prune_history("users")] == [[2, 3]]
+
+        report = _rhizo.prune_versions(catalog, branches, keep_last=1)
+        assert report.versions_pruned == {"orders": [1]}
+
+        catalog.set_retention_policy("users")
+        assert catalog.retention_policy("users") is None
+
+    def test_squash_versions_folds_history_into_base(self, temp_dir):
+        store = _rhizo.PyChunkStore(os.path.join(temp_dir, "chunks"))
+        catalog = _rhizo.PyCatalog(os.path.join(temp_dir, "catalog"))
+        branches = _rhizo.PyBranchManager(os.path.join(temp_dir, "branches"))
+
+        chunks = [store.put(f"users v{v}".encode()) for v in range(1, 5)]
+        for v, chunk in enumerate(chunks, start=1):
+            catalog.commit(_rhizo.PyTableVersion("users", v, [chunk]))
+        branches.update_head("main", "users", 2)
+
+        with pytest.raises(ValueError) as exc_info:
+            _rhizo.squash_versions(catalog, "users", 3, branches)
+        assert exc_info.value.code == "GC_VERSION_IN_USE"
+
+        branches.update_head("main", "users", 4)
+        report = _rhizo.squash_versions(catalog, "users", 3, branches)
+        assert report.versions_pruned == {"users": [1, 2]}
+        assert report.unreferenced_chunks == sorted(chunks[:2])
+        assert catalog.list_versions("users") == [3, 4]
+        assert [(base, versions) for base, versions, _ in catalog.squash_history("users")] == [(3, [1, 2])]
+        assert catalog.squashed_into("users", 1) == 3
+
+
+class TestPyFixtures:
+    """Test build_fixture_repo."""
+
+    def test_build_fixture_repo_is_deterministic(self, temp_dir):
+        def build(name, seed):
+            path = os.path.join(temp_dir, name)
+            report = _rhizo.build_fixture_repo(
+                path, tables=2, versions=3, branches=2, rows=20, chunks=2, seed=seed
+            )
+            catalog = _rhizo.PyCatalog(os.path.join(path, "catalog"))
+            versions = {
+                t: [catalog.get_version(t, v).chunk_hashes for v in catalog.list_versions(t)]
+                for t in report.tables
+            }
+            return path, report, versions
+
+        path, report, versions = build("a", seed=1)
+        assert report.tables == ["table_0", "table_1"]
+        assert report.branches == ["branch_1", "branch_2"]
+        assert report.versions_committed == 2 * 3 + 2 * 2
+
+        branches = _rhizo.PyBranchManager(os.path.join(path, "branches"))
+        assert branches.get("main").head == {"table_0": 3, "table_1": 3}
+
+        assert build("b", seed=1)[2] == versions
+        assert build("c", seed=2)[2] != versions
+
+        with pytest.raises(ValueError, match="Invalid fixture spec"):
+            _rhizo.build_fixture_repo(os.path.join(temp_dir, "d"), tables=0)
+
 
 # ===================================================================
 # TRANSACTION MANAGER BINDINGS
@@ -138,6 +466,95 @@ class TestPyTransactionManager:
         tm.commit(tx_id)
         assert tm.active_count() == 0
 
+    def test_transaction_context_manager(self, tx_env):
+        tm, catalog, _ = tx_env
+        with tm.transaction("main") as tx:
+            tx.set_metadata("author", "alice")
+            tx.add_write("users", 1, ["h1"])
+            assert tm.active_count() == 1
+        assert tx.state == "committed"
+        assert tm.active_count() == 0
+        assert catalog.get_version("users").chunk_hashes == ["h1"]
+        assert tm.get_changelog()[-1].tx_id == tx.tx_id
+
+        with pytest.raises(KeyError):
+            with tm.transaction("main") as failed:
+                failed.add_write("users", 2, ["h2"])
+                raise KeyError("boom")
+        assert failed.state == "aborted"
+        assert tm.active_count() == 0
+        assert catalog.get_version("users").version == 1
+
+        # Explicit commit inside the block; exit leaves it alone
+        with tm.transaction("main") as tx:
+            tx.add_write("users", 2, ["h1", "h2"])
+            tx.commit()
+            with pytest.raises(ValueError, match="already committed"):
+                tx.add_write("users", 3, ["h3"])
+        assert tx.state == "committed"
+
+    def test_transaction_context_manager_aborts_on_conflict(self, tx_env):
+        tm, *_ = tx_env
+        with tm.transaction("main") as tx:
+            tx.add_write("users", 1, ["base"])
+
+        other = tm.begin("main")
+        with pytest.raises(ValueError, match="conflict"):
+            with tm.transaction("main") as tx:
+                tx.add_write("users", 2, ["base", "mine"])
+                tm.add_write(other, "users", 2, ["base", "theirs"])
+                tm.commit(other)
+        assert tx.state == "aborted"
+        assert tm.active_count() == 0
+
+    def test_explain_commit_and_metrics_sink(self, temp_dir):
+        cat_dir = os.path.join(temp_dir, "catalog")
+        br_dir = os.path.join(temp_dir, "branches")
+        _rhizo.PyBranchManager(br_dir)
+        seen = []
+        tm = _rhizo.PyTransactionManager(
+            os.path.join(temp_dir, "tx"), cat_dir, br_dir, metrics_sink=seen.append
+        )
+        tx_id = tm.begin("main")
+        tm.add_write(tx_id, "users", 1, ["h1"])
+        tm.commit(tx_id)
+
+        timings = tm.explain_commit(tx_id)
+        assert timings.succeeded and timings.error is None
+        assert timings.branch == "main"
+        assert set(timings.phases_ms) == {
+            "queue", "conflict_check", "snapshot_validation",
+            "catalog_writes", "branch_updates", "log_persistence",
+        }
+        assert timings.total_ms >= sum(timings.phases_ms.values()) * 0.99
+        assert timings.slowest_phase in timings.phases_ms
+        assert [t.tx_id for t in seen] == [tx_id]
+        assert tm.explain_commit(tx_id + 100) is None
+
+    def test_transaction_hooks(self, tx_env):
+        tm, *_ = tx_env
+        events = []
+        commit_hook = tm.on_commit(
+            lambda info, changes: events.append(("commit", info.tx_id, [c.new_version for c in changes]))
+        )
+        tm.on_abort(lambda info, changes: events.append(("abort", info.tx_id, len(changes))))
+
+        def failing(info, changes):
+            raise RuntimeError("hook failure must not fail the commit")
+
+        tm.on_commit(failing)
+
+        tx1 = tm.begin("main")
+        tm.add_write(tx1, "users", 1, ["h1"])
+        tm.commit(tx1)
+        tx2 = tm.begin("main")
+        tm.add_write(tx2, "users", 2, ["h2"])
+        tm.abort(tx2, "changed my mind")
+
+        assert events == [("commit", tx1, [1]), ("abort", tx2, 1)]
+        assert tm.remove_hook(commit_hook)
+        assert not tm.remove_hook(commit_hook)
+
     def test_begin_and_abort(self, tx_env):
         tm, *_ = tx_env
         tx_id = tm.begin("main")
@@ -187,6 +604,200 @@ class TestPyTransactionManager:
         assert isinstance(issues, list)
         assert len(issues) == 0
 
+    def test_commit_on_conflict_analyze(self, temp_dir):
+        cat_dir = os.path.join(temp_dir, "catalog")
+        store = _rhizo.PyChunkStore(os.path.join(temp_dir, "chunks"))
+        registry = _rhizo.PyAlgebraicSchemaRegistry()
+        registry.register(_rhizo.PyTableAlgebraicSchema.all_additive("counters"))
+        tm = _rhizo.PyTransactionManager(
+            os.path.join(temp_dir, "tx"), cat_dir, schema_registry=registry, merge_store=store
+        )
+
+        def chunk(count):
+            batch = pa.record_batch({"id": [1], "count": [count]})
+            return store.put(_rhizo.PyParquetEncoder().encode(batch))
+
+        tx1 = tm.begin()
+        tx2 = tm.begin()
+        tm.add_write(tx1, "counters", 1, [chunk(1)])
+        tm.add_write(tx2, "counters", 1, [chunk(2)])
+        tm.commit(tx1)
+        tm.commit(tx2, on_conflict="analyze")
+
+        head = _rhizo.PyCatalog(cat_dir).get_version("counters")
+        assert head.version == 2
+        merged = _rhizo.PyParquetDecoder().decode(store.get(head.chunk_hashes[0]))
+        assert merged.column("count").to_pylist() == [3]
+        last = tm.get_changelog()[-1]
+        assert last.tx_id == tx2
+        assert last.is_auto_merge() is True
+        assert last.auto_merged == ["counters"]
+
+        with pytest.raises(ValueError, match="on_conflict"):
+            tm.commit(tm.begin(), on_conflict="merge")
+
+    def test_priority_commits(self, temp_dir):
+        cat_dir = os.path.join(temp_dir, "catalog")
+        tm = _rhizo.PyTransactionManager(
+            os.path.join(temp_dir, "tx"), cat_dir, priority_commits=True, max_bypass=4
+        )
+
+        for table, priority in [("events", "bulk"), ("sessions", "interactive")]:
+            tx = tm.begin()
+            tm.set_priority(tx, priority)
+            tm.add_write(tx, table, 1, [table])
+            tm.commit(tx)
+
+        stats = tm.commit_queue_stats()
+        assert stats.admitted == {"interactive": 1, "normal": 0, "bulk": 1}
+        assert sum(stats.waiting.values()) == 0
+        assert stats.starvation_grants == 0
+        assert stats.max_wait_ms["bulk"] >= 0.0
+
+        with pytest.raises(ValueError, match="priority"):
+            tm.set_priority(tm.begin(), "urgent")
+
+        plain = _rhizo.PyTransactionManager(os.path.join(temp_dir, "tx2"), cat_dir)
+        assert plain.commit_queue_stats() is None
+
+    def test_row_level_conflict_granularity(self, temp_dir):
+        tm = _rhizo.PyTransactionManager(
+            os.path.join(temp_dir, "tx"),
+            os.path.join(temp_dir, "catalog"),
+            epoch_config=_rhizo.PyEpochConfig(),
+            conflict_granularity="row",
+        )
+        assert tm.conflict_granularity == "row"
+
+        setup = tm.begin()
+        tm.add_write(setup, "users", 1, ["base"])
+        tm.commit(setup)
+
+        tx1, tx2, tx3 = tm.begin(), tm.begin(), tm.begin()
+        tm.add_write(tx1, "users", 2, ["base", "k1"], key_columns=["id"], keys=["1"])
+        tm.add_write(tx2, "users", 2, ["base", "k2"], key_columns=["id"], keys=["2"])
+        tm.add_write(tx3, "users", 2, ["base", "k1b"], key_columns=["id"], keys=["1"])
+        tm.commit(tx1)
+        tm.commit(tx2)
+        with pytest.raises(ValueError, match="conflict"):
+            tm.commit(tx3)
+
+        catalog = _rhizo.PyCatalog(os.path.join(temp_dir, "catalog"))
+        assert catalog.get_version("users").chunk_hashes == ["base", "k1", "k2"]
+
+        with pytest.raises(ValueError, match="granularity"):
+            _rhizo.PyTransactionManager(
+                os.path.join(temp_dir, "tx2"), os.path.join(temp_dir, "catalog"), conflict_granularity="cell"
+            )
+
+    def test_conflict_window_reads_evicted_commits(self, temp_dir):
+        # single_node keeps one commit in memory: tx2's commit evicts tx1's
+        def blind_writes(name, **kwargs):
+            tm = _rhizo.PyTransactionManager(
+                os.path.join(temp_dir, name, "tx"), os.path.join(temp_dir, name, "catalog"), **kwargs
+            )
+            tx1, tx2, tx3 = tm.begin(), tm.begin(), tm.begin()
+            tm.add_write(tx1, "users", 1, ["u1"])
+            tm.add_write(tx2, "orders", 1, ["o1"])
+            tm.add_write(tx3, "users", 1, ["u3"])
+            tm.commit(tx1)
+            tm.commit(tx2)
+            return tm, tx3
+
+        tm, tx3 = blind_writes("memory")
+        assert tm.conflict_window == "memory"
+        tm.commit(tx3)
+
+        tm, tx3 = blind_writes("lifetime", conflict_window="lifetime")
+        with pytest.raises(ValueError, match="conflict"):
+            tm.commit(tx3)
+
+        tm, _ = blind_writes("epochs", conflict_window="Epochs:2")
+        assert tm.conflict_window == "epochs:2"
+        with pytest.raises(ValueError, match="conflict window"):
+            blind_writes("bad", conflict_window="epochs:many")
+
+    def test_drop_and_rename_table(self, temp_dir):
+        cat_dir = os.path.join(temp_dir, "catalog")
+        tm = _rhizo.PyTransactionManager(
+            os.path.join(temp_dir, "tx"), cat_dir, os.path.join(temp_dir, "branches")
+        )
+        for table in ["users", "orders"]:
+            tx = tm.begin()
+            tm.add_write(tx, table, 1, [table])
+            tm.commit(tx)
+
+        assert tm.rename_table("users", "customers") == 1
+        with pytest.raises(OSError, match="already exists"):
+            tm.rename_table("orders", "customers")
+        dropped = tm.drop_table("orders")
+        assert [v.version for v in dropped] == [1]
+
+        catalog = _rhizo.PyCatalog(cat_dir)
+        assert catalog.list_tables() == ["customers"]
+        branches = _rhizo.PyBranchManager(os.path.join(temp_dir, "branches"))
+        assert branches.get("main").head == {"customers": 1}
+
+        rename, drop = [e.changes[0] for e in tm.get_changelog()[-2:]]
+        assert (rename.kind, rename.table_name, rename.renamed_to) == ("rename", "users", "customers")
+        assert drop.kind == "drop" and drop.is_drop() and not drop.is_new_table()
+
+    def test_drop_table_purges_unreachable_chunks(self, temp_dir):
+        store = _rhizo.PyChunkStore(os.path.join(temp_dir, "chunks"))
+        tm = _rhizo.PyTransactionManager(os.path.join(temp_dir, "tx"), os.path.join(temp_dir, "catalog"))
+        shared, own = store.put(b"shared"), store.put(b"orders only")
+        tx = tm.begin()
+        tm.add_write(tx, "orders", 1, [shared, own])
+        tm.add_write(tx, "users", 1, [shared])
+        tm.commit(tx)
+
+        tm.drop_table("orders", purge_store=store, grace_period_secs=0)
+        assert store.exists(shared) and not store.exists(own)
+
+    def test_merge_recorded_in_changelog(self, temp_dir):
+        tm = _rhizo.PyTransactionManager(
+            os.path.join(temp_dir, "tx"), os.path.join(temp_dir, "catalog"), os.path.join(temp_dir, "branches")
+        )
+        branches = _rhizo.PyBranchManager(os.path.join(temp_dir, "branches"))
+        tx = tm.begin()
+        tm.add_write(tx, "users", 1, ["u1"])
+        tm.commit(tx)
+        branches.create("dev", "main", None)
+        tx = tm.begin("dev")
+        tm.add_write(tx, "orders", 1, ["o1"])
+        tm.commit(tx)
+
+        merge = tm.merge("dev", "main")
+        assert (merge.strategy, merge.versions) == ("strict", {"orders": 1})
+        with pytest.raises(ValueError, match="strategy"):
+            tm.merge("dev", "main", "theirs-please")
+
+        entry = tm.get_changelog()[-1]
+        assert entry.is_branch_merge() and entry.merge.parents == merge.parents
+        change = entry.changes[0]
+        assert (change.kind, change.merged_from, change.chunk_hashes) == ("merge", "dev", ["o1"])
+        assert branches.history("main", 1)[0].parents == merge.parents
+
+    def test_savepoints(self, temp_dir):
+        tm = _rhizo.PyTransactionManager(
+            os.path.join(temp_dir, "tx"), os.path.join(temp_dir, "catalog")
+        )
+        tx = tm.begin()
+        tm.add_write(tx, "users", 1, ["a"])
+        tm.savepoint(tx, "sp1")
+        tm.add_write(tx, "orders", 1, ["b"])
+        assert tm.get_transaction(tx).written_tables == ["users", "orders"]
+
+        tm.rollback_to_savepoint(tx, "sp1")
+        info = tm.get_transaction(tx)
+        assert info.written_tables == ["users"]
+        assert info.savepoints == ["sp1"]
+
+        tm.release_savepoint(tx, "sp1")
+        with pytest.raises(ValueError, match="Savepoint not found"):
+            tm.rollback_to_savepoint(tx, "sp1")
+        tm.commit(tx)
+
     def test_latest_tx_id(self, tx_env):
         tm, *_ = tx_env
         # Initially None or some value
@@ -201,6 +812,46 @@ class TestPyTransactionManager:
 # CHANGELOG BINDINGS
 # ===================================================================
 
+
+class TestPyMicroBatcher:
+    """Test PyMicroBatcher, PyBatchTicket, PyBatchAck."""
+
+    def _manager(self, temp_dir):
+        cat_dir = os.path.join(temp_dir, "catalog")
+        br_dir = os.path.join(temp_dir, "branches")
+        _rhizo.PyBranchManager(br_dir)
+        epochs = _rhizo.PyEpochConfig()
+        tm = _rhizo.PyTransactionManager(os.path.join(temp_dir, "tx"), cat_dir, br_dir, epoch_config=epochs)
+        return tm, _rhizo.PyCatalog(cat_dir)
+
+    def test_writes_share_one_commit(self, temp_dir):
+        tm, catalog = self._manager(temp_dir)
+        batcher = _rhizo.PyMicroBatcher(tm, max_delay_ms=60_000)
+        tickets = [batcher.submit("main", "events", [h], rows=1) for h in ("a", "b", "c")]
+        assert tickets[0].wait(timeout=0.01) is None
+        batcher.flush()
+
+        acks = [t.wait() for t in tickets]
+        assert {a.tx_id for a in acks} == {acks[0].tx_id}
+        assert acks[0].writes == 3
+        assert acks[0].versions == {"events": 1}
+        assert catalog.get_version("events").chunk_hashes == ["a", "b", "c"]
+        assert tickets[0].wait().tx_id == acks[0].tx_id
+
+        stats = batcher.stats()
+        assert (stats.batches_committed, stats.writes_committed, stats.rows_committed) == (1, 3, 3)
+        batcher.close()
+        with pytest.raises(RuntimeError):
+            batcher.flush()
+
+    def test_failed_batch_raises(self, temp_dir):
+        tm, _ = self._manager(temp_dir)
+        batcher = _rhizo.PyMicroBatcher(tm, max_delay_ms=1)
+        with pytest.raises(Exception) as exc_info:
+            batcher.write("missing", "events", ["a"])
+        assert exc_info.value.context["branch"] == "missing"
+        assert batcher.stats().batches_failed == 1
+
 class TestPyChangelog:
     """Test PyChangelogEntry, PyTableChange bindings."""
 
@@ -492,6 +1143,146 @@ class TestPyAlgebraicTypes:
         result = _rhizo.algebraic_merge(op, a, b)
         assert result.is_set() is True
 
+    def test_algebraic_merge_or_set_add_wins(self):
+        op = _rhizo.PyOpType("or_set")
+        assert op.is_conflict_free() is True
+        base = _rhizo.PyAlgebraicValue.or_set(["x", "y"])
+        removed = _rhizo.algebraic_merge(op, base, base.or_set_removal("x"))
+        assert removed.or_set_elements() == ["y"]
+        # A concurrent re-add survives the removal
+        readded = _rhizo.algebraic_merge(op, base, _rhizo.PyAlgebraicValue.or_set(["x"]))
+        merged = _rhizo.algebraic_merge(op, removed, readded)
+        assert merged.or_set_elements() == ["x", "y"]
+        with pytest.raises(ValueError):
+            _rhizo.PyAlgebraicValue.integer(1).or_set_removal("x")
+
+    def test_algebraic_merge_pn_counter(self):
+        op = _rhizo.PyOpType("pn_counter")
+        assert op.is_semilattice() is True
+        east = _rhizo.PyAlgebraicValue.pn_counter("east", increments=10)
+        west = _rhizo.PyAlgebraicValue.pn_counter("west", increments=1, decrements=4)
+        merged = _rhizo.algebraic_merge(op, east, west)
+        assert merged.pn_counter_totals() == (7, 11, 4)
+        # Re-delivering a node's tallies does not double count
+        assert _rhizo.algebraic_merge(op, merged, west).pn_counter_totals() == (7, 11, 4)
+        assert str(merged) == "7"
+        with pytest.raises(ValueError):
+            _rhizo.PyAlgebraicValue.integer(1).pn_counter_totals()
+
+    def test_algebraic_merge_lww_register(self):
+        op = _rhizo.PyOpType("lww")
+        assert op.is_conflict_free() is True
+        old = _rhizo.PyAlgebraicValue.lww(_rhizo.PyAlgebraicValue.integer(1), 100, "a")
+        new = _rhizo.PyAlgebraicValue.lww(_rhizo.PyAlgebraicValue.integer(2), 200, "a")
+        tie = _rhizo.PyAlgebraicValue.lww(_rhizo.PyAlgebraicValue.integer(3), 200, "b")
+        assert str(_rhizo.algebraic_merge(op, new, old)) == "2"
+        assert str(_rhizo.algebraic_merge(op, old, new)) == "2"
+        value, timestamp, node = _rhizo.algebraic_merge(op, new, tie).lww_parts()
+        assert (str(value), timestamp, node) == ("3", 200, "b")
+        with pytest.raises(ValueError, match="conflict"):
+            forged = _rhizo.PyAlgebraicValue.lww(_rhizo.PyAlgebraicValue.integer(9), 200, "a")
+            _rhizo.algebraic_merge(op, new, forged)
+
+    def test_algebraic_merge_bounded_counter(self):
+        op = _rhizo.PyOpType("bounded_add")
+        assert op.is_conflict_free() is True
+        stock = _rhizo.PyAlgebraicValue.bounded_counter("east", 10, lower=0)
+        stock = stock.bounded_transfer("east", "west", decrement=4)
+        assert stock.bounded_state("west") == (10, 4, None)
+        east = stock.bounded_update("east", -6)
+        west = stock.bounded_update("west", -4)
+        with pytest.raises(ValueError, match="rights"):
+            west.bounded_update("west", -1)
+        merged = _rhizo.algebraic_merge(op, east, west)
+        assert str(merged) == "0"
+        assert merged.bounded_state("east") == (0, 0, None)
+        with pytest.raises(ValueError):
+            _rhizo.PyAlgebraicValue.bounded_counter("east", -1, lower=0)
+
+        schema = _rhizo.PyTableAlgebraicSchema("stock")
+        schema.add_bounded_column("quantity", lower=0)
+        assert str(schema.get_op_type("quantity")) == "BOUNDED_ADD"
+        assert schema.get_bounds("quantity") == (0, None)
+        assert schema.get_bounds("other") is None
+
+    def test_custom_merge_registration(self):
+        def longest(a, b):
+            # Longer string set wins; ties go to the larger sorted contents
+            return max(a, b, key=lambda v: (len(v.or_set_elements()), v.or_set_elements()))
+
+        samples = [_rhizo.PyAlgebraicValue.or_set(s) for s in (["a"], ["b", "c"], ["d"])]
+        _rhizo.register_custom_merge("test_longest", longest, samples)
+        try:
+            op = _rhizo.PyOpType.custom("test_longest")
+            assert op.is_conflict_free() is True
+            assert str(op) == "CUSTOM(test_longest)"
+            assert "test_longest" in _rhizo.custom_merge_names()
+            merged = _rhizo.algebraic_merge(op, samples[0], samples[1])
+            assert merged.or_set_elements() == ["b", "c"]
+            with pytest.raises(ValueError, match="conflict"):
+                _rhizo.algebraic_merge(op, samples[0], _rhizo.PyAlgebraicValue.integer(1))
+        finally:
+            assert _rhizo.unregister_custom_merge("test_longest") is True
+        assert _rhizo.PyOpType.custom("test_longest").is_conflict_free() is False
+
+        with pytest.raises(ValueError, match="not commutative"):
+            _rhizo.register_custom_merge("test_first", lambda a, b: a, samples)
+        assert "test_first" not in _rhizo.custom_merge_names()
+
+    def test_verify_algebraic_laws(self):
+        assert _rhizo.verify_algebraic_laws(_rhizo.PyOpType("or_set")) == []
+        assert _rhizo.verify_algebraic_laws(_rhizo.PyOpType.map(_rhizo.PyOpType("max")), count=10, seed=3) == []
+
+        # Float sums round, so regrouping them changes the result
+        floats = [_rhizo.PyAlgebraicValue.float(v) for v in (0.1, 0.2, 0.3)]
+        violations = _rhizo.verify_algebraic_laws(_rhizo.PyOpType("add"), floats)
+        assert violations and all(v.startswith("associativity fails") for v in violations)
+
+        _rhizo.register_custom_merge("test_first", lambda a, b: a)
+        try:
+            violations = _rhizo.verify_algebraic_laws(_rhizo.PyOpType.custom("test_first"), count=5)
+            assert violations[0].startswith("commutativity fails")
+        finally:
+            _rhizo.unregister_custom_merge("test_first")
+
+    def test_algebraic_merge_decimal(self):
+        from decimal import Decimal
+
+        V = _rhizo.PyAlgebraicValue
+        a = V(Decimal("0.10"))
+        assert a.type_name() == "Decimal" and a.is_numeric() is True
+        merged = _rhizo.algebraic_merge(_rhizo.PyOpType("add"), a, V.decimal("0.2"))
+        assert merged.to_decimal() == Decimal("0.30")
+        assert str(merged) == "0.30"
+        peak = _rhizo.algebraic_merge(_rhizo.PyOpType("max"), V.decimal(Decimal("1E+3")), V.integer(999))
+        assert peak.to_decimal() == Decimal("1000")
+        assert V.integer(5).to_decimal() == Decimal(5)
+
+        with pytest.raises(ValueError, match="Type mismatch"):
+            _rhizo.algebraic_merge(_rhizo.PyOpType("add"), a, V.float(0.5))
+        with pytest.raises(ValueError, match="Invalid decimal"):
+            V.decimal(Decimal("NaN"))
+        with pytest.raises(ValueError, match="Expected Decimal"):
+            V.float(1.5).to_decimal()
+
+    def test_algebraic_merge_map(self):
+        V = _rhizo.PyAlgebraicValue
+        op = _rhizo.PyOpType.map(_rhizo.PyOpType("add"))
+        assert op.is_conflict_free() is True
+        assert str(op) == "MAP(ADD)"
+        a = V.map({"alice": V.integer(3), "bob": V.integer(1)})
+        b = V.map({"bob": V.integer(4)})
+        merged = _rhizo.algebraic_merge(op, a, b).map_entries()
+        assert {k: str(v) for k, v in merged.items()} == {"alice": "3", "bob": "5"}
+        assert merged["bob"].type_name() == "Integer"
+
+        overwrite = _rhizo.PyOpType.map(_rhizo.PyOpType("overwrite"))
+        assert overwrite.is_conflict_free() is False
+        with pytest.raises(ValueError, match="conflict"):
+            _rhizo.algebraic_merge(overwrite, a, b)
+        with pytest.raises(ValueError, match="Expected Map"):
+            V.integer(1).map_entries()
+
     # --- PyTableAlgebraicSchema ---
 
     def test_table_schema_creation(self):
@@ -565,6 +1356,65 @@ class TestPyAlgebraicTypes:
         reg = _rhizo.PyAlgebraicSchemaRegistry()
         assert isinstance(repr(reg), str)
 
+    def test_registry_save_load_and_catalog_annotations(self, temp_dir):
+        reg = _rhizo.PyAlgebraicSchemaRegistry()
+        reg.register(_rhizo.PyTableAlgebraicSchema.all_additive("counters"))
+        path = os.path.join(temp_dir, "schemas.json")
+        reg.save(path)
+        loaded = _rhizo.PyAlgebraicSchemaRegistry.load(path)
+        assert loaded.get_op_type("counters", "views").is_abelian() is True
+
+        catalog = _rhizo.PyCatalog(os.path.join(temp_dir, "catalog"))
+        catalog.commit_next("counters", ["c1"])
+        assert catalog.algebraic_schema("counters") is None
+        assert catalog.set_algebraic_schema("counters", loaded.get("counters")) == 2
+        catalog.commit_next("counters", ["c3"])
+        assert catalog.algebraic_schema("counters", 3).table == "counters"
+        assert catalog.algebraic_registry({"counters": 3}).has_table("counters") is True
+        assert catalog.algebraic_registry({"counters": 1}).tables() == []
+
+    # --- merge_branch_data ---
+
+    def test_merge_branch_data_combines_rows(self, temp_dir):
+        store = _rhizo.PyChunkStore(os.path.join(temp_dir, "chunks"))
+        catalog = _rhizo.PyCatalog(os.path.join(temp_dir, "catalog"))
+        branches = _rhizo.PyBranchManager(os.path.join(temp_dir, "branches"))
+        encoder = _rhizo.PyParquetEncoder()
+        reg = _rhizo.PyAlgebraicSchemaRegistry()
+        reg.register(_rhizo.PyTableAlgebraicSchema.all_additive("counters"))
+
+        def commit(version, ids, views):
+            batch = pa.RecordBatch.from_pydict({"page": ids, "views": views})
+            chunk = store.put(encoder.encode(batch))
+            catalog.commit(_rhizo.PyTableVersion("counters", version, [chunk]))
+
+        commit(1, ["home", "about"], [10, 20])
+        branches.update_head("main", "counters", 1)
+        branches.create("feature", "main")
+        commit(2, ["home", "about"], [15, 20])
+        branches.update_head("main", "counters", 2)
+        commit(3, ["home", "about", "blog"], [12, 21, 5])
+        branches.update_head("feature", "counters", 3)
+
+        simulation = _rhizo.simulate_branch_merge(
+            reg, catalog, store, branches, "feature", "main", keys={"counters": ["page"]}
+        )
+        assert simulation.outcome.success
+        assert simulation.heads == {"counters": 4}
+        assert simulation.tables[0].rows_combined == 1
+        assert branches.get("main").head["counters"] == 2
+
+        outcome = _rhizo.merge_branch_data(
+            reg, catalog, store, branches, "feature", "main", keys={"counters": ["page"]}
+        )
+        assert outcome.success
+        assert outcome.algebraically_merged == ["counters"]
+
+        head = branches.get("main").head["counters"]
+        chunks = catalog.get_version("counters", head).chunk_hashes
+        merged = _rhizo.PyParquetDecoder().decode(store.get(chunks[0])).to_pydict()
+        assert dict(zip(merged["page"], merged["views"])) == {"home": 17, "about": 21, "blog": 5}
+
 
 # ===================================================================
 # DISTRIBUTED TYPE BINDINGS
@@ -963,3 +1813,249 @@ class TestPyParquetAdvanced:
         assert isinstance(pruned, int)
         assert isinstance(kept, int)
         assert total == pruned + kept
+
+
+class TestPyArrowTables:
+    """Test write_arrow / read_arrow / write_patch round trips."""
+
+    def test_round_trip_with_schema_hash(self, temp_dir):
+        store = _rhizo.PyChunkStore(os.path.join(temp_dir, "chunks"))
+        catalog = _rhizo.PyCatalog(os.path.join(temp_dir, "catalog"))
+        table = pa.table({"id": [1, 2, 3, 4, 5], "name": ["a", "b", "c", "d", "e"]})
+
+        version = _rhizo.write_arrow(
+            store, catalog, "users", table, max_rows_per_chunk=2, metadata={"source": "test"}
+        )
+        assert version.version == 1
+        assert len(version.chunk_hashes) == 3
+        assert version.schema_hash == _rhizo.arrow_schema_hash(table)
+        assert version.metadata["source"] == "test"
+
+        batches = _rhizo.read_arrow(store, catalog, "users")
+        assert pa.Table.from_batches(batches).equals(table)
+
+        _rhizo.write_arrow(store, catalog, "users", table.to_batches()[0].slice(0, 1))
+        assert _rhizo.read_arrow(store, catalog, "users", 2)[0].num_rows == 1
+
+        with pytest.raises(ValueError):
+            _rhizo.write_arrow(store, catalog, "users", [])
+
+    def test_write_patch(self, temp_dir):
+        store = _rhizo.PyChunkStore(os.path.join(temp_dir, "chunks"))
+        catalog = _rhizo.PyCatalog(os.path.join(temp_dir, "catalog"))
+        table = pa.table({"id": [1, 2, 3, 4], "name": ["a", "b", "c", "d"]})
+        first = _rhizo.write_arrow(store, catalog, "users", table, max_rows_per_chunk=2)
+
+        version = _rhizo.write_patch(
+            store, catalog, "users",
+            delete_rows=[0],
+            update_rows=[1],
+            updates=pa.table({"id": [20], "name": ["B"]}),
+            inserts=pa.table({"id": [5], "name": ["e"]}),
+        )
+        assert version.version == 2
+        assert version.schema_hash == first.schema_hash
+        assert version.chunk_hashes[1] != first.chunk_hashes[1]
+        patched = pa.Table.from_batches(_rhizo.read_arrow(store, catalog, "users"))
+        assert patched.column("id").to_pylist() == [20, 3, 4, 5]
+
+        with pytest.raises(ValueError):
+            _rhizo.write_patch(store, catalog, "users", delete_rows=[10])
+
+    def test_parquet_import_export(self, temp_dir):
+        import pyarrow.parquet as pq
+
+        store = _rhizo.PyChunkStore(os.path.join(temp_dir, "chunks"))
+        catalog = _rhizo.PyCatalog(os.path.join(temp_dir, "catalog"))
+        source = os.path.join(temp_dir, "users.parquet")
+        table = pa.table({"id": [1, 2, 3, 4, 5], "name": ["a", "b", "c", "d", "e"]})
+        pq.write_table(table, source)
+
+        version = _rhizo.import_parquet(store, catalog, source, "users", max_rows_per_chunk=2)
+        assert version.version == 1
+        assert len(version.chunk_hashes) == 3
+        assert pa.Table.from_batches(_rhizo.read_arrow(store, catalog, "users")).equals(table)
+
+        target = os.path.join(temp_dir, "export.parquet")
+        exported = _rhizo.export_parquet(store, catalog, "users", target)
+        assert exported.version == 1
+        assert pq.read_table(target).equals(table)
+
+        with pytest.raises(ValueError):
+            _rhizo.import_parquet(store, catalog, source, "users", tx_id=1)
+
+    def test_csv_and_jsonl_import(self, temp_dir):
+        store = _rhizo.PyChunkStore(os.path.join(temp_dir, "chunks"))
+        catalog = _rhizo.PyCatalog(os.path.join(temp_dir, "catalog"))
+        csv = os.path.join(temp_dir, "users.csv")
+        with open(csv, "w") as f:
+            f.write("id;name\n1;a\n2;b\n3;c\n")
+
+        version = _rhizo.import_csv(store, catalog, csv, "users", delimiter=";", max_rows_per_chunk=2)
+        assert version.version == 1
+        assert len(version.chunk_hashes) == 2
+        table = pa.Table.from_batches(_rhizo.read_arrow(store, catalog, "users"))
+        assert table.schema.field("id").type == pa.int64()
+        assert table.column("name").to_pylist() == ["a", "b", "c"]
+
+        jsonl = os.path.join(temp_dir, "users.jsonl")
+        with open(jsonl, "w") as f:
+            f.write('{"id": 4, "name": "d"}\n')
+        schema = pa.schema([("id", pa.float64()), ("name", pa.string())])
+        version = _rhizo.import_jsonl(store, catalog, jsonl, "users", schema=schema)
+        assert version.version == 2
+        batches = _rhizo.read_arrow(store, catalog, "users", 2)
+        assert batches[0].schema.field("id").type == pa.float64()
+
+        with pytest.raises(ValueError):
+            _rhizo.import_csv(store, catalog, csv, "users", delimiter=";;")
+
+    def test_export_tables(self, temp_dir):
+        import json
+
+        import pyarrow.parquet as pq
+
+        store = _rhizo.PyChunkStore(os.path.join(temp_dir, "chunks"))
+        catalog = _rhizo.PyCatalog(os.path.join(temp_dir, "catalog"))
+        users = pa.table({"id": [1, 2, 3], "name": ["a", "b", "c"]})
+        _rhizo.write_arrow(store, catalog, "users", users)
+        _rhizo.write_arrow(store, catalog, "orders", pa.table({"id": [10]}))
+        manager = _rhizo.PyTransactionManager(
+            os.path.join(temp_dir, "tx"), os.path.join(temp_dir, "catalog")
+        )
+
+        dest = os.path.join(temp_dir, "export")
+        manifest = _rhizo.export_tables(store, manager, dest)
+        assert manifest.tables == {"users": 1, "orders": 1}
+        assert manifest.rows == {"users": 3, "orders": 1}
+        assert manifest.event_tx_id is None
+        assert pq.read_table(os.path.join(dest, manifest.files["users"])).equals(users)
+        with open(os.path.join(dest, "_manifest.json")) as f:
+            assert json.load(f)["tables"]["users"]["version"] == 1
+
+        manifest = _rhizo.export_tables(
+            store, manager, dest, tables=["users"], format="csv", record_event=True
+        )
+        assert manifest.files == {"users": "users.csv"}
+        assert manifest.event_tx_id is not None
+
+        with pytest.raises(ValueError):
+            _rhizo.export_tables(store, manager, dest, format="orc")
+
+    def test_redact(self, temp_dir):
+        store = _rhizo.PyChunkStore(os.path.join(temp_dir, "chunks"))
+        catalog = _rhizo.PyCatalog(os.path.join(temp_dir, "catalog"))
+        _rhizo.write_arrow(store, catalog, "users", pa.table({"id": [1, 2, 3]}))
+        _rhizo.write_arrow(store, catalog, "users", pa.table({"id": [4]}))
+        _rhizo.write_arrow(store, catalog, "users", pa.table({"id": [2, 5]}))
+
+        removed = _rhizo.redact(
+            store, catalog, "users", _rhizo.PyPredicateFilter("id", "eq", 2), reason="erasure"
+        )
+        assert removed == {1: 1, 3: 1}
+        assert catalog.list_versions("users") == [1, 2, 3]
+        batches = _rhizo.read_arrow(store, catalog, "users", 1)
+        assert pa.Table.from_batches(batches).to_pydict() == {"id": [1, 3]}
+
+        [(predicate, reason, versions, rows, _)] = catalog.redaction_history("users")
+        assert (predicate, reason, versions, rows) == ("id = 2", "erasure", [1, 3], 2)
+
+        with pytest.raises(ValueError):
+            _rhizo.redact(store, catalog, "users", [])
+
+    def test_query(self, temp_dir):
+        store = _rhizo.PyChunkStore(os.path.join(temp_dir, "chunks"))
+        catalog = _rhizo.PyCatalog(os.path.join(temp_dir, "catalog"))
+        _rhizo.write_arrow(store, catalog, "users", pa.table({"id": [1, 2, 3], "age": [15, 30, 45]}))
+        _rhizo.write_arrow(store, catalog, "users", pa.table({"id": [4, 5], "age": [20, 50]}))
+
+        batches = _rhizo.query(
+            store, catalog, "users", version=1, columns=["id"],
+            filter=_rhizo.PyPredicateFilter("age", "gt", 21),
+        )
+        assert pa.Table.from_batches(batches).to_pydict() == {"id": [2, 3]}
+
+        batches = _rhizo.query(store, catalog, "users", filter=[
+            _rhizo.PyPredicateFilter("age", "gt", 21),
+            _rhizo.PyPredicateFilter("age", "lt", 100),
+        ])
+        assert pa.Table.from_batches(batches).to_pydict() == {"id": [5], "age": [50]}
+
+        with pytest.raises(ValueError) as exc_info:
+            _rhizo.query(store, catalog, "users", columns=["missing"])
+        assert exc_info.value.code == "QUERY_UNKNOWN_COLUMN"
+        with pytest.raises(ValueError):
+            _rhizo.query(store, catalog, "users", branch="main")
+
+
+class TestPyErrorCodes:
+    """Exceptions carry stable codes, structured context and retryability."""
+
+    def test_catalog_error_code(self, temp_dir):
+        catalog = _rhizo.PyCatalog(os.path.join(temp_dir, "catalog"))
+        with pytest.raises(IOError) as exc_info:
+            catalog.get_version("missing", 1)
+        assert exc_info.value.code == "CATALOG_TABLE_NOT_FOUND"
+        assert exc_info.value.category == "not_found"
+        assert exc_info.value.context == {"table": "missing"}
+        assert exc_info.value.retryable is False
+        assert exc_info.value.retry_after is None
+
+    def test_chunk_error_code(self, temp_dir):
+        store = _rhizo.PyChunkStore(os.path.join(temp_dir, "chunks"))
+        with pytest.raises(ValueError) as exc_info:
+            store.get("not-a-hash")
+        assert exc_info.value.code == "CHUNK_INVALID_HASH"
+        assert exc_info.value.category == "invalid_argument"
+        assert exc_info.value.context == {}
+
+    def test_write_conflict_is_retryable(self, temp_dir):
+        store = _rhizo.PyChunkStore(os.path.join(temp_dir, "chunks"))
+        tx_mgr = _rhizo.PyTransactionManager(
+            os.path.join(temp_dir, "tx"), os.path.join(temp_dir, "catalog")
+        )
+        chunk = store.put(b"row")
+        first, second = tx_mgr.begin(), tx_mgr.begin()
+        tx_mgr.add_write(first, "orders", 1, [chunk])
+        tx_mgr.add_write(second, "orders", 1, [chunk])
+        tx_mgr.commit(first)
+        with pytest.raises(ValueError) as exc_info:
+            tx_mgr.commit(second)
+        assert exc_info.value.code == "TX_WRITE_CONFLICT"
+        assert exc_info.value.retryable is True
+        assert exc_info.value.retry_after == 0.0
+
+    def test_typed_exceptions(self, temp_dir):
+        tx_mgr = _rhizo.PyTransactionManager(
+            os.path.join(temp_dir, "tx"),
+            os.path.join(temp_dir, "catalog"),
+            os.path.join(temp_dir, "branches"),
+        )
+        first, second = tx_mgr.begin(), tx_mgr.begin()
+        tx_mgr.add_write(first, "orders", 1, ["a"])
+        tx_mgr.add_write(second, "orders", 1, ["b"])
+        tx_mgr.commit(first)
+        with pytest.raises(_rhizo.WriteConflictError) as exc_info:
+            tx_mgr.commit(second)
+        assert isinstance(exc_info.value, _rhizo.ConflictError)
+        assert not isinstance(exc_info.value, _rhizo.SnapshotConflictError)
+        assert exc_info.value.code == "TX_WRITE_CONFLICT"
+
+        branches = _rhizo.PyBranchManager(os.path.join(temp_dir, "branches"))
+        with pytest.raises(_rhizo.BranchNotFoundError) as exc_info:
+            branches.get("missing")
+        assert isinstance(exc_info.value, (_rhizo.NotFoundError, IOError))
+        assert exc_info.value.context == {"branch": "missing"}
+
+        with pytest.raises(_rhizo.NotFoundError):
+            _rhizo.PyCatalog(os.path.join(temp_dir, "catalog")).get_version("missing", 1)
+        with pytest.raises(_rhizo.ChunkNotFoundError):
+            _rhizo.PyChunkStore(os.path.join(temp_dir, "chunks")).get("ab" * 32)
+
+        for cls in [_rhizo.ConflictError, _rhizo.NotFoundError, _rhizo.IntegrityError]:
+            assert issubclass(cls, _rhizo.RhizoError)
+        assert issubclass(_rhizo.HashMismatchError, ValueError)
+
+        import rhizo
+        assert rhizo.RhizoError is _rhizo.RhizoError
+        assert issubclass(rhizo.TableNotFoundError, _rhizo.NotFoundError)
@@ -134,6 +134,25 @@ class TestTableWriter:
         assert result.chunk_count > 1
         assert result.total_rows == len(large_dataframe)
 
+    def test_write_delta_chunks(self, temp_storage, large_dataframe):
+        """Delta chunk writes read back like full ones."""
+        store, catalog, _ = temp_storage
+        writer = TableWriter(store, catalog, chunk_size_rows=10000, delta_chunks=True)
+
+        result1 = writer.write("large_table", large_dataframe)
+        df2 = large_dataframe.copy()
+        df2.loc[0, "category"] = "Z"
+        result2 = writer.write("large_table", df2)
+
+        # Unchanged chunks deduplicate as before
+        assert result2.chunk_hashes[1:] == result1.chunk_hashes[1:]
+        for h in result2.chunk_hashes:
+            assert store.get_verified(h)
+
+        reader = TableReader(store, catalog)
+        assert reader.read_pandas("large_table").equals(df2)
+        assert reader.read_pandas("large_table", version=1).equals(large_dataframe)
+
     def test_write_empty_fails(self, temp_storage):
         """Test that writing empty DataFrame raises error."""
         store, catalog, _ = temp_storage
@@ -479,6 +498,51 @@ class TestQueryEngine:
             result = engine.query("SELECT * FROM users")
             assert result.row_count == 5
 
+    def test_view_reads_like_a_table(self, temp_storage, sample_dataframe):
+        """Views resolve at query time, including time travel and nesting."""
+        store, catalog, _ = temp_storage
+        engine = QueryEngine(store, catalog, enable_olap=False)
+
+        engine.write_table("users", sample_dataframe)
+        view = engine.create_view("adults", "SELECT * FROM users WHERE age > 30")
+        assert view.sources == ["users"]
+        assert engine.list_views() == ["adults"]
+        assert engine.query("SELECT * FROM adults").row_count == 2
+
+        engine.write_table("users", sample_dataframe.head(2))
+        assert engine.query("SELECT * FROM adults").row_count == 0
+        assert engine.query("SELECT * FROM adults", versions={"users": 1}).row_count == 2
+
+        engine.create_view("adult_count", "SELECT COUNT(*) AS n FROM adults")
+        assert catalog.view_base_tables("adult_count") == ["users"]
+        result = engine.query("SELECT n FROM adult_count", versions={"users": 1})
+        assert result.to_dict() == [{"n": 2}]
+
+        with pytest.raises(ValueError, match="Cannot write to a view"):
+            engine.write_table("adults", sample_dataframe)
+
+        engine.drop_view("adult_count")
+        assert engine.list_views() == ["adults"]
+
+    def test_materialized_view_cached_per_source_versions(self, temp_storage, sample_dataframe):
+        """Materialized views recompute only when a base table changes."""
+        store, catalog, _ = temp_storage
+        engine = QueryEngine(store, catalog, enable_olap=False)
+
+        engine.write_table("users", sample_dataframe)
+        engine.create_view("adults", "SELECT * FROM users WHERE age > 30", materialize=True)
+
+        assert engine.query("SELECT * FROM adults").row_count == 2
+        assert list(engine._view_cache) == [("adults", "users@1")]
+
+        engine.write_table("users", sample_dataframe.head(2))
+        assert engine.query("SELECT * FROM adults").row_count == 0
+        assert sorted(engine._view_cache) == [("adults", "users@1"), ("adults", "users@2")]
+
+        engine.create_view("adults", "SELECT * FROM users", materialize=True, replace=True)
+        assert engine._view_cache == {}
+        assert engine.query("SELECT * FROM adults").row_count == 2
+
 
 class TestParallelParsing:
     """Tests for parallel Parquet parsing (Phase 3)."""
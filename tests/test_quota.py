@@ -0,0 +1,122 @@
+"""
+Tests for storage quotas with soft warnings.
+
+Run with: pytest tests/test_quota.py -v
+"""
+
+import os
+import shutil
+import tempfile
+
+import pandas as pd
+import pytest
+
+import _rhizo
+import rhizo
+from rhizo.exceptions import QuotaExceededError
+from rhizo.quota import QUOTA_TOPIC, Quota, QuotaEnforcer
+
+
+@pytest.fixture
+def temp_dir():
+    d = tempfile.mkdtemp(prefix="rhizo_quota_test_")
+    yield d
+    shutil.rmtree(d, ignore_errors=True)
+
+
+@pytest.fixture
+def storage(temp_dir):
+    store = _rhizo.PyChunkStore(os.path.join(temp_dir, "chunks"))
+    catalog = _rhizo.PyCatalog(os.path.join(temp_dir, "catalog"))
+    return store, catalog
+
+
+def _write(store, catalog, enforcer, table, payload):
+    """Store one chunk, charge it, then commit (mirrors TableWriter)."""
+    h = store.put(payload)
+    enforcer.charge(table, {h: len(payload)})
+    catalog.commit_next(table, [h])
+    return h
+
+
+class TestQuotaPolicy:
+
+    def test_validation(self):
+        with pytest.raises(ValueError):
+            Quota(0)
+        with pytest.raises(ValueError):
+            Quota(100, warn_at=0.0)
+        with pytest.raises(ValueError):
+            Quota(100, warn_at=1.5)
+
+    def test_covers(self):
+        assert Quota(100).covers("anything")
+        scoped = Quota(100, tables=["Users"])
+        assert scoped.covers("users")
+        assert not scoped.covers("orders")
+        assert scoped.label == "users"
+
+
+class TestQuotaEnforcer:
+
+    def test_warning_then_exceeded(self, storage):
+        store, catalog = storage
+        enforcer = QuotaEnforcer(catalog, store, [Quota(100, warn_at=0.5, name="q")])
+        events = []
+        enforcer.bus.subscribe(QUOTA_TOPIC, events.append)
+
+        _write(store, catalog, enforcer, "t", b"a" * 40)
+        assert events == []
+
+        _write(store, catalog, enforcer, "t", b"b" * 20)
+        assert [e.level for e in events] == ["warning"]
+        assert events[0].used_bytes == 60
+
+        # Still above the threshold: no repeated warning
+        _write(store, catalog, enforcer, "t", b"c" * 10)
+        assert len(events) == 1
+
+        with pytest.raises(QuotaExceededError) as exc:
+            _write(store, catalog, enforcer, "t", b"d" * 40)
+        assert exc.value.limit_bytes == 100
+        assert events[-1].level == "exceeded"
+        assert enforcer.status()[0].used_bytes == 70
+
+    def test_deduplicated_chunks_not_double_charged(self, storage):
+        store, catalog = storage
+        enforcer = QuotaEnforcer(catalog, store, [Quota(100)])
+
+        for _ in range(5):
+            _write(store, catalog, enforcer, "t", b"x" * 60)
+        assert enforcer.status()[0].used_bytes == 60
+
+    def test_usage_loaded_from_catalog(self, storage):
+        store, catalog = storage
+        h = store.put(b"y" * 80)
+        catalog.commit_next("existing", [h])
+
+        enforcer = QuotaEnforcer(catalog, store, [Quota(100, tables=["existing"])])
+        assert enforcer.status()[0].used_bytes == 80
+        with pytest.raises(QuotaExceededError):
+            _write(store, catalog, enforcer, "existing", b"z" * 30)
+
+        # Other tables are not covered by the scoped quota
+        _write(store, catalog, enforcer, "other", b"z" * 30)
+
+
+class TestDatabaseQuotas:
+
+    def test_write_refused_over_quota(self, temp_dir):
+        with rhizo.open(temp_dir, quotas=[Quota(10**9, name="total")]) as db:
+            db.write("t", pd.DataFrame({"x": list(range(100))}))
+            used = db.quotas.status()[0].used_bytes
+            assert used > 0
+
+        with rhizo.open(temp_dir, quotas=[Quota(used + 1, name="total")]) as db:
+            with pytest.raises(QuotaExceededError):
+                db.write("t", pd.DataFrame({"x": list(range(1000))}))
+            assert db.versions("t") == [1]
+
+    def test_no_quotas_by_default(self, temp_dir):
+        with rhizo.open(temp_dir) as db:
+            assert db.quotas is None
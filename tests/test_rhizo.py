@@ -6,7 +6,9 @@ Requires: pip install maturin pytest
 Build first: maturin develop
 """
 
+import io
 import os
+import random
 import tempfile
 import shutil
 import pytest
@@ -302,6 +304,150 @@ class TestChunkStore:
         result = store.get_mmap_batch([])
         assert result == []
 
+    def test_max_open_files_limits_bulk_operations(self, temp_dir):
+        """Bulk operations queue under max_open_files instead of failing."""
+        store = _rhizo.PyChunkStore(os.path.join(temp_dir, "chunks"), max_open_files=2)
+
+        chunks = [b"handle %d" % i for i in range(50)]
+        hashes = store.put_batch(chunks)
+        assert store.get_batch(hashes) == chunks
+        assert store.get_mmap_batch(hashes[:2]) == chunks[:2]
+
+        # Every mapping keeps its file open, so more than the limit fails
+        with pytest.raises(IOError, match="Too many open chunk files"):
+            store.get_mmap_batch(hashes[:3])
+
+    def test_compression_transparent(self, temp_dir):
+        """Compressed stores read chunks written with any codec or none."""
+        path = os.path.join(temp_dir, "chunks")
+        plain = _rhizo.PyChunkStore(path)
+        old = plain.put(b"written uncompressed")
+
+        data = b"compressible " * 10000
+        zstd = _rhizo.PyChunkStore(path, compression="zstd")
+        new = zstd.put(data)
+        assert zstd.get(old) == b"written uncompressed"
+        assert zstd.get_verified(new) == data
+
+        stored = sum(
+            os.path.getsize(os.path.join(root, f))
+            for root, _, files in os.walk(path) for f in files
+        )
+        assert stored < len(data) // 10
+
+        lz4 = _rhizo.PyChunkStore(path, compression="lz4")
+        assert lz4.get(new) == data
+
+    def test_unknown_compression_rejected(self, temp_dir):
+        with pytest.raises(ValueError, match="Unknown chunk compression"):
+            _rhizo.PyChunkStore(os.path.join(temp_dir, "chunks"), compression="gzip")
+
+    # ========== Delta Chunks ==========
+
+    def test_put_delta_reconstructs(self, temp_dir):
+        """Delta chunks have put()'s hash and read back transparently."""
+        store = _rhizo.PyChunkStore(os.path.join(temp_dir, "chunks"), compression="zstd")
+
+        v1 = bytes((i * 7919) % 251 for i in range(100000))
+        v2 = v1[:5000] + b"changed" + v1[5007:]
+        base = store.put(v1)
+        head = store.put_delta(v2, base)
+
+        assert head == _rhizo.PyChunkStore(os.path.join(temp_dir, "plain")).put(v2)
+        assert store.delta_info(head) == (base, 1)
+        assert store.delta_info(base) is None
+        assert store.get(head) == v2
+        assert store.get_verified(head) == v2
+
+        assert store.materialize(head)
+        assert store.delta_info(head) is None
+        assert store.get(head) == v2
+
+    def test_put_delta_respects_chain_limit(self, temp_dir):
+        store = _rhizo.PyChunkStore(os.path.join(temp_dir, "chunks"), max_delta_chain=0)
+        v1 = bytes(range(256)) * 400
+        base = store.put(v1)
+        head = store.put_delta(v1[:-1] + b"x", base)
+        assert store.delta_info(head) is None
+
+        with pytest.raises(IOError, match="not found"):
+            store.put_delta(b"data", "a" * 64)
+
+    # ========== Streaming Operations ==========
+
+    def test_put_stream_matches_put(self, temp_dir):
+        """put_stream hashes a file-like object like put hashes bytes."""
+        store = _rhizo.PyChunkStore(os.path.join(temp_dir, "chunks"))
+
+        data = bytes(range(256)) * (1024 * 12)  # 3MB, several read blocks
+        streamed = store.put_stream(io.BytesIO(data))
+
+        assert streamed == store.put(data)
+        assert store.get(streamed) == data
+
+    def test_get_stream_reads_incrementally(self, temp_dir):
+        """get_stream returns a closable file-like reader."""
+        store = _rhizo.PyChunkStore(os.path.join(temp_dir, "chunks"), compression="zstd")
+        data = b"streamed " * 100000
+        hash_str = store.put_stream(io.BytesIO(data))
+
+        with store.get_stream(hash_str) as reader:
+            assert reader.size == len(data)
+            head = reader.read(9)
+            assert head + reader.read() == data
+            assert reader.read() == b""
+        assert reader.closed
+        with pytest.raises(ValueError, match="closed"):
+            reader.read()
+
+    def test_put_stream_propagates_read_errors(self, temp_dir):
+        store = _rhizo.PyChunkStore(os.path.join(temp_dir, "chunks"))
+
+        class Broken:
+            def read(self, size):
+                raise KeyError("boom")
+
+        with pytest.raises(KeyError):
+            store.put_stream(Broken())
+
+    def test_put_chunked_dedups_edited_versions(self, temp_dir):
+        """Content-defined chunks after an insert match the previous version."""
+        store = _rhizo.PyChunkStore(os.path.join(temp_dir, "chunks"), chunk_size=4096)
+        v1 = random.Random(7).randbytes(200000)
+        v2 = v1[:100] + b"inserted" + v1[100:]
+
+        first = store.put_chunked(io.BytesIO(v1))
+        second = store.put_chunked(v2)
+
+        assert b"".join(store.get_batch(first)) == v1
+        assert b"".join(store.get_batch(second)) == v2
+        assert len(set(second) - set(first)) <= 2
+
+    def test_put_chunked_fixed_and_invalid_mode(self, temp_dir):
+        store = _rhizo.PyChunkStore(os.path.join(temp_dir, "chunks"), chunking="fixed", chunk_size=1000)
+        assert len(store.put_chunked(b"x" * 2500)) == 3
+
+        with pytest.raises(ValueError, match="chunking"):
+            _rhizo.PyChunkStore(os.path.join(temp_dir, "other"), chunking="rolling")
+
+    def test_many_variants_dedup_and_keep_order(self, temp_dir):
+        store = _rhizo.PyChunkStore(os.path.join(temp_dir, "chunks"))
+        hashes = store.put_many([b"a", b"b", b"a"])
+        assert hashes[0] == hashes[2] != hashes[1]
+        assert store.get_many(hashes) == [b"a", b"b", b"a"]
+        missing = "0" * 64
+        assert store.exists_many([hashes[1], missing]) == [True, False]
+
+    def test_memory_cache_counts_hits(self, temp_dir):
+        store = _rhizo.PyChunkStore(os.path.join(temp_dir, "chunks"), memory_cache_bytes=1 << 20)
+        h = store.put(b"hot")
+        assert store.get(h) == b"hot"
+        assert store.get_verified(h) == b"hot"
+
+        stats = store.memory_cache_stats()
+        assert (stats["hits"], stats["misses"], stats["entries"]) == (1, 1, 1)
+        assert _rhizo.PyChunkStore(os.path.join(temp_dir, "cold")).memory_cache_stats()["capacity"] == 0
+
 
 class TestCatalog:
     """Tests for PyCatalog and PyTableVersion."""
@@ -409,6 +555,27 @@ class TestCatalog:
         assert version.schema_hash is None
         assert version.metadata == {}
 
+    def test_recover_pending_commits(self, temp_dir):
+        """Intents of crashed commits are reported; completed ones are not."""
+        import json
+
+        catalog_dir = os.path.join(temp_dir, "catalog")
+        catalog = _rhizo.PyCatalog(catalog_dir)
+        catalog.commit(_rhizo.PyTableVersion("done", 1, ["a" * 64]))
+
+        pending_dir = os.path.join(catalog_dir, ".pending")
+        os.makedirs(pending_dir, exist_ok=True)
+        for intent_id, table, hashes in [("i1", "done", ["a" * 64]), ("i2", "crashed", ["b" * 64])]:
+            with open(os.path.join(pending_dir, f"{intent_id}.json"), "w") as f:
+                json.dump({"intent_id": intent_id, "table_name": table,
+                           "chunk_hashes": hashes, "created_at": 0}, f)
+
+        orphaned = catalog.recover_pending_commits()
+        assert [(p.intent_id, p.table_name, p.chunk_hashes) for p in orphaned] == [
+            ("i2", "crashed", ["b" * 64])
+        ]
+        assert catalog.recover_pending_commits() == []
+
 
 class TestIntegration:
     """Integration tests combining ChunkStore and Catalog."""
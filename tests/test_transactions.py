@@ -265,6 +265,46 @@ class TestReadYourWrites:
             assert result2.to_pandas()["x"].iloc[0] == 999
 
 
+class TestSavepoints:
+    """Tests for partial rollback within transactions."""
+
+    def test_rollback_to_savepoint_discards_later_writes(self, engine_with_tx):
+        """Writes after a savepoint are dropped; earlier ones commit."""
+        engine, _ = engine_with_tx
+        engine.write_table("data", pd.DataFrame({"x": [1]}))
+
+        with engine.transaction() as tx:
+            tx.write_table("data", pd.DataFrame({"x": [2]}))
+            tx.savepoint("before_audit")
+
+            tx.write_table("data", pd.DataFrame({"x": [3]}))
+            tx.write_table("audit", pd.DataFrame({"event": ["update"]}))
+            assert tx.query("SELECT x FROM data").to_pandas()["x"].iloc[0] == 3
+
+            tx.rollback_to_savepoint("before_audit")
+            assert tx.query("SELECT x FROM data").to_pandas()["x"].iloc[0] == 2
+
+            with pytest.raises(ValueError, match="Savepoint not found"):
+                tx.rollback_to_savepoint("missing")
+
+        assert engine.query("SELECT x FROM data").to_pandas()["x"].iloc[0] == 2
+        assert "audit" not in engine.list_tables()
+
+    def test_release_savepoint(self, engine_with_tx):
+        """Released savepoints can no longer be rolled back to."""
+        engine, _ = engine_with_tx
+        tx_manager = engine.transaction_manager
+
+        with engine.transaction() as tx:
+            tx.savepoint("a")
+            tx.savepoint("b")
+            assert tx_manager.get_transaction(tx.tx_id).savepoints == ["a", "b"]
+
+            tx.release_savepoint("a")
+            assert tx_manager.get_transaction(tx.tx_id).savepoints == []
+            with pytest.raises(ValueError, match="Savepoint not found"):
+                tx.rollback_to_savepoint("b")
+
 class TestSnapshotIsolation:
     """Tests for snapshot isolation."""
 
@@ -452,6 +492,99 @@ class TestBranchIntegration:
         assert head["users"] == 1  # First version
 
 
+class TestReadSnapshot:
+    """Tests for the transaction-free read_snapshot() API."""
+
+    def test_read_snapshot_matches_branch_head(self, temp_storage):
+        """Snapshot reports every table at the branch head version."""
+        _, _, _, tx_manager, _ = temp_storage
+
+        tx_id = tx_manager.begin()
+        tx_manager.add_write(tx_id, "users", 1, ["u1"])
+        tx_manager.add_write(tx_id, "orders", 1, ["o1", "o2"])
+        tx_manager.commit(tx_id)
+
+        snapshot = tx_manager.read_snapshot()
+        assert snapshot["users"] == (1, ["u1"])
+        assert snapshot["orders"] == (1, ["o1", "o2"])
+        assert tx_manager.active_count() == 0
+
+    def test_read_snapshot_is_frozen(self, temp_storage):
+        """Returned mapping cannot be mutated."""
+        _, _, _, tx_manager, _ = temp_storage
+
+        snapshot = tx_manager.read_snapshot("main")
+        with pytest.raises(TypeError):
+            snapshot["users"] = (1, [])
+
+    def test_read_snapshot_isolated_from_later_commits(self, temp_storage):
+        """A snapshot taken earlier does not change when new versions commit."""
+        _, _, _, tx_manager, _ = temp_storage
+
+        tx1 = tx_manager.begin()
+        tx_manager.add_write(tx1, "users", 1, ["v1"])
+        tx_manager.commit(tx1)
+
+        before = tx_manager.read_snapshot()
+
+        tx2 = tx_manager.begin()
+        tx_manager.add_write(tx2, "orders", 1, ["o1"])
+        tx_manager.commit(tx2)
+
+        assert "orders" not in before
+        assert tx_manager.read_snapshot()["orders"] == (1, ["o1"])
+
+
+class TestEpochManagement:
+    """Tests for epoch configuration and sealing from Python."""
+
+    def test_default_epoch_config(self, temp_storage):
+        """New transaction logs default to single-node epochs."""
+        _, _, _, tx_manager, _ = temp_storage
+
+        config = tx_manager.epoch_config
+        assert config.duration_ms == 0
+        assert config.max_transactions == 1
+        assert config.is_immediate()
+
+    def test_custom_epoch_config(self, temp_storage):
+        """Epoch config passed at creation is persisted."""
+        _, _, _, _, base_dir = temp_storage
+        tx_dir = os.path.join(base_dir, "tx_custom")
+        catalog_dir = os.path.join(base_dir, "catalog")
+
+        tm = _rhizo.PyTransactionManager(
+            tx_dir, catalog_dir, epoch_config=_rhizo.PyEpochConfig.high_throughput()
+        )
+        assert tm.epoch_config.max_transactions == 10000
+
+        reopened = _rhizo.PyTransactionManager(tx_dir, catalog_dir)
+        assert reopened.epoch_config.max_transactions == 10000
+
+    def test_seal_epoch(self, temp_storage):
+        """Sealing closes the current epoch and routes new transactions forward."""
+        _, _, _, tx_manager, _ = temp_storage
+
+        tx1 = tx_manager.begin()
+        tx_manager.add_write(tx1, "users", 1, ["c1"])
+        tx_manager.commit(tx1)
+
+        current = tx_manager.current_epoch()
+        assert current.transactions == [tx1]
+        assert current.committed_count == 1
+
+        sealed = tx_manager.seal_epoch()
+        assert sealed.epoch_id == current.epoch_id
+        assert sealed.status == "Committed"
+
+        tx2 = tx_manager.begin()
+        assert tx_manager.get_transaction(tx2).epoch_id == sealed.epoch_id + 1
+
+        epochs = tx_manager.list_epochs()
+        assert [e.epoch_id for e in epochs] == [sealed.epoch_id, sealed.epoch_id + 1]
+        assert epochs[-1].status == "Active"
+
+
 class TestTransactionContext:
     """Tests for TransactionContext behavior."""
 
@@ -534,6 +667,71 @@ class TestTransactionContext:
                 tx.abort()
 
 
+class TestRetrying:
+    """Tests for QueryEngine.retrying()."""
+
+    def test_retries_commit_conflict_on_fresh_snapshot(self, temp_storage):
+        """A conflicting commit is aborted and the body runs again."""
+        store, catalog, branches, tx_manager, _ = temp_storage
+        engine = QueryEngine(store, catalog, branch_manager=branches, transaction_manager=tx_manager)
+        other = QueryEngine(store, catalog, branch_manager=branches, transaction_manager=tx_manager)
+
+        # Begun first, committed during the first attempt
+        competing = other.transaction()
+        competitor = competing.__enter__()
+
+        retrying = engine.retrying(base_delay=0.001)
+        bodies = 0
+        for attempt in retrying:
+            with attempt as tx:
+                bodies += 1
+                tx.write_table("counter", pd.DataFrame({"n": [bodies]}))
+                if bodies == 1:
+                    competitor.write_table("counter", pd.DataFrame({"n": [100]}))
+                    competing.__exit__(None, None, None)
+
+        assert bodies == 2
+        assert retrying.attempts == 2
+        assert engine.query("SELECT n FROM counter").to_pandas()["n"].tolist() == [2]
+        engine.close()
+        other.close()
+
+    def test_gives_up_after_max_attempts(self, engine_with_tx):
+        """RetriesExhaustedError chains the last conflict."""
+        from rhizo import RetriesExhaustedError
+
+        engine, _ = engine_with_tx
+
+        class Conflict(Exception):
+            code = "TX_WRITE_CONFLICT"
+
+        bodies = 0
+        with pytest.raises(RetriesExhaustedError) as exc_info:
+            for attempt in engine.retrying(max_attempts=3, base_delay=0.001):
+                with attempt:
+                    bodies += 1
+                    raise Conflict()
+
+        assert bodies == 3
+        assert exc_info.value.attempts == 3
+        assert isinstance(exc_info.value.__cause__, Conflict)
+        assert engine.transaction_manager.active_count() == 0
+
+    def test_other_errors_are_not_retried(self, engine_with_tx):
+        """Non-conflict errors abort and propagate after one attempt."""
+        engine, _ = engine_with_tx
+
+        bodies = 0
+        with pytest.raises(ValueError, match="boom"):
+            for attempt in engine.retrying():
+                with attempt:
+                    bodies += 1
+                    raise ValueError("boom")
+
+        assert bodies == 1
+        assert engine.transaction_manager.active_count() == 0
+
+
 class TestTransactionEdgeCases:
     """Edge case and error handling tests."""
 